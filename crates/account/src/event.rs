@@ -0,0 +1,96 @@
+//! Account events.
+
+use namada_core::address::Address;
+use namada_events::extend::{ComposeEvent, EventAttributeEntry};
+use namada_events::{Event, EventLevel, EventToEmit};
+
+pub mod types {
+    //! Account event types.
+
+    use namada_events::{event_type, EventType};
+
+    use super::AccountEvent;
+
+    /// Update of the public keys and threshold of an account.
+    pub const SIGNERS_UPDATE: EventType =
+        event_type!(AccountEvent, "signers-update");
+}
+
+/// Account event.
+#[derive(Debug)]
+pub enum AccountEvent {
+    /// The public keys or the threshold of an account were updated.
+    SignersUpdate {
+        /// The address of the account.
+        owner: Address,
+        /// The new number of public keys of the account.
+        num_public_keys: u8,
+        /// The new signature threshold of the account.
+        threshold: u8,
+    },
+}
+
+impl EventToEmit for AccountEvent {
+    const DOMAIN: &'static str = "account";
+}
+
+impl From<AccountEvent> for Event {
+    fn from(account_event: AccountEvent) -> Self {
+        match account_event {
+            AccountEvent::SignersUpdate {
+                owner,
+                num_public_keys,
+                threshold,
+            } => Event::new(types::SIGNERS_UPDATE, EventLevel::Tx)
+                .with(AccountOwner(owner))
+                .with(AccountNumPublicKeys(num_public_keys))
+                .with(AccountThreshold(threshold))
+                .into(),
+        }
+    }
+}
+
+/// Extend an [`Event`] with the address of an account.
+pub struct AccountOwner(pub Address);
+
+impl EventAttributeEntry<'static> for AccountOwner {
+    type Value = Address;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "account";
+    const VALUE_TYPE: &'static str = "address";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the number of public keys of an account.
+pub struct AccountNumPublicKeys(pub u8);
+
+impl EventAttributeEntry<'static> for AccountNumPublicKeys {
+    type Value = u8;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "num-public-keys";
+    const VALUE_TYPE: &'static str = "u8";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the signature threshold of an account.
+pub struct AccountThreshold(pub u8);
+
+impl EventAttributeEntry<'static> for AccountThreshold {
+    type Value = u8;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "threshold";
+    const VALUE_TYPE: &'static str = "u8";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
@@ -0,0 +1,320 @@
+//! Faucet accounts with rate limited withdrawals.
+//!
+//! A faucet is an established account with the `vp_faucet` validity
+//! predicate. Anyone can withdraw its tokens without its signature, but only
+//! up to the limits it sets for every token: an amount per receiving address
+//! and a total amount per epoch. The withdrawals made in the current epoch are
+//! recorded under the faucet's address, and the records of past epochs are
+//! simply overwritten by the next withdrawal.
+
+use namada_core::address::Address;
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::storage::{self, DbKeySeg, Epoch, KeySeg};
+use namada_core::token::Amount;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_storage::{Result, StorageRead, StorageWrite};
+use serde::{Deserialize, Serialize};
+
+const FAUCET_KEY: &str = "faucet";
+const LIMITS_KEY: &str = "limits";
+const WITHDRAWALS_KEY: &str = "withdrawals";
+const TOTAL_KEY: &str = "total";
+
+/// The withdrawal limits of a token of a faucet
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct FaucetLimits {
+    /// The maximum amount withdrawn to a single address per epoch
+    pub per_address: Amount,
+    /// The maximum amount withdrawn to all addresses per epoch
+    pub per_epoch: Amount,
+}
+
+/// The amount of a token withdrawn from a faucet in an epoch
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct Withdrawal {
+    /// The epoch of the withdrawals
+    pub epoch: Epoch,
+    /// The amount withdrawn in the epoch
+    pub amount: Amount,
+}
+
+impl Withdrawal {
+    /// The amount withdrawn in the given epoch according to this record
+    pub fn amount_in_epoch(&self, epoch: Epoch) -> Amount {
+        if self.epoch == epoch {
+            self.amount
+        } else {
+            Amount::zero()
+        }
+    }
+}
+
+/// A tx data type to withdraw tokens from a faucet
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct FaucetWithdraw {
+    /// The faucet account
+    pub faucet: Address,
+    /// The address receiving the tokens
+    pub target: Address,
+    /// The withdrawn token
+    pub token: Address,
+    /// The withdrawn amount
+    pub amount: Amount,
+}
+
+/// A tx data type to set the withdrawal limits of a token of a faucet
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct UpdateFaucetLimits {
+    /// The faucet account, which must authorize the update
+    pub faucet: Address,
+    /// The token
+    pub token: Address,
+    /// The new limits. Zero limits disable the withdrawals of the token.
+    pub limits: FaucetLimits,
+}
+
+fn faucet_prefix(faucet: &Address, sub_key: &str) -> storage::Key {
+    storage::Key::from(faucet.to_db_key())
+        .push(&FAUCET_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&sub_key.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the storage key of the withdrawal limits of a token of a faucet
+pub fn limits_key(faucet: &Address, token: &Address) -> storage::Key {
+    faucet_prefix(faucet, LIMITS_KEY)
+        .push(token)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if the given storage key is a faucet's withdrawal limits key. If it
+/// is, returns the faucet and the token.
+pub fn is_limits_key(key: &storage::Key) -> Option<[&Address; 2]> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(faucet), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(sub_key), DbKeySeg::AddressSeg(token)]
+            if prefix == FAUCET_KEY && sub_key == LIMITS_KEY =>
+        {
+            Some([faucet, token])
+        }
+        _ => None,
+    }
+}
+
+/// Get the storage key of the withdrawals of a token of a faucet to an
+/// address
+pub fn withdrawal_key(
+    faucet: &Address,
+    token: &Address,
+    target: &Address,
+) -> storage::Key {
+    faucet_prefix(faucet, WITHDRAWALS_KEY)
+        .push(token)
+        .expect("Cannot obtain a storage key")
+        .push(target)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if the given storage key is a faucet's withdrawal key. If it is,
+/// returns the faucet, the token and the receiving address.
+pub fn is_withdrawal_key(key: &storage::Key) -> Option<[&Address; 3]> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(faucet), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(sub_key), DbKeySeg::AddressSeg(token), DbKeySeg::AddressSeg(target)]
+            if prefix == FAUCET_KEY && sub_key == WITHDRAWALS_KEY =>
+        {
+            Some([faucet, token, target])
+        }
+        _ => None,
+    }
+}
+
+/// Get the storage key of the total withdrawals of a token of a faucet
+pub fn total_withdrawal_key(faucet: &Address, token: &Address) -> storage::Key {
+    faucet_prefix(faucet, TOTAL_KEY)
+        .push(token)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if the given storage key is a faucet's total withdrawal key. If it
+/// is, returns the faucet and the token.
+pub fn is_total_withdrawal_key(key: &storage::Key) -> Option<[&Address; 2]> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(faucet), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(sub_key), DbKeySeg::AddressSeg(token)]
+            if prefix == FAUCET_KEY && sub_key == TOTAL_KEY =>
+        {
+            Some([faucet, token])
+        }
+        _ => None,
+    }
+}
+
+/// Read the withdrawal limits of a token of a faucet
+pub fn read_limits<S>(
+    storage: &S,
+    faucet: &Address,
+    token: &Address,
+) -> Result<Option<FaucetLimits>>
+where
+    S: StorageRead,
+{
+    storage.read(&limits_key(faucet, token))
+}
+
+/// Set the withdrawal limits of a token of a faucet
+pub fn update_limits<S>(
+    storage: &mut S,
+    update: &UpdateFaucetLimits,
+) -> Result<()>
+where
+    S: StorageWrite,
+{
+    storage.write(&limits_key(&update.faucet, &update.token), update.limits)
+}
+
+/// Read the amount of a token withdrawn from a faucet in the given epoch,
+/// either to an address if the key is a withdrawal key, or in total
+pub fn read_withdrawn<S>(
+    storage: &S,
+    key: &storage::Key,
+    epoch: Epoch,
+) -> Result<Amount>
+where
+    S: StorageRead,
+{
+    Ok(storage
+        .read::<Withdrawal>(key)?
+        .map(|withdrawal| withdrawal.amount_in_epoch(epoch))
+        .unwrap_or_default())
+}
+
+/// Record a withdrawal from a faucet in the current epoch, checking it against
+/// the faucet's limits. The tokens must be transferred separately.
+pub fn record_withdrawal<S>(
+    storage: &mut S,
+    withdraw: &FaucetWithdraw,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let FaucetWithdraw {
+        faucet,
+        target,
+        token,
+        amount,
+    } = withdraw;
+    let limits = read_limits(storage, faucet, token)?.ok_or_else(|| {
+        namada_storage::Error::new_alloc(format!(
+            "The faucet {faucet} has no withdrawal limits for the token \
+             {token}"
+        ))
+    })?;
+    let epoch = storage.get_block_epoch()?;
+
+    let checks = [
+        (withdrawal_key(faucet, token, target), limits.per_address),
+        (total_withdrawal_key(faucet, token), limits.per_epoch),
+    ];
+    for (key, limit) in checks {
+        let withdrawn = read_withdrawn(storage, &key, epoch)?
+            .checked_add(*amount)
+            .filter(|withdrawn| *withdrawn <= limit)
+            .ok_or_else(|| {
+                namada_storage::Error::new_alloc(format!(
+                    "The withdrawal of {} from the faucet {faucet} exceeds \
+                     its limit of {} per epoch",
+                    amount.to_string_native(),
+                    limit.to_string_native()
+                ))
+            })?;
+        storage.write(
+            &key,
+            Withdrawal {
+                epoch,
+                amount: withdrawn,
+            },
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::address::testing::{
+        established_address_1, established_address_2, nam,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_faucet_keys() {
+        let faucet = established_address_1();
+        let target = established_address_2();
+        let token = nam();
+
+        let key = limits_key(&faucet, &token);
+        assert_eq!(is_limits_key(&key), Some([&faucet, &token]));
+        assert_eq!(is_total_withdrawal_key(&key), None);
+
+        let key = total_withdrawal_key(&faucet, &token);
+        assert_eq!(is_total_withdrawal_key(&key), Some([&faucet, &token]));
+        assert_eq!(is_limits_key(&key), None);
+
+        let key = withdrawal_key(&faucet, &token, &target);
+        assert_eq!(is_withdrawal_key(&key), Some([&faucet, &token, &target]));
+        assert_eq!(is_limits_key(&key), None);
+    }
+
+    #[test]
+    fn test_withdrawal_amount_in_epoch() {
+        let withdrawal = Withdrawal {
+            epoch: Epoch(2),
+            amount: Amount::native_whole(10),
+        };
+        assert_eq!(withdrawal.amount_in_epoch(Epoch(2)), withdrawal.amount);
+        assert_eq!(withdrawal.amount_in_epoch(Epoch(3)), Amount::zero());
+    }
+}
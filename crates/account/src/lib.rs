@@ -2,8 +2,12 @@
 //! using public key(s) and signature threshold (minimum number of signatures
 //! needed to authorize an action) stored on-chain.
 
+pub mod event;
+pub mod faucet;
+pub mod name_registry;
 mod storage;
 mod storage_key;
+pub mod transfer_policy;
 mod types;
 
 use borsh::{BorshDeserialize, BorshSerialize};
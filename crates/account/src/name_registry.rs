@@ -0,0 +1,238 @@
+//! On-chain registry of human-readable names of addresses.
+//!
+//! A name, e.g. `alice.namada`, is made of labels separated by dots. Every
+//! registered name points to a target address and is owned by an address,
+//! whose authorization is required to update or delete it. Governance can
+//! reserve namespaces, after which the names in them, e.g. `validator` and
+//! `*.validator`, can only be registered or updated by a governance proposal.
+
+use namada_core::address::{Address, NAME_REGISTRY};
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::storage::{self, DbKeySeg, KeySeg};
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_storage::{Result, StorageRead, StorageWrite};
+use serde::{Deserialize, Serialize};
+
+/// The maximum length of a name in bytes
+pub const MAX_NAME_LEN: usize = 64;
+
+const NAMES_KEY: &str = "names";
+const RESERVED_KEY: &str = "reserved";
+
+/// A registered name
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct NameRecord {
+    /// The owner of the name, who can update or delete it
+    pub owner: Address,
+    /// The address the name resolves to
+    pub target: Address,
+}
+
+/// A tx data type to register, update or delete a name
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct UpdateName {
+    /// The name
+    pub name: String,
+    /// The new owner of the name. Both the current and the new owner must
+    /// authorize the tx.
+    pub owner: Address,
+    /// The new target of the name, or `None` to delete it
+    pub target: Option<Address>,
+}
+
+/// Check that a name is made of non-empty labels of lowercase ASCII letters,
+/// digits and hyphens separated by dots, with no label starting or ending
+/// with a hyphen
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name.split('.').all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.bytes().all(|byte| {
+                    byte.is_ascii_lowercase()
+                        || byte.is_ascii_digit()
+                        || byte == b'-'
+                })
+        })
+}
+
+/// Get the storage key of a registered name
+pub fn name_key(name: &str) -> storage::Key {
+    storage::Key::from(NAME_REGISTRY.to_db_key())
+        .push(&NAMES_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&name.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if the given storage key is a name key. If it is, returns the name.
+pub fn is_name_key(key: &storage::Key) -> Option<&str> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(name)]
+            if addr == &NAME_REGISTRY && prefix == NAMES_KEY =>
+        {
+            Some(name.as_str())
+        }
+        _ => None,
+    }
+}
+
+/// Get the storage key of a namespace reserved by governance
+pub fn reserved_namespace_key(namespace: &str) -> storage::Key {
+    storage::Key::from(NAME_REGISTRY.to_db_key())
+        .push(&RESERVED_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&namespace.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if the given storage key is a reserved namespace key. If it is,
+/// returns the namespace.
+pub fn is_reserved_namespace_key(key: &storage::Key) -> Option<&str> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(namespace)]
+            if addr == &NAME_REGISTRY && prefix == RESERVED_KEY =>
+        {
+            Some(namespace.as_str())
+        }
+        _ => None,
+    }
+}
+
+/// Iterate over a name and the namespaces it belongs to, e.g. `alice.namada`
+/// and `namada` for `alice.namada`
+pub fn name_with_namespaces(name: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(name), |name| {
+        name.split_once('.').map(|(_, namespace)| namespace)
+    })
+}
+
+/// Check if a name is in a namespace reserved by governance
+pub fn is_reserved_name<S>(storage: &S, name: &str) -> Result<bool>
+where
+    S: StorageRead,
+{
+    for namespace in name_with_namespaces(name) {
+        if storage.has_key(&reserved_namespace_key(namespace))? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Reserve a namespace for governance
+pub fn reserve_namespace<S>(storage: &mut S, namespace: &str) -> Result<()>
+where
+    S: StorageWrite,
+{
+    storage.write(&reserved_namespace_key(namespace), ())
+}
+
+/// Release a namespace reserved for governance
+pub fn release_namespace<S>(storage: &mut S, namespace: &str) -> Result<()>
+where
+    S: StorageWrite,
+{
+    storage.delete(&reserved_namespace_key(namespace))
+}
+
+/// Read the record of a registered name
+pub fn read_name_record<S>(
+    storage: &S,
+    name: &str,
+) -> Result<Option<NameRecord>>
+where
+    S: StorageRead,
+{
+    storage.read(&name_key(name))
+}
+
+/// Resolve a registered name to its target address
+pub fn resolve_name<S>(storage: &S, name: &str) -> Result<Option<Address>>
+where
+    S: StorageRead,
+{
+    Ok(read_name_record(storage, name)?.map(|record| record.target))
+}
+
+/// Register, update or delete a name
+pub fn update_name<S>(storage: &mut S, update: &UpdateName) -> Result<()>
+where
+    S: StorageWrite,
+{
+    if !is_valid_name(&update.name) {
+        return Err(namada_storage::Error::new_alloc(format!(
+            "Invalid name {}",
+            update.name
+        )));
+    }
+    let key = name_key(&update.name);
+    match &update.target {
+        Some(target) => storage.write(
+            &key,
+            NameRecord {
+                owner: update.owner.clone(),
+                target: target.clone(),
+            },
+        ),
+        None => storage.delete(&key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_name() {
+        assert!(is_valid_name("alice.namada"));
+        assert!(is_valid_name("my-validator-1"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("Alice.namada"));
+        assert!(!is_valid_name("alice..namada"));
+        assert!(!is_valid_name(".namada"));
+        assert!(!is_valid_name("-alice.namada"));
+        assert!(!is_valid_name("alice/namada"));
+        assert!(!is_valid_name(&"a".repeat(MAX_NAME_LEN + 1)));
+    }
+
+    #[test]
+    fn test_name_keys() {
+        let key = name_key("alice.namada");
+        assert_eq!(is_name_key(&key), Some("alice.namada"));
+        assert_eq!(is_reserved_namespace_key(&key), None);
+
+        let key = reserved_namespace_key("validator");
+        assert_eq!(is_reserved_namespace_key(&key), Some("validator"));
+        assert_eq!(is_name_key(&key), None);
+
+        assert_eq!(
+            name_with_namespaces("alice.pool.namada").collect::<Vec<_>>(),
+            vec!["alice.pool.namada", "pool.namada", "namada"]
+        );
+    }
+}
@@ -94,6 +94,37 @@ where
     }
 }
 
+/// Get the epoch hook registered by an account, if any
+pub fn read_epoch_hook<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<Option<EpochHook>>
+where
+    S: StorageRead,
+{
+    storage.read(&epoch_hook_key(owner))
+}
+
+/// Get the next nonce of an account, which starts at 0
+pub fn read_nonce<S>(storage: &S, owner: &Address) -> Result<u64>
+where
+    S: StorageRead,
+{
+    Ok(storage.read(&nonce_key(owner))?.unwrap_or_default())
+}
+
+/// Consume the next nonce of an account
+pub fn increment_nonce<S>(storage: &mut S, owner: &Address) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    let nonce = read_nonce(storage, owner)?;
+    let next_nonce = nonce.checked_add(1).ok_or_else(|| {
+        namada_storage::Error::new_const("Account nonce overflow")
+    })?;
+    storage.write(&nonce_key(owner), next_nonce)
+}
+
 /// Set public key at specific index
 pub fn set_public_key_at<S>(
     storage: &mut S,
@@ -108,6 +139,65 @@ where
     Ok(())
 }
 
+/// Apply an update of the public keys and threshold of an account. The removed
+/// public keys must belong to the account and the added ones must not. The
+/// resulting threshold must be at least 1 and at most the number of the
+/// account's public keys. Returns the new number of public keys and threshold.
+pub fn update_account_signers<S>(
+    storage: &mut S,
+    update: &UpdateAccountSigners,
+) -> Result<(u8, u8)>
+where
+    S: StorageWrite + StorageRead,
+{
+    let owner = &update.addr;
+    let mut public_keys = public_keys(storage, owner)?;
+    for public_key in &update.remove_public_keys {
+        let Some(index) = public_keys.iter().position(|pk| pk == public_key)
+        else {
+            return Err(namada_storage::Error::new_alloc(format!(
+                "The public key {public_key} doesn't belong to the account \
+                 {owner}"
+            )));
+        };
+        public_keys.remove(index);
+    }
+    for public_key in &update.add_public_keys {
+        if public_keys.contains(public_key) {
+            return Err(namada_storage::Error::new_alloc(format!(
+                "The public key {public_key} already belongs to the account \
+                 {owner}"
+            )));
+        }
+        public_keys.push(public_key.clone());
+    }
+    let num_public_keys = u8::try_from(public_keys.len()).map_err(|_| {
+        namada_storage::Error::new_const("Too many public keys in an account")
+    })?;
+    let threshold = match update.threshold {
+        Some(threshold) => threshold,
+        None => threshold(storage, owner)?.unwrap_or(1),
+    };
+    if !is_valid_threshold(num_public_keys, threshold) {
+        return Err(namada_storage::Error::new_alloc(format!(
+            "Invalid threshold {threshold} of the account {owner} with \
+             {num_public_keys} public keys"
+        )));
+    }
+
+    clear_public_keys(storage, owner)?;
+    for (index, public_key) in public_keys.iter().enumerate() {
+        set_public_key_at(storage, owner, public_key, index as u8)?;
+    }
+    storage.write(&threshold_key(owner), threshold)?;
+    Ok((num_public_keys, threshold))
+}
+
+/// Check that a threshold can be satisfied by the given number of public keys
+pub fn is_valid_threshold(num_public_keys: u8, threshold: u8) -> bool {
+    threshold >= 1 && threshold <= num_public_keys
+}
+
 /// Clear the public keys account subtorage space
 pub fn clear_public_keys<S>(storage: &mut S, owner: &Address) -> Result<()>
 where
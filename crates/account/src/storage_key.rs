@@ -11,6 +11,8 @@ struct Keys {
     public_keys: &'static str,
     threshold: &'static str,
     protocol_public_keys: &'static str,
+    epoch_hook: &'static str,
+    nonce: &'static str,
 }
 
 /// Obtain a storage key for user's public key.
@@ -89,3 +91,49 @@ pub fn is_protocol_pk_key(key: &storage::Key) -> Option<&Address> {
         _ => None,
     }
 }
+
+/// Obtain the storage key for a user's epoch hook.
+pub fn epoch_hook_key(owner: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.epoch_hook.to_string()),
+        ],
+    }
+}
+
+/// Check if the given storage key is an epoch hook key. If it is, returns the
+/// owner.
+pub fn is_epoch_hook_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(key)]
+            if key.as_str() == Keys::VALUES.epoch_hook =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
+/// Obtain the storage key for a user's next account nonce.
+pub fn nonce_key(owner: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.nonce.to_string()),
+        ],
+    }
+}
+
+/// Check if the given storage key is an account nonce key. If it is, returns
+/// the owner.
+pub fn is_nonce_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(key)]
+            if key.as_str() == Keys::VALUES.nonce =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
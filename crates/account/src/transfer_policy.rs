@@ -0,0 +1,201 @@
+//! Account-level transfer policies.
+//!
+//! An established account with the `vp_user_policy` validity predicate can
+//! register a transfer policy in its storage, which restricts the debits of
+//! its balances on top of the signature checks: the amount of a token debited
+//! by a single tx may be capped and the tokens may only be sent to a set of
+//! allowed counterparties. The policy applies to every tx, even to the ones
+//! signed by the account, and only a signed tx can change it.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use namada_core::address::Address;
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::storage::{self, DbKeySeg, KeySeg};
+use namada_core::token::Amount;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_storage::{Result, StorageRead, StorageWrite};
+use serde::{Deserialize, Serialize};
+
+const TRANSFER_POLICY_KEY: &str = "transfer_policy";
+
+/// The transfer policy of an account
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct TransferPolicy {
+    /// The maximum amounts of the tokens that a single tx may debit. The
+    /// tokens without a maximum amount are not limited.
+    pub max_amounts_per_tx: BTreeMap<Address, Amount>,
+    /// The only addresses that may receive the account's tokens. Any address
+    /// may receive them if the set is empty.
+    pub allowed_counterparties: BTreeSet<Address>,
+}
+
+impl TransferPolicy {
+    /// Check that a tx debiting the given amount of a token from the account
+    /// and crediting it to the given receivers complies with the policy.
+    /// Returns the reason of the violation otherwise.
+    pub fn check_transfer<'a>(
+        &self,
+        token: &Address,
+        debited: Amount,
+        receivers: impl IntoIterator<Item = &'a Address>,
+    ) -> std::result::Result<(), String> {
+        if let Some(max_amount) = self.max_amounts_per_tx.get(token) {
+            if debited > *max_amount {
+                return Err(format!(
+                    "The debit of {} of the token {token} exceeds the maximum \
+                     amount of {} per tx",
+                    debited.to_string_native(),
+                    max_amount.to_string_native()
+                ));
+            }
+        }
+        if self.allowed_counterparties.is_empty() {
+            return Ok(());
+        }
+        receivers.into_iter().try_for_each(|receiver| {
+            if self.allowed_counterparties.contains(receiver) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "The receiver {receiver} of the token {token} is not an \
+                     allowed counterparty"
+                ))
+            }
+        })
+    }
+}
+
+/// A tx data type to set or remove the transfer policy of an account
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct UpdateTransferPolicy {
+    /// The account, which must authorize the update
+    pub owner: Address,
+    /// The new policy, or `None` to remove it
+    pub policy: Option<TransferPolicy>,
+}
+
+/// Get the storage key of the transfer policy of an account
+pub fn transfer_policy_key(owner: &Address) -> storage::Key {
+    storage::Key::from(owner.to_db_key())
+        .push(&TRANSFER_POLICY_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if the given storage key is the transfer policy key of an account.
+/// If it is, returns the account.
+pub fn is_transfer_policy_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(prefix)]
+            if prefix == TRANSFER_POLICY_KEY =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
+/// Read the transfer policy of an account
+pub fn read_transfer_policy<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<Option<TransferPolicy>>
+where
+    S: StorageRead,
+{
+    storage.read(&transfer_policy_key(owner))
+}
+
+/// Set or remove the transfer policy of an account
+pub fn update_transfer_policy<S>(
+    storage: &mut S,
+    update: &UpdateTransferPolicy,
+) -> Result<()>
+where
+    S: StorageWrite,
+{
+    let key = transfer_policy_key(&update.owner);
+    match &update.policy {
+        Some(policy) => storage.write(&key, policy),
+        None => storage.delete(&key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::address::testing::{
+        established_address_1, established_address_2, established_address_3,
+        nam,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_transfer_policy_key() {
+        let owner = established_address_1();
+        let key = transfer_policy_key(&owner);
+        assert_eq!(is_transfer_policy_key(&key), Some(&owner));
+        assert_eq!(is_transfer_policy_key(&crate::threshold_key(&owner)), None);
+    }
+
+    #[test]
+    fn test_check_transfer() {
+        let token = nam();
+        let allowed = established_address_2();
+        let other = established_address_3();
+
+        // An empty policy allows any transfer
+        let policy = TransferPolicy::default();
+        assert!(policy
+            .check_transfer(&token, Amount::native_whole(100), [&other])
+            .is_ok());
+
+        let policy = TransferPolicy {
+            max_amounts_per_tx: BTreeMap::from([(
+                token.clone(),
+                Amount::native_whole(10),
+            )]),
+            allowed_counterparties: BTreeSet::from([allowed.clone()]),
+        };
+        assert!(policy
+            .check_transfer(&token, Amount::native_whole(10), [&allowed])
+            .is_ok());
+        assert!(policy
+            .check_transfer(&token, Amount::native_whole(11), [&allowed])
+            .is_err());
+        assert!(policy
+            .check_transfer(&token, Amount::native_whole(1), [&allowed, &other])
+            .is_err());
+        // The other tokens are not limited
+        assert!(policy
+            .check_transfer(
+                &established_address_1(),
+                Amount::native_whole(100),
+                [&allowed]
+            )
+            .is_ok());
+    }
+}
@@ -55,6 +55,55 @@ pub struct UpdateAccount {
     pub threshold: Option<u8>,
 }
 
+/// A tx data type to add or remove public keys of an account and to change its
+/// signature threshold. It must be authorized with the account's threshold
+/// before the update.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct UpdateAccountSigners {
+    /// An address of the account
+    pub addr: Address,
+    /// Public keys to add to the account
+    pub add_public_keys: Vec<common::PublicKey>,
+    /// Public keys to remove from the account
+    pub remove_public_keys: Vec<common::PublicKey>,
+    /// The new account signature threshold. The current threshold is kept if
+    /// not set.
+    pub threshold: Option<u8>,
+}
+
+/// A transaction that an account schedules to be applied at the beginning of
+/// every epoch
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct EpochHook {
+    /// The serialized wrapper-less tx to apply. Its code section must only
+    /// contain the hash of an allowlisted tx code, which is then loaded from
+    /// storage when the hook runs.
+    pub tx: Vec<u8>,
+    /// The maximum amount of gas that the tx may consume. It is paid upfront
+    /// by the owner at the minimum gas price of the native token.
+    pub gas_limit: u64,
+}
+
 #[cfg(any(test, feature = "testing"))]
 /// Tests and strategies for accounts
 pub mod tests {
@@ -101,4 +150,24 @@ pub mod tests {
             }
         }
     }
+
+    prop_compose! {
+        /// Generate an arbitrary update of an account's signers
+        pub fn arb_update_account_signers()(
+            add_public_keys in collection::vec(arb_common_pk(), 0..5),
+            remove_public_keys in collection::vec(arb_common_pk(), 0..5),
+        )(
+            addr in arb_non_internal_address(),
+            threshold in option::of(1..=add_public_keys.len().max(1) as u8),
+            add_public_keys in Just(add_public_keys),
+            remove_public_keys in Just(remove_public_keys),
+        ) -> UpdateAccountSigners {
+            UpdateAccountSigners {
+                addr,
+                add_public_keys,
+                remove_public_keys,
+                threshold,
+            }
+        }
+    }
 }
@@ -51,6 +51,26 @@ fn main() {
         }
     };
 
+    // Discover the commit hash, if the repository exists
+    let commit_hash = match &repo {
+        Some(repo) => repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok())
+            .map(|commit| commit.id().to_string()),
+        None => None,
+    };
+    let pre = "\npub fn namada_commit_hash() -> &'static str { \"";
+    version_rs
+        .write_all(pre.as_bytes())
+        .expect("cannot write commit hash");
+    version_rs
+        .write_all(commit_hash.as_deref().unwrap_or("unknown").as_bytes())
+        .expect("cannot write commit hash");
+    version_rs
+        .write_all(post.as_bytes())
+        .expect("cannot write commit hash");
+
     // Tell Cargo that if the given file changes, to rerun this build script.
     println!("cargo:rerun-if-changed={}", PROTO_SRC);
 }
@@ -2,6 +2,7 @@ use color_eyre::eyre::Result;
 use namada_apps::cli::api::{CliApi, CliIo};
 use namada_apps::facade::tendermint_rpc::HttpClient;
 use namada_apps::{cli, logging};
+use namada_sdk::queries::multi_endpoint::MultiEndpointClient;
 use tracing_subscriber::filter::LevelFilter;
 
 #[tokio::main]
@@ -13,7 +14,7 @@ async fn main() -> Result<()> {
     let _log_guard = logging::init_from_env_or(LevelFilter::INFO)?;
 
     // run the CLI
-    CliApi::handle_client_command::<HttpClient, _>(
+    CliApi::handle_client_command::<MultiEndpointClient<HttpClient>, _>(
         None,
         cli::namada_client_cli()?,
         CliIo,
@@ -35,11 +35,36 @@ pub fn main() -> Result<()> {
                 let chain_ctx = ctx.take_chain_or_exit();
                 ledger::dump_db(chain_ctx.config.ledger, args);
             }
+            cmds::Ledger::DumpWrites(cmds::LedgerDumpWrites(args)) => {
+                let chain_ctx = ctx.take_chain_or_exit();
+                ledger::dump_writes(chain_ctx.config.ledger, args)
+                    .wrap_err("Failed to dump the writes")?;
+            }
+            cmds::Ledger::DiffWrites(cmds::LedgerDiffWrites(args)) => {
+                ledger::diff_writes(args)
+                    .wrap_err("Failed to diff the dumps of the writes")?;
+            }
             cmds::Ledger::RollBack(_) => {
                 let chain_ctx = ctx.take_chain_or_exit();
                 ledger::rollback(chain_ctx.config.ledger)
                     .wrap_err("Failed to rollback the Namada node")?;
             }
+            cmds::Ledger::RepairDb(_) => {
+                let chain_ctx = ctx.take_chain_or_exit();
+                ledger::repair_db(chain_ctx.config.ledger)
+                    .wrap_err("Failed to repair the Namada node's DB")?;
+            }
+            cmds::Ledger::Reindex(cmds::LedgerReindex(args)) => {
+                let chain_ctx = ctx.take_chain_or_exit();
+                ledger::reindex(chain_ctx.config.ledger, args)
+                    .wrap_err("Failed to reindex the Namada node")?;
+            }
+            cmds::Ledger::Verify(cmds::LedgerVerify(args)) => {
+                let chain_ctx = ctx.take_chain_or_exit();
+                let wasm_dir = chain_ctx.wasm_dir();
+                ledger::verify(chain_ctx.config.ledger, wasm_dir, args)
+                    .wrap_err("Failed to verify the Namada node state")?;
+            }
             cmds::Ledger::UpdateDB(cmds::LedgerUpdateDB(args)) => {
                 #[cfg(not(feature = "migrations"))]
                 {
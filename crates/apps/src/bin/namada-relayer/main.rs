@@ -2,6 +2,7 @@ use color_eyre::eyre::Result;
 use namada::tendermint_rpc::HttpClient;
 use namada_apps::cli::api::{CliApi, CliIo};
 use namada_apps::{cli, logging};
+use namada_sdk::queries::multi_endpoint::MultiEndpointClient;
 use tracing_subscriber::filter::LevelFilter;
 
 #[tokio::main]
@@ -14,5 +15,8 @@ async fn main() -> Result<()> {
 
     let cmd = cli::namada_relayer_cli()?;
     // run the CLI
-    CliApi::handle_relayer_command::<HttpClient>(None, cmd, CliIo).await
+    CliApi::handle_relayer_command::<MultiEndpointClient<HttpClient>>(
+        None, cmd, CliIo,
+    )
+    .await
 }
@@ -217,6 +217,11 @@ pub mod cmds {
                 .subcommand(TxTransfer::def().display_order(1))
                 .subcommand(TxIbcTransfer::def().display_order(1))
                 .subcommand(TxUpdateAccount::def().display_order(1))
+                .subcommand(TxUpdateAccountSigners::def().display_order(1))
+                .subcommand(TxUpdateName::def().display_order(1))
+                .subcommand(TxFaucetWithdraw::def().display_order(1))
+                .subcommand(TxUpdateFaucetLimits::def().display_order(1))
+                .subcommand(TxUpdateTransferPolicy::def().display_order(1))
                 .subcommand(TxInitAccount::def().display_order(1))
                 .subcommand(TxRevealPk::def().display_order(1))
                 // Governance transactions
@@ -236,6 +241,7 @@ pub mod cmds {
                 .subcommand(TxCommissionRateChange::def().display_order(2))
                 .subcommand(TxChangeConsensusKey::def().display_order(2))
                 .subcommand(TxMetadataChange::def().display_order(2))
+                .subcommand(TxOperationalMetadataChange::def().display_order(2))
                 // Ethereum bridge transactions
                 .subcommand(AddToEthBridgePool::def().display_order(3))
                 // PGF transactions
@@ -245,6 +251,8 @@ pub mod cmds {
                 .subcommand(QueryEpoch::def().display_order(5))
                 .subcommand(QueryNextEpochInfo::def().display_order(5))
                 .subcommand(QueryStatus::def().display_order(5))
+                .subcommand(QueryChainRegistry::def().display_order(5))
+                .subcommand(QueryStateChecksum::def().display_order(5))
                 .subcommand(QueryAccount::def().display_order(5))
                 .subcommand(QueryConversions::def().display_order(5))
                 .subcommand(QueryMaspRewardTokens::def().display_order(5))
@@ -260,12 +268,19 @@ pub mod cmds {
                 .subcommand(QueryProposal::def().display_order(5))
                 .subcommand(QueryProposalVotes::def().display_order(5))
                 .subcommand(QueryProposalResult::def().display_order(5))
+                .subcommand(VerifyProposalContent::def().display_order(5))
+                .subcommand(VerifyWasm::def().display_order(5))
+                .subcommand(Governance::def().display_order(5))
                 .subcommand(QueryProtocolParameters::def().display_order(5))
+                .subcommand(QueryParametersDiff::def().display_order(5))
                 .subcommand(QueryPgf::def().display_order(5))
                 .subcommand(QueryValidatorState::def().display_order(5))
+                .subcommand(QueryPendingConsensusKey::def().display_order(5))
                 .subcommand(QueryCommissionRate::def().display_order(5))
                 .subcommand(QueryRewards::def().display_order(5))
+                .subcommand(Staking::def().display_order(5))
                 .subcommand(QueryMetaData::def().display_order(5))
+                .subcommand(QueryTelemetry::def().display_order(5))
                 // Actions
                 .subcommand(SignTx::def().display_order(6))
                 .subcommand(ShieldedSync::def().display_order(6))
@@ -280,6 +295,15 @@ pub mod cmds {
             let tx_ibc_transfer = Self::parse_with_ctx(matches, TxIbcTransfer);
             let tx_update_account =
                 Self::parse_with_ctx(matches, TxUpdateAccount);
+            let tx_update_account_signers =
+                Self::parse_with_ctx(matches, TxUpdateAccountSigners);
+            let tx_update_name = Self::parse_with_ctx(matches, TxUpdateName);
+            let tx_faucet_withdraw =
+                Self::parse_with_ctx(matches, TxFaucetWithdraw);
+            let tx_update_faucet_limits =
+                Self::parse_with_ctx(matches, TxUpdateFaucetLimits);
+            let tx_update_transfer_policy =
+                Self::parse_with_ctx(matches, TxUpdateTransferPolicy);
             let tx_init_account = Self::parse_with_ctx(matches, TxInitAccount);
             let tx_become_validator =
                 Self::parse_with_ctx(matches, TxBecomeValidator);
@@ -306,6 +330,8 @@ pub mod cmds {
                 Self::parse_with_ctx(matches, TxChangeConsensusKey);
             let tx_change_metadata =
                 Self::parse_with_ctx(matches, TxMetadataChange);
+            let tx_change_operational_metadata =
+                Self::parse_with_ctx(matches, TxOperationalMetadataChange);
             let bond = Self::parse_with_ctx(matches, Bond);
             let unbond = Self::parse_with_ctx(matches, Unbond);
             let withdraw = Self::parse_with_ctx(matches, Withdraw);
@@ -315,6 +341,10 @@ pub mod cmds {
             let query_next_epoch_info =
                 Self::parse_with_ctx(matches, QueryNextEpochInfo);
             let query_status = Self::parse_with_ctx(matches, QueryStatus);
+            let query_chain_registry =
+                Self::parse_with_ctx(matches, QueryChainRegistry);
+            let query_state_checksum =
+                Self::parse_with_ctx(matches, QueryStateChecksum);
             let query_account = Self::parse_with_ctx(matches, QueryAccount);
             let query_conversions =
                 Self::parse_with_ctx(matches, QueryConversions);
@@ -327,6 +357,7 @@ pub mod cmds {
                 Self::parse_with_ctx(matches, QueryBondedStake);
             let query_slashes = Self::parse_with_ctx(matches, QuerySlashes);
             let query_rewards = Self::parse_with_ctx(matches, QueryRewards);
+            let staking = Self::parse_with_ctx(matches, Staking);
             let query_delegations =
                 Self::parse_with_ctx(matches, QueryDelegations);
             let query_find_validator =
@@ -338,14 +369,23 @@ pub mod cmds {
                 Self::parse_with_ctx(matches, QueryProposalVotes);
             let query_proposal_result =
                 Self::parse_with_ctx(matches, QueryProposalResult);
+            let verify_proposal_content =
+                Self::parse_with_ctx(matches, VerifyProposalContent);
+            let verify_wasm = Self::parse_with_ctx(matches, VerifyWasm);
+            let governance = Self::parse_with_ctx(matches, Governance);
             let query_protocol_parameters =
                 Self::parse_with_ctx(matches, QueryProtocolParameters);
+            let query_parameters_diff =
+                Self::parse_with_ctx(matches, QueryParametersDiff);
             let query_pgf = Self::parse_with_ctx(matches, QueryPgf);
             let query_validator_state =
                 Self::parse_with_ctx(matches, QueryValidatorState);
+            let query_pending_consensus_key =
+                Self::parse_with_ctx(matches, QueryPendingConsensusKey);
             let query_commission =
                 Self::parse_with_ctx(matches, QueryCommissionRate);
             let query_metadata = Self::parse_with_ctx(matches, QueryMetaData);
+            let query_telemetry = Self::parse_with_ctx(matches, QueryTelemetry);
             let add_to_eth_bridge_pool =
                 Self::parse_with_ctx(matches, AddToEthBridgePool);
             let sign_tx = Self::parse_with_ctx(matches, SignTx);
@@ -355,6 +395,11 @@ pub mod cmds {
                 .or(tx_transfer)
                 .or(tx_ibc_transfer)
                 .or(tx_update_account)
+                .or(tx_update_account_signers)
+                .or(tx_update_name)
+                .or(tx_faucet_withdraw)
+                .or(tx_update_faucet_limits)
+                .or(tx_update_transfer_policy)
                 .or(tx_init_account)
                 .or(tx_reveal_pk)
                 .or(tx_init_proposal)
@@ -364,6 +409,7 @@ pub mod cmds {
                 .or(tx_commission_rate_change)
                 .or(tx_change_consensus_key)
                 .or(tx_change_metadata)
+                .or(tx_change_operational_metadata)
                 .or(tx_unjail_validator)
                 .or(tx_deactivate_validator)
                 .or(tx_reactivate_validator)
@@ -378,6 +424,8 @@ pub mod cmds {
                 .or(query_epoch)
                 .or(query_next_epoch_info)
                 .or(query_status)
+                .or(query_chain_registry)
+                .or(query_state_checksum)
                 .or(query_conversions)
                 .or(query_masp_reward_tokens)
                 .or(query_block)
@@ -386,6 +434,7 @@ pub mod cmds {
                 .or(query_bonded_stake)
                 .or(query_slashes)
                 .or(query_rewards)
+                .or(staking)
                 .or(query_delegations)
                 .or(query_find_validator)
                 .or(query_result)
@@ -393,11 +442,17 @@ pub mod cmds {
                 .or(query_proposal)
                 .or(query_proposal_votes)
                 .or(query_proposal_result)
+                .or(verify_proposal_content)
+                .or(verify_wasm)
+                .or(governance)
                 .or(query_protocol_parameters)
+                .or(query_parameters_diff)
                 .or(query_pgf)
                 .or(query_validator_state)
+                .or(query_pending_consensus_key)
                 .or(query_commission)
                 .or(query_metadata)
+                .or(query_telemetry)
                 .or(query_account)
                 .or(sign_tx)
                 .or(shielded_sync)
@@ -443,12 +498,18 @@ pub mod cmds {
         TxIbcTransfer(TxIbcTransfer),
         QueryResult(QueryResult),
         TxUpdateAccount(TxUpdateAccount),
+        TxUpdateAccountSigners(TxUpdateAccountSigners),
+        TxUpdateName(TxUpdateName),
+        TxFaucetWithdraw(TxFaucetWithdraw),
+        TxUpdateFaucetLimits(TxUpdateFaucetLimits),
+        TxUpdateTransferPolicy(TxUpdateTransferPolicy),
         TxInitAccount(TxInitAccount),
         TxBecomeValidator(TxBecomeValidator),
         TxInitValidator(TxInitValidator),
         TxCommissionRateChange(TxCommissionRateChange),
         TxChangeConsensusKey(TxChangeConsensusKey),
         TxMetadataChange(TxMetadataChange),
+        TxOperationalMetadataChange(TxOperationalMetadataChange),
         TxUnjailValidator(TxUnjailValidator),
         TxDeactivateValidator(TxDeactivateValidator),
         TxReactivateValidator(TxReactivateValidator),
@@ -466,6 +527,8 @@ pub mod cmds {
         QueryEpoch(QueryEpoch),
         QueryNextEpochInfo(QueryNextEpochInfo),
         QueryStatus(QueryStatus),
+        QueryChainRegistry(QueryChainRegistry),
+        QueryStateChecksum(QueryStateChecksum),
         QueryAccount(QueryAccount),
         QueryConversions(QueryConversions),
         QueryMaspRewardTokens(QueryMaspRewardTokens),
@@ -475,6 +538,7 @@ pub mod cmds {
         QueryBondedStake(QueryBondedStake),
         QueryCommissionRate(QueryCommissionRate),
         QueryMetaData(QueryMetaData),
+        QueryTelemetry(QueryTelemetry),
         QuerySlashes(QuerySlashes),
         QueryDelegations(QueryDelegations),
         QueryFindValidator(QueryFindValidator),
@@ -482,10 +546,16 @@ pub mod cmds {
         QueryProposal(QueryProposal),
         QueryProposalVotes(QueryProposalVotes),
         QueryProposalResult(QueryProposalResult),
+        VerifyProposalContent(VerifyProposalContent),
+        VerifyWasm(VerifyWasm),
+        Governance(Governance),
         QueryProtocolParameters(QueryProtocolParameters),
+        QueryParametersDiff(QueryParametersDiff),
         QueryPgf(QueryPgf),
         QueryValidatorState(QueryValidatorState),
+        QueryPendingConsensusKey(QueryPendingConsensusKey),
         QueryRewards(QueryRewards),
+        Staking(Staking),
         SignTx(SignTx),
         ShieldedSync(ShieldedSync),
     }
@@ -495,10 +565,14 @@ pub mod cmds {
     pub enum NamadaWallet {
         /// Key generation
         KeyGen(WalletGen),
+        /// Vanity key generation
+        KeyGenVanity(WalletGenVanity),
         /// Key derivation
         KeyDerive(WalletDerive),
         /// Payment address generation
         PayAddrGen(WalletGenPaymentAddress),
+        /// Received payment addresses list
+        PayAddrList(WalletListPaymentAddresses),
         /// Key / address list
         KeyAddrList(WalletListKeysAddresses),
         /// Key / address search
@@ -518,8 +592,10 @@ pub mod cmds {
     impl Cmd for NamadaWallet {
         fn add_sub(app: App) -> App {
             app.subcommand(WalletGen::def())
+                .subcommand(WalletGenVanity::def())
                 .subcommand(WalletDerive::def())
                 .subcommand(WalletGenPaymentAddress::def())
+                .subcommand(WalletListPaymentAddresses::def())
                 .subcommand(WalletListKeysAddresses::def())
                 .subcommand(WalletFindKeysAddresses::def())
                 .subcommand(WalletExportKey::def())
@@ -531,8 +607,10 @@ pub mod cmds {
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             let gen = SubCmd::parse(matches).map(Self::KeyGen);
+            let gen_vanity = SubCmd::parse(matches).map(Self::KeyGenVanity);
             let derive = SubCmd::parse(matches).map(Self::KeyDerive);
             let pay_addr_gen = SubCmd::parse(matches).map(Self::PayAddrGen);
+            let pay_addr_list = SubCmd::parse(matches).map(Self::PayAddrList);
             let key_addr_list = SubCmd::parse(matches).map(Self::KeyAddrList);
             let key_addr_find = SubCmd::parse(matches).map(Self::KeyAddrFind);
             let export = SubCmd::parse(matches).map(Self::KeyExport);
@@ -541,8 +619,10 @@ pub mod cmds {
             let key_addr_add = SubCmd::parse(matches).map(Self::KeyAddrAdd);
             let key_addr_remove =
                 SubCmd::parse(matches).map(Self::KeyAddrRemove);
-            gen.or(derive)
+            gen.or(gen_vanity)
+                .or(derive)
                 .or(pay_addr_gen)
+                .or(pay_addr_list)
                 .or(key_addr_list)
                 .or(key_addr_find)
                 .or(export)
@@ -603,6 +683,36 @@ pub mod cmds {
         }
     }
 
+    /// Generate a new keypair whose implicit address matches a pattern
+    #[derive(Clone, Debug)]
+    pub struct WalletGenVanity(pub args::KeyGenVanity);
+
+    impl SubCmd for WalletGenVanity {
+        const CMD: &'static str = "gen-vanity";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::KeyGenVanity::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Generates a new transparent secret key whose implicit \
+                     address has the given prefix and / or suffix.",
+                )
+                .long_about(
+                    "Generates random non-HD keypairs on multiple threads \
+                     until the implicit address of one of them has the given \
+                     prefix and / or suffix. The keypair and its address are \
+                     stored with the given alias. Every character of the \
+                     pattern multiplies the expected search time by up to 32.",
+                )
+                .add_args::<args::KeyGenVanity>()
+        }
+    }
+
     /// In the transparent setting, derive a keypair and implicit address from
     /// the mnemonic code.
     /// In the shielded setting, derive a spending key from the mnemonic code.
@@ -824,15 +934,46 @@ pub mod cmds {
         }
     }
 
+    /// List the payment addresses of a viewing key that received notes
+    #[derive(Clone, Debug)]
+    pub struct WalletListPaymentAddresses(
+        pub args::PayAddressList<args::CliTypes>,
+    );
+
+    impl SubCmd for WalletListPaymentAddresses {
+        const CMD: &'static str = "list-payment-addrs";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::PayAddressList::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "List the diversified payment addresses of the given \
+                     viewing key that received notes, as of the last shielded \
+                     sync, with the number of notes received by each.",
+                )
+                .add_args::<args::PayAddressList<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub enum Ledger {
         Run(LedgerRun),
         RunUntil(LedgerRunUntil),
         Reset(LedgerReset),
         DumpDb(LedgerDumpDb),
+        DumpWrites(LedgerDumpWrites),
+        DiffWrites(LedgerDiffWrites),
         UpdateDB(LedgerUpdateDB),
         QueryDB(LedgerQueryDB),
         RollBack(LedgerRollBack),
+        RepairDb(LedgerRepairDb),
+        Reindex(LedgerReindex),
+        Verify(LedgerVerify),
     }
 
     impl SubCmd for Ledger {
@@ -843,15 +984,25 @@ pub mod cmds {
                 let run = SubCmd::parse(matches).map(Self::Run);
                 let reset = SubCmd::parse(matches).map(Self::Reset);
                 let dump_db = SubCmd::parse(matches).map(Self::DumpDb);
+                let dump_writes = SubCmd::parse(matches).map(Self::DumpWrites);
+                let diff_writes = SubCmd::parse(matches).map(Self::DiffWrites);
                 let update_db = SubCmd::parse(matches).map(Self::UpdateDB);
                 let query_db = SubCmd::parse(matches).map(Self::QueryDB);
                 let rollback = SubCmd::parse(matches).map(Self::RollBack);
+                let repair_db = SubCmd::parse(matches).map(Self::RepairDb);
+                let reindex = SubCmd::parse(matches).map(Self::Reindex);
+                let verify = SubCmd::parse(matches).map(Self::Verify);
                 let run_until = SubCmd::parse(matches).map(Self::RunUntil);
                 run.or(reset)
                     .or(dump_db)
+                    .or(dump_writes)
+                    .or(diff_writes)
                     .or(update_db)
                     .or(query_db)
                     .or(rollback)
+                    .or(repair_db)
+                    .or(reindex)
+                    .or(verify)
                     .or(run_until)
                     // The `run` command is the default if no sub-command given
                     .or(Some(Self::Run(LedgerRun(args::LedgerRun {
@@ -870,9 +1021,14 @@ pub mod cmds {
                 .subcommand(LedgerRunUntil::def())
                 .subcommand(LedgerReset::def())
                 .subcommand(LedgerDumpDb::def())
+                .subcommand(LedgerDumpWrites::def())
+                .subcommand(LedgerDiffWrites::def())
                 .subcommand(LedgerUpdateDB::def())
                 .subcommand(LedgerQueryDB::def())
                 .subcommand(LedgerRollBack::def())
+                .subcommand(LedgerRepairDb::def())
+                .subcommand(LedgerReindex::def())
+                .subcommand(LedgerVerify::def())
         }
     }
 
@@ -954,6 +1110,53 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct LedgerDumpWrites(pub args::LedgerDumpWrites);
+
+    impl SubCmd for LedgerDumpWrites {
+        const CMD: &'static str = "dump-writes";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::LedgerDumpWrites::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Dump the sorted keys written at a block height with the \
+                     hashes of their values into a file, to be compared with \
+                     the dump of another node after an app hash mismatch.",
+                )
+                .add_args::<args::LedgerDumpWrites>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerDiffWrites(pub args::LedgerDiffWrites);
+
+    impl SubCmd for LedgerDiffWrites {
+        const CMD: &'static str = "diff-writes";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::LedgerDiffWrites::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Compare the dumps of the keys written at a block height \
+                     by two nodes, produced with the `dump-writes` command. \
+                     The divergent keys are counted per subsystem, i.e. per \
+                     first key segment.",
+                )
+                .add_args::<args::LedgerDiffWrites>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct LedgerUpdateDB(pub args::LedgerUpdateDb);
 
@@ -1019,6 +1222,74 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct LedgerRepairDb;
+
+    impl SubCmd for LedgerRepairDb {
+        const CMD: &'static str = "repair-db";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|_matches| Self)
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD).about(
+                "Revert the last block if it was not written completely, e.g. \
+                 because the node crashed while committing it, so that it \
+                 gets replayed by CometBFT. The node refuses to start until \
+                 this command is run. The node must not be running.",
+            )
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerReindex(pub args::LedgerReindex);
+
+    impl SubCmd for LedgerReindex {
+        const CMD: &'static str = "reindex";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::LedgerReindex::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Rebuild the CometBFT indexes of the txs and block events \
+                     from the stored blocks, e.g. after enabling the tx \
+                     indexer, instead of resyncing from genesis. The ledger \
+                     must not be running.",
+                )
+                .add_args::<args::LedgerReindex>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerVerify(pub args::LedgerVerify);
+
+    impl SubCmd for LedgerVerify {
+        const CMD: &'static str = "verify";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::LedgerVerify::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Replay the blocks of a CometBFT node from genesis, or \
+                     from a copy of the DB of a node, into a separate DB and \
+                     compare the resulting app hashes with the ones agreed \
+                     upon by the network. Reports the first divergent height.",
+                )
+                .add_args::<args::LedgerVerify>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub enum Config {
         Gen(ConfigGen),
@@ -1146,6 +1417,71 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct VerifyProposalContent(
+        pub args::VerifyProposalContent<args::CliTypes>,
+    );
+
+    impl SubCmd for VerifyProposalContent {
+        const CMD: &'static str = "verify-proposal-content";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                VerifyProposalContent(args::VerifyProposalContent::parse(
+                    matches,
+                ))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Verify an off-chain proposal content against the hash \
+                     anchored in the proposal.",
+                )
+                .arg_required_else_help(true)
+                .add_args::<args::VerifyProposalContent<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct VerifyWasm(pub args::VerifyWasm<args::CliTypes>);
+
+    impl SubCmd for VerifyWasm {
+        const CMD: &'static str = "verify-wasm";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| VerifyWasm(args::VerifyWasm::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Verify the wasm artifacts of the wasm directory against \
+                     their checksums and the code deployed on chain.",
+                )
+                .long_about(
+                    "Verify the wasm artifacts of the wasm directory against \
+                     their checksums and the code deployed on chain under \
+                     their names, and report whether they are allowlisted. To \
+                     verify that the code on chain corresponds to the audited \
+                     source, rebuild the artifacts from the source with `make \
+                     build-wasm-scripts-docker` and point `--wasm-dir` at the \
+                     rebuilt artifacts. Exits with an error if an artifact \
+                     mismatches.",
+                )
+                .add_args::<args::VerifyWasm<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryProposalResult(
         pub args::QueryProposalResult<args::CliTypes>,
@@ -1170,6 +1506,80 @@ pub mod cmds {
         }
     }
 
+    /// Proposal lifecycle queries
+    #[derive(Clone, Debug)]
+    pub enum Governance {
+        /// Query the lifecycle of a proposal
+        Status(GovernanceStatus),
+        /// Query the current tally of a proposal with its thresholds
+        Tally(GovernanceTally),
+    }
+
+    impl SubCmd for Governance {
+        const CMD: &'static str = "governance";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).and_then(|matches| {
+                let status = GovernanceStatus::parse(matches).map(Self::Status);
+                let tally = GovernanceTally::parse(matches).map(Self::Tally);
+                status.or(tally)
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Governance proposal lifecycle queries.")
+                .subcommand_required(true)
+                .subcommand(GovernanceStatus::def().display_order(1))
+                .subcommand(GovernanceTally::def().display_order(1))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct GovernanceStatus(pub args::QueryProposalResult<args::CliTypes>);
+
+    impl SubCmd for GovernanceStatus {
+        const CMD: &'static str = "status";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                GovernanceStatus(args::QueryProposalResult::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the lifecycle of a proposal: its submission, \
+                     voting window, current tally with its thresholds and \
+                     activation schedule.",
+                )
+                .add_args::<args::QueryProposalResult<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct GovernanceTally(pub args::QueryProposalResult<args::CliTypes>);
+
+    impl SubCmd for GovernanceTally {
+        const CMD: &'static str = "tally";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                GovernanceTally(args::QueryProposalResult::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the current tally of a proposal with the \
+                     thresholds it must meet to pass.",
+                )
+                .add_args::<args::QueryProposalResult<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryProtocolParameters(
         pub args::QueryProtocolParameters<args::CliTypes>,
@@ -1196,6 +1606,33 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryParametersDiff(
+        pub args::QueryParametersDiff<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryParametersDiff {
+        const CMD: &'static str = "query-parameters-diff";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryParametersDiff(args::QueryParametersDiff::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the changes of the protocol, PoS, governance and \
+                     PGF parameters between two heights or epochs.",
+                )
+                .add_args::<args::QueryParametersDiff<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryPgf(pub args::QueryPgf<args::CliTypes>);
 
@@ -1298,96 +1735,219 @@ pub mod cmds {
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxInitAccount(pub args::TxInitAccount<args::CliTypes>);
+    pub struct TxUpdateAccountSigners(
+        pub args::TxUpdateAccountSigners<args::CliTypes>,
+    );
 
-    impl SubCmd for TxInitAccount {
-        const CMD: &'static str = "init-account";
+    impl SubCmd for TxUpdateAccountSigners {
+        const CMD: &'static str = "update-account-signers";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxInitAccount(args::TxInitAccount::parse(matches))
+                TxUpdateAccountSigners(args::TxUpdateAccountSigners::parse(
+                    matches,
+                ))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Send a signed transaction to create a new established \
-                     account.",
+                    "Send a transaction signed by the account's current keys \
+                     to add or remove its public keys and change its \
+                     threshold. Use --dump-tx and sign-tx to collect the \
+                     signatures of a multisignature account offline.",
                 )
-                .add_args::<args::TxInitAccount<args::CliTypes>>()
+                .add_args::<args::TxUpdateAccountSigners<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxBecomeValidator(pub args::TxBecomeValidator<args::CliTypes>);
+    pub struct TxUpdateName(pub args::TxUpdateName<args::CliTypes>);
 
-    impl SubCmd for TxBecomeValidator {
-        const CMD: &'static str = "become-validator";
+    impl SubCmd for TxUpdateName {
+        const CMD: &'static str = "update-name";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxBecomeValidator(args::TxBecomeValidator::parse(matches))
-            })
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| TxUpdateName(args::TxUpdateName::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Send a signed transaction to become a validator.")
-                .add_args::<args::TxBecomeValidator<args::CliTypes>>()
+                .about(
+                    "Send a signed transaction to register, update or delete \
+                     a name in the on-chain name registry.",
+                )
+                .add_args::<args::TxUpdateName<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxInitValidator(pub args::TxInitValidator<args::CliTypes>);
+    pub struct TxFaucetWithdraw(pub args::TxFaucetWithdraw<args::CliTypes>);
 
-    impl SubCmd for TxInitValidator {
-        const CMD: &'static str = "init-validator";
+    impl SubCmd for TxFaucetWithdraw {
+        const CMD: &'static str = "faucet-withdraw";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxInitValidator(args::TxInitValidator::parse(matches))
+                TxFaucetWithdraw(args::TxFaucetWithdraw::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Send a signed transaction to create an established \
-                     account and then become a validator.",
+                    "Send a transaction to withdraw tokens from a faucet, \
+                     within the faucet's limits per address and per epoch.",
                 )
-                .add_args::<args::TxInitValidator<args::CliTypes>>()
+                .add_args::<args::TxFaucetWithdraw<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxUnjailValidator(pub args::TxUnjailValidator<args::CliTypes>);
+    pub struct TxUpdateFaucetLimits(
+        pub args::TxUpdateFaucetLimits<args::CliTypes>,
+    );
 
-    impl SubCmd for TxUnjailValidator {
-        const CMD: &'static str = "unjail-validator";
+    impl SubCmd for TxUpdateFaucetLimits {
+        const CMD: &'static str = "update-faucet-limits";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                TxUnjailValidator(args::TxUnjailValidator::parse(matches))
+                TxUpdateFaucetLimits(args::TxUpdateFaucetLimits::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Send a signed transaction to unjail a jailed validator.",
+                    "Send a signed transaction to set the withdrawal limits \
+                     of a token of a faucet.",
                 )
-                .add_args::<args::TxUnjailValidator<args::CliTypes>>()
+                .add_args::<args::TxUpdateFaucetLimits<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxDeactivateValidator(
-        pub args::TxDeactivateValidator<args::CliTypes>,
+    pub struct TxUpdateTransferPolicy(
+        pub args::TxUpdateTransferPolicy<args::CliTypes>,
     );
 
-    impl SubCmd for TxDeactivateValidator {
-        const CMD: &'static str = "deactivate-validator";
+    impl SubCmd for TxUpdateTransferPolicy {
+        const CMD: &'static str = "update-transfer-policy";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxUpdateTransferPolicy(args::TxUpdateTransferPolicy::parse(
+                    matches,
+                ))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Send a signed transaction to update or remove the \
+                     transfer policy of an account. The policy is enforced by \
+                     the \"vp_user_policy\" validity predicate.",
+                )
+                .add_args::<args::TxUpdateTransferPolicy<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxInitAccount(pub args::TxInitAccount<args::CliTypes>);
+
+    impl SubCmd for TxInitAccount {
+        const CMD: &'static str = "init-account";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxInitAccount(args::TxInitAccount::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Send a signed transaction to create a new established \
+                     account.",
+                )
+                .add_args::<args::TxInitAccount<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxBecomeValidator(pub args::TxBecomeValidator<args::CliTypes>);
+
+    impl SubCmd for TxBecomeValidator {
+        const CMD: &'static str = "become-validator";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxBecomeValidator(args::TxBecomeValidator::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Send a signed transaction to become a validator.")
+                .add_args::<args::TxBecomeValidator<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxInitValidator(pub args::TxInitValidator<args::CliTypes>);
+
+    impl SubCmd for TxInitValidator {
+        const CMD: &'static str = "init-validator";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxInitValidator(args::TxInitValidator::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Send a signed transaction to create an established \
+                     account and then become a validator.",
+                )
+                .add_args::<args::TxInitValidator<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxUnjailValidator(pub args::TxUnjailValidator<args::CliTypes>);
+
+    impl SubCmd for TxUnjailValidator {
+        const CMD: &'static str = "unjail-validator";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxUnjailValidator(args::TxUnjailValidator::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Send a signed transaction to unjail a jailed validator.",
+                )
+                .add_args::<args::TxUnjailValidator<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxDeactivateValidator(
+        pub args::TxDeactivateValidator<args::CliTypes>,
+    );
+
+    impl SubCmd for TxDeactivateValidator {
+        const CMD: &'static str = "deactivate-validator";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
@@ -1613,6 +2173,50 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryChainRegistry(pub args::Query<args::CliTypes>);
+
+    impl SubCmd for QueryChainRegistry {
+        const CMD: &'static str = "chain-registry";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| QueryChainRegistry(args::Query::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Print the chain-registry-style JSON metadata of the \
+                     chain, from its on-chain parameters.",
+                )
+                .add_args::<args::Query<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryStateChecksum(pub args::Query<args::CliTypes>);
+
+    impl SubCmd for QueryStateChecksum {
+        const CMD: &'static str = "state-checksum";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| QueryStateChecksum(args::Query::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the checksum of the state at the last committed \
+                     height, to compare it with the state of other nodes.",
+                )
+                .add_args::<args::Query<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryAccount(pub args::QueryAccount<args::CliTypes>);
 
@@ -1792,6 +2396,32 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryPendingConsensusKey(
+        pub args::QueryPendingConsensusKey<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryPendingConsensusKey {
+        const CMD: &'static str = "pending-consensus-key";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryPendingConsensusKey(args::QueryPendingConsensusKey::parse(
+                    matches,
+                ))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the new consensus key of a PoS validator that is \
+                     not in effect yet, and when it comes into effect.",
+                )
+                .add_args::<args::QueryPendingConsensusKey<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryCommissionRate(
         pub args::QueryCommissionRate<args::CliTypes>,
@@ -1832,6 +2462,29 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryTelemetry(pub args::QueryTelemetry<args::CliTypes>);
+
+    impl SubCmd for QueryTelemetry {
+        const CMD: &'static str = "query-telemetry";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryTelemetry(args::QueryTelemetry::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the binaries reported by the telemetry heartbeats \
+                     of the consensus validators, weighted by stake, or the \
+                     last heartbeat of a validator.",
+                )
+                .add_args::<args::QueryTelemetry<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QuerySlashes(pub args::QuerySlashes<args::CliTypes>);
 
@@ -1876,6 +2529,55 @@ pub mod cmds {
         }
     }
 
+    /// Staking queries
+    #[derive(Clone, Debug)]
+    pub enum Staking {
+        /// Query the staking overview of an owner
+        Overview(QueryStakingOverview),
+    }
+
+    impl SubCmd for Staking {
+        const CMD: &'static str = "staking";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).and_then(|matches| {
+                QueryStakingOverview::parse(matches).map(Self::Overview)
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Staking queries.")
+                .subcommand_required(true)
+                .subcommand(QueryStakingOverview::def().display_order(1))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryStakingOverview(
+        pub args::QueryStakingOverview<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryStakingOverview {
+        const CMD: &'static str = "overview";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryStakingOverview(args::QueryStakingOverview::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the bonds, unbonds with their withdrawable epochs, \
+                     rewards available to claim and slashing exposure of an \
+                     owner with all their validators.",
+                )
+                .add_args::<args::QueryStakingOverview<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryDelegations(pub args::QueryDelegations<args::CliTypes>);
 
@@ -2063,6 +2765,37 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct TxOperationalMetadataChange(
+        pub args::OperationalMetadataChange<args::CliTypes>,
+    );
+
+    impl SubCmd for TxOperationalMetadataChange {
+        const CMD: &'static str = "change-operational-metadata";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxOperationalMetadataChange(
+                    args::OperationalMetadataChange::parse(matches),
+                )
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Publish a validator's operational metadata (public RPC \
+                     endpoint, seed node and emergency contact) with a \
+                     protocol tx signed by its protocol key. The published \
+                     metadata is replaced entirely.",
+                )
+                .add_args::<args::OperationalMetadataChange<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxChangeConsensusKey(
         pub args::ConsensusKeyChange<args::CliTypes>,
@@ -2971,18 +3704,22 @@ pub mod args {
     use namada::core::token::NATIVE_MAX_DECIMAL_PLACES;
     use namada::ibc::core::host::types::identifiers::{ChannelId, PortId};
     use namada::tx::data::GasLimit;
+    use namada::tx::DataEncoding;
     pub use namada_sdk::args::*;
     pub use namada_sdk::tx::{
         TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
         TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
         TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
-        TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
-        TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-        TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
-        TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-        TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
-        VP_USER_WASM,
+        TX_DEACTIVATE_VALIDATOR_WASM, TX_FAUCET_WITHDRAW_WASM, TX_IBC_WASM,
+        TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM,
+        TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM,
+        TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM,
+        TX_UPDATE_ACCOUNT_SIGNERS_WASM, TX_UPDATE_ACCOUNT_WASM,
+        TX_UPDATE_FAUCET_LIMITS_WASM, TX_UPDATE_NAME_WASM,
+        TX_UPDATE_STEWARD_COMMISSION, TX_UPDATE_TRANSFER_POLICY_WASM,
+        TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM, VP_USER_WASM,
     };
+    use namada_sdk::wallet::keystore::KeyFormat;
     use namada_sdk::DEFAULT_GAS_LIMIT;
 
     use super::context::*;
@@ -2994,6 +3731,8 @@ pub mod args {
     use crate::facade::tendermint::Timeout;
     use crate::facade::tendermint_rpc::Url;
 
+    pub const ADD_PUBLIC_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
+        arg_multi("add-public-keys");
     pub const ADDRESS: Arg<WalletAddress> = arg("address");
     pub const ALIAS_OPT: ArgOpt<String> = ALIAS.opt();
     pub const ALIAS: Arg<String> = arg("alias");
@@ -3046,11 +3785,14 @@ pub mod args {
     pub const CODE_PATH_OPT: ArgOpt<PathBuf> = CODE_PATH.opt();
     pub const COMMISSION_RATE: Arg<Dec> = arg("commission-rate");
     pub const COMMISSION_RATE_OPT: ArgOpt<Dec> = COMMISSION_RATE.opt();
+    pub const CONFIRM_FEES: ArgFlag = flag("confirm-fees");
     pub const CONSENSUS_TIMEOUT_COMMIT: ArgDefault<Timeout> = arg_default(
         "consensus-timeout-commit",
         DefaultFn(|| Timeout::from_str("1s").unwrap()),
     );
     pub const CONVERSION_TABLE: Arg<PathBuf> = arg("conversion-table");
+    pub const COUNTERPARTIES: ArgMulti<WalletAddress, GlobStar> =
+        arg_multi("counterparties");
     pub const DAEMON_MODE: ArgFlag = flag("daemon");
     pub const DAEMON_MODE_RETRY_DUR: ArgOpt<Duration> = arg_opt("retry-sleep");
     pub const DAEMON_MODE_SUCCESS_DUR: ArgOpt<Duration> =
@@ -3058,6 +3800,8 @@ pub mod args {
     pub const DATA_PATH_OPT: ArgOpt<PathBuf> = arg_opt("data-path");
     pub const DATA_PATH: Arg<PathBuf> = arg("data-path");
     pub const DB_KEY: Arg<String> = arg("db-key");
+    pub const DATA_ENCODING: ArgDefault<DataEncoding> =
+        arg_default("data-encoding", DefaultFn(|| DataEncoding::Borsh));
     pub const DB_COLUMN_FAMILY: ArgDefault<String> = arg_default(
         "db-column-family",
         DefaultFn(|| storage::SUBSPACE_CF.to_string()),
@@ -3068,6 +3812,7 @@ pub mod args {
     pub const DESTINATION_VALIDATOR: Arg<WalletAddress> =
         arg("destination-validator");
     pub const DISCORD_OPT: ArgOpt<String> = arg_opt("discord-handle");
+    pub const DIVERSIFIER_INDEX_OPT: ArgOpt<u64> = arg_opt("diversifier-index");
     pub const DO_IT: ArgFlag = flag("do-it");
     pub const DONT_ARCHIVE: ArgFlag = flag("dont-archive");
     pub const DONT_PREFETCH_WASM: ArgFlag = flag("dont-prefetch-wasm");
@@ -3075,6 +3820,8 @@ pub mod args {
     pub const DRY_RUN_WRAPPER_TX: ArgFlag = flag("dry-run-wrapper");
     pub const DUMP_TX: ArgFlag = flag("dump-tx");
     pub const EPOCH: ArgOpt<Epoch> = arg_opt("epoch");
+    pub const EPOCH_FROM_OPT: ArgOpt<Epoch> = arg_opt("from-epoch");
+    pub const EPOCH_TO_OPT: ArgOpt<Epoch> = arg_opt("to-epoch");
     pub const ERC20: Arg<EthAddress> = arg("erc20");
     pub const ETH_CONFIRMATIONS: Arg<u64> = arg("confirmations");
     pub const ETH_GAS: ArgOpt<u64> = arg_opt("eth-gas");
@@ -3089,8 +3836,19 @@ pub mod args {
     pub const EXPIRATION_OPT: ArgOpt<DateTimeUtc> = arg_opt("expiration");
     pub const EMAIL: Arg<String> = arg("email");
     pub const EMAIL_OPT: ArgOpt<String> = EMAIL.opt();
+    pub const EMERGENCY_CONTACT_OPT: ArgOpt<String> =
+        arg_opt("emergency-contact");
+    pub const FAUCET: Arg<WalletAddress> = arg("faucet");
+    pub const FAUCET_LIMIT_PER_ADDRESS: Arg<token::DenominatedAmount> =
+        arg("limit-per-address");
+    pub const FAUCET_LIMIT_PER_EPOCH: Arg<token::DenominatedAmount> =
+        arg("limit-per-epoch");
+    pub const FAUCET_TARGET: Arg<WalletAddress> = arg("target");
     pub const FEE_UNSHIELD_SPENDING_KEY: ArgOpt<WalletTransferSource> =
         arg_opt("gas-spending-key");
+    pub const FEE_UNSHIELD_HEADROOM_OPT: ArgOpt<token::DenominatedAmount> =
+        arg_opt("gas-unshielding-headroom");
+    pub const FEE_UNSHIELD_SHIELDED: ArgFlag = flag("gas-shielded");
     pub const FEE_AMOUNT_OPT: ArgOpt<token::DenominatedAmount> =
         arg_opt("gas-price");
     pub const FEE_PAYER_OPT: ArgOpt<WalletPublicKey> = arg_opt("gas-payer");
@@ -3130,6 +3888,8 @@ pub mod args {
     pub const HISTORIC: ArgFlag = flag("historic");
     pub const IBC_TRANSFER_MEMO_PATH: ArgOpt<PathBuf> = arg_opt("memo-path");
     pub const INPUT_OPT: ArgOpt<PathBuf> = arg_opt("input");
+    pub const KEY_FORMAT: ArgDefault<KeyFormat> =
+        arg_default("format", DefaultFn(|| KeyFormat::Namada));
     pub const LEDGER_ADDRESS_ABOUT: &str =
         "Address of a ledger node as \"{scheme}://{host}:{port}\". If the \
          scheme is not supplied, it is assumed to be TCP.";
@@ -3154,12 +3914,14 @@ pub mod args {
     pub const NO_CONVERSIONS: ArgFlag = flag("no-conversions");
     pub const NO_EXPIRATION: ArgFlag = flag("no-expiration");
     pub const NUT: ArgFlag = flag("nut");
+    pub const OTHER_PATH: Arg<PathBuf> = arg("other-path");
     pub const OUT_FILE_PATH_OPT: ArgOpt<PathBuf> = arg_opt("out-file-path");
     pub const OUTPUT: ArgOpt<PathBuf> = arg_opt("output");
     pub const OUTPUT_FOLDER_PATH: ArgOpt<PathBuf> =
         arg_opt("output-folder-path");
     pub const OWNER: Arg<WalletAddress> = arg("owner");
     pub const OWNER_OPT: ArgOpt<WalletAddress> = OWNER.opt();
+    pub const NAME_TARGET_OPT: ArgOpt<WalletAddress> = arg_opt("target");
     pub const PATH: Arg<PathBuf> = arg("path");
     pub const PORT_ID: ArgDefault<PortId> = arg_default(
         "port-id",
@@ -3169,6 +3931,10 @@ pub mod args {
     pub const PROPOSAL_ETH: ArgFlag = flag("eth");
     pub const PROPOSAL_PGF_STEWARD: ArgFlag = flag("pgf-stewards");
     pub const PROPOSAL_PGF_FUNDING: ArgFlag = flag("pgf-funding");
+    pub const PROPOSAL_EMERGENCY_PAUSE: ArgFlag = flag("emergency-pause");
+    pub const PROPOSAL_EXPEDITED: ArgFlag = flag("expedited");
+    pub const PROPOSAL_PGF_STEWARD_ELECTION: ArgFlag =
+        flag("pgf-steward-election");
     pub const PROTOCOL_KEY: ArgOpt<WalletPublicKey> = arg_opt("protocol-key");
     pub const PRE_GENESIS_PATH: ArgOpt<PathBuf> = arg_opt("pre-genesis-path");
     pub const PUBLIC_KEY: Arg<WalletPublicKey> = arg("public-key");
@@ -3196,10 +3962,15 @@ pub mod args {
     pub const REFUND: ArgFlag = flag("refund");
     pub const REFUND_TARGET: ArgOpt<WalletTransferTarget> =
         arg_opt("refund-target");
+    pub const REGISTERED_NAME: Arg<String> = arg("name");
     pub const RELAYER: Arg<Address> = arg("relayer");
+    pub const REMOVE_PUBLIC_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
+        arg_multi("remove-public-keys");
+    pub const RPC_ENDPOINT_OPT: ArgOpt<String> = arg_opt("rpc-endpoint");
     pub const SAFE_MODE: ArgFlag = flag("safe-mode");
     pub const SCHEME: ArgDefault<SchemeType> =
         arg_default("scheme", DefaultFn(|| SchemeType::Ed25519));
+    pub const SEED_NODE_OPT: ArgOpt<String> = arg_opt("seed-node");
     pub const SELF_BOND_AMOUNT: Arg<token::DenominatedAmount> =
         arg("self-bond-amount");
     pub const SENDER: Arg<String> = arg("sender");
@@ -3209,6 +3980,7 @@ pub mod args {
     pub const SIGNING_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
         arg_multi("signing-keys");
     pub const SIGNATURES: ArgMulti<PathBuf, GlobStar> = arg_multi("signatures");
+    pub const SNAPSHOT_PATH: ArgOpt<PathBuf> = arg_opt("snapshot");
     pub const SOURCE: Arg<WalletAddress> = arg("source");
     pub const SOURCE_OPT: ArgOpt<WalletAddress> = SOURCE.opt();
     pub const SPENDING_KEYS: ArgMulti<WalletSpendingKey, GlobStar> =
@@ -3218,6 +3990,7 @@ pub mod args {
     pub const STORAGE_KEY: Arg<storage::Key> = arg("storage-key");
     pub const SUSPEND_ACTION: ArgFlag = flag("suspend");
     pub const TEMPLATES_PATH: Arg<PathBuf> = arg("templates-path");
+    pub const THREADS: ArgOpt<usize> = arg_opt("threads");
     pub const TIMEOUT_HEIGHT: ArgOpt<u64> = arg_opt("timeout-height");
     pub const TIMEOUT_SEC_OFFSET: ArgOpt<u64> = arg_opt("timeout-sec-offset");
     pub const TM_ADDRESS: ArgOpt<String> = arg_opt("tm-address");
@@ -3225,6 +3998,9 @@ pub mod args {
     pub const TOKEN_STR_OPT: ArgOpt<String> = TOKEN_STR.opt();
     pub const TOKEN: Arg<WalletAddress> = arg("token");
     pub const TOKEN_STR: Arg<String> = arg("token");
+    pub const TRANSFER_POLICY_MAX_AMOUNT: ArgOpt<token::DenominatedAmount> =
+        arg_opt("max-amount");
+    pub const TRANSFER_POLICY_REMOVE: ArgFlag = flag("remove");
     pub const TRANSFER_SOURCE: Arg<WalletTransferSource> = arg("source");
     pub const TRANSFER_TARGET: Arg<WalletTransferTarget> = arg("target");
     pub const TRANSPARENT: ArgFlag = flag("transparent");
@@ -3249,6 +4025,8 @@ pub mod args {
     pub const VALIDATOR_ETH_HOT_KEY: ArgOpt<WalletPublicKey> =
         arg_opt("eth-hot-key");
     pub const VALUE: Arg<String> = arg("value");
+    pub const VANITY_PREFIX: ArgOpt<String> = arg_opt("prefix");
+    pub const VANITY_SUFFIX: ArgOpt<String> = arg_opt("suffix");
     pub const VOTER_OPT: ArgOpt<WalletAddress> = arg_opt("voter");
     pub const VIEWING_KEY: Arg<WalletViewingKey> = arg("key");
     pub const VIEWING_KEYS: ArgMulti<WalletViewingKey, GlobStar> =
@@ -3421,57 +4199,176 @@ pub mod args {
     }
 
     #[derive(Clone, Debug)]
-    pub struct LedgerUpdateDb {
-        pub updates: PathBuf,
-        pub dry_run: bool,
-        pub last_height: BlockHeight,
+    pub struct LedgerReindex {
+        pub from_height: Option<BlockHeight>,
+        pub to_height: Option<BlockHeight>,
     }
 
-    impl Args for LedgerUpdateDb {
+    impl Args for LedgerReindex {
         fn parse(matches: &ArgMatches) -> Self {
-            let updates = PATH.parse(matches);
-            let dry_run = DRY_RUN_TX.parse(matches);
-            let last_height = BLOCK_HEIGHT.parse(matches);
+            let from_height = BLOCK_HEIGHT_FROM_OPT.parse(matches);
+            let to_height = BLOCK_HEIGHT_TO_OPT.parse(matches);
             Self {
-                updates,
-                dry_run,
-                last_height,
+                from_height,
+                to_height,
             }
         }
 
         fn def(app: App) -> App {
-            app.arg(PATH.def().help(
-                "The path to a json of key-value pairs to update the DB with.",
+            app.arg(BLOCK_HEIGHT_FROM_OPT.def().help(
+                "The first block height to reindex. Defaults to the first \
+                 stored block.",
             ))
-            .arg(DRY_RUN_TX.def().help(
-                "If set, applies the updates but does not persist them. Using \
-                 for testing and debugging.",
+            .arg(BLOCK_HEIGHT_TO_OPT.def().help(
+                "The last block height to reindex. Defaults to the last \
+                 stored block.",
             ))
-            .arg(
-                BLOCK_HEIGHT
-                    .def()
-                    .help("The height at which the hard fork is happening."),
-            )
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct LedgerQueryDb {
-        pub key: storage::Key,
-        pub hash: [u8; 32],
-        pub cf: storage::DbColFam,
+    pub struct LedgerVerify {
+        pub ledger_address: Url,
+        pub snapshot: Option<PathBuf>,
+        pub to_height: Option<BlockHeight>,
     }
 
-    impl Args for LedgerQueryDb {
+    impl Args for LedgerVerify {
         fn parse(matches: &ArgMatches) -> Self {
-            let key = storage::Key::parse(DB_KEY.parse(matches)).unwrap();
-            let hex_hash = HASH.parse(matches);
-            let hash: [u8; 32] = HEXUPPER
-                .decode(hex_hash.to_uppercase().as_bytes())
-                .unwrap()
-                .try_into()
-                .unwrap();
-            let cf =
+            let ledger_address = LEDGER_ADDRESS.parse(matches);
+            let snapshot = SNAPSHOT_PATH.parse(matches);
+            let to_height = BLOCK_HEIGHT_TO_OPT.parse(matches);
+            Self {
+                ledger_address,
+                snapshot,
+                to_height,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(LEDGER_ADDRESS.def().help(
+                "The CometBFT RPC address of a node to fetch the blocks from.",
+            ))
+            .arg(SNAPSHOT_PATH.def().help(
+                "The path to a copy of the DB of a node to replay the blocks \
+                 on top of, instead of replaying them from genesis. The copy \
+                 is modified in place.",
+            ))
+            .arg(BLOCK_HEIGHT_TO_OPT.def().help(
+                "The last block height to verify. Defaults to the last block \
+                 whose app hash is known.",
+            ))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerDumpWrites {
+        pub block_height: Option<BlockHeight>,
+        pub out_file_path: PathBuf,
+    }
+
+    impl Args for LedgerDumpWrites {
+        fn parse(matches: &ArgMatches) -> Self {
+            let block_height = BLOCK_HEIGHT_OPT.parse(matches);
+            let out_file_path = OUT_FILE_PATH_OPT
+                .parse(matches)
+                .unwrap_or_else(|| PathBuf::from("writes_dump".to_string()));
+
+            Self {
+                block_height,
+                out_file_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(BLOCK_HEIGHT_OPT.def().help(
+                "The block height of the writes. Defaults to the latest \
+                 committed block. The keys written without persisting their \
+                 diffs are only included at the latest committed block.",
+            ))
+            .arg(OUT_FILE_PATH_OPT.def().help(
+                "Path for the output file (omitting file extension). Defaults \
+                 to \"writes_dump_{block_height}.toml\" in the current \
+                 working directory.",
+            ))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerDiffWrites {
+        pub path: PathBuf,
+        pub other_path: PathBuf,
+    }
+
+    impl Args for LedgerDiffWrites {
+        fn parse(matches: &ArgMatches) -> Self {
+            let path = PATH.parse(matches);
+            let other_path = OTHER_PATH.parse(matches);
+            Self { path, other_path }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(PATH.def().help("The dump of the writes of this node."))
+                .arg(
+                    OTHER_PATH
+                        .def()
+                        .help("The dump of the writes of the other node."),
+                )
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerUpdateDb {
+        pub updates: PathBuf,
+        pub dry_run: bool,
+        pub last_height: BlockHeight,
+    }
+
+    impl Args for LedgerUpdateDb {
+        fn parse(matches: &ArgMatches) -> Self {
+            let updates = PATH.parse(matches);
+            let dry_run = DRY_RUN_TX.parse(matches);
+            let last_height = BLOCK_HEIGHT.parse(matches);
+            Self {
+                updates,
+                dry_run,
+                last_height,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(PATH.def().help(
+                "The path to a json of key-value pairs to update the DB with.",
+            ))
+            .arg(DRY_RUN_TX.def().help(
+                "If set, applies the updates but does not persist them. Using \
+                 for testing and debugging.",
+            ))
+            .arg(
+                BLOCK_HEIGHT
+                    .def()
+                    .help("The height at which the hard fork is happening."),
+            )
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LedgerQueryDb {
+        pub key: storage::Key,
+        pub hash: [u8; 32],
+        pub cf: storage::DbColFam,
+    }
+
+    impl Args for LedgerQueryDb {
+        fn parse(matches: &ArgMatches) -> Self {
+            let key = storage::Key::parse(DB_KEY.parse(matches)).unwrap();
+            let hex_hash = HASH.parse(matches);
+            let hash: [u8; 32] = HEXUPPER
+                .decode(hex_hash.to_uppercase().as_bytes())
+                .unwrap()
+                .try_into()
+                .unwrap();
+            let cf =
                 storage::DbColFam::from_str(&DB_COLUMN_FAMILY.parse(matches))
                     .unwrap();
             Self { key, hash, cf }
@@ -4723,6 +5620,329 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<TxUpdateAccountSigners<SdkTypes>>
+        for TxUpdateAccountSigners<CliTypes>
+    {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<TxUpdateAccountSigners<SdkTypes>, Self::Error> {
+            let tx = self.tx.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+
+            Ok(TxUpdateAccountSigners::<SdkTypes> {
+                tx,
+                tx_code_path: self.tx_code_path,
+                addr: chain_ctx.get(&self.addr),
+                add_public_keys: self
+                    .add_public_keys
+                    .iter()
+                    .map(|pk| chain_ctx.get(pk))
+                    .collect(),
+                remove_public_keys: self
+                    .remove_public_keys
+                    .iter()
+                    .map(|pk| chain_ctx.get(pk))
+                    .collect(),
+                threshold: self.threshold,
+            })
+        }
+    }
+
+    impl Args for TxUpdateAccountSigners<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let addr = ADDRESS.parse(matches);
+            let tx_code_path = PathBuf::from(TX_UPDATE_ACCOUNT_SIGNERS_WASM);
+            let add_public_keys = ADD_PUBLIC_KEYS.parse(matches);
+            let remove_public_keys = REMOVE_PUBLIC_KEYS.parse(matches);
+            let threshold = THRESHOLD.parse(matches);
+            Self {
+                tx,
+                tx_code_path,
+                addr,
+                add_public_keys,
+                remove_public_keys,
+                threshold,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(ADDRESS.def().help("The account's address."))
+                .arg(ADD_PUBLIC_KEYS.def().help(
+                    "A list of public keys to add to the account in \
+                     hexadecimal encoding.",
+                ))
+                .arg(REMOVE_PUBLIC_KEYS.def().help(
+                    "A list of public keys to remove from the account in \
+                     hexadecimal encoding.",
+                ))
+                .arg(THRESHOLD.def().help(
+                    "The new minimum number of signatures to be provided for \
+                     authorization. Defaults to the current threshold. Must \
+                     not exceed the number of the account's public keys after \
+                     the update.",
+                ))
+        }
+    }
+
+    impl CliToSdk<TxUpdateName<SdkTypes>> for TxUpdateName<CliTypes> {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<TxUpdateName<SdkTypes>, Self::Error> {
+            let tx = self.tx.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+
+            Ok(TxUpdateName::<SdkTypes> {
+                tx,
+                tx_code_path: self.tx_code_path,
+                name: self.name,
+                owner: chain_ctx.get(&self.owner),
+                target: chain_ctx.get_opt(&self.target),
+            })
+        }
+    }
+
+    impl Args for TxUpdateName<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let tx_code_path = PathBuf::from(TX_UPDATE_NAME_WASM);
+            let name = REGISTERED_NAME.parse(matches);
+            let owner = OWNER.parse(matches);
+            let target = NAME_TARGET_OPT.parse(matches);
+            Self {
+                tx,
+                tx_code_path,
+                name,
+                owner,
+                target,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(REGISTERED_NAME.def().help(
+                    "The name, made of labels of lowercase letters, digits \
+                     and hyphens separated by dots, e.g. alice.namada.",
+                ))
+                .arg(OWNER.def().help(
+                    "The new owner of the name. A change of owner must also \
+                     be signed by the current owner.",
+                ))
+                .arg(NAME_TARGET_OPT.def().help(
+                    "The address the name resolves to. Omit it to delete the \
+                     name.",
+                ))
+        }
+    }
+
+    impl CliToSdk<TxFaucetWithdraw<SdkTypes>> for TxFaucetWithdraw<CliTypes> {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<TxFaucetWithdraw<SdkTypes>, Self::Error> {
+            let tx = self.tx.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+
+            Ok(TxFaucetWithdraw::<SdkTypes> {
+                tx,
+                tx_code_path: self.tx_code_path,
+                faucet: chain_ctx.get(&self.faucet),
+                target: chain_ctx.get(&self.target),
+                token: chain_ctx.get(&self.token),
+                amount: self.amount,
+            })
+        }
+    }
+
+    impl Args for TxFaucetWithdraw<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let tx_code_path = PathBuf::from(TX_FAUCET_WITHDRAW_WASM);
+            let faucet = FAUCET.parse(matches);
+            let target = FAUCET_TARGET.parse(matches);
+            let token = TOKEN.parse(matches);
+            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            Self {
+                tx,
+                tx_code_path,
+                faucet,
+                target,
+                token,
+                amount,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(FAUCET.def().help("The faucet account address."))
+                .arg(FAUCET_TARGET.def().help(
+                    "The address receiving the tokens. Its key is used to \
+                     sign the transaction and pay the fees by default.",
+                ))
+                .arg(TOKEN.def().help("The withdrawn token."))
+                .arg(AMOUNT.def().help("The amount to withdraw in decimal."))
+        }
+    }
+
+    impl CliToSdk<TxUpdateFaucetLimits<SdkTypes>>
+        for TxUpdateFaucetLimits<CliTypes>
+    {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<TxUpdateFaucetLimits<SdkTypes>, Self::Error> {
+            let tx = self.tx.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+
+            Ok(TxUpdateFaucetLimits::<SdkTypes> {
+                tx,
+                tx_code_path: self.tx_code_path,
+                faucet: chain_ctx.get(&self.faucet),
+                token: chain_ctx.get(&self.token),
+                per_address: self.per_address,
+                per_epoch: self.per_epoch,
+            })
+        }
+    }
+
+    impl Args for TxUpdateFaucetLimits<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let tx_code_path = PathBuf::from(TX_UPDATE_FAUCET_LIMITS_WASM);
+            let faucet = FAUCET.parse(matches);
+            let token = TOKEN.parse(matches);
+            let per_address = InputAmount::Unvalidated(
+                FAUCET_LIMIT_PER_ADDRESS.parse(matches),
+            );
+            let per_epoch =
+                InputAmount::Unvalidated(FAUCET_LIMIT_PER_EPOCH.parse(matches));
+            Self {
+                tx,
+                tx_code_path,
+                faucet,
+                token,
+                per_address,
+                per_epoch,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(FAUCET.def().help(
+                    "The faucet account address, which must sign the \
+                     transaction.",
+                ))
+                .arg(TOKEN.def().help("The token of the limits."))
+                .arg(FAUCET_LIMIT_PER_ADDRESS.def().help(
+                    "The maximum amount withdrawn to a single address per \
+                     epoch, in decimal.",
+                ))
+                .arg(FAUCET_LIMIT_PER_EPOCH.def().help(
+                    "The maximum amount withdrawn to all addresses per epoch, \
+                     in decimal. Zero limits disable the withdrawals.",
+                ))
+        }
+    }
+
+    impl CliToSdk<TxUpdateTransferPolicy<SdkTypes>>
+        for TxUpdateTransferPolicy<CliTypes>
+    {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<TxUpdateTransferPolicy<SdkTypes>, Self::Error> {
+            let tx = self.tx.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+
+            Ok(TxUpdateTransferPolicy::<SdkTypes> {
+                tx,
+                tx_code_path: self.tx_code_path,
+                owner: chain_ctx.get(&self.owner),
+                token: self.token.map(|token| chain_ctx.get(&token)),
+                max_amount: self.max_amount,
+                counterparties: self
+                    .counterparties
+                    .iter()
+                    .map(|counterparty| chain_ctx.get(counterparty))
+                    .collect(),
+                remove: self.remove,
+            })
+        }
+    }
+
+    impl Args for TxUpdateTransferPolicy<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let tx_code_path = PathBuf::from(TX_UPDATE_TRANSFER_POLICY_WASM);
+            let owner = OWNER.parse(matches);
+            let token = TOKEN_OPT.parse(matches);
+            let max_amount = TRANSFER_POLICY_MAX_AMOUNT
+                .parse(matches)
+                .map(InputAmount::Unvalidated);
+            let counterparties = COUNTERPARTIES.parse(matches);
+            let remove = TRANSFER_POLICY_REMOVE.parse(matches);
+            Self {
+                tx,
+                tx_code_path,
+                owner,
+                token,
+                max_amount,
+                counterparties,
+                remove,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(OWNER.def().help(
+                    "The account address, which must sign the transaction.",
+                ))
+                .arg(
+                    TOKEN_OPT
+                        .def()
+                        .help("The token whose maximum amount per tx is set.")
+                        .requires(TRANSFER_POLICY_MAX_AMOUNT.name),
+                )
+                .arg(
+                    TRANSFER_POLICY_MAX_AMOUNT
+                        .def()
+                        .help(
+                            "The maximum amount of the token that a single \
+                             transaction may debit from the account, in \
+                             decimal.",
+                        )
+                        .requires(TOKEN_OPT.name),
+                )
+                .arg(COUNTERPARTIES.def().help(
+                    "The only addresses that may receive the account's \
+                     tokens, replacing the current ones.",
+                ))
+                .arg(
+                    TRANSFER_POLICY_REMOVE
+                        .def()
+                        .help("Remove the transfer policy of the account.")
+                        .conflicts_with_all([
+                            TOKEN_OPT.name,
+                            TRANSFER_POLICY_MAX_AMOUNT.name,
+                            COUNTERPARTIES.name,
+                        ]),
+                )
+        }
+    }
+
     impl CliToSdk<Bond<SdkTypes>> for Bond<CliTypes> {
         type Error = std::io::Error;
 
@@ -4999,6 +6219,9 @@ pub mod args {
                 proposal_data,
                 is_pgf_stewards: self.is_pgf_stewards,
                 is_pgf_funding: self.is_pgf_funding,
+                is_pgf_steward_election: self.is_pgf_steward_election,
+                is_emergency_pause: self.is_emergency_pause,
+                is_expedited: self.is_expedited,
                 tx_code_path: self.tx_code_path,
             })
         }
@@ -5010,6 +6233,10 @@ pub mod args {
             let proposal_data = DATA_PATH.parse(matches);
             let is_pgf_stewards = PROPOSAL_PGF_STEWARD.parse(matches);
             let is_pgf_funding = PROPOSAL_PGF_FUNDING.parse(matches);
+            let is_pgf_steward_election =
+                PROPOSAL_PGF_STEWARD_ELECTION.parse(matches);
+            let is_emergency_pause = PROPOSAL_EMERGENCY_PAUSE.parse(matches);
+            let is_expedited = PROPOSAL_EXPEDITED.parse(matches);
             let tx_code_path = PathBuf::from(TX_INIT_PROPOSAL);
 
             Self {
@@ -5018,6 +6245,9 @@ pub mod args {
                 tx_code_path,
                 is_pgf_stewards,
                 is_pgf_funding,
+                is_pgf_steward_election,
+                is_emergency_pause,
+                is_expedited,
             }
         }
 
@@ -5033,6 +6263,9 @@ pub mod args {
                         .conflicts_with_all([
                             PROPOSAL_PGF_FUNDING.name,
                             PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_STEWARD_ELECTION.name,
+                            PROPOSAL_EMERGENCY_PAUSE.name,
+                            PROPOSAL_EXPEDITED.name,
                         ]),
                 )
                 .arg(
@@ -5045,6 +6278,9 @@ pub mod args {
                         .conflicts_with_all([
                             PROPOSAL_ETH.name,
                             PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_PGF_STEWARD_ELECTION.name,
+                            PROPOSAL_EMERGENCY_PAUSE.name,
+                            PROPOSAL_EXPEDITED.name,
                         ]),
                 )
                 .arg(
@@ -5057,6 +6293,61 @@ pub mod args {
                         .conflicts_with_all([
                             PROPOSAL_ETH.name,
                             PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_STEWARD_ELECTION.name,
+                            PROPOSAL_EMERGENCY_PAUSE.name,
+                            PROPOSAL_EXPEDITED.name,
+                        ]),
+                )
+                .arg(
+                    PROPOSAL_PGF_STEWARD_ELECTION
+                        .def()
+                        .help(
+                            "Flag if the proposal is of type \
+                             pgf-steward-election. Used to elect a new \
+                             steward set for a fixed term in epochs.",
+                        )
+                        .conflicts_with_all([
+                            PROPOSAL_ETH.name,
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_EMERGENCY_PAUSE.name,
+                            PROPOSAL_EXPEDITED.name,
+                        ]),
+                )
+                .arg(
+                    PROPOSAL_EMERGENCY_PAUSE
+                        .def()
+                        .help(
+                            "Flag if the proposal is of type emergency-pause. \
+                             Used to pause the IBC, bridge pool or MASP \
+                             transfers for a number of epochs. It is \
+                             expedited but requires 2/3 of the total voting \
+                             power to vote yay.",
+                        )
+                        .conflicts_with_all([
+                            PROPOSAL_ETH.name,
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_PGF_STEWARD_ELECTION.name,
+                            PROPOSAL_EXPEDITED.name,
+                        ]),
+                )
+                .arg(
+                    PROPOSAL_EXPEDITED
+                        .def()
+                        .help(
+                            "Flag if the proposal is of type expedited. Its \
+                             wasm code may only change the emergency pauses \
+                             and the expedited protocol parameters. It is \
+                             expedited but requires 2/3 of the total voting \
+                             power to vote yay.",
+                        )
+                        .conflicts_with_all([
+                            PROPOSAL_ETH.name,
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_PGF_STEWARD_ELECTION.name,
+                            PROPOSAL_EMERGENCY_PAUSE.name,
                         ]),
                 )
         }
@@ -5160,12 +6451,56 @@ pub mod args {
             let query = Query::parse(matches);
             let proposal_id = PROPOSAL_ID_OPT.parse(matches);
 
-            Self { query, proposal_id }
+            Self { query, proposal_id }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(PROPOSAL_ID_OPT.def().help("The proposal identifier."))
+        }
+    }
+
+    impl CliToSdk<VerifyProposalContent<SdkTypes>>
+        for VerifyProposalContent<CliTypes>
+    {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<VerifyProposalContent<SdkTypes>, Self::Error> {
+            let query = self.query.to_sdk(ctx)?;
+            let content = std::fs::read(self.content)
+                .expect("Failed to read the proposal content file");
+
+            Ok(VerifyProposalContent::<SdkTypes> {
+                query,
+                proposal_id: self.proposal_id,
+                content,
+            })
+        }
+    }
+
+    impl Args for VerifyProposalContent<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let proposal_id = PROPOSAL_ID.parse(matches);
+            let content = DATA_PATH.parse(matches);
+
+            Self {
+                query,
+                proposal_id,
+                content,
+            }
         }
 
         fn def(app: App) -> App {
             app.add_args::<Query<CliTypes>>()
-                .arg(PROPOSAL_ID_OPT.def().help("The proposal identifier."))
+                .arg(PROPOSAL_ID.def().help("The proposal identifier."))
+                .arg(DATA_PATH.def().help(
+                    "The path to the off-chain proposal content file, e.g. \
+                     markdown, JSON or any other artifact.",
+                ))
         }
     }
 
@@ -5267,6 +6602,98 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<VerifyWasm<SdkTypes>> for VerifyWasm<CliTypes> {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<VerifyWasm<SdkTypes>, Self::Error> {
+            Ok(VerifyWasm::<SdkTypes> {
+                query: self.query.to_sdk(ctx)?,
+            })
+        }
+    }
+
+    impl Args for VerifyWasm<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            Self { query }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+        }
+    }
+
+    impl CliToSdk<QueryParametersDiff<SdkTypes>>
+        for QueryParametersDiff<CliTypes>
+    {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<QueryParametersDiff<SdkTypes>, Self::Error> {
+            Ok(QueryParametersDiff::<SdkTypes> {
+                query: self.query.to_sdk(ctx)?,
+                from_height: self.from_height,
+                from_epoch: self.from_epoch,
+                to_height: self.to_height,
+                to_epoch: self.to_epoch,
+            })
+        }
+    }
+
+    impl Args for QueryParametersDiff<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let from_height = BLOCK_HEIGHT_FROM_OPT.parse(matches);
+            let from_epoch = EPOCH_FROM_OPT.parse(matches);
+            let to_height = BLOCK_HEIGHT_TO_OPT.parse(matches);
+            let to_epoch = EPOCH_TO_OPT.parse(matches);
+            Self {
+                query,
+                from_height,
+                from_epoch,
+                to_height,
+                to_epoch,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(
+                    BLOCK_HEIGHT_FROM_OPT
+                        .def()
+                        .help("The block height of the old parameters.")
+                        .conflicts_with(EPOCH_FROM_OPT.name),
+                )
+                .arg(EPOCH_FROM_OPT.def().help(
+                    "The epoch of the old parameters, taken at its first \
+                     block height.",
+                ))
+                .group(
+                    ArgGroup::new("from")
+                        .args([BLOCK_HEIGHT_FROM_OPT.name, EPOCH_FROM_OPT.name])
+                        .required(true),
+                )
+                .arg(
+                    BLOCK_HEIGHT_TO_OPT
+                        .def()
+                        .help(
+                            "The block height of the new parameters. Defaults \
+                             to the last committed height.",
+                        )
+                        .conflicts_with(EPOCH_TO_OPT.name),
+                )
+                .arg(EPOCH_TO_OPT.def().help(
+                    "The epoch of the new parameters, taken at its first \
+                     block height.",
+                ))
+        }
+    }
+
     impl Args for QueryPgf<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
@@ -5591,6 +7018,36 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<QueryPendingConsensusKey<SdkTypes>>
+        for QueryPendingConsensusKey<CliTypes>
+    {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<QueryPendingConsensusKey<SdkTypes>, Self::Error> {
+            Ok(QueryPendingConsensusKey::<SdkTypes> {
+                query: self.query.to_sdk(ctx)?,
+                validator: ctx.borrow_chain_or_exit().get(&self.validator),
+            })
+        }
+    }
+
+    impl Args for QueryPendingConsensusKey<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let validator = VALIDATOR.parse(matches);
+            Self { query, validator }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(VALIDATOR.def().help(
+                "The validator's address whose consensus key is queried.",
+            ))
+        }
+    }
+
     impl CliToSdk<QueryValidatorState<SdkTypes>> for QueryValidatorState<CliTypes> {
         type Error = std::convert::Infallible;
 
@@ -5821,6 +7278,73 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<OperationalMetadataChange<SdkTypes>>
+        for OperationalMetadataChange<CliTypes>
+    {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<OperationalMetadataChange<SdkTypes>, Self::Error> {
+            let tx = self.tx.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+
+            Ok(OperationalMetadataChange::<SdkTypes> {
+                tx,
+                validator: chain_ctx.get(&self.validator),
+                protocol_key: self.protocol_key.map(|x| chain_ctx.get(&x)),
+                rpc_endpoint: self.rpc_endpoint,
+                seed_node: self.seed_node,
+                emergency_contact: self.emergency_contact,
+            })
+        }
+    }
+
+    impl Args for OperationalMetadataChange<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let validator = VALIDATOR.parse(matches);
+            let protocol_key = PROTOCOL_KEY.parse(matches);
+            let rpc_endpoint = RPC_ENDPOINT_OPT.parse(matches);
+            let seed_node = SEED_NODE_OPT.parse(matches);
+            let emergency_contact = EMERGENCY_CONTACT_OPT.parse(matches);
+            Self {
+                tx,
+                validator,
+                protocol_key,
+                rpc_endpoint,
+                seed_node,
+                emergency_contact,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(VALIDATOR.def().help(
+                    "The validator's address whose operational metadata to \
+                     publish.",
+                ))
+                .arg(PROTOCOL_KEY.def().help(
+                    "The validator's protocol key, which must be in the \
+                     wallet. Defaults to the validator's protocol key in the \
+                     current epoch.",
+                ))
+                .arg(RPC_ENDPOINT_OPT.def().help(
+                    "The URL of a public RPC endpoint of the validator's \
+                     infrastructure.",
+                ))
+                .arg(SEED_NODE_OPT.def().help(
+                    "The address of a seed node, in the \
+                     `<node-id>@<host>:<port>` format.",
+                ))
+                .arg(EMERGENCY_CONTACT_OPT.def().help(
+                    "A contact for emergencies, such as an email or a chat \
+                     handle.",
+                ))
+        }
+    }
+
     impl CliToSdk<TxUnjailValidator<SdkTypes>> for TxUnjailValidator<CliTypes> {
         type Error = std::io::Error;
 
@@ -6193,6 +7717,54 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<QueryTelemetry<SdkTypes>> for QueryTelemetry<CliTypes> {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<QueryTelemetry<SdkTypes>, Self::Error> {
+            let query = self.query.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_chain_or_exit();
+
+            Ok(QueryTelemetry::<SdkTypes> {
+                query,
+                validator: self.validator.map(|x| chain_ctx.get(&x)),
+                epoch: self.epoch,
+            })
+        }
+    }
+
+    impl Args for QueryTelemetry<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let validator = VALIDATOR_OPT.parse(matches);
+            let epoch = EPOCH.parse(matches);
+            Self {
+                query,
+                validator,
+                epoch,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(VALIDATOR_OPT.def().help(
+                    "The validator's address whose last telemetry heartbeat \
+                     to query.",
+                ))
+                .arg(
+                    EPOCH
+                        .def()
+                        .help(
+                            "The epoch of the consensus validators to \
+                             summarize. Defaults to the current epoch.",
+                        )
+                        .conflicts_with(VALIDATOR_OPT.name),
+                )
+        }
+    }
+
     impl CliToSdk<QuerySlashes<SdkTypes>> for QuerySlashes<CliTypes> {
         type Error = std::convert::Infallible;
 
@@ -6240,6 +7812,36 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<QueryStakingOverview<SdkTypes>>
+        for QueryStakingOverview<CliTypes>
+    {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<QueryStakingOverview<SdkTypes>, Self::Error> {
+            Ok(QueryStakingOverview::<SdkTypes> {
+                query: self.query.to_sdk(ctx)?,
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+            })
+        }
+    }
+
+    impl Args for QueryStakingOverview<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let owner = OWNER.parse(matches);
+            Self { query, owner }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                OWNER.def().help("The owner address of the bonds to query."),
+            )
+        }
+    }
+
     impl Args for QueryRewards<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
@@ -6412,6 +8014,9 @@ pub mod args {
                 fee_unshield: self
                     .fee_unshield
                     .map(|ref fee_unshield| ctx.get_cached(fee_unshield)),
+                fee_unshield_headroom: self.fee_unshield_headroom,
+                shielded_fee: self.shielded_fee,
+                confirm_fees: self.confirm_fees,
                 gas_limit: self.gas_limit,
                 signing_keys: self
                     .signing_keys
@@ -6440,6 +8045,7 @@ pub mod args {
                 wrapper_fee_payer: self.wrapper_fee_payer.map(|x| ctx.get(&x)),
                 memo: self.memo,
                 use_device: self.use_device,
+                data_encoding: self.data_encoding,
             })
         }
     }
@@ -6491,10 +8097,36 @@ pub mod args {
                 "The spending key to be used for fee unshielding. If none is \
                  provided, fee will be paid from the unshielded balance only.",
             ))
+            .arg(
+                FEE_UNSHIELD_HEADROOM_OPT
+                    .def()
+                    .help(
+                        "The amount to unshield in addition to the part of \
+                         the fee missing from the transparent balance of the \
+                         gas payer. Defaults to zero.",
+                    )
+                    .requires(FEE_UNSHIELD_SPENDING_KEY.name),
+            )
+            .arg(
+                FEE_UNSHIELD_SHIELDED
+                    .def()
+                    .help(
+                        "Pay the whole fee out of the shielded balance of the \
+                         gas spending key, straight to the block proposer, \
+                         without crediting the transparent balance of the gas \
+                         payer.",
+                    )
+                    .requires(FEE_UNSHIELD_SPENDING_KEY.name)
+                    .conflicts_with(FEE_UNSHIELD_HEADROOM_OPT.name),
+            )
             .arg(GAS_LIMIT.def().help(
                 "The multiplier of the gas limit resolution defining the \
                  maximum amount of gas needed to run transaction.",
             ))
+            .arg(CONFIRM_FEES.def().help(
+                "Display the breakdown of the fee of the transaction and ask \
+                 for a confirmation before signing it.",
+            ))
             .arg(WALLET_ALIAS_FORCE.def().help(
                 "Override the alias without confirmation if it already exists.",
             ))
@@ -6574,6 +8206,11 @@ pub mod args {
                     .def()
                     .help("Attach a plaintext memo to the transaction."),
             )
+            .arg(DATA_ENCODING.def().help(
+                "The encoding of the transaction data, either `borsh` or \
+                 `json-v1`. The JSON encoding is only supported by the \
+                 transfer, bond, unbond, withdraw and proposal transactions.",
+            ))
         }
 
         fn parse(matches: &ArgMatches) -> Self {
@@ -6588,6 +8225,11 @@ pub mod args {
                 FEE_AMOUNT_OPT.parse(matches).map(InputAmount::Unvalidated);
             let fee_token = FEE_TOKEN.parse(matches);
             let fee_unshield = FEE_UNSHIELD_SPENDING_KEY.parse(matches);
+            let fee_unshield_headroom = FEE_UNSHIELD_HEADROOM_OPT
+                .parse(matches)
+                .map(InputAmount::Unvalidated);
+            let shielded_fee = FEE_UNSHIELD_SHIELDED.parse(matches);
+            let confirm_fees = CONFIRM_FEES.parse(matches);
             let _wallet_alias_force = WALLET_ALIAS_FORCE.parse(matches);
             let gas_limit = GAS_LIMIT.parse(matches);
             let wallet_alias_force = WALLET_ALIAS_FORCE.parse(matches);
@@ -6602,6 +8244,7 @@ pub mod args {
             let wrapper_fee_payer = FEE_PAYER_OPT.parse(matches);
             let output_folder = OUTPUT_FOLDER_PATH.parse(matches);
             let use_device = USE_DEVICE.parse(matches);
+            let data_encoding = DATA_ENCODING.parse(matches);
             let no_expiration = NO_EXPIRATION.parse(matches);
             let expiration = if no_expiration {
                 TxExpiration::NoExpiration
@@ -6623,6 +8266,9 @@ pub mod args {
                 fee_amount,
                 fee_token,
                 fee_unshield,
+                fee_unshield_headroom,
+                shielded_fee,
+                confirm_fees,
                 gas_limit,
                 expiration,
                 disposable_signing_key,
@@ -6635,6 +8281,7 @@ pub mod args {
                 output_folder,
                 memo,
                 use_device,
+                data_encoding,
             }
         }
     }
@@ -6730,6 +8377,7 @@ pub mod args {
                 alias: self.alias,
                 alias_force: self.alias_force,
                 viewing_key,
+                diversifier_index: self.diversifier_index,
             })
         }
     }
@@ -6739,10 +8387,12 @@ pub mod args {
             let alias = ALIAS.parse(matches);
             let alias_force = ALIAS_FORCE.parse(matches);
             let viewing_key = VIEWING_KEY.parse(matches);
+            let diversifier_index = DIVERSIFIER_INDEX_OPT.parse(matches);
             Self {
                 alias,
                 alias_force,
                 viewing_key,
+                diversifier_index,
             }
         }
 
@@ -6756,6 +8406,43 @@ pub mod args {
                 "Override the alias without confirmation if it already exists.",
             ))
             .arg(VIEWING_KEY.def().help("The viewing key."))
+            .arg(DIVERSIFIER_INDEX_OPT.def().help(
+                "The diversifier index from which to derive the payment \
+                 address. The first valid index from the given one is used. \
+                 Defaults to the index following the last address derived \
+                 from the viewing key in the wallet.",
+            ))
+        }
+    }
+
+    impl CliToSdk<PayAddressList<SdkTypes>> for PayAddressList<CliTypes> {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<PayAddressList<SdkTypes>, Self::Error> {
+            let viewing_key = ctx
+                .borrow_mut_chain_or_exit()
+                .wallet
+                .find_viewing_key(&self.viewing_key.raw)
+                .copied()
+                .unwrap_or_else(|_| {
+                    eprintln!("Unknown viewing key {}", self.viewing_key.raw);
+                    safe_exit(1)
+                });
+            Ok(PayAddressList::<SdkTypes> { viewing_key })
+        }
+    }
+
+    impl Args for PayAddressList<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let viewing_key = VIEWING_KEY.parse(matches);
+            Self { viewing_key }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(VIEWING_KEY.def().help("The viewing key."))
         }
     }
 
@@ -6842,6 +8529,58 @@ pub mod args {
         }
     }
 
+    impl Args for KeyGenVanity {
+        fn parse(matches: &ArgMatches) -> Self {
+            let scheme = SCHEME.parse(matches);
+            let prefix = VANITY_PREFIX.parse(matches);
+            let suffix = VANITY_SUFFIX.parse(matches);
+            let threads = THREADS.parse(matches);
+            let alias = ALIAS.parse(matches);
+            let alias_force = ALIAS_FORCE.parse(matches);
+            let unsafe_dont_encrypt = UNSAFE_DONT_ENCRYPT.parse(matches);
+            Self {
+                scheme,
+                prefix,
+                suffix,
+                threads,
+                alias,
+                alias_force,
+                unsafe_dont_encrypt,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(SCHEME.def().help(
+                "The type of key that should be generated. Argument must be \
+                 either ed25519 or secp256k1. If none provided, the default \
+                 key scheme is ed25519.",
+            ))
+            .arg(VANITY_PREFIX.def().help(
+                "The prefix of the address, with or without its `tnam1` \
+                 human-readable part.",
+            ))
+            .arg(VANITY_SUFFIX.def().help("The suffix of the address."))
+            .group(
+                ArgGroup::new("pattern")
+                    .args([VANITY_PREFIX.name, VANITY_SUFFIX.name])
+                    .multiple(true)
+                    .required(true),
+            )
+            .arg(THREADS.def().help(
+                "The number of threads of the search. Defaults to the number \
+                 of CPUs.",
+            ))
+            .arg(ALIAS.def().help("The key and address alias."))
+            .arg(ALIAS_FORCE.def().help(
+                "Override the alias without confirmation if it already exists.",
+            ))
+            .arg(UNSAFE_DONT_ENCRYPT.def().help(
+                "UNSAFE: Do not encrypt the keypair. Do not use this for keys \
+                 used in a live network.",
+            ))
+        }
+    }
+
     impl Args for KeyGen {
         fn parse(matches: &ArgMatches) -> Self {
             let scheme = SCHEME.parse(matches);
@@ -7104,13 +8843,21 @@ pub mod args {
     impl Args for KeyExport {
         fn parse(matches: &ArgMatches) -> Self {
             let alias = ALIAS.parse(matches);
-            Self { alias }
+            let format = KEY_FORMAT.parse(matches);
+            Self { alias, format }
         }
 
         fn def(app: App) -> App {
             app.arg(
                 ALIAS.def().help("The alias of the key you wish to export."),
             )
+            .arg(KEY_FORMAT.def().help(
+                "The format of the exported key: `namada` (default), `hex` or \
+                 `base64` for a raw ed25519 key, `tendermint` for a CometBFT \
+                 priv_validator_key.json, or `keystore` for a password \
+                 encrypted JSON keystore. Only the `namada` format supports \
+                 the shielded spending keys.",
+            ))
         }
     }
 
@@ -7130,6 +8877,7 @@ pub mod args {
     impl Args for KeyImport {
         fn parse(matches: &ArgMatches) -> Self {
             let file_path = FILE_PATH.parse(matches);
+            let format = KEY_FORMAT.parse(matches);
             let alias = ALIAS.parse(matches);
             let alias_force = ALIAS_FORCE.parse(matches);
             let unsafe_dont_encrypt = UNSAFE_DONT_ENCRYPT.parse(matches);
@@ -7137,6 +8885,7 @@ pub mod args {
                 alias,
                 alias_force,
                 file_path,
+                format,
                 unsafe_dont_encrypt,
             }
         }
@@ -7145,6 +8894,12 @@ pub mod args {
             app.arg(FILE_PATH.def().help(
                 "Path to the file containing the key you wish to import.",
             ))
+            .arg(KEY_FORMAT.def().help(
+                "The format of the imported key: `namada` (default), `hex` or \
+                 `base64` for a raw ed25519 key, `tendermint` for a CometBFT \
+                 priv_validator_key.json, or `keystore` for a password \
+                 encrypted JSON keystore.",
+            ))
             .arg(ALIAS.def().help("The alias assigned to the."))
             .arg(
                 ALIAS_FORCE
@@ -1,6 +1,9 @@
+use std::str::FromStr;
+
 use namada::io::Io;
 use namada::tendermint_rpc::HttpClient;
 use namada_sdk::error::Error;
+use namada_sdk::queries::multi_endpoint::{MultiEndpointClient, RetryConfig};
 use namada_sdk::queries::Client;
 use namada_sdk::rpc::wait_until_node_is_synched;
 use tendermint_rpc::Url as TendermintUrl;
@@ -29,6 +32,45 @@ impl CliClient for HttpClient {
     }
 }
 
+/// Env. var to set the comma-separated addresses of the fallback ledger
+/// nodes, to which the client fails over when the node given with `--node`
+/// is unavailable
+pub const ENV_VAR_FALLBACK_NODES: &str = "NAMADA_FALLBACK_NODES";
+
+#[async_trait::async_trait(?Send)]
+impl CliClient for MultiEndpointClient<HttpClient> {
+    fn from_tendermint_address(address: &TendermintUrl) -> Self {
+        let mut clients = vec![HttpClient::from_tendermint_address(address)];
+        if let Ok(fallbacks) = std::env::var(ENV_VAR_FALLBACK_NODES) {
+            clients.extend(
+                fallbacks
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|fallback| !fallback.is_empty())
+                    .map(|fallback| {
+                        let address = TendermintUrl::from_str(fallback)
+                            .unwrap_or_else(|err| {
+                                panic!(
+                                    "Invalid fallback node address {fallback} \
+                                     in {ENV_VAR_FALLBACK_NODES}: {err}"
+                                )
+                            });
+                        HttpClient::from_tendermint_address(&address)
+                    }),
+            );
+        }
+        MultiEndpointClient::new(clients, RetryConfig::default())
+            .expect("There is at least one endpoint")
+    }
+
+    async fn wait_until_node_is_synced(
+        &self,
+        io: &impl Io,
+    ) -> Result<(), Error> {
+        wait_until_node_is_synched(self, io).await
+    }
+}
+
 pub struct CliIo;
 
 #[async_trait::async_trait(?Send)]
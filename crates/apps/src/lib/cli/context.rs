@@ -1,11 +1,13 @@
 //! CLI input types can be used for command arguments
 
+use std::collections::BTreeMap;
 use std::env;
 use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use color_eyre::eyre::Result;
+use namada::account::name_registry;
 use namada::core::address::{Address, InternalAddress};
 use namada::core::chain::ChainId;
 use namada::core::ethereum_events::EthAddress;
@@ -14,10 +16,11 @@ use namada::core::key::*;
 use namada::core::masp::*;
 use namada::io::Io;
 use namada::ledger::ibc::storage::ibc_token;
+use namada::ledger::queries::Client;
 use namada_sdk::masp::fs::FsShieldedUtils;
 use namada_sdk::masp::ShieldedContext;
 use namada_sdk::wallet::Wallet;
-use namada_sdk::{Namada, NamadaImpl};
+use namada_sdk::{error, rpc, Namada, NamadaImpl};
 
 use super::args;
 use crate::cli::utils;
@@ -122,6 +125,8 @@ pub struct ChainContext {
     pub shielded: ShieldedContext<FsShieldedUtils>,
     /// Native token's address
     pub native_token: Address,
+    /// The addresses of the names resolved from the on-chain name registry
+    pub names: BTreeMap<String, Address>,
 }
 
 impl Context {
@@ -171,6 +176,7 @@ impl Context {
                     config,
                     shielded: FsShieldedUtils::new(chain_dir),
                     native_token,
+                    names: BTreeMap::new(),
                 })
             }
             _ => None,
@@ -277,6 +283,33 @@ impl ChainContext {
             .map(|from_context| from_context.arg_from_mut_ctx(self).unwrap())
     }
 
+    /// Resolve the given arguments that are not addresses nor known to the
+    /// wallet with the on-chain name registry, so that the following look-ups
+    /// of their addresses in this context succeed.
+    pub async fn resolve_names<'a, C, T: 'a>(
+        &mut self,
+        client: &C,
+        args: impl IntoIterator<Item = &'a FromContext<T>>,
+    ) -> Result<(), error::Error>
+    where
+        C: Client + Sync,
+    {
+        for arg in args {
+            let name = arg.raw.as_str();
+            let is_known = Address::from_str(name).is_ok()
+                || self.names.contains_key(name)
+                || self.wallet.find_address(name).is_some()
+                || self.wallet.find_payment_addr(name).is_some();
+            if is_known || !name_registry::is_valid_name(name) {
+                continue;
+            }
+            if let Some(address) = rpc::resolve_name(client, name).await? {
+                self.names.insert(name.to_owned(), address);
+            }
+        }
+        Ok(())
+    }
+
     /// Get the wasm directory configured for the chain.
     ///
     /// Note that in "dev" build, this may be the root `wasm` dir.
@@ -451,6 +484,8 @@ impl ArgFromContext for Address {
                     .map(|x| x.into_owned())
                     .ok_or(Skip)
             })
+            // Or a name resolved from the on-chain name registry
+            .or_else(|_| ctx.names.get(raw).cloned().ok_or(Skip))
             .map_err(|_| format!("Unknown address {raw}"))
     }
 }
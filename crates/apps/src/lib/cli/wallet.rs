@@ -16,9 +16,13 @@ use namada::core::address::{Address, DecodeError};
 use namada::core::key::*;
 use namada::core::masp::{ExtendedSpendingKey, MaspValue, PaymentAddress};
 use namada::io::Io;
-use namada_sdk::masp::find_valid_diversifier;
+use namada_sdk::wallet::keystore::{
+    decode_secret_key, encode_secret_key, KeyFormat,
+};
+use namada_sdk::wallet::vanity::{search_vanity_key, VanityPattern};
 use namada_sdk::wallet::{
     DecryptionError, DerivationPath, DerivationPathError, FindKeyError, Wallet,
+    WalletIo,
 };
 use namada_sdk::{display_line, edisplay_line};
 use rand_core::OsRng;
@@ -43,6 +47,9 @@ impl CliApi {
             cmds::NamadaWallet::KeyGen(cmds::WalletGen(args)) => {
                 key_gen(ctx, io, args)
             }
+            cmds::NamadaWallet::KeyGenVanity(cmds::WalletGenVanity(args)) => {
+                key_gen_vanity(ctx, io, args)
+            }
             cmds::NamadaWallet::KeyDerive(cmds::WalletDerive(args)) => {
                 key_derive(ctx, io, args).await
             }
@@ -73,6 +80,12 @@ impl CliApi {
                 let args = args.to_sdk(&mut ctx)?;
                 payment_address_gen(ctx, io, args)
             }
+            cmds::NamadaWallet::PayAddrList(
+                cmds::WalletListPaymentAddresses(args),
+            ) => {
+                let args = args.to_sdk(&mut ctx)?;
+                payment_address_list(ctx, io, args).await
+            }
         }
         Ok(())
     }
@@ -315,19 +328,18 @@ fn payment_address_gen(
         alias,
         alias_force,
         viewing_key,
-        ..
+        diversifier_index,
     }: args::PayAddressGen,
 ) {
     let mut wallet = load_wallet(ctx);
     let alias = alias.to_lowercase();
-    let viewing_key = ExtendedFullViewingKey::from(viewing_key).fvk.vk;
-    let (div, _g_d) = find_valid_diversifier(&mut OsRng);
-    let masp_payment_addr = viewing_key
-        .to_payment_address(div)
-        .expect("a PaymentAddress");
-    let payment_addr = PaymentAddress::from(masp_payment_addr);
-    let alias = wallet
-        .insert_payment_addr(alias, payment_addr, alias_force)
+    let (alias, payment_addr, diversifier_index) = wallet
+        .gen_diversified_payment_addr(
+            alias,
+            viewing_key,
+            diversifier_index,
+            alias_force,
+        )
         .unwrap_or_else(|| {
             edisplay_line!(io, "Payment address not added");
             cli::safe_exit(1);
@@ -335,12 +347,53 @@ fn payment_address_gen(
     wallet.save().unwrap_or_else(|err| eprintln!("{}", err));
     display_line!(
         io,
-        "Successfully generated payment address {} with alias {}",
+        "Successfully generated payment address {} with alias {} at \
+         diversifier index {}",
         payment_addr,
         alias,
+        diversifier_index,
     );
 }
 
+/// List the payment addresses of the given viewing key that received notes.
+async fn payment_address_list(
+    ctx: Context,
+    io: &impl Io,
+    args::PayAddressList { viewing_key }: args::PayAddressList,
+) {
+    let mut chain_ctx = ctx.take_chain_or_exit();
+    let _ = chain_ctx.shielded.load().await;
+    let vk = ExtendedFullViewingKey::from(viewing_key).fvk.vk;
+    let Some(addresses) = chain_ctx.shielded.received_payment_addresses(&vk)
+    else {
+        display_line!(
+            io,
+            "The viewing key has not been synced yet. Run the shielded sync \
+             with this key first."
+        );
+        return;
+    };
+    if addresses.is_empty() {
+        display_line!(io, "No notes were received by this viewing key.");
+        return;
+    }
+    display_line!(io, "Payment addresses that received notes:");
+    for (payment_addr, notes) in addresses {
+        let alias = chain_ctx
+            .wallet
+            .find_alias_by_payment_addr(&payment_addr)
+            .map(ToString::to_string)
+            .unwrap_or_else(|| "unknown".to_string());
+        display_line!(
+            io,
+            "  \"{}\": {} ({} note(s))",
+            alias,
+            payment_addr,
+            notes
+        );
+    }
+}
+
 /// Add a viewing key, spending key, or payment address to wallet.
 fn shielded_key_address_add(
     ctx: Context,
@@ -623,6 +676,71 @@ fn key_gen(ctx: Context, io: &impl Io, args_key_gen: args::KeyGen) {
     }
 }
 
+/// Generate a keypair whose implicit address matches a pattern
+fn key_gen_vanity(
+    ctx: Context,
+    io: &impl Io,
+    args::KeyGenVanity {
+        scheme,
+        prefix,
+        suffix,
+        threads,
+        alias,
+        alias_force,
+        unsafe_dont_encrypt,
+    }: args::KeyGenVanity,
+) {
+    let alias = alias.to_lowercase();
+    let pattern = VanityPattern::new(
+        prefix.as_deref().unwrap_or_default(),
+        suffix.as_deref().unwrap_or_default(),
+    )
+    .unwrap_or_else(|err| {
+        edisplay_line!(io, "{}", err);
+        cli::safe_exit(1)
+    });
+    // Read the password before the search, so that it can run unattended
+    let encryption_password =
+        read_and_confirm_encryption_password(unsafe_dont_encrypt);
+    let threads = threads.unwrap_or_else(num_cpus::get);
+    display_line!(
+        io,
+        "Searching with {} threads, about {} attempts are expected...",
+        threads,
+        pattern.expected_attempts()
+    );
+    let (sk, attempts) = search_vanity_key(
+        &pattern,
+        scheme,
+        threads,
+        || OsRng,
+        |attempts| display_line!(io, "{} attempts so far...", attempts),
+    );
+    display_line!(
+        io,
+        "Found the address {} after {} attempts.",
+        Address::from(&sk.ref_to()),
+        attempts
+    );
+
+    let mut wallet = load_wallet(ctx);
+    let alias = wallet
+        .insert_keypair(alias, alias_force, sk, encryption_password, None, None)
+        .unwrap_or_else(|| {
+            edisplay_line!(io, "Failed to add the keypair.");
+            display_line!(io, "No changes are persisted. Exiting.");
+            cli::safe_exit(1);
+        });
+    wallet
+        .save()
+        .unwrap_or_else(|err| edisplay_line!(io, "{}", err));
+    display_line!(
+        io,
+        "Successfully added a key and an address with alias: \"{}\"",
+        alias
+    );
+}
+
 /// HD key derivation from mnemonic code
 async fn key_derive(
     ctx: Context,
@@ -1220,32 +1338,49 @@ fn transparent_keys_list(
     }
 }
 
-/// Export a transparent keypair / MASP spending key to a file.
+/// Export a transparent keypair / MASP spending key to a file, in the given
+/// format. Only the Namada format supports the MASP spending keys.
 fn key_export(
     ctx: Context,
     io: &impl Io,
-    args::KeyExport { alias }: args::KeyExport,
+    args::KeyExport { alias, format }: args::KeyExport,
 ) {
     let alias = alias.to_lowercase();
     let mut wallet = load_wallet(ctx);
-    let key_to_export = wallet
-        .find_secret_key(&alias, None)
-        .map(|sk| Box::new(sk) as Box<dyn BorshSerializeExt>)
-        .or(wallet
-            .find_spending_key(&alias, None)
-            .map(|spk| Box::new(spk) as Box<dyn BorshSerializeExt>));
-    key_to_export
-        .map(|key| {
-            let file_data = key.serialize_to_vec();
-            let file_name = format!("key_{}", alias);
-            let mut file = File::create(&file_name).unwrap();
-            file.write_all(file_data.as_ref()).unwrap();
-            display_line!(io, "Exported to file {}", file_name);
-        })
-        .unwrap_or_else(|err| {
-            edisplay_line!(io, "{}", err);
-            cli::safe_exit(1)
-        })
+    let file_data = if format == KeyFormat::Namada {
+        wallet
+            .find_secret_key(&alias, None)
+            .map(|sk| Box::new(sk) as Box<dyn BorshSerializeExt>)
+            .or(wallet
+                .find_spending_key(&alias, None)
+                .map(|spk| Box::new(spk) as Box<dyn BorshSerializeExt>))
+            .map(|key| key.serialize_to_vec())
+            .map_err(|err| err.to_string())
+    } else {
+        wallet
+            .find_secret_key(&alias, None)
+            .map_err(|err| err.to_string())
+            .and_then(|sk| {
+                let password = (format == KeyFormat::Keystore)
+                    .then(|| CliWalletUtils::read_password(true));
+                encode_secret_key(&sk, format, password)
+                    .map_err(|err| err.to_string())
+            })
+    };
+    let file_data = file_data.unwrap_or_else(|err| {
+        edisplay_line!(io, "{}", err);
+        cli::safe_exit(1)
+    });
+    let file_name = match format {
+        KeyFormat::Namada => format!("key_{}", alias),
+        KeyFormat::Hex => format!("key_{}.hex", alias),
+        KeyFormat::Base64 => format!("key_{}.b64", alias),
+        KeyFormat::Tendermint => format!("priv_validator_key_{}.json", alias),
+        KeyFormat::Keystore => format!("keystore_{}.json", alias),
+    };
+    let mut file = File::create(&file_name).unwrap();
+    file.write_all(file_data.as_ref()).unwrap();
+    display_line!(io, "Exported to file {}", file_name);
 }
 
 /// Convert a consensus key to tendermint validator key in json format
@@ -1267,12 +1402,14 @@ fn key_convert(
     display_line!(io, "Converted to file {}", file_name);
 }
 
-/// Import a transparent keypair / MASP spending key from a file.
+/// Import a transparent keypair / MASP spending key from a file, in the
+/// given format. Only the Namada format supports the MASP spending keys.
 fn key_import(
     ctx: Context,
     io: &impl Io,
     args::KeyImport {
         file_path,
+        format,
         alias,
         alias_force,
         unsafe_dont_encrypt,
@@ -1283,6 +1420,25 @@ fn key_import(
         display_line!(io, "No changes are persisted. Exiting.");
         cli::safe_exit(1)
     });
+    if format != KeyFormat::Namada {
+        let password = (format == KeyFormat::Keystore)
+            .then(|| CliWalletUtils::read_password(false));
+        let sk = decode_secret_key(&file_data, format, password)
+            .unwrap_or_else(|err| {
+                edisplay_line!(io, "{}", err);
+                display_line!(io, "No changes are persisted. Exiting.");
+                cli::safe_exit(1)
+            });
+        transparent_secret_key_add(
+            ctx,
+            io,
+            alias,
+            alias_force,
+            sk,
+            unsafe_dont_encrypt,
+        );
+        return;
+    }
     if let Ok(sk) = common::SecretKey::try_from_slice(&file_data) {
         transparent_secret_key_add(
             ctx,
@@ -2,6 +2,7 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::io;
+use std::path::Path;
 
 use borsh::BorshDeserialize;
 use data_encoding::HEXLOWER;
@@ -12,11 +13,16 @@ use masp_primitives::transaction::components::I128Sum;
 use masp_primitives::zip32::ExtendedFullViewingKey;
 use namada::core::address::{Address, InternalAddress, MASP};
 use namada::core::collections::{HashMap, HashSet};
+use namada::core::dec::Dec;
 use namada::core::hash::Hash;
 use namada::core::key::*;
 use namada::core::masp::BalanceOwner;
 use namada::core::storage::{BlockHeight, BlockResults, Epoch};
+use namada::core::string_encoding;
 use namada::core::token::MaspDigitPos;
+use namada::governance::cli::content::{
+    content_hash, ContentVerification, CONTENT_HASH_KEY,
+};
 use namada::governance::parameters::GovernanceParameters;
 use namada::governance::pgf::parameters::PgfParameters;
 use namada::governance::pgf::storage::steward::StewardDetail;
@@ -24,7 +30,9 @@ use namada::governance::storage::keys as governance_storage;
 use namada::governance::storage::proposal::{
     StoragePgfFunding, StorageProposal,
 };
-use namada::governance::utils::{ProposalVotes, VotePower};
+use namada::governance::utils::{
+    ProposalResult, ProposalStatus, ProposalVotes, TallyResult, VotePower,
+};
 use namada::governance::ProposalVote;
 use namada::io::Io;
 use namada::ledger::events::Event;
@@ -32,8 +40,12 @@ use namada::ledger::parameters::{storage as param_storage, EpochDuration};
 use namada::ledger::pos::types::{CommissionPair, Slash};
 use namada::ledger::pos::PosParams;
 use namada::ledger::queries::RPC;
+use namada::proof_of_stake::operational_metadata::OperationalMetadata;
+use namada::proof_of_stake::telemetry::{
+    BinaryTelemetry, TelemetrySummary, ValidatorTelemetry,
+};
 use namada::proof_of_stake::types::{
-    ValidatorState, ValidatorStateInfo, WeightedValidator,
+    PendingConsensusKey, ValidatorState, ValidatorStateInfo, WeightedValidator,
 };
 use namada::{state as storage, token};
 use namada_sdk::control_flow::time::{Duration, Instant};
@@ -47,9 +59,11 @@ use namada_sdk::tendermint_rpc::endpoint::status;
 use namada_sdk::tx::display_inner_resp;
 use namada_sdk::wallet::AddressVpType;
 use namada_sdk::{display, display_line, edisplay_line, error, Namada};
+use serde::Serialize;
 
 use crate::cli::{self, args};
 use crate::facade::tendermint::merkle::proof::ProofOps;
+use crate::wasm_loader::{self, ArtifactStatus};
 
 /// Query the status of a given transaction.
 ///
@@ -75,27 +89,36 @@ pub async fn query_and_print_epoch(context: &impl Namada) -> Epoch {
 /// Query and print some information to help discern when the next epoch will
 /// begin.
 pub async fn query_and_print_next_epoch_info(context: &impl Namada) {
-    let (this_epoch_first_height, epoch_duration) =
-        rpc::query_next_epoch_info(context.client()).await.unwrap();
+    let epoch_info = rpc::query_epoch_info(context.client()).await.unwrap();
 
+    display_line!(context.io(), "Current epoch: {}.", epoch_info.epoch);
     display_line!(
         context.io(),
-        "First block height of this current epoch: {this_epoch_first_height}."
+        "First block height of this current epoch: {}.",
+        epoch_info.first_block_height
     );
     display_line!(
         context.io(),
         "Minimum number of blocks in an epoch: {}.",
-        epoch_duration.min_num_of_blocks
+        epoch_info.epoch_duration.min_num_of_blocks
     );
     display_line!(
         context.io(),
         "Minimum amount of time for an epoch: {} seconds.",
-        epoch_duration.min_duration
+        epoch_info.epoch_duration.min_duration
+    );
+    display_line!(
+        context.io(),
+        "\nEarliest height at which the next epoch can begin is block {}, not \
+         before {}.",
+        epoch_info.next_epoch_min_start_height,
+        epoch_info.next_epoch_min_start_time
     );
     display_line!(
         context.io(),
-        "\nEarliest height at which the next epoch can begin is block {}.",
-        this_epoch_first_height.0 + epoch_duration.min_num_of_blocks
+        "The next epoch is expected to begin at block {} around {}.",
+        epoch_info.next_epoch_start_height,
+        epoch_info.next_epoch_start_time
     );
 }
 
@@ -116,6 +139,198 @@ pub async fn query_and_print_status(
     }
 }
 
+/// Chain-registry-style metadata of a chain, for ecosystem tooling
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainRegistryMetadata {
+    /// The chain ID
+    pub chain_id: String,
+    /// The human-readable parts of the Bech32m encodings
+    pub bech32_prefixes: Bech32Prefixes,
+    /// The address of the native token
+    pub native_token: String,
+    /// The tokens accepted for the payment of fees
+    pub fee_tokens: Vec<FeeToken>,
+    /// The API endpoints of the chain
+    pub apis: ChainApis,
+    /// The first block of the chain
+    pub genesis: GenesisBlock,
+}
+
+/// The human-readable parts of the Bech32m encodings
+#[derive(Debug, Clone, Serialize)]
+pub struct Bech32Prefixes {
+    pub address: String,
+    pub public_key: String,
+    pub signature: String,
+    pub payment_address: String,
+    pub viewing_key: String,
+    pub spending_key: String,
+}
+
+/// A token accepted for the payment of fees, with the range of the prices
+/// of a unit of gas
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeToken {
+    /// The address of the token
+    pub denom: String,
+    /// The minimum gas price set in the protocol parameters
+    pub fixed_min_gas_price: String,
+    /// The lowest gas price currently accepted
+    pub low_gas_price: String,
+    /// The gas price currently accepted
+    pub average_gas_price: String,
+    /// The gas price accepted after the largest increase of the base fee
+    pub high_gas_price: String,
+}
+
+/// The API endpoints of a chain
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainApis {
+    pub rpc: Vec<ApiEndpoint>,
+}
+
+/// An API endpoint
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiEndpoint {
+    pub address: String,
+}
+
+/// The first block of a chain
+#[derive(Debug, Clone, Serialize)]
+pub struct GenesisBlock {
+    pub height: u64,
+    pub hash: String,
+}
+
+/// Query the chain's metadata and print it as chain-registry-style JSON. The
+/// fee tokens and their gas prices come from the protocol parameters and the
+/// current base fee, the RPC endpoint is the one of the client's config.
+pub async fn query_and_print_chain_registry(
+    context: &impl Namada,
+    rpc_address: String,
+) {
+    let status = context
+        .client()
+        .status()
+        .await
+        .expect("Status query should not fail.");
+    let native_token = context.native_token();
+
+    let key = param_storage::get_gas_cost_key();
+    let gas_cost_table: BTreeMap<Address, token::Amount> =
+        query_storage_value(context.client(), &key)
+            .await
+            .expect("Parameter should be defined.");
+    let key = param_storage::get_base_fee_max_change_rate_key();
+    let base_fee_max_change_rate: Dec =
+        query_storage_value(context.client(), &key)
+            .await
+            .expect("Parameter should be defined.");
+    let base_fee = namada_sdk::rpc::query_base_fee(context.client())
+        .await
+        .expect("Base fee should be defined.");
+
+    let mut fee_tokens = Vec::with_capacity(gas_cost_table.len());
+    for (token, min_gas_price) in gas_cost_table {
+        // Only the price of gas in the native token follows the base fee
+        let (average, high) = if token == native_token {
+            let average = std::cmp::max(min_gas_price, base_fee);
+            let high = Dec::one()
+                .checked_add(base_fee_max_change_rate)
+                .and_then(|rate| average.mul_ceil(rate).ok())
+                .expect("The highest gas price should not overflow.");
+            (average, high)
+        } else {
+            (min_gas_price, min_gas_price)
+        };
+        let denom = rpc::denominate_amount(
+            context.client(),
+            context.io(),
+            &token,
+            min_gas_price,
+        )
+        .await
+        .denom();
+        let format =
+            |amount| token::DenominatedAmount::new(amount, denom).to_string();
+        fee_tokens.push(FeeToken {
+            denom: token.to_string(),
+            fixed_min_gas_price: format(min_gas_price),
+            low_gas_price: format(min_gas_price),
+            average_gas_price: format(average),
+            high_gas_price: format(high),
+        });
+    }
+
+    let metadata = ChainRegistryMetadata {
+        chain_id: status.node_info.network.to_string(),
+        bech32_prefixes: Bech32Prefixes {
+            address: string_encoding::ADDRESS_HRP.to_string(),
+            public_key: string_encoding::COMMON_PK_HRP.to_string(),
+            signature: string_encoding::COMMON_SIG_HRP.to_string(),
+            payment_address: string_encoding::MASP_PAYMENT_ADDRESS_HRP
+                .to_string(),
+            viewing_key: string_encoding::MASP_EXT_FULL_VIEWING_KEY_HRP
+                .to_string(),
+            spending_key: string_encoding::MASP_EXT_SPENDING_KEY_HRP
+                .to_string(),
+        },
+        native_token: native_token.to_string(),
+        fee_tokens,
+        apis: ChainApis {
+            rpc: vec![ApiEndpoint {
+                address: rpc_address,
+            }],
+        },
+        // The earliest block stored by the node is the genesis block, unless
+        // the node has been pruned or restored from a snapshot
+        genesis: GenesisBlock {
+            height: status.sync_info.earliest_block_height.value(),
+            hash: status.sync_info.earliest_block_hash.to_string(),
+        },
+    };
+    display_line!(
+        context.io(),
+        "{}",
+        serde_json::to_string_pretty(&metadata)
+            .expect("Chain registry metadata should be serializable.")
+    );
+}
+
+/// Query and print the checksum of the state at the last committed height.
+/// Nodes whose states have diverged print different checksums at the same
+/// height.
+pub async fn query_and_print_state_checksum(context: &impl Namada) {
+    let checksum = rpc::query_state_checksum(context.client())
+        .await
+        .expect("State checksum query should not fail.");
+    display_line!(context.io(), "State checksum at height {}", checksum.height);
+    display_line!(
+        context.io(),
+        "{:2}Merkle root: {}",
+        "",
+        checksum.merkle_root
+    );
+    display_line!(context.io(), "{:2}Merkle sub-tree roots:", "");
+    for (store, root) in checksum.subtree_roots {
+        display_line!(context.io(), "{:4}{}: {}", "", store, root);
+    }
+    display_line!(context.io(), "{:2}Internal address roots:", "");
+    for (addr, root) in checksum.internal_address_roots {
+        match &addr {
+            Address::Internal(internal) => display_line!(
+                context.io(),
+                "{:4}{} ({}): {}",
+                "",
+                addr,
+                internal,
+                root
+            ),
+            _ => display_line!(context.io(), "{:4}{}: {}", "", addr, root),
+        }
+    }
+}
+
 /// Query the last committed block
 pub async fn query_block(context: &impl Namada) {
     let block = namada_sdk::rpc::query_block(context.client())
@@ -473,6 +688,320 @@ pub async fn query_proposal_result(
     }
 }
 
+/// Query the lifecycle of a proposal: its submission, voting window, current
+/// tally and activation schedule
+pub async fn query_governance_status(
+    context: &impl Namada,
+    args: args::QueryProposalResult,
+) {
+    let proposal_id = args.proposal_id;
+    let current_epoch = query_epoch(context.client()).await.unwrap();
+    let Some(proposal) =
+        namada_sdk::rpc::query_proposal_by_id(context.client(), proposal_id)
+            .await
+            .unwrap()
+    else {
+        edisplay_line!(context.io(), "Proposal {} not found.", proposal_id);
+        cli::safe_exit(1)
+    };
+    let status = proposal.get_status(current_epoch);
+
+    display_line!(context.io(), "Proposal Id: {}", proposal.id);
+    display_line!(context.io(), "{:4}Type: {}", "", proposal.r#type);
+    display_line!(context.io(), "{:4}Submitted by: {}", "", proposal.author);
+    display_line!(context.io(), "{:4}Current epoch: {}", "", current_epoch);
+    display_line!(
+        context.io(),
+        "{:4}Voting: {}, from epoch {} until epoch {} begins",
+        "",
+        status,
+        proposal.voting_start_epoch,
+        proposal.voting_end_epoch
+    );
+
+    let ended = matches!(status, ProposalStatus::Ended);
+    let proposal_result = if matches!(status, ProposalStatus::Pending) {
+        display_line!(context.io(), "{:4}Tally: voting has not started", "");
+        None
+    } else {
+        namada_sdk::rpc::query_proposal_result(context.client(), proposal_id)
+            .await
+            .unwrap()
+    };
+    if let Some(proposal_result) = &proposal_result {
+        display_line!(context.io(), "{:4}Tally:", "");
+        display_tally(context, proposal_result, ended, 8);
+    }
+
+    let activation_epoch = proposal.activation_epoch;
+    let activation = match proposal_result.map(|result| result.result) {
+        _ if !ended => format!("at epoch {activation_epoch} if passed"),
+        Some(TallyResult::Passed) if current_epoch >= activation_epoch => {
+            format!("activated at epoch {activation_epoch}")
+        }
+        Some(TallyResult::Passed) => {
+            format!("scheduled at epoch {activation_epoch}")
+        }
+        Some(TallyResult::Rejected) | None => {
+            "none, the proposal was rejected".to_string()
+        }
+    };
+    display_line!(context.io(), "{:4}Activation: {}", "", activation);
+}
+
+/// Query the current tally of a proposal with the thresholds it must meet to
+/// pass
+pub async fn query_governance_tally(
+    context: &impl Namada,
+    args: args::QueryProposalResult,
+) {
+    let proposal_id = args.proposal_id;
+    let current_epoch = query_epoch(context.client()).await.unwrap();
+    let proposal =
+        namada_sdk::rpc::query_proposal_by_id(context.client(), proposal_id)
+            .await
+            .unwrap();
+    let proposal_result =
+        namada_sdk::rpc::query_proposal_result(context.client(), proposal_id)
+            .await
+            .unwrap();
+    let (Some(proposal), Some(proposal_result)) = (proposal, proposal_result)
+    else {
+        edisplay_line!(context.io(), "Proposal {} not found.", proposal_id);
+        cli::safe_exit(1)
+    };
+
+    display_line!(context.io(), "Proposal Id: {}", proposal_id);
+    let ended = current_epoch >= proposal.voting_end_epoch;
+    if !ended {
+        display_line!(
+            context.io(),
+            "{:4}Still voting until epoch {} begins.",
+            "",
+            proposal.voting_end_epoch
+        );
+    }
+    display_tally(context, &proposal_result, ended, 4);
+}
+
+/// Display the tally of a proposal with its thresholds, as final if the
+/// voting has ended
+fn display_tally(
+    context: &impl Namada,
+    proposal_result: &ProposalResult,
+    ended: bool,
+    indent: usize,
+) {
+    let outcome = match (ended, proposal_result.result) {
+        (true, result) => result.to_string(),
+        (false, TallyResult::Passed) => "currently passing".to_string(),
+        (false, TallyResult::Rejected) => "currently failing".to_string(),
+    };
+    let total = proposal_result.total_voting_power;
+    let voted = proposal_result.total_voted_power().unwrap();
+    let quorum = proposal_result.tally_type.quorum(total).unwrap();
+
+    display_line!(context.io(), "{:indent$}Outcome: {}", "", outcome);
+    display_line!(
+        context.io(),
+        "{:indent$}Yay: {}",
+        "",
+        proposal_result.total_yay_power.to_string_native()
+    );
+    display_line!(
+        context.io(),
+        "{:indent$}Nay: {}",
+        "",
+        proposal_result.total_nay_power.to_string_native()
+    );
+    display_line!(
+        context.io(),
+        "{:indent$}Abstain: {}",
+        "",
+        proposal_result.total_abstain_power.to_string_native()
+    );
+    display_line!(
+        context.io(),
+        "{:indent$}Voted: {} out of a total voting power of {}",
+        "",
+        voted.to_string_native(),
+        total.to_string_native()
+    );
+    display_line!(
+        context.io(),
+        "{:indent$}Quorum: {}",
+        "",
+        quorum.to_string_native()
+    );
+    display_line!(
+        context.io(),
+        "{:indent$}Passes if {}",
+        "",
+        proposal_result.tally_type.pass_conditions()
+    );
+}
+
+pub async fn verify_proposal_content(
+    context: &impl Namada,
+    args: args::VerifyProposalContent,
+) {
+    let proposal_id = args.proposal_id;
+    let anchor =
+        rpc::query_proposal_content_anchor(context.client(), proposal_id)
+            .await
+            .unwrap();
+    let Some(anchor) = anchor else {
+        edisplay_line!(context.io(), "Proposal {} not found.", proposal_id);
+        cli::safe_exit(1)
+    };
+
+    display_line!(context.io(), "Proposal Id: {}", anchor.id);
+    display_line!(context.io(), "{:4}Author: {}", "", anchor.author);
+    display_line!(
+        context.io(),
+        "{:4}Voting epochs: {} - {}",
+        "",
+        anchor.voting_start_epoch,
+        anchor.voting_end_epoch
+    );
+    for (key, value) in &anchor.metadata {
+        display_line!(context.io(), "{:4}{}: {}", "", key, value);
+    }
+
+    match anchor.verify(&args.content) {
+        ContentVerification::Matches(hash) => {
+            display_line!(
+                context.io(),
+                "The content matches the proposal's content hash {hash}."
+            );
+        }
+        ContentVerification::Mismatch { anchored, found } => {
+            edisplay_line!(
+                context.io(),
+                "The content hash {found} doesn't match the proposal's \
+                 content hash {anchored}."
+            );
+            cli::safe_exit(1)
+        }
+        ContentVerification::NotAnchored => {
+            edisplay_line!(
+                context.io(),
+                "The proposal doesn't anchor an off-chain content. The \
+                 content hash is expected in the \"{CONTENT_HASH_KEY}\" field \
+                 of the proposal content. The hash of the given content is {}.",
+                content_hash(&args.content)
+            );
+            cli::safe_exit(1)
+        }
+        ContentVerification::InvalidAnchor(anchored) => {
+            edisplay_line!(
+                context.io(),
+                "The proposal's content hash {anchored} is not a valid hash."
+            );
+            cli::safe_exit(1)
+        }
+    }
+}
+
+/// Verify the wasm artifacts of the given directory against their checksums
+/// and the code deployed on chain under their names
+pub async fn verify_wasm(
+    context: &impl Namada,
+    _args: args::VerifyWasm,
+    wasm_dir: &Path,
+) {
+    // The error is printed by the loader
+    let checksums = wasm_loader::Checksums::read_checksums(wasm_dir)
+        .unwrap_or_else(|_| cli::safe_exit(1));
+    let artifacts: BTreeMap<String, String> = checksums.0.into_iter().collect();
+    let keys: Vec<storage::Key> =
+        artifacts.keys().map(storage::Key::wasm_hash).collect();
+    let (on_chain_hashes, _) = rpc::query_storage_multi_value_bytes(
+        context.client(),
+        &keys,
+        None,
+        false,
+    )
+    .await
+    .unwrap_or_else(|err| {
+        edisplay_line!(
+            context.io(),
+            "Failed to query the wasm codes on chain: {err}"
+        );
+        cli::safe_exit(1)
+    });
+    let mut allowlist = BTreeSet::new();
+    for key in [
+        param_storage::get_tx_allowlist_storage_key(),
+        param_storage::get_vp_allowlist_storage_key(),
+    ] {
+        let hashes: Vec<String> = query_storage_value(context.client(), &key)
+            .await
+            .expect("Parameter should be defined.");
+        allowlist.extend(hashes.into_iter().map(|hash| hash.to_lowercase()));
+    }
+
+    let mut mismatches = 0_usize;
+    for ((name, full_name), on_chain) in artifacts.iter().zip(on_chain_hashes) {
+        let hash = wasm_loader::hash_artifact(wasm_dir, full_name);
+        let on_chain =
+            on_chain.and_then(|bytes| Hash::try_from(&bytes[..]).ok());
+        let status = wasm_loader::verify_artifact(
+            hash.as_ref(),
+            full_name,
+            on_chain.as_ref(),
+        );
+        if status.is_mismatch() {
+            mismatches += 1;
+        }
+        let status = match status {
+            ArtifactStatus::Verified => "verified",
+            ArtifactStatus::Missing => "missing artifact",
+            ArtifactStatus::ChecksumMismatch => "MISMATCH with its checksum",
+            ArtifactStatus::NotOnChain => "not deployed on chain",
+            ArtifactStatus::OnChainMismatch => {
+                "MISMATCH with the code on chain"
+            }
+        };
+        // Any code is allowed when the allowlists are empty
+        let allowlisted = allowlist.is_empty()
+            || hash.as_ref().is_some_and(|hash| {
+                allowlist.contains(&hash.to_string().to_lowercase())
+            });
+        display_line!(
+            context.io(),
+            "{name}: {status}{}",
+            if allowlisted { "" } else { ", not allowlisted" }
+        );
+        display_line!(
+            context.io(),
+            "{:4}Artifact hash: {}",
+            "",
+            hash.map_or_else(|| "-".to_string(), |hash| hash.to_string())
+        );
+        display_line!(
+            context.io(),
+            "{:4}On-chain hash: {}",
+            "",
+            on_chain.map_or_else(|| "-".to_string(), |hash| hash.to_string())
+        );
+    }
+
+    if mismatches > 0 {
+        edisplay_line!(
+            context.io(),
+            "{mismatches} of the {} wasm artifacts mismatch.",
+            artifacts.len()
+        );
+        cli::safe_exit(1)
+    }
+    display_line!(
+        context.io(),
+        "None of the {} wasm artifacts mismatch.",
+        artifacts.len()
+    );
+}
+
 pub async fn query_account(context: &impl Namada, args: args::QueryAccount) {
     let account = rpc::get_account_info(context.client(), &args.owner)
         .await
@@ -504,6 +1033,20 @@ pub async fn query_pgf(context: &impl Namada, _args: args::QueryPgf) {
             display_line!(context.io(), "Pgf stewards:");
             for steward in stewards {
                 display_line!(context.io(), "{:4}- {}", "", steward.address);
+                let term_end = rpc::query_pgf_steward_term_end(
+                    context.client(),
+                    &steward.address,
+                )
+                .await
+                .unwrap();
+                if let Some(term_end) = term_end {
+                    display_line!(
+                        context.io(),
+                        "{:4}  Term ends at epoch: {}",
+                        "",
+                        term_end
+                    );
+                }
                 display_line!(context.io(), "{:4}  Reward distribution:", "");
                 for (address, percentage) in steward.reward_distribution {
                     display_line!(
@@ -540,6 +1083,16 @@ pub async fn query_pgf(context: &impl Namada, _args: args::QueryPgf) {
     }
 }
 
+/// Display the changes of a protocol parameter scheduled for future epochs
+fn display_pending_parameter<T: std::fmt::Display>(
+    context: &impl Namada,
+    pending: BTreeMap<Epoch, T>,
+) {
+    for (epoch, value) in pending {
+        display_line!(context.io(), "{:8}From epoch {}: {}", "", epoch, value);
+    }
+}
+
 pub async fn query_protocol_parameters(
     context: &impl Namada,
     _args: args::QueryProtocolParameters,
@@ -583,6 +1136,18 @@ pub async fn query_protocol_parameters(
         "",
         governance_parameters.min_proposal_grace_epochs
     );
+    let paused_modules = rpc::query_paused_modules(context.client())
+        .await
+        .unwrap_or_default();
+    for (module, pause_end) in paused_modules {
+        display_line!(
+            context.io(),
+            "{:4}Paused module: {} until epoch {}",
+            "",
+            module,
+            pause_end
+        );
+    }
 
     let pgf_parameters = query_pgf_parameters(context.client()).await;
     display_line!(context.io(), "\nPublic Goods Funding Parameters");
@@ -647,10 +1212,12 @@ pub async fn query_protocol_parameters(
     );
 
     let key = param_storage::get_max_block_gas_key();
-    let max_block_gas: u64 = query_storage_value(context.client(), &key)
-        .await
-        .expect("Parameter should be defined.");
+    let (max_block_gas, pending): (u64, _) =
+        namada_sdk::rpc::query_epoched_parameter(context.client(), &key)
+            .await
+            .expect("Parameter should be defined.");
     display_line!(context.io(), "{:4}Max block gas: {:?}", "", max_block_gas);
+    display_pending_parameter(context, pending);
 
     let key = param_storage::get_fee_unshielding_gas_limit_key();
     let fee_unshielding_gas_limit: u64 =
@@ -676,6 +1243,29 @@ pub async fn query_protocol_parameters(
         fee_unshielding_descriptions_limit
     );
 
+    let key = param_storage::get_max_epoch_hooks_gas_key();
+    let max_epoch_hooks_gas: u64 = query_storage_value(context.client(), &key)
+        .await
+        .expect("Parameter should be defined.");
+    display_line!(
+        context.io(),
+        "{:4}Max epoch hooks gas: {:?}",
+        "",
+        max_epoch_hooks_gas
+    );
+
+    let key = param_storage::get_epoch_hook_allowlist_storage_key();
+    let epoch_hook_allowlist: Vec<String> =
+        query_storage_value(context.client(), &key)
+            .await
+            .expect("Parameter should be defined.");
+    display_line!(
+        context.io(),
+        "{:4}Epoch hooks allowlist: {:?}",
+        "",
+        epoch_hook_allowlist
+    );
+
     let key = param_storage::get_gas_cost_key();
     let gas_cost_table: BTreeMap<Address, token::Amount> =
         query_storage_value(context.client(), &key)
@@ -686,6 +1276,63 @@ pub async fn query_protocol_parameters(
         display_line!(context.io(), "{:8}{}: {:?}", "", token, gas_cost);
     }
 
+    let key = param_storage::get_target_block_gas_key();
+    let (target_block_gas, pending): (u64, _) =
+        namada_sdk::rpc::query_epoched_parameter(context.client(), &key)
+            .await
+            .expect("Parameter should be defined.");
+    display_line!(
+        context.io(),
+        "{:4}Target block gas: {:?}",
+        "",
+        target_block_gas
+    );
+    display_pending_parameter(context, pending);
+
+    let key = param_storage::get_base_fee_max_change_rate_key();
+    let (base_fee_max_change_rate, pending): (Dec, _) =
+        namada_sdk::rpc::query_epoched_parameter(context.client(), &key)
+            .await
+            .expect("Parameter should be defined.");
+    display_line!(
+        context.io(),
+        "{:4}Base fee max change rate: {}",
+        "",
+        base_fee_max_change_rate
+    );
+    display_pending_parameter(context, pending);
+
+    let key = param_storage::get_masp_convert_anchor_grace_blocks_key();
+    let (masp_convert_anchor_grace_blocks, pending): (u64, _) =
+        namada_sdk::rpc::query_epoched_parameter(context.client(), &key)
+            .await
+            .expect("Parameter should be defined.");
+    display_line!(
+        context.io(),
+        "{:4}MASP convert anchor grace blocks: {}",
+        "",
+        masp_convert_anchor_grace_blocks
+    );
+    display_pending_parameter(context, pending);
+
+    let key = param_storage::get_masp_max_note_age_key();
+    let (masp_max_note_age, pending): (u64, _) =
+        namada_sdk::rpc::query_epoched_parameter(context.client(), &key)
+            .await
+            .expect("Parameter should be defined.");
+    display_line!(
+        context.io(),
+        "{:4}MASP max note age: {}",
+        "",
+        masp_max_note_age
+    );
+    display_pending_parameter(context, pending);
+
+    let base_fee = namada_sdk::rpc::query_base_fee(context.client())
+        .await
+        .expect("Base fee should be defined.");
+    display_line!(context.io(), "{:4}Base fee: {:?}", "", base_fee);
+
     display_line!(context.io(), "PoS parameters");
     let pos_params = query_pos_parameters(context.client()).await;
     display_line!(
@@ -778,6 +1425,32 @@ pub async fn query_protocol_parameters(
         "",
         pos_params.rewards_gain_d
     );
+    display_line!(
+        context.io(),
+        "{:4}Liquid staking receipts: {}",
+        "",
+        pos_params.liquid_staking_receipts
+    );
+    display_line!(
+        context.io(),
+        "{:4}Min validator commission rate: {}",
+        "",
+        pos_params.min_commission_rate
+    );
+    display_line!(
+        context.io(),
+        "{:4}Required validator metadata: {}",
+        "",
+        itertools::join(&pos_params.required_validator_metadata, ", ")
+    );
+    display_line!(
+        context.io(),
+        "{:4}Max validator stake share: {}",
+        "",
+        pos_params
+            .max_validator_stake_share
+            .map_or_else(|| "none".to_string(), |share| share.to_string())
+    );
     display_line!(
         context.io(),
         "{:4}Votes per raw token: {}",
@@ -786,6 +1459,89 @@ pub async fn query_protocol_parameters(
     );
 }
 
+/// Query the chain parameters at two heights or epochs and display the
+/// parameters that changed between them
+pub async fn query_parameters_diff(
+    context: &impl Namada,
+    args: args::QueryParametersDiff,
+) {
+    let from_height =
+        parameters_diff_height(context, args.from_height, args.from_epoch)
+            .await;
+    let to_height =
+        parameters_diff_height(context, args.to_height, args.to_epoch).await;
+    let old = rpc::query_chain_parameters(context.client(), from_height)
+        .await
+        .unwrap_or_else(|err| {
+            edisplay_line!(
+                context.io(),
+                "Failed to query the parameters: {err}"
+            );
+            cli::safe_exit(1)
+        });
+    let new = rpc::query_chain_parameters(context.client(), to_height)
+        .await
+        .unwrap_or_else(|err| {
+            edisplay_line!(
+                context.io(),
+                "Failed to query the parameters: {err}"
+            );
+            cli::safe_exit(1)
+        });
+
+    display_line!(
+        context.io(),
+        "Parameter changes from height {} (epoch {}) to height {} (epoch {}):",
+        old.height,
+        old.epoch,
+        new.height,
+        new.epoch
+    );
+    let changes = old.diff(&new);
+    if changes.is_empty() {
+        display_line!(context.io(), "{:2}No changes.", "");
+        return;
+    }
+    for change in changes {
+        display_line!(
+            context.io(),
+            "{:2}{}: {} -> {}",
+            "",
+            change.name,
+            change.old.as_deref().unwrap_or("(unset)"),
+            change.new.as_deref().unwrap_or("(unset)")
+        );
+    }
+}
+
+/// Get the height of the parameters to diff, either given or the first height
+/// of the given epoch
+async fn parameters_diff_height(
+    context: &impl Namada,
+    height: Option<BlockHeight>,
+    epoch: Option<Epoch>,
+) -> Option<BlockHeight> {
+    let epoch = match epoch {
+        Some(epoch) => epoch,
+        None => return height,
+    };
+    let height =
+        rpc::query_first_block_height_of_epoch(context.client(), epoch)
+            .await
+            .unwrap_or_else(|err| {
+                edisplay_line!(
+                    context.io(),
+                    "Failed to query the epoch: {err}"
+                );
+                cli::safe_exit(1)
+            });
+    if height.is_none() {
+        edisplay_line!(context.io(), "Epoch {epoch} not found.");
+        cli::safe_exit(1)
+    }
+    height
+}
+
 pub async fn query_bond<C: namada::ledger::queries::Client + Sync>(
     client: &C,
     source: &Address,
@@ -1064,7 +1820,24 @@ pub async fn query_bonded_stake<N: Namada>(
                         context.io(),
                         "Bonded stake of validator {validator}: {}",
                         stake.to_string_native()
-                    )
+                    );
+                    let capacity: Option<token::Amount> =
+                        unwrap_client_response::<N::Client, _>(
+                            RPC.vp()
+                                .pos()
+                                .validator_remaining_capacity(
+                                    context.client(),
+                                    &validator,
+                                )
+                                .await,
+                        );
+                    if let Some(capacity) = capacity {
+                        display_line!(
+                            context.io(),
+                            "Remaining bond capacity at the pipeline epoch: {}",
+                            capacity.to_string_native()
+                        );
+                    }
                 }
                 None => {
                     display_line!(
@@ -1162,6 +1935,21 @@ pub async fn query_metadata<C: namada::ledger::queries::Client + Sync>(
     )
 }
 
+/// Query and return validator's operational metadata
+pub async fn query_operational_metadata<
+    C: namada::ledger::queries::Client + Sync,
+>(
+    client: &C,
+    validator: &Address,
+) -> Option<OperationalMetadata> {
+    unwrap_client_response::<C, Option<OperationalMetadata>>(
+        RPC.vp()
+            .pos()
+            .validator_operational_metadata(client, validator)
+            .await,
+    )
+}
+
 /// Query and return validator's state
 pub async fn query_validator_state<
     C: namada::ledger::queries::Client + Sync,
@@ -1243,6 +2031,65 @@ pub async fn query_and_print_validator_state(
              epoch before the current epoch has been queried."
         ),
     }
+
+    let jail_history =
+        rpc::query_validator_jail_history(context.client(), &validator)
+            .await
+            .unwrap();
+    if !jail_history.is_empty() {
+        display_line!(context.io(), "Most recent jailings:");
+        for record in jail_history.iter().rev() {
+            display_line!(
+                context.io(),
+                "{:4}From epoch {}: {}",
+                "",
+                record.epoch,
+                record.reason
+            );
+        }
+    }
+}
+
+/// Query the pending change of a validator's consensus key
+pub async fn query_and_print_pending_consensus_key(
+    context: &impl Namada,
+    args: args::QueryPendingConsensusKey,
+) {
+    let validator = args.validator;
+    let pending =
+        rpc::query_pending_consensus_key(context.client(), &validator)
+            .await
+            .unwrap();
+    match pending {
+        Some(PendingConsensusKey {
+            consensus_key,
+            epoch,
+            height,
+        }) => {
+            display_line!(
+                context.io(),
+                "The new consensus key {consensus_key} of validator \
+                 {validator} comes into effect in epoch {epoch}."
+            );
+            match height {
+                Some(height) => display_line!(
+                    context.io(),
+                    "CometBFT must sign with the new key from block height \
+                     {height}."
+                ),
+                None => display_line!(
+                    context.io(),
+                    "The height of the first block of the epoch is not known \
+                     yet. A consensus key activation event is emitted at that \
+                     block."
+                ),
+            }
+        }
+        None => display_line!(
+            context.io(),
+            "Validator {validator} has no pending consensus key change."
+        ),
+    }
 }
 
 /// Query PoS validator's commission rate information
@@ -1251,6 +2098,7 @@ pub async fn query_and_print_commission_rate(
     args: args::QueryCommissionRate,
 ) {
     let validator = args.validator;
+    let query_current_epoch = args.epoch.is_none();
 
     let CommissionPair {
         commission_rate,
@@ -1264,7 +2112,10 @@ pub async fn query_and_print_commission_rate(
                 "Validator {validator} commission rate: {commission_rate}, \
                  max change per epoch: {max_commission_change_per_epoch} in \
                  epoch {query_epoch}"
-            )
+            );
+            if query_current_epoch {
+                print_pending_commission_rates(context, &validator).await;
+            }
         }
         (None, None) => display_line!(
             context.io(),
@@ -1283,6 +2134,35 @@ pub async fn query_and_print_commission_rate(
     }
 }
 
+/// Print the upcoming changes of a validator's commission rate
+async fn print_pending_commission_rates(
+    context: &impl Namada,
+    validator: &Address,
+) {
+    let Some(schedule) =
+        rpc::query_commission_schedule(context.client(), validator)
+            .await
+            .unwrap()
+    else {
+        return;
+    };
+    let mut prev_rate = schedule.current_rate;
+    let mut changes = Vec::new();
+    for (epoch, rate) in schedule.pending_rates {
+        if rate != prev_rate {
+            changes.push((epoch, rate));
+            prev_rate = rate;
+        }
+    }
+    if changes.is_empty() {
+        return;
+    }
+    display_line!(context.io(), "Pending commission rate changes:");
+    for (epoch, rate) in changes {
+        display_line!(context.io(), "{:4}From epoch {}: {}", "", epoch, rate);
+    }
+}
+
 /// Query PoS validator's metadata
 pub async fn query_and_print_metadata(
     context: &impl Namada,
@@ -1345,6 +2225,33 @@ pub async fn query_and_print_metadata(
         ),
     }
 
+    // Get the operational metadata published with protocol txs
+    let operational_metadata =
+        query_operational_metadata(context.client(), &validator).await;
+    match operational_metadata {
+        Some(OperationalMetadata {
+            rpc_endpoint,
+            seed_node,
+            emergency_contact,
+        }) => {
+            display_line!(context.io(), "Operational metadata:");
+            if let Some(rpc_endpoint) = rpc_endpoint {
+                display_line!(context.io(), "RPC endpoint: {}", rpc_endpoint);
+            }
+            if let Some(seed_node) = seed_node {
+                display_line!(context.io(), "Seed node: {}", seed_node);
+            }
+            if let Some(emergency_contact) = emergency_contact {
+                display_line!(
+                    context.io(),
+                    "Emergency contact: {}",
+                    emergency_contact
+                );
+            }
+        }
+        None => display_line!(context.io(), "No operational metadata"),
+    }
+
     // Get commission rate info for the current epoch
     let CommissionPair {
         commission_rate,
@@ -1377,6 +2284,78 @@ pub async fn query_and_print_metadata(
     }
 }
 
+/// Query the validators' telemetry
+pub async fn query_and_print_telemetry<N: Namada>(
+    context: &N,
+    args: args::QueryTelemetry,
+) {
+    if let Some(validator) = args.validator {
+        let telemetry: Option<ValidatorTelemetry> =
+            unwrap_client_response::<N::Client, _>(
+                RPC.vp()
+                    .pos()
+                    .validator_telemetry(context.client(), &validator)
+                    .await,
+            );
+        match telemetry {
+            Some(ValidatorTelemetry {
+                binary_version,
+                commit_hash,
+                block_height,
+            }) => display_line!(
+                context.io(),
+                "Validator {validator} last heartbeat at block height \
+                 {block_height}: binary version {binary_version}, commit \
+                 {commit_hash}"
+            ),
+            None => display_line!(
+                context.io(),
+                "No telemetry heartbeat found for validator {validator}"
+            ),
+        }
+        return;
+    }
+
+    let TelemetrySummary {
+        epoch,
+        total_stake,
+        binaries,
+        unreported_stake,
+    } = unwrap_client_response::<N::Client, _>(
+        RPC.vp()
+            .pos()
+            .telemetry_summary(context.client(), &args.epoch)
+            .await,
+    );
+    display_line!(
+        context.io(),
+        "Telemetry of the consensus validators in epoch {epoch}, with a total \
+         stake of {}:",
+        total_stake.to_string_native()
+    );
+    for BinaryTelemetry {
+        binary_version,
+        commit_hash,
+        validators,
+        stake,
+    } in binaries
+    {
+        display_line!(
+            context.io(),
+            "{:4}{binary_version} ({commit_hash}): {} from {validators} \
+             validator(s)",
+            "",
+            stake.to_string_native()
+        );
+    }
+    display_line!(
+        context.io(),
+        "{:4}No heartbeat: {}",
+        "",
+        unreported_stake.to_string_native()
+    );
+}
+
 /// Query PoS slashes
 pub async fn query_slashes<N: Namada>(context: &N, args: args::QuerySlashes) {
     match args.validator {
@@ -1543,6 +2522,147 @@ pub async fn query_and_print_rewards<N: Namada>(
     );
 }
 
+/// Query and print the bonds, unbonds, rewards and slashing exposure of an
+/// owner with all their validators
+pub async fn query_staking_overview(
+    context: &impl Namada,
+    args: args::QueryStakingOverview,
+) {
+    let owner = args.owner;
+    let overview = rpc::query_staking_overview(context.client(), &owner)
+        .await
+        .unwrap();
+    let epoch = overview.epoch;
+    if overview.staking.data.is_empty() {
+        display_line!(context.io(), "No bonds found for {owner}.");
+        return;
+    }
+
+    display_line!(context.io(), "Staking of {owner} at epoch {epoch}:");
+    for (validator, staking) in &overview.staking.data {
+        let details = &staking.bonds_and_unbonds;
+        let state = match staking.state {
+            Some(ValidatorState::Consensus) => "consensus",
+            Some(ValidatorState::BelowCapacity) => "below-capacity",
+            Some(ValidatorState::BelowThreshold) => "below-threshold",
+            Some(ValidatorState::Inactive) => "inactive",
+            Some(ValidatorState::Jailed) => "jailed",
+            None => "unknown",
+        };
+        display_line!(context.io(), "");
+        display_line!(context.io(), "Validator {validator} ({state}):");
+        display_line!(
+            context.io(),
+            "{:2}Bonds total: {} (slashed {})",
+            "",
+            details.bonds_total.to_string_native(),
+            details.bonds_total_slashed.to_string_native()
+        );
+        for bond in &details.data.bonds {
+            display_line!(
+                context.io(),
+                "{:4}Active from epoch {}: {} (slashed {})",
+                "",
+                bond.start,
+                bond.amount.to_string_native(),
+                bond.slashed_amount.unwrap_or_default().to_string_native()
+            );
+        }
+        if !details.data.unbonds.is_empty() {
+            display_line!(
+                context.io(),
+                "{:2}Unbonds total: {} (slashed {})",
+                "",
+                details.unbonds_total.to_string_native(),
+                details.unbonds_total_slashed.to_string_native()
+            );
+            for unbond in &details.data.unbonds {
+                let withdrawable = if unbond.withdraw <= epoch {
+                    "withdrawable now".to_string()
+                } else {
+                    format!("withdrawable from epoch {}", unbond.withdraw)
+                };
+                display_line!(
+                    context.io(),
+                    "{:4}{} (slashed {}), {}",
+                    "",
+                    unbond.amount.to_string_native(),
+                    unbond
+                        .slashed_amount
+                        .unwrap_or_default()
+                        .to_string_native(),
+                    withdrawable
+                );
+            }
+        }
+        display_line!(
+            context.io(),
+            "{:2}Withdrawable: {}",
+            "",
+            details.total_withdrawable.to_string_native()
+        );
+        display_line!(
+            context.io(),
+            "{:2}Rewards available to claim: {}",
+            "",
+            staking.rewards.to_string_native()
+        );
+        if !details.data.slashes.is_empty()
+            || !staking.enqueued_slashes.is_empty()
+        {
+            display_line!(context.io(), "{:2}Slashes:", "");
+            for slash in &details.data.slashes {
+                display_line!(
+                    context.io(),
+                    "{:4}{} at epoch {}, rate {}",
+                    "",
+                    slash.r#type,
+                    slash.epoch,
+                    slash.rate
+                );
+            }
+            for (process_epoch, slashes) in &staking.enqueued_slashes {
+                for slash in slashes {
+                    display_line!(
+                        context.io(),
+                        "{:4}{} at epoch {}, enqueued to be processed at \
+                         epoch {}",
+                        "",
+                        slash.r#type,
+                        slash.epoch,
+                        process_epoch
+                    );
+                }
+            }
+        }
+    }
+
+    let totals = &overview.staking;
+    display_line!(context.io(), "");
+    display_line!(
+        context.io(),
+        "All bonds total: {} (slashed {})",
+        totals.bonds_total.to_string_native(),
+        totals.bonds_total_slashed.to_string_native()
+    );
+    display_line!(
+        context.io(),
+        "All unbonds total: {} (slashed {})",
+        totals.unbonds_total.to_string_native(),
+        totals.unbonds_total_slashed.to_string_native()
+    );
+    display_line!(
+        context.io(),
+        "All withdrawable total: {}",
+        totals.total_withdrawable.to_string_native()
+    );
+    display_line!(
+        context.io(),
+        "All rewards total: {}",
+        overview.total_rewards.to_string_native()
+    );
+}
+
 pub async fn query_delegations<N: Namada>(
     context: &N,
     args: args::QueryDelegations,
@@ -2021,9 +3141,15 @@ pub async fn compute_proposal_votes<
     proposal_id: u64,
     epoch: Epoch,
 ) -> ProposalVotes {
-    let votes = namada_sdk::rpc::query_proposal_votes(client, proposal_id)
+    let mut votes = namada_sdk::rpc::query_proposal_votes(client, proposal_id)
         .await
         .unwrap();
+    // Count the votes of the voting delegates on behalf of their delegators
+    let delegated_votes =
+        namada_sdk::rpc::query_proposal_delegated_votes(client, proposal_id)
+            .await
+            .unwrap();
+    votes.extend(delegated_votes);
 
     let mut validators_vote: HashMap<Address, ProposalVote> =
         HashMap::default();
@@ -9,7 +9,8 @@ use namada::core::address::{Address, ImplicitAddress};
 use namada::core::collections::HashSet;
 use namada::core::key::*;
 use namada::governance::cli::onchain::{
-    DefaultProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, EmergencyPauseProposal, ExpeditedProposal,
+    PgfFundingProposal, PgfStewardElectionProposal, PgfStewardProposal,
 };
 use namada::io::Io;
 use namada::state::EPOCH_SWITCH_BLOCKS_DELAY;
@@ -18,7 +19,9 @@ use namada_sdk::args::TxBecomeValidator;
 use namada_sdk::rpc::{InnerTxResult, TxBroadcastData, TxResponse};
 use namada_sdk::wallet::alias::{validator_address, validator_consensus_key};
 use namada_sdk::wallet::{Wallet, WalletIo};
-use namada_sdk::{display_line, edisplay_line, error, signing, tx, Namada};
+use namada_sdk::{
+    display, display_line, edisplay_line, error, signing, tx, Namada,
+};
 use rand::rngs::OsRng;
 use tokio::sync::RwLock;
 
@@ -152,6 +155,9 @@ pub async fn sign<N: Namada>(
     args: &args::Tx,
     signing_data: SigningTxData,
 ) -> Result<(), error::Error> {
+    if args.confirm_fees && tx.header().wrapper().is_some() {
+        confirm_fees(context, tx).await?;
+    }
     // Setup a reusable context for signing transactions using the Ledger
     if args.use_device {
         // Setup a reusable context for signing transactions using the Ledger
@@ -186,6 +192,43 @@ pub async fn sign<N: Namada>(
     Ok(())
 }
 
+// Display the fee breakdown of the given wrapper transaction and ask for a
+// confirmation before signing it
+async fn confirm_fees<N: Namada>(
+    context: &N,
+    tx: &Tx,
+) -> Result<(), error::Error> {
+    let breakdown = signing::fee_breakdown(context, tx).await?;
+    display_line!(context.io(), "{breakdown}");
+    display!(
+        context.io(),
+        "\nDo you wish to sign the transaction? (y/n): "
+    );
+    context.io().flush();
+    loop {
+        let resp = context.io().read().await.map_err(|e| {
+            error::Error::Other(format!(
+                "Encountered error reading from STDIN: {e:?}"
+            ))
+        })?;
+        match resp.trim() {
+            "y" => return Ok(()),
+            "n" => {
+                return Err(error::Error::Other(
+                    "Aborted signing the transaction".into(),
+                ));
+            }
+            _ => {
+                display!(
+                    context.io(),
+                    "Expected 'y' or 'n'. Please try again: "
+                );
+                context.io().flush();
+            }
+        }
+    }
+}
+
 // Build a transaction to reveal the signer of the given transaction.
 pub async fn submit_reveal_aux(
     context: &impl Namada,
@@ -285,6 +328,106 @@ where
     Ok(())
 }
 
+pub async fn submit_update_account_signers<N: Namada>(
+    namada: &N,
+    args: args::TxUpdateAccountSigners,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_update_name<N: Namada>(
+    namada: &N,
+    args: args::TxUpdateName,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_faucet_withdraw<N: Namada>(
+    namada: &N,
+    args: args::TxFaucetWithdraw,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_update_faucet_limits<N: Namada>(
+    namada: &N,
+    args: args::TxUpdateFaucetLimits,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_update_transfer_policy<N: Namada>(
+    namada: &N,
+    args: args::TxUpdateTransferPolicy,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
 pub async fn submit_init_account<N: Namada>(
     namada: &N,
     args: args::TxInitAccount,
@@ -393,7 +536,9 @@ pub async fn submit_change_consensus_key(
                      the pipeline epoch relative to the current epoch \
                      (current epoch + pipeline offset), at which point you \
                      will need to give the new key to CometBFT in order to be \
-                     able to sign with it in consensus.",
+                     able to sign with it in consensus. The \
+                     `pending-consensus-key` query shows when the key comes \
+                     into effect.",
                 );
             }
         } else {
@@ -875,6 +1020,92 @@ where
             .await?;
 
         tx::build_pgf_stewards_proposal(namada, &args, proposal).await?
+    } else if args.is_pgf_steward_election {
+        let proposal =
+            PgfStewardElectionProposal::try_from(args.proposal_data.as_ref())
+                .map_err(|e| {
+                error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                    e.to_string(),
+                )
+            })?;
+        let author_balance = namada_sdk::rpc::get_token_balance(
+            namada.client(),
+            &namada.native_token(),
+            &proposal.proposal.author,
+        )
+        .await?;
+        let proposal = proposal
+            .validate(
+                &governance_parameters,
+                current_epoch,
+                author_balance,
+                args.tx.force,
+            )
+            .map_err(|e| {
+                error::TxSubmitError::InvalidProposal(e.to_string())
+            })?;
+
+        submit_reveal_aux(namada, args.tx.clone(), &proposal.proposal.author)
+            .await?;
+
+        tx::build_pgf_steward_election_proposal(namada, &args, proposal).await?
+    } else if args.is_emergency_pause {
+        let proposal =
+            EmergencyPauseProposal::try_from(args.proposal_data.as_ref())
+                .map_err(|e| {
+                    error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                        e.to_string(),
+                    )
+                })?;
+        let author_balance = namada_sdk::rpc::get_token_balance(
+            namada.client(),
+            &namada.native_token(),
+            &proposal.proposal.author,
+        )
+        .await?;
+        let proposal = proposal
+            .validate(
+                &governance_parameters,
+                current_epoch,
+                author_balance,
+                args.tx.force,
+            )
+            .map_err(|e| {
+                error::TxSubmitError::InvalidProposal(e.to_string())
+            })?;
+
+        submit_reveal_aux(namada, args.tx.clone(), &proposal.proposal.author)
+            .await?;
+
+        tx::build_emergency_pause_proposal(namada, &args, proposal).await?
+    } else if args.is_expedited {
+        let proposal = ExpeditedProposal::try_from(args.proposal_data.as_ref())
+            .map_err(|e| {
+                error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                    e.to_string(),
+                )
+            })?;
+        let author_balance = namada_sdk::rpc::get_token_balance(
+            namada.client(),
+            &namada.native_token(),
+            &proposal.proposal.author,
+        )
+        .await?;
+        let proposal = proposal
+            .validate(
+                &governance_parameters,
+                current_epoch,
+                author_balance,
+                args.tx.force,
+            )
+            .map_err(|e| {
+                error::TxSubmitError::InvalidProposal(e.to_string())
+            })?;
+
+        submit_reveal_aux(namada, args.tx.clone(), &proposal.proposal.author)
+            .await?;
+
+        tx::build_expedited_proposal(namada, &args, proposal).await?
     } else {
         let proposal = DefaultProposal::try_from(args.proposal_data.as_ref())
             .map_err(|e| {
@@ -1178,6 +1409,28 @@ where
     Ok(())
 }
 
+pub async fn submit_operational_metadata_change<N: Namada>(
+    namada: &N,
+    args: args::OperationalMetadataChange,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let tx = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        // Protocol txs are not wrapped, so they are only broadcast to the
+        // mempool and applied when included in a block
+        let tx_hash = tx.header_hash().to_string();
+        tx::broadcast_tx(namada, &TxBroadcastData::Live { tx, tx_hash })
+            .await?;
+    }
+
+    Ok(())
+}
+
 pub async fn submit_unjail_validator<N: Namada>(
     namada: &N,
     args: args::TxUnjailValidator,
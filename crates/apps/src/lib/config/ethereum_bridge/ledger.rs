@@ -1,4 +1,8 @@
 //! Runtime configuration for a validator node.
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use namada::core::address::Address;
 #[allow(unused_imports)]
 use namada::core::ethereum_events::EthereumEvent;
 use serde::{Deserialize, Serialize};
@@ -10,6 +14,10 @@ pub const DEFAULT_ORACLE_RPC_ENDPOINT: &str = "http://127.0.0.1:8545";
 /// the oracle and the shell can hold.
 pub const ORACLE_CHANNEL_BUFFER_SIZE: usize = 1000;
 
+/// The default interval, in seconds, between the relayer's checks of the
+/// Bridge pool.
+pub const DEFAULT_RELAYER_POLL_INTERVAL_SECS: u64 = 60;
+
 /// The mode in which to run the Ethereum bridge.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Mode {
@@ -37,6 +45,62 @@ pub struct Config {
     /// ledger subprocesses. This is the number of Ethereum events that
     /// can be held in the channel. The default is 1000.
     pub channel_buffer_size: usize,
+    /// When set, the node runs a relayer of the Bridge pool.
+    #[serde(default)]
+    pub relayer: Option<RelayerConfig>,
+}
+
+/// Configuration of the Bridge pool relayer built into the node. The relayer
+/// periodically looks for a profitable batch of the signed Bridge pool
+/// transfers and relays its proof to the Ethereum bridge contract.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RelayerConfig {
+    /// The Namada address receiving the fees of the relayed transfers.
+    pub relayer_address: Address,
+    /// The Ethereum JSON-RPC endpoint used to submit the relay txs. Defaults
+    /// to the oracle's endpoint.
+    #[serde(default)]
+    pub eth_rpc_endpoint: Option<String>,
+    /// Path to a file holding the hex encoded secp256k1 secret key that
+    /// signs the relay txs and pays their Ethereum gas. Only optional in
+    /// dry-run mode.
+    #[serde(default)]
+    pub eth_key_path: Option<PathBuf>,
+    /// Path to a JSON object mapping the addresses of the tokens paying the
+    /// Bridge pool gas fees to their conversion rates in gwei.
+    pub conversion_table_path: PathBuf,
+    /// The maximum amount of Ethereum gas that a relay tx may spend.
+    #[serde(default)]
+    pub max_gas: Option<u64>,
+    /// The net cost in gwei the relayer is willing to pay for a relay. When
+    /// unset, only profitable batches are relayed.
+    #[serde(default)]
+    pub max_net_cost: Option<u64>,
+    /// The price of the Ethereum gas of the relay txs. Estimated by the
+    /// Ethereum node when unset.
+    #[serde(default)]
+    pub gas_price: Option<u64>,
+    /// The number of Ethereum confirmations to wait for after a relay.
+    #[serde(default = "default_relayer_confirmations")]
+    pub confirmations: u64,
+    /// The interval, in seconds, between the checks of the Bridge pool.
+    #[serde(default = "default_relayer_poll_interval")]
+    pub poll_interval_secs: u64,
+    /// In dry-run mode, the relayer only logs the batches it would relay.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// When set, the relayer's metrics are served in the Prometheus text
+    /// format under `/metrics` on this address.
+    #[serde(default)]
+    pub metrics_endpoint: Option<SocketAddr>,
+}
+
+fn default_relayer_confirmations() -> u64 {
+    1
+}
+
+fn default_relayer_poll_interval() -> u64 {
+    DEFAULT_RELAYER_POLL_INTERVAL_SECS
 }
 
 impl Default for Config {
@@ -45,6 +109,7 @@ impl Default for Config {
             mode: Mode::RemoteEndpoint,
             oracle_rpc_endpoint: DEFAULT_ORACLE_RPC_ENDPOINT.to_owned(),
             channel_buffer_size: ORACLE_CHANNEL_BUFFER_SIZE,
+            relayer: None,
         }
     }
 }
@@ -295,6 +295,12 @@ impl Finalized {
             minimum_gas_price,
             max_tx_bytes,
             is_native_token_transferable,
+            max_epoch_hooks_gas,
+            epoch_hook_allowlist,
+            target_block_gas,
+            base_fee_max_change_rate,
+            masp_convert_anchor_grace_blocks,
+            masp_max_note_age,
             ..
         } = self.parameters.parameters.clone();
 
@@ -321,6 +327,7 @@ impl Finalized {
                 .into();
         let vp_allowlist = vp_allowlist.unwrap_or_default();
         let tx_allowlist = tx_allowlist.unwrap_or_default();
+        let epoch_hook_allowlist = epoch_hook_allowlist.unwrap_or_default();
 
         namada::ledger::parameters::Parameters {
             max_tx_bytes,
@@ -345,6 +352,12 @@ impl Finalized {
                 })
                 .collect(),
             is_native_token_transferable,
+            max_epoch_hooks_gas,
+            epoch_hook_allowlist,
+            target_block_gas,
+            base_fee_max_change_rate,
+            masp_convert_anchor_grace_blocks,
+            masp_max_note_age,
         }
     }
 
@@ -368,6 +381,10 @@ impl Finalized {
             liveness_threshold,
             rewards_gain_p,
             rewards_gain_d,
+            liquid_staking_receipts,
+            min_commission_rate,
+            required_validator_metadata,
+            max_validator_stake_share,
         } = self.parameters.pos_params.clone();
 
         namada::proof_of_stake::parameters::PosParams {
@@ -388,6 +405,10 @@ impl Finalized {
                 liveness_threshold,
                 rewards_gain_p,
                 rewards_gain_d,
+                liquid_staking_receipts,
+                min_commission_rate,
+                required_validator_metadata,
+                max_validator_stake_share,
             },
             max_proposal_period: self.parameters.gov_params.max_proposal_period,
         }
@@ -15,6 +15,7 @@ use namada::core::token::{
 use namada::eth_bridge::storage::parameters::{
     Contracts, Erc20WhitelistEntry, MinimumConfirmations,
 };
+use namada::proof_of_stake::types::ValidatorMetaDataField;
 use namada::token;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
@@ -303,6 +304,22 @@ pub struct ChainParams<T: TemplateValidation> {
     pub fee_unshielding_descriptions_limit: u64,
     /// Map of the cost per gas unit for every token allowed for fee payment
     pub minimum_gas_price: T::GasMinimums,
+    /// Max gas that can be spent by all the accounts' epoch hooks at an
+    /// epoch transition
+    pub max_epoch_hooks_gas: u64,
+    /// Hashes of the txs allowed as epoch hooks. `None` value or an empty
+    /// array disables epoch hooks.
+    pub epoch_hook_allowlist: Option<Vec<String>>,
+    /// Target gas of a block for the adjustment of the dynamic base fee
+    pub target_block_gas: u64,
+    /// Max change rate of the base fee from one block to the next
+    pub base_fee_max_change_rate: Dec,
+    /// Number of blocks, from the one of an epoch change, for which the MASP
+    /// convert anchor of the previous epoch is still accepted
+    pub masp_convert_anchor_grace_blocks: u64,
+    /// Max age, in epochs, of the MASP notes that can still be converted to
+    /// the latest epoch. Zero means that the conversions are never pruned.
+    pub masp_max_note_age: u64,
 }
 
 impl ChainParams<Unvalidated> {
@@ -326,6 +343,12 @@ impl ChainParams<Unvalidated> {
             fee_unshielding_gas_limit,
             fee_unshielding_descriptions_limit,
             minimum_gas_price,
+            max_epoch_hooks_gas,
+            epoch_hook_allowlist,
+            target_block_gas,
+            base_fee_max_change_rate,
+            masp_convert_anchor_grace_blocks,
+            masp_max_note_age,
         } = self;
         let mut min_gas_prices = BTreeMap::default();
         for (token, amount) in minimum_gas_price.into_iter() {
@@ -372,6 +395,12 @@ impl ChainParams<Unvalidated> {
             fee_unshielding_gas_limit,
             fee_unshielding_descriptions_limit,
             minimum_gas_price: min_gas_prices,
+            max_epoch_hooks_gas,
+            epoch_hook_allowlist,
+            target_block_gas,
+            base_fee_max_change_rate,
+            masp_convert_anchor_grace_blocks,
+            masp_max_note_age,
         })
     }
 }
@@ -426,6 +455,16 @@ pub struct PosParams {
     pub rewards_gain_p: Dec,
     /// PoS gain d (read only)
     pub rewards_gain_d: Dec,
+    /// Whether bonding mints transferable receipt tokens of the bonds, which
+    /// have to be burned to unbond
+    pub liquid_staking_receipts: bool,
+    /// The minimum commission rate of the validators
+    pub min_commission_rate: Dec,
+    /// The metadata fields that the validators must set, besides their email
+    pub required_validator_metadata: BTreeSet<ValidatorMetaDataField>,
+    /// The max share of the total stake that a validator may receive from
+    /// bonds and redelegations, if any
+    pub max_validator_stake_share: Option<Dec>,
 }
 
 #[derive(
@@ -871,6 +910,26 @@ pub fn validate_parameters(
         );
         is_valid = false;
     }
+    let max_tx_bytes = parameters.parameters.max_tx_bytes;
+    let max_proposal_bytes = parameters.parameters.max_proposal_bytes.get();
+    if u64::from(max_tx_bytes) > max_proposal_bytes {
+        eprintln!(
+            "The max tx size of {max_tx_bytes} bytes must not exceed the max \
+             proposal size of {max_proposal_bytes} bytes."
+        );
+        is_valid = false;
+    }
+    let base_fee_max_change_rate =
+        parameters.parameters.base_fee_max_change_rate;
+    if !namada::ledger::parameters::is_valid_base_fee_max_change_rate(
+        &base_fee_max_change_rate,
+    ) {
+        eprintln!(
+            "The base fee max change rate must be between 0 and 1, got \
+             {base_fee_max_change_rate}."
+        );
+        is_valid = false;
+    }
     // check that each PGF steward has an established account
     for steward in &parameters.pgf_params.stewards {
         let mut found_steward = false;
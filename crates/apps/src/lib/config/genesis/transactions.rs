@@ -86,6 +86,9 @@ fn get_tx_args(use_device: bool) -> TxArgs {
         wrapper_fee_payer: None,
         fee_token: genesis_fee_token_address(),
         fee_unshield: None,
+        fee_unshield_headroom: None,
+        shielded_fee: false,
+        confirm_fees: false,
         gas_limit: 0.into(),
         expiration: Default::default(),
         disposable_signing_key: false,
@@ -96,6 +99,7 @@ fn get_tx_args(use_device: bool) -> TxArgs {
         password: None,
         memo: None,
         use_device,
+        data_encoding: Default::default(),
     }
 }
 
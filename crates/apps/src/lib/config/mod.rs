@@ -7,6 +7,7 @@ pub mod utils;
 
 use std::fs::{create_dir_all, File};
 use std::io::Write;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 
 use directories::ProjectDirs;
@@ -35,6 +36,26 @@ pub const FILENAME: &str = "config.toml";
 pub const COMETBFT_DIR: &str = "cometbft";
 /// Chain-specific Namada DB. Nested in chain dirs.
 pub const DB_DIR: &str = "db";
+/// Chain-specific shielded context of the shielded queries. Nested in chain
+/// dirs.
+pub const SHIELDED_QUERY_DIR: &str = "shielded-query";
+/// The default maximum number of shielded queries per minute from an IP
+/// address.
+pub const DEFAULT_SHIELDED_QUERY_RATE_LIMIT: u32 = 10;
+/// The default maximum size of the body of a request to the HTTP services of
+/// the node, in bytes.
+pub const DEFAULT_MAX_SERVICE_REQUEST_BYTES: u64 = 1024 * 1024;
+/// The default number of consecutive blocks missed by the node's validator
+/// before a webhook notification is sent.
+pub const DEFAULT_WEBHOOK_MISSED_BLOCKS_THRESHOLD: u64 = 10;
+/// The default number of retries of the delivery of a webhook notification.
+pub const DEFAULT_WEBHOOK_MAX_RETRIES: u32 = 5;
+/// The default maximum gas of the tx and VP evaluated by the VP evaluation
+/// query.
+pub const DEFAULT_EVAL_VP_MAX_GAS: u64 = 10_000_000;
+/// The default maximum number of blocks below the last committed height that
+/// the VP evaluation query can evaluate a VP at.
+pub const DEFAULT_EVAL_VP_MAX_HEIGHT_RANGE: u64 = 1000;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Config {
@@ -111,6 +132,77 @@ pub struct Shell {
     /// When set, will limit the how many block heights in the past can the
     /// storage be queried for reading values.
     pub storage_read_past_height_limit: Option<u64>,
+    /// When set, the queries whose request data is larger than this number
+    /// of bytes are rejected, e.g. the txs submitted to be dry-run or
+    /// decoded.
+    #[serde(default)]
+    pub max_query_request_bytes: Option<u64>,
+    /// When set, the queries whose response data is larger than this number
+    /// of bytes are rejected.
+    #[serde(default)]
+    pub max_query_response_bytes: Option<u64>,
+    /// When set, the node persists the history of all the storage values,
+    /// which can then be queried at any past height, regardless of the
+    /// `storage_read_past_height_limit`. This must be set before the node
+    /// processes its first block.
+    #[serde(default)]
+    pub archive_mode: bool,
+    /// When set, the node indexes the txs that changed the balances of every
+    /// address, which can then be queried page by page. Only the blocks
+    /// processed after it is set are indexed.
+    #[serde(default)]
+    pub tx_history_index: bool,
+    /// When set, the node stores the results of the applied txs for this
+    /// number of blocks, which can then be queried by the hash of the wrapper
+    /// or of the inner tx.
+    #[serde(default)]
+    pub tx_results_retention: Option<u64>,
+    /// When set, the node serves the `/health` and `/ready` HTTP endpoints on
+    /// this address.
+    #[serde(default)]
+    pub health_endpoint: Option<SocketAddr>,
+    /// When set, the node serves the Rosetta Data and Construction APIs on
+    /// this address.
+    #[serde(default)]
+    pub rosetta_endpoint: Option<SocketAddr>,
+    /// When set, the node streams the committed blocks with their decoded
+    /// txs and results on this address.
+    #[serde(default)]
+    pub block_stream_endpoint: Option<SocketAddr>,
+    /// When set, the node serves the queries of the shielded balance and
+    /// history of viewing keys on this address. The viewing keys submitted
+    /// to the node are revealed to its operator, so this should only be
+    /// enabled on self-hosted infrastructure.
+    #[serde(default)]
+    pub shielded_query_endpoint: Option<SocketAddr>,
+    /// The maximum number of shielded queries per minute from an IP address.
+    #[serde(default = "default_shielded_query_rate_limit")]
+    pub shielded_query_rate_limit: u32,
+    /// The CORS and authentication settings of the services above.
+    #[serde(default)]
+    pub services: ServicesConfig,
+    /// When set on a validator node, it broadcasts a telemetry heartbeat
+    /// reporting the version and the commit hash of its binary every this
+    /// number of blocks, which cannot be less than the protocol's minimum
+    /// heartbeat interval.
+    #[serde(default)]
+    pub telemetry_heartbeat_interval: Option<u64>,
+    /// When set, the node POSTs JSON notifications of the events of interest
+    /// to its operator to the configured webhook URLs.
+    #[serde(default)]
+    pub webhooks: Option<WebhookConfig>,
+    /// When set, the node serves the developer query evaluating a VP against
+    /// a tx applied to the state at a past height. Every query compiles the
+    /// submitted wasm code, so this should only be enabled on nodes that
+    /// don't serve the public.
+    #[serde(default)]
+    pub eval_vp: Option<EvalVpConfig>,
+    /// When set, the node serves the query of the checksum of its state at
+    /// the last committed height. Every query rebuilds the merkle sub-trees
+    /// of the storage subspaces of the internal addresses, so this should
+    /// only be enabled on nodes that don't serve the public.
+    #[serde(default)]
+    pub state_checksum_query: bool,
     /// Use the [`Ledger::db_dir()`] method to read the value.
     db_dir: PathBuf,
     /// Use the [`Ledger::cometbft_dir()`] method to read the value.
@@ -121,6 +213,146 @@ pub struct Shell {
     pub tendermint_mode: TendermintMode,
 }
 
+fn default_shielded_query_rate_limit() -> u32 {
+    DEFAULT_SHIELDED_QUERY_RATE_LIMIT
+}
+
+/// The settings shared by the HTTP services of the node, i.e. the health
+/// endpoints, the Rosetta API, the block stream and the shielded queries.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ServicesConfig {
+    /// The origins allowed to make cross-origin requests to the services, or
+    /// `*` to allow any origin. The cross-origin requests are not allowed
+    /// when empty.
+    #[serde(default)]
+    pub cors_origins: Vec<String>,
+    /// When set, the requests to the sensitive services, i.e. the shielded
+    /// queries, and to the services with `auth` set must carry this token in
+    /// an `Authorization: Bearer <token>` header.
+    #[serde(default)]
+    pub auth_token: Option<String>,
+    /// When set, the maximum number of requests per minute from an IP
+    /// address to each service.
+    #[serde(default)]
+    pub rate_limit: Option<u32>,
+    /// The maximum size of the body of a request to the services, in bytes.
+    #[serde(default = "default_max_request_bytes")]
+    pub max_request_bytes: u64,
+    #[serde(default)]
+    pub health: ServiceFlags,
+    #[serde(default)]
+    pub rosetta: ServiceFlags,
+    #[serde(default)]
+    pub block_stream: ServiceFlags,
+    #[serde(default)]
+    pub shielded_query: ServiceFlags,
+}
+
+/// The flags of a service of the node
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceFlags {
+    /// When unset, the service isn't started even if its endpoint is set.
+    #[serde(default = "default_service_enabled")]
+    pub enabled: bool,
+    /// When set, the requests to the service require the `auth_token`.
+    #[serde(default)]
+    pub auth: bool,
+}
+
+impl Default for ServiceFlags {
+    fn default() -> Self {
+        Self {
+            enabled: default_service_enabled(),
+            auth: false,
+        }
+    }
+}
+
+impl Default for ServicesConfig {
+    fn default() -> Self {
+        Self {
+            cors_origins: vec![],
+            auth_token: None,
+            rate_limit: None,
+            max_request_bytes: default_max_request_bytes(),
+            health: ServiceFlags::default(),
+            rosetta: ServiceFlags::default(),
+            block_stream: ServiceFlags::default(),
+            shielded_query: ServiceFlags::default(),
+        }
+    }
+}
+
+fn default_service_enabled() -> bool {
+    true
+}
+
+/// The webhook notifications of the node, sent when its validator is jailed
+/// or misses blocks and when a governance proposal enters its voting period
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// The URLs that the notifications are POSTed to.
+    pub urls: Vec<String>,
+    /// When set, the notifications are signed with HMAC-SHA256 using this
+    /// secret, in a `X-Namada-Signature: sha256=<hex>` header.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// The address of the validator operated by this node, to be notified
+    /// when it's jailed.
+    #[serde(default)]
+    pub validator: Option<namada::core::address::Address>,
+    /// The number of consecutive blocks that the node's validator must miss
+    /// before a notification is sent.
+    #[serde(default = "default_webhook_missed_blocks_threshold")]
+    pub missed_blocks_threshold: u64,
+    /// The number of retries of the delivery of a notification, with an
+    /// exponential backoff.
+    #[serde(default = "default_webhook_max_retries")]
+    pub max_retries: u32,
+}
+
+/// The limits of the developer query evaluating a VP
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EvalVpConfig {
+    /// The maximum gas of the tx and VP of a query, also capped by the max
+    /// block gas.
+    #[serde(default = "default_eval_vp_max_gas")]
+    pub max_gas: u64,
+    /// The maximum number of blocks below the last committed height that a
+    /// VP can be evaluated at.
+    #[serde(default = "default_eval_vp_max_height_range")]
+    pub max_height_range: u64,
+}
+
+impl Default for EvalVpConfig {
+    fn default() -> Self {
+        Self {
+            max_gas: DEFAULT_EVAL_VP_MAX_GAS,
+            max_height_range: DEFAULT_EVAL_VP_MAX_HEIGHT_RANGE,
+        }
+    }
+}
+
+fn default_eval_vp_max_gas() -> u64 {
+    DEFAULT_EVAL_VP_MAX_GAS
+}
+
+fn default_eval_vp_max_height_range() -> u64 {
+    DEFAULT_EVAL_VP_MAX_HEIGHT_RANGE
+}
+
+fn default_webhook_missed_blocks_threshold() -> u64 {
+    DEFAULT_WEBHOOK_MISSED_BLOCKS_THRESHOLD
+}
+
+fn default_webhook_max_retries() -> u32 {
+    DEFAULT_WEBHOOK_MAX_RETRIES
+}
+
+fn default_max_request_bytes() -> u64 {
+    DEFAULT_MAX_SERVICE_REQUEST_BYTES
+}
+
 impl Ledger {
     pub fn new(
         base_dir: impl AsRef<Path>,
@@ -143,6 +375,21 @@ impl Ledger {
                 tx_wasm_compilation_cache_bytes: None,
                 // Default corresponds to 1 hour of past blocks at 1 block/sec
                 storage_read_past_height_limit: Some(3600),
+                max_query_request_bytes: None,
+                max_query_response_bytes: None,
+                archive_mode: false,
+                tx_history_index: false,
+                tx_results_retention: None,
+                health_endpoint: None,
+                rosetta_endpoint: None,
+                block_stream_endpoint: None,
+                shielded_query_endpoint: None,
+                shielded_query_rate_limit: DEFAULT_SHIELDED_QUERY_RATE_LIMIT,
+                services: ServicesConfig::default(),
+                telemetry_heartbeat_interval: None,
+                webhooks: None,
+                eval_vp: None,
+                state_checksum_query: false,
                 db_dir: DB_DIR.into(),
                 cometbft_dir: COMETBFT_DIR.into(),
                 action_at_height: None,
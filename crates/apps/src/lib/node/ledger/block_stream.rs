@@ -0,0 +1,369 @@
+//! An optional service streaming the blocks committed by the ledger with
+//! their decoded txs and results, for the indexers to follow the chain
+//! without decoding the raw CometBFT blocks themselves.
+//!
+//! `GET /blocks` responds with a stream of [Server-Sent Events], one `block`
+//! event per block whose data is a JSON [`StreamedBlock`] and whose id is the
+//! height of the block. By default, the stream starts at the block following
+//! the last committed one. The `from_height` query parameter starts it at an
+//! older block instead, and a client reconnecting with the `Last-Event-ID`
+//! header resumes the stream after the last block it received.
+//!
+//! The format of the streamed blocks is versioned by [`STREAM_VERSION`]. The
+//! results of the txs are only available while they are in the event log of
+//! the node.
+//!
+//! [Server-Sent Events]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use data_encoding::HEXLOWER;
+use futures::Stream;
+use namada::events::Event;
+use namada::storage::BlockHeight;
+use namada::time::DateTimeUtc;
+use namada::tx::Tx;
+use namada_sdk::queries::{DecodedTx, TxResultInfo};
+use namada_sdk::rpc;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use warp::{sse, Filter};
+
+use crate::facade::tendermint::block::Height;
+use crate::facade::tendermint_rpc::{self, Client, HttpClient};
+use crate::node::ledger::services::Policy;
+
+/// The version of the format of the streamed blocks, incremented on every
+/// breaking change
+pub const STREAM_VERSION: u32 = 1;
+
+/// The interval between the checks for a new block
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("CometBFT RPC request failed: {0}")]
+    Rpc(tendermint_rpc::Error),
+    #[error("Ledger query failed: {0}")]
+    Query(namada_sdk::error::Error),
+    #[error("Invalid block height {0}")]
+    Height(u64),
+    #[error("Invalid block time: {0}")]
+    Time(String),
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// A block with its decoded txs and their results
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StreamedBlock {
+    /// The version of the format, [`STREAM_VERSION`]
+    pub version: u32,
+    /// The height of the block
+    pub height: u64,
+    /// The hash of the block
+    pub hash: String,
+    /// The time of the block, in RFC 3339 format
+    pub time: String,
+    /// The txs of the block, in their order in the block
+    pub txs: Vec<StreamedTx>,
+    /// The events of the block that are not emitted by a tx, if the results
+    /// of the block are available
+    pub events: Vec<StreamedEvent>,
+}
+
+/// A decoded tx and its result
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StreamedTx {
+    /// The hash of the tx header
+    pub hash: String,
+    /// The hash of the header of the inner tx
+    pub inner_hash: String,
+    /// The name of the tx code in the wasm registry
+    pub code_name: Option<String>,
+    /// The hash of the tx code
+    pub code_hash: Option<String>,
+    /// The tx data, if the tx code is known and the data could be decoded
+    pub data: Option<serde_json::Value>,
+    /// The memo of the tx, hex encoded
+    pub memo: Option<String>,
+    /// The fee payer of a wrapper tx
+    pub fee_payer: Option<String>,
+    /// The token of the fee of a wrapper tx
+    pub fee_token: Option<String>,
+    /// The fee amount per gas unit of a wrapper tx
+    pub fee_amount_per_gas_unit: Option<String>,
+    /// The gas limit of a wrapper tx
+    pub gas_limit: Option<u64>,
+    /// The result of the tx, if it is available
+    pub result: Option<StreamedTxResult>,
+}
+
+/// The result of a tx
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StreamedTxResult {
+    /// The result code, as a [`namada::tx::data::ResultCode`]
+    pub code: u32,
+    /// The gas used by the tx
+    pub gas_used: String,
+    /// Additional information on the result
+    pub info: String,
+    /// The events emitted by the tx
+    pub events: Vec<StreamedEvent>,
+}
+
+/// An event emitted in a block
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StreamedEvent {
+    /// The type of the event
+    pub kind: String,
+    /// The attributes of the event
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl From<&Event> for StreamedEvent {
+    fn from(event: &Event) -> Self {
+        Self {
+            kind: event.kind().to_string(),
+            attributes: event.attributes().clone(),
+        }
+    }
+}
+
+impl StreamedTx {
+    /// Build a streamed tx from a decoded tx and its result
+    fn new(tx: DecodedTx, result: Option<&TxResultInfo>) -> Self {
+        Self {
+            hash: tx.hash.to_string(),
+            inner_hash: tx.raw_hash.to_string(),
+            code_name: tx.code_name,
+            code_hash: tx.code_hash.map(|hash| hash.to_string()),
+            data: tx.data.and_then(|data| serde_json::from_str(&data).ok()),
+            memo: tx.memo.map(|memo| HEXLOWER.encode(&memo)),
+            fee_payer: tx
+                .wrapper
+                .as_ref()
+                .map(|wrapper| wrapper.fee_payer().to_string()),
+            fee_token: tx
+                .wrapper
+                .as_ref()
+                .map(|wrapper| wrapper.fee.token.to_string()),
+            fee_amount_per_gas_unit: tx
+                .wrapper
+                .as_ref()
+                .map(|wrapper| wrapper.fee.amount_per_gas_unit.to_string()),
+            gas_limit: tx.wrapper.map(|wrapper| wrapper.gas_limit.into()),
+            result: result.map(|result| StreamedTxResult {
+                code: result.code,
+                gas_used: result.gas_used.to_string(),
+                info: result.info.clone(),
+                events: result.events.iter().map(StreamedEvent::from).collect(),
+            }),
+        }
+    }
+}
+
+/// The query parameters of the stream
+#[derive(Debug, Default, Deserialize)]
+struct StreamQuery {
+    from_height: Option<u64>,
+}
+
+/// Serve the block stream on the given address, using the RPC of the
+/// CometBFT node at `rpc_address` and with the given policy, until a signal
+/// is sent on `abort_recv`.
+pub async fn serve(
+    listen_addr: SocketAddr,
+    rpc_address: SocketAddr,
+    policy: Policy,
+    abort_recv: tokio::sync::oneshot::Receiver<()>,
+) {
+    let client = HttpClient::new(format!("http://{rpc_address}").as_str())
+        .expect("Failed to create the CometBFT RPC client");
+    let blocks = warp::get()
+        .and(warp::path!("blocks"))
+        .and(warp::query::<StreamQuery>())
+        .and(warp::header::optional::<String>("last-event-id"))
+        .map(move |query: StreamQuery, last_event_id: Option<String>| {
+            let next_height =
+                first_height(query.from_height, last_event_id.as_deref());
+            let stream = stream_blocks(client.clone(), next_height);
+            sse::reply(sse::keep_alive().stream(stream))
+        });
+
+    tracing::info!(?listen_addr, "Starting the block stream");
+    let (_, server) = warp::serve(policy.apply(blocks))
+        .bind_with_graceful_shutdown(listen_addr, async move {
+            if abort_recv.await.is_err() {
+                tracing::error!(
+                    "The block stream abort sender has unexpectedly dropped"
+                );
+            }
+            tracing::info!("Shutting down the block stream...");
+        },
+    );
+    server.await
+}
+
+/// The height of the first block of a stream. A reconnecting client resumes
+/// after the last block it received, otherwise the stream starts at the
+/// requested height, if any.
+fn first_height(
+    from_height: Option<u64>,
+    last_event_id: Option<&str>,
+) -> Option<u64> {
+    last_event_id
+        .and_then(|id| id.parse::<u64>().ok())
+        .and_then(|height| height.checked_add(1))
+        .or(from_height)
+}
+
+/// Stream the blocks committed by the ledger, starting at the given height or
+/// after the last committed block
+fn stream_blocks(
+    client: HttpClient,
+    next_height: Option<u64>,
+) -> impl Stream<Item = std::result::Result<sse::Event, Infallible>> {
+    futures::stream::unfold(
+        (client, next_height),
+        |(client, mut next_height)| async move {
+            loop {
+                match next_block(&client, &mut next_height).await {
+                    Ok(Some(block)) => {
+                        let event = sse::Event::default()
+                            .id(block.height.to_string())
+                            .event("block")
+                            .json_data(&block);
+                        match event {
+                            Ok(event) => {
+                                return Some((
+                                    Ok(event),
+                                    (client, next_height),
+                                ));
+                            }
+                            Err(err) => {
+                                tracing::error!(
+                                    "Failed to encode the block {}: {err}",
+                                    block.height
+                                );
+                                return None;
+                            }
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(err) => {
+                        tracing::debug!("Failed to fetch the next block: {err}")
+                    }
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        },
+    )
+}
+
+/// Fetch the next block of a stream if it has been committed by the ledger,
+/// and advance the stream's height
+async fn next_block(
+    client: &HttpClient,
+    next_height: &mut Option<u64>,
+) -> Result<Option<StreamedBlock>> {
+    let last_height = client
+        .abci_info()
+        .await
+        .map_err(Error::Rpc)?
+        .last_block_height;
+    let last_height = last_height.value();
+    let height = *next_height.get_or_insert(last_height.saturating_add(1));
+    if height > last_height {
+        return Ok(None);
+    }
+    let block = fetch_block(client, height).await?;
+    *next_height = Some(height.saturating_add(1));
+    Ok(Some(block))
+}
+
+/// Fetch a block from CometBFT and decode its txs with the ledger
+async fn fetch_block(
+    client: &HttpClient,
+    height: u64,
+) -> Result<StreamedBlock> {
+    let response = client
+        .block(Height::try_from(height).map_err(|_| Error::Height(height))?)
+        .await
+        .map_err(Error::Rpc)?;
+    let time = DateTimeUtc::try_from(response.block.header.time)
+        .map_err(|err| Error::Time(err.to_string()))?;
+    // The results are missing once they have been pruned from the event log
+    let results = rpc::query_block_results(client, BlockHeight(height))
+        .await
+        .ok();
+    let mut txs = Vec::with_capacity(response.block.data.len());
+    for bytes in response.block.data {
+        // The bytes that are not a tx were rejected by the ledger
+        if Tx::try_from(bytes.as_slice()).is_err() {
+            continue;
+        }
+        let tx = rpc::query_decoded_tx(client, bytes)
+            .await
+            .map_err(Error::Query)?;
+        let result = results.as_ref().and_then(|results| {
+            results.txs.iter().find(|result| result.hash == tx.hash)
+        });
+        txs.push(StreamedTx::new(tx, result));
+    }
+    Ok(StreamedBlock {
+        version: STREAM_VERSION,
+        height,
+        hash: response.block_id.hash.to_string(),
+        time: time.to_rfc3339(),
+        txs,
+        events: results
+            .map(|results| {
+                results.events.iter().map(StreamedEvent::from).collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use namada::events::{EventLevel, EventType};
+
+    use super::*;
+
+    /// Test the height at which a stream starts
+    #[test]
+    fn test_first_height() {
+        // Start after the last committed block by default
+        assert_eq!(first_height(None, None), None);
+        assert_eq!(first_height(Some(10), None), Some(10));
+        // Resume after the last received block
+        assert_eq!(first_height(Some(10), Some("15")), Some(16));
+        assert_eq!(first_height(None, Some("15")), Some(16));
+        // Ignore an invalid event id
+        assert_eq!(first_height(Some(10), Some("block")), Some(10));
+    }
+
+    /// Test the conversion of the events
+    #[test]
+    fn test_streamed_event() {
+        let mut event =
+            Event::new(EventType::new("test/event"), EventLevel::Block);
+        event
+            .attributes_mut()
+            .insert("height".to_string(), "10".to_string());
+        let streamed = StreamedEvent::from(&event);
+        assert_eq!(streamed.kind, event.kind().to_string());
+        assert_eq!(
+            streamed.attributes,
+            BTreeMap::from([("height".to_string(), "10".to_string())])
+        );
+        assert_eq!(
+            serde_json::to_value(&streamed).unwrap()["attributes"]["height"],
+            "10"
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! HTTP endpoints reporting the health of the node, for the supervision by
+//! orchestrators (e.g. Kubernetes probes or systemd watchdogs):
+//!
+//! - `/health` responds with `200 OK` when CometBFT and the ABCI app respond
+//!   and the state of the app is in line with the blocks stored by CometBFT.
+//! - `/ready` additionally requires the node to be caught up, to have peers and
+//!   to have received a block recently.
+//!
+//! Otherwise, they respond with `503 Service Unavailable`. Both respond with a
+//! JSON [`HealthReport`].
+
+use std::net::SocketAddr;
+
+use namada::time::{DateTimeUtc, Utc};
+use serde::Serialize;
+use warp::http::StatusCode;
+use warp::Filter;
+
+use crate::facade::tendermint_rpc::{Client, HttpClient};
+use crate::node::ledger::services::Policy;
+
+/// The maximum age of the last block, in seconds, for the node to be ready
+const MAX_READY_BLOCK_AGE_SECS: i64 = 60;
+
+/// The status of the node reported by the endpoints
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct HealthReport {
+    /// Whether CometBFT is catching up with the chain
+    pub catching_up: bool,
+    /// The height of the last block stored by CometBFT
+    pub last_block_height: u64,
+    /// The time elapsed since the last block, in seconds
+    pub last_block_age_secs: i64,
+    /// The number of peers of CometBFT
+    pub peers: u64,
+    /// The height of the last block committed by the ABCI app
+    pub app_height: u64,
+    /// The errors found while checking the node
+    pub errors: Vec<String>,
+}
+
+impl HealthReport {
+    /// Check if the node is running and its storage is consistent
+    pub fn is_healthy(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Check if the node is healthy and in sync with the chain
+    pub fn is_ready(&self) -> bool {
+        self.is_healthy()
+            && !self.catching_up
+            && self.peers > 0
+            && self.last_block_age_secs <= MAX_READY_BLOCK_AGE_SECS
+    }
+}
+
+/// Serve the health endpoints on the given address, using the RPC of the
+/// CometBFT node at `rpc_address` and with the given policy, until a signal
+/// is sent on `abort_recv`.
+pub async fn serve(
+    listen_addr: SocketAddr,
+    rpc_address: SocketAddr,
+    policy: Policy,
+    abort_recv: tokio::sync::oneshot::Receiver<()>,
+) {
+    let client = HttpClient::new(format!("http://{rpc_address}").as_str())
+        .expect("Failed to create the CometBFT RPC client");
+    let health = {
+        let client = client.clone();
+        warp::get().and(warp::path!("health")).then(move || {
+            let client = client.clone();
+            async move {
+                let report = check(&client).await;
+                let healthy = report.is_healthy();
+                reply(report, healthy)
+            }
+        })
+    };
+    let ready = warp::get().and(warp::path!("ready")).then(move || {
+        let client = client.clone();
+        async move {
+            let report = check(&client).await;
+            let ready = report.is_ready();
+            reply(report, ready)
+        }
+    });
+
+    tracing::info!(?listen_addr, "Starting the health endpoints");
+    let (_, server) = warp::serve(policy.apply(health.or(ready)))
+        .bind_with_graceful_shutdown(listen_addr, async move {
+            if abort_recv.await.is_err() {
+                tracing::error!(
+                    "The health endpoints abort sender has unexpectedly \
+                     dropped"
+                );
+            }
+            tracing::info!("Shutting down the health endpoints...");
+        });
+    server.await
+}
+
+/// Reply with the report and a status code according to the check result
+fn reply(
+    report: HealthReport,
+    is_ok: bool,
+) -> warp::reply::WithStatus<warp::reply::Json> {
+    let status = if is_ok {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    warp::reply::with_status(warp::reply::json(&report), status)
+}
+
+/// Check the status of the CometBFT node and of the ABCI app
+async fn check(client: &HttpClient) -> HealthReport {
+    let mut report = HealthReport::default();
+    match client.status().await {
+        Ok(status) => {
+            report.catching_up = status.sync_info.catching_up;
+            report.last_block_height =
+                status.sync_info.latest_block_height.value();
+            match DateTimeUtc::try_from(status.sync_info.latest_block_time) {
+                Ok(time) => {
+                    #[allow(clippy::disallowed_methods)]
+                    let now = Utc::now();
+                    report.last_block_age_secs =
+                        now.signed_duration_since(time.0).num_seconds();
+                }
+                Err(err) => report
+                    .errors
+                    .push(format!("Invalid time of the last block: {err}")),
+            }
+        }
+        Err(err) => report
+            .errors
+            .push(format!("Failed to query the CometBFT status: {err}")),
+    }
+    match client.net_info().await {
+        Ok(net_info) => report.peers = net_info.n_peers,
+        Err(err) => report
+            .errors
+            .push(format!("Failed to query the CometBFT peers: {err}")),
+    }
+    match client.abci_info().await {
+        Ok(info) => {
+            report.app_height = info.last_block_height.value();
+            report.errors.extend(check_app_height(&report));
+        }
+        Err(err) => report
+            .errors
+            .push(format!("Failed to query the ABCI app: {err}")),
+    }
+    report
+}
+
+/// Check that the app has committed the blocks stored by CometBFT. CometBFT
+/// stores a block before the app commits it, so the app may be one block
+/// behind.
+fn check_app_height(report: &HealthReport) -> Option<String> {
+    (report.last_block_height > report.app_height.saturating_add(1)).then(
+        || {
+            format!(
+                "The app state at height {} is behind the last block at \
+                 height {}",
+                report.app_height, report.last_block_height
+            )
+        },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Test the conditions of the health and readiness of the node
+    #[test]
+    fn test_health_report() {
+        let report = HealthReport {
+            catching_up: false,
+            last_block_height: 10,
+            last_block_age_secs: 5,
+            peers: 3,
+            app_height: 9,
+            errors: vec![],
+        };
+        assert!(check_app_height(&report).is_none());
+        assert!(report.is_healthy());
+        assert!(report.is_ready());
+
+        for not_ready in [
+            HealthReport {
+                catching_up: true,
+                ..report.clone()
+            },
+            HealthReport {
+                peers: 0,
+                ..report.clone()
+            },
+            HealthReport {
+                last_block_age_secs: MAX_READY_BLOCK_AGE_SECS + 1,
+                ..report.clone()
+            },
+        ] {
+            assert!(not_ready.is_healthy());
+            assert!(!not_ready.is_ready());
+        }
+
+        let behind = HealthReport {
+            app_height: 8,
+            ..report
+        };
+        let error = check_app_height(&behind).unwrap();
+        let unhealthy = HealthReport {
+            errors: vec![error],
+            ..behind
+        };
+        assert!(!unhealthy.is_healthy());
+        assert!(!unhealthy.is_ready());
+    }
+}
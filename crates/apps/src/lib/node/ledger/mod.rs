@@ -1,10 +1,19 @@
 mod abortable;
+mod block_stream;
 mod broadcaster;
 pub mod ethereum_oracle;
+mod health;
+mod relayer;
+mod rosetta;
+mod services;
 pub mod shell;
+mod shielded_query;
 pub mod shims;
+pub mod state_diff;
 pub mod storage;
 pub mod tendermint_node;
+mod verify;
+mod webhooks;
 
 use std::convert::TryInto;
 use std::net::SocketAddr;
@@ -96,9 +105,9 @@ impl Shell {
             Request::ProcessProposal(block) => {
                 tracing::debug!("Request ProcessProposal");
                 // TODO: use TM domain type in the handler
-                let (response, _tx_results) =
-                    self.process_proposal(block.into());
-                Ok(Response::ProcessProposal(response))
+                Ok(Response::ProcessProposal(
+                    self.process_and_cache_proposal(block.into()),
+                ))
             }
             Request::RevertProposal(_req) => {
                 Ok(Response::RevertProposal(self.revert_proposal(_req)))
@@ -189,6 +198,32 @@ pub fn reset(config: config::Ledger) -> Result<(), shell::Error> {
     shell::reset(config)
 }
 
+/// Rebuild the CometBFT indexes of the txs and block events from the stored
+/// blocks
+pub fn reindex(
+    config: config::Ledger,
+    args::LedgerReindex {
+        from_height,
+        to_height,
+    }: args::LedgerReindex,
+) -> Result<(), shell::Error> {
+    shell::reindex(config, from_height, to_height)
+}
+
+/// Replay the blocks from genesis or a snapshot of the DB and compare the
+/// resulting app hashes with the ones agreed upon by the network
+pub fn verify(
+    config: config::Ledger,
+    wasm_dir: PathBuf,
+    args: args::LedgerVerify,
+) -> Result<(), verify::Error> {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(verify::verify(config, wasm_dir, args))
+}
+
 /// Dump Namada ledger node's DB from a block into a file
 pub fn dump_db(
     config: config::Ledger,
@@ -205,6 +240,37 @@ pub fn dump_db(
     db.dump_block(out_file_path, historic, block_height);
 }
 
+/// Dump the keys written at a block height with the hashes of their values
+/// into a file
+pub fn dump_writes(
+    config: config::Ledger,
+    args::LedgerDumpWrites {
+        block_height,
+        out_file_path,
+    }: args::LedgerDumpWrites,
+) -> Result<(), state_diff::Error> {
+    let chain_id = config.chain_id;
+    let db_path = config.shell.db_dir(&chain_id);
+
+    let db = storage::PersistentDB::open(db_path, None);
+    let (height, writes) = db.read_writes(block_height);
+    let dump = state_diff::hash_writes(writes);
+    let full_path = state_diff::write_dump(&out_file_path, height, &dump)?;
+    println!(
+        "Dumped {} keys written at height {height} to {}",
+        dump.len(),
+        full_path.to_string_lossy()
+    );
+    Ok(())
+}
+
+/// Compare the dumps of the keys written at a block height by two nodes
+pub fn diff_writes(
+    args::LedgerDiffWrites { path, other_path }: args::LedgerDiffWrites,
+) -> Result<(), state_diff::Error> {
+    state_diff::diff_dumps(&path, &other_path)
+}
+
 #[cfg(feature = "migrations")]
 pub fn query_db(
     config: config::Ledger,
@@ -276,6 +342,23 @@ pub fn update_db_keys(config: config::Ledger, updates: PathBuf, dry_run: bool) {
             }
         }
     }
+    // Record the protocol version of a chain that predates its recording
+    let protocol_version_key =
+        namada::ledger::parameters::storage::get_protocol_version_key();
+    if db_visitor
+        .read(&protocol_version_key, &DbColFam::SUBSPACE)
+        .is_none()
+    {
+        use borsh_ext::BorshSerializeExt;
+
+        let version = namada::ledger::parameters::INITIAL_PROTOCOL_VERSION;
+        db_visitor.write(
+            &protocol_version_key,
+            &DbColFam::SUBSPACE,
+            version.serialize_to_vec(),
+        );
+        tracing::info!("Recording the protocol version {version} of the chain");
+    }
     if !dry_run {
         tracing::info!("Persisting DB changes...");
         let batch = db_visitor.take_batch();
@@ -294,6 +377,12 @@ pub fn rollback(config: config::Ledger) -> Result<(), shell::Error> {
     shell::rollback(config)
 }
 
+/// Revert the last block if it was not written completely, e.g. because of a
+/// crash, so that CometBFT replays it
+pub fn repair_db(config: config::Ledger) -> Result<(), shell::Error> {
+    shell::repair_db(config)
+}
+
 /// Runs and monitors a few concurrent tasks.
 ///
 /// This includes:
@@ -309,6 +398,9 @@ pub fn rollback(config: config::Ledger) -> Result<(), shell::Error> {
 async fn run_aux(config: config::Ledger, wasm_dir: PathBuf) {
     let setup_data = run_aux_setup(&config, &wasm_dir).await;
 
+    // Check the integrity of the DB before the shell opens it
+    check_db(&config);
+
     // Create an `AbortableSpawner` for signalling shut down from the shell or
     // from Tendermint
     let mut spawner = AbortableSpawner::new();
@@ -316,6 +408,24 @@ async fn run_aux(config: config::Ledger, wasm_dir: PathBuf) {
     // Start Tendermint node
     let tendermint_node = start_tendermint(&mut spawner, &config);
 
+    // Start the health endpoints if enabled
+    let health = start_health_endpoints(&mut spawner, &config);
+
+    // Start the Rosetta API if enabled
+    let rosetta = start_rosetta_api(&mut spawner, &config);
+
+    // Start the block stream if enabled
+    let block_stream = start_block_stream(&mut spawner, &config);
+
+    // Start the shielded queries if enabled
+    let shielded_query = start_shielded_query(&mut spawner, &config);
+
+    // Start the Bridge pool relayer if enabled
+    let relayer = start_bridge_pool_relayer(&mut spawner, &config);
+
+    // Start the webhook notifications if enabled
+    let webhooks = start_webhooks(&mut spawner, &config);
+
     // Start oracle if necessary
     let (eth_oracle_channels, eth_oracle) =
         match maybe_start_ethereum_oracle(&mut spawner, &config).await {
@@ -343,10 +453,21 @@ async fn run_aux(config: config::Ledger, wasm_dir: PathBuf) {
     let aborted = spawner.wait_for_abort().await.child_terminated();
 
     // Wait for all managed tasks to finish.
-    let res = tokio::try_join!(tendermint_node, abci, eth_oracle, broadcaster);
+    let res = tokio::try_join!(
+        tendermint_node,
+        abci,
+        eth_oracle,
+        broadcaster,
+        health,
+        rosetta,
+        block_stream,
+        shielded_query,
+        relayer,
+        webhooks
+    );
 
     match res {
-        Ok((tendermint_res, abci_res, _, _)) => {
+        Ok((tendermint_res, abci_res, _, _, _, _, _, _, _, _)) => {
             // we ignore errors on user-initiated shutdown
             if aborted {
                 if let Err(err) = tendermint_res {
@@ -374,6 +495,25 @@ async fn run_aux(config: config::Ledger, wasm_dir: PathBuf) {
     }
 }
 
+/// Refuse to start if the last block was not written completely, e.g. because
+/// of a crash. The operator must revert it with the `repair-db` command.
+fn check_db(config: &config::Ledger) {
+    let db_path = config.shell.db_dir(&config.chain_id);
+    if !db_path.exists() {
+        return;
+    }
+    let db = storage::PersistentDB::open(db_path, None);
+    match db.incomplete_last_block() {
+        Ok(Some(height)) => panic!(
+            "The last block at height {height} was not written completely. \
+             Run `namada-node ledger repair-db` to revert it, such that it \
+             gets replayed by CometBFT."
+        ),
+        Ok(None) => tracing::debug!("The last block is complete"),
+        Err(err) => panic!("Failed to check the DB: {err}"),
+    }
+}
+
 /// A [`RunAuxSetup`] stores some variables used to start child
 /// processes of the ledger.
 struct RunAuxSetup {
@@ -843,6 +983,213 @@ pub fn test_genesis_files(
     initializer.report();
 }
 
+/// Spawn the health endpoints, if an address is configured for them and they
+/// are enabled.
+fn start_health_endpoints(
+    spawner: &mut AbortableSpawner,
+    config: &config::Ledger,
+) -> task::JoinHandle<()> {
+    let service_config = &config.shell.services;
+    let Some(listen_addr) = config
+        .shell
+        .health_endpoint
+        .filter(|_| service_config.health.enabled)
+    else {
+        return spawn_dummy_task(());
+    };
+    let policy =
+        services::Policy::new(service_config, &service_config.health, false);
+    let rpc_address =
+        convert_tm_addr_to_socket_addr(&config.cometbft.rpc.laddr);
+    let (abort_send, abort_recv) = tokio::sync::oneshot::channel::<()>();
+    spawner
+        .spawn_abortable("Health endpoints", move |aborter| async move {
+            health::serve(listen_addr, rpc_address, policy, abort_recv).await;
+            tracing::info!("Health endpoints are no longer running.");
+
+            drop(aborter);
+        })
+        .with_cleanup(async move {
+            let _ = abort_send.send(());
+        })
+}
+
+/// Spawn the Rosetta API, if an address is configured for it and it is
+/// enabled.
+fn start_rosetta_api(
+    spawner: &mut AbortableSpawner,
+    config: &config::Ledger,
+) -> task::JoinHandle<()> {
+    let service_config = &config.shell.services;
+    let Some(listen_addr) = config
+        .shell
+        .rosetta_endpoint
+        .filter(|_| service_config.rosetta.enabled)
+    else {
+        return spawn_dummy_task(());
+    };
+    let policy =
+        services::Policy::new(service_config, &service_config.rosetta, false);
+    let rpc_address =
+        convert_tm_addr_to_socket_addr(&config.cometbft.rpc.laddr);
+    let chain_id = config.chain_id.clone();
+    let archive_mode = config.shell.archive_mode;
+    let (abort_send, abort_recv) = tokio::sync::oneshot::channel::<()>();
+    spawner
+        .spawn_abortable("Rosetta API", move |aborter| async move {
+            rosetta::serve(
+                listen_addr,
+                rpc_address,
+                chain_id,
+                archive_mode,
+                policy,
+                abort_recv,
+            )
+            .await;
+            tracing::info!("Rosetta API is no longer running.");
+
+            drop(aborter);
+        })
+        .with_cleanup(async move {
+            let _ = abort_send.send(());
+        })
+}
+
+/// Spawn the block stream, if an address is configured for it and it is
+/// enabled.
+fn start_block_stream(
+    spawner: &mut AbortableSpawner,
+    config: &config::Ledger,
+) -> task::JoinHandle<()> {
+    let service_config = &config.shell.services;
+    let Some(listen_addr) = config
+        .shell
+        .block_stream_endpoint
+        .filter(|_| service_config.block_stream.enabled)
+    else {
+        return spawn_dummy_task(());
+    };
+    let policy = services::Policy::new(
+        service_config,
+        &service_config.block_stream,
+        false,
+    );
+    let rpc_address =
+        convert_tm_addr_to_socket_addr(&config.cometbft.rpc.laddr);
+    let (abort_send, abort_recv) = tokio::sync::oneshot::channel::<()>();
+    spawner
+        .spawn_abortable("Block stream", move |aborter| async move {
+            block_stream::serve(listen_addr, rpc_address, policy, abort_recv)
+                .await;
+            tracing::info!("Block stream is no longer running.");
+
+            drop(aborter);
+        })
+        .with_cleanup(async move {
+            let _ = abort_send.send(());
+        })
+}
+
+/// Spawn the shielded queries, if an address is configured for them and they
+/// are enabled. They always require the auth token, if one is configured.
+fn start_shielded_query(
+    spawner: &mut AbortableSpawner,
+    config: &config::Ledger,
+) -> task::JoinHandle<()> {
+    let service_config = &config.shell.services;
+    let Some(listen_addr) = config
+        .shell
+        .shielded_query_endpoint
+        .filter(|_| service_config.shielded_query.enabled)
+    else {
+        return spawn_dummy_task(());
+    };
+    let policy = services::Policy::new(
+        service_config,
+        &service_config.shielded_query,
+        true,
+    );
+    let rpc_address =
+        convert_tm_addr_to_socket_addr(&config.cometbft.rpc.laddr);
+    let context_dir = config.chain_dir().join(config::SHIELDED_QUERY_DIR);
+    let rate_limit = config.shell.shielded_query_rate_limit;
+    let (abort_send, abort_recv) = tokio::sync::oneshot::channel::<()>();
+    spawner
+        .spawn_abortable("Shielded queries", move |aborter| async move {
+            shielded_query::serve(
+                listen_addr,
+                rpc_address,
+                context_dir,
+                rate_limit,
+                policy,
+                abort_recv,
+            )
+            .await;
+            tracing::info!("Shielded queries are no longer running.");
+
+            drop(aborter);
+        })
+        .with_cleanup(async move {
+            let _ = abort_send.send(());
+        })
+}
+
+/// Spawn the Bridge pool relayer, if it is configured.
+fn start_bridge_pool_relayer(
+    spawner: &mut AbortableSpawner,
+    config: &config::Ledger,
+) -> task::JoinHandle<()> {
+    let Some(relayer_config) = config.ethereum_bridge.relayer.clone() else {
+        return spawn_dummy_task(());
+    };
+    let eth_rpc_endpoint = relayer_config
+        .eth_rpc_endpoint
+        .clone()
+        .unwrap_or_else(|| config.ethereum_bridge.oracle_rpc_endpoint.clone());
+    let rpc_address =
+        convert_tm_addr_to_socket_addr(&config.cometbft.rpc.laddr);
+    let (abort_send, abort_recv) = tokio::sync::oneshot::channel::<()>();
+    spawner
+        .spawn_abortable("Bridge pool relayer", move |aborter| async move {
+            relayer::run(
+                relayer_config,
+                eth_rpc_endpoint,
+                rpc_address,
+                abort_recv,
+            )
+            .await;
+            tracing::info!("Bridge pool relayer is no longer running.");
+
+            drop(aborter);
+        })
+        .with_cleanup(async move {
+            let _ = abort_send.send(());
+        })
+}
+
+/// Spawn the webhook notifications, if they are configured.
+fn start_webhooks(
+    spawner: &mut AbortableSpawner,
+    config: &config::Ledger,
+) -> task::JoinHandle<()> {
+    let Some(webhook_config) = config.shell.webhooks.clone() else {
+        return spawn_dummy_task(());
+    };
+    let rpc_address =
+        convert_tm_addr_to_socket_addr(&config.cometbft.rpc.laddr);
+    let (abort_send, abort_recv) = tokio::sync::oneshot::channel::<()>();
+    spawner
+        .spawn_abortable("Webhooks", move |aborter| async move {
+            webhooks::run(webhook_config, rpc_address, abort_recv).await;
+            tracing::info!("Webhook notifications are no longer running.");
+
+            drop(aborter);
+        })
+        .with_cleanup(async move {
+            let _ = abort_send.send(());
+        })
+}
+
 /// Spawn a dummy asynchronous task into the runtime,
 /// which will resolve instantly.
 fn spawn_dummy_task<T: Send + 'static>(ready: T) -> task::JoinHandle<T> {
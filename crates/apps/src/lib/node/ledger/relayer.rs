@@ -0,0 +1,447 @@
+//! A relayer of the Bridge pool built into the node.
+//!
+//! The relayer periodically looks for the most economical batch of the signed
+//! Bridge pool transfers, according to the configured conversion table of the
+//! gas fee tokens, and relays its proof to the Ethereum bridge contract with
+//! the configured Ethereum key. A batch is only relayed once per Bridge pool
+//! nonce. In dry-run mode, the batches are only logged.
+//!
+//! The metrics of the relayer can be served in the Prometheus text format.
+
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use data_encoding::HEXLOWER_PERMISSIVE;
+use namada::core::address::Address;
+use namada::core::collections::HashMap;
+use namada::core::ethereum_events::Uint;
+use namada::eth_bridge::ethers::core::k256::elliptic_curve::SecretKey as Secp256k1Sk;
+use namada::eth_bridge::ethers::middleware::SignerMiddleware;
+use namada::eth_bridge::ethers::providers::{Http, Middleware, Provider};
+use namada::eth_bridge::ethers::signers::{LocalWallet, Signer, Wallet};
+use namada_sdk::args::{
+    self, BpConversionTableEntry, RecommendBatch, RelayBridgePoolProof,
+};
+use namada_sdk::eth_bridge::bridge_pool::{
+    find_recommended_batch, relay_bridge_pool_proof, RecommendedBatch,
+};
+use namada_sdk::eth_bridge::eth_syncing_status;
+use namada_sdk::eth_bridge::storage::bridge_pool::get_nonce_key;
+use namada_sdk::io::NullIo;
+use namada_sdk::rpc;
+use tokio::sync::oneshot;
+use warp::Filter;
+
+use crate::config::ethereum_bridge::ledger::RelayerConfig;
+use crate::facade::tendermint_rpc::HttpClient;
+
+/// The Ethereum client signing the relay txs
+type EthClient = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+/// The metrics of the relayer
+#[derive(Debug, Default)]
+pub struct RelayerMetrics {
+    /// The number of checks of the Bridge pool
+    pub checks: AtomicU64,
+    /// The number of batches worth relaying that were found
+    pub batches_found: AtomicU64,
+    /// The number of batches only logged in dry-run mode
+    pub dry_run_batches: AtomicU64,
+    /// The number of relay txs submitted successfully
+    pub relays_submitted: AtomicU64,
+    /// The number of failed relays
+    pub relays_failed: AtomicU64,
+    /// The Bridge pool nonce of the last relayed batch
+    pub last_relayed_nonce: AtomicU64,
+}
+
+impl RelayerMetrics {
+    /// Render the metrics in the Prometheus text format
+    pub fn render(&self) -> String {
+        let metrics = [
+            (
+                "checks_total",
+                "counter",
+                "Number of checks of the Bridge pool",
+                &self.checks,
+            ),
+            (
+                "batches_found_total",
+                "counter",
+                "Number of batches worth relaying that were found",
+                &self.batches_found,
+            ),
+            (
+                "dry_run_batches_total",
+                "counter",
+                "Number of batches only logged in dry-run mode",
+                &self.dry_run_batches,
+            ),
+            (
+                "relays_submitted_total",
+                "counter",
+                "Number of relay txs submitted successfully",
+                &self.relays_submitted,
+            ),
+            (
+                "relays_failed_total",
+                "counter",
+                "Number of failed relays",
+                &self.relays_failed,
+            ),
+            (
+                "last_relayed_nonce",
+                "gauge",
+                "Bridge pool nonce of the last relayed batch",
+                &self.last_relayed_nonce,
+            ),
+        ];
+        metrics
+            .into_iter()
+            .map(|(name, kind, help, value)| {
+                let value = value.load(Ordering::Relaxed);
+                format!(
+                    "# HELP namada_bridge_pool_relayer_{name} {help}\n# TYPE \
+                     namada_bridge_pool_relayer_{name} \
+                     {kind}\nnamada_bridge_pool_relayer_{name} {value}\n"
+                )
+            })
+            .collect()
+    }
+}
+
+/// Run the relayer until a signal is sent on `abort_recv`. The node's ledger
+/// is queried via the CometBFT RPC at `rpc_address`.
+pub async fn run(
+    config: RelayerConfig,
+    eth_rpc_endpoint: String,
+    rpc_address: SocketAddr,
+    mut abort_recv: oneshot::Receiver<()>,
+) {
+    let conversion_table =
+        match load_conversion_table(&config.conversion_table_path) {
+            Ok(table) => table,
+            Err(err) => {
+                tracing::error!(
+                    "Failed to load the Bridge pool relayer's conversion \
+                     table: {err}"
+                );
+                return;
+            }
+        };
+    let url = format!("http://{rpc_address}");
+    let client = HttpClient::new(url.as_str())
+        .expect("Failed to create the CometBFT RPC client");
+    let ledger_address = url
+        .parse()
+        .expect("The CometBFT RPC address should be a valid URL");
+
+    let metrics = Arc::new(RelayerMetrics::default());
+    let (metrics_abort_send, metrics_abort_recv) = oneshot::channel::<()>();
+    let metrics_server = config.metrics_endpoint.map(|listen_addr| {
+        tokio::spawn(serve_metrics(
+            listen_addr,
+            metrics.clone(),
+            metrics_abort_recv,
+        ))
+    });
+
+    tracing::info!(
+        dry_run = config.dry_run,
+        "Starting the Bridge pool relayer"
+    );
+    let mut eth_client: Option<Arc<EthClient>> = None;
+    let mut last_relayed_nonce: Option<Uint> = None;
+    let mut interval =
+        tokio::time::interval(Duration::from_secs(config.poll_interval_secs));
+    loop {
+        tokio::select! {
+            _ = &mut abort_recv => break,
+            _ = interval.tick() => {}
+        }
+        metrics.checks.fetch_add(1, Ordering::Relaxed);
+
+        let batch = match find_recommended_batch(
+            &client,
+            &NullIo,
+            &RecommendBatch {
+                query: args::Query {
+                    ledger_address: ledger_address.clone(),
+                },
+                max_gas: config.max_gas,
+                gas: config.max_net_cost,
+                conversion_table: conversion_table.clone(),
+            },
+        )
+        .await
+        {
+            Ok(Some(batch)) => batch,
+            Ok(None) => {
+                tracing::debug!("No Bridge pool batch worth relaying");
+                continue;
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "Failed to look for a Bridge pool batch to relay: {err}"
+                );
+                continue;
+            }
+        };
+        let nonce = match rpc::query_storage_value::<_, Uint>(
+            &client,
+            &get_nonce_key(),
+        )
+        .await
+        {
+            Ok(nonce) => nonce,
+            Err(err) => {
+                tracing::warn!("Failed to query the Bridge pool nonce: {err}");
+                continue;
+            }
+        };
+        if last_relayed_nonce == Some(nonce) {
+            tracing::debug!(
+                %nonce,
+                "The Bridge pool batch with this nonce was already relayed"
+            );
+            continue;
+        }
+        metrics.batches_found.fetch_add(1, Ordering::Relaxed);
+
+        if config.dry_run {
+            metrics.dry_run_batches.fetch_add(1, Ordering::Relaxed);
+            log_batch("Dry-run: would relay a Bridge pool batch", &batch);
+            last_relayed_nonce = Some(nonce);
+            continue;
+        }
+
+        let eth_client = match &eth_client {
+            Some(eth_client) => eth_client.clone(),
+            None => match load_eth_client(&config, &eth_rpc_endpoint).await {
+                Ok(client) => eth_client.insert(client).clone(),
+                Err(err) => {
+                    tracing::error!(
+                        "Failed to set up the Bridge pool relayer's Ethereum \
+                         client: {err}"
+                    );
+                    metrics.relays_failed.fetch_add(1, Ordering::Relaxed);
+                    continue;
+                }
+            },
+        };
+        match eth_syncing_status(&*eth_client).await {
+            Ok(status) if status.is_synchronized() => {}
+            Ok(_) => {
+                tracing::info!(
+                    "The Ethereum node is not synchronized, postponing the \
+                     relay of a Bridge pool batch"
+                );
+                continue;
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "Failed to query the syncing status of the Ethereum node: \
+                     {err}"
+                );
+                continue;
+            }
+        }
+
+        let transfers = match batch
+            .transfer_hashes
+            .iter()
+            .map(|hash| hash.parse())
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(transfers) => transfers,
+            Err(err) => {
+                tracing::error!(
+                    "Invalid hash of a recommended transfer: {err}"
+                );
+                metrics.relays_failed.fetch_add(1, Ordering::Relaxed);
+                continue;
+            }
+        };
+        log_batch("Relaying a Bridge pool batch", &batch);
+        let relay = relay_bridge_pool_proof(
+            eth_client,
+            &client,
+            &NullIo,
+            RelayBridgePoolProof {
+                ledger_address: ledger_address.clone(),
+                transfers,
+                relayer: config.relayer_address.clone(),
+                confirmations: config.confirmations,
+                eth_rpc_endpoint: (),
+                gas: config.max_gas,
+                gas_price: config.gas_price,
+                eth_addr: None,
+                sync: false,
+                safe_mode: false,
+            },
+        )
+        .await;
+        match relay {
+            Ok(()) => {
+                tracing::info!(%nonce, "Relayed a Bridge pool batch");
+                metrics.relays_submitted.fetch_add(1, Ordering::Relaxed);
+                // The nonce is reported by its lowest 64 bits
+                let [nonce_low, ..] = nonce.0;
+                metrics
+                    .last_relayed_nonce
+                    .store(nonce_low, Ordering::Relaxed);
+                last_relayed_nonce = Some(nonce);
+            }
+            Err(err) => {
+                tracing::error!(
+                    %nonce,
+                    "Failed to relay a Bridge pool batch: {err}"
+                );
+                metrics.relays_failed.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    tracing::info!("Shutting down the Bridge pool relayer...");
+    let _ = metrics_abort_send.send(());
+    if let Some(server) = metrics_server {
+        let _ = server.await;
+    }
+}
+
+/// Log the details of a recommended batch
+fn log_batch(msg: &str, batch: &RecommendedBatch) {
+    tracing::info!(
+        transfers = ?batch.transfer_hashes,
+        ethereum_gas_fees = %batch.ethereum_gas_fees,
+        net_profit = %batch.net_profit,
+        bridge_pool_gas_fees = ?batch.bridge_pool_gas_fees,
+        "{msg}"
+    );
+}
+
+/// Serve the metrics under `/metrics` until a signal is sent on `abort_recv`
+async fn serve_metrics(
+    listen_addr: SocketAddr,
+    metrics: Arc<RelayerMetrics>,
+    abort_recv: oneshot::Receiver<()>,
+) {
+    let route = warp::get()
+        .and(warp::path!("metrics"))
+        .map(move || metrics.render());
+    tracing::info!(?listen_addr, "Serving the Bridge pool relayer's metrics");
+    let (_, server) = warp::serve(route).bind_with_graceful_shutdown(
+        listen_addr,
+        async move {
+            let _ = abort_recv.await;
+        },
+    );
+    server.await
+}
+
+/// Load the conversion table of the gas fee tokens from a JSON object mapping
+/// the addresses of the tokens to their conversion rates in gwei
+fn load_conversion_table(
+    path: &Path,
+) -> Result<HashMap<Address, BpConversionTableEntry>, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+    parse_conversion_table(&json)
+}
+
+/// Parse the conversion table of the gas fee tokens
+fn parse_conversion_table(
+    json: &str,
+) -> Result<HashMap<Address, BpConversionTableEntry>, String> {
+    let table: HashMap<String, f64> = serde_json::from_str(json)
+        .map_err(|err| format!("Invalid conversion table: {err}"))?;
+    table
+        .into_iter()
+        .map(|(alias, conversion_rate)| {
+            let address = Address::decode(&alias).map_err(|err| {
+                format!("Invalid token address {alias}: {err}")
+            })?;
+            if conversion_rate <= 0.0 {
+                return Err(format!(
+                    "The conversion rate of {alias} must be positive"
+                ));
+            }
+            Ok((
+                address,
+                BpConversionTableEntry {
+                    alias,
+                    conversion_rate,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Set up the Ethereum client signing the relay txs with the configured key
+async fn load_eth_client(
+    config: &RelayerConfig,
+    eth_rpc_endpoint: &str,
+) -> Result<Arc<EthClient>, String> {
+    let key_path = config
+        .eth_key_path
+        .as_ref()
+        .ok_or("No Ethereum key is configured")?;
+    let key = std::fs::read_to_string(key_path).map_err(|err| {
+        format!("Failed to read {}: {err}", key_path.display())
+    })?;
+    let key = HEXLOWER_PERMISSIVE
+        .decode(key.trim().as_bytes())
+        .map_err(|err| format!("Invalid hex of the Ethereum key: {err}"))?;
+    let key = Secp256k1Sk::from_slice(&key)
+        .map_err(|err| format!("Invalid Ethereum key: {err}"))?;
+    let provider = Provider::<Http>::try_from(eth_rpc_endpoint)
+        .map_err(|err| format!("Invalid Ethereum RPC endpoint: {err}"))?;
+    let chain_id = provider
+        .get_chainid()
+        .await
+        .map_err(|err| format!("Failed to query the Ethereum chain id: {err}"))?
+        .as_u64();
+    let wallet: Wallet<_> = key.into();
+    let wallet = wallet.with_chain_id(chain_id);
+    Ok(Arc::new(SignerMiddleware::new(provider, wallet)))
+}
+
+#[cfg(test)]
+mod test {
+    use namada::core::address::testing::nam;
+
+    use super::*;
+
+    /// Test parsing the conversion table of the gas fee tokens
+    #[test]
+    fn test_parse_conversion_table() {
+        let nam = nam();
+        let table =
+            parse_conversion_table(&format!(r#"{{"{nam}": 0.5}}"#)).unwrap();
+        let entry = table.get(&nam).unwrap();
+        assert_eq!(entry.alias, nam.to_string());
+        assert_eq!(entry.conversion_rate, 0.5);
+
+        assert!(parse_conversion_table(r#"{"not-an-address": 1.0}"#).is_err());
+        assert!(parse_conversion_table(&format!(r#"{{"{nam}": 0}}"#)).is_err());
+    }
+
+    /// Test rendering the metrics in the Prometheus text format
+    #[test]
+    fn test_render_metrics() {
+        let metrics = RelayerMetrics::default();
+        metrics.relays_submitted.fetch_add(2, Ordering::Relaxed);
+        metrics.last_relayed_nonce.store(7, Ordering::Relaxed);
+        let rendered = metrics.render();
+        assert!(rendered
+            .contains("namada_bridge_pool_relayer_relays_submitted_total 2\n"));
+        assert!(rendered.contains(
+            "# TYPE namada_bridge_pool_relayer_last_relayed_nonce gauge\n"
+        ));
+        assert!(rendered
+            .contains("namada_bridge_pool_relayer_last_relayed_nonce 7\n"));
+    }
+}
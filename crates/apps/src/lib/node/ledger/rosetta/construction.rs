@@ -0,0 +1,536 @@
+//! The handlers of the Construction API
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use borsh::BorshDeserialize;
+use data_encoding::{HEXLOWER, HEXLOWER_PERMISSIVE};
+use namada::address::Address;
+use namada::chain::ChainId;
+use namada::hash::Hash;
+use namada::key::{common, ed25519, SigScheme};
+use namada::storage::Key;
+use namada::token::{self, DenominatedAmount, NATIVE_MAX_DECIMAL_PLACES};
+use namada::tx::data::{Fee, GasLimit, TxType};
+use namada::tx::{Authorization, Section, Signer, Tx};
+use namada_sdk::tx::TX_TRANSFER_WASM;
+use namada_sdk::{rpc, DEFAULT_GAS_LIMIT};
+use serde::{Deserialize, Serialize};
+
+use super::types::{
+    ConstructionCombineRequest, ConstructionCombineResponse,
+    ConstructionDeriveRequest, ConstructionDeriveResponse,
+    ConstructionMetadataRequest, ConstructionMetadataResponse,
+    ConstructionParseRequest, ConstructionParseResponse,
+    ConstructionPayloadsRequest, ConstructionPayloadsResponse,
+    ConstructionPreprocessRequest, ConstructionPreprocessResponse, Operation,
+    PublicKey, SignedTransactionRequest, SigningPayload, TransactionIdentifier,
+    TransactionIdentifierResponse,
+};
+use super::{
+    account, code_tag, native_amount, parse_account, parse_native_amount,
+    ApiError, Context, ErrorKind, Operations, Result, OP_TRANSFER,
+};
+use crate::facade::tendermint_rpc::Client;
+
+/// The curve of the supported public keys
+const CURVE_EDWARDS25519: &str = "edwards25519";
+/// The type of the supported signatures
+const SIGNATURE_ED25519: &str = "ed25519";
+
+/// The options passed from `/construction/preprocess` to
+/// `/construction/metadata`
+#[derive(Debug, Serialize, Deserialize)]
+struct Options {
+    source: Address,
+}
+
+/// The on-chain parameters needed to build a transfer
+#[derive(Debug, Serialize, Deserialize)]
+struct TransferMetadata {
+    chain_id: ChainId,
+    /// The address of the native token
+    token: Address,
+    /// The hex encoded hash of the transfer tx code
+    code_hash: String,
+    /// The fee per gas unit, in the smallest unit of the native token
+    gas_price: String,
+    gas_limit: u64,
+}
+
+/// A transfer of the native token described by Rosetta operations
+#[derive(Debug, PartialEq, Eq)]
+struct TransferIntent {
+    source: Address,
+    target: Address,
+    amount: token::Amount,
+}
+
+pub(super) async fn derive(
+    ctx: Context,
+    request: ConstructionDeriveRequest,
+) -> Result<ConstructionDeriveResponse> {
+    ctx.check_network(&request.network_identifier)?;
+    let public_key = parse_public_key(&request.public_key)?;
+    Ok(ConstructionDeriveResponse {
+        account_identifier: account(&Address::from(&public_key)),
+    })
+}
+
+pub(super) async fn preprocess(
+    ctx: Context,
+    request: ConstructionPreprocessRequest,
+) -> Result<ConstructionPreprocessResponse> {
+    ctx.check_network(&request.network_identifier)?;
+    let intent = parse_transfer(&request.operations)?;
+    let options = Options {
+        source: intent.source,
+    };
+    Ok(ConstructionPreprocessResponse {
+        required_public_keys: vec![account(&options.source)],
+        options: serde_json::to_value(options)
+            .map_err(ApiError::invalid_request)?,
+    })
+}
+
+pub(super) async fn metadata(
+    ctx: Context,
+    request: ConstructionMetadataRequest,
+) -> Result<ConstructionMetadataResponse> {
+    ctx.check_network(&request.network_identifier)?;
+    let options: Options = serde_json::from_value(request.options)
+        .map_err(ApiError::invalid_request)?;
+    if !rpc::is_public_key_revealed(&ctx.client, &options.source)
+        .await
+        .map_err(ApiError::node)?
+    {
+        return Err(ApiError::new(
+            ErrorKind::UnsupportedOperations,
+            format!(
+                "The public key of {} must be revealed before it can transfer \
+                 tokens",
+                options.source
+            ),
+        ));
+    }
+    let token = rpc::query_native_token(&ctx.client)
+        .await
+        .map_err(ApiError::node)?;
+    let gas_price = rpc::query_base_fee(&ctx.client)
+        .await
+        .map_err(ApiError::node)?;
+    let (code_hash, _) = rpc::query_storage_value_bytes(
+        &ctx.client,
+        &Key::wasm_hash(TX_TRANSFER_WASM),
+        None,
+        false,
+    )
+    .await
+    .map_err(ApiError::node)?;
+    let code_hash = match code_hash {
+        Some(hash) => Hash::try_from(&hash[..]).map_err(ApiError::node)?,
+        None => return Err(ApiError::node("The transfer code is not found")),
+    };
+    let fee = gas_price
+        .checked_mul(token::Amount::from_u64(DEFAULT_GAS_LIMIT))
+        .ok_or_else(|| ApiError::node("The fee overflows"))?;
+    let metadata = TransferMetadata {
+        chain_id: ctx.chain_id,
+        token,
+        code_hash: code_hash.to_string(),
+        gas_price: gas_price.to_string(),
+        gas_limit: DEFAULT_GAS_LIMIT,
+    };
+    Ok(ConstructionMetadataResponse {
+        metadata: serde_json::to_value(metadata)
+            .map_err(ApiError::invalid_request)?,
+        suggested_fee: vec![native_amount(fee, false)],
+    })
+}
+
+pub(super) async fn payloads(
+    ctx: Context,
+    request: ConstructionPayloadsRequest,
+) -> Result<ConstructionPayloadsResponse> {
+    ctx.check_network(&request.network_identifier)?;
+    let intent = parse_transfer(&request.operations)?;
+    let metadata: TransferMetadata = serde_json::from_value(request.metadata)
+        .map_err(ApiError::invalid_request)?;
+    let [public_key] = request.public_keys.as_slice() else {
+        return Err(ApiError::invalid_request(
+            "Expected the public key of the source",
+        ));
+    };
+    let public_key = parse_public_key(public_key)?;
+    if Address::from(&public_key) != intent.source {
+        return Err(ApiError::new(
+            ErrorKind::UnsupportedOperations,
+            "The source must be the implicit account of the public key, which \
+             pays the fee",
+        ));
+    }
+    let tx = build_transfer(&intent, &metadata, public_key)?;
+    Ok(ConstructionPayloadsResponse {
+        unsigned_transaction: HEXLOWER.encode(&tx.to_bytes()),
+        payloads: vec![SigningPayload {
+            account_identifier: account(&intent.source),
+            hex_bytes: HEXLOWER.encode(&sign_bytes(signing_targets(&tx)).0),
+            signature_type: SIGNATURE_ED25519.to_string(),
+        }],
+    })
+}
+
+pub(super) async fn combine(
+    ctx: Context,
+    request: ConstructionCombineRequest,
+) -> Result<ConstructionCombineResponse> {
+    ctx.check_network(&request.network_identifier)?;
+    let mut tx = decode_tx(&request.unsigned_transaction)?;
+    let [signature] = request.signatures.as_slice() else {
+        return Err(ApiError::invalid_request("Expected a single signature"));
+    };
+    let invalid_signature =
+        |details| ApiError::new(ErrorKind::InvalidSignature, details);
+    if signature.signature_type != SIGNATURE_ED25519 {
+        return Err(invalid_signature(format!(
+            "Only {SIGNATURE_ED25519} signatures are supported"
+        )));
+    }
+    let public_key = parse_public_key(&signature.public_key)?;
+    match &tx.header.tx_type {
+        TxType::Wrapper(wrapper) if wrapper.pk == public_key => {}
+        _ => {
+            return Err(invalid_signature(
+                "The signer must be the fee payer of the transaction"
+                    .to_string(),
+            ));
+        }
+    }
+    let targets = signing_targets(&tx);
+    let payload = sign_bytes(targets.clone());
+    if !signature
+        .signing_payload
+        .hex_bytes
+        .eq_ignore_ascii_case(&HEXLOWER.encode(&payload.0))
+    {
+        return Err(invalid_signature(
+            "The signed payload is not the payload of the transaction"
+                .to_string(),
+        ));
+    }
+    let sig = decode_hex(&signature.hex_bytes)
+        .and_then(|bytes| {
+            ed25519::Signature::try_from_slice(&bytes)
+                .map_err(ApiError::invalid_request)
+        })
+        .map(common::Signature::Ed25519)?;
+    common::SigScheme::verify_signature(&public_key, &payload, &sig)
+        .map_err(|err| invalid_signature(err.to_string()))?;
+
+    tx.protocol_filter();
+    tx.add_section(Section::Authorization(Authorization {
+        targets,
+        signer: Signer::PubKeys(vec![public_key]),
+        signatures: [(0, sig)].into_iter().collect(),
+    }));
+    Ok(ConstructionCombineResponse {
+        signed_transaction: HEXLOWER.encode(&tx.to_bytes()),
+    })
+}
+
+pub(super) async fn parse(
+    ctx: Context,
+    request: ConstructionParseRequest,
+) -> Result<ConstructionParseResponse> {
+    ctx.check_network(&request.network_identifier)?;
+    let tx = decode_tx(&request.transaction)?;
+    let intent = transfer_intent(&tx)?;
+    let mut operations = Operations::default();
+    operations.push_transfer(
+        OP_TRANSFER,
+        None,
+        &intent.source,
+        &intent.target,
+        intent.amount,
+    );
+    let account_identifier_signers = if request.signed {
+        tx.sections
+            .iter()
+            .filter_map(|section| match section {
+                Section::Authorization(Authorization {
+                    signer: Signer::PubKeys(public_keys),
+                    ..
+                }) => Some(public_keys),
+                _ => None,
+            })
+            .flatten()
+            .map(|public_key| account(&Address::from(public_key)))
+            .collect()
+    } else {
+        vec![]
+    };
+    Ok(ConstructionParseResponse {
+        operations: operations.0,
+        account_identifier_signers,
+    })
+}
+
+pub(super) async fn hash(
+    ctx: Context,
+    request: SignedTransactionRequest,
+) -> Result<TransactionIdentifierResponse> {
+    ctx.check_network(&request.network_identifier)?;
+    let tx = decode_tx(&request.signed_transaction)?;
+    Ok(TransactionIdentifierResponse {
+        transaction_identifier: TransactionIdentifier {
+            hash: tx.header_hash().to_string(),
+        },
+    })
+}
+
+pub(super) async fn submit(
+    ctx: Context,
+    request: SignedTransactionRequest,
+) -> Result<TransactionIdentifierResponse> {
+    ctx.check_network(&request.network_identifier)?;
+    let tx = decode_tx(&request.signed_transaction)?;
+    let response = ctx
+        .client
+        .broadcast_tx_sync(tx.to_bytes())
+        .await
+        .map_err(ApiError::node)?;
+    if response.code.is_err() {
+        return Err(ApiError::new(ErrorKind::SubmissionRejected, response.log));
+    }
+    Ok(TransactionIdentifierResponse {
+        transaction_identifier: TransactionIdentifier {
+            hash: tx.header_hash().to_string(),
+        },
+    })
+}
+
+/// Parse the operations of a transfer of the native token, which must debit
+/// the source and credit the target of the same amount
+fn parse_transfer(operations: &[Operation]) -> Result<TransferIntent> {
+    let unsupported =
+        |details| ApiError::new(ErrorKind::UnsupportedOperations, details);
+    let [first, second] = operations else {
+        return Err(unsupported("Expected the two operations of a transfer"));
+    };
+    let mut debit = None;
+    let mut credit = None;
+    for operation in [first, second] {
+        if operation.r#type != OP_TRANSFER {
+            return Err(unsupported("Only transfers are supported"));
+        }
+        let (Some(account), Some(amount)) =
+            (&operation.account, &operation.amount)
+        else {
+            return Err(unsupported(
+                "The operations of a transfer must have an account and an \
+                 amount",
+            ));
+        };
+        let address = parse_account(account)?;
+        let (amount, is_debit) = parse_native_amount(amount)?;
+        if is_debit {
+            debit = Some((address, amount));
+        } else {
+            credit = Some((address, amount));
+        }
+    }
+    match (debit, credit) {
+        (Some((source, sent)), Some((target, received)))
+            if sent == received && !sent.is_zero() =>
+        {
+            Ok(TransferIntent {
+                source,
+                target,
+                amount: sent,
+            })
+        }
+        _ => Err(unsupported(
+            "A transfer must debit the source and credit the target of the \
+             same non-zero amount",
+        )),
+    }
+}
+
+/// Build a transfer whose fee is paid by the source
+fn build_transfer(
+    intent: &TransferIntent,
+    metadata: &TransferMetadata,
+    public_key: common::PublicKey,
+) -> Result<Tx> {
+    let code_hash = Hash::from_str(&metadata.code_hash)
+        .map_err(ApiError::invalid_request)?;
+    let gas_price = token::Amount::from_str(&metadata.gas_price, 0u8)
+        .map_err(ApiError::invalid_request)?;
+    let mut tx = Tx::new(metadata.chain_id.clone(), None);
+    tx.add_code_from_hash(code_hash, Some(TX_TRANSFER_WASM.to_string()))
+        .add_data(token::Transfer {
+            source: intent.source.clone(),
+            target: intent.target.clone(),
+            token: metadata.token.clone(),
+            amount: DenominatedAmount::native(intent.amount),
+            shielded: None,
+        })
+        .add_wrapper(
+            Fee {
+                amount_per_gas_unit: DenominatedAmount::native(gas_price),
+                token: metadata.token.clone(),
+            },
+            public_key,
+            GasLimit::from(metadata.gas_limit),
+            None,
+        );
+    Ok(tx)
+}
+
+/// Get the transfer of the native token performed by a tx built by
+/// [`build_transfer`]
+fn transfer_intent(tx: &Tx) -> Result<TransferIntent> {
+    let invalid =
+        |details: &str| ApiError::new(ErrorKind::InvalidTransaction, details);
+    if code_tag(tx).as_deref() != Some(TX_TRANSFER_WASM) {
+        return Err(invalid("Only transfers are supported"));
+    }
+    let transfer: token::Transfer = tx
+        .decode_data()
+        .ok_or_else(|| invalid("The transfer data is missing"))?
+        .map_err(|err| invalid(&err.to_string()))?;
+    match &tx.header.tx_type {
+        TxType::Wrapper(wrapper) if wrapper.fee.token == transfer.token => {}
+        _ => {
+            return Err(invalid(
+                "The transfer must be wrapped with a fee in the same token",
+            ));
+        }
+    }
+    Ok(TransferIntent {
+        source: transfer.source,
+        target: transfer.target,
+        amount: transfer
+            .amount
+            .scale(NATIVE_MAX_DECIMAL_PLACES)
+            .map_err(|err| invalid(&err.to_string()))?,
+    })
+}
+
+/// The hashes covered by the single signature of a transfer: the sections of
+/// the wrapper, for the fee payment, and the raw header, for the transfer
+/// itself. The fee payment is verified against all the sections of the tx,
+/// which the signature section is exempted from.
+fn signing_targets(tx: &Tx) -> Vec<Hash> {
+    let mut targets = tx.sechashes();
+    targets.push(tx.raw_header_hash());
+    targets
+}
+
+/// Get the hash that commits to the given targets and that is signed by an
+/// authorization
+fn sign_bytes(targets: Vec<Hash>) -> Hash {
+    Authorization {
+        targets,
+        signer: Signer::PubKeys(vec![]),
+        signatures: BTreeMap::new(),
+    }
+    .get_raw_hash()
+}
+
+/// Parse an ed25519 public key
+fn parse_public_key(public_key: &PublicKey) -> Result<common::PublicKey> {
+    if public_key.curve_type != CURVE_EDWARDS25519 {
+        return Err(ApiError::invalid_request(format!(
+            "Only {CURVE_EDWARDS25519} public keys are supported"
+        )));
+    }
+    let bytes = decode_hex(&public_key.hex_bytes)?;
+    ed25519::PublicKey::try_from_slice(&bytes)
+        .map(common::PublicKey::Ed25519)
+        .map_err(|err| {
+            ApiError::invalid_request(format!("Invalid public key: {err}"))
+        })
+}
+
+/// Decode a hex encoded tx
+fn decode_tx(tx: &str) -> Result<Tx> {
+    Tx::try_from(decode_hex(tx)?.as_slice()).map_err(|err| {
+        ApiError::new(ErrorKind::InvalidTransaction, err.to_string())
+    })
+}
+
+/// Decode a hex string, in either case
+fn decode_hex(hex: &str) -> Result<Vec<u8>> {
+    HEXLOWER_PERMISSIVE.decode(hex.as_bytes()).map_err(|err| {
+        ApiError::invalid_request(format!("Invalid hex encoding: {err}"))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use namada::address::testing::{established_address_1, nam};
+    use namada::key::testing::keypair_1;
+    use namada::key::RefTo;
+
+    use super::*;
+
+    /// Test that a transfer built from its operations is parsed back into
+    /// them, and that the single signature of its payload authorizes both
+    /// the transfer and its fee payment
+    #[test]
+    fn test_transfer_construction() {
+        let keypair = keypair_1();
+        let public_key = keypair.ref_to();
+        let source = Address::from(&public_key);
+        let target = established_address_1();
+        let amount = token::Amount::from_u64(1_500_000);
+        let mut operations = Operations::default();
+        operations.push_transfer(OP_TRANSFER, None, &source, &target, amount);
+        let intent = parse_transfer(&operations.0).unwrap();
+        assert_eq!(
+            intent,
+            TransferIntent {
+                source: source.clone(),
+                target: target.clone(),
+                amount,
+            }
+        );
+
+        let metadata = TransferMetadata {
+            chain_id: ChainId::default(),
+            token: nam(),
+            code_hash: Hash::default().to_string(),
+            gas_price: "10".to_string(),
+            gas_limit: 20_000,
+        };
+        let mut tx =
+            build_transfer(&intent, &metadata, public_key.clone()).unwrap();
+        assert_eq!(transfer_intent(&tx).unwrap(), intent);
+
+        let targets = signing_targets(&tx);
+        let sig =
+            common::SigScheme::sign(&keypair, sign_bytes(targets.clone()));
+        tx.add_section(Section::Authorization(Authorization {
+            targets,
+            signer: Signer::PubKeys(vec![public_key.clone()]),
+            signatures: [(0, sig)].into_iter().collect(),
+        }));
+        // The fee payment
+        assert!(tx.validate_tx().is_ok());
+        // The transfer
+        assert!(tx
+            .verify_signature(&public_key, &[tx.raw_header_hash()])
+            .is_ok());
+
+        // The operations must be a debit and a credit of the same amount
+        let mut operations = Operations::default();
+        operations.push_transfer(OP_TRANSFER, None, &source, &target, amount);
+        operations.0[1].amount =
+            Some(native_amount(token::Amount::from_u64(1), false));
+        assert_eq!(
+            parse_transfer(&operations.0).unwrap_err().kind,
+            ErrorKind::UnsupportedOperations
+        );
+    }
+}
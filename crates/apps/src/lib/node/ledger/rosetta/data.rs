@@ -0,0 +1,603 @@
+//! The handlers of the Data API
+
+use std::str::FromStr;
+
+use namada::address::Address;
+use namada::events::Event;
+use namada::storage::BlockHeight;
+use namada::time::DateTimeUtc;
+use namada::token;
+use namada::token::event::{
+    types as token_events, Amount as AmountAttr, Descriptor, SourceAccount,
+    TargetAccount, TokenAddress, UserAccount,
+};
+use namada::tx::data::{pos, ResultCode, TxType};
+use namada::tx::Tx;
+use namada_sdk::queries::TxResultInfo;
+use namada_sdk::rpc;
+use namada_sdk::tx::{
+    TX_BOND_WASM, TX_CLAIM_REWARDS_WASM, TX_UNBOND_WASM, TX_WITHDRAW_WASM,
+};
+use serde_json::{json, Value};
+
+use super::types::{
+    AccountBalanceRequest, AccountBalanceResponse, Allow, Block,
+    BlockIdentifier, BlockRequest, BlockResponse, BlockTransactionRequest,
+    BlockTransactionResponse, MempoolResponse, MetadataRequest,
+    NetworkListResponse, NetworkOptionsResponse, NetworkRequest,
+    NetworkStatusResponse, OperationStatus, PartialBlockIdentifier, Peer,
+    SyncStatus, Transaction, TransactionIdentifier, Version,
+};
+use super::{
+    code_tag, native_amount, parse_account, ApiError, Context, ErrorKind,
+    Operations, Result, OPERATION_TYPES, OP_BOND, OP_CLAIM_REWARDS, OP_FEE,
+    OP_TRANSFER, OP_UNBOND, OP_WITHDRAW, ROSETTA_VERSION, STATUS_FAILED,
+    STATUS_SUCCESS,
+};
+use crate::facade::tendermint::block::Height;
+use crate::facade::tendermint::Hash;
+use crate::facade::tendermint_rpc::Client;
+use crate::facade::{tendermint, tendermint_rpc};
+
+/// The descriptor of the token events of the fee payments
+const FEE_PAYMENT_DESCRIPTOR: &str = "wrapper-fee-payment";
+
+/// A block stored by CometBFT
+struct FetchedBlock {
+    id: BlockIdentifier,
+    parent: BlockIdentifier,
+    height: BlockHeight,
+    /// The time of the block, in milliseconds since the Unix epoch
+    timestamp: i64,
+    txs: Vec<Vec<u8>>,
+}
+
+pub(super) async fn network_list(
+    ctx: Context,
+    _request: MetadataRequest,
+) -> Result<NetworkListResponse> {
+    Ok(NetworkListResponse {
+        network_identifiers: vec![ctx.network()],
+    })
+}
+
+pub(super) async fn network_options(
+    ctx: Context,
+    request: NetworkRequest,
+) -> Result<NetworkOptionsResponse> {
+    ctx.check_network(&request.network_identifier)?;
+    Ok(NetworkOptionsResponse {
+        version: Version {
+            rosetta_version: ROSETTA_VERSION.to_string(),
+            node_version: env!("CARGO_PKG_VERSION").to_string(),
+        },
+        allow: Allow {
+            operation_statuses: vec![
+                OperationStatus {
+                    status: STATUS_SUCCESS.to_string(),
+                    successful: true,
+                },
+                OperationStatus {
+                    status: STATUS_FAILED.to_string(),
+                    successful: false,
+                },
+            ],
+            operation_types: OPERATION_TYPES
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            errors: ErrorKind::ALL
+                .iter()
+                .map(|kind| kind.to_model(None))
+                .collect(),
+            historical_balance_lookup: ctx.archive_mode,
+            call_methods: vec![],
+            balance_exemptions: vec![],
+            mempool_coins: false,
+        },
+    })
+}
+
+pub(super) async fn network_status(
+    ctx: Context,
+    request: NetworkRequest,
+) -> Result<NetworkStatusResponse> {
+    ctx.check_network(&request.network_identifier)?;
+    let status = ctx.client.status().await.map_err(ApiError::node)?;
+    let net_info = ctx.client.net_info().await.map_err(ApiError::node)?;
+    let current = fetch_block(&ctx, &PartialBlockIdentifier::default()).await?;
+    let oldest = BlockIdentifier {
+        index: status.sync_info.earliest_block_height.value() as i64,
+        hash: status.sync_info.earliest_block_hash.to_string(),
+    };
+    // The nodes which were state synced do not store the genesis block, in
+    // which case we report their oldest block
+    let genesis = match fetch_block(
+        &ctx,
+        &PartialBlockIdentifier {
+            index: Some(1),
+            hash: None,
+        },
+    )
+    .await
+    {
+        Ok(genesis) => genesis.id,
+        Err(_) => oldest.clone(),
+    };
+    Ok(NetworkStatusResponse {
+        current_block_identifier: current.id,
+        current_block_timestamp: current.timestamp,
+        genesis_block_identifier: genesis,
+        oldest_block_identifier: oldest,
+        sync_status: SyncStatus {
+            current_index: status.sync_info.latest_block_height.value() as i64,
+            synced: !status.sync_info.catching_up,
+        },
+        peers: net_info
+            .peers
+            .iter()
+            .map(|peer| Peer {
+                peer_id: peer.node_info.id.to_string(),
+            })
+            .collect(),
+    })
+}
+
+pub(super) async fn account_balance(
+    ctx: Context,
+    request: AccountBalanceRequest,
+) -> Result<AccountBalanceResponse> {
+    ctx.check_network(&request.network_identifier)?;
+    let owner = parse_account(&request.account_identifier)?;
+    let block =
+        fetch_block(&ctx, &request.block_identifier.unwrap_or_default())
+            .await?;
+    let native_token = rpc::query_native_token(&ctx.client)
+        .await
+        .map_err(ApiError::node)?;
+    let balance = rpc::get_token_balance_at_height(
+        &ctx.client,
+        &native_token,
+        &owner,
+        block.height,
+    )
+    .await
+    .map_err(ApiError::node)?;
+    Ok(AccountBalanceResponse {
+        block_identifier: block.id,
+        balances: vec![native_amount(balance, false)],
+    })
+}
+
+pub(super) async fn block(
+    ctx: Context,
+    request: BlockRequest,
+) -> Result<BlockResponse> {
+    ctx.check_network(&request.network_identifier)?;
+    let block = fetch_block(&ctx, &request.block_identifier).await?;
+    let transactions = block_transactions(&ctx, &block).await?;
+    Ok(BlockResponse {
+        block: Block {
+            block_identifier: block.id,
+            parent_block_identifier: block.parent,
+            timestamp: block.timestamp,
+            transactions,
+        },
+    })
+}
+
+pub(super) async fn block_transaction(
+    ctx: Context,
+    request: BlockTransactionRequest,
+) -> Result<BlockTransactionResponse> {
+    ctx.check_network(&request.network_identifier)?;
+    let block = fetch_block(
+        &ctx,
+        &PartialBlockIdentifier {
+            index: Some(request.block_identifier.index),
+            hash: Some(request.block_identifier.hash),
+        },
+    )
+    .await?;
+    block_transactions(&ctx, &block)
+        .await?
+        .into_iter()
+        .find(|tx| tx.transaction_identifier == request.transaction_identifier)
+        .map(|transaction| BlockTransactionResponse { transaction })
+        .ok_or_else(|| {
+            ApiError::new(
+                ErrorKind::TransactionNotFound,
+                format!(
+                    "The transaction {} is not in the block {}",
+                    request.transaction_identifier.hash, block.id.index
+                ),
+            )
+        })
+}
+
+/// The mempool is not tracked, so that it is always reported empty
+pub(super) async fn mempool(
+    ctx: Context,
+    request: NetworkRequest,
+) -> Result<MempoolResponse> {
+    ctx.check_network(&request.network_identifier)?;
+    Ok(MempoolResponse {
+        transaction_identifiers: vec![],
+    })
+}
+
+/// Fetch a block from CometBFT. Without an index nor a hash, fetch the last
+/// block committed by the ledger, whose state can be queried.
+async fn fetch_block(
+    ctx: &Context,
+    block_id: &PartialBlockIdentifier,
+) -> Result<FetchedBlock> {
+    let not_found = |err: tendermint_rpc::Error| {
+        ApiError::new(ErrorKind::BlockNotFound, err)
+    };
+    let (id, block) = match (block_id.index, &block_id.hash) {
+        (Some(index), _) => {
+            let height = u64::try_from(index)
+                .ok()
+                .and_then(|index| Height::try_from(index).ok())
+                .ok_or_else(|| {
+                    ApiError::invalid_request(format!(
+                        "Invalid block index {index}"
+                    ))
+                })?;
+            let response = ctx.client.block(height).await.map_err(not_found)?;
+            (response.block_id, response.block)
+        }
+        (None, Some(hash)) => {
+            let hash = Hash::from_str(hash).map_err(|err| {
+                ApiError::invalid_request(format!(
+                    "Invalid block hash {hash}: {err}"
+                ))
+            })?;
+            let response =
+                ctx.client.block_by_hash(hash).await.map_err(not_found)?;
+            let block = response.block.ok_or_else(|| {
+                ApiError::new(
+                    ErrorKind::BlockNotFound,
+                    format!("No block with the hash {hash}"),
+                )
+            })?;
+            (response.block_id, block)
+        }
+        (None, None) => {
+            let info = ctx.client.abci_info().await.map_err(ApiError::node)?;
+            let response = ctx
+                .client
+                .block(info.last_block_height)
+                .await
+                .map_err(not_found)?;
+            (response.block_id, response.block)
+        }
+    };
+    if let Some(hash) = &block_id.hash {
+        if !hash.eq_ignore_ascii_case(&id.hash.to_string()) {
+            return Err(ApiError::new(
+                ErrorKind::BlockNotFound,
+                format!(
+                    "The block {} has the hash {}, not {hash}",
+                    block.header.height, id.hash
+                ),
+            ));
+        }
+    }
+    block_from_header(id, block)
+}
+
+/// Identify a block and its parent from its header
+fn block_from_header(
+    id: tendermint::block::Id,
+    block: tendermint::Block,
+) -> Result<FetchedBlock> {
+    let height = block.header.height.value();
+    let id = BlockIdentifier {
+        index: height as i64,
+        hash: id.hash.to_string(),
+    };
+    // The parent of the first block is itself
+    let parent = match block.header.last_block_id {
+        Some(parent) => BlockIdentifier {
+            index: id.index - 1,
+            hash: parent.hash.to_string(),
+        },
+        None => id.clone(),
+    };
+    let timestamp = DateTimeUtc::try_from(block.header.time)
+        .map_err(ApiError::node)?
+        .0
+        .timestamp_millis();
+    Ok(FetchedBlock {
+        id,
+        parent,
+        height: BlockHeight(height),
+        timestamp,
+        txs: block.data,
+    })
+}
+
+/// Map the wrapper txs of a block to Rosetta transactions, using the results
+/// of the block
+async fn block_transactions(
+    ctx: &Context,
+    block: &FetchedBlock,
+) -> Result<Vec<Transaction>> {
+    let txs: Vec<Tx> = block
+        .txs
+        .iter()
+        .filter_map(|bytes| Tx::try_from(bytes.as_slice()).ok())
+        .filter(|tx| matches!(tx.header.tx_type, TxType::Wrapper(_)))
+        .collect();
+    if txs.is_empty() {
+        return Ok(vec![]);
+    }
+    let native_token = rpc::query_native_token(&ctx.client)
+        .await
+        .map_err(ApiError::node)?;
+    let results = rpc::query_block_results(&ctx.client, block.height)
+        .await
+        .map_err(ApiError::node)?;
+    txs.iter()
+        .map(|tx| {
+            let hash = tx.header_hash();
+            let result = results
+                .txs
+                .iter()
+                .find(|result| result.hash == hash)
+                .ok_or_else(|| {
+                    ApiError::node(format!(
+                        "The results of the transaction {hash} are no longer \
+                         in the event log of the node"
+                    ))
+                })?;
+            Ok(transaction(tx, result, &native_token))
+        })
+        .collect()
+}
+
+/// Map a wrapper tx and its result to a Rosetta transaction. The balance
+/// changes are taken from the token events of the tx, which only a
+/// successful tx emits besides its fee payment, and the staking operations
+/// from its data.
+fn transaction(
+    tx: &Tx,
+    result: &TxResultInfo,
+    native_token: &Address,
+) -> Transaction {
+    let mut operations = Operations::default();
+    for event in &result.events {
+        if let Some((r#type, source, target, amount)) =
+            native_transfer(event, native_token)
+        {
+            operations.push_transfer(
+                r#type,
+                Some(STATUS_SUCCESS),
+                &source,
+                &target,
+                amount,
+            );
+        }
+    }
+    let status = if result.code == ResultCode::Ok.to_u32() {
+        STATUS_SUCCESS
+    } else {
+        STATUS_FAILED
+    };
+    if let Some((r#type, address, amount, metadata)) = staking_operation(tx) {
+        operations.push(
+            r#type,
+            Some(status),
+            &address,
+            amount.map(|amount| native_amount(amount, true)),
+            Some(metadata),
+        );
+    }
+    Transaction {
+        transaction_identifier: TransactionIdentifier {
+            hash: result.hash.to_string(),
+        },
+        operations: operations.0,
+    }
+}
+
+/// Parse a transfer of the native token from a token event, with the type of
+/// its operation
+fn native_transfer(
+    event: &Event,
+    native_token: &Address,
+) -> Option<(&'static str, Address, Address, token::Amount)> {
+    if *event.kind() != token_events::TRANSFER
+        || event.read_attribute::<TokenAddress>().ok()? != *native_token
+    {
+        return None;
+    }
+    let r#type = if event.read_attribute::<Descriptor<'_>>().ok()?
+        == FEE_PAYMENT_DESCRIPTOR
+    {
+        OP_FEE
+    } else {
+        OP_TRANSFER
+    };
+    let (UserAccount::Internal(source), UserAccount::Internal(target)) = (
+        event.read_attribute::<SourceAccount>().ok()?,
+        event.read_attribute::<TargetAccount>().ok()?,
+    ) else {
+        return None;
+    };
+    let amount = token::Amount::from_str(
+        event.raw_read_attribute::<AmountAttr<'_>>()?,
+        0u8,
+    )
+    .ok()?;
+    Some((r#type, source, target, amount))
+}
+
+/// Parse the staking operation of a tx from its data, with its account, the
+/// debited amount if any and its metadata
+fn staking_operation(
+    tx: &Tx,
+) -> Option<(&'static str, Address, Option<token::Amount>, Value)> {
+    match code_tag(tx)?.as_str() {
+        tag @ (TX_BOND_WASM | TX_UNBOND_WASM) => {
+            let bond: pos::Bond = tx.decode_data()?.ok()?;
+            let source = bond.source.unwrap_or_else(|| bond.validator.clone());
+            Some(if tag == TX_BOND_WASM {
+                (
+                    OP_BOND,
+                    source,
+                    Some(bond.amount),
+                    json!({ "validator": bond.validator }),
+                )
+            } else {
+                (
+                    OP_UNBOND,
+                    source,
+                    None,
+                    json!({
+                        "validator": bond.validator,
+                        "amount": bond.amount.to_string(),
+                    }),
+                )
+            })
+        }
+        TX_WITHDRAW_WASM => {
+            let withdraw: pos::Withdraw = tx.decode_data()?.ok()?;
+            let source = withdraw
+                .source
+                .unwrap_or_else(|| withdraw.validator.clone());
+            Some((
+                OP_WITHDRAW,
+                source,
+                None,
+                json!({ "validator": withdraw.validator }),
+            ))
+        }
+        TX_CLAIM_REWARDS_WASM => {
+            let claim: pos::ClaimRewards = tx.decode_data()?.ok()?;
+            let source =
+                claim.source.unwrap_or_else(|| claim.validator.clone());
+            Some((
+                OP_CLAIM_REWARDS,
+                source,
+                None,
+                json!({ "validator": claim.validator }),
+            ))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use namada::address::testing::{
+        established_address_1, established_address_2, nam,
+    };
+    use namada::chain::ChainId;
+    use namada::events::EventLevel;
+    use namada::gas::Gas;
+    use namada::hash::Hash;
+    use namada::key::testing::keypair_1;
+    use namada::key::RefTo;
+    use namada::token::event::{TokenEvent, TokenOperation};
+    use namada::token::DenominatedAmount;
+    use namada::tx::data::wrapper::GasLimit;
+    use namada::tx::data::Fee;
+
+    use super::*;
+
+    fn transfer_event(descriptor: &'static str, amount: u64) -> Event {
+        TokenEvent {
+            descriptor: descriptor.into(),
+            level: EventLevel::Tx,
+            token: nam(),
+            operation: TokenOperation::Transfer {
+                source: UserAccount::Internal(established_address_1()),
+                target: UserAccount::Internal(established_address_2()),
+                amount: token::Amount::from_u64(amount).into(),
+                source_post_balance: token::Amount::zero().into(),
+                target_post_balance: None,
+            },
+        }
+        .into()
+    }
+
+    /// Test the mapping of the events and the data of a tx to operations
+    #[test]
+    fn test_transaction_operations() {
+        let validator = established_address_2();
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code_from_hash(Hash::default(), Some(TX_BOND_WASM.to_string()))
+            .add_data(pos::Bond {
+                validator: validator.clone(),
+                amount: token::Amount::from_u64(30),
+                source: Some(established_address_1()),
+            })
+            .add_wrapper(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(1.into()),
+                    token: nam(),
+                },
+                keypair_1().ref_to(),
+                GasLimit::from(100),
+                None,
+            );
+        let mut result = TxResultInfo {
+            hash: tx.header_hash(),
+            code: ResultCode::Ok.to_u32(),
+            gas_used: Gas::default(),
+            info: String::new(),
+            events: vec![
+                transfer_event(FEE_PAYMENT_DESCRIPTOR, 100),
+                transfer_event("transfer-from-wasm", 7),
+            ],
+        };
+
+        let mapped = transaction(&tx, &result, &nam());
+        assert_eq!(mapped.transaction_identifier.hash, result.hash.to_string());
+        let summary: Vec<_> = mapped
+            .operations
+            .iter()
+            .map(|op| {
+                (
+                    op.operation_identifier.index,
+                    op.r#type.as_str(),
+                    op.status.as_deref(),
+                    op.account.as_ref().unwrap().address.clone(),
+                    op.amount.as_ref().map(|amount| amount.value.clone()),
+                )
+            })
+            .collect();
+        let source = established_address_1().to_string();
+        let target = established_address_2().to_string();
+        let success = Some(STATUS_SUCCESS);
+        assert_eq!(
+            summary,
+            vec![
+                (0, OP_FEE, success, source.clone(), Some("-100".into())),
+                (1, OP_FEE, success, target.clone(), Some("100".into())),
+                (2, OP_TRANSFER, success, source.clone(), Some("-7".into())),
+                (3, OP_TRANSFER, success, target, Some("7".into())),
+                (4, OP_BOND, success, source, Some("-30".into())),
+            ]
+        );
+        assert_eq!(
+            mapped.operations[1].related_operations,
+            vec![mapped.operations[0].operation_identifier]
+        );
+
+        // A failed tx only pays its fee, and its staking operation fails
+        result.code = ResultCode::WasmRuntimeError.to_u32();
+        result.events.truncate(1);
+        let mapped = transaction(&tx, &result, &nam());
+        assert_eq!(mapped.operations.len(), 3);
+        assert_eq!(mapped.operations[2].status.as_deref(), Some(STATUS_FAILED));
+
+        // The transfers of other tokens are ignored
+        let mapped = transaction(&tx, &result, &established_address_1());
+        assert_eq!(mapped.operations.len(), 1);
+        assert_eq!(mapped.operations[0].r#type, OP_BOND);
+    }
+}
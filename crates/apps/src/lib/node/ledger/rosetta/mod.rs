@@ -0,0 +1,484 @@
+//! An optional service implementing the [Rosetta API] over the RPC of the
+//! node, for the integration of Namada by exchanges.
+//!
+//! The Data API reports the balances of the native token and the
+//! transactions of the blocks as Rosetta operations:
+//!
+//! - `TRANSFER`: a transparent transfer of the native token, as a debit of the
+//!   source and a credit of the target.
+//! - `FEE`: the fee of a transaction, as a debit of the fee payer and a credit
+//!   of the block proposer.
+//! - `BOND`: a debit of the tokens bonded to a validator.
+//! - `UNBOND`, `WITHDRAW` and `CLAIM_REWARDS`: the other staking operations,
+//!   without an amount. The tokens returned by the last two are not carried by
+//!   the transaction, so they must be reconciled from the balances.
+//!
+//! The Construction API builds transparent transfers of the native token,
+//! whose fee is paid by the source. A single signature, made with the ed25519
+//! key of the source, authorizes both the transfer and the fee payment. The
+//! public key of the source must have been revealed on chain.
+//!
+//! Historical balances can only be queried from an archive node, and the
+//! results of a block can only be queried while they are in the event log of
+//! the node.
+//!
+//! [Rosetta API]: https://www.rosetta-api.org/
+
+mod construction;
+mod data;
+mod types;
+
+use std::fmt::Display;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use namada::address::Address;
+use namada::chain::ChainId;
+use namada::token::{self, NATIVE_MAX_DECIMAL_PLACES};
+use namada::tx::Tx;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use warp::http::StatusCode;
+use warp::path::FullPath;
+use warp::reply::{Json, WithStatus};
+use warp::Filter;
+
+use self::types::{
+    AccountIdentifier, Amount, Currency, NetworkIdentifier, Operation,
+    OperationIdentifier,
+};
+use crate::facade::tendermint_rpc::HttpClient;
+use crate::node::ledger::services::Policy;
+
+/// The version of the Rosetta specification implemented by the service
+const ROSETTA_VERSION: &str = "1.4.13";
+/// The name of the blockchain in the network identifier
+const BLOCKCHAIN: &str = "Namada";
+/// The symbol of the native token
+const NATIVE_SYMBOL: &str = "NAM";
+
+/// The operation types
+const OP_TRANSFER: &str = "TRANSFER";
+const OP_FEE: &str = "FEE";
+const OP_BOND: &str = "BOND";
+const OP_UNBOND: &str = "UNBOND";
+const OP_WITHDRAW: &str = "WITHDRAW";
+const OP_CLAIM_REWARDS: &str = "CLAIM_REWARDS";
+const OPERATION_TYPES: [&str; 6] = [
+    OP_TRANSFER,
+    OP_FEE,
+    OP_BOND,
+    OP_UNBOND,
+    OP_WITHDRAW,
+    OP_CLAIM_REWARDS,
+];
+
+/// The operation statuses
+const STATUS_SUCCESS: &str = "SUCCESS";
+const STATUS_FAILED: &str = "FAILED";
+
+/// The errors returned by the API. Their codes are stable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ErrorKind {
+    InvalidRequest = 1,
+    UnsupportedNetwork = 2,
+    NodeUnavailable = 3,
+    BlockNotFound = 4,
+    TransactionNotFound = 5,
+    UnsupportedOperations = 6,
+    InvalidTransaction = 7,
+    InvalidSignature = 8,
+    SubmissionRejected = 9,
+}
+
+impl ErrorKind {
+    /// All the errors, listed by `/network/options`
+    const ALL: [ErrorKind; 9] = [
+        ErrorKind::InvalidRequest,
+        ErrorKind::UnsupportedNetwork,
+        ErrorKind::NodeUnavailable,
+        ErrorKind::BlockNotFound,
+        ErrorKind::TransactionNotFound,
+        ErrorKind::UnsupportedOperations,
+        ErrorKind::InvalidTransaction,
+        ErrorKind::InvalidSignature,
+        ErrorKind::SubmissionRejected,
+    ];
+
+    fn message(self) -> &'static str {
+        match self {
+            ErrorKind::InvalidRequest => "Invalid request",
+            ErrorKind::UnsupportedNetwork => "Unsupported network",
+            ErrorKind::NodeUnavailable => "The node failed to respond",
+            ErrorKind::BlockNotFound => "Block not found",
+            ErrorKind::TransactionNotFound => "Transaction not found",
+            ErrorKind::UnsupportedOperations => "Unsupported operations",
+            ErrorKind::InvalidTransaction => "Invalid transaction",
+            ErrorKind::InvalidSignature => "Invalid signature",
+            ErrorKind::SubmissionRejected => {
+                "The transaction was rejected by the mempool"
+            }
+        }
+    }
+
+    fn retriable(self) -> bool {
+        matches!(self, ErrorKind::NodeUnavailable | ErrorKind::BlockNotFound)
+    }
+
+    fn to_model(self, details: Option<String>) -> types::Error {
+        types::Error {
+            code: self as u32,
+            message: self.message().to_string(),
+            retriable: self.retriable(),
+            details: details.map(|details| {
+                serde_json::json!({
+                    "error": details
+                })
+            }),
+        }
+    }
+}
+
+/// An error of a request, with its details
+#[derive(Debug)]
+struct ApiError {
+    kind: ErrorKind,
+    details: String,
+}
+
+impl ApiError {
+    fn new(kind: ErrorKind, details: impl Display) -> Self {
+        Self {
+            kind,
+            details: details.to_string(),
+        }
+    }
+
+    fn invalid_request(details: impl Display) -> Self {
+        Self::new(ErrorKind::InvalidRequest, details)
+    }
+
+    fn node(details: impl Display) -> Self {
+        Self::new(ErrorKind::NodeUnavailable, details)
+    }
+}
+
+type Result<T> = std::result::Result<T, ApiError>;
+
+/// The state shared by the handlers of the requests
+#[derive(Clone)]
+struct Context {
+    client: HttpClient,
+    chain_id: ChainId,
+    archive_mode: bool,
+}
+
+impl Context {
+    /// The identifier of the network of the node
+    fn network(&self) -> NetworkIdentifier {
+        NetworkIdentifier {
+            blockchain: BLOCKCHAIN.to_string(),
+            network: self.chain_id.to_string(),
+        }
+    }
+
+    /// Check that a request is made for the network of the node
+    fn check_network(&self, network: &NetworkIdentifier) -> Result<()> {
+        if *network == self.network() {
+            Ok(())
+        } else {
+            Err(ApiError::new(
+                ErrorKind::UnsupportedNetwork,
+                format!(
+                    "Expected the network {}/{}",
+                    BLOCKCHAIN, self.chain_id
+                ),
+            ))
+        }
+    }
+}
+
+/// Serve the Rosetta API on the given address, using the RPC of the CometBFT
+/// node at `rpc_address` and with the given policy, until a signal is sent on
+/// `abort_recv`.
+pub async fn serve(
+    listen_addr: SocketAddr,
+    rpc_address: SocketAddr,
+    chain_id: ChainId,
+    archive_mode: bool,
+    policy: Policy,
+    abort_recv: tokio::sync::oneshot::Receiver<()>,
+) {
+    let client = HttpClient::new(format!("http://{rpc_address}").as_str())
+        .expect("Failed to create the CometBFT RPC client");
+    let ctx = Context {
+        client,
+        chain_id,
+        archive_mode,
+    };
+    let api = warp::post()
+        .and(warp::path::full())
+        .and(warp::body::json())
+        .then(move |path: FullPath, body: Value| {
+            let ctx = ctx.clone();
+            async move { route(ctx, path.as_str(), body).await }
+        });
+
+    tracing::info!(?listen_addr, "Starting the Rosetta API");
+    let (_, server) = warp::serve(policy.apply(api))
+        .bind_with_graceful_shutdown(listen_addr, async move {
+            if abort_recv.await.is_err() {
+                tracing::error!(
+                    "The Rosetta API abort sender has unexpectedly dropped"
+                );
+            }
+            tracing::info!("Shutting down the Rosetta API...");
+        });
+    server.await
+}
+
+/// Dispatch a request to the handler of its endpoint
+async fn route(ctx: Context, path: &str, body: Value) -> WithStatus<Json> {
+    match path {
+        "/network/list" => handle(ctx, body, data::network_list).await,
+        "/network/options" => handle(ctx, body, data::network_options).await,
+        "/network/status" => handle(ctx, body, data::network_status).await,
+        "/account/balance" => handle(ctx, body, data::account_balance).await,
+        "/block" => handle(ctx, body, data::block).await,
+        "/block/transaction" => {
+            handle(ctx, body, data::block_transaction).await
+        }
+        "/mempool" => handle(ctx, body, data::mempool).await,
+        "/construction/derive" => handle(ctx, body, construction::derive).await,
+        "/construction/preprocess" => {
+            handle(ctx, body, construction::preprocess).await
+        }
+        "/construction/metadata" => {
+            handle(ctx, body, construction::metadata).await
+        }
+        "/construction/payloads" => {
+            handle(ctx, body, construction::payloads).await
+        }
+        "/construction/combine" => {
+            handle(ctx, body, construction::combine).await
+        }
+        "/construction/parse" => handle(ctx, body, construction::parse).await,
+        "/construction/hash" => handle(ctx, body, construction::hash).await,
+        "/construction/submit" => handle(ctx, body, construction::submit).await,
+        _ => warp::reply::with_status(
+            warp::reply::json(
+                &ErrorKind::InvalidRequest
+                    .to_model(Some(format!("Unknown endpoint {path}"))),
+            ),
+            StatusCode::NOT_FOUND,
+        ),
+    }
+}
+
+/// Decode a request, handle it and encode its response or its error
+async fn handle<Req, Res, F, Fut>(
+    ctx: Context,
+    body: Value,
+    handler: F,
+) -> WithStatus<Json>
+where
+    Req: DeserializeOwned,
+    Res: Serialize,
+    F: FnOnce(Context, Req) -> Fut,
+    Fut: Future<Output = Result<Res>>,
+{
+    let result = match serde_json::from_value(body) {
+        Ok(request) => handler(ctx, request).await,
+        Err(err) => Err(ApiError::invalid_request(err)),
+    };
+    match result {
+        Ok(response) => warp::reply::with_status(
+            warp::reply::json(&response),
+            StatusCode::OK,
+        ),
+        Err(err) => {
+            tracing::debug!(?err, "Rosetta API request failed");
+            warp::reply::with_status(
+                warp::reply::json(&err.kind.to_model(Some(err.details))),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+        }
+    }
+}
+
+/// The currency of the native token
+fn native_currency() -> Currency {
+    Currency {
+        symbol: NATIVE_SYMBOL.to_string(),
+        decimals: NATIVE_MAX_DECIMAL_PLACES.into(),
+        metadata: None,
+    }
+}
+
+/// A signed amount of the native token, in its smallest unit
+fn native_amount(amount: token::Amount, debit: bool) -> Amount {
+    let sign = if debit && !amount.is_zero() { "-" } else { "" };
+    Amount {
+        value: format!("{sign}{amount}"),
+        currency: native_currency(),
+    }
+}
+
+/// Parse a signed amount of the native token into its absolute value and
+/// whether it is a debit
+fn parse_native_amount(amount: &Amount) -> Result<(token::Amount, bool)> {
+    if amount.currency != native_currency() {
+        return Err(ApiError::new(
+            ErrorKind::UnsupportedOperations,
+            format!(
+                "Only the native token {NATIVE_SYMBOL} is supported, got {}",
+                amount.currency.symbol
+            ),
+        ));
+    }
+    let (value, debit) = match amount.value.strip_prefix('-') {
+        Some(value) => (value, true),
+        None => (amount.value.as_str(), false),
+    };
+    let value = token::Amount::from_str(value, 0u8).map_err(|err| {
+        ApiError::invalid_request(format!(
+            "Invalid amount {}: {err}",
+            amount.value
+        ))
+    })?;
+    Ok((value, debit))
+}
+
+/// The operations of a transaction, indexed in the order they are pushed
+#[derive(Debug, Default)]
+struct Operations(Vec<Operation>);
+
+impl Operations {
+    /// Push an operation of an account and return its identifier
+    fn push(
+        &mut self,
+        r#type: &str,
+        status: Option<&str>,
+        address: &Address,
+        amount: Option<Amount>,
+        metadata: Option<Value>,
+    ) -> OperationIdentifier {
+        let operation_identifier = OperationIdentifier {
+            index: self.0.len() as i64,
+        };
+        self.0.push(Operation {
+            operation_identifier,
+            related_operations: vec![],
+            r#type: r#type.to_string(),
+            status: status.map(ToString::to_string),
+            account: Some(account(address)),
+            amount,
+            metadata,
+        });
+        operation_identifier
+    }
+
+    /// Push the debit of the source and the related credit of the target of
+    /// a transfer of the native token
+    fn push_transfer(
+        &mut self,
+        r#type: &str,
+        status: Option<&str>,
+        source: &Address,
+        target: &Address,
+        amount: token::Amount,
+    ) {
+        let debit = self.push(
+            r#type,
+            status,
+            source,
+            Some(native_amount(amount, true)),
+            None,
+        );
+        self.push(
+            r#type,
+            status,
+            target,
+            Some(native_amount(amount, false)),
+            None,
+        );
+        if let Some(credit) = self.0.last_mut() {
+            credit.related_operations.push(debit);
+        }
+    }
+}
+
+/// The tag of the code of a transaction, if any
+fn code_tag(tx: &Tx) -> Option<String> {
+    tx.get_section(tx.code_sechash())?.code_sec()?.tag
+}
+
+/// The identifier of an account
+fn account(address: &Address) -> AccountIdentifier {
+    AccountIdentifier {
+        address: address.to_string(),
+    }
+}
+
+/// Parse the address of an account
+fn parse_account(account: &AccountIdentifier) -> Result<Address> {
+    Address::from_str(&account.address).map_err(|err| {
+        ApiError::invalid_request(format!(
+            "Invalid address {}: {err}",
+            account.address
+        ))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use namada::address::testing::established_address_1;
+
+    use super::*;
+
+    /// Test the conversions of the native token amounts and of the accounts
+    #[test]
+    fn test_native_amounts_and_accounts() {
+        let amount = token::Amount::from_u64(1_500_000);
+        let debit = native_amount(amount, true);
+        assert_eq!(debit.value, "-1500000");
+        assert_eq!(debit.currency.decimals, 6);
+        assert_eq!(parse_native_amount(&debit).unwrap(), (amount, true));
+
+        let credit = native_amount(amount, false);
+        assert_eq!(credit.value, "1500000");
+        assert_eq!(parse_native_amount(&credit).unwrap(), (amount, false));
+
+        // Zero amounts have no sign
+        assert_eq!(native_amount(token::Amount::zero(), true).value, "0");
+
+        let other = Amount {
+            currency: Currency {
+                symbol: "BTC".to_string(),
+                ..native_currency()
+            },
+            ..credit.clone()
+        };
+        assert_eq!(
+            parse_native_amount(&other).unwrap_err().kind,
+            ErrorKind::UnsupportedOperations
+        );
+        let invalid = Amount {
+            value: "1.5".to_string(),
+            ..credit
+        };
+        assert_eq!(
+            parse_native_amount(&invalid).unwrap_err().kind,
+            ErrorKind::InvalidRequest
+        );
+
+        let address = established_address_1();
+        assert_eq!(parse_account(&account(&address)).unwrap(), address);
+        assert!(parse_account(&AccountIdentifier {
+            address: "not an address".to_string()
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,422 @@
+//! The CORS, authentication and limits policy shared by the HTTP services of
+//! the node.
+//!
+//! The cross-origin requests are allowed from the configured origins only,
+//! and the requests to the authenticated services must carry the configured
+//! bearer token, otherwise they are rejected with `401 Unauthorized` before
+//! reaching the service. The CORS preflight requests don't require the token.
+//!
+//! The requests of every IP address may be rate limited, in which case the
+//! requests over the limit are rejected with `429 Too Many Requests`, and the
+//! bodies of the requests larger than the configured size are rejected with
+//! `413 Payload Too Large`.
+
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use namada::core::collections::HashMap;
+use warp::filters::BoxedFilter;
+use warp::http::{header, StatusCode, Uri};
+use warp::reply::Response;
+use warp::{Filter, Rejection, Reply};
+
+use crate::config::{ServiceFlags, ServicesConfig};
+
+/// The window of the rate limits
+pub const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// The routes of a service with its policy applied
+pub type Routes = BoxedFilter<(Response,)>;
+
+/// The policy of a service
+#[derive(Clone, Debug)]
+pub struct Policy {
+    /// The origins allowed to make cross-origin requests
+    cors_origins: Vec<String>,
+    /// The bearer token required by the service, if any
+    auth_token: Option<String>,
+    /// The rate limit of the requests of every IP address, if any
+    rate_limiter: Option<Arc<Mutex<RateLimiter>>>,
+    /// The maximum size of the body of a request
+    max_request_bytes: u64,
+}
+
+impl Policy {
+    /// The policy of a service with the given flags. A sensitive service
+    /// always requires the token, when it is configured.
+    pub fn new(
+        config: &ServicesConfig,
+        flags: &ServiceFlags,
+        sensitive: bool,
+    ) -> Self {
+        let auth_token = if sensitive || flags.auth {
+            config.auth_token.clone().filter(|token| !token.is_empty())
+        } else {
+            None
+        };
+        let rate_limiter = config.rate_limit.map(|limit| {
+            Arc::new(Mutex::new(RateLimiter::new(limit, RATE_LIMIT_WINDOW)))
+        });
+        Self {
+            cors_origins: config.cors_origins.clone(),
+            auth_token,
+            rate_limiter,
+            max_request_bytes: config.max_request_bytes,
+        }
+    }
+
+    /// Apply the policy to the routes of a service
+    pub fn apply<F, R>(self, routes: F) -> Routes
+    where
+        F: Filter<Extract = (R,), Error = Rejection>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        R: Reply,
+    {
+        let routes = limit_rate(self.rate_limiter)
+            .and(authorize(self.auth_token))
+            .and(limit_body(self.max_request_bytes))
+            .and(routes)
+            .map(Reply::into_response)
+            .recover(recover_rejection)
+            .unify();
+        if self.cors_origins.is_empty() {
+            return routes.boxed();
+        }
+        let cors = warp::cors().allow_methods(["GET", "POST"]).allow_headers([
+            header::AUTHORIZATION.as_str(),
+            header::CONTENT_TYPE.as_str(),
+            "last-event-id",
+        ]);
+        let cors = if self.cors_origins.iter().any(|origin| origin == "*") {
+            cors.allow_any_origin()
+        } else {
+            cors.allow_origins(self.cors_origins.iter().filter_map(|origin| {
+                if is_valid_origin(origin) {
+                    Some(origin.as_str())
+                } else {
+                    tracing::warn!("Ignoring the invalid CORS origin {origin}");
+                    None
+                }
+            }))
+        };
+        routes.with(cors).map(Reply::into_response).boxed()
+    }
+}
+
+/// Limits the number of requests of every IP address in a time window
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    requests: HashMap<IpAddr, (Instant, u32)>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            requests: HashMap::default(),
+        }
+    }
+
+    /// Record a request at the given time and check that it is allowed
+    pub fn check(&mut self, ip: IpAddr, now: Instant) -> bool {
+        let window = self.window;
+        self.requests
+            .retain(|_, (start, _)| now.duration_since(*start) < window);
+        let (_, count) = self.requests.entry(ip).or_insert((now, 0));
+        if *count >= self.limit {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+}
+
+/// The rejection of a request without the required token
+#[derive(Debug)]
+struct Unauthorized;
+
+impl warp::reject::Reject for Unauthorized {}
+
+/// The rejection of a request over the rate limit
+#[derive(Debug)]
+struct TooManyRequests;
+
+impl warp::reject::Reject for TooManyRequests {}
+
+/// Reject the requests of the IP addresses over the rate limit, if any
+fn limit_rate(
+    rate_limiter: Option<Arc<Mutex<RateLimiter>>>,
+) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::addr::remote()
+        .and_then(move |remote: Option<SocketAddr>| {
+            let rate_limiter = rate_limiter.clone();
+            async move {
+                let allowed = match (rate_limiter, remote) {
+                    (Some(rate_limiter), Some(remote)) => {
+                        #[allow(clippy::disallowed_methods)]
+                        let now = Instant::now();
+                        rate_limiter
+                            .lock()
+                            .expect(
+                                "The rate limiter lock should not be poisoned",
+                            )
+                            .check(remote.ip(), now)
+                    }
+                    _ => true,
+                };
+                if allowed {
+                    Ok(())
+                } else {
+                    Err(warp::reject::custom(TooManyRequests))
+                }
+            }
+        })
+        .untuple_one()
+}
+
+/// Reject the `POST` requests whose body is larger than the limit or has an
+/// unknown length
+fn limit_body(
+    max_bytes: u64,
+) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::get()
+        .or(warp::body::content_length_limit(max_bytes))
+        .unify()
+}
+
+/// Reject the requests that don't carry the token, if one is required
+fn authorize(
+    token: Option<String>,
+) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::header::optional::<String>(header::AUTHORIZATION.as_str())
+        .and_then(move |authorization: Option<String>| {
+            let token = token.clone();
+            async move {
+                let Some(token) = token else {
+                    return Ok(());
+                };
+                let authorized = authorization
+                    .as_deref()
+                    .and_then(|value| value.strip_prefix("Bearer "))
+                    .is_some_and(|given| {
+                        constant_time_eq(given.as_bytes(), token.as_bytes())
+                    });
+                if authorized {
+                    Ok(())
+                } else {
+                    Err(warp::reject::custom(Unauthorized))
+                }
+            }
+        })
+        .untuple_one()
+}
+
+/// Reply to the requests rejected by the policy
+async fn recover_rejection(
+    rejection: Rejection,
+) -> Result<Response, Rejection> {
+    if rejection.find::<Unauthorized>().is_some() {
+        Ok(warp::reply::with_status(
+            warp::reply::with_header(
+                "Unauthorized",
+                header::WWW_AUTHENTICATE,
+                "Bearer",
+            ),
+            StatusCode::UNAUTHORIZED,
+        )
+        .into_response())
+    } else if rejection.find::<TooManyRequests>().is_some() {
+        Ok(warp::reply::with_status(
+            "Too many requests, try again later",
+            StatusCode::TOO_MANY_REQUESTS,
+        )
+        .into_response())
+    } else {
+        Err(rejection)
+    }
+}
+
+/// Compare the tokens in a time independent of their common prefix
+fn constant_time_eq(given: &[u8], expected: &[u8]) -> bool {
+    given.len() == expected.len()
+        && given
+            .iter()
+            .zip(expected)
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Check that an origin is a scheme and a host, with an optional port
+fn is_valid_origin(origin: &str) -> bool {
+    Uri::from_str(origin).is_ok_and(|uri| {
+        uri.scheme().is_some()
+            && uri.authority().is_some()
+            && matches!(uri.path(), "" | "/")
+            && uri.query().is_none()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn services_config(
+        cors_origins: &[&str],
+        auth_token: Option<&str>,
+    ) -> ServicesConfig {
+        ServicesConfig {
+            cors_origins: cors_origins
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            auth_token: auth_token.map(ToString::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn routes(policy: Policy) -> Routes {
+        policy.apply(warp::path!("ping").map(|| "pong"))
+    }
+
+    /// Test that the sensitive services and the services with the `auth` flag
+    /// require the token
+    #[tokio::test]
+    async fn test_auth() {
+        let config = services_config(&[], Some("secret"));
+        let public = routes(Policy::new(&config, &config.health, false));
+        let res = warp::test::request().path("/ping").reply(&public).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let sensitive =
+            routes(Policy::new(&config, &config.shielded_query, true));
+        let res = warp::test::request().path("/ping").reply(&sensitive).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        let res = warp::test::request()
+            .path("/ping")
+            .header("authorization", "Bearer wrong")
+            .reply(&sensitive)
+            .await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        let res = warp::test::request()
+            .path("/ping")
+            .header("authorization", "Bearer secret")
+            .reply(&sensitive)
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.body(), "pong");
+
+        let flags = ServiceFlags {
+            enabled: true,
+            auth: true,
+        };
+        let authenticated = routes(Policy::new(&config, &flags, false));
+        let res = warp::test::request()
+            .path("/ping")
+            .reply(&authenticated)
+            .await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        // Without a token, no service requires one
+        let config = services_config(&[], None);
+        let sensitive =
+            routes(Policy::new(&config, &config.shielded_query, true));
+        let res = warp::test::request().path("/ping").reply(&sensitive).await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    /// Test that the cross-origin requests are only allowed from the
+    /// configured origins
+    #[tokio::test]
+    async fn test_cors() {
+        let config =
+            services_config(&["https://wallet.example", "not an origin"], None);
+        let routes = routes(Policy::new(&config, &config.health, false));
+        let res = warp::test::request()
+            .path("/ping")
+            .header("origin", "https://wallet.example")
+            .reply(&routes)
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            res.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://wallet.example"
+        );
+        let res = warp::test::request()
+            .path("/ping")
+            .header("origin", "https://other.example")
+            .reply(&routes)
+            .await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        assert!(is_valid_origin("http://localhost:3000"));
+        assert!(!is_valid_origin("wallet.example"));
+        assert!(!is_valid_origin("https://wallet.example/path"));
+    }
+
+    /// Test that the requests of every IP address are limited per window
+    #[test]
+    fn test_rate_limiter() {
+        let window = Duration::from_secs(60);
+        let mut limiter = RateLimiter::new(2, window);
+        let alice = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let bob = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+        #[allow(clippy::disallowed_methods)]
+        let start = Instant::now();
+
+        assert!(limiter.check(alice, start));
+        assert!(limiter.check(alice, start));
+        assert!(!limiter.check(alice, start + Duration::from_secs(1)));
+        // Other addresses have their own limit
+        assert!(limiter.check(bob, start + Duration::from_secs(1)));
+        // The limit is reset after the window
+        assert!(limiter.check(alice, start + window));
+    }
+
+    /// Test that the requests over the rate limit and the requests with a
+    /// body over the size limit are rejected
+    #[tokio::test]
+    async fn test_limits() {
+        let config = ServicesConfig {
+            rate_limit: Some(1),
+            max_request_bytes: 4,
+            ..Default::default()
+        };
+        let routes = routes(Policy::new(&config, &config.rosetta, false));
+        let alice = SocketAddr::from(([10, 0, 0, 1], 26660));
+        let bob = SocketAddr::from(([10, 0, 0, 2], 26660));
+
+        let res = warp::test::request()
+            .method("POST")
+            .path("/ping")
+            .remote_addr(alice)
+            .body("ping")
+            .reply(&routes)
+            .await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let res = warp::test::request()
+            .path("/ping")
+            .remote_addr(alice)
+            .reply(&routes)
+            .await;
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        let res = warp::test::request()
+            .method("POST")
+            .path("/ping")
+            .remote_addr(bob)
+            .body("ping!")
+            .reply(&routes)
+            .await;
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
@@ -0,0 +1,135 @@
+//! Block proposer contributions to the block seed.
+//!
+//! The seed itself is implemented in [`namada::proof_of_stake::block_seed`],
+//! which also documents why it is not an unbiasable source of randomness.
+//! This module only takes care of building, validating and applying the
+//! protocol txs that carry the proposers' contributions.
+
+use namada::hash::Hash;
+use namada::proof_of_stake::block_seed::{
+    make_block_seed_contribution, update_block_seed,
+    validate_block_seed_contribution, BlockSeedContribution,
+};
+use namada::state::LastBlock;
+use namada::tx::data::protocol::{ProtocolTx, ProtocolTxType};
+use namada::tx::{Authorization, Data};
+
+use super::*;
+use crate::node::ledger::shims::abcipp_shim_types::shim::request::ProcessedTx;
+use crate::node::ledger::shims::abcipp_shim_types::shim::TxBytes;
+
+impl<D, H> Shell<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// Get the hash of the last block decided through consensus, if any.
+    pub(super) fn last_block_hash(&self) -> Option<Hash> {
+        let LastBlock { height, .. } =
+            self.state.in_mem().last_block.as_ref()?;
+        if *height == BlockHeight(0) {
+            // the genesis state is not decided through consensus
+            return None;
+        }
+        self.state
+            .db()
+            .read_block_header(*height)
+            .expect("Must be able to read the last block header")
+            .map(|header| header.hash)
+    }
+
+    /// Build the protocol tx carrying this validator's contribution to the
+    /// seed of the block being proposed, if possible.
+    pub(super) fn build_block_seed_tx(&self) -> Option<TxBytes> {
+        let protocol_sk = self.mode.get_protocol_key()?;
+        let last_block_hash = self.last_block_hash()?;
+        let contribution = make_block_seed_contribution(
+            &self.state,
+            protocol_sk,
+            self.get_current_decision_height(),
+            &last_block_hash,
+        )
+        .map_err(|err| {
+            tracing::error!(
+                ?err,
+                "Failed to compute the block seed contribution"
+            );
+        })
+        .ok()?;
+
+        let mut tx = Tx::from_type(TxType::Protocol(Box::new(ProtocolTx {
+            pk: protocol_sk.to_public(),
+            tx: ProtocolTxType::BlockSeed,
+        })));
+        tx.header.chain_id = self.chain_id.clone();
+        tx.set_data(Data::new(contribution.serialize_to_vec()));
+        tx.add_section(Section::Authorization(Authorization::new(
+            tx.sechashes(),
+            [(0, protocol_sk.clone())].into_iter().collect(),
+            None,
+        )));
+        Some(tx.to_bytes().into())
+    }
+
+    /// Validate a block seed protocol tx included in a proposal by the
+    /// given block proposer.
+    pub(super) fn validate_block_seed_tx(
+        &self,
+        tx: &Tx,
+        block_proposer: &Address,
+    ) -> std::result::Result<(), String> {
+        let last_block_hash = self.last_block_hash().ok_or_else(|| {
+            "The block seed cannot be contributed to before the first \
+             block"
+                .to_string()
+        })?;
+        let contribution = read_block_seed_contribution(tx)?;
+        validate_block_seed_contribution(
+            &self.state,
+            &contribution,
+            block_proposer,
+            self.get_current_decision_height(),
+            &last_block_hash,
+        )
+        .map_err(|err| err.to_string())
+    }
+
+    /// Derive the seed of the block being finalized from the
+    /// proposer's contribution included in its txs, if any.
+    pub(super) fn update_block_seed(
+        &mut self,
+        txs: &[ProcessedTx],
+    ) -> Result<Hash> {
+        let contribution = txs.iter().find_map(|processed_tx| {
+            if processed_tx.result.code != u32::from(ResultCode::Ok) {
+                return None;
+            }
+            let tx = Tx::try_from(processed_tx.tx.as_ref()).ok()?;
+            match tx.header().tx_type {
+                TxType::Protocol(protocol_tx)
+                    if matches!(protocol_tx.tx, ProtocolTxType::BlockSeed) =>
+                {
+                    read_block_seed_contribution(&tx).ok()
+                }
+                _ => None,
+            }
+        });
+        let last_block_hash = self.last_block_hash().unwrap_or_default();
+        Ok(update_block_seed(
+            &mut self.state,
+            &last_block_hash,
+            contribution.as_ref(),
+        )?)
+    }
+}
+
+/// Deserialize the seed contribution carried by a protocol tx.
+fn read_block_seed_contribution(
+    tx: &Tx,
+) -> std::result::Result<BlockSeedContribution, String> {
+    let data = tx.data().ok_or_else(|| {
+        "Expected the block seed protocol tx to carry data".to_string()
+    })?;
+    BlockSeedContribution::try_from_slice(&data)
+        .map_err(|err| format!("Invalid block seed contribution: {err}"))
+}
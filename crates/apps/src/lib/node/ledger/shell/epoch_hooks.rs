@@ -0,0 +1,422 @@
+//! Application of the accounts' epoch hooks.
+//!
+//! An account may register an [`EpochHook`], a signed tx that is applied at
+//! the beginning of every epoch. The hook's tx is applied like any other inner
+//! tx, hence it must be authorized by the owner's VP (and the VPs of any other
+//! account it touches). Before it's applied:
+//!
+//! - its chain ID and expiration are checked,
+//! - its code must be the hash of a tx code in the `epoch_hook_allowlist`
+//!   parameter,
+//! - its gas limit must fit in what's left of the `max_epoch_hooks_gas`
+//!   parameter for the current epoch,
+//! - its owner pays the gas limit at the minimum gas price of the native token
+//!   to the block proposer.
+//!
+//! A hook that doesn't satisfy these conditions is skipped, but remains
+//! registered. At most [`MAX_EPOCH_HOOKS_PER_EPOCH`] hooks are visited in an
+//! epoch. If more accounts registered one, the following epochs resume after
+//! the owner of the last visited hook. A hook that fails to be applied, e.g.
+//! because of a storage error, is skipped without affecting the other hooks.
+
+use namada::account::{read_epoch_hook, EpochHook};
+use namada::parameters::is_epoch_hook_allowed;
+use namada::parameters::storage::{
+    epoch_hook_owners_handle, epoch_hooks_cursor_key, get_max_epoch_hooks_gas,
+};
+use namada::state::{in_batch, StorageResult, StorageWrite};
+use namada::tx::data::wrapper::GasLimit;
+use namada::tx::Commitment;
+
+use super::*;
+
+/// The maximum number of epoch hooks visited at the beginning of an epoch
+pub const MAX_EPOCH_HOOKS_PER_EPOCH: usize = 128;
+
+impl<D, H> Shell<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// Apply the epoch hooks of the next accounts that registered one, in the
+    /// order of their addresses, until the epoch hooks gas cap is exhausted.
+    pub(super) fn apply_epoch_hooks(
+        &mut self,
+        block_proposer: &Address,
+    ) -> Result<()> {
+        let owners = self.next_epoch_hook_owners()?;
+        let mut remaining_gas = get_max_epoch_hooks_gas(&self.state)?;
+        for owner in owners {
+            // A failure only skips the hook of this owner
+            match self.apply_epoch_hook(&owner, remaining_gas, block_proposer) {
+                Ok(gas_used) => {
+                    remaining_gas = remaining_gas.saturating_sub(gas_used);
+                }
+                Err(err) => {
+                    tracing::error!(%owner, %err, "Failed to apply epoch hook");
+                    self.state.drop_tx();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Find the owners of the epoch hooks to visit in the current epoch,
+    /// resuming after the last owner visited in the previous epoch, and
+    /// advance the cursor.
+    fn next_epoch_hook_owners(&mut self) -> Result<Vec<Address>> {
+        let handle = epoch_hook_owners_handle();
+        let cursor_key = epoch_hooks_cursor_key();
+        // The storage key of the last visited owner, in the iteration order
+        let cursor: Option<String> = self.state.read(&cursor_key)?;
+        let mut owners = Vec::new();
+        let mut last_owner_key = None;
+        for entry in handle.iter(&self.state)? {
+            let owner = match entry {
+                Ok(owner) => owner,
+                Err(err) => {
+                    tracing::error!(%err, "Failed to read an epoch hook owner");
+                    continue;
+                }
+            };
+            let owner_key = handle.get_key(&owner).to_string();
+            if cursor.as_ref().is_some_and(|cursor| owner_key <= *cursor) {
+                continue;
+            }
+            owners.push(owner);
+            last_owner_key = Some(owner_key);
+            if owners.len() == MAX_EPOCH_HOOKS_PER_EPOCH {
+                break;
+            }
+        }
+        match last_owner_key {
+            // Resume after the last owner in the next epoch
+            Some(owner_key) if owners.len() == MAX_EPOCH_HOOKS_PER_EPOCH => {
+                self.state.write(&cursor_key, owner_key)?;
+            }
+            // All the hooks have been visited, start over in the next epoch
+            _ if cursor.is_some() => self.state.delete(&cursor_key)?,
+            _ => {}
+        }
+        Ok(owners)
+    }
+
+    /// Apply the epoch hook of the given owner, if it can be applied with the
+    /// remaining gas of the epoch hooks. Returns the gas charged to the owner.
+    fn apply_epoch_hook(
+        &mut self,
+        owner: &Address,
+        remaining_gas: u64,
+        block_proposer: &Address,
+    ) -> Result<u64> {
+        let Some(hook) = read_epoch_hook(&self.state, owner)? else {
+            return Ok(0);
+        };
+        if hook.gas_limit > remaining_gas {
+            tracing::info!(
+                %owner,
+                gas_limit = hook.gas_limit,
+                remaining_gas,
+                "Skipping epoch hook exceeding the epoch hooks gas cap"
+            );
+            return Ok(0);
+        }
+        let tx = match self.check_epoch_hook(&hook) {
+            Ok(tx) => tx,
+            Err(msg) => {
+                tracing::info!(%owner, "Skipping epoch hook: {msg}");
+                return Ok(0);
+            }
+        };
+        // The fee is only transferred if the whole transfer succeeds
+        let is_charged = in_batch(&mut self.state, |state| {
+            charge_epoch_hook(state, owner, &hook, block_proposer)
+        })?;
+        if !is_charged {
+            tracing::info!(
+                %owner,
+                "Skipping epoch hook whose owner cannot pay for its gas"
+            );
+            return Ok(0);
+        }
+
+        let tx_result = protocol::dispatch_tx(
+            tx,
+            &[], /* this is used to compute the fee based on the code
+                  * size. We dont need it here. */
+            TxIndex::default(),
+            &RefCell::new(TxGasMeter::new(GasLimit::from(hook.gas_limit))),
+            &mut self.state,
+            &mut self.vp_wasm_cache,
+            &mut self.tx_wasm_cache,
+            None,
+        );
+        match tx_result {
+            Ok(tx_result) if tx_result.is_accepted() => {
+                tracing::info!(%owner, "Applied epoch hook");
+                self.state.commit_tx();
+            }
+            Ok(tx_result) => {
+                tracing::info!(
+                    %owner,
+                    ?tx_result,
+                    "Epoch hook rejected by the VPs"
+                );
+                self.state.drop_tx();
+            }
+            Err(err) => {
+                tracing::info!(%owner, %err, "Failed to apply epoch hook");
+                self.state.drop_tx();
+            }
+        }
+        Ok(hook.gas_limit)
+    }
+
+    /// Decode the tx of an epoch hook and check that it can be applied in the
+    /// current block.
+    fn check_epoch_hook(
+        &self,
+        hook: &EpochHook,
+    ) -> std::result::Result<Tx, String> {
+        let tx = Tx::try_from(hook.tx.as_ref())
+            .map_err(|err| format!("Invalid tx: {err}"))?;
+        if !matches!(tx.header().tx_type, TxType::Raw) {
+            return Err("Only raw txs can be used as epoch hooks".to_string());
+        }
+        if tx.header().chain_id != self.chain_id {
+            return Err(format!(
+                "Wrong chain id: expected {}, found {}",
+                self.chain_id,
+                tx.header().chain_id
+            ));
+        }
+        let block_time = self
+            .state
+            .in_mem()
+            .header
+            .as_ref()
+            .map(|header| header.time);
+        if let (Some(exp), Some(block_time)) =
+            (tx.header().expiration, block_time)
+        {
+            if block_time > exp {
+                return Err(format!(
+                    "Tx expired at {:#?}, block time: {:#?}",
+                    exp, block_time
+                ));
+            }
+        }
+        let code_hash = match tx
+            .get_section(tx.code_sechash())
+            .as_ref()
+            .map(|section| section.as_ref())
+        {
+            Some(Section::Code(code)) => match &code.code {
+                Commitment::Hash(hash) => *hash,
+                Commitment::Id(_) => {
+                    return Err("The tx code must be committed to by its hash"
+                        .to_string());
+                }
+            },
+            _ => return Err("Missing tx code".to_string()),
+        };
+        match is_epoch_hook_allowed(&self.state, &code_hash) {
+            Ok(true) => Ok(tx),
+            Ok(false) => Err(format!(
+                "Tx code {code_hash} is not allowed in epoch hooks"
+            )),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+}
+
+/// Transfer the gas fee of an epoch hook from its owner to the block proposer.
+/// Returns `false` if the owner's balance is insufficient.
+fn charge_epoch_hook<S>(
+    state: &mut S,
+    owner: &Address,
+    hook: &EpochHook,
+    block_proposer: &Address,
+) -> StorageResult<bool>
+where
+    S: StorageRead + StorageWrite,
+{
+    let native_token = state.get_native_token()?;
+    let gas_price =
+        parameters::read_gas_cost(state, &native_token)?.unwrap_or_default();
+    let Some(fee) = gas_price
+        .checked_mul(token::Amount::from(GasLimit::from(hook.gas_limit)))
+    else {
+        return Ok(false);
+    };
+    let balance = token::read_balance(state, &native_token, owner)?;
+    if balance < fee {
+        return Ok(false);
+    }
+    token::transfer(state, &native_token, owner, block_proposer, fee)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test_epoch_hooks {
+    use namada::account::epoch_hook_key;
+    use namada::core::hash::Hash;
+    use namada::parameters::update_epoch_hook_allowlist_parameter;
+    use namada::state::StorageWrite;
+    use namada::tx::Code;
+
+    use super::*;
+    use crate::node::ledger::shell::test_utils::{self, TestShell};
+
+    /// Register an epoch hook for the given owner, whose tx code is committed
+    /// to by the given hash.
+    fn register_hook(
+        shell: &mut TestShell,
+        owner: &Address,
+        code_hash: Hash,
+        gas_limit: u64,
+    ) {
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.header.chain_id = shell.chain_id.clone();
+        tx.set_code(Code::from_hash(code_hash, None));
+        let hook = EpochHook {
+            tx: tx.to_bytes(),
+            gas_limit,
+        };
+        shell.state.write(&epoch_hook_key(owner), hook).unwrap();
+        epoch_hook_owners_handle()
+            .insert(&mut shell.state, owner.clone())
+            .unwrap();
+        shell.state.commit_tx();
+    }
+
+    /// Test that only the hooks of allowlisted tx codes are accepted
+    #[test]
+    fn test_epoch_hook_allowlist() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        let owner = namada::core::address::testing::established_address_1();
+        let code_hash = Hash::sha256(b"hook code");
+        register_hook(&mut shell, &owner, code_hash, 1);
+        let hook = read_epoch_hook(&shell.state, &owner).unwrap().unwrap();
+
+        // The allowlist is empty by default
+        assert!(shell.check_epoch_hook(&hook).is_err());
+
+        update_epoch_hook_allowlist_parameter(
+            &mut shell.state,
+            vec![code_hash.to_string()],
+        )
+        .unwrap();
+        assert!(shell.check_epoch_hook(&hook).is_ok());
+    }
+
+    /// Test that the hooks visited in an epoch are capped, and that the
+    /// following epochs resume after the last visited owner
+    #[test]
+    fn test_epoch_hooks_cap() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        let code_hash = Hash::sha256(b"hook code");
+        let mut owners: Vec<Address> = (0..=MAX_EPOCH_HOOKS_PER_EPOCH)
+            .map(|_| namada::core::address::testing::gen_established_address())
+            .collect();
+        for owner in &owners {
+            register_hook(&mut shell, owner, code_hash, 1);
+        }
+        owners.sort_by_key(|owner| {
+            epoch_hook_owners_handle().get_key(owner).to_string()
+        });
+
+        let first = shell.next_epoch_hook_owners().unwrap();
+        assert_eq!(first, owners[..MAX_EPOCH_HOOKS_PER_EPOCH]);
+        let second = shell.next_epoch_hook_owners().unwrap();
+        assert_eq!(second, owners[MAX_EPOCH_HOOKS_PER_EPOCH..]);
+        assert!(!shell.state.has_key(&epoch_hooks_cursor_key()).unwrap());
+        let third = shell.next_epoch_hook_owners().unwrap();
+        assert_eq!(third, first);
+    }
+
+    /// Test that a hook that fails to be applied doesn't prevent the other
+    /// hooks from being applied
+    #[test]
+    fn test_epoch_hook_failure() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        let owner_1 = namada::core::address::testing::established_address_1();
+        let owner_2 = namada::core::address::testing::established_address_2();
+        let proposer = namada::core::address::testing::established_address_3();
+        let code_hash = Hash::sha256(b"hook code");
+        update_epoch_hook_allowlist_parameter(
+            &mut shell.state,
+            vec![code_hash.to_string()],
+        )
+        .unwrap();
+        register_hook(&mut shell, &owner_1, code_hash, 1);
+        register_hook(&mut shell, &owner_2, code_hash, 1);
+        let native_token = shell.state.in_mem().native_token.clone();
+        for owner in [&owner_1, &owner_2] {
+            token::credit_tokens(
+                &mut shell.state,
+                &native_token,
+                owner,
+                token::Amount::native_whole(1),
+            )
+            .unwrap();
+        }
+        // Corrupt the hook of the first owner
+        shell
+            .state
+            .write_bytes(&epoch_hook_key(&owner_1), [0xff])
+            .unwrap();
+
+        shell.apply_epoch_hooks(&proposer).unwrap();
+
+        // Only the owner of the valid hook has been charged
+        assert_eq!(
+            token::read_balance(&shell.state, &native_token, &owner_1).unwrap(),
+            token::Amount::native_whole(1)
+        );
+        assert!(
+            token::read_balance(&shell.state, &native_token, &owner_2).unwrap()
+                < token::Amount::native_whole(1)
+        );
+    }
+
+    /// Test that the owner of a hook pays for its gas and that a hook is
+    /// skipped if its owner cannot pay for it.
+    #[test]
+    fn test_charge_epoch_hook() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        let owner = namada::core::address::testing::established_address_1();
+        let proposer = namada::core::address::testing::established_address_2();
+        let native_token = shell.state.in_mem().native_token.clone();
+        let gas_price = parameters::read_gas_cost(&shell.state, &native_token)
+            .unwrap()
+            .unwrap();
+        let hook = EpochHook {
+            tx: vec![],
+            gas_limit: 10,
+        };
+        let fee = gas_price
+            .checked_mul(token::Amount::from(GasLimit::from(10)))
+            .unwrap();
+
+        assert!(
+            !charge_epoch_hook(&mut shell.state, &owner, &hook, &proposer)
+                .unwrap()
+        );
+
+        token::credit_tokens(&mut shell.state, &native_token, &owner, fee)
+            .unwrap();
+        assert!(
+            charge_epoch_hook(&mut shell.state, &owner, &hook, &proposer)
+                .unwrap()
+        );
+        assert!(token::read_balance(&shell.state, &native_token, &owner)
+            .unwrap()
+            .is_zero());
+        assert_eq!(
+            token::read_balance(&shell.state, &native_token, &proposer)
+                .unwrap(),
+            fee
+        );
+    }
+}
@@ -4,30 +4,34 @@ use data_encoding::HEXUPPER;
 use masp_primitives::merkle_tree::CommitmentTree;
 use masp_primitives::sapling::Node;
 use namada::core::storage::{BlockResults, Epoch, Header};
-use namada::gas::event::GasUsed;
+use namada::gas::event::{GasReport, GasUsed};
 use namada::governance::pgf::inflation as pgf_inflation;
 use namada::hash::Hash;
 use namada::ledger::events::extend::{
     ComposeEvent, Height, Info, TxHash, ValidMaspTx,
 };
+use namada::ledger::events::Event;
 use namada::ledger::gas::GasMetering;
 use namada::ledger::ibc;
-use namada::ledger::pos::namada_proof_of_stake;
+use namada::ledger::pos::{namada_proof_of_stake, PosQueries};
 use namada::ledger::protocol::WrapperArgs;
 use namada::proof_of_stake;
 use namada::proof_of_stake::storage::{
-    find_validator_by_raw_hash, write_last_block_proposer_address,
+    find_validator_by_raw_hash, read_consensus_validator_set_addresses,
+    write_last_block_proposer_address,
 };
+use namada::sdk::events::protocol::{EpochTransition, ValueList};
 use namada::sdk::events::EmitEvents;
 use namada::state::write_log::StorageModification;
 use namada::state::{ResultExt, StorageWrite, EPOCH_SWITCH_BLOCKS_DELAY};
 use namada::token::utils::is_masp_tx;
 use namada::tx::data::protocol::ProtocolTxType;
-use namada::tx::data::VpStatusFlags;
-use namada::tx::event::{Code, InnerTx};
+use namada::tx::data::{ErrorCode, VpStatusFlags};
+use namada::tx::event::{Code, InnerTx, TxErrorCode};
 use namada::tx::new_tx_event;
 use namada::vote_ext::ethereum_events::MultiSignedEthEvent;
 use namada::vote_ext::ethereum_tx_data_variants;
+use namada_sdk::queries::{AppliedTxResult, TxResultInfo};
 
 use super::*;
 use crate::facade::tendermint::abci::types::VoteInfo;
@@ -97,15 +101,32 @@ where
         let validator_set_update_epoch =
             self.get_validator_set_update_epoch(current_epoch);
 
+        // Derive the block seed before anything else can read it
+        self.update_block_seed(&req.txs)?;
+
         // Sub-system updates:
         // - Governance - applied first in case a proposal changes any of the
         //   other syb-systems
-        governance::finalize_block(
+        let proposals = governance::finalize_block(
             self,
             emit_events,
             current_epoch,
             new_epoch,
         )?;
+        // - Parameters - the changes scheduled for the new epoch
+        let activated_parameters = if new_epoch {
+            parameters::apply_pending_parameters(
+                &mut self.state,
+                current_epoch,
+            )?
+        } else {
+            Default::default()
+        };
+        // - Protocol version - halt if the new epoch upgraded the protocol
+        //   to a version that this binary doesn't implement
+        if new_epoch {
+            self.check_protocol_version_at_new_epoch()?;
+        }
         // - Token
         token::finalize_block(&mut self.state, emit_events, new_epoch)?;
         // - PoS
@@ -123,7 +144,23 @@ where
 
         if new_epoch {
             // Apply PoS and PGF inflation
+            let native_token = self.state.in_mem().native_token.clone();
+            let supply_before =
+                token::read_total_supply(&self.state, &native_token)?;
             self.apply_inflation(current_epoch, emit_events)?;
+            let supply_after =
+                token::read_total_supply(&self.state, &native_token)?;
+            let inflation =
+                supply_after.checked_sub(supply_before).unwrap_or_default();
+
+            let epoch_transition = self.epoch_transition_event(
+                height,
+                current_epoch,
+                proposals.passed,
+                activated_parameters.into_iter().collect(),
+                inflation,
+            )?;
+            emit_events.emit(epoch_transition);
         }
 
         let mut stats = InternalStats::default();
@@ -139,9 +176,16 @@ where
                 )
         };
 
+        if new_epoch {
+            // Apply the accounts' epoch hooks
+            self.apply_epoch_hooks(&native_block_proposer_address)?;
+        }
+
         // Tracks the accepted transactions
         self.state.in_mem_mut().block.results = BlockResults::default();
         let mut changed_keys = BTreeSet::new();
+        // Tracks the gas used by the wrappers, for the base fee
+        let mut block_gas: u64 = 0;
         for (tx_index, processed_tx) in req.txs.iter().enumerate() {
             let tx = if let Ok(tx) = Tx::try_from(processed_tx.tx.as_ref()) {
                 tx
@@ -214,7 +258,15 @@ where
                     TxType::Wrapper(wrapper) => {
                         stats.increment_wrapper_txs();
                         let tx_event = new_tx_event(&tx, height.0);
-                        let gas_meter = TxGasMeter::new(wrapper.gas_limit);
+                        let gas_subsidy = super::read_gas_subsidy(
+                            &self.state,
+                            &tx,
+                            processed_tx.tx.as_ref(),
+                        )?;
+                        let gas_meter = super::wrapper_gas_meter(
+                            wrapper,
+                            gas_subsidy.as_ref(),
+                        );
                         if let Some(code_sec) = tx
                             .get_section(tx.code_sechash())
                             .and_then(|x| Section::code_sec(x.as_ref()))
@@ -243,7 +295,10 @@ where
                         ProtocolTxType::BridgePoolVext
                         | ProtocolTxType::BridgePool
                         | ProtocolTxType::ValSetUpdateVext
-                        | ProtocolTxType::ValidatorSetUpdate => (
+                        | ProtocolTxType::ValidatorSetUpdate
+                        | ProtocolTxType::BlockSeed
+                        | ProtocolTxType::ValidatorOperationalMetadata
+                        | ProtocolTxType::TelemetryVext => (
                             new_tx_event(&tx, height.0),
                             TxGasMeter::new_from_sub_limit(0.into()),
                             None,
@@ -300,15 +355,19 @@ where
                         }
                     },
                 };
-            let replay_protection_hashes =
-                if matches!(tx_header.tx_type, TxType::Wrapper(_)) {
-                    Some(ReplayProtectionHashes {
-                        raw_header_hash: tx.raw_header_hash(),
-                        header_hash: tx.header_hash(),
-                    })
-                } else {
-                    None
-                };
+            let replay_protection_hashes = match &tx_header.tx_type {
+                TxType::Wrapper(wrapper) => Some(ReplayProtectionHashes {
+                    raw_header_hash: tx.raw_header_hash(),
+                    header_hash: tx.header_hash(),
+                    // The hash of a wrapper declaring an account nonce is not
+                    // recorded
+                    has_header_hash_entry: wrapper.nonce.is_none(),
+                }),
+                _ => None,
+            };
+            let applied_tx_hashes = replay_protection_hashes
+                .as_ref()
+                .map(|hashes| (hashes.header_hash, hashes.raw_header_hash));
             let tx_gas_meter = RefCell::new(tx_gas_meter);
             let tx_result = protocol::dispatch_tx(
                 tx.clone(),
@@ -322,6 +381,11 @@ where
             )
             .map_err(Error::TxApply);
             let tx_gas_meter = tx_gas_meter.into_inner();
+            if wrapper_args.is_some() {
+                block_gas = block_gas.saturating_add(
+                    tx_gas_meter.get_tx_consumed_gas().get_whole_gas_units(),
+                );
+            }
 
             // save the gas cost
             let tx_hash = tx.header_hash();
@@ -329,6 +393,7 @@ where
                 tx_hash,
                 tx_gas_meter.get_tx_consumed_gas().into(),
             );
+            let mut inner_events: Vec<Event> = vec![];
 
             match tx_result {
                 Ok(result) => {
@@ -351,6 +416,15 @@ where
                             result
                         );
 
+                        self.index_tx_history(
+                            height,
+                            tx_index,
+                            tx_hash,
+                            result
+                                .changed_keys
+                                .iter()
+                                .chain(result.wrapper_changed_keys.iter()),
+                        );
                         changed_keys
                             .extend(result.changed_keys.iter().cloned());
                         changed_keys.extend(
@@ -369,11 +443,18 @@ where
                                 .accept(tx_index);
                         }
                         // events from other sources
-                        response.events.emit_many(
-                            result.events.iter().map(|event| {
-                                event.clone().with(Height(height))
-                            }),
-                        );
+                        inner_events = result
+                            .events
+                            .iter()
+                            .map(|event| {
+                                event
+                                    .clone()
+                                    .with(Height(height))
+                                    .with(TxHash(tx_hash))
+                                    .into()
+                            })
+                            .collect();
+                        response.events.emit_many(inner_events.clone());
                     } else {
                         // this branch can only be reached by inner txs
                         tracing::trace!(
@@ -408,10 +489,21 @@ where
 
                         stats.increment_rejected_txs();
                         self.state.drop_tx();
+                        // The fees of the wrapper have been paid regardless
+                        self.index_tx_history(
+                            height,
+                            tx_index,
+                            tx_hash,
+                            result.wrapper_changed_keys.iter(),
+                        );
                         tx_event.extend(Code(ResultCode::InvalidTx));
+                        if let Some(error_code) = result.error_code() {
+                            tx_event.extend(TxErrorCode(error_code));
+                        }
                     }
                     tx_event
                         .extend(GasUsed(result.gas_used))
+                        .extend(GasReport(result.gas_breakdown.clone()))
                         .extend(Info("Check inner_tx for result.".to_string()))
                         .extend(InnerTx(&result));
                 }
@@ -428,7 +520,8 @@ where
                     tx_event
                         .extend(GasUsed(tx_gas_meter.get_tx_consumed_gas()))
                         .extend(Info(msg.to_string()))
-                        .extend(Code(ResultCode::InvalidTx));
+                        .extend(Code(ResultCode::InvalidTx))
+                        .extend(TxErrorCode(msg.error_code()));
                 }
                 Err(msg) => {
                     tracing::info!(
@@ -463,21 +556,30 @@ where
                             // hash. A replay of the wrapper is impossible since
                             // the inner tx hash is committed to storage and
                             // we validate the wrapper against that hash too
-                            let header_hash = replay_protection_hashes
-                                .expect("This cannot fail")
-                                .header_hash;
-                            self.state.redundant_tx_hash(&header_hash).expect(
-                                "Error while marking tx hash as redundant",
-                            );
+                            let hashes = replay_protection_hashes
+                                .expect("This cannot fail");
+                            if hashes.has_header_hash_entry {
+                                self.state
+                                    .redundant_tx_hash(&hashes.header_hash)
+                                    .expect(
+                                        "Error while marking tx hash as \
+                                         redundant",
+                                    );
+                            }
                         }
                     }
 
                     stats.increment_errored_txs();
                     self.state.drop_tx();
 
+                    let error_code = match &msg {
+                        Error::TxApply(err) => err.error_code(),
+                        _ => ErrorCode::Unknown,
+                    };
                     tx_event
                         .extend(GasUsed(tx_gas_meter.get_tx_consumed_gas()))
-                        .extend(Info(msg.to_string()));
+                        .extend(Info(msg.to_string()))
+                        .extend(TxErrorCode(error_code));
 
                     // If wrapper, invalid tx error code
                     tx_event.extend(Code(ResultCode::InvalidTx));
@@ -494,9 +596,24 @@ where
                     tx_event.extend(Code(ResultCode::WasmRuntimeError));
                 }
             }
+            self.store_tx_result(
+                height,
+                tx_index,
+                applied_tx_hashes,
+                &tx_event,
+                inner_events,
+            );
             response.events.emit(tx_event);
         }
 
+        if let Some(retention) = self.tx_results_retention {
+            if let Some(up_to) = height.0.checked_sub(retention) {
+                self.state
+                    .write_log_mut()
+                    .prune_tx_results(BlockHeight(up_to));
+            }
+        }
+
         stats.set_tx_cache_size(
             self.tx_wasm_cache.get_size(),
             self.tx_wasm_cache.get_cache_size(),
@@ -509,6 +626,10 @@ where
         tracing::info!("{}", stats);
         tracing::info!("{}", stats.format_tx_executed());
 
+        // Adjust the base fee for the next block
+        let base_fee = parameters::update_base_fee(&mut self.state, block_gas)?;
+        tracing::debug!("Block gas: {block_gas}, next base fee: {base_fee}");
+
         // Update the MASP commitment tree anchor if the tree was updated
         let tree_key = token::storage_key::masp_commitment_tree_key();
         if let Some(StorageModification::Write { value }) =
@@ -522,6 +643,10 @@ where
             self.state.write(&anchor_key, ())?;
         }
 
+        // Propagate a change of the max block size to CometBFT, to take
+        // effect from the next height
+        self.update_consensus_params(&mut response);
+
         if update_for_tendermint {
             self.update_epoch(&mut response);
             // send the latest oracle configs. These may have changed due to
@@ -534,6 +659,23 @@ where
             native_block_proposer_address,
         )?;
 
+        #[cfg(feature = "debug-invariants")]
+        if let Err(err) =
+            namada::ledger::native_vp::invariants::check_all(&self.state)
+        {
+            tracing::error!(
+                "A global invariant is violated at the end of block {height}: \
+                 {err}"
+            );
+        }
+
+        // Tag the block events with the height, so that the events of a
+        // block can be looked up in the event log
+        for event in response.events.iter_mut() {
+            if !event.has_attribute::<Height>() {
+                event.extend(Height(height));
+            }
+        }
         self.event_log_mut().emit_many(response.events.clone());
         tracing::debug!("End finalize_block {height} of epoch {current_epoch}");
 
@@ -588,6 +730,77 @@ where
             .expect("Must be able to update validator set");
     }
 
+    /// If the `max_proposal_bytes` parameter was changed in this block, e.g.
+    /// by a governance proposal, update the response to include the new max
+    /// block size of CometBFT. The `max_block_gas` parameter is only enforced
+    /// by the ledger, so it needs no update.
+    fn update_consensus_params(
+        &self,
+        response: &mut shim::response::FinalizeBlock,
+    ) {
+        use crate::facade::tendermint_proto::v0_37::types::{
+            BlockParams, ConsensusParams,
+        };
+
+        let key = parameters::storage::get_max_proposal_bytes_key();
+        if !matches!(
+            self.state.write_log().read(&key).0,
+            Some(StorageModification::Write { .. })
+        ) {
+            return;
+        }
+        let max_proposal_bytes =
+            self.state.pos_queries().get_max_proposal_bytes();
+        tracing::info!(
+            "Updating the max block size for max proposal bytes \
+             {max_proposal_bytes:?}"
+        );
+        let max_bytes = tendermint_node::block_max_bytes(max_proposal_bytes)
+            .try_into()
+            .expect("The max block size must fit in an i64");
+        response.consensus_param_updates = Some(ConsensusParams {
+            block: Some(BlockParams {
+                max_bytes,
+                // gas is metered app-side
+                max_gas: -1,
+            }),
+            ..Default::default()
+        });
+    }
+
+    /// Summarize the protocol changes of the first block of a new epoch
+    fn epoch_transition_event(
+        &self,
+        height: BlockHeight,
+        current_epoch: Epoch,
+        activated_proposals: Vec<u64>,
+        activated_parameters: Vec<String>,
+        inflation: token::Amount,
+    ) -> Result<EpochTransition> {
+        let consensus_set =
+            read_consensus_validator_set_addresses(&self.state, current_epoch)?;
+        let prev_consensus_set = match current_epoch.prev() {
+            Some(last_epoch) => {
+                read_consensus_validator_set_addresses(&self.state, last_epoch)?
+            }
+            None => Default::default(),
+        };
+        let validators_joined =
+            consensus_set.difference(&prev_consensus_set).count();
+        let validators_left =
+            prev_consensus_set.difference(&consensus_set).count();
+        Ok(EpochTransition {
+            epoch: current_epoch,
+            height,
+            consensus_validators: consensus_set.len() as u64,
+            validators_joined: validators_joined as u64,
+            validators_left: validators_left as u64,
+            activated_proposals: ValueList(activated_proposals),
+            activated_parameters: ValueList(activated_parameters),
+            inflation: token::DenominatedAmount::native(inflation),
+        })
+    }
+
     /// Calculate the new inflation rate, mint the new tokens to the PoS
     /// account, then update the reward products of the validators. This is
     /// executed while finalizing the first block of a new epoch and is applied
@@ -612,6 +825,7 @@ where
         // PoS inflation
         namada_proof_of_stake::rewards::apply_inflation(
             &mut self.state,
+            events,
             last_epoch,
             num_blocks_in_last_epoch,
         )?;
@@ -632,6 +846,75 @@ where
         Ok(())
     }
 
+    /// Record the tx in the node's index of the txs that changed the balances
+    /// of addresses, if it is enabled
+    fn index_tx_history<'a>(
+        &mut self,
+        height: BlockHeight,
+        tx_index: usize,
+        tx_hash: Hash,
+        changed_keys: impl IntoIterator<Item = &'a Key>,
+    ) {
+        if !self.tx_history_index {
+            return;
+        }
+        let owners: BTreeSet<Address> = changed_keys
+            .into_iter()
+            .filter_map(|key| {
+                token::storage_key::is_any_token_balance_key(key)
+                    .map(|[_token, owner]| owner.clone())
+            })
+            .collect();
+        for owner in owners {
+            self.state.write_log_mut().write_tx_history_entry(
+                &owner,
+                height,
+                TxIndex::must_from_usize(tx_index),
+                tx_hash,
+            );
+        }
+    }
+
+    /// Store the result of a wrapper tx under the hashes of the wrapper and of
+    /// its inner tx, if the node stores the tx results
+    fn store_tx_result(
+        &mut self,
+        height: BlockHeight,
+        tx_index: usize,
+        hashes: Option<(Hash, Hash)>,
+        tx_event: &Event,
+        events: Vec<Event>,
+    ) {
+        if self.tx_results_retention.is_none() {
+            return;
+        }
+        let Some((wrapper_hash, inner_hash)) = hashes else {
+            return;
+        };
+        let applied = AppliedTxResult {
+            height,
+            index: TxIndex::must_from_usize(tx_index),
+            wrapper_hash,
+            inner_hash,
+            result: TxResultInfo {
+                hash: wrapper_hash,
+                code: tx_event
+                    .read_attribute::<Code>()
+                    .map(|code| code.to_u32())
+                    .unwrap_or_default(),
+                gas_used: tx_event
+                    .read_attribute::<GasUsed>()
+                    .unwrap_or_default(),
+                info: tx_event.read_attribute::<Info>().unwrap_or_default(),
+                events,
+            },
+        };
+        let bytes = applied.serialize_to_vec();
+        let write_log = self.state.write_log_mut();
+        write_log.write_tx_result(height, wrapper_hash, bytes.clone());
+        write_log.write_tx_result(height, inner_hash, bytes);
+    }
+
     // Write the inner tx hash to storage and mark the corresponding wrapper
     // hash as redundant (we check the inner tx hash too when validating
     // the wrapper). Requires the wrapper transaction as argument to recover
@@ -640,15 +923,18 @@ where
         if let Some(ReplayProtectionHashes {
             raw_header_hash,
             header_hash,
+            has_header_hash_entry,
         }) = hashes
         {
             self.state
                 .write_tx_hash(raw_header_hash)
                 .expect("Error while writing tx hash to storage");
 
-            self.state
-                .redundant_tx_hash(&header_hash)
-                .expect("Error while marking tx hash as redundant");
+            if has_header_hash_entry {
+                self.state
+                    .redundant_tx_hash(&header_hash)
+                    .expect("Error while marking tx hash as redundant");
+            }
         }
     }
 }
@@ -656,6 +942,8 @@ where
 struct ReplayProtectionHashes {
     raw_header_hash: Hash,
     header_hash: Hash,
+    /// Whether the wrapper hash was written to the replay protection storage
+    has_header_hash_entry: bool,
 }
 
 /// Convert ABCI vote info to PoS vote info. Any info which fails the conversion
@@ -727,6 +1015,7 @@ mod test_finalize_block {
     use std::num::NonZeroU64;
     use std::str::FromStr;
 
+    use namada::core::chain::ProposalBytes;
     use namada::core::collections::{HashMap, HashSet};
     use namada::core::dec::{Dec, POS_DECIMAL_PRECISION};
     use namada::core::ethereum_events::{EthAddress, Uint as ethUint};
@@ -4982,4 +5271,245 @@ mod test_finalize_block {
             control_receiver.recv().await.expect("Test failed");
         assert_eq!(u64::from(cmd.min_confirmations), 42);
     }
+
+    /// Test that the base fee follows the gas of the blocks and never goes
+    /// below the minimum gas price of the native token.
+    #[test]
+    fn test_update_base_fee() {
+        let (mut shell, _recv, _, _) = setup();
+        shell
+            .state
+            .write(&parameters::storage::get_target_block_gas_key(), 1_000_u64)
+            .unwrap();
+        shell
+            .state
+            .write(
+                &parameters::storage::get_base_fee_max_change_rate_key(),
+                Dec::from_str("0.125").unwrap(),
+            )
+            .unwrap();
+        let native_token = shell.state.in_mem().native_token.clone();
+        let min_gas_price = token::Amount::from_u64(1_000);
+        shell
+            .state
+            .write(
+                &parameters::storage::get_gas_cost_key(),
+                BTreeMap::from([(native_token, min_gas_price)]),
+            )
+            .unwrap();
+        assert_eq!(
+            parameters::read_base_fee(&shell.state).unwrap(),
+            min_gas_price
+        );
+
+        // A full block increases the base fee by the max change rate
+        let base_fee =
+            parameters::update_base_fee(&mut shell.state, 2_000).unwrap();
+        assert_eq!(base_fee, token::Amount::from_u64(1_125));
+        assert_eq!(parameters::read_base_fee(&shell.state).unwrap(), base_fee);
+
+        // The increase is capped by the max change rate, whatever the gas
+        let base_fee =
+            parameters::update_base_fee(&mut shell.state, 10_000).unwrap();
+        assert_eq!(base_fee, token::Amount::from_u64(1_266));
+
+        // A block on target doesn't change it
+        assert_eq!(
+            parameters::update_base_fee(&mut shell.state, 1_000).unwrap(),
+            base_fee
+        );
+
+        // Empty blocks decrease it, down to the minimum gas price
+        for _ in 0..10 {
+            parameters::update_base_fee(&mut shell.state, 0).unwrap();
+        }
+        assert_eq!(
+            parameters::read_base_fee(&shell.state).unwrap(),
+            min_gas_price
+        );
+    }
+
+    /// Test that a parameter change scheduled for a future epoch only takes
+    /// effect at the start of that epoch
+    #[test]
+    fn test_apply_pending_parameters() {
+        let (mut shell, _recv, _, _) = setup();
+        let target_block_gas = parameters::EpochedParameter::<u64>::open(
+            parameters::storage::get_target_block_gas_key(),
+        )
+        .unwrap();
+        let initial = target_block_gas.get(&shell.state).unwrap();
+        let current_epoch = shell.state.in_mem().block.epoch;
+
+        // Cannot schedule a change for the current epoch
+        assert!(target_block_gas
+            .schedule(&mut shell.state, current_epoch, 1_000)
+            .is_err());
+        let activation_epoch = current_epoch.next().next();
+        target_block_gas
+            .schedule(&mut shell.state, activation_epoch, 1_000)
+            .unwrap();
+        assert_eq!(
+            target_block_gas.pending(&shell.state).unwrap(),
+            BTreeMap::from([(activation_epoch, 1_000)])
+        );
+        assert_eq!(
+            target_block_gas
+                .get_at(&shell.state, activation_epoch)
+                .unwrap(),
+            Some(1_000)
+        );
+
+        // The value is unchanged in the next epoch
+        shell.start_new_epoch(None);
+        assert_eq!(target_block_gas.get(&shell.state).unwrap(), initial);
+
+        // It's updated in the epoch it was scheduled for
+        let epoch = shell.start_new_epoch(None);
+        assert_eq!(epoch, activation_epoch);
+        assert_eq!(target_block_gas.get(&shell.state).unwrap(), Some(1_000));
+        assert!(target_block_gas.pending(&shell.state).unwrap().is_empty());
+    }
+
+    /// Test that the node halts in the first block of an epoch that upgraded
+    /// the protocol to a version that its binary doesn't implement
+    #[test]
+    fn test_protocol_version_upgrade_halts() {
+        use namada::ledger::protocol::PROTOCOL_VERSION;
+
+        let (mut shell, _recv, _, _) = setup();
+        assert_eq!(
+            parameters::read_protocol_version(&shell.state).unwrap(),
+            Some(PROTOCOL_VERSION)
+        );
+        let upgrade_epoch = shell.state.in_mem().block.epoch.next().next();
+        parameters::protocol_version_handle()
+            .schedule(&mut shell.state, upgrade_epoch, PROTOCOL_VERSION + 1)
+            .unwrap();
+
+        // The blocks before the upgrade are processed
+        shell.start_new_epoch(None);
+        shell.check_protocol_version_at_startup().unwrap();
+
+        // Finalize the blocks until the upgrade epoch
+        shell.start_new_epoch_in(1);
+        let next_epoch_min_start_height =
+            shell.state.in_mem().next_epoch_min_start_height;
+        if let Some(b) = shell.state.in_mem_mut().last_block.as_mut() {
+            b.height = next_epoch_min_start_height;
+        }
+        for _ in 0..EPOCH_SWITCH_BLOCKS_DELAY {
+            shell.finalize_and_commit(None);
+        }
+        let mut req = FinalizeBlock::default();
+        req.header.time = {
+            #[allow(clippy::disallowed_methods)]
+            DateTimeUtc::now()
+        };
+        let res = shell.finalize_block(req);
+        assert!(matches!(res, Err(Error::ProtocolVersion(_))));
+        assert_eq!(shell.state.in_mem().block.epoch, upgrade_epoch);
+    }
+
+    /// Test that the first block of a new epoch emits an epoch transition
+    /// event with the changes that took effect in it
+    #[test]
+    fn test_epoch_transition_event() {
+        use namada::sdk::events::schema::TypedEvent;
+
+        let (mut shell, _recv, _, _) = setup();
+        let target_block_gas = parameters::EpochedParameter::<u64>::open(
+            parameters::storage::get_target_block_gas_key(),
+        )
+        .unwrap();
+        let current_epoch = shell.state.in_mem().block.epoch;
+        target_block_gas
+            .schedule(&mut shell.state, current_epoch.next(), 1_000)
+            .unwrap();
+
+        // Finalize the blocks until the next epoch, collecting the events
+        shell.start_new_epoch_in(1);
+        let next_epoch_min_start_height =
+            shell.state.in_mem().next_epoch_min_start_height;
+        if let Some(b) = shell.state.in_mem_mut().last_block.as_mut() {
+            b.height = next_epoch_min_start_height;
+        }
+        let mut events = vec![];
+        for _ in 0..=EPOCH_SWITCH_BLOCKS_DELAY {
+            let mut req = FinalizeBlock::default();
+            req.header.time = {
+                #[allow(clippy::disallowed_methods)]
+                DateTimeUtc::now()
+            };
+            events.extend(shell.finalize_block(req).expect("Test failed"));
+            shell.commit();
+        }
+        let new_epoch = shell.state.in_mem().block.epoch;
+        assert_eq!(new_epoch, current_epoch.next());
+
+        let transitions: Vec<_> = events
+            .iter()
+            .filter_map(|event| EpochTransition::decode(event).ok())
+            .collect();
+        assert_eq!(transitions.len(), 1);
+        let transition = &transitions[0];
+        assert_eq!(transition.epoch, new_epoch);
+        assert_eq!(
+            transition.consensus_validators,
+            read_consensus_validator_set_addresses(&shell.state, new_epoch)
+                .unwrap()
+                .len() as u64
+        );
+        assert_eq!(transition.validators_joined, 0);
+        assert_eq!(transition.validators_left, 0);
+        assert!(transition.activated_proposals.0.is_empty());
+        assert_eq!(
+            transition.activated_parameters,
+            ValueList(vec![target_block_gas.name().to_string()])
+        );
+    }
+
+    /// Test that a change of the max proposal bytes parameter, e.g. by a
+    /// governance proposal, updates the max block size of CometBFT from the
+    /// next height
+    #[test]
+    fn test_update_max_proposal_bytes() {
+        let (mut shell, _recv, _, _) = setup();
+
+        // Nothing to update if the parameter didn't change
+        let response = shell
+            .finalize_block(FinalizeBlock::default())
+            .expect("Test failed");
+        assert_eq!(response.consensus_param_updates, None);
+        shell.commit();
+
+        let max_proposal_bytes = ProposalBytes::new(1024 * 1024).unwrap();
+        shell
+            .state
+            .write(
+                &parameters::storage::get_max_proposal_bytes_key(),
+                max_proposal_bytes,
+            )
+            .expect("Test failed");
+        let response = shell
+            .finalize_block(FinalizeBlock::default())
+            .expect("Test failed");
+        let block_params = response
+            .consensus_param_updates
+            .and_then(|params| params.block)
+            .expect("Test failed");
+        assert_eq!(block_params.max_bytes, 11 * 1024 * 1024);
+        assert_eq!(block_params.max_gas, -1);
+        shell.commit();
+
+        // The new value takes effect at the next height
+        assert_eq!(
+            shell.state.pos_queries().get_max_proposal_bytes(),
+            max_proposal_bytes
+        );
+        let response = shell
+            .finalize_block(FinalizeBlock::default())
+            .expect("Test failed");
+        assert_eq!(response.consensus_param_updates, None);
+    }
 }
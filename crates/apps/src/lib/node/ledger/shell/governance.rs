@@ -3,7 +3,6 @@ use namada::core::encode;
 use namada::core::storage::Epoch;
 use namada::governance::event::GovernanceEvent;
 use namada::governance::pgf::storage::keys as pgf_storage;
-use namada::governance::pgf::storage::steward::StewardDetail;
 use namada::governance::pgf::{storage as pgf, ADDRESS};
 use namada::governance::storage::proposal::{
     AddRemove, PGFAction, PGFTarget, ProposalType, StoragePgfFunding,
@@ -19,6 +18,7 @@ use namada::ibc;
 use namada::ledger::events::extend::{ComposeEvent, Height};
 use namada::proof_of_stake::bond_amount;
 use namada::proof_of_stake::parameters::PosParams;
+use namada::proof_of_stake::queries::find_delegation_validators;
 use namada::proof_of_stake::storage::{
     read_total_active_stake, validator_state_handle,
 };
@@ -38,21 +38,44 @@ pub fn finalize_block<D, H>(
     events: &mut impl EmitEvents,
     current_epoch: Epoch,
     is_new_epoch: bool,
-) -> Result<()>
+) -> Result<ProposalsResult>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
 {
     if is_new_epoch {
-        load_and_execute_governance_proposals(shell, events, current_epoch)?;
+        let expired_stewards =
+            pgf::remove_expired_stewards(&mut shell.state, current_epoch)?;
+        for steward in expired_stewards {
+            tracing::info!(
+                "The term of the PGF steward {steward} has ended at epoch \
+                 {current_epoch}."
+            );
+        }
+        let expired_pauses =
+            pause::remove_expired_pauses(&mut shell.state, current_epoch)?;
+        for module in expired_pauses {
+            tracing::info!(
+                "The emergency pause of the module {module} has ended at \
+                 epoch {current_epoch}."
+            );
+        }
+        return load_and_execute_governance_proposals(
+            shell,
+            events,
+            current_epoch,
+        );
     }
-    Ok(())
+    Ok(ProposalsResult::default())
 }
 
+/// The ids of the governance proposals that ended at an epoch
 #[derive(Default)]
 pub struct ProposalsResult {
-    passed: Vec<u64>,
-    rejected: Vec<u64>,
+    /// The proposals that passed and were executed
+    pub passed: Vec<u64>,
+    /// The proposals that were rejected
+    pub rejected: Vec<u64>,
 }
 
 pub fn load_and_execute_governance_proposals<D, H>(
@@ -66,8 +89,12 @@ where
 {
     let proposal_ids = load_proposals(&shell.state, current_epoch)?;
 
-    let proposals_result =
-        execute_governance_proposals(shell, events, proposal_ids)?;
+    let proposals_result = execute_governance_proposals(
+        shell,
+        events,
+        current_epoch,
+        proposal_ids,
+    )?;
 
     Ok(proposals_result)
 }
@@ -75,6 +102,7 @@ where
 fn execute_governance_proposals<D, H>(
     shell: &mut Shell<D, H>,
     events: &mut impl EmitEvents,
+    current_epoch: Epoch,
     proposal_ids: BTreeSet<u64>,
 ) -> Result<ProposalsResult>
 where
@@ -130,6 +158,7 @@ where
                             shell,
                             id,
                             proposal_code.clone(),
+                            false,
                         )?;
                         tracing::info!(
                             "Default Governance proposal {} has been executed \
@@ -147,6 +176,7 @@ where
                             shell,
                             id,
                             proposal_code.clone(),
+                            false,
                         )?;
                         tracing::info!(
                             "DefaultWithWasm Governance proposal {} has been \
@@ -157,10 +187,30 @@ where
 
                         GovernanceEvent::passed_proposal(id, true, result)
                     }
+                    ProposalType::ExpeditedWithWasm(_) => {
+                        let proposal_code =
+                            gov_api::get_proposal_code(&shell.state, id)?
+                                .unwrap_or_default();
+                        let result = execute_default_proposal(
+                            shell,
+                            id,
+                            proposal_code.clone(),
+                            true,
+                        )?;
+                        tracing::info!(
+                            "ExpeditedWithWasm Governance proposal {} has \
+                             been executed and passed, wasm execution was {}.",
+                            id,
+                            if result { "successful" } else { "unsuccessful" }
+                        );
+
+                        GovernanceEvent::passed_proposal(id, true, result)
+                    }
                     ProposalType::PGFSteward(stewards) => {
                         let _result = execute_pgf_steward_proposal(
                             &mut shell.state,
                             stewards,
+                            current_epoch,
                         )?;
                         tracing::info!(
                             "Governance proposal (pgf stewards){} has been \
@@ -170,6 +220,58 @@ where
 
                         GovernanceEvent::passed_proposal(id, false, false)
                     }
+                    ProposalType::PGFStewardElection(election) => {
+                        let term_end = Epoch(
+                            current_epoch.0.saturating_add(election.term),
+                        );
+                        pgf::elect_stewards(
+                            &mut shell.state,
+                            &election.stewards,
+                            term_end,
+                        )?;
+                        tracing::info!(
+                            "Governance proposal (pgf steward election) {} \
+                             has been executed and passed, the term of the \
+                             elected stewards ends at epoch {}.",
+                            id,
+                            term_end
+                        );
+
+                        GovernanceEvent::passed_proposal(id, false, false)
+                    }
+                    ProposalType::EmergencyPause(emergency_pause) => {
+                        let pause_end = Epoch(
+                            current_epoch
+                                .0
+                                .saturating_add(emergency_pause.epochs),
+                        );
+                        for module in &emergency_pause.modules {
+                            if emergency_pause.epochs == 0 {
+                                pause::lift_pause(&mut shell.state, *module)?;
+                                tracing::info!(
+                                    "The emergency pause of the module \
+                                     {module} has been lifted."
+                                );
+                            } else {
+                                pause::pause_module(
+                                    &mut shell.state,
+                                    *module,
+                                    pause_end,
+                                )?;
+                                tracing::info!(
+                                    "The module {module} is paused until \
+                                     epoch {pause_end}."
+                                );
+                            }
+                        }
+                        tracing::info!(
+                            "Governance proposal (emergency pause) {} has \
+                             been executed and passed.",
+                            id
+                        );
+
+                        GovernanceEvent::passed_proposal(id, false, false)
+                    }
                     ProposalType::PGFPayment(payments) => {
                         let native_token = &shell.state.get_native_token()?;
                         let _result = execute_pgf_funding_proposal(
@@ -227,7 +329,7 @@ where
                 }
                 let proposal_event = GovernanceEvent::rejected_proposal(
                     id,
-                    matches!(proposal_type, ProposalType::DefaultWithWasm(_)),
+                    proposal_type.has_code(),
                 );
                 events.emit(proposal_event);
                 proposals_result.rejected.push(id);
@@ -308,7 +410,13 @@ fn compute_proposal_votes<S>(
 where
     S: StorageRead,
 {
-    let votes = gov_api::get_proposal_votes(storage, proposal_id)?;
+    let mut votes = gov_api::get_proposal_votes(storage, proposal_id)?;
+    // Count the votes of the voting delegates on behalf of their delegators
+    let delegated_votes =
+        gov_api::get_delegated_votes(storage, &votes, |delegator| {
+            find_delegation_validators(storage, delegator, &epoch)
+        })?;
+    votes.extend(delegated_votes);
 
     let mut validators_vote: HashMap<Address, ProposalVote> =
         HashMap::default();
@@ -383,10 +491,13 @@ where
     })
 }
 
+/// Execute the code of a proposal. The code of an expedited proposal may only
+/// change the expedited keys, otherwise its changes are dropped.
 fn execute_default_proposal<D, H>(
     shell: &mut Shell<D, H>,
     id: u64,
     proposal_code: Vec<u8>,
+    is_expedited: bool,
 ) -> namada::state::StorageResult<bool>
 where
     D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
@@ -418,7 +529,19 @@ where
         .expect("Should be able to delete the storage.");
     match tx_result {
         Ok(tx_result) => {
-            if tx_result.is_accepted() {
+            let invalid_key = tx_result.changed_keys.iter().find(|key| {
+                is_expedited
+                    && **key != pending_execution_key
+                    && !gov_storage::is_expedited_key(key)
+            });
+            if let Some(key) = invalid_key {
+                tracing::info!(
+                    "The code of the expedited proposal {id} changed the key \
+                     {key}, which is not allowed to expedited proposals."
+                );
+                shell.state.drop_tx();
+                Ok(false)
+            } else if tx_result.is_accepted() {
                 shell.state.commit_tx();
                 Ok(true)
             } else {
@@ -436,6 +559,7 @@ where
 fn execute_pgf_steward_proposal<S>(
     storage: &mut S,
     stewards: BTreeSet<AddRemove<Address>>,
+    current_epoch: Epoch,
 ) -> Result<bool>
 where
     S: StorageRead + StorageWrite,
@@ -443,14 +567,15 @@ where
     for action in stewards {
         match action {
             AddRemove::Add(address) => {
-                pgf_storage::stewards_handle().insert(
-                    storage,
-                    address.to_owned(),
-                    StewardDetail::base(address),
-                )?;
+                let term_end =
+                    pgf::add_steward(storage, &address, current_epoch)?;
+                tracing::info!(
+                    "The steward {address} has been added until epoch \
+                     {term_end}."
+                );
             }
             AddRemove::Remove(address) => {
-                pgf_storage::stewards_handle().remove(storage, &address)?;
+                pgf::remove_steward(storage, &address)?;
             }
         }
     }
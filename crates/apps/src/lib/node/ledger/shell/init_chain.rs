@@ -230,8 +230,14 @@ where
 
         // Initialize protocol parameters
         let parameters = genesis.get_chain_parameters(&self.wasm_dir);
-        self.store_wasms(&parameters)?;
+        self.store_wasms(&parameters, initial_height)?;
         parameters::init_storage(&parameters, &mut self.state).unwrap();
+        // Record the protocol version implemented by this binary
+        parameters::write_protocol_version(
+            &mut self.state,
+            protocol::PROTOCOL_VERSION,
+        )
+        .unwrap();
 
         // Initialize governance parameters
         let gov_params = genesis.get_gov_params();
@@ -348,7 +354,11 @@ where
         self.proceed_with(code)
     }
 
-    fn store_wasms(&mut self, params: &Parameters) -> ControlFlow<()> {
+    fn store_wasms(
+        &mut self,
+        params: &Parameters,
+        initial_height: BlockHeight,
+    ) -> ControlFlow<()> {
         let Parameters {
             tx_allowlist,
             vp_allowlist,
@@ -429,6 +439,7 @@ where
                 let code_len_key = Key::wasm_code_len(&code_hash);
                 let hash_key = Key::wasm_hash(name);
                 let code_name_key = Key::wasm_code_name(name.to_owned());
+                let code_height_key = Key::wasm_code_height(&code_hash);
 
                 self.state.write(&code_key, code).unwrap();
                 self.state.write(&code_len_key, code_len).unwrap();
@@ -437,6 +448,7 @@ where
                     is_implicit_vp_stored = true;
                 }
                 self.state.write(&code_name_key, code_hash).unwrap();
+                self.state.write(&code_height_key, initial_height).unwrap();
             } else {
                 tracing::warn!("The wasm {name} isn't allowed.");
                 self.warn(Warning::DisallowedWasm(name.to_string()));
@@ -1061,8 +1073,10 @@ mod test {
         let genesis = genesis::make_dev_genesis(1, &shell.base_dir);
         let mut initializer = InitChainValidation::new(&mut shell, true);
 
-        let res = initializer
-            .store_wasms(&genesis.get_chain_parameters(PathBuf::new()));
+        let res = initializer.store_wasms(
+            &genesis.get_chain_parameters(PathBuf::new()),
+            BlockHeight::first(),
+        );
         assert_eq!(res, ControlFlow::Continue(()));
         let expected = vec![Panic::ChecksumsFile];
         assert_eq!(expected, initializer.panics);
@@ -1076,8 +1090,10 @@ mod test {
         }"#,
         )
         .expect("Test failed");
-        let res = initializer
-            .store_wasms(&genesis.get_chain_parameters(test_dir.path()));
+        let res = initializer.store_wasms(
+            &genesis.get_chain_parameters(test_dir.path()),
+            BlockHeight::first(),
+        );
         assert_eq!(res, ControlFlow::Continue(()));
         let errors = initializer.errors.iter().collect::<Vec<_>>();
         let [Error::ReadingWasm(_), Error::LoadingWasm(_)]: [&Error; 2] =
@@ -1101,8 +1117,10 @@ mod test {
         }"#,
         )
         .expect("Test failed");
-        let res = initializer
-            .store_wasms(&genesis.get_chain_parameters(test_dir.path()));
+        let res = initializer.store_wasms(
+            &genesis.get_chain_parameters(test_dir.path()),
+            BlockHeight::first(),
+        );
         assert_eq!(res, ControlFlow::Continue(()));
         let errors = initializer.errors.iter().collect::<Vec<_>>();
         let [
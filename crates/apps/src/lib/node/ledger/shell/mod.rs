@@ -6,17 +6,23 @@
 //! (unless we can simply overwrite them in the next block).
 //! More info in <https://github.com/anoma/namada/issues/362>.
 pub mod block_alloc;
+mod block_seed;
+mod epoch_hooks;
 mod finalize_block;
 mod governance;
 mod init_chain;
 pub use init_chain::InitChainValidation;
 use namada::vm::wasm::run::check_tx_allowed;
 use namada_sdk::state::StateRead;
+mod operational_metadata;
 pub mod prepare_proposal;
 use namada::state::State;
 pub mod process_proposal;
+mod proposal_cache;
+mod protocol_version;
 pub(super) mod queries;
 mod stats;
+mod telemetry;
 #[cfg(any(test, feature = "testing"))]
 #[allow(dead_code)]
 pub mod testing;
@@ -34,6 +40,7 @@ use borsh_ext::BorshSerializeExt;
 use masp_primitives::transaction::Transaction;
 use namada::core::address::Address;
 use namada::core::chain::ChainId;
+use namada::core::dec::Dec;
 use namada::core::ethereum_events::EthereumEvent;
 use namada::core::hints;
 use namada::core::key::*;
@@ -58,7 +65,9 @@ use namada::state::{
 };
 use namada::token;
 pub use namada::tx::data::ResultCode;
-use namada::tx::data::{TxType, WrapperTx, WrapperTxErr};
+use namada::tx::data::{
+    AccountNonce, GasLimit, TxType, WrapperTx, WrapperTxErr,
+};
 use namada::tx::{Section, Tx};
 use namada::vm::wasm::{TxCache, VpCache};
 use namada::vm::{WasmCacheAccess, WasmCacheRwAccess};
@@ -68,6 +77,7 @@ use namada_sdk::tendermint::AppHash;
 use thiserror::Error;
 use tokio::sync::mpsc::{Receiver, UnboundedSender};
 
+use self::proposal_cache::ProposalCache;
 use super::ethereum_oracle::{self as oracle, last_processed_block};
 use crate::config::{self, genesis, TendermintMode, ValidatorLocalConfig};
 use crate::facade::tendermint::v0_37::abci::{request, response};
@@ -120,6 +130,10 @@ pub enum Error {
     Storage(#[from] namada::state::StorageError),
     #[error("Transaction replay attempt: {0}")]
     ReplayAttempt(String),
+    #[error("Invalid account nonce: {0}")]
+    InvalidNonce(String),
+    #[error("Unsupported protocol version: {0}")]
+    ProtocolVersion(String),
 }
 
 impl From<Error> for TxResult {
@@ -161,6 +175,44 @@ pub fn rollback(config: config::Ledger) -> Result<()> {
         .map_err(|e| Error::Storage(namada::state::StorageError::new(e)))
 }
 
+pub fn repair_db(config: config::Ledger) -> Result<()> {
+    let db_path = config.shell.db_dir(&config.chain_id);
+    let mut db = storage::PersistentDB::open(db_path, None);
+    match db
+        .repair_last_block()
+        .map_err(|e| Error::Storage(namada::state::StorageError::new(e)))?
+    {
+        Some(height) => tracing::info!(
+            "Reverted the incomplete block at height {height}, it will be \
+             replayed by CometBFT"
+        ),
+        None => tracing::info!("The last block is complete, nothing to repair"),
+    }
+    Ok(())
+}
+
+pub fn reindex(
+    config: config::Ledger,
+    from_height: Option<BlockHeight>,
+    to_height: Option<BlockHeight>,
+) -> Result<()> {
+    // The Namada state, including e.g. the slashes history, is canonical, so
+    // only the CometBFT indexes of the events have to be rebuilt
+    tracing::info!("Reindex CometBFT events");
+    let cometbft_dir = config.cometbft_dir();
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(tendermint_node::reindex_events(
+            cometbft_dir,
+            config.cometbft,
+            from_height,
+            to_height,
+        ))
+        .map_err(Error::Tendermint)
+}
+
 #[derive(Debug)]
 #[allow(dead_code, clippy::large_enum_variant)]
 pub(super) enum ShellMode {
@@ -345,8 +397,32 @@ where
     /// limit the how many block heights in the past can the storage be
     /// queried for reading values.
     storage_read_past_height_limit: Option<u64>,
+    /// Taken from config `max_query_request_bytes`. When set, the queries
+    /// with larger request data are rejected.
+    max_query_request_bytes: Option<u64>,
+    /// Taken from config `max_query_response_bytes`. When set, the queries
+    /// with larger response data are rejected.
+    max_query_response_bytes: Option<u64>,
+    /// Taken from config `eval_vp`. When set, the developer query evaluating
+    /// a VP is served with these limits.
+    eval_vp: Option<config::EvalVpConfig>,
+    /// Taken from config `state_checksum_query`. When set, the query of the
+    /// state checksum is served.
+    state_checksum_query: bool,
+    /// Taken from config `tx_history_index`. When set, the txs that changed
+    /// the balances of addresses are indexed by address.
+    tx_history_index: bool,
+    /// Taken from config `tx_results_retention`. When set, the results of the
+    /// applied txs are stored for this number of blocks.
+    tx_results_retention: Option<u64>,
+    /// Taken from config `telemetry_heartbeat_interval`. When set, a
+    /// validator broadcasts a telemetry heartbeat every this number of
+    /// blocks.
+    telemetry_heartbeat_interval: Option<u64>,
     /// Log of events emitted by `FinalizeBlock` ABCI calls.
     event_log: EventLog,
+    /// Validation results of the txs of the processed block proposals
+    proposal_cache: ProposalCache,
 }
 
 /// Merkle tree storage key filter. Return `false` for keys that shouldn't be
@@ -354,6 +430,7 @@ where
 pub fn is_merklized_storage_key(key: &namada_sdk::storage::Key) -> bool {
     !(token::storage_key::is_masp_key(key)
         && *key != token::storage_key::masp_convert_anchor_key()
+        && *key != token::storage_key::masp_prev_convert_anchor_key()
         && *key != token::storage_key::masp_token_map_key()
         && *key != token::storage_key::masp_assets_hash_key()
         && !token::storage_key::is_masp_commitment_anchor_key(key)
@@ -404,8 +481,21 @@ where
         let db_path = config.shell.db_dir(&chain_id);
         let base_dir = config.shell.base_dir;
         let mode = config.shell.tendermint_mode;
-        let storage_read_past_height_limit =
-            config.shell.storage_read_past_height_limit;
+        let archive_mode = config.shell.archive_mode;
+        let tx_history_index = config.shell.tx_history_index;
+        let tx_results_retention = config.shell.tx_results_retention;
+        let telemetry_heartbeat_interval =
+            config.shell.telemetry_heartbeat_interval;
+        let max_query_request_bytes = config.shell.max_query_request_bytes;
+        let max_query_response_bytes = config.shell.max_query_response_bytes;
+        let eval_vp = config.shell.eval_vp;
+        let state_checksum_query = config.shell.state_checksum_query;
+        // The history of an archive node can be queried at any height
+        let storage_read_past_height_limit = if archive_mode {
+            None
+        } else {
+            config.shell.storage_read_past_height_limit
+        };
         if !Path::new(&base_dir).is_dir() {
             std::fs::create_dir(&base_dir)
                 .expect("Creating directory for Namada should not fail");
@@ -431,14 +521,15 @@ where
         };
 
         // load last state from storage
-        let state = FullAccessState::open(
+        let mut state = FullAccessState::open(
             db_path,
             db_cache,
             chain_id.clone(),
             native_token,
-            config.shell.storage_read_past_height_limit,
+            storage_read_past_height_limit,
             is_merklized_storage_key,
         );
+        state.set_archive_mode(archive_mode);
         let vp_wasm_cache_dir =
             base_dir.join(chain_id.as_str()).join("vp_wasm_cache");
         let tx_wasm_cache_dir =
@@ -522,10 +613,27 @@ where
                 tx_wasm_compilation_cache as usize,
             ),
             storage_read_past_height_limit,
+            max_query_request_bytes,
+            max_query_response_bytes,
+            eval_vp,
+            state_checksum_query,
+            tx_history_index,
+            tx_results_retention,
+            telemetry_heartbeat_interval,
             // TODO: config event log params
             event_log: EventLog::default(),
+            proposal_cache: ProposalCache::default(),
         };
+        // Reload the wasm modules compiled before the restart
+        shell.vp_wasm_cache.load_from_disk();
+        shell.tx_wasm_cache.load_from_disk();
         shell.update_eth_oracle(&Default::default());
+        // Refuse to start with a binary that doesn't implement the protocol
+        // version of the chain
+        if let Err(err) = shell.check_protocol_version_at_startup() {
+            tracing::error!("{err}");
+            panic!("{err}");
+        }
         shell
     }
 
@@ -704,7 +812,13 @@ where
     fn broadcast_protocol_txs(&mut self) {
         use crate::node::ledger::shell::vote_extensions::iter_protocol_txs;
 
-        let ext = self.craft_extension();
+        let mut ext = self.craft_extension();
+
+        if let Some(telemetry) = ext.telemetry.take() {
+            if let Some(tx) = self.sign_telemetry_vext(&telemetry) {
+                self.mode.broadcast(tx.to_bytes());
+            }
+        }
 
         let protocol_key = self
             .mode
@@ -884,11 +998,11 @@ where
         //
         // NB: always keep this as the first tx check,
         // as it is a pretty cheap one
-        if !validate_tx_bytes(&self.state, tx_bytes.len())
+        if let Err(err) = validate_tx_bytes(&self.state, tx_bytes.len())
             .expect("Failed to get max tx bytes param from storage")
         {
             response.code = ResultCode::TooLarge.into();
-            response.log = format!("{INVALID_MSG}: Tx too large");
+            response.log = format!("{INVALID_MSG}: {err}");
             return response;
         }
 
@@ -1035,6 +1149,29 @@ where
                         response.priority = i64::MAX;
                     }
                 }
+                ProtocolTxType::ValidatorOperationalMetadata => {
+                    if let Err(err) = self.validate_operational_metadata_tx(&tx)
+                    {
+                        response.code = ResultCode::InvalidTx.into();
+                        response.log = format!(
+                            "{INVALID_MSG}: Invalid validator operational \
+                             metadata: {err}",
+                        );
+                    } else {
+                        response.log = String::from(VALID_MSG);
+                    }
+                }
+                ProtocolTxType::TelemetryVext => {
+                    if let Err(err) = self.validate_telemetry_tx(&tx) {
+                        response.code = ResultCode::InvalidVoteExtension.into();
+                        response.log = format!(
+                            "{INVALID_MSG}: Invalid telemetry vote extension: \
+                             {err}",
+                        );
+                    } else {
+                        response.log = String::from(VALID_MSG);
+                    }
+                }
                 _ => {
                     response.code = ResultCode::InvalidTx.into();
                     response.log = format!(
@@ -1056,7 +1193,10 @@ where
                 }
 
                 // Tx gas limit
-                let mut gas_meter = TxGasMeter::new(wrapper.gas_limit);
+                let gas_subsidy = read_gas_subsidy(&self.state, &tx, tx_bytes)
+                    .expect("Must be able to read gas subsidies");
+                let mut gas_meter =
+                    wrapper_gas_meter(&wrapper, gas_subsidy.as_ref());
                 if gas_meter.add_wrapper_gas(tx_bytes).is_err() {
                     response.code = ResultCode::TxGasLimit.into();
                     response.log = "{INVALID_MSG}: Wrapper transaction \
@@ -1108,10 +1248,38 @@ where
                     return response;
                 }
 
+                // Account nonce check. Nonces ahead of the next one are
+                // accepted, such that consecutive txs can be queued
+                if let Some(account_nonce) = &wrapper.nonce {
+                    match next_account_nonce(
+                        &self.state,
+                        &wrapper,
+                        account_nonce,
+                    ) {
+                        Ok(next_nonce) if account_nonce.nonce < next_nonce => {
+                            response.code = ResultCode::InvalidNonce.into();
+                            response.log = format!(
+                                "{INVALID_MSG}: Nonce {} of account {} has \
+                                 already been used, the next nonce is \
+                                 {next_nonce}",
+                                account_nonce.nonce, account_nonce.account
+                            );
+                            return response;
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            response.code = ResultCode::InvalidNonce.into();
+                            response.log = format!("{INVALID_MSG}: {e}");
+                            return response;
+                        }
+                    }
+                }
+
                 // Validate wrapper fees
                 if let Err(e) = mempool_fee_check(
                     &wrapper,
                     get_fee_unshielding_transaction(&tx, &wrapper),
+                    gas_subsidy.map(|subsidy| subsidy.discount),
                     &mut ShellParams::new(
                         &RefCell::new(gas_meter),
                         &mut self.state.with_temp_write_log(),
@@ -1201,7 +1369,8 @@ where
 
 /// Checks that neither the wrapper nor the inner transaction have already
 /// been applied. Requires a [`TempWlState`] to perform the check during
-/// block construction and validation
+/// block construction and validation. The hash of a wrapper declaring an
+/// account nonce is not recorded, see [`nonce_checks`].
 pub fn replay_protection_checks<D, H>(
     wrapper: &Tx,
     temp_state: &mut TempWlState<D, H>,
@@ -1234,16 +1403,81 @@ where
         )));
     }
 
+    // The replays of a wrapper declaring an account nonce are prevented by
+    // the nonce checks instead
+    if matches!(
+        &wrapper.header.tx_type,
+        TxType::Wrapper(wrapper) if wrapper.nonce.is_some()
+    ) {
+        return Ok(());
+    }
+
     // Write wrapper hash to WAL
     temp_state
         .write_tx_hash(wrapper_hash)
         .map_err(|e| Error::ReplayAttempt(e.to_string()))
 }
 
+/// Check that the fee payer of a wrapper declaring an account nonce is
+/// authorized to use the account's nonce and return the next nonce of the
+/// account.
+fn next_account_nonce<S>(
+    storage: &S,
+    wrapper: &WrapperTx,
+    AccountNonce { account, .. }: &AccountNonce,
+) -> Result<u64>
+where
+    S: StorageRead,
+{
+    if &wrapper.fee_payer() != account
+        && !namada::account::public_keys(storage, account)?
+            .contains(&wrapper.pk)
+    {
+        return Err(Error::InvalidNonce(format!(
+            "The wrapper public key {} is not authorized to use the nonce of \
+             {account}",
+            wrapper.pk
+        )));
+    }
+    Ok(namada::account::read_nonce(storage, account)?)
+}
+
+/// Check that the account nonce declared by a wrapper, if any, is the next
+/// nonce of the account and consume it in the tx write log, so that the
+/// following txs of the block can use the subsequent nonces.
+pub fn nonce_checks<D, H>(
+    wrapper: &WrapperTx,
+    temp_state: &mut TempWlState<D, H>,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let Some(account_nonce) = &wrapper.nonce else {
+        return Ok(());
+    };
+    let next_nonce = next_account_nonce(temp_state, wrapper, account_nonce)?;
+    if account_nonce.nonce != next_nonce {
+        return Err(Error::InvalidNonce(format!(
+            "Expected nonce {next_nonce} for account {}, found {}",
+            account_nonce.account, account_nonce.nonce
+        )));
+    }
+    temp_state
+        .write_log_mut()
+        .write(
+            &namada::account::nonce_key(&account_nonce.account),
+            (next_nonce + 1).serialize_to_vec(),
+        )
+        .map_err(|e| Error::InvalidNonce(e.to_string()))?;
+    Ok(())
+}
+
 // Perform the fee check in mempool
 fn mempool_fee_check<D, H, CA>(
     wrapper: &WrapperTx,
     masp_transaction: Option<Transaction>,
+    gas_subsidy: Option<Dec>,
     shell_params: &mut ShellParams<'_, TempWlState<D, H>, D, H, CA>,
 ) -> Result<()>
 where
@@ -1261,21 +1495,75 @@ where
         wrapper.fee.token
     ))))?;
 
+    let fee_source = protocol::get_fee_source(
+        shell_params.state,
+        wrapper,
+        masp_transaction.as_ref(),
+    );
     wrapper_fee_check(
         wrapper,
         masp_transaction,
         minimum_gas_price,
+        gas_subsidy,
         shell_params,
     )?;
-    protocol::check_fees(shell_params.state, wrapper).map_err(Error::TxApply)
+    protocol::check_fees(shell_params.state, wrapper, &fee_source)
+        .map_err(Error::TxApply)
+}
+
+/// Read the gas subsidy of a wrapper tx from its inner tx code. The subsidy
+/// is full and doesn't limit the gas if the code is exempt from the minimum
+/// fee, otherwise it's the code's gas subsidy, if it's subsidized for txs of
+/// its size.
+pub fn read_gas_subsidy<S>(
+    storage: &S,
+    tx: &Tx,
+    tx_bytes: &[u8],
+) -> namada::state::StorageResult<Option<parameters::GasSubsidy>>
+where
+    S: StorageRead,
+{
+    let Some(code_sec) = tx
+        .get_section(tx.code_sechash())
+        .and_then(|x| Section::code_sec(&x))
+    else {
+        return Ok(None);
+    };
+    let code_hash = code_sec.code.hash();
+    if parameters::is_fee_exempt(storage, &code_hash)? {
+        return Ok(Some(parameters::GasSubsidy {
+            discount: Dec::one(),
+            max_tx_bytes: u64::MAX,
+            max_gas: u64::MAX,
+        }));
+    }
+    let tx_bytes = u64::try_from(tx_bytes.len()).unwrap_or(u64::MAX);
+    parameters::read_gas_subsidy(storage, &code_hash, tx_bytes)
+}
+
+/// Initialize the gas meter of a wrapper tx. A subsidized tx is metered
+/// against the subsidy's max gas, if it's below the tx's gas limit.
+pub fn wrapper_gas_meter(
+    wrapper: &WrapperTx,
+    gas_subsidy: Option<&parameters::GasSubsidy>,
+) -> TxGasMeter {
+    let gas_limit = match gas_subsidy {
+        Some(subsidy) => {
+            std::cmp::min(u64::from(wrapper.gas_limit), subsidy.max_gas)
+        }
+        None => u64::from(wrapper.gas_limit),
+    };
+    TxGasMeter::new(GasLimit::from(gas_limit))
 }
 
 /// Check the validity of the fee payment, including the minimum amounts
-/// required and the optional unshield
+/// required, the base fee for the native token, the discount of a gas subsidy
+/// and the optional unshield
 pub fn wrapper_fee_check<D, H, CA>(
     wrapper: &WrapperTx,
     masp_transaction: Option<Transaction>,
     minimum_gas_price: token::Amount,
+    gas_subsidy: Option<Dec>,
     shell_params: &mut ShellParams<'_, TempWlState<D, H>, D, H, CA>,
 ) -> Result<()>
 where
@@ -1283,6 +1571,24 @@ where
     H: StorageHasher + Sync + 'static,
     CA: 'static + WasmCacheAccess + Sync,
 {
+    // The dynamic base fee is the minimum price of gas in the native token
+    let minimum_gas_price =
+        if wrapper.fee.token == shell_params.state.in_mem().native_token {
+            std::cmp::max(
+                minimum_gas_price,
+                parameters::read_base_fee(shell_params.state)?,
+            )
+        } else {
+            minimum_gas_price
+        };
+    // A subsidized tx only has to pay the discounted price
+    let minimum_gas_price = match gas_subsidy {
+        Some(discount) => {
+            parameters::subsidized_gas_price(minimum_gas_price, discount)?
+        }
+        None => minimum_gas_price,
+    };
+
     match token::denom_to_amount(
         wrapper.fee.amount_per_gas_unit,
         &wrapper.fee.token,
@@ -1956,6 +2262,7 @@ mod test_utils {
 
 #[cfg(test)]
 mod shell_tests {
+    use assert_matches::assert_matches;
     use namada::core::storage::Epoch;
     use namada::eth_bridge::storage::eth_bridge_queries::is_bridge_comptime_enabled;
     use namada::token::read_denom;
@@ -2504,6 +2811,94 @@ mod shell_tests {
         )
     }
 
+    /// Check that the account nonces declared by wrappers must be used in
+    /// order and only by the account's keys
+    #[test]
+    fn test_account_nonce_checks() {
+        let (shell, _recv, _, _) = test_utils::setup();
+
+        let keypair = super::test_utils::gen_keypair();
+        let account = Address::from(&keypair.ref_to());
+        let wrapper = |nonce: u64, pk: common::PublicKey| {
+            WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(
+                        token::Amount::from_uint(100, 0)
+                            .expect("This can't fail"),
+                    ),
+                    token: shell.state.in_mem().native_token.clone(),
+                },
+                pk,
+                GAS_LIMIT_MULTIPLIER.into(),
+                None,
+            )
+            .with_nonce(AccountNonce {
+                account: account.clone(),
+                nonce,
+            })
+        };
+        let mut temp_state = shell.state.with_temp_write_log();
+
+        // The first nonce of an account is 0
+        assert_matches!(
+            nonce_checks(&wrapper(1, keypair.ref_to()), &mut temp_state),
+            Err(Error::InvalidNonce(_))
+        );
+        nonce_checks(&wrapper(0, keypair.ref_to()), &mut temp_state)
+            .expect("Test failed");
+        // A nonce cannot be reused
+        assert_matches!(
+            nonce_checks(&wrapper(0, keypair.ref_to()), &mut temp_state),
+            Err(Error::InvalidNonce(_))
+        );
+        // Only the account's keys can use its nonce
+        assert_matches!(
+            nonce_checks(
+                &wrapper(1, super::test_utils::gen_keypair().ref_to()),
+                &mut temp_state
+            ),
+            Err(Error::InvalidNonce(_))
+        );
+        nonce_checks(&wrapper(1, keypair.ref_to()), &mut temp_state)
+            .expect("Test failed");
+    }
+
+    /// Check that the hash of a wrapper declaring an account nonce is not
+    /// recorded for replay protection, unlike the hash of its inner tx
+    #[test]
+    fn test_account_nonce_replay_protection() {
+        let (shell, _recv, _, _) = test_utils::setup();
+
+        let keypair = super::test_utils::gen_keypair();
+        let wrapper = Tx::from_type(TxType::Wrapper(Box::new(
+            WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(
+                        token::Amount::from_uint(100, 0)
+                            .expect("This can't fail"),
+                    ),
+                    token: shell.state.in_mem().native_token.clone(),
+                },
+                keypair.ref_to(),
+                GAS_LIMIT_MULTIPLIER.into(),
+                None,
+            )
+            .with_nonce(AccountNonce {
+                account: Address::from(&keypair.ref_to()),
+                nonce: 0,
+            }),
+        )));
+        let mut temp_state = shell.state.with_temp_write_log();
+
+        replay_protection_checks(&wrapper, &mut temp_state)
+            .expect("Test failed");
+        assert!(
+            !temp_state
+                .has_replay_protection_entry(&wrapper.header_hash())
+                .expect("Test failed")
+        );
+    }
+
     /// Check that a transaction with a wrong chain id gets discarded
     #[test]
     fn test_wrong_chain_id() {
@@ -2693,6 +3088,162 @@ mod shell_tests {
         assert_eq!(result.code, ResultCode::FeeError.into());
     }
 
+    // Check that a wrapper of a subsidized tx code can pay a discounted fee,
+    // not below the floor and only if it's not bigger than the subsidy's max
+    // size
+    #[test]
+    fn test_fee_gas_subsidy() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+
+        let native_token = shell.state.in_mem().native_token.clone();
+        let minimum_gas_price = std::cmp::max(
+            parameters::read_gas_cost(&shell.state, &native_token)
+                .unwrap()
+                .unwrap(),
+            parameters::read_base_fee(&shell.state).unwrap(),
+        );
+        let floor_gas_price = parameters::subsidized_gas_price(
+            minimum_gas_price,
+            parameters::max_gas_subsidy_discount(),
+        )
+        .unwrap();
+        assert!(!floor_gas_price.is_zero());
+
+        let chain_id = shell.chain_id.clone();
+        let code = Code::new("wasm_code".as_bytes().to_owned(), None);
+        let code_hash = code.code.hash();
+        let wrapper_bytes = |amount_per_gas_unit: token::Amount| {
+            let mut wrapper =
+                Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                    Fee {
+                        amount_per_gas_unit: DenominatedAmount::native(
+                            amount_per_gas_unit,
+                        ),
+                        token: native_token.clone(),
+                    },
+                    crate::wallet::defaults::albert_keypair().ref_to(),
+                    GAS_LIMIT_MULTIPLIER.into(),
+                    None,
+                ))));
+            wrapper.header.chain_id = chain_id.clone();
+            wrapper.set_code(code.clone());
+            wrapper
+                .set_data(Data::new("transaction data".as_bytes().to_owned()));
+            wrapper.add_section(Section::Authorization(Authorization::new(
+                wrapper.sechashes(),
+                [(0, crate::wallet::defaults::albert_keypair())]
+                    .into_iter()
+                    .collect(),
+                None,
+            )));
+            wrapper.to_bytes()
+        };
+        let tx_bytes = wrapper_bytes(floor_gas_price);
+
+        // The tx is bigger than the subsidy's max size
+        let tx_bytes_len = u64::try_from(tx_bytes.len()).unwrap();
+        let mut subsidy = parameters::GasSubsidy {
+            discount: Dec::one(),
+            max_tx_bytes: tx_bytes_len - 1,
+            max_gas: u64::MAX,
+        };
+        parameters::gas_subsidies_handle()
+            .insert(&mut shell.state, code_hash, subsidy.clone())
+            .unwrap();
+        let result = shell
+            .mempool_validate(tx_bytes.as_ref(), MempoolTxType::NewTransaction);
+        assert_eq!(result.code, ResultCode::FeeError.into());
+
+        // The tx is fully subsidized, down to the floor
+        subsidy.max_tx_bytes = tx_bytes_len;
+        parameters::gas_subsidies_handle()
+            .insert(&mut shell.state, code_hash, subsidy)
+            .unwrap();
+        let result = shell
+            .mempool_validate(tx_bytes.as_ref(), MempoolTxType::NewTransaction);
+        assert_ne!(result.code, ResultCode::FeeError.into());
+
+        // A zero gas price is below the floor
+        let tx_bytes = wrapper_bytes(token::Amount::zero());
+        let result = shell
+            .mempool_validate(tx_bytes.as_ref(), MempoolTxType::NewTransaction);
+        assert_eq!(result.code, ResultCode::FeeError.into());
+    }
+
+    // Check that the gas meter of a subsidized wrapper is initialized with the
+    // subsidy's max gas, if it's below the wrapper's gas limit
+    #[test]
+    fn test_gas_subsidy_gas_meter() {
+        let wrapper = WrapperTx::new(
+            Fee {
+                amount_per_gas_unit: DenominatedAmount::native(0.into()),
+                token: address::testing::nam(),
+            },
+            crate::wallet::defaults::albert_keypair().ref_to(),
+            100.into(),
+            None,
+        );
+        let mut subsidy = parameters::GasSubsidy {
+            discount: Dec::one(),
+            max_tx_bytes: u64::MAX,
+            max_gas: 10,
+        };
+
+        let gas_meter = wrapper_gas_meter(&wrapper, None);
+        assert_eq!(gas_meter.tx_gas_limit, Gas::from(GasLimit::from(100)));
+        let gas_meter = wrapper_gas_meter(&wrapper, Some(&subsidy));
+        assert_eq!(gas_meter.tx_gas_limit, Gas::from(GasLimit::from(10)));
+        subsidy.max_gas = 1000;
+        let gas_meter = wrapper_gas_meter(&wrapper, Some(&subsidy));
+        assert_eq!(gas_meter.tx_gas_limit, Gas::from(GasLimit::from(100)));
+    }
+
+    // Check that a wrapper of a tx code exempt from the minimum fee can pay a
+    // zero gas price
+    #[test]
+    fn test_fee_exemption() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+
+        let mut wrapper =
+            Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(0.into()),
+                    token: shell.state.in_mem().native_token.clone(),
+                },
+                crate::wallet::defaults::albert_keypair().ref_to(),
+                GAS_LIMIT_MULTIPLIER.into(),
+                None,
+            ))));
+        wrapper.header.chain_id = shell.chain_id.clone();
+        let code = Code::new("wasm_code".as_bytes().to_owned(), None);
+        let code_hash = code.code.hash();
+        wrapper.set_code(code);
+        wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
+        wrapper.add_section(Section::Authorization(Authorization::new(
+            wrapper.sechashes(),
+            [(0, crate::wallet::defaults::albert_keypair())]
+                .into_iter()
+                .collect(),
+            None,
+        )));
+        let tx_bytes = wrapper.to_bytes();
+
+        let result = shell
+            .mempool_validate(tx_bytes.as_ref(), MempoolTxType::NewTransaction);
+        assert_eq!(result.code, ResultCode::FeeError.into());
+
+        parameters::fee_exemptions_handle()
+            .insert(&mut shell.state, code_hash)
+            .unwrap();
+        assert_eq!(
+            parameters::read_fee_exemptions(&shell.state).unwrap(),
+            vec![code_hash]
+        );
+        let result = shell
+            .mempool_validate(tx_bytes.as_ref(), MempoolTxType::NewTransaction);
+        assert_ne!(result.code, ResultCode::FeeError.into());
+    }
+
     // Check that a wrapper transactions whose fees cannot be paid is rejected
     #[test]
     fn test_insufficient_balance_for_fee() {
@@ -2816,5 +3367,10 @@ mod shell_tests {
             MempoolTxType::NewTransaction,
         );
         assert_eq!(result.code, ResultCode::TooLarge.into());
+        assert!(
+            result
+                .log
+                .contains(&format!("max tx size of {max_tx_bytes} bytes"))
+        );
     }
 }
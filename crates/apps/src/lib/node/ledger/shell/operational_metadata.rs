@@ -0,0 +1,57 @@
+//! Validators' operational metadata published with protocol txs.
+//!
+//! The metadata itself is implemented in
+//! [`namada::proof_of_stake::operational_metadata`], this module only takes
+//! care of validating the protocol txs that carry the updates.
+
+use namada::proof_of_stake::operational_metadata::{
+    validate_operational_metadata_update, OperationalMetadataUpdate,
+};
+use namada::tx::data::protocol::ProtocolTxType;
+
+use super::*;
+
+impl<D, H> Shell<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// Validate a protocol tx publishing a validator's operational metadata.
+    /// The tx signature must have been verified beforehand.
+    pub(super) fn validate_operational_metadata_tx(
+        &self,
+        tx: &Tx,
+    ) -> std::result::Result<(), String> {
+        let protocol_tx = match tx.header().tx_type {
+            TxType::Protocol(protocol_tx)
+                if matches!(
+                    protocol_tx.tx,
+                    ProtocolTxType::ValidatorOperationalMetadata
+                ) =>
+            {
+                protocol_tx
+            }
+            _ => {
+                return Err("Expected a validator operational metadata \
+                            protocol tx"
+                    .to_string());
+            }
+        };
+        let data = tx.data().ok_or_else(|| {
+            "Expected the validator operational metadata protocol tx to carry \
+             data"
+                .to_string()
+        })?;
+        let update = OperationalMetadataUpdate::try_from_slice(&data).map_err(
+            |err| {
+                format!("Invalid validator operational metadata update: {err}")
+            },
+        )?;
+        validate_operational_metadata_update(
+            &self.state,
+            &update,
+            &protocol_tx.pk,
+        )
+        .map_err(|err| err.to_string())
+    }
+}
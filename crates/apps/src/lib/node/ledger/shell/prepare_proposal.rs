@@ -4,8 +4,8 @@ use std::cell::RefCell;
 
 use masp_primitives::transaction::Transaction;
 use namada::core::address::Address;
+use namada::core::dec::Dec;
 use namada::core::key::tm_raw_hash_to_string;
-use namada::gas::TxGasMeter;
 use namada::hash::Hash;
 use namada::ledger::protocol::{self, ShellParams};
 use namada::proof_of_stake::storage::find_validator_by_raw_hash;
@@ -174,12 +174,19 @@ where
 
     /// Allocate an initial set of protocol txs and advance to the
     /// next allocation state.
+    ///
+    /// The block proposer's contribution to the block seed, if
+    /// any, is always the first tx of the block.
     fn build_protocol_tx_with_normal_txs(
         &self,
-        alloc: BlockAllocator<BuildingProtocolTxBatch<WithNormalTxs>>,
+        mut alloc: BlockAllocator<BuildingProtocolTxBatch<WithNormalTxs>>,
         txs: &mut Vec<TxBytes>,
     ) -> (BlockAllocator<BuildingNormalTxBatch>, Vec<TxBytes>) {
-        let (alloc, txs) = self.build_protocol_txs(alloc, txs);
+        let seed_tx = self
+            .build_block_seed_tx()
+            .filter(|tx_bytes| alloc.try_alloc(&tx_bytes[..]).is_ok());
+        let (alloc, protocol_txs) = self.build_protocol_txs(alloc, txs);
+        let txs = seed_tx.into_iter().chain(protocol_txs).collect();
         (alloc.next_state(), txs)
     }
 
@@ -288,16 +295,21 @@ where
     tx.validate_tx().map_err(|_| ())?;
     if let TxType::Wrapper(wrapper) = tx.header().tx_type {
         // Check tx gas limit for tx size
-        let mut tx_gas_meter = TxGasMeter::new(wrapper.gas_limit);
+        let gas_subsidy = super::read_gas_subsidy(temp_state, &tx, tx_bytes)
+            .map_err(|_| ())?;
+        let mut tx_gas_meter =
+            super::wrapper_gas_meter(&wrapper, gas_subsidy.as_ref());
         tx_gas_meter.add_wrapper_gas(tx_bytes).map_err(|_| ())?;
 
         super::replay_protection_checks(&tx, temp_state).map_err(|_| ())?;
+        super::nonce_checks(&wrapper, temp_state).map_err(|_| ())?;
 
         // Check fees and extract the gas limit of this transaction
         match prepare_proposal_fee_check(
             &wrapper,
             tx.header_hash(),
             protocol::get_fee_unshielding_transaction(&tx, &wrapper),
+            gas_subsidy.map(|subsidy| subsidy.discount),
             block_proposer,
             proposer_local_config,
             &mut ShellParams::new(
@@ -320,6 +332,7 @@ fn prepare_proposal_fee_check<D, H, CA>(
     wrapper: &WrapperTx,
     wrapper_tx_hash: Hash,
     masp_transaction: Option<Transaction>,
+    gas_subsidy: Option<Dec>,
     proposer: &Address,
     proposer_local_config: Option<&ValidatorLocalConfig>,
     shell_params: &mut ShellParams<'_, TempWlState<D, H>, D, H, CA>,
@@ -354,10 +367,16 @@ where
         }
     };
 
+    let fee_source = protocol::get_fee_source(
+        shell_params.state,
+        wrapper,
+        masp_transaction.as_ref(),
+    );
     super::wrapper_fee_check(
         wrapper,
         masp_transaction,
         minimum_gas_price,
+        gas_subsidy,
         shell_params,
     )?;
 
@@ -365,6 +384,7 @@ where
         shell_params.state,
         proposer,
         wrapper,
+        &fee_source,
         wrapper_tx_hash,
     )
     .map_err(Error::TxApply)
@@ -700,9 +720,10 @@ mod test_prepare_proposal {
             txs: vec![vote.into()],
             ..Default::default()
         });
-        assert_eq!(rsp.txs.len(), 1);
+        // the first tx is the proposer's block seed contribution
+        assert_eq!(rsp.txs.len(), 2);
 
-        let tx_bytes = rsp.txs.remove(0);
+        let tx_bytes = rsp.txs.remove(1);
         let got = Tx::try_from(&tx_bytes[..]).unwrap();
         let eth_tx_data = (&got).try_into().expect("Test failed");
         let rsp_ext = match eth_tx_data {
@@ -790,6 +811,60 @@ mod test_prepare_proposal {
         assert_eq!(received_txs.len(), 1);
     }
 
+    /// Test that a change of the max block gas parameter, e.g. by a
+    /// governance proposal, changes which txs fit in the following blocks
+    #[test]
+    fn test_max_block_gas_param_update() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+
+        let keypair = crate::wallet::defaults::daewon_keypair();
+        let mut wrapper =
+            Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(1.into()),
+                    token: shell.state.in_mem().native_token.clone(),
+                },
+                keypair.ref_to(),
+                GAS_LIMIT_MULTIPLIER.into(),
+                None,
+            ))));
+        wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+        wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
+        wrapper.add_section(Section::Authorization(Authorization::new(
+            wrapper.sechashes(),
+            [(0, keypair)].into_iter().collect(),
+            None,
+        )));
+        let req = || RequestPrepareProposal {
+            txs: vec![wrapper.to_bytes().into()],
+            ..Default::default()
+        };
+        assert_eq!(shell.prepare_proposal(req()).txs.len(), 1);
+
+        // Lower the max block gas below the gas limit of the tx
+        shell
+            .state
+            .write(
+                &namada::parameters::storage::get_max_block_gas_key(),
+                GAS_LIMIT_MULTIPLIER - 1,
+            )
+            .expect("Test failed");
+        shell.finalize_and_commit(None);
+        assert!(shell.prepare_proposal(req()).txs.is_empty());
+
+        // Raise it back
+        shell
+            .state
+            .write(
+                &namada::parameters::storage::get_max_block_gas_key(),
+                GAS_LIMIT_MULTIPLIER,
+            )
+            .expect("Test failed");
+        shell.finalize_and_commit(None);
+        assert_eq!(shell.prepare_proposal(req()).txs.len(), 1);
+    }
+
     /// Test that if the unsigned inner tx hash is known (replay attack), the
     /// transaction is not included in the block
     #[test]
@@ -2,6 +2,7 @@
 //! and [`RevertProposal`] ABCI++ methods for the Shell
 
 use data_encoding::HEXUPPER;
+use namada::core::key::batch::BatchVerifier;
 use namada::hash::Hash;
 use namada::ledger::pos::PosQueries;
 use namada::proof_of_stake::storage::find_validator_by_raw_hash;
@@ -23,6 +24,9 @@ pub struct ValidationMeta {
     pub user_gas: TxBin<BlockGas>,
     /// Space utilized by all txs.
     pub txs_bin: TxBin<BlockSpace>,
+    /// Whether the block proposer's contribution to the block seed
+    /// has already been included.
+    pub has_block_seed: bool,
 }
 
 impl<D, H> From<&WlState<D, H>> for ValidationMeta
@@ -38,7 +42,11 @@ where
 
         let user_gas = TxBin::init(max_block_gas);
         let txs_bin = TxBin::init(max_proposal_bytes);
-        Self { user_gas, txs_bin }
+        Self {
+            user_gas,
+            txs_bin,
+            has_block_seed: false,
+        }
     }
 }
 
@@ -117,6 +125,36 @@ where
         )
     }
 
+    /// Check all the txs in a block like [`Self::process_proposal`] and cache
+    /// their results, to be reused by [`Self::process_finalized_txs`].
+    pub fn process_and_cache_proposal(
+        &mut self,
+        req: RequestProcessProposal,
+    ) -> ProcessProposal {
+        let block_hash = req.hash.to_vec();
+        let txs = req.txs.clone();
+        let (response, tx_results) = self.process_proposal(req);
+        self.proposal_cache.insert(block_hash, &txs, tx_results);
+        response
+    }
+
+    /// Evaluates the corresponding [`TxResult`] for each tx of a block that
+    /// is being finalized. The results obtained when the block's proposal
+    /// was processed are reused if available, otherwise the txs are
+    /// processed again.
+    pub fn process_finalized_txs(
+        &mut self,
+        block_hash: &[u8],
+        txs: &[TxBytes],
+        block_time: DateTimeUtc,
+        block_proposer: &Address,
+    ) -> Vec<TxResult> {
+        match self.proposal_cache.take(block_hash, txs) {
+            Some(tx_results) => tx_results,
+            None => self.process_txs(txs, block_time, block_proposer),
+        }
+    }
+
     /// Evaluates the corresponding [`TxResult`] for each tx in the
     /// proposal. Additionally, counts the number of digest
     /// txs and the bytes used by encrypted txs in the proposal.
@@ -134,12 +172,15 @@ where
         let mut metadata = ValidationMeta::from(self.state.read_only());
         let mut vp_wasm_cache = self.vp_wasm_cache.clone();
         let mut tx_wasm_cache = self.tx_wasm_cache.clone();
+        let signatures_verified = batch_verify_signatures(txs);
 
         let tx_results: Vec<_> = txs
             .iter()
-            .map(|tx_bytes| {
+            .zip(signatures_verified)
+            .map(|(tx_bytes, signatures_verified)| {
                 let result = self.check_proposal_tx(
                     tx_bytes,
+                    signatures_verified,
                     &mut metadata,
                     &mut temp_state,
                     block_time,
@@ -167,6 +208,8 @@ where
 
     /// Checks if the Tx can be deserialized from bytes. Checks the fees and
     /// signatures of the fee payer for a transaction if it is a wrapper tx.
+    /// The signatures are not checked again if `signatures_verified` is set,
+    /// i.e. if they have already been verified in a batch.
     ///
     /// Checks validity of a decrypted tx or that a tx marked un-decryptable
     /// is in fact so. Also checks that decrypted txs were submitted in
@@ -191,6 +234,7 @@ where
     pub fn check_proposal_tx<CA>(
         &self,
         tx_bytes: &[u8],
+        signatures_verified: bool,
         metadata: &mut ValidationMeta,
         temp_state: &mut TempWlState<D, H>,
         block_time: DateTimeUtc,
@@ -205,12 +249,12 @@ where
         //
         // NB: always keep this as the first tx check,
         // as it is a pretty cheap one
-        if !validate_tx_bytes(&self.state, tx_bytes.len())
+        if let Err(err) = validate_tx_bytes(&self.state, tx_bytes.len())
             .expect("Failed to get max tx bytes param from storage")
         {
             return TxResult {
                 code: ResultCode::TooLarge.into(),
-                info: "Tx too large".into(),
+                info: err.to_string(),
             };
         }
 
@@ -247,6 +291,9 @@ where
             |tx| {
                 let tx_chain_id = tx.header.chain_id.clone();
                 let tx_expiration = tx.header.expiration;
+                if signatures_verified {
+                    return Ok((tx_chain_id, tx_expiration, tx));
+                }
                 if let Err(err) = tx.validate_tx() {
                     // This occurs if the wrapper / protocol tx signature is
                     // invalid
@@ -263,12 +310,6 @@ where
             Err(tx_result) => return tx_result,
         };
 
-        if let Err(err) = tx.validate_tx() {
-            return TxResult {
-                code: ResultCode::InvalidSig.into(),
-                info: err.to_string(),
-            };
-        }
         match tx.header().tx_type {
             // If it is a raw transaction, we do no further validation
             TxType::Raw => TxResult {
@@ -391,6 +432,64 @@ where
                             }
                         })
                     }
+                    ProtocolTxType::BlockSeed => {
+                        if metadata.has_block_seed {
+                            return TxResult {
+                                code: ResultCode::InvalidTx.into(),
+                                info: "Process proposal rejected this \
+                                       proposal because it contains more than \
+                                       one block seed contribution"
+                                    .into(),
+                            };
+                        }
+                        metadata.has_block_seed = true;
+                        self.validate_block_seed_tx(&tx, block_proposer)
+                            .map(|_| TxResult {
+                                code: ResultCode::Ok.into(),
+                                info: "Process Proposal accepted this \
+                                       transaction"
+                                    .into(),
+                            })
+                            .unwrap_or_else(|err| TxResult {
+                                code: ResultCode::InvalidTx.into(),
+                                info: format!(
+                                    "Process proposal rejected this proposal \
+                                     because its block seed \
+                                     contribution was invalid: {err}"
+                                ),
+                            })
+                    }
+                    ProtocolTxType::ValidatorOperationalMetadata => self
+                        .validate_operational_metadata_tx(&tx)
+                        .map(|_| TxResult {
+                            code: ResultCode::Ok.into(),
+                            info: "Process Proposal accepted this transaction"
+                                .into(),
+                        })
+                        .unwrap_or_else(|err| TxResult {
+                            code: ResultCode::InvalidTx.into(),
+                            info: format!(
+                                "Process proposal rejected this proposal \
+                                 because one of the included validator \
+                                 operational metadata updates was invalid: \
+                                 {err}"
+                            ),
+                        }),
+                    ProtocolTxType::TelemetryVext => self
+                        .validate_telemetry_tx(&tx)
+                        .map(|_| TxResult {
+                            code: ResultCode::Ok.into(),
+                            info: "Process Proposal accepted this transaction"
+                                .into(),
+                        })
+                        .unwrap_or_else(|err| TxResult {
+                            code: ResultCode::InvalidVoteExtension.into(),
+                            info: format!(
+                                "Process proposal rejected this proposal \
+                                 because one of the included telemetry vote \
+                                 extensions was invalid: {err}"
+                            ),
+                        }),
                     ProtocolTxType::EthereumEvents
                     | ProtocolTxType::BridgePool
                     | ProtocolTxType::ValidatorSetUpdate => TxResult {
@@ -407,7 +506,18 @@ where
                 // Account for the tx's resources
                 let allocated_gas =
                     metadata.user_gas.try_dump(u64::from(wrapper.gas_limit));
-                let mut tx_gas_meter = TxGasMeter::new(wrapper.gas_limit);
+                let gas_subsidy =
+                    match super::read_gas_subsidy(temp_state, &tx, tx_bytes) {
+                        Ok(gas_subsidy) => gas_subsidy,
+                        Err(e) => {
+                            return TxResult {
+                                code: ResultCode::FeeError.into(),
+                                info: e.to_string(),
+                            };
+                        }
+                    };
+                let mut tx_gas_meter =
+                    super::wrapper_gas_meter(&wrapper, gas_subsidy.as_ref());
                 if tx_gas_meter.add_wrapper_gas(tx_bytes).is_err()
                     || allocated_gas.is_err()
                 {
@@ -463,11 +573,20 @@ where
                     };
                 }
 
+                // Account nonce checks
+                if let Err(e) = super::nonce_checks(&wrapper, temp_state) {
+                    return TxResult {
+                        code: ResultCode::InvalidNonce.into(),
+                        info: e.to_string(),
+                    };
+                }
+
                 // Check that the fee payer has sufficient balance.
                 match process_proposal_fee_check(
                     &wrapper,
                     tx.header_hash(),
                     get_fee_unshielding_transaction(&tx, &wrapper),
+                    gas_subsidy.map(|subsidy| subsidy.discount),
                     block_proposer,
                     &mut ShellParams::new(
                         &RefCell::new(tx_gas_meter),
@@ -498,10 +617,44 @@ where
     }
 }
 
+/// Verify the wrapper and protocol tx signatures of all the given txs in a
+/// single batch. Returns, for each tx, whether its signatures have been
+/// verified. If the batch is invalid, none of them is considered verified,
+/// and the txs fall back to verifying their signatures individually, in
+/// order to find out which of them are invalid.
+fn batch_verify_signatures(txs: &[TxBytes]) -> Vec<bool> {
+    let txs: Vec<_> = txs.iter().map(|tx| Tx::try_from(tx.as_ref())).collect();
+    let mut verifier = BatchVerifier::new();
+    let queued: Vec<bool> = txs
+        .iter()
+        .map(|tx| {
+            let Some(signatures) =
+                tx.as_ref().ok().and_then(|tx| tx.signatures_to_validate())
+            else {
+                return false;
+            };
+            for (pk, hash, sig) in signatures {
+                verifier.queue(pk, &hash, sig);
+            }
+            true
+        })
+        .collect();
+    if let Err(err) = verifier.verify(rand::thread_rng()) {
+        tracing::debug!(
+            ?err,
+            "Batch verification of the proposal signatures failed, falling \
+             back to individual verification"
+        );
+        return vec![false; queued.len()];
+    }
+    queued
+}
+
 fn process_proposal_fee_check<D, H, CA>(
     wrapper: &WrapperTx,
     wrapper_tx_hash: Hash,
     masp_transaction: Option<Transaction>,
+    gas_subsidy: Option<Dec>,
     proposer: &Address,
     shell_params: &mut ShellParams<'_, TempWlState<D, H>, D, H, CA>,
 ) -> Result<()>
@@ -520,10 +673,16 @@ where
         wrapper.fee.token
     ))))?;
 
+    let fee_source = protocol::get_fee_source(
+        shell_params.state,
+        wrapper,
+        masp_transaction.as_ref(),
+    );
     wrapper_fee_check(
         wrapper,
         masp_transaction,
         minimum_gas_price,
+        gas_subsidy,
         shell_params,
     )?;
 
@@ -531,6 +690,7 @@ where
         shell_params.state,
         proposer,
         wrapper,
+        &fee_source,
         wrapper_tx_hash,
     )
     .map_err(Error::TxApply)
@@ -955,6 +1115,51 @@ mod test_process_proposal {
         }
     }
 
+    /// Test that the signatures of a proposal are only considered verified if
+    /// the whole batch is valid
+    #[test]
+    fn test_batch_verify_signatures() {
+        let keypair = gen_keypair();
+        let mut outer_tx =
+            Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(
+                        Amount::from_uint(100, 0).expect("Test failed"),
+                    ),
+                    token: address::testing::nam(),
+                },
+                keypair.ref_to(),
+                GAS_LIMIT_MULTIPLIER.into(),
+                None,
+            ))));
+        outer_tx.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+        outer_tx.set_data(Data::new("transaction data".as_bytes().to_owned()));
+        outer_tx.add_section(Section::Authorization(Authorization::new(
+            outer_tx.sechashes(),
+            [(0, keypair)].into_iter().collect(),
+            None,
+        )));
+        let mut bad_tx = outer_tx.clone();
+        if let TxType::Wrapper(wrapper) = &mut bad_tx.header.tx_type {
+            wrapper.fee.amount_per_gas_unit =
+                DenominatedAmount::native(Default::default());
+        } else {
+            panic!("Test failed")
+        };
+        let outer_tx = TxBytes::from(outer_tx.to_bytes());
+        let bad_tx = TxBytes::from(bad_tx.to_bytes());
+        let garbage = TxBytes::from_static(b"not a tx");
+
+        assert_eq!(
+            batch_verify_signatures(&[outer_tx.clone(), garbage]),
+            vec![true, false]
+        );
+        assert_eq!(
+            batch_verify_signatures(&[outer_tx, bad_tx]),
+            vec![false, false]
+        );
+    }
+
     /// Test that if the account submitting the tx is not known and the fee is
     /// non-zero, [`process_proposal`] rejects that block
     #[test]
@@ -0,0 +1,126 @@
+//! Cache of the validation results of the processed block proposals.
+//!
+//! With CometBFT v0.37, the txs of a block are validated once in
+//! `ProcessProposal` and again before the block is finalized. Since the block
+//! hash commits to the previous state, the block time and the proposer, the
+//! results of the two validations are identical, so the results obtained in
+//! `ProcessProposal` are cached and reused by `FinalizeBlock`. Nodes that
+//! didn't process the proposal, e.g. while syncing, validate the txs again.
+
+use std::collections::VecDeque;
+
+use namada::core::hash::Hash;
+
+use crate::node::ledger::shims::abcipp_shim_types::shim::response::TxResult;
+use crate::node::ledger::shims::abcipp_shim_types::shim::TxBytes;
+
+/// The maximum number of proposals whose results are cached. Multiple
+/// proposals may be processed at the same height if consensus requires
+/// multiple rounds.
+const MAX_CACHED_PROPOSALS: usize = 8;
+
+/// The validation results of the txs of a block proposal.
+#[derive(Debug)]
+struct CachedProposal {
+    /// The hash of the proposed block
+    block_hash: Vec<u8>,
+    /// The hash of each tx and its validation result, in block order
+    txs: Vec<(Hash, TxResult)>,
+}
+
+/// Bounded cache of the validation results of the processed proposals.
+#[derive(Debug, Default)]
+pub struct ProposalCache {
+    /// The cached proposals, from the least to the most recently processed
+    proposals: VecDeque<CachedProposal>,
+}
+
+impl ProposalCache {
+    /// Cache the validation results of the txs of a block proposal, evicting
+    /// the least recently processed proposal if the cache is full.
+    pub fn insert(
+        &mut self,
+        block_hash: Vec<u8>,
+        txs: &[TxBytes],
+        tx_results: Vec<TxResult>,
+    ) {
+        if txs.len() != tx_results.len() {
+            return;
+        }
+        self.proposals
+            .retain(|proposal| proposal.block_hash != block_hash);
+        if self.proposals.len() >= MAX_CACHED_PROPOSALS {
+            self.proposals.pop_front();
+        }
+        let txs = txs.iter().map(Hash::sha256).zip(tx_results).collect();
+        self.proposals.push_back(CachedProposal { block_hash, txs });
+    }
+
+    /// Take the validation results of the txs of the given block, if its
+    /// proposal was processed with the very same txs. The whole cache is
+    /// cleared, since the other proposals at this height are now obsolete.
+    pub fn take(
+        &mut self,
+        block_hash: &[u8],
+        txs: &[TxBytes],
+    ) -> Option<Vec<TxResult>> {
+        let proposal = std::mem::take(&mut self.proposals)
+            .into_iter()
+            .find(|proposal| proposal.block_hash == block_hash)?;
+        if proposal.txs.len() != txs.len()
+            || proposal
+                .txs
+                .iter()
+                .zip(txs)
+                .any(|((tx_hash, _), tx)| *tx_hash != Hash::sha256(tx))
+        {
+            return None;
+        }
+        Some(proposal.txs.into_iter().map(|(_, result)| result).collect())
+    }
+}
+
+#[cfg(test)]
+mod test_proposal_cache {
+    use super::*;
+
+    fn tx_result(code: u32) -> TxResult {
+        TxResult {
+            code,
+            info: String::new(),
+        }
+    }
+
+    /// Test that the results are only reused for the same block and txs
+    #[test]
+    fn test_proposal_cache_take() {
+        let txs =
+            vec![TxBytes::from_static(b"tx1"), TxBytes::from_static(b"tx2")];
+        let results = vec![tx_result(0), tx_result(1)];
+
+        let mut cache = ProposalCache::default();
+        cache.insert(b"block".to_vec(), &txs, results.clone());
+        assert_eq!(cache.take(b"another block", &txs), None);
+        // The cache is cleared once a block is finalized
+        assert_eq!(cache.take(b"block", &txs), None);
+
+        cache.insert(b"block".to_vec(), &txs, results.clone());
+        assert_eq!(cache.take(b"block", &txs[..1]), None);
+
+        cache.insert(b"block".to_vec(), &txs, results.clone());
+        assert_eq!(cache.take(b"block", &txs), Some(results));
+    }
+
+    /// Test that the least recently processed proposals are evicted
+    #[test]
+    fn test_proposal_cache_bound() {
+        let txs = vec![TxBytes::from_static(b"tx")];
+        let mut cache = ProposalCache::default();
+        for i in 0..=MAX_CACHED_PROPOSALS {
+            cache.insert(vec![i as u8], &txs, vec![tx_result(0)]);
+        }
+        assert_eq!(cache.proposals.len(), MAX_CACHED_PROPOSALS);
+        assert_eq!(cache.proposals[0].block_hash, vec![1]);
+        assert!(cache.take(&[MAX_CACHED_PROPOSALS as u8], &txs).is_some());
+    }
+}
@@ -0,0 +1,144 @@
+//! Handshake of the protocol version implemented by the node's binary with
+//! the protocol version of the chain.
+//!
+//! The chain's protocol version is recorded in storage at genesis and bumped
+//! by the governance proposals that schedule network upgrades, see
+//! [`namada::ledger::parameters::protocol_version_handle`]. A node refuses to
+//! start, or halts at the start of an epoch, if its binary doesn't implement
+//! the version of the chain, instead of risking a consensus split.
+
+use namada::ledger::parameters::{
+    read_protocol_version, read_protocol_version_at, INITIAL_PROTOCOL_VERSION,
+};
+use namada::ledger::protocol::PROTOCOL_VERSION;
+
+use super::*;
+
+impl<D, H> Shell<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// Check that this binary can process the next block of the chain. The
+    /// binary must implement the current protocol version, or the version of
+    /// an upgrade that takes effect from the next block, i.e. after the node
+    /// halted at the start of the upgrade epoch.
+    pub(super) fn check_protocol_version_at_startup(&self) -> Result<()> {
+        if self.state.in_mem().last_block.is_none() {
+            // The version is recorded by `InitChain`
+            return Ok(());
+        }
+        let current_version = self.current_protocol_version()?;
+        if current_version == PROTOCOL_VERSION {
+            return Ok(());
+        }
+        // The next block starts a new epoch once the epoch switch delay
+        // elapsed
+        let new_epoch_next =
+            matches!(self.state.in_mem().update_epoch_blocks_delay, Some(1));
+        if new_epoch_next {
+            let next_epoch = self.state.in_mem().block.epoch.next();
+            let next_version =
+                read_protocol_version_at(&self.state, next_epoch)?
+                    .unwrap_or(current_version);
+            if next_version == PROTOCOL_VERSION {
+                tracing::info!(
+                    "This binary implements the protocol version \
+                     {PROTOCOL_VERSION} of the upgrade taking effect in the \
+                     next block, at the start of epoch {next_epoch}, the \
+                     chain is at version {current_version}"
+                );
+                return Ok(());
+            }
+        }
+        Err(protocol_version_mismatch(current_version))
+    }
+
+    /// Check that this binary implements the protocol version in effect in
+    /// the new epoch, after the upgrades scheduled for it were applied.
+    pub(super) fn check_protocol_version_at_new_epoch(&self) -> Result<()> {
+        let version = self.current_protocol_version()?;
+        if version != PROTOCOL_VERSION {
+            let err = protocol_version_mismatch(version);
+            tracing::error!("{err}");
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Read the current protocol version of the chain. The chains that
+    /// didn't record it are at the initial version.
+    fn current_protocol_version(&self) -> Result<u64> {
+        match read_protocol_version(&self.state)? {
+            Some(version) => Ok(version),
+            None => {
+                tracing::warn!(
+                    "The chain didn't record its protocol version, assuming \
+                     the initial version {INITIAL_PROTOCOL_VERSION}"
+                );
+                Ok(INITIAL_PROTOCOL_VERSION)
+            }
+        }
+    }
+}
+
+fn protocol_version_mismatch(chain_version: u64) -> Error {
+    Error::ProtocolVersion(format!(
+        "The chain is at protocol version {chain_version}, but this binary \
+         implements version {PROTOCOL_VERSION}. Install a binary that \
+         implements the protocol version {chain_version} to keep processing \
+         blocks."
+    ))
+}
+
+#[cfg(test)]
+mod test_protocol_version {
+    use namada::core::time::DateTimeUtc;
+    use namada::ledger::parameters::{
+        protocol_version_handle, storage as params_storage,
+        write_protocol_version,
+    };
+    use namada::state::{LastBlock, StorageWrite};
+
+    use super::*;
+    use crate::node::ledger::shell::test_utils;
+
+    /// Test that a node only starts with a binary that implements the current
+    /// protocol version, or the version of an upgrade taking effect in the
+    /// next block
+    #[test]
+    fn test_protocol_version_at_startup() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        shell.state.in_mem_mut().last_block = Some(LastBlock {
+            height: BlockHeight(1),
+            time: DateTimeUtc::unix_epoch(),
+        });
+        shell.check_protocol_version_at_startup().unwrap();
+
+        // The chain is at an older version, with an upgrade to the version of
+        // this binary scheduled for the next epoch
+        write_protocol_version(&mut shell.state, PROTOCOL_VERSION - 1).unwrap();
+        let next_epoch = shell.state.in_mem().block.epoch.next();
+        protocol_version_handle()
+            .schedule(&mut shell.state, next_epoch, PROTOCOL_VERSION)
+            .unwrap();
+        let res = shell.check_protocol_version_at_startup();
+        assert!(matches!(res, Err(Error::ProtocolVersion(_))));
+
+        // The upgrade takes effect in the next block
+        shell.state.in_mem_mut().update_epoch_blocks_delay = Some(2);
+        let res = shell.check_protocol_version_at_startup();
+        assert!(matches!(res, Err(Error::ProtocolVersion(_))));
+        shell.state.in_mem_mut().update_epoch_blocks_delay = Some(1);
+        shell.check_protocol_version_at_startup().unwrap();
+
+        // A chain that didn't record its version is at the initial version
+        shell.state.in_mem_mut().update_epoch_blocks_delay = None;
+        shell
+            .state
+            .delete(&params_storage::get_protocol_version_key())
+            .unwrap();
+        let res = shell.check_protocol_version_at_startup();
+        assert_eq!(res.is_ok(), PROTOCOL_VERSION == INITIAL_PROTOCOL_VERSION);
+    }
+}
@@ -1,7 +1,7 @@
 //! Shell methods for querying state
 
-use namada::ledger::dry_run_tx;
 use namada::ledger::queries::{RequestCtx, ResponseQuery};
+use namada::ledger::{dry_run_tx, eval_vp};
 
 use super::*;
 
@@ -15,6 +15,17 @@ where
     /// the default if `path` is not a supported string.
     /// INVARIANT: This method must be stateless.
     pub fn query(&self, query: request::Query) -> response::Query {
+        if let Err(info) = check_query_size(
+            "request",
+            query.data.len(),
+            self.max_query_request_bytes,
+        ) {
+            return response::Query {
+                code: 1.into(),
+                info,
+                ..Default::default()
+            };
+        }
         let ctx = RequestCtx {
             state: self.state.read_only(),
             event_log: self.event_log(),
@@ -23,12 +34,42 @@ where
             storage_read_past_height_limit: self.storage_read_past_height_limit,
         };
 
+        // The router ignores a trailing slash, the gated paths must be matched
+        // without it
+        let path = query.path.trim_end_matches('/');
         // Invoke the root RPC handler - returns borsh-encoded data on success
-        let result = if query.path == "/shell/dry_run_tx" {
+        let result = if path == "/shell/dry_run_tx" {
             dry_run_tx(ctx, &query)
+        } else if path == "/shell/dev/eval_vp" {
+            match &self.eval_vp {
+                Some(config) => eval_vp(
+                    ctx,
+                    &query,
+                    config.max_gas,
+                    config.max_height_range,
+                ),
+                None => Err(namada::state::StorageError::new_const(
+                    "The VP evaluation query is disabled on this node",
+                )),
+            }
+        } else if path == "/shell/state_checksum" && !self.state_checksum_query
+        {
+            Err(namada::state::StorageError::new_const(
+                "The state checksum query is disabled on this node",
+            ))
         } else {
             namada::ledger::queries::handle_path(ctx, &query)
         };
+        let result = result
+            .map_err(|err| format!("RPC error: {}", err))
+            .and_then(|response| {
+                check_query_size(
+                    "response",
+                    response.data.len(),
+                    self.max_query_response_bytes,
+                )?;
+                Ok(response)
+            });
         match result {
             Ok(ResponseQuery {
                 data,
@@ -42,9 +83,9 @@ where
                 height: height.0.try_into().expect("Height should be parsable"),
                 ..Default::default()
             },
-            Err(err) => response::Query {
+            Err(info) => response::Query {
                 code: 1.into(),
-                info: format!("RPC error: {}", err),
+                info,
                 ..Default::default()
             },
         }
@@ -64,6 +105,25 @@ where
     }
 }
 
+/// Check that the size of the data of a query is within the limit, if any
+fn check_query_size(
+    kind: &str,
+    len: usize,
+    max_bytes: Option<u64>,
+) -> Result<(), String> {
+    match max_bytes {
+        Some(max_bytes)
+            if u64::try_from(len).unwrap_or(u64::MAX) > max_bytes =>
+        {
+            Err(format!(
+                "The query {kind} of {len} bytes exceeds the limit of \
+                 {max_bytes} bytes"
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
 // NOTE: we are testing `namada::ledger::queries_ext`,
 // which is not possible from `namada` since we do not have
 // access to the `Shell` there
@@ -72,9 +132,12 @@ mod test_queries {
     use namada::core::storage::Epoch;
     use namada::eth_bridge::storage::eth_bridge_queries::is_bridge_comptime_enabled;
     use namada::ledger::pos::PosQueries;
+    use namada::ledger::queries::EvalVpRequest;
     use namada::proof_of_stake::storage::read_consensus_validator_set_addresses_with_stake;
     use namada::proof_of_stake::types::WeightedValidator;
+    use namada::state::LastBlock;
     use namada::tendermint::abci::types::VoteInfo;
+    use namada::tx::Tx;
     use namada_sdk::eth_bridge::SendValsetUpd;
 
     use super::*;
@@ -179,6 +242,97 @@ mod test_queries {
         }
     }
 
+    /// Test that the queries with a request or a response larger than the
+    /// configured limits are rejected
+    #[test]
+    fn test_query_size_limits() {
+        let (mut shell, _recv, _, _oracle_control_recv) =
+            test_utils::setup_at_height(0u64);
+        let query = request::Query {
+            path: "/shell/epoch".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(shell.query(query.clone()).code, 0.into());
+
+        shell.max_query_response_bytes = Some(0);
+        let response = shell.query(query.clone());
+        assert_eq!(response.code, 1.into());
+        assert!(response.info.contains("The query response"));
+
+        shell.max_query_response_bytes = None;
+        shell.max_query_request_bytes = Some(4);
+        let response = shell.query(request::Query {
+            data: vec![0; 5].into(),
+            ..query
+        });
+        assert_eq!(response.code, 1.into());
+        assert!(response.info.contains("The query request of 5 bytes"));
+    }
+
+    /// Test that the VP evaluation query is only served when enabled and
+    /// within the configured height range
+    #[test]
+    fn test_eval_vp_config() {
+        let (mut shell, _recv, _, _oracle_control_recv) = test_utils::setup();
+        shell.state.in_mem_mut().last_block = Some(LastBlock {
+            height: BlockHeight(3),
+            time: DateTimeUtc::unix_epoch(),
+        });
+        let request = EvalVpRequest {
+            height: BlockHeight(1),
+            address: namada::core::address::testing::established_address_1(),
+            vp_code: vec![],
+            tx: Tx::new(shell.chain_id.clone(), None),
+        };
+        let query = request::Query {
+            path: "/shell/dev/eval_vp".to_string(),
+            data: request.serialize_to_vec().into(),
+            ..Default::default()
+        };
+        let response = shell.query(query.clone());
+        assert_eq!(response.code, 1.into());
+        assert!(response.info.contains("disabled"));
+        // A trailing slash doesn't bypass the config
+        let response = shell.query(request::Query {
+            path: "/shell/dev/eval_vp/".to_string(),
+            ..query.clone()
+        });
+        assert_eq!(response.code, 1.into());
+        assert!(response.info.contains("disabled"));
+
+        shell.eval_vp = Some(config::EvalVpConfig {
+            max_height_range: 1,
+            ..Default::default()
+        });
+        let response = shell.query(query);
+        assert_eq!(response.code, 1.into());
+        assert!(response.info.contains("more than 1 blocks below"));
+    }
+
+    /// Test that the state checksum query is only served when enabled
+    #[test]
+    fn test_state_checksum_config() {
+        let (mut shell, _recv, _, _oracle_control_recv) = test_utils::setup();
+        let query = request::Query {
+            path: "/shell/state_checksum".to_string(),
+            ..Default::default()
+        };
+        let response = shell.query(query.clone());
+        assert_eq!(response.code, 1.into());
+        assert!(response.info.contains("disabled"));
+        // A trailing slash doesn't bypass the config
+        let response = shell.query(request::Query {
+            path: "/shell/state_checksum/".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(response.code, 1.into());
+        assert!(response.info.contains("disabled"));
+
+        shell.state_checksum_query = true;
+        let response = shell.query(query);
+        assert_eq!(response.code, 0.into());
+    }
+
     test_must_send_valset_upd! {
         epoch_assertions: [
             // (current epoch, current block height, must send valset upd)
@@ -0,0 +1,118 @@
+//! Validators' telemetry heartbeats sent as vote extensions.
+//!
+//! The heartbeats themselves are implemented in
+//! [`namada::proof_of_stake::telemetry`], this module only takes care of
+//! crafting, signing and validating the protocol txs that carry them.
+
+use namada::proof_of_stake::telemetry::{
+    read_validator_telemetry, validate_telemetry_heartbeat, ValidatorTelemetry,
+    MIN_TELEMETRY_HEARTBEAT_INTERVAL,
+};
+use namada::tx::data::protocol::{ProtocolTx, ProtocolTxType};
+use namada::tx::{Authorization, Data};
+use namada::vote_ext::telemetry;
+
+use super::*;
+use crate::cli::{namada_commit_hash, namada_version};
+
+impl<D, H> Shell<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// Extend PreCommit votes with a [`telemetry::Vext`], if this node opted
+    /// in to send telemetry heartbeats and the last heartbeat of the
+    /// validator in storage is older than the interval, which cannot be
+    /// shorter than [`MIN_TELEMETRY_HEARTBEAT_INTERVAL`].
+    ///
+    /// This only depends on storage, so a heartbeat is extended again until
+    /// one is included in a block.
+    pub fn extend_vote_with_telemetry(&self) -> Option<telemetry::Vext> {
+        let interval = self
+            .telemetry_heartbeat_interval?
+            .max(MIN_TELEMETRY_HEARTBEAT_INTERVAL);
+        let validator_addr = self.mode.get_validator_address()?;
+        let block_height = self.state.in_mem().get_last_block_height();
+        if block_height == BlockHeight(0) {
+            // a heartbeat must be signed after the genesis block
+            return None;
+        }
+        let last_telemetry =
+            read_validator_telemetry(&self.state, validator_addr)
+                .expect("Reading the last telemetry heartbeat shouldn't fail");
+        if let Some(ValidatorTelemetry {
+            block_height: last_height,
+            ..
+        }) = last_telemetry
+        {
+            if block_height.0 < last_height.0.saturating_add(interval) {
+                return None;
+            }
+        }
+        Some(telemetry::Vext {
+            validator_addr: validator_addr.clone(),
+            block_height,
+            binary_version: namada_version().to_string(),
+            commit_hash: namada_commit_hash().to_string(),
+        })
+    }
+
+    /// Sign a telemetry heartbeat with this validator's protocol key and wrap
+    /// it in a protocol tx.
+    pub(super) fn sign_telemetry_vext(
+        &self,
+        ext: &telemetry::Vext,
+    ) -> Option<Tx> {
+        let protocol_sk = self.mode.get_protocol_key()?;
+        let mut tx = Tx::from_type(TxType::Protocol(Box::new(ProtocolTx {
+            pk: protocol_sk.to_public(),
+            tx: ProtocolTxType::TelemetryVext,
+        })));
+        tx.header.chain_id = self.chain_id.clone();
+        tx.set_data(Data::new(ext.serialize_to_vec()));
+        tx.add_section(Section::Authorization(Authorization::new(
+            tx.sechashes(),
+            [(0, protocol_sk.clone())].into_iter().collect(),
+            None,
+        )));
+        Some(tx)
+    }
+
+    /// Validate a protocol tx carrying a validator's telemetry heartbeat.
+    /// The tx signature must have been verified beforehand.
+    pub(super) fn validate_telemetry_tx(
+        &self,
+        tx: &Tx,
+    ) -> std::result::Result<(), String> {
+        let protocol_tx = match tx.header().tx_type {
+            TxType::Protocol(protocol_tx)
+                if matches!(protocol_tx.tx, ProtocolTxType::TelemetryVext) =>
+            {
+                protocol_tx
+            }
+            _ => {
+                return Err("Expected a telemetry vote extension protocol tx"
+                    .to_string());
+            }
+        };
+        let telemetry::Vext {
+            validator_addr,
+            block_height,
+            binary_version,
+            commit_hash,
+        } = telemetry::Vext::try_from(tx).map_err(|err| {
+            format!("Invalid telemetry vote extension: {err}")
+        })?;
+        validate_telemetry_heartbeat(
+            &self.state,
+            &validator_addr,
+            &ValidatorTelemetry {
+                binary_version,
+                commit_hash,
+                block_height,
+            },
+            &protocol_tx.pk,
+        )
+        .map_err(|err| err.to_string())
+    }
+}
@@ -9,6 +9,7 @@ use namada::ethereum_bridge::protocol::transactions::bridge_pool_roots::sign_bri
 use namada::ethereum_bridge::protocol::transactions::ethereum_events::sign_ethereum_events;
 use namada::ethereum_bridge::protocol::transactions::validator_set_update::sign_validator_set_update;
 pub use namada::ethereum_bridge::protocol::validation::VoteExtensionError;
+use namada::tx::data::protocol::ProtocolTxType;
 use namada::tx::Signed;
 use namada::vote_ext::{
     bridge_pool_roots, ethereum_events, validator_set_update, VoteExtension,
@@ -36,6 +37,7 @@ where
                 .extend_vote_with_bp_roots()
                 .map(namada::vote_ext::bridge_pool_roots::SignedVext),
             validator_set_update: self.extend_vote_with_valset_update(),
+            telemetry: self.extend_vote_with_telemetry(),
         }
     }
 
@@ -113,7 +115,9 @@ where
     }
 
     /// Given a slice of [`TxBytes`], return an iterator over the
-    /// ones we could deserialize to vote extension protocol txs.
+    /// ones we could deserialize to vote extension protocol txs, or to
+    /// validator operational metadata or telemetry protocol txs that are
+    /// still valid.
     pub fn deserialize_vote_extensions<'shell>(
         &'shell self,
         txs: &'shell mut Vec<TxBytes>,
@@ -131,6 +135,19 @@ where
                     return false;
                 }
             };
+            if let TxType::Protocol(protocol_tx) = tx.header().tx_type {
+                match protocol_tx.tx {
+                    ProtocolTxType::ValidatorOperationalMetadata => {
+                        return self
+                            .validate_operational_metadata_tx(&tx)
+                            .is_ok();
+                    }
+                    ProtocolTxType::TelemetryVext => {
+                        return self.validate_telemetry_tx(&tx).is_ok();
+                    }
+                    _ => {}
+                }
+            }
             match (&tx).try_into().ok() {
                 Some(EthereumTxData::BridgePoolVext(_)) => true,
                 Some(EthereumTxData::EthEventsVext(ext)) => {
@@ -164,8 +181,9 @@ where
     }
 }
 
-/// Yields an iterator over the protocol transactions
-/// in a [`VoteExtension`].
+/// Yields an iterator over the Ethereum protocol transactions
+/// in a [`VoteExtension`]. The telemetry heartbeat, which
+/// carries no Ethereum data, is left out.
 pub fn iter_protocol_txs(
     ext: VoteExtension,
 ) -> impl Iterator<Item = EthereumTxData> {
@@ -173,6 +191,7 @@ pub fn iter_protocol_txs(
         ethereum_events,
         bridge_pool_root,
         validator_set_update,
+        telemetry: _,
     } = ext;
     [
         ethereum_events.map(|e| {
@@ -0,0 +1,524 @@
+//! An opt-in service answering the queries of the shielded balance and of the
+//! history of the notes of viewing keys, for self-hosted infrastructure and
+//! custodians.
+//!
+//! The viewing key is submitted in the JSON body of a `POST` request to
+//! `/shielded/balance` or `/shielded/history`, as `{"viewing_key": "..."}`.
+//! The node scans the shielded transactions of the chain with the key and
+//! keeps the results in a shielded context of the key stored in its chain
+//! directory, so that the next queries only scan the new blocks. Every key has
+//! its own context, so the queries of different keys are processed
+//! concurrently. At most [`MAX_STORED_KEYS`] contexts are kept and a context
+//! is removed [`KEY_EXPIRY`] after the last query of its key.
+//!
+//! Privacy: a viewing key reveals all the shielded transactions of its owner.
+//! The operator of the node learns the key, can link it to the address of the
+//! requester and keeps the decrypted notes on disk until the context expires.
+//! The responses carry a warning to this effect.
+//!
+//! Since scanning is expensive, the requests are rate limited per IP address.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use masp_primitives::sapling::ViewingKey;
+use namada::core::collections::HashMap;
+use namada::core::hash::Hash;
+use namada::core::masp::ExtendedViewingKey;
+use namada::token;
+use namada_sdk::io::NullIo;
+use namada_sdk::masp::fs::FsShieldedUtils;
+use namada_sdk::masp::{DefaultLogger, ShieldedContext};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use warp::http::StatusCode;
+use warp::reply::{Json, WithStatus};
+use warp::Filter;
+
+use crate::facade::tendermint_rpc::HttpClient;
+use crate::node::ledger::services::{Policy, RateLimiter, RATE_LIMIT_WINDOW};
+
+/// The max number of viewing keys whose shielded context is kept
+pub const MAX_STORED_KEYS: usize = 64;
+
+/// The duration after the last query of a viewing key after which its
+/// shielded context is removed
+pub const KEY_EXPIRY: Duration = Duration::from_secs(60 * 60);
+
+/// The warning attached to every response
+const PRIVACY_WARNING: &str = "The viewing key was revealed to this node. Its \
+                               operator can see all the shielded transactions \
+                               of the key and link them to the requester. \
+                               Only query nodes that you trust.";
+
+/// A query of a viewing key
+#[derive(Debug, Deserialize)]
+struct Request {
+    viewing_key: String,
+}
+
+/// The shielded balance of a viewing key in a token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TokenBalance {
+    /// The address of the token
+    pub token: String,
+    /// The epoch of the asset type, if it is epoched
+    pub epoch: Option<u64>,
+    /// The amount, in the smallest unit of the token
+    pub amount: String,
+}
+
+/// A note received by a viewing key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NoteEntry {
+    /// The height of the block of the tx that created the note
+    pub height: u64,
+    /// The index of the tx in its block
+    pub tx_index: u32,
+    /// The address of the token, if the asset type could be decoded
+    pub token: Option<String>,
+    /// The epoch of the asset type, if it is epoched
+    pub epoch: Option<u64>,
+    /// The amount, in the smallest unit of the token
+    pub amount: String,
+    /// Whether the note has been spent
+    pub spent: bool,
+}
+
+/// The response of a query
+#[derive(Debug, Serialize)]
+struct Response<T> {
+    warning: &'static str,
+    #[serde(flatten)]
+    result: T,
+}
+
+#[derive(Debug, Serialize)]
+struct Balances {
+    balances: Vec<TokenBalance>,
+}
+
+#[derive(Debug, Serialize)]
+struct History {
+    notes: Vec<NoteEntry>,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// The state shared by the handlers of the requests
+#[derive(Clone)]
+struct Context {
+    client: HttpClient,
+    shielded: Arc<std::sync::Mutex<KeyContexts<KeyContext>>>,
+    rate_limiter: Arc<std::sync::Mutex<RateLimiter>>,
+}
+
+/// The shielded context of a viewing key, locked while the key is queried
+type KeyContext = Mutex<ShieldedContext<FsShieldedUtils>>;
+
+/// The stored contexts of the viewing keys, by the hash of the keys
+struct KeyContexts<T> {
+    /// The directory of the contexts, each one being stored in a
+    /// sub-directory named after the hash of its key
+    dir: PathBuf,
+    /// The contexts with the time of the last query of their key
+    contexts: HashMap<Hash, (Arc<T>, Instant)>,
+}
+
+impl<T> KeyContexts<T> {
+    fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            contexts: HashMap::default(),
+        }
+    }
+
+    /// Get the context of a key, or initialize it from its directory with
+    /// `init`. The expired contexts are removed first and, if
+    /// [`MAX_STORED_KEYS`] contexts are still stored, the least recently
+    /// queried one that is not in use is evicted. Returns `None` if every
+    /// stored context is in use.
+    fn get_or_init(
+        &mut self,
+        key_hash: Hash,
+        now: Instant,
+        init: impl FnOnce(PathBuf) -> T,
+    ) -> Option<Arc<T>> {
+        let expired: Vec<Hash> = self
+            .contexts
+            .iter()
+            .filter(|(_, (context, last_query))| {
+                Arc::strong_count(context) == 1
+                    && now.saturating_duration_since(*last_query) >= KEY_EXPIRY
+            })
+            .map(|(key_hash, _)| *key_hash)
+            .collect();
+        for key_hash in expired {
+            self.remove(&key_hash);
+        }
+
+        if let Some((context, last_query)) = self.contexts.get_mut(&key_hash) {
+            *last_query = now;
+            return Some(context.clone());
+        }
+        if self.contexts.len() >= MAX_STORED_KEYS {
+            let evicted = self
+                .contexts
+                .iter()
+                .filter(|(_, (context, _))| Arc::strong_count(context) == 1)
+                .min_by_key(|(_, (_, last_query))| *last_query)
+                .map(|(key_hash, _)| *key_hash)?;
+            self.remove(&evicted);
+        }
+        let context = Arc::new(init(self.key_dir(&key_hash)));
+        self.contexts.insert(key_hash, (context.clone(), now));
+        Some(context)
+    }
+
+    /// Remove the context of a key together with its directory
+    fn remove(&mut self, key_hash: &Hash) {
+        self.contexts.swap_remove(key_hash);
+        let dir = self.key_dir(key_hash);
+        if dir.exists() {
+            if let Err(err) = std::fs::remove_dir_all(&dir) {
+                tracing::error!(
+                    "Failed to remove the shielded context in {}: {err}",
+                    dir.to_string_lossy()
+                );
+            }
+        }
+    }
+
+    /// The directory of the context of a key
+    fn key_dir(&self, key_hash: &Hash) -> PathBuf {
+        self.dir.join(key_hash.to_string())
+    }
+}
+
+/// Serve the shielded queries on the given address, using the RPC of the
+/// CometBFT node at `rpc_address` and storing the shielded context in
+/// `context_dir`, with the given policy, until a signal is sent on
+/// `abort_recv`.
+pub async fn serve(
+    listen_addr: SocketAddr,
+    rpc_address: SocketAddr,
+    context_dir: PathBuf,
+    rate_limit: u32,
+    policy: Policy,
+    abort_recv: tokio::sync::oneshot::Receiver<()>,
+) {
+    let client = HttpClient::new(format!("http://{rpc_address}").as_str())
+        .expect("Failed to create the CometBFT RPC client");
+    if let Err(err) = std::fs::create_dir_all(&context_dir) {
+        tracing::error!(
+            "Failed to create the directory of the shielded queries' context: \
+             {err}"
+        );
+        return;
+    }
+    let ctx = Context {
+        client,
+        shielded: Arc::new(std::sync::Mutex::new(KeyContexts::new(
+            context_dir,
+        ))),
+        rate_limiter: Arc::new(std::sync::Mutex::new(RateLimiter::new(
+            rate_limit,
+            RATE_LIMIT_WINDOW,
+        ))),
+    };
+    let api = warp::post()
+        .and(warp::path!("shielded" / String))
+        .and(warp::addr::remote())
+        .and(warp::body::json())
+        .then(
+            move |endpoint: String,
+                  remote: Option<SocketAddr>,
+                  request: Request| {
+                let ctx = ctx.clone();
+                async move { route(ctx, &endpoint, remote, request).await }
+            },
+        );
+
+    tracing::warn!(
+        ?listen_addr,
+        "Starting the shielded queries. The viewing keys submitted to them \
+         are revealed to this node."
+    );
+    let (_, server) = warp::serve(policy.apply(api))
+        .bind_with_graceful_shutdown(listen_addr, async move {
+            if abort_recv.await.is_err() {
+                tracing::error!(
+                    "The shielded queries abort sender has unexpectedly \
+                     dropped"
+                );
+            }
+            tracing::info!("Shutting down the shielded queries...");
+        });
+    server.await
+}
+
+/// Check the rate limit and dispatch a request to the handler of its endpoint
+async fn route(
+    ctx: Context,
+    endpoint: &str,
+    remote: Option<SocketAddr>,
+    request: Request,
+) -> WithStatus<Json> {
+    if !matches!(endpoint, "balance" | "history") {
+        return error_reply(
+            StatusCode::NOT_FOUND,
+            format!("Unknown endpoint /shielded/{endpoint}"),
+        );
+    }
+    if let Some(remote) = remote {
+        #[allow(clippy::disallowed_methods)]
+        let now = Instant::now();
+        let allowed = ctx
+            .rate_limiter
+            .lock()
+            .expect("The rate limiter lock should not be poisoned")
+            .check(remote.ip(), now);
+        if !allowed {
+            return error_reply(
+                StatusCode::TOO_MANY_REQUESTS,
+                "Too many shielded queries, try again later".to_string(),
+            );
+        }
+    }
+    let vk = match ExtendedViewingKey::from_str(&request.viewing_key) {
+        Ok(vk) => vk,
+        Err(err) => {
+            return error_reply(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid viewing key: {err}"),
+            );
+        }
+    };
+    let key_hash = Hash::sha256(vk.to_string());
+    let vk: ViewingKey = vk.into();
+
+    #[allow(clippy::disallowed_methods)]
+    let now = Instant::now();
+    let key_context = ctx
+        .shielded
+        .lock()
+        .expect("The shielded contexts lock should not be poisoned")
+        .get_or_init(key_hash, now, |dir| {
+            if let Err(err) = std::fs::create_dir_all(&dir) {
+                tracing::error!(
+                    "Failed to create the directory of a shielded context: \
+                     {err}"
+                );
+            }
+            Mutex::new(FsShieldedUtils::new(dir))
+        });
+    let Some(key_context) = key_context else {
+        return error_reply(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Too many viewing keys are being queried, try again later"
+                .to_string(),
+        );
+    };
+    // Only the queries of the same key wait for each other
+    let mut shielded = key_context.lock().await;
+    if let Err(err) = shielded
+        .fetch(
+            &ctx.client,
+            &DefaultLogger::new(&NullIo),
+            None,
+            None,
+            1,
+            &[],
+            &[vk],
+        )
+        .await
+    {
+        tracing::debug!(?err, "Failed to scan the shielded transactions");
+        return error_reply(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to scan the shielded transactions: {err}"),
+        );
+    }
+    if endpoint == "balance" {
+        let balances = balances(&mut shielded, &ctx.client, &vk).await;
+        ok_reply(Balances { balances })
+    } else {
+        let notes = history(&mut shielded, &ctx.client, &vk).await;
+        ok_reply(History { notes })
+    }
+}
+
+/// The shielded balance of a viewing key per token and epoch
+async fn balances(
+    shielded: &mut ShieldedContext<FsShieldedUtils>,
+    client: &HttpClient,
+    vk: &ViewingKey,
+) -> Vec<TokenBalance> {
+    let Ok(Some(balance)) = shielded.compute_shielded_balance(vk).await else {
+        return vec![];
+    };
+    let (decoded, _undecoded) =
+        shielded.decode_combine_sum(client, balance).await;
+    decoded
+        .components()
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|((epoch, token), amount)| TokenBalance {
+            token: token.to_string(),
+            epoch: epoch.map(|epoch| epoch.0),
+            amount: amount.to_string(),
+        })
+        .collect()
+}
+
+/// The notes received by a viewing key, in chronological order
+async fn history(
+    shielded: &mut ShieldedContext<FsShieldedUtils>,
+    client: &HttpClient,
+    vk: &ViewingKey,
+) -> Vec<NoteEntry> {
+    let positions: Vec<usize> = shielded
+        .pos_map
+        .get(vk)
+        .map(|positions| positions.iter().copied().collect())
+        .unwrap_or_default();
+    let mut notes = Vec::with_capacity(positions.len());
+    for pos in positions {
+        let Some(note) = shielded.note_map.get(&pos).cloned() else {
+            continue;
+        };
+        // The tx of a note is the last one whose first note precedes it
+        let Some(indexed_tx) = shielded
+            .tx_note_map
+            .iter()
+            .filter(|(_, first_pos)| **first_pos <= pos)
+            .map(|(indexed_tx, _)| *indexed_tx)
+            .last()
+        else {
+            continue;
+        };
+        let spent = shielded.spents.contains(&pos);
+        let decoded = shielded.decode_asset_type(client, note.asset_type).await;
+        let (token, epoch, amount) = match decoded {
+            Some(asset) => (
+                Some(asset.token.to_string()),
+                asset.epoch.map(|epoch| epoch.0),
+                token::Amount::from_masp_denominated(
+                    note.value,
+                    asset.position,
+                )
+                .to_string(),
+            ),
+            None => (None, None, note.value.to_string()),
+        };
+        notes.push(NoteEntry {
+            height: indexed_tx.height.0,
+            tx_index: indexed_tx.index.0,
+            token,
+            epoch,
+            amount,
+            spent,
+        });
+    }
+    notes
+}
+
+fn ok_reply<T: Serialize>(result: T) -> WithStatus<Json> {
+    warp::reply::with_status(
+        warp::reply::json(&Response {
+            warning: PRIVACY_WARNING,
+            result,
+        }),
+        StatusCode::OK,
+    )
+}
+
+fn error_reply(status: StatusCode, error: String) -> WithStatus<Json> {
+    warp::reply::with_status(
+        warp::reply::json(&Response {
+            warning: PRIVACY_WARNING,
+            result: ErrorResponse { error },
+        }),
+        status,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn key_hash(i: usize) -> Hash {
+        Hash::sha256(i.to_le_bytes())
+    }
+
+    /// Test that the number of stored contexts is bounded and that only the
+    /// contexts that are not in use are evicted
+    #[test]
+    fn test_key_contexts_bound() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut contexts = KeyContexts::new(dir.path().to_path_buf());
+        #[allow(clippy::disallowed_methods)]
+        let start = Instant::now();
+
+        // Keep all the contexts in use
+        let in_use: Vec<_> = (0..MAX_STORED_KEYS)
+            .map(|i| {
+                let now = start + Duration::from_secs(i as u64);
+                contexts.get_or_init(key_hash(i), now, |_| ()).unwrap()
+            })
+            .collect();
+        assert_eq!(contexts.contexts.len(), MAX_STORED_KEYS);
+        let now = start + Duration::from_secs(MAX_STORED_KEYS as u64);
+        assert!(
+            contexts
+                .get_or_init(key_hash(MAX_STORED_KEYS), now, |_| ())
+                .is_none()
+        );
+        // A stored key is still served
+        assert!(contexts.get_or_init(key_hash(0), now, |_| ()).is_some());
+
+        // Once released, the least recently queried context is evicted
+        drop(in_use);
+        assert!(
+            contexts
+                .get_or_init(key_hash(MAX_STORED_KEYS), now, |_| ())
+                .is_some()
+        );
+        assert_eq!(contexts.contexts.len(), MAX_STORED_KEYS);
+        assert!(!contexts.contexts.contains_key(&key_hash(1)));
+        assert!(contexts.contexts.contains_key(&key_hash(0)));
+    }
+
+    /// Test that the contexts are removed with their directory once expired
+    #[test]
+    fn test_key_contexts_expiry() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut contexts = KeyContexts::new(dir.path().to_path_buf());
+        #[allow(clippy::disallowed_methods)]
+        let start = Instant::now();
+
+        contexts.get_or_init(key_hash(0), start, |dir| {
+            std::fs::create_dir_all(dir).unwrap()
+        });
+        let key_dir = contexts.key_dir(&key_hash(0));
+        assert!(key_dir.exists());
+
+        // A context in use doesn't expire
+        let now = start + KEY_EXPIRY;
+        let in_use = contexts.get_or_init(key_hash(1), now, |_| ()).unwrap();
+        let now = now + KEY_EXPIRY;
+        contexts.get_or_init(key_hash(2), now, |_| ());
+        assert!(!contexts.contexts.contains_key(&key_hash(0)));
+        assert!(contexts.contexts.contains_key(&key_hash(1)));
+        assert!(!key_dir.exists());
+
+        drop(in_use);
+        contexts.get_or_init(key_hash(2), now, |_| ());
+        assert!(!contexts.contexts.contains_key(&key_hash(1)));
+    }
+}
@@ -8,6 +8,7 @@ use namada::core::hash::Hash;
 use namada::core::key::tm_raw_hash_to_string;
 use namada::core::storage::BlockHeight;
 use namada::proof_of_stake::storage::find_validator_by_raw_hash;
+use namada::state::DB;
 use namada::time::{DateTimeUtc, Utc};
 use namada::tx::data::hash_tx;
 use tokio::sync::broadcast;
@@ -89,90 +90,117 @@ impl AbcippShim {
         hash_tx(bytes.as_slice())
     }
 
+    /// Last committed block height, if any
+    pub fn last_block_height(&self) -> Option<BlockHeight> {
+        self.service
+            .state
+            .in_mem()
+            .last_block
+            .as_ref()
+            .map(|last_block| last_block.height)
+    }
+
     /// Run the shell's blocking loop that receives messages from the
     /// [`AbciService`].
     pub fn run(mut self) {
         while let Ok((req, resp_sender)) = self.shell_recv.recv() {
-            let resp = match req {
-                Req::ProcessProposal(proposal) => self
-                    .service
-                    .call(Request::ProcessProposal(proposal))
-                    .map_err(Error::from)
-                    .and_then(|resp| resp.try_into()),
-                Req::BeginBlock(block) => {
-                    // we save this data to be forwarded to finalize later
-                    self.begin_block_request = Some(block);
-                    Ok(Resp::BeginBlock(Default::default()))
-                }
-                Req::DeliverTx(tx) => {
-                    self.delivered_txs.push(tx.tx);
-                    Ok(Resp::DeliverTx(Default::default()))
-                }
-                Req::EndBlock(_) => {
-                    let begin_block_request =
-                        self.begin_block_request.take().unwrap();
-                    let block_time = begin_block_request
-                        .header
-                        .time
-                        .try_into()
-                        .expect("valid RFC3339 block time");
-
-                    let tm_raw_hash_string = tm_raw_hash_to_string(
-                        begin_block_request.header.proposer_address,
-                    );
-                    let block_proposer = find_validator_by_raw_hash(
-                        &self.service.state,
-                        tm_raw_hash_string,
-                    )
-                    .unwrap()
-                    .expect(
-                        "Unable to find native validator address of block \
-                         proposer from tendermint raw hash",
-                    );
-
-                    let processing_results = self.service.process_txs(
-                        &self.delivered_txs,
-                        block_time,
-                        &block_proposer,
-                    );
-                    let mut txs = Vec::with_capacity(self.delivered_txs.len());
-                    let mut delivered = vec![];
-                    std::mem::swap(&mut self.delivered_txs, &mut delivered);
-                    for (result, tx) in processing_results
-                        .into_iter()
-                        .zip(delivered.into_iter())
-                    {
-                        txs.push(ProcessedTx { tx, result });
-                    }
-                    let mut end_block_request: FinalizeBlock =
-                        begin_block_request.into();
-                    end_block_request.txs = txs;
-                    self.service
-                        .call(Request::FinalizeBlock(end_block_request))
-                        .map_err(Error::from)
-                        .and_then(|res| match res {
-                            Response::FinalizeBlock(resp) => {
-                                Ok(Resp::EndBlock(crate::facade::tendermint_proto::v0_37::abci::ResponseEndBlock::from(resp).try_into().unwrap()))
-                            }
-                            _ => Err(Error::ConvertResp(res)),
-                        })
-                }
-                _ => match Request::try_from(req.clone()) {
-                    Ok(request) => self
-                        .service
-                        .call(request)
-                        .map(Resp::try_from)
-                        .map_err(Error::Shell)
-                        .and_then(|inner| inner),
-                    Err(err) => Err(err),
-                },
-            };
-
+            let resp = self.handle(req);
             let resp = resp.map_err(|e| e.into());
             if resp_sender.send(resp).is_err() {
                 tracing::info!("ABCI response channel is closed")
             }
         }
+
+        // The ABCI server has shut down and all the in-flight requests have
+        // been processed, so the DB can be flushed before it's closed
+        self.flush_db();
+    }
+
+    /// Flush the DB of the shell.
+    pub fn flush_db(&self) {
+        tracing::info!("Flushing the DB...");
+        match self.service.state.db().flush(true) {
+            Ok(()) => tracing::info!("The DB has been flushed"),
+            Err(err) => tracing::error!("Failed to flush the DB: {err}"),
+        }
+    }
+
+    /// Handle a single ABCI request with the shell.
+    pub fn handle(&mut self, req: Req) -> Result<Resp, Error> {
+        match req {
+            Req::ProcessProposal(proposal) => self
+                .service
+                .call(Request::ProcessProposal(proposal))
+                .map_err(Error::from)
+                .and_then(|resp| resp.try_into()),
+            Req::BeginBlock(block) => {
+                // we save this data to be forwarded to finalize later
+                self.begin_block_request = Some(block);
+                Ok(Resp::BeginBlock(Default::default()))
+            }
+            Req::DeliverTx(tx) => {
+                self.delivered_txs.push(tx.tx);
+                Ok(Resp::DeliverTx(Default::default()))
+            }
+            Req::EndBlock(_) => {
+                let begin_block_request =
+                    self.begin_block_request.take().unwrap();
+                let block_time = begin_block_request
+                    .header
+                    .time
+                    .try_into()
+                    .expect("valid RFC3339 block time");
+
+                let tm_raw_hash_string = tm_raw_hash_to_string(
+                    begin_block_request.header.proposer_address,
+                );
+                let block_proposer = find_validator_by_raw_hash(
+                    &self.service.state,
+                    tm_raw_hash_string,
+                )
+                .unwrap()
+                .expect(
+                    "Unable to find native validator address of block \
+                     proposer from tendermint raw hash",
+                );
+
+                let processing_results = self.service.process_finalized_txs(
+                    begin_block_request.hash.as_bytes(),
+                    &self.delivered_txs,
+                    block_time,
+                    &block_proposer,
+                );
+                let mut txs = Vec::with_capacity(self.delivered_txs.len());
+                let mut delivered = vec![];
+                std::mem::swap(&mut self.delivered_txs, &mut delivered);
+                for (result, tx) in
+                    processing_results.into_iter().zip(delivered.into_iter())
+                {
+                    txs.push(ProcessedTx { tx, result });
+                }
+                let mut end_block_request: FinalizeBlock =
+                    begin_block_request.into();
+                end_block_request.txs = txs;
+                self.service
+                    .call(Request::FinalizeBlock(end_block_request))
+                    .map_err(Error::from)
+                    .and_then(|res| match res {
+                        Response::FinalizeBlock(resp) => {
+                            Ok(Resp::EndBlock(crate::facade::tendermint_proto::v0_37::abci::ResponseEndBlock::from(resp).try_into().unwrap()))
+                        }
+                        _ => Err(Error::ConvertResp(res)),
+                    })
+            }
+            _ => match Request::try_from(req.clone()) {
+                Ok(request) => self
+                    .service
+                    .call(request)
+                    .map(Resp::try_from)
+                    .map_err(Error::Shell)
+                    .and_then(|inner| inner),
+                Err(err) => Err(err),
+            },
+        }
     }
 }
 
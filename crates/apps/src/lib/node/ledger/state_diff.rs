@@ -0,0 +1,232 @@
+//! Diagnostics of app hash divergences.
+//!
+//! A dump holds the sorted subspace keys written at a block height with the
+//! hashes of their new values. Diffing the dumps of two nodes at the height
+//! of a consensus failure shows which keys diverged, grouped by their first
+//! segment to localize the subsystem at fault.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use namada::core::hash::Hash;
+use namada::core::storage::BlockHeight;
+use thiserror::Error;
+
+/// The value in a dump of a key deleted at the dumped height
+pub const DELETED: &str = "deleted";
+
+/// The written keys mapped to the hashes of their new values
+pub type WritesDump = BTreeMap<String, String>;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Failed to access the dump file {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+    #[error("Failed to encode the dump: {0}")]
+    Encode(toml::ser::Error),
+    #[error("Failed to decode the dump file {0}: {1}")]
+    Decode(PathBuf, toml::de::Error),
+}
+
+/// A key on which two dumps disagree
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeyDiff {
+    /// The key was only written by this node
+    OnlyThis(String),
+    /// The key was only written by the other node
+    OnlyOther(String),
+    /// The key was written with different values
+    Value(String),
+}
+
+impl KeyDiff {
+    /// The key on which the dumps disagree
+    pub fn key(&self) -> &str {
+        match self {
+            KeyDiff::OnlyThis(key)
+            | KeyDiff::OnlyOther(key)
+            | KeyDiff::Value(key) => key,
+        }
+    }
+
+    /// The subsystem owning the key, which is its first segment, e.g. the
+    /// address of an internal account
+    pub fn subsystem(&self) -> &str {
+        let key = self.key();
+        key.split_once('/').map_or(key, |(first, _)| first)
+    }
+}
+
+/// Hash the new values of the written keys
+pub fn hash_writes(writes: BTreeMap<String, Option<Vec<u8>>>) -> WritesDump {
+    writes
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Some(value) => Hash::sha256(value).to_string(),
+                None => DELETED.to_string(),
+            };
+            (key, value)
+        })
+        .collect()
+}
+
+/// Write a dump into a TOML file, suffixed with its height, and return the
+/// path of the file
+pub fn write_dump(
+    out_file_path: &Path,
+    height: BlockHeight,
+    dump: &WritesDump,
+) -> Result<PathBuf, Error> {
+    let full_path = out_file_path
+        .with_file_name(format!(
+            "{}_{height}",
+            out_file_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "writes_dump".to_string())
+        ))
+        .with_extension("toml");
+    let contents = toml::to_string(dump).map_err(Error::Encode)?;
+    std::fs::write(&full_path, contents)
+        .map_err(|err| Error::Io(full_path.clone(), err))?;
+    Ok(full_path)
+}
+
+/// Read a dump from a TOML file
+pub fn read_dump(path: &Path) -> Result<WritesDump, Error> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|err| Error::Io(path.to_path_buf(), err))?;
+    toml::from_str(&contents)
+        .map_err(|err| Error::Decode(path.to_path_buf(), err))
+}
+
+/// Find the keys on which two dumps disagree, sorted by key
+pub fn diff(this: &WritesDump, other: &WritesDump) -> Vec<KeyDiff> {
+    let mut diffs: Vec<KeyDiff> = this
+        .iter()
+        .filter_map(|(key, value)| match other.get(key) {
+            None => Some(KeyDiff::OnlyThis(key.clone())),
+            Some(other_value) if other_value != value => {
+                Some(KeyDiff::Value(key.clone()))
+            }
+            Some(_) => None,
+        })
+        .chain(
+            other
+                .keys()
+                .filter(|key| !this.contains_key(*key))
+                .map(|key| KeyDiff::OnlyOther(key.clone())),
+        )
+        .collect();
+    diffs.sort_by(|a, b| a.key().cmp(b.key()));
+    diffs
+}
+
+/// Count the keys on which two dumps disagree per subsystem
+pub fn diffs_per_subsystem(diffs: &[KeyDiff]) -> BTreeMap<&str, usize> {
+    let mut counts = BTreeMap::new();
+    for diff in diffs {
+        *counts.entry(diff.subsystem()).or_default() += 1;
+    }
+    counts
+}
+
+/// Diff the dumps in the given files and print a report
+pub fn diff_dumps(this_path: &Path, other_path: &Path) -> Result<(), Error> {
+    let this = read_dump(this_path)?;
+    let other = read_dump(other_path)?;
+    let diffs = diff(&this, &other);
+    if diffs.is_empty() {
+        println!("The dumps are identical ({} written keys).", this.len());
+        return Ok(());
+    }
+
+    println!("The dumps disagree on {} keys:", diffs.len());
+    for (subsystem, count) in diffs_per_subsystem(&diffs) {
+        println!("  {subsystem}: {count}");
+    }
+    println!();
+    for diff in &diffs {
+        match diff {
+            KeyDiff::OnlyThis(key) => {
+                println!("- {key} (only in {})", this_path.display())
+            }
+            KeyDiff::OnlyOther(key) => {
+                println!("+ {key} (only in {})", other_path.display())
+            }
+            KeyDiff::Value(key) => println!(
+                "~ {key}: {} != {}",
+                this[key.as_str()],
+                other[key.as_str()]
+            ),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dump(entries: &[(&str, &str)]) -> WritesDump {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_dumps() {
+        let this = dump(&[
+            ("#pos/a", "1"),
+            ("#pos/b", "2"),
+            ("#token/c", "3"),
+            ("#token/d", DELETED),
+        ]);
+        let other = dump(&[
+            ("#pos/a", "1"),
+            ("#pos/b", "4"),
+            ("#token/d", DELETED),
+            ("#token/e", "5"),
+            ("#gov", "6"),
+        ]);
+        assert!(diff(&this, &this).is_empty());
+
+        let diffs = diff(&this, &other);
+        assert_eq!(
+            diffs,
+            vec![
+                KeyDiff::OnlyOther("#gov".to_string()),
+                KeyDiff::Value("#pos/b".to_string()),
+                KeyDiff::OnlyThis("#token/c".to_string()),
+                KeyDiff::OnlyOther("#token/e".to_string()),
+            ]
+        );
+        let per_subsystem: Vec<_> =
+            diffs_per_subsystem(&diffs).into_iter().collect();
+        assert_eq!(
+            per_subsystem,
+            vec![("#gov", 1), ("#pos", 1), ("#token", 2)]
+        );
+    }
+
+    #[test]
+    fn test_dump_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let writes = BTreeMap::from([
+            ("#pos/a".to_string(), Some(vec![1, 2, 3])),
+            ("#pos/\"quoted\"".to_string(), Some(vec![])),
+            ("#token/b".to_string(), None),
+        ]);
+        let dump = hash_writes(writes);
+        assert_eq!(dump["#token/b"], DELETED);
+        assert_eq!(dump["#pos/a"], Hash::sha256([1, 2, 3]).to_string());
+
+        let path =
+            write_dump(&dir.path().join("writes"), BlockHeight(7), &dump)
+                .unwrap();
+        assert_eq!(path, dir.path().join("writes_7.toml"));
+        assert_eq!(read_dump(&path).unwrap(), dump);
+    }
+}
@@ -57,6 +57,7 @@ mod tests {
     use itertools::Itertools;
     use namada::core::chain::ChainId;
     use namada::core::collections::HashMap;
+    use namada::core::dec::Dec;
     use namada::core::ethereum_events::Uint;
     use namada::core::hash::Hash;
     use namada::core::keccak::KeccakHash;
@@ -175,6 +176,12 @@ mod tests {
             fee_unshielding_descriptions_limit: 0,
             minimum_gas_price: Default::default(),
             is_native_token_transferable: true,
+            max_epoch_hooks_gas: 0,
+            epoch_hook_allowlist: vec![],
+            target_block_gas: 0,
+            base_fee_max_change_rate: Dec::zero(),
+            masp_convert_anchor_grace_blocks: 0,
+            masp_max_note_age: 0,
         };
         parameters::init_storage(&params, &mut state).expect("Test failed");
         // insert and commit
@@ -951,4 +958,143 @@ mod tests {
             .unwrap();
         assert!(res2.is_none());
     }
+
+    #[test]
+    fn test_archive_mode_read_with_height() {
+        let db_path =
+            TempDir::new().expect("Unable to create a temporary DB directory");
+        let mut state = PersistentState::open(
+            db_path.path(),
+            None,
+            ChainId::default(),
+            address::testing::nam(),
+            None,
+            // Don't merklize any key
+            |_key: &Key| -> bool { false },
+        );
+        state.set_archive_mode(true);
+
+        let prefix = Key::parse("archive").unwrap();
+        let key_a = prefix.push(&"a".to_owned()).unwrap();
+        let key_b = prefix.push(&"b".to_owned()).unwrap();
+        // A sub-key of `key_a`, which must not be mixed up with it
+        let key_a_sub = key_a.push(&"0".to_owned()).unwrap();
+
+        state.in_mem_mut().begin_block(BlockHeight(1)).unwrap();
+        state.write(&key_a, 1u64).unwrap();
+        state.write(&key_b, 2u64).unwrap();
+        state.commit_block().unwrap();
+
+        state.in_mem_mut().begin_block(BlockHeight(2)).unwrap();
+        state.write(&key_a, 3u64).unwrap();
+        state.write(&key_a_sub, 4u64).unwrap();
+        state.delete(&key_b).unwrap();
+        state.commit_block().unwrap();
+
+        state.in_mem_mut().begin_block(BlockHeight(3)).unwrap();
+        state.commit_block().unwrap();
+
+        // The values of non-merklized keys are kept at past heights
+        let (value, _gas) =
+            state.db_read_with_height(&key_b, BlockHeight(1)).unwrap();
+        assert_eq!(value, Some(encode(&2u64)));
+        let (value, _gas) =
+            state.db_read_with_height(&key_b, BlockHeight(2)).unwrap();
+        assert_eq!(value, None);
+        let (value, _gas) =
+            state.db_read_with_height(&key_a, BlockHeight(1)).unwrap();
+        assert_eq!(value, Some(encode(&1u64)));
+        let (value, _gas) = state
+            .db_read_with_height(&key_a_sub, BlockHeight(1))
+            .unwrap();
+        assert_eq!(value, None);
+
+        // Prefixes are reconstructed at past heights, including the deleted
+        // keys
+        let values = state
+            .db_prefix_with_height(&prefix, BlockHeight(1))
+            .unwrap();
+        assert_eq!(
+            values,
+            vec![
+                (key_a.clone(), encode(&1u64)),
+                (key_b.clone(), encode(&2u64))
+            ]
+        );
+        let values = state
+            .db_prefix_with_height(&prefix, BlockHeight(2))
+            .unwrap();
+        assert_eq!(
+            values,
+            vec![
+                (key_a.clone(), encode(&3u64)),
+                (key_a_sub.clone(), encode(&4u64))
+            ]
+        );
+
+        // Without the archive mode, past prefixes are not available
+        state.set_archive_mode(false);
+        assert!(state
+            .db_prefix_with_height(&prefix, BlockHeight(1))
+            .is_err());
+    }
+
+    #[test]
+    fn test_historical_state() {
+        let db_path =
+            TempDir::new().expect("Unable to create a temporary DB directory");
+        let mut state = PersistentState::open(
+            db_path.path(),
+            None,
+            ChainId::default(),
+            address::testing::nam(),
+            None,
+            // Don't merklize any key
+            |_key: &Key| -> bool { false },
+        );
+        state.set_archive_mode(true);
+
+        let prefix = Key::parse("archive").unwrap();
+        let key_a = prefix.push(&"a".to_owned()).unwrap();
+        let key_b = prefix.push(&"b".to_owned()).unwrap();
+        let key_c = prefix.push(&"c".to_owned()).unwrap();
+
+        state.in_mem_mut().begin_block(BlockHeight(1)).unwrap();
+        state.write(&key_a, 1u64).unwrap();
+        state.write(&key_b, 2u64).unwrap();
+        state.commit_block().unwrap();
+
+        state.in_mem_mut().begin_block(BlockHeight(2)).unwrap();
+        state.write(&key_a, 3u64).unwrap();
+        state.delete(&key_b).unwrap();
+        state.write(&key_c, 4u64).unwrap();
+        state.commit_block().unwrap();
+
+        let historical = state.at_height(BlockHeight(1));
+        assert_eq!(historical.get_block_height().unwrap(), BlockHeight(1));
+        assert_eq!(historical.read::<u64>(&key_a).unwrap(), Some(1));
+        assert_eq!(historical.read::<u64>(&key_b).unwrap(), Some(2));
+        assert!(!historical.has_key(&key_c).unwrap());
+        let values: Vec<(Key, u64)> = state::iter_prefix(&historical, &prefix)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(values, vec![(key_a.clone(), 1), (key_b.clone(), 2)]);
+
+        // The changes restore the state at the height
+        assert_eq!(
+            historical.changes_since().unwrap(),
+            vec![
+                (key_a.clone(), Some(encode(&1u64))),
+                (key_b.clone(), Some(encode(&2u64))),
+                (key_c.clone(), None),
+            ]
+        );
+
+        // The last height has no changes
+        let last = state.at_height(BlockHeight(0));
+        assert_eq!(last.height(), BlockHeight(2));
+        assert!(last.changes_since().unwrap().is_empty());
+        assert_eq!(last.read::<u64>(&key_c).unwrap(), Some(4));
+    }
 }
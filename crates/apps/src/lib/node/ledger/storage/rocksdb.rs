@@ -43,7 +43,19 @@
 //! - `replay_protection`: hashes of processed tx for replay protection purposes
 //!     - `current/{hash}`: a hash included in the current block
 //!     - `{hash}`: a hash included in previous blocks
-
+//! - `tx_history`: optional index of the txs that changed the balances of
+//!   addresses
+//!   - `{address}/{height}/{index}`: the hash of the tx at the given index of
+//!     the block at the given height
+//!   - `by_height/{height}/{address}/{index}`: an empty entry of the same
+//!     tx, to find the entries of a block
+//! - `tx_results`: optional store of the results of the applied txs
+//!   - `by_height/{height}/{hash}`: the result of the tx with the given hash
+//!     applied at the given height
+//!   - `by_hash/{hash}`: the height at which the tx with the given hash was
+//!     applied
+
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{BufWriter, Write};
 use std::path::Path;
@@ -53,6 +65,7 @@ use std::sync::Mutex;
 use borsh::{BorshDeserialize, BorshSerialize};
 use data_encoding::HEXLOWER;
 use itertools::Either;
+use namada::core::address::Address;
 use namada::core::collections::HashSet;
 use namada::core::storage::{BlockHeight, Epoch, Header, Key, KeySeg};
 use namada::core::{decode, encode, ethereum_events};
@@ -63,13 +76,13 @@ use namada::state::merkle_tree::{
     tree_key_prefix_with_epoch, tree_key_prefix_with_height,
 };
 use namada::state::{
-    BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, DbError as Error,
-    DbResult as Result, MerkleTreeStoresRead, PatternIterator, PrefixIterator,
-    StoreType, DB,
+    tx_history, tx_results, BlockStateRead, BlockStateWrite, DBIter,
+    DBWriteBatch, DbError as Error, DbResult as Result, MerkleTreeStoresRead,
+    PatternIterator, PrefixIterator, StoreType, DB,
 };
 use namada::storage::{
-    DbColFam, BLOCK_CF, DIFFS_CF, REPLAY_PROTECTION_CF, ROLLBACK_CF, STATE_CF,
-    SUBSPACE_CF,
+    DbColFam, BLOCK_CF, DIFFS_CF, KEY_DIFFS_CF, REPLAY_PROTECTION_CF,
+    ROLLBACK_CF, STATE_CF, SUBSPACE_CF, TX_HISTORY_CF, TX_RESULTS_CF,
 };
 use namada_sdk::migrations::DBUpdateVisitor;
 use rayon::prelude::*;
@@ -109,10 +122,18 @@ const ADDRESS_GEN_KEY_SEGMENT: &str = "address_gen";
 
 const OLD_DIFF_PREFIX: &str = "old";
 const NEW_DIFF_PREFIX: &str = "new";
+/// The separator of the subspace key and the height in the index of the
+/// persisted diffs by key
+const KEY_DIFF_SEPARATOR: u8 = 0;
 
 /// RocksDB handle
 #[derive(Debug)]
-pub struct RocksDB(rocksdb::DB);
+pub struct RocksDB {
+    inner: rocksdb::DB,
+    /// When set, the persisted diffs are also indexed by key in
+    /// `KEY_DIFFS_CF`
+    archive_mode: bool,
+}
 
 /// DB Handle for batch writes.
 #[derive(Default)]
@@ -217,8 +238,41 @@ pub fn open(
         replay_protection_cf_opts,
     ));
 
+    // for the tx history index (insert-intensive)
+    let mut tx_history_cf_opts = Options::default();
+    tx_history_cf_opts.set_compression_type(DBCompressionType::Zstd);
+    tx_history_cf_opts.set_compression_options(0, 0, 0, 1024 * 1024);
+    tx_history_cf_opts.set_compaction_style(DBCompactionStyle::Universal);
+    tx_history_cf_opts.set_block_based_table_factory(&table_opts);
+    cfs.push(ColumnFamilyDescriptor::new(
+        TX_HISTORY_CF,
+        tx_history_cf_opts,
+    ));
+
+    // for the tx results store (insert-intensive)
+    let mut tx_results_cf_opts = Options::default();
+    tx_results_cf_opts.set_compression_type(DBCompressionType::Zstd);
+    tx_results_cf_opts.set_compression_options(0, 0, 0, 1024 * 1024);
+    tx_results_cf_opts.set_compaction_style(DBCompactionStyle::Universal);
+    tx_results_cf_opts.set_block_based_table_factory(&table_opts);
+    cfs.push(ColumnFamilyDescriptor::new(
+        TX_RESULTS_CF,
+        tx_results_cf_opts,
+    ));
+
+    // for the index of the persisted diffs by key (insert-intensive)
+    let mut key_diffs_cf_opts = Options::default();
+    key_diffs_cf_opts.set_compression_type(DBCompressionType::Zstd);
+    key_diffs_cf_opts.set_compression_options(0, 0, 0, 1024 * 1024);
+    key_diffs_cf_opts.set_compaction_style(DBCompactionStyle::Universal);
+    key_diffs_cf_opts.set_block_based_table_factory(&table_opts);
+    cfs.push(ColumnFamilyDescriptor::new(KEY_DIFFS_CF, key_diffs_cf_opts));
+
     rocksdb::DB::open_cf_descriptors(&db_opts, path, cfs)
-        .map(RocksDB)
+        .map(|inner| RocksDB {
+            inner,
+            archive_mode: false,
+        })
         .map_err(|e| Error::DBError(e.into_string()))
 }
 
@@ -230,7 +284,7 @@ impl Drop for RocksDB {
 
 impl RocksDB {
     fn get_column_family(&self, cf_name: &str) -> Result<&ColumnFamily> {
-        self.0
+        self.inner
             .cf_handle(cf_name)
             .ok_or(Error::DBError("No {cf_name} column family".to_string()))
     }
@@ -253,7 +307,7 @@ impl RocksDB {
         cf: &ColumnFamily,
         key: impl AsRef<str>,
     ) -> Result<Option<Vec<u8>>> {
-        self.0
+        self.inner
             .get_cf(cf, key.as_ref())
             .map_err(|e| Error::DBError(e.into_string()))
     }
@@ -330,9 +384,52 @@ impl RocksDB {
         if let Some(new_value) = new_value {
             batch.0.put_cf(cf, new_val_key, new_value);
         }
+
+        if persist_diffs && self.archive_mode {
+            // Index the diff by key to be able to seek the value of the key
+            // at any height
+            let key_diffs_cf = self.get_column_family(KEY_DIFFS_CF)?;
+            batch.0.put_cf(
+                key_diffs_cf,
+                key_diff_index_key(&key.to_string(), height),
+                [],
+            );
+        }
         Ok(())
     }
 
+    /// Read the value of the given subspace key from the persisted diffs
+    /// indexed by the entry at the current position of the iterator over the
+    /// `KEY_DIFFS_CF`, if it's an entry of the key. Returns `None` if there's
+    /// no such entry or if the key was deleted at its height.
+    fn read_key_diff_at(
+        &self,
+        iter: &rocksdb::DBRawIterator<'_>,
+        key: &str,
+    ) -> Result<Option<Vec<u8>>> {
+        let Some(index_key) = iter.key() else {
+            return Ok(None);
+        };
+        let Some(height) = index_key
+            .strip_prefix(key.as_bytes())
+            .and_then(|suffix| suffix.strip_prefix(&[KEY_DIFF_SEPARATOR]))
+        else {
+            return Ok(None);
+        };
+        let height = std::str::from_utf8(height)
+            .ok()
+            .and_then(|height| height.parse::<u64>().ok())
+            .ok_or_else(|| {
+                Error::DBError(format!(
+                    "Invalid height in the key diffs index of the key {key}"
+                ))
+            })?;
+        // A key that was deleted at this height has no new value
+        let diffs_cf = self.get_column_family(DIFFS_CF)?;
+        let new_val_key = format!("{height}/{NEW_DIFF_PREFIX}/{key}");
+        self.read_value_bytes(diffs_cf, new_val_key)
+    }
+
     /// Dump last known block
     pub fn dump_block(
         &self,
@@ -442,6 +539,49 @@ impl RocksDB {
         println!("Done writing to {}", full_path.to_string_lossy());
     }
 
+    /// Read the subspace keys written at the given height, defaulting to the
+    /// last committed one, with their new values or `None` for the deleted
+    /// keys. The keys written without persisting their diffs can only be read
+    /// at the last committed height. Returns the height with the writes.
+    pub fn read_writes(
+        &self,
+        height: Option<BlockHeight>,
+    ) -> (BlockHeight, BTreeMap<String, Option<Vec<u8>>>) {
+        let state_cf = self
+            .get_column_family(STATE_CF)
+            .expect("State column family should exist");
+        let last_height: BlockHeight = self
+            .read_value(state_cf, BLOCK_HEIGHT_KEY)
+            .expect("Unable to read DB")
+            .expect("No block height found");
+        let height = height.unwrap_or(last_height);
+
+        let mut cfs = vec![self
+            .get_column_family(DIFFS_CF)
+            .expect("Diffs column family should exist")];
+        if height == last_height {
+            cfs.push(
+                self.get_column_family(ROLLBACK_CF)
+                    .expect("Rollback column family should exist"),
+            );
+        }
+        let mut writes = BTreeMap::new();
+        for cf in cfs {
+            // A deleted key only has an old value
+            for (key, _old_value, _gas) in
+                iter_diffs_prefix(self, cf, height, None, true)
+            {
+                writes.insert(key, None);
+            }
+            for (key, new_value, _gas) in
+                iter_diffs_prefix(self, cf, height, None, false)
+            {
+                writes.insert(key, Some(new_value));
+            }
+        }
+        (height, writes)
+    }
+
     /// Dump data
     fn dump_it(
         &self,
@@ -451,13 +591,14 @@ impl RocksDB {
     ) {
         let read_opts = make_iter_read_opts(prefix.clone());
         let iter = if let Some(prefix) = prefix {
-            self.0.iterator_cf_opt(
+            self.inner.iterator_cf_opt(
                 cf,
                 read_opts,
                 IteratorMode::From(prefix.as_bytes(), Direction::Forward),
             )
         } else {
-            self.0.iterator_cf_opt(cf, read_opts, IteratorMode::Start)
+            self.inner
+                .iterator_cf_opt(cf, read_opts, IteratorMode::Start)
         };
 
         let mut buf = BufWriter::new(file);
@@ -474,6 +615,135 @@ impl RocksDB {
         buf.flush().expect("Unable to write to output file");
     }
 
+    /// Check that the last committed block has been written completely.
+    /// Returns the height of the last block if it hasn't, e.g. because the
+    /// node crashed while committing it.
+    pub fn incomplete_last_block(&self) -> Result<Option<BlockHeight>> {
+        let state_cf = self.get_column_family(STATE_CF)?;
+        let height: BlockHeight =
+            match self.read_value(state_cf, BLOCK_HEIGHT_KEY)? {
+                Some(height) => height,
+                // No block has been committed yet
+                None => return Ok(None),
+            };
+        if self.read_last_block()?.is_some() {
+            return Ok(None);
+        }
+        Ok(Some(height))
+    }
+
+    /// If the last committed block hasn't been written completely, revert the
+    /// block metadata to the previous height and the subspace writes of the
+    /// block using its diffs, so that the last block gets replayed by CometBFT
+    /// on start-up. Every rewritten key is logged. Returns the height of the
+    /// reverted block, if any.
+    pub fn repair_last_block(&mut self) -> Result<Option<BlockHeight>> {
+        let Some(height) = self.incomplete_last_block()? else {
+            return Ok(None);
+        };
+        let state_cf = self.get_column_family(STATE_CF)?;
+        tracing::warn!(
+            "The last block at height {height} is incomplete, reverting to \
+             the previous height"
+        );
+        let previous_height = height.prev_height().ok_or_else(|| {
+            Error::DBError(
+                "The first block is incomplete, the DB must be reset"
+                    .to_string(),
+            )
+        })?;
+
+        let mut batch = RocksDB::batch();
+        self.batch_revert_subspace_diffs(&mut batch, height)?;
+        tracing::info!(
+            "Restoring the block height metadata to {previous_height}"
+        );
+        batch
+            .0
+            .put_cf(state_cf, BLOCK_HEIGHT_KEY, encode(&previous_height));
+        for metadata_key in [
+            NEXT_EPOCH_MIN_START_HEIGHT_KEY,
+            NEXT_EPOCH_MIN_START_TIME_KEY,
+            COMMIT_ONLY_DATA_KEY,
+            UPDATE_EPOCH_BLOCKS_DELAY_KEY,
+        ] {
+            let previous_key = format!("{PRED_KEY_PREFIX}/{metadata_key}");
+            let previous_value = self
+                .read_value_bytes(state_cf, &previous_key)?
+                .ok_or(Error::UnknownKey { key: previous_key })?;
+            tracing::info!(
+                "Restoring the {metadata_key} metadata of the previous height"
+            );
+            self.add_value_bytes_to_batch(
+                state_cf,
+                metadata_key,
+                previous_value,
+                &mut batch,
+            );
+        }
+        self.exec_batch(batch)?;
+
+        if self.read_last_block()?.is_none() {
+            return Err(Error::DBError(format!(
+                "The block at height {previous_height} is also incomplete, \
+                 the DB must be restored from a snapshot or reset"
+            )));
+        }
+        Ok(Some(height))
+    }
+
+    /// Revert the subspace writes of the block at the given height using its
+    /// persisted and non-persisted diffs, and delete these diffs.
+    fn batch_revert_subspace_diffs(
+        &self,
+        batch: &mut RocksDBWriteBatch,
+        height: BlockHeight,
+    ) -> Result<()> {
+        let subspace_cf = self.get_column_family(SUBSPACE_CF)?;
+        let key_diffs_cf = self.get_column_family(KEY_DIFFS_CF)?;
+        for diffs_cf_name in [DIFFS_CF, ROLLBACK_CF] {
+            let diffs_cf = self.get_column_family(diffs_cf_name)?;
+            // Restore the old values of the keys written or deleted in the
+            // block
+            let mut keys_with_old_value = HashSet::<String>::new();
+            for (key_str, val, _) in
+                iter_diffs_prefix(self, diffs_cf, height, None, true)
+            {
+                tracing::info!("Restoring the previous value of key {key_str}");
+                batch.0.put_cf(subspace_cf, &key_str, val);
+                keys_with_old_value.insert(key_str);
+            }
+            // Delete the keys that were created in the block
+            let mut keys_with_new_value = HashSet::<String>::new();
+            for (key_str, _val, _) in
+                iter_diffs_prefix(self, diffs_cf, height, None, false)
+            {
+                if !keys_with_old_value.contains(&key_str) {
+                    tracing::info!(
+                        "Deleting key {key_str} created in the block"
+                    );
+                    batch.0.delete_cf(subspace_cf, &key_str);
+                }
+                keys_with_new_value.insert(key_str);
+            }
+            // Delete the diffs of the block
+            for key_str in keys_with_old_value.union(&keys_with_new_value) {
+                let key = Key::parse(key_str).map_err(Error::KeyError)?;
+                let (old_val_key, new_val_key) =
+                    old_and_new_diff_key(&key, height)?;
+                batch.0.delete_cf(diffs_cf, old_val_key);
+                batch.0.delete_cf(diffs_cf, new_val_key);
+                if diffs_cf_name == DIFFS_CF {
+                    batch.0.delete_cf(
+                        key_diffs_cf,
+                        key_diff_index_key(key_str, height),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Rollback to previous block. Given the inner working of tendermint
     /// rollback and of the key structure of Namada, calling rollback more than
     /// once without restarting the chain results in a single rollback.
@@ -569,6 +839,40 @@ impl RocksDB {
             batch.0.delete_cf(reprot_cf, current_key);
         }
 
+        // Remove the tx history entries of the last block
+        let tx_history_cf = self.get_column_family(TX_HISTORY_CF)?;
+        tracing::info!("Removing last block tx history entries");
+        let prefix = tx_history::height_prefix(last_block.height);
+        for (ref height_key, _, _) in
+            iter_prefix(self, tx_history_cf, None, Some(&prefix))
+        {
+            if let Some(key) = Key::parse(height_key)
+                .ok()
+                .as_ref()
+                .and_then(tx_history::parse_height_key)
+            {
+                batch.0.delete_cf(tx_history_cf, key.to_string());
+            }
+            batch.0.delete_cf(tx_history_cf, height_key);
+        }
+
+        // Remove the tx results of the last block
+        let tx_results_cf = self.get_column_family(TX_RESULTS_CF)?;
+        tracing::info!("Removing last block tx results");
+        for (ref key, _, _) in self.iter_tx_results(Some(last_block.height)) {
+            if let Some((_, hash)) = Key::parse(key)
+                .ok()
+                .as_ref()
+                .and_then(tx_results::parse_result_key)
+            {
+                batch.0.delete_cf(
+                    tx_results_cf,
+                    tx_results::height_key(&hash).to_string(),
+                );
+            }
+            batch.0.delete_cf(tx_results_cf, key);
+        }
+
         // Execute next step in parallel
         let batch = Mutex::new(batch);
 
@@ -620,6 +924,23 @@ impl RocksDB {
             }
         }
 
+        // Remove the diffs of this block from the index of the diffs by key
+        let key_diffs_cf = self.get_column_family(KEY_DIFFS_CF)?;
+        for is_old in [true, false] {
+            for (key_str, _val, _) in iter_diffs_prefix(
+                self,
+                diffs_cf,
+                last_block.height,
+                None,
+                is_old,
+            ) {
+                batch.0.delete_cf(
+                    key_diffs_cf,
+                    key_diff_index_key(&key_str, last_block.height),
+                );
+            }
+        }
+
         // Look for non-persisted diffs for rollback
         let rollback_cf = self.get_column_family(ROLLBACK_CF)?;
         // Iterate the old keys first and keep a set of keys that have old val
@@ -647,7 +968,7 @@ impl RocksDB {
         let prefix = last_block.height.to_string();
         let mut delete_keys = |cf: &ColumnFamily| {
             let read_opts = make_iter_read_opts(Some(prefix.clone()));
-            let iter = self.0.iterator_cf_opt(
+            let iter = self.inner.iterator_cf_opt(
                 cf,
                 read_opts,
                 IteratorMode::From(prefix.as_bytes(), Direction::Forward),
@@ -686,7 +1007,7 @@ impl RocksDB {
             old_and_new_diff_key(key, height)?.1
         };
 
-        self.0
+        self.inner
             .get_cf(rollback_cf, key)
             .map_err(|e| Error::DBError(e.into_string()))
     }
@@ -703,10 +1024,14 @@ impl DB for RocksDB {
         open(db_path, cache).expect("cannot open the DB")
     }
 
+    fn set_archive_mode(&mut self, archive_mode: bool) {
+        self.archive_mode = archive_mode;
+    }
+
     fn flush(&self, wait: bool) -> Result<()> {
         let mut flush_opts = FlushOptions::default();
         flush_opts.set_wait(wait);
-        self.0
+        self.inner
             .flush_opt(&flush_opts)
             .map_err(|e| Error::DBError(e.into_string()))
     }
@@ -1026,6 +1351,59 @@ impl DB for RocksDB {
         Ok(false)
     }
 
+    fn read_tx_history_rev(
+        &self,
+        owner: &Address,
+        start: Option<&Key>,
+        limit: usize,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        let tx_history_cf = self.get_column_family(TX_HISTORY_CF)?;
+        let prefix = format!("{}/", tx_history::prefix(owner));
+        let start = match start {
+            Some(start) => start.to_string(),
+            // The first key after the entries of the address
+            None => format!("{}0", tx_history::prefix(owner)),
+        };
+        let mut entries = vec![];
+        let mut iter = self.inner.raw_iterator_cf(tx_history_cf);
+        iter.seek_for_prev(start);
+        while entries.len() < limit {
+            let (Some(key), Some(value)) = (iter.key(), iter.value()) else {
+                break;
+            };
+            let key = String::from_utf8(key.to_vec())
+                .map_err(|e| Error::DBError(e.to_string()))?;
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            entries.push((key, value.to_vec()));
+            iter.prev();
+        }
+        iter.status().map_err(|e| Error::DBError(e.into_string()))?;
+        Ok(entries)
+    }
+
+    fn read_tx_result(
+        &self,
+        tx_hash: &namada::core::hash::Hash,
+    ) -> Result<Option<Vec<u8>>> {
+        let tx_results_cf = self.get_column_family(TX_RESULTS_CF)?;
+        let height: BlockHeight = match self
+            .0
+            .get_cf(tx_results_cf, tx_results::height_key(tx_hash).to_string())
+            .map_err(|e| Error::DBError(e.into_string()))?
+        {
+            Some(bytes) => decode(bytes).map_err(Error::CodingError)?,
+            None => return Ok(None),
+        };
+        self.inner
+            .get_cf(
+                tx_results_cf,
+                tx_results::result_key(height, tx_hash).to_string(),
+            )
+            .map_err(|e| Error::DBError(e.into_string()))
+    }
+
     fn read_diffs_val(
         &self,
         key: &Key,
@@ -1063,7 +1441,7 @@ impl DB for RocksDB {
             }
             None => {
                 // If it has an "old" val, it was deleted at this height
-                if self.0.key_may_exist_cf(diffs_cf, &old_val_key) {
+                if self.inner.key_may_exist_cf(diffs_cf, &old_val_key) {
                     // check if it actually exists
                     if self.read_value_bytes(diffs_cf, old_val_key)?.is_some() {
                         return Ok(None);
@@ -1086,7 +1464,7 @@ impl DB for RocksDB {
                 None => {
                     // Check if the value was created at this height instead,
                     // which would mean that it wasn't present before
-                    if self.0.key_may_exist_cf(diffs_cf, &new_val_key) {
+                    if self.inner.key_may_exist_cf(diffs_cf, &new_val_key) {
                         // check if it actually exists
                         if self
                             .read_value_bytes(diffs_cf, new_val_key)?
@@ -1107,6 +1485,60 @@ impl DB for RocksDB {
         }
     }
 
+    fn read_archived_subspace_val(
+        &self,
+        key: &Key,
+        height: BlockHeight,
+    ) -> Result<Option<Vec<u8>>> {
+        let key_diffs_cf = self.get_column_family(KEY_DIFFS_CF)?;
+        let key = key.to_string();
+        let mut iter = self.inner.raw_iterator_cf(key_diffs_cf);
+        // Seek the last diff of the key at or before the height
+        iter.seek_for_prev(key_diff_index_key(&key, height));
+        iter.status().map_err(|e| Error::DBError(e.into_string()))?;
+        self.read_key_diff_at(&iter, &key)
+    }
+
+    fn read_archived_subspace_prefix(
+        &self,
+        prefix: &Key,
+        height: BlockHeight,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        let key_diffs_cf = self.get_column_family(KEY_DIFFS_CF)?;
+        let prefix = if prefix.is_empty() {
+            String::new()
+        } else {
+            format!("{prefix}/")
+        };
+        let mut values = vec![];
+        let mut iter = self.inner.raw_iterator_cf(key_diffs_cf);
+        iter.seek(&prefix);
+        while let Some(index_key) = iter.key() {
+            let index_key = String::from_utf8(index_key.to_vec())
+                .map_err(|e| Error::DBError(e.to_string()))?;
+            if !index_key.starts_with(&prefix) {
+                break;
+            }
+            let Some((key, _height)) =
+                index_key.rsplit_once(char::from(KEY_DIFF_SEPARATOR))
+            else {
+                return Err(Error::DBError(format!(
+                    "Invalid key diffs index key {index_key}"
+                )));
+            };
+            // Seek the last diff of this key at or before the height
+            iter.seek_for_prev(key_diff_index_key(key, height));
+            if let Some(value) = self.read_key_diff_at(&iter, key)? {
+                values.push((key.to_owned(), value));
+            }
+            // Skip the remaining diffs of this key, which come before the
+            // diffs of its sub-keys
+            iter.seek(format!("{key}{}", char::from(KEY_DIFF_SEPARATOR + 1)));
+        }
+        iter.status().map_err(|e| Error::DBError(e.into_string()))?;
+        Ok(values)
+    }
+
     fn write_subspace_val(
         &mut self,
         height: BlockHeight,
@@ -1148,7 +1580,7 @@ impl DB for RocksDB {
     }
 
     fn exec_batch(&self, batch: Self::WriteBatch) -> Result<()> {
-        self.0
+        self.inner
             .write(batch.0)
             .map_err(|e| Error::DBError(e.into_string()))
     }
@@ -1316,6 +1748,85 @@ impl DB for RocksDB {
         Ok(())
     }
 
+    fn write_tx_history_entry(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        key: &Key,
+        tx_hash: &namada::core::hash::Hash,
+    ) -> Result<()> {
+        let tx_history_cf = self.get_column_family(TX_HISTORY_CF)?;
+
+        if let Some((owner, height, index)) = tx_history::parse_key(key) {
+            self.add_value_bytes_to_batch(
+                tx_history_cf,
+                tx_history::height_key(&owner, height, index).to_string(),
+                vec![],
+                batch,
+            );
+        }
+        self.add_value_bytes_to_batch(
+            tx_history_cf,
+            key.to_string(),
+            encode(tx_hash),
+            batch,
+        );
+
+        Ok(())
+    }
+
+    fn write_tx_result(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        tx_hash: &namada::core::hash::Hash,
+        result: &[u8],
+    ) -> Result<()> {
+        let tx_results_cf = self.get_column_family(TX_RESULTS_CF)?;
+
+        self.add_value_bytes_to_batch(
+            tx_results_cf,
+            tx_results::height_key(tx_hash).to_string(),
+            encode(&height),
+            batch,
+        );
+        self.add_value_bytes_to_batch(
+            tx_results_cf,
+            tx_results::result_key(height, tx_hash).to_string(),
+            result.to_vec(),
+            batch,
+        );
+
+        Ok(())
+    }
+
+    fn prune_tx_results(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        up_to: BlockHeight,
+    ) -> Result<()> {
+        let tx_results_cf = self.get_column_family(TX_RESULTS_CF)?;
+
+        for (ref key, _, _) in self.iter_tx_results(None) {
+            let Some((height, hash)) = Key::parse(key)
+                .ok()
+                .as_ref()
+                .and_then(tx_results::parse_result_key)
+            else {
+                continue;
+            };
+            if height > up_to {
+                break;
+            }
+            batch.0.delete_cf(
+                tx_results_cf,
+                tx_results::height_key(&hash).to_string(),
+            );
+            batch.0.delete_cf(tx_results_cf, key);
+        }
+
+        Ok(())
+    }
+
     fn prune_non_persisted_diffs(
         &mut self,
         batch: &mut Self::WriteBatch,
@@ -1516,7 +2027,7 @@ impl<'iter> DBIter<'iter> for RocksDB {
             .get_column_family(BLOCK_CF)
             .expect("{BLOCK_CF} column family should exist");
         let read_opts = make_iter_read_opts(Some(prefix.clone()));
-        let iter = self.0.iterator_cf_opt(
+        let iter = self.inner.iterator_cf_opt(
             block_cf,
             read_opts,
             IteratorMode::From(prefix.as_bytes(), Direction::Forward),
@@ -1554,6 +2065,27 @@ impl<'iter> DBIter<'iter> for RocksDB {
         let prefix = Some(replay_protection::current_prefix());
         iter_prefix(self, replay_protection_cf, None, prefix.as_ref())
     }
+
+    fn iter_tx_history(&'iter self, owner: &Address) -> Self::PrefixIter {
+        let tx_history_cf = self
+            .get_column_family(TX_HISTORY_CF)
+            .expect("{TX_HISTORY_CF} column family should exist");
+
+        let prefix = tx_history::prefix(owner);
+        iter_prefix(self, tx_history_cf, None, Some(&prefix))
+    }
+
+    fn iter_tx_results(
+        &'iter self,
+        height: Option<BlockHeight>,
+    ) -> Self::PrefixIter {
+        let tx_results_cf = self
+            .get_column_family(TX_RESULTS_CF)
+            .expect("{TX_RESULTS_CF} column family should exist");
+
+        let prefix = tx_results::height_prefix(height);
+        iter_prefix(self, tx_results_cf, None, Some(&prefix))
+    }
 }
 
 fn iter_subspace_prefix<'iter>(
@@ -1621,7 +2153,7 @@ fn iter_prefix<'a>(
         _ => stripped_prefix.clone(),
     };
     let read_opts = make_iter_read_opts(Some(prefix.clone()));
-    let iter = db.0.iterator_cf_opt(
+    let iter = db.inner.iterator_cf_opt(
         cf,
         read_opts,
         IteratorMode::From(prefix.as_bytes(), Direction::Forward),
@@ -1718,6 +2250,14 @@ fn make_iter_read_opts(prefix: Option<String>) -> ReadOptions {
 
 impl DBWriteBatch for RocksDBWriteBatch {}
 
+/// Make the key of the diff of the given subspace key at the given height in
+/// the index of the persisted diffs by key. The separator sorts before any
+/// other character, so that the diffs of a key are contiguous and come before
+/// the diffs of its sub-keys, and the height is zero-padded to be ordered.
+fn key_diff_index_key(key: &str, height: BlockHeight) -> String {
+    format!("{key}{}{:020}", char::from(KEY_DIFF_SEPARATOR), height.0)
+}
+
 fn old_and_new_diff_key(
     key: &Key,
     height: BlockHeight,
@@ -1793,9 +2333,10 @@ mod imp {
 
 #[cfg(test)]
 mod test {
+    use namada::address::testing::established_address_1;
     use namada::address::EstablishedAddressGen;
     use namada::core::hash::Hash;
-    use namada::core::storage::Epochs;
+    use namada::core::storage::{Epochs, TxIndex};
     use namada::ledger::storage::ConversionState;
     use namada::state::{MerkleTree, Sha256Hasher};
     use namada::storage::{BlockResults, EthEventsQueue};
@@ -2006,6 +2547,7 @@ mod test {
             let delete_key = Key::parse("delete").unwrap();
             // A key that's gonna be overwritten on a second block
             let overwrite_key = Key::parse("overwrite").unwrap();
+            let owner = established_address_1();
 
             // Write first block
             let mut batch = RocksDB::batch();
@@ -2046,6 +2588,19 @@ mod test {
                 )
                 .unwrap();
             }
+            db.write_tx_history_entry(
+                &mut batch,
+                &tx_history::key(&owner, height_0, TxIndex(0)),
+                &Hash::sha256(b"tx1"),
+            )
+            .unwrap();
+            db.write_tx_result(
+                &mut batch,
+                height_0,
+                &Hash::sha256(b"tx1"),
+                b"result1",
+            )
+            .unwrap();
 
             add_block_to_batch(
                 &db,
@@ -2099,6 +2654,19 @@ mod test {
                 )
                 .unwrap();
             }
+            db.write_tx_history_entry(
+                &mut batch,
+                &tx_history::key(&owner, height_1, TxIndex(1)),
+                &Hash::sha256(b"tx5"),
+            )
+            .unwrap();
+            db.write_tx_result(
+                &mut batch,
+                height_1,
+                &Hash::sha256(b"tx5"),
+                b"result5",
+            )
+            .unwrap();
 
             add_block_to_batch(
                 &db,
@@ -2124,6 +2692,11 @@ mod test {
                     db.has_replay_protection_entry(&Hash::sha256(tx)).unwrap()
                 );
             }
+            assert_eq!(db.iter_tx_history(&owner).count(), 2);
+            assert_eq!(
+                db.read_tx_result(&Hash::sha256(b"tx5")).unwrap(),
+                Some(b"result5".to_vec())
+            );
 
             // Rollback to the first block height
             db.rollback(height_0).unwrap();
@@ -2137,10 +2710,11 @@ mod test {
             assert_eq!(deleted, Some(to_delete_val));
             // Check the conversion state
             let state_cf = db.get_column_family(STATE_CF).unwrap();
-            let conversion_state =
-                db.0.get_cf(state_cf, "conversion_state".as_bytes())
-                    .unwrap()
-                    .unwrap();
+            let conversion_state = db
+                .inner
+                .get_cf(state_cf, "conversion_state".as_bytes())
+                .unwrap()
+                .unwrap();
             assert_eq!(conversion_state, encode(&conversion_state_0));
             for tx in [b"tx1", b"tx2", b"tx3", b"tx4"] {
                 assert!(
@@ -2153,9 +2727,165 @@ mod test {
                     !db.has_replay_protection_entry(&Hash::sha256(tx)).unwrap()
                 );
             }
+            // Check that only the tx history of the first block is left
+            let history: Vec<_> = db
+                .iter_tx_history(&owner)
+                .map(|(_, value, _)| value)
+                .collect();
+            assert_eq!(history, vec![encode(&Hash::sha256(b"tx1"))]);
+            // Check that only the tx results of the first block are left
+            assert_eq!(
+                db.read_tx_result(&Hash::sha256(b"tx1")).unwrap(),
+                Some(b"result1".to_vec())
+            );
+            assert_eq!(db.read_tx_result(&Hash::sha256(b"tx5")).unwrap(), None);
         }
     }
 
+    /// Test that an incomplete last block is reverted on repair.
+    #[test]
+    fn test_repair_last_block() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+        // Index the persisted diffs by key to check that they get reverted
+        db.set_archive_mode(true);
+
+        // Nothing to repair in an empty DB
+        assert_eq!(db.repair_last_block().unwrap(), None);
+
+        let height_0 = BlockHeight(100);
+        let height_1 = BlockHeight(101);
+        let mut pred_epochs = Epochs::default();
+        pred_epochs.new_epoch(height_0);
+        let key_a = Key::parse("a").unwrap();
+        let key_b = Key::parse("b").unwrap();
+        let key_c = Key::parse("c").unwrap();
+        // The writes of each block, with the new value, if any, and whether
+        // the diffs are persisted
+        let writes = [
+            (
+                height_0,
+                vec![(&key_a, Some(1_u8), true), (&key_b, Some(1), false)],
+            ),
+            (
+                height_1,
+                vec![
+                    (&key_a, Some(2), true),
+                    (&key_b, None, false),
+                    (&key_c, Some(2), true),
+                ],
+            ),
+        ];
+        for (height, block_writes) in writes {
+            let mut batch = RocksDB::batch();
+            for (key, value, persist_diffs) in block_writes {
+                match value {
+                    Some(value) => db.batch_write_subspace_val(
+                        &mut batch,
+                        height,
+                        key,
+                        [value],
+                        persist_diffs,
+                    ),
+                    None => db.batch_delete_subspace_val(
+                        &mut batch,
+                        height,
+                        key,
+                        persist_diffs,
+                    ),
+                }
+                .unwrap();
+            }
+            add_block_to_batch(
+                &db,
+                &mut batch,
+                height,
+                Epoch::default(),
+                pred_epochs.clone(),
+                &ConversionState::default(),
+            )
+            .unwrap();
+            db.exec_batch(batch).unwrap();
+        }
+
+        // Nothing to repair when the last block is complete
+        assert_eq!(db.incomplete_last_block().unwrap(), None);
+        assert_eq!(db.repair_last_block().unwrap(), None);
+        assert_eq!(db.read_last_block().unwrap().unwrap().height, height_1);
+
+        // Remove a part of the last block
+        let block_cf = db.get_column_family(BLOCK_CF).unwrap();
+        db.inner
+            .delete_cf(
+                block_cf,
+                format!("{RESULTS_KEY_PREFIX}/{}", height_1.raw()),
+            )
+            .unwrap();
+        assert!(db.read_last_block().unwrap().is_none());
+        assert_eq!(db.incomplete_last_block().unwrap(), Some(height_1));
+
+        // The last block is reverted, together with its subspace writes and
+        // diffs
+        assert_eq!(db.repair_last_block().unwrap(), Some(height_1));
+        assert_eq!(db.read_last_block().unwrap().unwrap().height, height_0);
+        assert_eq!(db.incomplete_last_block().unwrap(), None);
+        assert_eq!(db.read_subspace_val(&key_a).unwrap(), Some(vec![1]));
+        assert_eq!(db.read_subspace_val(&key_b).unwrap(), Some(vec![1]));
+        assert_eq!(db.read_subspace_val(&key_c).unwrap(), None);
+        for key in [&key_a, &key_b, &key_c] {
+            for is_old in [true, false] {
+                assert_eq!(
+                    db.read_diffs_val(key, height_1, is_old).unwrap(),
+                    None
+                );
+                assert_eq!(
+                    db.read_rollback_val(key, height_1, is_old).unwrap(),
+                    None
+                );
+            }
+        }
+        assert_eq!(
+            db.read_archived_subspace_val(&key_a, height_1).unwrap(),
+            Some(vec![1])
+        );
+    }
+
+    #[test]
+    fn test_prune_tx_results() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+
+        let mut batch = RocksDB::batch();
+        for height in 1..=3 {
+            db.write_tx_result(
+                &mut batch,
+                BlockHeight(height),
+                &Hash::sha256(height.to_string()),
+                &[height as u8],
+            )
+            .unwrap();
+        }
+        db.exec_batch(batch).unwrap();
+        assert_eq!(db.iter_tx_results(None).count(), 3);
+        assert_eq!(db.iter_tx_results(Some(BlockHeight(2))).count(), 1);
+
+        let mut batch = RocksDB::batch();
+        db.prune_tx_results(&mut batch, BlockHeight(2)).unwrap();
+        db.exec_batch(batch).unwrap();
+        for height in 1..=2 {
+            assert_eq!(
+                db.read_tx_result(&Hash::sha256(height.to_string()))
+                    .unwrap(),
+                None
+            );
+        }
+        assert_eq!(
+            db.read_tx_result(&Hash::sha256("3")).unwrap(),
+            Some(vec![3])
+        );
+        assert_eq!(db.iter_tx_results(None).count(), 1);
+    }
+
     #[test]
     fn test_diffs() {
         let dir = tempdir().unwrap();
@@ -2196,15 +2926,15 @@ mod test {
             // present
             let (old_with_h0, new_with_h0) =
                 old_and_new_diff_key(&key_with_diffs, height_0).unwrap();
-            assert!(db.0.get_cf(diffs_cf, old_with_h0).unwrap().is_none());
-            assert!(db.0.get_cf(diffs_cf, new_with_h0).unwrap().is_some());
+            assert!(db.inner.get_cf(diffs_cf, old_with_h0).unwrap().is_none());
+            assert!(db.inner.get_cf(diffs_cf, new_with_h0).unwrap().is_some());
 
             // Diffs new key for `key_without_diffs` at height_0 must be
             // present
             let (old_wo_h0, new_wo_h0) =
                 old_and_new_diff_key(&key_without_diffs, height_0).unwrap();
-            assert!(db.0.get_cf(rollback_cf, old_wo_h0).unwrap().is_none());
-            assert!(db.0.get_cf(rollback_cf, new_wo_h0).unwrap().is_some());
+            assert!(db.inner.get_cf(rollback_cf, old_wo_h0).unwrap().is_none());
+            assert!(db.inner.get_cf(rollback_cf, new_wo_h0).unwrap().is_some());
         }
 
         // Write second block
@@ -2236,27 +2966,27 @@ mod test {
             // Diffs keys for `key_with_diffs` at height_0 must be present
             let (old_with_h0, new_with_h0) =
                 old_and_new_diff_key(&key_with_diffs, height_0).unwrap();
-            assert!(db.0.get_cf(diffs_cf, old_with_h0).unwrap().is_none());
-            assert!(db.0.get_cf(diffs_cf, new_with_h0).unwrap().is_some());
+            assert!(db.inner.get_cf(diffs_cf, old_with_h0).unwrap().is_none());
+            assert!(db.inner.get_cf(diffs_cf, new_with_h0).unwrap().is_some());
 
             // Diffs keys for `key_without_diffs` at height_0 must be gone
             let (old_wo_h0, new_wo_h0) =
                 old_and_new_diff_key(&key_without_diffs, height_0).unwrap();
-            assert!(db.0.get_cf(rollback_cf, old_wo_h0).unwrap().is_none());
-            assert!(db.0.get_cf(rollback_cf, new_wo_h0).unwrap().is_none());
+            assert!(db.inner.get_cf(rollback_cf, old_wo_h0).unwrap().is_none());
+            assert!(db.inner.get_cf(rollback_cf, new_wo_h0).unwrap().is_none());
 
             // Diffs keys for `key_with_diffs` at height_1 must be present
             let (old_with_h1, new_with_h1) =
                 old_and_new_diff_key(&key_with_diffs, height_1).unwrap();
-            assert!(db.0.get_cf(diffs_cf, old_with_h1).unwrap().is_some());
-            assert!(db.0.get_cf(diffs_cf, new_with_h1).unwrap().is_some());
+            assert!(db.inner.get_cf(diffs_cf, old_with_h1).unwrap().is_some());
+            assert!(db.inner.get_cf(diffs_cf, new_with_h1).unwrap().is_some());
 
             // Diffs keys for `key_without_diffs` at height_1 must be
             // present
             let (old_wo_h1, new_wo_h1) =
                 old_and_new_diff_key(&key_without_diffs, height_1).unwrap();
-            assert!(db.0.get_cf(rollback_cf, old_wo_h1).unwrap().is_some());
-            assert!(db.0.get_cf(rollback_cf, new_wo_h1).unwrap().is_some());
+            assert!(db.inner.get_cf(rollback_cf, old_wo_h1).unwrap().is_some());
+            assert!(db.inner.get_cf(rollback_cf, new_wo_h1).unwrap().is_some());
         }
 
         // Write third block
@@ -2288,27 +3018,27 @@ mod test {
             // Diffs keys for `key_with_diffs` at height_1 must be present
             let (old_with_h1, new_with_h1) =
                 old_and_new_diff_key(&key_with_diffs, height_1).unwrap();
-            assert!(db.0.get_cf(diffs_cf, old_with_h1).unwrap().is_some());
-            assert!(db.0.get_cf(diffs_cf, new_with_h1).unwrap().is_some());
+            assert!(db.inner.get_cf(diffs_cf, old_with_h1).unwrap().is_some());
+            assert!(db.inner.get_cf(diffs_cf, new_with_h1).unwrap().is_some());
 
             // Diffs keys for `key_without_diffs` at height_1 must be gone
             let (old_wo_h1, new_wo_h1) =
                 old_and_new_diff_key(&key_without_diffs, height_1).unwrap();
-            assert!(db.0.get_cf(rollback_cf, old_wo_h1).unwrap().is_none());
-            assert!(db.0.get_cf(rollback_cf, new_wo_h1).unwrap().is_none());
+            assert!(db.inner.get_cf(rollback_cf, old_wo_h1).unwrap().is_none());
+            assert!(db.inner.get_cf(rollback_cf, new_wo_h1).unwrap().is_none());
 
             // Diffs keys for `key_with_diffs` at height_2 must be present
             let (old_with_h2, new_with_h2) =
                 old_and_new_diff_key(&key_with_diffs, height_2).unwrap();
-            assert!(db.0.get_cf(diffs_cf, old_with_h2).unwrap().is_some());
-            assert!(db.0.get_cf(diffs_cf, new_with_h2).unwrap().is_some());
+            assert!(db.inner.get_cf(diffs_cf, old_with_h2).unwrap().is_some());
+            assert!(db.inner.get_cf(diffs_cf, new_with_h2).unwrap().is_some());
 
             // Diffs keys for `key_without_diffs` at height_2 must be
             // present
             let (old_wo_h2, new_wo_h2) =
                 old_and_new_diff_key(&key_without_diffs, height_2).unwrap();
-            assert!(db.0.get_cf(rollback_cf, old_wo_h2).unwrap().is_some());
-            assert!(db.0.get_cf(rollback_cf, new_wo_h2).unwrap().is_some());
+            assert!(db.inner.get_cf(rollback_cf, old_wo_h2).unwrap().is_some());
+            assert!(db.inner.get_cf(rollback_cf, new_wo_h2).unwrap().is_some());
         }
     }
 
@@ -4,7 +4,7 @@ use std::process::Stdio;
 use std::str::FromStr;
 
 use borsh_ext::BorshSerializeExt;
-use namada::core::chain::ChainId;
+use namada::core::chain::{ChainId, ProposalBytes};
 use namada::core::key::*;
 use namada::core::storage::BlockHeight;
 use namada::core::time::DateTimeUtc;
@@ -22,7 +22,7 @@ use crate::config;
 use crate::facade::tendermint::node::Id as TendermintNodeId;
 use crate::facade::tendermint::{block, Genesis, Moniker};
 use crate::facade::tendermint_config::{
-    Error as TendermintError, TendermintConfig,
+    Error as TendermintError, TendermintConfig, TxIndexer,
 };
 
 /// Env. var to output Tendermint log to stdout
@@ -46,6 +46,8 @@ pub enum Error {
     Runtime(String),
     #[error("Failed to rollback CometBFT state: {0}")]
     RollBack(String),
+    #[error("Failed to reindex CometBFT events: {0}")]
+    Reindex(String),
     #[error("Failed to convert to String: {0:?}")]
     TendermintPath(std::ffi::OsString),
     #[error("Couldn't write {0}")]
@@ -276,41 +278,56 @@ pub fn rollback(tendermint_dir: impl AsRef<Path>) -> Result<BlockHeight> {
         .into())
 }
 
+/// Rebuild the indexes of the txs and block events of the blocks in the given
+/// range from the CometBFT block store, e.g. after enabling the tx indexer.
+/// The range defaults to all the stored blocks.
+pub async fn reindex_events(
+    home_dir: impl AsRef<Path>,
+    config: TendermintConfig,
+    from_height: Option<BlockHeight>,
+    to_height: Option<BlockHeight>,
+) -> Result<()> {
+    if matches!(config.tx_index.indexer, TxIndexer::Null) {
+        return Err(Error::Reindex(
+            "The tx indexer is disabled in the CometBFT config \
+             (`cometbft.tx_index.indexer`)"
+                .to_string(),
+        ));
+    }
+    let tendermint_path = from_env_or_default()?;
+    let home_dir_string = home_dir.as_ref().to_string_lossy().to_string();
+
+    // CometBFT reads the indexer from its own config, so first apply the
+    // ledger's config to it
+    update_tendermint_config(&home_dir, config).await?;
+
+    let mut args = vec!["reindex-event".to_string()];
+    if let Some(height) = from_height {
+        args.extend(["--start-height".to_string(), height.to_string()]);
+    }
+    if let Some(height) = to_height {
+        args.extend(["--end-height".to_string(), height.to_string()]);
+    }
+    args.extend(["--home".to_string(), home_dir_string]);
+    let output = Command::new(tendermint_path)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| Error::Reindex(e.to_string()))?;
+    if !output.status.success() {
+        return Err(Error::Reindex(
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        ));
+    }
+    Ok(())
+}
+
 /// Convert a common signing scheme validator key into JSON for
 /// Tendermint
 pub fn validator_key_to_json(
     sk: &common::SecretKey,
 ) -> std::result::Result<serde_json::Value, ParseSecretKeyError> {
-    let raw_hash = tm_consensus_key_raw_hash(&sk.ref_to());
-    let (id_str, pk_arr, kp_arr) = match sk {
-        common::SecretKey::Ed25519(_) => {
-            let sk_ed: ed25519::SecretKey = sk.try_to_sk().unwrap();
-            let keypair =
-                [sk_ed.serialize_to_vec(), sk_ed.ref_to().serialize_to_vec()]
-                    .concat();
-            ("Ed25519", sk_ed.ref_to().serialize_to_vec(), keypair)
-        }
-        common::SecretKey::Secp256k1(_) => {
-            let sk_sec: secp256k1::SecretKey = sk.try_to_sk().unwrap();
-            (
-                "Secp256k1",
-                sk_sec.ref_to().serialize_to_vec(),
-                sk_sec.serialize_to_vec(),
-            )
-        }
-    };
-
-    Ok(json!({
-        "address": raw_hash,
-        "pub_key": {
-            "type": format!("tendermint/PubKey{}",id_str),
-            "value": base64::encode(pk_arr),
-        },
-        "priv_key": {
-            "type": format!("tendermint/PrivKey{}",id_str),
-            "value": base64::encode(kp_arr),
-        }
-    }))
+    Ok(namada_sdk::wallet::keystore::tendermint_key_json(sk))
 }
 
 /// Initialize validator private key for Tendermint
@@ -364,6 +381,16 @@ pub fn write_validator(
     })
 }
 
+/// The number of bytes of a block reserved for evidence data, block headers
+/// and protobuf serialization overhead, on top of the txs.
+const RESERVED_BLOCK_BYTES: u64 = 10 * 1024 * 1024;
+
+/// The maximum size of a serialized CometBFT block whose txs take up to the
+/// given number of bytes.
+pub fn block_max_bytes(max_proposal_bytes: ProposalBytes) -> u64 {
+    max_proposal_bytes.get() + RESERVED_BLOCK_BYTES
+}
+
 /// Length of a Tendermint Node ID in bytes
 const TENDERMINT_NODE_ID_LENGTH: usize = 20;
 
@@ -479,8 +506,12 @@ async fn write_tm_genesis(
         // maximum size of a serialized Tendermint block.
         // on Namada, we have a hard-cap of 16 MiB (6 MiB max
         // txs in a block + 10 MiB reserved for evidence data,
-        // block headers and protobuf serialization overhead)
-        max_bytes: 16 * 1024 * 1024,
+        // block headers and protobuf serialization overhead).
+        // the ledger updates this value whenever the
+        // `max_proposal_bytes` parameter is changed
+        max_bytes: block_max_bytes(ProposalBytes::MAX)
+            .try_into()
+            .expect("The max block size must fit in a u32"),
         // gas is metered app-side, so we disable it
         // at the Tendermint level
         max_gas: -1,
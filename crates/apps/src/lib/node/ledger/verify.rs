@@ -0,0 +1,348 @@
+//! Verification of the ledger state by replaying the blocks.
+//!
+//! The blocks are fetched from a CometBFT RPC endpoint and applied by the
+//! protocol logic of this node to a separate DB, starting either from genesis
+//! or from a copy of the DB of a node. The app hash committed at every height
+//! is compared with the one agreed upon by the network, which is stored in
+//! the header of the next block.
+
+use std::path::PathBuf;
+
+use data_encoding::HEXUPPER;
+use namada::core::storage::BlockHeight;
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+use super::shims::abcipp_shim::AbcippShim;
+use super::shims::abcipp_shim_types::shim;
+use super::{run_aux_setup, RunAuxSetup};
+use crate::cli::args;
+use crate::config::{self, TendermintMode};
+use crate::facade::tendermint::abci::types::{
+    BlockSignatureInfo, CommitInfo, Misbehavior, MisbehaviorKind, Validator,
+    VoteInfo,
+};
+use crate::facade::tendermint::block::{BlockIdFlag, CommitSig, Height};
+use crate::facade::tendermint::evidence::Evidence;
+use crate::facade::tendermint::v0_37::abci::{
+    request, Request as Req, Response as Resp,
+};
+use crate::facade::tendermint::{account, validator, Block};
+use crate::facade::tendermint_rpc::{self, Client, HttpClient, Paging};
+
+/// The DB directory of the replayed state, relative to the chain directory
+pub const VERIFY_DB_DIR: &str = "db-verify";
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("CometBFT RPC request failed: {0}")]
+    Rpc(tendermint_rpc::Error),
+    #[error("The shell failed to process a request: {0}")]
+    Shell(shim::Error),
+    #[error("Unexpected response from the shell: {0:?}")]
+    UnexpectedResponse(Resp),
+    #[error("Invalid block at height {0}: {1}")]
+    InvalidBlock(BlockHeight, String),
+    #[error("Failed to reset the verification DB: {0}")]
+    ResetDb(std::io::Error),
+    #[error(
+        "The app hash diverged at height {height}: expected {expected}, got \
+         {actual}"
+    )]
+    Divergence {
+        height: BlockHeight,
+        expected: String,
+        actual: String,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Replay the blocks fetched from the given CometBFT RPC endpoint and check
+/// the resulting app hashes. Stops at the first divergent height.
+pub async fn verify(
+    mut config: config::Ledger,
+    wasm_dir: PathBuf,
+    args::LedgerVerify {
+        ledger_address,
+        snapshot,
+        to_height,
+    }: args::LedgerVerify,
+) -> Result<()> {
+    let client = HttpClient::new(ledger_address).map_err(Error::Rpc)?;
+
+    // The replay never participates in consensus
+    config.shell.tendermint_mode = TendermintMode::Full;
+    match snapshot {
+        Some(snapshot) => {
+            tracing::info!("Replaying blocks on top of {}", snapshot.display());
+            config.shell.db_dir = snapshot;
+        }
+        None => {
+            config.shell.db_dir = VERIFY_DB_DIR.into();
+            let db_dir = config.db_dir();
+            if db_dir.exists() {
+                std::fs::remove_dir_all(&db_dir).map_err(Error::ResetDb)?;
+            }
+            tracing::info!("Replaying blocks from genesis");
+        }
+    }
+
+    let RunAuxSetup {
+        vp_wasm_compilation_cache,
+        tx_wasm_compilation_cache,
+        db_block_cache_size_bytes,
+    } = run_aux_setup(&config, &wasm_dir).await;
+    // The DB cache must outlive the DB instance that's in the shell
+    let db_cache =
+        rocksdb::Cache::new_lru_cache(db_block_cache_size_bytes as usize);
+    // Protocol txs are only broadcast by validators
+    let (broadcast_sender, _broadcast_receiver) = mpsc::unbounded_channel();
+    let (mut shim, _abci_service, _server_shutdown) = AbcippShim::new(
+        config,
+        wasm_dir,
+        broadcast_sender,
+        None,
+        &db_cache,
+        vp_wasm_compilation_cache,
+        tx_wasm_compilation_cache,
+    );
+
+    let from_height = match shim.last_block_height() {
+        Some(last_height) => last_height.next_height(),
+        None => init_chain(&client, &mut shim).await?,
+    };
+    // The app hash of a block is only known from the header of the next one
+    let latest_height: BlockHeight = client
+        .status()
+        .await
+        .map_err(Error::Rpc)?
+        .sync_info
+        .latest_block_height
+        .into();
+    let last_verifiable_height = BlockHeight(latest_height.0.saturating_sub(1));
+    let to_height = match to_height {
+        Some(to_height) if to_height <= last_verifiable_height => to_height,
+        Some(to_height) => {
+            tracing::warn!(
+                "The app hash of the block at height {to_height} is not known \
+                 yet, verifying up to {last_verifiable_height}"
+            );
+            last_verifiable_height
+        }
+        None => last_verifiable_height,
+    };
+
+    let res = replay(&client, &mut shim, from_height, to_height).await;
+    shim.flush_db();
+    res?;
+    if from_height <= to_height {
+        println!(
+            "The app hashes of all the blocks from height {from_height} to \
+             {to_height} match."
+        );
+    } else {
+        println!("There are no new blocks to verify.");
+    }
+    Ok(())
+}
+
+/// Initialize the chain from the genesis of the CometBFT node and return the
+/// initial block height.
+async fn init_chain(
+    client: &HttpClient,
+    shim: &mut AbcippShim,
+) -> Result<BlockHeight> {
+    let genesis = client
+        .genesis::<serde_json::Value>()
+        .await
+        .map_err(Error::Rpc)?;
+    let initial_height = Height::try_from(genesis.initial_height)
+        .map_err(|err| Error::InvalidBlock(BlockHeight(0), err.to_string()))?;
+    let request = Req::InitChain(request::InitChain {
+        time: genesis.genesis_time,
+        chain_id: genesis.chain_id.to_string(),
+        consensus_params: genesis.consensus_params,
+        validators: genesis
+            .validators
+            .into_iter()
+            .map(|validator| validator::Update {
+                pub_key: validator.pub_key,
+                power: validator.power,
+            })
+            .collect(),
+        app_state_bytes: Default::default(),
+        initial_height,
+    });
+    match shim.handle(request).map_err(Error::Shell)? {
+        Resp::InitChain(_) => Ok(initial_height.into()),
+        resp => Err(Error::UnexpectedResponse(resp)),
+    }
+}
+
+/// Replay the blocks in the given range of heights
+async fn replay(
+    client: &HttpClient,
+    shim: &mut AbcippShim,
+    from_height: BlockHeight,
+    to_height: BlockHeight,
+) -> Result<()> {
+    let mut height = from_height;
+    while height <= to_height {
+        let block = fetch_block(client, height).await?;
+        let next_block = fetch_block(client, height.next_height()).await?;
+        let last_commit_info = last_commit_info(client, &block).await?;
+        let byzantine_validators = misbehaviors(&block)?;
+        let block_hash = block.header.hash();
+
+        shim.handle(Req::BeginBlock(request::BeginBlock {
+            hash: block_hash,
+            header: block.header.clone(),
+            last_commit_info,
+            byzantine_validators,
+        }))
+        .map_err(Error::Shell)?;
+        for tx in block.data {
+            shim.handle(Req::DeliverTx(request::DeliverTx { tx: tx.into() }))
+                .map_err(Error::Shell)?;
+        }
+        shim.handle(Req::EndBlock(request::EndBlock {
+            height: block.header.height.into(),
+        }))
+        .map_err(Error::Shell)?;
+        let app_hash = match shim.handle(Req::Commit).map_err(Error::Shell)? {
+            Resp::Commit(commit) => commit.data,
+            resp => return Err(Error::UnexpectedResponse(resp)),
+        };
+
+        let expected = next_block.header.app_hash.as_bytes();
+        if app_hash.as_ref() != expected {
+            return Err(Error::Divergence {
+                height,
+                expected: HEXUPPER.encode(expected),
+                actual: HEXUPPER.encode(&app_hash),
+            });
+        }
+        tracing::info!("Verified the app hash at height {height}");
+        height = height.next_height();
+    }
+    Ok(())
+}
+
+/// Fetch the block at the given height
+async fn fetch_block(
+    client: &HttpClient,
+    height: BlockHeight,
+) -> Result<Block> {
+    let tm_height = Height::try_from(height)
+        .map_err(|err| Error::InvalidBlock(height, err.to_string()))?;
+    client
+        .block(tm_height)
+        .await
+        .map(|response| response.block)
+        .map_err(Error::Rpc)
+}
+
+/// Rebuild the votes of the last commit of a block, as they're sent to the
+/// app by CometBFT. The signatures of a commit are ordered like the validator
+/// set at its height.
+async fn last_commit_info(
+    client: &HttpClient,
+    block: &Block,
+) -> Result<CommitInfo> {
+    let Some(last_commit) = block.last_commit.as_ref() else {
+        return Ok(CommitInfo {
+            round: Default::default(),
+            votes: vec![],
+        });
+    };
+    let height = block.header.height.into();
+    let validators = client
+        .validators(last_commit.height, Paging::All)
+        .await
+        .map_err(Error::Rpc)?
+        .validators;
+    if validators.len() != last_commit.signatures.len() {
+        return Err(Error::InvalidBlock(
+            height,
+            format!(
+                "The last commit has {} signatures for {} validators",
+                last_commit.signatures.len(),
+                validators.len()
+            ),
+        ));
+    }
+    let votes = validators
+        .into_iter()
+        .zip(&last_commit.signatures)
+        .map(|(validator, signature)| {
+            let flag = match signature {
+                CommitSig::BlockIdFlagAbsent => BlockIdFlag::Absent,
+                CommitSig::BlockIdFlagCommit { .. } => BlockIdFlag::Commit,
+                CommitSig::BlockIdFlagNil { .. } => BlockIdFlag::Nil,
+            };
+            Ok(VoteInfo {
+                validator: Validator {
+                    address: raw_address(height, &validator.address)?,
+                    power: validator.power,
+                },
+                sig_info: BlockSignatureInfo::Flag(flag),
+            })
+        })
+        .collect::<Result<_>>()?;
+    Ok(CommitInfo {
+        round: last_commit.round,
+        votes,
+    })
+}
+
+/// Convert the evidence included in a block into the misbehaviors reported
+/// to the app
+fn misbehaviors(block: &Block) -> Result<Vec<Misbehavior>> {
+    let height = block.header.height.into();
+    let mut misbehaviors = vec![];
+    for evidence in block.evidence.iter() {
+        match evidence {
+            Evidence::DuplicateVote(evidence) => {
+                misbehaviors.push(Misbehavior {
+                    kind: MisbehaviorKind::DuplicateVote,
+                    validator: Validator {
+                        address: raw_address(
+                            height,
+                            &evidence.vote_a.validator_address,
+                        )?,
+                        power: evidence.validator_power,
+                    },
+                    height: evidence.vote_a.height,
+                    time: evidence.timestamp,
+                    total_voting_power: evidence.total_voting_power,
+                })
+            }
+            Evidence::LightClientAttack(evidence) => {
+                for validator in &evidence.byzantine_validators {
+                    misbehaviors.push(Misbehavior {
+                        kind: MisbehaviorKind::LightClientAttack,
+                        validator: Validator {
+                            address: raw_address(height, &validator.address)?,
+                            power: validator.power,
+                        },
+                        height: evidence.common_height,
+                        time: evidence.timestamp,
+                        total_voting_power: evidence.total_voting_power,
+                    })
+                }
+            }
+        }
+    }
+    Ok(misbehaviors)
+}
+
+/// Get the raw bytes of a CometBFT validator address
+fn raw_address(height: BlockHeight, address: &account::Id) -> Result<[u8; 20]> {
+    address.as_bytes().try_into().map_err(|_| {
+        Error::InvalidBlock(
+            height,
+            format!("Invalid validator address {address}"),
+        )
+    })
+}
@@ -0,0 +1,491 @@
+//! Webhook notifications of the events of interest to the operator of a node.
+//!
+//! When configured, the node follows the committed blocks via the CometBFT
+//! RPC and POSTs a JSON [`Notification`] to every configured URL when:
+//! - the node's validator is jailed,
+//! - the node's validator missed `missed_blocks_threshold` consecutive
+//!   blocks,
+//! - a governance proposal enters its voting period.
+//!
+//! A failed delivery is retried up to `max_retries` times with an exponential
+//! backoff. When a secret is configured, the body of a notification is signed
+//! with HMAC-SHA256 in the [`SIGNATURE_HEADER`] header, which the receivers
+//! should check before acting on it. No notification is sent while the node
+//! is catching up with the chain.
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use data_encoding::HEXLOWER;
+use namada::core::storage::Epoch;
+use namada::governance::storage::keys as governance_storage;
+use namada::governance::storage::proposal::StorageProposal;
+use namada::proof_of_stake::types::ValidatorState;
+use namada_sdk::rpc;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::sync::oneshot;
+
+use crate::config::WebhookConfig;
+use crate::facade::tendermint::block::{CommitSig, Height};
+use crate::facade::tendermint_rpc::{self, Client, HttpClient};
+
+/// The header carrying the HMAC-SHA256 signature of a notification's body,
+/// as `sha256=<hex>`
+pub const SIGNATURE_HEADER: &str = "X-Namada-Signature";
+
+/// The interval between the checks for a new block
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The delay before the first retry of a failed delivery, doubled on every
+/// retry
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// The block size of SHA-256, used by HMAC
+const SHA256_BLOCK_LEN: usize = 64;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("CometBFT RPC request failed: {0}")]
+    Rpc(tendermint_rpc::Error),
+    #[error("Ledger query failed: {0}")]
+    Query(namada_sdk::error::Error),
+    #[error("Invalid block height {0}")]
+    Height(u64),
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// A webhook notification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Notification {
+    /// The node's validator was jailed
+    ValidatorJailed {
+        /// The address of the validator
+        validator: String,
+        /// The epoch from which the validator is jailed
+        epoch: u64,
+        /// The height of the block at which it was noticed
+        height: u64,
+    },
+    /// The node's validator missed consecutive blocks
+    MissedBlocks {
+        /// The consensus address of the validator
+        consensus_address: String,
+        /// The number of consecutive blocks missed
+        missed_blocks: u64,
+        /// The height of the last missed block
+        height: u64,
+    },
+    /// A governance proposal entered its voting period
+    ProposalVotingStarted {
+        /// The id of the proposal
+        proposal_id: u64,
+        /// The address of the author of the proposal
+        author: String,
+        /// The first epoch of the voting period
+        voting_start_epoch: u64,
+        /// The epoch from which voting is stopped
+        voting_end_epoch: u64,
+        /// The height of the block at which it was noticed
+        height: u64,
+    },
+}
+
+/// The state of the watched events between two checks
+#[derive(Debug, Default)]
+struct Watcher {
+    /// The height of the last checked block
+    last_height: Option<u64>,
+    /// Whether the node's validator was jailed at the last checked block
+    jailed: Option<bool>,
+    /// The number of consecutive blocks missed by the node's validator
+    missed_blocks: u64,
+    /// The epoch of the last checked block
+    epoch: Option<Epoch>,
+    /// The id of the next proposal to load
+    next_proposal_id: u64,
+    /// The proposals whose voting period hasn't started yet
+    pending_proposals: BTreeMap<u64, StorageProposal>,
+}
+
+impl Watcher {
+    /// Record whether the node's validator signed the last block. Returns the
+    /// number of consecutive missed blocks when it reaches the threshold, such
+    /// that a streak of missed blocks is only notified once.
+    fn record_signature(
+        &mut self,
+        signed: bool,
+        threshold: u64,
+    ) -> Option<u64> {
+        if signed {
+            self.missed_blocks = 0;
+            return None;
+        }
+        self.missed_blocks = self.missed_blocks.saturating_add(1);
+        (self.missed_blocks == threshold.max(1)).then_some(self.missed_blocks)
+    }
+
+    /// Record whether the node's validator is jailed. Returns `true` if it
+    /// was jailed since the last check, but not if it already was on the
+    /// first check.
+    fn record_jailed(&mut self, jailed: bool) -> bool {
+        let was_jailed = self.jailed.replace(jailed);
+        jailed && was_jailed == Some(false)
+    }
+
+    /// Keep track of a new proposal, if its voting period starts after the
+    /// given epoch
+    fn add_proposal(&mut self, proposal: StorageProposal, epoch: Epoch) {
+        if proposal.voting_start_epoch > epoch {
+            self.pending_proposals.insert(proposal.id, proposal);
+        }
+    }
+
+    /// Take the proposals whose voting period started at the given epoch, or
+    /// before, if the epoch changed since the last check
+    fn take_proposals_entering_voting(
+        &mut self,
+        epoch: Epoch,
+    ) -> Vec<StorageProposal> {
+        if self.epoch.replace(epoch) == Some(epoch) {
+            return vec![];
+        }
+        let pending = std::mem::take(&mut self.pending_proposals);
+        let (started, pending) = pending
+            .into_iter()
+            .partition(|(_, proposal)| proposal.voting_start_epoch <= epoch);
+        self.pending_proposals = pending;
+        started.into_values().collect()
+    }
+
+    /// Check the events of the last committed block, if it wasn't checked
+    /// yet
+    async fn check(
+        &mut self,
+        client: &HttpClient,
+        config: &WebhookConfig,
+    ) -> Result<Vec<Notification>> {
+        let status = client.status().await.map_err(Error::Rpc)?;
+        let height = status.sync_info.latest_block_height.value();
+        if status.sync_info.catching_up
+            || self.last_height.is_some_and(|last| last >= height)
+        {
+            return Ok(vec![]);
+        }
+        let mut notifications = vec![];
+
+        // Only the validators in the consensus set have a voting power
+        if status.validator_info.power.value() > 0 {
+            let own_address = status.validator_info.address;
+            let block = client
+                .block(
+                    Height::try_from(height)
+                        .map_err(|_| Error::Height(height))?,
+                )
+                .await
+                .map_err(Error::Rpc)?
+                .block;
+            let signed = block.last_commit.map_or(true, |commit| {
+                commit.signatures.iter().any(|sig| {
+                    matches!(
+                        sig,
+                        CommitSig::BlockIdFlagCommit { validator_address, .. }
+                            if *validator_address == own_address
+                    )
+                })
+            });
+            if let Some(missed_blocks) =
+                self.record_signature(signed, config.missed_blocks_threshold)
+            {
+                notifications.push(Notification::MissedBlocks {
+                    consensus_address: own_address.to_string(),
+                    missed_blocks,
+                    height,
+                });
+            }
+        } else {
+            self.missed_blocks = 0;
+        }
+
+        if let Some(validator) = &config.validator {
+            let (state, epoch) =
+                rpc::get_validator_state(client, validator, None)
+                    .await
+                    .map_err(Error::Query)?;
+            if self.record_jailed(matches!(state, Some(ValidatorState::Jailed)))
+            {
+                notifications.push(Notification::ValidatorJailed {
+                    validator: validator.to_string(),
+                    epoch: epoch.0,
+                    height,
+                });
+            }
+        }
+
+        let epoch = rpc::query_epoch(client).await.map_err(Error::Query)?;
+        let next_proposal_id: u64 = rpc::query_storage_value(
+            client,
+            &governance_storage::get_counter_key(),
+        )
+        .await
+        .map_err(Error::Query)?;
+        for id in self.next_proposal_id..next_proposal_id {
+            if let Some(proposal) = rpc::query_proposal_by_id(client, id)
+                .await
+                .map_err(Error::Query)?
+            {
+                self.add_proposal(proposal, epoch);
+            }
+            self.next_proposal_id = id.saturating_add(1);
+        }
+        notifications.extend(
+            self.take_proposals_entering_voting(epoch).into_iter().map(
+                |proposal| Notification::ProposalVotingStarted {
+                    proposal_id: proposal.id,
+                    author: proposal.author.to_string(),
+                    voting_start_epoch: proposal.voting_start_epoch.0,
+                    voting_end_epoch: proposal.voting_end_epoch.0,
+                    height,
+                },
+            ),
+        );
+
+        self.last_height = Some(height);
+        Ok(notifications)
+    }
+}
+
+/// Run the webhook notifications until a signal is sent on `abort_recv`. The
+/// node's ledger is queried via the CometBFT RPC at `rpc_address`.
+pub async fn run(
+    config: WebhookConfig,
+    rpc_address: SocketAddr,
+    mut abort_recv: oneshot::Receiver<()>,
+) {
+    if config.urls.is_empty() {
+        tracing::warn!("No webhook URL is configured, no notification is sent");
+        return;
+    }
+    let client = HttpClient::new(format!("http://{rpc_address}").as_str())
+        .expect("Failed to create the CometBFT RPC client");
+    let http_client = reqwest::Client::new();
+
+    tracing::info!(urls = ?config.urls, "Starting the webhook notifications");
+    let mut watcher = Watcher::default();
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = &mut abort_recv => break,
+            _ = interval.tick() => {}
+        }
+        let notifications = match watcher.check(&client, &config).await {
+            Ok(notifications) => notifications,
+            Err(err) => {
+                tracing::debug!(
+                    "Failed to check the events of the webhook \
+                     notifications: {err}"
+                );
+                continue;
+            }
+        };
+        for notification in notifications {
+            let body = match serde_json::to_vec(&notification) {
+                Ok(body) => body,
+                Err(err) => {
+                    tracing::error!(
+                        ?notification,
+                        "Failed to encode a webhook notification: {err}"
+                    );
+                    continue;
+                }
+            };
+            tracing::info!(?notification, "Sending a webhook notification");
+            for url in &config.urls {
+                // The deliveries are retried in the background, so as not to
+                // delay the next checks
+                tokio::spawn(deliver(
+                    http_client.clone(),
+                    url.clone(),
+                    body.clone(),
+                    config.secret.clone(),
+                    config.max_retries,
+                ));
+            }
+        }
+    }
+}
+
+/// POST a notification to a webhook URL, retrying with an exponential backoff
+/// until it's accepted or the retries are exhausted
+async fn deliver(
+    http_client: reqwest::Client,
+    url: String,
+    body: Vec<u8>,
+    secret: Option<String>,
+    max_retries: u32,
+) {
+    let signature = secret.map(|secret| {
+        format!(
+            "sha256={}",
+            HEXLOWER.encode(&hmac_sha256(secret.as_bytes(), &body))
+        )
+    });
+    let mut delay = INITIAL_RETRY_DELAY;
+    for attempt in 0..=max_retries {
+        if attempt > 0 {
+            tokio::time::sleep(delay).await;
+            delay = delay.saturating_mul(2);
+        }
+        let mut request = http_client
+            .post(&url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.clone());
+        if let Some(signature) = &signature {
+            request = request.header(SIGNATURE_HEADER, signature);
+        }
+        match request.send().await {
+            Ok(response) if response.status().is_success() => return,
+            Ok(response) => tracing::debug!(
+                url,
+                attempt,
+                "The webhook responded with status {}",
+                response.status()
+            ),
+            Err(err) => {
+                tracing::debug!(
+                    url,
+                    attempt,
+                    "Failed to POST to the webhook: {err}"
+                )
+            }
+        }
+    }
+    tracing::warn!(
+        url,
+        "Failed to deliver a webhook notification after {} attempts",
+        max_retries.saturating_add(1)
+    );
+}
+
+/// Compute the HMAC-SHA256 of a message, as specified by RFC 2104
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut block_key = [0u8; SHA256_BLOCK_LEN];
+    if key.len() > SHA256_BLOCK_LEN {
+        block_key[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+    let mut inner = Sha256::new();
+    inner.update(block_key.map(|byte| byte ^ 0x36));
+    inner.update(message);
+    let mut outer = Sha256::new();
+    outer.update(block_key.map(|byte| byte ^ 0x5c));
+    outer.update(inner.finalize());
+    outer.finalize().into()
+}
+
+#[cfg(test)]
+mod test {
+    use namada::core::address::testing::established_address_1;
+    use namada::governance::storage::proposal::ProposalType;
+
+    use super::*;
+
+    /// Test the HMAC-SHA256 against the test vectors of RFC 4231
+    #[test]
+    fn test_hmac_sha256() {
+        assert_eq!(
+            HEXLOWER
+                .encode(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // A key longer than the block size is hashed first
+        assert_eq!(
+            HEXLOWER.encode(&hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    /// Test that a streak of missed blocks is notified once, when it reaches
+    /// the threshold
+    #[test]
+    fn test_record_signature() {
+        let mut watcher = Watcher::default();
+        assert_eq!(watcher.record_signature(false, 3), None);
+        assert_eq!(watcher.record_signature(false, 3), None);
+        assert_eq!(watcher.record_signature(false, 3), Some(3));
+        assert_eq!(watcher.record_signature(false, 3), None);
+        // A signed block ends the streak
+        assert_eq!(watcher.record_signature(true, 3), None);
+        assert_eq!(watcher.record_signature(false, 3), None);
+        assert_eq!(watcher.missed_blocks, 1);
+    }
+
+    /// Test that only the jailing of the validator after the first check is
+    /// notified
+    #[test]
+    fn test_record_jailed() {
+        let mut watcher = Watcher::default();
+        assert!(!watcher.record_jailed(true));
+        assert!(!watcher.record_jailed(false));
+        assert!(watcher.record_jailed(true));
+        assert!(!watcher.record_jailed(true));
+    }
+
+    /// Test that a proposal is notified at the epoch its voting period starts
+    #[test]
+    fn test_proposals_entering_voting() {
+        let proposal = |id: u64, voting_start_epoch: u64| StorageProposal {
+            id,
+            content: BTreeMap::new(),
+            author: established_address_1(),
+            r#type: ProposalType::Default,
+            voting_start_epoch: Epoch(voting_start_epoch),
+            voting_end_epoch: Epoch(voting_start_epoch + 12),
+            activation_epoch: Epoch(voting_start_epoch + 14),
+        };
+        let mut watcher = Watcher::default();
+        // A proposal whose voting period already started is ignored
+        watcher.add_proposal(proposal(0, 1), Epoch(1));
+        watcher.add_proposal(proposal(1, 2), Epoch(1));
+        watcher.add_proposal(proposal(2, 3), Epoch(1));
+        assert!(watcher.take_proposals_entering_voting(Epoch(1)).is_empty());
+
+        let ids = |proposals: Vec<StorageProposal>| -> Vec<u64> {
+            proposals.into_iter().map(|proposal| proposal.id).collect()
+        };
+        let started = watcher.take_proposals_entering_voting(Epoch(2));
+        assert_eq!(ids(started), vec![1]);
+        // Only notified once per epoch
+        assert!(watcher.take_proposals_entering_voting(Epoch(2)).is_empty());
+        let started = watcher.take_proposals_entering_voting(Epoch(3));
+        assert_eq!(ids(started), vec![2]);
+        assert!(watcher.pending_proposals.is_empty());
+    }
+
+    /// Test the JSON encoding of the notifications
+    #[test]
+    fn test_notification_json() {
+        let notification = Notification::MissedBlocks {
+            consensus_address: "ABCD".to_string(),
+            missed_blocks: 10,
+            height: 100,
+        };
+        assert_eq!(
+            serde_json::to_value(&notification).unwrap(),
+            serde_json::json!({
+                "kind": "missed_blocks",
+                "consensus_address": "ABCD",
+                "missed_blocks": 10,
+                "height": 100,
+            })
+        );
+    }
+}
@@ -2,11 +2,13 @@
 use core::borrow::Borrow;
 use std::fs;
 use std::path::Path;
+use std::str::FromStr;
 
 use data_encoding::HEXLOWER;
 use eyre::{eyre, WrapErr};
 use futures::future::join_all;
 use namada::core::collections::HashMap;
+use namada::core::hash::Hash;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use thiserror::Error;
@@ -141,6 +143,65 @@ fn valid_wasm_checksum(
     }
 }
 
+/// The verification of a wasm artifact against its checksum and the code
+/// deployed on chain under its name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactStatus {
+    /// The artifact matches its checksum and the code on chain
+    Verified,
+    /// The artifact couldn't be read
+    Missing,
+    /// The hash of the artifact doesn't match the checksum in its file name
+    ChecksumMismatch,
+    /// No code is deployed on chain under the name of the artifact
+    NotOnChain,
+    /// The code deployed on chain differs from the artifact
+    OnChainMismatch,
+}
+
+impl ArtifactStatus {
+    /// Check if the artifact differs from its checksum or from the chain
+    pub fn is_mismatch(&self) -> bool {
+        matches!(self, Self::ChecksumMismatch | Self::OnChainMismatch)
+    }
+}
+
+/// Compute the hash of a wasm artifact from its full file name in the given
+/// directory, or `None` if it cannot be read.
+pub fn hash_artifact(
+    wasm_directory: impl AsRef<Path>,
+    full_name: &str,
+) -> Option<Hash> {
+    fs::read(wasm_directory.as_ref().join(full_name))
+        .ok()
+        .map(Hash::sha256)
+}
+
+/// Verify a wasm artifact, given its hash if it could be read, its full file
+/// name with its checksum and the hash of the code deployed on chain under its
+/// name, if any.
+pub fn verify_artifact(
+    hash: Option<&Hash>,
+    full_name: &str,
+    on_chain: Option<&Hash>,
+) -> ArtifactStatus {
+    let Some(hash) = hash else {
+        return ArtifactStatus::Missing;
+    };
+    let checksum = full_name
+        .split('.')
+        .nth(1)
+        .and_then(|checksum| Hash::from_str(checksum).ok());
+    if checksum.as_ref() != Some(hash) {
+        return ArtifactStatus::ChecksumMismatch;
+    }
+    match on_chain {
+        None => ArtifactStatus::NotOnChain,
+        Some(on_chain) if on_chain == hash => ArtifactStatus::Verified,
+        Some(_) => ArtifactStatus::OnChainMismatch,
+    }
+}
+
 /// Download all the pre-built wasms, or if they're already downloaded, verify
 /// their checksums.
 pub async fn pre_fetch_wasm(wasm_directory: impl AsRef<Path>) {
@@ -300,3 +361,45 @@ async fn download_wasm(name: &str, full_name: &str) -> Result<Vec<u8>, Error> {
         Err(e) => Err(Error::Download(url, e)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test the verification of a wasm artifact
+    #[test]
+    fn test_verify_artifact() {
+        let code = b"code";
+        let hash = Hash::sha256(code);
+        let full_name =
+            format!("tx_test.{}.wasm", hash.to_string().to_lowercase());
+        let other = Hash::sha256(b"other");
+
+        assert_eq!(
+            verify_artifact(Some(&hash), &full_name, Some(&hash)),
+            ArtifactStatus::Verified
+        );
+        assert_eq!(
+            verify_artifact(None, &full_name, Some(&hash)),
+            ArtifactStatus::Missing
+        );
+        assert_eq!(
+            verify_artifact(Some(&other), &full_name, Some(&other)),
+            ArtifactStatus::ChecksumMismatch
+        );
+        assert_eq!(
+            verify_artifact(Some(&hash), "tx_test.wasm", Some(&hash)),
+            ArtifactStatus::ChecksumMismatch
+        );
+        assert_eq!(
+            verify_artifact(Some(&hash), &full_name, None),
+            ArtifactStatus::NotOnChain
+        );
+        assert_eq!(
+            verify_artifact(Some(&hash), &full_name, Some(&other)),
+            ArtifactStatus::OnChainMismatch
+        );
+        assert!(ArtifactStatus::OnChainMismatch.is_mismatch());
+        assert!(!ArtifactStatus::NotOnChain.is_mismatch());
+    }
+}
@@ -78,6 +78,7 @@ fn process_tx(c: &mut Criterion) {
                     shell
                         .check_proposal_tx(
                             &wrapper,
+                            false,
                             &mut validation_meta,
                             &mut temp_state,
                             datetime,
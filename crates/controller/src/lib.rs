@@ -1,8 +1,13 @@
 use namada_core::arith::{self, checked};
-use namada_core::dec::Dec;
-use namada_core::uint::Uint;
+use namada_core::dec::{Dec, POS_DECIMAL_PRECISION};
+use namada_core::uint::{Uint, I256};
 use thiserror::Error;
 
+/// The first protocol version computing the inflation exactly. The previous
+/// versions truncate every intermediate product of [`Dec`]s, which can make
+/// the inflation differ by one unit.
+pub const EXACT_INFLATION_PROTOCOL_VERSION: u64 = 2;
+
 #[derive(Clone, Debug)]
 pub struct PDController {
     total_native_amount: Uint,
@@ -25,6 +30,8 @@ pub enum Error {
     MaxInflationOverflow,
     #[error("Inflation amount overflow")]
     InflationOverflow,
+    #[error("Control value overflow")]
+    ControlOverflow,
 }
 
 impl PDController {
@@ -51,11 +58,22 @@ impl PDController {
         }
     }
 
+    /// Compute the inflation amount from the control coefficient and the
+    /// current value of the metric, as specified by the given protocol
+    /// version. From [`EXACT_INFLATION_PROTOCOL_VERSION`], the computation is
+    /// carried out on signed 256-bit fixed-point numbers without any
+    /// intermediate rounding, so the inflation is exact until it's rounded
+    /// down at the end.
     pub fn compute_inflation(
         &self,
         control_coeff: Dec,
         current_metric: Dec,
+        protocol_version: u64,
     ) -> Result<Uint, Error> {
+        if protocol_version < EXACT_INFLATION_PROTOCOL_VERSION {
+            return self
+                .compute_inflation_truncated(control_coeff, current_metric);
+        }
         let control = self.compute_control(control_coeff, current_metric)?;
         self.compute_inflation_aux(control)
     }
@@ -68,24 +86,38 @@ impl PDController {
         self.epochs_per_year
     }
 
+    /// The maximum inflation per epoch, rounded down
     fn get_max_inflation(&self) -> Result<Uint, Error> {
-        let total_native = self.get_total_native_dec()?;
-        let epochs_py: Dec = self.epochs_per_year.into();
-        let max_inflation =
-            checked!(total_native * self.max_reward_rate / epochs_py)?;
-        max_inflation.to_uint().ok_or(Error::MaxInflationOverflow)
+        let max_inflation = I256::try_from(self.total_native_amount)
+            .ok()
+            .and_then(|total| total.checked_mul(self.max_reward_rate.0))
+            .and_then(|max_inflation| {
+                let epochs_py = I256::from(self.epochs_per_year)
+                    .checked_mul(scaling_factor(1))?;
+                max_inflation.checked_div(epochs_py)
+            })
+            .ok_or(Error::MaxInflationOverflow)?;
+        if max_inflation.is_negative() {
+            return Err(Error::MaxInflationOverflow);
+        }
+        Ok(max_inflation.abs())
     }
 
-    // TODO: could possibly use I256 instead of Dec here (need to account for
-    // negative vals)
-    fn compute_inflation_aux(&self, control: Dec) -> Result<Uint, Error> {
-        let last_inflation_amount = Dec::try_from(self.last_inflation_amount)?;
-        let new_inflation_amount = checked!(last_inflation_amount + control)?;
-        let new_inflation_amount = if new_inflation_amount.is_negative() {
+    /// Add the control value to the last inflation amount. The result is
+    /// rounded down and bounded by zero and the maximum inflation.
+    fn compute_inflation_aux(&self, control: I256) -> Result<Uint, Error> {
+        let scaling = scaling_factor(CONTROL_SCALE);
+        let new_inflation = I256::try_from(self.last_inflation_amount)
+            .ok()
+            .and_then(|last_inflation| last_inflation.checked_mul(scaling))
+            .and_then(|last_inflation| last_inflation.checked_add(control))
+            .ok_or(Error::InflationOverflow)?;
+        let new_inflation_amount = if new_inflation.is_negative() {
             Uint::zero()
         } else {
-            new_inflation_amount
-                .to_uint()
+            new_inflation
+                .abs()
+                .checked_div(scaling.abs())
                 .ok_or(Error::InflationOverflow)?
         };
 
@@ -93,18 +125,170 @@ impl PDController {
         Ok(std::cmp::min(new_inflation_amount, max_inflation))
     }
 
-    // NOTE: This formula is the comactification of all the old intermediate
-    // computations that were done in multiple steps (as in the specs)
-    fn compute_control(
+    /// Compute the inflation with [`Dec`]s, truncating every intermediate
+    /// product, as done before [`EXACT_INFLATION_PROTOCOL_VERSION`]
+    fn compute_inflation_truncated(
         &self,
         coeff: Dec,
         current_metric: Dec,
-    ) -> Result<Dec, arith::Error> {
+    ) -> Result<Uint, Error> {
         let val: Dec = checked!(
             current_metric * (self.d_gain_nom - self.p_gain_nom)
                 + (self.target_metric * self.p_gain_nom)
                 - (self.last_metric * self.d_gain_nom)
         )?;
-        checked!(coeff * val)
+        let control: Dec = checked!(coeff * val)?;
+
+        let last_inflation_amount = Dec::try_from(self.last_inflation_amount)?;
+        let new_inflation_amount = checked!(last_inflation_amount + control)?;
+        let new_inflation_amount = if new_inflation_amount.is_negative() {
+            Uint::zero()
+        } else {
+            new_inflation_amount
+                .to_uint()
+                .ok_or(Error::InflationOverflow)?
+        };
+
+        let total_native = self.get_total_native_dec()?;
+        let epochs_py: Dec = self.epochs_per_year.into();
+        let max_inflation =
+            checked!(total_native * self.max_reward_rate / epochs_py)?
+                .to_uint()
+                .ok_or(Error::MaxInflationOverflow)?;
+        Ok(std::cmp::min(new_inflation_amount, max_inflation))
+    }
+
+    /// Compute the control value as a fixed-point number with
+    /// `CONTROL_SCALE * POS_DECIMAL_PRECISION` decimal places. Every
+    /// product of [`Dec`]s is kept at full precision instead of being
+    /// truncated.
+    fn compute_control(
+        &self,
+        coeff: Dec,
+        current_metric: Dec,
+    ) -> Result<I256, Error> {
+        // NOTE: This formula is the comactification of all the old
+        // intermediate computations that were done in multiple steps (as in
+        // the specs)
+        let gains_diff = self.d_gain_nom.0.checked_sub(self.p_gain_nom.0);
+        let val = gains_diff
+            .and_then(|gains_diff| current_metric.0.checked_mul(gains_diff))
+            .and_then(|val| {
+                val.checked_add(
+                    self.target_metric.0.checked_mul(self.p_gain_nom.0)?,
+                )
+            })
+            .and_then(|val| {
+                val.checked_sub(
+                    self.last_metric.0.checked_mul(self.d_gain_nom.0)?,
+                )
+            });
+        val.and_then(|val| coeff.0.checked_mul(val))
+            .ok_or(Error::ControlOverflow)
+    }
+}
+
+/// The number of [`Dec`] factors multiplied in the control value
+const CONTROL_SCALE: usize = 3;
+
+/// The scaling factor of a product of `num_factors` [`Dec`]s
+fn scaling_factor(num_factors: usize) -> I256 {
+    I256(Uint::exp10(
+        usize::from(POS_DECIMAL_PRECISION).saturating_mul(num_factors),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// A [`Dec`] between 0 and 1
+    fn arb_ratio() -> impl Strategy<Value = Dec> {
+        (0..=1_000_000_000_000_i128).prop_map(|inner| Dec(I256::from(inner)))
+    }
+
+    proptest! {
+        /// Test that the exact inflation differs by at most one unit from the
+        /// one computed with [`Dec`]s by the previous protocol versions,
+        /// whose truncated intermediate products can drift by less than one
+        /// unit when the control coefficient is below 10^11
+        #[test]
+        fn test_inflation_against_dec(
+            total_native_amount in any::<u64>(),
+            max_reward_rate in arb_ratio(),
+            last_inflation_amount in any::<u64>(),
+            p_gain_nom in arb_ratio(),
+            d_gain_nom in arb_ratio(),
+            epochs_per_year in 1..=100_000_u64,
+            target_metric in arb_ratio(),
+            last_metric in arb_ratio(),
+            coeff in 0..=100_000_000_000_000_000_000_000_i128,
+            current_metric in arb_ratio(),
+        ) {
+            let controller = PDController::new(
+                total_native_amount.into(),
+                max_reward_rate,
+                last_inflation_amount.into(),
+                p_gain_nom,
+                d_gain_nom,
+                epochs_per_year,
+                target_metric,
+                last_metric,
+            );
+            let coeff = Dec(I256::from(coeff));
+
+            let inflation = controller
+                .compute_inflation(
+                    coeff,
+                    current_metric,
+                    EXACT_INFLATION_PROTOCOL_VERSION,
+                )
+                .unwrap();
+            let inflation_dec = controller
+                .compute_inflation(
+                    coeff,
+                    current_metric,
+                    EXACT_INFLATION_PROTOCOL_VERSION - 1,
+                )
+                .unwrap();
+            let diff = if inflation > inflation_dec {
+                inflation - inflation_dec
+            } else {
+                inflation_dec - inflation
+            };
+            prop_assert!(diff <= Uint::one());
+        }
+    }
+
+    /// Test that the inflation is rounded down once, after the control is
+    /// added to the last inflation
+    #[test]
+    fn test_inflation_rounding() {
+        let controller = PDController::new(
+            Uint::from(1_000_000_000_u64),
+            Dec::one(),
+            Uint::from(10_u64),
+            Dec::one(),
+            Dec::zero(),
+            1,
+            Dec::zero(),
+            Dec::zero(),
+        );
+        // The control is about -1.5 (1/3 * -4.5), which brings the inflation
+        // to about 8.5, rounded down to 8
+        let coeff = Dec::one_third();
+        let current_metric = Dec::new(45, 1).unwrap();
+        assert_eq!(
+            controller
+                .compute_inflation(
+                    coeff,
+                    current_metric,
+                    EXACT_INFLATION_PROTOCOL_VERSION
+                )
+                .unwrap(),
+            Uint::from(8_u64)
+        );
     }
 }
@@ -71,6 +71,12 @@ pub const ETH_BRIDGE: Address = Address::Internal(InternalAddress::EthBridge);
 /// never committed to DB
 pub const TEMP_STORAGE: Address =
     Address::Internal(InternalAddress::TempStorage);
+/// Internal name registry address
+pub const NAME_REGISTRY: Address =
+    Address::Internal(InternalAddress::NameRegistry);
+/// Internal fee collector address
+pub const FEE_COLLECTOR: Address =
+    Address::Internal(InternalAddress::FeeCollector);
 
 /// Error from decoding address from string
 pub type DecodeError = string_encoding::DecodeError;
@@ -143,6 +149,17 @@ impl From<raw::Address<'_, raw::Validated>> for Address {
             raw::Discriminant::TempStorage => {
                 Address::Internal(InternalAddress::TempStorage)
             }
+            raw::Discriminant::BondReceipt => Address::Internal(
+                InternalAddress::BondReceipt(EstablishedAddress {
+                    hash: *raw_addr.data(),
+                }),
+            ),
+            raw::Discriminant::NameRegistry => {
+                Address::Internal(InternalAddress::NameRegistry)
+            }
+            raw::Discriminant::FeeCollector => {
+                Address::Internal(InternalAddress::FeeCollector)
+            }
         }
     }
 }
@@ -242,6 +259,24 @@ impl<'addr> From<&'addr Address> for raw::Address<'addr, raw::Validated> {
                     .validate()
                     .expect("This raw address is valid")
             }
+            Address::Internal(InternalAddress::BondReceipt(
+                EstablishedAddress { hash },
+            )) => {
+                raw::Address::from_discriminant(raw::Discriminant::BondReceipt)
+                    .with_data_array_ref(hash)
+                    .validate()
+                    .expect("This raw address is valid")
+            }
+            Address::Internal(InternalAddress::NameRegistry) => {
+                raw::Address::from_discriminant(raw::Discriminant::NameRegistry)
+                    .validate()
+                    .expect("This raw address is valid")
+            }
+            Address::Internal(InternalAddress::FeeCollector) => {
+                raw::Address::from_discriminant(raw::Discriminant::FeeCollector)
+                    .validate()
+                    .expect("This raw address is valid")
+            }
         }
     }
 }
@@ -578,6 +613,14 @@ pub enum InternalAddress {
     /// Address with temporary storage is used to pass data from txs to VPs
     /// which is never committed to DB
     TempStorage,
+    /// Liquid staking receipt token of the bonds to the validator with the
+    /// given established address
+    BondReceipt(EstablishedAddress),
+    /// Registry of the names of addresses
+    NameRegistry,
+    /// Collector of the fees paid out of the shielded pool, which the
+    /// protocol forwards to the block proposer
+    FeeCollector,
 }
 
 impl Display for InternalAddress {
@@ -600,6 +643,12 @@ impl Display for InternalAddress {
                 Self::Pgf => "PublicGoodFundings".to_string(),
                 Self::Masp => "MASP".to_string(),
                 Self::TempStorage => "TempStorage".to_string(),
+                Self::BondReceipt(validator) => format!(
+                    "BondReceipt: {}",
+                    Address::Established(validator.clone())
+                ),
+                Self::NameRegistry => "NameRegistry".to_string(),
+                Self::FeeCollector => "FeeCollector".to_string(),
             }
         )
     }
@@ -615,6 +664,7 @@ impl InternalAddress {
             "bridgepool" => Some(InternalAddress::EthBridgePool),
             "governance" => Some(InternalAddress::Governance),
             "masp" => Some(InternalAddress::Masp),
+            "names" => Some(InternalAddress::NameRegistry),
             _ => None,
         }
     }
@@ -821,8 +871,11 @@ pub mod testing {
             InternalAddress::Pgf => {}
             InternalAddress::Masp => {}
             InternalAddress::Multitoken => {}
-            InternalAddress::TempStorage => {} /* Add new addresses in the
-                                                * `prop_oneof` below. */
+            InternalAddress::TempStorage => {}
+            InternalAddress::BondReceipt(_) => {}
+            InternalAddress::NameRegistry => {}
+            InternalAddress::FeeCollector => {} /* Add new addresses in the
+                                                 * `prop_oneof` below. */
         };
         prop_oneof![
             Just(InternalAddress::PoS),
@@ -839,6 +892,9 @@ pub mod testing {
             Just(InternalAddress::Pgf),
             Just(InternalAddress::Masp),
             Just(InternalAddress::TempStorage),
+            arb_established_address().prop_map(InternalAddress::BondReceipt),
+            Just(InternalAddress::NameRegistry),
+            Just(InternalAddress::FeeCollector),
         ]
     }
 
@@ -67,6 +67,12 @@ pub enum Discriminant {
     Masp = 14,
     /// Temporary storage address.
     TempStorage = 15,
+    /// Bond receipt token raw address.
+    BondReceipt = 16,
+    /// Name registry raw address.
+    NameRegistry = 17,
+    /// Fee collector raw address.
+    FeeCollector = 18,
 }
 
 /// Raw address representation.
@@ -116,7 +122,8 @@ impl<S> Address<'_, S> {
                 | Discriminant::Established
                 | Discriminant::Erc20
                 | Discriminant::Nut
-                | Discriminant::IbcToken,
+                | Discriminant::IbcToken
+                | Discriminant::BondReceipt,
         )
     }
 }
@@ -0,0 +1,106 @@
+//! Batched signature verification.
+//!
+//! Ed25519 signatures are verified together in a single batch, which is
+//! considerably cheaper than verifying them one by one. Secp256k1 has no batch
+//! verification, so its signatures are queued and verified individually.
+
+use ed25519_consensus::{batch, VerificationKeyBytes};
+use rand::{CryptoRng, RngCore};
+
+use super::{common, SigScheme, SignableBytes, VerifySigError};
+use crate::hash::{Hash, Sha256Hasher};
+
+/// Batch verifier of signatures over data hashed with SHA256.
+#[derive(Default)]
+pub struct BatchVerifier {
+    /// The queued ed25519 signatures
+    ed25519: batch::Verifier,
+    /// The queued signatures of the schemes without batch verification
+    individual: Vec<(common::PublicKey, Hash, common::Signature)>,
+}
+
+impl BatchVerifier {
+    /// Create a new empty batch verifier.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a signature of the given data to be verified against the given
+    /// public key.
+    pub fn queue(
+        &mut self,
+        pk: &common::PublicKey,
+        data: &impl SignableBytes,
+        sig: &common::Signature,
+    ) {
+        let msg = data.signable_hash::<Sha256Hasher>();
+        match (pk, sig) {
+            (
+                common::PublicKey::Ed25519(pk),
+                common::Signature::Ed25519(sig),
+            ) => {
+                self.ed25519.queue((
+                    VerificationKeyBytes::from(pk.0),
+                    sig.0,
+                    &msg,
+                ));
+            }
+            _ => self.individual.push((pk.clone(), Hash(msg), sig.clone())),
+        }
+    }
+
+    /// Verify all the queued signatures. Fails if any of them is invalid,
+    /// without telling which one.
+    pub fn verify<R: RngCore + CryptoRng>(
+        self,
+        rng: R,
+    ) -> Result<(), VerifySigError> {
+        self.ed25519
+            .verify(rng)
+            .map_err(|err| VerifySigError::SigVerifyError(err.to_string()))?;
+        self.individual.iter().try_for_each(|(pk, hash, sig)| {
+            common::SigScheme::verify_signature(pk, hash, sig)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::key::testing::{keypair_1, keypair_2, keypair_3};
+    use crate::key::RefTo;
+
+    /// Test that a batch is only valid if all of its signatures are valid
+    #[test]
+    fn test_batch_verify() {
+        let mut rng = rand::thread_rng();
+        let ed_sk = keypair_1();
+        let ed_sk_2 = keypair_2();
+        let secp_sk = keypair_3();
+        let data = b"signed data".to_vec();
+        let other_data = b"other data".to_vec();
+
+        let mut verifier = BatchVerifier::new();
+        for sk in [&ed_sk, &ed_sk_2, &secp_sk] {
+            let sig = common::SigScheme::sign(sk, &data);
+            verifier.queue(&sk.ref_to(), &data, &sig);
+        }
+        assert!(verifier.verify(&mut rng).is_ok());
+
+        // An invalid ed25519 signature fails the batch
+        let mut verifier = BatchVerifier::new();
+        let sig = common::SigScheme::sign(&ed_sk, &data);
+        verifier.queue(&ed_sk.ref_to(), &data, &sig);
+        let sig = common::SigScheme::sign(&ed_sk_2, &other_data);
+        verifier.queue(&ed_sk_2.ref_to(), &data, &sig);
+        assert!(verifier.verify(&mut rng).is_err());
+
+        // An invalid secp256k1 signature fails the batch
+        let mut verifier = BatchVerifier::new();
+        let sig = common::SigScheme::sign(&ed_sk, &data);
+        verifier.queue(&ed_sk.ref_to(), &data, &sig);
+        let sig = common::SigScheme::sign(&secp_sk, &other_data);
+        verifier.queue(&secp_sk.ref_to(), &data, &sig);
+        assert!(verifier.verify(&mut rng).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 //! Cryptographic keys
 
+#[cfg(any(test, feature = "rand"))]
+pub mod batch;
 pub mod common;
 pub mod ed25519;
 pub mod secp256k1;
@@ -8,6 +8,7 @@ use namada_migrations::*;
 
 use super::address::Address;
 use super::chain::ProposalBytes;
+use super::dec::Dec;
 use super::hash::Hash;
 use super::time::DurationSecs;
 use super::token;
@@ -56,6 +57,24 @@ pub struct Parameters {
     pub minimum_gas_price: BTreeMap<Address, token::Amount>,
     /// Enable the native token transfer if it is true
     pub is_native_token_transferable: bool,
+    /// Max gas that can be spent by all the accounts' epoch hooks at an
+    /// epoch transition
+    pub max_epoch_hooks_gas: u64,
+    /// Allowed epoch hook tx code hashes. An empty array disables epoch
+    /// hooks.
+    pub epoch_hook_allowlist: Vec<String>,
+    /// Target gas of a block for the adjustment of the dynamic base fee. A
+    /// zero target disables the adjustment.
+    pub target_block_gas: u64,
+    /// Max change rate of the base fee from one block to the next
+    pub base_fee_max_change_rate: Dec,
+    /// Number of blocks, from the one of an epoch change, for which the MASP
+    /// convert anchor of the previous epoch is still accepted
+    pub masp_convert_anchor_grace_blocks: u64,
+    /// Max age, in epochs, of the MASP notes that can still be converted to
+    /// the latest epoch. The conversions of older epochs are pruned from the
+    /// conversion tree. Zero means that the conversions are never pruned.
+    pub masp_max_note_age: u64,
 }
 
 /// Epoch duration. A new epoch begins as soon as both the `min_num_of_blocks`
@@ -85,6 +85,8 @@ pub const WASM_CODE_NAME_PREFIX: &str = "name";
 pub const WASM_CODE_LEN_PREFIX: &str = "len";
 /// The reserved storage key prefix for wasm code hashes
 pub const WASM_HASH_PREFIX: &str = "hash";
+/// The reserved storage key prefix for wasm codes' heights of addition
+pub const WASM_CODE_HEIGHT_PREFIX: &str = "height";
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
 /// Storage column families
@@ -101,6 +103,12 @@ pub enum DbColFam {
     ROLLBACK,
     /// Replay protection
     REPLAYPROT,
+    /// Index of the txs that changed the balances of addresses
+    TXHISTORY,
+    /// Results of the applied txs
+    TXRESULTS,
+    /// Index of the persisted diffs by key
+    KEYDIFFS,
 }
 
 /// Subspace column family name
@@ -115,6 +123,12 @@ pub const STATE_CF: &str = "state";
 pub const BLOCK_CF: &str = "block";
 /// Replay protection column family name
 pub const REPLAY_PROTECTION_CF: &str = "replay_protection";
+/// Index of the txs that changed the balances of addresses column family name
+pub const TX_HISTORY_CF: &str = "tx_history";
+/// Results of the applied txs column family name
+pub const TX_RESULTS_CF: &str = "tx_results";
+/// Index of the persisted diffs by key column family name
+pub const KEY_DIFFS_CF: &str = "key_diffs";
 
 impl DbColFam {
     /// Get the name of the column family
@@ -126,6 +140,9 @@ impl DbColFam {
             DbColFam::DIFFS => DIFFS_CF,
             DbColFam::ROLLBACK => ROLLBACK_CF,
             DbColFam::REPLAYPROT => REPLAY_PROTECTION_CF,
+            DbColFam::TXHISTORY => TX_HISTORY_CF,
+            DbColFam::TXRESULTS => TX_RESULTS_CF,
+            DbColFam::KEYDIFFS => KEY_DIFFS_CF,
         }
     }
 }
@@ -141,6 +158,9 @@ impl FromStr for DbColFam {
             STATE_CF => Ok(Self::STATE),
             REPLAY_PROTECTION_CF => Ok(Self::REPLAYPROT),
             BLOCK_CF => Ok(Self::BLOCK),
+            TX_HISTORY_CF => Ok(Self::TXHISTORY),
+            TX_RESULTS_CF => Ok(Self::TXRESULTS),
+            KEY_DIFFS_CF => Ok(Self::KEYDIFFS),
             _ => Err(Error::DbColFamily(s.to_string())),
         }
     }
@@ -756,6 +776,15 @@ impl Key {
         Key { segments }
     }
 
+    /// Returns a prefix of the keys of wasm codes' hashes by their names
+    pub fn wasm_code_name_prefix() -> Self {
+        let mut segments =
+            Self::from(PARAMETERS.to_owned().to_db_key()).segments;
+        segments.push(DbKeySeg::StringSeg(WASM_KEY_PREFIX.to_owned()));
+        segments.push(DbKeySeg::StringSeg(WASM_CODE_NAME_PREFIX.to_owned()));
+        Key { segments }
+    }
+
     /// Returns a key of the wasm code's length of the given hash
     pub fn wasm_code_len(code_hash: &Hash) -> Self {
         let mut segments =
@@ -766,6 +795,17 @@ impl Key {
         Key { segments }
     }
 
+    /// Returns a key of the block height at which the wasm code of the given
+    /// hash was added
+    pub fn wasm_code_height(code_hash: &Hash) -> Self {
+        let mut segments =
+            Self::from(PARAMETERS.to_owned().to_db_key()).segments;
+        segments.push(DbKeySeg::StringSeg(WASM_KEY_PREFIX.to_owned()));
+        segments.push(DbKeySeg::StringSeg(WASM_CODE_HEIGHT_PREFIX.to_owned()));
+        segments.push(DbKeySeg::StringSeg(code_hash.to_string()));
+        Key { segments }
+    }
+
     /// Returns a key of the wasm code hash of the given code path
     pub fn wasm_hash(code_path: impl AsRef<str>) -> Self {
         let mut segments =
@@ -536,6 +536,87 @@ impl DenominatedAmount {
         })
     }
 
+    /// Check that the denomination of the given amount matches this one
+    fn check_denom(
+        &self,
+        rhs: &DenominatedAmount,
+    ) -> Result<(), DenominatedAmountError> {
+        if self.denom == rhs.denom {
+            Ok(())
+        } else {
+            Err(DenominatedAmountError::DenominationMismatch(
+                self.denom.0,
+                rhs.denom.0,
+            ))
+        }
+    }
+
+    /// Addition of amounts of the same denomination. Unlike
+    /// [`Self::checked_add`], the amounts are not rescaled, so that
+    /// amounts of different tokens cannot be mixed silently.
+    pub fn try_add(
+        &self,
+        rhs: DenominatedAmount,
+    ) -> Result<Self, DenominatedAmountError> {
+        self.check_denom(&rhs)?;
+        let amount = self
+            .amount
+            .checked_add(rhs.amount)
+            .ok_or(DenominatedAmountError::Overflow)?;
+        Ok(Self {
+            amount,
+            denom: self.denom,
+        })
+    }
+
+    /// Subtraction of amounts of the same denomination. Unlike
+    /// [`Self::checked_sub`], the amounts are not rescaled.
+    pub fn try_sub(
+        &self,
+        rhs: DenominatedAmount,
+    ) -> Result<Self, DenominatedAmountError> {
+        self.check_denom(&rhs)?;
+        let amount = self
+            .amount
+            .checked_sub(rhs.amount)
+            .ok_or(DenominatedAmountError::Underflow)?;
+        Ok(Self {
+            amount,
+            denom: self.denom,
+        })
+    }
+
+    /// Multiplication by a scalar, keeping the denomination. Unlike
+    /// [`Self::checked_mul`], the denominations are not summed up.
+    pub fn try_mul(
+        &self,
+        rhs: impl Into<Amount>,
+    ) -> Result<Self, DenominatedAmountError> {
+        let amount = self
+            .amount
+            .checked_mul(rhs)
+            .ok_or(DenominatedAmountError::Overflow)?;
+        Ok(Self {
+            amount,
+            denom: self.denom,
+        })
+    }
+
+    /// Division by a scalar, rounded down, keeping the denomination.
+    pub fn try_div(
+        &self,
+        rhs: impl Into<Amount>,
+    ) -> Result<Self, DenominatedAmountError> {
+        let amount = self
+            .amount
+            .checked_div(rhs.into())
+            .ok_or(DenominatedAmountError::DivisionByZero)?;
+        Ok(Self {
+            amount,
+            denom: self.denom,
+        })
+    }
+
     /// Returns the significand of this number
     pub const fn amount(&self) -> Amount {
         self.amount
@@ -818,6 +899,19 @@ pub enum AmountParseError {
     PrecisionDecrease,
 }
 
+#[allow(missing_docs)]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DenominatedAmountError {
+    #[error("Mismatched denominations of the amounts: {0} and {1}")]
+    DenominationMismatch(u8, u8),
+    #[error("The denominated amount overflowed")]
+    Overflow,
+    #[error("The denominated amount underflowed")]
+    Underflow,
+    #[error("Division of a denominated amount by zero")]
+    DivisionByZero,
+}
+
 impl From<Amount> for Change {
     fn from(amount: Amount) -> Self {
         amount.raw.try_into().unwrap()
@@ -1373,4 +1467,33 @@ mod tests {
             Ordering::Less
         );
     }
+
+    #[test]
+    fn test_denominated_amt_arithmetic() {
+        let one = DenominatedAmount::new(Amount::from(10), 1.into());
+        let two = DenominatedAmount::new(Amount::from(20), 1.into());
+        assert_eq!(one.try_add(one), Ok(two));
+        assert_eq!(two.try_sub(one), Ok(one));
+        assert_eq!(one.try_mul(2), Ok(two));
+        assert_eq!(two.try_div(2), Ok(one));
+
+        // The same value in another denomination is not accepted
+        let other = DenominatedAmount::new(Amount::from(100), 2.into());
+        assert_eq!(other.cmp(&one), Ordering::Equal);
+        assert_eq!(
+            one.try_add(other),
+            Err(DenominatedAmountError::DenominationMismatch(1, 2))
+        );
+        assert_eq!(
+            one.try_sub(other),
+            Err(DenominatedAmountError::DenominationMismatch(1, 2))
+        );
+
+        assert_eq!(one.try_sub(two), Err(DenominatedAmountError::Underflow));
+        assert_eq!(
+            DenominatedAmount::new(Amount::max(), 1.into()).try_add(one),
+            Err(DenominatedAmountError::Overflow)
+        );
+        assert_eq!(one.try_div(0), Err(DenominatedAmountError::DivisionByZero));
+    }
 }
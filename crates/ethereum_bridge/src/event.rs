@@ -157,6 +157,7 @@ impl<'tx> EventAttributeEntry<'tx> for BridgePoolTxHash<'tx> {
     type ValueOwned = KeccakHash;
 
     const KEY: &'static str = "bridge_pool_tx_hash";
+    const VALUE_TYPE: &'static str = "keccak-hash";
 
     fn into_value(self) -> Self::Value {
         self.0
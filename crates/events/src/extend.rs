@@ -238,6 +238,10 @@ pub trait EventAttributeEntry<'a> {
     /// Key to read or write and event attribute to.
     const KEY: &'static str;
 
+    /// Stable name of the type of the data, listed in the event schemas. It
+    /// must not change when the Rust type is renamed or moved.
+    const VALUE_TYPE: &'static str;
+
     /// Data to be stored in the given `KEY`.
     type Value;
 
@@ -417,6 +421,7 @@ impl EventAttributeEntry<'static> for Height {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "height";
+    const VALUE_TYPE: &'static str = "block-height";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -431,6 +436,7 @@ impl EventAttributeEntry<'static> for TxHash {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "hash";
+    const VALUE_TYPE: &'static str = "hash";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -445,6 +451,7 @@ impl EventAttributeEntry<'static> for Log {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "log";
+    const VALUE_TYPE: &'static str = "string";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -459,6 +466,7 @@ impl EventAttributeEntry<'static> for Info {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "info";
+    const VALUE_TYPE: &'static str = "string";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -473,6 +481,7 @@ impl EventAttributeEntry<'static> for ValidMaspTx {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "is_valid_masp_tx";
+    const VALUE_TYPE: &'static str = "tx-index";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -487,6 +496,7 @@ impl EventAttributeEntry<'static> for Success {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "success";
+    const VALUE_TYPE: &'static str = "bool";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -551,6 +561,7 @@ where
     type ValueOwned = ParsedDomain<E>;
 
     const KEY: &'static str = "event-domain";
+    const VALUE_TYPE: &'static str = "event-domain";
 
     fn into_value(self) -> Self::Value {
         E::DOMAIN
@@ -1,6 +1,7 @@
 //! Events emitted by the Namada ledger.
 
 pub mod extend;
+pub mod schema;
 #[cfg(any(test, feature = "testing"))]
 pub mod testing;
 
@@ -316,6 +317,19 @@ pub enum EventError {
     /// Missing value in attributes.
     #[error("Attributes missing value: {0}")]
     MissingValue(String),
+    /// Unknown version of the schema of an event.
+    #[error(
+        "Expected version {expected} of the schema of event {event_type}, \
+         found {found}"
+    )]
+    SchemaVersion {
+        /// The type of the event.
+        event_type: EventType,
+        /// The version of the schema that the event was decoded with.
+        expected: u32,
+        /// The version of the schema of the event.
+        found: u32,
+    },
 }
 
 impl Event {
@@ -0,0 +1,361 @@
+//! Versioned schemas of the events.
+//!
+//! A typed event, declared with the [`typed_event`](crate::typed_event)
+//! macro, has a struct with a field per attribute and a schema version, which
+//! is emitted in the [`SchemaVersion`] attribute of the event. Its schema,
+//! i.e. the key and type of its attributes, can be listed in an
+//! [`EventSchemaRegistry`], so that indexers can check the attributes of the
+//! events they decode. Renaming or retyping an attribute of an event requires
+//! bumping its version.
+
+use std::collections::BTreeMap;
+
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::extend::EventAttributeEntry;
+use crate::{Event, EventError, EventLevel, EventToEmit, EventType};
+
+/// Declare a typed event, with a field per attribute. The type of each
+/// attribute must be an [`EventAttributeEntry`] whose owned value can be
+/// encoded with [`ToString`] and decoded with [`FromStr`](std::str::FromStr).
+///
+/// # Example
+///
+/// ```ignore
+/// typed_event! {
+///     /// Consensus key activation event.
+///     pub struct ConsensusKeyActivation {
+///         domain: PosEvent,
+///         event_type: types::CONSENSUS_KEY_ACTIVATED,
+///         version: 1,
+///         level: EventLevel::Block,
+///         attributes: {
+///             /// The address of the validator.
+///             validator: ConsensusKeyValidator,
+///         },
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! typed_event {
+    (
+        $(#[$attrs:meta])*
+        $vis:vis struct $name:ident {
+            domain: $domain:ty,
+            event_type: $event_type:expr,
+            version: $version:expr,
+            level: $level:expr,
+            attributes: {
+                $(
+                    $(#[$field_attrs:meta])*
+                    $field:ident: $entry:ty
+                ),* $(,)?
+            } $(,)?
+        }
+    ) => {
+        $(#[$attrs])*
+        #[derive(Clone, Debug, PartialEq)]
+        $vis struct $name {
+            $(
+                $(#[$field_attrs])*
+                pub $field: <
+                    $entry as $crate::extend::EventAttributeEntry<'static>
+                >::ValueOwned,
+            )*
+        }
+
+        impl $crate::EventToEmit for $name {
+            const DOMAIN: &'static str =
+                <$domain as $crate::EventToEmit>::DOMAIN;
+        }
+
+        impl $crate::schema::TypedEvent for $name {
+            const EVENT_TYPE: $crate::EventType = $event_type;
+            const LEVEL: $crate::EventLevel = $level;
+            const VERSION: u32 = $version;
+
+            fn attributes() -> ::std::vec::Vec<$crate::schema::AttributeSchema>
+            {
+                ::std::vec![
+                    $($crate::schema::AttributeSchema::of::<$entry>()),*
+                ]
+            }
+
+            fn into_attributes(
+                self,
+            ) -> ::std::vec::Vec<(&'static str, ::std::string::String)> {
+                ::std::vec![
+                    $((
+                        <
+                            $entry as $crate::extend::EventAttributeEntry<
+                                'static,
+                            >
+                        >::KEY,
+                        ::std::string::ToString::to_string(&self.$field),
+                    )),*
+                ]
+            }
+
+            fn from_attributes(
+                event: &$crate::Event,
+            ) -> ::std::result::Result<Self, $crate::EventError> {
+                ::std::result::Result::Ok(Self {
+                    $($field: event.read_attribute::<$entry>()?,)*
+                })
+            }
+        }
+
+        impl ::std::convert::From<$name> for $crate::Event {
+            fn from(event: $name) -> Self {
+                $crate::schema::TypedEvent::into_event(event)
+            }
+        }
+    };
+}
+
+/// The schema of an event attribute
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct AttributeSchema {
+    /// The key of the attribute
+    pub key: String,
+    /// The stable name of the type of the value of the attribute, which is
+    /// string encoded in the event
+    pub value_type: String,
+}
+
+impl AttributeSchema {
+    /// Get the schema of the given attribute
+    pub fn of<E>() -> Self
+    where
+        E: EventAttributeEntry<'static>,
+    {
+        Self {
+            key: E::KEY.to_string(),
+            value_type: E::VALUE_TYPE.to_string(),
+        }
+    }
+}
+
+/// The schema of a version of an event type
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct EventSchema {
+    /// The type of the event
+    pub event_type: EventType,
+    /// The version of the schema
+    pub version: u32,
+    /// The level of the event
+    pub level: EventLevel,
+    /// The attributes of the event
+    pub attributes: Vec<AttributeSchema>,
+}
+
+/// Extend an [`Event`] with the version of its schema.
+pub struct SchemaVersion(pub u32);
+
+impl EventAttributeEntry<'static> for SchemaVersion {
+    type Value = u32;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "schema-version";
+    const VALUE_TYPE: &'static str = "u32";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// An event with a versioned schema, declared with the
+/// [`typed_event`](crate::typed_event) macro.
+pub trait TypedEvent: EventToEmit + Sized {
+    /// The type of the event.
+    const EVENT_TYPE: EventType;
+
+    /// The level of the event.
+    const LEVEL: EventLevel;
+
+    /// The version of the schema of the event.
+    const VERSION: u32;
+
+    /// The schemas of the attributes of the event.
+    fn attributes() -> Vec<AttributeSchema>;
+
+    /// Encode the attributes of the event.
+    fn into_attributes(self) -> Vec<(&'static str, String)>;
+
+    /// Decode the attributes of the event.
+    fn from_attributes(event: &Event) -> Result<Self, EventError>;
+
+    /// The schema of the event.
+    fn schema() -> EventSchema {
+        EventSchema {
+            event_type: Self::EVENT_TYPE,
+            version: Self::VERSION,
+            level: Self::LEVEL,
+            attributes: Self::attributes(),
+        }
+    }
+
+    /// Encode the event, with the version of its schema.
+    fn into_event(self) -> Event {
+        let mut event = Event::new(Self::EVENT_TYPE, Self::LEVEL);
+        event.extend(SchemaVersion(Self::VERSION));
+        let attributes = event.attributes_mut();
+        for (key, value) in self.into_attributes() {
+            attributes.insert(key.to_string(), value);
+        }
+        event
+    }
+
+    /// Decode an event of this type, checking the version of its schema. The
+    /// events emitted without a schema version are of the first version.
+    fn decode(event: &Event) -> Result<Self, EventError> {
+        if event.kind() != &Self::EVENT_TYPE {
+            return Err(EventError::InvalidEventType);
+        }
+        let version = event.read_attribute_opt::<SchemaVersion>()?.unwrap_or(1);
+        if version != Self::VERSION {
+            return Err(EventError::SchemaVersion {
+                event_type: Self::EVENT_TYPE,
+                expected: Self::VERSION,
+                found: version,
+            });
+        }
+        Self::from_attributes(event)
+    }
+}
+
+/// A registry of the schemas of the typed events, by event type and version
+#[derive(Clone, Debug, Default)]
+pub struct EventSchemaRegistry {
+    schemas: BTreeMap<(EventType, u32), EventSchema>,
+}
+
+impl EventSchemaRegistry {
+    /// Register the schema of a typed event.
+    pub fn register<E: TypedEvent>(&mut self) -> &mut Self {
+        self.schemas
+            .insert((E::EVENT_TYPE, E::VERSION), E::schema());
+        self
+    }
+
+    /// Register the schema of a typed event.
+    pub fn with<E: TypedEvent>(mut self) -> Self {
+        self.register::<E>();
+        self
+    }
+
+    /// Get the schema of the given version of an event type.
+    pub fn get(
+        &self,
+        event_type: &EventType,
+        version: u32,
+    ) -> Option<&EventSchema> {
+        self.schemas.get(&(event_type.clone(), version))
+    }
+
+    /// Iterate over the registered schemas, ordered by event type and
+    /// version.
+    pub fn iter(&self) -> impl Iterator<Item = &EventSchema> {
+        self.schemas.values()
+    }
+
+    /// Get the registered schemas, ordered by event type and version.
+    pub fn into_schemas(self) -> Vec<EventSchema> {
+        self.schemas.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::storage::BlockHeight;
+
+    use super::*;
+    use crate::extend::{Height, Log};
+
+    crate::typed_event! {
+        /// A typed event for testing.
+        struct LogEvent {
+            domain: Event,
+            event_type: EventType::new("test/log"),
+            version: 2,
+            level: EventLevel::Block,
+            attributes: {
+                /// The height of the log.
+                height: Height,
+                /// The log.
+                log: Log,
+            },
+        }
+    }
+
+    #[test]
+    fn test_typed_event_roundtrip() {
+        let typed = LogEvent {
+            height: BlockHeight(42),
+            log: "hello".to_string(),
+        };
+        let event = Event::from(typed.clone());
+        assert_eq!(event.read_attribute::<SchemaVersion>().unwrap(), 2);
+        assert_eq!(event.read_attribute::<Log>().unwrap(), "hello");
+        assert_eq!(LogEvent::decode(&event).unwrap(), typed);
+
+        // an event of another version is rejected
+        let mut old_event = event.clone();
+        old_event.extend(SchemaVersion(1));
+        assert!(matches!(
+            LogEvent::decode(&old_event),
+            Err(EventError::SchemaVersion {
+                expected: 2,
+                found: 1,
+                ..
+            })
+        ));
+
+        // as is an event of another type
+        let other = Event::new(EventType::new("test/other"), EventLevel::Block);
+        assert!(matches!(
+            LogEvent::decode(&other),
+            Err(EventError::InvalidEventType)
+        ));
+    }
+
+    #[test]
+    fn test_event_schema_registry() {
+        let registry = EventSchemaRegistry::default().with::<LogEvent>();
+        let schema = registry.get(&LogEvent::EVENT_TYPE, 2).unwrap();
+        assert_eq!(schema.version, 2);
+        let keys: Vec<&str> = schema
+            .attributes
+            .iter()
+            .map(|attr| attr.key.as_str())
+            .collect();
+        assert_eq!(keys, vec!["height", "log"]);
+        let value_types: Vec<&str> = schema
+            .attributes
+            .iter()
+            .map(|attr| attr.value_type.as_str())
+            .collect();
+        assert_eq!(value_types, vec!["block-height", "string"]);
+        assert!(registry.get(&LogEvent::EVENT_TYPE, 1).is_none());
+        assert_eq!(registry.into_schemas().len(), 1);
+    }
+}
@@ -0,0 +1,124 @@
+/*
+ * C ABI bindings for the Namada SDK.
+ *
+ * Every fallible function returns a `NamadaError` code. On error, a message
+ * describing it can be retrieved with `namada_last_error_message`. The strings
+ * and the bytes returned through the output parameters are owned by the
+ * caller, who must release them with `namada_string_free` and
+ * `namada_bytes_free` respectively.
+ */
+
+#ifndef NAMADA_H
+#define NAMADA_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum NamadaError {
+  NAMADA_OK = 0,
+  NAMADA_NULL_POINTER = 1,
+  NAMADA_INVALID_UTF8 = 2,
+  NAMADA_INVALID_ARGUMENT = 3,
+  NAMADA_INVALID_TX = 4,
+  NAMADA_UNSUPPORTED = 5,
+  NAMADA_OVERFLOW = 6,
+  NAMADA_INTERNAL = 7,
+} NamadaError;
+
+typedef enum NamadaTxKind {
+  NAMADA_TX_TRANSFER = 0,
+  NAMADA_TX_BOND = 1,
+  NAMADA_TX_UNBOND = 2,
+  NAMADA_TX_WITHDRAW = 3,
+  NAMADA_TX_INIT_PROPOSAL = 4,
+  NAMADA_TX_VOTE_PROPOSAL = 5,
+  NAMADA_TX_REVEAL_PK = 6,
+} NamadaTxKind;
+
+typedef struct NamadaBytes {
+  uint8_t *data;
+  size_t len;
+} NamadaBytes;
+
+/* Memory and errors */
+
+char *namada_last_error_message(void);
+
+void namada_string_free(char *string);
+
+void namada_bytes_free(NamadaBytes bytes);
+
+/* Keys and addresses */
+
+NamadaError namada_address_parse(const char *address, char **out);
+
+NamadaError namada_public_key(const char *secret_key, char **out);
+
+NamadaError namada_implicit_address(const char *public_key, char **out);
+
+/* Transactions */
+
+NamadaError namada_build_tx(NamadaTxKind kind,
+                            const char *chain_id,
+                            const char *code_hash,
+                            const char *expiration,
+                            const char *data_encoding,
+                            const char *data,
+                            NamadaBytes *out);
+
+NamadaError namada_attach_fee(const uint8_t *tx,
+                              size_t tx_len,
+                              const char *fee_per_gas_unit,
+                              const char *token,
+                              const char *fee_payer,
+                              uint64_t gas_limit,
+                              NamadaBytes *out);
+
+NamadaError namada_estimate_fee(const char *fee_per_gas_unit,
+                                uint64_t gas_limit,
+                                char **out);
+
+NamadaError namada_tx_sign_bytes(const uint8_t *tx, size_t tx_len, char **out);
+
+NamadaError namada_wrapper_sign_bytes(const uint8_t *tx,
+                                      size_t tx_len,
+                                      char **out);
+
+NamadaError namada_attach_tx_signature(const uint8_t *tx,
+                                       size_t tx_len,
+                                       const char *public_key,
+                                       const char *signature,
+                                       NamadaBytes *out);
+
+NamadaError namada_attach_wrapper_signature(const uint8_t *tx,
+                                            size_t tx_len,
+                                            const char *public_key,
+                                            const char *signature,
+                                            NamadaBytes *out);
+
+NamadaError namada_sign_tx(const uint8_t *tx,
+                           size_t tx_len,
+                           const char *secret_key,
+                           NamadaBytes *out);
+
+NamadaError namada_sign_wrapper(const uint8_t *tx,
+                                size_t tx_len,
+                                const char *secret_key,
+                                NamadaBytes *out);
+
+/* MASP */
+
+NamadaError namada_masp_payment_address(const char *viewing_key,
+                                        uint64_t diversifier_index,
+                                        char **out,
+                                        uint64_t *out_index);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* NAMADA_H */
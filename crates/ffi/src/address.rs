@@ -0,0 +1,120 @@
+//! Bindings for the key and address types
+
+use std::os::raw::c_char;
+use std::str::FromStr;
+
+use namada_sdk::address::Address;
+use namada_sdk::key::{common, RefTo};
+
+use crate::error::{run, Error, NamadaError};
+use crate::types::{read_str, write_string};
+
+/// Parse an address and write its canonical encoding to `out`
+///
+/// # Safety
+///
+/// `address` must be a NUL-terminated string and `out` must be valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_address_parse(
+    address: *const c_char,
+    out: *mut *mut c_char,
+) -> NamadaError {
+    run(|| {
+        let address = read_str("address", address)?;
+        let address = Address::decode(address)
+            .map_err(|err| Error::invalid_argument("address", err))?;
+        write_string(out, address.encode())
+    })
+}
+
+/// Write the public key of a secret key to `out`
+///
+/// # Safety
+///
+/// `secret_key` must be a NUL-terminated string and `out` must be valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_public_key(
+    secret_key: *const c_char,
+    out: *mut *mut c_char,
+) -> NamadaError {
+    run(|| {
+        let secret_key = read_str("secret key", secret_key)?;
+        let secret_key = common::SecretKey::from_str(secret_key)
+            .map_err(|err| Error::invalid_argument("secret key", err))?;
+        write_string(out, secret_key.ref_to().to_string())
+    })
+}
+
+/// Write the implicit address of a public key to `out`
+///
+/// # Safety
+///
+/// `public_key` must be a NUL-terminated string and `out` must be valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_implicit_address(
+    public_key: *const c_char,
+    out: *mut *mut c_char,
+) -> NamadaError {
+    run(|| {
+        let public_key = read_str("public key", public_key)?;
+        let public_key = common::PublicKey::from_str(public_key)
+            .map_err(|err| Error::invalid_argument("public key", err))?;
+        write_string(out, Address::from(&public_key).encode())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::{CStr, CString};
+
+    use namada_sdk::address::testing::established_address_1;
+    use namada_sdk::key::testing::keypair_1;
+
+    use super::*;
+    use crate::types::namada_string_free;
+
+    #[test]
+    fn test_address_bindings() {
+        let address = established_address_1();
+        let input = CString::new(address.encode()).unwrap();
+        let mut out = std::ptr::null_mut();
+        unsafe {
+            assert_eq!(
+                namada_address_parse(input.as_ptr(), &mut out),
+                NamadaError::Ok
+            );
+            assert_eq!(CStr::from_ptr(out).to_str().unwrap(), address.encode());
+            namada_string_free(out);
+
+            let input = CString::new("invalid").unwrap();
+            assert_eq!(
+                namada_address_parse(input.as_ptr(), &mut out),
+                NamadaError::InvalidArgument
+            );
+            assert_eq!(
+                namada_address_parse(std::ptr::null(), &mut out),
+                NamadaError::NullPointer
+            );
+
+            let secret_key = CString::new(keypair_1().to_string()).unwrap();
+            let mut public_key = std::ptr::null_mut();
+            assert_eq!(
+                namada_public_key(secret_key.as_ptr(), &mut public_key),
+                NamadaError::Ok
+            );
+            assert_eq!(
+                namada_implicit_address(public_key, &mut out),
+                NamadaError::Ok
+            );
+            assert_eq!(
+                CStr::from_ptr(out).to_str().unwrap(),
+                Address::from(&keypair_1().ref_to()).encode()
+            );
+            namada_string_free(public_key);
+            namada_string_free(out);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Error codes and messages
+
+use std::cell::RefCell;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// The error codes returned by the exported functions. The values of the
+/// codes are stable.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamadaError {
+    /// No error
+    Ok = 0,
+    /// A required pointer argument is null
+    NullPointer = 1,
+    /// A string argument is not valid UTF-8
+    InvalidUtf8 = 2,
+    /// An argument cannot be parsed
+    InvalidArgument = 3,
+    /// The transaction bytes cannot be decoded
+    InvalidTx = 4,
+    /// The operation is not supported
+    Unsupported = 5,
+    /// An arithmetic overflow occurred
+    Overflow = 6,
+    /// An unexpected internal error occurred
+    Internal = 7,
+}
+
+/// An error with its code and message
+#[derive(Debug)]
+pub(crate) struct Error {
+    pub code: NamadaError,
+    pub message: String,
+}
+
+impl Error {
+    /// Make a new error with the given code and message
+    pub fn new(code: NamadaError, message: impl ToString) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+        }
+    }
+
+    /// Make a new invalid argument error
+    pub fn invalid_argument(name: &str, err: impl ToString) -> Self {
+        Self::new(
+            NamadaError::InvalidArgument,
+            format!("Invalid {name}: {}", err.to_string()),
+        )
+    }
+}
+
+/// The result of an exported function
+pub(crate) type Result<T> = std::result::Result<T, Error>;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Run the body of an exported function, converting its result and any panic
+/// into an error code and recording the error message
+pub(crate) fn run(body: impl FnOnce() -> Result<()>) -> NamadaError {
+    let result = catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|_| {
+        Err(Error::new(NamadaError::Internal, "Unexpected panic"))
+    });
+    match result {
+        Ok(()) => {
+            LAST_ERROR.with(|last| last.borrow_mut().take());
+            NamadaError::Ok
+        }
+        Err(Error { code, message }) => {
+            LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+            code
+        }
+    }
+}
+
+/// Get the message of the last error that occurred in the calling thread, or
+/// null if the last call succeeded. The returned string must be released with
+/// `namada_string_free`.
+#[no_mangle]
+pub extern "C" fn namada_last_error_message() -> *mut c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .and_then(|message| CString::new(message.as_str()).ok())
+            .map_or(std::ptr::null_mut(), CString::into_raw)
+    })
+}
@@ -0,0 +1,29 @@
+//! C ABI bindings for the Namada SDK, aimed at mobile wallet integrations,
+//! e.g. from Swift or Kotlin. The declarations of the exported functions and
+//! structs are in `include/namada.h`.
+//!
+//! # Conventions
+//!
+//! - Every fallible function returns a [`NamadaError`] code. On error, a
+//!   message describing it can be retrieved with [`namada_last_error_message`].
+//! - Strings are passed as NUL-terminated UTF-8 strings. Keys, addresses,
+//!   hashes and amounts use their usual string encodings.
+//! - Transactions are passed as the bytes that are broadcast to a node.
+//! - The strings and the bytes returned through the output parameters are owned
+//!   by the caller, who must release them with [`namada_string_free`] and
+//!   [`namada_bytes_free`] respectively.
+//!
+//! # Structure
+//!
+//! - [`address`]: parse keys and addresses
+//! - [`tx`]: build and sign transactions
+//! - [`masp`]: derive MASP payment addresses
+
+pub mod address;
+mod error;
+pub mod masp;
+pub mod tx;
+mod types;
+
+pub use error::{namada_last_error_message, NamadaError};
+pub use types::{namada_bytes_free, namada_string_free, NamadaBytes};
@@ -0,0 +1,49 @@
+//! Bindings for the MASP key types
+
+use std::os::raw::c_char;
+use std::str::FromStr;
+
+use namada_sdk::masp::{find_diversified_address, ExtendedViewingKey};
+
+use crate::error::{run, Error, NamadaError};
+use crate::types::{read_str, write_string};
+
+/// Derive the payment address of a viewing key with the first valid
+/// diversifier index starting from `diversifier_index`. The payment address
+/// is written to `out` and the index that was used to `out_index`, so that
+/// the next address can be derived from the following index.
+///
+/// # Safety
+///
+/// `viewing_key` must be a NUL-terminated string and `out` and `out_index`
+/// must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_masp_payment_address(
+    viewing_key: *const c_char,
+    diversifier_index: u64,
+    out: *mut *mut c_char,
+    out_index: *mut u64,
+) -> NamadaError {
+    run(|| {
+        if out_index.is_null() {
+            return Err(Error::new(
+                NamadaError::NullPointer,
+                "The output index pointer is null",
+            ));
+        }
+        let viewing_key =
+            ExtendedViewingKey::from_str(read_str("viewing key", viewing_key)?)
+                .map_err(|err| Error::invalid_argument("viewing key", err))?;
+        let (index, payment_address) =
+            find_diversified_address(&viewing_key, diversifier_index)
+                .ok_or_else(|| {
+                    Error::new(
+                        NamadaError::Overflow,
+                        "No valid diversifier index fits in a u64",
+                    )
+                })?;
+        write_string(out, payment_address.to_string())?;
+        *out_index = index;
+        Ok(())
+    })
+}
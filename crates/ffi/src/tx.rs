@@ -0,0 +1,507 @@
+//! Bindings for the construction and the signing of transactions
+
+use std::collections::BTreeMap;
+use std::os::raw::c_char;
+use std::str::FromStr;
+
+use namada_sdk::account::AccountPublicKeysMap;
+use namada_sdk::address::Address;
+use namada_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use namada_sdk::chain::ChainId;
+use namada_sdk::governance::storage::proposal::{
+    InitProposalData, VoteProposalData,
+};
+use namada_sdk::hash::Hash;
+use namada_sdk::key::common;
+use namada_sdk::time::DateTimeUtc;
+use namada_sdk::token::{self, Amount, DenominatedAmount};
+use namada_sdk::tx::data::{pos, Fee, GasLimit};
+use namada_sdk::tx::{
+    Authorization, DataEncoding, Section, Signer, Tx, TX_BOND_WASM,
+    TX_INIT_PROPOSAL, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
+    TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::{run, Error, NamadaError, Result};
+use crate::types::{
+    read_bytes, read_opt_str, read_str, write_bytes, write_string, NamadaBytes,
+};
+
+/// The kinds of transactions that can be built. The values of the kinds are
+/// stable.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamadaTxKind {
+    /// A transparent transfer, with `token::Transfer` data
+    Transfer = 0,
+    /// A bond, with `pos::Bond` data
+    Bond = 1,
+    /// An unbond, with `pos::Unbond` data
+    Unbond = 2,
+    /// A withdrawal, with `pos::Withdraw` data
+    Withdraw = 3,
+    /// A governance proposal, with `InitProposalData` data
+    InitProposal = 4,
+    /// A governance vote, with `VoteProposalData` data
+    VoteProposal = 5,
+    /// A public key revelation, with `common::PublicKey` data
+    RevealPk = 6,
+}
+
+impl NamadaTxKind {
+    /// The tag of the code of the transaction
+    fn code_tag(&self) -> &'static str {
+        match self {
+            Self::Transfer => TX_TRANSFER_WASM,
+            Self::Bond => TX_BOND_WASM,
+            Self::Unbond => TX_UNBOND_WASM,
+            Self::Withdraw => TX_WITHDRAW_WASM,
+            Self::InitProposal => TX_INIT_PROPOSAL,
+            Self::VoteProposal => TX_VOTE_PROPOSAL,
+            Self::RevealPk => TX_REVEAL_PK,
+        }
+    }
+}
+
+/// Parse the JSON representation of the tx data and add it to the tx in the
+/// given encoding
+fn add_data<T>(tx: &mut Tx, data: &str, encoding: DataEncoding) -> Result<()>
+where
+    T: BorshSerialize + BorshDeserialize + Serialize + DeserializeOwned,
+{
+    let data: T = serde_json::from_str(data)
+        .map_err(|err| Error::invalid_argument("tx data", err))?;
+    tx.add_encoded_data(data, encoding)
+        .map_err(|err| Error::invalid_argument("tx data", err))?;
+    Ok(())
+}
+
+fn decode_tx(tx: &[u8]) -> Result<Tx> {
+    Tx::try_from(tx).map_err(|err| Error::new(NamadaError::InvalidTx, err))
+}
+
+fn parse_secret_key(secret_key: &str) -> Result<common::SecretKey> {
+    common::SecretKey::from_str(secret_key)
+        .map_err(|err| Error::invalid_argument("secret key", err))
+}
+
+/// Build a transaction of the given kind from the serde JSON representation
+/// of its data and write it to `out`. The expiration is an optional RFC 3339
+/// date time and the data encoding is either `borsh`, the default, or
+/// `json-v1`, which is only supported by the transfer, bond, unbond, withdraw
+/// and proposal transactions.
+///
+/// # Safety
+///
+/// The string arguments must be NUL-terminated strings, the optional ones
+/// may be null, and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_build_tx(
+    kind: NamadaTxKind,
+    chain_id: *const c_char,
+    code_hash: *const c_char,
+    expiration: *const c_char,
+    data_encoding: *const c_char,
+    data: *const c_char,
+    out: *mut NamadaBytes,
+) -> NamadaError {
+    run(|| {
+        let chain_id = ChainId::from_str(read_str("chain ID", chain_id)?)
+            .map_err(|err| Error::invalid_argument("chain ID", err))?;
+        let code_hash = Hash::from_str(read_str("code hash", code_hash)?)
+            .map_err(|err| Error::invalid_argument("code hash", err))?;
+        let expiration = read_opt_str("expiration", expiration)?
+            .map(DateTimeUtc::from_str)
+            .transpose()
+            .map_err(|err| Error::invalid_argument("expiration", err))?;
+        let encoding = read_opt_str("data encoding", data_encoding)?
+            .map(DataEncoding::from_str)
+            .transpose()
+            .map_err(|err| Error::invalid_argument("data encoding", err))?
+            .unwrap_or_default();
+        let data = read_str("tx data", data)?;
+
+        let mut tx = Tx::new(chain_id, expiration);
+        tx.add_code_from_hash(code_hash, Some(kind.code_tag().to_string()));
+        match kind {
+            NamadaTxKind::Transfer => {
+                add_data::<token::Transfer>(&mut tx, data, encoding)?
+            }
+            NamadaTxKind::Bond => {
+                add_data::<pos::Bond>(&mut tx, data, encoding)?
+            }
+            NamadaTxKind::Unbond => {
+                add_data::<pos::Unbond>(&mut tx, data, encoding)?
+            }
+            NamadaTxKind::Withdraw => {
+                add_data::<pos::Withdraw>(&mut tx, data, encoding)?
+            }
+            NamadaTxKind::InitProposal => {
+                add_data::<InitProposalData>(&mut tx, data, encoding)?
+            }
+            NamadaTxKind::VoteProposal | NamadaTxKind::RevealPk
+                if encoding != DataEncoding::Borsh =>
+            {
+                return Err(Error::new(
+                    NamadaError::Unsupported,
+                    format!(
+                        "The {kind:?} transaction doesn't support the \
+                         {encoding} data encoding"
+                    ),
+                ));
+            }
+            NamadaTxKind::VoteProposal => {
+                add_data::<VoteProposalData>(&mut tx, data, encoding)?
+            }
+            NamadaTxKind::RevealPk => {
+                add_data::<common::PublicKey>(&mut tx, data, encoding)?
+            }
+        }
+        write_bytes(out, tx.to_bytes())
+    })
+}
+
+/// Attach the fee data to the tx and write it to `out`
+///
+/// # Safety
+///
+/// `tx` must point to `tx_len` bytes, the string arguments must be
+/// NUL-terminated strings and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_attach_fee(
+    tx: *const u8,
+    tx_len: usize,
+    fee_per_gas_unit: *const c_char,
+    token: *const c_char,
+    fee_payer: *const c_char,
+    gas_limit: u64,
+    out: *mut NamadaBytes,
+) -> NamadaError {
+    run(|| {
+        let mut tx = decode_tx(read_bytes("tx", tx, tx_len)?)?;
+        let amount_per_gas_unit = DenominatedAmount::from_str(read_str(
+            "fee per gas unit",
+            fee_per_gas_unit,
+        )?)
+        .map_err(|err| Error::invalid_argument("fee per gas unit", err))?;
+        let token = Address::decode(read_str("fee token", token)?)
+            .map_err(|err| Error::invalid_argument("fee token", err))?;
+        let fee_payer =
+            common::PublicKey::from_str(read_str("fee payer", fee_payer)?)
+                .map_err(|err| Error::invalid_argument("fee payer", err))?;
+        tx.add_wrapper(
+            Fee {
+                amount_per_gas_unit,
+                token,
+            },
+            fee_payer,
+            GasLimit::from(gas_limit),
+            None,
+        );
+        write_bytes(out, tx.to_bytes())
+    })
+}
+
+/// Write the fee paid for the given gas limit to `out`
+///
+/// # Safety
+///
+/// `fee_per_gas_unit` must be a NUL-terminated string and `out` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_estimate_fee(
+    fee_per_gas_unit: *const c_char,
+    gas_limit: u64,
+    out: *mut *mut c_char,
+) -> NamadaError {
+    run(|| {
+        let fee_per_gas_unit = DenominatedAmount::from_str(read_str(
+            "fee per gas unit",
+            fee_per_gas_unit,
+        )?)
+        .map_err(|err| Error::invalid_argument("fee per gas unit", err))?;
+        let fee = fee_per_gas_unit
+            .checked_mul(Amount::from(GasLimit::from(gas_limit)).into())
+            .ok_or_else(|| {
+                Error::new(NamadaError::Overflow, "The fee overflows")
+            })?;
+        write_string(out, fee.to_string())
+    })
+}
+
+/// Get the hash that commits to the given targets and that is signed by an
+/// authorization
+fn sign_bytes(targets: Vec<Hash>) -> Hash {
+    Authorization {
+        targets,
+        signer: Signer::PubKeys(vec![]),
+        signatures: BTreeMap::new(),
+    }
+    .get_raw_hash()
+}
+
+/// Write the hash to sign to authorize the inner tx to `out`
+///
+/// # Safety
+///
+/// `tx` must point to `tx_len` bytes and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_sign_bytes(
+    tx: *const u8,
+    tx_len: usize,
+    out: *mut *mut c_char,
+) -> NamadaError {
+    run(|| {
+        let tx = decode_tx(read_bytes("tx", tx, tx_len)?)?;
+        write_string(out, sign_bytes(vec![tx.raw_header_hash()]).to_string())
+    })
+}
+
+/// Write the hash to sign to authorize the fee payment to `out`. The fee
+/// must be attached and the inner tx signed before.
+///
+/// # Safety
+///
+/// `tx` must point to `tx_len` bytes and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_wrapper_sign_bytes(
+    tx: *const u8,
+    tx_len: usize,
+    out: *mut *mut c_char,
+) -> NamadaError {
+    run(|| {
+        let tx = decode_tx(read_bytes("tx", tx, tx_len)?)?;
+        write_string(out, sign_bytes(tx.sechashes()).to_string())
+    })
+}
+
+/// Attach a signature of the given targets to the tx
+unsafe fn attach_signature(
+    tx: *const u8,
+    tx_len: usize,
+    wrapper: bool,
+    public_key: *const c_char,
+    signature: *const c_char,
+    out: *mut NamadaBytes,
+) -> Result<()> {
+    let mut tx = decode_tx(read_bytes("tx", tx, tx_len)?)?;
+    let public_key =
+        common::PublicKey::from_str(read_str("public key", public_key)?)
+            .map_err(|err| Error::invalid_argument("public key", err))?;
+    let signature =
+        common::Signature::from_str(read_str("signature", signature)?)
+            .map_err(|err| Error::invalid_argument("signature", err))?;
+    let targets = if wrapper {
+        tx.sechashes()
+    } else {
+        vec![tx.raw_header_hash()]
+    };
+    tx.protocol_filter();
+    tx.add_section(Section::Authorization(Authorization {
+        targets,
+        signer: Signer::PubKeys(vec![public_key]),
+        signatures: [(0, signature)].into_iter().collect(),
+    }));
+    write_bytes(out, tx.to_bytes())
+}
+
+/// Attach a signature of the inner tx sign bytes to the tx and write it to
+/// `out`
+///
+/// # Safety
+///
+/// `tx` must point to `tx_len` bytes, the string arguments must be
+/// NUL-terminated strings and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_attach_tx_signature(
+    tx: *const u8,
+    tx_len: usize,
+    public_key: *const c_char,
+    signature: *const c_char,
+    out: *mut NamadaBytes,
+) -> NamadaError {
+    run(|| attach_signature(tx, tx_len, false, public_key, signature, out))
+}
+
+/// Attach a signature of the wrapper sign bytes to the tx and write it to
+/// `out`
+///
+/// # Safety
+///
+/// `tx` must point to `tx_len` bytes, the string arguments must be
+/// NUL-terminated strings and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_attach_wrapper_signature(
+    tx: *const u8,
+    tx_len: usize,
+    public_key: *const c_char,
+    signature: *const c_char,
+    out: *mut NamadaBytes,
+) -> NamadaError {
+    run(|| attach_signature(tx, tx_len, true, public_key, signature, out))
+}
+
+/// Sign the inner tx with the given secret key and write it to `out`
+///
+/// # Safety
+///
+/// `tx` must point to `tx_len` bytes, `secret_key` must be a NUL-terminated
+/// string and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_sign_tx(
+    tx: *const u8,
+    tx_len: usize,
+    secret_key: *const c_char,
+    out: *mut NamadaBytes,
+) -> NamadaError {
+    run(|| {
+        let mut tx = decode_tx(read_bytes("tx", tx, tx_len)?)?;
+        let secret_key = parse_secret_key(read_str("secret key", secret_key)?)?;
+        tx.sign_raw(vec![secret_key], AccountPublicKeysMap::default(), None);
+        write_bytes(out, tx.to_bytes())
+    })
+}
+
+/// Sign the fee payment with the given secret key and write the tx to `out`.
+/// The fee must be attached and the inner tx signed before.
+///
+/// # Safety
+///
+/// `tx` must point to `tx_len` bytes, `secret_key` must be a NUL-terminated
+/// string and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_sign_wrapper(
+    tx: *const u8,
+    tx_len: usize,
+    secret_key: *const c_char,
+    out: *mut NamadaBytes,
+) -> NamadaError {
+    run(|| {
+        let mut tx = decode_tx(read_bytes("tx", tx, tx_len)?)?;
+        let secret_key = parse_secret_key(read_str("secret key", secret_key)?)?;
+        tx.sign_wrapper(secret_key);
+        write_bytes(out, tx.to_bytes())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CString;
+
+    use namada_sdk::address::testing::{established_address_1, nam};
+    use namada_sdk::key::testing::keypair_1;
+    use namada_sdk::key::RefTo;
+
+    use super::*;
+    use crate::types::namada_bytes_free;
+
+    fn into_vec(bytes: &NamadaBytes) -> Vec<u8> {
+        unsafe { std::slice::from_raw_parts(bytes.data, bytes.len).to_vec() }
+    }
+
+    #[test]
+    fn test_build_and_sign_tx() {
+        let bond = pos::Bond {
+            validator: established_address_1(),
+            amount: Amount::native_whole(1),
+            source: None,
+        };
+        let chain_id = CString::new(ChainId::default().to_string()).unwrap();
+        let code_hash = CString::new(Hash::default().to_string()).unwrap();
+        let data = CString::new(serde_json::to_string(&bond).unwrap()).unwrap();
+        let encoding = CString::new("json-v1").unwrap();
+        let secret_key = CString::new(keypair_1().to_string()).unwrap();
+        let public_key =
+            CString::new(keypair_1().ref_to().to_string()).unwrap();
+        let fee = CString::new("0.01").unwrap();
+        let token = CString::new(nam().encode()).unwrap();
+        let mut out = NamadaBytes {
+            data: std::ptr::null_mut(),
+            len: 0,
+        };
+
+        unsafe {
+            assert_eq!(
+                namada_build_tx(
+                    NamadaTxKind::Bond,
+                    chain_id.as_ptr(),
+                    code_hash.as_ptr(),
+                    std::ptr::null(),
+                    encoding.as_ptr(),
+                    data.as_ptr(),
+                    &mut out,
+                ),
+                NamadaError::Ok
+            );
+            let tx = into_vec(&out);
+            namada_bytes_free(out);
+            let decoded = decode_tx(&tx).unwrap().decode_data::<pos::Bond>();
+            assert_eq!(decoded.unwrap().unwrap(), bond);
+
+            assert_eq!(
+                namada_sign_tx(
+                    tx.as_ptr(),
+                    tx.len(),
+                    secret_key.as_ptr(),
+                    &mut out
+                ),
+                NamadaError::Ok
+            );
+            let tx = into_vec(&out);
+            namada_bytes_free(out);
+
+            assert_eq!(
+                namada_attach_fee(
+                    tx.as_ptr(),
+                    tx.len(),
+                    fee.as_ptr(),
+                    token.as_ptr(),
+                    public_key.as_ptr(),
+                    10_000,
+                    &mut out,
+                ),
+                NamadaError::Ok
+            );
+            let tx = into_vec(&out);
+            namada_bytes_free(out);
+
+            assert_eq!(
+                namada_sign_wrapper(
+                    tx.as_ptr(),
+                    tx.len(),
+                    secret_key.as_ptr(),
+                    &mut out
+                ),
+                NamadaError::Ok
+            );
+            let tx = decode_tx(&into_vec(&out)).unwrap();
+            namada_bytes_free(out);
+            assert!(tx.header().wrapper().is_some());
+
+            // The vote and reveal PK txs only support the Borsh encoding
+            assert_eq!(
+                namada_build_tx(
+                    NamadaTxKind::RevealPk,
+                    chain_id.as_ptr(),
+                    code_hash.as_ptr(),
+                    std::ptr::null(),
+                    encoding.as_ptr(),
+                    public_key.as_ptr(),
+                    &mut out,
+                ),
+                NamadaError::Unsupported
+            );
+            assert_eq!(
+                namada_sign_tx(
+                    [0_u8; 4].as_ptr(),
+                    4,
+                    secret_key.as_ptr(),
+                    &mut out
+                ),
+                NamadaError::InvalidTx
+            );
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Conversions of the arguments and the outputs of the exported functions
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+use crate::error::{Error, NamadaError, Result};
+
+/// A byte buffer owned by the caller
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct NamadaBytes {
+    /// The pointer to the bytes
+    pub data: *mut u8,
+    /// The number of bytes
+    pub len: usize,
+}
+
+impl From<Vec<u8>> for NamadaBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        let bytes = bytes.into_boxed_slice();
+        let len = bytes.len();
+        let data = Box::into_raw(bytes) as *mut u8;
+        Self { data, len }
+    }
+}
+
+/// Release a byte buffer returned by this library
+///
+/// # Safety
+///
+/// The buffer must have been returned by this library and not released
+/// before.
+#[no_mangle]
+pub unsafe extern "C" fn namada_bytes_free(bytes: NamadaBytes) {
+    if !bytes.data.is_null() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            bytes.data, bytes.len,
+        )));
+    }
+}
+
+/// Release a string returned by this library
+///
+/// # Safety
+///
+/// The string must have been returned by this library and not released
+/// before.
+#[no_mangle]
+pub unsafe extern "C" fn namada_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Read a required string argument
+///
+/// # Safety
+///
+/// The pointer must be null or point to a NUL-terminated string that is valid
+/// for the given lifetime.
+pub(crate) unsafe fn read_str<'a>(
+    name: &str,
+    string: *const c_char,
+) -> Result<&'a str> {
+    read_opt_str(name, string)?.ok_or_else(|| {
+        Error::new(NamadaError::NullPointer, format!("The {name} is null"))
+    })
+}
+
+/// Read an optional string argument, which is null when absent
+///
+/// # Safety
+///
+/// The pointer must be null or point to a NUL-terminated string that is valid
+/// for the given lifetime.
+pub(crate) unsafe fn read_opt_str<'a>(
+    name: &str,
+    string: *const c_char,
+) -> Result<Option<&'a str>> {
+    if string.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(string).to_str().map(Some).map_err(|err| {
+        Error::new(
+            NamadaError::InvalidUtf8,
+            format!("The {name} is not valid UTF-8: {err}"),
+        )
+    })
+}
+
+/// Read a bytes argument
+///
+/// # Safety
+///
+/// The pointer must be null or point to `len` bytes that are valid for the
+/// given lifetime.
+pub(crate) unsafe fn read_bytes<'a>(
+    name: &str,
+    data: *const u8,
+    len: usize,
+) -> Result<&'a [u8]> {
+    if data.is_null() {
+        return Err(Error::new(
+            NamadaError::NullPointer,
+            format!("The {name} is null"),
+        ));
+    }
+    Ok(std::slice::from_raw_parts(data, len))
+}
+
+/// Write a string to an output parameter
+///
+/// # Safety
+///
+/// The pointer must be null or valid for writes.
+pub(crate) unsafe fn write_string(
+    out: *mut *mut c_char,
+    string: String,
+) -> Result<()> {
+    if out.is_null() {
+        return Err(Error::new(
+            NamadaError::NullPointer,
+            "The output pointer is null",
+        ));
+    }
+    let string = CString::new(string)
+        .map_err(|err| Error::new(NamadaError::Internal, err))?;
+    *out = string.into_raw();
+    Ok(())
+}
+
+/// Write bytes to an output parameter
+///
+/// # Safety
+///
+/// The pointer must be null or valid for writes.
+pub(crate) unsafe fn write_bytes(
+    out: *mut NamadaBytes,
+    bytes: Vec<u8>,
+) -> Result<()> {
+    if out.is_null() {
+        return Err(Error::new(
+            NamadaError::NullPointer,
+            "The output pointer is null",
+        ));
+    }
+    *out = NamadaBytes::from(bytes);
+    Ok(())
+}
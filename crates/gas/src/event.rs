@@ -2,7 +2,7 @@
 
 use namada_events::extend::EventAttributeEntry;
 
-use super::Gas;
+use super::{Gas, GasBreakdown};
 
 /// Extend an [`Event`] with gas used data.
 pub struct GasUsed(pub Gas);
@@ -12,6 +12,23 @@ impl EventAttributeEntry<'static> for GasUsed {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "gas_used";
+    const VALUE_TYPE: &'static str = "gas";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the breakdown of the gas used by a tx, by
+/// section of its execution.
+pub struct GasReport(pub GasBreakdown);
+
+impl EventAttributeEntry<'static> for GasReport {
+    type Value = GasBreakdown;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "gas_report";
+    const VALUE_TYPE: &'static str = "gas-breakdown";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -4,11 +4,13 @@
 pub mod event;
 pub mod storage;
 
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::num::ParseIntError;
 use std::ops::Div;
 use std::str::FromStr;
 
+use namada_core::address::Address;
 use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use namada_core::hints;
 use namada_macros::BorshDeserializer;
@@ -62,6 +64,9 @@ pub const VERIFY_TX_SIG_GAS: u64 = 594_290;
 /// The cost for requesting one more page in wasm (64KiB)
 pub const WASM_MEMORY_PAGE_GAS: u32 =
     MEMORY_ACCESS_GAS_PER_BYTE as u32 * 64 * 1_024;
+/// The fixed cost of a PoS query from the tx or VP environment, on top of the
+/// storage reads it performs
+pub const POS_QUERY_GAS: u64 = 10_000;
 /// The cost to validate an Ibc action
 pub const IBC_ACTION_VALIDATE_GAS: u64 = 1_472_023;
 /// The cost to execute an Ibc action
@@ -118,7 +123,7 @@ impl Gas {
 
     /// Converts the sub gas units to whole ones. If the sub units are not a
     /// multiple of the `SCALE` than ceil the quotient
-    fn get_whole_gas_units(&self) -> u64 {
+    pub fn get_whole_gas_units(&self) -> u64 {
         let quotient = self.sub / SCALE;
         if self.sub % SCALE == 0 {
             quotient
@@ -252,6 +257,49 @@ pub struct VpsGas {
     rest: Vec<Gas>,
 }
 
+/// Breakdown of the gas used by a tx, by section of its execution
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct GasBreakdown {
+    /// The gas limit of the tx
+    pub gas_limit: Gas,
+    /// Gas used to settle the fee, including the optional fee unshielding
+    pub fee_settlement: Gas,
+    /// Gas used to validate the wrapper tx and to store and transmit its bytes
+    pub wrapper: Gas,
+    /// Gas used by the tx code
+    pub tx: Gas,
+    /// Gas used by each of the triggered VPs
+    pub vps: BTreeMap<Address, Gas>,
+    /// Gas charged for all the VPs, which accounts for their parallel
+    /// execution
+    pub vps_total: Gas,
+}
+
+impl Display for GasBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| std::fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+impl FromStr for GasBreakdown {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
 impl GasMetering for TxGasMeter {
     fn consume(&mut self, gas: u64) -> Result<()> {
         if self.gas_overflow {
@@ -408,6 +456,24 @@ impl VpGasMeter {
             current_gas: Gas::default(),
         }
     }
+
+    /// Get the gas consumed by the VP alone
+    pub fn get_vp_consumed_gas(&self) -> Gas {
+        if !self.gas_overflow {
+            self.current_gas
+        } else {
+            hints::cold();
+            u64::MAX.into()
+        }
+    }
+
+    /// Get the amount of gas still available to the VP
+    pub fn get_available_gas(&self) -> Gas {
+        self.initial_gas
+            .checked_add(self.current_gas)
+            .and_then(|consumed| self.tx_gas_limit.checked_sub(consumed))
+            .unwrap_or_default()
+    }
 }
 
 impl VpsGas {
@@ -518,6 +584,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_vp_available_gas() {
+        let tx_gas_meter = TxGasMeter {
+            gas_overflow: false,
+            tx_gas_limit: TX_GAS_LIMIT.into(),
+            transaction_gas: 1_000.into(),
+        };
+        let mut meter = VpGasMeter::new_from_tx_meter(&tx_gas_meter);
+        meter.consume(500).expect("cannot add the gas");
+        assert_eq!(meter.get_available_gas(), (TX_GAS_LIMIT - 1_500).into());
+
+        // Exceeding the limit leaves no gas available
+        let _ = meter.consume(TX_GAS_LIMIT);
+        assert_eq!(meter.get_available_gas(), Gas::default());
+    }
+
     #[test]
     fn test_tx_gas_overflow() {
         let mut meter = TxGasMeter::new_from_sub_limit(BLOCK_GAS_LIMIT.into());
@@ -528,6 +610,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_vp_consumed_gas() {
+        let tx_gas_meter = TxGasMeter {
+            gas_overflow: false,
+            tx_gas_limit: TX_GAS_LIMIT.into(),
+            transaction_gas: 1_000.into(),
+        };
+        let mut meter = VpGasMeter::new_from_tx_meter(&tx_gas_meter);
+        meter.consume(500).expect("cannot add the gas");
+        assert_eq!(meter.get_vp_consumed_gas(), 500.into());
+    }
+
+    #[test]
+    fn test_gas_breakdown_parse() {
+        let breakdown = GasBreakdown {
+            gas_limit: TX_GAS_LIMIT.into(),
+            fee_settlement: 1.into(),
+            wrapper: 2.into(),
+            tx: 3.into(),
+            vps: BTreeMap::from([(
+                Address::Internal(namada_core::address::InternalAddress::PoS),
+                4.into(),
+            )]),
+            vps_total: 4.into(),
+        };
+        assert_eq!(
+            breakdown.to_string().parse::<GasBreakdown>().unwrap(),
+            breakdown
+        );
+    }
+
     #[test]
     fn test_tx_gas_limit() {
         let mut meter = TxGasMeter::new_from_sub_limit(TX_GAS_LIMIT.into());
@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::address::Address;
+use namada_core::hash::Hash;
+use namada_core::storage::Epoch;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use serde::{Deserialize, Serialize};
+
+use crate::storage::proposal::StorageProposal;
+
+/// The key of the on-chain proposal content field that anchors an off-chain
+/// content by the hash of its canonical serialization
+pub const CONTENT_HASH_KEY: &str = "content-hash";
+
+/// Get the canonical serialization of an off-chain proposal content. JSON
+/// contents are re-encoded in the compact form with sorted object keys, so
+/// that their formatting doesn't affect the hash. Any other content, e.g.
+/// markdown or an artifact, is used as is, except that `\r\n` line endings
+/// are replaced with `\n` in UTF-8 text.
+pub fn canonical_content_bytes(content: &[u8]) -> Vec<u8> {
+    if let Ok(json) = serde_json::from_slice::<serde_json::Value>(content) {
+        // `serde_json::Map` keeps its keys sorted
+        return serde_json::to_vec(&json)
+            .expect("Serializing a JSON value should not fail");
+    }
+    match std::str::from_utf8(content) {
+        Ok(text) => text.replace("\r\n", "\n").into_bytes(),
+        Err(_) => content.to_vec(),
+    }
+}
+
+/// Compute the hash of the canonical serialization of an off-chain proposal
+/// content
+pub fn content_hash(content: &[u8]) -> Hash {
+    Hash::sha256(canonical_content_bytes(content))
+}
+
+/// Add the hash of an off-chain content to an on-chain proposal content
+pub fn anchor_content(
+    on_chain_content: &mut BTreeMap<String, String>,
+    off_chain_content: &[u8],
+) -> Hash {
+    let hash = content_hash(off_chain_content);
+    on_chain_content.insert(CONTENT_HASH_KEY.to_string(), hash.to_string());
+    hash
+}
+
+/// The result of verifying an off-chain content against a proposal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentVerification {
+    /// The content matches the anchored hash
+    Matches(Hash),
+    /// The content doesn't match the anchored hash
+    Mismatch {
+        /// The hash anchored on chain
+        anchored: Hash,
+        /// The hash of the given content
+        found: Hash,
+    },
+    /// The proposal doesn't anchor any content hash
+    NotAnchored,
+    /// The anchored content hash is not a valid hash
+    InvalidAnchor(String),
+}
+
+/// Verify an off-chain content against the hash anchored in an on-chain
+/// proposal content
+pub fn verify_content(
+    on_chain_content: &BTreeMap<String, String>,
+    off_chain_content: &[u8],
+) -> ContentVerification {
+    verify_content_hash(
+        on_chain_content.get(CONTENT_HASH_KEY).map(String::as_str),
+        off_chain_content,
+    )
+}
+
+/// Verify an off-chain content against an anchored content hash
+pub fn verify_content_hash(
+    anchored: Option<&str>,
+    off_chain_content: &[u8],
+) -> ContentVerification {
+    let Some(anchored) = anchored else {
+        return ContentVerification::NotAnchored;
+    };
+    let Ok(anchored) = Hash::try_from(anchored) else {
+        return ContentVerification::InvalidAnchor(anchored.to_string());
+    };
+    let found = content_hash(off_chain_content);
+    if found == anchored {
+        ContentVerification::Matches(found)
+    } else {
+        ContentVerification::Mismatch { anchored, found }
+    }
+}
+
+/// The anchored content hash and the metadata of a proposal
+#[derive(
+    Debug,
+    Clone,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct ProposalContentAnchor {
+    /// The proposal id
+    pub id: u64,
+    /// The proposal author
+    pub author: Address,
+    /// The epoch in which voting begins
+    pub voting_start_epoch: Epoch,
+    /// The final epoch in which voting is allowed
+    pub voting_end_epoch: Epoch,
+    /// The hash of the off-chain content, if any. The raw value is kept if it
+    /// is not a valid hash.
+    pub content_hash: Option<String>,
+    /// The rest of the on-chain content
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl ProposalContentAnchor {
+    /// Verify an off-chain content against the anchored content hash
+    pub fn verify(&self, off_chain_content: &[u8]) -> ContentVerification {
+        verify_content_hash(self.content_hash.as_deref(), off_chain_content)
+    }
+}
+
+impl From<StorageProposal> for ProposalContentAnchor {
+    fn from(proposal: StorageProposal) -> Self {
+        let mut metadata = proposal.content;
+        let content_hash = metadata.remove(CONTENT_HASH_KEY);
+        Self {
+            id: proposal.id,
+            author: proposal.author,
+            voting_start_epoch: proposal.voting_start_epoch,
+            voting_end_epoch: proposal.voting_end_epoch,
+            content_hash,
+            metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash_is_canonical() {
+        let json = br#"{ "title": "Upgrade",  "details": "..." }"#;
+        let reordered = b"{\"details\":\"...\",\r\n\"title\":\"Upgrade\"}";
+        assert_eq!(content_hash(json), content_hash(reordered));
+
+        let markdown = b"# Upgrade\r\n\r\nDetails\r\n";
+        assert_eq!(
+            content_hash(markdown),
+            content_hash(b"# Upgrade\n\nDetails\n")
+        );
+        assert_ne!(content_hash(markdown), content_hash(b"# Upgrade\n"));
+    }
+
+    #[test]
+    fn test_verify_content() {
+        let off_chain = b"# Upgrade\n\nDetails\n";
+        let mut on_chain = BTreeMap::new();
+        assert_eq!(
+            verify_content(&on_chain, off_chain),
+            ContentVerification::NotAnchored
+        );
+
+        let hash = anchor_content(&mut on_chain, off_chain);
+        assert_eq!(
+            verify_content(&on_chain, off_chain),
+            ContentVerification::Matches(hash)
+        );
+        assert_eq!(
+            verify_content(&on_chain, b"# Downgrade\n"),
+            ContentVerification::Mismatch {
+                anchored: hash,
+                found: content_hash(b"# Downgrade\n"),
+            }
+        );
+
+        on_chain.insert(CONTENT_HASH_KEY.to_string(), "invalid".to_string());
+        assert_eq!(
+            verify_content(&on_chain, off_chain),
+            ContentVerification::InvalidAnchor("invalid".to_string())
+        );
+    }
+}
@@ -1,3 +1,5 @@
+/// CLi governance off-chain content anchoring
+pub mod content;
 /// CLi governance on chain structures
 pub mod onchain;
 /// CLi governance validation
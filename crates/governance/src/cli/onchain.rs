@@ -12,12 +12,17 @@ use serde::{Deserialize, Serialize};
 
 use super::validation::{
     is_valid_activation_epoch, is_valid_author_balance, is_valid_content,
-    is_valid_default_proposal_data, is_valid_end_epoch,
-    is_valid_pgf_funding_data, is_valid_pgf_stewards_data,
-    is_valid_proposal_period, is_valid_start_epoch, ProposalValidation,
+    is_valid_default_proposal_data, is_valid_emergency_pause_data,
+    is_valid_end_epoch, is_valid_expedited_proposal_data,
+    is_valid_pgf_funding_data, is_valid_pgf_steward_election_data,
+    is_valid_pgf_stewards_data, is_valid_proposal_period,
+    is_valid_start_epoch, ProposalValidation,
 };
-use crate::parameters::GovernanceParameters;
-use crate::storage::proposal::PGFTarget;
+use crate::parameters::{
+    GovernanceParameters, EXPEDITED_MIN_PROPOSAL_GRACE_EPOCHS,
+    EXPEDITED_MIN_PROPOSAL_VOTING_PERIOD,
+};
+use crate::storage::proposal::{EmergencyPause, PGFTarget, StewardElection};
 
 #[derive(
     Debug,
@@ -119,6 +124,91 @@ impl TryFrom<&[u8]> for DefaultProposal {
     }
 }
 
+/// Expedited proposal with wasm code
+#[derive(
+    Debug,
+    Clone,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct ExpeditedProposal {
+    /// The proposal data
+    pub proposal: OnChainProposal,
+    /// The code of the proposal, which may only change the emergency pauses
+    /// and some protocol parameters
+    pub data: Vec<u8>,
+}
+
+impl ExpeditedProposal {
+    /// Validate an expedited proposal. It may have the shortest voting period
+    /// and grace period.
+    pub fn validate(
+        self,
+        governance_parameters: &GovernanceParameters,
+        current_epoch: Epoch,
+        balance: token::Amount,
+        force: bool,
+    ) -> Result<Self, ProposalValidation> {
+        if force {
+            return Ok(self);
+        }
+        let min_proposal_voting_period = governance_parameters
+            .min_proposal_voting_period
+            .min(EXPEDITED_MIN_PROPOSAL_VOTING_PERIOD);
+        let min_proposal_grace_epochs = governance_parameters
+            .min_proposal_grace_epochs
+            .min(EXPEDITED_MIN_PROPOSAL_GRACE_EPOCHS);
+        is_valid_start_epoch(
+            self.proposal.voting_start_epoch,
+            current_epoch,
+            min_proposal_voting_period,
+        )?;
+        is_valid_end_epoch(
+            self.proposal.voting_start_epoch,
+            self.proposal.voting_end_epoch,
+            current_epoch,
+            min_proposal_voting_period,
+            min_proposal_voting_period,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_activation_epoch(
+            self.proposal.activation_epoch,
+            self.proposal.voting_end_epoch,
+            min_proposal_grace_epochs,
+        )?;
+        is_valid_proposal_period(
+            self.proposal.voting_start_epoch,
+            self.proposal.activation_epoch,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_author_balance(
+            balance,
+            governance_parameters.min_proposal_fund,
+        )?;
+        is_valid_content(
+            &self.proposal.content,
+            governance_parameters.max_proposal_content_size,
+        )?;
+        is_valid_expedited_proposal_data(
+            &self.data,
+            governance_parameters.max_proposal_code_size,
+        )?;
+
+        Ok(self)
+    }
+}
+
+impl TryFrom<&[u8]> for ExpeditedProposal {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(value)
+    }
+}
+
 /// Pgf stewards proposal
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PgfStewardProposal {
@@ -194,6 +284,145 @@ impl TryFrom<&[u8]> for PgfStewardProposal {
     }
 }
 
+/// Pgf steward election proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PgfStewardElectionProposal {
+    /// The proposal data
+    pub proposal: OnChainProposal,
+    /// The elected stewards and the length of their term
+    pub data: StewardElection,
+}
+
+impl PgfStewardElectionProposal {
+    /// Validate a Pgf steward election proposal
+    pub fn validate(
+        self,
+        governance_parameters: &GovernanceParameters,
+        current_epoch: Epoch,
+        balance: token::Amount,
+        force: bool,
+    ) -> Result<Self, ProposalValidation> {
+        if force {
+            return Ok(self);
+        }
+        is_valid_start_epoch(
+            self.proposal.voting_start_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+        )?;
+        is_valid_end_epoch(
+            self.proposal.voting_start_epoch,
+            self.proposal.voting_end_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_activation_epoch(
+            self.proposal.activation_epoch,
+            self.proposal.voting_end_epoch,
+            governance_parameters.min_proposal_grace_epochs,
+        )?;
+        is_valid_proposal_period(
+            self.proposal.voting_start_epoch,
+            self.proposal.activation_epoch,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_author_balance(
+            balance,
+            governance_parameters.min_proposal_fund,
+        )?;
+        is_valid_content(
+            &self.proposal.content,
+            governance_parameters.max_proposal_content_size,
+        )?;
+        is_valid_pgf_steward_election_data(&self.data)?;
+
+        Ok(self)
+    }
+}
+
+impl TryFrom<&[u8]> for PgfStewardElectionProposal {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(value)
+    }
+}
+
+/// Expedited emergency pause proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmergencyPauseProposal {
+    /// The proposal data
+    pub proposal: OnChainProposal,
+    /// The paused modules and the length of the pause
+    pub data: EmergencyPause,
+}
+
+impl EmergencyPauseProposal {
+    /// Validate an emergency pause proposal. Being expedited, it may have
+    /// the shortest voting period and grace period.
+    pub fn validate(
+        self,
+        governance_parameters: &GovernanceParameters,
+        current_epoch: Epoch,
+        balance: token::Amount,
+        force: bool,
+    ) -> Result<Self, ProposalValidation> {
+        if force {
+            return Ok(self);
+        }
+        let min_proposal_voting_period = governance_parameters
+            .min_proposal_voting_period
+            .min(EXPEDITED_MIN_PROPOSAL_VOTING_PERIOD);
+        let min_proposal_grace_epochs = governance_parameters
+            .min_proposal_grace_epochs
+            .min(EXPEDITED_MIN_PROPOSAL_GRACE_EPOCHS);
+        is_valid_start_epoch(
+            self.proposal.voting_start_epoch,
+            current_epoch,
+            min_proposal_voting_period,
+        )?;
+        is_valid_end_epoch(
+            self.proposal.voting_start_epoch,
+            self.proposal.voting_end_epoch,
+            current_epoch,
+            min_proposal_voting_period,
+            min_proposal_voting_period,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_activation_epoch(
+            self.proposal.activation_epoch,
+            self.proposal.voting_end_epoch,
+            min_proposal_grace_epochs,
+        )?;
+        is_valid_proposal_period(
+            self.proposal.voting_start_epoch,
+            self.proposal.activation_epoch,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_author_balance(
+            balance,
+            governance_parameters.min_proposal_fund,
+        )?;
+        is_valid_content(
+            &self.proposal.content,
+            governance_parameters.max_proposal_content_size,
+        )?;
+        is_valid_emergency_pause_data(&self.data)?;
+
+        Ok(self)
+    }
+}
+
+impl TryFrom<&[u8]> for EmergencyPauseProposal {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(value)
+    }
+}
+
 /// Pgf funding proposal
 #[derive(
     Debug,
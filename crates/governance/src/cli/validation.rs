@@ -7,6 +7,8 @@ use namada_core::token;
 use thiserror::Error;
 
 use super::onchain::{PgfFunding, StewardsUpdate};
+use crate::pause::MAX_EMERGENCY_PAUSE_EPOCHS;
+use crate::storage::proposal::{EmergencyPause, StewardElection};
 
 /// This enum represents proposal data
 #[derive(Debug, Error)]
@@ -64,6 +66,23 @@ pub enum ProposalValidation {
     /// The pgf funding data is not valid
     #[error("invalid proposal extra data: cannot be empty.")]
     InvalidPgfFundingExtraData,
+    /// The pgf steward election data is not valid
+    #[error(
+        "Invalid proposal extra data: the elected stewards cannot be empty \
+         and the term must be at least one epoch."
+    )]
+    InvalidPgfStewardElectionExtraData,
+    /// The emergency pause data is not valid
+    #[error(
+        "Invalid proposal extra data: the paused modules cannot be empty."
+    )]
+    InvalidEmergencyPauseExtraData,
+    /// The emergency pause lasts too long
+    #[error(
+        "Invalid proposal extra data: the pause lasts {0} epochs, but it \
+         cannot last more than {MAX_EMERGENCY_PAUSE_EPOCHS} epochs."
+    )]
+    InvalidEmergencyPauseEpochs(u64),
     #[error("Arithmetic {0}.")]
     Arith(arith::Error),
 }
@@ -206,6 +225,21 @@ pub fn is_valid_default_proposal_data(
     }
 }
 
+pub fn is_valid_expedited_proposal_data(
+    data: &[u8],
+    max_extra_data_size: u64,
+) -> Result<(), ProposalValidation> {
+    let extra_data_length = data.len() as u64;
+    if extra_data_length > 0 && extra_data_length <= max_extra_data_size {
+        Ok(())
+    } else {
+        Err(ProposalValidation::InvalidDefaultProposalExtraData(
+            extra_data_length,
+            max_extra_data_size,
+        ))
+    }
+}
+
 pub fn is_valid_pgf_stewards_data(
     data: &StewardsUpdate,
     author: &Address,
@@ -226,6 +260,28 @@ pub fn is_valid_pgf_stewards_data(
     }
 }
 
+pub fn is_valid_pgf_steward_election_data(
+    data: &StewardElection,
+) -> Result<(), ProposalValidation> {
+    if !data.stewards.is_empty() && data.term > 0 {
+        Ok(())
+    } else {
+        Err(ProposalValidation::InvalidPgfStewardElectionExtraData)
+    }
+}
+
+pub fn is_valid_emergency_pause_data(
+    data: &EmergencyPause,
+) -> Result<(), ProposalValidation> {
+    if data.modules.is_empty() {
+        Err(ProposalValidation::InvalidEmergencyPauseExtraData)
+    } else if data.epochs > MAX_EMERGENCY_PAUSE_EPOCHS {
+        Err(ProposalValidation::InvalidEmergencyPauseEpochs(data.epochs))
+    } else {
+        Ok(())
+    }
+}
+
 pub fn is_valid_pgf_funding_data(
     data: &PgfFunding,
 ) -> Result<(), ProposalValidation> {
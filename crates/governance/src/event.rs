@@ -204,6 +204,7 @@ impl EventAttributeEntry<'static> for TallyResult {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "tally_result";
+    const VALUE_TYPE: &'static str = "tally-result";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -218,6 +219,7 @@ impl EventAttributeEntry<'static> for ProposalType {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "proposal_type";
+    const VALUE_TYPE: &'static str = "proposal-type";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -232,6 +234,7 @@ impl EventAttributeEntry<'static> for ProposalId {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "proposal_id";
+    const VALUE_TYPE: &'static str = "u64";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -246,6 +249,7 @@ impl EventAttributeEntry<'static> for HasProposalCode {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "has_proposal_code";
+    const VALUE_TYPE: &'static str = "bool";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -260,6 +264,7 @@ impl EventAttributeEntry<'static> for ProposalCodeExitStatus {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "proposal_code_exit_status";
+    const VALUE_TYPE: &'static str = "bool";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -7,6 +7,7 @@ pub mod cli;
 pub mod event;
 /// governance parameters
 pub mod parameters;
+pub mod pause;
 /// governance public good fundings
 pub mod pgf;
 /// governance storage
@@ -15,8 +16,10 @@ pub mod storage;
 pub mod utils;
 
 pub use storage::proposal::{InitProposalData, ProposalType, VoteProposalData};
-pub use storage::vote::ProposalVote;
-pub use storage::{init_proposal, is_proposal_accepted, vote_proposal};
+pub use storage::vote::{ProposalVote, VotingDelegationData};
+pub use storage::{
+    delegate_voting_power, init_proposal, is_proposal_accepted, vote_proposal,
+};
 
 /// The governance internal address
 pub const ADDRESS: Address = address::GOV;
@@ -7,6 +7,14 @@ use namada_storage::{Result, StorageRead, StorageWrite};
 
 use super::storage::keys as goverance_storage;
 
+/// The minimum number of epochs between the start and end epochs of an
+/// expedited proposal, of which these epochs must be a multiple
+pub const EXPEDITED_MIN_PROPOSAL_VOTING_PERIOD: u64 = 1;
+
+/// The minimum number of epochs between the end and activation epochs of an
+/// expedited proposal
+pub const EXPEDITED_MIN_PROPOSAL_GRACE_EPOCHS: u64 = 1;
+
 #[derive(
     Clone,
     Debug,
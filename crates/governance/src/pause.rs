@@ -0,0 +1,224 @@
+//! Emergency pauses of the transfers of some modules, set by expedited
+//! governance proposals. A pause expires on its own after the number of epochs
+//! set by its proposal, unless another proposal renews it.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::storage::Epoch;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_storage::{Result, StorageRead, StorageWrite};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::keys as gov_keys;
+
+/// The maximum number of epochs that an emergency pause can last. A longer
+/// pause must be renewed by another proposal.
+pub const MAX_EMERGENCY_PAUSE_EPOCHS: u64 = 30;
+
+/// A module whose transfers can be paused
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PausableModule {
+    /// The IBC transfers of tokens
+    IbcTransfers,
+    /// The transfers to Ethereum through the bridge pool
+    BridgePool,
+    /// The shielded transfers of the MASP
+    MaspTransfers,
+}
+
+impl PausableModule {
+    /// All the pausable modules
+    pub const ALL: [PausableModule; 3] = [
+        PausableModule::IbcTransfers,
+        PausableModule::BridgePool,
+        PausableModule::MaspTransfers,
+    ];
+}
+
+impl Display for PausableModule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PausableModule::IbcTransfers => write!(f, "ibc_transfers"),
+            PausableModule::BridgePool => write!(f, "bridge_pool"),
+            PausableModule::MaspTransfers => write!(f, "masp_transfers"),
+        }
+    }
+}
+
+/// Query the epoch from which a module is no longer paused, if it is paused
+/// in the given epoch
+pub fn get_pause_end<S>(
+    storage: &S,
+    module: PausableModule,
+    current_epoch: Epoch,
+) -> Result<Option<Epoch>>
+where
+    S: StorageRead,
+{
+    let pause_end: Option<Epoch> =
+        storage.read(&gov_keys::get_pause_key(module))?;
+    Ok(pause_end.filter(|pause_end| current_epoch < *pause_end))
+}
+
+/// Check if the transfers of a module are paused in the given epoch
+pub fn is_paused<S>(
+    storage: &S,
+    module: PausableModule,
+    current_epoch: Epoch,
+) -> Result<bool>
+where
+    S: StorageRead,
+{
+    Ok(get_pause_end(storage, module, current_epoch)?.is_some())
+}
+
+/// Query the modules paused in the given epoch with the epochs from which
+/// they are no longer paused
+pub fn get_paused_modules<S>(
+    storage: &S,
+    current_epoch: Epoch,
+) -> Result<BTreeMap<PausableModule, Epoch>>
+where
+    S: StorageRead,
+{
+    let mut paused = BTreeMap::new();
+    for module in PausableModule::ALL {
+        if let Some(pause_end) = get_pause_end(storage, module, current_epoch)?
+        {
+            paused.insert(module, pause_end);
+        }
+    }
+    Ok(paused)
+}
+
+/// Pause a module until the given epoch, replacing its current pause if any
+pub fn pause_module<S>(
+    storage: &mut S,
+    module: PausableModule,
+    pause_end: Epoch,
+) -> Result<()>
+where
+    S: StorageWrite,
+{
+    storage.write(&gov_keys::get_pause_key(module), pause_end)
+}
+
+/// Lift the pause of a module, if any
+pub fn lift_pause<S>(storage: &mut S, module: PausableModule) -> Result<()>
+where
+    S: StorageWrite,
+{
+    storage.delete(&gov_keys::get_pause_key(module))
+}
+
+/// Remove the pauses that have ended by the given epoch. Returns the modules
+/// that are no longer paused.
+pub fn remove_expired_pauses<S>(
+    storage: &mut S,
+    current_epoch: Epoch,
+) -> Result<Vec<PausableModule>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let mut expired = vec![];
+    for module in PausableModule::ALL {
+        let key = gov_keys::get_pause_key(module);
+        match storage.read::<Epoch>(&key)? {
+            Some(pause_end) if pause_end <= current_epoch => {
+                storage.delete(&key)?;
+                expired.push(module);
+            }
+            _ => {}
+        }
+    }
+    Ok(expired)
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_storage::testing::TestStorage;
+
+    use super::*;
+
+    #[test]
+    fn test_pauses() {
+        let mut storage = TestStorage::default();
+        let epoch = Epoch(5);
+        for module in PausableModule::ALL {
+            assert!(!is_paused(&storage, module, epoch).unwrap());
+        }
+
+        pause_module(&mut storage, PausableModule::IbcTransfers, Epoch(10))
+            .unwrap();
+        pause_module(&mut storage, PausableModule::BridgePool, Epoch(8))
+            .unwrap();
+        assert!(
+            is_paused(&storage, PausableModule::IbcTransfers, epoch).unwrap()
+        );
+        assert!(
+            is_paused(&storage, PausableModule::BridgePool, epoch).unwrap()
+        );
+        assert!(
+            !is_paused(&storage, PausableModule::MaspTransfers, epoch).unwrap()
+        );
+        assert_eq!(
+            get_paused_modules(&storage, epoch).unwrap(),
+            BTreeMap::from([
+                (PausableModule::IbcTransfers, Epoch(10)),
+                (PausableModule::BridgePool, Epoch(8)),
+            ])
+        );
+
+        // A renewal replaces the end of the pause
+        pause_module(&mut storage, PausableModule::BridgePool, Epoch(12))
+            .unwrap();
+        assert_eq!(
+            get_pause_end(&storage, PausableModule::BridgePool, epoch).unwrap(),
+            Some(Epoch(12))
+        );
+        lift_pause(&mut storage, PausableModule::BridgePool).unwrap();
+        assert!(
+            !is_paused(&storage, PausableModule::BridgePool, epoch).unwrap()
+        );
+
+        // The pauses expire at their end epoch
+        assert!(
+            !is_paused(&storage, PausableModule::IbcTransfers, Epoch(10))
+                .unwrap()
+        );
+        assert!(remove_expired_pauses(&mut storage, Epoch(9))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            remove_expired_pauses(&mut storage, Epoch(10)).unwrap(),
+            vec![PausableModule::IbcTransfers]
+        );
+        assert_eq!(
+            storage
+                .read::<Epoch>(&gov_keys::get_pause_key(
+                    PausableModule::IbcTransfers
+                ))
+                .unwrap(),
+            None
+        );
+    }
+}
@@ -5,7 +5,9 @@ use namada_parameters::storage as params_storage;
 use namada_storage::{Result, StorageRead, StorageWrite};
 use namada_trans_token::{credit_tokens, get_effective_total_native_supply};
 
-use crate::pgf::storage::{get_parameters, get_payments, get_stewards};
+use crate::pgf::storage::{
+    get_parameters, get_payments, get_stewards, write_last_inflation_amount,
+};
 use crate::storage::proposal::{PGFIbcTarget, PGFTarget};
 
 /// Apply the PGF inflation.
@@ -84,6 +86,7 @@ where
 
     // Pgf steward inflation
     let stewards = get_stewards(storage)?;
+    let mut minted = pgf_inflation_amount;
     let pgf_steward_inflation = total_supply
         .mul_floor(pgf_parameters.stewards_inflation_rate)?
         .checked_div_u64(epochs_per_year)
@@ -102,6 +105,11 @@ where
             )
             .is_ok()
             {
+                // The reward was added to the total supply, so the sum
+                // cannot overflow
+                minted = minted
+                    .checked_add(pgf_steward_reward)
+                    .expect("The minted amount must not exceed the supply");
                 tracing::info!(
                     "Minting {} tokens for steward {} (total supply {})..",
                     pgf_steward_reward.to_string_native(),
@@ -120,5 +128,5 @@ where
         }
     }
 
-    Ok(())
+    write_last_inflation_amount(storage, minted)
 }
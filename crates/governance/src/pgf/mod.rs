@@ -8,6 +8,8 @@ pub mod cli;
 pub mod inflation;
 /// Pgf parameters
 pub mod parameters;
+/// Pgf treasury simulation
+pub mod simulation;
 /// Pgf storage
 pub mod storage;
 
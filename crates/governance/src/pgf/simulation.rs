@@ -0,0 +1,422 @@
+//! Simulation of the PGF treasury balance under a hypothetical funding set.
+//!
+//! The simulation replays the PGF inflation applied at every new epoch with
+//! the current PGF parameters: the PGF inflation is minted into the treasury
+//! and the continuous fundings are paid from it in the order in which they
+//! were added, skipping the fundings that the treasury cannot afford. The
+//! growth of the total supply only accounts for the PGF and the steward
+//! inflation, so that the minted amounts are slightly underestimated when PoS
+//! inflation is non-zero.
+//!
+//! The simulated epochs are returned in pages of at most
+//! [`MAX_SIMULATED_EPOCHS_PER_PAGE`] epochs. A page ends with a
+//! [`TreasurySimulationCursor`] from which the next page is simulated.
+
+use namada_core::arith::checked;
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::storage::Epoch;
+use namada_core::token::Amount;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_parameters::storage as params_storage;
+use namada_storage::{Result, StorageRead};
+use namada_trans_token::{read_balance, read_total_supply};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::onchain::PgfFunding;
+use crate::pgf::storage::{get_parameters, get_payments, get_stewards};
+use crate::storage::proposal::PGFTarget;
+
+/// The maximum number of epochs that can be simulated
+pub const MAX_SIMULATED_EPOCHS: u64 = 10_000;
+
+/// The maximum number of epochs simulated in a page
+pub const MAX_SIMULATED_EPOCHS_PER_PAGE: u64 = 500;
+
+/// The maximum number of hypothetical fundings of a simulation
+pub const MAX_SIMULATED_FUNDINGS: usize = 100;
+
+/// A request to simulate the PGF treasury under a hypothetical funding set
+#[derive(
+    Debug,
+    Clone,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct TreasurySimulationRequest {
+    /// The hypothetical fundings. As in a PGF funding proposal, a continuous
+    /// funding replaces the current funding of the same target and one with
+    /// a zero amount removes it. The retro fundings are paid once, at the
+    /// start of the first simulated epoch.
+    pub fundings: PgfFunding,
+    /// The number of epochs to simulate
+    pub epochs: u64,
+    /// The cursor returned with the previous page of the simulation, or
+    /// `None` for the first page
+    pub cursor: Option<TreasurySimulationCursor>,
+}
+
+/// The state of the simulated PGF treasury at the end of a page, from which
+/// the next page is simulated
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct TreasurySimulationCursor {
+    /// The epoch from which the simulation started
+    pub start_epoch: Epoch,
+    /// The number of epochs simulated so far
+    pub simulated_epochs: u64,
+    /// The simulated balance of the treasury
+    pub balance: Amount,
+    /// The simulated total supply of the native token
+    pub total_supply: Amount,
+}
+
+/// The simulated PGF treasury in an epoch
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct TreasuryEpochProjection {
+    /// The epoch
+    pub epoch: Epoch,
+    /// The amount minted into the treasury
+    pub minted: Amount,
+    /// The total amount paid from the treasury
+    pub paid: Amount,
+    /// The total amount of the fundings that the treasury could not afford
+    pub unpaid: Amount,
+    /// The balance of the treasury at the end of the epoch
+    pub balance: Amount,
+}
+
+/// A page of the result of a PGF treasury simulation
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct TreasurySimulation {
+    /// The current balance of the treasury
+    pub initial_balance: Amount,
+    /// The simulated treasury in each of the epochs of the page
+    pub projections: Vec<TreasuryEpochProjection>,
+    /// The cursor from which the next page is simulated, if any
+    pub next: Option<TreasurySimulationCursor>,
+}
+
+impl TreasurySimulation {
+    /// Find the first simulated epoch in which a funding could not be paid,
+    /// if any
+    pub fn first_shortfall(&self) -> Option<Epoch> {
+        self.projections
+            .iter()
+            .find(|projection| !projection.unpaid.is_zero())
+            .map(|projection| projection.epoch)
+    }
+}
+
+/// The running balance of the treasury and the amounts paid from it
+#[derive(Default)]
+struct Payments {
+    balance: Amount,
+    paid: Amount,
+    unpaid: Amount,
+}
+
+impl Payments {
+    /// Pay the target if the treasury can afford it
+    fn pay(&mut self, target: &PGFTarget) -> Result<()> {
+        let amount = target.amount();
+        if self.balance.can_spend(&amount) {
+            self.balance = checked!(self.balance - amount)?;
+            self.paid = checked!(self.paid + amount)?;
+        } else {
+            self.unpaid = checked!(self.unpaid + amount)?;
+        }
+        Ok(())
+    }
+}
+
+/// Simulate a page of the PGF treasury over the requested number of epochs
+/// following the current epoch, with the current fundings updated by the
+/// hypothetical ones
+pub fn simulate_treasury<S>(
+    storage: &S,
+    request: &TreasurySimulationRequest,
+) -> Result<TreasurySimulation>
+where
+    S: StorageRead,
+{
+    if request.epochs > MAX_SIMULATED_EPOCHS {
+        return Err(namada_storage::Error::new_alloc(format!(
+            "Cannot simulate more than {MAX_SIMULATED_EPOCHS} epochs, got {}",
+            request.epochs
+        )));
+    }
+    let num_fundings = checked!(
+        request.fundings.continuous.len() + request.fundings.retro.len()
+    )?;
+    if num_fundings > MAX_SIMULATED_FUNDINGS {
+        return Err(namada_storage::Error::new_alloc(format!(
+            "Cannot simulate more than {MAX_SIMULATED_FUNDINGS} fundings, got \
+             {num_fundings}"
+        )));
+    }
+
+    let pgf_parameters = get_parameters(storage)?;
+    let staking_token = storage.get_native_token()?;
+    let epochs_per_year: u64 = storage
+        .read(&params_storage::get_epochs_per_year_key())?
+        .ok_or_else(|| {
+            namada_storage::Error::new_const(
+                "Epochs per year should exist in storage",
+            )
+        })?;
+    let initial_balance =
+        read_balance(storage, &staking_token, &super::ADDRESS)?;
+    let cursor = match request.cursor.clone() {
+        Some(cursor) => cursor,
+        None => TreasurySimulationCursor {
+            start_epoch: storage.get_block_epoch()?,
+            simulated_epochs: 0,
+            balance: initial_balance,
+            total_supply: read_total_supply(storage, &staking_token)?,
+        },
+    };
+
+    // The current fundings are paid first, followed by the hypothetical ones
+    let mut current_fundings = get_payments(storage)?;
+    current_fundings.sort_by(|a, b| a.id.cmp(&b.id));
+    let mut fundings = current_fundings
+        .into_iter()
+        .map(|funding| funding.detail)
+        .collect::<Vec<PGFTarget>>();
+    for target in &request.fundings.continuous {
+        fundings.retain(|funding| funding.target() != target.target());
+        if !target.amount().is_zero() {
+            fundings.push(target.clone());
+        }
+    }
+
+    let stewards = get_stewards(storage)?;
+
+    let mut payments = Payments {
+        balance: cursor.balance,
+        ..Default::default()
+    };
+    let mut total_supply = cursor.total_supply;
+    if request.cursor.is_none() {
+        for target in &request.fundings.retro {
+            payments.pay(target)?;
+        }
+    }
+
+    let first = checked!(cursor.simulated_epochs + 1)?;
+    let last = request.epochs.min(checked!(
+        cursor.simulated_epochs + MAX_SIMULATED_EPOCHS_PER_PAGE
+    )?);
+    let mut projections = Vec::new();
+    for offset in first..=last {
+        let effective_total_supply = checked!(total_supply - payments.balance)?;
+
+        let minted = effective_total_supply
+            .mul_floor(pgf_parameters.pgf_inflation_rate)?
+            .checked_div_u64(epochs_per_year)
+            .unwrap_or_default();
+        payments.balance = checked!(payments.balance + minted)?;
+        total_supply = checked!(total_supply + minted)?;
+
+        for target in &fundings {
+            payments.pay(target)?;
+        }
+
+        let pgf_steward_inflation = effective_total_supply
+            .mul_floor(pgf_parameters.stewards_inflation_rate)?
+            .checked_div_u64(epochs_per_year)
+            .unwrap_or_default();
+        for steward in &stewards {
+            for percentage in steward.reward_distribution.values() {
+                let reward = pgf_steward_inflation.mul_floor(*percentage)?;
+                total_supply = checked!(total_supply + reward)?;
+            }
+        }
+
+        projections.push(TreasuryEpochProjection {
+            epoch: Epoch(checked!(cursor.start_epoch.0 + offset)?),
+            minted,
+            paid: payments.paid,
+            unpaid: payments.unpaid,
+            balance: payments.balance,
+        });
+        payments.paid = Amount::zero();
+        payments.unpaid = Amount::zero();
+    }
+
+    let next = (last < request.epochs).then_some(TreasurySimulationCursor {
+        start_epoch: cursor.start_epoch,
+        simulated_epochs: last,
+        balance: payments.balance,
+        total_supply,
+    });
+    Ok(TreasurySimulation {
+        initial_balance,
+        projections,
+        next,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::address;
+    use namada_storage::testing::TestStorage;
+    use namada_storage::StorageWrite;
+    use namada_trans_token::credit_tokens;
+
+    use super::*;
+    use crate::pgf::parameters::PgfParameters;
+    use crate::storage::proposal::PGFInternalTarget;
+
+    fn internal_target(amount: u64) -> PGFTarget {
+        PGFTarget::Internal(PGFInternalTarget {
+            target: address::testing::established_address_1(),
+            amount: Amount::native_whole(amount),
+        })
+    }
+
+    #[test]
+    fn test_simulate_treasury() {
+        let mut storage = TestStorage::default();
+        PgfParameters::default().init_storage(&mut storage).unwrap();
+        storage
+            .write(&params_storage::get_epochs_per_year_key(), 10_u64)
+            .unwrap();
+        credit_tokens(
+            &mut storage,
+            &address::testing::nam(),
+            &address::testing::established_address_2(),
+            Amount::native_whole(1_000),
+        )
+        .unwrap();
+
+        // With 10% of PGF inflation over 10 epochs per year, 10 tokens are
+        // minted in every epoch
+        let request = TreasurySimulationRequest {
+            fundings: PgfFunding {
+                continuous: vec![internal_target(15)],
+                retro: vec![internal_target(5)],
+            },
+            epochs: 3,
+            cursor: None,
+        };
+        let simulation = simulate_treasury(&storage, &request).unwrap();
+        assert_eq!(simulation.initial_balance, Amount::zero());
+        assert_eq!(simulation.first_shortfall(), Some(Epoch(1)));
+
+        let projections = simulation.projections;
+        assert_eq!(projections.len(), 3);
+        assert_eq!(projections[0].minted, Amount::native_whole(10));
+        assert_eq!(projections[0].paid, Amount::zero());
+        assert_eq!(projections[0].unpaid, Amount::native_whole(20));
+        assert_eq!(projections[0].balance, Amount::native_whole(10));
+        assert_eq!(projections[1].epoch, Epoch(2));
+        assert_eq!(projections[1].paid, Amount::native_whole(15));
+        assert_eq!(projections[1].balance, Amount::native_whole(5));
+
+        // A continuous funding with a zero amount cancels the funding of the
+        // same target
+        let request = TreasurySimulationRequest {
+            fundings: PgfFunding {
+                continuous: vec![internal_target(15), internal_target(0)],
+                retro: vec![],
+            },
+            epochs: 3,
+            cursor: None,
+        };
+        let simulation = simulate_treasury(&storage, &request).unwrap();
+        assert_eq!(simulation.first_shortfall(), None);
+        assert_eq!(simulation.projections[2].balance, Amount::native_whole(30));
+
+        let too_many_epochs = TreasurySimulationRequest {
+            epochs: MAX_SIMULATED_EPOCHS + 1,
+            ..request.clone()
+        };
+        assert!(simulate_treasury(&storage, &too_many_epochs).is_err());
+        let too_many_fundings = TreasurySimulationRequest {
+            fundings: PgfFunding {
+                continuous: vec![internal_target(1); MAX_SIMULATED_FUNDINGS],
+                retro: vec![internal_target(1)],
+            },
+            ..request
+        };
+        assert!(simulate_treasury(&storage, &too_many_fundings).is_err());
+    }
+
+    #[test]
+    fn test_simulate_treasury_pages() {
+        let mut storage = TestStorage::default();
+        PgfParameters::default().init_storage(&mut storage).unwrap();
+        storage
+            .write(&params_storage::get_epochs_per_year_key(), 10_u64)
+            .unwrap();
+        credit_tokens(
+            &mut storage,
+            &address::testing::nam(),
+            &address::testing::established_address_2(),
+            Amount::native_whole(1_000),
+        )
+        .unwrap();
+
+        let epochs = MAX_SIMULATED_EPOCHS_PER_PAGE + 2;
+        let mut request = TreasurySimulationRequest {
+            fundings: PgfFunding {
+                continuous: vec![internal_target(1)],
+                retro: vec![internal_target(5)],
+            },
+            epochs,
+            cursor: None,
+        };
+        let first_page = simulate_treasury(&storage, &request).unwrap();
+        assert_eq!(
+            first_page.projections.len() as u64,
+            MAX_SIMULATED_EPOCHS_PER_PAGE
+        );
+        let cursor = first_page.next.clone().unwrap();
+        assert_eq!(cursor.simulated_epochs, MAX_SIMULATED_EPOCHS_PER_PAGE);
+        assert_eq!(
+            cursor.balance,
+            first_page.projections.last().unwrap().balance
+        );
+
+        // The next page resumes from the cursor, without paying the retro
+        // fundings again
+        request.cursor = Some(cursor);
+        let last_page = simulate_treasury(&storage, &request).unwrap();
+        assert_eq!(last_page.next, None);
+        assert_eq!(last_page.projections.len(), 2);
+        assert_eq!(last_page.projections[1].epoch, Epoch(epochs));
+        assert_eq!(last_page.projections[0].unpaid, Amount::zero());
+    }
+}
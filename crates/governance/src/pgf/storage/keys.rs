@@ -1,5 +1,5 @@
 use namada_core::address::Address;
-use namada_core::storage::{DbKeySeg, Key, KeySeg};
+use namada_core::storage::{DbKeySeg, Epoch, Key, KeySeg};
 use namada_macros::StorageKeys;
 use namada_storage::collections::{lazy_map, LazyCollection, LazyMap};
 
@@ -11,9 +11,11 @@ use crate::storage::proposal::StoragePgfFunding;
 #[derive(StorageKeys)]
 struct Keys {
     stewards: &'static str,
+    steward_terms: &'static str,
     fundings: &'static str,
     pgf_inflation_rate: &'static str,
     steward_inflation_rate: &'static str,
+    last_inflation_amount: &'static str,
 }
 
 /// Obtain a storage key for stewards key
@@ -50,6 +52,41 @@ pub fn is_stewards_key(key: &Key) -> Option<&Address> {
     }
 }
 
+/// Obtain a storage key for the end of the terms of elected stewards
+pub fn steward_terms_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.steward_terms.to_string()),
+        ],
+    }
+}
+
+/// LazyMap handler for the epochs from which the elected stewards are
+/// removed
+pub fn steward_terms_handle() -> LazyMap<Address, Epoch> {
+    LazyMap::open(steward_terms_key_prefix())
+}
+
+/// Check if the given storage key is a steward term key. If it is, returns
+/// the steward address.
+pub fn is_steward_term_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(pgf),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(data),
+            DbKeySeg::AddressSeg(steward),
+        ] if pgf.eq(&ADDRESS)
+            && prefix.as_str() == Keys::VALUES.steward_terms
+            && data.as_str() == lazy_map::DATA_SUBKEY =>
+        {
+            Some(steward)
+        }
+        _ => None,
+    }
+}
+
 /// Obtain a storage key for pgf fundings.
 pub fn fundings_key_prefix() -> Key {
     Key {
@@ -100,3 +137,10 @@ pub fn get_steward_inflation_rate_key() -> Key {
         .push(&Keys::VALUES.steward_inflation_rate.to_owned())
         .expect("Cannot obtain a storage key")
 }
+
+/// Get key for last epoch's PGF inflation amount
+pub fn get_last_inflation_amount_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.last_inflation_amount.to_owned())
+        .expect("Cannot obtain a storage key")
+}
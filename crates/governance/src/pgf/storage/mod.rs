@@ -5,9 +5,14 @@ pub mod keys;
 /// Pgf steward structures
 pub mod steward;
 
+use std::collections::BTreeSet;
+
 use namada_core::address::Address;
 use namada_core::collections::HashMap;
 use namada_core::dec::Dec;
+use namada_core::storage::Epoch;
+use namada_core::token::Amount;
+use namada_parameters::storage as params_storage;
 use namada_storage::{Result, StorageRead, StorageWrite};
 
 use crate::pgf::parameters::PgfParameters;
@@ -56,10 +61,117 @@ where
     S: StorageRead + StorageWrite,
 {
     pgf_keys::stewards_handle().remove(storage, address)?;
+    pgf_keys::steward_terms_handle().remove(storage, address)?;
 
     Ok(())
 }
 
+/// Query the epoch from which an elected steward is removed, if any
+pub fn get_steward_term_end<S>(
+    storage: &S,
+    address: &Address,
+) -> Result<Option<Epoch>>
+where
+    S: StorageRead,
+{
+    pgf_keys::steward_terms_handle().get(storage, address)
+}
+
+/// Replace the steward set with the elected stewards, whose term ends at the
+/// given epoch. The re-elected stewards keep their reward distribution.
+pub fn elect_stewards<S>(
+    storage: &mut S,
+    stewards: &BTreeSet<Address>,
+    term_end: Epoch,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let current_stewards = pgf_keys::stewards_handle()
+        .iter(storage)?
+        .map(|data| data.map(|(address, _)| address))
+        .collect::<Result<Vec<Address>>>()?;
+    for address in current_stewards {
+        if !stewards.contains(&address) {
+            remove_steward(storage, &address)?;
+        }
+    }
+
+    for address in stewards {
+        if !is_steward(storage, address)? {
+            pgf_keys::stewards_handle().insert(
+                storage,
+                address.clone(),
+                StewardDetail::base(address.clone()),
+            )?;
+        }
+        pgf_keys::steward_terms_handle().insert(
+            storage,
+            address.clone(),
+            term_end,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Add a steward outside of a steward election, i.e. with a PGF steward
+/// proposal. The steward gets the default term of one year from the given
+/// epoch, as the elected stewards never keep their role indefinitely.
+/// Returns the epoch from which the steward is removed.
+pub fn add_steward<S>(
+    storage: &mut S,
+    address: &Address,
+    current_epoch: Epoch,
+) -> Result<Epoch>
+where
+    S: StorageRead + StorageWrite,
+{
+    let epochs_per_year: u64 = storage
+        .read(&params_storage::get_epochs_per_year_key())?
+        .expect("Epochs per year should exist in storage");
+    let term_end = Epoch(current_epoch.0.saturating_add(epochs_per_year));
+
+    pgf_keys::stewards_handle().insert(
+        storage,
+        address.clone(),
+        StewardDetail::base(address.clone()),
+    )?;
+    pgf_keys::steward_terms_handle().insert(
+        storage,
+        address.clone(),
+        term_end,
+    )?;
+
+    Ok(term_end)
+}
+
+/// Remove the elected stewards whose term has ended by the given epoch.
+/// Returns the removed stewards.
+pub fn remove_expired_stewards<S>(
+    storage: &mut S,
+    current_epoch: Epoch,
+) -> Result<Vec<Address>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let expired = pgf_keys::steward_terms_handle()
+        .iter(storage)?
+        .filter_map(|data| match data {
+            Ok((address, term_end)) if term_end <= current_epoch => {
+                Some(Ok(address))
+            }
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+        .collect::<Result<Vec<Address>>>()?;
+    for address in &expired {
+        remove_steward(storage, address)?;
+    }
+
+    Ok(expired)
+}
+
 /// Query the current pgf continuous payments
 pub fn get_payments<S>(storage: &S) -> Result<Vec<StoragePgfFunding>>
 where
@@ -99,6 +211,26 @@ where
     })
 }
 
+/// Read last epoch's PGF inflation amount, of the PGF account and of the
+/// stewards
+pub fn get_last_inflation_amount<S>(storage: &S) -> Result<Option<Amount>>
+where
+    S: StorageRead,
+{
+    storage.read(&pgf_keys::get_last_inflation_amount_key())
+}
+
+/// Write last epoch's PGF inflation amount
+pub fn write_last_inflation_amount<S>(
+    storage: &mut S,
+    inflation: Amount,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    storage.write(&pgf_keys::get_last_inflation_amount_key(), inflation)
+}
+
 /// Update the commission for a steward
 pub fn update_commission<S>(
     storage: &mut S,
@@ -119,3 +251,90 @@ where
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use namada_core::address;
+    use namada_storage::testing::TestStorage;
+
+    use super::*;
+
+    #[test]
+    fn test_steward_election_terms() {
+        let mut storage = TestStorage::default();
+        let steward_1 = address::testing::established_address_1();
+        let steward_2 = address::testing::established_address_2();
+        let steward_3 = address::testing::established_address_3();
+
+        // A steward without a term, as the genesis stewards
+        update_commission(
+            &mut storage,
+            steward_1.clone(),
+            HashMap::from_iter([(steward_3.clone(), Dec::one())]),
+        )
+        .unwrap();
+
+        // The re-elected steward keeps their reward distribution
+        elect_stewards(
+            &mut storage,
+            &BTreeSet::from([steward_1.clone(), steward_2.clone()]),
+            Epoch(10),
+        )
+        .unwrap();
+        let steward = get_steward(&storage, &steward_1).unwrap().unwrap();
+        assert_eq!(
+            steward.reward_distribution.get(&steward_3),
+            Some(&Dec::one())
+        );
+        assert!(is_steward(&storage, &steward_2).unwrap());
+        assert_eq!(
+            get_steward_term_end(&storage, &steward_2).unwrap(),
+            Some(Epoch(10))
+        );
+
+        // A new election replaces the steward set
+        elect_stewards(
+            &mut storage,
+            &BTreeSet::from([steward_2.clone(), steward_3.clone()]),
+            Epoch(20),
+        )
+        .unwrap();
+        assert!(!is_steward(&storage, &steward_1).unwrap());
+        assert_eq!(get_steward_term_end(&storage, &steward_1).unwrap(), None);
+        assert_eq!(
+            get_steward_term_end(&storage, &steward_2).unwrap(),
+            Some(Epoch(20))
+        );
+
+        // The stewards are removed once their term ends
+        assert!(remove_expired_stewards(&mut storage, Epoch(19))
+            .unwrap()
+            .is_empty());
+        let mut expired =
+            remove_expired_stewards(&mut storage, Epoch(20)).unwrap();
+        expired.sort();
+        assert_eq!(expired, vec![steward_2.clone(), steward_3.clone()]);
+        assert!(get_stewards(&storage).unwrap().is_empty());
+        assert_eq!(get_steward_term_end(&storage, &steward_3).unwrap(), None);
+
+        // A steward added outside of an election gets the default term of
+        // one year
+        storage
+            .write(&params_storage::get_epochs_per_year_key(), 365_u64)
+            .unwrap();
+        let term_end =
+            add_steward(&mut storage, &steward_1, Epoch(30)).unwrap();
+        assert_eq!(term_end, Epoch(395));
+        assert_eq!(
+            get_steward_term_end(&storage, &steward_1).unwrap(),
+            Some(Epoch(395))
+        );
+        assert!(remove_expired_stewards(&mut storage, Epoch(394))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            remove_expired_stewards(&mut storage, Epoch(395)).unwrap(),
+            vec![steward_1]
+        );
+    }
+}
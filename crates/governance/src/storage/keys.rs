@@ -2,6 +2,7 @@ use namada_core::address::Address;
 use namada_core::storage::{DbKeySeg, Key, KeySeg};
 use namada_macros::StorageKeys;
 
+use crate::pause::PausableModule;
 use crate::ADDRESS;
 
 /// Storage keys for governance internal address.
@@ -28,6 +29,8 @@ struct Keys {
     counter: &'static str,
     pending: &'static str,
     result: &'static str,
+    voting_delegate: &'static str,
+    pause: &'static str,
 }
 
 /// Check if key is inside governance address space
@@ -55,6 +58,22 @@ pub fn is_vote_key(key: &Key) -> bool {
     }
 }
 
+/// Check if a key is a voting delegate key. Returns the address of the
+/// delegator.
+pub fn is_voting_delegate_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(delegator),
+        ] if addr == &ADDRESS && prefix == Keys::VALUES.voting_delegate =>
+        {
+            Some(delegator)
+        }
+        _ => None,
+    }
+}
+
 /// Check if key is author key
 pub fn is_author_key(key: &Key) -> bool {
     match &key.segments[..] {
@@ -286,6 +305,25 @@ pub fn is_start_or_end_epoch_key(key: &Key) -> bool {
     is_end_epoch_key(key) || is_start_epoch_key(key)
 }
 
+/// Check if key is the key of the pause of a module
+pub fn is_pause_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(_module),
+        ] => addr == &ADDRESS && prefix == Keys::VALUES.pause,
+        _ => false,
+    }
+}
+
+/// Check if key may be changed by the code of an expedited proposal, which
+/// is limited to the emergency pauses and to some protocol parameters
+pub fn is_expedited_key(key: &Key) -> bool {
+    is_pause_key(key)
+        || namada_parameters::storage::is_expedited_parameter_key(key)
+}
+
 /// Get governance prefix key
 pub fn proposal_prefix() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -459,6 +497,29 @@ pub fn get_vote_proposal_key(
         .expect("Cannot obtain a storage key")
 }
 
+/// Get the prefix key of the voting delegates
+pub fn get_voting_delegate_prefix_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.voting_delegate.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the voting delegate of a delegator
+pub fn get_voting_delegate_key(delegator: &Address) -> Key {
+    get_voting_delegate_prefix_key()
+        .push(delegator)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the end epoch of the pause of a module
+pub fn get_pause_key(module: PausableModule) -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.pause.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&module.to_string())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get the proposal execution key
 pub fn get_proposal_execution_key(id: u64) -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -11,7 +11,7 @@ use std::collections::{BTreeMap, BTreeSet};
 
 use namada_core::address::Address;
 use namada_core::borsh::BorshDeserialize;
-use namada_core::collections::HashSet;
+use namada_core::collections::{HashMap, HashSet};
 use namada_core::storage::Epoch;
 use namada_storage::{iter_prefix, Error, Result, StorageRead, StorageWrite};
 use namada_trans_token as token;
@@ -50,7 +50,8 @@ where
 
     let proposal_type_key = governance_keys::get_proposal_type_key(proposal_id);
     match data.r#type {
-        ProposalType::DefaultWithWasm(_) => {
+        ProposalType::DefaultWithWasm(_)
+        | ProposalType::ExpeditedWithWasm(_) => {
             storage.write(&proposal_type_key, data.r#type.clone())?;
             let proposal_code_key =
                 governance_keys::get_proposal_code_key(proposal_id);
@@ -122,6 +123,86 @@ where
     Ok(())
 }
 
+/// A voting power delegation transaction. The delegate votes on behalf of the
+/// delegator on the proposals that the delegator doesn't vote on itself.
+pub fn delegate_voting_power<S>(
+    storage: &mut S,
+    delegator: &Address,
+    delegate: Option<Address>,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = governance_keys::get_voting_delegate_key(delegator);
+    match delegate {
+        Some(delegate) if &delegate == delegator => Err(Error::new_const(
+            "The voting power cannot be delegated to the delegator itself",
+        )),
+        Some(delegate) => storage.write(&key, delegate),
+        None => storage.delete(&key),
+    }
+}
+
+/// Read the voting delegate of a delegator, if any
+pub fn get_voting_delegate<S>(
+    storage: &S,
+    delegator: &Address,
+) -> Result<Option<Address>>
+where
+    S: StorageRead,
+{
+    let key = governance_keys::get_voting_delegate_key(delegator);
+    storage.read(&key)
+}
+
+/// Find the votes cast by the voting delegates on behalf of the delegators
+/// that didn't vote themselves, given all the votes of a proposal. A delegated
+/// vote applies to the bonds of the delegator to each of its
+/// `delegation_targets`. The delegates' own delegations are not followed.
+pub fn get_delegated_votes<S, F>(
+    storage: &S,
+    votes: &[Vote],
+    mut delegation_targets: F,
+) -> Result<Vec<Vote>>
+where
+    S: StorageRead,
+    F: FnMut(&Address) -> Result<HashSet<Address>>,
+{
+    let voters: HashMap<&Address, &ProposalVote> = votes
+        .iter()
+        .map(|vote| (&vote.delegator, &vote.data))
+        .collect();
+    let prefix = governance_keys::get_voting_delegate_prefix_key();
+
+    let mut delegated_votes = vec![];
+    for delegation in iter_prefix::<Address>(storage, &prefix)? {
+        let (key, delegate) = delegation?;
+        let Some(delegator) = governance_keys::is_voting_delegate_key(&key)
+        else {
+            continue;
+        };
+        // The own vote of a delegator takes precedence
+        if voters.contains_key(delegator) {
+            continue;
+        }
+        let Some(data) = voters.get(&delegate) else {
+            continue;
+        };
+        for validator in delegation_targets(delegator)? {
+            // Validators vote with their own stake
+            if &validator == delegator {
+                continue;
+            }
+            delegated_votes.push(Vote {
+                validator,
+                delegator: delegator.clone(),
+                data: (*data).clone(),
+            });
+        }
+    }
+    Ok(delegated_votes)
+}
+
 /// Write the proposal result to storage.
 pub fn write_proposal_result<S>(
     storage: &mut S,
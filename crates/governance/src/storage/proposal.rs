@@ -16,9 +16,11 @@ use thiserror::Error;
 
 use super::vote::ProposalVote;
 use crate::cli::onchain::{
-    DefaultProposal, PgfAction, PgfContinuous, PgfFundingProposal, PgfRetro,
-    PgfSteward, PgfStewardProposal, StewardsUpdate,
+    DefaultProposal, EmergencyPauseProposal, ExpeditedProposal, PgfAction,
+    PgfContinuous, PgfFundingProposal, PgfRetro, PgfSteward,
+    PgfStewardElectionProposal, PgfStewardProposal, StewardsUpdate,
 };
+use crate::pause::PausableModule;
 use crate::utils::{ProposalStatus, TallyType};
 
 #[allow(missing_docs)]
@@ -58,7 +60,8 @@ impl InitProposalData {
     /// Get the hash of the corresponding extra data section
     pub fn get_section_code_hash(&self) -> Option<Hash> {
         match self.r#type {
-            ProposalType::DefaultWithWasm(hash) => Some(hash),
+            ProposalType::DefaultWithWasm(hash)
+            | ProposalType::ExpeditedWithWasm(hash) => Some(hash),
             _ => None,
         }
     }
@@ -120,6 +123,53 @@ impl TryFrom<PgfStewardProposal> for InitProposalData {
     }
 }
 
+impl TryFrom<PgfStewardElectionProposal> for InitProposalData {
+    type Error = ProposalError;
+
+    fn try_from(
+        value: PgfStewardElectionProposal,
+    ) -> Result<Self, Self::Error> {
+        Ok(InitProposalData {
+            content: Hash::default(),
+            author: value.proposal.author,
+            r#type: ProposalType::PGFStewardElection(value.data),
+            voting_start_epoch: value.proposal.voting_start_epoch,
+            voting_end_epoch: value.proposal.voting_end_epoch,
+            activation_epoch: value.proposal.activation_epoch,
+        })
+    }
+}
+
+impl TryFrom<EmergencyPauseProposal> for InitProposalData {
+    type Error = ProposalError;
+
+    fn try_from(value: EmergencyPauseProposal) -> Result<Self, Self::Error> {
+        Ok(InitProposalData {
+            content: Hash::default(),
+            author: value.proposal.author,
+            r#type: ProposalType::EmergencyPause(value.data),
+            voting_start_epoch: value.proposal.voting_start_epoch,
+            voting_end_epoch: value.proposal.voting_end_epoch,
+            activation_epoch: value.proposal.activation_epoch,
+        })
+    }
+}
+
+impl TryFrom<ExpeditedProposal> for InitProposalData {
+    type Error = ProposalError;
+
+    fn try_from(value: ExpeditedProposal) -> Result<Self, Self::Error> {
+        Ok(InitProposalData {
+            content: Hash::default(),
+            author: value.proposal.author,
+            r#type: ProposalType::ExpeditedWithWasm(Hash::default()),
+            voting_start_epoch: value.proposal.voting_start_epoch,
+            voting_end_epoch: value.proposal.voting_end_epoch,
+            activation_epoch: value.proposal.activation_epoch,
+        })
+    }
+}
+
 impl TryFrom<PgfFundingProposal> for InitProposalData {
     type Error = ProposalError;
 
@@ -210,6 +260,61 @@ pub enum ProposalType {
     PGFSteward(BTreeSet<AddRemove<Address>>),
     /// PGF funding proposal
     PGFPayment(BTreeSet<PGFAction>),
+    /// PGF steward election proposal
+    PGFStewardElection(StewardElection),
+    /// Expedited emergency pause proposal
+    EmergencyPause(EmergencyPause),
+    /// Expedited governance proposal with wasm code, which may only change
+    /// the expedited keys
+    ExpeditedWithWasm(Hash),
+}
+
+/// The election of a new PGF steward set for a fixed term
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
+pub struct StewardElection {
+    /// The elected stewards, replacing the current steward set
+    pub stewards: BTreeSet<Address>,
+    /// The length of the term of the elected stewards in epochs, after which
+    /// they are removed
+    pub term: u64,
+}
+
+/// The emergency pause of the transfers of some modules for a number of
+/// epochs. A pause replaces the current pause of its modules, so that it can
+/// renew them, and a pause of zero epochs lifts them.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+)]
+pub struct EmergencyPause {
+    /// The paused modules
+    pub modules: BTreeSet<PausableModule>,
+    /// The number of epochs from the activation of the proposal after which
+    /// the pause expires
+    pub epochs: u64,
 }
 
 /// An add or remove action for PGF
@@ -441,9 +546,32 @@ impl ProposalType {
         matches!(self, ProposalType::DefaultWithWasm(_))
     }
 
+    /// Check if the proposal type is expedited with wasm
+    pub fn is_expedited_with_wasm(&self) -> bool {
+        matches!(self, ProposalType::ExpeditedWithWasm(_))
+    }
+
+    /// Check if the proposal has wasm code
+    pub fn has_code(&self) -> bool {
+        self.is_default_with_wasm() || self.is_expedited_with_wasm()
+    }
+
+    /// Check if the proposal is expedited, in which case it may have the
+    /// shortest voting period and grace period
+    pub fn is_expedited(&self) -> bool {
+        matches!(
+            self,
+            ProposalType::EmergencyPause(_)
+                | ProposalType::ExpeditedWithWasm(_)
+        )
+    }
+
     fn format_data(&self) -> String {
         match self {
-            ProposalType::DefaultWithWasm(hash) => format!("Hash: {}", &hash),
+            ProposalType::DefaultWithWasm(hash)
+            | ProposalType::ExpeditedWithWasm(hash) => {
+                format!("Hash: {}", &hash)
+            }
             ProposalType::Default => "".to_string(),
             ProposalType::PGFSteward(addresses) => format!(
                 "Addresses:{}",
@@ -459,6 +587,24 @@ impl ProposalType {
                     .map(|action| format!("\n  {}", &action))
                     .join("")
             ),
+            ProposalType::PGFStewardElection(election) => format!(
+                "Term: {} epochs\nStewards:{}",
+                election.term,
+                election
+                    .stewards
+                    .iter()
+                    .map(|steward| format!("\n  {}", &steward))
+                    .join("")
+            ),
+            ProposalType::EmergencyPause(pause) => format!(
+                "Duration: {} epochs\nModules:{}",
+                pause.epochs,
+                pause
+                    .modules
+                    .iter()
+                    .map(|module| format!("\n  {}", &module))
+                    .join("")
+            ),
         }
     }
 }
@@ -470,6 +616,13 @@ impl Display for ProposalType {
             ProposalType::DefaultWithWasm(_) => write!(f, "Default with Wasm"),
             ProposalType::PGFSteward(_) => write!(f, "PGF steward"),
             ProposalType::PGFPayment(_) => write!(f, "PGF funding"),
+            ProposalType::PGFStewardElection(_) => {
+                write!(f, "PGF steward election")
+            }
+            ProposalType::EmergencyPause(_) => write!(f, "Emergency pause"),
+            ProposalType::ExpeditedWithWasm(_) => {
+                write!(f, "Expedited with Wasm")
+            }
         }
     }
 }
@@ -657,6 +810,7 @@ pub mod testing {
     use proptest::{collection, prop_compose};
 
     use super::*;
+    use crate::pause::MAX_EMERGENCY_PAUSE_EPOCHS;
     use crate::storage::vote::testing::arb_proposal_vote;
 
     /// Generate an arbitrary add or removal of what's generated by the supplied
@@ -747,9 +901,41 @@ pub mod testing {
             .prop_map(ProposalType::PGFSteward),
             collection::btree_set(arb_pgf_action(), 0..10)
                 .prop_map(ProposalType::PGFPayment),
+            arb_steward_election().prop_map(ProposalType::PGFStewardElection),
+            arb_emergency_pause().prop_map(ProposalType::EmergencyPause),
+            arb_hash().prop_map(ProposalType::ExpeditedWithWasm),
         ]
     }
 
+    /// Generate an arbitrary pausable module
+    pub fn arb_pausable_module() -> impl Strategy<Value = PausableModule> {
+        prop_oneof![
+            Just(PausableModule::IbcTransfers),
+            Just(PausableModule::BridgePool),
+            Just(PausableModule::MaspTransfers),
+        ]
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary emergency pause
+        pub fn arb_emergency_pause()(
+            modules in collection::btree_set(arb_pausable_module(), 1..4),
+            epochs in 0..=MAX_EMERGENCY_PAUSE_EPOCHS,
+        ) -> EmergencyPause {
+            EmergencyPause { modules, epochs }
+        }
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary PGF steward election
+        pub fn arb_steward_election()(
+            stewards in collection::btree_set(arb_non_internal_address(), 1..10),
+            term in 1..100_u64,
+        ) -> StewardElection {
+            StewardElection { stewards, term }
+        }
+    }
+
     prop_compose! {
         /// Generate a proposal initialization
         pub fn arb_init_proposal()(
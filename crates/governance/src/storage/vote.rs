@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::address::Address;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
@@ -79,6 +80,26 @@ impl TryFrom<String> for ProposalVote {
     }
 }
 
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+/// A delegation of the governance voting power of a delegator
+pub struct VotingDelegationData {
+    /// The address whose voting power is delegated
+    pub delegator: Address,
+    /// The address that votes on behalf of the delegator, or `None` to
+    /// revoke the delegation
+    pub delegate: Option<Address>,
+}
+
 #[cfg(any(test, feature = "testing"))]
 /// Testing helpers and and strategies for governance proposals
 pub mod testing {
@@ -77,6 +77,9 @@ pub enum TallyType {
     /// Represent a tally type for proposal requiring less than 1/2 of nay
     /// votes over at least 1/3 of the voting power
     LessOneHalfOverOneThirdNay,
+    /// Represent a tally type for proposal requiring 2/3 of the total voting
+    /// power to vote yay
+    TwoThirdsOfTotalYay,
 }
 
 impl TallyType {
@@ -86,6 +89,13 @@ impl TallyType {
             (ProposalType::Default, _) => TallyType::TwoThirds,
             (ProposalType::DefaultWithWasm(_), _) => TallyType::TwoThirds,
             (ProposalType::PGFSteward(_), _) => TallyType::OneHalfOverOneThird,
+            (ProposalType::PGFStewardElection(_), _) => {
+                TallyType::OneHalfOverOneThird
+            }
+            (ProposalType::EmergencyPause(_), _)
+            | (ProposalType::ExpeditedWithWasm(_), _) => {
+                TallyType::TwoThirdsOfTotalYay
+            }
             (ProposalType::PGFPayment(_), true) => {
                 TallyType::LessOneHalfOverOneThirdNay
             }
@@ -94,6 +104,46 @@ impl TallyType {
             }
         }
     }
+
+    /// Get the min voting power, including the abstained votes, that must
+    /// vote out of the total voting power. With a
+    /// [`TallyType::LessOneHalfOverOneThirdNay`] tally, the proposal also
+    /// passes if less voting power voted.
+    pub fn quorum(
+        &self,
+        total_voting_power: VotePower,
+    ) -> Result<VotePower, arith::Error> {
+        match self {
+            TallyType::TwoThirds | TallyType::TwoThirdsOfTotalYay => {
+                total_voting_power.mul_ceil(Dec::two_thirds())
+            }
+            TallyType::OneHalfOverOneThird
+            | TallyType::LessOneHalfOverOneThirdNay => {
+                total_voting_power.mul_ceil(Dec::one_third())
+            }
+        }
+    }
+
+    /// Describe the conditions for a proposal to pass with this tally
+    pub fn pass_conditions(&self) -> &'static str {
+        match self {
+            TallyType::TwoThirds => {
+                "at least 2/3 of the total voting power votes and at least 2/3 \
+                 of the yay and nay votes are yay"
+            }
+            TallyType::OneHalfOverOneThird => {
+                "at least 1/3 of the total voting power votes and there are \
+                 more yay than nay votes"
+            }
+            TallyType::LessOneHalfOverOneThirdNay => {
+                "less than 1/3 of the total voting power votes or there are \
+                 more yay than nay votes"
+            }
+            TallyType::TwoThirdsOfTotalYay => {
+                "at least 2/3 of the total voting power votes yay"
+            }
+        }
+    }
 }
 
 /// The result of a proposal
@@ -182,6 +232,10 @@ impl TallyResult {
 
                 less_than_one_third || more_than_half_voted_yay
             }
+            TallyType::TwoThirdsOfTotalYay => {
+                yay_voting_power
+                    >= total_voting_power.mul_ceil(Dec::two_thirds())?
+            }
         };
 
         Ok(if passed { Self::Passed } else { Self::Rejected })
@@ -216,6 +270,15 @@ pub struct ProposalResult {
 }
 
 impl ProposalResult {
+    /// Get the voting power that voted, including the abstained votes
+    pub fn total_voted_power(&self) -> Result<VotePower, arith::Error> {
+        TallyResult::get_total_voted_power(
+            self.total_yay_power,
+            self.total_nay_power,
+            self.total_abstain_power,
+        )
+    }
+
     /// Return true if at least 2/3 of the total voting power voted and at least
     /// two third of the non-abstained voting power voted nay.
     /// Returns `false` if any arithmetic fails.
@@ -244,13 +307,8 @@ impl ProposalResult {
 
 impl Display for ProposalResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let threshold = match self.tally_type {
-            TallyType::TwoThirds => {
-                self.total_voting_power.mul_ceil(Dec::two_thirds())
-            }
-            _ => self.total_voting_power.mul_ceil(Dec::one_third()),
-        }
-        .unwrap();
+        let threshold =
+            self.tally_type.quorum(self.total_voting_power).unwrap();
 
         let thresh_frac = Dec::try_from(threshold)
             .unwrap()
@@ -1457,4 +1515,88 @@ mod test {
             5.into()
         ));
     }
+
+    #[test]
+    fn test_tally_quorum() {
+        let total = token::Amount::from_u64(100);
+        assert_eq!(
+            TallyType::TwoThirds.quorum(total).unwrap(),
+            token::Amount::from_u64(67)
+        );
+        for tally_type in [
+            TallyType::OneHalfOverOneThird,
+            TallyType::LessOneHalfOverOneThirdNay,
+        ] {
+            assert_eq!(
+                tally_type.quorum(total).unwrap(),
+                token::Amount::from_u64(34)
+            );
+        }
+
+        let mut proposal_votes = ProposalVotes::default();
+        proposal_votes.add_validator(
+            &address::testing::established_address_1(),
+            token::Amount::from_u64(40),
+            ProposalVote::Yay,
+        );
+        proposal_votes.add_validator(
+            &address::testing::established_address_2(),
+            token::Amount::from_u64(10),
+            ProposalVote::Abstain,
+        );
+        let proposal_result = compute_proposal_result(
+            proposal_votes,
+            total,
+            TallyType::TwoThirds,
+        )
+        .unwrap();
+        assert_eq!(
+            proposal_result.total_voted_power().unwrap(),
+            token::Amount::from_u64(50)
+        );
+        assert!(matches!(proposal_result.result, TallyResult::Rejected));
+    }
+
+    #[test]
+    fn test_two_thirds_of_total_yay_tally() {
+        let total = token::Amount::from_u64(100);
+        assert_eq!(
+            TallyType::TwoThirdsOfTotalYay.quorum(total).unwrap(),
+            token::Amount::from_u64(67)
+        );
+
+        // The yay votes must be at least 2/3 of the total voting power, even
+        // without any nay votes
+        let mut proposal_votes = ProposalVotes::default();
+        proposal_votes.add_validator(
+            &address::testing::established_address_1(),
+            token::Amount::from_u64(60),
+            ProposalVote::Yay,
+        );
+        proposal_votes.add_validator(
+            &address::testing::established_address_2(),
+            token::Amount::from_u64(30),
+            ProposalVote::Abstain,
+        );
+        let proposal_result = compute_proposal_result(
+            proposal_votes.clone(),
+            total,
+            TallyType::TwoThirdsOfTotalYay,
+        )
+        .unwrap();
+        assert!(matches!(proposal_result.result, TallyResult::Rejected));
+
+        proposal_votes.add_validator(
+            &address::testing::established_address_3(),
+            token::Amount::from_u64(7),
+            ProposalVote::Yay,
+        );
+        let proposal_result = compute_proposal_result(
+            proposal_votes,
+            total,
+            TallyType::TwoThirdsOfTotalYay,
+        )
+        .unwrap();
+        assert!(matches!(proposal_result.result, TallyResult::Passed));
+    }
 }
@@ -681,6 +681,30 @@ pub trait IbcCommonContext: IbcStorageContext {
         Ok(())
     }
 
+    /// Write the full denom trace of an IBC token, if it's not registered yet
+    fn store_ibc_denom_trace(
+        &mut self,
+        trace_hash: impl AsRef<str>,
+        trace: impl AsRef<str>,
+    ) -> Result<()> {
+        let key = storage::ibc_denom_trace_key(trace_hash);
+        let has_key = self.has_key(&key).map_err(|_| ChannelError::Other {
+            description: format!(
+                "Reading the IBC denom trace failed: Key {key}"
+            ),
+        })?;
+        if !has_key {
+            self.write(&key, trace.as_ref()).map_err(|_| {
+                ChannelError::Other {
+                    description: format!(
+                        "Writing the denom trace failed: Key {key}",
+                    ),
+                }
+            })?;
+        }
+        Ok(())
+    }
+
     /// Get the NFT class
     fn nft_class(
         &self,
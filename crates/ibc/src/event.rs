@@ -250,6 +250,7 @@ impl EventAttributeEntry<'static> for PacketSequence {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "packet_sequence";
+    const VALUE_TYPE: &'static str = "ibc-sequence";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -264,6 +265,7 @@ impl EventAttributeEntry<'static> for PacketSrcPort {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "packet_src_port";
+    const VALUE_TYPE: &'static str = "ibc-port-id";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -278,6 +280,7 @@ impl EventAttributeEntry<'static> for PacketSrcChannel {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "packet_src_channel";
+    const VALUE_TYPE: &'static str = "ibc-channel-id";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -292,6 +295,7 @@ impl EventAttributeEntry<'static> for PacketDstPort {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "packet_dst_port";
+    const VALUE_TYPE: &'static str = "ibc-port-id";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -306,6 +310,7 @@ impl EventAttributeEntry<'static> for PacketDstChannel {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "packet_dst_channel";
+    const VALUE_TYPE: &'static str = "ibc-channel-id";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -320,6 +325,7 @@ impl EventAttributeEntry<'static> for ClientId {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = CLIENT_ID_ATTRIBUTE_KEY;
+    const VALUE_TYPE: &'static str = "ibc-client-id";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -334,6 +340,7 @@ impl EventAttributeEntry<'static> for ConsensusHeights {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = CONSENSUS_HEIGHTS_ATTRIBUTE_KEY;
+    const VALUE_TYPE: &'static str = "ibc-height";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -348,6 +355,7 @@ impl EventAttributeEntry<'static> for ConnectionId {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "connection_id";
+    const VALUE_TYPE: &'static str = "ibc-connection-id";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -362,6 +370,7 @@ impl<'data> EventAttributeEntry<'data> for PacketData<'data> {
     type ValueOwned = String;
 
     const KEY: &'static str = "packet_data";
+    const VALUE_TYPE: &'static str = "string";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -405,6 +414,7 @@ impl EventAttributeEntry<'static> for PacketTimeoutHeight {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "packet_timeout_height";
+    const VALUE_TYPE: &'static str = "ibc-timeout-height";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -419,6 +429,7 @@ impl EventAttributeEntry<'static> for PacketTimeoutTimestamp {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "packet_timeout_timestamp";
+    const VALUE_TYPE: &'static str = "ibc-timestamp";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -433,6 +444,7 @@ impl EventAttributeEntry<'static> for ChannelId {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "channel_id";
+    const VALUE_TYPE: &'static str = "ibc-channel-id";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -447,6 +459,7 @@ impl<'ack> EventAttributeEntry<'ack> for PacketAck<'ack> {
     type ValueOwned = String;
 
     const KEY: &'static str = "packet_ack";
+    const VALUE_TYPE: &'static str = "string";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -0,0 +1,108 @@
+//! Hooks triggered by the memo of incoming fungible token transfers.
+//!
+//! A memo hook is an allowed tx code registered by governance under a name at
+//! the [`namada_parameters::storage::get_memo_hook_key`]. The memo of a
+//! transfer to a transparent address can request a registered hook, e.g.
+//!
+//! ```json
+//! {"namada_hook": {"name": "auto_bond", "args": {"validator": "tnam1..."}}}
+//! ```
+//!
+//! When the transfer is received, the fee of the hook, i.e. its gas limit
+//! multiplied by the minimum gas price of the received token, is paid from
+//! the received amount to the fee payer of the tx that relayed the packet.
+//! The hook is then called within the same tx with an [`IbcHookData`] and
+//! the hook's gas limit. The VPs of the accounts modified by the hook must
+//! accept its changes as for any other tx. As the receiver doesn't sign the
+//! tx, its VP accepts that the hook bonds the received amount of the native
+//! token on its behalf, as recorded by the `IbcAction::MemoHook` tx action. A
+//! failing hook fails the receiving of the packet, so that the transfer is
+//! eventually refunded on the source chain.
+
+use namada_core::address::Address;
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::hash::Hash;
+use namada_core::token::Amount;
+use serde::Deserialize;
+
+/// A hook registered for incoming transfers
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct MemoHook {
+    /// The hash of the tx code of the hook, which must be allowed
+    pub code_hash: Hash,
+    /// The maximum gas the hook can use
+    pub gas_limit: u64,
+}
+
+/// A hook requested in the memo of a transfer
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct MemoHookRequest {
+    /// The name of the registered hook
+    pub name: String,
+    /// The arguments of the hook
+    #[serde(default)]
+    pub args: serde_json::Value,
+}
+
+/// The data given to a memo hook
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct IbcHookData {
+    /// The receiver of the transfer
+    pub receiver: Address,
+    /// The received token
+    pub token: Address,
+    /// The received amount minus the fee of the hook
+    pub amount: Amount,
+    /// The JSON encoded arguments from the memo
+    pub args: String,
+}
+
+/// A memo hook to be called after an IBC message has been executed
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct PendingMemoHook {
+    /// The hash of the tx code of the hook
+    pub code_hash: Hash,
+    /// The maximum gas the hook can use
+    pub gas_limit: u64,
+    /// The data of the hook
+    pub data: IbcHookData,
+}
+
+/// Parse the hook requested in a memo, if any. Other fields of the memo,
+/// e.g. for packet forwarding, are ignored.
+pub fn parse_memo_hook(memo: &str) -> Option<MemoHookRequest> {
+    #[derive(Deserialize)]
+    struct Memo {
+        namada_hook: MemoHookRequest,
+    }
+    serde_json::from_str::<Memo>(memo)
+        .ok()
+        .map(|memo| memo.namada_hook)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_memo_hook() {
+        let request = parse_memo_hook(
+            r#"{"namada_hook": {"name": "auto_bond", "args": {"v": 1}}}"#,
+        )
+        .unwrap();
+        assert_eq!(request.name, "auto_bond");
+        assert_eq!(request.args.to_string(), r#"{"v":1}"#);
+
+        let request = parse_memo_hook(
+            r#"{"forward": {}, "namada_hook": {"name": "auto_shield"}}"#,
+        )
+        .unwrap();
+        assert_eq!(request.name, "auto_shield");
+        assert!(request.args.is_null());
+
+        assert!(parse_memo_hook("").is_none());
+        assert!(parse_memo_hook("a plain memo").is_none());
+        assert!(parse_memo_hook(r#"{"forward": {}}"#).is_none());
+        assert!(parse_memo_hook(r#"{"namada_hook": "auto_bond"}"#).is_none());
+    }
+}
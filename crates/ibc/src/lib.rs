@@ -3,6 +3,7 @@
 mod actions;
 pub mod context;
 pub mod event;
+pub mod hook;
 pub mod parameters;
 pub mod storage;
 
@@ -22,6 +23,7 @@ pub use context::token_transfer::TokenTransferContext;
 pub use context::transfer_mod::{ModuleWrapper, TransferModule};
 use context::IbcContext;
 pub use context::ValidationParams;
+use hook::{IbcHookData, MemoHook, PendingMemoHook};
 use namada_core::address::{Address, MASP};
 use namada_core::ibc::apps::nft_transfer::handler::{
     send_nft_transfer_execute, send_nft_transfer_validate,
@@ -56,8 +58,10 @@ use namada_core::ibc::core::router::types::error::RouterError;
 use namada_core::ibc::primitives::proto::Any;
 pub use namada_core::ibc::*;
 use namada_core::masp::PaymentAddress;
+use namada_core::uint::Uint;
 use namada_events::extend::{ReadFromEventAttributes, Success as SuccessAttr};
-use namada_token::Transfer;
+use namada_storage::StorageRead;
+use namada_token::{read_denom, Amount, Denomination, Transfer};
 use prost::Message;
 use thiserror::Error;
 
@@ -82,6 +86,8 @@ pub enum Error {
     ChainId(IdentifierError),
     #[error("Handling MASP transaction error: {0}")]
     MaspTx(String),
+    #[error("Memo hook error: {0}")]
+    MemoHook(String),
 }
 
 /// IBC actions to handle IBC operations
@@ -205,6 +211,114 @@ where
         }
     }
 
+    /// Get the memo hook requested by a fungible token transfer received
+    /// with the executed IBC transaction, if any. The fee of the hook is paid
+    /// from the received amount to the given fee payer. A hook that isn't
+    /// registered or whose fee can't be paid with the received token is
+    /// skipped without failing the transfer.
+    pub fn memo_hook(
+        &mut self,
+        tx_data: &[u8],
+        fee_payer: &Address,
+    ) -> Result<Option<PendingMemoHook>, Error> {
+        let msg = match decode_message(tx_data)? {
+            IbcMessage::RecvPacket(msg) => msg.message,
+            IbcMessage::Envelope(envelope) => match *envelope {
+                MsgEnvelope::Packet(PacketMsg::Recv(msg)) => msg,
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        let Ok(data) = serde_json::from_slice::<PacketData>(&msg.packet.data)
+        else {
+            return Ok(None);
+        };
+        let Some(request) = hook::parse_memo_hook(data.memo.as_ref()) else {
+            return Ok(None);
+        };
+        if !self.is_receiving_success()? {
+            return Ok(None);
+        }
+        // Hooks only act for transparent receivers
+        let Ok(receiver) = Address::decode(data.receiver.as_ref()) else {
+            self.log_skipped_hook(
+                &request.name,
+                "the receiver isn't transparent",
+            );
+            return Ok(None);
+        };
+
+        let key = namada_parameters::storage::get_memo_hook_key(&request.name);
+        let Some(memo_hook) = self
+            .ctx
+            .inner
+            .borrow()
+            .read::<MemoHook>(&key)
+            .map_err(|e| Error::MemoHook(e.to_string()))?
+        else {
+            self.log_skipped_hook(&request.name, "it isn't registered");
+            return Ok(None);
+        };
+
+        let token = received_ibc_token(
+            data.token.denom.to_string(),
+            &msg.packet.port_id_on_a,
+            &msg.packet.chan_id_on_a,
+            &msg.packet.port_id_on_b,
+            &msg.packet.chan_id_on_b,
+        )?;
+        let denom = read_denom(&*self.ctx.inner.borrow(), &token)
+            .map_err(|e| Error::MemoHook(e.to_string()))?
+            .unwrap_or(Denomination(0));
+        let uint_amount =
+            Uint(primitive_types::U256::from(data.token.amount).0);
+        let amount = Amount::from_uint(uint_amount, denom)
+            .map_err(|e| Error::MemoHook(e.to_string()))?;
+
+        let Some(gas_price) =
+            namada_parameters::read_gas_cost(&*self.ctx.inner.borrow(), &token)
+                .map_err(|e| Error::MemoHook(e.to_string()))?
+        else {
+            self.log_skipped_hook(
+                &request.name,
+                "the received token can't pay for gas",
+            );
+            return Ok(None);
+        };
+        let Some((fee, amount)) = gas_price
+            .checked_mul(memo_hook.gas_limit)
+            .and_then(|fee| Some((fee, amount.checked_sub(fee)?)))
+        else {
+            self.log_skipped_hook(
+                &request.name,
+                "the received amount doesn't cover its fee",
+            );
+            return Ok(None);
+        };
+        self.ctx
+            .inner
+            .borrow_mut()
+            .transfer_token(&receiver, fee_payer, &token, fee)
+            .map_err(|e| Error::MemoHook(e.to_string()))?;
+
+        Ok(Some(PendingMemoHook {
+            code_hash: memo_hook.code_hash,
+            gas_limit: memo_hook.gas_limit,
+            data: IbcHookData {
+                receiver,
+                token,
+                amount,
+                args: request.args.to_string(),
+            },
+        }))
+    }
+
+    fn log_skipped_hook(&self, name: &str, reason: &str) {
+        self.ctx.inner.borrow().log_string(format!(
+            "Skipped the memo hook {name} because {reason}"
+        ));
+    }
+
     /// Store the trace path when transfer with MsgRecvPacket
     fn store_trace(&mut self, msg: &IbcMsgRecvPacket) -> Result<(), Error> {
         // Get the IBC trace, and the receiver from the packet data
@@ -265,6 +379,16 @@ where
             // restored from the trace hash.
             for ibc_trace in ibc_traces {
                 let trace_hash = storage::calc_hash(&ibc_trace);
+                self.ctx
+                    .inner
+                    .borrow_mut()
+                    .store_ibc_denom_trace(&trace_hash, &ibc_trace)
+                    .map_err(|e| {
+                        Error::Trace(format!(
+                            "Writing the IBC denom trace failed: {}",
+                            e
+                        ))
+                    })?;
                 self.ctx
                     .inner
                     .borrow_mut()
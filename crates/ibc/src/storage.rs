@@ -4,6 +4,9 @@ use std::str::FromStr;
 
 use namada_core::address::{Address, InternalAddress, HASH_LEN, SHA_HASH_LEN};
 use namada_core::ibc::apps::nft_transfer::types::{PrefixedClassId, TokenId};
+use namada_core::ibc::core::channel::types::commitment::{
+    AcknowledgementCommitment, PacketCommitment,
+};
 use namada_core::ibc::core::client::types::Height;
 use namada_core::ibc::core::host::types::identifiers::{
     ChannelId, ClientId, ConnectionId, PortId, Sequence,
@@ -17,7 +20,7 @@ use namada_core::ibc::IbcTokenHash;
 use namada_core::storage::{DbKeySeg, Key, KeySeg};
 use namada_core::token::Amount;
 use namada_events::{EmitEvents, EventLevel};
-use namada_state::{StorageRead, StorageResult, StorageWrite};
+use namada_state::{StorageError, StorageRead, StorageResult, StorageWrite};
 use namada_token as token;
 use namada_token::event::{TokenEvent, TokenOperation, UserAccount};
 use sha2::{Digest, Sha256};
@@ -31,6 +34,7 @@ const CONNECTIONS_COUNTER_PREFIX: &str = "connections";
 const CHANNELS_COUNTER_PREFIX: &str = "channelEnds";
 const COUNTER_SEG: &str = "counter";
 const TRACE: &str = "ibc_trace";
+const DENOM_TRACE: &str = "denom_trace";
 const NFT_CLASS: &str = "nft_class";
 const NFT_METADATA: &str = "nft_meta";
 const PARAMS: &str = "params";
@@ -267,6 +271,78 @@ pub fn ack_key(
         .expect("Creating a key for the ack shouldn't fail")
 }
 
+/// The next sequence numbers stored for a channel
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SequenceKind {
+    /// The sequence of the next packet to send
+    Send,
+    /// The sequence of the next packet to receive on an ordered channel
+    Recv,
+    /// The sequence of the next acknowledgement to process on an ordered
+    /// channel
+    Ack,
+}
+
+/// Returns a key for the next sequence of the given kind
+pub fn next_sequence_key(
+    kind: SequenceKind,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Key {
+    match kind {
+        SequenceKind::Send => next_sequence_send_key(port_id, channel_id),
+        SequenceKind::Recv => next_sequence_recv_key(port_id, channel_id),
+        SequenceKind::Ack => next_sequence_ack_key(port_id, channel_id),
+    }
+}
+
+/// Read the next sequence of the given kind. Returns the initial sequence if
+/// it has never been used.
+pub fn read_next_sequence<S: StorageRead>(
+    storage: &S,
+    kind: SequenceKind,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> StorageResult<Sequence> {
+    let key = next_sequence_key(kind, port_id, channel_id);
+    match storage.read_bytes(&key)? {
+        Some(value) => {
+            let value: [u8; 8] = value.try_into().map_err(|_| {
+                StorageError::new_alloc(format!(
+                    "The sequence value wasn't u64: Key {key}"
+                ))
+            })?;
+            Ok(u64::from_be_bytes(value).into())
+        }
+        None => Ok(1.into()),
+    }
+}
+
+/// Read the commitment of a sent packet, which exists until the packet has
+/// been acknowledged or timed out
+pub fn read_packet_commitment<S: StorageRead>(
+    storage: &S,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> StorageResult<Option<PacketCommitment>> {
+    let key = commitment_key(port_id, channel_id, sequence);
+    Ok(storage.read_bytes(&key)?.map(PacketCommitment::from))
+}
+
+/// Read the commitment of the acknowledgement written for a received packet
+pub fn read_packet_ack<S: StorageRead>(
+    storage: &S,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> StorageResult<Option<AcknowledgementCommitment>> {
+    let key = ack_key(port_id, channel_id, sequence);
+    Ok(storage
+        .read_bytes(&key)?
+        .map(AcknowledgementCommitment::from))
+}
+
 /// Returns a key for the timestamp for the client update
 pub fn client_update_timestamp_key(client_id: &ClientId) -> Key {
     let path = format!("clients/{}/update_timestamp", client_id);
@@ -480,6 +556,17 @@ pub fn ibc_trace_key(
         .expect("Cannot obtain a storage key")
 }
 
+/// The storage key of the full denom trace of an IBC token, e.g.
+/// `transfer/channel-0/uatom`, with the hash of the trace. Unlike the
+/// [`ibc_trace_key`], it doesn't depend on an owner.
+pub fn ibc_denom_trace_key(token_hash: impl AsRef<str>) -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&DENOM_TRACE.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&token_hash.as_ref().to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Hash the denom
 #[inline]
 pub fn calc_hash(denom: impl AsRef<str>) -> String {
@@ -542,6 +629,26 @@ pub fn is_ibc_trace_key(key: &Key) -> Option<(String, String)> {
     }
 }
 
+/// Returns the token hash if the given key is the denom trace key
+pub fn is_ibc_denom_trace_key(key: &Key) -> Option<String> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(hash),
+        ] => {
+            if addr == &Address::Internal(InternalAddress::Ibc)
+                && prefix == DENOM_TRACE
+            {
+                Some(hash.clone())
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
 /// Returns true if the given key is for an IBC counter for clients,
 /// connections, or channelEnds
 pub fn is_ibc_counter_key(key: &Key) -> bool {
@@ -7,6 +7,10 @@ use std::collections::BTreeSet;
 use borsh::BorshDeserialize;
 use namada_core::arith::checked;
 use namada_core::booleans::{BoolResultUnitExt, ResultBoolExt};
+use namada_governance::parameters::{
+    EXPEDITED_MIN_PROPOSAL_GRACE_EPOCHS, EXPEDITED_MIN_PROPOSAL_VOTING_PERIOD,
+};
+use namada_governance::pause::MAX_EMERGENCY_PAUSE_EPOCHS;
 use namada_governance::storage::proposal::{
     AddRemove, PGFAction, ProposalType,
 };
@@ -108,6 +112,7 @@ where
         }
 
         // Check action authorization
+        let mut delegating_voters: BTreeSet<Address> = Default::default();
         for action in actions {
             match action {
                 Action::Gov(gov_action) => match gov_action {
@@ -133,6 +138,18 @@ where
                             ));
                         }
                     }
+                    GovAction::DelegateVotingPower { delegator } => {
+                        if !verifiers.contains(&delegator) {
+                            tracing::info!(
+                                "Unauthorized GovAction::DelegateVotingPower"
+                            );
+                            return Err(Error::Unauthorized(
+                                "DelegateVotingPower",
+                                delegator,
+                            ));
+                        }
+                        delegating_voters.insert(delegator);
+                    }
                 },
                 _ => {
                     // Other actions are not relevant to Governance VP
@@ -178,6 +195,10 @@ where
                     self.is_valid_proposal_commit()
                 }
                 (KeyType::PARAMETER, _) => self.is_valid_parameter(tx_data),
+                (KeyType::PAUSE, _) => self.is_valid_pause(tx_data),
+                (KeyType::VOTING_DELEGATE, _) => {
+                    self.is_valid_voting_delegate(key, &delegating_voters)
+                }
                 (KeyType::BALANCE, _) => self.is_valid_balance(&native_token),
                 (KeyType::UNKNOWN_GOVERNANCE, _) => {
                     Err(native_vp::Error::new_alloc(format!(
@@ -542,6 +563,47 @@ where
                     .into());
                 }
             }
+            ProposalType::PGFStewardElection(election) => {
+                if election.stewards.is_empty() {
+                    return Err(native_vp::Error::new_const(
+                        "A steward election must elect at least one steward",
+                    )
+                    .into());
+                }
+                if election.term == 0 {
+                    return Err(native_vp::Error::new_const(
+                        "The term of the elected stewards must be at least \
+                         one epoch",
+                    )
+                    .into());
+                }
+                if election.stewards.len() >= MAX_PGF_ACTIONS {
+                    return Err(native_vp::Error::new_alloc(format!(
+                        "Maximum number of elected stewards \
+                         ({MAX_PGF_ACTIONS}) exceeded ({})",
+                        election.stewards.len()
+                    ))
+                    .into());
+                }
+                Ok(())
+            }
+            ProposalType::EmergencyPause(pause) => {
+                if pause.modules.is_empty() {
+                    return Err(native_vp::Error::new_const(
+                        "An emergency pause must pause at least one module",
+                    )
+                    .into());
+                }
+                if pause.epochs > MAX_EMERGENCY_PAUSE_EPOCHS {
+                    return Err(native_vp::Error::new_alloc(format!(
+                        "An emergency pause lasts {} epochs, but it cannot \
+                         last more than {MAX_EMERGENCY_PAUSE_EPOCHS} epochs",
+                        pause.epochs
+                    ))
+                    .into());
+                }
+                Ok(())
+            }
             ProposalType::PGFPayment(fundings) => {
                 // collect all the funding target that we have to add and are
                 // unique
@@ -620,16 +682,40 @@ where
         }
     }
 
+    /// Check if a proposal is expedited
+    fn is_expedited(&self, proposal_id: u64) -> Result<bool> {
+        let proposal_type_key = gov_storage::get_proposal_type_key(proposal_id);
+        let proposal_type: ProposalType =
+            self.force_read(&proposal_type_key, ReadType::Post)?;
+        Ok(proposal_type.is_expedited())
+    }
+
+    /// Read the minimum voting period of a proposal, which is shorter for
+    /// the expedited proposals
+    fn min_voting_period(
+        &self,
+        proposal_id: u64,
+        min_period_parameter_key: &Key,
+    ) -> Result<u64> {
+        let min_period: u64 =
+            self.force_read(min_period_parameter_key, ReadType::Pre)?;
+        Ok(if self.is_expedited(proposal_id)? {
+            min_period.min(EXPEDITED_MIN_PROPOSAL_VOTING_PERIOD)
+        } else {
+            min_period
+        })
+    }
+
     /// Validate a proposal code
     pub fn is_valid_proposal_code(&self, proposal_id: u64) -> Result<()> {
         let proposal_type_key = gov_storage::get_proposal_type_key(proposal_id);
         let proposal_type: ProposalType =
             self.force_read(&proposal_type_key, ReadType::Post)?;
 
-        if !proposal_type.is_default_with_wasm() {
+        if !proposal_type.has_code() {
             return Err(native_vp::Error::new_alloc(format!(
                 "Proposal with id {proposal_id} modified a proposal code key, \
-                 but its type is not default.",
+                 but its type has no code.",
             ))
             .into());
         }
@@ -696,6 +782,11 @@ where
             self.force_read(&activation_epoch_key, ReadType::Post)?;
         let min_grace_epochs: u64 =
             self.force_read(&min_grace_epochs_key, ReadType::Pre)?;
+        let min_grace_epochs = if self.is_expedited(proposal_id)? {
+            min_grace_epochs.min(EXPEDITED_MIN_PROPOSAL_GRACE_EPOCHS)
+        } else {
+            min_grace_epochs
+        };
         let max_proposal_period: u64 =
             self.force_read(&max_proposal_period, ReadType::Pre)?;
 
@@ -786,7 +877,7 @@ where
         let end_epoch: Epoch =
             self.force_read(&end_epoch_key, ReadType::Post)?;
         let min_period: u64 =
-            self.force_read(&min_period_parameter_key, ReadType::Pre)?;
+            self.min_voting_period(proposal_id, &min_period_parameter_key)?;
 
         if end_epoch <= start_epoch {
             return Err(native_vp::Error::new_alloc(format!(
@@ -887,7 +978,7 @@ where
         let end_epoch: Epoch =
             self.force_read(&end_epoch_key, ReadType::Post)?;
         let min_period: u64 =
-            self.force_read(&min_period_parameter_key, ReadType::Pre)?;
+            self.min_voting_period(proposal_id, &min_period_parameter_key)?;
         let max_period: u64 =
             self.force_read(&max_period_parameter_key, ReadType::Pre)?;
 
@@ -1140,6 +1231,68 @@ where
         )
     }
 
+    /// Validate a change of the pause of a module
+    pub fn is_valid_pause(&self, tx: &Tx) -> Result<()> {
+        tx.data().map_or_else(
+            || {
+                Err(native_vp::Error::new_const(
+                    "Emergency pause changes require tx data to be present",
+                )
+                .into())
+            },
+            |data| {
+                is_proposal_accepted(&self.ctx.pre(), data.as_ref())
+                    .map_err(Error::NativeVpError)?
+                    .ok_or_else(|| {
+                        native_vp::Error::new_const(
+                            "Emergency pause changes can only be performed by \
+                             a governance proposal that has been accepted",
+                        )
+                        .into()
+                    })
+            },
+        )
+    }
+
+    /// Validate a voting delegate key
+    pub fn is_valid_voting_delegate(
+        &self,
+        key: &Key,
+        delegating_voters: &BTreeSet<Address>,
+    ) -> Result<()> {
+        let delegator = gov_storage::is_voting_delegate_key(key).ok_or(
+            native_vp::Error::new_alloc(format!(
+                "Failed to parse a delegator from the voting delegate key \
+                 {key}",
+            )),
+        )?;
+
+        if !delegating_voters.contains(delegator) {
+            return Err(native_vp::Error::new_alloc(format!(
+                "The voting delegate of {delegator} changed without a \
+                 corresponding action"
+            ))
+            .into());
+        }
+
+        // Validators vote with their own stake
+        if is_validator(&self.ctx.pre(), delegator)? {
+            return Err(native_vp::Error::new_alloc(format!(
+                "The validator {delegator} cannot delegate its voting power"
+            ))
+            .into());
+        }
+
+        let delegate: Option<Address> = self.ctx.read_post(key)?;
+        if delegate.as_ref() == Some(delegator) {
+            return Err(native_vp::Error::new_alloc(format!(
+                "The voting power of {delegator} cannot be delegated to itself"
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
     /// Check if a vote is from a validator
     pub fn is_validator(
         &self,
@@ -1246,6 +1399,10 @@ enum KeyType {
     #[allow(non_camel_case_types)]
     PARAMETER,
     #[allow(non_camel_case_types)]
+    VOTING_DELEGATE,
+    #[allow(non_camel_case_types)]
+    PAUSE,
+    #[allow(non_camel_case_types)]
     UNKNOWN_GOVERNANCE,
     #[allow(non_camel_case_types)]
     UNKNOWN,
@@ -1277,6 +1434,10 @@ impl KeyType {
             KeyType::COUNTER
         } else if gov_storage::is_parameter_key(key) {
             KeyType::PARAMETER
+        } else if gov_storage::is_voting_delegate_key(key).is_some() {
+            KeyType::VOTING_DELEGATE
+        } else if gov_storage::is_pause_key(key) {
+            KeyType::PAUSE
         } else if token::storage_key::is_balance_key(native_token, key)
             .is_some()
         {
@@ -1299,8 +1460,8 @@ mod test {
     use namada_governance::storage::keys::{
         get_activation_epoch_key, get_author_key, get_committing_proposals_key,
         get_content_key, get_counter_key, get_funds_key, get_proposal_type_key,
-        get_vote_proposal_key, get_voting_end_epoch_key,
-        get_voting_start_epoch_key,
+        get_vote_proposal_key, get_voting_delegate_key,
+        get_voting_end_epoch_key, get_voting_start_epoch_key,
     };
     use namada_governance::{ProposalType, ProposalVote, ADDRESS};
     use namada_proof_of_stake::bond_tokens;
@@ -3077,4 +3238,75 @@ mod test {
             Err(_)
         );
     }
+
+    #[test]
+    fn test_governance_voting_delegation() {
+        let mut state = init_storage();
+
+        let gas_meter = RefCell::new(VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        ));
+        let (vp_wasm_cache, _vp_cache_dir) =
+            wasm::compilation_cache::common::testing::cache();
+
+        let tx_index = TxIndex::default();
+
+        let delegate = Address::from(&keypair_1().ref_to());
+        let delegator = established_address_3();
+        let validator = established_address_1();
+
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.header.chain_id = state.in_mem().chain_id.clone();
+        tx.set_code(Code::new(vec![], None));
+        tx.set_data(Data::new(vec![]));
+
+        let delegate_voting_power =
+            |state: &mut TestState, delegator: &Address, delegate: &Address| {
+                state
+                    .push_action(Action::Gov(GovAction::DelegateVotingPower {
+                        delegator: delegator.clone(),
+                    }))
+                    .unwrap();
+                let key = get_voting_delegate_key(delegator);
+                state
+                    .write_log_mut()
+                    .write(&key, delegate.serialize_to_vec())
+                    .unwrap();
+                let keys_changed = BTreeSet::from([key]);
+                let verifiers = BTreeSet::from([delegator.clone()]);
+
+                let ctx = Ctx::new(
+                    &ADDRESS,
+                    state,
+                    &tx,
+                    &tx_index,
+                    &gas_meter,
+                    &keys_changed,
+                    &verifiers,
+                    vp_wasm_cache.clone(),
+                );
+                let result = GovernanceVp { ctx }.validate_tx(
+                    &tx,
+                    &keys_changed,
+                    &verifiers,
+                );
+                state.write_log_mut().drop_tx();
+                result
+            };
+
+        assert_matches!(
+            delegate_voting_power(&mut state, &delegator, &delegate),
+            Ok(_)
+        );
+        // The voting power cannot be delegated to the delegator itself
+        assert_matches!(
+            delegate_voting_power(&mut state, &delegator, &delegator),
+            Err(_)
+        );
+        // Validators vote with their own stake
+        assert_matches!(
+            delegate_voting_power(&mut state, &validator, &delegate),
+            Err(_)
+        );
+    }
 }
@@ -14,6 +14,8 @@ pub mod vp_host_fns;
 
 #[cfg(feature = "wasm-runtime")]
 pub use dry_run_tx::dry_run_tx;
+#[cfg(feature = "wasm-runtime")]
+pub use eval_vp::eval_vp;
 pub use {
     namada_gas as gas, namada_parameters as parameters,
     namada_tx_env as tx_env, namada_vp_env as vp_env,
@@ -42,7 +44,7 @@ mod dry_run_tx {
         CA: 'static + WasmCacheAccess + Sync,
     {
         use borsh_ext::BorshSerializeExt;
-        use namada_gas::{Gas, GasMetering, TxGasMeter};
+        use namada_gas::{Gas, GasBreakdown, GasMetering, TxGasMeter};
         use namada_tx::data::TxType;
         use namada_tx::Tx;
 
@@ -54,13 +56,14 @@ mod dry_run_tx {
         tx.validate_tx().into_storage_result()?;
 
         let mut cumulated_gas = Gas::default();
+        let mut wrapper_gas = None;
 
         // Wrapper dry run to allow estimating the gas cost of a transaction
         let tx_gas_meter = match tx.header().tx_type {
             TxType::Wrapper(wrapper) => {
                 let tx_gas_meter =
                     RefCell::new(TxGasMeter::new(wrapper.gas_limit.to_owned()));
-                protocol::apply_wrapper_tx(
+                let (_, gas_breakdown) = protocol::apply_wrapper_tx(
                     tx.clone(),
                     &wrapper,
                     None,
@@ -74,6 +77,10 @@ mod dry_run_tx {
                     None,
                 )
                 .into_storage_result()?;
+                wrapper_gas = Some(GasBreakdown {
+                    gas_limit: tx_gas_meter.borrow().tx_gas_limit,
+                    ..gas_breakdown
+                });
 
                 temp_state.write_log_mut().commit_tx();
                 cumulated_gas = tx_gas_meter.borrow_mut().get_tx_consumed_gas();
@@ -117,6 +124,11 @@ mod dry_run_tx {
             ))?;
         // Account gas for both inner and wrapper (if available)
         data.gas_used = cumulated_gas;
+        if let Some(wrapper_gas) = wrapper_gas {
+            data.gas_breakdown.gas_limit = wrapper_gas.gas_limit;
+            data.gas_breakdown.fee_settlement = wrapper_gas.fee_settlement;
+            data.gas_breakdown.wrapper = wrapper_gas.wrapper;
+        }
         // NOTE: the keys changed by the wrapper transaction (if any) are
         // not returned from this function
         let data = data.serialize_to_vec();
@@ -129,6 +141,156 @@ mod dry_run_tx {
     }
 }
 
+#[cfg(feature = "wasm-runtime")]
+mod eval_vp {
+    use std::cell::RefCell;
+
+    use borsh::BorshDeserialize;
+    use borsh_ext::BorshSerializeExt;
+    use namada_gas::{TxGasMeter, VpGasMeter};
+    use namada_sdk::queries::{
+        EncodedResponseQuery, EvalVpRequest, EvalVpResult, RequestCtx,
+        RequestQuery,
+    };
+    use namada_state::{DBIter, ResultExt, StorageHasher, DB};
+    use namada_tx::data::GasLimit;
+
+    use crate::hash::Hash;
+    use crate::storage::{Key, TxIndex};
+    use crate::vm::wasm::{self, TxCache, VpCache};
+    use crate::vm::{WasmCacheAccess, WasmCacheRwAccess};
+
+    /// The in-memory size of the throwaway wasm caches of an evaluation
+    const EVAL_VP_CACHE_BYTES: usize = 50 * 1024 * 1024;
+
+    /// Evaluate a VP against a tx applied to the state at a past height, at
+    /// most `max_height_range` blocks below the last committed height.
+    ///
+    /// The values of the keys modified since the height are written over the
+    /// last committed state in a temporary write log, so the in-memory block
+    /// metadata (e.g. the block hash and header) is that of the last
+    /// committed block. The VP code is written under its hash in the same
+    /// write log, so it doesn't have to be allowed. The wasm is compiled in a
+    /// throwaway cache, so that the submitted code never reaches the caches
+    /// of the node.
+    pub fn eval_vp<'a, D, H, CA>(
+        ctx: RequestCtx<'a, D, H, VpCache<CA>, TxCache<CA>>,
+        request: &RequestQuery,
+        max_gas: u64,
+        max_height_range: u64,
+    ) -> namada_state::StorageResult<EncodedResponseQuery>
+    where
+        D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+        H: 'static + StorageHasher + Sync,
+        CA: 'static + WasmCacheAccess + Sync,
+    {
+        let EvalVpRequest {
+            height,
+            address,
+            vp_code,
+            tx,
+        } = EvalVpRequest::try_from_slice(&request.data)
+            .into_storage_result()?;
+        tx.validate_tx().into_storage_result()?;
+
+        let historical = ctx.state.at_height(height);
+        let height = historical.height();
+        let last_height = ctx.state.in_mem().get_last_block_height();
+        if last_height.0.saturating_sub(height.0) > max_height_range {
+            return Err(namada_state::StorageError::new_alloc(format!(
+                "The height {height} is more than {max_height_range} blocks \
+                 below the last committed height {last_height}"
+            )));
+        }
+        let changes = historical.changes_since().into_storage_result()?;
+
+        let mut temp_state = ctx.state.with_temp_write_log();
+        for (key, value) in changes {
+            let write_log = temp_state.write_log_mut();
+            match value {
+                Some(value) => write_log.protocol_write(&key, value),
+                // The VPs of the accounts initialized since the height are
+                // left in place, as they cannot be deleted
+                None if key.is_validity_predicate().is_some() => Ok(()),
+                None => write_log.protocol_delete(&key),
+            }
+            .into_storage_result()?;
+        }
+        let vp_code_hash = Hash::sha256(&vp_code);
+        let vp_code_len = u64::try_from(vp_code.len()).into_storage_result()?;
+        let write_log = temp_state.write_log_mut();
+        write_log
+            .protocol_write(
+                &Key::wasm_code(&vp_code_hash),
+                vp_code.serialize_to_vec(),
+            )
+            .into_storage_result()?;
+        write_log
+            .protocol_write(
+                &Key::wasm_code_len(&vp_code_hash),
+                vp_code_len.serialize_to_vec(),
+            )
+            .into_storage_result()?;
+
+        let cache_dir = tempfile::tempdir().into_storage_result()?;
+        let mut vp_wasm_cache: VpCache<WasmCacheRwAccess> =
+            VpCache::new(cache_dir.path().join("vp"), EVAL_VP_CACHE_BYTES);
+        let mut tx_wasm_cache: TxCache<WasmCacheRwAccess> =
+            TxCache::new(cache_dir.path().join("tx"), EVAL_VP_CACHE_BYTES);
+
+        // Use the max block gas as the gas limit, as for the dry run of an
+        // inner tx, capped by the configured max
+        let max_block_gas = namada_parameters::get_max_block_gas(ctx.state)
+            .into_storage_result()?;
+        let tx_gas_meter = RefCell::new(TxGasMeter::new(GasLimit::from(
+            std::cmp::min(max_block_gas, max_gas),
+        )));
+        let verifiers_from_tx = wasm::run::tx(
+            &mut temp_state,
+            &tx_gas_meter,
+            &TxIndex(0),
+            &tx,
+            &mut vp_wasm_cache,
+            &mut tx_wasm_cache,
+        )
+        .into_storage_result()?;
+        let (verifiers, keys_changed) = temp_state
+            .write_log()
+            .verifiers_and_changed_keys(&verifiers_from_tx);
+
+        let gas_meter =
+            RefCell::new(VpGasMeter::new_from_tx_meter(&tx_gas_meter.borrow()));
+        let result = wasm::run::vp(
+            vp_code_hash,
+            &tx,
+            &TxIndex(0),
+            &address,
+            &temp_state,
+            &gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+        let gas_used = gas_meter.borrow().get_vp_consumed_gas();
+
+        let data = EvalVpResult {
+            height,
+            accepted: result.is_ok(),
+            error: result.err().map(|err| err.to_string()),
+            keys_changed,
+            verifiers,
+            gas_used,
+        }
+        .serialize_to_vec();
+        Ok(EncodedResponseQuery {
+            data,
+            proof: None,
+            info: Default::default(),
+            height: ctx.state.in_mem().get_last_block_height(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use borsh::BorshDeserialize;
@@ -136,6 +298,7 @@ mod test {
     use namada_core::address;
     use namada_core::hash::Hash;
     use namada_core::storage::{BlockHeight, Key};
+    use namada_proof_of_stake::event::PosEvent;
     use namada_sdk::queries::{
         EncodedResponseQuery, RequestCtx, RequestQuery, Router, RPC,
     };
@@ -143,11 +306,16 @@ mod test {
     use namada_state::testing::TestState;
     use namada_state::StorageWrite;
     use namada_test_utils::TestWasms;
-    use namada_tx::data::TxType;
+    use namada_tx::data::{ResultCode, TxType};
+    use namada_tx::event::types::APPLIED as APPLIED_TX;
+    use namada_tx::event::Code as CodeAttr;
     use namada_tx::{Code, Data, Tx};
     use tempfile::TempDir;
 
+    use crate::ledger::events::extend::{ComposeEvent, Height, TxHash};
     use crate::ledger::events::log::EventLog;
+    use crate::ledger::events::{EmitEvents, Event, EventLevel};
+    use crate::ledger::gas::event::GasUsed;
     use crate::ledger::queries::Client;
     use crate::token;
     use crate::vm::wasm::{TxCache, VpCache};
@@ -298,6 +466,25 @@ mod test {
             .unwrap();
         assert!(result.data.is_accepted());
 
+        // Request to decode the tx, with its code in the wasm registry
+        let name_key = Key::wasm_code_name("tx_no_op.wasm".to_string());
+        client
+            .state
+            .db_write(&name_key, tx_hash.serialize_to_vec())
+            .unwrap();
+        let decoded = RPC
+            .shell()
+            .decode_tx(&client, Some(outer_tx.to_bytes()), None, false)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(decoded.hash, outer_tx.header_hash());
+        assert_eq!(decoded.code_hash, Some(tx_hash));
+        assert_eq!(decoded.code_name, Some("tx_no_op.wasm".to_string()));
+        assert!(decoded.wrapper.is_none());
+        assert!(decoded.data.is_none());
+        assert!(decoded.signatures.is_empty());
+
         // Request storage value for a balance key ...
         let token_addr = address::testing::established_address_1();
         let owner = address::testing::established_address_2();
@@ -344,6 +531,47 @@ mod test {
             token::Amount::try_from_slice(&read_balance.data).unwrap()
         );
 
+        // Request multiple storage values at once, in the order of the keys
+        let other_owner = address::testing::established_address_3();
+        let other_balance_key =
+            token::storage_key::balance_key(&token_addr, &other_owner);
+        let read_values = RPC
+            .shell()
+            .storage_multi_value(
+                &client,
+                Some(
+                    vec![other_balance_key, balance_key.clone()]
+                        .serialize_to_vec(),
+                ),
+                None,
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            read_values.data,
+            vec![None, Some(balance.serialize_to_vec())]
+        );
+
+        // Request multiple balances at once
+        let read_balances = RPC
+            .shell()
+            .storage_multi_balance(
+                &client,
+                Some(
+                    vec![
+                        (token_addr.clone(), owner.clone()),
+                        (token_addr.clone(), other_owner),
+                    ]
+                    .serialize_to_vec(),
+                ),
+                None,
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(read_balances.data, vec![balance, token::Amount::zero()]);
+
         // Request storage prefix iterator
         let balance_prefix = token::storage_key::balance_prefix(&token_addr);
         let read_balances = RPC
@@ -361,6 +589,40 @@ mod test {
             .unwrap();
         assert!(has_balance_key);
 
+        // Request the results of a block with a tx and a slash
+        let height = client.state.in_mem().get_last_block_height();
+        let tx_hash = Hash::sha256(b"tx");
+        let validator = address::testing::established_address_3();
+        let slash = token::Amount::native_whole(5);
+        let events: [Event; 3] = [
+            Event::new(APPLIED_TX, EventLevel::Tx)
+                .with(TxHash(tx_hash))
+                .with(Height(height))
+                .with(CodeAttr(ResultCode::Ok))
+                .with(GasUsed(10.into()))
+                .into(),
+            Event::new(APPLIED_TX, EventLevel::Tx)
+                .with(TxHash(Hash::sha256(b"other tx")))
+                .with(Height(height.next_height()))
+                .with(CodeAttr(ResultCode::Ok))
+                .into(),
+            Event::from(PosEvent::Slash {
+                validator: validator.clone(),
+                amount: slash,
+            })
+            .with(Height(height))
+            .into(),
+        ];
+        client.event_log.emit_many(events);
+        let results =
+            RPC.shell().block_results(&client, &height).await.unwrap();
+        assert_eq!(results.txs.len(), 1);
+        assert_eq!(results.txs[0].hash, tx_hash);
+        assert_eq!(results.txs[0].code, ResultCode::Ok.to_u32());
+        assert_eq!(results.txs[0].gas_used, 10.into());
+        assert_eq!(results.slashes, vec![(validator, slash)]);
+        assert!(results.rewards.is_empty());
+
         Ok(())
     }
 }
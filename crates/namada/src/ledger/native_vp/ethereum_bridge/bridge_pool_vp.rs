@@ -28,8 +28,10 @@ use namada_ethereum_bridge::storage::eth_bridge_queries::is_bridge_active_at;
 use namada_ethereum_bridge::storage::parameters::read_native_erc20_address;
 use namada_ethereum_bridge::storage::whitelist;
 use namada_ethereum_bridge::ADDRESS as BRIDGE_ADDRESS;
+use namada_governance::pause::{is_paused, PausableModule};
 use namada_state::{ResultExt, StateRead};
 use namada_tx::Tx;
+use namada_vp_env::VpEnv;
 
 use crate::address::{Address, InternalAddress};
 use crate::eth_bridge_pool::{PendingTransfer, TransferToEthereumKind};
@@ -566,6 +568,22 @@ where
             )
             .into());
         }
+        if is_paused(
+            &self.ctx.pre(),
+            PausableModule::BridgePool,
+            self.ctx.get_block_epoch().map_err(Error)?,
+        )
+        .map_err(Error)?
+        {
+            tracing::debug!(
+                "Rejecting transaction, since the Bridge pool is paused."
+            );
+            return Err(native_vp::Error::SimpleMessage(
+                "Rejecting transaction, since the Bridge pool is paused by \
+                 governance.",
+            )
+            .into());
+        }
         let Some(tx_data) = tx.data() else {
             return Err(native_vp::Error::SimpleMessage(
                 "No transaction data found",
@@ -10,8 +10,9 @@ use std::time::Duration;
 use context::{PseudoExecutionContext, VpValidationContext};
 use namada_core::address::Address;
 use namada_core::collections::HashSet;
-use namada_core::storage::Key;
+use namada_core::storage::{Epoch, Key};
 use namada_gas::{IBC_ACTION_EXECUTE_GAS, IBC_ACTION_VALIDATE_GAS};
+use namada_governance::pause::{get_pause_end, PausableModule};
 use namada_ibc::event::IbcEvent;
 use namada_ibc::{
     Error as ActionError, IbcActions, NftTransferModule, TransferModule,
@@ -26,8 +27,8 @@ use thiserror::Error;
 
 use crate::ibc::core::host::types::identifiers::ChainId as IbcChainId;
 use crate::ledger::ibc::storage::{
-    calc_hash, deposit_key, get_limits, is_ibc_key, is_ibc_trace_key,
-    mint_amount_key, withdraw_key,
+    calc_hash, deposit_key, get_limits, is_ibc_denom_trace_key, is_ibc_key,
+    is_ibc_trace_key, mint_amount_key, withdraw_key,
 };
 use crate::ledger::native_vp::{self, Ctx, NativeVp};
 use crate::ledger::parameters::read_epoch_duration_parameter;
@@ -52,6 +53,8 @@ pub enum Error {
     IbcEvent(String),
     #[error("IBC rate limit: {0}")]
     RateLimit(String),
+    #[error("IBC VP error: IBC transfers are paused until epoch {0}")]
+    Paused(Epoch),
 }
 
 /// IBC functions result
@@ -83,6 +86,9 @@ where
         let signed = tx_data;
         let tx_data = signed.data().ok_or(Error::NoTxData)?;
 
+        // Reject the transfers while they are paused by governance
+        self.check_pause(keys_changed)?;
+
         // Pseudo execution and compare them
         self.validate_state(&tx_data, keys_changed)?;
 
@@ -210,7 +216,10 @@ where
 
     fn validate_trace(&self, keys_changed: &BTreeSet<Key>) -> VpResult<()> {
         for key in keys_changed {
-            if let Some((_, hash)) = is_ibc_trace_key(key) {
+            let hash = is_ibc_trace_key(key)
+                .map(|(_, hash)| hash)
+                .or_else(|| is_ibc_denom_trace_key(key));
+            if let Some(hash) = hash {
                 match self.ctx.read_post::<String>(key).map_err(|e| {
                     ActionError::Trace(format!(
                         "Getting the trace failed: Key {}, Error {}",
@@ -239,6 +248,24 @@ where
         Ok(())
     }
 
+    fn check_pause(&self, keys_changed: &BTreeSet<Key>) -> VpResult<()> {
+        let is_transfer = keys_changed
+            .iter()
+            .any(|key| is_any_token_balance_key(key).is_some());
+        if !is_transfer {
+            return Ok(());
+        }
+        let epoch = self.ctx.get_block_epoch()?;
+        match get_pause_end(
+            &self.ctx.pre(),
+            PausableModule::IbcTransfers,
+            epoch,
+        )? {
+            Some(pause_end) => Err(Error::Paused(pause_end)),
+            None => Ok(()),
+        }
+    }
+
     fn check_limits(&self, keys_changed: &BTreeSet<Key>) -> VpResult<bool> {
         let tokens: BTreeSet<&Address> = keys_changed
             .iter()
@@ -490,10 +517,10 @@ mod tests {
         ack_key, calc_hash, channel_counter_key, channel_key,
         client_connections_key, client_counter_key, client_state_key,
         client_update_height_key, client_update_timestamp_key, commitment_key,
-        connection_counter_key, connection_key, consensus_state_key, ibc_token,
-        ibc_trace_key, mint_amount_key, next_sequence_ack_key,
-        next_sequence_recv_key, next_sequence_send_key, nft_class_key,
-        nft_metadata_key, receipt_key,
+        connection_counter_key, connection_key, consensus_state_key,
+        ibc_denom_trace_key, ibc_token, ibc_trace_key, mint_amount_key,
+        next_sequence_ack_key, next_sequence_recv_key, next_sequence_send_key,
+        nft_class_key, nft_metadata_key, receipt_key,
     };
     use crate::ibc::{NftClass, NftMetadata};
     use crate::key::testing::keypair_1;
@@ -2383,6 +2410,13 @@ mod tests {
             .write(&trace_key, bytes)
             .expect("write failed");
         keys_changed.insert(trace_key);
+        let trace_key = ibc_denom_trace_key(&trace_hash);
+        let bytes = coin.denom.to_string().serialize_to_vec();
+        state
+            .write_log_mut()
+            .write(&trace_key, bytes)
+            .expect("write failed");
+        keys_changed.insert(trace_key);
         // event
         let recv_event = RecvEvent {
             sender: sender.to_string().into(),
@@ -3212,6 +3246,13 @@ mod tests {
             .write(&trace_key, bytes)
             .expect("write failed");
         keys_changed.insert(trace_key);
+        let trace_key = ibc_denom_trace_key(&trace_hash);
+        let bytes = ibc_trace.serialize_to_vec();
+        state
+            .write_log_mut()
+            .write(&trace_key, bytes)
+            .expect("write failed");
+        keys_changed.insert(trace_key);
         // NFT class
         let class_key = nft_class_key(&class_id);
         let mut class = dummy_nft_class();
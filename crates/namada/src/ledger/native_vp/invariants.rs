@@ -0,0 +1,335 @@
+//! Debug native VP checking the global accounting invariants.
+//!
+//! The invariants are:
+//!
+//! - the minted supply of every token equals the sum of its balances, which
+//!   also covers the PGF payments and any other transfers, and
+//! - the total bonded to every validator at each epoch equals the sum of the
+//!   bonds to it at that epoch.
+//!
+//! The checks scan the storage, so they are only compiled in with the
+//! `debug-invariants` feature, to catch accounting bugs early on devnets and
+//! testnets. The VP is run after every tx, checking the tokens and validators
+//! touched by it, and [`check_all`] is run after every block. A violation is
+//! only logged and never rejects a tx.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use namada_core::arith::checked;
+use namada_core::borsh::BorshDeserialize;
+use namada_state::StateRead;
+use namada_tx::Tx;
+use thiserror::Error;
+
+use crate::address::{Address, InternalAddress, POS};
+use crate::ledger::native_vp::{self, Ctx, NativeVp};
+use crate::proof_of_stake::storage::{
+    read_all_validator_addresses, total_bonded_handle,
+};
+use crate::proof_of_stake::storage_key::{
+    bonds_prefix, is_bond_key, is_validator_total_bond_or_unbond_key,
+};
+use crate::state::{iter_prefix_bytes, ResultExt, StorageRead};
+use crate::storage::{DbKeySeg, Epoch, Key, KeySeg};
+use crate::token::storage_key::{
+    is_any_minted_balance_key, is_any_token_balance_key,
+};
+use crate::token::Amount;
+use crate::vm::WasmCacheAccess;
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Invariants VP error: Native VP error: {0}")]
+    NativeVpError(#[from] native_vp::Error),
+    #[error(
+        "The minted supply of token {token} is {minted}, but its balances sum \
+         up to {balances}"
+    )]
+    TokenSupply {
+        token: Address,
+        minted: Amount,
+        balances: Amount,
+    },
+    #[error(
+        "The total bonded to validator {validator} at epoch {epoch} is \
+         {total}, but its bonds sum up to {bonds}"
+    )]
+    TotalBonded {
+        validator: Address,
+        epoch: Epoch,
+        total: Amount,
+        bonds: Amount,
+    },
+}
+
+impl Error {
+    /// The internal address of the module whose invariant is violated
+    pub fn address(&self) -> Address {
+        match self {
+            Self::TotalBonded { .. } => POS,
+            Self::NativeVpError(_) | Self::TokenSupply { .. } => {
+                Address::Internal(InternalAddress::Multitoken)
+            }
+        }
+    }
+}
+
+/// Invariants functions result
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Invariants VP
+pub struct InvariantsVp<'a, S, CA>
+where
+    S: StateRead,
+    CA: WasmCacheAccess,
+{
+    /// Context to interact with the host structures.
+    pub ctx: Ctx<'a, S, CA>,
+}
+
+impl<'a, S, CA> NativeVp for InvariantsVp<'a, S, CA>
+where
+    S: StateRead,
+    CA: 'static + WasmCacheAccess,
+{
+    type Error = Error;
+
+    fn validate_tx(
+        &self,
+        _tx_data: &Tx,
+        keys_changed: &BTreeSet<Key>,
+        _verifiers: &BTreeSet<Address>,
+    ) -> Result<()> {
+        let mut tokens = BTreeSet::new();
+        let mut validators = BTreeSet::new();
+        for key in keys_changed {
+            if let Some([token, _owner]) = is_any_token_balance_key(key) {
+                tokens.insert(token.clone());
+            } else if let Some(token) = is_any_minted_balance_key(key) {
+                tokens.insert(token.clone());
+            } else if let Some((bond_id, _epoch)) = is_bond_key(key) {
+                validators.insert(bond_id.validator);
+            } else if let Some(validator) = total_bonds_key_validator(key) {
+                validators.insert(validator.clone());
+            }
+        }
+
+        let post = self.ctx.post();
+        if !tokens.is_empty() {
+            check_token_supplies(&post, Some(&tokens))?;
+        }
+        if !validators.is_empty() {
+            check_total_bonds(&post, Some(&validators))?;
+        }
+        Ok(())
+    }
+}
+
+/// Check the invariants of all the tokens and validators.
+pub fn check_all<S>(storage: &S) -> Result<()>
+where
+    S: StorageRead,
+{
+    check_token_supplies(storage, None)?;
+    check_total_bonds(storage, None)
+}
+
+/// Check that the minted supply of the given tokens, or of all the tokens if
+/// `None`, equals the sum of their balances.
+fn check_token_supplies<S>(
+    storage: &S,
+    tokens: Option<&BTreeSet<Address>>,
+) -> Result<()>
+where
+    S: StorageRead,
+{
+    let is_checked =
+        |token: &Address| tokens.map_or(true, |tokens| tokens.contains(token));
+    let mut balances: BTreeMap<Address, Amount> = BTreeMap::new();
+    let mut minted: BTreeMap<Address, Amount> = BTreeMap::new();
+
+    let prefix =
+        Key::from(Address::Internal(InternalAddress::Multitoken).to_db_key());
+    for entry in iter_prefix_bytes(storage, &prefix)? {
+        let (key, bytes) = entry?;
+        if let Some([token, _owner]) = is_any_token_balance_key(&key) {
+            if is_checked(token) {
+                let amount =
+                    Amount::try_from_slice(&bytes).into_storage_result()?;
+                let sum = balances.entry(token.clone()).or_default();
+                *sum =
+                    checked!(*sum + amount).map_err(native_vp::Error::from)?;
+            }
+        } else if let Some(token) = is_any_minted_balance_key(&key) {
+            if is_checked(token) {
+                let amount =
+                    Amount::try_from_slice(&bytes).into_storage_result()?;
+                minted.insert(token.clone(), amount);
+            }
+        }
+    }
+
+    let all_tokens: BTreeSet<&Address> =
+        balances.keys().chain(minted.keys()).collect();
+    for token in all_tokens {
+        let minted = minted.get(token).copied().unwrap_or_default();
+        let balances = balances.get(token).copied().unwrap_or_default();
+        if minted != balances {
+            return Err(Error::TokenSupply {
+                token: token.clone(),
+                minted,
+                balances,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Check that the total bonded to the given validators, or to all the
+/// validators if `None`, equals the sum of the bonds to them at every epoch.
+fn check_total_bonds<S>(
+    storage: &S,
+    validators: Option<&BTreeSet<Address>>,
+) -> Result<()>
+where
+    S: StorageRead,
+{
+    let is_checked = |validator: &Address| {
+        validators.map_or(true, |validators| validators.contains(validator))
+    };
+    let mut bonds: BTreeMap<Address, BTreeMap<Epoch, Amount>> = BTreeMap::new();
+
+    for entry in iter_prefix_bytes(storage, &bonds_prefix())? {
+        let (key, bytes) = entry?;
+        let Some((bond_id, start)) = is_bond_key(&key) else {
+            continue;
+        };
+        if !is_checked(&bond_id.validator) {
+            continue;
+        }
+        let amount = Amount::try_from_slice(&bytes).into_storage_result()?;
+        let sum = bonds
+            .entry(bond_id.validator)
+            .or_default()
+            .entry(start)
+            .or_default();
+        *sum = checked!(*sum + amount).map_err(native_vp::Error::from)?;
+    }
+
+    let mut all_validators: BTreeSet<Address> = match validators {
+        Some(validators) => validators.clone(),
+        None => {
+            let epoch = storage.get_block_epoch()?;
+            read_all_validator_addresses(storage, epoch)?
+                .into_iter()
+                .collect()
+        }
+    };
+    all_validators.extend(bonds.keys().cloned());
+
+    for validator in all_validators {
+        let mut totals: BTreeMap<Epoch, Amount> = BTreeMap::new();
+        for entry in total_bonded_handle(&validator)
+            .get_data_handler()
+            .iter(storage)?
+        {
+            let (start, amount) = entry?;
+            totals.insert(start, amount);
+        }
+        let bonds = bonds.remove(&validator).unwrap_or_default();
+        let epochs: BTreeSet<Epoch> =
+            totals.keys().chain(bonds.keys()).copied().collect();
+        for epoch in epochs {
+            let total = totals.get(&epoch).copied().unwrap_or_default();
+            let bonds = bonds.get(&epoch).copied().unwrap_or_default();
+            if total != bonds {
+                return Err(Error::TotalBonded {
+                    validator,
+                    epoch,
+                    total,
+                    bonds,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Get the validator of a key of its total bonds or unbonds, if it is one.
+fn total_bonds_key_validator(key: &Key) -> Option<&Address> {
+    if !is_validator_total_bond_or_unbond_key(key) {
+        return None;
+    }
+    match key.segments.get(2) {
+        Some(DbKeySeg::AddressSeg(validator)) => Some(validator),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::address::testing::{
+        established_address_1, established_address_2, nam,
+    };
+    use namada_state::testing::TestState;
+
+    use super::*;
+    use crate::proof_of_stake::storage::bond_handle;
+    use crate::state::StorageWrite;
+    use crate::token::credit_tokens;
+    use crate::token::storage_key::balance_key;
+
+    #[test]
+    fn test_check_token_supplies() {
+        let mut state = TestState::default();
+        let token = nam();
+        let owner = established_address_1();
+        credit_tokens(&mut state, &token, &owner, Amount::native_whole(10))
+            .unwrap();
+        check_all(&state).unwrap();
+
+        // a balance written without minting breaks the invariant
+        state
+            .write(&balance_key(&token, &POS), Amount::native_whole(1))
+            .unwrap();
+        let err = check_token_supplies(&state, None).unwrap_err();
+        assert!(matches!(err, Error::TokenSupply { .. }));
+        assert_eq!(
+            err.address(),
+            Address::Internal(InternalAddress::Multitoken)
+        );
+    }
+
+    #[test]
+    fn test_check_total_bonds() {
+        let mut state = TestState::default();
+        let source = established_address_1();
+        let validator = established_address_2();
+        let amount = Amount::native_whole(10);
+        bond_handle(&source, &validator)
+            .get_data_handler()
+            .insert(&mut state, Epoch(2), amount)
+            .unwrap();
+        total_bonded_handle(&validator)
+            .get_data_handler()
+            .insert(&mut state, Epoch(2), amount)
+            .unwrap();
+        check_total_bonds(&state, None).unwrap();
+
+        // a bond not added to the total breaks the invariant
+        bond_handle(&source, &validator)
+            .get_data_handler()
+            .insert(&mut state, Epoch(3), amount)
+            .unwrap();
+        let err = check_total_bonds(&state, None).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::TotalBonded {
+                epoch: Epoch(3),
+                ..
+            }
+        ));
+        assert_eq!(err.address(), POS);
+    }
+}
@@ -14,8 +14,11 @@ use namada_core::address::InternalAddress::Masp;
 use namada_core::arith::checked;
 use namada_core::booleans::BoolResultUnitExt;
 use namada_core::collections::{HashMap, HashSet};
+use namada_core::hash::Hash;
 use namada_core::masp::encode_asset_type;
-use namada_core::storage::Key;
+use namada_core::storage::{BlockHeight, Key};
+use namada_governance::pause::{is_paused, PausableModule};
+use namada_parameters::storage::get_masp_convert_anchor_grace_blocks_key;
 use namada_sdk::masp::verify_shielded_tx;
 use namada_state::{OptionExt, ResultExt, StateRead};
 use namada_token::read_denom;
@@ -29,6 +32,7 @@ use token::storage_key::{
     balance_key, is_any_shielded_action_balance_key, is_masp_allowed_key,
     is_masp_key, is_masp_nullifier_key, masp_commitment_anchor_key,
     masp_commitment_tree_key, masp_convert_anchor_key, masp_nullifier_key,
+    masp_prev_convert_anchor_key,
 };
 use token::Amount;
 
@@ -226,17 +230,30 @@ where
                 let anchor_key = masp_convert_anchor_key();
                 let expected_anchor = self
                     .ctx
-                    .read_pre::<namada_core::hash::Hash>(&anchor_key)?
+                    .read_pre::<Hash>(&anchor_key)?
                     .ok_or(Error::NativeVpError(
                         native_vp::Error::SimpleMessage("Cannot read storage"),
                     ))?;
+                let prev_anchor = self.ctx.read_pre::<(Hash, BlockHeight)>(
+                    &masp_prev_convert_anchor_key(),
+                )?;
+                let grace_blocks: u64 = self
+                    .ctx
+                    .read_pre(&get_masp_convert_anchor_grace_blocks_key())?
+                    .unwrap_or_default();
+                let height = self.ctx.get_block_height()?;
 
                 for description in &bundle.shielded_converts {
                     // Check if the provided anchor matches the current
-                    // conversion tree's one
-                    if namada_core::hash::Hash(description.anchor.to_bytes())
-                        != expected_anchor
-                    {
+                    // conversion tree's one, or the previous one in its grace
+                    // period
+                    if !is_valid_convert_anchor(
+                        &Hash(description.anchor.to_bytes()),
+                        &expected_anchor,
+                        prev_anchor.as_ref(),
+                        grace_blocks,
+                        height,
+                    ) {
                         let error = Error::NativeVpError(
                             native_vp::Error::SimpleMessage(
                                 "Convert description refers to an invalid \
@@ -364,6 +381,29 @@ where
     }
 }
 
+// Check that a convert anchor is the current one, or the one of the previous
+// epoch within the grace period following its replacement. The previous anchor
+// is accepted in the blocks at heights `[replaced, replaced + grace_blocks)`,
+// so that the txs built just before an epoch change are not rejected.
+fn is_valid_convert_anchor(
+    anchor: &Hash,
+    current: &Hash,
+    prev: Option<&(Hash, BlockHeight)>,
+    grace_blocks: u64,
+    height: BlockHeight,
+) -> bool {
+    if anchor == current {
+        return true;
+    }
+    match prev {
+        Some((prev_anchor, replaced)) => {
+            anchor == prev_anchor
+                && height.0 < replaced.0.saturating_add(grace_blocks)
+        }
+        None => false,
+    }
+}
+
 // Make a map to help recognize asset types lacking an epoch
 fn unepoched_tokens(
     token: &Address,
@@ -405,6 +445,15 @@ where
             return Err(error);
         }
 
+        if is_paused(&self.ctx.pre(), PausableModule::MaspTransfers, epoch)? {
+            let error = native_vp::Error::new_const(
+                "MASP transfers are paused by governance",
+            )
+            .into();
+            tracing::debug!("{error}");
+            return Err(error);
+        }
+
         let mut transparent_tx_pool = I128Sum::zero();
         // The Sapling value balance adds to the transparent tx pool
         transparent_tx_pool += shielded_tx.sapling_value_balance();
@@ -784,3 +833,68 @@ where
             .map_err(Error::NativeVpError)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_anchor_grace_period() {
+        let prev = Hash::sha256(b"prev");
+        let current = Hash::sha256(b"current");
+        // The conversion tree was updated at the epoch change at height 10
+        let replaced = (prev, BlockHeight(10));
+
+        // A tx built on the previous conversion tree and included in the
+        // block of the epoch change is accepted in the grace period
+        for height in [10, 11] {
+            assert!(is_valid_convert_anchor(
+                &prev,
+                &current,
+                Some(&replaced),
+                2,
+                BlockHeight(height),
+            ));
+        }
+        // but not after it
+        assert!(!is_valid_convert_anchor(
+            &prev,
+            &current,
+            Some(&replaced),
+            2,
+            BlockHeight(12),
+        ));
+        // nor without a grace period
+        assert!(!is_valid_convert_anchor(
+            &prev,
+            &current,
+            Some(&replaced),
+            0,
+            BlockHeight(10),
+        ));
+
+        // The current anchor is always accepted
+        assert!(is_valid_convert_anchor(
+            &current,
+            &current,
+            Some(&replaced),
+            0,
+            BlockHeight(100),
+        ));
+        assert!(is_valid_convert_anchor(
+            &current,
+            &current,
+            None,
+            0,
+            BlockHeight(100),
+        ));
+        // and any other anchor is rejected
+        assert!(!is_valid_convert_anchor(
+            &Hash::sha256(b"other"),
+            &current,
+            Some(&replaced),
+            2,
+            BlockHeight(10),
+        ));
+    }
+}
@@ -3,9 +3,13 @@
 
 pub mod ethereum_bridge;
 pub mod ibc;
+#[cfg(feature = "debug-invariants")]
+pub mod invariants;
 pub mod masp;
 pub mod multitoken;
+pub mod name_registry;
 pub mod parameters;
+pub mod registry;
 
 use std::cell::RefCell;
 use std::collections::BTreeSet;
@@ -473,6 +477,10 @@ where
         })
     }
 
+    fn get_gas_remaining(&self) -> Result<u64, state::StorageError> {
+        vp_host_fns::get_gas_remaining(self.gas_meter).into_storage_result()
+    }
+
     fn get_tx_code_hash(&self) -> Result<Option<Hash>, state::StorageError> {
         vp_host_fns::get_tx_code_hash(self.gas_meter, self.tx)
             .into_storage_result()
@@ -7,7 +7,9 @@ use namada_core::collections::HashMap;
 use namada_governance::is_proposal_accepted;
 use namada_parameters::storage::is_native_token_transferable;
 use namada_state::StateRead;
-use namada_token::storage_key::is_any_token_parameter_key;
+use namada_token::storage_key::{
+    is_any_token_parameter_key, is_any_token_policy_key,
+};
 use namada_tx::Tx;
 use namada_vp_env::VpEnv;
 use thiserror::Error;
@@ -80,6 +82,9 @@ where
             if let Some([token, owner]) = is_any_token_balance_key(key) {
                 let pre: Amount = self.ctx.read_pre(key)?.unwrap_or_default();
                 let post: Amount = self.ctx.read_post(key)?.unwrap_or_default();
+                if pre != post {
+                    self.is_allowed_by_policy(token, Some(owner))?;
+                }
                 match post.checked_sub(pre) {
                     Some(diff) => {
                         if !is_allowed_inc(token, owner) {
@@ -143,6 +148,9 @@ where
 
                 let pre: Amount = self.ctx.read_pre(key)?.unwrap_or_default();
                 let post: Amount = self.ctx.read_post(key)?.unwrap_or_default();
+                if pre != post {
+                    self.is_allowed_by_policy(token, None)?;
+                }
                 match post.checked_sub(pre) {
                     Some(diff) => {
                         let mint = inc_mints.entry(token.clone()).or_default();
@@ -172,6 +180,8 @@ where
                 self.is_valid_minter(token, verifiers)?;
             } else if let Some(token) = is_any_minter_key(key) {
                 self.is_valid_minter(token, verifiers)?;
+            } else if let Some(token) = is_any_token_policy_key(key) {
+                self.is_valid_policy_change(token, tx_data, verifiers)?;
             } else if is_any_token_parameter_key(key).is_some() {
                 return self.is_valid_parameter(tx_data);
             } else if key.segments.first()
@@ -272,6 +282,25 @@ where
                     .into()),
                 }
             }
+            Address::Internal(InternalAddress::BondReceipt(_)) => {
+                // Bond receipts are minted and burned by PoS, which checks
+                // that they are kept in sync with the bonds
+                let minter_key = minter_key(token);
+                match self.ctx.read_post::<Address>(&minter_key)? {
+                    Some(minter) if minter == POS => {
+                        verifiers.contains(&minter).ok_or_else(|| {
+                            native_vp::Error::new_const(
+                                "The PoS VP was not triggered",
+                            )
+                            .into()
+                        })
+                    }
+                    _ => Err(native_vp::Error::new_const(
+                        "Only the PoS account is able to mint bond receipts",
+                    )
+                    .into()),
+                }
+            }
             _ => Err(native_vp::Error::new_alloc(format!(
                 "Attempted to mint non-IBC token {token}"
             ))
@@ -279,6 +308,59 @@ where
         }
     }
 
+    /// Return if the token's policy, before the tx, allows to change its
+    /// total supply or the balance of the given owner
+    pub fn is_allowed_by_policy(
+        &self,
+        token: &Address,
+        owner: Option<&Address>,
+    ) -> Result<()> {
+        if namada_token::is_token_paused(&self.ctx.pre(), token)? {
+            return Err(native_vp::Error::new_alloc(format!(
+                "The balance changes of token {token} are paused"
+            ))
+            .into());
+        }
+        if let Some(owner) = owner {
+            if namada_token::is_owner_frozen(&self.ctx.pre(), token, owner)? {
+                return Err(native_vp::Error::new_alloc(format!(
+                    "The balance of {owner} in token {token} is frozen"
+                ))
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Return if the change of the token's policy was authorized by the
+    /// token's account or done via a governance proposal
+    pub fn is_valid_policy_change(
+        &self,
+        token: &Address,
+        tx: &Tx,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<()> {
+        // The VP of an established token's account must verify the tx. Other
+        // tokens have no owner, so only governance can set their policy.
+        if matches!(token, Address::Established(_)) && verifiers.contains(token)
+        {
+            return Ok(());
+        }
+        let is_gov_proposal = match tx.data() {
+            Some(data) => is_proposal_accepted(&self.ctx.pre(), data.as_ref())
+                .map_err(Error::NativeVpError)?,
+            None => false,
+        };
+        is_gov_proposal.ok_or_else(|| {
+            native_vp::Error::new_alloc(format!(
+                "The policy of token {token} can only be changed by the \
+                 token's account or by a governance proposal that has been \
+                 accepted"
+            ))
+            .into()
+        })
+    }
+
     /// Return if the parameter change was done via a governance proposal
     pub fn is_valid_parameter(&self, tx: &Tx) -> Result<()> {
         tx.data().map_or_else(
@@ -324,7 +406,9 @@ mod tests {
     use crate::ledger::gas::VpGasMeter;
     use crate::ledger::ibc::storage::ibc_token;
     use crate::storage::TxIndex;
-    use crate::token::storage_key::{balance_key, minted_balance_key};
+    use crate::token::storage_key::{
+        balance_key, frozen_key, minted_balance_key, paused_key,
+    };
     use crate::vm::wasm::compilation_cache::common::testing::cache as wasm_cache;
 
     const ADDRESS: Address = Address::Internal(InternalAddress::Multitoken);
@@ -850,4 +934,163 @@ mod tests {
         let vp = MultitokenVp { ctx };
         assert_matches!(vp.validate_tx(&tx, &keys_changed, &verifiers), Err(_));
     }
+
+    #[test]
+    fn test_bond_receipt_minter() {
+        // Bond receipts can only be minted by PoS
+        for (minter, is_valid) in [
+            (POS, true),
+            (Address::Internal(InternalAddress::Ibc), false),
+        ] {
+            let mut state = init_state();
+            let mut keys_changed = BTreeSet::new();
+
+            let validator = match established_address_2() {
+                Address::Established(validator) => validator,
+                _ => unreachable!(),
+            };
+            let token =
+                Address::Internal(InternalAddress::BondReceipt(validator));
+
+            // mint 100
+            let target = established_address_1();
+            let target_key = balance_key(&token, &target);
+            let amount = Amount::native_whole(100);
+            state
+                .write_log_mut()
+                .write(&target_key, amount.serialize_to_vec())
+                .expect("write failed");
+            keys_changed.insert(target_key);
+            let minted_key = minted_balance_key(&token);
+            state
+                .write_log_mut()
+                .write(&minted_key, amount.serialize_to_vec())
+                .expect("write failed");
+            keys_changed.insert(minted_key);
+
+            let minter_key = minter_key(&token);
+            state
+                .write_log_mut()
+                .write(&minter_key, minter.serialize_to_vec())
+                .expect("write failed");
+            keys_changed.insert(minter_key);
+
+            let tx_index = TxIndex::default();
+            let tx = dummy_tx(&state);
+            let gas_meter = RefCell::new(VpGasMeter::new_from_tx_meter(
+                &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+            ));
+            let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+            let mut verifiers = BTreeSet::new();
+            verifiers.insert(minter);
+            let ctx = Ctx::new(
+                &ADDRESS,
+                &state,
+                &tx,
+                &tx_index,
+                &gas_meter,
+                &keys_changed,
+                &verifiers,
+                vp_wasm_cache,
+            );
+
+            let vp = MultitokenVp { ctx };
+            assert_eq!(
+                vp.validate_tx(&tx, &keys_changed, &verifiers).is_ok(),
+                is_valid
+            );
+        }
+    }
+
+    #[test]
+    fn test_token_policy_blocks_transfer() {
+        let src = established_address_1();
+        let dest = established_address_2();
+        // Pausing the token or freezing any of the owners blocks the transfer
+        for policy_key in [
+            paused_key(&nam()),
+            frozen_key(&nam(), &src),
+            frozen_key(&nam(), &dest),
+        ] {
+            let mut state = init_state();
+            let keys_changed = transfer(&mut state, &src, &dest);
+            state
+                .db_write(&policy_key, true.serialize_to_vec())
+                .expect("write failed");
+
+            let tx_index = TxIndex::default();
+            let tx = dummy_tx(&state);
+            let gas_meter = RefCell::new(VpGasMeter::new_from_tx_meter(
+                &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+            ));
+            let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+            let mut verifiers = BTreeSet::new();
+            verifiers.insert(src.clone());
+            let ctx = Ctx::new(
+                &ADDRESS,
+                &state,
+                &tx,
+                &tx_index,
+                &gas_meter,
+                &keys_changed,
+                &verifiers,
+                vp_wasm_cache,
+            );
+
+            let vp = MultitokenVp { ctx };
+            assert_matches!(
+                vp.validate_tx(&tx, &keys_changed, &verifiers),
+                Err(_)
+            );
+        }
+    }
+
+    #[test]
+    fn test_token_policy_update() {
+        // The policy can only be changed by the token's account
+        for (verifier, is_valid) in
+            [(nam(), true), (established_address_1(), false)]
+        {
+            let mut state = init_state();
+            let mut keys_changed = BTreeSet::new();
+
+            let paused_key = paused_key(&nam());
+            state
+                .write_log_mut()
+                .write(&paused_key, true.serialize_to_vec())
+                .expect("write failed");
+            keys_changed.insert(paused_key);
+            let frozen_key = frozen_key(&nam(), &established_address_2());
+            state
+                .write_log_mut()
+                .write(&frozen_key, ().serialize_to_vec())
+                .expect("write failed");
+            keys_changed.insert(frozen_key);
+
+            let tx_index = TxIndex::default();
+            let tx = dummy_tx(&state);
+            let gas_meter = RefCell::new(VpGasMeter::new_from_tx_meter(
+                &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+            ));
+            let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+            let mut verifiers = BTreeSet::new();
+            verifiers.insert(verifier);
+            let ctx = Ctx::new(
+                &ADDRESS,
+                &state,
+                &tx,
+                &tx_index,
+                &gas_meter,
+                &keys_changed,
+                &verifiers,
+                vp_wasm_cache,
+            );
+
+            let vp = MultitokenVp { ctx };
+            assert_eq!(
+                vp.validate_tx(&tx, &keys_changed, &verifiers).is_ok(),
+                is_valid
+            );
+        }
+    }
 }
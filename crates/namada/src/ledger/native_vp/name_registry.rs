@@ -0,0 +1,133 @@
+//! Native VP for the registry of the names of addresses
+
+use std::collections::BTreeSet;
+
+use namada_account::name_registry::{
+    is_name_key, is_reserved_name, is_reserved_namespace_key, is_valid_name,
+    read_name_record,
+};
+use namada_core::address::Address;
+use namada_core::booleans::BoolResultUnitExt;
+use namada_core::storage::Key;
+use namada_state::StateRead;
+use namada_tx::Tx;
+use thiserror::Error;
+
+use crate::ledger::native_vp::{self, Ctx, NativeVp};
+use crate::vm::WasmCacheAccess;
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Name registry VP error: Native VP error: {0}")]
+    NativeVpError(#[from] native_vp::Error),
+}
+
+/// Name registry functions result
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Name registry VP
+pub struct NameRegistryVp<'a, S, CA>
+where
+    S: StateRead,
+    CA: WasmCacheAccess,
+{
+    /// Context to interact with the host structures.
+    pub ctx: Ctx<'a, S, CA>,
+}
+
+impl<'a, S, CA> NativeVp for NameRegistryVp<'a, S, CA>
+where
+    S: StateRead,
+    CA: 'static + WasmCacheAccess,
+{
+    type Error = Error;
+
+    fn validate_tx(
+        &self,
+        tx_data: &Tx,
+        keys_changed: &BTreeSet<Key>,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<()> {
+        keys_changed.iter().try_for_each(|key| {
+            if let Some(name) = is_name_key(key) {
+                self.is_valid_name_change(tx_data, name, verifiers)
+            } else if let Some(namespace) = is_reserved_namespace_key(key) {
+                self.is_governance_proposal(tx_data).map_err(|_| {
+                    native_vp::Error::new_alloc(format!(
+                        "The namespace {namespace} can only be reserved or \
+                         released by a governance proposal",
+                    ))
+                    .into()
+                })
+            } else {
+                Err(native_vp::Error::new_alloc(format!(
+                    "Unexpected change to the name registry: {key}",
+                ))
+                .into())
+            }
+        })
+    }
+}
+
+impl<'a, S, CA> NameRegistryVp<'a, S, CA>
+where
+    S: StateRead,
+    CA: 'static + WasmCacheAccess,
+{
+    /// A name must be valid. The names in reserved namespaces can only be
+    /// changed by governance. Other names must be authorized by their owner
+    /// before and after the change.
+    fn is_valid_name_change(
+        &self,
+        tx_data: &Tx,
+        name: &str,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<()> {
+        if !is_valid_name(name) {
+            return Err(native_vp::Error::new_alloc(format!(
+                "Invalid name {name}"
+            ))
+            .into());
+        }
+        if is_reserved_name(&self.ctx.pre(), name)? {
+            return self.is_governance_proposal(tx_data).map_err(|_| {
+                native_vp::Error::new_alloc(format!(
+                    "The name {name} is in a reserved namespace and can only \
+                     be changed by a governance proposal",
+                ))
+                .into()
+            });
+        }
+        let pre = read_name_record(&self.ctx.pre(), name)?;
+        let post = read_name_record(&self.ctx.post(), name)?;
+        for owner in pre.iter().chain(post.iter()).map(|record| &record.owner) {
+            verifiers.contains(owner).ok_or_else(|| {
+                native_vp::Error::new_alloc(format!(
+                    "The change of the name {name} must be authorized by its \
+                     owner {owner}",
+                ))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Check that the tx is the code of an accepted governance proposal
+    fn is_governance_proposal(&self, tx_data: &Tx) -> Result<()> {
+        let data = tx_data.data().ok_or_else(|| {
+            native_vp::Error::new_const(
+                "Governance changes require tx data to be present",
+            )
+        })?;
+        namada_governance::storage::is_proposal_accepted(
+            &self.ctx.pre(),
+            &data,
+        )?
+        .ok_or_else(|| {
+            native_vp::Error::new_const(
+                "The tx is not an accepted governance proposal",
+            )
+            .into()
+        })
+    }
+}
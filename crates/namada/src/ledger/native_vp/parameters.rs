@@ -4,9 +4,12 @@ use std::collections::BTreeSet;
 
 use namada_core::address::Address;
 use namada_core::booleans::BoolResultUnitExt;
-use namada_core::storage::Key;
-use namada_state::StateRead;
+use namada_core::chain::ProposalBytes;
+use namada_core::dec::Dec;
+use namada_core::storage::{Epoch, Key};
+use namada_state::{StateRead, StorageRead};
 use namada_tx::Tx;
+use namada_vp_env::VpEnv;
 use thiserror::Error;
 
 use crate::ledger::native_vp::{self, Ctx, NativeVp};
@@ -47,6 +50,25 @@ where
     ) -> Result<()> {
         keys_changed.iter().try_for_each(|key| {
             let key_type: KeyType = key.into();
+            if let KeyType::EPOCH_HOOK_OWNER(owner) | KeyType::EPOCH_HOOK(owner) =
+                key_type
+            {
+                return self.is_valid_epoch_hook_change(owner);
+            }
+            if let KeyType::EPOCH_HOOKS_CURSOR = key_type {
+                return Err(native_vp::Error::new_const(
+                    "The epoch hooks cursor can only be updated by the \
+                     protocol",
+                )
+                .into());
+            }
+            if let KeyType::ACCOUNT_NONCE(owner) = key_type {
+                return Err(native_vp::Error::new_alloc(format!(
+                    "The account nonce of {owner} can only be incremented by \
+                     the protocol",
+                ))
+                .into());
+            }
             let data = if let Some(data) = tx_data.data() {
                 data
             } else {
@@ -56,41 +78,236 @@ where
                 .into());
             };
             match key_type {
-                KeyType::PARAMETER | KeyType::UNKNOWN_PARAMETER => {
-                    namada_governance::storage::is_proposal_accepted(
-                        &self.ctx.pre(),
-                        &data,
-                    )
-                    .map_err(Error::NativeVpError)?
-                    .ok_or_else(|| {
-                        native_vp::Error::new_alloc(format!(
-                            "Attempted to change a protocol parameter from \
-                             outside of a governance proposal, or from a \
-                             non-accepted governance proposal: {key}",
-                        ))
-                        .into()
-                    })
+                KeyType::PARAMETER => {
+                    if namada_parameters::storage::is_protocol_version_key(key)
+                    {
+                        self.is_valid_protocol_version_change(key, false)?;
+                    }
+                    if namada_parameters::storage::is_base_fee_max_change_rate_key(
+                        key,
+                    ) {
+                        self.is_valid_base_fee_max_change_rate(key)?;
+                    }
+                    if namada_parameters::storage::is_max_tx_bytes_key(key)
+                        || namada_parameters::storage::is_max_proposal_bytes_key(
+                            key,
+                        )
+                    {
+                        self.is_valid_max_tx_bytes()?;
+                    }
+                    self.is_accepted_proposal_change(key, &data)
                 }
-                KeyType::UNKNOWN => Ok(()),
+                KeyType::UNKNOWN_PARAMETER => {
+                    self.is_accepted_proposal_change(key, &data)
+                }
+                KeyType::PENDING_PARAMETER(name, epoch) => {
+                    self.is_valid_pending_parameter(name, epoch)?;
+                    if name
+                        == namada_parameters::protocol_version_handle().name()
+                    {
+                        self.is_valid_protocol_version_change(key, true)?;
+                    }
+                    if namada_parameters::storage::is_base_fee_max_change_rate_name(
+                        name,
+                    ) {
+                        self.is_valid_base_fee_max_change_rate(key)?;
+                    }
+                    self.is_accepted_proposal_change(key, &data)
+                }
+                KeyType::EPOCH_HOOK_OWNER(_)
+                | KeyType::EPOCH_HOOK(_)
+                | KeyType::EPOCH_HOOKS_CURSOR
+                | KeyType::ACCOUNT_NONCE(_)
+                | KeyType::UNKNOWN => Ok(()),
             }
         })
     }
 }
 
+impl<'a, S, CA> ParametersVp<'a, S, CA>
+where
+    S: StateRead,
+    CA: 'static + WasmCacheAccess,
+{
+    /// Parameters can only be changed by an accepted governance proposal
+    fn is_accepted_proposal_change(
+        &self,
+        key: &Key,
+        data: &[u8],
+    ) -> Result<()> {
+        namada_governance::storage::is_proposal_accepted(&self.ctx.pre(), data)
+            .map_err(Error::NativeVpError)?
+            .ok_or_else(|| {
+                native_vp::Error::new_alloc(format!(
+                    "Attempted to change a protocol parameter from outside of \
+                     a governance proposal, or from a non-accepted governance \
+                     proposal: {key}",
+                ))
+                .into()
+            })
+    }
+
+    /// A parameter change can only be scheduled, or cancelled, for a known
+    /// protocol parameter and a future epoch
+    fn is_valid_pending_parameter(
+        &self,
+        name: &str,
+        epoch: Epoch,
+    ) -> Result<()> {
+        if !namada_parameters::storage::is_protocol_parameter_name(name) {
+            return Err(native_vp::Error::new_alloc(format!(
+                "Attempted to schedule a change of an unknown parameter {name}",
+            ))
+            .into());
+        }
+        let current_epoch = self.ctx.get_block_epoch()?;
+        (epoch > current_epoch).ok_or_else(|| {
+            native_vp::Error::new_alloc(format!(
+                "The change of parameter {name} must be scheduled after the \
+                 current epoch {current_epoch}, got {epoch}",
+            ))
+            .into()
+        })
+    }
+
+    /// The protocol version can only be bumped, such that a network upgrade
+    /// cannot be rolled back to the binaries it replaced. An upgrade scheduled
+    /// for a future epoch can be cancelled, but the current version cannot be
+    /// removed.
+    fn is_valid_protocol_version_change(
+        &self,
+        key: &Key,
+        is_pending: bool,
+    ) -> Result<()> {
+        let new_version: Option<u64> = self.ctx.read_post(key)?;
+        let Some(new_version) = new_version else {
+            return is_pending.ok_or_else(|| {
+                native_vp::Error::new_const(
+                    "The protocol version cannot be removed",
+                )
+                .into()
+            });
+        };
+        let current_version =
+            namada_parameters::read_protocol_version(&self.ctx.pre())?
+                .unwrap_or_default();
+        (new_version > current_version).ok_or_else(|| {
+            native_vp::Error::new_alloc(format!(
+                "The protocol version can only be bumped, the current \
+                 version is {current_version}, got {new_version}",
+            ))
+            .into()
+        })
+    }
+
+    /// The base fee max change rate must be between 0 and 1, otherwise a
+    /// block could drive the base fee below zero
+    fn is_valid_base_fee_max_change_rate(&self, key: &Key) -> Result<()> {
+        let max_change_rate: Option<Dec> = self.ctx.read_post(key)?;
+        let Some(max_change_rate) = max_change_rate else {
+            // A removed rate defaults to 0
+            return Ok(());
+        };
+        namada_parameters::is_valid_base_fee_max_change_rate(&max_change_rate)
+            .ok_or_else(|| {
+                native_vp::Error::new_alloc(format!(
+                    "The base fee max change rate must be between 0 and 1, \
+                     got {max_change_rate}",
+                ))
+                .into()
+            })
+    }
+
+    /// The max size of a tx must not exceed the max size of the txs of a
+    /// block proposal, otherwise a tx accepted in the mempool could never be
+    /// included in a block.
+    fn is_valid_max_tx_bytes(&self) -> Result<()> {
+        let max_tx_bytes =
+            namada_parameters::read_max_tx_bytes(&self.ctx.post())?;
+        let max_proposal_bytes: ProposalBytes = self
+            .ctx
+            .read_post(
+                &namada_parameters::storage::get_max_proposal_bytes_key(),
+            )?
+            .ok_or_else(|| {
+                native_vp::Error::new_const(
+                    "The max proposal bytes parameter cannot be removed",
+                )
+            })?;
+        let max_proposal_bytes = max_proposal_bytes.get();
+        (u64::from(max_tx_bytes) <= max_proposal_bytes).ok_or_else(|| {
+            native_vp::Error::new_alloc(format!(
+                "The max tx size of {max_tx_bytes} bytes must not exceed the \
+                 max proposal size of {max_proposal_bytes} bytes",
+            ))
+            .into()
+        })
+    }
+
+    /// The set of accounts that registered an epoch hook must mirror the
+    /// hooks stored in the accounts' subspaces, whose changes are authorized
+    /// by the accounts' VPs. This VP is triggered by changes to either of
+    /// them, so that they are always validated together.
+    fn is_valid_epoch_hook_change(&self, owner: &Address) -> Result<()> {
+        let is_owner = namada_parameters::storage::epoch_hook_owners_handle()
+            .contains(&self.ctx.post(), owner)?;
+        let has_hook = self
+            .ctx
+            .post()
+            .has_key(&namada_account::epoch_hook_key(owner))?;
+        (is_owner == has_hook).ok_or_else(|| {
+            native_vp::Error::new_alloc(format!(
+                "The set of epoch hook owners is out of sync with the epoch \
+                 hook of {owner}",
+            ))
+            .into()
+        })
+    }
+}
+
 #[allow(clippy::upper_case_acronyms)]
-enum KeyType {
+enum KeyType<'a> {
     #[allow(clippy::upper_case_acronyms)]
     PARAMETER,
     #[allow(clippy::upper_case_acronyms)]
     #[allow(non_camel_case_types)]
     UNKNOWN_PARAMETER,
     #[allow(clippy::upper_case_acronyms)]
+    #[allow(non_camel_case_types)]
+    PENDING_PARAMETER(&'a str, Epoch),
+    #[allow(clippy::upper_case_acronyms)]
+    #[allow(non_camel_case_types)]
+    EPOCH_HOOK_OWNER(&'a Address),
+    #[allow(clippy::upper_case_acronyms)]
+    #[allow(non_camel_case_types)]
+    EPOCH_HOOK(&'a Address),
+    #[allow(clippy::upper_case_acronyms)]
+    #[allow(non_camel_case_types)]
+    EPOCH_HOOKS_CURSOR,
+    #[allow(clippy::upper_case_acronyms)]
+    #[allow(non_camel_case_types)]
+    ACCOUNT_NONCE(&'a Address),
+    #[allow(clippy::upper_case_acronyms)]
     UNKNOWN,
 }
 
-impl From<&Key> for KeyType {
-    fn from(value: &Key) -> Self {
-        if namada_parameters::storage::is_protocol_parameter_key(value) {
+impl<'a> From<&'a Key> for KeyType<'a> {
+    fn from(value: &'a Key) -> Self {
+        if let Some(owner) =
+            namada_parameters::storage::is_epoch_hook_owner_key(value)
+        {
+            KeyType::EPOCH_HOOK_OWNER(owner)
+        } else if let Some(owner) = namada_account::is_epoch_hook_key(value) {
+            KeyType::EPOCH_HOOK(owner)
+        } else if namada_parameters::storage::is_epoch_hooks_cursor_key(value) {
+            KeyType::EPOCH_HOOKS_CURSOR
+        } else if let Some(owner) = namada_account::is_nonce_key(value) {
+            KeyType::ACCOUNT_NONCE(owner)
+        } else if let Some((name, epoch)) =
+            namada_parameters::storage::is_pending_parameter_key(value)
+        {
+            KeyType::PENDING_PARAMETER(name, epoch)
+        } else if namada_parameters::storage::is_protocol_parameter_key(value) {
             KeyType::PARAMETER
         } else if namada_parameters::storage::is_parameter_key(value) {
             KeyType::UNKNOWN_PARAMETER
@@ -0,0 +1,144 @@
+//! Registry of the native VPs associated with internal addresses.
+//!
+//! A native VP validates the changes in the storage subspace of its internal
+//! address, i.e. the keys whose first segment is the address. To add a native
+//! VP, implement [`NativeVp`] for a type constructed from a [`Ctx`], add an
+//! error variant for it to [`protocol::Error`] and register both for the
+//! internal address in the `native_vps!` invocation below. Every internal
+//! address must be listed there, either with its native VP or with the
+//! restriction that applies to its subspace. If the native VP must also
+//! validate keys outside of its subspace, add them to its key namespace in
+//! [`KEY_NAMESPACES`].
+
+use std::collections::BTreeSet;
+
+use namada_core::booleans::BoolResultUnitExt;
+use namada_state::StateRead;
+use namada_tx::Tx;
+
+use crate::address::{Address, InternalAddress};
+use crate::ledger::governance::GovernanceVp;
+use crate::ledger::native_vp::ethereum_bridge::bridge_pool_vp::BridgePoolVp;
+use crate::ledger::native_vp::ethereum_bridge::nut::NonUsableTokens;
+use crate::ledger::native_vp::ethereum_bridge::vp::EthBridge;
+use crate::ledger::native_vp::ibc::Ibc;
+use crate::ledger::native_vp::masp::MaspVp;
+use crate::ledger::native_vp::multitoken::MultitokenVp;
+use crate::ledger::native_vp::name_registry::NameRegistryVp;
+use crate::ledger::native_vp::parameters::ParametersVp;
+use crate::ledger::native_vp::{Ctx, NativeVp};
+use crate::ledger::pgf::PgfVp;
+use crate::ledger::pos::PosVP;
+use crate::ledger::protocol::{self, Error};
+use crate::storage::Key;
+use crate::vm::WasmCacheAccess;
+
+/// Generate [`validate_tx`] from the list of internal addresses. Each native
+/// VP entry maps an internal address pattern to the native VP type and the
+/// [`protocol::Error`] variant that wraps the VP's errors. The addresses that
+/// can only be modified as part of a multitoken key and the addresses that
+/// cannot be modified by txs at all are listed separately. The generated
+/// `match` has no wildcard arm, so every internal address must be listed.
+macro_rules! native_vps {
+    (
+        $($addr:pat => $vp:ident, $err:ident;)*
+        multitoken: [$($token_addr:pat),* $(,)?]
+        forbidden: [$($forbidden_addr:pat),* $(,)?]
+    ) => {
+        /// Validate a tx with the native VP registered for the given internal
+        /// address. Internal addresses without a native VP may not be modified,
+        /// except for the token addresses that are part of a multitoken key.
+        pub fn validate_tx<'a, S, CA>(
+            internal_addr: &InternalAddress,
+            ctx: Ctx<'a, S, CA>,
+            tx: &Tx,
+            keys_changed: &BTreeSet<Key>,
+            verifiers: &BTreeSet<Address>,
+        ) -> protocol::Result<()>
+        where
+            S: StateRead,
+            CA: 'static + WasmCacheAccess,
+        {
+            match internal_addr {
+                $(
+                    $addr => {
+                        let vp = $vp { ctx };
+                        vp.validate_tx(tx, keys_changed, verifiers)
+                            .map_err(Error::$err)
+                    }
+                )*
+                $($token_addr)|* => {
+                    // The address should be a part of a multitoken key
+                    let multitoken =
+                        Address::Internal(InternalAddress::Multitoken);
+                    verifiers
+                        .contains(&multitoken)
+                        .ok_or_else(|| {
+                            Error::AccessForbidden(internal_addr.clone())
+                        })
+                }
+                $($forbidden_addr)|* => {
+                    Err(Error::AccessForbidden(internal_addr.clone()))
+                }
+            }
+        }
+    };
+}
+
+/// The keys outside of the subspace of an internal address that must be
+/// validated by its native VP
+const KEY_NAMESPACES: &[(InternalAddress, fn(&Key) -> bool)] = &[
+    // The epoch hooks stored in the accounts' subspaces must be validated
+    // together with the set of their owners
+    (InternalAddress::Parameters, is_epoch_hook_key),
+    // The account nonces can only be changed by the protocol
+    (InternalAddress::Parameters, is_nonce_key),
+];
+
+fn is_epoch_hook_key(key: &Key) -> bool {
+    namada_account::is_epoch_hook_key(key).is_some()
+}
+
+fn is_nonce_key(key: &Key) -> bool {
+    namada_account::is_nonce_key(key).is_some()
+}
+
+/// Add the internal addresses whose key namespace contains any of the changed
+/// keys to the verifiers
+pub fn add_key_namespace_verifiers(
+    keys_changed: &BTreeSet<Key>,
+    verifiers: &mut BTreeSet<Address>,
+) {
+    for (internal_addr, is_in_namespace) in KEY_NAMESPACES {
+        if keys_changed.iter().any(is_in_namespace) {
+            verifiers.insert(Address::Internal(internal_addr.clone()));
+        }
+    }
+}
+
+native_vps! {
+    InternalAddress::PoS => PosVP, PosNativeVpError;
+    InternalAddress::Ibc => Ibc, IbcNativeVpError;
+    InternalAddress::Parameters => ParametersVp, ParametersNativeVpError;
+    InternalAddress::Governance => GovernanceVp, GovernanceNativeVpError;
+    InternalAddress::Multitoken => MultitokenVp, MultitokenNativeVpError;
+    InternalAddress::EthBridge => EthBridge, EthBridgeNativeVpError;
+    InternalAddress::EthBridgePool => BridgePoolVp, BridgePoolNativeVpError;
+    InternalAddress::Pgf => PgfVp, PgfNativeVpError;
+    InternalAddress::Nut(_) => NonUsableTokens, NutNativeVpError;
+    InternalAddress::Masp => MaspVp, MaspNativeVpError;
+    InternalAddress::NameRegistry => NameRegistryVp, NameRegistryNativeVpError;
+    multitoken: [
+        InternalAddress::IbcToken(_),
+        InternalAddress::Erc20(_),
+        InternalAddress::BondReceipt(_),
+    ]
+    // Temp storage changes must never be committed, the slash pool is only
+    // modified by the protocol and the funds of the fee collector are only
+    // forwarded by the protocol
+    forbidden: [
+        InternalAddress::PosSlashPool,
+        InternalAddress::TempStorage,
+        InternalAddress::FeeCollector,
+    ]
+}
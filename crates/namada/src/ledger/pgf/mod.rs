@@ -134,6 +134,22 @@ where
                         .into());
                     }
 
+                    let was_steward = pgf::storage::is_steward(
+                        &self.ctx.pre(),
+                        steward_address,
+                    )?;
+                    let is_steward = pgf::storage::is_steward(
+                        &self.ctx.post(),
+                        steward_address,
+                    )?;
+                    if !was_steward && is_steward {
+                        return Err(native_vp::Error::new_alloc(format!(
+                            "The steward {steward_address} can only be added \
+                             via governance proposals",
+                        ))
+                        .into());
+                    }
+
                     pgf::storage::get_steward(
                         &self.ctx.post(),
                         steward_address,
@@ -177,6 +193,27 @@ where
                         },
                     )
                 }
+                KeyType::StewardTerm(steward_address) => {
+                    // The terms of the elected stewards can only be changed
+                    // by the protocol, except that a steward's term is
+                    // removed together with the steward on resignation
+                    let is_steward = pgf::storage::is_steward(
+                        &self.ctx.post(),
+                        steward_address,
+                    )?;
+                    let term_end = pgf::storage::get_steward_term_end(
+                        &self.ctx.post(),
+                        steward_address,
+                    )?;
+                    let is_resignation = !is_steward && term_end.is_none();
+                    is_resignation.ok_or_else(|| {
+                        native_vp::Error::new_alloc(format!(
+                            "The term of the steward {steward_address} can \
+                             only be set by a steward election"
+                        ))
+                        .into()
+                    })
+                }
                 KeyType::Fundings => Err(native_vp::Error::new_alloc(format!(
                     "Cannot update PGF fundings key: {key}"
                 ))
@@ -227,6 +264,7 @@ where
 #[derive(Debug)]
 enum KeyType<'a> {
     Stewards(&'a Address),
+    StewardTerm(&'a Address),
     Fundings,
     PgfInflationRate,
     StewardInflationRate,
@@ -238,6 +276,8 @@ impl<'k> From<&'k Key> for KeyType<'k> {
     fn from(key: &'k Key) -> Self {
         if let Some(addr) = pgf_storage::is_stewards_key(key) {
             Self::Stewards(addr)
+        } else if let Some(addr) = pgf_storage::is_steward_term_key(key) {
+            Self::StewardTerm(addr)
         } else if pgf_storage::is_fundings_key(key) {
             KeyType::Fundings
         } else if pgf_storage::is_pgf_inflation_rate_key(key) {
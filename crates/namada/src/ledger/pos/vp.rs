@@ -2,14 +2,21 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 
+use namada_core::arith::checked;
 use namada_core::booleans::BoolResultUnitExt;
 pub use namada_proof_of_stake;
+use namada_proof_of_stake::delegation_pool::read_delegation_pool;
 pub use namada_proof_of_stake::parameters::PosParams;
-use namada_proof_of_stake::storage::read_pos_params;
+use namada_proof_of_stake::storage::{
+    read_pos_params, validator_commission_rate_handle,
+};
 use namada_proof_of_stake::storage_key::is_params_key;
 pub use namada_proof_of_stake::types;
 use namada_proof_of_stake::types::BondId;
-use namada_proof_of_stake::{storage_key, token};
+use namada_proof_of_stake::{
+    find_validator_stake_over_cap, storage_key, token,
+    validate_validator_metadata,
+};
 use namada_state::StateRead;
 use namada_tx::action::{
     Action, Bond, ClaimRewards, PosAction, Read, Redelegation, Unbond, Withdraw,
@@ -17,8 +24,8 @@ use namada_tx::action::{
 use namada_tx::Tx;
 use thiserror::Error;
 
-use crate::address::Address;
-use crate::ledger::native_vp::{self, Ctx, NativeVp};
+use crate::address::{Address, InternalAddress, GOV};
+use crate::ledger::native_vp::{self, Ctx, NativeVp, VpEnv};
 use crate::storage::Key;
 use crate::vm::WasmCacheAccess;
 
@@ -62,18 +69,7 @@ where
         tracing::debug!("\nValidating PoS Tx\n");
 
         // Check if this is a governance proposal first
-        if tx
-            .data()
-            .map(|tx_data| {
-                namada_governance::is_proposal_accepted(
-                    &self.ctx.pre(),
-                    &tx_data,
-                )
-            })
-            .transpose()
-            .map_err(Error::NativeVpError)?
-            .unwrap_or(false)
-        {
+        if self.is_accepted_proposal(tx)? {
             for key in keys_changed {
                 if is_params_key(key) {
                     // If governance changes PoS params, the params have to be
@@ -116,6 +112,10 @@ where
         let mut changed_commission: BTreeSet<Address> = Default::default();
         let mut changed_metadata: BTreeSet<Address> = Default::default();
         let mut changed_consensus_key: BTreeSet<Address> = Default::default();
+        let mut updated_pools: BTreeSet<String> = Default::default();
+        let mut changed_pool_membership: BTreeSet<Address> = Default::default();
+        let mut changed_auto_redelegation: BTreeSet<Address> =
+            Default::default();
 
         // Accumulate changes from the actions
         for action in actions {
@@ -292,6 +292,44 @@ where
                         }
                         changed_consensus_key.insert(validator);
                     }
+                    PosAction::DelegationPoolUpdate { name, operator } => {
+                        if !verifiers.contains(&operator) {
+                            tracing::info!(
+                                "Unauthorized PosAction::DelegationPoolUpdate"
+                            );
+                            return Err(Error::Unauthorized(
+                                "DelegationPoolUpdate",
+                                operator,
+                            ));
+                        }
+                        updated_pools.insert(name);
+                    }
+                    PosAction::DelegationPoolMembership(delegator) => {
+                        if !verifiers.contains(&delegator) {
+                            tracing::info!(
+                                "Unauthorized \
+                                 PosAction::DelegationPoolMembership"
+                            );
+                            return Err(Error::Unauthorized(
+                                "DelegationPoolMembership",
+                                delegator,
+                            ));
+                        }
+                        changed_pool_membership.insert(delegator);
+                    }
+                    PosAction::AutoRedelegationUpdate(delegator) => {
+                        if !verifiers.contains(&delegator) {
+                            tracing::info!(
+                                "Unauthorized \
+                                 PosAction::AutoRedelegationUpdate"
+                            );
+                            return Err(Error::Unauthorized(
+                                "AutoRedelegationUpdate",
+                                delegator,
+                            ));
+                        }
+                        changed_auto_redelegation.insert(delegator);
+                    }
                 },
                 _ => {
                     // Other actions are not relevant to PoS VP
@@ -300,6 +338,13 @@ where
             }
         }
 
+        // The changes of the outstanding bond receipts and of the receipt
+        // tokens supply of every validator
+        let mut receipt_changes: BTreeMap<Address, token::Change> =
+            Default::default();
+        let mut receipt_supply_changes: BTreeMap<Address, token::Change> =
+            Default::default();
+
         for key in keys_changed {
             if is_params_key(key) {
                 return Err(Error::NativeVpError(native_vp::Error::new_const(
@@ -307,8 +352,158 @@ where
                      governance proposal that has been accepted",
                 )));
             }
+            if let Some(bond_id) = storage_key::is_bond_receipts_key(key) {
+                let is_redelegation_dest =
+                    redelegations.iter().any(|(src, (dest, _))| {
+                        src.source == bond_id.source
+                            && *dest == bond_id.validator
+                    });
+                if !bonds.contains_key(&bond_id)
+                    && !unbonds.contains_key(&bond_id)
+                    && !redelegations.contains_key(&bond_id)
+                    && !is_redelegation_dest
+                {
+                    return Err(Error::NativeVpError(
+                        native_vp::Error::new_alloc(format!(
+                            "The bond receipts of {} to {} changed without a \
+                             corresponding action",
+                            bond_id.source, bond_id.validator
+                        )),
+                    ));
+                }
+                let change = self.read_amount_change(key)?;
+                let total =
+                    receipt_changes.entry(bond_id.validator).or_default();
+                *total = checked!(*total + change)
+                    .map_err(|e| Error::NativeVpError(e.into()))?;
+            }
+            if let Some(Address::Internal(InternalAddress::BondReceipt(
+                validator,
+            ))) = token::storage_key::is_any_minted_balance_key(key)
+            {
+                let change = self.read_amount_change(key)?;
+                let total = receipt_supply_changes
+                    .entry(Address::Established(validator.clone()))
+                    .or_default();
+                *total = checked!(*total + change)
+                    .map_err(|e| Error::NativeVpError(e.into()))?;
+            }
+            if storage_key::is_block_seed_key(key) {
+                return Err(Error::NativeVpError(native_vp::Error::new_const(
+                    "The block seed can only be updated by the protocol",
+                )));
+            }
+            if storage_key::is_validator_operational_metadata_key(key).is_some()
+                || storage_key::is_validator_operational_metadata_nonce_key(key)
+                    .is_some()
+            {
+                return Err(Error::NativeVpError(native_vp::Error::new_const(
+                    "The validators' operational metadata can only be updated \
+                     by a protocol tx",
+                )));
+            }
+            if storage_key::is_validator_telemetry_key(key).is_some() {
+                return Err(Error::NativeVpError(native_vp::Error::new_const(
+                    "The validators' telemetry heartbeats can only be updated \
+                     by a protocol tx",
+                )));
+            }
+            if storage_key::is_validator_jail_history_key(key).is_some() {
+                return Err(Error::NativeVpError(native_vp::Error::new_const(
+                    "The validators' jail history can only be updated by the \
+                     protocol",
+                )));
+            }
+            if storage_key::is_evidence_records_key(key) {
+                return Err(Error::NativeVpError(native_vp::Error::new_const(
+                    "The evidence records can only be updated by the protocol",
+                )));
+            }
+            if storage_key::is_delegation_pool_rebalance_cursor_key(key) {
+                return Err(Error::NativeVpError(native_vp::Error::new_const(
+                    "The delegation pools rebalancing can only be updated by \
+                     the protocol",
+                )));
+            }
+            if storage_key::is_auto_redelegation_cursor_key(key) {
+                return Err(Error::NativeVpError(native_vp::Error::new_const(
+                    "The automatic redelegations processing can only be \
+                     updated by the protocol",
+                )));
+            }
+            if let Some(name) = storage_key::is_delegation_pool_key(key) {
+                self.is_valid_delegation_pool_update(
+                    tx,
+                    name,
+                    &updated_pools,
+                    verifiers,
+                )?;
+            }
+            if let Some(delegator) =
+                storage_key::is_delegation_pool_member_key(key)
+            {
+                if !changed_pool_membership.contains(delegator) {
+                    return Err(Error::NativeVpError(
+                        native_vp::Error::new_alloc(format!(
+                            "The delegation pool membership of {delegator} \
+                             changed without a corresponding action"
+                        )),
+                    ));
+                }
+            }
+            if let Some(delegator) = storage_key::is_auto_redelegation_key(key)
+            {
+                if !changed_auto_redelegation.contains(delegator) {
+                    return Err(Error::NativeVpError(
+                        native_vp::Error::new_alloc(format!(
+                            "The automatic redelegation of {delegator} \
+                             changed without a corresponding action"
+                        )),
+                    ));
+                }
+            }
             // TODO: validate changes keys against the accumulated changes
         }
+
+        // The new validators and the validators that changed their commission
+        // rate or their metadata must meet the requirements of the parameters
+        self.is_valid_validator_requirements(
+            &became_validator,
+            &changed_commission,
+            &changed_metadata,
+        )?;
+
+        // The validators that received bonds or redelegations must not
+        // exceed the cap of their share of the total stake
+        let bonded_validators: BTreeSet<&Address> = bonds
+            .keys()
+            .map(|bond_id| &bond_id.validator)
+            .chain(redelegations.values().map(|(dest, _)| dest))
+            .collect();
+        self.is_valid_validator_stake_caps(bonded_validators)?;
+
+        // The receipt tokens must only be minted and burned together with
+        // the bonds they represent
+        let validators: BTreeSet<&Address> = receipt_changes
+            .keys()
+            .chain(receipt_supply_changes.keys())
+            .collect();
+        for validator in validators {
+            let change =
+                receipt_changes.get(validator).copied().unwrap_or_default();
+            let supply_change = receipt_supply_changes
+                .get(validator)
+                .copied()
+                .unwrap_or_default();
+            if change != supply_change {
+                return Err(Error::NativeVpError(native_vp::Error::new_alloc(
+                    format!(
+                        "The supply of the bond receipts of {validator} is \
+                         out of sync with the bonds"
+                    ),
+                )));
+            }
+        }
         Ok(())
     }
 }
@@ -323,6 +518,141 @@ where
         Self { ctx }
     }
 
+    /// Is the tx applying the code of an accepted governance proposal?
+    fn is_accepted_proposal(&self, tx: &Tx) -> Result<bool> {
+        Ok(tx
+            .data()
+            .map(|tx_data| {
+                namada_governance::is_proposal_accepted(
+                    &self.ctx.pre(),
+                    &tx_data,
+                )
+            })
+            .transpose()
+            .map_err(Error::NativeVpError)?
+            .unwrap_or(false))
+    }
+
+    /// Return `Ok` if the update of a delegation pool is authorized by both
+    /// its prior and its new operator. Pools operated by governance can only
+    /// be updated by governance proposals.
+    fn is_valid_delegation_pool_update(
+        &self,
+        tx: &Tx,
+        name: &str,
+        updated_pools: &BTreeSet<String>,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<()> {
+        if self.is_accepted_proposal(tx)? {
+            return Ok(());
+        }
+        if !updated_pools.contains(name) {
+            return Err(Error::NativeVpError(native_vp::Error::new_alloc(
+                format!(
+                    "The delegation pool {name} changed without a \
+                     corresponding action"
+                ),
+            )));
+        }
+        let pre = read_delegation_pool(&self.ctx.pre(), name)?;
+        let post = read_delegation_pool(&self.ctx.post(), name)?;
+        for pool in pre.iter().chain(post.iter()) {
+            if pool.operator == GOV {
+                return Err(Error::NativeVpError(native_vp::Error::new_alloc(
+                    format!(
+                        "The delegation pool {name} can only be updated by a \
+                         governance proposal"
+                    ),
+                )));
+            }
+            if !verifiers.contains(&pool.operator) {
+                return Err(Error::Unauthorized(
+                    "DelegationPoolUpdate",
+                    pool.operator.clone(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Return `Ok` if the commission rates and the metadata of the given
+    /// validators meet the minimum requirements of the PoS parameters
+    fn is_valid_validator_requirements(
+        &self,
+        became_validator: &BTreeSet<Address>,
+        changed_commission: &BTreeSet<Address>,
+        changed_metadata: &BTreeSet<Address>,
+    ) -> Result<()> {
+        let params =
+            read_pos_params(&self.ctx.post()).map_err(Error::NativeVpError)?;
+        let current_epoch = self.ctx.get_block_epoch()?;
+        let pipeline_epoch = checked!(current_epoch + params.pipeline_len)
+            .map_err(|e| Error::NativeVpError(e.into()))?;
+        for validator in became_validator.union(changed_commission) {
+            let rate = validator_commission_rate_handle(validator)
+                .get(&self.ctx.post(), pipeline_epoch, &params)
+                .map_err(Error::NativeVpError)?;
+            if rate.is_some_and(|rate| rate < params.min_commission_rate) {
+                return Err(Error::NativeVpError(native_vp::Error::new_alloc(
+                    format!(
+                        "The commission rate of {validator} is lower than the \
+                         minimum commission rate {}",
+                        params.min_commission_rate
+                    ),
+                )));
+            }
+        }
+        for validator in became_validator.union(changed_metadata) {
+            validate_validator_metadata(&self.ctx.post(), validator)
+                .map_err(Error::NativeVpError)?;
+        }
+        Ok(())
+    }
+
+    /// Return `Ok` if the stake of the given validators at the pipeline epoch
+    /// doesn't exceed the cap of their share of the total stake
+    fn is_valid_validator_stake_caps<'v>(
+        &self,
+        validators: impl IntoIterator<Item = &'v Address>,
+    ) -> Result<()> {
+        let params =
+            read_pos_params(&self.ctx.post()).map_err(Error::NativeVpError)?;
+        if params.max_validator_stake_share.is_none() {
+            return Ok(());
+        }
+        let current_epoch = self.ctx.get_block_epoch()?;
+        let pipeline_epoch = checked!(current_epoch + params.pipeline_len)
+            .map_err(|e| Error::NativeVpError(e.into()))?;
+        for validator in validators {
+            let over_cap = find_validator_stake_over_cap(
+                &self.ctx.post(),
+                &params,
+                validator,
+                pipeline_epoch,
+            )
+            .map_err(Error::NativeVpError)?;
+            if let Some((stake, max_stake)) = over_cap {
+                return Err(Error::NativeVpError(native_vp::Error::new_alloc(
+                    format!(
+                        "The stake {} of {validator} exceeds the max stake {} \
+                         allowed by the cap of its share of the total stake",
+                        stake.to_string_native(),
+                        max_stake.to_string_native()
+                    ),
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the change of a token amount under the given key
+    fn read_amount_change(&self, key: &Key) -> Result<token::Change> {
+        let pre: token::Amount = self.ctx.read_pre(key)?.unwrap_or_default();
+        let post: token::Amount = self.ctx.read_post(key)?.unwrap_or_default();
+        checked!(post.change() - pre.change())
+            .map_err(|e| Error::NativeVpError(e.into()))
+    }
+
     /// Return `Ok` if the changed parameters are valid
     fn is_valid_parameter_change(&self) -> Result<()> {
         let validation_errors = read_pos_params(&self.ctx.post())
@@ -339,3 +669,118 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use assert_matches::assert_matches;
+    use namada_core::borsh::BorshSerializeExt;
+    use namada_gas::TxGasMeter;
+    use namada_governance::parameters::GovernanceParameters;
+    use namada_governance::storage::keys::get_proposal_execution_key;
+    use namada_proof_of_stake::delegation_pool::{
+        delegation_pools_handle, DelegationPool,
+    };
+    use namada_proof_of_stake::parameters::OwnedPosParams;
+    use namada_proof_of_stake::storage::write_pos_params;
+    use namada_state::testing::TestState;
+    use namada_state::StorageWrite;
+    use namada_tx::action::Write;
+    use namada_tx::data::TxType;
+    use namada_tx::{Code, Data};
+
+    use super::*;
+    use crate::core::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use crate::core::dec::Dec;
+    use crate::ledger::gas::VpGasMeter;
+    use crate::storage::TxIndex;
+    use crate::vm::wasm::compilation_cache::common::testing::cache as wasm_cache;
+
+    const ADDRESS: Address = Address::Internal(InternalAddress::PoS);
+    const POOL_NAME: &str = "gov-pool";
+
+    /// Update a delegation pool operated by governance in a tx applying the
+    /// code of the proposal with ID 0 and validate it with the PoS VP
+    fn validate_gov_pool_update(is_proposal_accepted: bool) -> Result<()> {
+        let mut state = TestState::default();
+        namada_parameters::init_test_storage(&mut state).unwrap();
+        GovernanceParameters::default()
+            .init_storage(&mut state)
+            .unwrap();
+        write_pos_params(&mut state, &OwnedPosParams::default()).unwrap();
+        let pool = |validator: Address| DelegationPool {
+            operator: GOV,
+            validators: BTreeMap::from([(validator, Dec::one())]),
+        };
+        delegation_pools_handle()
+            .insert(
+                &mut state,
+                POOL_NAME.to_string(),
+                pool(established_address_1()),
+            )
+            .unwrap();
+        if is_proposal_accepted {
+            state.write(&get_proposal_execution_key(0), ()).unwrap();
+        }
+        state.commit_tx();
+
+        delegation_pools_handle()
+            .insert(
+                &mut state,
+                POOL_NAME.to_string(),
+                pool(established_address_2()),
+            )
+            .unwrap();
+        state
+            .push_action(Action::Pos(PosAction::DelegationPoolUpdate {
+                name: POOL_NAME.to_string(),
+                operator: GOV,
+            }))
+            .unwrap();
+        let pool_key =
+            delegation_pools_handle().get_data_key(&POOL_NAME.to_string());
+        let keys_changed = BTreeSet::from([pool_key]);
+        let verifiers = BTreeSet::from([GOV]);
+
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.header.chain_id = state.in_mem().chain_id.clone();
+        tx.set_code(Code::new(vec![], None));
+        tx.set_data(Data::new(0_u64.serialize_to_vec()));
+        let tx_index = TxIndex::default();
+        let gas_meter = RefCell::new(VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        ));
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &state,
+            &tx,
+            &tx_index,
+            &gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+        PosVP::new(ctx).validate_tx(&tx, &keys_changed, &verifiers)
+    }
+
+    /// Test that a delegation pool operated by governance can be updated by
+    /// an accepted governance proposal
+    #[test]
+    fn test_gov_pool_update_by_accepted_proposal() {
+        assert!(validate_gov_pool_update(true).is_ok());
+    }
+
+    /// Test that a delegation pool operated by governance cannot be updated
+    /// by a tx, even if it's authorized by the governance address
+    #[test]
+    fn test_gov_pool_update_without_proposal() {
+        assert_matches!(
+            validate_gov_pool_update(false),
+            Err(Error::NativeVpError(_))
+        );
+    }
+}
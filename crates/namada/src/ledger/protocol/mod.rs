@@ -6,46 +6,44 @@ use std::fmt::Debug;
 use borsh_ext::BorshSerializeExt;
 use eyre::{eyre, WrapErr};
 use masp_primitives::transaction::Transaction;
-use namada_core::booleans::BoolResultUnitExt;
 use namada_core::hash::Hash;
 use namada_core::storage::Key;
 use namada_events::extend::{
     ComposeEvent, Height as HeightAttr, TxHash as TxHashAttr,
 };
 use namada_events::EventLevel;
-use namada_gas::TxGasMeter;
+use namada_gas::{Gas, GasBreakdown, TxGasMeter};
 use namada_sdk::tx::TX_TRANSFER_WASM;
 use namada_state::StorageWrite;
 use namada_token::event::{TokenEvent, TokenOperation, UserAccount};
 use namada_tx::data::protocol::ProtocolTxType;
 use namada_tx::data::{
-    GasLimit, TxResult, TxType, VpStatusFlags, VpsResult, WrapperTx,
+    AccountNonce, ErrorCode, GasLimit, TxResult, TxType, VpStatusFlags,
+    VpsResult, WrapperTx, WrapperTxErr,
 };
 use namada_tx::{Section, Tx};
 use namada_vote_ext::EthereumTxData;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use ripemd::Digest as RipemdDigest;
+use sha2::Digest as Sha2Digest;
 use thiserror::Error;
 
-use crate::address::{Address, InternalAddress};
+use crate::address::{Address, InternalAddress, FEE_COLLECTOR};
 use crate::ledger::gas::{GasMetering, VpGasMeter};
-use crate::ledger::governance::GovernanceVp;
-use crate::ledger::native_vp::ethereum_bridge::bridge_pool_vp::BridgePoolVp;
-use crate::ledger::native_vp::ethereum_bridge::nut::NonUsableTokens;
-use crate::ledger::native_vp::ethereum_bridge::vp::EthBridge;
-use crate::ledger::native_vp::ibc::Ibc;
-use crate::ledger::native_vp::masp::MaspVp;
-use crate::ledger::native_vp::multitoken::MultitokenVp;
-use crate::ledger::native_vp::parameters::{self, ParametersVp};
-use crate::ledger::native_vp::{self, NativeVp};
-use crate::ledger::pgf::PgfVp;
-use crate::ledger::pos::{self, PosVP};
+use crate::ledger::native_vp::{self, parameters};
+use crate::ledger::pos;
 use crate::state::{DBIter, State, StorageHasher, StorageRead, WlState, DB};
 use crate::storage;
 use crate::storage::TxIndex;
-use crate::token::Amount;
+use crate::token::{Amount, DenominatedAmount, MaspDigitPos};
 use crate::vm::wasm::{TxCache, VpCache};
 use crate::vm::{self, wasm, WasmCacheAccess};
 
+/// The version of the protocol implemented by this crate. It must be bumped
+/// with every change that breaks consensus with the previous version, and
+/// recorded on chain by the governance proposal scheduling the upgrade.
+pub const PROTOCOL_VERSION: u64 = 2;
+
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
 pub enum Error {
@@ -56,7 +54,7 @@ pub enum Error {
     #[error("Storage error: {0}")]
     StorageError(namada_state::StorageError),
     #[error("Wrapper tx runner error: {0}")]
-    WrapperRunnerError(String),
+    WrapperRunnerError(Box<Error>),
     #[error("Transaction runner error: {0}")]
     TxRunnerError(vm::wasm::run::Error),
     #[error("{0:?}")]
@@ -101,11 +99,60 @@ pub enum Error {
     NutNativeVpError(native_vp::ethereum_bridge::nut::Error),
     #[error("MASP native VP error: {0}")]
     MaspNativeVpError(native_vp::masp::Error),
+    #[error("Name registry native VP error: {0}")]
+    NameRegistryNativeVpError(native_vp::name_registry::Error),
     #[error("Access to an internal address {0:?} is forbidden")]
     AccessForbidden(InternalAddress),
 }
 
 impl Error {
+    /// The stable code of the cause of the error, to be attached to the tx
+    /// results and events
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::MissingSection(_) => ErrorCode::MissingSection,
+            Self::StateError(_) | Self::StorageError(_) => {
+                ErrorCode::StorageError
+            }
+            Self::WrapperRunnerError(err) => err.error_code(),
+            Self::TxRunnerError(err) => match err {
+                wasm::run::Error::GasError(_) => ErrorCode::OutOfGas,
+                wasm::run::Error::MissingSection(_) => {
+                    ErrorCode::MissingSection
+                }
+                wasm::run::Error::InvalidSectionSignature(_) => {
+                    ErrorCode::InvalidSectionSignature
+                }
+                _ => ErrorCode::TxRuntimeError,
+            },
+            Self::ProtocolTxError(_) | Self::TxTypeError => ErrorCode::Unknown,
+            Self::FeeUnshieldingError(_) => ErrorCode::FeeUnshielding,
+            Self::GasError(_) => ErrorCode::OutOfGas,
+            Self::FeeError(_) => ErrorCode::FeePayment,
+            Self::InvalidSectionSignature(_) => {
+                ErrorCode::InvalidSectionSignature
+            }
+            Self::ReplayAttempt(_) => ErrorCode::ReplayAttempt,
+            Self::VpRunnerError(_) => ErrorCode::AccountVpRejected,
+            Self::MissingAddress(_) => ErrorCode::MissingAddress,
+            Self::IbcNativeVpError(_) => ErrorCode::IbcVpRejected,
+            Self::PosNativeVpError(_) => ErrorCode::PosVpRejected,
+            Self::PosNativeVpRuntime => ErrorCode::PosVpRuntime,
+            Self::ParametersNativeVpError(_) => ErrorCode::ParametersVpRejected,
+            Self::MultitokenNativeVpError(_) => ErrorCode::MultitokenVpRejected,
+            Self::GovernanceNativeVpError(_) => ErrorCode::GovernanceVpRejected,
+            Self::PgfNativeVpError(_) => ErrorCode::PgfVpRejected,
+            Self::EthBridgeNativeVpError(_) => ErrorCode::EthBridgeVpRejected,
+            Self::BridgePoolNativeVpError(_) => ErrorCode::BridgePoolVpRejected,
+            Self::NutNativeVpError(_) => ErrorCode::NutVpRejected,
+            Self::MaspNativeVpError(_) => ErrorCode::MaspVpRejected,
+            Self::NameRegistryNativeVpError(_) => {
+                ErrorCode::NameRegistryVpRejected
+            }
+            Self::AccessForbidden(_) => ErrorCode::AccessForbidden,
+        }
+    }
+
     /// Determine if the error originates from an invalid transaction
     /// section signature. This is required for replay protection.
     const fn invalid_section_signature_flag(&self) -> VpStatusFlags {
@@ -191,7 +238,8 @@ where
     CA: 'static + WasmCacheAccess + Sync,
 {
     match tx.header().tx_type {
-        // Raw trasaction type is allowed only for governance proposals
+        // Raw trasaction type is allowed only for governance proposals and
+        // accounts' epoch hooks
         TxType::Raw => apply_wasm_tx(
             tx,
             &tx_index,
@@ -208,7 +256,7 @@ where
         TxType::Wrapper(ref wrapper) => {
             let fee_unshielding_transaction =
                 get_fee_unshielding_transaction(&tx, wrapper);
-            let changed_keys = apply_wrapper_tx(
+            let (changed_keys, wrapper_gas) = apply_wrapper_tx(
                 tx.clone(),
                 wrapper,
                 fee_unshielding_transaction,
@@ -221,7 +269,7 @@ where
                 },
                 wrapper_args,
             )
-            .map_err(|e| Error::WrapperRunnerError(e.to_string()))?;
+            .map_err(|e| Error::WrapperRunnerError(Box::new(e)))?;
             let mut inner_res = apply_wasm_tx(
                 tx,
                 &tx_index,
@@ -234,6 +282,8 @@ where
             )?;
 
             inner_res.wrapper_changed_keys = changed_keys;
+            inner_res.gas_breakdown.fee_settlement = wrapper_gas.fee_settlement;
+            inner_res.gas_breakdown.wrapper = wrapper_gas.wrapper;
             Ok(inner_res)
         }
     }
@@ -258,9 +308,11 @@ where
 /// Performs the required operation on a wrapper transaction:
 ///  - replay protection
 ///  - fee payment
+///  - account nonce increment
 ///  - gas accounting
 ///
-/// Returns the set of changed storage keys.
+/// Returns the set of changed storage keys and the breakdown of the gas used
+/// by the fee settlement and by the wrapper.
 pub(crate) fn apply_wrapper_tx<S, D, H, CA>(
     tx: Tx,
     wrapper: &WrapperTx,
@@ -268,7 +320,7 @@ pub(crate) fn apply_wrapper_tx<S, D, H, CA>(
     tx_bytes: &[u8],
     mut shell_params: ShellParams<'_, S, D, H, CA>,
     wrapper_args: Option<&mut WrapperArgs>,
-) -> Result<BTreeSet<Key>>
+) -> Result<(BTreeSet<Key>, GasBreakdown)>
 where
     S: State<D = D, H = H> + Sync,
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
@@ -279,12 +331,17 @@ where
 
     let wrapper_tx_hash = tx.header_hash();
 
-    // Write wrapper tx hash to storage
-    shell_params
-        .state
-        .write_log_mut()
-        .write_tx_hash(wrapper_tx_hash)
-        .expect("Error while writing tx hash to storage");
+    // Write wrapper tx hash to storage, unless the wrapper is protected from
+    // replays by its account nonce
+    if wrapper.nonce.is_none() {
+        shell_params
+            .state
+            .write_log_mut()
+            .write_tx_hash(wrapper_tx_hash)
+            .expect("Error while writing tx hash to storage");
+    }
+
+    let initial_gas = shell_params.tx_gas_meter.borrow().get_tx_consumed_gas();
 
     // Charge fee before performing any fallible operations
     charge_fee(
@@ -296,6 +353,18 @@ where
         wrapper_args,
     )?;
 
+    // Consume the account nonce, if any, together with the fee
+    if let Some(AccountNonce { account, .. }) = &wrapper.nonce {
+        namada_account::increment_nonce(shell_params.state, account)
+            .map_err(Error::StorageError)?;
+        changed_keys.insert(namada_account::nonce_key(account));
+    }
+
+    let fee_settlement_gas =
+        consumed_gas_since(&shell_params.tx_gas_meter.borrow(), initial_gas);
+    let wrapper_initial_gas =
+        shell_params.tx_gas_meter.borrow().get_tx_consumed_gas();
+
     // Account for gas
     shell_params
         .tx_gas_meter
@@ -303,7 +372,16 @@ where
         .add_wrapper_gas(tx_bytes)
         .map_err(|err| Error::GasError(err.to_string()))?;
 
-    Ok(changed_keys)
+    let gas_breakdown = GasBreakdown {
+        fee_settlement: fee_settlement_gas,
+        wrapper: consumed_gas_since(
+            &shell_params.tx_gas_meter.borrow(),
+            wrapper_initial_gas,
+        ),
+        ..Default::default()
+    };
+
+    Ok((changed_keys, gas_breakdown))
 }
 
 /// Retrieve the Masp `Transaction` for fee unshielding from the provided
@@ -324,6 +402,73 @@ pub fn get_fee_unshielding_transaction(
         })
 }
 
+/// Check if a fee unshielding transaction pays the fee straight out of the
+/// shielded pool, i.e. if all of its transparent outputs go to the fee
+/// collector, whose funds the protocol forwards to the block proposer, rather
+/// than to the fee payer, and if they sum up to exactly the fee of the wrapper.
+/// This way, the transparent balance of the fee payer is never credited with
+/// the shielded funds and the fee is never paid out of the balance pooled in
+/// the fee collector.
+pub fn is_shielded_fee_payment<S>(
+    state: &S,
+    wrapper: &WrapperTx,
+    transaction: &Transaction,
+) -> bool
+where
+    S: State,
+{
+    let collector_hash: [u8; 20] = ripemd::Ripemd160::digest(
+        sha2::Sha256::digest(FEE_COLLECTOR.serialize_to_vec()),
+    )
+    .into();
+    let to_collector = transaction.transparent_bundle().is_some_and(|bundle| {
+        !bundle.vout.is_empty()
+            && bundle
+                .vout
+                .iter()
+                .all(|out| out.address.0 == collector_hash)
+    });
+    to_collector
+        && matches!(
+            (
+                get_fee_unshielding_amount(state, wrapper, transaction),
+                get_fee_amount(state, wrapper),
+            ),
+            (Ok(amount), Some(fee)) if amount.amount() == fee
+        )
+}
+
+/// Get the address from whose balance the fee of a wrapper is paid: the fee
+/// collector if the fee is paid out of the shielded pool, the fee payer
+/// otherwise
+pub fn get_fee_source<S>(
+    state: &S,
+    wrapper: &WrapperTx,
+    masp_transaction: Option<&Transaction>,
+) -> Address
+where
+    S: State,
+{
+    match masp_transaction {
+        Some(transaction)
+            if is_shielded_fee_payment(state, wrapper, transaction) =>
+        {
+            FEE_COLLECTOR
+        }
+        _ => wrapper.fee_payer(),
+    }
+}
+
+/// Get the fee of a wrapper in the raw amount of its fee token, if it doesn't
+/// overflow and matches the denomination of the token
+fn get_fee_amount<S>(state: &S, wrapper: &WrapperTx) -> Option<Amount>
+where
+    S: State,
+{
+    let fee = wrapper.get_tx_fee().ok()?;
+    crate::token::denom_to_amount(fee, &wrapper.fee.token, state).ok()
+}
+
 /// Charge fee for the provided wrapper transaction. Returns error if:
 /// - The unshielding fails because of gas (other errors are ignored cause we
 ///   still try to get the fees amount from the transparent balance and, if it
@@ -346,11 +491,20 @@ where
     CA: 'static + WasmCacheAccess + Sync,
 {
     // Unshield funds if requested
+    let fee_source =
+        get_fee_source(shell_params.state, wrapper, masp_transaction.as_ref());
     let valid_fee_unshielding = if let Some(transaction) = masp_transaction {
         run_fee_unshielding(wrapper, shell_params, transaction)
     } else {
         Ok(false)
     };
+    // If the unshielding failed, the fee is paid from the transparent balance
+    // of the fee payer, even if it was meant to be paid out of the shielded
+    // pool
+    let fee_source = match valid_fee_unshielding {
+        Ok(true) => fee_source,
+        _ => wrapper.fee_payer(),
+    };
 
     // Charge or check fees before propagating any possible error coming from
     // the fee unshielding. If fee unshielding failed for non-gas reasons but
@@ -364,9 +518,10 @@ where
             shell_params.state,
             block_proposer,
             wrapper,
+            &fee_source,
             wrapper_tx_hash,
         )?,
-        None => check_fees(shell_params.state, wrapper)?,
+        None => check_fees(shell_params.state, wrapper, &fee_source)?,
     }
 
     changed_keys
@@ -422,11 +577,19 @@ where
         .map_err(|e| Error::GasError(e.to_string()))?;
     let ref_unshield_gas_meter = RefCell::new(unshield_gas_meter);
 
-    let result = match wrapper.generate_fee_unshielding(
-        get_transfer_hash_from_storage(*state),
-        Some(TX_TRANSFER_WASM.to_string()),
-        transaction,
-    ) {
+    let target = get_fee_source(*state, wrapper, Some(&transaction));
+    let fee_unshielding_tx =
+        match get_fee_unshielding_amount(*state, wrapper, &transaction) {
+            Ok(amount) => wrapper.generate_fee_unshielding(
+                get_transfer_hash_from_storage(*state),
+                Some(TX_TRANSFER_WASM.to_string()),
+                transaction,
+                amount,
+                target,
+            ),
+            Err(e) => Err(e),
+        };
+    let result = match fee_unshielding_tx {
         Ok(fee_unshielding_tx) => {
             // NOTE: A clean tx write log must be provided to this call
             // for a correct vp validation. Block write log, instead,
@@ -436,6 +599,12 @@ where
             // commit the tx write log yet cause the tx could still
             // be invalid.
             state.write_log_mut().precommit_tx();
+            let collector_balance = crate::token::read_balance(
+                *state,
+                &wrapper.fee.token,
+                &FEE_COLLECTOR,
+            )
+            .unwrap_or_default();
             match apply_wasm_tx(
                 fee_unshielding_tx,
                 &TxIndex::default(),
@@ -457,9 +626,25 @@ where
                              it: {:#?}",
                             result.vps_result.rejected_vps
                         );
+                        false
+                    } else if target == FEE_COLLECTOR
+                        && !is_exact_fee_credit(
+                            *state,
+                            wrapper,
+                            collector_balance,
+                        )
+                    {
+                        // The fee must never be paid out of the balance
+                        // pooled in the fee collector
+                        state.write_log_mut().drop_tx_keep_precommit();
+                        tracing::error!(
+                            "The unshielding tx is invalid, it didn't credit \
+                             the fee collector with exactly the fee"
+                        );
+                        false
+                    } else {
+                        true
                     }
-
-                    result.is_accepted()
                 }
                 Err(e) => {
                     state.write_log_mut().drop_tx_keep_precommit();
@@ -490,23 +675,105 @@ where
     Ok(result)
 }
 
-/// Perform the actual transfer of fess from the fee payer to the block
-/// proposer.
+/// Check that a fee unshielding to the fee collector credited its balance,
+/// which was `collector_balance` before the unshielding, with exactly the fee
+/// of the wrapper
+fn is_exact_fee_credit<S>(
+    state: &S,
+    wrapper: &WrapperTx,
+    collector_balance: Amount,
+) -> bool
+where
+    S: State,
+{
+    let balance =
+        crate::token::read_balance(state, &wrapper.fee.token, &FEE_COLLECTOR)
+            .unwrap_or_default();
+    matches!(
+        (balance.checked_sub(collector_balance), get_fee_amount(state, wrapper)),
+        (Some(credit), Some(fee)) if credit == fee
+    )
+}
+
+/// Compute the amount of the fee token unshielded by a fee unshielding
+/// transaction from its transparent outputs. The amount may be lower than the
+/// fee, in which case the rest is paid from the transparent balance of the fee
+/// payer. The MASP VP validates the outputs against this amount.
+fn get_fee_unshielding_amount<S>(
+    state: &S,
+    wrapper: &WrapperTx,
+    transaction: &Transaction,
+) -> std::result::Result<DenominatedAmount, WrapperTxErr>
+where
+    S: State,
+{
+    let token = &wrapper.fee.token;
+    let denom = namada_token::read_denom(state, token)
+        .map_err(|e| WrapperTxErr::InvalidUnshield(e.to_string()))?
+        .ok_or_else(|| {
+            WrapperTxErr::InvalidUnshield(format!(
+                "Missing denomination of the fee token {token}"
+            ))
+        })?;
+    let transparent_bundle =
+        transaction.transparent_bundle().ok_or_else(|| {
+            WrapperTxErr::InvalidUnshield(
+                "Missing transparent outputs in the unshielding transaction"
+                    .to_string(),
+            )
+        })?;
+    let epoch = state
+        .get_block_epoch()
+        .map_err(|e| WrapperTxErr::InvalidUnshield(e.to_string()))?;
+    let conversion_state = state.in_mem().get_conversion_state();
+    let mut amount = Amount::zero();
+    for out in &transparent_bundle.vout {
+        let digit = match conversion_state.assets.get(&out.asset_type) {
+            Some(((address, asset_denom, digit), asset_epoch, _, _))
+                if address == token
+                    && *asset_denom == denom
+                    && *asset_epoch <= epoch =>
+            {
+                Some(*digit)
+            }
+            _ => MaspDigitPos::iter().find(|digit| {
+                namada_core::masp::encode_asset_type(
+                    token.clone(),
+                    denom,
+                    *digit,
+                    None,
+                )
+                .is_ok_and(|asset_type| asset_type == out.asset_type)
+            }),
+        }
+        .ok_or_else(|| {
+            WrapperTxErr::InvalidUnshield(format!(
+                "Unrecognized asset {} in the unshielding transaction",
+                out.asset_type
+            ))
+        })?;
+        amount = amount
+            .checked_add(Amount::from_masp_denominated(out.value, digit))
+            .ok_or(WrapperTxErr::OverflowingFee)?;
+    }
+    Ok(DenominatedAmount::new(amount, denom))
+}
+
+/// Perform the actual transfer of fess from the fee source, i.e. the fee payer
+/// or the fee collector (see [`get_fee_source`]), to the block proposer.
 pub fn transfer_fee<S>(
     state: &mut S,
     block_proposer: &Address,
     wrapper: &WrapperTx,
+    fee_source: &Address,
     wrapper_tx_hash: Hash,
 ) -> Result<()>
 where
     S: State + StorageRead + StorageWrite,
 {
-    let balance = crate::token::read_balance(
-        state,
-        &wrapper.fee.token,
-        &wrapper.fee_payer(),
-    )
-    .unwrap();
+    let balance =
+        crate::token::read_balance(state, &wrapper.fee.token, fee_source)
+            .unwrap();
 
     const FEE_PAYMENT_DESCRIPTOR: std::borrow::Cow<'static, str> =
         std::borrow::Cow::Borrowed("wrapper-fee-payment");
@@ -524,7 +791,7 @@ where
                 token_transfer(
                     state,
                     &wrapper.fee.token,
-                    &wrapper.fee_payer(),
+                    fee_source,
                     block_proposer,
                     fees,
                 )
@@ -547,7 +814,7 @@ where
                         token: wrapper.fee.token.clone(),
                         operation: TokenOperation::Transfer {
                             amount: fees.into(),
-                            source: UserAccount::Internal(wrapper.fee_payer()),
+                            source: UserAccount::Internal(fee_source.clone()),
                             target: UserAccount::Internal(
                                 block_proposer.clone(),
                             ),
@@ -563,7 +830,7 @@ where
             } else {
                 // Balance was insufficient for fee payment, move all the
                 // available funds in the transparent balance of
-                // the fee payer. This shouldn't happen as it should be
+                // the fee source. This shouldn't happen as it should be
                 // prevented from mempool/process_proposal.
                 tracing::error!(
                     "Transfer of tx fee cannot be applied to due to \
@@ -574,7 +841,7 @@ where
                 token_transfer(
                     state,
                     &wrapper.fee.token,
-                    &wrapper.fee_payer(),
+                    fee_source,
                     block_proposer,
                     balance,
                 )
@@ -597,7 +864,7 @@ where
                         token: wrapper.fee.token.clone(),
                         operation: TokenOperation::Transfer {
                             amount: balance.into(),
-                            source: UserAccount::Internal(wrapper.fee_payer()),
+                            source: UserAccount::Internal(fee_source.clone()),
                             target: UserAccount::Internal(
                                 block_proposer.clone(),
                             ),
@@ -609,12 +876,11 @@ where
                     .with(TxHashAttr(wrapper_tx_hash)),
                 );
 
-                Err(Error::FeeError(
-                    "Transparent balance of wrapper's signer was insufficient \
-                     to pay fee. All the available transparent funds have \
-                     been moved to the block proposer"
-                        .to_string(),
-                ))
+                Err(Error::FeeError(format!(
+                    "Transparent balance of the fee source {fee_source} was \
+                     insufficient to pay fee. All the available transparent \
+                     funds have been moved to the block proposer"
+                )))
             }
         }
         Err(e) => {
@@ -680,17 +946,19 @@ where
     }
 }
 
-/// Check if the fee payer has enough transparent balance to pay fees
-pub fn check_fees<S>(state: &S, wrapper: &WrapperTx) -> Result<()>
+/// Check if the fee source, i.e. the fee payer or the fee collector (see
+/// [`get_fee_source`]), has enough transparent balance to pay fees
+pub fn check_fees<S>(
+    state: &S,
+    wrapper: &WrapperTx,
+    fee_source: &Address,
+) -> Result<()>
 where
     S: State + StorageRead,
 {
-    let balance = crate::token::read_balance(
-        state,
-        &wrapper.fee.token,
-        &wrapper.fee_payer(),
-    )
-    .unwrap();
+    let balance =
+        crate::token::read_balance(state, &wrapper.fee.token, fee_source)
+            .unwrap();
 
     let fees = wrapper
         .get_tx_fee()
@@ -734,6 +1002,7 @@ where
         return Err(Error::ReplayAttempt(tx_hash));
     }
 
+    let initial_gas = tx_gas_meter.borrow().get_tx_consumed_gas();
     let verifiers = execute_tx(
         &tx,
         tx_index,
@@ -742,6 +1011,8 @@ where
         vp_wasm_cache,
         tx_wasm_cache,
     )?;
+    let tx_gas = consumed_gas_since(&tx_gas_meter.borrow(), initial_gas);
+    let vps_initial_gas = tx_gas_meter.borrow().get_tx_consumed_gas();
 
     let vps_result = check_vps(CheckVps {
         tx: &tx,
@@ -753,6 +1024,13 @@ where
     })?;
 
     let gas_used = tx_gas_meter.borrow().get_tx_consumed_gas();
+    let gas_breakdown = GasBreakdown {
+        gas_limit: tx_gas_meter.borrow().tx_gas_limit,
+        tx: tx_gas,
+        vps: vps_result.gas_used_by_vp.clone(),
+        vps_total: consumed_gas_since(&tx_gas_meter.borrow(), vps_initial_gas),
+        ..Default::default()
+    };
     let initialized_accounts = state.write_log().get_initialized_accounts();
     let changed_keys = state.write_log().get_keys();
     let events = state.write_log_mut().take_events();
@@ -764,9 +1042,18 @@ where
         vps_result,
         initialized_accounts,
         events,
+        gas_breakdown,
     })
 }
 
+/// Get the gas consumed by a tx since the given amount of gas was consumed
+fn consumed_gas_since(tx_gas_meter: &TxGasMeter, since: Gas) -> Gas {
+    tx_gas_meter
+        .get_tx_consumed_gas()
+        .checked_sub(since)
+        .unwrap_or_default()
+}
+
 /// Apply a derived transaction to storage based on some protocol transaction.
 /// The logic here must be completely deterministic and will be executed by all
 /// full nodes every time a protocol transaction is included in a block. Storage
@@ -785,6 +1072,17 @@ where
     use namada_ethereum_bridge::protocol::transactions;
     use namada_vote_ext::{ethereum_events, validator_set_update};
 
+    if let ProtocolTxType::BlockSeed = tx {
+        // The block seed is updated at the beginning of the block,
+        // before any tx is applied
+        return Ok(TxResult::default());
+    }
+    if let ProtocolTxType::ValidatorOperationalMetadata = tx {
+        return apply_operational_metadata_tx(data, state);
+    }
+    if let ProtocolTxType::TelemetryVext = tx {
+        return apply_telemetry_tx(data, state);
+    }
     let Some(data) = data else {
         return Err(Error::ProtocolTxError(eyre!(
             "Protocol tx data must be present"
@@ -839,6 +1137,92 @@ where
     }
 }
 
+/// Apply a validator's operational metadata update. The update is validated
+/// when the protocol tx is included in a block proposal.
+fn apply_operational_metadata_tx<D, H>(
+    data: Option<Vec<u8>>,
+    state: &mut WlState<D, H>,
+) -> Result<TxResult>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    use namada_core::borsh::BorshDeserialize;
+
+    use crate::proof_of_stake::operational_metadata::{
+        write_operational_metadata, OperationalMetadataUpdate,
+    };
+    use crate::proof_of_stake::storage_key::{
+        validator_operational_metadata_key,
+        validator_operational_metadata_nonce_key,
+    };
+
+    let update = data
+        .ok_or_else(|| eyre!("Protocol tx data must be present"))
+        .and_then(|data| {
+            OperationalMetadataUpdate::try_from_slice(&data)
+                .wrap_err("Invalid validator operational metadata update")
+        })
+        .map_err(Error::ProtocolTxError)?;
+    write_operational_metadata(state, &update)
+        .map_err(|err| Error::ProtocolTxError(err.into()))?;
+    Ok(TxResult {
+        changed_keys: BTreeSet::from([
+            validator_operational_metadata_key(&update.validator),
+            validator_operational_metadata_nonce_key(&update.validator),
+        ]),
+        ..Default::default()
+    })
+}
+
+/// Apply a validator's telemetry heartbeat. The heartbeat is validated when
+/// the protocol tx is included in a block proposal.
+fn apply_telemetry_tx<D, H>(
+    data: Option<Vec<u8>>,
+    state: &mut WlState<D, H>,
+) -> Result<TxResult>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    use namada_core::borsh::BorshDeserialize;
+    use namada_vote_ext::telemetry;
+
+    use crate::proof_of_stake::storage_key::validator_telemetry_key;
+    use crate::proof_of_stake::telemetry::{
+        write_validator_telemetry, ValidatorTelemetry,
+    };
+
+    let telemetry::Vext {
+        validator_addr,
+        block_height,
+        binary_version,
+        commit_hash,
+    } = data
+        .ok_or_else(|| eyre!("Protocol tx data must be present"))
+        .and_then(|data| {
+            telemetry::Vext::try_from_slice(&data)
+                .wrap_err("Invalid telemetry vote extension")
+        })
+        .map_err(Error::ProtocolTxError)?;
+    write_validator_telemetry(
+        state,
+        &validator_addr,
+        &ValidatorTelemetry {
+            binary_version,
+            commit_hash,
+            block_height,
+        },
+    )
+    .map_err(|err| Error::ProtocolTxError(err.into()))?;
+    Ok(TxResult {
+        changed_keys: BTreeSet::from([validator_telemetry_key(
+            &validator_addr,
+        )]),
+        ..Default::default()
+    })
+}
+
 /// Execute a transaction code. Returns verifiers requested by the transaction.
 #[allow(clippy::too_many_arguments)]
 fn execute_tx<S, D, H, CA>(
@@ -899,9 +1283,13 @@ where
     S: State + Sync,
     CA: 'static + WasmCacheAccess + Sync,
 {
-    let (verifiers, keys_changed) = state
+    let (mut verifiers, keys_changed) = state
         .write_log()
         .verifiers_and_changed_keys(verifiers_from_tx);
+    native_vp::registry::add_key_namespace_verifiers(
+        &keys_changed,
+        &mut verifiers,
+    );
 
     let vps_result = execute_vps(
         verifiers,
@@ -985,86 +1373,13 @@ where
                         vp_wasm_cache.clone(),
                     );
 
-                    match internal_addr {
-                        InternalAddress::PoS => {
-                            let pos = PosVP { ctx };
-                            pos.validate_tx(tx, &keys_changed, &verifiers)
-                                .map_err(Error::PosNativeVpError)
-                        }
-                        InternalAddress::Ibc => {
-                            let ibc = Ibc { ctx };
-                            ibc.validate_tx(tx, &keys_changed, &verifiers)
-                                .map_err(Error::IbcNativeVpError)
-                        }
-                        InternalAddress::Parameters => {
-                            let parameters = ParametersVp { ctx };
-                            parameters
-                                .validate_tx(tx, &keys_changed, &verifiers)
-                                .map_err(Error::ParametersNativeVpError)
-                        }
-                        InternalAddress::PosSlashPool => Err(
-                            Error::AccessForbidden((*internal_addr).clone()),
-                        ),
-                        InternalAddress::Governance => {
-                            let governance = GovernanceVp { ctx };
-                            governance
-                                .validate_tx(tx, &keys_changed, &verifiers)
-                                .map_err(Error::GovernanceNativeVpError)
-                        }
-                        InternalAddress::Multitoken => {
-                            let multitoken = MultitokenVp { ctx };
-                            multitoken
-                                .validate_tx(tx, &keys_changed, &verifiers)
-                                .map_err(Error::MultitokenNativeVpError)
-                        }
-                        InternalAddress::EthBridge => {
-                            let bridge = EthBridge { ctx };
-                            bridge
-                                .validate_tx(tx, &keys_changed, &verifiers)
-                                .map_err(Error::EthBridgeNativeVpError)
-                        }
-                        InternalAddress::EthBridgePool => {
-                            let bridge_pool = BridgePoolVp { ctx };
-                            bridge_pool
-                                .validate_tx(tx, &keys_changed, &verifiers)
-                                .map_err(Error::BridgePoolNativeVpError)
-                        }
-                        InternalAddress::Pgf => {
-                            let pgf_vp = PgfVp { ctx };
-                            pgf_vp
-                                .validate_tx(tx, &keys_changed, &verifiers)
-                                .map_err(Error::PgfNativeVpError)
-                        }
-                        InternalAddress::Nut(_) => {
-                            let non_usable_tokens = NonUsableTokens { ctx };
-                            non_usable_tokens
-                                .validate_tx(tx, &keys_changed, &verifiers)
-                                .map_err(Error::NutNativeVpError)
-                        }
-                        internal_addr @ (InternalAddress::IbcToken(_)
-                        | InternalAddress::Erc20(_)) => {
-                            // The address should be a part of a multitoken
-                            // key
-                            verifiers
-                                .contains(&Address::Internal(
-                                    InternalAddress::Multitoken,
-                                ))
-                                .ok_or_else(|| {
-                                    Error::AccessForbidden(
-                                        internal_addr.clone(),
-                                    )
-                                })
-                        }
-                        InternalAddress::Masp => {
-                            let masp = MaspVp { ctx };
-                            masp.validate_tx(tx, &keys_changed, &verifiers)
-                                .map_err(Error::MaspNativeVpError)
-                        }
-                        InternalAddress::TempStorage => Err(
-                            // Temp storage changes must never be committed
-                            Error::AccessForbidden((*internal_addr).clone()),
-                        ),
-                    }
+                    native_vp::registry::validate_tx(
+                        internal_addr,
+                        ctx,
+                        tx,
+                        &keys_changed,
+                        &verifiers,
+                    )
                 }
             };
 
@@ -1075,6 +1390,7 @@ where
                         .insert(err.invalid_section_signature_flag());
                     result.rejected_vps.insert(addr.clone());
                     result.errors.push((addr.clone(), err.to_string()));
+                    result.error_codes.insert(addr.clone(), err.error_code());
                 },
                 |()| {
                     result.accepted_vps.insert(addr.clone());
@@ -1088,9 +1404,13 @@ where
             // all the other errors we keep evaluating the vps. This
             // allows to display a consistent VpsResult across all
             // nodes and find any invalid signatures
+            let gas_meter = gas_meter.into_inner();
+            result
+                .gas_used_by_vp
+                .insert(addr.clone(), gas_meter.get_vp_consumed_gas());
             result
                 .gas_used
-                .set(gas_meter.into_inner())
+                .set(gas_meter)
                 .map_err(|err| Error::GasError(err.to_string()))?;
 
             Ok(result)
@@ -1099,9 +1419,61 @@ where
             merge_vp_results(a, b, tx_gas_meter)
         })?;
 
+    #[cfg(feature = "debug-invariants")]
+    check_invariants(
+        &keys_changed,
+        &verifiers,
+        tx,
+        tx_index,
+        state,
+        vp_wasm_cache,
+    );
+
     Ok(vps_result)
 }
 
+/// Check the global accounting invariants after a tx. A violation is only
+/// logged, like the checks at the end of the block, so that the feature
+/// doesn't change which txs are accepted.
+#[cfg(feature = "debug-invariants")]
+fn check_invariants<S, CA>(
+    keys_changed: &BTreeSet<storage::Key>,
+    verifiers: &BTreeSet<Address>,
+    tx: &Tx,
+    tx_index: &TxIndex,
+    state: &S,
+    vp_wasm_cache: &mut VpCache<CA>,
+) where
+    S: State + Sync,
+    CA: 'static + WasmCacheAccess + Sync,
+{
+    use native_vp::invariants::InvariantsVp;
+    use native_vp::NativeVp;
+
+    // The checks scan the storage, so their gas is not metered
+    let gas_meter =
+        RefCell::new(VpGasMeter::new_from_tx_meter(&TxGasMeter::new(u64::MAX)));
+    let multitoken = Address::Internal(InternalAddress::Multitoken);
+    let ctx = native_vp::Ctx::new(
+        &multitoken,
+        state,
+        tx,
+        tx_index,
+        &gas_meter,
+        keys_changed,
+        verifiers,
+        vp_wasm_cache.clone(),
+    );
+    let vp = InvariantsVp { ctx };
+    if let Err(err) = vp.validate_tx(tx, keys_changed, verifiers) {
+        tracing::error!(
+            "Tx {} violates a global invariant of {}: {err}",
+            tx.header_hash(),
+            err.address(),
+        );
+    }
+}
+
 /// Merge VP results from parallel runs
 fn merge_vp_results(
     a: VpsResult,
@@ -1116,6 +1488,10 @@ fn merge_vp_results(
     errors.append(&mut b.errors);
     let status_flags = a.status_flags | b.status_flags;
     let mut gas_used = a.gas_used;
+    let mut gas_used_by_vp = a.gas_used_by_vp;
+    gas_used_by_vp.append(&mut b.gas_used_by_vp);
+    let mut error_codes = a.error_codes;
+    error_codes.append(&mut b.error_codes);
 
     gas_used
         .merge(b.gas_used, tx_gas_meter)
@@ -1125,7 +1501,9 @@ fn merge_vp_results(
         accepted_vps,
         rejected_vps,
         gas_used,
+        gas_used_by_vp,
         errors,
+        error_codes,
         status_flags,
     })
 }
@@ -1133,11 +1511,18 @@ fn merge_vp_results(
 #[cfg(test)]
 mod tests {
     use eyre::Result;
+    use masp_primitives::consensus::BranchId;
+    use masp_primitives::transaction::components::transparent::{self, TxOut};
+    use masp_primitives::transaction::{
+        TransactionData, TransparentAddress, TxVersion,
+    };
     use namada_core::collections::HashMap;
     use namada_core::ethereum_events::testing::DAI_ERC20_ETH_ADDRESS;
     use namada_core::ethereum_events::{EthereumEvent, TransferToNamada};
     use namada_core::keccak::keccak_hash;
+    use namada_core::key::RefTo;
     use namada_core::storage::BlockHeight;
+    use namada_core::token::Denomination;
     use namada_core::voting_power::FractionalVotingPower;
     use namada_core::{address, key};
     use namada_ethereum_bridge::protocol::transactions::votes::{
@@ -1147,6 +1532,7 @@ mod tests {
     use namada_ethereum_bridge::storage::proof::EthereumProof;
     use namada_ethereum_bridge::storage::{vote_tallies, vp};
     use namada_ethereum_bridge::test_utils;
+    use namada_tx::data::Fee;
     use namada_tx::{SignableEthMessage, Signed};
     use namada_vote_ext::bridge_pool_roots::BridgePoolRootVext;
     use namada_vote_ext::ethereum_events::EthereumEventsVext;
@@ -1361,4 +1747,154 @@ mod tests {
         );
         assert!(matches!(result.unwrap_err(), Error::GasError(_)));
     }
+
+    /// Build a fee unshielding transaction with a single transparent output of
+    /// the given value of the token to the fee collector
+    fn fee_collector_unshielding(
+        token: &Address,
+        denom: Denomination,
+        value: u64,
+    ) -> Transaction {
+        let collector_hash: [u8; 20] = ripemd::Ripemd160::digest(
+            sha2::Sha256::digest(FEE_COLLECTOR.serialize_to_vec()),
+        )
+        .into();
+        let asset_type = namada_core::masp::encode_asset_type(
+            token.clone(),
+            denom,
+            MaspDigitPos::Zero,
+            None,
+        )
+        .unwrap();
+        let bundle = transparent::Bundle {
+            vin: vec![],
+            vout: vec![TxOut {
+                asset_type,
+                value,
+                address: TransparentAddress(collector_hash),
+            }],
+            authorization: transparent::Authorized,
+        };
+        TransactionData::from_parts(
+            TxVersion::MASPv5,
+            BranchId::MASP,
+            0,
+            masp_primitives::consensus::BlockHeight::from_u32(0),
+            Some(bundle),
+            None,
+        )
+        .freeze()
+        .unwrap()
+    }
+
+    /// Test that the fee is paid out of the shielded pool only if the outputs
+    /// of the unshielding to the fee collector sum up to exactly the fee
+    #[test]
+    fn test_shielded_fee_payment_exact_fee() {
+        let (mut state, _validators) = test_utils::setup_default_storage();
+
+        // some random token address
+        let token = Address::Established([0xff; 20].into());
+        let denom = Denomination(0);
+        namada_token::write_denom(&mut state, &token, denom).unwrap();
+
+        // a fee of 100 tokens
+        let wrapper = WrapperTx::new(
+            Fee {
+                amount_per_gas_unit: DenominatedAmount::new(
+                    Amount::from_u64(1),
+                    denom,
+                ),
+                token: token.clone(),
+            },
+            key::testing::keypair_1().ref_to(),
+            100.into(),
+            None,
+        );
+
+        let exact = fee_collector_unshielding(&token, denom, 100);
+        assert!(is_shielded_fee_payment(&state, &wrapper, &exact));
+        assert_eq!(
+            get_fee_source(&state, &wrapper, Some(&exact)),
+            FEE_COLLECTOR
+        );
+
+        // an output larger than the fee
+        let larger = fee_collector_unshielding(&token, denom, 101);
+        assert!(!is_shielded_fee_payment(&state, &wrapper, &larger));
+        assert_eq!(
+            get_fee_source(&state, &wrapper, Some(&larger)),
+            wrapper.fee_payer()
+        );
+
+        // an output smaller than the fee
+        let smaller = fee_collector_unshielding(&token, denom, 99);
+        assert!(!is_shielded_fee_payment(&state, &wrapper, &smaller));
+        assert_eq!(
+            get_fee_source(&state, &wrapper, Some(&smaller)),
+            wrapper.fee_payer()
+        );
+    }
+
+    /// Test that an unshielding to the fee collector must credit it with
+    /// exactly the fee, so that the fee is never paid out of the balance
+    /// pooled in the fee collector
+    #[test]
+    fn test_shielded_fee_payment_exact_credit() {
+        let (mut state, _validators) = test_utils::setup_default_storage();
+
+        // some random token address
+        let token = Address::Established([0xff; 20].into());
+        let denom = Denomination(0);
+        namada_token::write_denom(&mut state, &token, denom).unwrap();
+
+        // a fee of 100 tokens
+        let wrapper = WrapperTx::new(
+            Fee {
+                amount_per_gas_unit: DenominatedAmount::new(
+                    Amount::from_u64(1),
+                    denom,
+                ),
+                token: token.clone(),
+            },
+            key::testing::keypair_1().ref_to(),
+            100.into(),
+            None,
+        );
+
+        // the fee collector already pools 1000 tokens
+        let pooled = Amount::from_u64(1000);
+        namada_token::credit_tokens(&mut state, &token, &FEE_COLLECTOR, pooled)
+            .unwrap();
+
+        // an unshielding of less than the fee
+        namada_token::credit_tokens(
+            &mut state,
+            &token,
+            &FEE_COLLECTOR,
+            Amount::from_u64(99),
+        )
+        .unwrap();
+        assert!(!is_exact_fee_credit(&state, &wrapper, pooled));
+
+        // an unshielding of exactly the fee
+        namada_token::credit_tokens(
+            &mut state,
+            &token,
+            &FEE_COLLECTOR,
+            Amount::from_u64(1),
+        )
+        .unwrap();
+        assert!(is_exact_fee_credit(&state, &wrapper, pooled));
+
+        // an unshielding of more than the fee
+        namada_token::credit_tokens(
+            &mut state,
+            &token,
+            &FEE_COLLECTOR,
+            Amount::from_u64(1),
+        )
+        .unwrap();
+        assert!(!is_exact_fee_credit(&state, &wrapper, pooled));
+    }
 }
@@ -4,7 +4,7 @@ use std::cell::RefCell;
 use std::fmt::Debug;
 use std::num::TryFromIntError;
 
-use namada_core::address::{Address, ESTABLISHED_ADDRESS_BYTES_LEN};
+use namada_core::address::{self, Address, ESTABLISHED_ADDRESS_BYTES_LEN};
 use namada_core::hash::{Hash, HASH_LENGTH};
 use namada_core::storage::{
     BlockHeight, Epoch, Epochs, Header, Key, TxIndex, TX_INDEX_LENGTH,
@@ -31,6 +31,8 @@ pub enum RuntimeError {
     StorageDataError(crate::storage::Error),
     #[error("Encoding error: {0}")]
     EncodingError(std::io::Error),
+    #[error("Address error: {0}")]
+    AddressError(address::DecodeError),
     #[error("Numeric conversion error: {0}")]
     NumConversionError(TryFromIntError),
     #[error("Memory error: {0}")]
@@ -277,6 +279,13 @@ where
     Ok(epoch)
 }
 
+/// Getting the amount of gas still available to the VP, after charging for
+/// this query.
+pub fn get_gas_remaining(gas_meter: &RefCell<VpGasMeter>) -> EnvResult<u64> {
+    add_gas(gas_meter, 8 * MEMORY_ACCESS_GAS_PER_BYTE)?;
+    Ok(gas_meter.borrow().get_available_gas().into())
+}
+
 /// Getting the block epoch. The epoch is that of the block to which the
 /// current transaction is being applied.
 pub fn get_tx_index(
@@ -36,7 +36,8 @@ use crate::address::{self, Address};
 use crate::hash::Hash;
 use crate::internal::HostEnvResult;
 use crate::ledger::vp_host_fns;
-use crate::storage::{BlockHeight, Key, TxIndex};
+use crate::proof_of_stake;
+use crate::storage::{BlockHeight, Epoch, Key, TxIndex};
 use crate::token::storage_key::{
     is_any_minted_balance_key, is_any_minter_key, is_any_token_balance_key,
 };
@@ -83,6 +84,8 @@ pub enum TxRuntimeError {
     NoValueInResultBuffer,
     #[error("VP code is not allowed in allowlist parameter.")]
     DisallowedVp,
+    #[error("Tx call failed: {0}")]
+    TxCallError(String),
 }
 
 /// Result of a tx host env fn call
@@ -140,6 +143,8 @@ where
     /// Tx WASM compilation cache
     #[cfg(feature = "wasm-runtime")]
     pub tx_wasm_cache: MutHostRef<'a, &'a TxCache<CA>>,
+    /// The depth of the nested tx calls of the tx, 0 for the top-level tx
+    pub call_depth: u8,
     /// To avoid unused parameter without "wasm-runtime" feature
     #[cfg(not(feature = "wasm-runtime"))]
     pub cache_access: std::marker::PhantomData<CA>,
@@ -207,6 +212,7 @@ where
             vp_wasm_cache,
             #[cfg(feature = "wasm-runtime")]
             tx_wasm_cache,
+            call_depth: 0,
             #[cfg(not(feature = "wasm-runtime"))]
             cache_access: std::marker::PhantomData,
         };
@@ -290,6 +296,7 @@ where
             vp_wasm_cache: self.vp_wasm_cache.clone(),
             #[cfg(feature = "wasm-runtime")]
             tx_wasm_cache: self.tx_wasm_cache.clone(),
+            call_depth: self.call_depth,
             #[cfg(not(feature = "wasm-runtime"))]
             cache_access: std::marker::PhantomData,
         }
@@ -1652,6 +1659,24 @@ where
     Ok(epoch.0)
 }
 
+/// Getting the amount of gas still available to the transaction, exposed to
+/// the wasm VM Tx environment. The cost of this call is charged before the
+/// remaining gas is computed.
+pub fn tx_get_gas_remaining<MEM, D, H, CA>(
+    env: &TxVmEnv<MEM, D, H, CA>,
+) -> TxResult<u64>
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: WasmCacheAccess,
+{
+    tx_charge_gas::<MEM, D, H, CA>(env, 8 * MEMORY_ACCESS_GAS_PER_BYTE)?;
+    let (gas_meter, _sentinel) = env.ctx.gas_meter_and_sentinel();
+    let remaining = gas_meter.borrow().get_available_gas();
+    Ok(remaining.into())
+}
+
 /// Get predecessor epochs function exposed to the wasm VM Tx environment.
 pub fn tx_get_pred_epochs<MEM, D, H, CA>(
     env: &TxVmEnv<MEM, D, H, CA>,
@@ -1736,6 +1761,171 @@ where
     })
 }
 
+/// Getting the bonded stake of a validator at the given epoch, exposed to the
+/// wasm VM Tx environment. The stake is written to the result buffer as a
+/// borsh-encoded [`token::Amount`](crate::token::Amount).
+///
+/// Returns the length of the encoded stake.
+pub fn tx_get_validator_stake<MEM, D, H, CA>(
+    env: &TxVmEnv<MEM, D, H, CA>,
+    addr_ptr: u64,
+    addr_len: u64,
+    epoch: u64,
+) -> TxResult<i64>
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let (addr, gas) = env
+        .memory
+        .read_string(addr_ptr, addr_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas::<MEM, D, H, CA>(env, gas)?;
+    tx_charge_gas::<MEM, D, H, CA>(env, gas::POS_QUERY_GAS)?;
+
+    tracing::debug!("tx_get_validator_stake {}, epoch {}", addr, epoch);
+
+    let validator =
+        Address::decode(&addr).map_err(TxRuntimeError::AddressError)?;
+    let state = env.state();
+    let params = proof_of_stake::storage::read_pos_params(&state)?;
+    let stake = proof_of_stake::storage::read_validator_stake(
+        &state,
+        &params,
+        &validator,
+        Epoch(epoch),
+    )?;
+    let value = stake.serialize_to_vec();
+    let len: i64 = value
+        .len()
+        .try_into()
+        .map_err(TxRuntimeError::NumConversionError)?;
+    let result_buffer = unsafe { env.ctx.result_buffer.get() };
+    result_buffer.replace(value);
+    Ok(len)
+}
+
+/// Getting the state of a validator at the given epoch, exposed to the wasm VM
+/// Tx environment. The state is written to the result buffer as a
+/// borsh-encoded [`ValidatorState`](proof_of_stake::types::ValidatorState).
+///
+/// Returns `-1` when the address is not a validator at the given epoch, or the
+/// length of the encoded state otherwise.
+pub fn tx_get_validator_state<MEM, D, H, CA>(
+    env: &TxVmEnv<MEM, D, H, CA>,
+    addr_ptr: u64,
+    addr_len: u64,
+    epoch: u64,
+) -> TxResult<i64>
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let (addr, gas) = env
+        .memory
+        .read_string(addr_ptr, addr_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas::<MEM, D, H, CA>(env, gas)?;
+    tx_charge_gas::<MEM, D, H, CA>(env, gas::POS_QUERY_GAS)?;
+
+    tracing::debug!("tx_get_validator_state {}, epoch {}", addr, epoch);
+
+    let validator =
+        Address::decode(&addr).map_err(TxRuntimeError::AddressError)?;
+    let state = env.state();
+    let params = proof_of_stake::storage::read_pos_params(&state)?;
+    let validator_state = proof_of_stake::storage::validator_state_handle(
+        &validator,
+    )
+    .get(&state, Epoch(epoch), &params)?;
+    Ok(match validator_state {
+        Some(validator_state) => {
+            let value = validator_state.serialize_to_vec();
+            let len: i64 = value
+                .len()
+                .try_into()
+                .map_err(TxRuntimeError::NumConversionError)?;
+            let result_buffer = unsafe { env.ctx.result_buffer.get() };
+            result_buffer.replace(value);
+            len
+        }
+        None => HostEnvResult::Fail.to_i64(),
+    })
+}
+
+/// Getting the total bonded stake at the given epoch, exposed to the wasm VM
+/// Tx environment. The stake is written to the result buffer as a
+/// borsh-encoded [`token::Amount`](crate::token::Amount).
+///
+/// Returns the length of the encoded stake.
+pub fn tx_get_total_stake<MEM, D, H, CA>(
+    env: &TxVmEnv<MEM, D, H, CA>,
+    epoch: u64,
+) -> TxResult<i64>
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: WasmCacheAccess,
+{
+    tx_charge_gas::<MEM, D, H, CA>(env, gas::POS_QUERY_GAS)?;
+
+    tracing::debug!("tx_get_total_stake epoch {}", epoch);
+
+    let state = env.state();
+    let params = proof_of_stake::storage::read_pos_params(&state)?;
+    let stake = proof_of_stake::storage::read_total_stake(
+        &state,
+        &params,
+        Epoch(epoch),
+    )?;
+    let value = stake.serialize_to_vec();
+    let len: i64 = value
+        .len()
+        .try_into()
+        .map_err(TxRuntimeError::NumConversionError)?;
+    let result_buffer = unsafe { env.ctx.result_buffer.get() };
+    result_buffer.replace(value);
+    Ok(len)
+}
+
+/// Getting the randomness of the current block, i.e. its seed, exposed to the
+/// wasm VM Tx environment. The seed is written to the result buffer as a
+/// borsh-encoded [`Hash`]. It can be biased by the block proposer, see
+/// [`proof_of_stake::block_seed`] for the guarantees it provides.
+///
+/// Returns `-1` when no block seed has been derived yet, or the length
+/// of the encoded seed otherwise.
+pub fn tx_get_block_randomness<MEM, D, H, CA>(
+    env: &TxVmEnv<MEM, D, H, CA>,
+) -> TxResult<i64>
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let state = env.state();
+    let seed = proof_of_stake::block_seed::read_block_seed(&state)?;
+    Ok(match seed {
+        Some(seed) => {
+            let value = seed.serialize_to_vec();
+            let len: i64 = value
+                .len()
+                .try_into()
+                .map_err(TxRuntimeError::NumConversionError)?;
+            let result_buffer = unsafe { env.ctx.result_buffer.get() };
+            result_buffer.replace(value);
+            len
+        }
+        None => HostEnvResult::Fail.to_i64(),
+    })
+}
+
 /// Getting the chain ID function exposed to the wasm VM VP environment.
 pub fn vp_get_chain_id<MEM, D, H, EVAL, CA>(
     env: &VpVmEnv<MEM, D, H, EVAL, CA>,
@@ -1858,6 +2048,23 @@ where
     Ok(epoch.0)
 }
 
+/// Getting the amount of gas still available to the VP, exposed to the wasm
+/// VM VP environment. The cost of this call is charged before the remaining
+/// gas is computed.
+pub fn vp_get_gas_remaining<MEM, D, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, D, H, EVAL, CA>,
+) -> vp_host_fns::EnvResult<u64>
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let gas_meter = env.ctx.gas_meter();
+    vp_host_fns::get_gas_remaining(gas_meter)
+}
+
 /// Get predecessor epochs function exposed to the wasm VM VP environment.
 pub fn vp_get_pred_epochs<MEM, D, H, EVAL, CA>(
     env: &VpVmEnv<MEM, D, H, EVAL, CA>,
@@ -1882,6 +2089,181 @@ where
     Ok(len)
 }
 
+/// Getting the bonded stake of a validator at the given epoch, exposed to the
+/// wasm VM VP environment. The stake is read from the posterior state and
+/// written to the result buffer as a borsh-encoded
+/// [`token::Amount`](crate::token::Amount).
+///
+/// Returns the length of the encoded stake.
+pub fn vp_get_validator_stake<MEM, D, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, D, H, EVAL, CA>,
+    addr_ptr: u64,
+    addr_len: u64,
+    epoch: u64,
+) -> vp_host_fns::EnvResult<i64>
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let (addr, gas) = env
+        .memory
+        .read_string(addr_ptr, addr_len as _)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    let gas_meter = env.ctx.gas_meter();
+    vp_host_fns::add_gas(gas_meter, gas)?;
+    vp_host_fns::add_gas(gas_meter, gas::POS_QUERY_GAS)?;
+
+    let validator = Address::decode(&addr)
+        .map_err(vp_host_fns::RuntimeError::AddressError)?;
+    let state = env.state();
+    let params = proof_of_stake::storage::read_pos_params(&state)
+        .map_err(|e| vp_host_fns::RuntimeError::StorageError(e.into()))?;
+    let stake = proof_of_stake::storage::read_validator_stake(
+        &state,
+        &params,
+        &validator,
+        Epoch(epoch),
+    )
+    .map_err(|e| vp_host_fns::RuntimeError::StorageError(e.into()))?;
+    let value = stake.serialize_to_vec();
+    let len: i64 = value
+        .len()
+        .try_into()
+        .map_err(vp_host_fns::RuntimeError::NumConversionError)?;
+    let result_buffer = unsafe { env.ctx.result_buffer.get() };
+    result_buffer.replace(value);
+    Ok(len)
+}
+
+/// Getting the state of a validator at the given epoch, exposed to the wasm VM
+/// VP environment. The state is read from the posterior state and written to
+/// the result buffer as a borsh-encoded
+/// [`ValidatorState`](proof_of_stake::types::ValidatorState).
+///
+/// Returns `-1` when the address is not a validator at the given epoch, or the
+/// length of the encoded state otherwise.
+pub fn vp_get_validator_state<MEM, D, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, D, H, EVAL, CA>,
+    addr_ptr: u64,
+    addr_len: u64,
+    epoch: u64,
+) -> vp_host_fns::EnvResult<i64>
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let (addr, gas) = env
+        .memory
+        .read_string(addr_ptr, addr_len as _)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    let gas_meter = env.ctx.gas_meter();
+    vp_host_fns::add_gas(gas_meter, gas)?;
+    vp_host_fns::add_gas(gas_meter, gas::POS_QUERY_GAS)?;
+
+    let validator = Address::decode(&addr)
+        .map_err(vp_host_fns::RuntimeError::AddressError)?;
+    let state = env.state();
+    let params = proof_of_stake::storage::read_pos_params(&state)
+        .map_err(|e| vp_host_fns::RuntimeError::StorageError(e.into()))?;
+    let validator_state =
+        proof_of_stake::storage::validator_state_handle(&validator)
+            .get(&state, Epoch(epoch), &params)
+            .map_err(|e| vp_host_fns::RuntimeError::StorageError(e.into()))?;
+    Ok(match validator_state {
+        Some(validator_state) => {
+            let value = validator_state.serialize_to_vec();
+            let len: i64 = value
+                .len()
+                .try_into()
+                .map_err(vp_host_fns::RuntimeError::NumConversionError)?;
+            let result_buffer = unsafe { env.ctx.result_buffer.get() };
+            result_buffer.replace(value);
+            len
+        }
+        None => HostEnvResult::Fail.to_i64(),
+    })
+}
+
+/// Getting the total bonded stake at the given epoch, exposed to the wasm VM
+/// VP environment. The stake is read from the posterior state and written to
+/// the result buffer as a borsh-encoded
+/// [`token::Amount`](crate::token::Amount).
+///
+/// Returns the length of the encoded stake.
+pub fn vp_get_total_stake<MEM, D, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, D, H, EVAL, CA>,
+    epoch: u64,
+) -> vp_host_fns::EnvResult<i64>
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let gas_meter = env.ctx.gas_meter();
+    vp_host_fns::add_gas(gas_meter, gas::POS_QUERY_GAS)?;
+
+    let state = env.state();
+    let params = proof_of_stake::storage::read_pos_params(&state)
+        .map_err(|e| vp_host_fns::RuntimeError::StorageError(e.into()))?;
+    let stake = proof_of_stake::storage::read_total_stake(
+        &state,
+        &params,
+        Epoch(epoch),
+    )
+    .map_err(|e| vp_host_fns::RuntimeError::StorageError(e.into()))?;
+    let value = stake.serialize_to_vec();
+    let len: i64 = value
+        .len()
+        .try_into()
+        .map_err(vp_host_fns::RuntimeError::NumConversionError)?;
+    let result_buffer = unsafe { env.ctx.result_buffer.get() };
+    result_buffer.replace(value);
+    Ok(len)
+}
+
+/// Getting the randomness of the current block, i.e. its seed, exposed to the
+/// wasm VM VP environment. The seed is written to the result buffer as a
+/// borsh-encoded [`Hash`]. It can be biased by the block proposer, see
+/// [`proof_of_stake::block_seed`] for the guarantees it provides.
+///
+/// Returns `-1` when no block seed has been derived yet, or the length
+/// of the encoded seed otherwise.
+pub fn vp_get_block_randomness<MEM, D, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, D, H, EVAL, CA>,
+) -> vp_host_fns::EnvResult<i64>
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let state = env.state();
+    let seed = proof_of_stake::block_seed::read_block_seed(&state)
+        .map_err(|e| vp_host_fns::RuntimeError::StorageError(e.into()))?;
+    Ok(match seed {
+        Some(seed) => {
+            let value = seed.serialize_to_vec();
+            let len: i64 = value
+                .len()
+                .try_into()
+                .map_err(vp_host_fns::RuntimeError::NumConversionError)?;
+            let result_buffer = unsafe { env.ctx.result_buffer.get() };
+            result_buffer.replace(value);
+            len
+        }
+        None => HostEnvResult::Fail.to_i64(),
+    })
+}
+
 /// Expose the functionality to query events from the wasm VM's VP environment.
 pub fn vp_get_events<MEM, D, H, EVAL, CA>(
     env: &VpVmEnv<MEM, D, H, EVAL, CA>,
@@ -2018,7 +2400,9 @@ where
     Ok(())
 }
 
-/// Execute IBC tx.
+/// Execute IBC tx. Returns the transfer of the IBC message and the memo hook
+/// requested by a received transfer, whose fee is paid to the fee payer of
+/// the wrapper tx.
 // Temporarily the IBC tx execution is implemented via a host function to
 // workaround wasm issue.
 pub fn tx_ibc_execute<MEM, D, H, CA>(
@@ -2046,13 +2430,20 @@ where
     // Verifier set populated in tx execution
     let verifiers = Rc::new(RefCell::new(BTreeSet::<Address>::new()));
     // Scoped to drop `verifiers.clone`s after `actions.execute`
-    let transfer = {
+    let result = {
         let mut actions = IbcActions::new(state.clone(), verifiers.clone());
         let module = TransferModule::new(state.clone(), verifiers.clone());
         actions.add_transfer_module(module);
         let module = NftTransferModule::new(state);
         actions.add_transfer_module(module);
-        actions.execute(&tx_data)?
+        let transfer = actions.execute(&tx_data)?;
+        let memo_hook = match tx.header().wrapper() {
+            Some(wrapper) => {
+                actions.memo_hook(&tx_data, &wrapper.fee_payer())?
+            }
+            None => None,
+        };
+        (transfer, memo_hook)
     };
     // NB: There must be no other strong references to this Rc
     let verifiers = Rc::into_inner(verifiers)
@@ -2069,7 +2460,7 @@ where
         verifiers_in_env.insert(addr);
     }
 
-    let value = transfer.serialize_to_vec();
+    let value = result.serialize_to_vec();
     let len: i64 = value
         .len()
         .try_into()
@@ -2,7 +2,9 @@
 //! The cache is backed by in-memory LRU cache with configurable size
 //! limit and a file system cache of compiled modules (either to dynamic libs
 //! compiled via the `dylib` module, or serialized modules compiled via the
-//! `universal` module).
+//! `universal` module). The file system cache is keyed by the code hash and
+//! the versions of Namada, wasmer and the toolchain, so that it can be reloaded
+//! after a restart with [`Cache::load_from_disk`].
 
 use std::collections::hash_map::RandomState;
 use std::fs;
@@ -86,8 +88,9 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
             hasher.finish()
         };
         let version = format!(
-            "{}_{:x}",
+            "{}_{}_{:x}",
             concat!(env!("CARGO_PKG_VERSION"), "_", env!("RUSTUP_TOOLCHAIN")),
+            wasmer::VERSION,
             target_hash,
         );
         let dir = dir.into().join(version);
@@ -469,6 +472,67 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
         }
     }
 
+    /// Reload the modules compiled before a restart from the file system cache
+    /// into the in-memory cache, until it's full. Every file is validated by
+    /// loading it and the invalid ones are removed, so that their modules get
+    /// recompiled when they're needed. Returns the number of loaded modules.
+    pub fn load_from_disk(&mut self) -> usize {
+        if !A::is_read_write() {
+            return 0;
+        }
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) => {
+                tracing::error!(
+                    "Couldn't read the {} directory: {err}",
+                    N::name()
+                );
+                return 0;
+            }
+        };
+        let mut loaded = 0;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(hash) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| Hash::try_from(name).ok())
+            else {
+                continue;
+            };
+            if !module_file_exists(&self.dir, &hash) {
+                continue;
+            }
+            match file_load_module(&self.dir, &hash) {
+                Ok((module, _store)) => {
+                    self.progress
+                        .write()
+                        .unwrap()
+                        .insert(hash, Compilation::Done);
+                    let mut in_memory = self.in_memory.write().unwrap();
+                    if in_memory.put_with_weight(hash, module).is_ok() {
+                        loaded += 1;
+                    }
+                }
+                Err(_) => {
+                    tracing::info!(
+                        "Removing invalid {} file {}.",
+                        N::name(),
+                        hash.to_string()
+                    );
+                    if let Err(err) = fs::remove_dir_all(&path) {
+                        tracing::error!(
+                            "Couldn't remove {}: {err}",
+                            path.to_string_lossy()
+                        );
+                    }
+                }
+            }
+        }
+        tracing::info!("Loaded {loaded} {} from the file system.", N::name());
+        loaded
+    }
+
     /// Get a read-only cache handle.
     pub fn read_only(&self) -> Cache<N, WasmCacheRoAccess> {
         Cache {
@@ -1052,6 +1116,47 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_load_from_disk() {
+        let tx_no_op = load_wasm(TestWasms::TxNoOp.path());
+        let (mut cache, tmp_dir) = cache(100);
+        cache.compile_or_fetch(&tx_no_op.code).unwrap().unwrap();
+
+        // Write an invalid module file
+        let invalid_hash = hash_of_code([1_u8]);
+        let invalid_dir =
+            cache.dir.join(invalid_hash.to_string().to_lowercase());
+        fs::create_dir_all(&invalid_dir).unwrap();
+        fs::write(
+            invalid_dir.join(format!(
+                "{}.{}",
+                invalid_hash.to_string().to_lowercase(),
+                file_ext()
+            )),
+            [1_u8],
+        )
+        .unwrap();
+        assert!(module_file_exists(&cache.dir, &invalid_hash));
+
+        // Restart with a new cache in the same dir
+        let mut cache: Cache<TestCache, WasmCacheRwAccess> =
+            Cache::new(tmp_dir.path(), 100);
+        assert_eq!(cache.load_from_disk(), 1);
+        {
+            let in_memory = cache.in_memory.read().unwrap();
+            assert_matches!(
+                in_memory.peek(&tx_no_op.hash),
+                Some(_),
+                "The valid module must be loaded in memory"
+            );
+        }
+        assert!(
+            !module_file_exists(&cache.dir, &invalid_hash),
+            "The invalid file must be removed"
+        );
+        assert_matches!(cache.fetch(&tx_no_op.hash).unwrap(), Some(_));
+    }
+
     /// Get the WASM code bytes, its hash and find the compiled module's size
     fn load_wasm(file: impl AsRef<Path>) -> WasmWithMeta {
         // When `WeightScale` calls `loupe::size_of_val` in the cache, for some
@@ -11,6 +11,7 @@ use wasmer::{
 
 use crate::vm::host_env::{TxVmEnv, VpEvaluator, VpVmEnv};
 use crate::vm::wasm::memory::WasmMemory;
+use crate::vm::wasm::run;
 use crate::vm::{host_env, WasmCacheAccess};
 
 impl<D, H, CA> WasmerEnv for TxVmEnv<'_, WasmMemory, D, H, CA>
@@ -51,9 +52,9 @@ pub fn tx_imports<D, H, CA>(
     env: TxVmEnv<'static, WasmMemory, D, H, CA>,
 ) -> ImportObject
 where
-    D: DB + for<'iter> DBIter<'iter>,
-    H: StorageHasher,
-    CA: WasmCacheAccess,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: 'static + WasmCacheAccess,
 {
     wasmer::imports! {
         // default namespace
@@ -79,8 +80,13 @@ where
             "namada_tx_get_tx_index" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_tx_index),
             "namada_tx_get_block_height" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_block_height),
             "namada_tx_get_block_header" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_block_header),
+            "namada_tx_get_validator_stake" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_validator_stake),
+            "namada_tx_get_validator_state" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_validator_state),
+            "namada_tx_get_total_stake" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_total_stake),
+            "namada_tx_get_block_randomness" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_block_randomness),
             "namada_tx_get_block_epoch" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_block_epoch),
             "namada_tx_get_pred_epochs" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_pred_epochs),
+            "namada_tx_get_gas_remaining" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_gas_remaining),
             "namada_tx_get_native_token" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_native_token),
             "namada_tx_log_string" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_log_string),
             "namada_tx_ibc_execute" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_ibc_execute),
@@ -88,6 +94,8 @@ where
             "namada_tx_verify_tx_section_signature" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_verify_tx_section_signature),
             "namada_tx_update_masp_note_commitment_tree" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_update_masp_note_commitment_tree),
             "namada_tx_yield_value" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_yield_value),
+            "namada_tx_call" => Function::new_native_with_env(wasm_store, env.clone(), run::tx_call),
+            "namada_tx_call_with_gas_limit" => Function::new_native_with_env(wasm_store, env.clone(), run::tx_call_with_gas_limit),
         },
     }
 }
@@ -127,6 +135,11 @@ where
             "namada_vp_get_tx_code_hash" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_tx_code_hash),
             "namada_vp_get_block_epoch" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_block_epoch),
             "namada_vp_get_pred_epochs" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_pred_epochs),
+            "namada_vp_get_gas_remaining" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_gas_remaining),
+            "namada_vp_get_validator_stake" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_validator_stake),
+            "namada_vp_get_validator_state" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_validator_state),
+            "namada_vp_get_total_stake" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_total_stake),
+            "namada_vp_get_block_randomness" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_block_randomness),
             "namada_vp_get_events" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_events),
             "namada_vp_yield_value" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_yield_value),
             "namada_vp_verify_tx_section_signature" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_verify_tx_section_signature),
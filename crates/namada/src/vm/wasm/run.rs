@@ -9,10 +9,10 @@ use std::num::NonZeroU32;
 
 use borsh::BorshDeserialize;
 use namada_core::validity_predicate::VpError;
-use namada_gas::{GasMetering, TxGasMeter, WASM_MEMORY_PAGE_GAS};
+use namada_gas::{Gas, GasMetering, TxGasMeter, WASM_MEMORY_PAGE_GAS};
 use namada_state::{DBIter, State, StateRead, StorageHasher, StorageRead, DB};
 use namada_tx::data::{TxSentinel, TxType};
-use namada_tx::{Commitment, Section, Tx};
+use namada_tx::{Code, Commitment, Data, Section, Tx};
 use parity_wasm::elements::Instruction::*;
 use parity_wasm::elements::{self, SignExtInstruction};
 use thiserror::Error;
@@ -26,18 +26,23 @@ use crate::internal::HostEnvResult;
 use crate::ledger::gas::VpGasMeter;
 use crate::ledger::vp_host_fns;
 use crate::storage::{Key, TxIndex};
-use crate::vm::host_env::{TxVmEnv, VpCtx, VpEvaluator, VpVmEnv};
+use crate::vm::host_env::{
+    self, TxCtx, TxRuntimeError, TxVmEnv, VpCtx, VpEvaluator, VpVmEnv,
+};
 use crate::vm::prefix_iter::PrefixIterators;
 use crate::vm::types::VpInput;
 use crate::vm::wasm::host_env::{tx_imports, vp_imports};
 use crate::vm::wasm::{memory, Cache, CacheName, VpCache};
 use crate::vm::{
-    validate_untrusted_wasm, MutHostRef, WasmCacheAccess, WasmValidationError,
+    validate_untrusted_wasm, HostRef, MutHostRef, WasmCacheAccess,
+    WasmValidationError,
 };
 
 const TX_ENTRYPOINT: &str = "_apply_tx";
 const VP_ENTRYPOINT: &str = "_validate_tx";
 const WASM_STACK_LIMIT: u32 = u16::MAX as u32;
+/// The maximum depth of nested tx calls made with [`tx_call`]
+const MAX_TX_CALL_DEPTH: u8 = 4;
 
 /// The error type returned by transactions.
 // TODO: move this to `core`, to be shared with the wasm vm,
@@ -273,6 +278,210 @@ where
     }
 }
 
+/// Host function that calls another allowed tx code with the given data from
+/// within a running tx. The called tx shares the write log, the gas meter and
+/// the verifiers with the calling tx and it's executed in the same context as
+/// the calling tx, except for its code and data. A failure of the called tx
+/// aborts the calling tx.
+pub fn tx_call<D, H, CA>(
+    env: &TxVmEnv<'static, WasmMemory, D, H, CA>,
+    code_hash_ptr: u64,
+    code_hash_len: u64,
+    data_ptr: u64,
+    data_len: u64,
+) -> host_env::TxResult<()>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    tx_call_aux(env, code_hash_ptr, code_hash_len, data_ptr, data_len, None)
+}
+
+/// Host function that calls another allowed tx code like [`tx_call`], except
+/// that the called tx can use at most the given amount of gas
+pub fn tx_call_with_gas_limit<D, H, CA>(
+    env: &TxVmEnv<'static, WasmMemory, D, H, CA>,
+    code_hash_ptr: u64,
+    code_hash_len: u64,
+    data_ptr: u64,
+    data_len: u64,
+    gas_limit: u64,
+) -> host_env::TxResult<()>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    tx_call_aux(
+        env,
+        code_hash_ptr,
+        code_hash_len,
+        data_ptr,
+        data_len,
+        Some(gas_limit),
+    )
+}
+
+fn tx_call_aux<D, H, CA>(
+    env: &TxVmEnv<'static, WasmMemory, D, H, CA>,
+    code_hash_ptr: u64,
+    code_hash_len: u64,
+    data_ptr: u64,
+    data_len: u64,
+    gas_limit: Option<u64>,
+) -> host_env::TxResult<()>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    let (code_hash, gas) = env
+        .memory
+        .read_bytes(code_hash_ptr, code_hash_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    host_env::tx_charge_gas::<WasmMemory, D, H, CA>(env, gas)?;
+    let (data, gas) = env
+        .memory
+        .read_bytes(data_ptr, data_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    host_env::tx_charge_gas::<WasmMemory, D, H, CA>(env, gas)?;
+    let code_hash = Hash::try_from(code_hash.as_slice()).map_err(|e| {
+        TxRuntimeError::TxCallError(format!("Not a valid hash: {e}"))
+    })?;
+
+    if env.ctx.call_depth >= MAX_TX_CALL_DEPTH {
+        return Err(TxRuntimeError::TxCallError(format!(
+            "Exceeded the maximum depth of nested tx calls of \
+             {MAX_TX_CALL_DEPTH}"
+        )));
+    }
+    // Lower the gas limit of the shared gas meter for the duration of the
+    // call, without raising it above the limit of the calling tx
+    let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let caller_gas_limit = gas_meter.borrow().tx_gas_limit;
+    if let Some(gas_limit) = gas_limit {
+        let call_gas_limit = gas_meter
+            .borrow()
+            .get_tx_consumed_gas()
+            .checked_add(Gas::from_whole_units(gas_limit))
+            .filter(|limit| *limit < caller_gas_limit)
+            .unwrap_or(caller_gas_limit);
+        gas_meter.borrow_mut().tx_gas_limit = call_gas_limit;
+    }
+    let result = run_tx_call(env.ctx.clone(), code_hash, data);
+    gas_meter.borrow_mut().tx_gas_limit = caller_gas_limit;
+
+    result.map_err(|err| {
+        tracing::debug!("Tx call of {code_hash} failed with {err}");
+        TxRuntimeError::TxCallError(err.to_string())
+    })
+}
+
+// Run the tx code with the given hash and data in the context of the calling
+// tx
+fn run_tx_call<D, H, CA>(
+    ctx: TxCtx<'static, D, H, CA>,
+    code_hash: Hash,
+    data: Vec<u8>,
+) -> Result<()>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    let state = ctx.state();
+    if !crate::parameters::is_tx_allowed(&state, &code_hash)
+        .map_err(|e| Error::StorageError(e.to_string()))?
+    {
+        return Err(Error::DisallowedTx);
+    }
+    let tx_wasm_cache = unsafe { ctx.tx_wasm_cache.get() };
+    let gas_meter = unsafe { ctx.gas_meter.get() };
+    let sentinel = unsafe { ctx.sentinel.get() };
+    let (module, store) = fetch_or_compile(
+        tx_wasm_cache,
+        &Commitment::Hash(code_hash),
+        &state,
+        gas_meter,
+    )?;
+
+    // The called tx gets the sections of the calling tx with its own code and
+    // data. The salts are left empty to keep the execution deterministic.
+    let mut tx = unsafe { ctx.tx.get() }.clone();
+    tx.set_code(Code {
+        salt: [0; 8],
+        code: Commitment::Hash(code_hash),
+        tag: None,
+    });
+    tx.set_data(Data { salt: [0; 8], data });
+
+    let mut iterators: PrefixIterators<'static, D> = PrefixIterators::default();
+    let mut result_buffer: Option<Vec<u8>> = None;
+    let mut yielded_value: Option<Vec<u8>> = None;
+    let env = TxVmEnv {
+        memory: WasmMemory::default(),
+        ctx: TxCtx {
+            iterators: unsafe { MutHostRef::new(&mut iterators) },
+            tx: unsafe { HostRef::new(&tx) },
+            result_buffer: unsafe { MutHostRef::new(&mut result_buffer) },
+            yielded_value: unsafe { MutHostRef::new(&mut yielded_value) },
+            call_depth: ctx.call_depth + 1,
+            ..ctx
+        },
+    };
+
+    let initial_memory =
+        memory::prepare_tx_memory(&store).map_err(Error::MemoryError)?;
+    let imports = tx_imports(&store, initial_memory, env);
+    let instance = wasmer::Instance::new(&module, &imports)
+        .map_err(|e| Error::InstantiationError(Box::new(e)))?;
+    let memory = instance
+        .exports
+        .get_memory("memory")
+        .map_err(Error::MissingModuleMemory)?;
+    let memory::TxCallInput {
+        tx_data_ptr,
+        tx_data_len,
+    } = memory::write_tx_inputs(memory, &tx).map_err(Error::MemoryError)?;
+    let apply_tx = instance
+        .exports
+        .get_function(TX_ENTRYPOINT)
+        .map_err(Error::MissingModuleEntrypoint)?
+        .native::<(u64, u64), u64>()
+        .map_err(|error| Error::UnexpectedModuleEntrypointInterface {
+            entrypoint: TX_ENTRYPOINT,
+            error,
+        })?;
+    let ok =
+        apply_tx.call(tx_data_ptr, tx_data_len).map_err(
+            |err| match *sentinel.borrow() {
+                TxSentinel::None => Error::RuntimeError(err),
+                TxSentinel::OutOfGas => Error::GasError(err.to_string()),
+                TxSentinel::InvalidCommitment => {
+                    Error::MissingSection(err.to_string())
+                }
+            },
+        )?;
+
+    if ok == 1 {
+        Ok(())
+    } else {
+        // NB: drop imports so we can safely access the
+        // `&mut` ptrs we shared with the guest
+        _ = (instance, imports);
+
+        let err = yielded_value.take().map_or_else(
+            || Ok("Execution ended abruptly with an unknown error".to_owned()),
+            |borsh_encoded_err| {
+                TxError::try_from_slice(&borsh_encoded_err)
+                    .map_err(|e| Error::ConversionError(e.to_string()))
+            },
+        )?;
+        Err(Error::TxError(err))
+    }
+}
+
 /// Execute a validity predicate code. Returns whether the validity
 /// predicate accepted storage modifications performed by the transaction
 /// that triggered the execution.
@@ -1610,6 +1819,88 @@ mod tests {
         }
     }
 
+    /// Test that a tx can call an allowed tx code, but not a disallowed one
+    #[test]
+    fn test_tx_call_allowlist() {
+        let mut state = TestState::default();
+        let caller_hash = store_tx_code(&mut state, tx_call_wasm());
+        let no_op_hash =
+            store_tx_code(&mut state, TestWasms::TxNoOp.read_bytes());
+        let target_key = Key::parse(TX_CALL_TARGET_KEY).unwrap();
+        state.write(&target_key, no_op_hash).unwrap();
+
+        crate::parameters::update_tx_allowlist_parameter(
+            &mut state,
+            vec![caller_hash.to_string(), no_op_hash.to_string()],
+        )
+        .unwrap();
+        let (result, _) = execute_tx_call(&mut state, caller_hash);
+        assert!(result.is_ok());
+
+        crate::parameters::update_tx_allowlist_parameter(
+            &mut state,
+            vec![caller_hash.to_string()],
+        )
+        .unwrap();
+        let (result, _) = execute_tx_call(&mut state, caller_hash);
+        let error = result.expect_err("The called tx code is not allowed");
+        assert!(error.to_string().contains(&Error::DisallowedTx.to_string()));
+    }
+
+    /// Test that the depth of the nested tx calls is limited
+    #[test]
+    fn test_tx_call_depth_limit() {
+        let mut state = TestState::default();
+        let caller_hash = store_tx_code(&mut state, tx_call_wasm());
+        // The tx calls itself
+        let target_key = Key::parse(TX_CALL_TARGET_KEY).unwrap();
+        state.write(&target_key, caller_hash).unwrap();
+
+        let (result, _) = execute_tx_call(&mut state, caller_hash);
+        let error = result.expect_err("The nested calls must be limited");
+        assert!(
+            error.to_string().contains(&format!(
+                "Exceeded the maximum depth of nested tx calls of \
+                 {MAX_TX_CALL_DEPTH}"
+            ))
+        );
+    }
+
+    /// Test that a failure of the called tx aborts the calling tx
+    #[test]
+    fn test_tx_call_failure_propagation() {
+        let mut state = TestState::default();
+        let caller_hash = store_tx_code(&mut state, tx_call_wasm());
+        let fail_hash =
+            store_tx_code(&mut state, TestWasms::TxFail.read_bytes());
+        let target_key = Key::parse(TX_CALL_TARGET_KEY).unwrap();
+        state.write(&target_key, fail_hash).unwrap();
+
+        let (result, _) = execute_tx_call(&mut state, caller_hash);
+        let error = result.expect_err("The called tx fails");
+        assert!(error.to_string().contains("failed tx"));
+    }
+
+    /// Test that the called tx consumes the gas of the calling tx
+    #[test]
+    fn test_tx_call_gas_sharing() {
+        let mut state = TestState::default();
+        let caller_hash = store_tx_code(&mut state, tx_call_wasm());
+        let no_op_hash =
+            store_tx_code(&mut state, TestWasms::TxNoOp.read_bytes());
+
+        // Without a target, the tx doesn't call any code
+        let (result, gas_without_call) =
+            execute_tx_call(&mut state, caller_hash);
+        assert!(result.is_ok());
+
+        let target_key = Key::parse(TX_CALL_TARGET_KEY).unwrap();
+        state.write(&target_key, no_op_hash).unwrap();
+        let (result, gas_with_call) = execute_tx_call(&mut state, caller_hash);
+        assert!(result.is_ok());
+        assert!(gas_with_call > gas_without_call);
+    }
+
     /// Test that when a function runs out of gas in guest, the execution is
     /// aborted
     #[test]
@@ -1809,6 +2100,86 @@ mod tests {
         )
     }
 
+    /// The storage key of the hash of the tx code called by [`tx_call_wasm`]
+    const TX_CALL_TARGET_KEY: &str = "tx_call_target";
+
+    /// A tx that calls the tx code whose hash is stored under
+    /// [`TX_CALL_TARGET_KEY`], if any, with empty data. The key and the hash
+    /// are placed in memory past the inputs of the tx.
+    fn tx_call_wasm() -> Vec<u8> {
+        wasmer::wat2wasm(
+            r#"
+            (module
+                (import "env" "namada_tx_read"
+                    (func $read (param i64 i64) (result i64)))
+                (import "env" "namada_tx_result_buffer"
+                    (func $result_buffer (param i64)))
+                (import "env" "namada_tx_call"
+                    (func $call (param i64 i64 i64 i64)))
+
+                (func $_apply_tx (param i64 i64) (result i64)
+                (if
+                (i64.ne
+                    (call $read (i64.const 524288) (i64.const 14))
+                    (i64.const -1))
+                (then
+                    (call $result_buffer (i64.const 524352))
+                    (call $call
+                        (i64.const 524352) (i64.const 32)
+                        (i64.const 0) (i64.const 0))))
+                (i64.const 1))
+
+                (memory (;0;) 16)
+                (data (i32.const 524288) "tx_call_target")
+                (export "memory" (memory 0))
+                (export "_apply_tx" (func $_apply_tx)))
+            "#
+            .as_bytes(),
+        )
+        .expect("unexpected error converting wat2wasm")
+        .into_owned()
+    }
+
+    /// Store the given tx code and return its hash
+    fn store_tx_code(state: &mut TestState, code: Vec<u8>) -> Hash {
+        let code_hash = Hash::sha256(&code);
+        let code_len = code.len() as u64;
+        state.write(&Key::wasm_code(&code_hash), code).unwrap();
+        state
+            .write(&Key::wasm_code_len(&code_hash), code_len)
+            .unwrap();
+        code_hash
+    }
+
+    /// Execute the stored tx code with the given hash and return the result
+    /// with the consumed gas
+    fn execute_tx_call(
+        state: &mut TestState,
+        code_hash: Hash,
+    ) -> (Result<BTreeSet<Address>>, Gas) {
+        let gas_meter =
+            RefCell::new(TxGasMeter::new_from_sub_limit(TX_GAS_LIMIT.into()));
+        let tx_index = TxIndex::default();
+        let (mut vp_cache, _) =
+            wasm::compilation_cache::common::testing::cache();
+        let (mut tx_cache, _) =
+            wasm::compilation_cache::common::testing::cache();
+        let mut outer_tx = Tx::from_type(TxType::Raw);
+        outer_tx.set_code(Code::from_hash(code_hash, None));
+        outer_tx.set_data(Data::new(vec![]));
+
+        let result = tx(
+            state,
+            &gas_meter,
+            &tx_index,
+            &outer_tx,
+            &mut vp_cache,
+            &mut tx_cache,
+        );
+        let consumed_gas = gas_meter.borrow().get_tx_consumed_gas();
+        (result, consumed_gas)
+    }
+
     fn loop_in_tx_wasm(loops: u32) -> Result<BTreeSet<Address>> {
         // A transaction with a recursive loop.
         // The boilerplate code is generated from tx_template.wasm using
@@ -0,0 +1,93 @@
+//! Dynamic base fee per unit of gas.
+//!
+//! The base fee is the minimum price of a unit of gas in the native token.
+//! After every block, it increases if the gas used by the block's wrapper txs
+//! exceeded the `target_block_gas` parameter and decreases otherwise, in
+//! proportion to the deviation from the target and by at most the
+//! `base_fee_max_change_rate` parameter, which must be between 0 and 1. It
+//! never goes below the native token's `minimum_gas_price`.
+
+use std::cmp;
+
+use namada_core::dec::Dec;
+use namada_core::token;
+use namada_storage::{StorageRead, StorageWrite};
+
+use crate::{read_gas_cost, storage};
+
+/// Read the current base fee per unit of gas, in the native token. Defaults
+/// to the native token's minimum gas price before the first update.
+pub fn read_base_fee<S>(storage: &S) -> namada_storage::Result<token::Amount>
+where
+    S: StorageRead,
+{
+    match storage.read(&storage::get_base_fee_key())? {
+        Some(base_fee) => Ok(base_fee),
+        None => min_base_fee(storage),
+    }
+}
+
+/// Update the base fee from the gas used by the wrapper txs of the last block,
+/// in whole gas units. Returns the new base fee.
+pub fn update_base_fee<S>(
+    storage: &mut S,
+    block_gas: u64,
+) -> namada_storage::Result<token::Amount>
+where
+    S: StorageRead + StorageWrite,
+{
+    let target_block_gas: u64 = storage
+        .read(&storage::get_target_block_gas_key())?
+        .unwrap_or_default();
+    let base_fee = read_base_fee(storage)?;
+    if target_block_gas == 0 {
+        return Ok(base_fee);
+    }
+    // The max change rate is validated, but clamp it to keep the base fee
+    // positive regardless
+    let max_change_rate: Dec = storage
+        .read(&storage::get_base_fee_max_change_rate_key())?
+        .unwrap_or_default();
+    let max_change_rate =
+        cmp::min(cmp::max(max_change_rate, Dec::zero()), Dec::one());
+
+    // The change is proportional to the deviation from the target, up to the
+    // max change rate
+    let deviation = block_gas.abs_diff(target_block_gas);
+    let change_rate = Dec::from(deviation)
+        .checked_div(Dec::from(target_block_gas))
+        .map(|ratio| cmp::min(ratio, Dec::one()))
+        .and_then(|ratio| ratio.checked_mul(max_change_rate))
+        .ok_or_else(|| {
+            namada_storage::Error::new_const("Base fee change rate overflow")
+        })?;
+    let new_base_fee = if block_gas > target_block_gas {
+        let change = base_fee.mul_ceil(change_rate)?;
+        base_fee.checked_add(change).ok_or_else(|| {
+            namada_storage::Error::new_const("Base fee overflow")
+        })?
+    } else {
+        let change = base_fee.mul_floor(change_rate)?;
+        base_fee.checked_sub(change).ok_or_else(|| {
+            namada_storage::Error::new_const("Base fee underflow")
+        })?
+    };
+    let new_base_fee = cmp::max(new_base_fee, min_base_fee(storage)?);
+    storage.write(&storage::get_base_fee_key(), new_base_fee)?;
+    Ok(new_base_fee)
+}
+
+/// Check that a base fee max change rate is between 0 and 1, such that the
+/// base fee can at most double or drop to zero in a block.
+pub fn is_valid_base_fee_max_change_rate(max_change_rate: &Dec) -> bool {
+    !max_change_rate.is_negative() && *max_change_rate <= Dec::one()
+}
+
+/// The base fee cannot go below the minimum gas price of the native token
+fn min_base_fee<S>(storage: &S) -> namada_storage::Result<token::Amount>
+where
+    S: StorageRead,
+{
+    let native_token = storage.get_native_token()?;
+    Ok(read_gas_cost(storage, &native_token)?.unwrap_or_default())
+}
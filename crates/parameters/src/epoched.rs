@@ -0,0 +1,192 @@
+//! Protocol parameters with changes scheduled to take effect at a future
+//! epoch.
+//!
+//! A governance proposal can schedule a new value of a parameter for a given
+//! epoch instead of changing it right away. The value is kept under
+//! `#Parameters/pending/<parameter>/<epoch>` until the start of that epoch,
+//! when the protocol moves it to the parameter's storage key.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::marker::PhantomData;
+
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::storage::{DbKeySeg, Epoch, Key};
+use namada_storage::{StorageRead, StorageWrite};
+
+use crate::{storage, ADDRESS};
+
+/// Handle of a protocol parameter with its changes scheduled for future
+/// epochs
+#[derive(Debug, Clone)]
+pub struct EpochedParameter<T> {
+    /// The storage key of the parameter's current value
+    key: Key,
+    /// The sub-key of the parameter
+    name: String,
+    phantom: PhantomData<T>,
+}
+
+impl<T> EpochedParameter<T> {
+    /// Open the handle of the parameter stored at the given key. Returns
+    /// `None` if the key is not a protocol parameter key.
+    pub fn open(key: Key) -> Option<Self> {
+        if !storage::is_protocol_parameter_key(&key) {
+            return None;
+        }
+        let name = match key.last()? {
+            DbKeySeg::StringSeg(name) => name.clone(),
+            _ => return None,
+        };
+        Some(Self {
+            key,
+            name,
+            phantom: PhantomData,
+        })
+    }
+
+    /// The storage key of the parameter's current value
+    pub fn key(&self) -> &Key {
+        &self.key
+    }
+
+    /// The sub-key of the parameter
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The storage key prefix of the parameter's scheduled changes
+    pub fn pending_prefix(&self) -> Key {
+        storage::pending_parameter_prefix(&self.name)
+    }
+}
+
+impl<T> EpochedParameter<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    /// Read the current value of the parameter
+    pub fn get<S>(&self, storage: &S) -> namada_storage::Result<Option<T>>
+    where
+        S: StorageRead,
+    {
+        storage.read(&self.key)
+    }
+
+    /// Read the value of the parameter that will be in effect at the given
+    /// epoch, given the changes scheduled so far
+    pub fn get_at<S>(
+        &self,
+        storage: &S,
+        epoch: Epoch,
+    ) -> namada_storage::Result<Option<T>>
+    where
+        S: StorageRead,
+    {
+        let mut pending = self.pending(storage)?;
+        match pending.range(..=epoch).next_back() {
+            Some((&epoch, _)) => Ok(pending.remove(&epoch)),
+            None => self.get(storage),
+        }
+    }
+
+    /// Schedule a new value of the parameter to take effect at the start of
+    /// the given epoch, which must be in the future. Replaces any change
+    /// previously scheduled for the same epoch.
+    pub fn schedule<S>(
+        &self,
+        storage: &mut S,
+        epoch: Epoch,
+        value: T,
+    ) -> namada_storage::Result<()>
+    where
+        S: StorageRead + StorageWrite,
+    {
+        let current_epoch = storage.get_block_epoch()?;
+        if epoch <= current_epoch {
+            return Err(namada_storage::Error::new_alloc(format!(
+                "Parameter {} change must be scheduled after the current \
+                 epoch {current_epoch}, got {epoch}",
+                self.name
+            )));
+        }
+        storage.write(
+            &storage::get_pending_parameter_key(&self.name, epoch),
+            value,
+        )
+    }
+
+    /// Cancel the change of the parameter scheduled for the given epoch, if
+    /// any
+    pub fn cancel<S>(
+        &self,
+        storage: &mut S,
+        epoch: Epoch,
+    ) -> namada_storage::Result<()>
+    where
+        S: StorageRead + StorageWrite,
+    {
+        storage.delete(&storage::get_pending_parameter_key(&self.name, epoch))
+    }
+
+    /// Read the changes of the parameter scheduled for future epochs
+    pub fn pending<S>(
+        &self,
+        storage: &S,
+    ) -> namada_storage::Result<BTreeMap<Epoch, T>>
+    where
+        S: StorageRead,
+    {
+        let mut pending = BTreeMap::new();
+        for res in namada_storage::iter_prefix(storage, &self.pending_prefix())?
+        {
+            let (key, value) = res?;
+            match storage::is_pending_parameter_key(&key) {
+                Some((name, epoch)) if name == self.name => {
+                    pending.insert(epoch, value);
+                }
+                _ => {}
+            }
+        }
+        Ok(pending)
+    }
+}
+
+/// Apply the parameter changes scheduled for the given epoch, or any earlier
+/// one, by moving their values to the parameters' storage keys. Called by the
+/// protocol at the start of every epoch. Returns the names of the parameters
+/// that were changed.
+pub fn apply_pending_parameters<S>(
+    storage: &mut S,
+    current_epoch: Epoch,
+) -> namada_storage::Result<BTreeSet<String>>
+where
+    S: StorageRead + StorageWrite,
+{
+    // The epochs are not ordered in the storage keys, so the changes are
+    // sorted before being applied
+    let mut due: BTreeMap<(Epoch, String), (Key, Vec<u8>)> = BTreeMap::new();
+    for res in namada_storage::iter_prefix_bytes(
+        storage,
+        &storage::pending_parameters_prefix(),
+    )? {
+        let (key, value) = res?;
+        if let Some((name, epoch)) = storage::is_pending_parameter_key(&key) {
+            if epoch <= current_epoch {
+                due.insert((epoch, name.to_owned()), (key, value));
+            }
+        }
+    }
+    let mut applied = BTreeSet::new();
+    for ((_epoch, name), (pending_key, value)) in due {
+        let key = Key {
+            segments: vec![
+                DbKeySeg::AddressSeg(ADDRESS),
+                DbKeySeg::StringSeg(name.clone()),
+            ],
+        };
+        storage.write_bytes(&key, value)?;
+        storage.delete(&pending_key)?;
+        applied.insert(name);
+    }
+    Ok(applied)
+}
@@ -0,0 +1,48 @@
+//! Exemptions of tx codes from the minimum fee.
+//!
+//! Governance can exempt the txs running a given tx code, e.g. `tx_reveal_pk`,
+//! from the minimum gas price by adding the hash of the code to the set of fee
+//! exemptions. An exempt wrapper tx may pay any gas price, including zero, but
+//! its gas is still metered against its gas limit and the block's.
+
+use namada_core::hash::Hash;
+use namada_core::storage::{DbKeySeg, Key};
+use namada_storage::collections::{LazyCollection, LazySet};
+use namada_storage::{Result, StorageRead};
+
+use crate::ADDRESS;
+
+/// Sub-key of the set of tx code hashes exempt from the minimum fee
+const FEE_EXEMPTIONS_KEY: &str = "fee_exemptions";
+
+/// Obtain the storage key prefix of the set of tx code hashes exempt from the
+/// minimum fee
+pub fn fee_exemptions_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(FEE_EXEMPTIONS_KEY.to_string()),
+        ],
+    }
+}
+
+/// LazySet handler for the set of tx code hashes exempt from the minimum fee
+pub fn fee_exemptions_handle() -> LazySet<Hash> {
+    LazySet::open(fee_exemptions_key_prefix())
+}
+
+/// Check if the txs running the given tx code are exempt from the minimum fee
+pub fn is_fee_exempt<S>(storage: &S, code_hash: &Hash) -> Result<bool>
+where
+    S: StorageRead,
+{
+    fee_exemptions_handle().contains(storage, code_hash)
+}
+
+/// Read the hashes of the tx codes exempt from the minimum fee
+pub fn read_fee_exemptions<S>(storage: &S) -> Result<Vec<Hash>>
+where
+    S: StorageRead,
+{
+    fee_exemptions_handle().iter(storage)?.collect()
+}
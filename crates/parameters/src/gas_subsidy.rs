@@ -0,0 +1,90 @@
+//! Gas subsidies for protocol-critical txs.
+//!
+//! Governance can subsidize the txs running a given tx code, e.g.
+//! `tx_reveal_pk` or `tx_claim_rewards`, by registering a [`GasSubsidy`] under
+//! the hash of the code. The minimum gas price required from a subsidized
+//! wrapper tx is discounted by the subsidy, down to a floor of a tenth of the
+//! price, so that the subsidized txs still cost something to spam. Only the
+//! txs up to the subsidy's max size are subsidized, and their gas meter is
+//! initialized with the subsidy's max gas if it's below their gas limit, so
+//! that they can't fill the blocks at a discount.
+
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::dec::Dec;
+use namada_core::hash::Hash;
+use namada_core::storage::{DbKeySeg, Key};
+use namada_core::token;
+use namada_storage::collections::{LazyCollection, LazyMap};
+use namada_storage::{Result, StorageRead};
+
+use crate::ADDRESS;
+
+/// Sub-key of the gas subsidies, keyed by tx code hash
+const GAS_SUBSIDIES_KEY: &str = "gas_subsidies";
+
+/// The subsidy of the gas of the txs running a given tx code
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct GasSubsidy {
+    /// The discount of the minimum gas price, between 0 and 1
+    pub discount: Dec,
+    /// The max size in bytes of a subsidized wrapper tx
+    pub max_tx_bytes: u64,
+    /// The max gas that a subsidized tx can use, whatever its gas limit
+    pub max_gas: u64,
+}
+
+/// The max discount of a gas subsidy, keeping a non-zero floor of the
+/// minimum gas price of the subsidized txs
+pub fn max_gas_subsidy_discount() -> Dec {
+    Dec::new(9, 1).expect("Cannot fail")
+}
+
+/// Obtain the storage key prefix of the gas subsidies
+pub fn gas_subsidies_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(GAS_SUBSIDIES_KEY.to_string()),
+        ],
+    }
+}
+
+/// LazyMap handler for the gas subsidies, keyed by tx code hash
+pub fn gas_subsidies_handle() -> LazyMap<Hash, GasSubsidy> {
+    LazyMap::open(gas_subsidies_key_prefix())
+}
+
+/// Read the gas subsidy of a wrapper tx of the given size running the given
+/// tx code, with its discount capped by [`max_gas_subsidy_discount`]. Returns
+/// `None` if the code is not subsidized or if the tx is above the subsidy's
+/// max size.
+pub fn read_gas_subsidy<S>(
+    storage: &S,
+    code_hash: &Hash,
+    tx_bytes: u64,
+) -> Result<Option<GasSubsidy>>
+where
+    S: StorageRead,
+{
+    Ok(gas_subsidies_handle()
+        .get(storage, code_hash)?
+        .filter(|subsidy| tx_bytes <= subsidy.max_tx_bytes)
+        .map(|subsidy| GasSubsidy {
+            discount: std::cmp::min(
+                subsidy.discount,
+                max_gas_subsidy_discount(),
+            ),
+            ..subsidy
+        }))
+}
+
+/// Apply the discount of a gas subsidy, clamped between 0 and 1, to a minimum
+/// gas price, rounding the discounted price up.
+pub fn subsidized_gas_price(
+    minimum_gas_price: token::Amount,
+    discount: Dec,
+) -> Result<token::Amount> {
+    let discount = discount.clamp(Dec::zero(), Dec::one());
+    let rate = Dec::one().checked_sub(discount).unwrap_or_default();
+    Ok(minimum_gas_price.mul_ceil(rate)?)
+}
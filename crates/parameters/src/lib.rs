@@ -1,18 +1,41 @@
 //! Protocol parameters
+mod base_fee;
+mod epoched;
+mod fee_exemption;
+mod gas_subsidy;
+mod protocol_version;
 pub mod storage;
 mod wasm_allowlist;
 use std::collections::BTreeMap;
 
+pub use base_fee::{
+    is_valid_base_fee_max_change_rate, read_base_fee, update_base_fee,
+};
+pub use epoched::{apply_pending_parameters, EpochedParameter};
+pub use fee_exemption::{
+    fee_exemptions_handle, fee_exemptions_key_prefix, is_fee_exempt,
+    read_fee_exemptions,
+};
+pub use gas_subsidy::{
+    gas_subsidies_handle, gas_subsidies_key_prefix, max_gas_subsidy_discount,
+    read_gas_subsidy, subsidized_gas_price, GasSubsidy,
+};
 use namada_core::address::{Address, InternalAddress};
 use namada_core::chain::ProposalBytes;
+use namada_core::dec::Dec;
 pub use namada_core::parameters::*;
 use namada_core::storage::Key;
 use namada_core::time::DurationSecs;
 use namada_core::token;
 use namada_storage::{ResultExt, StorageRead, StorageWrite};
+pub use protocol_version::{
+    protocol_version_handle, read_current_protocol_version,
+    read_protocol_version, read_protocol_version_at, write_protocol_version,
+    INITIAL_PROTOCOL_VERSION,
+};
 pub use storage::get_max_block_gas;
 use thiserror::Error;
-pub use wasm_allowlist::{is_tx_allowed, is_vp_allowed};
+pub use wasm_allowlist::{is_epoch_hook_allowed, is_tx_allowed, is_vp_allowed};
 
 /// The internal address for storage keys representing parameters than
 /// can be changed via governance.
@@ -38,6 +61,20 @@ pub enum WriteError {
     SerializeError(String),
 }
 
+/// A tx whose size exceeds the max tx size parameter, which is distinct from,
+/// and at most, the max size of the txs of a block proposal
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error(
+    "The tx is {tx_size} bytes, which exceeds the max tx size of \
+     {max_tx_bytes} bytes"
+)]
+pub struct TxTooLarge {
+    /// The size of the tx, in bytes
+    pub tx_size: usize,
+    /// The max size of a tx, in bytes
+    pub max_tx_bytes: u32,
+}
+
 /// Initialize parameters in storage in the genesis block.
 pub fn init_storage<S>(
     parameters: &Parameters,
@@ -61,6 +98,12 @@ where
         fee_unshielding_gas_limit,
         fee_unshielding_descriptions_limit,
         is_native_token_transferable,
+        max_epoch_hooks_gas,
+        epoch_hook_allowlist,
+        target_block_gas,
+        base_fee_max_change_rate,
+        masp_convert_anchor_grace_blocks,
+        masp_max_note_age,
     } = parameters;
 
     // write max tx bytes parameter
@@ -141,6 +184,35 @@ where
     storage
         .write(&native_token_transferable_key, is_native_token_transferable)?;
 
+    let max_epoch_hooks_gas_key = storage::get_max_epoch_hooks_gas_key();
+    storage.write(&max_epoch_hooks_gas_key, max_epoch_hooks_gas)?;
+
+    // write epoch hook allowlist parameter
+    let epoch_hook_allowlist_key =
+        storage::get_epoch_hook_allowlist_storage_key();
+    let epoch_hook_allowlist = epoch_hook_allowlist
+        .iter()
+        .map(|id| id.to_lowercase())
+        .collect::<Vec<String>>();
+    storage.write(&epoch_hook_allowlist_key, epoch_hook_allowlist)?;
+
+    let target_block_gas_key = storage::get_target_block_gas_key();
+    storage.write(&target_block_gas_key, target_block_gas)?;
+
+    let base_fee_max_change_rate_key =
+        storage::get_base_fee_max_change_rate_key();
+    storage.write(&base_fee_max_change_rate_key, base_fee_max_change_rate)?;
+
+    let masp_convert_anchor_grace_blocks_key =
+        storage::get_masp_convert_anchor_grace_blocks_key();
+    storage.write(
+        &masp_convert_anchor_grace_blocks_key,
+        masp_convert_anchor_grace_blocks,
+    )?;
+
+    let masp_max_note_age_key = storage::get_masp_max_note_age_key();
+    storage.write(&masp_max_note_age_key, masp_max_note_age)?;
+
     Ok(())
 }
 
@@ -206,6 +278,24 @@ where
     )
 }
 
+/// Update the epoch hook allowlist parameter in storage.
+pub fn update_epoch_hook_allowlist_parameter<S>(
+    storage: &mut S,
+    value: Vec<String>,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = storage::get_epoch_hook_allowlist_storage_key();
+    storage.write(
+        &key,
+        value
+            .iter()
+            .map(|id| id.to_lowercase())
+            .collect::<Vec<String>>(),
+    )
+}
+
 /// Update the epoch parameter in storage. Returns the parameters and gas
 /// cost.
 pub fn update_epoch_parameter<S>(
@@ -396,6 +486,47 @@ where
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
 
+    // read max epoch hooks gas
+    let max_epoch_hooks_gas = storage::get_max_epoch_hooks_gas(storage)?;
+
+    // read epoch hook allowlist
+    let epoch_hook_allowlist_key =
+        storage::get_epoch_hook_allowlist_storage_key();
+    let value = storage.read(&epoch_hook_allowlist_key)?;
+    let epoch_hook_allowlist: Vec<String> = value
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()?;
+
+    // read target block gas
+    let target_block_gas_key = storage::get_target_block_gas_key();
+    let value = storage.read(&target_block_gas_key)?;
+    let target_block_gas: u64 = value
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()?;
+
+    // read base fee max change rate
+    let base_fee_max_change_rate_key =
+        storage::get_base_fee_max_change_rate_key();
+    let value = storage.read(&base_fee_max_change_rate_key)?;
+    let base_fee_max_change_rate: Dec = value
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()?;
+
+    // read MASP convert anchor grace blocks
+    let masp_convert_anchor_grace_blocks_key =
+        storage::get_masp_convert_anchor_grace_blocks_key();
+    let value = storage.read(&masp_convert_anchor_grace_blocks_key)?;
+    let masp_convert_anchor_grace_blocks: u64 = value
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()?;
+
+    // read MASP max note age
+    let masp_max_note_age_key = storage::get_masp_max_note_age_key();
+    let value = storage.read(&masp_max_note_age_key)?;
+    let masp_max_note_age: u64 = value
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()?;
+
     Ok(Parameters {
         max_tx_bytes,
         epoch_duration,
@@ -411,21 +542,51 @@ where
         fee_unshielding_gas_limit,
         fee_unshielding_descriptions_limit,
         is_native_token_transferable,
+        max_epoch_hooks_gas,
+        epoch_hook_allowlist,
+        target_block_gas,
+        base_fee_max_change_rate,
+        masp_convert_anchor_grace_blocks,
+        masp_max_note_age,
     })
 }
 
-/// Validate the size of a tx.
+/// Read the max size of a tx, in bytes.
+pub fn read_max_tx_bytes<S>(storage: &S) -> namada_storage::Result<u32>
+where
+    S: StorageRead,
+{
+    storage
+        .read(&storage::get_max_tx_bytes_key())?
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()
+}
+
+/// Validate the size of a tx against the max tx size parameter.
 pub fn validate_tx_bytes<S>(
     storage: &S,
     tx_size: usize,
-) -> namada_storage::Result<bool>
+) -> namada_storage::Result<Result<(), TxTooLarge>>
 where
     S: StorageRead,
 {
-    let max_tx_bytes: u32 = storage
-        .read(&storage::get_max_tx_bytes_key())?
-        .expect("The max tx bytes param should be present in storage");
-    Ok(tx_size <= max_tx_bytes as usize)
+    let max_tx_bytes = read_max_tx_bytes(storage)?;
+    Ok(check_tx_bytes(tx_size, max_tx_bytes))
+}
+
+/// Check the size of a tx against the given max tx size.
+pub fn check_tx_bytes(
+    tx_size: usize,
+    max_tx_bytes: u32,
+) -> Result<(), TxTooLarge> {
+    if tx_size <= max_tx_bytes as usize {
+        Ok(())
+    } else {
+        Err(TxTooLarge {
+            tx_size,
+            max_tx_bytes,
+        })
+    }
 }
 
 /// Storage key for the Ethereum address of wNam.
@@ -457,6 +618,12 @@ where
         fee_unshielding_descriptions_limit: 0,
         minimum_gas_price: Default::default(),
         is_native_token_transferable: true,
+        max_epoch_hooks_gas: 0,
+        epoch_hook_allowlist: vec![],
+        target_block_gas: 0,
+        base_fee_max_change_rate: Dec::zero(),
+        masp_convert_anchor_grace_blocks: 0,
+        masp_max_note_age: 0,
     };
     init_storage(&params, storage)
 }
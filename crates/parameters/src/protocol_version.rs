@@ -0,0 +1,71 @@
+//! Protocol version of the chain.
+//!
+//! The protocol version is recorded in storage at genesis, from the version
+//! implemented by the node's binary. It can only be bumped by a governance
+//! proposal, usually by scheduling the new version at the epoch of a network
+//! upgrade. A node must refuse to process the chain's blocks with a binary
+//! that implements another version, so that validators running mismatched
+//! binaries halt instead of splitting consensus. The chains that predate the
+//! recording of the protocol version are at [`INITIAL_PROTOCOL_VERSION`],
+//! which is recorded by their migration.
+
+use namada_core::storage::Epoch;
+use namada_storage::{StorageRead, StorageWrite};
+
+use crate::{storage, EpochedParameter};
+
+/// The protocol version of the chains that didn't record it
+pub const INITIAL_PROTOCOL_VERSION: u64 = 1;
+
+/// Read the current protocol version of the chain. Returns `None` for a chain
+/// that never recorded it.
+pub fn read_protocol_version<S>(
+    storage: &S,
+) -> namada_storage::Result<Option<u64>>
+where
+    S: StorageRead,
+{
+    storage.read(&storage::get_protocol_version_key())
+}
+
+/// Read the current protocol version of the chain, which is the initial version
+/// for a chain that never recorded it
+pub fn read_current_protocol_version<S>(
+    storage: &S,
+) -> namada_storage::Result<u64>
+where
+    S: StorageRead,
+{
+    Ok(read_protocol_version(storage)?.unwrap_or(INITIAL_PROTOCOL_VERSION))
+}
+
+/// Write the protocol version of the chain. Must only be called by the
+/// protocol at genesis or by the migration of a chain that didn't record it.
+pub fn write_protocol_version<S>(
+    storage: &mut S,
+    version: u64,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    storage.write(&storage::get_protocol_version_key(), version)
+}
+
+/// Handle of the protocol version parameter, with the upgrades scheduled for
+/// future epochs
+pub fn protocol_version_handle() -> EpochedParameter<u64> {
+    EpochedParameter::open(storage::get_protocol_version_key())
+        .expect("The protocol version must be a protocol parameter")
+}
+
+/// Read the protocol version that will be in effect at the given epoch, given
+/// the upgrades scheduled so far
+pub fn read_protocol_version_at<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> namada_storage::Result<Option<u64>>
+where
+    S: StorageRead,
+{
+    protocol_version_handle().get_at(storage, epoch)
+}
@@ -1,8 +1,9 @@
 //! Parameters storage
 
 use namada_core::address::Address;
-use namada_core::storage::{DbKeySeg, Key};
+use namada_core::storage::{DbKeySeg, Epoch, Key, KeySeg};
 use namada_macros::StorageKeys;
+use namada_storage::collections::{LazyCollection, LazySet};
 use namada_storage::StorageRead;
 
 use super::ADDRESS;
@@ -41,8 +42,31 @@ struct Keys {
     fee_unshielding_descriptions_limit: &'static str,
     max_signatures_per_transaction: &'static str,
     native_token_transferable: &'static str,
+    max_epoch_hooks_gas: &'static str,
+    epoch_hook_allowlist: &'static str,
+    target_block_gas: &'static str,
+    base_fee_max_change_rate: &'static str,
+    masp_convert_anchor_grace_blocks: &'static str,
+    masp_max_note_age: &'static str,
+    protocol_version: &'static str,
 }
 
+/// Sub-key of the set of accounts that registered an epoch hook
+const EPOCH_HOOK_OWNERS_KEY: &str = "epoch_hook_owners";
+
+/// Sub-key of the cursor of the epoch hooks applied so far, which is updated
+/// by the protocol at the beginning of an epoch
+const EPOCH_HOOKS_CURSOR_KEY: &str = "epoch_hooks_cursor";
+
+/// Sub-key of the base fee, which is updated by the protocol in every block
+const BASE_FEE_KEY: &str = "base_fee";
+
+/// Sub-key of the parameter changes scheduled to take effect at a future epoch
+const PENDING_KEY: &str = "pending";
+
+/// Sub-key of the memo hooks of incoming IBC transfers, by name
+const MEMO_HOOK_KEY: &str = "memo_hook";
+
 /// Returns if the key is a parameter key.
 pub fn is_parameter_key(key: &Key) -> bool {
     matches!(&key.segments[0], DbKeySeg::AddressSeg(addr) if addr == &ADDRESS)
@@ -58,7 +82,7 @@ pub fn is_protocol_parameter_key(key: &Key) -> bool {
         }
         _ => return false,
     };
-    Keys::ALL.binary_search(&segment).is_ok()
+    is_protocol_parameter_name(segment)
 }
 
 /// Returns if the key is an epoch storage key.
@@ -101,6 +125,30 @@ pub fn is_max_tx_bytes_key(key: &Key) -> bool {
     is_max_tx_bytes_key_at_addr(key, &ADDRESS)
 }
 
+/// The sub-keys of the protocol parameters that an expedited governance
+/// proposal may change, to respond to an emergency
+pub const EXPEDITED_PARAMETERS: [&str; 6] = [
+    Keys::VALUES.tx_allowlist,
+    Keys::VALUES.vp_allowlist,
+    Keys::VALUES.max_tx_bytes,
+    Keys::VALUES.max_block_gas,
+    Keys::VALUES.minimum_gas_price,
+    Keys::VALUES.native_token_transferable,
+];
+
+/// Returns if the key is a protocol parameter that an expedited governance
+/// proposal may change.
+pub fn is_expedited_parameter_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(name)]
+            if addr == &ADDRESS =>
+        {
+            EXPEDITED_PARAMETERS.contains(&name.as_str())
+        }
+        _ => false,
+    }
+}
+
 /// Storage key used for epoch parameter.
 pub fn get_epoch_duration_storage_key() -> Key {
     get_epoch_duration_key_at_addr(ADDRESS)
@@ -194,3 +242,186 @@ pub fn is_native_token_transferable(
         ),
     )
 }
+
+/// Storage key used for the max gas of all the epoch hooks in an epoch
+pub fn get_max_epoch_hooks_gas_key() -> Key {
+    get_max_epoch_hooks_gas_key_at_addr(ADDRESS)
+}
+
+/// Storage key used for the epoch hook allowlist parameter.
+pub fn get_epoch_hook_allowlist_storage_key() -> Key {
+    get_epoch_hook_allowlist_key_at_addr(ADDRESS)
+}
+
+/// Helper function to retrieve the `max_epoch_hooks_gas` protocol parameter
+/// from storage
+pub fn get_max_epoch_hooks_gas(
+    storage: &impl StorageRead,
+) -> std::result::Result<u64, namada_storage::Error> {
+    storage.read(&get_max_epoch_hooks_gas_key())?.ok_or(
+        namada_storage::Error::SimpleMessage(
+            "Missing max_epoch_hooks_gas parameter from storage",
+        ),
+    )
+}
+
+/// Obtain the storage key prefix of the set of accounts that registered an
+/// epoch hook
+pub fn epoch_hook_owners_key_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(EPOCH_HOOK_OWNERS_KEY.to_string()),
+        ],
+    }
+}
+
+/// LazySet handler for the set of accounts that registered an epoch hook. The
+/// set is kept in sync with the hooks stored in the accounts' subspaces.
+pub fn epoch_hook_owners_handle() -> LazySet<Address> {
+    LazySet::open(epoch_hook_owners_key_prefix())
+}
+
+/// Check if the given storage key is an element of the set of accounts that
+/// registered an epoch hook. If it is, returns the account address.
+pub fn is_epoch_hook_owner_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(params), DbKeySeg::StringSeg(prefix), DbKeySeg::AddressSeg(owner)]
+            if params == &ADDRESS
+                && prefix.as_str() == EPOCH_HOOK_OWNERS_KEY =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
+/// Storage key of the set element of the owner of the last epoch hook applied,
+/// if the hooks of the following owners are yet to be applied
+pub fn epoch_hooks_cursor_key() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(EPOCH_HOOKS_CURSOR_KEY.to_string()),
+        ],
+    }
+}
+
+/// Is the given key the cursor of the epoch hooks applied so far
+pub fn is_epoch_hooks_cursor_key(key: &Key) -> bool {
+    matches!(
+        &key.segments[..],
+        [DbKeySeg::AddressSeg(params), DbKeySeg::StringSeg(cursor)]
+            if params == &ADDRESS && cursor.as_str() == EPOCH_HOOKS_CURSOR_KEY
+    )
+}
+
+/// Storage key used for the target block gas parameter.
+pub fn get_target_block_gas_key() -> Key {
+    get_target_block_gas_key_at_addr(ADDRESS)
+}
+
+/// Storage key used for the base fee max change rate parameter.
+pub fn get_base_fee_max_change_rate_key() -> Key {
+    get_base_fee_max_change_rate_key_at_addr(ADDRESS)
+}
+
+/// Storage key used for the MASP convert anchor grace blocks parameter.
+pub fn get_masp_convert_anchor_grace_blocks_key() -> Key {
+    get_masp_convert_anchor_grace_blocks_key_at_addr(ADDRESS)
+}
+
+/// Storage key used for the MASP max note age parameter.
+pub fn get_masp_max_note_age_key() -> Key {
+    get_masp_max_note_age_key_at_addr(ADDRESS)
+}
+
+/// Storage key used for the protocol version of the chain.
+pub fn get_protocol_version_key() -> Key {
+    get_protocol_version_key_at_addr(ADDRESS)
+}
+
+/// Returns if the key is the base fee max change rate key.
+pub fn is_base_fee_max_change_rate_key(key: &Key) -> bool {
+    is_base_fee_max_change_rate_key_at_addr(key, &ADDRESS)
+}
+
+/// Returns if the given sub-key is the base fee max change rate parameter.
+pub fn is_base_fee_max_change_rate_name(name: &str) -> bool {
+    name == Keys::VALUES.base_fee_max_change_rate
+}
+
+/// Returns if the key is the protocol version key.
+pub fn is_protocol_version_key(key: &Key) -> bool {
+    is_protocol_version_key_at_addr(key, &ADDRESS)
+}
+
+/// Storage key of the current base fee per unit of gas, in the native token
+pub fn get_base_fee_key() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(BASE_FEE_KEY.to_string()),
+        ],
+    }
+}
+
+/// Storage key of the memo hook of incoming IBC transfers registered under the
+/// given name. Like the parameters, it can only be changed by an accepted
+/// governance proposal.
+pub fn get_memo_hook_key(name: &str) -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(MEMO_HOOK_KEY.to_string()),
+            DbKeySeg::StringSeg(name.to_string()),
+        ],
+    }
+}
+
+/// Check if the given name is the sub-key of a protocol parameter
+pub fn is_protocol_parameter_name(name: &str) -> bool {
+    Keys::ALL.binary_search(&name).is_ok()
+}
+
+/// Obtain the storage key prefix of the parameter changes scheduled for a
+/// future epoch
+pub fn pending_parameters_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(ADDRESS.to_owned()),
+            DbKeySeg::StringSeg(PENDING_KEY.to_string()),
+        ],
+    }
+}
+
+/// Obtain the storage key prefix of the changes of the given parameter
+/// scheduled for a future epoch
+pub fn pending_parameter_prefix(name: &str) -> Key {
+    pending_parameters_prefix()
+        .push(&name.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key of the value of the given parameter scheduled to take effect
+/// at the given epoch
+pub fn get_pending_parameter_key(name: &str, epoch: Epoch) -> Key {
+    pending_parameter_prefix(name)
+        .push(&epoch)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if the given storage key is a parameter change scheduled for a
+/// future epoch. If it is, returns the sub-key of the parameter and the epoch
+/// at which the change takes effect.
+pub fn is_pending_parameter_key(key: &Key) -> Option<(&str, Epoch)> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(params), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(name), DbKeySeg::StringSeg(epoch)]
+            if params == &ADDRESS && prefix.as_str() == PENDING_KEY =>
+        {
+            let epoch = Epoch::parse(epoch.clone()).ok()?;
+            Some((name.as_str(), epoch))
+        }
+        _ => None,
+    }
+}
@@ -3,7 +3,8 @@ use namada_core::storage;
 use namada_storage::{Result, StorageRead};
 
 use crate::storage::{
-    get_tx_allowlist_storage_key, get_vp_allowlist_storage_key,
+    get_epoch_hook_allowlist_storage_key, get_tx_allowlist_storage_key,
+    get_vp_allowlist_storage_key,
 };
 
 /// Check if the given tx code `Hash` is in the allowlist. When the allowlist is
@@ -26,6 +27,18 @@ where
     is_allowed(storage, key, vp_hash)
 }
 
+/// Check if the given tx code `Hash` is in the epoch hook allowlist. Unlike
+/// the other allowlists, an empty epoch hook allowlist doesn't allow any hook.
+pub fn is_epoch_hook_allowed<S>(storage: &S, tx_hash: &Hash) -> Result<bool>
+where
+    S: StorageRead,
+{
+    let allowlist: Vec<String> = storage
+        .read(&get_epoch_hook_allowlist_storage_key())?
+        .unwrap_or_default();
+    Ok(allowlist.contains(&tx_hash.to_string().to_lowercase()))
+}
+
 fn is_allowed<S>(
     storage: &S,
     allowlist_key: storage::Key,
@@ -0,0 +1,350 @@
+//! Automatic redelegations away from jailed validators.
+//!
+//! A delegator can opt in to have its bonds redelegated to a fallback
+//! validator of its choice once the validators it bonded to have been jailed
+//! for a given number of epochs, so that its tokens don't stop earning
+//! rewards indefinitely. The redelegations are applied at the beginning of
+//! every epoch. Bonds that cannot be redelegated yet, i.e. bonds to frozen
+//! validators, chained redelegations or bonds whose receipt tokens are not
+//! held by the delegator anymore, are redelegated at a later epoch. At most
+//! [`MAX_AUTO_REDELEGATED_DELEGATORS_PER_EPOCH`] delegators are processed per
+//! epoch, the following ones are processed in the next epochs. The bonds of
+//! the members of the delegation pools are managed by their pools and are
+//! never redelegated automatically.
+
+use namada_core::address::Address;
+use namada_core::arith::checked;
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::storage::{BlockHeight, Epoch};
+use namada_events::EmitEvents;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_storage::collections::lazy_map::LazyMap;
+use namada_storage::collections::LazyCollection;
+use namada_storage::{in_batch, StorageBatch, StorageRead, StorageWrite};
+use serde::{Deserialize, Serialize};
+
+use crate::bond_receipt::has_bond_receipts_to_unbond;
+use crate::delegation_pool::read_delegation_pool_membership;
+use crate::event::PosEvent;
+use crate::storage::{
+    bond_handle, delegation_targets_handle, read_pos_params,
+    read_validator_jail_history, validator_state_handle,
+};
+use crate::types::{BondId, ValidatorState};
+use crate::{
+    is_chained_redelegation, is_validator, is_validator_frozen,
+    redelegate_tokens, storage_key, token, validator_redelegation_capacity,
+    AutoRedelegationError, PosParams,
+};
+
+/// The maximum number of delegators whose bonds are automatically
+/// redelegated in an epoch
+pub const MAX_AUTO_REDELEGATED_DELEGATORS_PER_EPOCH: usize = 256;
+
+/// The automatic redelegation settings of a delegator.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct AutoRedelegation {
+    /// The validator that the bonds are redelegated to
+    pub fallback: Address,
+    /// The number of epochs that a validator must have been jailed for
+    /// before the bonds to it are redelegated
+    pub jailed_epochs: u64,
+}
+
+/// Get the storage handle to the automatic redelegation settings, keyed by
+/// the addresses of the delegators
+pub fn auto_redelegations_handle() -> LazyMap<Address, AutoRedelegation> {
+    LazyMap::open(storage_key::auto_redelegations_key())
+}
+
+/// Read the automatic redelegation settings of a delegator, if it opted in.
+pub fn read_auto_redelegation<S>(
+    storage: &S,
+    delegator: &Address,
+) -> namada_storage::Result<Option<AutoRedelegation>>
+where
+    S: StorageRead,
+{
+    auto_redelegations_handle().get(storage, delegator)
+}
+
+/// Set the automatic redelegation settings of a delegator, or opt it out with
+/// `None`.
+pub fn update_auto_redelegation<S>(
+    storage: &mut S,
+    delegator: &Address,
+    auto_redelegation: Option<AutoRedelegation>,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let Some(auto_redelegation) = auto_redelegation else {
+        auto_redelegations_handle().remove(storage, delegator)?;
+        return Ok(());
+    };
+    if is_validator(storage, delegator)? {
+        return Err(AutoRedelegationError::DelegatorIsValidator(
+            delegator.clone(),
+        )
+        .into());
+    }
+    if !is_validator(storage, &auto_redelegation.fallback)? {
+        return Err(AutoRedelegationError::NotAValidator(
+            auto_redelegation.fallback,
+        )
+        .into());
+    }
+    if auto_redelegation.jailed_epochs == 0 {
+        return Err(AutoRedelegationError::ZeroJailedEpochs.into());
+    }
+    auto_redelegations_handle().insert(
+        storage,
+        delegator.clone(),
+        auto_redelegation,
+    )?;
+    Ok(())
+}
+
+/// Redelegate the bonds of the delegators that opted in from the validators
+/// jailed for long enough to their fallback validators. Applied at the
+/// beginning of every epoch to at most
+/// [`MAX_AUTO_REDELEGATED_DELEGATORS_PER_EPOCH`] delegators, resuming after the
+/// last delegator processed in the previous epoch. A delegator whose
+/// redelegations fail is skipped, without keeping any of its writes.
+pub fn process_auto_redelegations<S>(
+    storage: &mut S,
+    events: &mut impl EmitEvents,
+    current_epoch: Epoch,
+    height: BlockHeight,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageBatch,
+{
+    let handle = auto_redelegations_handle();
+    let cursor_key = storage_key::auto_redelegation_cursor_key();
+    // The storage key of the last processed delegator, in the iteration order
+    let cursor: Option<String> = storage.read(&cursor_key)?;
+    let mut delegators = Vec::new();
+    for entry in handle.iter(storage)? {
+        let (delegator, auto_redelegation) = entry?;
+        let delegator_key = handle.get_data_key(&delegator).to_string();
+        if cursor
+            .as_ref()
+            .is_some_and(|cursor| delegator_key <= *cursor)
+        {
+            continue;
+        }
+        delegators.push((delegator, auto_redelegation, delegator_key));
+        if delegators.len() == MAX_AUTO_REDELEGATED_DELEGATORS_PER_EPOCH {
+            break;
+        }
+    }
+    match delegators.last() {
+        // Resume after the last delegator in the next epoch
+        Some((_, _, delegator_key))
+            if delegators.len()
+                == MAX_AUTO_REDELEGATED_DELEGATORS_PER_EPOCH =>
+        {
+            storage.write(&cursor_key, delegator_key.clone())?;
+        }
+        // All the delegators have been processed, start over in the next
+        // epoch
+        _ if cursor.is_some() => storage.delete(&cursor_key)?,
+        _ => {}
+    }
+    if delegators.is_empty() {
+        return Ok(());
+    }
+    let params = read_pos_params(storage)?;
+    for (delegator, auto_redelegation, _) in delegators {
+        // The bonds of each delegator are redelegated in their own batch of
+        // writes, so that a failure only skips this delegator
+        let result = in_batch(storage, |storage| {
+            auto_redelegate_bonds(
+                storage,
+                &params,
+                &delegator,
+                &auto_redelegation,
+                current_epoch,
+            )
+        });
+        match result {
+            Ok(redelegations) => {
+                for (src_validator, amount) in redelegations {
+                    events.emit(PosEvent::AutoRedelegation {
+                        delegator: delegator.clone(),
+                        src_validator,
+                        dest_validator: auto_redelegation.fallback.clone(),
+                        amount,
+                        height,
+                    });
+                }
+            }
+            Err(err) => {
+                tracing::error!(
+                    %delegator,
+                    "Failed to apply the automatic redelegations of a \
+                     delegator: {err}"
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Redelegate the bonds of a delegator from the validators jailed for long
+/// enough to its fallback validator. Returns the source validators and the
+/// amounts of the applied redelegations.
+fn auto_redelegate_bonds<S>(
+    storage: &mut S,
+    params: &PosParams,
+    delegator: &Address,
+    auto_redelegation: &AutoRedelegation,
+    current_epoch: Epoch,
+) -> namada_storage::Result<Vec<(Address, token::Amount)>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let AutoRedelegation {
+        fallback,
+        jailed_epochs,
+    } = auto_redelegation;
+    let mut redelegations = Vec::new();
+    // Validators cannot redelegate and the bonds of the members of the
+    // delegation pools are managed by their pools
+    if is_validator(storage, delegator)?
+        || read_delegation_pool_membership(storage, delegator)?.is_some()
+    {
+        return Ok(redelegations);
+    }
+    // Redelegations take effect at the pipeline epoch
+    let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
+    // The fallback validator must be able to receive the bonds
+    let fallback_state = validator_state_handle(fallback).get(
+        storage,
+        pipeline_epoch,
+        params,
+    )?;
+    if matches!(
+        fallback_state,
+        None | Some(ValidatorState::Jailed | ValidatorState::Inactive)
+    ) {
+        tracing::debug!(
+            "Postponing the automatic redelegations of {delegator} as the \
+             fallback validator {fallback} cannot receive bonds"
+        );
+        return Ok(redelegations);
+    }
+    let validators: Vec<Address> = delegation_targets_handle(delegator)
+        .iter(storage)?
+        .map(|res| res.map(|(validator, _)| validator))
+        .collect::<namada_storage::Result<_>>()?;
+    for validator in validators {
+        if validator == *fallback
+            || !is_jailed_for(
+                storage,
+                params,
+                &validator,
+                *jailed_epochs,
+                current_epoch,
+            )?
+        {
+            continue;
+        }
+        let bonded = bond_handle(delegator, &validator)
+            .get_sum(storage, pipeline_epoch, params)?
+            .unwrap_or_default();
+        // The stake of the fallback validator must stay under the cap of its
+        // share of the total stake
+        let capacity = validator_redelegation_capacity(
+            storage,
+            params,
+            fallback,
+            pipeline_epoch,
+        )?;
+        let amount =
+            capacity.map_or(bonded, |capacity| std::cmp::min(bonded, capacity));
+        if amount.is_zero() {
+            continue;
+        }
+        let bond_id = BondId {
+            source: delegator.clone(),
+            validator: validator.clone(),
+        };
+        // The receipts of the redelegated tokens must be held by the delegator
+        if is_validator_frozen(storage, &validator, current_epoch, params)?
+            || is_chained_redelegation(
+                storage,
+                params,
+                delegator,
+                &validator,
+                current_epoch,
+            )?
+            || !has_bond_receipts_to_unbond(
+                storage,
+                params,
+                &bond_id,
+                delegator,
+                amount,
+                current_epoch,
+            )?
+        {
+            tracing::debug!(
+                "Postponing the automatic redelegation of the bond of \
+                 {delegator} to {validator}"
+            );
+            continue;
+        }
+        redelegate_tokens(
+            storage,
+            delegator,
+            &validator,
+            fallback,
+            current_epoch,
+            amount,
+        )?;
+        redelegations.push((validator, amount));
+    }
+    Ok(redelegations)
+}
+
+/// Check if a validator is jailed in the current epoch and has been jailed
+/// for at least the given number of epochs.
+fn is_jailed_for<S>(
+    storage: &S,
+    params: &PosParams,
+    validator: &Address,
+    epochs: u64,
+    current_epoch: Epoch,
+) -> namada_storage::Result<bool>
+where
+    S: StorageRead,
+{
+    let state = validator_state_handle(validator).get(
+        storage,
+        current_epoch,
+        params,
+    )?;
+    if state != Some(ValidatorState::Jailed) {
+        return Ok(false);
+    }
+    let jailed_since = read_validator_jail_history(storage, validator)?
+        .last()
+        .map(|record| record.epoch);
+    Ok(jailed_since.is_some_and(|jailed_since| {
+        current_epoch.0.saturating_sub(jailed_since.0) >= epochs
+    }))
+}
@@ -0,0 +1,166 @@
+//! Block seed.
+//!
+//! Every block proposer contributes to the seed by signing, with its protocol
+//! key, a message that commits to the chain ID, the height of the proposed
+//! block, the hash of the previous block and the previous block seed. The seed
+//! of a block is then the hash of the previous block seed, the previous block
+//! hash and the proposer's signature. It is updated at the beginning of
+//! `FinalizeBlock`, before any of the block's txs are applied, so that the txs
+//! can read it.
+//!
+//! The seed is NOT an unbiasable randomness beacon. It is unpredictable to
+//! everyone but the block proposer, which can bias it:
+//!
+//! - The block proposer learns the value before anyone else.
+//! - The proposer may omit its contribution, in which case the seed is
+//!   derived from the previous seed and block hash only. Proposers can
+//!   therefore always choose between two outcomes.
+//! - A protocol key signature is unique for an honest signer, but it is not a
+//!   VRF proof. A dishonest proposer may produce other valid signatures over
+//!   the same message and grind the outcome offline.
+//!
+//! It must therefore not be used where a proposer would profit from choosing
+//! the outcome, e.g. lotteries whose prize exceeds the proposer's rewards.
+//!
+//! Proposal validation only accepts a single contribution per block, which
+//! must be signed by the block proposer over the expected message. Txs cannot
+//! write the seed, which is enforced by the PoS VP.
+
+use namada_core::address::Address;
+use namada_core::borsh::{
+    BorshDeserialize, BorshSchema, BorshSerialize, BorshSerializeExt,
+};
+use namada_core::hash::Hash;
+use namada_core::key::{common, SigScheme};
+use namada_core::storage::BlockHeight;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_storage::{StorageRead, StorageWrite};
+
+use crate::storage::{read_pos_params, validator_protocol_key_handle};
+use crate::{storage_key, BlockSeedError};
+
+/// Domain separator of the signed seed message
+const BLOCK_SEED_DOMAIN: &[u8] = b"namada-block-seed";
+
+/// A block proposer's contribution to the block seed.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+)]
+pub struct BlockSeedContribution {
+    /// Height of the block this contribution is for
+    pub height: BlockHeight,
+    /// Signature of the proposer's protocol key over the seed message
+    pub signature: common::Signature,
+}
+
+/// Read the seed of the current block. Returns `None` before the
+/// first block seed has been derived.
+pub fn read_block_seed<S>(storage: &S) -> namada_storage::Result<Option<Hash>>
+where
+    S: StorageRead,
+{
+    storage.read(&storage_key::block_seed_key())
+}
+
+/// Compute the message that a block proposer signs to contribute to the
+/// seed of the block at the given height.
+pub fn block_seed_message<S>(
+    storage: &S,
+    height: BlockHeight,
+    last_block_hash: &Hash,
+) -> namada_storage::Result<Hash>
+where
+    S: StorageRead,
+{
+    let chain_id = storage.get_chain_id()?;
+    let prev_seed = read_block_seed(storage)?.unwrap_or_default();
+    let mut message = BLOCK_SEED_DOMAIN.to_vec();
+    message.extend(chain_id.as_bytes());
+    message.extend(height.serialize_to_vec());
+    message.extend(last_block_hash.0);
+    message.extend(prev_seed.0);
+    Ok(Hash::sha256(message))
+}
+
+/// Sign the seed message of the block at the given height with the
+/// proposer's protocol key.
+pub fn make_block_seed_contribution<S>(
+    storage: &S,
+    protocol_sk: &common::SecretKey,
+    height: BlockHeight,
+    last_block_hash: &Hash,
+) -> namada_storage::Result<BlockSeedContribution>
+where
+    S: StorageRead,
+{
+    let message = block_seed_message(storage, height, last_block_hash)?;
+    let signature = common::SigScheme::sign(protocol_sk, message);
+    Ok(BlockSeedContribution { height, signature })
+}
+
+/// Check that a seed contribution is for the block at the given height
+/// and that it has been signed by the protocol key of the block proposer.
+pub fn validate_block_seed_contribution<S>(
+    storage: &S,
+    contribution: &BlockSeedContribution,
+    proposer: &Address,
+    height: BlockHeight,
+    last_block_hash: &Hash,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead,
+{
+    if contribution.height != height {
+        return Err(BlockSeedError::WrongHeight {
+            expected: height,
+            got: contribution.height,
+        }
+        .into());
+    }
+    let params = read_pos_params(storage)?;
+    let epoch = storage.get_block_epoch()?;
+    let protocol_pk = validator_protocol_key_handle(proposer)
+        .get(storage, epoch, &params)?
+        .ok_or_else(|| {
+            BlockSeedError::MissingProtocolKey(proposer.clone(), epoch)
+        })?;
+    let message = block_seed_message(storage, height, last_block_hash)?;
+    common::SigScheme::verify_signature(
+        &protocol_pk,
+        &message,
+        &contribution.signature,
+    )
+    .map_err(|err| BlockSeedError::InvalidSignature(err.to_string()))?;
+    Ok(())
+}
+
+/// Derive and write the seed of the current block from the previous
+/// seed, the previous block hash and the proposer's contribution, if
+/// any. The contribution must have been validated beforehand.
+pub fn update_block_seed<S>(
+    storage: &mut S,
+    last_block_hash: &Hash,
+    contribution: Option<&BlockSeedContribution>,
+) -> namada_storage::Result<Hash>
+where
+    S: StorageRead + StorageWrite,
+{
+    let prev_seed = read_block_seed(storage)?.unwrap_or_default();
+    let mut preimage = prev_seed.0.to_vec();
+    preimage.extend(last_block_hash.0);
+    if let Some(contribution) = contribution {
+        preimage.extend(contribution.signature.serialize_to_vec());
+    }
+    let seed = Hash::sha256(preimage);
+    storage.write(&storage_key::block_seed_key(), seed)?;
+    Ok(seed)
+}
@@ -0,0 +1,163 @@
+//! Liquid staking receipts.
+//!
+//! When enabled by the `liquid_staking_receipts` PoS parameter, bonding mints
+//! receipt tokens of the bonded validator to the source of the bond, one for
+//! every bonded token. The receipts are regular multitoken balances that can
+//! be transferred, but unbonding or redelegating a bond burns them again from
+//! the redeemer, i.e. the account giving the receipts back, so the bonded
+//! tokens can only be unbonded with the holder's consent. The redeemer is the
+//! source of the bond, unless the unbond specifies another one. The
+//! receipts are not adjusted when the validator is slashed, which keeps the
+//! slashing exposure attached to the receipt holder.
+//!
+//! The outstanding receipts of every bond are tracked, so that bonds created
+//! while the receipts were disabled can be unbonded without receipts. The part
+//! of a bond without receipts is always unbonded first.
+
+use namada_core::address::{Address, InternalAddress};
+use namada_core::arith::checked;
+use namada_core::storage::Epoch;
+use namada_storage::{StorageRead, StorageWrite};
+
+use crate::storage::bond_handle;
+use crate::storage_key::bond_receipts_key;
+use crate::types::BondId;
+use crate::{token, PosParams, UnbondError, ADDRESS};
+
+/// Get the address of the receipt token of the bonds to the given validator.
+/// Returns `None` if the address is not an established address, which every
+/// validator must have.
+pub fn bond_receipt_token(validator: &Address) -> Option<Address> {
+    match validator {
+        Address::Established(validator) => Some(Address::Internal(
+            InternalAddress::BondReceipt(validator.clone()),
+        )),
+        _ => None,
+    }
+}
+
+/// Read the amount of outstanding receipt tokens of a bond.
+pub fn read_bond_receipts<S>(
+    storage: &S,
+    bond_id: &BondId,
+) -> namada_storage::Result<token::Amount>
+where
+    S: StorageRead,
+{
+    Ok(storage
+        .read(&bond_receipts_key(bond_id))?
+        .unwrap_or_default())
+}
+
+/// Find the amount of receipt tokens that must be burned to unbond the given
+/// amount from a bond.
+pub fn bond_receipts_to_burn<S>(
+    storage: &S,
+    params: &PosParams,
+    bond_id: &BondId,
+    amount: token::Amount,
+    current_epoch: Epoch,
+) -> namada_storage::Result<token::Amount>
+where
+    S: StorageRead,
+{
+    let receipts = read_bond_receipts(storage, bond_id)?;
+    if receipts.is_zero() {
+        return Ok(receipts);
+    }
+    let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
+    let bonded = bond_handle(&bond_id.source, &bond_id.validator)
+        .get_sum(storage, pipeline_epoch, params)?
+        .unwrap_or_default();
+    // The part of the bond without receipts is unbonded first
+    let without_receipts = bonded.checked_sub(receipts).unwrap_or_default();
+    let to_burn = amount.checked_sub(without_receipts).unwrap_or_default();
+    Ok(std::cmp::min(to_burn, receipts))
+}
+
+/// Check if the redeemer holds enough receipt tokens to unbond the given
+/// amount from a bond.
+pub fn has_bond_receipts_to_unbond<S>(
+    storage: &S,
+    params: &PosParams,
+    bond_id: &BondId,
+    redeemer: &Address,
+    amount: token::Amount,
+    current_epoch: Epoch,
+) -> namada_storage::Result<bool>
+where
+    S: StorageRead,
+{
+    let Some(receipt_token) = bond_receipt_token(&bond_id.validator) else {
+        return Ok(true);
+    };
+    let to_burn =
+        bond_receipts_to_burn(storage, params, bond_id, amount, current_epoch)?;
+    if to_burn.is_zero() {
+        return Ok(true);
+    }
+    let balance = token::read_balance(storage, &receipt_token, redeemer)?;
+    Ok(balance >= to_burn)
+}
+
+/// Mint receipt tokens of a bond to its source.
+pub(crate) fn mint_bond_receipts<S>(
+    storage: &mut S,
+    bond_id: &BondId,
+    amount: token::Amount,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let Some(receipt_token) = bond_receipt_token(&bond_id.validator) else {
+        return Ok(());
+    };
+    if amount.is_zero() {
+        return Ok(());
+    }
+    token::mint_tokens(
+        storage,
+        &ADDRESS,
+        &receipt_token,
+        &bond_id.source,
+        amount,
+    )?;
+    let receipts = read_bond_receipts(storage, bond_id)?;
+    storage.write(&bond_receipts_key(bond_id), checked!(receipts + amount)?)
+}
+
+/// Burn receipt tokens of a bond from the redeemer. Fails if the redeemer
+/// doesn't hold enough receipts, e.g. after transferring them to another
+/// account.
+pub(crate) fn burn_bond_receipts<S>(
+    storage: &mut S,
+    bond_id: &BondId,
+    redeemer: &Address,
+    amount: token::Amount,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let Some(receipt_token) = bond_receipt_token(&bond_id.validator) else {
+        return Ok(());
+    };
+    if amount.is_zero() {
+        return Ok(());
+    }
+    let balance = token::read_balance(storage, &receipt_token, redeemer)?;
+    if balance < amount {
+        return Err(UnbondError::InsufficientBondReceipts {
+            validator: bond_id.validator.clone(),
+            required: amount.to_string_native(),
+            available: balance.to_string_native(),
+        }
+        .into());
+    }
+    token::burn_tokens(storage, &receipt_token, redeemer, amount)?;
+    let receipts = read_bond_receipts(storage, bond_id)?;
+    let key = bond_receipts_key(bond_id);
+    match receipts.checked_sub(amount) {
+        Some(receipts) if !receipts.is_zero() => storage.write(&key, receipts),
+        _ => storage.delete(&key),
+    }
+}
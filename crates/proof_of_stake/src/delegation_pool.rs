@@ -0,0 +1,463 @@
+//! Delegation pools.
+//!
+//! A delegation pool is a named list of validators with target weights,
+//! curated by its operator. The operator is either an account, or the
+//! governance address for pools that can only be updated by governance
+//! proposals. Delegators join a pool by bonding to it, which splits the bonded
+//! tokens across the pool's validators according to their weights.
+//!
+//! All the bonds of a pool member are managed by the pool: at every epoch
+//! boundary, the member's bonds are redelegated to match the current weights
+//! of the pool, e.g. after the operator changed them. At most
+//! [`MAX_REBALANCED_MEMBERS_PER_EPOCH`] members are rebalanced per epoch, the
+//! following ones are rebalanced in the next epochs. Bonds that cannot be
+//! redelegated yet, i.e. bonds to frozen validators or chained redelegations,
+//! are rebalanced at a later epoch. The rewards of the pool's bonds are
+//! claimed together, proportionally to the stake bonded to each validator.
+//! Bonds whose receipt tokens (see [`crate::bond_receipt`]) are not held by
+//! the member anymore are not rebalanced.
+//! Members can leave a pool at any time, which keeps their bonds as they are.
+
+use std::collections::BTreeMap;
+
+use namada_core::address::Address;
+use namada_core::arith::checked;
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::dec::Dec;
+use namada_core::storage::Epoch;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_storage::collections::lazy_map::{Collectable, LazyMap};
+use namada_storage::collections::LazyCollection;
+use namada_storage::{
+    in_batch, OptionExt, StorageBatch, StorageRead, StorageWrite,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::bond_receipt::has_bond_receipts_to_unbond;
+use crate::storage::{bond_handle, delegation_targets_handle, read_pos_params};
+use crate::types::BondId;
+use crate::{
+    bond_tokens, claim_reward_tokens, is_chained_redelegation, is_validator,
+    is_validator_frozen, redelegate_tokens, storage_key, token,
+    validator_redelegation_capacity, DelegationPoolError, PosParams,
+};
+
+/// The maximum length of the name of a delegation pool
+pub const MAX_POOL_NAME_LEN: usize = 64;
+
+/// The maximum number of validators of a delegation pool, which bounds the
+/// number of redelegations needed to rebalance the bonds of a member
+pub const MAX_POOL_VALIDATORS: usize = 32;
+
+/// The maximum number of delegation pool members whose bonds are rebalanced
+/// at an epoch boundary, which bounds the work done at the beginning of an
+/// epoch
+pub const MAX_REBALANCED_MEMBERS_PER_EPOCH: usize = 256;
+
+/// A delegation pool.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct DelegationPool {
+    /// The address allowed to update the pool
+    pub operator: Address,
+    /// The target weight of each validator of the pool, summing up to 1
+    pub validators: BTreeMap<Address, Dec>,
+}
+
+/// Get the storage handle to the delegation pools, keyed by their names
+pub fn delegation_pools_handle() -> LazyMap<String, DelegationPool> {
+    LazyMap::open(storage_key::delegation_pools_key())
+}
+
+/// Get the storage handle to the members of the delegation pools, mapped to
+/// the names of their pools
+pub fn delegation_pool_members_handle() -> LazyMap<Address, String> {
+    LazyMap::open(storage_key::delegation_pool_members_key())
+}
+
+/// Read the delegation pool with the given name.
+pub fn read_delegation_pool<S>(
+    storage: &S,
+    name: &str,
+) -> namada_storage::Result<Option<DelegationPool>>
+where
+    S: StorageRead,
+{
+    delegation_pools_handle().get(storage, &name.to_owned())
+}
+
+/// Read all the delegation pools.
+pub fn read_delegation_pools<S>(
+    storage: &S,
+) -> namada_storage::Result<BTreeMap<String, DelegationPool>>
+where
+    S: StorageRead,
+{
+    delegation_pools_handle().collect_map(storage)
+}
+
+/// Read the name of the delegation pool of a delegator, if any.
+pub fn read_delegation_pool_membership<S>(
+    storage: &S,
+    delegator: &Address,
+) -> namada_storage::Result<Option<String>>
+where
+    S: StorageRead,
+{
+    delegation_pool_members_handle().get(storage, delegator)
+}
+
+/// Create or update a delegation pool. The changes to the weights take effect
+/// when the members' bonds are rebalanced, at the next epoch boundary.
+pub fn update_delegation_pool<S>(
+    storage: &mut S,
+    name: &str,
+    pool: DelegationPool,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let is_valid_name = !name.is_empty()
+        && name.len() <= MAX_POOL_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !is_valid_name {
+        return Err(DelegationPoolError::InvalidName(name.to_owned()).into());
+    }
+    let num_validators = pool.validators.len();
+    if num_validators == 0 || num_validators > MAX_POOL_VALIDATORS {
+        return Err(
+            DelegationPoolError::InvalidValidatorCount(num_validators).into()
+        );
+    }
+    let mut total_weight = Dec::zero();
+    for (validator, weight) in &pool.validators {
+        if !is_validator(storage, validator)? {
+            return Err(
+                DelegationPoolError::NotAValidator(validator.clone()).into()
+            );
+        }
+        if weight.is_negative() || weight.is_zero() {
+            return Err(DelegationPoolError::InvalidWeights.into());
+        }
+        total_weight = checked!(total_weight + *weight)?;
+    }
+    if total_weight != Dec::one() {
+        return Err(DelegationPoolError::InvalidWeights.into());
+    }
+    delegation_pools_handle().insert(storage, name.to_owned(), pool)?;
+    Ok(())
+}
+
+/// Bond tokens from a delegator to a delegation pool, making it a member of
+/// the pool. The tokens are split across the pool's validators according to
+/// their weights. Returns the amount bonded to each validator.
+pub fn bond_to_delegation_pool<S>(
+    storage: &mut S,
+    delegator: &Address,
+    name: &str,
+    amount: token::Amount,
+    current_epoch: Epoch,
+) -> namada_storage::Result<BTreeMap<Address, token::Amount>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let pool = read_delegation_pool(storage, name)?
+        .ok_or_else(|| DelegationPoolError::UnknownPool(name.to_owned()))?;
+    match read_delegation_pool_membership(storage, delegator)? {
+        Some(pool) if pool != name => {
+            return Err(DelegationPoolError::AlreadyInAnotherPool {
+                delegator: delegator.clone(),
+                pool,
+            }
+            .into());
+        }
+        Some(_) => {}
+        None => {
+            delegation_pool_members_handle().insert(
+                storage,
+                delegator.clone(),
+                name.to_owned(),
+            )?;
+        }
+    }
+
+    let bonds = split_by_weights(amount, &pool)?;
+    for (validator, amount) in &bonds {
+        bond_tokens(
+            storage,
+            Some(delegator),
+            validator,
+            *amount,
+            current_epoch,
+            None,
+        )?;
+    }
+    Ok(bonds)
+}
+
+/// Remove a delegator from its delegation pool. Its bonds are kept as they
+/// are, but they are no longer rebalanced.
+pub fn leave_delegation_pool<S>(
+    storage: &mut S,
+    delegator: &Address,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    delegation_pool_members_handle()
+        .remove(storage, delegator)?
+        .ok_or_else(|| DelegationPoolError::NotAMember(delegator.clone()))?;
+    Ok(())
+}
+
+/// Claim the rewards of all the bonds of a member of a delegation pool.
+/// Returns the amount of rewards claimed from each validator.
+pub fn claim_delegation_pool_rewards<S>(
+    storage: &mut S,
+    delegator: &Address,
+    current_epoch: Epoch,
+) -> namada_storage::Result<BTreeMap<Address, token::Amount>>
+where
+    S: StorageRead + StorageWrite,
+{
+    if read_delegation_pool_membership(storage, delegator)?.is_none() {
+        return Err(DelegationPoolError::NotAMember(delegator.clone()).into());
+    }
+    let validators: Vec<Address> = delegation_targets_handle(delegator)
+        .iter(storage)?
+        .map(|res| res.map(|(validator, _)| validator))
+        .collect::<namada_storage::Result<_>>()?;
+    let mut rewards = BTreeMap::new();
+    for validator in validators {
+        let amount = claim_reward_tokens(
+            storage,
+            Some(delegator),
+            &validator,
+            current_epoch,
+        )?;
+        rewards.insert(validator, amount);
+    }
+    Ok(rewards)
+}
+
+/// Split an amount of tokens across the validators of a pool according to
+/// their weights. The remainder of the rounding goes to the validator with
+/// the highest weight.
+pub fn split_by_weights(
+    amount: token::Amount,
+    pool: &DelegationPool,
+) -> namada_storage::Result<BTreeMap<Address, token::Amount>> {
+    let mut split = BTreeMap::new();
+    let mut remainder = amount;
+    for (validator, weight) in &pool.validators {
+        let share = amount.mul_floor(*weight)?;
+        remainder = checked!(remainder - share)?;
+        split.insert(validator.clone(), share);
+    }
+    let heaviest = pool
+        .validators
+        .iter()
+        .max_by_key(|(_, weight)| **weight)
+        .map(|(validator, _)| validator)
+        .ok_or_err_msg("A delegation pool must have validators")?;
+    let share = split.get_mut(heaviest).expect("The validator must exist");
+    *share = checked!(*share + remainder)?;
+    Ok(split)
+}
+
+/// Redelegate the bonds of the members of the delegation pools to match the
+/// weights of their pools. Applied at the beginning of every epoch to at most
+/// [`MAX_REBALANCED_MEMBERS_PER_EPOCH`] members, resuming after the last
+/// member rebalanced in the previous epoch. A member whose rebalancing fails
+/// is skipped, without keeping any of its writes.
+pub fn rebalance_delegation_pools<S>(
+    storage: &mut S,
+    current_epoch: Epoch,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageBatch,
+{
+    let members_handle = delegation_pool_members_handle();
+    let cursor_key = storage_key::delegation_pool_rebalance_cursor_key();
+    // The storage key of the last rebalanced member, in the iteration order
+    let cursor: Option<String> = storage.read(&cursor_key)?;
+    let mut members = Vec::new();
+    for member in members_handle.iter(storage)? {
+        let (delegator, name) = member?;
+        let member_key = members_handle.get_data_key(&delegator).to_string();
+        if cursor.as_ref().is_some_and(|cursor| member_key <= *cursor) {
+            continue;
+        }
+        members.push((delegator, name, member_key));
+        if members.len() == MAX_REBALANCED_MEMBERS_PER_EPOCH {
+            break;
+        }
+    }
+    match members.last() {
+        // Resume after the last member in the next epoch
+        Some((_, _, member_key))
+            if members.len() == MAX_REBALANCED_MEMBERS_PER_EPOCH =>
+        {
+            storage.write(&cursor_key, member_key.clone())?;
+        }
+        // All the members have been rebalanced, start over in the next epoch
+        _ if cursor.is_some() => storage.delete(&cursor_key)?,
+        _ => {}
+    }
+    if members.is_empty() {
+        return Ok(());
+    }
+    let params = read_pos_params(storage)?;
+    for (delegator, name, _) in members {
+        // Each member is rebalanced in its own batch of writes, so that a
+        // failure only skips this member
+        let result = in_batch(storage, |storage| {
+            let Some(pool) = read_delegation_pool(storage, &name)? else {
+                return Ok(());
+            };
+            // Validators cannot redelegate
+            if is_validator(storage, &delegator)? {
+                return Ok(());
+            }
+            rebalance_member_bonds(
+                storage,
+                &params,
+                &delegator,
+                &pool,
+                current_epoch,
+            )
+        });
+        if let Err(err) = result {
+            tracing::error!(
+                %delegator,
+                pool = %name,
+                "Failed to rebalance the bonds of a delegation pool member: \
+                 {err}"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Redelegate the bonds of a member of a delegation pool to match the weights
+/// of the pool, as far as the redelegation rules allow.
+fn rebalance_member_bonds<S>(
+    storage: &mut S,
+    params: &PosParams,
+    delegator: &Address,
+    pool: &DelegationPool,
+    current_epoch: Epoch,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    // Redelegations take effect at the pipeline epoch
+    let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
+    let mut bonds = BTreeMap::new();
+    let validators: Vec<Address> = delegation_targets_handle(delegator)
+        .iter(storage)?
+        .map(|res| res.map(|(validator, _)| validator))
+        .collect::<namada_storage::Result<_>>()?;
+    for validator in validators {
+        let amount = bond_handle(delegator, &validator)
+            .get_sum(storage, pipeline_epoch, params)?
+            .unwrap_or_default();
+        if !amount.is_zero() {
+            bonds.insert(validator, amount);
+        }
+    }
+    let total = token::Amount::sum(bonds.values().copied())
+        .ok_or_err_msg("token amount overflow")?;
+    let targets = split_by_weights(total, pool)?;
+
+    let mut deficits = Vec::new();
+    for (validator, target) in &targets {
+        let bonded = bonds.get(validator).copied().unwrap_or_default();
+        if *target > bonded {
+            deficits.push((validator, checked!(*target - bonded)?));
+        }
+    }
+    for (src_validator, bonded) in &bonds {
+        let target = targets.get(src_validator).copied().unwrap_or_default();
+        if *bonded <= target {
+            continue;
+        }
+        let mut surplus = checked!(*bonded - target)?;
+        let bond_id = BondId {
+            source: delegator.clone(),
+            validator: src_validator.clone(),
+        };
+        // The receipts of the redelegated tokens must be held by the member
+        if is_validator_frozen(storage, src_validator, current_epoch, params)?
+            || is_chained_redelegation(
+                storage,
+                params,
+                delegator,
+                src_validator,
+                current_epoch,
+            )?
+            || !has_bond_receipts_to_unbond(
+                storage,
+                params,
+                &bond_id,
+                delegator,
+                surplus,
+                current_epoch,
+            )?
+        {
+            tracing::debug!(
+                "Postponing the rebalancing of the bond of {delegator} to \
+                 {src_validator}"
+            );
+            continue;
+        }
+        while let Some((dest_validator, deficit)) = deficits.last_mut() {
+            if surplus.is_zero() {
+                break;
+            }
+            // The stake of the dest validator must stay under the cap of its
+            // share of the total stake
+            let capacity = validator_redelegation_capacity(
+                storage,
+                params,
+                dest_validator,
+                pipeline_epoch,
+            )?;
+            let amount = std::cmp::min(surplus, *deficit);
+            let amount = capacity
+                .map_or(amount, |capacity| std::cmp::min(amount, capacity));
+            if amount.is_zero() {
+                deficits.pop();
+                continue;
+            }
+            redelegate_tokens(
+                storage,
+                delegator,
+                src_validator,
+                dest_validator,
+                current_epoch,
+                amount,
+            )?;
+            surplus = checked!(surplus - amount)?;
+            *deficit = checked!(*deficit - amount)?;
+            if deficit.is_zero() {
+                deficits.pop();
+            }
+        }
+    }
+    Ok(())
+}
@@ -3,7 +3,7 @@ use std::num::TryFromIntError;
 
 use namada_core::address::Address;
 use namada_core::dec::Dec;
-use namada_core::storage::Epoch;
+use namada_core::storage::{BlockHeight, Epoch};
 use thiserror::Error;
 
 use crate::rewards;
@@ -30,6 +30,12 @@ pub enum InflationError {
 pub enum BecomeValidatorError {
     #[error("The given address {0} is already a validator")]
     AlreadyValidator(Address),
+    #[error(
+        "The commission rate {0} is lower than the minimum commission rate {1}"
+    )]
+    CommissionRateTooLow(Dec, Dec),
+    #[error("The validator metadata is missing the required fields: {0}")]
+    MissingMetadata(String),
 }
 
 #[allow(missing_docs)]
@@ -46,6 +52,12 @@ pub enum BondError {
     InactiveValidator(Address),
     #[error("Voting power overflow: {0}")]
     VotingPowerOverflow(TryFromIntError),
+    #[error(
+        "The bond would increase the stake of the validator {0} to {1}, above \
+         the max stake of {2} allowed by the cap of its share of the total \
+         stake"
+    )]
+    ValidatorStakeCapExceeded(Address, String, String),
 }
 
 #[allow(missing_docs)]
@@ -65,6 +77,15 @@ pub enum UnbondError {
     VotingPowerOverflow(TryFromIntError),
     #[error("Trying to unbond from a frozen validator: {0}")]
     ValidatorIsFrozen(Address),
+    #[error(
+        "Unbonding requires burning {required} bond receipts of the validator \
+         {validator}, but only {available} are available"
+    )]
+    InsufficientBondReceipts {
+        validator: Address,
+        required: String,
+        available: String,
+    },
 }
 
 #[allow(missing_docs)]
@@ -102,6 +123,11 @@ pub enum CommissionRateChangeError {
     LargerThanOne(Dec, Address),
     #[error("Rate change of {0} is too large for validator {1}")]
     RateChangeTooLarge(Dec, Address),
+    #[error(
+        "Commission rate {0} is lower than the minimum commission rate {1} \
+         for validator {2}"
+    )]
+    LowerThanMinimum(Dec, Dec, Address),
     #[error(
         "There is no maximum rate change written in storage for validator {0}"
     )]
@@ -137,6 +163,12 @@ pub enum RedelegationError {
     DelegatorIsValidator,
     #[error("The address {0} must be a validator")]
     NotAValidator(Address),
+    #[error(
+        "The redelegation would increase the stake of the validator {0} to \
+         {1}, above the max stake of {2} allowed by the cap of its share of \
+         the total stake"
+    )]
+    ValidatorStakeCapExceeded(Address, String, String),
 }
 
 #[allow(missing_docs)]
@@ -167,6 +199,8 @@ pub enum ReactivationError {
 pub enum MetadataError {
     #[error("The validator email cannot be removed")]
     CannotRemoveEmail,
+    #[error("The validator metadata is missing the required fields: {0}")]
+    MissingRequired(String),
 }
 
 #[allow(missing_docs)]
@@ -176,6 +210,135 @@ pub enum ConsensusKeyChangeError {
     MustBeEd25519,
 }
 
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum BlockSeedError {
+    #[error(
+        "The seed contribution is for block height {got}, expected \
+         {expected}"
+    )]
+    WrongHeight {
+        expected: BlockHeight,
+        got: BlockHeight,
+    },
+    #[error("No protocol key found for the block proposer {0} in epoch {1}")]
+    MissingProtocolKey(Address, Epoch),
+    #[error("Invalid seed contribution signature: {0}")]
+    InvalidSignature(String),
+}
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum OperationalMetadataError {
+    #[error(
+        "The operational metadata update is for epoch {got}, expected \
+         {expected}"
+    )]
+    WrongEpoch { expected: Epoch, got: Epoch },
+    #[error(
+        "The operational metadata update has the nonce {got}, expected \
+         {expected}"
+    )]
+    WrongNonce { expected: u64, got: u64 },
+    #[error(
+        "The operational metadata fields must not be empty or longer than \
+         {max} bytes",
+        max = crate::operational_metadata::MAX_OPERATIONAL_METADATA_FIELD_LEN
+    )]
+    InvalidField,
+    #[error("The address {0} is not a validator")]
+    NotAValidator(Address),
+    #[error("No protocol key found for the validator {0} in epoch {1}")]
+    MissingProtocolKey(Address, Epoch),
+    #[error(
+        "The operational metadata update must be signed by the protocol key \
+         of the validator {0}"
+    )]
+    WrongSigner(Address),
+}
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum TelemetryError {
+    #[error(
+        "The telemetry heartbeat was signed at block height {got}, which is \
+         not after the last heartbeat's height {last} and at most the current \
+         height {current}"
+    )]
+    InvalidHeight {
+        got: BlockHeight,
+        last: BlockHeight,
+        current: BlockHeight,
+    },
+    #[error(
+        "The telemetry heartbeat was signed at block height {got}, less than \
+         {min} blocks after the last heartbeat's height {last}",
+        min = crate::telemetry::MIN_TELEMETRY_HEARTBEAT_INTERVAL
+    )]
+    TooFrequent { got: BlockHeight, last: BlockHeight },
+    #[error(
+        "The telemetry fields must not be empty or longer than {max} bytes",
+        max = crate::telemetry::MAX_TELEMETRY_FIELD_LEN
+    )]
+    InvalidField,
+    #[error("The address {0} is not a validator")]
+    NotAValidator(Address),
+    #[error("No protocol key found for the validator {0} in epoch {1}")]
+    MissingProtocolKey(Address, Epoch),
+    #[error(
+        "The telemetry heartbeat must be signed by the protocol key of the \
+         validator {0}"
+    )]
+    WrongSigner(Address),
+}
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum DelegationPoolError {
+    #[error(
+        "Invalid delegation pool name {0:?}. The name must be 1 to {max} \
+         ASCII alphanumeric, '-' or '_' characters",
+        max = crate::delegation_pool::MAX_POOL_NAME_LEN
+    )]
+    InvalidName(String),
+    #[error("The delegation pool {0} doesn't exist")]
+    UnknownPool(String),
+    #[error(
+        "A delegation pool must have between 1 and {max} validators, got {0}",
+        max = crate::delegation_pool::MAX_POOL_VALIDATORS
+    )]
+    InvalidValidatorCount(usize),
+    #[error("The given address {0} is not a validator address")]
+    NotAValidator(Address),
+    #[error(
+        "The weights of the validators of a delegation pool must be positive \
+         and sum up to 1"
+    )]
+    InvalidWeights,
+    #[error(
+        "The delegator {delegator} is already a member of the pool {pool}"
+    )]
+    AlreadyInAnotherPool { delegator: Address, pool: String },
+    #[error("The address {0} is not a member of any delegation pool")]
+    NotAMember(Address),
+}
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum AutoRedelegationError {
+    #[error(
+        "The given address {0} is a validator address. Validators may not \
+         redelegate."
+    )]
+    DelegatorIsValidator(Address),
+    #[error("The given fallback address {0} is not a validator address")]
+    NotAValidator(Address),
+    #[error(
+        "The number of jailed epochs before redelegating must be positive"
+    )]
+    ZeroJailedEpochs,
+}
+
 impl From<BecomeValidatorError> for namada_storage::Error {
     fn from(err: BecomeValidatorError) -> Self {
         Self::new(err)
@@ -247,3 +410,33 @@ impl From<ConsensusKeyChangeError> for namada_storage::Error {
         Self::new(err)
     }
 }
+
+impl From<BlockSeedError> for namada_storage::Error {
+    fn from(err: BlockSeedError) -> Self {
+        Self::new(err)
+    }
+}
+
+impl From<OperationalMetadataError> for namada_storage::Error {
+    fn from(err: OperationalMetadataError) -> Self {
+        Self::new(err)
+    }
+}
+
+impl From<TelemetryError> for namada_storage::Error {
+    fn from(err: TelemetryError) -> Self {
+        Self::new(err)
+    }
+}
+
+impl From<DelegationPoolError> for namada_storage::Error {
+    fn from(err: DelegationPoolError) -> Self {
+        Self::new(err)
+    }
+}
+
+impl From<AutoRedelegationError> for namada_storage::Error {
+    fn from(err: AutoRedelegationError) -> Self {
+        Self::new(err)
+    }
+}
@@ -1,10 +1,14 @@
 //! Proof of Stake events.
 
 use namada_core::address::Address;
+use namada_core::key::common;
+use namada_core::storage::{BlockHeight, Epoch};
 use namada_core::token;
 use namada_core::uint::Uint;
-use namada_events::extend::{ComposeEvent, EventAttributeEntry};
-use namada_events::{Event, EventLevel, EventToEmit};
+use namada_events::extend::{ComposeEvent, EventAttributeEntry, Height};
+use namada_events::{typed_event, Event, EventLevel, EventToEmit};
+
+use crate::evidence::{EvidenceKind, EvidenceOutcome, EvidenceRecord};
 
 pub mod types {
     //! Proof of Stake event types.
@@ -15,6 +19,20 @@ pub mod types {
 
     /// Slash event.
     pub const SLASH: EventType = event_type!(PosEvent, "slash");
+
+    /// Rewards event.
+    pub const REWARDS: EventType = event_type!(PosEvent, "rewards");
+
+    /// Consensus key activation event.
+    pub const CONSENSUS_KEY_ACTIVATED: EventType =
+        event_type!(PosEvent, "consensus-key-activated");
+
+    /// Automatic redelegation event.
+    pub const AUTO_REDELEGATION: EventType =
+        event_type!(PosEvent, "auto-redelegation");
+
+    /// Byzantine evidence event.
+    pub const EVIDENCE: EventType = event_type!(PosEvent, "evidence");
 }
 
 /// Proof of Stake event.
@@ -27,6 +45,43 @@ pub enum PosEvent {
         /// Amount of tokens that have been slashed.
         amount: token::Amount,
     },
+    /// Rewards event.
+    Rewards {
+        /// The address of the rewarded validator.
+        validator: Address,
+        /// Amount of inflation tokens that have been attributed to the
+        /// validator and its delegators.
+        amount: token::Amount,
+    },
+    /// Consensus key activation event.
+    ConsensusKeyActivated {
+        /// The address of the validator.
+        validator: Address,
+        /// The consensus key that has come into effect.
+        consensus_key: common::PublicKey,
+        /// The epoch from which the key is used for consensus.
+        epoch: Epoch,
+        /// The height of the first block of the epoch.
+        height: BlockHeight,
+    },
+    /// Automatic redelegation event.
+    AutoRedelegation {
+        /// The address of the delegator.
+        delegator: Address,
+        /// The jailed validator that the bond was redelegated from.
+        src_validator: Address,
+        /// The fallback validator that the bond was redelegated to.
+        dest_validator: Address,
+        /// Amount of tokens that have been redelegated.
+        amount: token::Amount,
+        /// The height of the first block of the epoch.
+        height: BlockHeight,
+    },
+    /// Byzantine evidence event.
+    Evidence {
+        /// The evidence received from CometBFT and its outcome.
+        record: EvidenceRecord,
+    },
 }
 
 impl EventToEmit for PosEvent {
@@ -42,10 +97,122 @@ impl From<PosEvent> for Event {
                     .with(SlashedAmount(&amount.into()))
                     .into()
             }
+            PosEvent::Rewards { validator, amount } => {
+                Event::new(types::REWARDS, EventLevel::Block)
+                    .with(RewardedValidator(validator))
+                    .with(RewardsAmount(&amount.into()))
+                    .into()
+            }
+            PosEvent::ConsensusKeyActivated {
+                validator,
+                consensus_key,
+                epoch,
+                height,
+            } => ConsensusKeyActivation {
+                validator,
+                consensus_key,
+                epoch,
+                height,
+            }
+            .into(),
+            PosEvent::AutoRedelegation {
+                delegator,
+                src_validator,
+                dest_validator,
+                amount,
+                height,
+            } => AutoRedelegated {
+                delegator,
+                src_validator,
+                dest_validator,
+                amount: token::DenominatedAmount::native(amount),
+                height,
+            }
+            .into(),
+            PosEvent::Evidence { record } => {
+                let event = EvidenceReceived {
+                    kind: record.kind,
+                    consensus_address: record.consensus_address,
+                    evidence_height: record.evidence_height,
+                    outcome: record.outcome,
+                    height: record.height,
+                };
+                match record.validator {
+                    Some(validator) => {
+                        event.with(EvidenceValidator(validator)).into()
+                    }
+                    None => event.into(),
+                }
+            }
         }
     }
 }
 
+typed_event! {
+    /// Typed consensus key activation event.
+    pub struct ConsensusKeyActivation {
+        domain: PosEvent,
+        event_type: types::CONSENSUS_KEY_ACTIVATED,
+        version: 1,
+        level: EventLevel::Block,
+        attributes: {
+            /// The address of the validator.
+            validator: ConsensusKeyValidator,
+            /// The consensus key that has come into effect.
+            consensus_key: ConsensusKey,
+            /// The epoch from which the key is used for consensus.
+            epoch: ConsensusKeyEpoch,
+            /// The height of the first block of the epoch.
+            height: Height,
+        },
+    }
+}
+
+typed_event! {
+    /// Typed automatic redelegation event.
+    pub struct AutoRedelegated {
+        domain: PosEvent,
+        event_type: types::AUTO_REDELEGATION,
+        version: 1,
+        level: EventLevel::Block,
+        attributes: {
+            /// The address of the delegator.
+            delegator: RedelegationDelegator,
+            /// The jailed validator that the bond was redelegated from.
+            src_validator: RedelegationSrcValidator,
+            /// The fallback validator that the bond was redelegated to.
+            dest_validator: RedelegationDestValidator,
+            /// Amount of tokens that have been redelegated.
+            amount: RedelegatedAmount,
+            /// The height of the first block of the epoch.
+            height: Height,
+        },
+    }
+}
+
+typed_event! {
+    /// Typed byzantine evidence event. If the misbehaving validator is known,
+    /// the event is extended with its [address](EvidenceValidator).
+    pub struct EvidenceReceived {
+        domain: PosEvent,
+        event_type: types::EVIDENCE,
+        version: 1,
+        level: EventLevel::Block,
+        attributes: {
+            /// The kind of misbehavior.
+            kind: EvidenceType,
+            /// The consensus address of the misbehaving validator.
+            consensus_address: EvidenceConsensusAddress,
+            /// The block height at which the misbehavior occurred.
+            evidence_height: EvidenceHeight,
+            /// What the protocol did with the evidence.
+            outcome: EvidenceResult,
+            /// The height of the block in which the evidence was committed.
+            height: Height,
+        },
+    }
+}
+
 /// Extend an [`Event`] with slashed validator data.
 pub struct SlashedValidator(pub Address);
 
@@ -54,6 +221,7 @@ impl EventAttributeEntry<'static> for SlashedValidator {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "slashed-validator";
+    const VALUE_TYPE: &'static str = "address";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -68,6 +236,217 @@ impl<'amt> EventAttributeEntry<'amt> for SlashedAmount<'amt> {
     type ValueOwned = Uint;
 
     const KEY: &'static str = "slashed-amount";
+    const VALUE_TYPE: &'static str = "uint";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with rewarded validator data.
+pub struct RewardedValidator(pub Address);
+
+impl EventAttributeEntry<'static> for RewardedValidator {
+    type Value = Address;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "rewarded-validator";
+    const VALUE_TYPE: &'static str = "address";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with rewards amount data.
+pub struct RewardsAmount<'amt>(pub &'amt Uint);
+
+impl<'amt> EventAttributeEntry<'amt> for RewardsAmount<'amt> {
+    type Value = &'amt Uint;
+    type ValueOwned = Uint;
+
+    const KEY: &'static str = "rewards-amount";
+    const VALUE_TYPE: &'static str = "uint";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the validator of a consensus key.
+pub struct ConsensusKeyValidator(pub Address);
+
+impl EventAttributeEntry<'static> for ConsensusKeyValidator {
+    type Value = Address;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "consensus-key-validator";
+    const VALUE_TYPE: &'static str = "address";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with a consensus key.
+pub struct ConsensusKey(pub common::PublicKey);
+
+impl EventAttributeEntry<'static> for ConsensusKey {
+    type Value = common::PublicKey;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "consensus-key";
+    const VALUE_TYPE: &'static str = "public-key";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the epoch from which a consensus key is used.
+pub struct ConsensusKeyEpoch(pub Epoch);
+
+impl EventAttributeEntry<'static> for ConsensusKeyEpoch {
+    type Value = Epoch;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "consensus-key-epoch";
+    const VALUE_TYPE: &'static str = "epoch";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the delegator of a redelegation.
+pub struct RedelegationDelegator(pub Address);
+
+impl EventAttributeEntry<'static> for RedelegationDelegator {
+    type Value = Address;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "redelegation-delegator";
+    const VALUE_TYPE: &'static str = "address";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the source validator of a redelegation.
+pub struct RedelegationSrcValidator(pub Address);
+
+impl EventAttributeEntry<'static> for RedelegationSrcValidator {
+    type Value = Address;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "redelegation-src-validator";
+    const VALUE_TYPE: &'static str = "address";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the destination validator of a redelegation.
+pub struct RedelegationDestValidator(pub Address);
+
+impl EventAttributeEntry<'static> for RedelegationDestValidator {
+    type Value = Address;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "redelegation-dest-validator";
+    const VALUE_TYPE: &'static str = "address";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the amount of a redelegation.
+pub struct RedelegatedAmount(pub token::DenominatedAmount);
+
+impl EventAttributeEntry<'static> for RedelegatedAmount {
+    type Value = token::DenominatedAmount;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "redelegated-amount";
+    const VALUE_TYPE: &'static str = "denominated-amount";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the kind of a validator's misbehavior.
+pub struct EvidenceType(pub EvidenceKind);
+
+impl EventAttributeEntry<'static> for EvidenceType {
+    type Value = EvidenceKind;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "evidence-kind";
+    const VALUE_TYPE: &'static str = "evidence-kind";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the consensus address of a misbehaving validator.
+pub struct EvidenceConsensusAddress(pub String);
+
+impl EventAttributeEntry<'static> for EvidenceConsensusAddress {
+    type Value = String;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "evidence-consensus-address";
+    const VALUE_TYPE: &'static str = "string";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the block height at which a misbehavior occurred.
+pub struct EvidenceHeight(pub BlockHeight);
+
+impl EventAttributeEntry<'static> for EvidenceHeight {
+    type Value = BlockHeight;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "evidence-height";
+    const VALUE_TYPE: &'static str = "block-height";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the outcome of a piece of evidence.
+pub struct EvidenceResult(pub EvidenceOutcome);
+
+impl EventAttributeEntry<'static> for EvidenceResult {
+    type Value = EvidenceOutcome;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "evidence-outcome";
+    const VALUE_TYPE: &'static str = "evidence-outcome";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the address of a misbehaving validator.
+pub struct EvidenceValidator(pub Address);
+
+impl EventAttributeEntry<'static> for EvidenceValidator {
+    type Value = Address;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "evidence-validator";
+    const VALUE_TYPE: &'static str = "address";
 
     fn into_value(self) -> Self::Value {
         self.0
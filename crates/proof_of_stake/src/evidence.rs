@@ -0,0 +1,340 @@
+//! Byzantine evidence received from CometBFT.
+//!
+//! CometBFT reports the evidence of validators' misbehavior, i.e. duplicate
+//! votes and light client attacks, that has been committed in a block. Every
+//! piece of evidence is recorded in storage together with its outcome, that is
+//! whether the validator has been jailed and slashed for it or why the
+//! evidence was disregarded, and an evidence event is emitted.
+//!
+//! The records are kept until the slashes they led to have been processed and
+//! for as many epochs after that, such that clients can list the recent
+//! evidence with the resulting slashes. Txs cannot write the records, which is
+//! enforced by the PoS VP.
+
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use namada_core::address::Address;
+use namada_core::arith::checked;
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::storage::{BlockHeight, Epoch};
+use namada_core::tendermint::abci::types::MisbehaviorKind;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_storage::collections::lazy_map::{self, NestedMap};
+use namada_storage::collections::{LazyCollection, LazyVec};
+use namada_storage::{StorageRead, StorageWrite};
+
+use crate::storage::{
+    enqueued_slashes_handle, read_pos_params, validator_slashes_handle,
+};
+use crate::types::{Slash, SlashType};
+use crate::{storage_key, PosParams};
+
+/// The evidence records, keyed by the epoch in which they were received
+pub type EvidenceRecords = NestedMap<Epoch, LazyVec<EvidenceRecord>>;
+
+/// The kind of a validator's misbehavior
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+)]
+pub enum EvidenceKind {
+    /// Duplicate block vote
+    DuplicateVote,
+    /// Light client attack
+    LightClientAttack,
+    /// A kind of misbehavior that is unknown to CometBFT
+    Unknown,
+}
+
+impl EvidenceKind {
+    /// The type of slash that this kind of misbehavior leads to, if any
+    pub fn slash_type(&self) -> Option<SlashType> {
+        match self {
+            Self::DuplicateVote => Some(SlashType::DuplicateVote),
+            Self::LightClientAttack => Some(SlashType::LightClientAttack),
+            Self::Unknown => None,
+        }
+    }
+}
+
+impl From<MisbehaviorKind> for EvidenceKind {
+    fn from(kind: MisbehaviorKind) -> Self {
+        match kind {
+            MisbehaviorKind::DuplicateVote => Self::DuplicateVote,
+            MisbehaviorKind::LightClientAttack => Self::LightClientAttack,
+            MisbehaviorKind::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl Display for EvidenceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicateVote => write!(f, "duplicate-vote"),
+            Self::LightClientAttack => write!(f, "light-client-attack"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl FromStr for EvidenceKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "duplicate-vote" => Ok(Self::DuplicateVote),
+            "light-client-attack" => Ok(Self::LightClientAttack),
+            "unknown" => Ok(Self::Unknown),
+            _ => Err(format!("Unknown evidence kind {s}")),
+        }
+    }
+}
+
+/// What the protocol did with a piece of evidence
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+)]
+pub enum EvidenceOutcome {
+    /// The validator has been jailed and a slash has been enqueued, or it
+    /// had already been enqueued from the same evidence height
+    Slashed,
+    /// The evidence is too old to be processed
+    Outdated,
+    /// The epoch of the evidence height is unknown
+    UnknownEpoch,
+    /// The kind of misbehavior is unknown
+    UnknownKind,
+    /// No validator has the consensus address of the evidence
+    UnknownValidator,
+    /// Slashing the validator failed
+    Failed,
+}
+
+impl Display for EvidenceOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Slashed => write!(f, "slashed"),
+            Self::Outdated => write!(f, "outdated"),
+            Self::UnknownEpoch => write!(f, "unknown-epoch"),
+            Self::UnknownKind => write!(f, "unknown-kind"),
+            Self::UnknownValidator => write!(f, "unknown-validator"),
+            Self::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl FromStr for EvidenceOutcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "slashed" => Ok(Self::Slashed),
+            "outdated" => Ok(Self::Outdated),
+            "unknown-epoch" => Ok(Self::UnknownEpoch),
+            "unknown-kind" => Ok(Self::UnknownKind),
+            "unknown-validator" => Ok(Self::UnknownValidator),
+            "failed" => Ok(Self::Failed),
+            _ => Err(format!("Unknown evidence outcome {s}")),
+        }
+    }
+}
+
+/// A piece of evidence received from CometBFT
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+)]
+pub struct EvidenceRecord {
+    /// The kind of misbehavior
+    pub kind: EvidenceKind,
+    /// The consensus address of the misbehaving validator, i.e. the
+    /// hex-encoded raw hash of its consensus key
+    pub consensus_address: String,
+    /// The address of the misbehaving validator, if known
+    pub validator: Option<Address>,
+    /// The block height at which the misbehavior occurred
+    pub evidence_height: BlockHeight,
+    /// The epoch at which the misbehavior occurred, if known
+    pub evidence_epoch: Option<Epoch>,
+    /// The height of the block in which the evidence was committed
+    pub height: BlockHeight,
+    /// What the protocol did with the evidence
+    pub outcome: EvidenceOutcome,
+}
+
+/// A recent piece of evidence, with the slash that resulted from it
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+)]
+pub struct EvidenceInfo {
+    /// The epoch in which the evidence was received
+    pub epoch: Epoch,
+    /// The evidence
+    pub record: EvidenceRecord,
+    /// The resulting slash, if any. Its rate is zero until the slash has been
+    /// processed.
+    pub slash: Option<Slash>,
+}
+
+/// Get the storage handle to the evidence records
+pub fn evidence_records_handle() -> EvidenceRecords {
+    EvidenceRecords::open(storage_key::evidence_records_key())
+}
+
+/// Record a piece of evidence received in the given epoch.
+pub fn record_evidence<S>(
+    storage: &mut S,
+    epoch: Epoch,
+    record: EvidenceRecord,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    evidence_records_handle().at(&epoch).push(storage, record)
+}
+
+/// Remove the evidence records whose slashes have been processed at least
+/// `slash_processing_epoch_offset` epochs ago.
+pub fn prune_evidence_records<S>(
+    storage: &mut S,
+    params: &PosParams,
+    current_epoch: Epoch,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let retention = checked!(2 * params.slash_processing_epoch_offset())?;
+    let Some(oldest_kept) = current_epoch.checked_sub(retention) else {
+        return Ok(());
+    };
+    let handle = evidence_records_handle();
+    let pruned_epochs = read_evidence_epochs(storage)?
+        .into_iter()
+        .filter(|epoch| *epoch < oldest_kept);
+    for epoch in pruned_epochs {
+        handle.remove_all(storage, &epoch)?;
+    }
+    Ok(())
+}
+
+/// Find the slash that resulted from a piece of evidence, either processed or
+/// still enqueued.
+pub fn find_evidence_slash<S>(
+    storage: &S,
+    params: &PosParams,
+    record: &EvidenceRecord,
+) -> namada_storage::Result<Option<Slash>>
+where
+    S: StorageRead,
+{
+    let (
+        EvidenceOutcome::Slashed,
+        Some(validator),
+        Some(evidence_epoch),
+        Some(slash_type),
+    ) = (
+        record.outcome,
+        record.validator.as_ref(),
+        record.evidence_epoch,
+        record.kind.slash_type(),
+    )
+    else {
+        return Ok(None);
+    };
+    let evidence_height = record.evidence_height.0;
+    for slash in validator_slashes_handle(validator).iter(storage)? {
+        let slash = slash?;
+        if slash.block_height == evidence_height && slash.r#type == slash_type {
+            return Ok(Some(slash));
+        }
+    }
+    let processing_epoch =
+        checked!(evidence_epoch + params.slash_processing_epoch_offset())?;
+    enqueued_slashes_handle()
+        .get_data_handler()
+        .at(&processing_epoch)
+        .at(validator)
+        .get(storage, &evidence_height)
+}
+
+/// Read the evidence received since the given epoch, or all the evidence
+/// that is still recorded, with the resulting slashes. The evidence is sorted
+/// by the order in which it was received.
+pub fn read_recent_evidence<S>(
+    storage: &S,
+    since: Option<Epoch>,
+) -> namada_storage::Result<Vec<EvidenceInfo>>
+where
+    S: StorageRead,
+{
+    let params = read_pos_params(storage)?;
+    let since = since.unwrap_or_default();
+    let handle = evidence_records_handle();
+    let mut evidence = Vec::new();
+    for epoch in read_evidence_epochs(storage)?.range(since..) {
+        for record in handle.at(epoch).iter(storage)? {
+            let record = record?;
+            let slash = find_evidence_slash(storage, &params, &record)?;
+            evidence.push(EvidenceInfo {
+                epoch: *epoch,
+                record,
+                slash,
+            });
+        }
+    }
+    Ok(evidence)
+}
+
+/// Read the epochs in which evidence has been received and is still recorded
+fn read_evidence_epochs<S>(
+    storage: &S,
+) -> namada_storage::Result<BTreeSet<Epoch>>
+where
+    S: StorageRead,
+{
+    evidence_records_handle()
+        .iter(storage)?
+        .map(|entry| {
+            let (
+                lazy_map::NestedSubKey::Data {
+                    key: epoch,
+                    nested_sub_key: _,
+                },
+                _record,
+            ) = entry?;
+            Ok(epoch)
+        })
+        .collect()
+}
@@ -6,8 +6,14 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![deny(rustdoc::private_intra_doc_links)]
 
+pub mod auto_redelegation;
+pub mod block_seed;
+pub mod bond_receipt;
+pub mod delegation_pool;
 pub mod epoched;
 pub mod event;
+pub mod evidence;
+pub mod operational_metadata;
 pub mod parameters;
 pub mod pos_queries;
 pub mod queries;
@@ -15,6 +21,7 @@ pub mod rewards;
 pub mod slashing;
 pub mod storage;
 pub mod storage_key;
+pub mod telemetry;
 pub mod types;
 pub mod validator_set_update;
 // pub mod validation;
@@ -39,12 +46,13 @@ pub use namada_core::storage::{Epoch, Key, KeySeg};
 use namada_core::tendermint::abci::types::Misbehavior;
 use namada_events::EmitEvents;
 use namada_storage::collections::lazy_map::{self, Collectable, LazyMap};
-use namada_storage::{OptionExt, StorageRead, StorageWrite};
+use namada_storage::{OptionExt, StorageBatch, StorageRead, StorageWrite};
 pub use namada_trans_token as token;
 pub use parameters::{OwnedPosParams, PosParams};
 use storage::write_validator_name;
 use types::{into_tm_voting_power, DelegationEpochs};
 
+use crate::event::PosEvent;
 use crate::queries::{find_bonds, has_bonds};
 use crate::rewards::{
     add_rewards_to_counter, compute_current_rewards_from_bonds,
@@ -60,11 +68,12 @@ use crate::storage::{
     delegator_redelegated_bonds_handle, delegator_redelegated_unbonds_handle,
     get_last_reward_claim_epoch, liveness_missed_votes_handle,
     liveness_sum_missed_votes_handle, read_consensus_validator_set_addresses,
-    read_non_pos_owned_params, read_pos_params,
+    read_non_pos_owned_params, read_pos_params, read_total_stake,
     read_validator_last_slash_epoch, read_validator_max_commission_rate_change,
-    read_validator_stake, total_bonded_handle, total_consensus_stake_handle,
-    total_unbonded_handle, try_insert_consensus_key, unbond_handle,
-    update_total_deltas, update_validator_deltas, validator_addresses_handle,
+    read_validator_metadata, read_validator_stake, total_bonded_handle,
+    total_consensus_stake_handle, total_unbonded_handle,
+    try_insert_consensus_key, unbond_handle, update_total_deltas,
+    update_validator_deltas, validator_addresses_handle,
     validator_commission_rate_handle, validator_consensus_key_handle,
     validator_deltas_handle, validator_eth_cold_key_handle,
     validator_eth_hot_key_handle, validator_incoming_redelegations_handle,
@@ -77,14 +86,16 @@ use crate::storage::{
     write_last_staked_ratio, write_pos_params,
     write_validator_address_raw_hash, write_validator_avatar,
     write_validator_description, write_validator_discord_handle,
-    write_validator_email, write_validator_max_commission_rate_change,
-    write_validator_metadata, write_validator_website,
+    write_validator_email, write_validator_jail_record,
+    write_validator_max_commission_rate_change, write_validator_metadata,
+    write_validator_website,
 };
 use crate::storage_key::{bonds_for_source_prefix, is_bond_key};
 use crate::types::{
-    BondId, ConsensusValidator, ConsensusValidatorSet,
-    EagerRedelegatedBondsMap, RedelegatedBondsOrUnbonds, RedelegatedTokens,
-    ResultSlashing, Slash, Unbonds, ValidatorMetaData, ValidatorSetUpdate,
+    BondId, CommissionSchedule, ConsensusValidator, ConsensusValidatorSet,
+    EagerRedelegatedBondsMap, JailReason, JailRecord,
+    RedelegatedBondsOrUnbonds, RedelegatedTokens, ResultSlashing, Slash,
+    Unbonds, ValidatorMetaData, ValidatorMetaDataField, ValidatorSetUpdate,
     ValidatorState, VoteInfo,
 };
 use crate::validator_set_update::{
@@ -276,6 +287,14 @@ where
         tracing::debug!("\nBonds after incrementing: {bonds:#?}");
     }
 
+    if params.liquid_staking_receipts {
+        let bond_id = BondId {
+            source: source.clone(),
+            validator: validator.clone(),
+        };
+        bond_receipt::mint_bond_receipts(storage, &bond_id, amount)?;
+    }
+
     // Add the validator to the delegation targets
     add_delegation_target(
         storage,
@@ -322,9 +341,100 @@ where
         !is_jailed_or_inactive_at_offset,
     )?;
 
+    // The genesis bonds, applied without the pipeline offset, are not capped as
+    // the first validators hold all the stake
+    if offset_opt.is_none() {
+        if let Some((stake, max_stake)) = find_validator_stake_over_cap(
+            storage,
+            &params,
+            validator,
+            offset_epoch,
+        )? {
+            return Err(BondError::ValidatorStakeCapExceeded(
+                validator.clone(),
+                stake.to_string_native(),
+                max_stake.to_string_native(),
+            )
+            .into());
+        }
+    }
+
     Ok(())
 }
 
+/// Find if the stake of a validator at the given epoch exceeds the cap of its
+/// share of the total stake, if any. Returns the stake of the validator and
+/// its max stake if it does.
+pub fn find_validator_stake_over_cap<S>(
+    storage: &S,
+    params: &PosParams,
+    validator: &Address,
+    epoch: Epoch,
+) -> namada_storage::Result<Option<(token::Amount, token::Amount)>>
+where
+    S: StorageRead,
+{
+    let Some(share) = params.max_validator_stake_share else {
+        return Ok(None);
+    };
+    let total_stake = read_total_stake(storage, params, epoch)?;
+    let max_stake = total_stake.mul_floor(share)?;
+    let stake = read_validator_stake(storage, params, validator, epoch)?;
+    Ok((stake > max_stake).then_some((stake, max_stake)))
+}
+
+/// Get the amount of tokens that can still be bonded to a validator at the
+/// given epoch before its stake exceeds the cap of its share of the total
+/// stake. Returns `None` if the stake of the validators is not capped.
+pub fn validator_remaining_capacity<S>(
+    storage: &S,
+    params: &PosParams,
+    validator: &Address,
+    epoch: Epoch,
+) -> namada_storage::Result<Option<token::Amount>>
+where
+    S: StorageRead,
+{
+    let Some(share) = params.max_validator_stake_share else {
+        return Ok(None);
+    };
+    if share >= Dec::one() {
+        return Ok(None);
+    }
+    let total_stake = read_total_stake(storage, params, epoch)?;
+    let max_stake = total_stake.mul_floor(share)?;
+    let stake = read_validator_stake(storage, params, validator, epoch)?;
+    let Some(headroom) = max_stake.checked_sub(stake) else {
+        return Ok(Some(token::Amount::zero()));
+    };
+    // A bond adds to both the stake of the validator and the total stake, so
+    // the max bond is `(share * total_stake - stake) / (1 - share)`
+    let bond_factor = checked!(Dec::one() / (Dec::one() - share))?;
+    Ok(Some(headroom.mul_floor(bond_factor)?))
+}
+
+/// Get the amount of tokens that can still be redelegated to a validator at
+/// the given epoch before its stake exceeds the cap of its share of the total
+/// stake, which redelegations don't change. Returns `None` if the stake of the
+/// validators is not capped.
+pub fn validator_redelegation_capacity<S>(
+    storage: &S,
+    params: &PosParams,
+    validator: &Address,
+    epoch: Epoch,
+) -> namada_storage::Result<Option<token::Amount>>
+where
+    S: StorageRead,
+{
+    let Some(share) = params.max_validator_stake_share else {
+        return Ok(None);
+    };
+    let total_stake = read_total_stake(storage, params, epoch)?;
+    let max_stake = total_stake.mul_floor(share)?;
+    let stake = read_validator_stake(storage, params, validator, epoch)?;
+    Ok(Some(max_stake.checked_sub(stake).unwrap_or_default()))
+}
+
 /// Compute total validator stake for the current epoch
 fn compute_total_consensus_stake<S>(
     storage: &S,
@@ -380,6 +490,10 @@ struct BondAndUnbondUpdates {
 ///
 /// This fn is also called during redelegation for a source validator, in
 /// which case the `is_redelegation` param must be true.
+///
+/// The bond receipts of the unbonded tokens, if any, are burned from the
+/// source. Use [`unbond_tokens_with_redeemer`] to burn them from another
+/// account.
 pub fn unbond_tokens<S>(
     storage: &mut S,
     source: Option<&Address>,
@@ -388,6 +502,33 @@ pub fn unbond_tokens<S>(
     current_epoch: Epoch,
     is_redelegation: bool,
 ) -> namada_storage::Result<ResultSlashing>
+where
+    S: StorageRead + StorageWrite,
+{
+    unbond_tokens_with_redeemer(
+        storage,
+        source,
+        validator,
+        amount,
+        current_epoch,
+        is_redelegation,
+        None,
+    )
+}
+
+/// Unbond tokens that are bonded between a validator and a source (self or
+/// delegator), burning the bond receipts of the unbonded tokens, if any, from
+/// the `redeemer`. The receipts are burned from the source when the
+/// `redeemer` is `None`.
+pub fn unbond_tokens_with_redeemer<S>(
+    storage: &mut S,
+    source: Option<&Address>,
+    validator: &Address,
+    amount: token::Amount,
+    current_epoch: Epoch,
+    is_redelegation: bool,
+    redeemer: Option<&Address>,
+) -> namada_storage::Result<ResultSlashing>
 where
     S: StorageRead + StorageWrite,
 {
@@ -438,6 +579,26 @@ where
         .into());
     }
 
+    // Burn the receipts of the unbonded tokens, if any
+    let bond_id = BondId {
+        source: source.clone(),
+        validator: validator.clone(),
+    };
+    let receipts_to_burn = bond_receipt::bond_receipts_to_burn(
+        storage,
+        &params,
+        &bond_id,
+        amount,
+        current_epoch,
+    )?;
+    let redeemer = redeemer.unwrap_or(source);
+    bond_receipt::burn_bond_receipts(
+        storage,
+        &bond_id,
+        redeemer,
+        receipts_to_burn,
+    )?;
+
     if tracing::level_enabled!(tracing::Level::DEBUG) {
         let bonds = find_bonds(storage, source, validator)?;
         tracing::debug!("\nBonds before decrementing: {bonds:#?}");
@@ -1299,6 +1460,22 @@ where
         ));
     }
 
+    if commission_rate < params.min_commission_rate {
+        return Err(BecomeValidatorError::CommissionRateTooLow(
+            commission_rate,
+            params.min_commission_rate,
+        )
+        .into());
+    }
+    let missing_metadata =
+        metadata.missing_fields(&params.required_validator_metadata);
+    if !missing_metadata.is_empty() {
+        return Err(BecomeValidatorError::MissingMetadata(join_fields(
+            &missing_metadata,
+        ))
+        .into());
+    }
+
     // This will fail if the key is already being used
     try_insert_consensus_key(storage, consensus_key)?;
 
@@ -1413,6 +1590,46 @@ where
     Ok(())
 }
 
+/// Emit an event for every validator whose new consensus key comes into effect
+/// in the current epoch, at the first block of the epoch
+fn emit_consensus_key_activations<S>(
+    storage: &S,
+    events: &mut impl EmitEvents,
+    params: &PosParams,
+    current_epoch: Epoch,
+    height: BlockHeight,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead,
+{
+    let Some(prev_epoch) = current_epoch.prev() else {
+        return Ok(());
+    };
+    // Sort the validators for a deterministic order of the events
+    let validators: BTreeSet<Address> =
+        storage::read_all_validator_addresses(storage, current_epoch)?
+            .into_iter()
+            .collect();
+    for validator in validators {
+        let handle = validator_consensus_key_handle(&validator);
+        let consensus_key = handle.get(storage, current_epoch, params)?;
+        let prev_consensus_key = handle.get(storage, prev_epoch, params)?;
+        if let (Some(consensus_key), Some(prev_consensus_key)) =
+            (consensus_key, prev_consensus_key)
+        {
+            if consensus_key != prev_consensus_key {
+                events.emit(PosEvent::ConsensusKeyActivated {
+                    validator,
+                    consensus_key,
+                    epoch: current_epoch,
+                    height,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Withdraw tokens from those that have been unbonded from proof-of-stake
 pub fn withdraw_tokens<S>(
     storage: &mut S,
@@ -1561,6 +1778,38 @@ pub fn change_validator_commission_rate<S>(
 ) -> namada_storage::Result<()>
 where
     S: StorageRead + StorageWrite,
+{
+    if validate_commission_rate_change(
+        storage,
+        validator,
+        new_rate,
+        current_epoch,
+    )? {
+        let params = read_pos_params(storage)?;
+        validator_commission_rate_handle(validator).set(
+            storage,
+            new_rate,
+            current_epoch,
+            params.pipeline_len,
+        )?;
+    }
+    Ok(())
+}
+
+/// Check that a validator's commission rate can be changed to the given rate
+/// in the current epoch. The change is applied at the pipeline epoch and it
+/// must not differ from the rate in the epoch before by more than the
+/// validator's max commission rate change per epoch. Returns `false` if the
+/// new rate is already set at the pipeline epoch, in which case there is
+/// nothing to change.
+pub fn validate_commission_rate_change<S>(
+    storage: &S,
+    validator: &Address,
+    new_rate: Dec,
+    current_epoch: Epoch,
+) -> namada_storage::Result<bool>
+where
+    S: StorageRead,
 {
     if new_rate.is_negative() {
         return Err(CommissionRateChangeError::NegativeRate(
@@ -1578,13 +1827,22 @@ where
         .into());
     }
 
+    let params = read_pos_params(storage)?;
+    if new_rate < params.min_commission_rate {
+        return Err(CommissionRateChangeError::LowerThanMinimum(
+            new_rate,
+            params.min_commission_rate,
+            validator.clone(),
+        )
+        .into());
+    }
+
     let max_change =
         read_validator_max_commission_rate_change(storage, validator)?
             .ok_or_else(|| {
                 CommissionRateChangeError::NoMaxSetInStorage(validator.clone())
             })?;
 
-    let params = read_pos_params(storage)?;
     let commission_handle = validator_commission_rate_handle(validator);
     let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
 
@@ -1592,7 +1850,7 @@ where
         .get(storage, pipeline_epoch, &params)?
         .expect("Could not find a rate in given epoch");
     if new_rate == rate_at_pipeline {
-        return Ok(());
+        return Ok(false);
     }
     let rate_before_pipeline = commission_handle
         .get(
@@ -1611,7 +1869,45 @@ where
         .into());
     }
 
-    commission_handle.set(storage, new_rate, current_epoch, params.pipeline_len)
+    Ok(true)
+}
+
+/// Read the commission rate of a validator in the current epoch together with
+/// the rates already scheduled for the following epochs up to the pipeline
+/// epoch. Returns `None` if the address is not a validator.
+pub fn read_validator_commission_schedule<S>(
+    storage: &S,
+    params: &PosParams,
+    validator: &Address,
+    current_epoch: Epoch,
+) -> namada_storage::Result<Option<CommissionSchedule>>
+where
+    S: StorageRead,
+{
+    let Some(max_commission_change_per_epoch) =
+        read_validator_max_commission_rate_change(storage, validator)?
+    else {
+        return Ok(None);
+    };
+    let commission_handle = validator_commission_rate_handle(validator);
+    let Some(current_rate) =
+        commission_handle.get(storage, current_epoch, params)?
+    else {
+        return Ok(None);
+    };
+    let mut pending_rates = BTreeMap::new();
+    for offset in 1..=params.pipeline_len {
+        let epoch = checked!(current_epoch + offset)?;
+        if let Some(rate) = commission_handle.get(storage, epoch, params)? {
+            pending_rates.insert(epoch, rate);
+        }
+    }
+    Ok(Some(CommissionSchedule {
+        epoch: current_epoch,
+        current_rate,
+        pending_rates,
+        max_commission_change_per_epoch,
+    }))
 }
 
 fn bond_amounts_for_query<S>(
@@ -2114,32 +2410,29 @@ where
 
     let params = read_pos_params(storage)?;
     let pipeline_epoch = checked!(current_epoch + params.pipeline_len)?;
-    let src_redel_end_epoch =
-        validator_incoming_redelegations_handle(src_validator)
-            .get(storage, delegator)?;
-
-    // Forbid chained redelegations. A redelegation is "chained" if:
-    // 1. the source validator holds bonded tokens that themselves were
-    // redelegated to the src validator
-    // 2. given the latest epoch at which the most recently redelegated tokens
-    // started contributing to the src validator's voting power, these tokens
-    // cannot be slashed anymore
-    let is_not_chained = if let Some(end_epoch) = src_redel_end_epoch {
-        let last_contrib_epoch =
-            end_epoch.prev().expect("End epoch cannot be 0");
-        // If the source validator's slashes that would cause slash on
-        // redelegation are now outdated (would have to be processed before or
-        // on start of the current epoch), the redelegation can be redelegated
-        // again
-        checked!(last_contrib_epoch + params.slash_processing_epoch_offset())?
-            <= current_epoch
-    } else {
-        true
-    };
-    if !is_not_chained {
+    if is_chained_redelegation(
+        storage,
+        &params,
+        delegator,
+        src_validator,
+        current_epoch,
+    )? {
         return Err(RedelegationError::IsChainedRedelegation.into());
     }
 
+    // The receipts burned when unbonding from the src validator are re-issued
+    // for the dest validator
+    let src_receipts = bond_receipt::bond_receipts_to_burn(
+        storage,
+        &params,
+        &BondId {
+            source: delegator.clone(),
+            validator: src_validator.clone(),
+        },
+        amount,
+        current_epoch,
+    )?;
+
     // Unbond the redelegated tokens from the src validator.
     // `resultUnbond` in quint
     let result_unbond = unbond_tokens(
@@ -2204,6 +2497,17 @@ where
         tracing::debug!("\nRedeleg dest bonds after incrementing: {bonds:#?}");
     }
 
+    // Re-issue the burned receipts for the dest validator. A slashed amount
+    // is not re-issued, so the slashing is borne by the receipt holder.
+    bond_receipt::mint_bond_receipts(
+        storage,
+        &BondId {
+            source: delegator.clone(),
+            validator: dest_validator.clone(),
+        },
+        std::cmp::min(src_receipts, amount_after_slashing),
+    )?;
+
     // Add outgoing redelegation to the src validator.
     // `updateOutgoingRedelegations` with `updatedSrcValidator`
     let outgoing_redelegations =
@@ -2292,9 +2596,58 @@ where
         !is_jailed_or_inactive_at_pipeline,
     )?;
 
+    if let Some((stake, max_stake)) = find_validator_stake_over_cap(
+        storage,
+        &params,
+        dest_validator,
+        pipeline_epoch,
+    )? {
+        return Err(RedelegationError::ValidatorStakeCapExceeded(
+            dest_validator.clone(),
+            stake.to_string_native(),
+            max_stake.to_string_native(),
+        )
+        .into());
+    }
+
     Ok(())
 }
 
+/// Check if a redelegation of the delegator's bonds from the source validator
+/// would be chained, which is forbidden. A redelegation is "chained" if:
+/// 1. the source validator holds bonded tokens that themselves were
+/// redelegated to the src validator
+/// 2. given the latest epoch at which the most recently redelegated tokens
+/// started contributing to the src validator's voting power, these tokens
+/// can still be slashed
+pub(crate) fn is_chained_redelegation<S>(
+    storage: &S,
+    params: &PosParams,
+    delegator: &Address,
+    src_validator: &Address,
+    current_epoch: Epoch,
+) -> namada_storage::Result<bool>
+where
+    S: StorageRead,
+{
+    let src_redel_end_epoch =
+        validator_incoming_redelegations_handle(src_validator)
+            .get(storage, delegator)?;
+    if let Some(end_epoch) = src_redel_end_epoch {
+        let last_contrib_epoch =
+            end_epoch.prev().expect("End epoch cannot be 0");
+        // If the source validator's slashes that would cause slash on
+        // redelegation are now outdated (would have to be processed before or
+        // on start of the current epoch), the redelegation can be redelegated
+        // again
+        Ok(checked!(
+            last_contrib_epoch + params.slash_processing_epoch_offset()
+        )? > current_epoch)
+    } else {
+        Ok(false)
+    }
+}
+
 /// Deactivate a validator by removing it from any validator sets. A validator
 /// can only be deactivated if it is not jailed or already inactive.
 pub fn deactivate_validator<S>(
@@ -2584,14 +2937,14 @@ where
             // Check if validator failed to match the threshold and jail
             // them
             if missed_votes >= missing_votes_threshold {
-                Some(address)
+                Some((address, missed_votes))
             } else {
                 None
             }
         })
-        .collect::<BTreeSet<_>>();
+        .collect::<BTreeMap<_, _>>();
 
-    for validator in &validators_to_jail {
+    for (validator, missed_votes) in &validators_to_jail {
         let state_jail_epoch = validator_state_handle(validator)
             .get(storage, jail_epoch, params)?
             .expect("Validator should have a state for the jail epoch");
@@ -2604,7 +2957,16 @@ where
             validator,
             jail_epoch,
         );
-        jail_validator(storage, params, validator, current_epoch, jail_epoch)?;
+        jail_validator(
+            storage,
+            params,
+            validator,
+            current_epoch,
+            jail_epoch,
+            JailReason::Downtime {
+                missed_votes: *missed_votes,
+            },
+        )?;
     }
 
     Ok(())
@@ -2718,6 +3080,12 @@ pub mod test_utils {
             fee_unshielding_descriptions_limit: 15,
             minimum_gas_price: BTreeMap::new(),
             is_native_token_transferable: true,
+            max_epoch_hooks_gas: 0,
+            epoch_hook_allowlist: vec![],
+            target_block_gas: 0,
+            base_fee_max_change_rate: Dec::zero(),
+            masp_convert_anchor_grace_blocks: 0,
+            masp_max_note_age: 0,
         };
         init_storage(&chain_parameters, storage).unwrap();
         init_genesis_helper(storage, &params, validators, current_epoch)?;
@@ -2762,6 +3130,7 @@ where
     if let Some(name) = name {
         write_validator_name(storage, validator, &name)?;
     }
+    validate_validator_metadata(storage, validator)?;
     if let Some(commission_rate) = commission_rate {
         change_validator_commission_rate(
             storage,
@@ -2773,6 +3142,37 @@ where
     Ok(())
 }
 
+/// Check that a validator has set all the metadata fields required by the PoS
+/// parameters
+pub fn validate_validator_metadata<S>(
+    storage: &S,
+    validator: &Address,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead,
+{
+    let params = read_pos_params(storage)?;
+    let missing = read_validator_metadata(storage, validator)?
+        .map(|metadata| {
+            metadata.missing_fields(&params.required_validator_metadata)
+        })
+        .unwrap_or_default();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(MetadataError::MissingRequired(join_fields(&missing)).into())
+    }
+}
+
+/// Join the names of some metadata fields for display
+fn join_fields(fields: &[ValidatorMetaDataField]) -> String {
+    fields
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Claim available rewards, triggering an immediate transfer of tokens from the
 /// PoS account to the source address.
 pub fn claim_reward_tokens<S>(
@@ -2838,13 +3238,14 @@ where
 
 /// Jail a validator by removing it from and updating the validator sets and
 /// changing a its state to `Jailed`. Validators are jailed for liveness and for
-/// misbehaving.
+/// misbehaving. The reason is recorded in the validator's jail history.
 pub fn jail_validator<S>(
     storage: &mut S,
     params: &PosParams,
     validator: &Address,
     current_epoch: Epoch,
     validator_set_update_epoch: Epoch,
+    reason: JailReason,
 ) -> namada_storage::Result<()>
 where
     S: StorageRead + StorageWrite,
@@ -2928,7 +3329,15 @@ where
             offset,
         )?;
     }
-    Ok(())
+
+    write_validator_jail_record(
+        storage,
+        validator,
+        JailRecord {
+            epoch: validator_set_update_epoch,
+            reason,
+        },
+    )
 }
 
 /// Apply PoS updates for a block
@@ -2941,7 +3350,7 @@ pub fn finalize_block<S>(
     byzantine_validators: Vec<Misbehavior>,
 ) -> namada_storage::Result<()>
 where
-    S: StorageWrite + StorageRead,
+    S: StorageWrite + StorageRead + StorageBatch,
 {
     let height = storage.get_block_height()?;
     let current_epoch = storage.get_block_epoch()?;
@@ -2960,6 +3369,14 @@ where
         // Compute the total stake of the consensus validator set and record
         // it in storage
         compute_and_store_total_consensus_stake(storage, current_epoch)?;
+
+        emit_consensus_key_activations(
+            storage,
+            events,
+            &pos_params,
+            current_epoch,
+            height,
+        )?;
     }
 
     // Invariant: Has to be applied before `record_slashes_from_evidence`
@@ -2979,9 +3396,11 @@ where
     // `copy_validator_sets_and_positions` and before `self.update_epoch`.
     slashing::record_slashes_from_evidence(
         storage,
+        events,
         byzantine_validators,
         &pos_params,
         current_epoch,
+        height,
         validator_set_update_epoch,
     )?;
 
@@ -3035,6 +3454,23 @@ where
         // Prune liveness data from validators that are no longer in the
         // consensus set
         prune_liveness_data(storage, current_epoch)?;
+
+        // Prune the records of the evidence whose slashes have been
+        // processed long enough ago
+        evidence::prune_evidence_records(storage, &pos_params, current_epoch)?;
+
+        // Rebalance the bonds of the delegation pools' members, after the
+        // slashes have been processed
+        delegation_pool::rebalance_delegation_pools(storage, current_epoch)?;
+
+        // Redelegate the bonds away from the validators jailed for long
+        // enough, for the delegators that opted in
+        auto_redelegation::process_auto_redelegations(
+            storage,
+            events,
+            current_epoch,
+            height,
+        )?;
     }
 
     Ok(())
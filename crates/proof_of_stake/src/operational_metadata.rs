@@ -0,0 +1,220 @@
+//! Validators' operational metadata.
+//!
+//! Besides the metadata set with transactions (email, website, etc.), a
+//! validator may publish information about how to reach its node operators
+//! and infrastructure during incidents: a public RPC endpoint, the address of
+//! a seed node and an emergency contact. This metadata is published with a
+//! protocol tx signed by the validator's protocol key, so that it can be
+//! updated by the node operators without the validator account's keys.
+//!
+//! An update replaces all the previously published fields. It must be made in
+//! the current epoch and carry the validator's next update nonce, which is
+//! incremented by every applied update, so that an update cannot be replayed.
+//! Txs cannot write the operational metadata nor its nonce, which is enforced
+//! by the PoS VP.
+
+use namada_core::address::Address;
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::key::common;
+use namada_core::storage::Epoch;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_storage::{StorageRead, StorageWrite};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::{read_pos_params, validator_protocol_key_handle};
+use crate::{is_validator, storage_key, OperationalMetadataError};
+
+/// The maximum length of a field of the operational metadata, in bytes
+pub const MAX_OPERATIONAL_METADATA_FIELD_LEN: usize = 256;
+
+/// The operational metadata of a validator
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct OperationalMetadata {
+    /// The URL of a public RPC endpoint of the validator's infrastructure
+    pub rpc_endpoint: Option<String>,
+    /// The address of a seed node, in the `<node-id>@<host>:<port>` format
+    pub seed_node: Option<String>,
+    /// A contact for emergencies, such as an email or a chat handle
+    pub emergency_contact: Option<String>,
+}
+
+impl OperationalMetadata {
+    /// Check if none of the fields are set
+    pub fn is_empty(&self) -> bool {
+        self.rpc_endpoint.is_none()
+            && self.seed_node.is_none()
+            && self.emergency_contact.is_none()
+    }
+}
+
+/// The data of the protocol tx publishing a validator's operational metadata
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+)]
+pub struct OperationalMetadataUpdate {
+    /// The validator
+    pub validator: Address,
+    /// The epoch in which the update is published
+    pub epoch: Epoch,
+    /// The nonce of the validator's next update
+    pub nonce: u64,
+    /// The new metadata. Empty metadata removes the published one.
+    pub metadata: OperationalMetadata,
+}
+
+/// Read the operational metadata published by a validator, if any.
+pub fn read_operational_metadata<S>(
+    storage: &S,
+    validator: &Address,
+) -> namada_storage::Result<Option<OperationalMetadata>>
+where
+    S: StorageRead,
+{
+    storage.read(&storage_key::validator_operational_metadata_key(validator))
+}
+
+/// Read the nonce of the next operational metadata update of a validator.
+pub fn read_operational_metadata_nonce<S>(
+    storage: &S,
+    validator: &Address,
+) -> namada_storage::Result<u64>
+where
+    S: StorageRead,
+{
+    Ok(storage
+        .read(&storage_key::validator_operational_metadata_nonce_key(
+            validator,
+        ))?
+        .unwrap_or_default())
+}
+
+/// Check that the nonce of an operational metadata update is the next one of
+/// the validator.
+fn check_operational_metadata_nonce<S>(
+    storage: &S,
+    update: &OperationalMetadataUpdate,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead,
+{
+    let expected = read_operational_metadata_nonce(storage, &update.validator)?;
+    if update.nonce != expected {
+        return Err(OperationalMetadataError::WrongNonce {
+            expected,
+            got: update.nonce,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Check that an operational metadata update is for the current epoch with the
+/// validator's next nonce, that its fields are within the length limit and
+/// that it has been signed by the protocol key of the validator.
+pub fn validate_operational_metadata_update<S>(
+    storage: &S,
+    update: &OperationalMetadataUpdate,
+    signer: &common::PublicKey,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead,
+{
+    let OperationalMetadataUpdate {
+        validator,
+        epoch,
+        nonce: _,
+        metadata,
+    } = update;
+    let current_epoch = storage.get_block_epoch()?;
+    if *epoch != current_epoch {
+        return Err(OperationalMetadataError::WrongEpoch {
+            expected: current_epoch,
+            got: *epoch,
+        }
+        .into());
+    }
+    check_operational_metadata_nonce(storage, update)?;
+    let fields = [
+        &metadata.rpc_endpoint,
+        &metadata.seed_node,
+        &metadata.emergency_contact,
+    ];
+    if fields.into_iter().flatten().any(|field| {
+        field.is_empty() || field.len() > MAX_OPERATIONAL_METADATA_FIELD_LEN
+    }) {
+        return Err(OperationalMetadataError::InvalidField.into());
+    }
+    if !is_validator(storage, validator)? {
+        return Err(
+            OperationalMetadataError::NotAValidator(validator.clone()).into()
+        );
+    }
+    let params = read_pos_params(storage)?;
+    let protocol_pk = validator_protocol_key_handle(validator)
+        .get(storage, current_epoch, &params)?
+        .ok_or_else(|| {
+            OperationalMetadataError::MissingProtocolKey(
+                validator.clone(),
+                current_epoch,
+            )
+        })?;
+    if protocol_pk != *signer {
+        return Err(
+            OperationalMetadataError::WrongSigner(validator.clone()).into()
+        );
+    }
+    Ok(())
+}
+
+/// Write the operational metadata of a validator, or remove it if the update
+/// is empty, and increment the validator's nonce. The update must have been
+/// validated beforehand, but its nonce is checked again to reject a replay of
+/// an update in the same block.
+pub fn write_operational_metadata<S>(
+    storage: &mut S,
+    update: &OperationalMetadataUpdate,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    check_operational_metadata_nonce(storage, update)?;
+    let next_nonce = update.nonce.checked_add(1).ok_or_else(|| {
+        namada_storage::Error::new_const(
+            "The operational metadata nonce overflowed",
+        )
+    })?;
+    storage.write(
+        &storage_key::validator_operational_metadata_nonce_key(
+            &update.validator,
+        ),
+        next_nonce,
+    )?;
+    let key =
+        storage_key::validator_operational_metadata_key(&update.validator);
+    if update.metadata.is_empty() {
+        storage.delete(&key)
+    } else {
+        storage.write(&key, &update.metadata)
+    }
+}
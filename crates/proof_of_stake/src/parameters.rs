@@ -1,5 +1,6 @@
 //! Proof-of-Stake system parameters
 
+use std::collections::BTreeSet;
 use std::str::FromStr;
 
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -14,6 +15,8 @@ use namada_macros::BorshDeserializer;
 use namada_migrations::*;
 use thiserror::Error;
 
+use crate::types::ValidatorMetaDataField;
+
 /// Proof-of-Stake system parameters. This includes parameters that are used in
 /// PoS but are read from other accounts storage (governance).
 #[derive(Debug, Clone, BorshDeserialize, BorshDeserializer, BorshSerialize)]
@@ -74,6 +77,16 @@ pub struct OwnedPosParams {
     pub rewards_gain_p: Dec,
     /// PoS gain d (read only)
     pub rewards_gain_d: Dec,
+    /// Whether bonding mints transferable receipt tokens of the bonds, which
+    /// have to be burned to unbond
+    pub liquid_staking_receipts: bool,
+    /// The minimum commission rate of the validators
+    pub min_commission_rate: Dec,
+    /// The metadata fields that the validators must set, besides their email
+    pub required_validator_metadata: BTreeSet<ValidatorMetaDataField>,
+    /// The max share of the total stake that a validator may receive from
+    /// bonds and redelegations, if any
+    pub max_validator_stake_share: Option<Dec>,
 }
 
 impl Default for PosParams {
@@ -113,6 +126,10 @@ impl Default for OwnedPosParams {
             liveness_threshold: Dec::new(9, 1).expect("Test failed"),
             rewards_gain_p: Dec::from_str("0.25").expect("Test failed"),
             rewards_gain_d: Dec::from_str("0.25").expect("Test failed"),
+            liquid_staking_receipts: false,
+            min_commission_rate: Dec::zero(),
+            required_validator_metadata: BTreeSet::new(),
+            max_validator_stake_share: None,
         }
     }
 }
@@ -136,6 +153,10 @@ pub enum ValidationError {
          pipeline: {1}"
     )]
     UnbondingLenTooShort(u64, u64),
+    #[error("Minimum commission rate must be between 0 and 1, got {0}")]
+    MinCommissionRateOutOfRange(Dec),
+    #[error("Max validator stake share must be > 0 and <= 1, got {0}")]
+    MaxValidatorStakeShareOutOfRange(Dec),
 }
 
 /// The maximum string length of any validator metadata
@@ -205,6 +226,22 @@ impl OwnedPosParams {
             ))
         }
 
+        if self.min_commission_rate.is_negative()
+            || self.min_commission_rate > Dec::one()
+        {
+            errors.push(ValidationError::MinCommissionRateOutOfRange(
+                self.min_commission_rate,
+            ))
+        }
+
+        if let Some(share) = self.max_validator_stake_share {
+            if share <= Dec::zero() || share > Dec::one() {
+                errors.push(ValidationError::MaxValidatorStakeShareOutOfRange(
+                    share,
+                ))
+            }
+        }
+
         errors
     }
 
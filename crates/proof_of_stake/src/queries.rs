@@ -7,6 +7,7 @@ use borsh::BorshDeserialize;
 use namada_core::address::Address;
 use namada_core::collections::{HashMap, HashSet};
 use namada_core::dec::Dec;
+use namada_core::key::common;
 use namada_core::storage::Epoch;
 use namada_core::token;
 use namada_storage::collections::lazy_map::{NestedSubKey, SubKey};
@@ -15,6 +16,7 @@ use namada_storage::StorageRead;
 use crate::slashing::{find_validator_slashes, get_slashed_amount};
 use crate::storage::{
     bond_handle, delegation_targets_handle, read_pos_params, unbond_handle,
+    validator_consensus_key_handle,
 };
 use crate::types::{
     BondDetails, BondId, BondsAndUnbondsDetail, BondsAndUnbondsDetails,
@@ -22,6 +24,31 @@ use crate::types::{
 };
 use crate::{raw_bond_amount, storage_key, PosParams};
 
+/// Find the latest change of a validator's consensus key that is not in effect
+/// in the current epoch yet. Returns the new key and the epoch from which it
+/// is used for consensus.
+pub fn find_pending_consensus_key<S>(
+    storage: &S,
+    validator: &Address,
+    current_epoch: Epoch,
+) -> namada_storage::Result<Option<(common::PublicKey, Epoch)>>
+where
+    S: StorageRead,
+{
+    let params = read_pos_params(storage)?;
+    let handle = validator_consensus_key_handle(validator);
+    let mut prev_key = handle.get(storage, current_epoch, &params)?;
+    let mut pending = None;
+    for epoch in current_epoch.next().iter_range(params.pipeline_len) {
+        let key = handle.get(storage, epoch, &params)?;
+        if key != prev_key {
+            pending = key.clone().map(|key| (key, epoch));
+        }
+        prev_key = key;
+    }
+    Ok(pending)
+}
+
 /// Find all validators to which a given bond `owner` (or source) has a
 /// delegation
 pub fn find_delegation_validators<S>(
@@ -8,7 +8,10 @@ use namada_core::dec::Dec;
 use namada_core::storage::{BlockHeight, Epoch};
 use namada_core::token::{self, Amount};
 use namada_core::uint::{Uint, I256};
-use namada_parameters::storage as params_storage;
+use namada_events::EmitEvents;
+use namada_parameters::{
+    read_current_protocol_version, storage as params_storage,
+};
 use namada_storage::collections::lazy_map::NestedSubKey;
 use namada_storage::{ResultExt, StorageRead, StorageWrite};
 use namada_trans_token::get_effective_total_native_supply;
@@ -23,7 +26,10 @@ use crate::storage::{
     write_last_staked_ratio,
 };
 use crate::token::credit_tokens;
-use crate::types::{into_tm_voting_power, BondId, ValidatorState, VoteInfo};
+use crate::types::{
+    into_tm_voting_power, BondId, ValidatorRewardsEstimate, ValidatorState,
+    VoteInfo,
+};
 use crate::{
     bond_amounts_for_rewards, get_total_consensus_stake, staking_token_address,
     storage, storage_key, InflationError, PosParams,
@@ -67,6 +73,7 @@ pub fn compute_inflation(
     epochs_per_year: u64,
     target_ratio: Dec,
     last_ratio: Dec,
+    protocol_version: u64,
 ) -> namada_storage::Result<token::Amount> {
     let controller = PDController::new(
         total_native_amount.into(),
@@ -86,11 +93,108 @@ pub fn compute_inflation(
         total_native_dec * max_reward_rate / controller.get_epochs_per_year()
     )?;
     let amount_uint = controller
-        .compute_inflation(control_coeff, metric)
+        .compute_inflation(control_coeff, metric, protocol_version)
         .into_storage_result()?;
     token::Amount::from_uint(amount_uint, 0).into_storage_result()
 }
 
+/// Estimate the annualized rewards rates of a validator from the PoS inflation
+/// that the PD controller would compute for the current state. The rewards of
+/// the consensus validators are assumed to be proportional to their stake,
+/// which is the case when all of them are signing blocks. Returns `None` if
+/// the address is not a validator.
+pub fn estimate_validator_rewards_rate<S>(
+    storage: &S,
+    validator: &Address,
+    current_epoch: Epoch,
+) -> namada_storage::Result<Option<ValidatorRewardsEstimate>>
+where
+    S: StorageRead,
+{
+    let params = read_pos_params(storage)?;
+    let Some(commission_rate) = validator_commission_rate_handle(validator)
+        .get(storage, current_epoch, &params)?
+    else {
+        return Ok(None);
+    };
+    let is_consensus = validator_state_handle(validator).get(
+        storage,
+        current_epoch,
+        &params,
+    )? == Some(ValidatorState::Consensus);
+
+    let epochs_per_year: u64 = storage
+        .read(&params_storage::get_epochs_per_year_key())?
+        .ok_or_else(|| {
+            namada_storage::Error::new_const(
+                "Epochs per year should exist in parameters storage",
+            )
+        })?;
+    let total_tokens = get_effective_total_native_supply(storage)?;
+    let locked_amount = read_total_stake(storage, &params, current_epoch)?;
+    let last_staked_ratio =
+        read_last_staked_ratio(storage)?.ok_or_else(|| {
+            namada_storage::Error::new_const(
+                "Last staked ratio should exist in PoS storage",
+            )
+        })?;
+    let last_inflation_amount = read_last_pos_inflation_amount(storage)?
+        .ok_or_else(|| {
+            namada_storage::Error::new_const(
+                "Last inflation amount should exist in PoS storage",
+            )
+        })?;
+    let protocol_version = read_current_protocol_version(storage)?;
+
+    let epoch_inflation = compute_inflation(
+        locked_amount,
+        total_tokens,
+        params.max_inflation_rate,
+        last_inflation_amount,
+        params.rewards_gain_p,
+        params.rewards_gain_d,
+        epochs_per_year,
+        params.target_staked_ratio,
+        last_staked_ratio,
+        protocol_version,
+    )?;
+
+    let total_tokens = Dec::try_from(total_tokens).into_storage_result()?;
+    let locked_amount = Dec::try_from(locked_amount).into_storage_result()?;
+    let staked_ratio = if total_tokens.is_zero() {
+        Dec::zero()
+    } else {
+        checked!(locked_amount / total_tokens)?
+    };
+
+    let consensus_stake = Dec::try_from(get_total_consensus_stake(
+        storage,
+        current_epoch,
+        &params,
+    )?)
+    .into_storage_result()?;
+    let gross_rate = if is_consensus && !consensus_stake.is_zero() {
+        let epoch_inflation =
+            Dec::try_from(epoch_inflation).into_storage_result()?;
+        let annual_inflation =
+            checked!(epoch_inflation * Dec::from(epochs_per_year))?;
+        checked!(annual_inflation / consensus_stake)?
+    } else {
+        Dec::zero()
+    };
+    let delegator_rate = checked!(gross_rate * (Dec::one() - commission_rate))?;
+
+    Ok(Some(ValidatorRewardsEstimate {
+        epoch: current_epoch,
+        epoch_inflation,
+        staked_ratio,
+        is_consensus,
+        gross_rate,
+        commission_rate,
+        delegator_rate,
+    }))
+}
+
 /// Holds coefficients for the three different ways to get PoS rewards
 #[derive(Debug, Copy, Clone)]
 #[allow(missing_docs)]
@@ -359,6 +463,7 @@ where
 /// Apply inflation to the Proof of Stake system.
 pub fn apply_inflation<S>(
     storage: &mut S,
+    events: &mut impl EmitEvents,
     last_epoch: Epoch,
     num_blocks_in_last_epoch: u64,
 ) -> namada_storage::Result<()>
@@ -386,6 +491,7 @@ where
     let max_inflation_rate = params.max_inflation_rate;
     let p_gain_nom = params.rewards_gain_p;
     let d_gain_nom = params.rewards_gain_d;
+    let protocol_version = read_current_protocol_version(storage)?;
 
     // Compute the new inflation
     let inflation = compute_inflation(
@@ -398,12 +504,14 @@ where
         epochs_per_year,
         locked_ratio_target,
         last_staked_ratio,
+        protocol_version,
     )?;
 
     // Mint inflation and partition rewards among all accounts that earn a
     // portion of it
     update_rewards_products_and_mint_inflation(
         storage,
+        events,
         &params,
         last_epoch,
         num_blocks_in_last_epoch,
@@ -436,6 +544,7 @@ struct Rewards {
 /// rewards is given to the governance address.
 pub fn update_rewards_products_and_mint_inflation<S>(
     storage: &mut S,
+    events: &mut impl EmitEvents,
     params: &PosParams,
     last_epoch: Epoch,
     num_blocks_in_last_epoch: u64,
@@ -485,6 +594,10 @@ where
         // instead of `reward_tokens`
         let commissions = reward_tokens.mul_floor(commission_rate)?;
 
+        events.emit(PosEvent::Rewards {
+            validator: validator.clone(),
+            amount: reward_tokens,
+        });
         new_rewards_products.insert(
             validator,
             Rewards {
@@ -653,6 +766,8 @@ where
 mod tests {
     use std::str::FromStr;
 
+    use namada_controller::EXACT_INFLATION_PROTOCOL_VERSION;
+
     use super::*;
 
     #[test]
@@ -676,6 +791,7 @@ mod tests {
             epochs_per_year,
             target_ratio,
             Dec::from_str("0.5").unwrap(),
+            EXACT_INFLATION_PROTOCOL_VERSION,
         )
         .unwrap();
         let locked_ratio_0 = Dec::try_from(locked_amount).unwrap()
@@ -701,6 +817,7 @@ mod tests {
             epochs_per_year,
             target_ratio,
             last_locked_ratio,
+            EXACT_INFLATION_PROTOCOL_VERSION,
         )
         .unwrap();
 
@@ -731,6 +848,7 @@ mod tests {
             epochs_per_year,
             target_ratio,
             last_locked_ratio,
+            EXACT_INFLATION_PROTOCOL_VERSION,
         )
         .unwrap();
 
@@ -742,7 +860,7 @@ mod tests {
         assert!(locked_ratio_2 > locked_ratio_1);
         assert!(locked_ratio_2 > Dec::from_str("0.5").unwrap());
         assert!(locked_ratio_2 < Dec::from_str("0.51").unwrap());
-        assert_eq!(inflation_2, token::Amount::from_u64(54794017950));
+        assert_eq!(inflation_2, token::Amount::from_u64(54794017949));
     }
 
     #[test]
@@ -766,6 +884,7 @@ mod tests {
             epochs_per_year,
             target_ratio,
             Dec::from_str("0.9").unwrap(),
+            EXACT_INFLATION_PROTOCOL_VERSION,
         )
         .unwrap();
         let locked_ratio_0 = Dec::try_from(locked_amount).unwrap()
@@ -791,6 +910,7 @@ mod tests {
             epochs_per_year,
             target_ratio,
             last_locked_ratio,
+            EXACT_INFLATION_PROTOCOL_VERSION,
         )
         .unwrap();
 
@@ -821,6 +941,7 @@ mod tests {
             epochs_per_year,
             target_ratio,
             last_locked_ratio,
+            EXACT_INFLATION_PROTOCOL_VERSION,
         )
         .unwrap();
 
@@ -870,6 +991,7 @@ mod tests {
                 epochs_per_year,
                 target_ratio,
                 last_locked_ratio,
+                EXACT_INFLATION_PROTOCOL_VERSION,
             )
             .unwrap();
             let locked_ratio = Dec::try_from(locked_amount).unwrap()
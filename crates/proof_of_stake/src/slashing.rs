@@ -10,7 +10,7 @@ use namada_core::collections::HashMap;
 use namada_core::dec::Dec;
 use namada_core::key::tm_raw_hash_to_string;
 use namada_core::storage::{BlockHeight, Epoch};
-use namada_core::tendermint::abci::types::{Misbehavior, MisbehaviorKind};
+use namada_core::tendermint::abci::types::Misbehavior;
 use namada_core::token;
 use namada_events::EmitEvents;
 use namada_storage::collections::lazy_map::{
@@ -20,6 +20,7 @@ use namada_storage::collections::LazyMap;
 use namada_storage::{OptionExt, ResultExt, StorageRead, StorageWrite};
 
 use crate::event::PosEvent;
+use crate::evidence::{self, EvidenceOutcome, EvidenceRecord};
 use crate::storage::{
     enqueued_slashes_handle, read_pos_params, read_validator_last_slash_epoch,
     read_validator_stake, total_bonded_handle, total_unbonded_handle,
@@ -30,8 +31,8 @@ use crate::storage::{
     write_validator_last_slash_epoch,
 };
 use crate::types::{
-    EagerRedelegatedBondsMap, ResultSlashing, Slash, SlashType, SlashedAmount,
-    Slashes, TotalRedelegatedUnbonded, ValidatorState,
+    EagerRedelegatedBondsMap, JailReason, ResultSlashing, Slash, SlashType,
+    SlashedAmount, Slashes, TotalRedelegatedUnbonded, ValidatorState,
 };
 use crate::validator_set_update::update_validator_set;
 use crate::{
@@ -40,12 +41,16 @@ use crate::{
     FoldRedelegatedBondsResult, OwnedPosParams, PosParams,
 };
 
-/// Apply PoS slashes from the evidence
+/// Apply PoS slashes from the evidence. Every piece of evidence is recorded
+/// with its outcome and an evidence event is emitted for it.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn record_slashes_from_evidence<S>(
     storage: &mut S,
+    events: &mut impl EmitEvents,
     byzantine_validators: Vec<Misbehavior>,
     pos_params: &PosParams,
     current_epoch: Epoch,
+    height: BlockHeight,
     validator_set_update_epoch: Epoch,
 ) -> namada_storage::Result<()>
 where
@@ -56,85 +61,109 @@ where
         for evidence in byzantine_validators {
             // dbg!(&evidence);
             tracing::info!("Processing evidence {evidence:?}.");
-            let evidence_height = u64::from(evidence.height);
-            let evidence_epoch =
-                match pred_epochs.get_epoch(BlockHeight(evidence_height)) {
-                    Some(epoch) => epoch,
-                    None => {
-                        tracing::error!(
-                            "Couldn't find epoch for evidence block height {}",
-                            evidence_height
-                        );
-                        continue;
-                    }
-                };
-            // Disregard evidences that should have already been processed
-            // at this time
-            if checked!(
-                evidence_epoch + pos_params.slash_processing_epoch_offset()
-                    - pos_params.cubic_slashing_window_length
-            )? <= current_epoch
-            {
-                tracing::info!(
-                    "Skipping outdated evidence from epoch {evidence_epoch}"
-                );
-                continue;
-            }
-            let slash_type = match evidence.kind {
-                MisbehaviorKind::DuplicateVote => {
-                    types::SlashType::DuplicateVote
-                }
-                MisbehaviorKind::LightClientAttack => {
-                    types::SlashType::LightClientAttack
-                }
-                MisbehaviorKind::Unknown => {
-                    tracing::error!("Unknown evidence: {:#?}", evidence);
-                    continue;
-                }
-            };
-            let validator_raw_hash =
+            let evidence_height = BlockHeight(u64::from(evidence.height));
+            let consensus_address =
                 tm_raw_hash_to_string(evidence.validator.address);
-            let validator = match storage::find_validator_by_raw_hash(
+            let validator = storage::find_validator_by_raw_hash(
                 storage,
-                &validator_raw_hash,
-            )? {
-                Some(validator) => validator,
-                None => {
-                    tracing::error!(
-                        "Cannot find validator's address from raw hash {}",
-                        validator_raw_hash
-                    );
-                    continue;
-                }
-            };
-            // Check if we're gonna switch to a new epoch after a delay
-            tracing::info!(
-                "Slashing {} for {} in epoch {}, block height {} (current \
-                 epoch = {}, validator set update epoch = \
-                 {validator_set_update_epoch})",
+                &consensus_address,
+            )?;
+            let mut record = EvidenceRecord {
+                kind: evidence.kind.into(),
+                consensus_address,
                 validator,
-                slash_type,
-                evidence_epoch,
                 evidence_height,
-                current_epoch
-            );
-            if let Err(err) = slash(
+                evidence_epoch: pred_epochs.get_epoch(evidence_height),
+                height,
+                outcome: EvidenceOutcome::Slashed,
+            };
+            record.outcome = slash_from_evidence(
                 storage,
+                &record,
                 pos_params,
                 current_epoch,
-                evidence_epoch,
-                evidence_height,
-                slash_type,
-                &validator,
                 validator_set_update_epoch,
-            ) {
-                tracing::error!("Error in slashing: {}", err);
-            }
+            )?;
+            events.emit(PosEvent::Evidence {
+                record: record.clone(),
+            });
+            evidence::record_evidence(storage, current_epoch, record)?;
         }
     }
     Ok(())
 }
 
+/// Jail the validator of a piece of evidence and enqueue its slash, unless
+/// the evidence must be disregarded. Returns the outcome of the evidence.
+fn slash_from_evidence<S>(
+    storage: &mut S,
+    record: &EvidenceRecord,
+    pos_params: &PosParams,
+    current_epoch: Epoch,
+    validator_set_update_epoch: Epoch,
+) -> namada_storage::Result<EvidenceOutcome>
+where
+    S: StorageWrite + StorageRead,
+{
+    let evidence_height = record.evidence_height;
+    let Some(evidence_epoch) = record.evidence_epoch else {
+        tracing::error!(
+            "Couldn't find epoch for evidence block height {}",
+            evidence_height
+        );
+        return Ok(EvidenceOutcome::UnknownEpoch);
+    };
+    // Disregard evidences that should have already been processed
+    // at this time
+    if checked!(
+        evidence_epoch + pos_params.slash_processing_epoch_offset()
+            - pos_params.cubic_slashing_window_length
+    )? <= current_epoch
+    {
+        tracing::info!(
+            "Skipping outdated evidence from epoch {evidence_epoch}"
+        );
+        return Ok(EvidenceOutcome::Outdated);
+    }
+    let Some(slash_type) = record.kind.slash_type() else {
+        tracing::error!("Unknown evidence: {:#?}", record);
+        return Ok(EvidenceOutcome::UnknownKind);
+    };
+    let Some(validator) = record.validator.as_ref() else {
+        tracing::error!(
+            "Cannot find validator's address from raw hash {}",
+            record.consensus_address
+        );
+        return Ok(EvidenceOutcome::UnknownValidator);
+    };
+    // Check if we're gonna switch to a new epoch after a delay
+    tracing::info!(
+        "Slashing {} for {} in epoch {}, block height {} (current epoch = {}, \
+         validator set update epoch = {validator_set_update_epoch})",
+        validator,
+        slash_type,
+        evidence_epoch,
+        evidence_height,
+        current_epoch
+    );
+    match slash(
+        storage,
+        pos_params,
+        current_epoch,
+        evidence_epoch,
+        evidence_height,
+        slash_type,
+        validator,
+        validator_set_update_epoch,
+    ) {
+        Ok(()) => Ok(EvidenceOutcome::Slashed),
+        Err(err) => {
+            tracing::error!("Error in slashing: {}", err);
+            Ok(EvidenceOutcome::Failed)
+        }
+    }
+}
+
 /// Record a slash for a misbehavior that has been received from Tendermint and
 /// then jail the validator, removing it from the validator set. The slash rate
 /// will be computed at a later epoch.
@@ -190,6 +219,11 @@ where
         validator,
         current_epoch,
         validator_set_update_epoch,
+        JailReason::Misbehavior {
+            slash_type,
+            evidence_epoch,
+            evidence_height: evidence_block_height,
+        },
     )?;
 
     // No other actions are performed here until the epoch in which the slash is
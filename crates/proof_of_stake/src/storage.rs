@@ -21,17 +21,20 @@ use crate::types::{
     BelowCapacityValidatorSets, BondId, Bonds, CommissionRates,
     ConsensusValidatorSets, DelegationTargets, DelegatorRedelegatedBonded,
     DelegatorRedelegatedUnbonded, EpochedSlashes, IncomingRedelegations,
-    LivenessMissedVotes, LivenessSumMissedVotes, OutgoingRedelegations,
-    ReverseOrdTokenAmount, RewardsAccumulator, RewardsProducts, Slashes,
-    TotalConsensusStakes, TotalDeltas, TotalRedelegatedBonded,
-    TotalRedelegatedUnbonded, Unbonds, ValidatorAddresses,
-    ValidatorConsensusKeys, ValidatorDeltas, ValidatorEthColdKeys,
-    ValidatorEthHotKeys, ValidatorMetaData, ValidatorProtocolKeys,
-    ValidatorSetPositions, ValidatorState, ValidatorStates,
-    ValidatorTotalUnbonded, WeightedValidator,
+    JailRecord, LivenessMissedVotes, LivenessSumMissedVotes,
+    OutgoingRedelegations, ReverseOrdTokenAmount, RewardsAccumulator,
+    RewardsProducts, Slashes, TotalConsensusStakes, TotalDeltas,
+    TotalRedelegatedBonded, TotalRedelegatedUnbonded, Unbonds,
+    ValidatorAddresses, ValidatorConsensusKeys, ValidatorDeltas,
+    ValidatorEthColdKeys, ValidatorEthHotKeys, ValidatorMetaData,
+    ValidatorProtocolKeys, ValidatorSetPositions, ValidatorState,
+    ValidatorStates, ValidatorTotalUnbonded, WeightedValidator,
 };
 use crate::{storage_key, MetadataError, OwnedPosParams, PosParams};
 
+/// The maximum number of records kept in the jail history of a validator
+pub const MAX_VALIDATOR_JAIL_HISTORY_LEN: usize = 10;
+
 // ---- Storage handles ----
 
 /// Get the storage handle to the epoched consensus validator set
@@ -377,6 +380,39 @@ where
     storage.write(&key, epoch)
 }
 
+/// Read the most recent records of the given validator being jailed, from the
+/// oldest to the newest.
+pub fn read_validator_jail_history<S>(
+    storage: &S,
+    validator: &Address,
+) -> namada_storage::Result<Vec<JailRecord>>
+where
+    S: StorageRead,
+{
+    let key = storage_key::validator_jail_history_key(validator);
+    Ok(storage.read(&key)?.unwrap_or_default())
+}
+
+/// Record the given validator being jailed. Only the most recent
+/// [`MAX_VALIDATOR_JAIL_HISTORY_LEN`] records are kept.
+pub fn write_validator_jail_record<S>(
+    storage: &mut S,
+    validator: &Address,
+    record: JailRecord,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let mut history = read_validator_jail_history(storage, validator)?;
+    history.push(record);
+    if history.len() > MAX_VALIDATOR_JAIL_HISTORY_LEN {
+        let excess = history.len() - MAX_VALIDATOR_JAIL_HISTORY_LEN;
+        history.drain(..excess);
+    }
+    let key = storage_key::validator_jail_history_key(validator);
+    storage.write(&key, history)
+}
+
 /// Read last block proposer address.
 pub fn read_last_block_proposer_address<S>(
     storage: &S,
@@ -954,6 +990,28 @@ where
     Ok(())
 }
 
+/// Read validator's metadata. Returns `None` if the validator has no email,
+/// the only required field in storage.
+pub fn read_validator_metadata<S>(
+    storage: &S,
+    validator: &Address,
+) -> namada_storage::Result<Option<ValidatorMetaData>>
+where
+    S: StorageRead,
+{
+    let Some(email) = read_validator_email(storage, validator)? else {
+        return Ok(None);
+    };
+    Ok(Some(ValidatorMetaData {
+        email,
+        description: read_validator_description(storage, validator)?,
+        website: read_validator_website(storage, validator)?,
+        discord_handle: read_validator_discord_handle(storage, validator)?,
+        avatar: read_validator_avatar(storage, validator)?,
+        name: read_validator_name(storage, validator)?,
+    }))
+}
+
 /// Get the last epoch in which rewards were claimed from storage, if any
 pub fn get_last_reward_claim_epoch<S>(
     storage: &S,
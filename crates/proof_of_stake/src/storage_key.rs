@@ -27,8 +27,10 @@ const VALIDATOR_LAST_KNOWN_PRODUCT_EPOCH_KEY: &str =
 const SLASHES_PREFIX: &str = "slash";
 const ENQUEUED_SLASHES_KEY: &str = "enqueued_slashes";
 const VALIDATOR_LAST_SLASH_EPOCH: &str = "last_slash_epoch";
+const VALIDATOR_JAIL_HISTORY: &str = "jail_history";
 const BOND_STORAGE_KEY: &str = "bond";
 const UNBOND_STORAGE_KEY: &str = "unbond";
+const BOND_RECEIPTS_STORAGE_KEY: &str = "bond_receipts";
 const VALIDATOR_TOTAL_BONDED_STORAGE_KEY: &str = "total_bonded";
 const VALIDATOR_TOTAL_UNBONDED_STORAGE_KEY: &str = "total_unbonded";
 const VALIDATOR_SETS_STORAGE_PREFIX: &str = "validator_sets";
@@ -56,13 +58,25 @@ const VALIDATOR_WEBSITE_KEY: &str = "website";
 const VALIDATOR_DISCORD_KEY: &str = "discord_handle";
 const VALIDATOR_AVATAR_KEY: &str = "avatar";
 const VALIDATOR_NAME_KEY: &str = "name";
+const VALIDATOR_OPERATIONAL_METADATA_KEY: &str = "operational_metadata";
+const VALIDATOR_OPERATIONAL_METADATA_NONCE_KEY: &str =
+    "operational_metadata_nonce";
+const VALIDATOR_TELEMETRY_KEY: &str = "telemetry";
 const LIVENESS_PREFIX: &str = "liveness";
 const LIVENESS_MISSED_VOTES: &str = "missed_votes";
 const LIVENESS_MISSED_VOTES_SUM: &str = "sum_missed_votes";
 const LAST_STAKED_RATIO_KEY: &str = "last_staked_ratio";
 const LAST_POS_INFLATION_AMOUNT_KEY: &str = "last_inflation_amount";
 const TOTAL_ACTIVE_DELTAS_KEY: &str = "total_active_deltas";
+const BLOCK_SEED_KEY: &str = "block_seed";
 const DELEGATION_TARGETS_PREFIX: &str = "delegation_targets";
+const DELEGATION_POOLS_KEY: &str = "delegation_pools";
+const DELEGATION_POOL_MEMBERS_KEY: &str = "delegation_pool_members";
+const DELEGATION_POOL_REBALANCE_CURSOR_KEY: &str =
+    "delegation_pool_rebalance_cursor";
+const AUTO_REDELEGATIONS_KEY: &str = "auto_redelegations";
+const AUTO_REDELEGATION_CURSOR_KEY: &str = "auto_redelegation_cursor";
+const EVIDENCE_RECORDS_KEY: &str = "evidence_records";
 
 /// Is the given key a PoS storage key?
 pub fn is_pos_key(key: &Key) -> bool {
@@ -640,6 +654,27 @@ pub fn validator_last_slash_key(validator: &Address) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Storage key for the most recent records of a validator being jailed
+pub fn validator_jail_history_key(validator: &Address) -> Key {
+    validator_prefix(validator)
+        .push(&VALIDATOR_JAIL_HISTORY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for a validator's jail history?
+pub fn is_validator_jail_history_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::AddressSeg(validator), DbKeySeg::StringSeg(history)]
+            if addr == &ADDRESS
+                && prefix == VALIDATOR_STORAGE_PREFIX
+                && history == VALIDATOR_JAIL_HISTORY =>
+        {
+            Some(validator)
+        }
+        _ => None,
+    }
+}
+
 /// Storage key prefix for all bonds.
 pub fn bonds_prefix() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -722,6 +757,34 @@ pub fn is_bond_epoched_meta_key(key: &Key) -> Option<BondId> {
     }
 }
 
+/// Storage key for the amount of outstanding receipt tokens of a bond with the
+/// given ID (source and validator).
+pub fn bond_receipts_key(bond_id: &BondId) -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&BOND_RECEIPTS_STORAGE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&bond_id.source.to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&bond_id.validator.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for the outstanding receipt tokens of a bond? Returns the
+/// bond ID if so.
+pub fn is_bond_receipts_key(key: &Key) -> Option<BondId> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::AddressSeg(source), DbKeySeg::AddressSeg(validator)]
+            if addr == &ADDRESS && prefix == BOND_RECEIPTS_STORAGE_KEY =>
+        {
+            Some(BondId {
+                source: source.clone(),
+                validator: validator.clone(),
+            })
+        }
+        _ => None,
+    }
+}
+
 /// Storage key for the total bonds for a given validator.
 pub fn validator_total_bonded_key(validator: &Address) -> Key {
     validator_prefix(validator)
@@ -1037,6 +1100,81 @@ pub fn validator_name_key(validator: &Address) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Storage key for a validator's operational metadata
+pub fn validator_operational_metadata_key(validator: &Address) -> Key {
+    validator_prefix(validator)
+        .push(&VALIDATOR_OPERATIONAL_METADATA_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for a validator's operational metadata?
+pub fn is_validator_operational_metadata_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(validator),
+            DbKeySeg::StringSeg(metadata),
+        ] if addr == &ADDRESS
+            && prefix == VALIDATOR_STORAGE_PREFIX
+            && metadata == VALIDATOR_OPERATIONAL_METADATA_KEY =>
+        {
+            Some(validator)
+        }
+        _ => None,
+    }
+}
+
+/// Storage key for the nonce of a validator's next operational metadata
+/// update
+pub fn validator_operational_metadata_nonce_key(validator: &Address) -> Key {
+    validator_prefix(validator)
+        .push(&VALIDATOR_OPERATIONAL_METADATA_NONCE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for the nonce of a validator's next operational metadata
+/// update?
+pub fn is_validator_operational_metadata_nonce_key(
+    key: &Key,
+) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(validator),
+            DbKeySeg::StringSeg(nonce),
+        ] if addr == &ADDRESS
+            && prefix == VALIDATOR_STORAGE_PREFIX
+            && nonce == VALIDATOR_OPERATIONAL_METADATA_NONCE_KEY =>
+        {
+            Some(validator)
+        }
+        _ => None,
+    }
+}
+
+/// Storage key for a validator's last telemetry heartbeat
+pub fn validator_telemetry_key(validator: &Address) -> Key {
+    validator_prefix(validator)
+        .push(&VALIDATOR_TELEMETRY_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for a validator's last telemetry heartbeat?
+pub fn is_validator_telemetry_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::AddressSeg(validator), DbKeySeg::StringSeg(telemetry)]
+            if addr == &ADDRESS
+                && prefix == VALIDATOR_STORAGE_PREFIX
+                && telemetry == VALIDATOR_TELEMETRY_KEY =>
+        {
+            Some(validator)
+        }
+        _ => None,
+    }
+}
+
 /// Storage prefix for the liveness data of the cosnensus validator set.
 pub fn liveness_data_prefix() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -1072,6 +1210,18 @@ pub fn last_pos_inflation_amount_key() -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Storage key for the seed of the current block.
+pub fn block_seed_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&BLOCK_SEED_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for the seed of the current block?
+pub fn is_block_seed_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(key)] if addr == &ADDRESS && key == BLOCK_SEED_KEY)
+}
+
 /// Storage key for total active deltas (Consensus, Below-Capacity, and
 /// Below-threshold validators).
 pub fn total_active_deltas_key() -> Key {
@@ -1123,3 +1273,109 @@ pub fn is_delegation_targets_key(key: &Key) -> bool {
         false
     }
 }
+
+/// Storage key for the delegation pools, keyed by their names.
+pub fn delegation_pools_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&DELEGATION_POOLS_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for a delegation pool? Returns the name of the pool.
+pub fn is_delegation_pool_key(key: &Key) -> Option<&String> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(data), DbKeySeg::StringSeg(name)]
+            if addr == &ADDRESS
+                && prefix == DELEGATION_POOLS_KEY
+                && data == lazy_map::DATA_SUBKEY =>
+        {
+            Some(name)
+        }
+        _ => None,
+    }
+}
+
+/// Storage key for the members of the delegation pools, mapped to the names
+/// of their pools.
+pub fn delegation_pool_members_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&DELEGATION_POOL_MEMBERS_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the position of the last delegation pool member whose bonds
+/// were rebalanced.
+pub fn delegation_pool_rebalance_cursor_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&DELEGATION_POOL_REBALANCE_CURSOR_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for the position of the last delegation pool member whose
+/// bonds were rebalanced?
+pub fn is_delegation_pool_rebalance_cursor_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(key)] if addr == &ADDRESS && key == DELEGATION_POOL_REBALANCE_CURSOR_KEY)
+}
+
+/// Is storage key for the membership of a delegator in a delegation pool?
+/// Returns the address of the delegator.
+pub fn is_delegation_pool_member_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(data), DbKeySeg::AddressSeg(delegator)]
+            if addr == &ADDRESS
+                && prefix == DELEGATION_POOL_MEMBERS_KEY
+                && data == lazy_map::DATA_SUBKEY =>
+        {
+            Some(delegator)
+        }
+        _ => None,
+    }
+}
+
+/// Storage key for the automatic redelegation settings of the delegators.
+pub fn auto_redelegations_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&AUTO_REDELEGATIONS_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for the automatic redelegation settings of a delegator?
+/// Returns the address of the delegator.
+pub fn is_auto_redelegation_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(data), DbKeySeg::AddressSeg(delegator)]
+            if addr == &ADDRESS
+                && prefix == AUTO_REDELEGATIONS_KEY
+                && data == lazy_map::DATA_SUBKEY =>
+        {
+            Some(delegator)
+        }
+        _ => None,
+    }
+}
+
+/// Storage key for the position of the last delegator whose bonds were
+/// automatically redelegated.
+pub fn auto_redelegation_cursor_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&AUTO_REDELEGATION_CURSOR_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for the position of the last delegator whose bonds were
+/// automatically redelegated?
+pub fn is_auto_redelegation_cursor_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(key)] if addr == &ADDRESS && key == AUTO_REDELEGATION_CURSOR_KEY)
+}
+
+/// Storage key for the records of the evidence received from CometBFT.
+pub fn evidence_records_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&EVIDENCE_RECORDS_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for the records of the evidence received from CometBFT?
+pub fn is_evidence_records_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), ..] if addr == &ADDRESS && prefix == EVIDENCE_RECORDS_KEY)
+}
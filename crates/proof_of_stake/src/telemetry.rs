@@ -0,0 +1,258 @@
+//! Validators' telemetry heartbeats.
+//!
+//! A validator's node may opt in to periodically broadcast a heartbeat with a
+//! protocol tx signed by the validator's protocol key, reporting the version
+//! and the commit hash of the node's binary. The last heartbeat of every
+//! validator is kept in storage, which gives governance data on the upgrade
+//! readiness of the consensus validators before scheduling a halt, and tells
+//! the last block height at which a validator was seen live.
+//!
+//! A heartbeat must have been signed at least
+//! [`MIN_TELEMETRY_HEARTBEAT_INTERVAL`] blocks after the last recorded one,
+//! which prevents the replay of old heartbeats and bounds the number of
+//! heartbeats that a validator can have included in blocks, whatever the
+//! interval configured by its node. Txs cannot write the heartbeats, which is
+//! enforced by the PoS VP.
+//!
+//! Only the last heartbeat of every validator is written to storage. The
+//! aggregates of the heartbeats of the consensus validators are not stored,
+//! they are computed with [`aggregate_telemetry`] when queried.
+
+use std::collections::BTreeMap;
+
+use namada_core::address::Address;
+use namada_core::arith::checked;
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::key::common;
+use namada_core::storage::{BlockHeight, Epoch};
+use namada_core::token;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_storage::{StorageRead, StorageWrite};
+use serde::{Deserialize, Serialize};
+
+use crate::storage::{
+    read_consensus_validator_set_addresses_with_stake, read_pos_params,
+    validator_protocol_key_handle,
+};
+use crate::{is_validator, storage_key, TelemetryError};
+
+/// The maximum length of a field of a telemetry heartbeat, in bytes
+pub const MAX_TELEMETRY_FIELD_LEN: usize = 128;
+
+/// The minimum number of blocks between two heartbeats of a validator
+pub const MIN_TELEMETRY_HEARTBEAT_INTERVAL: u64 = 100;
+
+/// The telemetry reported by a validator's last heartbeat
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct ValidatorTelemetry {
+    /// The version of the validator node's binary
+    pub binary_version: String,
+    /// The commit hash from which the validator node's binary was built
+    pub commit_hash: String,
+    /// The block height at which the heartbeat was signed
+    pub block_height: BlockHeight,
+}
+
+/// The telemetry of the consensus validators running the same binary
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct BinaryTelemetry {
+    /// The version of the binary
+    pub binary_version: String,
+    /// The commit hash from which the binary was built
+    pub commit_hash: String,
+    /// The number of consensus validators whose last heartbeat reported this
+    /// binary
+    pub validators: u64,
+    /// The total stake of these validators
+    pub stake: token::Amount,
+}
+
+/// The telemetry of the consensus validator set in an epoch
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct TelemetrySummary {
+    /// The epoch of the consensus validator set
+    pub epoch: Epoch,
+    /// The total stake of the consensus validators
+    pub total_stake: token::Amount,
+    /// The binaries reported by the consensus validators, sorted by
+    /// descending stake
+    pub binaries: Vec<BinaryTelemetry>,
+    /// The total stake of the consensus validators that haven't sent any
+    /// heartbeat
+    pub unreported_stake: token::Amount,
+}
+
+/// Read the telemetry of a validator's last heartbeat, if any.
+pub fn read_validator_telemetry<S>(
+    storage: &S,
+    validator: &Address,
+) -> namada_storage::Result<Option<ValidatorTelemetry>>
+where
+    S: StorageRead,
+{
+    storage.read(&storage_key::validator_telemetry_key(validator))
+}
+
+/// Check that a telemetry heartbeat has been signed after the validator's
+/// last heartbeat and not after the current block height, at least
+/// [`MIN_TELEMETRY_HEARTBEAT_INTERVAL`] blocks after the last heartbeat if
+/// any, that its fields are within the length limit and that it has been
+/// signed by the protocol key of the validator.
+pub fn validate_telemetry_heartbeat<S>(
+    storage: &S,
+    validator: &Address,
+    telemetry: &ValidatorTelemetry,
+    signer: &common::PublicKey,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead,
+{
+    let current_height = storage.get_block_height()?;
+    let last_height = read_validator_telemetry(storage, validator)?
+        .map(|last| last.block_height);
+    if telemetry.block_height <= last_height.unwrap_or_default()
+        || telemetry.block_height > current_height
+    {
+        return Err(TelemetryError::InvalidHeight {
+            got: telemetry.block_height,
+            last: last_height.unwrap_or_default(),
+            current: current_height,
+        }
+        .into());
+    }
+    if let Some(last_height) = last_height {
+        let interval = telemetry.block_height.0.saturating_sub(last_height.0);
+        if interval < MIN_TELEMETRY_HEARTBEAT_INTERVAL {
+            return Err(TelemetryError::TooFrequent {
+                got: telemetry.block_height,
+                last: last_height,
+            }
+            .into());
+        }
+    }
+    if [&telemetry.binary_version, &telemetry.commit_hash]
+        .into_iter()
+        .any(|field| field.is_empty() || field.len() > MAX_TELEMETRY_FIELD_LEN)
+    {
+        return Err(TelemetryError::InvalidField.into());
+    }
+    if !is_validator(storage, validator)? {
+        return Err(TelemetryError::NotAValidator(validator.clone()).into());
+    }
+    let current_epoch = storage.get_block_epoch()?;
+    let params = read_pos_params(storage)?;
+    let protocol_pk = validator_protocol_key_handle(validator)
+        .get(storage, current_epoch, &params)?
+        .ok_or_else(|| {
+            TelemetryError::MissingProtocolKey(validator.clone(), current_epoch)
+        })?;
+    if protocol_pk != *signer {
+        return Err(TelemetryError::WrongSigner(validator.clone()).into());
+    }
+    Ok(())
+}
+
+/// Write the telemetry of a validator's last heartbeat. The heartbeat must
+/// have been validated beforehand.
+pub fn write_validator_telemetry<S>(
+    storage: &mut S,
+    validator: &Address,
+    telemetry: &ValidatorTelemetry,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    storage.write(&storage_key::validator_telemetry_key(validator), telemetry)
+}
+
+/// Aggregate the last heartbeats of the consensus validators of the given
+/// epoch by the binary they reported. The aggregates are computed from the
+/// stored heartbeats every time this is called, e.g. by the queries, and they
+/// are not written to storage.
+pub fn aggregate_telemetry<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> namada_storage::Result<TelemetrySummary>
+where
+    S: StorageRead,
+{
+    let mut total_stake = token::Amount::zero();
+    let mut unreported_stake = token::Amount::zero();
+    let mut binaries: BTreeMap<(String, String), (u64, token::Amount)> =
+        BTreeMap::new();
+    for validator in
+        read_consensus_validator_set_addresses_with_stake(storage, epoch)?
+    {
+        total_stake = checked!(total_stake + validator.bonded_stake)?;
+        match read_validator_telemetry(storage, &validator.address)? {
+            Some(ValidatorTelemetry {
+                binary_version,
+                commit_hash,
+                ..
+            }) => {
+                let (validators, stake) =
+                    binaries.entry((binary_version, commit_hash)).or_default();
+                *validators = validators.saturating_add(1);
+                *stake = checked!(*stake + validator.bonded_stake)?;
+            }
+            None => {
+                unreported_stake =
+                    checked!(unreported_stake + validator.bonded_stake)?;
+            }
+        }
+    }
+    let mut binaries: Vec<BinaryTelemetry> = binaries
+        .into_iter()
+        .map(|((binary_version, commit_hash), (validators, stake))| {
+            BinaryTelemetry {
+                binary_version,
+                commit_hash,
+                validators,
+                stake,
+            }
+        })
+        .collect();
+    binaries.sort_by(|a, b| b.stake.cmp(&a.stake));
+    Ok(TelemetrySummary {
+        epoch,
+        total_stake,
+        binaries,
+        unreported_stake,
+    })
+}
@@ -1,8 +1,15 @@
 mod helpers;
 mod state_machine;
 mod state_machine_v2;
+mod test_auto_redelegation;
+mod test_block_seed;
+mod test_bond_receipt;
+mod test_delegation_pool;
+mod test_evidence;
 mod test_helper_fns;
+mod test_operational_metadata;
 mod test_pos;
 mod test_slash_and_redel;
+mod test_telemetry;
 mod test_validator;
 mod utils;
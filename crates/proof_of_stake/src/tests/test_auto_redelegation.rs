@@ -0,0 +1,301 @@
+use std::ops::Deref;
+
+use assert_matches::assert_matches;
+use namada_core::address;
+use namada_events::Event;
+use namada_state::testing::TestState;
+use namada_state::StorageWrite;
+// Use `RUST_LOG=info` (or another tracing level) and `--nocapture` to see
+// `tracing` logs from tests
+use test_log::test;
+
+use crate::auto_redelegation::{
+    process_auto_redelegations, read_auto_redelegation,
+    update_auto_redelegation, AutoRedelegation,
+};
+use crate::delegation_pool::delegation_pool_members_handle;
+use crate::event::types::AUTO_REDELEGATION;
+use crate::storage::bond_handle;
+use crate::test_utils::test_init_genesis;
+use crate::tests::helpers::{advance_epoch, get_genesis_validators};
+use crate::token::credit_tokens;
+use crate::types::JailReason;
+use crate::{
+    bond_tokens, jail_validator, staking_token_address, token,
+    AutoRedelegationError, OwnedPosParams,
+};
+
+/// Test that only valid automatic redelegation settings can be set
+#[test]
+fn test_auto_redelegation_validation() {
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(2, vec![token::Amount::native_whole(1); 2]);
+    let validator_1 = genesis_validators[0].address.clone();
+    let validator_2 = genesis_validators[1].address.clone();
+    test_init_genesis(
+        &mut storage,
+        OwnedPosParams::default(),
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    let delegator = address::testing::established_address_1();
+    let auto_redelegation = AutoRedelegation {
+        fallback: validator_2.clone(),
+        jailed_epochs: 2,
+    };
+
+    let err = update_auto_redelegation(
+        &mut storage,
+        &validator_1,
+        Some(auto_redelegation.clone()),
+    )
+    .unwrap_err();
+    assert_matches!(
+        err.downcast::<AutoRedelegationError>().unwrap().deref(),
+        AutoRedelegationError::DelegatorIsValidator(_)
+    );
+
+    let err = update_auto_redelegation(
+        &mut storage,
+        &delegator,
+        Some(AutoRedelegation {
+            fallback: address::testing::established_address_2(),
+            jailed_epochs: 2,
+        }),
+    )
+    .unwrap_err();
+    assert_matches!(
+        err.downcast::<AutoRedelegationError>().unwrap().deref(),
+        AutoRedelegationError::NotAValidator(_)
+    );
+
+    let err = update_auto_redelegation(
+        &mut storage,
+        &delegator,
+        Some(AutoRedelegation {
+            fallback: validator_2,
+            jailed_epochs: 0,
+        }),
+    )
+    .unwrap_err();
+    assert_matches!(
+        err.downcast::<AutoRedelegationError>().unwrap().deref(),
+        AutoRedelegationError::ZeroJailedEpochs
+    );
+
+    update_auto_redelegation(
+        &mut storage,
+        &delegator,
+        Some(auto_redelegation.clone()),
+    )
+    .unwrap();
+    assert_eq!(
+        read_auto_redelegation(&storage, &delegator).unwrap(),
+        Some(auto_redelegation)
+    );
+
+    // Opt out
+    update_auto_redelegation(&mut storage, &delegator, None).unwrap();
+    assert_eq!(read_auto_redelegation(&storage, &delegator).unwrap(), None);
+}
+
+/// Test that the bonds to a validator are redelegated to the fallback
+/// validator once it has been jailed for long enough
+#[test]
+fn test_auto_redelegation_from_jailed_validator() {
+    let mut storage = TestState::default();
+    let mut current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(3, vec![token::Amount::native_whole(100); 3]);
+    let validator_1 = genesis_validators[0].address.clone();
+    let validator_2 = genesis_validators[1].address.clone();
+    let params = test_init_genesis(
+        &mut storage,
+        OwnedPosParams::default(),
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    let delegator = address::testing::gen_implicit_address();
+    let staking_token = staking_token_address(&storage);
+    let amount = token::Amount::native_whole(50);
+    credit_tokens(&mut storage, &staking_token, &delegator, amount).unwrap();
+    bond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator_1,
+        amount,
+        current_epoch,
+        None,
+    )
+    .unwrap();
+    update_auto_redelegation(
+        &mut storage,
+        &delegator,
+        Some(AutoRedelegation {
+            fallback: validator_2.clone(),
+            jailed_epochs: 2,
+        }),
+    )
+    .unwrap();
+
+    jail_validator(
+        &mut storage,
+        &params,
+        &validator_1,
+        current_epoch,
+        current_epoch.next(),
+        JailReason::Downtime { missed_votes: 10 },
+    )
+    .unwrap();
+
+    let bonded = |storage: &TestState, validator, epoch| {
+        bond_handle(&delegator, validator)
+            .get_sum(storage, epoch, &params)
+            .unwrap()
+            .unwrap_or_default()
+    };
+    let height = storage.in_mem().block.height;
+
+    // The bond is kept until the validator has been jailed for 2 epochs
+    let mut events: Vec<Event> = vec![];
+    for _ in 0..2 {
+        current_epoch = advance_epoch(&mut storage, &params);
+        process_auto_redelegations(
+            &mut storage,
+            &mut events,
+            current_epoch,
+            height,
+        )
+        .unwrap();
+        assert!(events.is_empty());
+    }
+
+    current_epoch = advance_epoch(&mut storage, &params);
+    process_auto_redelegations(
+        &mut storage,
+        &mut events,
+        current_epoch,
+        height,
+    )
+    .unwrap();
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    assert_eq!(bonded(&storage, &validator_1, pipeline_epoch), 0.into());
+    assert_eq!(bonded(&storage, &validator_2, pipeline_epoch), amount);
+    assert_eq!(events.len(), 1);
+    assert_eq!(*events[0].kind(), AUTO_REDELEGATION);
+
+    // The redelegated bond is not redelegated again
+    current_epoch = advance_epoch(&mut storage, &params);
+    process_auto_redelegations(
+        &mut storage,
+        &mut events,
+        current_epoch,
+        height,
+    )
+    .unwrap();
+    assert_eq!(events.len(), 1);
+}
+
+/// Test that a delegator whose automatic redelegations fail is skipped
+/// without affecting the other delegators
+#[test]
+fn test_auto_redelegation_failure() {
+    let mut storage = TestState::default();
+    let mut current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(3, vec![token::Amount::native_whole(100); 3]);
+    let validator_1 = genesis_validators[0].address.clone();
+    let validator_2 = genesis_validators[1].address.clone();
+    let params = test_init_genesis(
+        &mut storage,
+        OwnedPosParams::default(),
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    let delegators = [
+        address::testing::gen_implicit_address(),
+        address::testing::gen_implicit_address(),
+    ];
+    let staking_token = staking_token_address(&storage);
+    let amount = token::Amount::native_whole(50);
+    for delegator in &delegators {
+        credit_tokens(&mut storage, &staking_token, delegator, amount).unwrap();
+        bond_tokens(
+            &mut storage,
+            Some(delegator),
+            &validator_1,
+            amount,
+            current_epoch,
+            None,
+        )
+        .unwrap();
+        update_auto_redelegation(
+            &mut storage,
+            delegator,
+            Some(AutoRedelegation {
+                fallback: validator_2.clone(),
+                jailed_epochs: 1,
+            }),
+        )
+        .unwrap();
+    }
+    // Make the processing of the second delegator fail
+    storage
+        .write_bytes(
+            &delegation_pool_members_handle().get_data_key(&delegators[1]),
+            [0xff],
+        )
+        .unwrap();
+
+    jail_validator(
+        &mut storage,
+        &params,
+        &validator_1,
+        current_epoch,
+        current_epoch.next(),
+        JailReason::Downtime { missed_votes: 10 },
+    )
+    .unwrap();
+
+    let bonded = |storage: &TestState, delegator, validator, epoch| {
+        bond_handle(delegator, validator)
+            .get_sum(storage, epoch, &params)
+            .unwrap()
+            .unwrap_or_default()
+    };
+    let height = storage.in_mem().block.height;
+
+    let mut events: Vec<Event> = vec![];
+    for _ in 0..2 {
+        current_epoch = advance_epoch(&mut storage, &params);
+        process_auto_redelegations(
+            &mut storage,
+            &mut events,
+            current_epoch,
+            height,
+        )
+        .unwrap();
+    }
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    assert_eq!(
+        bonded(&storage, &delegators[0], &validator_2, pipeline_epoch),
+        amount
+    );
+    assert_eq!(
+        bonded(&storage, &delegators[1], &validator_1, pipeline_epoch),
+        amount
+    );
+    assert_eq!(
+        bonded(&storage, &delegators[1], &validator_2, pipeline_epoch),
+        0.into()
+    );
+    assert_eq!(events.len(), 1);
+}
@@ -0,0 +1,134 @@
+use assert_matches::assert_matches;
+use namada_core::hash::Hash;
+use namada_core::key::testing::{keypair_1, keypair_2};
+use namada_core::storage::BlockHeight;
+use namada_core::token;
+use namada_state::testing::TestState;
+// Use `RUST_LOG=info` (or another tracing level) and `--nocapture` to see
+// `tracing` logs from tests
+use test_log::test;
+
+use crate::block_seed::{
+    make_block_seed_contribution, read_block_seed, update_block_seed,
+    validate_block_seed_contribution,
+};
+use crate::parameters::OwnedPosParams;
+use crate::test_utils::test_init_genesis;
+use crate::tests::helpers::get_genesis_validators;
+
+/// Test that a contribution signed by the proposer's protocol key is only
+/// valid for the expected block.
+#[test]
+fn test_block_seed_contribution_validation() {
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(2, vec![token::Amount::native_whole(1); 2]);
+    let proposer = genesis_validators[0].address.clone();
+    test_init_genesis(
+        &mut storage,
+        OwnedPosParams::default(),
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    let height = BlockHeight(2);
+    let last_block_hash = Hash::sha256(b"last block");
+    // The genesis validators' protocol key
+    let protocol_sk = keypair_1();
+    let contribution = make_block_seed_contribution(
+        &storage,
+        &protocol_sk,
+        height,
+        &last_block_hash,
+    )
+    .unwrap();
+    validate_block_seed_contribution(
+        &storage,
+        &contribution,
+        &proposer,
+        height,
+        &last_block_hash,
+    )
+    .unwrap();
+
+    // Wrong height
+    let res = validate_block_seed_contribution(
+        &storage,
+        &contribution,
+        &proposer,
+        height.next_height(),
+        &last_block_hash,
+    );
+    assert_matches!(res, Err(_));
+
+    // Wrong last block hash
+    let res = validate_block_seed_contribution(
+        &storage,
+        &contribution,
+        &proposer,
+        height,
+        &Hash::sha256(b"another block"),
+    );
+    assert_matches!(res, Err(_));
+
+    // Not signed with the proposer's protocol key
+    let contribution = make_block_seed_contribution(
+        &storage,
+        &keypair_2(),
+        height,
+        &last_block_hash,
+    )
+    .unwrap();
+    let res = validate_block_seed_contribution(
+        &storage,
+        &contribution,
+        &proposer,
+        height,
+        &last_block_hash,
+    );
+    assert_matches!(res, Err(_));
+
+    // Not a validator
+    let res = validate_block_seed_contribution(
+        &storage,
+        &contribution,
+        &namada_core::address::testing::established_address_1(),
+        height,
+        &last_block_hash,
+    );
+    assert_matches!(res, Err(_));
+}
+
+/// Test that the block seed is chained and depends on the proposer's
+/// contribution.
+#[test]
+fn test_update_block_seed() {
+    let mut storage = TestState::default();
+    let height = BlockHeight(2);
+    let last_block_hash = Hash::sha256(b"last block");
+    assert_eq!(read_block_seed(&storage).unwrap(), None);
+
+    let contribution = make_block_seed_contribution(
+        &storage,
+        &keypair_1(),
+        height,
+        &last_block_hash,
+    )
+    .unwrap();
+    let without_contribution =
+        update_block_seed(&mut TestState::default(), &last_block_hash, None)
+            .unwrap();
+    let seed =
+        update_block_seed(&mut storage, &last_block_hash, Some(&contribution))
+            .unwrap();
+    assert_ne!(seed, without_contribution);
+    assert_eq!(read_block_seed(&storage).unwrap(), Some(seed));
+
+    // The next seed commits to the previous one
+    let next_seed =
+        update_block_seed(&mut storage, &last_block_hash, None).unwrap();
+    assert_ne!(next_seed, seed);
+    assert_ne!(next_seed, without_contribution);
+}
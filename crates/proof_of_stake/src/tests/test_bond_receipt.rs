@@ -0,0 +1,129 @@
+use std::ops::Deref;
+
+use assert_matches::assert_matches;
+use namada_core::address;
+use namada_state::testing::TestState;
+// Use `RUST_LOG=info` (or another tracing level) and `--nocapture` to see
+// `tracing` logs from tests
+use test_log::test;
+
+use crate::bond_receipt::{bond_receipt_token, read_bond_receipts};
+use crate::test_utils::test_init_genesis;
+use crate::tests::helpers::get_genesis_validators;
+use crate::token::{credit_tokens, read_balance, transfer};
+use crate::types::BondId;
+use crate::{
+    bond_tokens, redelegate_tokens, staking_token_address, token,
+    unbond_tokens, unbond_tokens_with_redeemer, OwnedPosParams, UnbondError,
+};
+
+/// Test that bond receipts are minted on bonding, burned on unbonding and
+/// re-issued on redelegation
+#[test]
+fn test_bond_receipts() {
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(2, vec![token::Amount::native_whole(1); 2]);
+    let validator_1 = genesis_validators[0].address.clone();
+    let validator_2 = genesis_validators[1].address.clone();
+    test_init_genesis(
+        &mut storage,
+        OwnedPosParams {
+            liquid_staking_receipts: true,
+            ..Default::default()
+        },
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    let staking_token = staking_token_address(&storage);
+    let delegator = address::testing::gen_implicit_address();
+    let holder = address::testing::gen_implicit_address();
+    credit_tokens(
+        &mut storage,
+        &staking_token,
+        &delegator,
+        token::Amount::native_whole(100),
+    )
+    .unwrap();
+
+    let amount = token::Amount::native_whole(100);
+    let half_amount = token::Amount::native_whole(50);
+    bond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator_1,
+        amount,
+        current_epoch,
+        None,
+    )
+    .unwrap();
+    let receipt_1 = bond_receipt_token(&validator_1).unwrap();
+    let receipt_2 = bond_receipt_token(&validator_2).unwrap();
+    assert_eq!(
+        read_balance(&storage, &receipt_1, &delegator).unwrap(),
+        amount
+    );
+    let bond_id = BondId {
+        source: delegator.clone(),
+        validator: validator_1.clone(),
+    };
+    assert_eq!(read_bond_receipts(&storage, &bond_id).unwrap(), amount);
+
+    // Transfer half of the receipts to another account, which prevents the
+    // delegator from unbonding all its tokens
+    transfer(&mut storage, &receipt_1, &delegator, &holder, half_amount)
+        .unwrap();
+    let err = unbond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator_1,
+        amount,
+        current_epoch,
+        false,
+    )
+    .unwrap_err();
+    assert_matches!(
+        err.downcast::<UnbondError>().unwrap().deref(),
+        UnbondError::InsufficientBondReceipts { .. }
+    );
+
+    // Redelegating the other half re-issues its receipts for the dest
+    // validator
+    redelegate_tokens(
+        &mut storage,
+        &delegator,
+        &validator_1,
+        &validator_2,
+        current_epoch,
+        half_amount,
+    )
+    .unwrap();
+    assert!(read_balance(&storage, &receipt_1, &delegator)
+        .unwrap()
+        .is_zero());
+    assert_eq!(
+        read_balance(&storage, &receipt_2, &delegator).unwrap(),
+        half_amount
+    );
+    assert_eq!(read_bond_receipts(&storage, &bond_id).unwrap(), half_amount);
+
+    // The remaining bond can be unbonded with the receipts of the holder,
+    // which are burned from the holder
+    unbond_tokens_with_redeemer(
+        &mut storage,
+        Some(&delegator),
+        &validator_1,
+        half_amount,
+        current_epoch,
+        false,
+        Some(&holder),
+    )
+    .unwrap();
+    assert!(read_balance(&storage, &receipt_1, &holder)
+        .unwrap()
+        .is_zero());
+    assert!(read_bond_receipts(&storage, &bond_id).unwrap().is_zero());
+}
@@ -0,0 +1,330 @@
+use std::collections::BTreeMap;
+use std::ops::Deref;
+
+use assert_matches::assert_matches;
+use namada_core::address;
+use namada_core::dec::Dec;
+use namada_state::testing::TestState;
+use namada_state::StorageWrite;
+// Use `RUST_LOG=info` (or another tracing level) and `--nocapture` to see
+// `tracing` logs from tests
+use test_log::test;
+
+use crate::delegation_pool::{
+    bond_to_delegation_pool, claim_delegation_pool_rewards,
+    delegation_pools_handle, leave_delegation_pool,
+    read_delegation_pool_membership, rebalance_delegation_pools,
+    split_by_weights, update_delegation_pool, DelegationPool,
+};
+use crate::storage::bond_handle;
+use crate::test_utils::test_init_genesis;
+use crate::tests::helpers::{advance_epoch, get_genesis_validators};
+use crate::token::credit_tokens;
+use crate::{
+    staking_token_address, token, DelegationPoolError, OwnedPosParams,
+};
+
+/// Test that only valid delegation pools can be created
+#[test]
+fn test_delegation_pool_validation() {
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(2, vec![token::Amount::native_whole(1); 2]);
+    let validator_1 = genesis_validators[0].address.clone();
+    let validator_2 = genesis_validators[1].address.clone();
+    test_init_genesis(
+        &mut storage,
+        OwnedPosParams::default(),
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    let operator = address::testing::established_address_1();
+    let half = Dec::new(5, 1).unwrap();
+    let pool = DelegationPool {
+        operator: operator.clone(),
+        validators: BTreeMap::from([
+            (validator_1.clone(), half),
+            (validator_2.clone(), half),
+        ]),
+    };
+
+    let err = update_delegation_pool(&mut storage, "a/pool", pool.clone())
+        .unwrap_err();
+    assert_matches!(
+        err.downcast::<DelegationPoolError>().unwrap().deref(),
+        DelegationPoolError::InvalidName(_)
+    );
+
+    let bad_weights = DelegationPool {
+        operator: operator.clone(),
+        validators: BTreeMap::from([
+            (validator_1.clone(), half),
+            (validator_2.clone(), Dec::new(4, 1).unwrap()),
+        ]),
+    };
+    let err =
+        update_delegation_pool(&mut storage, "pool", bad_weights).unwrap_err();
+    assert_matches!(
+        err.downcast::<DelegationPoolError>().unwrap().deref(),
+        DelegationPoolError::InvalidWeights
+    );
+
+    let not_a_validator = DelegationPool {
+        operator: operator.clone(),
+        validators: BTreeMap::from([
+            (validator_1, half),
+            (address::testing::established_address_2(), half),
+        ]),
+    };
+    let err = update_delegation_pool(&mut storage, "pool", not_a_validator)
+        .unwrap_err();
+    assert_matches!(
+        err.downcast::<DelegationPoolError>().unwrap().deref(),
+        DelegationPoolError::NotAValidator(_)
+    );
+
+    let empty = DelegationPool {
+        operator,
+        validators: BTreeMap::new(),
+    };
+    let err = update_delegation_pool(&mut storage, "pool", empty).unwrap_err();
+    assert_matches!(
+        err.downcast::<DelegationPoolError>().unwrap().deref(),
+        DelegationPoolError::InvalidValidatorCount(0)
+    );
+
+    update_delegation_pool(&mut storage, "pool-1", pool).unwrap();
+}
+
+/// Test that the rounding remainder of a split goes to the heaviest validator
+#[test]
+fn test_split_by_weights() {
+    let validator_1 = address::testing::established_address_1();
+    let validator_2 = address::testing::established_address_2();
+    let pool = DelegationPool {
+        operator: address::testing::established_address_3(),
+        validators: BTreeMap::from([
+            (validator_1.clone(), Dec::new(3, 1).unwrap()),
+            (validator_2.clone(), Dec::new(7, 1).unwrap()),
+        ]),
+    };
+    let split = split_by_weights(token::Amount::from_u64(11), &pool).unwrap();
+    assert_eq!(split[&validator_1], token::Amount::from_u64(3));
+    assert_eq!(split[&validator_2], token::Amount::from_u64(8));
+}
+
+/// Test bonding to a delegation pool and the rebalancing of the members'
+/// bonds after the pool's weights change
+#[test]
+fn test_delegation_pool_bond_and_rebalance() {
+    let mut storage = TestState::default();
+    let mut current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(3, vec![token::Amount::native_whole(1); 3]);
+    let validators: Vec<_> = genesis_validators
+        .iter()
+        .map(|validator| validator.address.clone())
+        .collect();
+    let params = test_init_genesis(
+        &mut storage,
+        OwnedPosParams::default(),
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    storage.commit_block().unwrap();
+
+    let half = Dec::new(5, 1).unwrap();
+    let operator = address::testing::established_address_1();
+    let pool = DelegationPool {
+        operator: operator.clone(),
+        validators: BTreeMap::from([
+            (validators[0].clone(), half),
+            (validators[1].clone(), half),
+        ]),
+    };
+    update_delegation_pool(&mut storage, "pool", pool.clone()).unwrap();
+    update_delegation_pool(&mut storage, "other-pool", pool).unwrap();
+
+    let staking_token = staking_token_address(&storage);
+    let delegator = address::testing::gen_implicit_address();
+    credit_tokens(
+        &mut storage,
+        &staking_token,
+        &delegator,
+        token::Amount::native_whole(1_000),
+    )
+    .unwrap();
+
+    let amount = token::Amount::native_whole(100);
+    let bonds = bond_to_delegation_pool(
+        &mut storage,
+        &delegator,
+        "pool",
+        amount,
+        current_epoch,
+    )
+    .unwrap();
+    let half_amount = token::Amount::native_whole(50);
+    assert_eq!(
+        bonds,
+        BTreeMap::from([
+            (validators[0].clone(), half_amount),
+            (validators[1].clone(), half_amount),
+        ])
+    );
+    assert_eq!(
+        read_delegation_pool_membership(&storage, &delegator).unwrap(),
+        Some("pool".to_string())
+    );
+
+    // A delegator can only be a member of a single pool
+    let err = bond_to_delegation_pool(
+        &mut storage,
+        &delegator,
+        "other-pool",
+        amount,
+        current_epoch,
+    )
+    .unwrap_err();
+    assert_matches!(
+        err.downcast::<DelegationPoolError>().unwrap().deref(),
+        DelegationPoolError::AlreadyInAnotherPool { .. }
+    );
+
+    // Replace the first validator of the pool with the third one
+    let pool = DelegationPool {
+        operator,
+        validators: BTreeMap::from([
+            (validators[1].clone(), half),
+            (validators[2].clone(), half),
+        ]),
+    };
+    update_delegation_pool(&mut storage, "pool", pool).unwrap();
+
+    current_epoch = advance_epoch(&mut storage, &params);
+    rebalance_delegation_pools(&mut storage, current_epoch).unwrap();
+
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    let bonded = |storage: &TestState, validator| {
+        bond_handle(&delegator, validator)
+            .get_sum(storage, pipeline_epoch, &params)
+            .unwrap()
+            .unwrap_or_default()
+    };
+    assert_eq!(bonded(&storage, &validators[0]), token::Amount::zero());
+    assert_eq!(bonded(&storage, &validators[1]), half_amount);
+    assert_eq!(bonded(&storage, &validators[2]), half_amount);
+
+    // The rewards are claimed from all the bonds of the member
+    let rewards =
+        claim_delegation_pool_rewards(&mut storage, &delegator, current_epoch)
+            .unwrap();
+    assert_eq!(rewards.len(), 3);
+
+    // After leaving the pool, the bonds are no longer managed by it
+    leave_delegation_pool(&mut storage, &delegator).unwrap();
+    let err =
+        claim_delegation_pool_rewards(&mut storage, &delegator, current_epoch)
+            .unwrap_err();
+    assert_matches!(
+        err.downcast::<DelegationPoolError>().unwrap().deref(),
+        DelegationPoolError::NotAMember(_)
+    );
+}
+
+/// Test that a member whose rebalancing fails is skipped, without keeping any
+/// of its writes nor preventing the other members from being rebalanced
+#[test]
+fn test_delegation_pool_rebalance_failure() {
+    let mut storage = TestState::default();
+    let mut current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(3, vec![token::Amount::native_whole(1); 3]);
+    let validators: Vec<_> = genesis_validators
+        .iter()
+        .map(|validator| validator.address.clone())
+        .collect();
+    let params = test_init_genesis(
+        &mut storage,
+        OwnedPosParams::default(),
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    storage.commit_block().unwrap();
+
+    let half = Dec::new(5, 1).unwrap();
+    let operator = address::testing::established_address_1();
+    let pool = DelegationPool {
+        operator: operator.clone(),
+        validators: BTreeMap::from([
+            (validators[0].clone(), half),
+            (validators[1].clone(), half),
+        ]),
+    };
+    update_delegation_pool(&mut storage, "pool", pool.clone()).unwrap();
+    update_delegation_pool(&mut storage, "other-pool", pool).unwrap();
+
+    let staking_token = staking_token_address(&storage);
+    let amount = token::Amount::native_whole(100);
+    let half_amount = token::Amount::native_whole(50);
+    let mut members = vec![];
+    for name in ["pool", "other-pool"] {
+        let delegator = address::testing::gen_implicit_address();
+        credit_tokens(&mut storage, &staking_token, &delegator, amount)
+            .unwrap();
+        bond_to_delegation_pool(
+            &mut storage,
+            &delegator,
+            name,
+            amount,
+            current_epoch,
+        )
+        .unwrap();
+        members.push(delegator);
+    }
+
+    // Move both pools to the third validator, but corrupt the second one
+    let pool = DelegationPool {
+        operator,
+        validators: BTreeMap::from([
+            (validators[1].clone(), half),
+            (validators[2].clone(), half),
+        ]),
+    };
+    update_delegation_pool(&mut storage, "pool", pool.clone()).unwrap();
+    update_delegation_pool(&mut storage, "other-pool", pool).unwrap();
+    storage
+        .write_bytes(
+            &delegation_pools_handle().get_data_key(&"other-pool".to_string()),
+            [0xff],
+        )
+        .unwrap();
+
+    current_epoch = advance_epoch(&mut storage, &params);
+    rebalance_delegation_pools(&mut storage, current_epoch).unwrap();
+
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    let bonded = |storage: &TestState, delegator, validator| {
+        bond_handle(delegator, validator)
+            .get_sum(storage, pipeline_epoch, &params)
+            .unwrap()
+            .unwrap_or_default()
+    };
+    // The member of the valid pool is rebalanced
+    assert_eq!(
+        bonded(&storage, &members[0], &validators[0]),
+        token::Amount::zero()
+    );
+    assert_eq!(bonded(&storage, &members[0], &validators[2]), half_amount);
+    // The member of the corrupted pool keeps its bonds
+    assert_eq!(bonded(&storage, &members[1], &validators[0]), half_amount);
+    assert_eq!(
+        bonded(&storage, &members[1], &validators[2]),
+        token::Amount::zero()
+    );
+}
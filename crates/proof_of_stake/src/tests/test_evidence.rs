@@ -0,0 +1,141 @@
+use data_encoding::HEXUPPER;
+use namada_core::dec::Dec;
+use namada_core::key::tm_consensus_key_raw_hash;
+use namada_core::storage::{BlockHeight, Epoch};
+use namada_core::tendermint::abci::types::{
+    Misbehavior, MisbehaviorKind, Validator,
+};
+use namada_core::tendermint::Time;
+use namada_events::Event;
+use namada_state::testing::TestState;
+// Use `RUST_LOG=info` (or another tracing level) and `--nocapture` to see
+// `tracing` logs from tests
+use test_log::test;
+
+use crate::event::types::EVIDENCE;
+use crate::event::{EvidenceResult, EvidenceValidator};
+use crate::evidence::{
+    prune_evidence_records, read_recent_evidence, EvidenceKind, EvidenceOutcome,
+};
+use crate::slashing::record_slashes_from_evidence;
+use crate::test_utils::test_init_genesis;
+use crate::tests::helpers::get_genesis_validators;
+use crate::types::SlashType;
+use crate::{token, OwnedPosParams};
+
+/// Test that every piece of evidence received from CometBFT is recorded with
+/// its outcome and the resulting slash, that an event is emitted for it and
+/// that the records are pruned once the slashes have been processed long
+/// enough ago.
+#[test]
+fn test_evidence_records() {
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(2, vec![token::Amount::native_whole(1); 2]);
+    let validator = genesis_validators[0].address.clone();
+    let consensus_key = genesis_validators[0].consensus_key.clone();
+    let params = test_init_genesis(
+        &mut storage,
+        OwnedPosParams::default(),
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    let height = BlockHeight(10);
+    storage.in_mem_mut().block.height = height;
+    storage
+        .in_mem_mut()
+        .block
+        .pred_epochs
+        .new_epoch(BlockHeight(1));
+
+    let raw_hash: [u8; 20] = HEXUPPER
+        .decode(tm_consensus_key_raw_hash(&consensus_key).as_bytes())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let misbehavior = |kind, address, height: u32| Misbehavior {
+        kind,
+        validator: Validator {
+            address,
+            power: Default::default(),
+        },
+        height: height.into(),
+        time: Time::unix_epoch(),
+        total_voting_power: Default::default(),
+    };
+    let byzantine_validators = vec![
+        misbehavior(MisbehaviorKind::DuplicateVote, raw_hash, 5),
+        misbehavior(MisbehaviorKind::LightClientAttack, [0; 20], 6),
+    ];
+    let mut events: Vec<Event> = vec![];
+    record_slashes_from_evidence(
+        &mut storage,
+        &mut events,
+        byzantine_validators,
+        &params,
+        current_epoch,
+        height,
+        current_epoch.next(),
+    )
+    .unwrap();
+
+    // An event is emitted for every piece of evidence
+    assert_eq!(events.len(), 2);
+    assert!(events.iter().all(|event| *event.kind() == EVIDENCE));
+    assert_eq!(
+        events[0].read_attribute::<EvidenceValidator>().unwrap(),
+        validator
+    );
+    assert_eq!(
+        events[0].read_attribute::<EvidenceResult>().unwrap(),
+        EvidenceOutcome::Slashed
+    );
+    assert!(!events[1].has_attribute::<EvidenceValidator>());
+    assert_eq!(
+        events[1].read_attribute::<EvidenceResult>().unwrap(),
+        EvidenceOutcome::UnknownValidator
+    );
+
+    // The evidence is listed with the resulting slash, still enqueued
+    let evidence = read_recent_evidence(&storage, None).unwrap();
+    assert_eq!(evidence.len(), 2);
+    assert_eq!(evidence[0].epoch, current_epoch);
+    assert_eq!(evidence[0].record.kind, EvidenceKind::DuplicateVote);
+    assert_eq!(evidence[0].record.validator, Some(validator));
+    assert_eq!(evidence[0].record.evidence_height, BlockHeight(5));
+    assert_eq!(evidence[0].record.evidence_epoch, Some(Epoch(0)));
+    assert_eq!(evidence[0].record.height, height);
+    let slash = evidence[0].slash.clone().unwrap();
+    assert_eq!(slash.block_height, 5);
+    assert_eq!(slash.r#type, SlashType::DuplicateVote);
+    assert_eq!(slash.rate, Dec::zero());
+    assert_eq!(evidence[1].record.kind, EvidenceKind::LightClientAttack);
+    assert_eq!(evidence[1].record.validator, None);
+    assert_eq!(
+        evidence[1].record.outcome,
+        EvidenceOutcome::UnknownValidator
+    );
+    assert_eq!(evidence[1].slash, None);
+
+    // The evidence can be filtered by the epoch in which it was received
+    assert!(
+        read_recent_evidence(&storage, Some(current_epoch.next()))
+            .unwrap()
+            .is_empty()
+    );
+
+    // The records are kept for twice the slash processing offset
+    let retention = 2 * params.slash_processing_epoch_offset();
+    prune_evidence_records(&mut storage, &params, current_epoch + retention)
+        .unwrap();
+    assert_eq!(read_recent_evidence(&storage, None).unwrap().len(), 2);
+    prune_evidence_records(
+        &mut storage,
+        &params,
+        current_epoch + retention + 1,
+    )
+    .unwrap();
+    assert!(read_recent_evidence(&storage, None).unwrap().is_empty());
+}
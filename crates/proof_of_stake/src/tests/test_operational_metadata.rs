@@ -0,0 +1,156 @@
+use assert_matches::assert_matches;
+use namada_core::key::testing::{keypair_1, keypair_2};
+use namada_core::key::RefTo;
+use namada_core::token;
+use namada_state::testing::TestState;
+// Use `RUST_LOG=info` (or another tracing level) and `--nocapture` to see
+// `tracing` logs from tests
+use test_log::test;
+
+use crate::operational_metadata::{
+    read_operational_metadata, read_operational_metadata_nonce,
+    validate_operational_metadata_update, write_operational_metadata,
+    OperationalMetadata, OperationalMetadataUpdate,
+    MAX_OPERATIONAL_METADATA_FIELD_LEN,
+};
+use crate::parameters::OwnedPosParams;
+use crate::test_utils::test_init_genesis;
+use crate::tests::helpers::get_genesis_validators;
+
+/// Test that an operational metadata update is only valid in the current
+/// epoch, with valid fields and when signed by the validator's protocol key.
+#[test]
+fn test_operational_metadata_update_validation() {
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(1, vec![token::Amount::native_whole(1)]);
+    let validator = genesis_validators[0].address.clone();
+    test_init_genesis(
+        &mut storage,
+        OwnedPosParams::default(),
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    // The genesis validators' protocol key
+    let protocol_pk = keypair_1().ref_to();
+    let update = OperationalMetadataUpdate {
+        validator: validator.clone(),
+        epoch: current_epoch,
+        nonce: 0,
+        metadata: OperationalMetadata {
+            rpc_endpoint: Some("https://rpc.validator.example".to_string()),
+            seed_node: None,
+            emergency_contact: Some("ops@validator.example".to_string()),
+        },
+    };
+    validate_operational_metadata_update(&storage, &update, &protocol_pk)
+        .unwrap();
+
+    // Not signed with the validator's protocol key
+    let res = validate_operational_metadata_update(
+        &storage,
+        &update,
+        &keypair_2().ref_to(),
+    );
+    assert_matches!(res, Err(_));
+
+    // Wrong epoch
+    let stale_update = OperationalMetadataUpdate {
+        epoch: current_epoch.next(),
+        ..update.clone()
+    };
+    let res = validate_operational_metadata_update(
+        &storage,
+        &stale_update,
+        &protocol_pk,
+    );
+    assert_matches!(res, Err(_));
+
+    // Wrong nonce
+    let res = validate_operational_metadata_update(
+        &storage,
+        &OperationalMetadataUpdate {
+            nonce: 1,
+            ..update.clone()
+        },
+        &protocol_pk,
+    );
+    assert_matches!(res, Err(_));
+
+    // Invalid fields
+    for field in [
+        String::new(),
+        "a".repeat(MAX_OPERATIONAL_METADATA_FIELD_LEN + 1),
+    ] {
+        let mut invalid_update = update.clone();
+        invalid_update.metadata.seed_node = Some(field);
+        let res = validate_operational_metadata_update(
+            &storage,
+            &invalid_update,
+            &protocol_pk,
+        );
+        assert_matches!(res, Err(_));
+    }
+
+    // Not a validator
+    let res = validate_operational_metadata_update(
+        &storage,
+        &OperationalMetadataUpdate {
+            validator: namada_core::address::testing::established_address_1(),
+            ..update
+        },
+        &protocol_pk,
+    );
+    assert_matches!(res, Err(_));
+}
+
+/// Test that an update replaces the published operational metadata, that an
+/// empty update removes it and that an applied update cannot be replayed.
+#[test]
+fn test_write_operational_metadata() {
+    let mut storage = TestState::default();
+    let validator = namada_core::address::testing::established_address_1();
+    assert_eq!(
+        read_operational_metadata(&storage, &validator).unwrap(),
+        None
+    );
+
+    let metadata = OperationalMetadata {
+        rpc_endpoint: None,
+        seed_node: Some("id@seed.validator.example:26656".to_string()),
+        emergency_contact: None,
+    };
+    let update = OperationalMetadataUpdate {
+        validator: validator.clone(),
+        epoch: storage.in_mem().block.epoch,
+        nonce: 0,
+        metadata: metadata.clone(),
+    };
+    write_operational_metadata(&mut storage, &update).unwrap();
+    assert_eq!(
+        read_operational_metadata(&storage, &validator).unwrap(),
+        Some(metadata)
+    );
+    assert_eq!(
+        read_operational_metadata_nonce(&storage, &validator).unwrap(),
+        1
+    );
+
+    // The same update cannot be applied again
+    let res = write_operational_metadata(&mut storage, &update);
+    assert_matches!(res, Err(_));
+
+    let update = OperationalMetadataUpdate {
+        nonce: 1,
+        metadata: OperationalMetadata::default(),
+        ..update
+    };
+    write_operational_metadata(&mut storage, &update).unwrap();
+    assert_eq!(
+        read_operational_metadata(&storage, &validator).unwrap(),
+        None
+    );
+}
@@ -1,6 +1,7 @@
 //! PoS system tests
 
 use std::collections::BTreeMap;
+use std::ops::Deref;
 
 use assert_matches::assert_matches;
 use namada_core::address::Address;
@@ -12,7 +13,7 @@ use namada_core::storage::{BlockHeight, Epoch};
 use namada_core::{address, key};
 use namada_state::testing::TestState;
 use namada_storage::collections::lazy_map::Collectable;
-use namada_storage::StorageRead;
+use namada_storage::{StorageRead, StorageWrite};
 use proptest::prelude::*;
 use proptest::test_runner::Config;
 // Use `RUST_LOG=info` (or another tracing level) and `--nocapture` to see
@@ -22,13 +23,14 @@ use token::get_effective_total_native_supply;
 
 use crate::epoched::EpochOffset;
 use crate::parameters::testing::arb_pos_params;
-use crate::parameters::OwnedPosParams;
+use crate::parameters::{OwnedPosParams, PosParams};
 use crate::queries::{
     bonds_and_unbonds, find_delegation_validators, find_delegations,
+    find_pending_consensus_key,
 };
 use crate::rewards::{
-    log_block_rewards_aux, update_rewards_products_and_mint_inflation,
-    PosRewardsCalculator,
+    estimate_validator_rewards_rate, log_block_rewards_aux,
+    update_rewards_products_and_mint_inflation, PosRewardsCalculator,
 };
 use crate::slashing::{process_slashes, slash};
 use crate::storage::{
@@ -36,8 +38,8 @@ use crate::storage::{
     liveness_sum_missed_votes_handle,
     read_below_threshold_validator_set_addresses,
     read_consensus_validator_set_addresses_with_stake, read_total_stake,
-    read_validator_deltas_value, rewards_accumulator_handle,
-    total_deltas_handle,
+    read_validator_deltas_value, read_validator_jail_history,
+    rewards_accumulator_handle, total_deltas_handle,
 };
 use crate::test_utils::test_init_genesis;
 use crate::tests::helpers::{
@@ -47,8 +49,8 @@ use crate::tests::helpers::{
 use crate::token::{credit_tokens, read_balance};
 use crate::types::{
     into_tm_voting_power, BondDetails, BondId, BondsAndUnbondsDetails,
-    GenesisValidator, SlashType, UnbondDetails, ValidatorState, VoteInfo,
-    WeightedValidator,
+    GenesisValidator, JailReason, JailRecord, SlashType, UnbondDetails,
+    ValidatorState, VoteInfo, WeightedValidator,
 };
 use crate::{
     below_capacity_validator_set_handle, bond_handle, bond_tokens,
@@ -56,7 +58,8 @@ use crate::{
     is_validator, jail_for_liveness, read_validator_stake, redelegate_tokens,
     staking_token_address, token, unbond_handle, unbond_tokens,
     unjail_validator, validator_consensus_key_handle,
-    validator_set_positions_handle, validator_state_handle, withdraw_tokens,
+    validator_remaining_capacity, validator_set_positions_handle,
+    validator_state_handle, withdraw_tokens, BondError, RedelegationError,
 };
 
 proptest! {
@@ -1457,6 +1460,7 @@ fn test_update_rewards_products_aux(validators: Vec<GenesisValidator>) {
     let inflation = token::Amount::native_whole(10_000_000);
     update_rewards_products_and_mint_inflation(
         &mut s,
+        &mut namada_events::testing::VoidEventSink,
         &params,
         last_epoch,
         num_blocks_in_last_epoch,
@@ -1556,6 +1560,12 @@ fn test_consensus_key_change_aux(validators: Vec<GenesisValidator>) {
         .unwrap();
     assert_eq!(ck, ck_2);
 
+    // The new key is pending until the pipeline epoch
+    let pending =
+        find_pending_consensus_key(&storage, &validator, current_epoch)
+            .unwrap();
+    assert_eq!(pending, Some((ck_2.clone(), pipeline_epoch)));
+
     // Advance to the pipeline epoch
     loop {
         current_epoch = advance_epoch(&mut storage, &params);
@@ -1563,6 +1573,10 @@ fn test_consensus_key_change_aux(validators: Vec<GenesisValidator>) {
             break;
         }
     }
+    let pending =
+        find_pending_consensus_key(&storage, &validator, current_epoch)
+            .unwrap();
+    assert_eq!(pending, None);
 
     // Check the consensus keys again
     let consensus_keys = get_consensus_key_set(&storage).unwrap();
@@ -1784,6 +1798,13 @@ fn test_jail_for_liveness_aux(validators: Vec<GenesisValidator>) {
                 .unwrap()
                 .expect("Validator should have a state for the jail epoch");
             assert_eq!(state_jail_epoch, ValidatorState::Jailed);
+            assert_eq!(
+                read_validator_jail_history(s, address).unwrap(),
+                vec![JailRecord {
+                    epoch: jail_epoch,
+                    reason: JailReason::Downtime { missed_votes },
+                }]
+            );
         }
     }
 
@@ -2199,3 +2220,184 @@ fn test_delegation_targets() {
     assert!(de_2.prev_ranges.is_empty());
     assert_eq!(de_2.last_range.1, None);
 }
+
+/// Test the estimation of the annualized rewards rates of validators
+#[test]
+fn test_estimate_validator_rewards_rate() {
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(2, vec![token::Amount::native_whole(100); 2]);
+    let validator = genesis_validators[0].address.clone();
+    let commission_rate = genesis_validators[0].commission_rate;
+    test_init_genesis(
+        &mut storage,
+        OwnedPosParams::default(),
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    storage
+        .write(
+            &namada_parameters::storage::get_epochs_per_year_key(),
+            365_u64,
+        )
+        .unwrap();
+    // Only a part of the native tokens is staked
+    let staking_token = staking_token_address(&storage);
+    credit_tokens(
+        &mut storage,
+        &staking_token,
+        &address::testing::established_address_1(),
+        token::Amount::native_whole(1_000),
+    )
+    .unwrap();
+
+    let estimate =
+        estimate_validator_rewards_rate(&storage, &validator, current_epoch)
+            .unwrap()
+            .unwrap();
+    assert_eq!(estimate.epoch, current_epoch);
+    assert!(estimate.is_consensus);
+    assert_eq!(estimate.commission_rate, commission_rate);
+    assert!(!estimate.epoch_inflation.is_zero());
+    assert!(estimate.staked_ratio > Dec::zero());
+    assert!(estimate.staked_ratio < Dec::one());
+    assert!(estimate.gross_rate > Dec::zero());
+    assert_eq!(
+        estimate.delegator_rate,
+        estimate.gross_rate * (Dec::one() - commission_rate)
+    );
+
+    // There is no estimate for non-validators
+    assert!(estimate_validator_rewards_rate(
+        &storage,
+        &address::testing::established_address_2(),
+        current_epoch
+    )
+    .unwrap()
+    .is_none());
+}
+
+/// Test that the bonds and redelegations cannot increase the stake of a
+/// validator above the cap of its share of the total stake
+#[test]
+fn test_validator_stake_cap() {
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(4, vec![token::Amount::native_whole(100); 4]);
+    let validator1 = genesis_validators[0].address.clone();
+    let validator2 = genesis_validators[1].address.clone();
+    let validator3 = genesis_validators[2].address.clone();
+    let params = OwnedPosParams {
+        max_validator_stake_share: Some(Dec::new(3, 1).unwrap()),
+        ..Default::default()
+    };
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+
+    let delegator = address::testing::gen_implicit_address();
+    let staking_token = staking_token_address(&storage);
+    credit_tokens(
+        &mut storage,
+        &staking_token,
+        &delegator,
+        token::Amount::native_whole(100),
+    )
+    .unwrap();
+
+    // The validator can receive `(0.3 * 400 - 100) / 0.7` tokens
+    let capacity = validator_remaining_capacity(
+        &storage,
+        &params,
+        &validator1,
+        pipeline_epoch,
+    )
+    .unwrap()
+    .unwrap();
+    assert!(capacity > token::Amount::native_whole(28));
+    assert!(capacity < token::Amount::native_whole(29));
+
+    bond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator1,
+        token::Amount::native_whole(28),
+        current_epoch,
+        None,
+    )
+    .unwrap();
+    bond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator3,
+        token::Amount::native_whole(20),
+        current_epoch,
+        None,
+    )
+    .unwrap();
+    redelegate_tokens(
+        &mut storage,
+        &delegator,
+        &validator3,
+        &validator2,
+        current_epoch,
+        token::Amount::native_whole(10),
+    )
+    .unwrap();
+    let capacity = validator_remaining_capacity(
+        &storage,
+        &params,
+        &validator1,
+        pipeline_epoch,
+    )
+    .unwrap()
+    .unwrap();
+    assert!(capacity < token::Amount::native_whole(1));
+
+    // Any bond or redelegation above the remaining capacity is rejected
+    let err = bond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator1,
+        token::Amount::native_whole(1),
+        current_epoch,
+        None,
+    )
+    .unwrap_err();
+    assert_matches!(
+        err.downcast::<BondError>().unwrap().deref(),
+        BondError::ValidatorStakeCapExceeded(_, _, _)
+    );
+    let err = redelegate_tokens(
+        &mut storage,
+        &delegator,
+        &validator3,
+        &validator1,
+        current_epoch,
+        token::Amount::native_whole(10),
+    )
+    .unwrap_err();
+    assert_matches!(
+        err.downcast::<RedelegationError>().unwrap().deref(),
+        RedelegationError::ValidatorStakeCapExceeded(_, _, _)
+    );
+
+    // The stake is not capped by default
+    let params = PosParams::default();
+    assert!(validator_remaining_capacity(
+        &storage,
+        &params,
+        &validator1,
+        pipeline_epoch,
+    )
+    .unwrap()
+    .is_none());
+}
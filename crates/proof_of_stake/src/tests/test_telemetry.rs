@@ -0,0 +1,201 @@
+use assert_matches::assert_matches;
+use namada_core::key::testing::{keypair_1, keypair_2};
+use namada_core::key::RefTo;
+use namada_core::storage::BlockHeight;
+use namada_core::token;
+use namada_state::testing::TestState;
+// Use `RUST_LOG=info` (or another tracing level) and `--nocapture` to see
+// `tracing` logs from tests
+use test_log::test;
+
+use crate::parameters::OwnedPosParams;
+use crate::telemetry::{
+    aggregate_telemetry, read_validator_telemetry,
+    validate_telemetry_heartbeat, write_validator_telemetry, BinaryTelemetry,
+    ValidatorTelemetry, MAX_TELEMETRY_FIELD_LEN,
+    MIN_TELEMETRY_HEARTBEAT_INTERVAL,
+};
+use crate::test_utils::test_init_genesis;
+use crate::tests::helpers::get_genesis_validators;
+
+/// Test that a telemetry heartbeat is only valid when signed at least the
+/// minimum interval after the last heartbeat, with valid fields and by the
+/// validator's protocol key.
+#[test]
+fn test_telemetry_heartbeat_validation() {
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(1, vec![token::Amount::native_whole(1)]);
+    let validator = genesis_validators[0].address.clone();
+    test_init_genesis(
+        &mut storage,
+        OwnedPosParams::default(),
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    storage.in_mem_mut().block.height = BlockHeight(10);
+
+    // The genesis validators' protocol key
+    let protocol_pk = keypair_1().ref_to();
+    let telemetry = ValidatorTelemetry {
+        binary_version: "v0.35.1".to_string(),
+        commit_hash: "5b8c3d1".to_string(),
+        block_height: BlockHeight(9),
+    };
+    validate_telemetry_heartbeat(
+        &storage,
+        &validator,
+        &telemetry,
+        &protocol_pk,
+    )
+    .unwrap();
+
+    // Not signed with the validator's protocol key
+    let res = validate_telemetry_heartbeat(
+        &storage,
+        &validator,
+        &telemetry,
+        &keypair_2().ref_to(),
+    );
+    assert_matches!(res, Err(_));
+
+    // Signed after the current height
+    let res = validate_telemetry_heartbeat(
+        &storage,
+        &validator,
+        &ValidatorTelemetry {
+            block_height: BlockHeight(11),
+            ..telemetry.clone()
+        },
+        &protocol_pk,
+    );
+    assert_matches!(res, Err(_));
+
+    // Replay of the last heartbeat
+    write_validator_telemetry(&mut storage, &validator, &telemetry).unwrap();
+    let res = validate_telemetry_heartbeat(
+        &storage,
+        &validator,
+        &telemetry,
+        &protocol_pk,
+    );
+    assert_matches!(res, Err(_));
+
+    // Signed before the minimum interval has elapsed
+    let next_height = 9 + MIN_TELEMETRY_HEARTBEAT_INTERVAL;
+    storage.in_mem_mut().block.height = BlockHeight(next_height);
+    let res = validate_telemetry_heartbeat(
+        &storage,
+        &validator,
+        &ValidatorTelemetry {
+            block_height: BlockHeight(next_height - 1),
+            ..telemetry.clone()
+        },
+        &protocol_pk,
+    );
+    assert_matches!(res, Err(_));
+    let next_telemetry = ValidatorTelemetry {
+        block_height: BlockHeight(next_height),
+        ..telemetry.clone()
+    };
+    validate_telemetry_heartbeat(
+        &storage,
+        &validator,
+        &next_telemetry,
+        &protocol_pk,
+    )
+    .unwrap();
+
+    // Invalid fields
+    for field in [String::new(), "a".repeat(MAX_TELEMETRY_FIELD_LEN + 1)] {
+        let mut invalid_telemetry = next_telemetry.clone();
+        invalid_telemetry.commit_hash = field;
+        let res = validate_telemetry_heartbeat(
+            &storage,
+            &validator,
+            &invalid_telemetry,
+            &protocol_pk,
+        );
+        assert_matches!(res, Err(_));
+    }
+
+    // Not a validator
+    let res = validate_telemetry_heartbeat(
+        &storage,
+        &namada_core::address::testing::established_address_1(),
+        &next_telemetry,
+        &protocol_pk,
+    );
+    assert_matches!(res, Err(_));
+}
+
+/// Test that the heartbeats of the consensus validators are aggregated by
+/// binary, weighted by stake.
+#[test]
+fn test_aggregate_telemetry() {
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators = get_genesis_validators(
+        3,
+        vec![
+            token::Amount::native_whole(1),
+            token::Amount::native_whole(2),
+            token::Amount::native_whole(3),
+        ],
+    );
+    let validators: Vec<_> = genesis_validators
+        .iter()
+        .map(|validator| (validator.address.clone(), validator.tokens))
+        .collect();
+    test_init_genesis(
+        &mut storage,
+        OwnedPosParams::default(),
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    let summary = aggregate_telemetry(&storage, current_epoch).unwrap();
+    assert_eq!(summary.total_stake, token::Amount::native_whole(6));
+    assert_eq!(summary.unreported_stake, summary.total_stake);
+    assert!(summary.binaries.is_empty());
+
+    let old = ValidatorTelemetry {
+        binary_version: "v0.35.0".to_string(),
+        commit_hash: "1a2b3c4".to_string(),
+        block_height: BlockHeight(1),
+    };
+    let new = ValidatorTelemetry {
+        binary_version: "v0.35.1".to_string(),
+        commit_hash: "5b8c3d1".to_string(),
+        block_height: BlockHeight(1),
+    };
+    write_validator_telemetry(&mut storage, &validators[0].0, &new).unwrap();
+    write_validator_telemetry(&mut storage, &validators[2].0, &old).unwrap();
+    assert_eq!(
+        read_validator_telemetry(&storage, &validators[0].0).unwrap(),
+        Some(new.clone())
+    );
+
+    let summary = aggregate_telemetry(&storage, current_epoch).unwrap();
+    assert_eq!(summary.unreported_stake, validators[1].1);
+    assert_eq!(
+        summary.binaries,
+        vec![
+            BinaryTelemetry {
+                binary_version: old.binary_version,
+                commit_hash: old.commit_hash,
+                validators: 1,
+                stake: validators[2].1,
+            },
+            BinaryTelemetry {
+                binary_version: new.binary_version,
+                commit_hash: new.commit_hash,
+                validators: 1,
+                stake: validators[0].1,
+            },
+        ]
+    );
+}
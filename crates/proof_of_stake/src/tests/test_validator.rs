@@ -1,5 +1,8 @@
 use std::cmp::min;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Deref;
 
+use assert_matches::assert_matches;
 use namada_core::address::testing::arb_established_address;
 use namada_core::address::{self, Address, EstablishedAddressGen};
 use namada_core::dec::Dec;
@@ -24,27 +27,32 @@ use crate::storage::{
     get_num_consensus_validators,
     read_below_capacity_validator_set_addresses_with_stake,
     read_below_threshold_validator_set_addresses,
-    read_consensus_validator_set_addresses_with_stake, update_validator_deltas,
-    validator_addresses_handle, validator_consensus_key_handle,
-    validator_set_positions_handle, write_validator_address_raw_hash,
+    read_consensus_validator_set_addresses_with_stake, read_pos_params,
+    update_validator_deltas, validator_addresses_handle,
+    validator_consensus_key_handle, validator_set_positions_handle,
+    write_pos_params, write_validator_address_raw_hash,
 };
 use crate::test_utils::{init_genesis_helper, test_init_genesis};
 use crate::tests::helpers::{
     advance_epoch, arb_genesis_validators, arb_params_and_genesis_validators,
-    get_tendermint_set_updates,
+    get_genesis_validators, get_tendermint_set_updates,
 };
 use crate::token::credit_tokens;
 use crate::types::{
-    into_tm_voting_power, ConsensusValidator, GenesisValidator, Position,
-    ReverseOrdTokenAmount, ValidatorSetUpdate, WeightedValidator,
+    into_tm_voting_power, CommissionSchedule, ConsensusValidator,
+    GenesisValidator, Position, ReverseOrdTokenAmount, ValidatorMetaData,
+    ValidatorMetaDataField, ValidatorSetUpdate, WeightedValidator,
 };
 use crate::validator_set_update::{
     insert_validator_into_validator_set, update_validator_set,
 };
 use crate::{
-    become_validator, bond_tokens, change_consensus_key, is_validator,
-    staking_token_address, unbond_tokens, withdraw_tokens, BecomeValidator,
-    OwnedPosParams,
+    become_validator, bond_tokens, change_consensus_key,
+    change_validator_commission_rate, change_validator_metadata, is_validator,
+    read_validator_commission_schedule, staking_token_address, unbond_tokens,
+    validate_commission_rate_change, validate_validator_metadata,
+    withdraw_tokens, BecomeValidator, BecomeValidatorError,
+    CommissionRateChangeError, MetadataError, OwnedPosParams,
 };
 
 proptest! {
@@ -1393,3 +1401,226 @@ fn test_purge_validator_information_aux(validators: Vec<GenesisValidator>) {
         assert!(!consensus_val_set.at(&ep).is_empty(&s).unwrap());
     }
 }
+
+/// Test the validation of commission rate changes and the schedule of the
+/// pending commission rates
+#[test]
+fn test_commission_rate_change_schedule() {
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(1, vec![token::Amount::native_whole(1)]);
+    let validator = genesis_validators[0].address.clone();
+    let initial_rate = genesis_validators[0].commission_rate;
+    let params = test_init_genesis(
+        &mut storage,
+        OwnedPosParams::default(),
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    // The genesis validators can change their rate by 0.01 per epoch
+    let too_large = Dec::new(7, 2).unwrap();
+    let err = validate_commission_rate_change(
+        &storage,
+        &validator,
+        too_large,
+        current_epoch,
+    )
+    .unwrap_err();
+    assert_matches!(
+        err.downcast::<CommissionRateChangeError>().unwrap().deref(),
+        CommissionRateChangeError::RateChangeTooLarge(_, _)
+    );
+
+    let new_rate = Dec::new(6, 2).unwrap();
+    assert!(validate_commission_rate_change(
+        &storage,
+        &validator,
+        new_rate,
+        current_epoch
+    )
+    .unwrap());
+    change_validator_commission_rate(
+        &mut storage,
+        &validator,
+        new_rate,
+        current_epoch,
+    )
+    .unwrap();
+    // Setting the same rate again is a no-op
+    assert!(!validate_commission_rate_change(
+        &storage,
+        &validator,
+        new_rate,
+        current_epoch
+    )
+    .unwrap());
+
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    let mut pending_rates = BTreeMap::new();
+    for epoch in
+        Epoch::iter_bounds_inclusive(current_epoch.next(), pipeline_epoch)
+    {
+        let rate = if epoch == pipeline_epoch {
+            new_rate
+        } else {
+            initial_rate
+        };
+        pending_rates.insert(epoch, rate);
+    }
+    assert_eq!(
+        read_validator_commission_schedule(
+            &storage,
+            &params,
+            &validator,
+            current_epoch
+        )
+        .unwrap(),
+        Some(CommissionSchedule {
+            epoch: current_epoch,
+            current_rate: initial_rate,
+            pending_rates,
+            max_commission_change_per_epoch: Dec::new(1, 2).unwrap(),
+        })
+    );
+    assert_eq!(
+        read_validator_commission_schedule(
+            &storage,
+            &params,
+            &address::testing::gen_established_address(),
+            current_epoch
+        )
+        .unwrap(),
+        None
+    );
+}
+
+/// Test that the validators must meet the minimum commission rate and the
+/// required metadata of the PoS parameters
+#[test]
+fn test_validator_minimum_requirements() {
+    let mut storage = TestState::default();
+    let current_epoch = storage.in_mem().block.epoch;
+    let genesis_validators =
+        get_genesis_validators(1, vec![token::Amount::native_whole(1)]);
+    let validator = genesis_validators[0].address.clone();
+    let params = test_init_genesis(
+        &mut storage,
+        OwnedPosParams::default(),
+        genesis_validators.into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+
+    // Raise the requirements after genesis
+    let min_commission_rate = Dec::new(4, 2).unwrap();
+    let mut owned = params.owned.clone();
+    owned.min_commission_rate = min_commission_rate;
+    owned.required_validator_metadata =
+        BTreeSet::from([ValidatorMetaDataField::Website]);
+    write_pos_params(&mut storage, &owned).unwrap();
+    let params = read_pos_params(&storage).unwrap();
+
+    // The commission rate cannot be changed to a rate below the minimum
+    let err = validate_commission_rate_change(
+        &storage,
+        &validator,
+        Dec::new(3, 2).unwrap(),
+        current_epoch,
+    )
+    .unwrap_err();
+    assert_matches!(
+        err.downcast::<CommissionRateChangeError>().unwrap().deref(),
+        CommissionRateChangeError::LowerThanMinimum(_, _, _)
+    );
+
+    // The metadata cannot be changed without the required fields
+    let err = change_validator_metadata(
+        &mut storage,
+        &validator,
+        None,
+        Some("A validator".to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        current_epoch,
+    )
+    .unwrap_err();
+    assert_matches!(
+        err.downcast::<MetadataError>().unwrap().deref(),
+        MetadataError::MissingRequired(_)
+    );
+    change_validator_metadata(
+        &mut storage,
+        &validator,
+        None,
+        None,
+        Some("https://validator.example".to_string()),
+        None,
+        None,
+        None,
+        None,
+        current_epoch,
+    )
+    .unwrap();
+    validate_validator_metadata(&storage, &validator).unwrap();
+
+    // A new validator must meet the requirements too
+    let new_validator = address::testing::established_address_2();
+    let consensus_key = common_sk_from_simple_seed(100).ref_to();
+    let protocol_key = key::testing::keypair_1().ref_to();
+    let eth_hot_key = key::testing::keypair_3().ref_to();
+    let eth_cold_key = key::testing::keypair_3().ref_to();
+    let args = |commission_rate, metadata| BecomeValidator {
+        params: &params,
+        address: &new_validator,
+        consensus_key: &consensus_key,
+        protocol_key: &protocol_key,
+        eth_cold_key: &eth_cold_key,
+        eth_hot_key: &eth_hot_key,
+        current_epoch,
+        commission_rate,
+        max_commission_rate_change: Dec::new(1, 2).unwrap(),
+        metadata,
+        offset_opt: None,
+    };
+    let metadata = |website: Option<&str>| ValidatorMetaData {
+        email: "validator@example.com".to_string(),
+        description: None,
+        website: website.map(str::to_string),
+        discord_handle: None,
+        avatar: None,
+        name: None,
+    };
+    let err = become_validator(
+        &mut storage,
+        args(
+            Dec::new(3, 2).unwrap(),
+            metadata(Some("https://new.example")),
+        ),
+    )
+    .unwrap_err();
+    assert_matches!(
+        err.downcast::<BecomeValidatorError>().unwrap().deref(),
+        BecomeValidatorError::CommissionRateTooLow(_, _)
+    );
+    let err = become_validator(
+        &mut storage,
+        args(min_commission_rate, metadata(None)),
+    )
+    .unwrap_err();
+    assert_matches!(
+        err.downcast::<BecomeValidatorError>().unwrap().deref(),
+        BecomeValidatorError::MissingMetadata(_)
+    );
+    become_validator(
+        &mut storage,
+        args(min_commission_rate, metadata(Some("https://new.example"))),
+    )
+    .unwrap();
+    assert!(is_validator(&storage, &new_validator).unwrap());
+}
@@ -13,7 +13,7 @@ use namada_core::address::Address;
 use namada_core::collections::HashMap;
 use namada_core::dec::Dec;
 use namada_core::key::common;
-use namada_core::storage::{Epoch, KeySeg};
+use namada_core::storage::{BlockHeight, Epoch, KeySeg};
 use namada_core::token;
 use namada_core::token::Amount;
 use namada_macros::BorshDeserializer;
@@ -306,6 +306,61 @@ pub struct CommissionPair {
     pub epoch: Epoch,
 }
 
+#[derive(
+    Debug,
+    Clone,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    PartialEq,
+    Eq,
+)]
+/// The commission rate of a validator in the current epoch and its pending
+/// changes up to the pipeline epoch
+pub struct CommissionSchedule {
+    /// The current epoch
+    pub epoch: Epoch,
+    /// Validator commission rate in the current epoch
+    pub current_rate: Dec,
+    /// Validator commission rates in the epochs after the current one, up to
+    /// and including the pipeline epoch
+    pub pending_rates: BTreeMap<Epoch, Dec>,
+    /// Validator max commission rate change per epoch
+    pub max_commission_change_per_epoch: Dec,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    PartialEq,
+    Eq,
+)]
+/// Estimated annualized PoS rewards rates of a validator, derived from the
+/// current inflation, total stake and the validator's commission rate
+pub struct ValidatorRewardsEstimate {
+    /// The epoch of the estimate
+    pub epoch: Epoch,
+    /// PoS inflation per epoch as computed by the PD controller from the
+    /// current state
+    pub epoch_inflation: token::Amount,
+    /// Ratio of the staked tokens to the total native tokens supply
+    pub staked_ratio: Dec,
+    /// Whether the validator is in the consensus set and is thus earning
+    /// rewards
+    pub is_consensus: bool,
+    /// Annual rewards rate of the validator's total stake, before the
+    /// commission is taken out
+    pub gross_rate: Dec,
+    /// Validator commission rate
+    pub commission_rate: Dec,
+    /// Annual rewards rate of the delegations to the validator, after the
+    /// commission is taken out
+    pub delegator_rate: Dec,
+}
+
 /// Epoched rewards products
 pub type RewardsProducts = LazyMap<Epoch, Dec>;
 
@@ -409,6 +464,76 @@ impl Default for ValidatorMetaData {
     }
 }
 
+impl ValidatorMetaData {
+    /// Get the fields among the given ones that are not set
+    pub fn missing_fields<'a>(
+        &self,
+        fields: impl IntoIterator<Item = &'a ValidatorMetaDataField>,
+    ) -> Vec<ValidatorMetaDataField> {
+        fields
+            .into_iter()
+            .filter(|field| !self.is_set(**field))
+            .copied()
+            .collect()
+    }
+
+    /// Check if the given field is set and not empty
+    pub fn is_set(&self, field: ValidatorMetaDataField) -> bool {
+        let value = match field {
+            ValidatorMetaDataField::Description => &self.description,
+            ValidatorMetaDataField::Website => &self.website,
+            ValidatorMetaDataField::DiscordHandle => &self.discord_handle,
+            ValidatorMetaDataField::Avatar => &self.avatar,
+            ValidatorMetaDataField::Name => &self.name,
+        };
+        value.as_ref().is_some_and(|value| !value.is_empty())
+    }
+}
+
+/// An optional field of the [`ValidatorMetaData`], which the PoS parameters
+/// may require the validators to set
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    BorshSerialize,
+    BorshSchema,
+    BorshDeserialize,
+    BorshDeserializer,
+    Deserialize,
+    Serialize,
+    Eq,
+    Ord,
+    PartialOrd,
+    PartialEq,
+    Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorMetaDataField {
+    /// The validator description
+    Description,
+    /// The validator website
+    Website,
+    /// The validator's discord handle
+    DiscordHandle,
+    /// The validator avatar
+    Avatar,
+    /// The validator's name
+    Name,
+}
+
+impl Display for ValidatorMetaDataField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Description => write!(f, "description"),
+            Self::Website => write!(f, "website"),
+            Self::DiscordHandle => write!(f, "discord_handle"),
+            Self::Avatar => write!(f, "avatar"),
+            Self::Name => write!(f, "name"),
+        }
+    }
+}
+
 /// An update of the consensus and below-capacity validator set.
 #[derive(Debug, Clone, PartialEq)]
 pub enum ValidatorSetUpdate {
@@ -740,6 +865,93 @@ impl Display for SlashType {
     }
 }
 
+/// The reason a validator was jailed.
+#[derive(
+    Debug,
+    Clone,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSerialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+)]
+pub enum JailReason {
+    /// Missed too many votes within the liveness window.
+    Downtime {
+        /// The number of missed votes in the liveness window
+        missed_votes: u64,
+    },
+    /// Evidence of a slashable misbehavior, e.g. a double-sign.
+    Misbehavior {
+        /// The type of the misbehavior
+        slash_type: SlashType,
+        /// Epoch at which the misbehavior occurred
+        evidence_epoch: Epoch,
+        /// Block height at which the misbehavior occurred
+        evidence_height: u64,
+    },
+}
+
+impl Display for JailReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JailReason::Downtime { missed_votes } => write!(
+                f,
+                "Downtime, {missed_votes} missed votes in the liveness window"
+            ),
+            JailReason::Misbehavior {
+                slash_type,
+                evidence_epoch,
+                evidence_height,
+            } => write!(
+                f,
+                "{slash_type} at block height {evidence_height} in epoch \
+                 {evidence_epoch}"
+            ),
+        }
+    }
+}
+
+/// A record of a validator being jailed.
+#[derive(
+    Debug,
+    Clone,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSerialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+)]
+pub struct JailRecord {
+    /// Epoch from which the validator is jailed
+    pub epoch: Epoch,
+    /// The reason the validator was jailed
+    pub reason: JailReason,
+}
+
+/// A change of a validator's consensus key that is not in effect yet
+#[derive(
+    Debug,
+    Clone,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSerialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+)]
+pub struct PendingConsensusKey {
+    /// The new consensus key
+    pub consensus_key: common::PublicKey,
+    /// The epoch from which the new key is used for consensus
+    pub epoch: Epoch,
+    /// The height of the first block of the epoch. It is only known once the
+    /// start of the epoch has been scheduled, a couple of blocks before it.
+    pub height: Option<BlockHeight>,
+}
+
 /// Calculate voting power in the tendermint context (which is stored as i64)
 /// from the number of tokens
 pub fn into_tm_voting_power(votes_per_token: Dec, tokens: Amount) -> i64 {
@@ -15,16 +15,18 @@ use namada_core::storage::{BlockHeight, Epoch};
 use namada_core::time::DateTimeUtc;
 use namada_core::{storage, token};
 use namada_governance::cli::onchain::{
-    DefaultProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, EmergencyPauseProposal, ExpeditedProposal,
+    PgfFundingProposal, PgfStewardElectionProposal, PgfStewardProposal,
 };
 use namada_tx::data::GasLimit;
-use namada_tx::Memo;
+use namada_tx::{DataEncoding, Memo};
 use serde::{Deserialize, Serialize};
 use zeroize::Zeroizing;
 
 use crate::eth_bridge::bridge_pool;
 use crate::ibc::core::host::types::identifiers::{ChannelId, PortId};
 use crate::signing::SigningTxData;
+use crate::wallet::keystore::KeyFormat;
 use crate::{rpc, tx, Namada};
 
 /// [`Duration`](StdDuration) wrapper that provides a
@@ -296,6 +298,70 @@ impl TxTransfer {
     }
 }
 
+/// Arguments of a shielded transfer of several assets in a single MASP
+/// transaction
+#[derive(Clone, Debug)]
+pub struct TxShieldedMultiAssetTransfer<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Transfer source, which must be a spending key
+    pub source: C::TransferSource,
+    /// Transfer target, which must be a payment address
+    pub target: C::TransferTarget,
+    /// Transferred token addresses and amounts
+    pub assets: Vec<(C::Address, InputAmount)>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxShieldedMultiAssetTransfer<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxShieldedMultiAssetTransfer {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxShieldedMultiAssetTransfer<C> {
+    /// Transfer source spending key
+    pub fn source(self, source: C::TransferSource) -> Self {
+        Self { source, ..self }
+    }
+
+    /// Transfer target payment address
+    pub fn receiver(self, target: C::TransferTarget) -> Self {
+        Self { target, ..self }
+    }
+
+    /// Transferred token addresses and amounts
+    pub fn assets(self, assets: Vec<(C::Address, InputAmount)>) -> Self {
+        Self { assets, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxShieldedMultiAssetTransfer {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &mut self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData, Option<Epoch>)>
+    {
+        tx::build_shielded_multi_asset_transfer(context, self).await
+    }
+}
+
 /// IBC transfer transaction arguments
 #[derive(Clone, Debug)]
 pub struct TxIbcTransfer<C: NamadaTypes = SdkTypes> {
@@ -431,6 +497,12 @@ pub struct InitProposal<C: NamadaTypes = SdkTypes> {
     pub is_pgf_stewards: bool,
     /// Flag if proposal is of type Pgf funding
     pub is_pgf_funding: bool,
+    /// Flag if proposal is of type Pgf steward election
+    pub is_pgf_steward_election: bool,
+    /// Flag if proposal is of type emergency pause
+    pub is_emergency_pause: bool,
+    /// Flag if proposal is of type expedited with wasm
+    pub is_expedited: bool,
     /// Path to the tx WASM file
     pub tx_code_path: PathBuf,
 }
@@ -472,6 +544,33 @@ impl<C: NamadaTypes> InitProposal<C> {
         }
     }
 
+    /// Flag if proposal is of type Pgf steward election
+    pub fn is_pgf_steward_election(
+        self,
+        is_pgf_steward_election: bool,
+    ) -> Self {
+        Self {
+            is_pgf_steward_election,
+            ..self
+        }
+    }
+
+    /// Flag if proposal is of type emergency pause
+    pub fn is_emergency_pause(self, is_emergency_pause: bool) -> Self {
+        Self {
+            is_emergency_pause,
+            ..self
+        }
+    }
+
+    /// Flag if proposal is of type expedited with wasm
+    pub fn is_expedited(self, is_expedited: bool) -> Self {
+        Self {
+            is_expedited,
+            ..self
+        }
+    }
+
     /// Path to the tx WASM file
     pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
         Self {
@@ -534,6 +633,91 @@ impl InitProposal {
                 })?;
 
             tx::build_pgf_stewards_proposal(context, self, proposal).await
+        } else if self.is_pgf_steward_election {
+            let proposal = PgfStewardElectionProposal::try_from(
+                self.proposal_data.as_ref(),
+            )
+            .map_err(|e| {
+                crate::error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                    e.to_string(),
+                )
+            })?;
+            let nam_address = context.native_token();
+            let author_balance = rpc::get_token_balance(
+                context.client(),
+                &nam_address,
+                &proposal.proposal.author,
+            )
+            .await?;
+            let proposal = proposal
+                .validate(
+                    &governance_parameters,
+                    current_epoch,
+                    author_balance,
+                    self.tx.force,
+                )
+                .map_err(|e| {
+                    crate::error::TxSubmitError::InvalidProposal(e.to_string())
+                })?;
+
+            tx::build_pgf_steward_election_proposal(context, self, proposal)
+                .await
+        } else if self.is_emergency_pause {
+            let proposal = EmergencyPauseProposal::try_from(
+                self.proposal_data.as_ref(),
+            )
+            .map_err(|e| {
+                crate::error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                    e.to_string(),
+                )
+            })?;
+            let nam_address = context.native_token();
+            let author_balance = rpc::get_token_balance(
+                context.client(),
+                &nam_address,
+                &proposal.proposal.author,
+            )
+            .await?;
+            let proposal = proposal
+                .validate(
+                    &governance_parameters,
+                    current_epoch,
+                    author_balance,
+                    self.tx.force,
+                )
+                .map_err(|e| {
+                    crate::error::TxSubmitError::InvalidProposal(e.to_string())
+                })?;
+
+            tx::build_emergency_pause_proposal(context, self, proposal).await
+        } else if self.is_expedited {
+            let proposal = ExpeditedProposal::try_from(
+                self.proposal_data.as_ref(),
+            )
+            .map_err(|e| {
+                crate::error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                    e.to_string(),
+                )
+            })?;
+            let nam_address = context.native_token();
+            let author_balance = rpc::get_token_balance(
+                context.client(),
+                &nam_address,
+                &proposal.proposal.author,
+            )
+            .await?;
+            let proposal = proposal
+                .validate(
+                    &governance_parameters,
+                    current_epoch,
+                    author_balance,
+                    self.tx.force,
+                )
+                .map_err(|e| {
+                    crate::error::TxSubmitError::InvalidProposal(e.to_string())
+                })?;
+
+            tx::build_expedited_proposal(context, self, proposal).await
         } else {
             let proposal = DefaultProposal::try_from(
                 self.proposal_data.as_ref(),
@@ -923,6 +1107,318 @@ impl TxUpdateAccount {
     }
 }
 
+/// Transaction to add or remove public keys of an account and change its
+/// threshold
+#[derive(Clone, Debug)]
+pub struct TxUpdateAccountSigners<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+    /// Address of the account whose signers are to be updated
+    pub addr: C::Address,
+    /// Public keys to add to the account
+    pub add_public_keys: Vec<C::PublicKey>,
+    /// Public keys to remove from the account
+    pub remove_public_keys: Vec<C::PublicKey>,
+    /// The new account threshold
+    pub threshold: Option<u8>,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxUpdateAccountSigners<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxUpdateAccountSigners {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxUpdateAccountSigners<C> {
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+
+    /// Public keys to add to the account
+    pub fn add_public_keys(self, add_public_keys: Vec<C::PublicKey>) -> Self {
+        Self {
+            add_public_keys,
+            ..self
+        }
+    }
+
+    /// Public keys to remove from the account
+    pub fn remove_public_keys(
+        self,
+        remove_public_keys: Vec<C::PublicKey>,
+    ) -> Self {
+        Self {
+            remove_public_keys,
+            ..self
+        }
+    }
+
+    /// The new account threshold
+    pub fn threshold(self, threshold: u8) -> Self {
+        Self {
+            threshold: Some(threshold),
+            ..self
+        }
+    }
+}
+
+impl TxUpdateAccountSigners {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_update_account_signers(context, self).await
+    }
+}
+
+/// Transaction to register, update or delete a name in the name registry
+#[derive(Clone, Debug)]
+pub struct TxUpdateName<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+    /// The name
+    pub name: String,
+    /// The new owner of the name
+    pub owner: C::Address,
+    /// The new target of the name, or `None` to delete it
+    pub target: Option<C::Address>,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxUpdateName<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxUpdateName {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxUpdateName<C> {
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+
+    /// The new owner of the name
+    pub fn owner(self, owner: C::Address) -> Self {
+        Self { owner, ..self }
+    }
+
+    /// The new target of the name
+    pub fn target(self, target: C::Address) -> Self {
+        Self {
+            target: Some(target),
+            ..self
+        }
+    }
+}
+
+impl TxUpdateName {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_update_name(context, self).await
+    }
+}
+
+/// Transaction to withdraw tokens from a faucet
+#[derive(Clone, Debug)]
+pub struct TxFaucetWithdraw<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+    /// The faucet account
+    pub faucet: C::Address,
+    /// The address receiving the tokens
+    pub target: C::Address,
+    /// The withdrawn token
+    pub token: C::Address,
+    /// The withdrawn amount
+    pub amount: InputAmount,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxFaucetWithdraw<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxFaucetWithdraw {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxFaucetWithdraw<C> {
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxFaucetWithdraw {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_faucet_withdraw(context, self).await
+    }
+}
+
+/// Transaction to set the withdrawal limits of a token of a faucet
+#[derive(Clone, Debug)]
+pub struct TxUpdateFaucetLimits<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+    /// The faucet account
+    pub faucet: C::Address,
+    /// The token
+    pub token: C::Address,
+    /// The maximum amount withdrawn to a single address per epoch
+    pub per_address: InputAmount,
+    /// The maximum amount withdrawn to all addresses per epoch
+    pub per_epoch: InputAmount,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxUpdateFaucetLimits<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxUpdateFaucetLimits {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxUpdateFaucetLimits<C> {
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxUpdateFaucetLimits {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_update_faucet_limits(context, self).await
+    }
+}
+
+/// Transaction to update or remove the transfer policy of an account
+#[derive(Clone, Debug)]
+pub struct TxUpdateTransferPolicy<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+    /// The account
+    pub owner: C::Address,
+    /// The token whose maximum amount per tx is set
+    pub token: Option<C::Address>,
+    /// The maximum amount of the token that a single tx may debit
+    pub max_amount: Option<InputAmount>,
+    /// The new allowed counterparties, if any
+    pub counterparties: Vec<C::Address>,
+    /// Remove the transfer policy
+    pub remove: bool,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxUpdateTransferPolicy<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxUpdateTransferPolicy {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxUpdateTransferPolicy<C> {
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+
+    /// The maximum amount of a token that a single tx may debit
+    pub fn max_amount(
+        self,
+        token: C::Address,
+        max_amount: InputAmount,
+    ) -> Self {
+        Self {
+            token: Some(token),
+            max_amount: Some(max_amount),
+            ..self
+        }
+    }
+
+    /// The new allowed counterparties
+    pub fn counterparties(self, counterparties: Vec<C::Address>) -> Self {
+        Self {
+            counterparties,
+            ..self
+        }
+    }
+
+    /// Remove the transfer policy
+    pub fn remove(self, remove: bool) -> Self {
+        Self { remove, ..self }
+    }
+}
+
+impl TxUpdateTransferPolicy {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_update_transfer_policy(context, self).await
+    }
+}
+
 /// Bond arguments
 #[derive(Clone, Debug)]
 pub struct Bond<C: NamadaTypes = SdkTypes> {
@@ -1189,6 +1685,17 @@ pub struct QueryProposal<C: NamadaTypes = SdkTypes> {
     pub proposal_id: Option<u64>,
 }
 
+/// Verify an off-chain proposal content against the hash anchored on chain
+#[derive(Clone, Debug)]
+pub struct VerifyProposalContent<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Proposal id
+    pub proposal_id: u64,
+    /// The off-chain proposal content
+    pub content: C::Data,
+}
+
 /// Query protocol parameters
 #[derive(Clone, Debug)]
 pub struct QueryProtocolParameters<C: NamadaTypes = SdkTypes> {
@@ -1376,6 +1883,15 @@ pub struct QueryValidatorState<C: NamadaTypes = SdkTypes> {
     pub epoch: Option<Epoch>,
 }
 
+/// Query the pending change of a validator's consensus key
+#[derive(Clone, Debug)]
+pub struct QueryPendingConsensusKey<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Address of a validator
+    pub validator: C::Address,
+}
+
 #[derive(Clone, Debug)]
 /// Commission rate change args
 pub struct CommissionRateChange<C: NamadaTypes = SdkTypes> {
@@ -1609,6 +2125,85 @@ impl MetaDataChange {
     }
 }
 
+#[derive(Clone, Debug)]
+/// Validator operational metadata publication args
+pub struct OperationalMetadataChange<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Validator address
+    pub validator: C::Address,
+    /// The validator's protocol key, which signs the protocol tx. Defaults to
+    /// the validator's protocol key in the current epoch.
+    pub protocol_key: Option<C::PublicKey>,
+    /// URL of a public RPC endpoint
+    pub rpc_endpoint: Option<String>,
+    /// Address of a seed node
+    pub seed_node: Option<String>,
+    /// Contact for emergencies
+    pub emergency_contact: Option<String>,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for OperationalMetadataChange<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        OperationalMetadataChange {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> OperationalMetadataChange<C> {
+    /// Validator address
+    pub fn validator(self, validator: C::Address) -> Self {
+        Self { validator, ..self }
+    }
+
+    /// The validator's protocol key
+    pub fn protocol_key(self, protocol_key: C::PublicKey) -> Self {
+        Self {
+            protocol_key: Some(protocol_key),
+            ..self
+        }
+    }
+
+    /// URL of a public RPC endpoint
+    pub fn rpc_endpoint(self, rpc_endpoint: String) -> Self {
+        Self {
+            rpc_endpoint: Some(rpc_endpoint),
+            ..self
+        }
+    }
+
+    /// Address of a seed node
+    pub fn seed_node(self, seed_node: String) -> Self {
+        Self {
+            seed_node: Some(seed_node),
+            ..self
+        }
+    }
+
+    /// Contact for emergencies
+    pub fn emergency_contact(self, emergency_contact: String) -> Self {
+        Self {
+            emergency_contact: Some(emergency_contact),
+            ..self
+        }
+    }
+}
+
+impl OperationalMetadataChange {
+    /// Build the protocol tx signed with the validator's protocol key
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<namada_tx::Tx> {
+        tx::build_operational_metadata_change(context, self).await
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Commission rate change args
 pub struct UpdateStewardCommission<C: NamadaTypes = SdkTypes> {
@@ -1906,6 +2501,18 @@ pub struct QueryMetaData<C: NamadaTypes = SdkTypes> {
     pub validator: C::Address,
 }
 
+/// Query the validators' telemetry
+#[derive(Clone, Debug)]
+pub struct QueryTelemetry<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Address of a validator whose last heartbeat to query, instead of the
+    /// summary of the consensus validators
+    pub validator: Option<C::Address>,
+    /// Epoch of the consensus validators to summarize
+    pub epoch: Option<Epoch>,
+}
+
 /// Query PoS slashes
 #[derive(Clone, Debug)]
 pub struct QuerySlashes<C: NamadaTypes = SdkTypes> {
@@ -1926,6 +2533,39 @@ pub struct QueryRewards<C: NamadaTypes = SdkTypes> {
     pub validator: C::Address,
 }
 
+/// Query the difference of the chain parameters between two heights
+#[derive(Clone, Debug)]
+pub struct QueryParametersDiff<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The height of the old parameters
+    pub from_height: Option<BlockHeight>,
+    /// The epoch of the old parameters, from its first height
+    pub from_epoch: Option<Epoch>,
+    /// The height of the new parameters, the last committed height if
+    /// neither it nor `to_epoch` is set
+    pub to_height: Option<BlockHeight>,
+    /// The epoch of the new parameters, from its first height
+    pub to_epoch: Option<Epoch>,
+}
+
+/// Verify the wasm artifacts against their checksums and the code deployed on
+/// chain
+#[derive(Clone, Debug)]
+pub struct VerifyWasm<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+}
+
+/// Query the staking overview of an owner
+#[derive(Clone, Debug)]
+pub struct QueryStakingOverview<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Address of the owner
+    pub owner: C::Address,
+}
+
 /// Query PoS delegations
 #[derive(Clone, Debug)]
 pub struct QueryDelegations<C: NamadaTypes = SdkTypes> {
@@ -2014,6 +2654,16 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     pub fee_token: C::AddrOrNativeToken,
     /// The optional spending key for fee unshielding
     pub fee_unshield: Option<C::TransferSource>,
+    /// The amount unshielded in addition to the shortfall of the transparent
+    /// balance of the fee payer, in case the balance changes before the tx is
+    /// applied
+    pub fee_unshield_headroom: Option<InputAmount>,
+    /// Pay the whole fee out of the shielded pool, straight to the block
+    /// proposer, without crediting the transparent balance of the fee payer
+    pub shielded_fee: bool,
+    /// Display the breakdown of the fee and ask for a confirmation before
+    /// signing the tx
+    pub confirm_fees: bool,
     /// The max amount of gas used to process tx
     pub gas_limit: GasLimit,
     /// The optional expiration of the transaction
@@ -2035,6 +2685,9 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     pub memo: Option<Memo>,
     /// Use device to sign the transaction
     pub use_device: bool,
+    /// The encoding of the transaction data. The encodings other than Borsh
+    /// are only supported by some transactions.
+    pub data_encoding: DataEncoding,
 }
 
 /// Builder functions for Tx
@@ -2130,6 +2783,24 @@ pub trait TxBuilder<C: NamadaTypes>: Sized {
             ..x
         })
     }
+    /// The amount unshielded in addition to the shortfall of the transparent
+    /// balance of the fee payer
+    fn fee_unshield_headroom(self, fee_unshield_headroom: InputAmount) -> Self {
+        self.tx(|x| Tx {
+            fee_unshield_headroom: Some(fee_unshield_headroom),
+            ..x
+        })
+    }
+    /// Pay the whole fee out of the shielded pool, straight to the block
+    /// proposer
+    fn shielded_fee(self, shielded_fee: bool) -> Self {
+        self.tx(|x| Tx { shielded_fee, ..x })
+    }
+    /// Display the breakdown of the fee and ask for a confirmation before
+    /// signing the tx
+    fn confirm_fees(self, confirm_fees: bool) -> Self {
+        self.tx(|x| Tx { confirm_fees, ..x })
+    }
     /// The max amount of gas used to process tx
     fn gas_limit(self, gas_limit: GasLimit) -> Self {
         self.tx(|x| Tx { gas_limit, ..x })
@@ -2175,6 +2846,10 @@ pub trait TxBuilder<C: NamadaTypes>: Sized {
             ..x
         })
     }
+    /// The encoding of the transaction data
+    fn data_encoding(self, data_encoding: DataEncoding) -> Self {
+        self.tx(|x| Tx { data_encoding, ..x })
+    }
 }
 
 impl<C: NamadaTypes> TxBuilder<C> for Tx<C> {
@@ -2209,6 +2884,25 @@ pub struct KeyGen {
     pub allow_non_compliant: bool,
 }
 
+/// Wallet vanity key and implicit address generation arguments
+#[derive(Clone, Debug)]
+pub struct KeyGenVanity {
+    /// Scheme type
+    pub scheme: SchemeType,
+    /// The prefix of the address
+    pub prefix: Option<String>,
+    /// The suffix of the address
+    pub suffix: Option<String>,
+    /// The number of threads of the search, the number of CPUs by default
+    pub threads: Option<usize>,
+    /// Key alias
+    pub alias: String,
+    /// Whether to force overwrite the alias
+    pub alias_force: bool,
+    /// Don't encrypt the keypair
+    pub unsafe_dont_encrypt: bool,
+}
+
 /// Wallet restore key and implicit address arguments
 #[derive(Clone, Debug)]
 pub struct KeyDerive {
@@ -2276,6 +2970,8 @@ pub struct KeyAddressFind {
 pub struct KeyExport {
     /// Key alias
     pub alias: String,
+    /// The format of the exported key
+    pub format: KeyFormat,
 }
 
 /// Wallet key export arguments
@@ -2290,6 +2986,8 @@ pub struct KeyConvert {
 pub struct KeyImport {
     /// File name
     pub file_path: String,
+    /// The format of the imported key
+    pub format: KeyFormat,
     /// Key alias
     pub alias: String,
     /// Whether to force overwrite the alias
@@ -2329,6 +3027,15 @@ pub struct PayAddressGen<C: NamadaTypes = SdkTypes> {
     pub alias_force: bool,
     /// Viewing key
     pub viewing_key: C::ViewingKey,
+    /// The diversifier index from which to derive the payment address
+    pub diversifier_index: Option<u64>,
+}
+
+/// List the payment addresses of a viewing key that received notes
+#[derive(Clone, Debug)]
+pub struct PayAddressList<C: NamadaTypes = SdkTypes> {
+    /// Viewing key
+    pub viewing_key: C::ViewingKey,
 }
 
 /// Bridge pool batch recommendation.
@@ -4,8 +4,10 @@ use namada_core::address::Address;
 use namada_core::dec::Dec;
 use namada_core::ethereum_events::EthAddress;
 use namada_core::storage::Epoch;
+use namada_core::token::DenominatedAmountError;
 use namada_core::{arith, storage};
 use namada_events::EventError;
+use namada_parameters::TxTooLarge;
 use namada_tx::Tx;
 use prost::EncodeError;
 use tendermint_rpc::Error as RpcError;
@@ -40,6 +42,9 @@ pub enum Error {
     EthereumBridge(#[from] EthereumBridgeError),
     #[error("Arithmetic {0}")]
     Arith(#[from] arith::Error),
+    /// Errors of the arithmetic on denominated amounts
+    #[error("{0}")]
+    DenominatedAmount(#[from] DenominatedAmountError),
     /// Any Other errors that are uncategorized
     #[error("{0}")]
     Other(String),
@@ -107,6 +112,9 @@ pub enum TxSubmitError {
     /// Error during broadcasting a transaction
     #[error("Encountered error while broadcasting transaction: {0}")]
     TxBroadcast(RpcError),
+    /// The transaction exceeds the max tx size
+    #[error("{0}")]
+    TxTooLarge(TxTooLarge),
     /// Invalid commission rate set
     #[error("Invalid new commission rate, received {0}")]
     InvalidCommissionRate(Dec),
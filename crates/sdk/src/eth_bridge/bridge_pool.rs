@@ -761,7 +761,7 @@ mod recommendations {
         get_nonce_key, get_signed_root_key,
     };
 
-    const fn unsigned_transfer_fee() -> Uint {
+    pub(crate) const fn unsigned_transfer_fee() -> Uint {
         Uint::from_u64(37_500_u64)
     }
 
@@ -769,11 +769,11 @@ mod recommendations {
         I256(unsigned_transfer_fee())
     }
 
-    const fn signature_fee() -> Uint {
+    pub(crate) const fn signature_fee() -> Uint {
         Uint::from_u64(24_500)
     }
 
-    const fn valset_fee() -> Uint {
+    pub(crate) const fn valset_fee() -> Uint {
         Uint::from_u64(2000)
     }
 
@@ -820,18 +820,18 @@ mod recommendations {
     /// Batch of recommended transfers to Ethereum that generate
     /// a profit after a relay operation.
     #[derive(Debug, Eq, PartialEq)]
-    struct RecommendedBatch {
+    pub struct RecommendedBatch {
         /// Hashes of the recommended transfers to be relayed.
-        transfer_hashes: Vec<String>,
+        pub transfer_hashes: Vec<String>,
         /// Estimate of the total fees, measured in gwei, that will be paid
         /// on Ethereum.
-        ethereum_gas_fees: Uint,
+        pub ethereum_gas_fees: Uint,
         /// Net profitt in gwei, based on the conversion rates provided
         /// to the algorithm.
-        net_profit: I256,
+        pub net_profit: I256,
         /// Gas fees paid by the transfers considered for relaying,
         /// paid in various token types.
-        bridge_pool_gas_fees: HashMap<String, Uint>,
+        pub bridge_pool_gas_fees: HashMap<String, Uint>,
     }
 
     /// Recommend the most economical batch of transfers to relay based
@@ -841,12 +841,59 @@ mod recommendations {
         context: &impl Namada,
         args: args::RecommendBatch,
     ) -> Result<(), Error> {
+        find_recommended_batch(context.client(), context.io(), &args)
+            .await?
+            .map(
+                |RecommendedBatch {
+                     transfer_hashes,
+                     ethereum_gas_fees,
+                     net_profit,
+                     bridge_pool_gas_fees,
+                 }| {
+                    display_line!(
+                        context.io(),
+                        "Recommended batch: {transfer_hashes:#?}"
+                    );
+                    display_line!(
+                        context.io(),
+                        "Estimated Ethereum transaction gas (in gwei): \
+                         {ethereum_gas_fees}",
+                    );
+                    display_line!(
+                        context.io(),
+                        "Estimated net profit (in gwei): {net_profit}"
+                    );
+                    display_line!(
+                        context.io(),
+                        "Total fees: {bridge_pool_gas_fees:#?}"
+                    );
+                },
+            )
+            .unwrap_or_else(|| {
+                display_line!(
+                    context.io(),
+                    "Unable to find a recommendation satisfying the input \
+                     parameters."
+                );
+            });
+
+        Ok(())
+    }
+
+    /// Find the most economical batch of transfers to relay based on a
+    /// conversion rate estimates from NAM to ETH and gas usage heuristics,
+    /// if any satisfies the input parameters.
+    pub async fn find_recommended_batch(
+        client: &(impl Client + Sync),
+        io: &impl Io,
+        args: &args::RecommendBatch,
+    ) -> Result<Option<RecommendedBatch>, Error> {
         // get transfers that can already been relayed but are awaiting a quorum
         // of backing votes.
         let in_progress = RPC
             .shell()
             .eth_bridge()
-            .transfer_to_ethereum_progress(context.client())
+            .transfer_to_ethereum_progress(client)
             .await
             .map_err(|e| {
                 Error::EthereumBridge(
@@ -863,7 +910,7 @@ mod recommendations {
             <(BridgePoolRootProof, BlockHeight)>::try_from_slice(
                 &RPC.shell()
                     .storage_value(
-                        context.client(),
+                        client,
                         None,
                         None,
                         false,
@@ -872,7 +919,7 @@ mod recommendations {
                     .await
                     .map_err(|err| {
                         Error::Query(QueryError::General(echo_error!(
-                            context.io(),
+                            io,
                             "Failed to query Bridge pool proof: {err}"
                         )))
                     })?
@@ -880,7 +927,7 @@ mod recommendations {
             )
             .map_err(|err| {
                 Error::Encode(EncodingError::Decoding(echo_error!(
-                    context.io(),
+                    io,
                     "Failed to decode Bridge pool proof: {err}"
                 )))
             })?;
@@ -888,17 +935,11 @@ mod recommendations {
         // get the latest bridge pool nonce
         let latest_bp_nonce = EthUint::try_from_slice(
             &RPC.shell()
-                .storage_value(
-                    context.client(),
-                    None,
-                    None,
-                    false,
-                    &get_nonce_key(),
-                )
+                .storage_value(client, None, None, false, &get_nonce_key())
                 .await
                 .map_err(|err| {
                     Error::Query(QueryError::General(echo_error!(
-                        context.io(),
+                        io,
                         "Failed to query Bridge pool nonce: {err}"
                     )))
                 })?
@@ -906,14 +947,14 @@ mod recommendations {
         )
         .map_err(|err| {
             Error::Encode(EncodingError::Decoding(echo_error!(
-                context.io(),
+                io,
                 "Failed to decode Bridge pool nonce: {err}"
             )))
         })?;
 
         if latest_bp_nonce != bp_root.data.1 {
             edisplay_line!(
-                context.io(),
+                io,
                 "The signed Bridge pool nonce is not up to date, repeat this \
                  query at a later time"
             );
@@ -927,7 +968,7 @@ mod recommendations {
         let voting_powers = RPC
             .shell()
             .eth_bridge()
-            .voting_powers_at_height(context.client(), &height)
+            .voting_powers_at_height(client, &height)
             .await
             .map_err(|e| {
                 Error::EthereumBridge(EthereumBridgeError::QueryVotingPowers(
@@ -944,10 +985,10 @@ mod recommendations {
 
         // we don't recommend transfers that have already been relayed
         let eligible = generate_eligible(
-            context.io(),
+            io,
             &args.conversion_table,
             &in_progress,
-            query_signed_bridge_pool(context.client(), context.io()).await?,
+            query_signed_bridge_pool(client, io).await?,
         )?;
 
         let max_gas =
@@ -955,48 +996,13 @@ mod recommendations {
         let max_cost = args.gas.map(I256::from).unwrap_or_default();
 
         generate_recommendations(
-            context.io(),
+            io,
             eligible,
             &args.conversion_table,
             validator_gas,
             max_gas,
             max_cost,
-        )?
-        .map(
-            |RecommendedBatch {
-                 transfer_hashes,
-                 ethereum_gas_fees,
-                 net_profit,
-                 bridge_pool_gas_fees,
-             }| {
-                display_line!(
-                    context.io(),
-                    "Recommended batch: {transfer_hashes:#?}"
-                );
-                display_line!(
-                    context.io(),
-                    "Estimated Ethereum transaction gas (in gwei): \
-                     {ethereum_gas_fees}",
-                );
-                display_line!(
-                    context.io(),
-                    "Estimated net profit (in gwei): {net_profit}"
-                );
-                display_line!(
-                    context.io(),
-                    "Total fees: {bridge_pool_gas_fees:#?}"
-                );
-            },
         )
-        .unwrap_or_else(|| {
-            display_line!(
-                context.io(),
-                "Unable to find a recommendation satisfying the input \
-                 parameters."
-            );
-        });
-
-        Ok(())
     }
 
     /// Given an ordered list of signatures, figure out the size of the first
@@ -1004,7 +1010,7 @@ mod recommendations {
     ///
     /// The function is generic to make unit testing easier (otherwise a dev
     /// dependency needs to be added).
-    fn signature_checks<T>(
+    pub(crate) fn signature_checks<T>(
         voting_powers: VotingPowersMap,
         sigs: &HashMap<EthAddrBook, T>,
     ) -> Result<Uint, Error> {
@@ -1625,4 +1631,9 @@ mod recommendations {
     }
 }
 
-pub use recommendations::recommend_batch;
+pub use recommendations::{
+    find_recommended_batch, recommend_batch, RecommendedBatch,
+};
+pub(crate) use recommendations::{
+    signature_checks, signature_fee, unsigned_transfer_fee, valset_fee,
+};
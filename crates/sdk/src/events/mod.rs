@@ -1,13 +1,26 @@
 //! Logic to do with events emitted by the ledger.
 pub mod log;
+pub mod protocol;
 
 use namada_core::collections::HashMap;
 pub use namada_events::*;
+use namada_proof_of_stake::event::{
+    AutoRedelegated, ConsensusKeyActivation, EvidenceReceived,
+};
 use serde_json::Value;
 
 // use crate::ledger::governance::utils::ProposalEvent;
 use crate::error::{EncodingError, Error};
 
+/// The registry of the schemas of the typed events emitted by the ledger
+pub fn known_event_schemas() -> schema::EventSchemaRegistry {
+    schema::EventSchemaRegistry::default()
+        .with::<ConsensusKeyActivation>()
+        .with::<AutoRedelegated>()
+        .with::<EvidenceReceived>()
+        .with::<protocol::EpochTransition>()
+}
+
 /// A thin wrapper around a HashMap for parsing event JSONs
 /// returned in tendermint subscription responses.
 #[derive(Debug)]
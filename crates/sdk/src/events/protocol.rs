@@ -0,0 +1,242 @@
+//! Protocol events, of the changes of the chain made by the protocol rather
+//! than by txs.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use namada_core::storage::Epoch;
+use namada_core::token::DenominatedAmount;
+use namada_events::extend::{EventAttributeEntry, Height};
+use namada_events::{typed_event, EventLevel, EventToEmit};
+
+pub mod types {
+    //! Protocol event types.
+
+    use namada_events::{event_type, EventType};
+
+    use super::ProtocolEvent;
+
+    /// Epoch transition event.
+    pub const EPOCH_TRANSITION: EventType =
+        event_type!(ProtocolEvent, "epoch-transition");
+}
+
+/// Protocol event.
+#[derive(Debug)]
+pub struct ProtocolEvent;
+
+impl EventToEmit for ProtocolEvent {
+    const DOMAIN: &'static str = "protocol";
+}
+
+typed_event! {
+    /// Epoch transition event, emitted in the first block of every epoch
+    /// with a summary of the changes that took effect in it.
+    pub struct EpochTransition {
+        domain: ProtocolEvent,
+        event_type: types::EPOCH_TRANSITION,
+        version: 1,
+        level: EventLevel::Block,
+        attributes: {
+            /// The new epoch.
+            epoch: NewEpoch,
+            /// The height of the first block of the epoch.
+            height: Height,
+            /// The number of consensus validators in the new epoch.
+            consensus_validators: ConsensusValidators,
+            /// The number of validators that joined the consensus set.
+            validators_joined: ValidatorsJoined,
+            /// The number of validators that left the consensus set.
+            validators_left: ValidatorsLeft,
+            /// The ids of the governance proposals that passed and were
+            /// executed.
+            activated_proposals: ActivatedProposals,
+            /// The names of the protocol parameters whose scheduled changes
+            /// took effect.
+            activated_parameters: ActivatedParameters,
+            /// The native tokens minted by the PoS and PGF inflation of the
+            /// last epoch.
+            inflation: InflationMinted,
+        },
+    }
+}
+
+/// A list of values of an event attribute, encoded as comma separated values.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValueList<T>(pub Vec<T>);
+
+impl<T: Display> Display for ValueList<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, value) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{value}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: FromStr> FromStr for ValueList<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Ok(Self(vec![]));
+        }
+        s.split(',')
+            .map(T::from_str)
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// Extend an [`Event`](namada_events::Event) with a new epoch.
+pub struct NewEpoch(pub Epoch);
+
+impl EventAttributeEntry<'static> for NewEpoch {
+    type Value = Epoch;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "new-epoch";
+    const VALUE_TYPE: &'static str = "epoch";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`](namada_events::Event) with the number of consensus
+/// validators.
+pub struct ConsensusValidators(pub u64);
+
+impl EventAttributeEntry<'static> for ConsensusValidators {
+    type Value = u64;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "consensus-validators";
+    const VALUE_TYPE: &'static str = "u64";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`](namada_events::Event) with the number of validators
+/// that joined the consensus set.
+pub struct ValidatorsJoined(pub u64);
+
+impl EventAttributeEntry<'static> for ValidatorsJoined {
+    type Value = u64;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "validators-joined";
+    const VALUE_TYPE: &'static str = "u64";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`](namada_events::Event) with the number of validators
+/// that left the consensus set.
+pub struct ValidatorsLeft(pub u64);
+
+impl EventAttributeEntry<'static> for ValidatorsLeft {
+    type Value = u64;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "validators-left";
+    const VALUE_TYPE: &'static str = "u64";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`](namada_events::Event) with the ids of the activated
+/// governance proposals.
+pub struct ActivatedProposals(pub ValueList<u64>);
+
+impl EventAttributeEntry<'static> for ActivatedProposals {
+    type Value = ValueList<u64>;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "activated-proposals";
+    const VALUE_TYPE: &'static str = "list<u64>";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`](namada_events::Event) with the names of the activated
+/// protocol parameters.
+pub struct ActivatedParameters(pub ValueList<String>);
+
+impl EventAttributeEntry<'static> for ActivatedParameters {
+    type Value = ValueList<String>;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "activated-parameters";
+    const VALUE_TYPE: &'static str = "list<string>";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`](namada_events::Event) with the minted inflation.
+pub struct InflationMinted(pub DenominatedAmount);
+
+impl EventAttributeEntry<'static> for InflationMinted {
+    type Value = DenominatedAmount;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "inflation-minted";
+    const VALUE_TYPE: &'static str = "denominated-amount";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::storage::BlockHeight;
+    use namada_core::token::Amount;
+    use namada_events::schema::TypedEvent;
+    use namada_events::Event;
+
+    use super::*;
+
+    #[test]
+    fn test_epoch_transition_event() {
+        let typed = EpochTransition {
+            epoch: Epoch(4),
+            height: BlockHeight(40),
+            consensus_validators: 3,
+            validators_joined: 1,
+            validators_left: 0,
+            activated_proposals: ValueList(vec![2, 5]),
+            activated_parameters: ValueList(vec![]),
+            inflation: DenominatedAmount::native(Amount::native_whole(12)),
+        };
+        let event = Event::from(typed.clone());
+        assert_eq!(
+            event.read_attribute::<ActivatedProposals>().unwrap(),
+            ValueList(vec![2, 5])
+        );
+        assert_eq!(
+            event
+                .read_attribute::<InflationMinted>()
+                .unwrap()
+                .to_string(),
+            typed.inflation.to_string()
+        );
+        let decoded = EpochTransition::decode(&event).unwrap();
+        assert_eq!(decoded.epoch, typed.epoch);
+        assert_eq!(decoded.activated_proposals, typed.activated_proposals);
+        assert!(decoded.activated_parameters.0.is_empty());
+    }
+}
@@ -20,6 +20,7 @@ pub mod masp;
 pub mod signing;
 #[allow(clippy::result_large_err)]
 pub mod tx;
+pub mod tx_preview;
 
 pub mod control_flow;
 pub mod error;
@@ -46,7 +47,7 @@ use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
 use namada_core::key::*;
 use namada_core::masp::{TransferSource, TransferTarget};
 use namada_tx::data::wrapper::GasLimit;
-use namada_tx::Tx;
+use namada_tx::{DataEncoding, Tx};
 use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use crate::io::Io;
@@ -60,12 +61,14 @@ use crate::tx::{
     ProcessTxResponse, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
     TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
     TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
-    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
-    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM,
-    TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM,
-    TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-    TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
-    VP_USER_WASM,
+    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM,
+    TX_FAUCET_WITHDRAW_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
+    TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
+    TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
+    TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_SIGNERS_WASM,
+    TX_UPDATE_ACCOUNT_WASM, TX_UPDATE_FAUCET_LIMITS_WASM, TX_UPDATE_NAME_WASM,
+    TX_UPDATE_STEWARD_COMMISSION, TX_UPDATE_TRANSFER_POLICY_WASM,
+    TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM, VP_USER_WASM,
 };
 use crate::wallet::{Wallet, WalletIo, WalletStorage};
 
@@ -141,6 +144,9 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             wrapper_fee_payer: None,
             fee_token: self.native_token(),
             fee_unshield: None,
+            fee_unshield_headroom: None,
+            shielded_fee: false,
+            confirm_fees: false,
             gas_limit: GasLimit::from(DEFAULT_GAS_LIMIT),
             expiration: Default::default(),
             disposable_signing_key: false,
@@ -151,6 +157,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             password: None,
             memo: None,
             use_device: false,
+            data_encoding: DataEncoding::Borsh,
         }
     }
 
@@ -172,6 +179,23 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a TxShieldedMultiAssetTransfer builder from the given minimum set
+    /// of arguments
+    fn new_shielded_multi_asset_transfer(
+        &self,
+        source: TransferSource,
+        target: TransferTarget,
+        assets: Vec<(Address, InputAmount)>,
+    ) -> args::TxShieldedMultiAssetTransfer {
+        args::TxShieldedMultiAssetTransfer {
+            source,
+            target,
+            assets,
+            tx_code_path: PathBuf::from(TX_TRANSFER_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a InitAccount builder from the given minimum set of arguments
     fn new_init_account(
         &self,
@@ -279,6 +303,9 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             proposal_data,
             is_pgf_stewards: false,
             is_pgf_funding: false,
+            is_pgf_steward_election: false,
+            is_emergency_pause: false,
+            is_expedited: false,
             tx_code_path: PathBuf::from(TX_INIT_PROPOSAL),
             tx: self.tx_builder(),
         }
@@ -301,6 +328,92 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a TxUpdateAccountSigners builder from the given minimum set of
+    /// arguments
+    fn new_update_account_signers(
+        &self,
+        addr: Address,
+    ) -> args::TxUpdateAccountSigners {
+        args::TxUpdateAccountSigners {
+            addr,
+            add_public_keys: vec![],
+            remove_public_keys: vec![],
+            threshold: None,
+            tx_code_path: PathBuf::from(TX_UPDATE_ACCOUNT_SIGNERS_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a TxUpdateName builder from the given minimum set of arguments
+    fn new_update_name(
+        &self,
+        name: String,
+        owner: Address,
+        target: Option<Address>,
+    ) -> args::TxUpdateName {
+        args::TxUpdateName {
+            name,
+            owner,
+            target,
+            tx_code_path: PathBuf::from(TX_UPDATE_NAME_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a TxFaucetWithdraw builder from the given minimum set of arguments
+    fn new_faucet_withdraw(
+        &self,
+        faucet: Address,
+        target: Address,
+        token: Address,
+        amount: InputAmount,
+    ) -> args::TxFaucetWithdraw {
+        args::TxFaucetWithdraw {
+            faucet,
+            target,
+            token,
+            amount,
+            tx_code_path: PathBuf::from(TX_FAUCET_WITHDRAW_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a TxUpdateFaucetLimits builder from the given minimum set of
+    /// arguments
+    fn new_update_faucet_limits(
+        &self,
+        faucet: Address,
+        token: Address,
+        per_address: InputAmount,
+        per_epoch: InputAmount,
+    ) -> args::TxUpdateFaucetLimits {
+        args::TxUpdateFaucetLimits {
+            faucet,
+            token,
+            per_address,
+            per_epoch,
+            tx_code_path: PathBuf::from(TX_UPDATE_FAUCET_LIMITS_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a TxUpdateTransferPolicy builder from the given minimum set of
+    /// arguments
+    fn new_update_transfer_policy(
+        &self,
+        owner: Address,
+    ) -> args::TxUpdateTransferPolicy {
+        args::TxUpdateTransferPolicy {
+            owner,
+            token: None,
+            max_amount: None,
+            counterparties: vec![],
+            remove: false,
+            tx_code_path: PathBuf::from(TX_UPDATE_TRANSFER_POLICY_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a VoteProposal builder from the given minimum set of arguments
     fn new_proposal_vote(
         &self,
@@ -364,6 +477,22 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make an OperationalMetadataChange builder from the given minimum set
+    /// of arguments
+    fn new_change_operational_metadata(
+        &self,
+        validator: Address,
+    ) -> args::OperationalMetadataChange {
+        args::OperationalMetadataChange {
+            validator,
+            protocol_key: None,
+            rpc_endpoint: None,
+            seed_node: None,
+            emergency_contact: None,
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a TxBecomeValidator builder from the given minimum set of arguments
     #[allow(clippy::too_many_arguments)]
     fn new_become_validator(
@@ -669,6 +798,9 @@ where
                 wrapper_fee_payer: None,
                 fee_token: native_token,
                 fee_unshield: None,
+                fee_unshield_headroom: None,
+                shielded_fee: false,
+                confirm_fees: false,
                 gas_limit: GasLimit::from(DEFAULT_GAS_LIMIT),
                 expiration: Default::default(),
                 disposable_signing_key: false,
@@ -679,6 +811,7 @@ where
                 password: None,
                 memo: None,
                 use_device: false,
+                data_encoding: DataEncoding::Borsh,
             },
         }
     }
@@ -803,7 +936,7 @@ pub mod testing {
         BecomeValidator, Bond, CommissionChange, ConsensusKeyChange,
         MetaDataChange, Redelegation, Unbond, Withdraw,
     };
-    use namada_tx::data::{DecryptedTx, Fee, TxType, WrapperTx};
+    use namada_tx::data::{AccountNonce, DecryptedTx, Fee, TxType, WrapperTx};
     use proptest::prelude::{Just, Strategy};
     use proptest::{arbitrary, collection, option, prop_compose, prop_oneof};
     use prost::Message;
@@ -949,6 +1082,16 @@ pub mod testing {
         }
     }
 
+    prop_compose! {
+        // Generate an arbitrary account nonce
+        pub fn arb_account_nonce()(
+            account in arb_non_internal_address(),
+            nonce: u64,
+        ) -> AccountNonce {
+            AccountNonce { account, nonce }
+        }
+    }
+
     prop_compose! {
         // Generate an arbitrary wrapper transaction
         pub fn arb_wrapper_tx()(
@@ -956,12 +1099,14 @@ pub mod testing {
             pk in arb_common_pk(),
             gas_limit in arb_gas_limit(),
             unshield_section_hash in option::of(arb_hash()),
+            nonce in option::of(arb_account_nonce()),
         ) -> WrapperTx {
             WrapperTx {
                 fee,
                 pk,
                 gas_limit,
                 unshield_section_hash,
+                nonce,
             }
         }
     }
@@ -1187,7 +1332,7 @@ pub mod testing {
             let mut tx = Tx { header, sections: vec![] };
             let content_hash = tx.add_section(Section::ExtraData(content_extra_data)).get_hash();
             init_proposal.content = content_hash;
-            if let ProposalType::DefaultWithWasm(hash) = &mut init_proposal.r#type {
+            if let ProposalType::DefaultWithWasm(hash) | ProposalType::ExpeditedWithWasm(hash) = &mut init_proposal.r#type {
                 let type_hash = tx.add_section(Section::ExtraData(type_extra_data)).get_hash();
                 *hash = type_hash;
             }
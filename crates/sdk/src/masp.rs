@@ -1,5 +1,7 @@
 //! MASP verification wrappers.
 
+pub mod proving;
+
 use std::cmp::Ordering;
 use std::collections::{btree_map, BTreeMap, BTreeSet};
 use std::env;
@@ -24,6 +26,7 @@ use masp_primitives::merkle_tree::{
 };
 use masp_primitives::sapling::keys::FullViewingKey;
 use masp_primitives::sapling::note_encryption::*;
+use masp_primitives::sapling::prover::TxProver;
 use masp_primitives::sapling::redjubjub::PublicKey;
 use masp_primitives::sapling::{
     Diversifier, Node, Note, Nullifier, ViewingKey,
@@ -44,7 +47,9 @@ use masp_primitives::transaction::{
     Authorization, Authorized, Transaction, TransactionData,
     TransparentAddress, Unauthorized,
 };
-use masp_primitives::zip32::{ExtendedFullViewingKey, ExtendedSpendingKey};
+use masp_primitives::zip32::{
+    DiversifierIndex, ExtendedFullViewingKey, ExtendedSpendingKey,
+};
 use masp_proofs::bellman::groth16::PreparedVerifyingKey;
 use masp_proofs::bls12_381::Bls12;
 use masp_proofs::prover::LocalTxProver;
@@ -89,6 +94,11 @@ pub const ENV_VAR_MASP_PARAMS_DIR: &str = "NAMADA_MASP_PARAMS_DIR";
 /// deterministic rng.
 pub const ENV_VAR_MASP_TEST_SEED: &str = "NAMADA_MASP_TEST_SEED";
 
+/// Env var to select the prover of MASP transactions in place of the local
+/// prover: either `parallel`, or the command line of an external prover.
+#[cfg(feature = "pluggable-prover")]
+pub const ENV_VAR_MASP_PROVER: &str = "NAMADA_MASP_PROVER";
+
 /// The network to use for MASP
 #[cfg(feature = "mainnet")]
 const NETWORK: MainNetwork = MainNetwork;
@@ -455,6 +465,13 @@ pub trait ShieldedUtils:
     /// Get a MASP transaction prover
     fn local_tx_prover(&self) -> LocalTxProver;
 
+    /// Get the prover that generates the proofs of MASP transactions in place
+    /// of the local prover, if any
+    #[cfg(feature = "pluggable-prover")]
+    fn masp_prover(&self) -> Option<Box<dyn proving::MaspProver>> {
+        None
+    }
+
     /// Load up the currently saved ShieldedContext
     async fn load<U: ShieldedUtils + MaybeSend>(
         &self,
@@ -494,6 +511,27 @@ pub fn find_valid_diversifier<R: RngCore + CryptoRng>(
     (diversifier, g_d)
 }
 
+/// Derive the diversified payment address of a viewing key with the first
+/// valid diversifier index starting from `index`. Return the index that was
+/// used, so that the next address can be derived from the following index, or
+/// `None` if no valid index fits in a `u64`.
+pub fn find_diversified_address(
+    viewing_key: &ExtendedViewingKey,
+    index: u64,
+) -> Option<(u64, PaymentAddress)> {
+    let mut start = [0; 11];
+    start[..8].copy_from_slice(&index.to_le_bytes());
+    let (DiversifierIndex(found), payment_address) =
+        ExtendedFullViewingKey::from(*viewing_key)
+            .find_address(DiversifierIndex(start))?;
+    if found[8..].iter().any(|byte| *byte != 0) {
+        return None;
+    }
+    let mut index = [0; 8];
+    index.copy_from_slice(&found[..8]);
+    Some((u64::from_le_bytes(index), payment_address.into()))
+}
+
 /// Determine if using the current note would actually bring us closer to our
 /// target
 pub fn is_amount_required(src: I128Sum, dest: I128Sum, delta: I128Sum) -> bool {
@@ -903,9 +941,9 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         let tx_data = tx
             .data()
             .ok_or_else(|| Error::Other("Missing data section".to_string()))?;
-        let maybe_masp_tx = match Transfer::try_from_slice(&tx_data) {
-            Ok(transfer) => Some(transfer),
-            Err(_) => {
+        let maybe_masp_tx = match tx.decode_data::<Transfer>() {
+            Some(Ok(transfer)) => Some(transfer),
+            _ => {
                 // This should be a MASP over IBC transaction, it
                 // could be a ShieldedTransfer or an Envelope
                 // message, need to try both
@@ -1081,6 +1119,32 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         Ok(Some(val_acc))
     }
 
+    /// Count the notes received by each of the diversified payment addresses
+    /// of the viewing key in the context, i.e. by each of the diversifiers
+    /// recorded while scanning. If the key is not in the context, then we do
+    /// not know which addresses received notes and hence we return None.
+    pub fn received_payment_addresses(
+        &self,
+        vk: &ViewingKey,
+    ) -> Option<BTreeMap<PaymentAddress, usize>> {
+        let notes = self.pos_map.get(vk)?;
+        let mut addresses = BTreeMap::new();
+        for note_idx in notes {
+            let Some(payment_address) = self
+                .div_map
+                .get(note_idx)
+                .and_then(|div| vk.to_payment_address(*div))
+            else {
+                continue;
+            };
+            let count: &mut usize = addresses
+                .entry(PaymentAddress::from(payment_address))
+                .or_default();
+            *count = count.saturating_add(1);
+        }
+        Some(addresses)
+    }
+
     /// Use the addresses already stored in the wallet to precompute as many
     /// asset types as possible.
     pub async fn precompute_asset_types<C: Client + Sync>(
@@ -1585,53 +1649,16 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         res
     }
 
-    /// Make shielded components to embed within a Transfer object. If no
-    /// shielded payment address nor spending key is specified, then no
-    /// shielded components are produced. Otherwise a transaction containing
-    /// nullifiers and/or note commitments are produced. Dummy transparent
-    /// UTXOs are sometimes used to make transactions balanced, but it is
-    /// understood that transparent account changes are effected only by the
-    /// amounts and signatures specified by the containing Transfer object.
-    pub async fn gen_shielded_transfer(
-        context: &impl Namada,
-        source: &TransferSource,
-        target: &TransferTarget,
-        token: &Address,
-        amount: token::DenominatedAmount,
-        update_ctx: bool,
-    ) -> Result<Option<ShieldedTransfer>, TransferErr> {
-        // No shielded components are needed when neither source nor destination
-        // are shielded
-
+    /// Make the RNG used to build shielded transactions. With the `testing`
+    /// feature, it can be seeded from an env var.
+    fn new_build_rng() -> rand::rngs::StdRng {
         use rand::rngs::StdRng;
         use rand_core::SeedableRng;
 
-        let spending_key = source.spending_key();
-        let payment_address = target.payment_address();
-        // No shielded components are needed when neither source nor
-        // destination are shielded
-        if spending_key.is_none() && payment_address.is_none() {
-            return Ok(None);
-        }
-        // We want to fund our transaction solely from supplied spending key
-        let spending_key = spending_key.map(|x| x.into());
-        {
-            // Load the current shielded context given the spending key we
-            // possess
-            let mut shielded = context.shielded_mut().await;
-            let _ = shielded.load().await;
-        }
-        // Determine epoch in which to submit potential shielded transaction
-        let epoch = rpc::query_epoch(context.client()).await?;
-        // Context required for storing which notes are in the source's
-        // possession
-        let memo = MemoBytes::empty();
-
+        let rng = StdRng::from_rng(OsRng).unwrap();
         // Try to get a seed from env var, if any.
-        #[allow(unused_mut)]
-        let mut rng = StdRng::from_rng(OsRng).unwrap();
         #[cfg(feature = "testing")]
-        let mut rng = if let Ok(seed) = env::var(ENV_VAR_MASP_TEST_SEED)
+        let rng = if let Ok(seed) = env::var(ENV_VAR_MASP_TEST_SEED)
             .map_err(|e| Error::Other(e.to_string()))
             .and_then(|seed| {
                 let exp_str =
@@ -1648,14 +1675,18 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         } else {
             rng
         };
+        rng
+    }
 
-        // Now we build up the transaction within this object
+    /// Compute the expiration height of a shielded transaction from the
+    /// expiration of the tx being built
+    async fn expiration_height(context: &impl Namada) -> Result<u32, Error> {
         // TODO: if the user requested the default expiration, there might be a
         // small discrepancy between the datetime we calculate here and the one
         // we set for the transaction. This should be small enough to not cause
         // any issue, in case refactor this function to request the precise
         // datetime to the caller
-        let expiration_height: u32 = match context
+        let expiration_height = match context
             .tx_builder()
             .expiration
             .to_datetime()
@@ -1696,6 +1727,93 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
                 u32::MAX - 20
             }
         };
+        Ok(expiration_height)
+    }
+
+    /// Make shielded components to embed within a Transfer object. If no
+    /// shielded payment address nor spending key is specified, then no
+    /// shielded components are produced. Otherwise a transaction containing
+    /// nullifiers and/or note commitments are produced. Dummy transparent
+    /// UTXOs are sometimes used to make transactions balanced, but it is
+    /// understood that transparent account changes are effected only by the
+    /// amounts and signatures specified by the containing Transfer object.
+    pub async fn gen_shielded_transfer(
+        context: &impl Namada,
+        source: &TransferSource,
+        target: &TransferTarget,
+        token: &Address,
+        amount: token::DenominatedAmount,
+        update_ctx: bool,
+    ) -> Result<Option<ShieldedTransfer>, TransferErr> {
+        #[cfg(feature = "pluggable-prover")]
+        if let Some(masp_prover) = context.shielded().await.utils.masp_prover()
+        {
+            let recorder = proving::RecordingTxProver::default();
+            let transfer = Self::gen_shielded_transfer_with_prover(
+                context, source, target, token, amount, update_ctx, &recorder,
+            )
+            .await?;
+            return transfer
+                .map(|transfer| {
+                    proving::prove_recorded_transfer(
+                        masp_prover.as_ref(),
+                        recorder,
+                        transfer,
+                    )
+                })
+                .transpose()
+                .map_err(TransferErr::from);
+        }
+        #[cfg(not(feature = "testing"))]
+        let prover = context.shielded().await.utils.local_tx_prover();
+        #[cfg(feature = "testing")]
+        let prover = testing::MockTxProver(std::sync::Mutex::new(OsRng));
+        Self::gen_shielded_transfer_with_prover(
+            context, source, target, token, amount, update_ctx, &prover,
+        )
+        .await
+    }
+
+    /// Make a shielded transfer like [`Self::gen_shielded_transfer`], but
+    /// generate its proofs with the given prover. To delegate the proving to
+    /// another machine, use a [`proving::RecordingTxProver`] and import the
+    /// proofs of the recorded inputs in the MASP transaction afterwards.
+    pub async fn gen_shielded_transfer_with_prover(
+        context: &impl Namada,
+        source: &TransferSource,
+        target: &TransferTarget,
+        token: &Address,
+        amount: token::DenominatedAmount,
+        update_ctx: bool,
+        prover: &(impl TxProver + MaybeSync),
+    ) -> Result<Option<ShieldedTransfer>, TransferErr> {
+        // No shielded components are needed when neither source nor destination
+        // are shielded
+
+        let spending_key = source.spending_key();
+        let payment_address = target.payment_address();
+        // No shielded components are needed when neither source nor
+        // destination are shielded
+        if spending_key.is_none() && payment_address.is_none() {
+            return Ok(None);
+        }
+        // We want to fund our transaction solely from supplied spending key
+        let spending_key = spending_key.map(|x| x.into());
+        {
+            // Load the current shielded context given the spending key we
+            // possess
+            let mut shielded = context.shielded_mut().await;
+            let _ = shielded.load().await;
+        }
+        // Determine epoch in which to submit potential shielded transaction
+        let epoch = rpc::query_epoch(context.client()).await?;
+        // Context required for storing which notes are in the source's
+        // possession
+        let memo = MemoBytes::empty();
+
+        let mut rng = Self::new_build_rng();
+        // Now we build up the transaction within this object
+        let expiration_height = Self::expiration_height(context).await?;
         let mut builder = Builder::<TestNetwork, _>::new(
             NETWORK,
             // NOTE: this is going to add 20 more blocks to the actual
@@ -1949,12 +2067,286 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
 
         let builder_clone = builder.clone().map_builder(WalletMap);
         // Build and return the constructed transaction
+        let (masp_tx, metadata) = builder.build(
+            prover,
+            &FeeRule::non_standard(U64Sum::zero()),
+            &mut rng,
+            &mut RngBuildParams::new(OsRng),
+        )?;
+
+        if update_ctx {
+            // Cache the generated transfer
+            let mut shielded_ctx = context.shielded_mut().await;
+            shielded_ctx
+                .pre_cache_transaction(context, &masp_tx)
+                .await?;
+        }
+
+        Ok(Some(ShieldedTransfer {
+            builder: builder_clone,
+            masp_tx,
+            metadata,
+            epoch,
+        }))
+    }
+
+    /// Make a shielded transfer of several assets from a spending key to a
+    /// payment address in a single MASP transaction. The notes of the source
+    /// are selected to cover all the amounts at once and the change in every
+    /// asset type is sent back to the source.
+    pub async fn gen_shielded_multi_asset_transfer(
+        context: &impl Namada,
+        source: &TransferSource,
+        target: &TransferTarget,
+        assets: &[(Address, token::DenominatedAmount)],
+        update_ctx: bool,
+    ) -> Result<ShieldedTransfer, TransferErr> {
+        #[cfg(feature = "pluggable-prover")]
+        if let Some(masp_prover) = context.shielded().await.utils.masp_prover()
+        {
+            let recorder = proving::RecordingTxProver::default();
+            let transfer = Self::gen_shielded_multi_asset_transfer_with_prover(
+                context, source, target, assets, update_ctx, &recorder,
+            )
+            .await?;
+            return proving::prove_recorded_transfer(
+                masp_prover.as_ref(),
+                recorder,
+                transfer,
+            )
+            .map_err(TransferErr::from);
+        }
         #[cfg(not(feature = "testing"))]
         let prover = context.shielded().await.utils.local_tx_prover();
         #[cfg(feature = "testing")]
         let prover = testing::MockTxProver(std::sync::Mutex::new(OsRng));
+        Self::gen_shielded_multi_asset_transfer_with_prover(
+            context, source, target, assets, update_ctx, &prover,
+        )
+        .await
+    }
+
+    /// Make a shielded transfer of several assets like
+    /// [`Self::gen_shielded_multi_asset_transfer`], but generate its proofs
+    /// with the given prover.
+    pub async fn gen_shielded_multi_asset_transfer_with_prover(
+        context: &impl Namada,
+        source: &TransferSource,
+        target: &TransferTarget,
+        assets: &[(Address, token::DenominatedAmount)],
+        update_ctx: bool,
+        prover: &(impl TxProver + MaybeSync),
+    ) -> Result<ShieldedTransfer, TransferErr> {
+        let (Some(spending_key), Some(payment_address)) =
+            (source.spending_key(), target.payment_address())
+        else {
+            return Err(TransferErr::General(Error::Other(
+                "A multi-asset transfer must be from a spending key to a \
+                 payment address"
+                    .to_string(),
+            )));
+        };
+        let sk: ExtendedSpendingKey = spending_key.into();
+        {
+            // Load the current shielded context given the spending key we
+            // possess
+            let mut shielded = context.shielded_mut().await;
+            let _ = shielded.load().await;
+        }
+        // Determine epoch in which to submit the shielded transaction
+        let epoch = rpc::query_epoch(context.client()).await?;
+        let memo = MemoBytes::empty();
+
+        let mut rng = Self::new_build_rng();
+        let expiration_height = Self::expiration_height(context).await?;
+        let mut builder = Builder::<TestNetwork, _>::new(
+            NETWORK,
+            // NOTE: this is going to add 20 more blocks to the actual
+            // expiration, as in `gen_shielded_transfer`
+            expiration_height.into(),
+        );
+
+        // Convert the amounts of all the assets into MASP types. For every
+        // asset, keep its token, denomination, asset types and the amounts
+        // that remain to be sent to the receiver.
+        let mut required = I128Sum::zero();
+        let mut outputs = Vec::with_capacity(assets.len());
+        for (token, amount) in assets {
+            if outputs
+                .iter()
+                .any(|output: &(&Address, _, _, _)| output.0 == token)
+            {
+                return Err(TransferErr::General(Error::Other(format!(
+                    "The token {token} is transferred more than once"
+                ))));
+            }
+            let Some(denom) = query_denom(context.client(), token).await else {
+                return Err(TransferErr::General(Error::from(
+                    QueryError::General(format!(
+                        "denomination for token {token}"
+                    )),
+                )));
+            };
+            let (asset_types, masp_amount) = context
+                .shielded_mut()
+                .await
+                .convert_amount(
+                    context.client(),
+                    epoch,
+                    token,
+                    denom,
+                    amount.amount(),
+                )
+                .await?;
+            required += I128Sum::from_sum(masp_amount);
+            outputs.push((
+                token,
+                denom,
+                asset_types,
+                amount.amount().raw_amount().0,
+            ));
+        }
+        // Make sure to save any decodings of the asset types used so that
+        // balance queries involving them are successful
+        let _ = context.shielded_mut().await.save().await;
+
+        // Locate unspent notes that can meet all the amounts at once
+        let (_, unspent_notes, used_convs) = context
+            .shielded_mut()
+            .await
+            .collect_unspent_notes(
+                context,
+                &to_viewing_key(&sk).vk,
+                required,
+                epoch,
+            )
+            .await?;
+        // Commit the notes found to our transaction
+        for (diversifier, note, merkle_path) in unspent_notes {
+            builder
+                .add_sapling_spend(sk, diversifier, note, merkle_path)
+                .map_err(builder::Error::SaplingBuild)?;
+        }
+        // Commit the conversion notes used during summation
+        for (conv, wit, value) in used_convs.values() {
+            if value.is_positive() {
+                builder
+                    .add_sapling_convert(
+                        conv.clone(),
+                        *value as u64,
+                        wit.clone(),
+                    )
+                    .map_err(builder::Error::SaplingBuild)?;
+            }
+        }
+
+        // Anotate the asset type in the value balance with its decoding in
+        // order to facilitate cross-epoch computations
+        let value_balance = builder.value_balance().map_err(|e| {
+            Error::Other(format!("unable to complete value balance: {}", e))
+        })?;
+        let value_balance = context
+            .shielded_mut()
+            .await
+            .decode_sum(context.client(), value_balance)
+            .await;
+
+        // Loop through the value balance components and give to the receiver
+        // the ones needed by any of the assets
+        for ((asset_type, decoded), val) in value_balance.components() {
+            let Some(output) = outputs.iter_mut().find(|output| {
+                decoded.token == *output.0 && decoded.denom == output.1
+            }) else {
+                continue;
+            };
+            let rem_amount = &mut output.3[decoded.position as usize];
+            if decoded.epoch.map_or(true, |vbal_epoch| vbal_epoch <= epoch)
+                && *rem_amount > 0
+            {
+                let val = u128::try_from(*val).expect(
+                    "value balance in absence of output descriptors should be \
+                     non-negative",
+                );
+                // We want to take at most the remaining quota for the
+                // current denomination to the receiver
+                let contr = std::cmp::min(*rem_amount as u128, val) as u64;
+                builder
+                    .add_sapling_output(
+                        Some(sk.expsk.ovk),
+                        payment_address.into(),
+                        *asset_type,
+                        contr,
+                        memo.clone(),
+                    )
+                    .map_err(builder::Error::SaplingBuild)?;
+                // Lower what is required of the remaining contribution
+                *rem_amount -= contr;
+            }
+        }
+
+        // Nothing must remain to be included in output
+        let mut shortfall = I128Sum::zero();
+        for (_, _, asset_types, rem_amount) in &outputs {
+            for (asset_type, val) in asset_types.iter().zip(rem_amount) {
+                shortfall += I128Sum::from_pair(*asset_type, (*val).into())
+                    .expect("unable to construct value sum");
+            }
+        }
+        if !shortfall.is_zero() {
+            return Err(TransferErr::from(builder::Error::InsufficientFunds(
+                shortfall,
+            )));
+        }
+
+        // Now add outputs representing the change from this payment, while
+        // recording the amount of inputs we are short by
+        let mut additional = I128Sum::zero();
+        for (asset_type, amt) in builder
+            .value_balance()
+            .map_err(|e| {
+                Error::Other(format!("unable to complete value balance: {}", e))
+            })?
+            .components()
+        {
+            match amt.cmp(&0) {
+                Ordering::Greater => {
+                    // Send the change in this asset type back to the sender
+                    builder
+                        .add_sapling_output(
+                            Some(sk.expsk.ovk),
+                            sk.default_address().1,
+                            *asset_type,
+                            *amt as u64,
+                            memo.clone(),
+                        )
+                        .map_err(builder::Error::SaplingBuild)?;
+                }
+                Ordering::Less => {
+                    // Record how much of the current asset type we are
+                    // short by
+                    additional += I128Sum::from_nonnegative(*asset_type, -*amt)
+                        .map_err(|()| {
+                            Error::Other(format!(
+                                "from non negative conversion: {}",
+                                line!()
+                            ))
+                        })?;
+                }
+                Ordering::Equal => {}
+            }
+        }
+        // If we are short by a non-zero amount, then we have insufficient
+        // funds
+        if !additional.is_zero() {
+            return Err(TransferErr::from(builder::Error::InsufficientFunds(
+                additional,
+            )));
+        }
+
+        let builder_clone = builder.clone().map_builder(WalletMap);
+        // Build and return the constructed transaction
         let (masp_tx, metadata) = builder.build(
-            &prover,
+            prover,
             &FeeRule::non_standard(U64Sum::zero()),
             &mut rng,
             &mut RngBuildParams::new(OsRng),
@@ -1968,12 +2360,12 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
                 .await?;
         }
 
-        Ok(Some(ShieldedTransfer {
+        Ok(ShieldedTransfer {
             builder: builder_clone,
             masp_tx,
             metadata,
             epoch,
-        }))
+        })
     }
 
     // Updates the internal state with the data of the newly generated
@@ -3208,6 +3600,23 @@ pub mod fs {
             }
         }
 
+        #[cfg(feature = "pluggable-prover")]
+        fn masp_prover(&self) -> Option<Box<dyn proving::MaspProver>> {
+            let prover = env::var(ENV_VAR_MASP_PROVER).ok()?;
+            if prover == "parallel" {
+                let threads = std::thread::available_parallelism()
+                    .unwrap_or(std::num::NonZeroUsize::MIN);
+                Some(Box::new(proving::ParallelTxProver {
+                    prover: self.local_tx_prover(),
+                    threads,
+                }))
+            } else {
+                let prover =
+                    proving::ExternalTxProver::from_command_line(&prover)?;
+                Some(Box::new(prover))
+            }
+        }
+
         /// Try to load the last saved shielded context from the given context
         /// directory. If this fails, then leave the current context unchanged.
         async fn load<U: ShieldedUtils + MaybeSend>(
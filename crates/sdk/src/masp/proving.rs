@@ -0,0 +1,672 @@
+//! Delegation of the MASP proofs to an external proving service.
+//!
+//! Building a shielded transaction requires a zero-knowledge proof for each of
+//! its spend, convert and output descriptions, which is by far the most
+//! expensive step. To delegate it to a trusted machine, the transaction is
+//! first built with the [`RecordingTxProver`], which records the inputs of
+//! every proof and fills the descriptions with placeholder proofs. The
+//! portable [`ProvingInputs`] (i.e. the notes, their merkle paths and anchors
+//! and the randomness picked by the builder) are then proven by the trusted
+//! machine with [`ProvingInputs::prove`] and the resulting [`MaspProofs`] are
+//! imported into the transaction with [`import_proofs`], or into the MASP
+//! section of a tx under construction with [`import_tx_proofs`].
+//!
+//! The proofs are not committed to by the id of a MASP transaction, hence
+//! importing them changes neither the id nor the signatures of the
+//! transaction.
+//!
+//! With the `pluggable-prover` feature, the same mechanism lets the shielded
+//! utils replace the local prover of the SDK with a [`MaspProver`], such as
+//! the [`ParallelTxProver`] or an [`ExternalTxProver`] backed by an accelerated
+//! proving process.
+
+use std::sync::{Mutex, PoisonError};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use masp_primitives::asset_type::AssetType;
+use masp_primitives::constants::{
+    SPENDING_KEY_GENERATOR, VALUE_COMMITMENT_RANDOMNESS_GENERATOR,
+};
+use masp_primitives::convert::AllowedConversion;
+use masp_primitives::group::GroupEncoding;
+use masp_primitives::merkle_tree::MerklePath;
+use masp_primitives::sapling::prover::TxProver;
+use masp_primitives::sapling::redjubjub::{PrivateKey, PublicKey, Signature};
+use masp_primitives::sapling::{
+    self, Diversifier, Node, Note, ProofGenerationKey, Rseed,
+};
+use masp_primitives::transaction::components::{I128Sum, GROTH_PROOF_SIZE};
+use masp_primitives::transaction::{Transaction, TransactionData};
+use masp_proofs::{bls12_381, jubjub};
+use namada_tx::{Section, Tx};
+use rand_core::OsRng;
+
+use crate::error::Error;
+#[cfg(feature = "pluggable-prover")]
+use crate::masp::ShieldedTransfer;
+#[cfg(feature = "pluggable-prover")]
+use crate::{MaybeSend, MaybeSync};
+
+/// A serialized Groth16 proof of a MASP description
+pub type GrothProof = [u8; GROTH_PROOF_SIZE];
+
+/// The proof that fills the descriptions of a transaction built with the
+/// [`RecordingTxProver`] until the actual proofs are imported
+pub const PLACEHOLDER_PROOF: GrothProof = [0; GROTH_PROOF_SIZE];
+
+/// The inputs of the proof of a spend description
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct SpendProvingInputs {
+    /// The spend validating key of the proof generation key
+    pub ak: [u8; 32],
+    /// The nullifier private key of the proof generation key
+    pub nsk: [u8; 32],
+    /// The diversifier of the address that received the note
+    pub diversifier: Diversifier,
+    /// The spent note
+    pub note: Note,
+    /// The randomness of the re-randomized spend validating key
+    pub ar: [u8; 32],
+    /// The anchor, i.e. the root of the note commitment tree
+    pub anchor: [u8; 32],
+    /// The merkle path of the note commitment to the anchor
+    pub merkle_path: MerklePath<Node>,
+    /// The randomness of the value commitment
+    pub rcv: [u8; 32],
+}
+
+/// The inputs of the proof of a convert description
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct ConvertProvingInputs {
+    /// The conversion being used
+    pub allowed_conversion: AllowedConversion,
+    /// The number of times the conversion is applied
+    pub value: u64,
+    /// The anchor, i.e. the root of the conversion tree
+    pub anchor: [u8; 32],
+    /// The merkle path of the conversion to the anchor
+    pub merkle_path: MerklePath<Node>,
+    /// The randomness of the value commitment
+    pub rcv: [u8; 32],
+}
+
+/// The inputs of the proof of an output description
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct OutputProvingInputs {
+    /// The ephemeral secret key of the note encryption
+    pub esk: [u8; 32],
+    /// The recipient of the note
+    pub payment_address: sapling::PaymentAddress,
+    /// The randomness of the note commitment
+    pub rcm: [u8; 32],
+    /// The asset type of the note
+    pub asset_type: AssetType,
+    /// The value of the note
+    pub value: u64,
+    /// The randomness of the value commitment
+    pub rcv: [u8; 32],
+}
+
+/// The inputs of all the proofs of a MASP transaction, in the order of the
+/// descriptions of the transaction
+#[derive(Clone, Debug, Default, BorshSerialize, BorshDeserialize)]
+pub struct ProvingInputs {
+    /// The inputs of the spend proofs
+    pub spends: Vec<SpendProvingInputs>,
+    /// The inputs of the convert proofs
+    pub converts: Vec<ConvertProvingInputs>,
+    /// The inputs of the output proofs
+    pub outputs: Vec<OutputProvingInputs>,
+}
+
+/// The proofs of a MASP transaction, in the order of the descriptions of the
+/// transaction
+#[derive(Clone, Debug, Default, BorshSerialize, BorshDeserialize)]
+pub struct MaspProofs {
+    /// The spend proofs
+    pub spends: Vec<GrothProof>,
+    /// The convert proofs
+    pub converts: Vec<GrothProof>,
+    /// The output proofs
+    pub outputs: Vec<GrothProof>,
+}
+
+impl ProvingInputs {
+    /// Generate the proofs of these inputs with the given prover, e.g. a
+    /// [`masp_proofs::prover::LocalTxProver`] on the trusted machine.
+    pub fn prove(&self, prover: &impl TxProver) -> Result<MaspProofs, Error> {
+        let mut ctx = prover.new_sapling_proving_context();
+        let spends = self
+            .spends
+            .iter()
+            .map(|spend| {
+                let proof_generation_key = ProofGenerationKey {
+                    ak: decode_point(&spend.ak, "spend validating key")?,
+                    nsk: decode_scalar(&spend.nsk, "nullifier private key")?,
+                };
+                let (proof, _cv, _rk) = prover
+                    .spend_proof(
+                        &mut ctx,
+                        proof_generation_key,
+                        spend.diversifier,
+                        spend.note.rseed,
+                        decode_scalar(&spend.ar, "spend randomness")?,
+                        spend.note.asset_type,
+                        spend.note.value,
+                        decode_anchor(&spend.anchor)?,
+                        spend.merkle_path.clone(),
+                        decode_scalar(&spend.rcv, "value randomness")?,
+                    )
+                    .map_err(|()| {
+                        Error::Other("Failed to prove a MASP spend".to_string())
+                    })?;
+                Ok(proof)
+            })
+            .collect::<Result<_, Error>>()?;
+        let converts = self
+            .converts
+            .iter()
+            .map(|convert| {
+                let (proof, _cv) = prover
+                    .convert_proof(
+                        &mut ctx,
+                        convert.allowed_conversion.clone(),
+                        convert.value,
+                        decode_anchor(&convert.anchor)?,
+                        convert.merkle_path.clone(),
+                        decode_scalar(&convert.rcv, "value randomness")?,
+                    )
+                    .map_err(|()| {
+                        Error::Other(
+                            "Failed to prove a MASP conversion".to_string(),
+                        )
+                    })?;
+                Ok(proof)
+            })
+            .collect::<Result<_, Error>>()?;
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|output| {
+                let (proof, _cv) = prover.output_proof(
+                    &mut ctx,
+                    decode_scalar(&output.esk, "ephemeral secret key")?,
+                    output.payment_address,
+                    decode_scalar(&output.rcm, "note randomness")?,
+                    output.asset_type,
+                    output.value,
+                    decode_scalar(&output.rcv, "value randomness")?,
+                );
+                Ok(proof)
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(MaspProofs {
+            spends,
+            converts,
+            outputs,
+        })
+    }
+}
+
+/// A prover that records the inputs of the proofs of a MASP transaction
+/// instead of generating them. The transaction that it builds is complete and
+/// signed, but its descriptions hold the [`PLACEHOLDER_PROOF`] until the
+/// actual proofs are imported.
+#[derive(Debug, Default)]
+pub struct RecordingTxProver(Mutex<ProvingInputs>);
+
+impl RecordingTxProver {
+    /// Take the inputs recorded while building a transaction
+    pub fn into_inputs(self) -> ProvingInputs {
+        self.0.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn record(&self, update: impl FnOnce(&mut ProvingInputs)) {
+        update(&mut self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+/// The proving context of the [`RecordingTxProver`]
+pub struct RecordingProvingContext {
+    /// The sum of the randomness of the value commitments, i.e. the secret
+    /// key of the binding signature
+    bsk: jubjub::Fr,
+}
+
+impl TxProver for RecordingTxProver {
+    type SaplingProvingContext = RecordingProvingContext;
+
+    fn new_sapling_proving_context(&self) -> Self::SaplingProvingContext {
+        RecordingProvingContext {
+            bsk: jubjub::Fr::zero(),
+        }
+    }
+
+    fn spend_proof(
+        &self,
+        ctx: &mut Self::SaplingProvingContext,
+        proof_generation_key: ProofGenerationKey,
+        diversifier: Diversifier,
+        rseed: Rseed,
+        ar: jubjub::Fr,
+        asset_type: AssetType,
+        value: u64,
+        anchor: bls12_381::Scalar,
+        merkle_path: MerklePath<Node>,
+        rcv: jubjub::Fr,
+    ) -> Result<(GrothProof, jubjub::ExtendedPoint, PublicKey), ()> {
+        let payment_address = proof_generation_key
+            .to_viewing_key()
+            .to_payment_address(diversifier)
+            .ok_or(())?;
+        let note = Note {
+            asset_type,
+            value,
+            g_d: diversifier.g_d().ok_or(())?,
+            pk_d: *payment_address.pk_d(),
+            rseed,
+        };
+        ctx.bsk += rcv;
+        let cv = asset_type.value_commitment(value, rcv).commitment().into();
+        let rk = PublicKey(proof_generation_key.ak.into())
+            .randomize(ar, SPENDING_KEY_GENERATOR);
+        self.record(|inputs| {
+            inputs.spends.push(SpendProvingInputs {
+                ak: proof_generation_key.ak.to_bytes(),
+                nsk: proof_generation_key.nsk.to_bytes(),
+                diversifier,
+                note,
+                ar: ar.to_bytes(),
+                anchor: anchor.to_bytes(),
+                merkle_path,
+                rcv: rcv.to_bytes(),
+            })
+        });
+        Ok((PLACEHOLDER_PROOF, cv, rk))
+    }
+
+    fn output_proof(
+        &self,
+        ctx: &mut Self::SaplingProvingContext,
+        esk: jubjub::Fr,
+        payment_address: sapling::PaymentAddress,
+        rcm: jubjub::Fr,
+        asset_type: AssetType,
+        value: u64,
+        rcv: jubjub::Fr,
+    ) -> (GrothProof, jubjub::ExtendedPoint) {
+        // Outputs subtract from the total
+        ctx.bsk -= rcv;
+        let cv = asset_type.value_commitment(value, rcv).commitment().into();
+        self.record(|inputs| {
+            inputs.outputs.push(OutputProvingInputs {
+                esk: esk.to_bytes(),
+                payment_address,
+                rcm: rcm.to_bytes(),
+                asset_type,
+                value,
+                rcv: rcv.to_bytes(),
+            })
+        });
+        (PLACEHOLDER_PROOF, cv)
+    }
+
+    fn convert_proof(
+        &self,
+        ctx: &mut Self::SaplingProvingContext,
+        allowed_conversion: AllowedConversion,
+        value: u64,
+        anchor: bls12_381::Scalar,
+        merkle_path: MerklePath<Node>,
+        rcv: jubjub::Fr,
+    ) -> Result<(GrothProof, jubjub::ExtendedPoint), ()> {
+        ctx.bsk += rcv;
+        let cv = allowed_conversion
+            .value_commitment(value, rcv)
+            .commitment()
+            .into();
+        self.record(|inputs| {
+            inputs.converts.push(ConvertProvingInputs {
+                allowed_conversion,
+                value,
+                anchor: anchor.to_bytes(),
+                merkle_path,
+                rcv: rcv.to_bytes(),
+            })
+        });
+        Ok((PLACEHOLDER_PROOF, cv))
+    }
+
+    fn binding_sig(
+        &self,
+        ctx: &mut Self::SaplingProvingContext,
+        _assets_and_values: &I128Sum,
+        sighash: &[u8; 32],
+    ) -> Result<Signature, ()> {
+        let bsk = PrivateKey(ctx.bsk);
+        let bvk = PublicKey::from_private(
+            &bsk,
+            VALUE_COMMITMENT_RANDOMNESS_GENERATOR,
+        );
+        let mut data_to_be_signed = [0u8; 64];
+        data_to_be_signed[0..32].copy_from_slice(&bvk.0.to_bytes());
+        data_to_be_signed[32..64].copy_from_slice(&sighash[..]);
+        Ok(bsk.sign(
+            &data_to_be_signed,
+            &mut OsRng,
+            VALUE_COMMITMENT_RANDOMNESS_GENERATOR,
+        ))
+    }
+}
+
+/// Replace the proofs of the descriptions of the given MASP transaction with
+/// the given ones, which must be in the order of the descriptions. The id of
+/// the transaction is unchanged.
+pub fn import_proofs(
+    masp_tx: &Transaction,
+    proofs: &MaspProofs,
+) -> Result<Transaction, Error> {
+    let mut bundle = masp_tx.sapling_bundle().cloned().ok_or_else(|| {
+        Error::Other(
+            "The MASP transaction has no shielded descriptions".to_string(),
+        )
+    })?;
+    if bundle.shielded_spends.len() != proofs.spends.len()
+        || bundle.shielded_converts.len() != proofs.converts.len()
+        || bundle.shielded_outputs.len() != proofs.outputs.len()
+    {
+        return Err(Error::Other(format!(
+            "The proofs of {} spends, {} conversions and {} outputs do not \
+             match the MASP transaction with {} spends, {} conversions and \
+             {} outputs",
+            proofs.spends.len(),
+            proofs.converts.len(),
+            proofs.outputs.len(),
+            bundle.shielded_spends.len(),
+            bundle.shielded_converts.len(),
+            bundle.shielded_outputs.len(),
+        )));
+    }
+    for (spend, proof) in bundle.shielded_spends.iter_mut().zip(&proofs.spends)
+    {
+        spend.zkproof = *proof;
+    }
+    for (convert, proof) in
+        bundle.shielded_converts.iter_mut().zip(&proofs.converts)
+    {
+        convert.zkproof = *proof;
+    }
+    for (output, proof) in
+        bundle.shielded_outputs.iter_mut().zip(&proofs.outputs)
+    {
+        output.zkproof = *proof;
+    }
+    let proven_tx = TransactionData::from_parts(
+        masp_tx.version(),
+        masp_tx.consensus_branch_id(),
+        masp_tx.lock_time(),
+        masp_tx.expiry_height(),
+        masp_tx.transparent_bundle().cloned(),
+        Some(bundle),
+    )
+    .freeze()
+    .map_err(|err| {
+        Error::Other(format!("Failed to import the MASP proofs: {err}"))
+    })?;
+    if proven_tx.txid() != masp_tx.txid() {
+        return Err(Error::Other(
+            "Importing the MASP proofs changed the transaction id".to_string(),
+        ));
+    }
+    Ok(proven_tx)
+}
+
+/// Replace the proofs of the MASP transaction of the given tx with the given
+/// ones. The hash of the MASP section, and hence the signatures of the tx, are
+/// unchanged.
+pub fn import_tx_proofs(tx: &mut Tx, proofs: &MaspProofs) -> Result<(), Error> {
+    let masp_tx = tx
+        .sections
+        .iter_mut()
+        .find_map(|section| match section {
+            Section::MaspTx(masp_tx) => Some(masp_tx),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            Error::Other("The tx has no MASP transaction".to_string())
+        })?;
+    *masp_tx = import_proofs(masp_tx, proofs)?;
+    Ok(())
+}
+
+/// A prover of the recorded inputs of MASP transactions, which can be plugged
+/// into the SDK in place of the local prover
+#[cfg(feature = "pluggable-prover")]
+pub trait MaspProver: MaybeSend + MaybeSync {
+    /// Generate the proofs of the given inputs, in the same order
+    fn prove(&self, inputs: &ProvingInputs) -> Result<MaspProofs, Error>;
+}
+
+#[cfg(feature = "pluggable-prover")]
+impl MaspProver for masp_proofs::prover::LocalTxProver {
+    fn prove(&self, inputs: &ProvingInputs) -> Result<MaspProofs, Error> {
+        inputs.prove(self)
+    }
+}
+
+/// A prover that splits the inputs into batches which are proven in parallel
+/// by the local prover
+#[cfg(feature = "pluggable-prover")]
+pub struct ParallelTxProver {
+    /// The local prover
+    pub prover: masp_proofs::prover::LocalTxProver,
+    /// The number of batches proven in parallel
+    pub threads: std::num::NonZeroUsize,
+}
+
+#[cfg(feature = "pluggable-prover")]
+impl MaspProver for ParallelTxProver {
+    fn prove(&self, inputs: &ProvingInputs) -> Result<MaspProofs, Error> {
+        let threads = self.threads.get();
+        let batch_len = |len: usize| len.div_ceil(threads).max(1);
+        let mut spends = inputs.spends.chunks(batch_len(inputs.spends.len()));
+        let mut converts =
+            inputs.converts.chunks(batch_len(inputs.converts.len()));
+        let mut outputs =
+            inputs.outputs.chunks(batch_len(inputs.outputs.len()));
+        let batches: Vec<ProvingInputs> = (0..threads)
+            .map(|_| ProvingInputs {
+                spends: spends.next().unwrap_or_default().to_vec(),
+                converts: converts.next().unwrap_or_default().to_vec(),
+                outputs: outputs.next().unwrap_or_default().to_vec(),
+            })
+            .collect();
+        let batch_proofs = std::thread::scope(|scope| {
+            let handles: Vec<_> = batches
+                .iter()
+                .map(|batch| scope.spawn(move || batch.prove(&self.prover)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle.join().map_err(|_| {
+                        Error::Other(
+                            "A MASP proving thread panicked".to_string(),
+                        )
+                    })?
+                })
+                .collect::<Result<Vec<_>, Error>>()
+        })?;
+        // The batches are contiguous, hence concatenating their proofs
+        // preserves the order of the descriptions
+        let mut proofs = MaspProofs::default();
+        for batch in batch_proofs {
+            proofs.spends.extend(batch.spends);
+            proofs.converts.extend(batch.converts);
+            proofs.outputs.extend(batch.outputs);
+        }
+        Ok(proofs)
+    }
+}
+
+/// A prover that delegates the proving to an external process, e.g. a
+/// GPU-accelerated prover. The process reads the borsh-encoded
+/// [`ProvingInputs`] from its standard input and writes the borsh-encoded
+/// [`MaspProofs`] to its standard output.
+#[cfg(feature = "pluggable-prover")]
+#[derive(Clone, Debug)]
+pub struct ExternalTxProver {
+    /// The program of the prover
+    pub program: std::path::PathBuf,
+    /// The arguments of the program
+    pub args: Vec<String>,
+}
+
+#[cfg(feature = "pluggable-prover")]
+impl ExternalTxProver {
+    /// Parse the command line of an external prover, i.e. its program
+    /// followed by its arguments separated by whitespace
+    pub fn from_command_line(command_line: &str) -> Option<Self> {
+        let mut words = command_line.split_whitespace();
+        let program = words.next()?.into();
+        let args = words.map(ToString::to_string).collect();
+        Some(Self { program, args })
+    }
+}
+
+#[cfg(feature = "pluggable-prover")]
+impl MaspProver for ExternalTxProver {
+    fn prove(&self, inputs: &ProvingInputs) -> Result<MaspProofs, Error> {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        use borsh_ext::BorshSerializeExt;
+
+        let prover_err = |err: String| {
+            Error::Other(format!(
+                "The external MASP prover {} failed: {err}",
+                self.program.display()
+            ))
+        };
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|err| prover_err(err.to_string()))?;
+        child
+            .stdin
+            .take()
+            .ok_or_else(|| prover_err("no standard input".to_string()))?
+            .write_all(&inputs.serialize_to_vec())
+            .map_err(|err| prover_err(err.to_string()))?;
+        let output = child
+            .wait_with_output()
+            .map_err(|err| prover_err(err.to_string()))?;
+        if !output.status.success() {
+            return Err(prover_err(output.status.to_string()));
+        }
+        MaspProofs::try_from_slice(&output.stdout)
+            .map_err(|err| prover_err(format!("invalid proofs: {err}")))
+    }
+}
+
+/// Generate the proofs of a shielded transfer built with the given
+/// [`RecordingTxProver`] with the given prover, and import them into the MASP
+/// transaction of the transfer
+#[cfg(feature = "pluggable-prover")]
+pub fn prove_recorded_transfer(
+    prover: &dyn MaspProver,
+    recorder: RecordingTxProver,
+    mut transfer: ShieldedTransfer,
+) -> Result<ShieldedTransfer, Error> {
+    let proofs = prover.prove(&recorder.into_inputs())?;
+    transfer.masp_tx = import_proofs(&transfer.masp_tx, &proofs)?;
+    Ok(transfer)
+}
+
+fn decode_scalar(bytes: &[u8; 32], what: &str) -> Result<jubjub::Fr, Error> {
+    Option::from(jubjub::Fr::from_bytes(bytes)).ok_or_else(|| {
+        Error::Other(format!("Invalid {what} in the MASP proving inputs"))
+    })
+}
+
+fn decode_point(
+    bytes: &[u8; 32],
+    what: &str,
+) -> Result<jubjub::SubgroupPoint, Error> {
+    Option::from(jubjub::SubgroupPoint::from_bytes(bytes)).ok_or_else(|| {
+        Error::Other(format!("Invalid {what} in the MASP proving inputs"))
+    })
+}
+
+fn decode_anchor(bytes: &[u8; 32]) -> Result<bls12_381::Scalar, Error> {
+    Option::from(bls12_381::Scalar::from_bytes(bytes)).ok_or_else(|| {
+        Error::Other("Invalid anchor in the MASP proving inputs".to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh_ext::BorshSerializeExt;
+    use masp_primitives::transaction::components::sapling::builder::RngBuildParams;
+    use masp_primitives::transaction::components::U64Sum;
+    use masp_primitives::transaction::fees::fixed::FeeRule;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::masp::testing::{arb_shielded_builder, MockTxProver};
+
+    proptest! {
+        /// Test that the proofs generated from the inputs recorded while
+        /// building a MASP transaction replace its placeholder proofs without
+        /// changing its id
+        #[test]
+        fn test_import_recorded_proofs(
+            (builder, _asset_types) in arb_shielded_builder(1..5),
+        ) {
+            let recorder = RecordingTxProver::default();
+            let (masp_tx, _metadata) = builder
+                .build(
+                    &recorder,
+                    &FeeRule::non_standard(U64Sum::zero()),
+                    &mut OsRng,
+                    &mut RngBuildParams::new(OsRng),
+                )
+                .unwrap();
+            let bundle = masp_tx.sapling_bundle().unwrap();
+            let inputs = recorder.into_inputs();
+            assert_eq!(inputs.spends.len(), bundle.shielded_spends.len());
+            assert_eq!(inputs.converts.len(), bundle.shielded_converts.len());
+            assert_eq!(inputs.outputs.len(), bundle.shielded_outputs.len());
+
+            // The inputs can be sent to and proven by another machine
+            let inputs =
+                ProvingInputs::try_from_slice(&inputs.serialize_to_vec())
+                    .unwrap();
+            let proofs = inputs
+                .prove(&MockTxProver(Mutex::new(OsRng)))
+                .unwrap();
+            let proven_tx = import_proofs(&masp_tx, &proofs).unwrap();
+            assert_eq!(proven_tx.txid(), masp_tx.txid());
+            let proven_bundle = proven_tx.sapling_bundle().unwrap();
+            assert!(
+                proven_bundle
+                    .shielded_spends
+                    .iter()
+                    .all(|spend| spend.zkproof != PLACEHOLDER_PROOF)
+            );
+            assert!(
+                proven_bundle
+                    .shielded_outputs
+                    .iter()
+                    .all(|output| output.zkproof != PLACEHOLDER_PROOF)
+            );
+
+            // The number of proofs must match the descriptions
+            let mut extra_proofs = proofs;
+            extra_proofs.outputs.push(PLACEHOLDER_PROOF);
+            assert!(import_proofs(&masp_tx, &extra_proofs).is_err());
+        }
+    }
+}
@@ -4,7 +4,12 @@
 // Re-export to show in rustdoc!
 use namada_core::storage::BlockHeight;
 use namada_state::{DBIter, StorageHasher, DB};
-pub use shell::Shell;
+pub use shell::{
+    AppliedTxResult, BlockResultsInfo, ChainParameters, DecodedSignature,
+    DecodedTx, EpochInfo, EvalVpRequest, EvalVpResult, ParameterChange, Shell,
+    StateChecksum, SupplyStats, TxHistoryCursor, TxHistoryEntry, TxHistoryPage,
+    TxResultInfo, WasmCode, MAX_TX_HISTORY_PAGE_SIZE,
+};
 use shell::SHELL;
 pub use types::{
     EncodedResponseQuery, Error, RequestCtx, RequestQuery, ResponseQuery,
@@ -14,12 +19,15 @@ use vp::{Vp, VP};
 
 pub use self::shell::eth_bridge::{
     Erc20FlowControl, GenBridgePoolProofReq, GenBridgePoolProofRsp,
-    TransferToErcArgs, TransferToEthereumStatus,
+    PendingTransferRelayStatus, TransferToErcArgs, TransferToEthereumStatus,
+    ValidatorSetUpdateProof, MAX_VALSET_UPD_PROOFS_PER_QUERY,
 };
 use crate::MaybeSend;
 
 #[macro_use]
 mod router;
+#[cfg(any(test, feature = "async-client"))]
+pub mod multi_endpoint;
 mod shell;
 mod types;
 pub mod vp;
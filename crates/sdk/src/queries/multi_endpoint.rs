@@ -0,0 +1,395 @@
+//! A client dispatching the requests to several RPC endpoints.
+//!
+//! The [`MultiEndpointClient`] sends the requests to its current endpoint.
+//! When an endpoint fails with a transport error, it is marked as unhealthy
+//! and the client fails over to the next healthy endpoint. The idempotent
+//! requests, i.e. all the requests but the broadcasts of txs, are retried
+//! with an exponential backoff. The errors returned by a node that answered,
+//! e.g. of a failed ABCI query, are not retried, so that the client maps the
+//! errors of its endpoints as a single endpoint client would.
+
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use namada_core::storage::BlockHeight;
+use tendermint_rpc::error::ErrorDetail;
+use tendermint_rpc::{Error as RpcError, Method};
+
+use super::{Client, EncodedResponseQuery, Error};
+use crate::control_flow::time::{sleep, Duration};
+
+/// The retries of the idempotent requests of a [`MultiEndpointClient`]
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    /// The max number of retries of a request, over all the endpoints
+    pub max_retries: usize,
+    /// The backoff before the first retry, doubled before every other retry
+    pub initial_backoff: Duration,
+    /// The max backoff before a retry
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+/// An error of a client request, which may or may not succeed if retried
+pub trait RetryableError: Display {
+    /// Check if the failed request may succeed if retried, possibly with
+    /// another endpoint.
+    fn is_retryable(&self) -> bool;
+}
+
+impl RetryableError for RpcError {
+    fn is_retryable(&self) -> bool {
+        // A node that answered with an error would answer the same again
+        !matches!(self.detail(), ErrorDetail::Response(_))
+    }
+}
+
+impl RetryableError for Error {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Error::Tendermint(err) => err.is_retryable(),
+            Error::Decoding(_)
+            | Error::Query(_, _)
+            | Error::InvalidHeight(_) => false,
+        }
+    }
+}
+
+impl RetryableError for std::io::Error {
+    fn is_retryable(&self) -> bool {
+        use std::io::ErrorKind;
+        matches!(
+            self.kind(),
+            ErrorKind::ConnectionRefused
+                | ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::NotConnected
+                | ErrorKind::BrokenPipe
+                | ErrorKind::TimedOut
+                | ErrorKind::Interrupted
+        )
+    }
+}
+
+/// An RPC endpoint of a [`MultiEndpointClient`]
+#[derive(Debug)]
+struct Endpoint<C> {
+    client: C,
+    healthy: AtomicBool,
+}
+
+/// A client dispatching the requests to several RPC endpoints, with failover
+/// and retries.
+#[derive(Debug)]
+pub struct MultiEndpointClient<C> {
+    endpoints: Vec<Endpoint<C>>,
+    current: AtomicUsize,
+    retry: RetryConfig,
+}
+
+impl<C> MultiEndpointClient<C> {
+    /// Create a client of the given endpoints, in the order of preference.
+    /// Returns `None` if there is no endpoint.
+    pub fn new(
+        clients: impl IntoIterator<Item = C>,
+        retry: RetryConfig,
+    ) -> Option<Self> {
+        let endpoints: Vec<_> = clients
+            .into_iter()
+            .map(|client| Endpoint {
+                client,
+                healthy: AtomicBool::new(true),
+            })
+            .collect();
+        if endpoints.is_empty() {
+            return None;
+        }
+        Some(Self {
+            endpoints,
+            current: AtomicUsize::new(0),
+            retry,
+        })
+    }
+
+    /// Iterate over the clients of the endpoints with their health, in the
+    /// order of preference.
+    pub fn endpoints(&self) -> impl Iterator<Item = (&C, bool)> {
+        self.endpoints.iter().map(|endpoint| {
+            (&endpoint.client, endpoint.healthy.load(Ordering::Relaxed))
+        })
+    }
+
+    /// Select the current endpoint if it's healthy, otherwise the next
+    /// healthy one. If none is healthy, the endpoints are tried in turn.
+    fn select_endpoint(&self) -> usize {
+        let len = self.endpoints.len();
+        let current = self.current.load(Ordering::Relaxed) % len;
+        (0..len)
+            .map(|offset| (current + offset) % len)
+            .find(|&index| {
+                self.endpoints[index].healthy.load(Ordering::Relaxed)
+            })
+            .unwrap_or(current)
+    }
+
+    /// Record the health of an endpoint after a request, failing over to the
+    /// next endpoint if it's unhealthy.
+    fn report(&self, index: usize, healthy: bool) {
+        self.endpoints[index]
+            .healthy
+            .store(healthy, Ordering::Relaxed);
+        let current = if healthy {
+            index
+        } else {
+            (index + 1) % self.endpoints.len()
+        };
+        self.current.store(current, Ordering::Relaxed);
+    }
+
+    /// Call a request with failover, retrying it with a backoff if it's
+    /// idempotent.
+    async fn with_failover<'a, T, E, F, Fut>(
+        &'a self,
+        idempotent: bool,
+        mut call: F,
+    ) -> Result<T, E>
+    where
+        E: RetryableError,
+        F: FnMut(&'a C) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let max_retries = if idempotent {
+            self.retry.max_retries
+        } else {
+            0
+        };
+        let mut retries = 0;
+        let mut backoff = self.retry.initial_backoff;
+        loop {
+            let index = self.select_endpoint();
+            match call(&self.endpoints[index].client).await {
+                Err(err) if err.is_retryable() => {
+                    self.report(index, false);
+                    if retries >= max_retries {
+                        return Err(err);
+                    }
+                    retries += 1;
+                    tracing::debug!(
+                        "Request to RPC endpoint {index} failed, retrying in \
+                         {backoff:?}: {err}"
+                    );
+                    sleep(backoff).await;
+                    backoff = std::cmp::min(
+                        backoff.saturating_mul(2),
+                        self.retry.max_backoff,
+                    );
+                }
+                result => {
+                    self.report(index, true);
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+impl<C> MultiEndpointClient<C>
+where
+    C: Client,
+{
+    /// Check the health of all the endpoints with their `/health` RPC, e.g.
+    /// periodically or before sending the first requests. Returns the number
+    /// of healthy endpoints.
+    pub async fn check_health(&self) -> usize {
+        let mut healthy = 0;
+        for endpoint in &self.endpoints {
+            let is_healthy = endpoint.client.health().await.is_ok();
+            endpoint.healthy.store(is_healthy, Ordering::Relaxed);
+            healthy += usize::from(is_healthy);
+        }
+        healthy
+    }
+}
+
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+impl<C> Client for MultiEndpointClient<C>
+where
+    C: Client + crate::MaybeSync,
+    C::Error: RetryableError,
+{
+    type Error = C::Error;
+
+    async fn request(
+        &self,
+        path: String,
+        data: Option<Vec<u8>>,
+        height: Option<BlockHeight>,
+        prove: bool,
+    ) -> Result<EncodedResponseQuery, Self::Error> {
+        self.with_failover(true, |client| {
+            client.request(path.clone(), data.clone(), height, prove)
+        })
+        .await
+    }
+
+    async fn perform<R>(&self, request: R) -> Result<R::Output, RpcError>
+    where
+        R: tendermint_rpc::SimpleRequest,
+    {
+        // Broadcasting a tx is not idempotent
+        let idempotent = !matches!(
+            tendermint_rpc::Request::method(&request),
+            Method::BroadcastTxAsync
+                | Method::BroadcastTxSync
+                | Method::BroadcastTxCommit
+        );
+        // The requests cannot be cloned, so they are copied from their JSON
+        // encoding to be retried
+        let json = serde_json::to_string(&request).ok();
+        let mut request = Some(request);
+        self.with_failover(idempotent && json.is_some(), |client| {
+            let request = request.take().unwrap_or_else(|| {
+                serde_json::from_str(json.as_deref().unwrap_or_default())
+                    .expect("A request must be decodable from its encoding")
+            });
+            client.perform(request)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use super::*;
+
+    /// A client whose requests fail with the given errors, before succeeding
+    struct FlakyClient {
+        failures: std::sync::Mutex<Vec<ErrorKind>>,
+        calls: AtomicUsize,
+    }
+
+    impl FlakyClient {
+        fn new(failures: Vec<ErrorKind>) -> Self {
+            Self {
+                failures: std::sync::Mutex::new(failures),
+                calls: AtomicUsize::new(0),
+            }
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::Relaxed)
+        }
+    }
+
+    #[cfg_attr(feature = "async-send", async_trait::async_trait)]
+    #[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+    impl Client for FlakyClient {
+        type Error = std::io::Error;
+
+        async fn request(
+            &self,
+            _path: String,
+            _data: Option<Vec<u8>>,
+            _height: Option<BlockHeight>,
+            _prove: bool,
+        ) -> Result<EncodedResponseQuery, Self::Error> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            match self.failures.lock().unwrap().pop() {
+                Some(kind) => Err(std::io::Error::from(kind)),
+                None => Ok(EncodedResponseQuery::default()),
+            }
+        }
+
+        async fn perform<R>(&self, _request: R) -> Result<R::Output, RpcError>
+        where
+            R: tendermint_rpc::SimpleRequest,
+        {
+            unimplemented!()
+        }
+    }
+
+    fn retry_config(max_retries: usize) -> RetryConfig {
+        RetryConfig {
+            max_retries,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_multi_endpoint_failover() {
+        let client = MultiEndpointClient::new(
+            [
+                FlakyClient::new(vec![ErrorKind::ConnectionRefused]),
+                FlakyClient::new(vec![]),
+            ],
+            retry_config(3),
+        )
+        .unwrap();
+
+        // The first endpoint is down, so the request fails over to the second
+        client.simple_request("/".to_string()).await.unwrap();
+        let health: Vec<bool> =
+            client.endpoints().map(|(_, healthy)| healthy).collect();
+        assert_eq!(health, vec![false, true]);
+
+        // The next requests are sent to the second endpoint
+        client.simple_request("/".to_string()).await.unwrap();
+        let calls: Vec<usize> = client
+            .endpoints()
+            .map(|(client, _)| client.calls())
+            .collect();
+        assert_eq!(calls, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_multi_endpoint_retries() {
+        // A single endpoint is retried until it succeeds
+        let client = MultiEndpointClient::new(
+            [FlakyClient::new(vec![
+                ErrorKind::TimedOut,
+                ErrorKind::ConnectionReset,
+            ])],
+            retry_config(2),
+        )
+        .unwrap();
+        client.simple_request("/".to_string()).await.unwrap();
+        assert_eq!(client.endpoints().next().unwrap().0.calls(), 3);
+
+        // but not more than the max number of retries
+        let client = MultiEndpointClient::new(
+            [FlakyClient::new(vec![ErrorKind::TimedOut; 3])],
+            retry_config(2),
+        )
+        .unwrap();
+        let err = client.simple_request("/".to_string()).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TimedOut);
+        assert_eq!(client.endpoints().next().unwrap().0.calls(), 3);
+
+        // The errors that are not retryable are returned right away
+        let client = MultiEndpointClient::new(
+            [FlakyClient::new(vec![ErrorKind::InvalidData])],
+            retry_config(2),
+        )
+        .unwrap();
+        let err = client.simple_request("/".to_string()).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(client.endpoints().next().unwrap().0.calls(), 1);
+
+        assert!(MultiEndpointClient::<FlakyClient>::new([], retry_config(0))
+            .is_none());
+    }
+}
@@ -1,41 +1,576 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
 
 pub(super) mod eth_bridge;
 
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use borsh_ext::BorshSerializeExt;
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
-use namada_account::{Account, AccountPublicKeysMap};
-use namada_core::address::Address;
+use namada_account::{
+    Account, AccountPublicKeysMap, InitAccount, UpdateAccount,
+};
+use namada_core::address::{Address, InternalAddress};
 use namada_core::arith::checked;
+use namada_core::chain::ChainId;
 use namada_core::dec::Dec;
 use namada_core::hash::Hash;
 use namada_core::hints;
+use namada_core::key::common;
 use namada_core::masp::TokenMap;
+use namada_core::parameters::{EpochDuration, Parameters};
 use namada_core::storage::{
-    self, BlockHeight, BlockResults, Epoch, KeySeg, PrefixValue,
+    self, BlockHeight, BlockResults, Epoch, Epochs, KeySeg, PrefixValue,
+    TxIndex,
 };
-use namada_core::token::{Denomination, MaspDigitPos};
+use namada_core::time::{DateTimeUtc, DurationSecs};
+use namada_core::token::{self, Denomination, MaspDigitPos};
 use namada_core::uint::Uint;
+use namada_gas::event::GasUsed;
+use namada_gas::Gas;
+use namada_governance::parameters::GovernanceParameters;
+use namada_governance::pgf::parameters::PgfParameters;
+use namada_governance::storage::proposal::{
+    InitProposalData, VoteProposalData,
+};
 use namada_ibc::event::IbcEventType;
-use namada_state::{DBIter, LastBlock, StateRead, StorageHasher, DB};
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_proof_of_stake::parameters::{OwnedPosParams, PosParams};
+use namada_state::merkle_tree::{Smt, SubTreeRead, SubTreeWrite};
+use namada_state::{
+    DBIter, LastBlock, StateRead, StorageHasher, StoreType, DB,
+    EPOCH_SWITCH_BLOCKS_DELAY,
+};
 use namada_storage::{ResultExt, StorageRead};
-use namada_token::storage_key::masp_token_map_key;
+use namada_token::storage_key::{balance_key, masp_token_map_key};
+use namada_token::CompactConversionState;
+use namada_tx::data::pgf::UpdateStewardCommission;
+use namada_tx::data::wrapper::WrapperTx;
 #[cfg(any(test, feature = "async-client"))]
 use namada_tx::data::TxResult;
+use namada_tx::data::{pos, TxType};
+use namada_tx::event::Code;
+use namada_tx::{DataEncoding, Section, Signer, Tx};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 use self::eth_bridge::{EthBridge, ETH_BRIDGE};
+use crate::events::extend::{Height, Info, TxHash};
 use crate::events::log::dumb_queries;
-use crate::events::Event;
+use crate::events::schema::EventSchema;
+use crate::events::{known_event_schemas, Event};
 use crate::ibc::core::host::types::identifiers::{
     ChannelId, ClientId, PortId, Sequence,
 };
 use crate::masp::MaspTokenRewardData;
-use crate::queries::types::{RequestCtx, RequestQuery};
+use crate::queries::types::{RequestCtx, RequestQuery, ResponseQuery};
 use crate::queries::{require_latest_height, EncodedResponseQuery};
 use crate::tendermint::merkle::proof::ProofOps;
+use crate::tx::event::types::APPLIED as APPLIED_TX;
+use crate::tx::{
+    TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_CHANGE_COMMISSION_WASM,
+    TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
+    TX_CLAIM_REWARDS_WASM, TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL,
+    TX_REDELEGATE_WASM, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
+    TX_UPDATE_ACCOUNT_WASM, TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL,
+    TX_WITHDRAW_WASM,
+};
+
+/// Information about the current epoch and the start of the next one
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshDeserializer)]
+pub struct EpochInfo {
+    /// The epoch of the last committed block
+    pub epoch: Epoch,
+    /// The first block height of the current epoch
+    pub first_block_height: BlockHeight,
+    /// The height of the last committed block
+    pub last_block_height: BlockHeight,
+    /// The minimum block height at which the next epoch may start
+    pub next_epoch_min_start_height: BlockHeight,
+    /// The minimum block time at which the next epoch may start
+    pub next_epoch_min_start_time: DateTimeUtc,
+    /// The expected height of the first block of the next epoch. Until the
+    /// minimum start time of the next epoch is reached, this is only a lower
+    /// bound.
+    pub next_epoch_start_height: BlockHeight,
+    /// The estimated time of the first block of the next epoch, assuming that
+    /// blocks take the maximum expected time per block
+    pub next_epoch_start_time: DateTimeUtc,
+    /// The epoch duration parameters
+    pub epoch_duration: EpochDuration,
+    /// The first block heights of the known epochs
+    pub pred_epochs: Epochs,
+}
+
+/// The protocol, PoS, governance and PGF parameters at a block height
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshDeserializer)]
+pub struct ChainParameters {
+    /// The height of the parameters
+    pub height: BlockHeight,
+    /// The epoch of the height
+    pub epoch: Epoch,
+    /// The protocol parameters
+    pub protocol: Parameters,
+    /// The PoS parameters
+    pub pos: PosParams,
+    /// The governance parameters
+    pub governance: GovernanceParameters,
+    /// The PGF parameters
+    pub pgf: PgfParameters,
+}
+
+/// A parameter that differs between two [`ChainParameters`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParameterChange {
+    /// The name of the parameter, prefixed with its module
+    pub name: String,
+    /// The old value of the parameter, if it was set
+    pub old: Option<String>,
+    /// The new value of the parameter, if it is set
+    pub new: Option<String>,
+}
+
+impl ChainParameters {
+    /// List the parameters by their name prefixed with their module, with
+    /// their values formatted for display. The values of maps and sets are
+    /// listed per key, so that their entries can be compared.
+    pub fn entries(&self) -> BTreeMap<String, String> {
+        let Parameters {
+            max_tx_bytes,
+            epoch_duration,
+            max_expected_time_per_block,
+            max_proposal_bytes,
+            max_block_gas,
+            vp_allowlist,
+            tx_allowlist,
+            implicit_vp_code_hash,
+            epochs_per_year,
+            max_signatures_per_transaction,
+            fee_unshielding_gas_limit,
+            fee_unshielding_descriptions_limit,
+            minimum_gas_price,
+            is_native_token_transferable,
+            max_epoch_hooks_gas,
+            epoch_hook_allowlist,
+            target_block_gas,
+            base_fee_max_change_rate,
+            masp_convert_anchor_grace_blocks,
+            masp_max_note_age,
+        } = &self.protocol;
+        let OwnedPosParams {
+            max_validator_slots,
+            pipeline_len,
+            unbonding_len,
+            tm_votes_per_token,
+            block_proposer_reward,
+            block_vote_reward,
+            max_inflation_rate,
+            target_staked_ratio,
+            duplicate_vote_min_slash_rate,
+            light_client_attack_min_slash_rate,
+            cubic_slashing_window_length,
+            validator_stake_threshold,
+            liveness_window_check,
+            liveness_threshold,
+            rewards_gain_p,
+            rewards_gain_d,
+            liquid_staking_receipts,
+            min_commission_rate,
+            required_validator_metadata,
+            max_validator_stake_share,
+        } = &self.pos.owned;
+        let GovernanceParameters {
+            min_proposal_fund,
+            max_proposal_code_size,
+            min_proposal_voting_period,
+            max_proposal_period,
+            max_proposal_content_size,
+            min_proposal_grace_epochs,
+            max_proposal_latency,
+        } = &self.governance;
+        let PgfParameters {
+            stewards,
+            pgf_inflation_rate,
+            stewards_inflation_rate,
+        } = &self.pgf;
+
+        let mut entries = BTreeMap::new();
+        macro_rules! add_entries {
+            ($module:literal: $($field:ident = $value:expr),* $(,)?) => {
+                $(entries.insert(
+                    concat!($module, ".", stringify!($field)).to_string(),
+                    $value.to_string(),
+                );)*
+            };
+        }
+        add_entries!("protocol":
+            max_tx_bytes = max_tx_bytes,
+            epoch_min_num_of_blocks = epoch_duration.min_num_of_blocks,
+            epoch_min_duration = epoch_duration.min_duration,
+            max_expected_time_per_block = max_expected_time_per_block,
+            max_proposal_bytes = max_proposal_bytes.get(),
+            max_block_gas = max_block_gas,
+            vp_allowlist = vp_allowlist.join(", "),
+            tx_allowlist = tx_allowlist.join(", "),
+            implicit_vp_code_hash = implicit_vp_code_hash
+                .as_ref()
+                .map_or_else(|| "none".to_string(), Hash::to_string),
+            epochs_per_year = epochs_per_year,
+            max_signatures_per_transaction = max_signatures_per_transaction,
+            fee_unshielding_gas_limit = fee_unshielding_gas_limit,
+            fee_unshielding_descriptions_limit =
+                fee_unshielding_descriptions_limit,
+            is_native_token_transferable = is_native_token_transferable,
+            max_epoch_hooks_gas = max_epoch_hooks_gas,
+            epoch_hook_allowlist = epoch_hook_allowlist.join(", "),
+            target_block_gas = target_block_gas,
+            base_fee_max_change_rate = base_fee_max_change_rate,
+            masp_convert_anchor_grace_blocks =
+                masp_convert_anchor_grace_blocks,
+            masp_max_note_age = masp_max_note_age,
+        );
+        for (token, price) in minimum_gas_price {
+            entries.insert(
+                format!("protocol.minimum_gas_price.{token}"),
+                price.to_string(),
+            );
+        }
+        add_entries!("pos":
+            max_validator_slots = max_validator_slots,
+            pipeline_len = pipeline_len,
+            unbonding_len = unbonding_len,
+            tm_votes_per_token = tm_votes_per_token,
+            block_proposer_reward = block_proposer_reward,
+            block_vote_reward = block_vote_reward,
+            max_inflation_rate = max_inflation_rate,
+            target_staked_ratio = target_staked_ratio,
+            duplicate_vote_min_slash_rate = duplicate_vote_min_slash_rate,
+            light_client_attack_min_slash_rate =
+                light_client_attack_min_slash_rate,
+            cubic_slashing_window_length = cubic_slashing_window_length,
+            validator_stake_threshold =
+                validator_stake_threshold.to_string_native(),
+            liveness_window_check = liveness_window_check,
+            liveness_threshold = liveness_threshold,
+            rewards_gain_p = rewards_gain_p,
+            rewards_gain_d = rewards_gain_d,
+            liquid_staking_receipts = liquid_staking_receipts,
+            min_commission_rate = min_commission_rate,
+            required_validator_metadata =
+                itertools::join(required_validator_metadata, ", "),
+            max_validator_stake_share = max_validator_stake_share
+                .map_or_else(|| "none".to_string(), |share| share.to_string()),
+        );
+        add_entries!("governance":
+            min_proposal_fund = min_proposal_fund.to_string_native(),
+            max_proposal_code_size = max_proposal_code_size,
+            min_proposal_voting_period = min_proposal_voting_period,
+            max_proposal_period = max_proposal_period,
+            max_proposal_content_size = max_proposal_content_size,
+            min_proposal_grace_epochs = min_proposal_grace_epochs,
+            max_proposal_latency = max_proposal_latency,
+        );
+        for steward in stewards {
+            entries.insert(
+                format!("pgf.stewards.{steward}"),
+                "steward".to_string(),
+            );
+        }
+        add_entries!("pgf":
+            pgf_inflation_rate = pgf_inflation_rate,
+            stewards_inflation_rate = stewards_inflation_rate,
+        );
+        entries
+    }
+
+    /// Get the parameters that differ from the `other` parameters, ordered by
+    /// name. The old values are the ones of `self`.
+    pub fn diff(&self, other: &ChainParameters) -> Vec<ParameterChange> {
+        let mut old = self.entries();
+        let mut changes = vec![];
+        for (name, new) in other.entries() {
+            match old.remove(&name) {
+                Some(old) if old == new => {}
+                old => changes.push(ParameterChange {
+                    name,
+                    old,
+                    new: Some(new),
+                }),
+            }
+        }
+        changes.extend(old.into_iter().map(|(name, old)| ParameterChange {
+            name,
+            old: Some(old),
+            new: None,
+        }));
+        changes.sort_by(|a, b| a.name.cmp(&b.name));
+        changes
+    }
+}
+
+/// Statistics of the supply and the inflation of the native token
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshDeserializer)]
+pub struct SupplyStats {
+    /// The epoch of the last committed block
+    pub epoch: Epoch,
+    /// The total supply of the native token
+    pub total_supply: token::Amount,
+    /// The total supply, without the balance of the PGF account, from which
+    /// the inflation is computed
+    pub effective_supply: token::Amount,
+    /// The PoS inflation minted at the start of the epoch
+    pub last_pos_inflation: token::Amount,
+    /// The PGF inflation minted at the start of the epoch, for the PGF
+    /// account and the stewards
+    pub last_pgf_inflation: token::Amount,
+    /// The annual inflation rate, at the last epoch's inflation
+    pub annual_inflation_rate: Dec,
+    /// The ratio of the effective supply that was staked in the last epoch
+    pub staked_ratio: Dec,
+}
+
+/// A structured description of a transaction
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshDeserializer)]
+pub struct DecodedTx {
+    /// The hash of the transaction header
+    pub hash: Hash,
+    /// The hash of the header of the inner transaction
+    pub raw_hash: Hash,
+    /// The chain which the transaction is being submitted to
+    pub chain_id: ChainId,
+    /// The time at which the transaction expires
+    pub expiration: Option<DateTimeUtc>,
+    /// The transaction timestamp
+    pub timestamp: DateTimeUtc,
+    /// The wrapper fields, if the transaction is wrapped
+    pub wrapper: Option<WrapperTx>,
+    /// The hash of the transaction code
+    pub code_hash: Option<Hash>,
+    /// The tag of the transaction code
+    pub code_tag: Option<String>,
+    /// The name of the transaction code in the wasm registry
+    pub code_name: Option<String>,
+    /// The encoding of the transaction data
+    pub data_encoding: Option<DataEncoding>,
+    /// The transaction data formatted as JSON, if the transaction code is
+    /// known and the data could be decoded
+    pub data: Option<String>,
+    /// The transaction memo
+    pub memo: Option<Vec<u8>>,
+    /// The signature sections of the transaction
+    pub signatures: Vec<DecodedSignature>,
+}
+
+/// A wasm code allowed on chain, with its metadata from the wasm registry
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshDeserializer)]
+pub struct WasmCode {
+    /// The hash of the code
+    pub hash: Hash,
+    /// The name of the code in the wasm registry, if it's registered
+    pub name: Option<String>,
+    /// The size of the code in bytes, if it's stored on chain
+    pub size: Option<u64>,
+    /// The height at which the code was added to the wasm registry, if known
+    pub added_at: Option<BlockHeight>,
+}
+
+/// A signature section of a decoded transaction
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshDeserializer)]
+pub struct DecodedSignature {
+    /// The hashes of the signed sections
+    pub targets: Vec<Hash>,
+    /// The address of the multisignature account that signed, if the signer
+    /// is given by its address
+    pub address: Option<Address>,
+    /// The indices of the signatures, with the public keys that made them if
+    /// they are included in the section
+    pub signers: Vec<(u8, Option<common::PublicKey>)>,
+}
+
+/// The results of the txs applied in a block and the protocol events
+/// processed in it
+#[derive(
+    Clone, Debug, Default, BorshSerialize, BorshDeserialize, BorshDeserializer,
+)]
+pub struct BlockResultsInfo {
+    /// The height of the block
+    pub height: BlockHeight,
+    /// The results of the txs in the block, in the order of their application
+    pub txs: Vec<TxResultInfo>,
+    /// The inflation rewards attributed to validators and their delegators
+    pub rewards: Vec<(Address, token::Amount)>,
+    /// The slashes processed in the block
+    pub slashes: Vec<(Address, token::Amount)>,
+    /// The other events of the block that are not emitted by a tx
+    pub events: Vec<Event>,
+}
+
+/// The result of a tx applied in a block
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshDeserializer)]
+pub struct TxResultInfo {
+    /// The hash of the tx
+    pub hash: Hash,
+    /// The result code of the tx, as a [`namada_tx::data::ResultCode`]
+    pub code: u32,
+    /// The gas used by the tx
+    pub gas_used: Gas,
+    /// Additional information on the result of the tx
+    pub info: String,
+    /// The events emitted by the tx
+    pub events: Vec<Event>,
+}
+
+/// The result of a wrapper tx, stored by the node under the hashes of the
+/// wrapper and of its inner tx
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshDeserializer)]
+pub struct AppliedTxResult {
+    /// The height of the block of the tx
+    pub height: BlockHeight,
+    /// The index of the tx in its block
+    pub index: TxIndex,
+    /// The hash of the wrapper tx
+    pub wrapper_hash: Hash,
+    /// The hash of the inner tx
+    pub inner_hash: Hash,
+    /// The result of the tx
+    pub result: TxResultInfo,
+}
+
+/// A tx that changed the balances of an address
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+)]
+pub struct TxHistoryEntry {
+    /// The height of the block of the tx
+    pub height: BlockHeight,
+    /// The index of the tx in its block
+    pub index: TxIndex,
+    /// The hash of the tx
+    pub hash: Hash,
+}
+
+/// The position of a tx in the history of an address, from which a page of
+/// the history starts. Formatted as `{height}.{index}` in query paths.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+)]
+pub struct TxHistoryCursor {
+    /// The height of the block of the tx
+    pub height: BlockHeight,
+    /// The index of the tx in its block
+    pub index: TxIndex,
+}
+
+impl std::fmt::Display for TxHistoryCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.height, self.index)
+    }
+}
+
+impl FromStr for TxHistoryCursor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (height, index) = s
+            .split_once('.')
+            .ok_or_else(|| format!("Invalid tx history cursor {s}"))?;
+        Ok(Self {
+            height: BlockHeight::from_str(height)
+                .map_err(|e| format!("Invalid tx history cursor {s}: {e}"))?,
+            index: TxIndex::from_str(index)
+                .map_err(|e| format!("Invalid tx history cursor {s}: {e}"))?,
+        })
+    }
+}
+
+/// A page of the history of the txs that changed the balances of an address,
+/// from the most recent one
+#[derive(
+    Clone, Debug, Default, BorshSerialize, BorshDeserialize, BorshDeserializer,
+)]
+pub struct TxHistoryPage {
+    /// The txs of the page
+    pub entries: Vec<TxHistoryEntry>,
+    /// The position of the tx from which the next page starts, if any
+    pub next: Option<TxHistoryCursor>,
+}
+
+/// The maximum number of txs in a page of the history of an address
+pub const MAX_TX_HISTORY_PAGE_SIZE: u64 = 100;
+
+/// A request to evaluate a VP against a tx applied to the state at a past
+/// height
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct EvalVpRequest {
+    /// The height of the state, or the last committed height when 0
+    pub height: BlockHeight,
+    /// The address whose VP is evaluated
+    pub address: Address,
+    /// The wasm code of the VP, which doesn't have to be allowed
+    pub vp_code: Vec<u8>,
+    /// The tx whose changes are validated by the VP
+    pub tx: Tx,
+}
+
+/// The result of the evaluation of a VP against a tx
+#[derive(
+    Clone, Debug, Default, BorshSerialize, BorshDeserialize, BorshDeserializer,
+)]
+pub struct EvalVpResult {
+    /// The height of the state that the tx was applied to
+    pub height: BlockHeight,
+    /// Whether the VP accepted the tx
+    pub accepted: bool,
+    /// The error of the VP, if it rejected the tx
+    pub error: Option<String>,
+    /// The keys changed by the tx
+    pub keys_changed: BTreeSet<storage::Key>,
+    /// The verifiers of the tx
+    pub verifiers: BTreeSet<Address>,
+    /// The gas used by the VP
+    pub gas_used: Gas,
+}
+
+/// A deterministic checksum of the application state at the last committed
+/// height, with which the state of different nodes can be compared
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+)]
+pub struct StateChecksum {
+    /// The last committed height
+    pub height: BlockHeight,
+    /// The root of the merkle tree
+    pub merkle_root: Hash,
+    /// The roots of the merkle sub-trees, by the name of their store
+    pub subtree_roots: BTreeMap<String, Hash>,
+    /// The roots of the storage subspaces of the internal addresses. The root
+    /// of a subspace is the root of a sparse merkle tree of its key-value
+    /// pairs, built like the sub-trees of the merkle tree.
+    pub internal_address_roots: BTreeMap<Address, Hash>,
+}
 
 type ConversionWithoutPath = (
     Address,
@@ -66,6 +601,12 @@ router! {SHELL,
     // The address of the native token
     ( "native_token" ) -> Address = native_token,
 
+    // The current base fee per unit of gas in the native token
+    ( "base_fee" ) -> token::Amount = base_fee,
+
+    // The max size of a tx, in bytes
+    ( "max_tx_bytes" ) -> u32 = max_tx_bytes,
+
     // Epoch of the input block height
     ( "epoch_at_height" / [height: BlockHeight]) -> Option<Epoch> = epoch_at_height,
 
@@ -75,13 +616,58 @@ router! {SHELL,
     // First block height of the current epoch
     ( "first_block_height_of_current_epoch" ) -> BlockHeight = first_block_height_of_current_epoch,
 
+    // First block height of the input epoch, if it's known
+    ( "first_block_height_of_epoch" / [epoch: Epoch] )
+        -> Option<BlockHeight> = first_block_height_of_epoch,
+
+    // The protocol, PoS, governance and PGF parameters at the input height,
+    // or at the last committed height if 0
+    ( "chain_parameters" / [height: BlockHeight] )
+        -> ChainParameters = chain_parameters,
+
+    // The current epoch, its predecessors and the expected start of the next
+    // one
+    ( "epoch_info" ) -> EpochInfo = epoch_info,
+
+    // The supply of the native token, the last inflation and the staked ratio
+    ( "supply_stats" ) -> SupplyStats = supply_stats,
+
     // Raw storage access - read value
     ( "value" / [storage_key: storage::Key] )
         -> Vec<u8> = (with_options storage_value),
 
+    // Raw storage access - read the values of the storage keys given as the
+    // request data, in one round-trip
+    ( "multi_value" )
+        -> Vec<Option<Vec<u8>>> = (with_options storage_multi_value),
+
+    // Read the balances of the (token, owner) pairs given as the request
+    // data, in one round-trip
+    ( "multi_balance" )
+        -> Vec<token::Amount> = (with_options storage_multi_balance),
+
     // Dry run a transaction
     ( "dry_run_tx" ) -> TxResult = (with_options dry_run_tx),
 
+    // Evaluate a VP against a tx applied to the state at a past height, to
+    // investigate why a VP rejected a tx
+    ( "dev" / "eval_vp" ) -> EvalVpResult = (with_options eval_vp),
+
+    // Decode the transaction given as the request data
+    ( "decode_tx" ) -> DecodedTx = (with_options decode_tx),
+
+    // The wasm codes of the transactions allowed by the tx allowlist
+    ( "wasm" / "allowed_txs" ) -> Vec<WasmCode> = allowed_tx_codes,
+
+    // The wasm codes of the validity predicates allowed by the vp allowlist
+    ( "wasm" / "allowed_vps" ) -> Vec<WasmCode> = allowed_vp_codes,
+
+    // The name of the given wasm code hash in the wasm registry
+    ( "wasm" / "name" / [code_hash: Hash] ) -> Option<String> = code_name,
+
+    // The wasm codes of the transactions exempt from the minimum fee
+    ( "wasm" / "fee_exemptions" ) -> Vec<WasmCode> = fee_exempt_codes,
+
     // Raw storage access - prefix iterator
     ( "prefix" / [storage_key: storage::Key] )
         -> Vec<PrefixValue> = (with_options storage_prefix),
@@ -90,21 +676,46 @@ router! {SHELL,
     ( "has_key" / [storage_key: storage::Key] )
         -> bool = storage_has_key,
 
+    // Checksum of the state at the last committed height. This merklizes the
+    // whole storage of the internal addresses, so the node only serves it
+    // when enabled in its config.
+    ( "state_checksum" ) -> StateChecksum = state_checksum,
+
     // Conversion state access - read conversion
     ( "conv" / [asset_type: AssetType] ) -> Option<Conversion> = read_conversion,
 
     // Conversion state access - read conversion
     ( "conversions" ) -> BTreeMap<AssetType, ConversionWithoutPath> = read_conversions,
 
+    // Conversion state access - read the compact conversion state
+    ( "compact_conversions" ) -> CompactConversionState = read_compact_conversions,
+
     // Conversion state access - read conversion
     ( "masp_reward_tokens" ) -> Vec<MaspTokenRewardData> = masp_reward_tokens,
 
     // Block results access - read bit-vec
     ( "results" ) -> Vec<BlockResults> = read_results,
 
+    // The results of the txs and the protocol events of a block, for as long
+    // as its events are held in the event log
+    ( "block_results" / [height: BlockHeight] ) -> BlockResultsInfo = block_results,
+
     // was the transaction applied?
     ( "applied" / [tx_hash: Hash] ) -> Option<Event> = applied,
 
+    // The result of a wrapper tx, by the hash of the wrapper or of its inner
+    // tx, if the node stores the tx results
+    ( "applied_tx" / [tx_hash: Hash] ) -> Option<AppliedTxResult> = applied_tx,
+
+    // The schemas of the typed events emitted by the ledger
+    ( "events" / "schemas" ) -> Vec<EventSchema> = event_schemas,
+
+    // The txs that changed the balances of an address, backwards from the
+    // given position or from the most recent one, if the node indexes them
+    ( "tx_history" / [owner: Address] / [per_page: u64]
+        / [start: opt TxHistoryCursor] )
+        -> TxHistoryPage = tx_history,
+
     // Query account subspace
     ( "account" / [owner: Address] ) -> Option<Account> = account,
 
@@ -114,6 +725,9 @@ router! {SHELL,
     // IBC UpdateClient event
     ( "ibc_client_update" / [client_id: ClientId] / [consensus_height: BlockHeight] ) -> Option<Event> = ibc_client_update,
 
+    // The full denom trace of an IBC token, registered at its first receipt
+    ( "ibc_denom_trace" / [token: Address] ) -> Option<String> = ibc_denom_trace,
+
     // IBC packet event
     ( "ibc_packet" / [event_type: IbcEventType] / [source_port: PortId] / [source_channel: ChannelId] / [destination_port: PortId] / [destination_channel: ChannelId] / [sequence: Sequence]) -> Option<Event> = ibc_packet,
 }
@@ -131,6 +745,21 @@ where
     unimplemented!("Dry running tx requires \"wasm-runtime\" feature.")
 }
 
+fn eval_vp<D, H, V, T>(
+    _ctx: RequestCtx<'_, D, H, V, T>,
+    _request: &RequestQuery,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    // The query is only served by the nodes that enabled it, which intercept
+    // it before it reaches the router
+    Err(namada_storage::Error::new_const(
+        "The VP evaluation query is disabled on this node",
+    ))
+}
+
 /// Query to read block results from storage
 pub fn read_results<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -168,6 +797,48 @@ where
     Ok(results)
 }
 
+/// Query the checksum of the state at the last committed height
+fn state_checksum<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<StateChecksum>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let in_mem = ctx.state.in_mem();
+    let tree = &in_mem.block.tree;
+    let subtree_roots = StoreType::iter_subtrees()
+        .map(|store_type| {
+            (store_type.to_string(), Hash(tree.sub_root(store_type).0))
+        })
+        .collect();
+
+    // The committed key-value pairs are read from the DB and merklized per
+    // internal address
+    let mut subtrees = BTreeMap::<Address, Smt<H>>::new();
+    for (key, value, _gas) in ctx.state.db().iter_prefix(None) {
+        let key = storage::Key::parse(key).into_storage_result()?;
+        let Some(addr @ Address::Internal(_)) = key.fst_address() else {
+            continue;
+        };
+        let mut subtree = subtrees.entry(addr.clone()).or_default();
+        subtree.subtree_update(&key, &value).into_storage_result()?;
+    }
+    let internal_address_roots = subtrees
+        .iter()
+        .map(|(addr, subtree)| {
+            (addr.clone(), SubTreeRead::root(&subtree).into())
+        })
+        .collect();
+
+    Ok(StateChecksum {
+        height: in_mem.get_last_block_height(),
+        merkle_root: Hash(tree.root().0),
+        subtree_roots,
+        internal_address_roots,
+    })
+}
+
 /// Query to read the conversion state
 fn read_conversions<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -193,6 +864,17 @@ where
         .collect())
 }
 
+/// Query to read the conversion state in its compact form
+fn read_compact_conversions<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<CompactConversionState>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    Ok(ctx.state.in_mem().conversion_state.to_compact())
+}
+
 /// Query to read a conversion from storage
 fn read_conversion<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -324,6 +1006,26 @@ where
     Ok(data)
 }
 
+fn base_fee<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<token::Amount>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_parameters::read_base_fee(ctx.state)
+}
+
+fn max_tx_bytes<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<u32>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_parameters::read_max_tx_bytes(ctx.state)
+}
+
 fn epoch_at_height<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     height: BlockHeight,
@@ -365,6 +1067,497 @@ where
         .cloned()
 }
 
+fn first_block_height_of_epoch<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    epoch: Epoch,
+) -> namada_storage::Result<Option<BlockHeight>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    Ok(ctx
+        .state
+        .in_mem()
+        .block
+        .pred_epochs
+        .get_start_height_of_epoch(epoch))
+}
+
+/// Read the parameters from the state at the given height. The node must keep
+/// the diffs of the height to read the parameters changed since then.
+fn chain_parameters<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    height: BlockHeight,
+) -> namada_storage::Result<ChainParameters>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let state = ctx.state.at_height(height);
+    Ok(ChainParameters {
+        height: state.height(),
+        epoch: state.get_block_epoch()?,
+        protocol: namada_parameters::read(&state)?,
+        pos: namada_proof_of_stake::storage::read_pos_params(&state)?,
+        governance: namada_governance::storage::get_parameters(&state)?,
+        pgf: namada_governance::pgf::storage::get_parameters(&state)?,
+    })
+}
+
+fn supply_stats<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<SupplyStats>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let native_token = ctx.state.in_mem().native_token.clone();
+    let total_supply =
+        namada_token::read_total_supply(ctx.state, &native_token)?;
+    let effective_supply =
+        namada_token::get_effective_total_native_supply(ctx.state)?;
+    let last_pos_inflation =
+        namada_proof_of_stake::storage::read_last_pos_inflation_amount(
+            ctx.state,
+        )?
+        .unwrap_or_default();
+    let last_pgf_inflation =
+        namada_governance::pgf::storage::get_last_inflation_amount(ctx.state)?
+            .unwrap_or_default();
+    let staked_ratio =
+        namada_proof_of_stake::storage::read_last_staked_ratio(ctx.state)?
+            .unwrap_or_default();
+
+    let epochs_per_year: u64 = ctx
+        .state
+        .read(&namada_parameters::storage::get_epochs_per_year_key())?
+        .expect("Epochs per year should exist in parameters storage");
+    let annual_inflation_rate = if effective_supply.is_zero() {
+        Dec::zero()
+    } else {
+        let last_inflation =
+            Dec::try_from(checked!(last_pos_inflation + last_pgf_inflation)?)
+                .into_storage_result()?;
+        let effective_supply =
+            Dec::try_from(effective_supply).into_storage_result()?;
+        checked!(
+            last_inflation * Dec::from(epochs_per_year) / effective_supply
+        )?
+    };
+
+    Ok(SupplyStats {
+        epoch: ctx.state.in_mem().last_epoch,
+        total_supply,
+        effective_supply,
+        last_pos_inflation,
+        last_pgf_inflation,
+        annual_inflation_rate,
+        staked_ratio,
+    })
+}
+
+fn epoch_info<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<EpochInfo>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let in_mem = ctx.state.in_mem();
+    let parameters = namada_parameters::read(ctx.state)?;
+    let pred_epochs = in_mem.block.pred_epochs.clone();
+    let first_block_height =
+        pred_epochs.first_block_heights.last().cloned().ok_or(
+            namada_storage::Error::new(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "The pred_epochs is unexpectedly empty",
+            )),
+        )?;
+    let last_block_height = in_mem.get_last_block_height();
+    let last_block_time = in_mem
+        .last_block
+        .as_ref()
+        .map(|last_block| last_block.time)
+        .unwrap_or(in_mem.next_epoch_min_start_time);
+    let time_per_block = parameters.max_expected_time_per_block.0;
+
+    let (next_epoch_start_height, next_epoch_start_time) = match in_mem
+        .update_epoch_blocks_delay
+    {
+        // The switch to the next epoch is already scheduled
+        Some(blocks_until_switch) => {
+            let blocks_until_switch = u64::from(blocks_until_switch);
+            (
+                BlockHeight(checked!(
+                    last_block_height.0 + blocks_until_switch
+                )?),
+                last_block_time
+                    + DurationSecs(checked!(
+                        blocks_until_switch * time_per_block
+                    )?),
+            )
+        }
+        // The switch is scheduled in the first block that satisfies the
+        // minimum epoch duration
+        None => {
+            let switch_delay = u64::from(EPOCH_SWITCH_BLOCKS_DELAY);
+            let first_eligible_height = std::cmp::max(
+                in_mem.next_epoch_min_start_height,
+                last_block_height.next_height(),
+            );
+            let start_height =
+                BlockHeight(checked!(first_eligible_height.0 + switch_delay)?);
+            let blocks_until_start =
+                checked!(start_height.0 - last_block_height.0)?;
+            let start_time = std::cmp::max(
+                last_block_time
+                    + DurationSecs(checked!(
+                        blocks_until_start * time_per_block
+                    )?),
+                in_mem.next_epoch_min_start_time
+                    + DurationSecs(checked!(switch_delay * time_per_block)?),
+            );
+            (start_height, start_time)
+        }
+    };
+
+    Ok(EpochInfo {
+        epoch: in_mem.last_epoch,
+        first_block_height,
+        last_block_height,
+        next_epoch_min_start_height: in_mem.next_epoch_min_start_height,
+        next_epoch_min_start_time: in_mem.next_epoch_min_start_time,
+        next_epoch_start_height,
+        next_epoch_start_time,
+        epoch_duration: parameters.epoch_duration,
+        pred_epochs,
+    })
+}
+
+fn decode_tx<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let tx = Tx::try_from(&request.data[..]).into_storage_result()?;
+
+    let code = tx
+        .get_section(tx.code_sechash())
+        .and_then(|section| section.code_sec());
+    let code_hash = code.as_ref().map(|code| code.code.hash());
+    let code_name = match &code_hash {
+        Some(code_hash) => wasm_code_name(ctx.state, code_hash)?,
+        None => None,
+    };
+    let data = code_name
+        .as_deref()
+        .and_then(|code_name| decode_tx_data(&tx, code_name));
+    let signatures = tx
+        .sections
+        .iter()
+        .filter_map(|section| match section {
+            Section::Authorization(auth) => Some(DecodedSignature {
+                targets: auth.targets.clone(),
+                address: match &auth.signer {
+                    Signer::Address(address) => Some(address.clone()),
+                    Signer::PubKeys(_) => None,
+                },
+                signers: auth
+                    .signatures
+                    .keys()
+                    .map(|idx| {
+                        let public_key = match &auth.signer {
+                            Signer::PubKeys(pks) => {
+                                pks.get(usize::from(*idx)).cloned()
+                            }
+                            Signer::Address(_) => None,
+                        };
+                        (*idx, public_key)
+                    })
+                    .collect(),
+            }),
+            _ => None,
+        })
+        .collect();
+
+    let decoded = DecodedTx {
+        hash: tx.header_hash(),
+        raw_hash: tx.raw_header_hash(),
+        chain_id: tx.header.chain_id.clone(),
+        expiration: tx.header.expiration,
+        timestamp: tx.header.timestamp,
+        wrapper: match &tx.header.tx_type {
+            TxType::Wrapper(wrapper) => Some(*wrapper.clone()),
+            _ => None,
+        },
+        code_hash,
+        code_tag: code.and_then(|code| code.tag),
+        code_name,
+        data_encoding: tx.data_encoding(),
+        data,
+        memo: tx.memo(),
+        signatures,
+    };
+    Ok(EncodedResponseQuery {
+        data: decoded.serialize_to_vec(),
+        ..Default::default()
+    })
+}
+
+fn allowed_tx_codes<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<Vec<WasmCode>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    allowed_wasm_codes(
+        ctx.state,
+        &namada_parameters::storage::get_tx_allowlist_storage_key(),
+        "tx_",
+    )
+}
+
+fn allowed_vp_codes<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<Vec<WasmCode>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    allowed_wasm_codes(
+        ctx.state,
+        &namada_parameters::storage::get_vp_allowlist_storage_key(),
+        "vp_",
+    )
+}
+
+fn code_name<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    code_hash: Hash,
+) -> namada_storage::Result<Option<String>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    wasm_code_name(ctx.state, &code_hash)
+}
+
+fn fee_exempt_codes<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<Vec<WasmCode>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_parameters::read_fee_exemptions(ctx.state)?
+        .into_iter()
+        .map(|hash| {
+            Ok(WasmCode {
+                name: wasm_code_name(ctx.state, &hash)?,
+                size: ctx.state.read(&storage::Key::wasm_code_len(&hash))?,
+                added_at: ctx
+                    .state
+                    .read(&storage::Key::wasm_code_height(&hash))?,
+                hash,
+            })
+        })
+        .collect()
+}
+
+/// List the wasm codes allowed by the given allowlist. An empty allowlist
+/// allows any code, in which case the codes of the wasm registry whose names
+/// start with `name_prefix` are listed.
+fn allowed_wasm_codes<S>(
+    storage: &S,
+    allowlist_key: &storage::Key,
+    name_prefix: &str,
+) -> namada_storage::Result<Vec<WasmCode>>
+where
+    S: StorageRead,
+{
+    let prefix = storage::Key::wasm_code_name_prefix();
+    let mut registry = BTreeMap::new();
+    for entry in namada_storage::iter_prefix::<Hash>(storage, &prefix)? {
+        let (key, hash) = entry?;
+        if let Some(storage::DbKeySeg::StringSeg(name)) = key.last() {
+            registry.insert(hash, name.clone());
+        }
+    }
+
+    let allowlist: Vec<String> =
+        storage.read(allowlist_key)?.unwrap_or_default();
+    let codes: Vec<(Hash, Option<String>)> = if allowlist.is_empty() {
+        registry
+            .into_iter()
+            .filter(|(_, name)| name.starts_with(name_prefix))
+            .map(|(hash, name)| (hash, Some(name)))
+            .collect()
+    } else {
+        allowlist
+            .iter()
+            .map(|hash| {
+                let hash = Hash::from_str(hash).into_storage_result()?;
+                Ok((hash, registry.get(&hash).cloned()))
+            })
+            .collect::<namada_storage::Result<_>>()?
+    };
+    codes
+        .into_iter()
+        .map(|(hash, name)| {
+            Ok(WasmCode {
+                size: storage.read(&storage::Key::wasm_code_len(&hash))?,
+                added_at: storage
+                    .read(&storage::Key::wasm_code_height(&hash))?,
+                hash,
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Find the name of the given code hash in the wasm registry
+fn wasm_code_name<S>(
+    storage: &S,
+    code_hash: &Hash,
+) -> namada_storage::Result<Option<String>>
+where
+    S: StorageRead,
+{
+    let prefix = storage::Key::wasm_code_name_prefix();
+    for entry in namada_storage::iter_prefix::<Hash>(storage, &prefix)? {
+        let (key, hash) = entry?;
+        if &hash == code_hash {
+            if let Some(storage::DbKeySeg::StringSeg(name)) = key.last() {
+                return Ok(Some(name.clone()));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Format the data of a transaction with known code as JSON
+fn decode_tx_data(tx: &Tx, code_name: &str) -> Option<String> {
+    fn to_json<T>(tx: &Tx) -> Option<String>
+    where
+        T: BorshDeserialize + DeserializeOwned + Serialize,
+    {
+        let data = tx.decode_data::<T>()?.ok()?;
+        serde_json::to_string(&data).ok()
+    }
+
+    match code_name {
+        TX_TRANSFER_WASM => to_json::<token::Transfer>(tx),
+        TX_BOND_WASM | TX_UNBOND_WASM => to_json::<pos::Bond>(tx),
+        TX_WITHDRAW_WASM => to_json::<pos::Withdraw>(tx),
+        TX_CLAIM_REWARDS_WASM => to_json::<pos::ClaimRewards>(tx),
+        TX_REDELEGATE_WASM => to_json::<pos::Redelegation>(tx),
+        TX_CHANGE_COMMISSION_WASM => to_json::<pos::CommissionChange>(tx),
+        TX_CHANGE_CONSENSUS_KEY_WASM => to_json::<pos::ConsensusKeyChange>(tx),
+        TX_CHANGE_METADATA_WASM => to_json::<pos::MetaDataChange>(tx),
+        TX_BECOME_VALIDATOR_WASM => to_json::<pos::BecomeValidator>(tx),
+        TX_INIT_ACCOUNT_WASM => to_json::<InitAccount>(tx),
+        TX_UPDATE_ACCOUNT_WASM => to_json::<UpdateAccount>(tx),
+        TX_INIT_PROPOSAL => to_json::<InitProposalData>(tx),
+        TX_VOTE_PROPOSAL => to_json::<VoteProposalData>(tx),
+        TX_REVEAL_PK => to_json::<common::PublicKey>(tx),
+        TX_UPDATE_STEWARD_COMMISSION => to_json::<UpdateStewardCommission>(tx),
+        _ => None,
+    }
+}
+
+fn block_results<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    height: BlockHeight,
+) -> namada_storage::Result<BlockResultsInfo>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let last_height = ctx.state.in_mem().get_last_block_height();
+    if height > last_height {
+        return Err(namada_storage::Error::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "The block at height {height} has not been committed yet, the \
+                 last committed block height is {last_height}"
+            ),
+        )));
+    }
+
+    let mut results = BlockResultsInfo {
+        height,
+        ..Default::default()
+    };
+    let mut events = vec![];
+    for event in ctx.event_log.iter() {
+        if event.read_attribute_opt::<Height>().into_storage_result()?
+            == Some(height)
+        {
+            events.push(event);
+        }
+    }
+    let mut tx_events: Vec<(Hash, Event)> = vec![];
+    // The event log iterates from the most recent events
+    for event in events.into_iter().rev() {
+        let kind = event.kind();
+        if *kind == APPLIED_TX {
+            results.txs.push(TxResultInfo {
+                hash: event.read_attribute::<TxHash>().into_storage_result()?,
+                code: event
+                    .read_attribute::<Code>()
+                    .into_storage_result()?
+                    .to_u32(),
+                gas_used: event
+                    .read_attribute_opt::<GasUsed>()
+                    .into_storage_result()?
+                    .unwrap_or_default(),
+                info: event
+                    .read_attribute_opt::<Info>()
+                    .into_storage_result()?
+                    .unwrap_or_default(),
+                events: vec![],
+            });
+        } else if *kind == pos_events::SLASH {
+            results.slashes.push((
+                event
+                    .read_attribute::<SlashedValidator>()
+                    .into_storage_result()?,
+                raw_amount(event.raw_read_attribute::<SlashedAmount<'_>>())?,
+            ));
+        } else if *kind == pos_events::REWARDS {
+            results.rewards.push((
+                event
+                    .read_attribute::<RewardedValidator>()
+                    .into_storage_result()?,
+                raw_amount(event.raw_read_attribute::<RewardsAmount<'_>>())?,
+            ));
+        } else if let Some(hash) =
+            event.read_attribute_opt::<TxHash>().into_storage_result()?
+        {
+            tx_events.push((hash, event.clone()));
+        } else {
+            results.events.push(event.clone());
+        }
+    }
+    for (hash, event) in tx_events {
+        match results.txs.iter_mut().find(|tx| tx.hash == hash) {
+            Some(tx) => tx.events.push(event),
+            None => results.events.push(event),
+        }
+    }
+    Ok(results)
+}
+
+/// Parse a token amount event attribute, which holds the raw amount
+fn raw_amount(value: Option<&str>) -> namada_storage::Result<token::Amount> {
+    token::Amount::from_str(value.unwrap_or_default(), 0u8)
+        .into_storage_result()
+}
+
 /// Returns data with `vec![]` when the storage key is not found. For all
 /// borsh-encoded types, it is safe to check `data.is_empty()` to see if the
 /// value was found, except for unit - see `fn query_storage_value` in
@@ -374,6 +1567,137 @@ fn storage_value<D, H, V, T>(
     request: &RequestQuery,
     storage_key: storage::Key,
 ) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let queried_height = queried_value_height(&ctx, request)?;
+    let (value, proof) = read_value_with_proof(
+        &ctx,
+        &storage_key,
+        queried_height,
+        request.prove,
+    )?;
+    match value {
+        Some(value) => Ok(EncodedResponseQuery {
+            data: value,
+            proof,
+            info: Default::default(),
+            height: queried_height,
+        }),
+        None => Ok(EncodedResponseQuery {
+            data: vec![],
+            proof,
+            info: format!("No value found for key: {}", storage_key),
+            height: queried_height,
+        }),
+    }
+}
+
+/// The maximum number of storage keys in a multi-value query
+const MAX_MULTI_VALUE_KEYS: usize = 1_000;
+
+fn storage_multi_value<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let keys: Vec<storage::Key> =
+        BorshDeserialize::try_from_slice(&request.data)
+            .into_storage_result()?;
+    let response = read_multi_values(&ctx, request, &keys)?;
+    Ok(EncodedResponseQuery {
+        data: response.data.serialize_to_vec(),
+        proof: response.proof,
+        height: response.height,
+        ..Default::default()
+    })
+}
+
+fn storage_multi_balance<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let balances: Vec<(Address, Address)> =
+        BorshDeserialize::try_from_slice(&request.data)
+            .into_storage_result()?;
+    let keys: Vec<storage::Key> = balances
+        .iter()
+        .map(|(token, owner)| balance_key(token, owner))
+        .collect();
+    let response = read_multi_values(&ctx, request, &keys)?;
+    let amounts = response
+        .data
+        .into_iter()
+        .map(|value| {
+            value
+                .map(|value| token::Amount::try_from_slice(&value[..]))
+                .transpose()
+                .map(Option::unwrap_or_default)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .into_storage_result()?;
+    Ok(EncodedResponseQuery {
+        data: amounts.serialize_to_vec(),
+        proof: response.proof,
+        height: response.height,
+        ..Default::default()
+    })
+}
+
+/// Read the values of the given storage keys at the queried height, in the
+/// same order, with a proof of all of them if requested.
+fn read_multi_values<D, H, V, T>(
+    ctx: &RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+    keys: &[storage::Key],
+) -> namada_storage::Result<ResponseQuery<Vec<Option<Vec<u8>>>>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    if keys.len() > MAX_MULTI_VALUE_KEYS {
+        return Err(namada_storage::Error::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "Cannot query more than {MAX_MULTI_VALUE_KEYS} storage keys \
+                 at once, got {}.",
+                keys.len()
+            ),
+        )));
+    }
+    let queried_height = queried_value_height(ctx, request)?;
+    let mut values = Vec::with_capacity(keys.len());
+    let mut ops = vec![];
+    for key in keys {
+        let (value, proof) =
+            read_value_with_proof(ctx, key, queried_height, request.prove)?;
+        values.push(value);
+        if let Some(mut proof) = proof {
+            ops.append(&mut proof.ops);
+        }
+    }
+    Ok(ResponseQuery {
+        data: values,
+        proof: request.prove.then_some(ProofOps { ops }),
+        height: queried_height,
+        ..Default::default()
+    })
+}
+
+/// Get the height of the storage values queried by the request, which may not
+/// be older than the node's `storage_read_past_height_limit`.
+fn queried_value_height<D, H, V, T>(
+    ctx: &RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+) -> namada_storage::Result<BlockHeight>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
@@ -403,47 +1727,42 @@ where
             )));
         }
     }
+    Ok(queried_height)
+}
 
-    match ctx
+/// Read a storage value at the given height, with its existence or
+/// non-existence proof if requested.
+fn read_value_with_proof<D, H, V, T>(
+    ctx: &RequestCtx<'_, D, H, V, T>,
+    storage_key: &storage::Key,
+    queried_height: BlockHeight,
+    prove: bool,
+) -> namada_storage::Result<(Option<Vec<u8>>, Option<ProofOps>)>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let (value, _gas) = ctx
         .state
-        .db_read_with_height(&storage_key, queried_height)
-        .into_storage_result()?
-    {
-        (Some(value), _gas) => {
-            let proof = if request.prove {
-                let proof = ctx
-                    .state
-                    .get_existence_proof(&storage_key, &value, queried_height)
-                    .into_storage_result()?;
-                Some(proof)
-            } else {
-                None
-            };
-            Ok(EncodedResponseQuery {
-                data: value,
-                proof,
-                info: Default::default(),
-                height: queried_height,
-            })
-        }
-        (None, _gas) => {
-            let proof = if request.prove {
-                let proof = ctx
-                    .state
-                    .get_non_existence_proof(&storage_key, queried_height)
-                    .into_storage_result()?;
-                Some(proof)
-            } else {
-                None
-            };
-            Ok(EncodedResponseQuery {
-                data: vec![],
-                proof,
-                info: format!("No value found for key: {}", storage_key),
-                height: queried_height,
-            })
+        .db_read_with_height(storage_key, queried_height)
+        .into_storage_result()?;
+    let proof = if prove {
+        let proof = match &value {
+            Some(value) => ctx.state.get_existence_proof(
+                storage_key,
+                value,
+                queried_height,
+            ),
+            None => ctx
+                .state
+                .get_non_existence_proof(storage_key, queried_height),
         }
-    }
+        .into_storage_result()?;
+        Some(proof)
+    } else {
+        None
+    };
+    Ok((value, proof))
 }
 
 fn storage_prefix<D, H, V, T>(
@@ -455,26 +1774,37 @@ where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
 {
-    require_latest_height(&ctx, request)?;
-
-    let iter = namada_storage::iter_prefix_bytes(ctx.state, &storage_key)?;
-    let data: namada_storage::Result<Vec<PrefixValue>> = iter
-        .map(|iter_result| {
-            let (key, value) = iter_result?;
-            Ok(PrefixValue { key, value })
-        })
-        .collect();
-    let data = data?;
+    let last_committed_height = ctx.state.in_mem().get_last_block_height();
     let queried_height = {
         let height: BlockHeight = request.height.into();
         let is_last_height_query = height.0 == 0;
 
         if hints::likely(is_last_height_query) {
-            ctx.state.in_mem().get_last_block_height()
+            last_committed_height
         } else {
             height
         }
     };
+
+    let data = if queried_height < last_committed_height {
+        // The values at past heights are only available on archive nodes
+        ctx.state
+            .db_prefix_with_height(&storage_key, queried_height)
+            .into_storage_result()?
+            .into_iter()
+            .map(|(key, value)| PrefixValue { key, value })
+            .collect()
+    } else {
+        require_latest_height(&ctx, request)?;
+        let iter = namada_storage::iter_prefix_bytes(ctx.state, &storage_key)?;
+        let data: namada_storage::Result<Vec<PrefixValue>> = iter
+            .map(|iter_result| {
+                let (key, value) = iter_result?;
+                Ok(PrefixValue { key, value })
+            })
+            .collect();
+        data?
+    };
     let proof = if request.prove {
         let mut ops = vec![];
         for PrefixValue { key, value } in &data {
@@ -523,6 +1853,34 @@ where
     Ok(ctx.event_log.with_matcher(matcher).iter().next().cloned())
 }
 
+fn applied_tx<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    tx_hash: Hash,
+) -> namada_storage::Result<Option<AppliedTxResult>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    ctx.state
+        .db()
+        .read_tx_result(&tx_hash)
+        .into_storage_result()?
+        .map(|bytes| {
+            AppliedTxResult::try_from_slice(&bytes).into_storage_result()
+        })
+        .transpose()
+}
+
+fn event_schemas<D, H, V, T>(
+    _ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<Vec<EventSchema>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    Ok(known_event_schemas().into_schemas())
+}
+
 fn ibc_client_update<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     client_id: ClientId,
@@ -563,6 +1921,64 @@ where
     Ok(ctx.event_log.with_matcher(matcher).iter().next().cloned())
 }
 
+fn tx_history<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    owner: Address,
+    per_page: u64,
+    start: Option<TxHistoryCursor>,
+) -> namada_storage::Result<TxHistoryPage>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    if per_page == 0 || per_page > MAX_TX_HISTORY_PAGE_SIZE {
+        return Err(namada_storage::Error::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "The page size must be between 1 and \
+                 {MAX_TX_HISTORY_PAGE_SIZE}, got {per_page}"
+            ),
+        )));
+    }
+
+    let start = start.map(|TxHistoryCursor { height, index }| {
+        namada_storage::tx_history::key(&owner, height, index)
+    });
+    // Read one more entry to find the start of the next page
+    let limit = checked!(per_page + 1)? as usize;
+    let mut entries = vec![];
+    let history = ctx
+        .state
+        .db()
+        .read_tx_history_rev(&owner, start.as_ref(), limit)
+        .into_storage_result()?;
+    for (key, value) in history {
+        let key = storage::Key::parse(key).into_storage_result()?;
+        let (_, height, index) = namada_storage::tx_history::parse_key(&key)
+            .ok_or_else(|| {
+                namada_storage::Error::new(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Invalid tx history key {key}"),
+                ))
+            })?;
+        let hash = Hash::try_from_slice(&value).into_storage_result()?;
+        entries.push(TxHistoryEntry {
+            height,
+            index,
+            hash,
+        });
+    }
+    let next = if entries.len() > per_page as usize {
+        entries.pop().map(|TxHistoryEntry { height, index, .. }| {
+            TxHistoryCursor { height, index }
+        })
+    } else {
+        None
+    };
+
+    Ok(TxHistoryPage { entries, next })
+}
+
 fn account<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     owner: Address,
@@ -587,6 +2003,24 @@ where
     }
 }
 
+fn ibc_denom_trace<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    token: Address,
+) -> namada_storage::Result<Option<String>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    match token {
+        Address::Internal(InternalAddress::IbcToken(hash)) => {
+            let key =
+                namada_ibc::storage::ibc_denom_trace_key(hash.to_string());
+            ctx.state.read(&key)
+        }
+        _ => Ok(None),
+    }
+}
+
 fn revealed<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     owner: Address,
@@ -603,8 +2037,23 @@ where
 #[cfg(test)]
 mod test {
     use namada_core::address;
+    use namada_core::dec::Dec;
+    use namada_core::hash::Hash;
+    use namada_core::storage::{BlockHeight, Key, TxIndex};
+    use namada_core::token::Amount;
+    use namada_ibc::storage::{calc_hash, ibc_denom_trace_key, ibc_token};
+    use namada_parameters::storage::get_tx_allowlist_storage_key;
+    use namada_state::testing::TestState;
+    use namada_state::{StateRead, DB};
+    use namada_storage::mockdb::MockDBWriteBatch;
+    use namada_storage::{tx_history, StorageWrite};
     use namada_token::storage_key::balance_key;
 
+    use super::{
+        allowed_wasm_codes, wasm_code_name, ChainParameters, ParameterChange,
+        TxHistoryCursor,
+    };
+    use crate::queries::testing::TestClient;
     use crate::queries::RPC;
 
     #[test]
@@ -612,19 +2061,363 @@ mod test {
         let path = RPC.shell().epoch_path();
         assert_eq!("/shell/epoch", path);
 
+        let path = RPC.shell().epoch_info_path();
+        assert_eq!("/shell/epoch_info", path);
+
         let token_addr = address::testing::established_address_1();
         let owner = address::testing::established_address_2();
         let key = balance_key(&token_addr, &owner);
         let path = RPC.shell().storage_value_path(&key);
         assert_eq!(format!("/shell/value/{}", key), path);
 
+        let path = RPC.shell().storage_multi_value_path();
+        assert_eq!("/shell/multi_value", path);
+
+        let path = RPC.shell().storage_multi_balance_path();
+        assert_eq!("/shell/multi_balance", path);
+
         let path = RPC.shell().dry_run_tx_path();
         assert_eq!("/shell/dry_run_tx", path);
 
+        let path = RPC.shell().decode_tx_path();
+        assert_eq!("/shell/decode_tx", path);
+
+        let height = BlockHeight(3);
+        let path = RPC.shell().block_results_path(&height);
+        assert_eq!("/shell/block_results/3", path);
+
         let path = RPC.shell().storage_prefix_path(&key);
         assert_eq!(format!("/shell/prefix/{}", key), path);
 
         let path = RPC.shell().storage_has_key_path(&key);
         assert_eq!(format!("/shell/has_key/{}", key), path);
+
+        let path = RPC.shell().allowed_tx_codes_path();
+        assert_eq!("/shell/wasm/allowed_txs", path);
+
+        let code_hash = Hash::sha256(b"code");
+        let path = RPC.shell().code_name_path(&code_hash);
+        assert_eq!(format!("/shell/wasm/name/{}", code_hash), path);
+    }
+
+    /// Test resolving the full denom trace of an IBC token
+    #[tokio::test]
+    async fn test_ibc_denom_trace() {
+        let mut client = TestClient::new(RPC);
+        let trace = "transfer/channel-0/uatom";
+        let ibc_token = ibc_token(trace);
+        let path = RPC.shell().ibc_denom_trace_path(&ibc_token);
+        assert_eq!(format!("/shell/ibc_denom_trace/{}", ibc_token), path);
+
+        // The trace isn't registered yet
+        let resolved = RPC
+            .shell()
+            .ibc_denom_trace(&client, &ibc_token)
+            .await
+            .unwrap();
+        assert!(resolved.is_none());
+
+        client
+            .state
+            .write(&ibc_denom_trace_key(calc_hash(trace)), trace.to_string())
+            .unwrap();
+        client
+            .state
+            .commit_block_from_batch(MockDBWriteBatch)
+            .unwrap();
+        let resolved = RPC
+            .shell()
+            .ibc_denom_trace(&client, &ibc_token)
+            .await
+            .unwrap();
+        assert_eq!(resolved.as_deref(), Some(trace));
+
+        // Other tokens have no trace
+        let resolved = RPC
+            .shell()
+            .ibc_denom_trace(&client, &address::testing::nam())
+            .await
+            .unwrap();
+        assert!(resolved.is_none());
+    }
+
+    /// Test reading the tx history of an address page by page
+    #[tokio::test]
+    async fn test_tx_history_pages() {
+        let mut client = TestClient::new(RPC);
+        let owner = address::testing::established_address_1();
+        let other = address::testing::established_address_2();
+        let txs = [(9, 0), (9, 300), (256, 1), (300, 2), (1000, 0)];
+        for (height, index) in txs {
+            for owner in [&owner, &other] {
+                client
+                    .state
+                    .db_mut()
+                    .write_tx_history_entry(
+                        &mut MockDBWriteBatch,
+                        &tx_history::key(
+                            owner,
+                            BlockHeight(height),
+                            TxIndex(index),
+                        ),
+                        &Hash::sha256(format!("{height}/{index}")),
+                    )
+                    .unwrap();
+            }
+        }
+
+        let cursor = TxHistoryCursor {
+            height: BlockHeight(9),
+            index: TxIndex(300),
+        };
+        let path = RPC.shell().tx_history_path(&owner, &2, &Some(cursor));
+        assert_eq!(format!("/shell/tx_history/{owner}/2/9.300"), path);
+        assert_eq!(cursor.to_string().parse(), Ok(cursor));
+
+        // Read the history backwards, 2 txs at a time
+        let mut start = None;
+        let mut read = vec![];
+        loop {
+            let page = RPC
+                .shell()
+                .tx_history(&client, &owner, &2, &start)
+                .await
+                .unwrap();
+            assert!(page.entries.len() <= 2);
+            read.extend(page.entries);
+            match page.next {
+                Some(next) => start = Some(next),
+                None => break,
+            }
+        }
+        let expected: Vec<_> = txs
+            .iter()
+            .rev()
+            .map(|(height, index)| {
+                (
+                    BlockHeight(*height),
+                    TxIndex(*index),
+                    Hash::sha256(format!("{height}/{index}")),
+                )
+            })
+            .collect();
+        assert_eq!(
+            read.into_iter()
+                .map(|entry| (entry.height, entry.index, entry.hash))
+                .collect::<Vec<_>>(),
+            expected
+        );
+
+        // The page size is bounded
+        assert!(
+            RPC.shell()
+                .tx_history(&client, &owner, &0, &None)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_supply_stats() {
+        let mut client = TestClient::new(RPC);
+        let native_token = address::testing::nam();
+        namada_token::credit_tokens(
+            &mut client.state,
+            &native_token,
+            &address::testing::established_address_1(),
+            Amount::native_whole(1_000),
+        )
+        .unwrap();
+        namada_token::credit_tokens(
+            &mut client.state,
+            &native_token,
+            &address::PGF,
+            Amount::native_whole(200),
+        )
+        .unwrap();
+        namada_parameters::update_epochs_per_year_parameter(
+            &mut client.state,
+            &10,
+        )
+        .unwrap();
+        namada_proof_of_stake::storage::write_last_pos_inflation_amount(
+            &mut client.state,
+            Amount::native_whole(8),
+        )
+        .unwrap();
+        namada_governance::pgf::storage::write_last_inflation_amount(
+            &mut client.state,
+            Amount::native_whole(2),
+        )
+        .unwrap();
+        namada_proof_of_stake::storage::write_last_staked_ratio(
+            &mut client.state,
+            Dec::new(4, 1).unwrap(),
+        )
+        .unwrap();
+        client
+            .state
+            .commit_block_from_batch(MockDBWriteBatch)
+            .unwrap();
+
+        let stats = RPC.shell().supply_stats(&client).await.unwrap();
+        assert_eq!(stats.total_supply, Amount::native_whole(1_200));
+        // The balance of the PGF account is not part of the effective supply
+        assert_eq!(stats.effective_supply, Amount::native_whole(1_000));
+        assert_eq!(stats.last_pos_inflation, Amount::native_whole(8));
+        assert_eq!(stats.last_pgf_inflation, Amount::native_whole(2));
+        // 10 tokens per epoch, 10 epochs per year, out of 1000 tokens
+        assert_eq!(stats.annual_inflation_rate, Dec::new(1, 1).unwrap());
+        assert_eq!(stats.staked_ratio, Dec::new(4, 1).unwrap());
+    }
+
+    /// Test that the state checksum only covers the committed state
+    #[tokio::test]
+    async fn test_state_checksum() {
+        let mut client = TestClient::new(RPC);
+        let path = RPC.shell().state_checksum_path();
+        assert_eq!("/shell/state_checksum", path);
+
+        let native_token = address::testing::nam();
+        namada_token::credit_tokens(
+            &mut client.state,
+            &native_token,
+            &address::testing::established_address_1(),
+            Amount::native_whole(1_000),
+        )
+        .unwrap();
+        client.state.commit_block().unwrap();
+
+        let checksum = RPC.shell().state_checksum(&client).await.unwrap();
+        assert_eq!(
+            checksum.merkle_root,
+            Hash(client.state.in_mem().merkle_root().0)
+        );
+        assert!(
+            checksum
+                .internal_address_roots
+                .keys()
+                .all(|addr| addr.is_internal())
+        );
+        let multitoken_root =
+            checksum.internal_address_roots[&address::MULTITOKEN];
+        let parameters_root =
+            checksum.internal_address_roots[&address::PARAMETERS];
+
+        // Uncommitted changes don't affect the checksum
+        namada_token::credit_tokens(
+            &mut client.state,
+            &native_token,
+            &address::testing::established_address_2(),
+            Amount::native_whole(1_000),
+        )
+        .unwrap();
+        assert_eq!(
+            RPC.shell().state_checksum(&client).await.unwrap(),
+            checksum
+        );
+
+        // Only the roots of the changed subspaces change once committed
+        client.state.commit_block().unwrap();
+        let checksum = RPC.shell().state_checksum(&client).await.unwrap();
+        assert_ne!(
+            checksum.internal_address_roots[&address::MULTITOKEN],
+            multitoken_root
+        );
+        assert_eq!(
+            checksum.internal_address_roots[&address::PARAMETERS],
+            parameters_root
+        );
+    }
+
+    /// Test listing the allowed wasm codes with their metadata from the wasm
+    /// registry
+    #[test]
+    fn test_allowed_wasm_codes() {
+        let mut state = TestState::default();
+        let tx_hash = Hash::sha256(b"tx_transfer");
+        let vp_hash = Hash::sha256(b"vp_user");
+        for (name, hash) in
+            [("tx_transfer.wasm", tx_hash), ("vp_user.wasm", vp_hash)]
+        {
+            state
+                .write(&Key::wasm_code_name(name.to_string()), hash)
+                .unwrap();
+            state.write(&Key::wasm_code_len(&hash), 100_u64).unwrap();
+            state
+                .write(&Key::wasm_code_height(&hash), BlockHeight(1))
+                .unwrap();
+        }
+        assert_eq!(
+            wasm_code_name(&state, &vp_hash).unwrap().as_deref(),
+            Some("vp_user.wasm")
+        );
+
+        // An empty allowlist allows all the registered codes
+        let allowlist_key = get_tx_allowlist_storage_key();
+        let codes = allowed_wasm_codes(&state, &allowlist_key, "tx_").unwrap();
+        assert_eq!(codes.len(), 1);
+        assert_eq!(codes[0].hash, tx_hash);
+        assert_eq!(codes[0].name.as_deref(), Some("tx_transfer.wasm"));
+        assert_eq!(codes[0].size, Some(100));
+        assert_eq!(codes[0].added_at, Some(BlockHeight(1)));
+
+        // The allowed codes that aren't registered have no metadata
+        let unknown_hash = Hash::sha256(b"tx_unknown");
+        let allowlist = [tx_hash, unknown_hash]
+            .iter()
+            .map(|hash| hash.to_string().to_lowercase())
+            .collect::<Vec<_>>();
+        state.write(&allowlist_key, allowlist).unwrap();
+        let codes = allowed_wasm_codes(&state, &allowlist_key, "tx_").unwrap();
+        assert_eq!(codes.len(), 2);
+        assert_eq!(codes[0].hash, tx_hash);
+        assert_eq!(codes[1].hash, unknown_hash);
+        assert!(codes[1].name.is_none());
+        assert!(codes[1].size.is_none());
+        assert!(codes[1].added_at.is_none());
+    }
+
+    #[test]
+    fn test_chain_parameters_diff() {
+        let mut state = TestState::default();
+        namada_parameters::init_test_storage(&mut state).unwrap();
+        let old = ChainParameters {
+            height: BlockHeight(1),
+            epoch: Default::default(),
+            protocol: namada_parameters::read(&state).unwrap(),
+            pos: Default::default(),
+            governance: Default::default(),
+            pgf: Default::default(),
+        };
+        assert!(old.diff(&old).is_empty());
+
+        let steward = address::testing::established_address_1();
+        let mut new = old.clone();
+        new.height = BlockHeight(10);
+        new.protocol.max_block_gas += 1;
+        new.pos.owned.unbonding_len += 1;
+        new.pgf.stewards.insert(steward.clone());
+        let changes = new.diff(&old);
+        assert_eq!(
+            changes,
+            vec![
+                ParameterChange {
+                    name: format!("pgf.stewards.{steward}"),
+                    old: Some("steward".to_string()),
+                    new: None,
+                },
+                ParameterChange {
+                    name: "pos.unbonding_len".to_string(),
+                    old: Some(new.pos.owned.unbonding_len.to_string()),
+                    new: Some(old.pos.owned.unbonding_len.to_string()),
+                },
+                ParameterChange {
+                    name: "protocol.max_block_gas".to_string(),
+                    old: Some(new.protocol.max_block_gas.to_string()),
+                    new: Some(old.protocol.max_block_gas.to_string()),
+                },
+            ]
+        );
     }
 }
@@ -1,6 +1,8 @@
 //! Ethereum bridge related shell queries.
 
 use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -16,6 +18,7 @@ use namada_core::ethereum_events::{
 use namada_core::keccak::KeccakHash;
 use namada_core::storage::{BlockHeight, DbKeySeg, Epoch, Key};
 use namada_core::token::Amount;
+use namada_core::uint::Uint;
 use namada_core::voting_power::FractionalVotingPower;
 use namada_core::{ethereum_structs, hints};
 use namada_ethereum_bridge::event::{BpTransferStatus, BridgePoolTxHash};
@@ -42,6 +45,9 @@ use namada_vote_ext::validator_set_update::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::eth_bridge::bridge_pool::{
+    signature_checks, signature_fee, unsigned_transfer_fee, valset_fee,
+};
 use crate::eth_bridge::ethers::abi::AbiDecode;
 use crate::queries::{EncodedResponseQuery, RequestCtx, RequestQuery};
 
@@ -80,6 +86,60 @@ pub struct TransferToEthereumStatus {
     pub unrecognized: HashSet<KeccakHash>,
 }
 
+/// The relay status of a transfer in the Ethereum bridge pool.
+#[derive(
+    Debug,
+    Clone,
+    Eq,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+)]
+pub struct PendingTransferRelayStatus {
+    /// The hash of the transfer.
+    pub transfer_hash: KeccakHash,
+    /// The pending transfer.
+    pub transfer: PendingTransfer,
+    /// The position of the transfer among the pending transfers
+    /// paying their gas fees in the same token, by decreasing fees,
+    /// starting at 1. Relayers favor the transfers at the front.
+    pub position: u64,
+    /// Whether the transfer is covered by the latest signed Bridge
+    /// pool root. Only covered transfers can be relayed.
+    pub signed: bool,
+    /// The estimated Ethereum gas to relay the batch of the transfers
+    /// up to this one in the same gas fee token, if a signed root
+    /// exists.
+    pub estimated_batch_gas: Option<Uint>,
+    /// Whether the offered fee is at least the median fee of the
+    /// pending transfers paying their gas fees in the same token.
+    pub competitive: bool,
+}
+
+/// A validator set update proof, along with the set of validators
+/// that signed it.
+#[derive(
+    Debug,
+    Clone,
+    Eq,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+)]
+pub struct ValidatorSetUpdateProof {
+    /// The epoch of the new set of validators.
+    pub epoch: Epoch,
+    /// The set of Bridge validators at the epoch prior to [`Self::epoch`],
+    /// whose signatures are aggregated in the proof. These are the
+    /// validator set arguments to pass to `updateValidatorSet`.
+    pub signing_validator_set: ValidatorSetArgs,
+    /// The Ethereum ABI encoded proof, i.e. the hashes of the new Bridge
+    /// and Governance validator sets and the signatures over them.
+    pub proof: EncodeCell<EthereumProof<(Epoch, VotingPowersMap)>>,
+}
+
 /// Contains information about the flow control of some ERC20
 /// wrapped asset.
 #[derive(
@@ -188,6 +248,11 @@ router! {ETH_BRIDGE,
     ( "pool" / "transfer_status" )
         -> TransferToEthereumStatus = (with_options pending_eth_transfer_status),
 
+    // Get the relay status of each transfer in the Ethereum bridge
+    // pool, to explain why it hasn't been relayed yet.
+    ( "pool" / "relay_status" )
+        -> Vec<PendingTransferRelayStatus> = read_bridge_pool_relay_status,
+
     // Request a proof of a validator set signed off for
     // the given epoch.
     //
@@ -196,6 +261,23 @@ router! {ETH_BRIDGE,
         -> EncodeCell<EthereumProof<(Epoch, VotingPowersMap)>>
         = read_valset_upd_proof,
 
+    // Request the proof of the latest validator set signed off,
+    // along with the set of validators that signed it.
+    //
+    // The request may fail if no proof is complete yet.
+    ( "validator_set" / "proofs" / "latest" )
+        -> ValidatorSetUpdateProof = read_latest_valset_upd_proof,
+
+    // Request the proofs of the validator sets signed off in the
+    // given inclusive range of epochs, along with the sets of
+    // validators that signed them. Epochs without a complete proof
+    // are skipped.
+    //
+    // The request fails if the range spans more than
+    // `MAX_VALSET_UPD_PROOFS_PER_QUERY` epochs.
+    ( "validator_set" / "proofs" / "range" / [start: Epoch] / [end: Epoch] )
+        -> Vec<ValidatorSetUpdateProof> = read_valset_upd_proofs,
+
     // Request the set of bridge validators at the given epoch.
     //
     // The request may fail if no validator set exists at that epoch.
@@ -419,7 +501,7 @@ where
 {
     Ok(read_ethereum_bridge_pool_at_height(
         ctx.state.in_mem().get_last_block_height(),
-        ctx,
+        &ctx,
     ))
 }
 
@@ -441,13 +523,13 @@ where
             "No signed root for the Ethereum bridge pool exists in storage.",
         ))
         .into_storage_result()?;
-    Ok(read_ethereum_bridge_pool_at_height(height, ctx))
+    Ok(read_ethereum_bridge_pool_at_height(height, &ctx))
 }
 
 /// Read the Ethereum bridge pool contents at a specified height.
 fn read_ethereum_bridge_pool_at_height<D, H, V, T>(
     height: BlockHeight,
-    ctx: RequestCtx<'_, D, H, V, T>,
+    ctx: &RequestCtx<'_, D, H, V, T>,
 ) -> Vec<PendingTransfer>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
@@ -480,6 +562,84 @@ where
     transfers
 }
 
+/// Get the relay status of each transfer in the Ethereum bridge pool.
+fn read_bridge_pool_relay_status<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<Vec<PendingTransferRelayStatus>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let pool = read_ethereum_bridge_pool_at_height(
+        ctx.state.in_mem().get_last_block_height(),
+        &ctx,
+    );
+
+    // the transfers covered by the latest signed root, and the gas
+    // cost of verifying its signatures on Ethereum
+    let (signed, validator_gas) =
+        match ctx.state.ethbridge_queries().get_signed_bridge_pool_root() {
+            Some((signed_root, height)) => {
+                let signed: HashSet<KeccakHash> =
+                    read_ethereum_bridge_pool_at_height(height, &ctx)
+                        .iter()
+                        .map(PendingTransfer::keccak256)
+                        .collect();
+                // the signatures are checked against the current set of
+                // bridge validators, as when generating a proof
+                let (_, voting_powers) = ctx
+                    .state
+                    .ethbridge_queries()
+                    .get_bridge_validator_set(None);
+                let valset_size = Uint::from_u64(voting_powers.len() as u64);
+                let validator_gas =
+                    signature_checks(voting_powers, &signed_root.signatures)
+                        .ok()
+                        .and_then(|checks| {
+                            signature_fee().checked_mul(checks)?.checked_add(
+                                valset_fee().checked_mul(valset_size)?,
+                            )
+                        });
+                (signed, validator_gas)
+            }
+            None => (HashSet::new(), None),
+        };
+
+    // rank the transfers by decreasing fees per gas fee token
+    let mut by_token: BTreeMap<Address, Vec<PendingTransfer>> = BTreeMap::new();
+    for transfer in pool {
+        by_token
+            .entry(transfer.gas_fee.token.clone())
+            .or_default()
+            .push(transfer);
+    }
+    let mut statuses = vec![];
+    for (_, mut transfers) in by_token {
+        transfers.sort_by_cached_key(|transfer| {
+            (Reverse(transfer.gas_fee.amount), transfer.keccak256())
+        });
+        let median_fee = transfers[(transfers.len() - 1) / 2].gas_fee.amount;
+        for (position, transfer) in (1u64..).zip(transfers) {
+            let transfer_hash = transfer.keccak256();
+            let estimated_batch_gas = validator_gas.and_then(|gas| {
+                gas.checked_add(
+                    unsigned_transfer_fee()
+                        .checked_mul(Uint::from_u64(position))?,
+                )
+            });
+            statuses.push(PendingTransferRelayStatus {
+                signed: signed.contains(&transfer_hash),
+                competitive: transfer.gas_fee.amount >= median_fee,
+                transfer_hash,
+                transfer,
+                position,
+                estimated_batch_gas,
+            });
+        }
+    }
+    Ok(statuses)
+}
+
 /// Generate a merkle proof for the inclusion of the
 /// requested transfers in the Ethereum bridge pool.
 fn generate_bridge_pool_proof<D, H, V, T>(
@@ -722,6 +882,112 @@ where
         )));
     }
 
+    read_seen_valset_upd_proof(&ctx, epoch)
+}
+
+/// The maximum number of epochs whose validator set update proofs
+/// can be requested at once.
+pub const MAX_VALSET_UPD_PROOFS_PER_QUERY: u64 = 100;
+
+/// Read the validator set update proof of the latest epoch for
+/// which one is complete.
+fn read_latest_valset_upd_proof<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<ValidatorSetUpdateProof>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let last_epoch = ctx.state.in_mem().last_epoch.next();
+    let epoch = (1..=last_epoch.0)
+        .rev()
+        .map(Epoch)
+        .find(|&epoch| ctx.state.ethbridge_queries().valset_upd_seen(epoch))
+        .ok_or_else(|| {
+            namada_storage::Error::Custom(CustomError(
+                "No validator set update proof is available yet".into(),
+            ))
+        })?;
+    read_valset_upd_proof_with_signers(&ctx, epoch)
+}
+
+/// Read the validator set update proofs of the given inclusive range
+/// of epochs, skipping the epochs without a complete proof.
+fn read_valset_upd_proofs<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    start: Epoch,
+    end: Epoch,
+) -> namada_storage::Result<Vec<ValidatorSetUpdateProof>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    if start.0 == 0 {
+        return Err(namada_storage::Error::Custom(CustomError(
+            "Validator set update proofs should only be requested from epoch \
+             1 onwards"
+                .into(),
+        )));
+    }
+    if start > end {
+        return Err(namada_storage::Error::Custom(CustomError(
+            format!("Invalid range of epochs from {start:?} to {end:?}").into(),
+        )));
+    }
+    if end.0.saturating_sub(start.0) >= MAX_VALSET_UPD_PROOFS_PER_QUERY {
+        return Err(namada_storage::Error::Custom(CustomError(
+            format!(
+                "Requesting validator set update proofs for more than \
+                 {MAX_VALSET_UPD_PROOFS_PER_QUERY} epochs"
+            )
+            .into(),
+        )));
+    }
+    let last_epoch = ctx.state.in_mem().last_epoch.next();
+    (start.0..=end.0.min(last_epoch.0))
+        .map(Epoch)
+        .filter(|&epoch| ctx.state.ethbridge_queries().valset_upd_seen(epoch))
+        .map(|epoch| read_valset_upd_proof_with_signers(&ctx, epoch))
+        .collect()
+}
+
+/// Read the validator set update proof of the given epoch, along
+/// with the set of Bridge validators that signed it.
+///
+/// The proof must have been seen.
+fn read_valset_upd_proof_with_signers<D, H, V, T>(
+    ctx: &RequestCtx<'_, D, H, V, T>,
+    epoch: Epoch,
+) -> namada_storage::Result<ValidatorSetUpdateProof>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let signing_epoch = epoch
+        .prev()
+        .expect("Validator set update proofs start at epoch 1");
+    let signing_validator_set = ctx
+        .state
+        .ethbridge_queries()
+        .get_bridge_validator_set(Some(signing_epoch))
+        .0;
+    Ok(ValidatorSetUpdateProof {
+        epoch,
+        signing_validator_set,
+        proof: read_seen_valset_upd_proof(ctx, epoch)?,
+    })
+}
+
+/// Read the validator set update proof of the given epoch, which
+/// must have been seen.
+fn read_seen_valset_upd_proof<D, H, V, T>(
+    ctx: &RequestCtx<'_, D, H, V, T>,
+    epoch: Epoch,
+) -> namada_storage::Result<EncodeCell<EthereumProof<(Epoch, VotingPowersMap)>>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
     let valset_upd_keys = vote_tallies::Keys::from(&epoch);
     let proof: EthereumProof<VotingPowersMap> =
         StorageRead::read(ctx.state, &valset_upd_keys.body())?.expect(
@@ -1047,6 +1313,93 @@ mod test_ethbridge_router {
         );
     }
 
+    /// Test that the latest validator set update proofs can be read,
+    /// along with the validators that signed them.
+    #[tokio::test]
+    async fn test_read_valset_upd_proofs() {
+        let mut client = TestClient::new(RPC);
+        assert_eq!(client.state.in_mem().last_epoch.0, 0);
+
+        // write validator to storage
+        let keys = test_utils::init_default_storage(&mut client.state);
+
+        // no proof is available yet
+        let result = RPC
+            .shell()
+            .eth_bridge()
+            .read_latest_valset_upd_proof(&client)
+            .await;
+        assert!(result.is_err());
+
+        // write proof to storage
+        let vext = validator_set_update::Vext {
+            voting_powers: VotingPowersMap::new(),
+            validator_addr: established_address_1(),
+            signing_epoch: 0.into(),
+        }
+        .sign(
+            &keys
+                .get(&established_address_1())
+                .expect("Test failed")
+                .eth_bridge,
+        );
+        aggregate_votes(
+            &mut client.state,
+            validator_set_update::VextDigest::singleton(vext),
+            0.into(),
+        )
+        .expect("Test failed");
+
+        // commit the changes
+        client
+            .state
+            .commit_block_from_batch(MockDBWriteBatch)
+            .expect("Test failed");
+
+        // check the responses
+        let expected = ValidatorSetUpdateProof {
+            epoch: Epoch(1),
+            signing_validator_set: client
+                .state
+                .ethbridge_queries()
+                .get_bridge_validator_set(Some(0.into()))
+                .0,
+            proof: RPC
+                .shell()
+                .eth_bridge()
+                .read_valset_upd_proof(&client, &Epoch(1))
+                .await
+                .unwrap(),
+        };
+        let latest = RPC
+            .shell()
+            .eth_bridge()
+            .read_latest_valset_upd_proof(&client)
+            .await
+            .unwrap();
+        assert_eq!(latest, expected);
+
+        let proofs = RPC
+            .shell()
+            .eth_bridge()
+            .read_valset_upd_proofs(&client, &Epoch(1), &Epoch(10))
+            .await
+            .unwrap();
+        assert_eq!(proofs, vec![expected]);
+
+        // too many epochs
+        let result = RPC
+            .shell()
+            .eth_bridge()
+            .read_valset_upd_proofs(
+                &client,
+                &Epoch(1),
+                &Epoch(MAX_VALSET_UPD_PROOFS_PER_QUERY + 1),
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
     /// Test that reading the bridge pool works
     #[tokio::test]
     async fn test_read_bridge_pool() {
@@ -1770,6 +2123,101 @@ mod test_ethbridge_router {
             "unexpected unrecognized transfers"
         );
     }
+
+    /// Test the relay status of the transfers in the bridge pool
+    #[tokio::test]
+    async fn test_bridge_pool_relay_status() {
+        let mut client = TestClient::new(RPC);
+        let transfer = |amount: u64, fee: u64| PendingTransfer {
+            transfer: TransferToEthereum {
+                kind: TransferToEthereumKind::Erc20,
+                asset: EthAddress([0; 20]),
+                recipient: EthAddress([0; 20]),
+                sender: bertha_address(),
+                amount: amount.into(),
+            },
+            gas_fee: GasFee {
+                token: nam(),
+                amount: fee.into(),
+                payer: bertha_address(),
+            },
+        };
+        // write validator to storage
+        test_utils::init_default_storage(&mut client.state);
+
+        // without a signed root, no batch gas can be estimated
+        let low_fee = transfer(0, 1);
+        let high_fee = transfer(1, 3);
+        for transfer in [&low_fee, &high_fee] {
+            client
+                .state
+                .write(&get_pending_key(transfer), transfer)
+                .expect("Test failed");
+        }
+        let signed_root = BridgePoolRootProof {
+            signatures: Default::default(),
+            data: (KeccakHash([0; 32]), 0.into()),
+        };
+        let written_height = client.state.in_mem().block.height;
+        client.state.commit_block().expect("Test failed");
+        client.state.in_mem_mut().block.height += 1;
+
+        let statuses = RPC
+            .shell()
+            .eth_bridge()
+            .read_bridge_pool_relay_status(&client)
+            .await
+            .unwrap();
+        assert_eq!(statuses.len(), 2);
+        assert!(statuses.iter().all(
+            |status| !status.signed && status.estimated_batch_gas.is_none()
+        ));
+
+        // sign the pool, then add a transfer not covered by the root
+        let mid_fee = transfer(2, 2);
+        client
+            .state
+            .write(&get_pending_key(&mid_fee), &mid_fee)
+            .expect("Test failed");
+        client
+            .state
+            .write(&get_signed_root_key(), (signed_root, written_height))
+            .expect("Test failed");
+        client.state.commit_block().expect("Test failed");
+        client.state.in_mem_mut().block.height += 1;
+
+        let statuses = RPC
+            .shell()
+            .eth_bridge()
+            .read_bridge_pool_relay_status(&client)
+            .await
+            .unwrap();
+        let summary: Vec<_> = statuses
+            .iter()
+            .map(|status| {
+                (
+                    status.transfer.clone(),
+                    status.position,
+                    status.signed,
+                    status.competitive,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (high_fee, 1, true, true),
+                (mid_fee, 2, false, true),
+                (low_fee, 3, true, false),
+            ]
+        );
+        let gas: Vec<Uint> = statuses
+            .iter()
+            .map(|status| status.estimated_batch_gas.unwrap())
+            .collect();
+        assert_eq!(gas[1] - gas[0], unsigned_transfer_fee());
+        assert_eq!(gas[2] - gas[1], unsigned_transfer_fee());
+    }
 }
 
 #[cfg(any(feature = "testing", test))]
@@ -1,8 +1,15 @@
 // cd namada && cargo expand ledger::queries::vp::governance
 
+use std::collections::BTreeMap;
+
+use namada_core::address::Address;
+use namada_core::storage::Epoch;
+use namada_governance::cli::content::ProposalContentAnchor;
 use namada_governance::parameters::GovernanceParameters;
+use namada_governance::pause::PausableModule;
 use namada_governance::storage::proposal::StorageProposal;
 use namada_governance::utils::{ProposalResult, Vote};
+use namada_proof_of_stake::queries::find_delegation_validators;
 use namada_state::{DBIter, StorageHasher, DB};
 
 use crate::queries::types::RequestCtx;
@@ -11,8 +18,12 @@ use crate::queries::types::RequestCtx;
 router! {GOV,
     ( "proposal" / [id: u64 ] ) -> Option<StorageProposal> = proposal_id,
     ( "proposal" / [id: u64 ] / "votes" ) -> Vec<Vote> = proposal_id_votes,
+    ( "proposal" / [id: u64 ] / "delegated_votes" ) -> Vec<Vote> = proposal_id_delegated_votes,
+    ( "proposal" / [id: u64 ] / "content_anchor" ) -> Option<ProposalContentAnchor> = proposal_content_anchor,
     ( "parameters" ) -> GovernanceParameters = parameters,
     ( "stored_proposal_result" / [id: u64] ) -> Option<ProposalResult> = proposal_result,
+    ( "voting_delegate" / [delegator: Address] ) -> Option<Address> = voting_delegate,
+    ( "paused_modules" ) -> BTreeMap<PausableModule, Epoch> = paused_modules,
 }
 
 /// Query the provided proposal id
@@ -27,6 +38,22 @@ where
     namada_governance::storage::get_proposal_by_id(ctx.state, id)
 }
 
+/// Query the anchored off-chain content hash and the metadata of the provided
+/// proposal id
+fn proposal_content_anchor<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> namada_storage::Result<Option<ProposalContentAnchor>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    Ok(
+        namada_governance::storage::get_proposal_by_id(ctx.state, id)?
+            .map(ProposalContentAnchor::from),
+    )
+}
+
 /// Query all the votes for the given proposal id
 fn proposal_id_votes<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -39,6 +66,36 @@ where
     namada_governance::storage::get_proposal_votes(ctx.state, id)
 }
 
+/// Query the votes cast by the voting delegates on behalf of the delegators
+/// for the given proposal id, with the delegators' bonds at the end of the
+/// voting period
+fn proposal_id_delegated_votes<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> namada_storage::Result<Vec<Vote>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let Some(proposal) =
+        namada_governance::storage::get_proposal_by_id(ctx.state, id)?
+    else {
+        return Ok(vec![]);
+    };
+    let votes = namada_governance::storage::get_proposal_votes(ctx.state, id)?;
+    namada_governance::storage::get_delegated_votes(
+        ctx.state,
+        &votes,
+        |delegator| {
+            find_delegation_validators(
+                ctx.state,
+                delegator,
+                &proposal.voting_end_epoch,
+            )
+        },
+    )
+}
+
 /// Get the governance parameters
 fn parameters<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -61,3 +118,28 @@ where
 {
     namada_governance::storage::get_proposal_result(ctx.state, id)
 }
+
+/// Get the voting delegate of the given delegator, if any
+fn voting_delegate<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    delegator: Address,
+) -> namada_storage::Result<Option<Address>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_voting_delegate(ctx.state, &delegator)
+}
+
+/// Get the modules paused by governance with the epochs from which they are
+/// no longer paused
+fn paused_modules<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<BTreeMap<PausableModule, Epoch>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let current_epoch = ctx.state.in_mem().get_current_epoch().0;
+    namada_governance::pause::get_paused_modules(ctx.state, current_epoch)
+}
@@ -1,17 +1,28 @@
+use borsh::BorshDeserialize;
+use borsh_ext::BorshSerializeExt;
 use namada_core::address::Address;
+use namada_core::storage::Epoch;
 use namada_governance::pgf::parameters::PgfParameters;
+use namada_governance::pgf::simulation::{
+    simulate_treasury, TreasurySimulation, TreasurySimulationRequest,
+};
 use namada_governance::pgf::storage::steward::StewardDetail;
 use namada_governance::storage::proposal::StoragePgfFunding;
 use namada_state::{DBIter, StorageHasher, DB};
+use namada_storage::ResultExt;
 
 use crate::queries::types::RequestCtx;
+use crate::queries::{EncodedResponseQuery, RequestQuery};
 
 // PoS validity predicate queries
 router! {PGF,
     ( "stewards" / [ address: Address ] ) -> bool = is_steward,
     ( "stewards" ) -> Vec<StewardDetail> = stewards,
+    ( "steward_term" / [ address: Address ] ) -> Option<Epoch> = steward_term_end,
     ( "fundings" ) -> Vec<StoragePgfFunding> = funding,
     ( "parameters" ) -> PgfParameters = parameters,
+    ( "simulate_treasury" )
+        -> TreasurySimulation = (with_options treasury_simulation),
 }
 
 /// Query the current pgf steward set
@@ -37,6 +48,18 @@ where
     namada_governance::pgf::storage::is_steward(ctx.state, &address)
 }
 
+/// Query the epoch from which an elected pgf steward is removed
+fn steward_term_end<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    address: Address,
+) -> namada_storage::Result<Option<Epoch>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::pgf::storage::get_steward_term_end(ctx.state, &address)
+}
+
 /// Query the continuous pgf fundings
 fn funding<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -58,3 +81,24 @@ where
 {
     namada_governance::pgf::storage::get_parameters(ctx.state)
 }
+
+/// Simulate the PGF treasury under the hypothetical fundings given in the
+/// request data
+fn treasury_simulation<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let simulation_request =
+        TreasurySimulationRequest::try_from_slice(&request.data)
+            .into_storage_result()?;
+    let simulation = simulate_treasury(ctx.state, &simulation_request)?;
+    Ok(EncodedResponseQuery {
+        data: simulation.serialize_to_vec(),
+        height: ctx.state.in_mem().get_last_block_height(),
+        ..Default::default()
+    })
+}
@@ -6,13 +6,28 @@ use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use namada_core::address::Address;
 use namada_core::arith::{self, checked};
 use namada_core::collections::{HashMap, HashSet};
+use namada_core::dec::Dec;
 use namada_core::key::common;
 use namada_core::storage::Epoch;
 use namada_core::token;
+use namada_proof_of_stake::auto_redelegation::{
+    read_auto_redelegation, AutoRedelegation,
+};
+use namada_proof_of_stake::bond_receipt::read_bond_receipts;
+use namada_proof_of_stake::delegation_pool::{
+    read_delegation_pool, read_delegation_pool_membership,
+    read_delegation_pools, DelegationPool,
+};
+use namada_proof_of_stake::evidence::{read_recent_evidence, EvidenceInfo};
+use namada_proof_of_stake::operational_metadata::{
+    read_operational_metadata, read_operational_metadata_nonce,
+    OperationalMetadata,
+};
 use namada_proof_of_stake::parameters::PosParams;
 use namada_proof_of_stake::queries::{
-    find_delegation_validators, find_delegations,
+    find_delegation_validators, find_delegations, find_pending_consensus_key,
 };
+use namada_proof_of_stake::rewards::estimate_validator_rewards_rate;
 use namada_proof_of_stake::slashing::{
     find_all_enqueued_slashes, find_all_slashes,
 };
@@ -20,19 +35,27 @@ use namada_proof_of_stake::storage::{
     bond_handle, read_all_validator_addresses,
     read_below_capacity_validator_set_addresses_with_stake,
     read_consensus_validator_set_addresses_with_stake, read_pos_params,
-    read_total_stake, read_validator_avatar, read_validator_description,
-    read_validator_discord_handle, read_validator_email,
+    read_total_stake, read_validator_jail_history,
     read_validator_last_slash_epoch, read_validator_max_commission_rate_change,
-    read_validator_name, read_validator_stake, read_validator_website,
-    unbond_handle, validator_commission_rate_handle,
-    validator_incoming_redelegations_handle, validator_slashes_handle,
+    read_validator_metadata, read_validator_stake, unbond_handle,
+    validator_commission_rate_handle, validator_incoming_redelegations_handle,
+    validator_protocol_key_handle, validator_slashes_handle,
+};
+use namada_proof_of_stake::telemetry::{
+    aggregate_telemetry, read_validator_telemetry, TelemetrySummary,
+    ValidatorTelemetry,
 };
 pub use namada_proof_of_stake::types::ValidatorStateInfo;
 use namada_proof_of_stake::types::{
     BondId, BondsAndUnbondsDetail, BondsAndUnbondsDetails, CommissionPair,
-    Slash, ValidatorMetaData, WeightedValidator,
+    CommissionSchedule, JailRecord, PendingConsensusKey, Slash,
+    ValidatorMetaData, ValidatorRewardsEstimate, ValidatorState,
+    WeightedValidator,
+};
+use namada_proof_of_stake::{
+    bond_amount, query_reward_tokens, read_validator_commission_schedule,
+    validate_commission_rate_change, CommissionRateChangeError,
 };
-use namada_proof_of_stake::{bond_amount, query_reward_tokens};
 use namada_state::{DBIter, StorageHasher, DB};
 use namada_storage::collections::lazy_map;
 use namada_storage::OptionExt;
@@ -46,21 +69,45 @@ router! {POS,
 
         ( "consensus_key" / [addr: Address] ) -> Option<common::PublicKey> = consensus_key,
 
+        ( "protocol_key" / [validator: Address] )
+            -> Option<common::PublicKey> = validator_protocol_key,
+
         ( "addresses" / [epoch: opt Epoch] )
             -> HashSet<Address> = validator_addresses,
 
         ( "stake" / [validator: Address] / [epoch: opt Epoch] )
             -> Option<token::Amount> = validator_stake,
 
+        ( "remaining_capacity" / [validator: Address] )
+            -> Option<token::Amount> = validator_remaining_capacity,
+
         ( "slashes" / [validator: Address] )
             -> Vec<Slash> = validator_slashes,
 
         ( "commission" / [validator: Address] / [epoch: opt Epoch] )
             -> CommissionPair = validator_commission,
 
+        ( "commission_schedule" / [validator: Address] )
+            -> Option<CommissionSchedule> = validator_commission_schedule,
+
+        ( "check_commission_change" / [validator: Address] / [rate: Dec] )
+            -> Option<String> = check_validator_commission_change,
+
+        ( "rewards_estimate" / [validator: Address] )
+            -> Option<ValidatorRewardsEstimate> = validator_rewards_estimate,
+
         ( "metadata" / [validator: Address] )
             -> Option<ValidatorMetaData> = validator_metadata,
 
+        ( "operational_metadata" / [validator: Address] )
+            -> Option<OperationalMetadata> = validator_operational_metadata,
+
+        ( "operational_metadata_nonce" / [validator: Address] )
+            -> u64 = validator_operational_metadata_nonce,
+
+        ( "telemetry" / [validator: Address] )
+            -> Option<ValidatorTelemetry> = validator_telemetry,
+
         ( "state" / [validator: Address] / [epoch: opt Epoch] )
             -> ValidatorStateInfo = validator_state,
 
@@ -69,6 +116,12 @@ router! {POS,
 
         ( "last_infraction_epoch" / [validator: Address] )
             -> Option<Epoch> = validator_last_infraction_epoch,
+
+        ( "jail_history" / [validator: Address] )
+            -> Vec<JailRecord> = validator_jail_history,
+
+        ( "pending_consensus_key" / [validator: Address] )
+            -> Option<PendingConsensusKey> = validator_pending_consensus_key,
     },
 
     ( "validator_set" ) = {
@@ -102,6 +155,9 @@ router! {POS,
     ( "bond_with_slashing" / [source: Address] / [validator: Address] / [epoch: opt Epoch] )
         -> token::Amount = bond_with_slashing,
 
+    ( "bond_receipts" / [source: Address] / [validator: Address] )
+        -> token::Amount = bond_receipts,
+
     ( "unbond" / [source: Address] / [validator: Address] )
         -> HashMap<(Epoch, Epoch), token::Amount> = unbond,
 
@@ -118,6 +174,9 @@ router! {POS,
     ( "bonds_and_unbonds" / [source: opt Address] / "to" / [validator: opt Address] )
         -> BondsAndUnbondsDetails = bonds_and_unbonds,
 
+    ( "staking_overview" / [owner: Address] )
+        -> StakingOverview = staking_overview,
+
     ( "enqueued_slashes" )
         -> HashMap<Address, BTreeMap<Epoch, Vec<Slash>>> = enqueued_slashes,
 
@@ -133,6 +192,25 @@ router! {POS,
     ( "has_bonds" / [source: Address] )
         -> bool = has_bonds,
 
+    ( "delegation_pool" ) = {
+        ( "pool" / [name: String] )
+            -> Option<DelegationPool> = delegation_pool,
+
+        ( "all" ) -> BTreeMap<String, DelegationPool> = delegation_pools,
+
+        ( "membership" / [delegator: Address] )
+            -> Option<String> = delegation_pool_membership,
+    },
+
+    ( "auto_redelegation" / [delegator: Address] )
+        -> Option<AutoRedelegation> = auto_redelegation,
+
+    ( "telemetry_summary" / [epoch: opt Epoch] )
+        -> TelemetrySummary = telemetry_summary,
+
+    ( "recent_evidence" / [since: opt Epoch] )
+        -> Vec<EvidenceInfo> = recent_evidence,
+
 }
 
 /// Enriched bonds data with extra information calculated from the data queried
@@ -163,6 +241,33 @@ pub type EnrichedBondsAndUnbondsDetails =
 /// with extra information calculated from the data queried from the node.
 pub type EnrichedBondsAndUnbondsDetail = Enriched<BondsAndUnbondsDetail>;
 
+/// The staking of an owner with a validator
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct ValidatorStakingOverview {
+    /// The bonds and unbonds to the validator with their slashes, enriched
+    /// with their totals and withdrawable amount
+    pub bonds_and_unbonds: EnrichedBondsAndUnbondsDetail,
+    /// The rewards that can be claimed from the validator
+    pub rewards: token::Amount,
+    /// The state of the validator at the current epoch
+    pub state: Option<ValidatorState>,
+    /// The slashes of the validator enqueued to be processed, by epoch, to
+    /// which the bonds are exposed
+    pub enqueued_slashes: BTreeMap<Epoch, Vec<Slash>>,
+}
+
+/// The consolidated staking of an owner: their bonds, unbonds, rewards and
+/// slashing exposure with every validator
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct StakingOverview {
+    /// The epoch of the overview
+    pub epoch: Epoch,
+    /// The staking with each validator, with the totals over all of them
+    pub staking: Enriched<BTreeMap<Address, ValidatorStakingOverview>>,
+    /// Sum of the rewards that can be claimed
+    pub total_rewards: token::Amount,
+}
+
 impl<T> Enriched<T> {
     /// The bonds amount reduced by slashes
     pub fn bonds_total_active(&self) -> Option<token::Amount> {
@@ -217,6 +322,24 @@ where
     )
 }
 
+/// Get the protocol key of the given validator in the current epoch
+fn validator_protocol_key<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+) -> namada_storage::Result<Option<common::PublicKey>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let current_epoch = ctx.state.in_mem().last_epoch;
+    let params = read_pos_params(ctx.state)?;
+    validator_protocol_key_handle(&validator).get(
+        ctx.state,
+        current_epoch,
+        &params,
+    )
+}
+
 /// Find if the given address is a delegator
 fn is_delegator<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -268,6 +391,66 @@ where
     })
 }
 
+/// Get the validator commission rate in the current epoch and its pending
+/// changes up to the pipeline epoch
+fn validator_commission_schedule<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+) -> namada_storage::Result<Option<CommissionSchedule>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let current_epoch = ctx.state.in_mem().last_epoch;
+    let params = read_pos_params(ctx.state)?;
+    read_validator_commission_schedule(
+        ctx.state,
+        &params,
+        &validator,
+        current_epoch,
+    )
+}
+
+/// Check if the validator's commission rate can be changed to the given rate
+/// in the current epoch. Returns the reason of the rejection if the change is
+/// not allowed.
+fn check_validator_commission_change<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+    rate: Dec,
+) -> namada_storage::Result<Option<String>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let current_epoch = ctx.state.in_mem().last_epoch;
+    match validate_commission_rate_change(
+        ctx.state,
+        &validator,
+        rate,
+        current_epoch,
+    ) {
+        Ok(_) => Ok(None),
+        Err(err) => match err.downcast::<CommissionRateChangeError>() {
+            Ok(err) => Ok(Some(err.to_string())),
+            Err(err) => Err(err),
+        },
+    }
+}
+
+/// Estimate the annualized rewards rates of the validator and its delegators
+fn validator_rewards_estimate<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+) -> namada_storage::Result<Option<ValidatorRewardsEstimate>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let current_epoch = ctx.state.in_mem().last_epoch;
+    estimate_validator_rewards_rate(ctx.state, &validator, current_epoch)
+}
+
 /// Get the validator metadata
 fn validator_metadata<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -277,25 +460,43 @@ where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
 {
-    let email = read_validator_email(ctx.state, &validator)?;
-    let description = read_validator_description(ctx.state, &validator)?;
-    let website = read_validator_website(ctx.state, &validator)?;
-    let discord_handle = read_validator_discord_handle(ctx.state, &validator)?;
-    let avatar = read_validator_avatar(ctx.state, &validator)?;
-    let name = read_validator_name(ctx.state, &validator)?;
+    read_validator_metadata(ctx.state, &validator)
+}
 
-    // Email is the only required field for a validator in storage
-    match email {
-        Some(email) => Ok(Some(ValidatorMetaData {
-            email,
-            description,
-            website,
-            discord_handle,
-            avatar,
-            name,
-        })),
-        _ => Ok(None),
-    }
+/// Get the operational metadata published by the given validator
+fn validator_operational_metadata<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+) -> namada_storage::Result<Option<OperationalMetadata>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_operational_metadata(ctx.state, &validator)
+}
+
+/// Get the nonce of the given validator's next operational metadata update
+fn validator_operational_metadata_nonce<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+) -> namada_storage::Result<u64>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_operational_metadata_nonce(ctx.state, &validator)
+}
+
+/// Get the telemetry of the given validator's last heartbeat
+fn validator_telemetry<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+) -> namada_storage::Result<Option<ValidatorTelemetry>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_validator_telemetry(ctx.state, &validator)
 }
 
 /// Get the validator state
@@ -327,6 +528,50 @@ where
     read_validator_last_slash_epoch(ctx.state, &validator)
 }
 
+/// Get the most recent records of a validator being jailed, from the oldest
+/// to the newest.
+fn validator_jail_history<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+) -> namada_storage::Result<Vec<JailRecord>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_validator_jail_history(ctx.state, &validator)
+}
+
+/// Get the latest change of a validator's consensus key that is not in effect
+/// yet, with the epoch from which the new key is used for consensus. The
+/// height of the first block of that epoch is only known once its start has
+/// been scheduled.
+fn validator_pending_consensus_key<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+) -> namada_storage::Result<Option<PendingConsensusKey>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let in_mem = ctx.state.in_mem();
+    let current_epoch = in_mem.last_epoch;
+    let pending =
+        find_pending_consensus_key(ctx.state, &validator, current_epoch)?;
+    Ok(pending.map(|(consensus_key, epoch)| {
+        let height = in_mem
+            .update_epoch_blocks_delay
+            .filter(|_| epoch == current_epoch.next())
+            .and_then(|delay| {
+                in_mem.get_last_block_height().checked_add(u64::from(delay))
+            });
+        PendingConsensusKey {
+            consensus_key,
+            epoch,
+            height,
+        }
+    }))
+}
+
 /// Get the total stake of a validator at the given epoch or current when
 /// `None`. The total stake is a sum of validator's self-bonds and delegations
 /// to their address.
@@ -352,6 +597,31 @@ where
     }
 }
 
+/// Get the amount of tokens that can still be bonded to a validator at the
+/// pipeline epoch under the cap of its share of the total stake. Returns
+/// `None` if the address is not a validator or if the stake is not capped.
+fn validator_remaining_capacity<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+) -> namada_storage::Result<Option<token::Amount>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    if !namada_proof_of_stake::is_validator(ctx.state, &validator)? {
+        return Ok(None);
+    }
+    let params = read_pos_params(ctx.state)?;
+    let pipeline_epoch =
+        checked!(ctx.state.in_mem().last_epoch + params.pipeline_len)?;
+    namada_proof_of_stake::validator_remaining_capacity(
+        ctx.state,
+        &params,
+        &validator,
+        pipeline_epoch,
+    )
+}
+
 /// Get the incoming redelegation epoch for a source validator - delegator pair,
 /// if there is any.
 fn validator_incoming_redelegation<D, H, V, T>(
@@ -407,6 +677,33 @@ where
     read_total_stake(ctx.state, &params, epoch)
 }
 
+/// Get the telemetry of the consensus validators at the given epoch or current
+/// when `None`, aggregated by the binary they reported.
+fn telemetry_summary<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    epoch: Option<Epoch>,
+) -> namada_storage::Result<TelemetrySummary>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let epoch = epoch.unwrap_or(ctx.state.in_mem().last_epoch);
+    aggregate_telemetry(ctx.state, epoch)
+}
+
+/// Get the evidence received from CometBFT since the given epoch, or all the
+/// evidence that is still recorded when `None`, with the resulting slashes.
+fn recent_evidence<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    since: Option<Epoch>,
+) -> namada_storage::Result<Vec<EvidenceInfo>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_recent_evidence(ctx.state, since)
+}
+
 fn bond_deltas<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     source: Address,
@@ -568,6 +865,66 @@ where
     )
 }
 
+/// Get the staking overview of an owner at the current epoch, so that the
+/// clients don't have to query every validator separately
+fn staking_overview<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    owner: Address,
+) -> namada_storage::Result<StakingOverview>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let epoch = ctx.state.in_mem().last_epoch;
+    let bonds_and_unbonds = namada_proof_of_stake::queries::bonds_and_unbonds(
+        ctx.state,
+        Some(owner.clone()),
+        None,
+    )?;
+    let Enriched {
+        data,
+        bonds_total,
+        bonds_total_slashed,
+        unbonds_total,
+        unbonds_total_slashed,
+        total_withdrawable,
+    } = enrich_bonds_and_unbonds(epoch, bonds_and_unbonds)?;
+    let mut enqueued_slashes = find_all_enqueued_slashes(ctx.state, epoch)?;
+
+    let mut total_rewards = token::Amount::zero();
+    let mut validators = BTreeMap::new();
+    for (BondId { validator, .. }, bonds_and_unbonds) in data {
+        let rewards =
+            query_reward_tokens(ctx.state, Some(&owner), &validator, epoch)?;
+        total_rewards = checked!(total_rewards + rewards)?;
+        let state = namada_proof_of_stake::storage::read_validator_state(
+            ctx.state, &validator, &epoch,
+        )?;
+        let overview = ValidatorStakingOverview {
+            bonds_and_unbonds,
+            rewards,
+            state,
+            enqueued_slashes: enqueued_slashes
+                .remove(&validator)
+                .unwrap_or_default(),
+        };
+        validators.insert(validator, overview);
+    }
+
+    Ok(StakingOverview {
+        epoch,
+        staking: Enriched {
+            data: validators,
+            bonds_total,
+            bonds_total_slashed,
+            unbonds_total,
+            unbonds_total_slashed,
+            total_withdrawable,
+        },
+        total_rewards,
+    })
+}
+
 /// Find all the validator addresses to whom the given `owner` address has
 /// some delegation in any epoch
 fn delegation_validators<D, H, V, T>(
@@ -671,6 +1028,67 @@ where
     namada_proof_of_stake::queries::has_bonds(ctx.state, &source)
 }
 
+/// Find the amount of outstanding receipt tokens of a bond.
+fn bond_receipts<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    source: Address,
+    validator: Address,
+) -> namada_storage::Result<token::Amount>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_bond_receipts(ctx.state, &BondId { source, validator })
+}
+
+/// Find the delegation pool with the given name.
+fn delegation_pool<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    name: String,
+) -> namada_storage::Result<Option<DelegationPool>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_delegation_pool(ctx.state, &name)
+}
+
+/// Find all the delegation pools.
+fn delegation_pools<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<BTreeMap<String, DelegationPool>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_delegation_pools(ctx.state)
+}
+
+/// Find the name of the delegation pool of the given delegator, if any.
+fn delegation_pool_membership<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    delegator: Address,
+) -> namada_storage::Result<Option<String>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_delegation_pool_membership(ctx.state, &delegator)
+}
+
+/// Get the automatic redelegation settings of the given delegator, if it
+/// opted in.
+fn auto_redelegation<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    delegator: Address,
+) -> namada_storage::Result<Option<AutoRedelegation>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_auto_redelegation(ctx.state, &delegator)
+}
+
 /// Client-only methods for the router type are composed from router functions.
 #[cfg(any(test, feature = "async-client"))]
 pub mod client_only_methods {
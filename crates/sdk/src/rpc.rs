@@ -5,13 +5,18 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::ops::ControlFlow;
 
 use borsh::BorshDeserialize;
+use borsh_ext::BorshSerializeExt;
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
+use namada_account::faucet::{self, FaucetLimits};
+use namada_account::name_registry::{self, NameRecord};
+use namada_account::transfer_policy::{self, TransferPolicy};
 use namada_account::Account;
 use namada_core::address::{Address, InternalAddress};
 use namada_core::arith::checked;
 use namada_core::collections::{HashMap, HashSet};
+use namada_core::dec::Dec;
 use namada_core::hash::Hash;
 use namada_core::ibc::IbcTokenHash;
 use namada_core::key::common;
@@ -24,8 +29,13 @@ use namada_core::token::{
 use namada_core::{storage, token};
 use namada_gas::event::GasUsed as GasUsedAttr;
 use namada_gas::Gas;
+use namada_governance::cli::content::ProposalContentAnchor;
 use namada_governance::parameters::GovernanceParameters;
+use namada_governance::pause::PausableModule;
 use namada_governance::pgf::parameters::PgfParameters;
+use namada_governance::pgf::simulation::{
+    TreasurySimulation, TreasurySimulationRequest,
+};
 use namada_governance::pgf::storage::steward::StewardDetail;
 use namada_governance::storage::proposal::StorageProposal;
 use namada_governance::utils::{
@@ -34,14 +44,26 @@ use namada_governance::utils::{
 use namada_ibc::storage::{
     ibc_trace_key, ibc_trace_key_prefix, is_ibc_trace_key,
 };
-use namada_parameters::{storage as params_storage, EpochDuration};
-use namada_proof_of_stake::parameters::PosParams;
+use namada_parameters::{EpochDuration, EpochedParameter};
+use namada_proof_of_stake::auto_redelegation::AutoRedelegation;
+use namada_proof_of_stake::delegation_pool::DelegationPool;
+use namada_proof_of_stake::evidence::EvidenceInfo;
+use namada_proof_of_stake::operational_metadata::OperationalMetadata;
+use namada_proof_of_stake::parameters::{OwnedPosParams, PosParams};
+use namada_proof_of_stake::storage_key::{bond_key, is_bond_key, params_key};
+use namada_proof_of_stake::telemetry::{TelemetrySummary, ValidatorTelemetry};
 use namada_proof_of_stake::types::{
-    BondsAndUnbondsDetails, CommissionPair, ValidatorMetaData,
+    BondId, BondsAndUnbondsDetails, CommissionPair, CommissionSchedule,
+    JailRecord, PendingConsensusKey, ValidatorMetaData,
+    ValidatorRewardsEstimate,
 };
 use namada_state::LastBlock;
-use namada_tx::data::{ResultCode, TxResult};
-use namada_tx::event::{Code as CodeAttr, InnerTx as InnerTxAttr};
+use namada_token::storage_key::balance_key;
+use namada_token::CompactConversionState;
+use namada_tx::data::{ErrorCode, ResultCode, TxResult};
+use namada_tx::event::{
+    Code as CodeAttr, InnerTx as InnerTxAttr, TxErrorCode as TxErrorCodeAttr,
+};
 use serde::Serialize;
 
 use crate::args::InputAmount;
@@ -52,9 +74,13 @@ use crate::internal_macros::echo_error;
 use crate::io::Io;
 use crate::masp::MaspTokenRewardData;
 use crate::queries::vp::pos::{
-    EnrichedBondsAndUnbondsDetails, ValidatorStateInfo,
+    EnrichedBondsAndUnbondsDetails, StakingOverview, ValidatorStateInfo,
+};
+use crate::queries::{
+    AppliedTxResult, BlockResultsInfo, ChainParameters, Client, DecodedTx,
+    EpochInfo, EvalVpRequest, EvalVpResult, StateChecksum, SupplyStats,
+    TxHistoryCursor, TxHistoryPage, WasmCode, RPC,
 };
-use crate::queries::{Client, RPC};
 use crate::tendermint::block::Height;
 use crate::tendermint::merkle::proof::ProofOps;
 use crate::tendermint_rpc::query::Query;
@@ -143,6 +169,21 @@ pub async fn query_native_token<C: crate::queries::Client + Sync>(
     convert_response::<C, _>(RPC.shell().native_token(client).await)
 }
 
+/// Query the current base fee per unit of gas in the native token.
+pub async fn query_base_fee<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<token::Amount, error::Error> {
+    convert_response::<C, _>(RPC.shell().base_fee(client).await)
+}
+
+/// Query the max size of a tx, in bytes. Txs larger than this are rejected
+/// by the mempool and by block proposals.
+pub async fn query_max_tx_bytes<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<u32, error::Error> {
+    convert_response::<C, _>(RPC.shell().max_tx_bytes(client).await)
+}
+
 /// Query the epoch of the given block height, if it exists.
 /// Will return none if the input block height is greater than
 /// the latest committed block height.
@@ -180,6 +221,58 @@ fn convert_response<C: crate::queries::Client, T>(
     response.map_err(|err| Error::from(QueryError::NoResponse(err.to_string())))
 }
 
+/// Query the results of the txs and the protocol events of the block at the
+/// given height
+pub async fn query_block_results<C: crate::queries::Client + Sync>(
+    client: &C,
+    height: BlockHeight,
+) -> Result<BlockResultsInfo, Error> {
+    convert_response::<C, _>(RPC.shell().block_results(client, &height).await)
+}
+
+/// Query the checksum of the state at the last committed height, made of the
+/// merkle root, the roots of its sub-trees and the roots of the storage of the
+/// internal addresses. The query is only served by the nodes that enable it
+/// in their config.
+pub async fn query_state_checksum<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<StateChecksum, Error> {
+    convert_response::<C, _>(RPC.shell().state_checksum(client).await)
+}
+
+/// Query the schemas of the typed events emitted by the ledger
+pub async fn query_event_schemas<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<Vec<crate::events::schema::EventSchema>, Error> {
+    convert_response::<C, _>(RPC.shell().event_schemas(client).await)
+}
+
+/// Query the result of a wrapper tx by the hash of the wrapper or of its inner
+/// tx. The node must store the tx results.
+pub async fn query_applied_tx<C: crate::queries::Client + Sync>(
+    client: &C,
+    tx_hash: &Hash,
+) -> Result<Option<AppliedTxResult>, Error> {
+    convert_response::<C, _>(RPC.shell().applied_tx(client, tx_hash).await)
+}
+
+/// Query a page of the history of the txs that changed the balances of the
+/// given address, backwards from the given position or from the most recent
+/// tx. The next page starts from the `next` position of the returned page. The
+/// node must index the history.
+pub async fn query_tx_history<C: crate::queries::Client + Sync>(
+    client: &C,
+    owner: &Address,
+    per_page: u64,
+    start: Option<TxHistoryCursor>,
+) -> Result<TxHistoryPage, Error> {
+    convert_response::<C, _>(
+        RPC.shell()
+            .tx_history(client, owner, &per_page, &start)
+            .await,
+    )
+}
+
 /// Query the results of the last committed block
 pub async fn query_results<C: crate::queries::Client + Sync>(
     client: &C,
@@ -256,6 +349,67 @@ pub async fn has_bonds<C: crate::queries::Client + Sync>(
     convert_response::<C, bool>(RPC.vp().pos().has_bonds(client, source).await)
 }
 
+/// Query the amount of outstanding receipt tokens of a bond
+pub async fn query_bond_receipts<C: crate::queries::Client + Sync>(
+    client: &C,
+    source: &Address,
+    validator: &Address,
+) -> Result<token::Amount, error::Error> {
+    convert_response::<C, token::Amount>(
+        RPC.vp()
+            .pos()
+            .bond_receipts(client, source, validator)
+            .await,
+    )
+}
+
+/// Query the delegation pool with the given name
+pub async fn query_delegation_pool<C: crate::queries::Client + Sync>(
+    client: &C,
+    name: &str,
+) -> Result<Option<DelegationPool>, error::Error> {
+    convert_response::<C, Option<DelegationPool>>(
+        RPC.vp()
+            .pos()
+            .delegation_pool(client, &name.to_owned())
+            .await,
+    )
+}
+
+/// Query all the delegation pools
+pub async fn query_delegation_pools<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<BTreeMap<String, DelegationPool>, error::Error> {
+    convert_response::<C, BTreeMap<String, DelegationPool>>(
+        RPC.vp().pos().delegation_pools(client).await,
+    )
+}
+
+/// Query the name of the delegation pool of a delegator, if any
+pub async fn query_delegation_pool_membership<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    delegator: &Address,
+) -> Result<Option<String>, error::Error> {
+    convert_response::<C, Option<String>>(
+        RPC.vp()
+            .pos()
+            .delegation_pool_membership(client, delegator)
+            .await,
+    )
+}
+
+/// Query the automatic redelegation settings of a delegator, if it opted in
+pub async fn query_auto_redelegation<C: crate::queries::Client + Sync>(
+    client: &C,
+    delegator: &Address,
+) -> Result<Option<AutoRedelegation>, error::Error> {
+    convert_response::<C, Option<AutoRedelegation>>(
+        RPC.vp().pos().auto_redelegation(client, delegator).await,
+    )
+}
+
 /// Get the set of pgf stewards
 pub async fn query_pgf_stewards<C: crate::queries::Client + Sync>(
     client: &C,
@@ -265,6 +419,50 @@ pub async fn query_pgf_stewards<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Get the epoch from which an elected pgf steward is removed, if any
+pub async fn query_pgf_steward_term_end<C: crate::queries::Client + Sync>(
+    client: &C,
+    address: &Address,
+) -> Result<Option<Epoch>, error::Error> {
+    convert_response::<C, Option<Epoch>>(
+        RPC.vp().pgf().steward_term_end(client, address).await,
+    )
+}
+
+/// Simulate the PGF treasury over the next epochs under a hypothetical
+/// funding set. All the pages of the simulation are queried, starting from
+/// the cursor of the request.
+pub async fn simulate_pgf_treasury<C: crate::queries::Client + Sync>(
+    client: &C,
+    request: &TreasurySimulationRequest,
+) -> Result<TreasurySimulation, error::Error> {
+    let mut request = request.clone();
+    let mut projections = Vec::new();
+    loop {
+        let page: TreasurySimulation = convert_response::<C, _>(
+            RPC.vp()
+                .pgf()
+                .simulate_treasury(
+                    client,
+                    Some(request.serialize_to_vec()),
+                    None,
+                    false,
+                )
+                .await,
+        )?
+        .data;
+        projections.extend(page.projections);
+        if page.next.is_none() {
+            return Ok(TreasurySimulation {
+                initial_balance: page.initial_balance,
+                projections,
+                next: None,
+            });
+        }
+        request.cursor = page.next;
+    }
+}
+
 /// Query the consensus key by validator address
 pub async fn query_validator_consensus_keys<
     C: crate::queries::Client + Sync,
@@ -320,7 +518,8 @@ pub async fn query_conversion<C: crate::queries::Client + Sync>(
     )
 }
 
-/// Query conversions
+/// Query conversions. They are rebuilt from the compact conversion state,
+/// which is cheaper to transfer.
 pub async fn query_conversions<C: crate::queries::Client + Sync>(
     client: &C,
 ) -> Result<
@@ -336,7 +535,25 @@ pub async fn query_conversions<C: crate::queries::Client + Sync>(
     >,
     error::Error,
 > {
-    convert_response::<C, _>(RPC.shell().read_conversions(client).await)
+    let state = query_compact_conversions(client)
+        .await?
+        .into_conversion_state()
+        .map_err(|err| Error::from(EncodingError::Decoding(err.to_string())))?;
+    Ok(state
+        .assets
+        .into_iter()
+        .map(|(asset_type, ((addr, denom, digit), epoch, conv, _))| {
+            (asset_type, (addr, denom, digit, epoch, conv.into()))
+        })
+        .collect())
+}
+
+/// Query the conversion state in its compact form. The full conversion state,
+/// including the conversion tree, can be rebuilt from it.
+pub async fn query_compact_conversions<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<CompactConversionState, error::Error> {
+    convert_response::<C, _>(RPC.shell().read_compact_conversions(client).await)
 }
 
 /// Query to read the tokens that earn masp rewards.
@@ -346,6 +563,40 @@ pub async fn query_masp_reward_tokens<C: crate::queries::Client + Sync>(
     convert_response::<C, _>(RPC.shell().masp_reward_tokens(client).await)
 }
 
+/// Query the wasm codes of the transactions allowed on chain, with their
+/// names, sizes and the heights at which they were added
+pub async fn query_allowed_tx_codes<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<Vec<WasmCode>, error::Error> {
+    convert_response::<C, _>(RPC.shell().allowed_tx_codes(client).await)
+}
+
+/// Query the wasm codes of the validity predicates allowed on chain, with
+/// their names, sizes and the heights at which they were added
+pub async fn query_allowed_vp_codes<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<Vec<WasmCode>, error::Error> {
+    convert_response::<C, _>(RPC.shell().allowed_vp_codes(client).await)
+}
+
+/// Query the wasm codes of the transactions exempt from the minimum fee by
+/// governance, with their names, sizes and the heights at which they were
+/// added
+pub async fn query_fee_exempt_codes<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<Vec<WasmCode>, error::Error> {
+    convert_response::<C, _>(RPC.shell().fee_exempt_codes(client).await)
+}
+
+/// Resolve the name of a wasm code from its hash, using the wasm registry of
+/// the chain. Returns `None` if the code isn't registered.
+pub async fn resolve_code_name<C: crate::queries::Client + Sync>(
+    client: &C,
+    code_hash: &Hash,
+) -> Result<Option<String>, error::Error> {
+    convert_response::<C, _>(RPC.shell().code_name(client, code_hash).await)
+}
+
 /// Query a wasm code hash
 pub async fn query_wasm_code_hash(
     context: &impl Namada,
@@ -406,6 +657,48 @@ where
         .map_err(|err| Error::from(EncodingError::Decoding(err.to_string())))
 }
 
+/// Query the current value of a protocol parameter stored at the given key,
+/// and the changes of the parameter scheduled for future epochs.
+pub async fn query_epoched_parameter<C, T>(
+    client: &C,
+    key: &storage::Key,
+) -> Result<(T, BTreeMap<Epoch, T>), Error>
+where
+    T: BorshDeserialize,
+    C: crate::queries::Client + Sync,
+{
+    let parameter =
+        EpochedParameter::<T>::open(key.clone()).ok_or_else(|| {
+            Error::Other(format!("{key} is not a protocol parameter key"))
+        })?;
+    let current = query_storage_value(client, parameter.key()).await?;
+    let values = convert_response::<C, _>(
+        RPC.shell()
+            .storage_prefix(
+                client,
+                None,
+                None,
+                false,
+                &parameter.pending_prefix(),
+            )
+            .await,
+    )?;
+    let mut pending = BTreeMap::new();
+    for PrefixValue { key, value } in values.data {
+        let pending_key =
+            namada_parameters::storage::is_pending_parameter_key(&key);
+        if let Some((_, epoch)) =
+            pending_key.filter(|(name, _)| *name == parameter.name())
+        {
+            let value = T::try_from_slice(&value[..]).map_err(|err| {
+                Error::from(EncodingError::Decoding(err.to_string()))
+            })?;
+            pending.insert(epoch, value);
+        }
+    }
+    Ok((current, pending))
+}
+
 /// Query a storage value and the proof without decoding.
 pub async fn query_storage_value_bytes<C: crate::queries::Client + Sync>(
     client: &C,
@@ -426,6 +719,124 @@ pub async fn query_storage_value_bytes<C: crate::queries::Client + Sync>(
     })
 }
 
+/// Query the values of multiple storage keys and the proof of all of them in
+/// one round-trip, without decoding. The values are in the order of the keys.
+pub async fn query_storage_multi_value_bytes<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    keys: &[storage::Key],
+    height: Option<BlockHeight>,
+    prove: bool,
+) -> Result<(Vec<Option<Vec<u8>>>, Option<ProofOps>), error::Error> {
+    let data = Some(keys.serialize_to_vec());
+    let response = convert_response::<C, _>(
+        RPC.shell()
+            .storage_multi_value(client, data, height, prove)
+            .await,
+    )?;
+    Ok((response.data, response.proof))
+}
+
+/// Query the balances of multiple (token, owner) pairs in one round-trip. The
+/// balances are in the order of the pairs.
+pub async fn get_token_balances<C: crate::queries::Client + Sync>(
+    client: &C,
+    balances: &[(Address, Address)],
+) -> Result<Vec<token::Amount>, error::Error> {
+    let data = Some(balances.serialize_to_vec());
+    convert_response::<C, _>(
+        RPC.shell()
+            .storage_multi_balance(client, data, None, false)
+            .await,
+    )
+    .map(|response| response.data)
+}
+
+/// Query a storage value as of the given height and decode it with
+/// [`BorshDeserialize`]. Returns `None` if the key had no value at that height.
+/// Heights older than the node's `storage_read_past_height_limit` can only be
+/// queried from archive nodes.
+pub async fn query_storage_value_at_height<C, T>(
+    client: &C,
+    key: &storage::Key,
+    height: BlockHeight,
+) -> Result<Option<T>, Error>
+where
+    T: BorshDeserialize,
+    C: crate::queries::Client + Sync,
+{
+    let (value, _proof) =
+        query_storage_value_bytes(client, key, Some(height), false).await?;
+    value
+        .map(|value| {
+            T::try_from_slice(&value[..]).map_err(|err| {
+                Error::from(EncodingError::Decoding(err.to_string()))
+            })
+        })
+        .transpose()
+}
+
+/// Query token amount of owner as of the given height.
+pub async fn get_token_balance_at_height<C: crate::queries::Client + Sync>(
+    client: &C,
+    token: &Address,
+    owner: &Address,
+    height: BlockHeight,
+) -> Result<token::Amount, error::Error> {
+    let key = balance_key(token, owner);
+    Ok(query_storage_value_at_height(client, &key, height)
+        .await?
+        .unwrap_or_default())
+}
+
+/// Query the bonds of the source to the validator as of the given height,
+/// keyed by their start epoch. Only archive nodes can answer for heights
+/// before the last committed block.
+pub async fn query_bonds_at_height<C: crate::queries::Client + Sync>(
+    client: &C,
+    source: &Address,
+    validator: &Address,
+    height: BlockHeight,
+) -> Result<BTreeMap<Epoch, token::Amount>, error::Error> {
+    let prefix = bond_key(&BondId {
+        source: source.clone(),
+        validator: validator.clone(),
+    });
+    let response = convert_response::<C, _>(
+        RPC.shell()
+            .storage_prefix(client, None, Some(height), false, &prefix)
+            .await,
+    )?;
+    response
+        .data
+        .into_iter()
+        .filter_map(|PrefixValue { key, value }| {
+            let (_bond_id, epoch) = is_bond_key(&key)?;
+            Some(
+                token::Amount::try_from_slice(&value[..])
+                    .map(|amount| (epoch, amount))
+                    .map_err(|err| {
+                        Error::from(EncodingError::Decoding(err.to_string()))
+                    }),
+            )
+        })
+        .collect()
+}
+
+/// Query the PoS parameters as of the given height.
+pub async fn query_pos_parameters_at_height<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    height: BlockHeight,
+) -> Result<OwnedPosParams, error::Error> {
+    let key = params_key();
+    query_storage_value_at_height(client, &key, height)
+        .await?
+        .ok_or_else(|| Error::from(QueryError::NoSuchKey(key.to_string())))
+}
+
 /// Query a range of storage values with a matching prefix and decode them with
 /// [`BorshDeserialize`]. Returns an iterator of the storage keys paired with
 /// their associated values.
@@ -519,6 +930,32 @@ pub async fn query_tx_events<C: crate::queries::Client + Sync>(
     }
 }
 
+/// Decode a transaction from its bytes into a structured description
+pub async fn query_decoded_tx<C: crate::queries::Client + Sync>(
+    client: &C,
+    tx_bytes: Vec<u8>,
+) -> Result<DecodedTx, error::Error> {
+    let (data, height, prove) = (Some(tx_bytes), None, false);
+    convert_response::<C, _>(
+        RPC.shell().decode_tx(client, data, height, prove).await,
+    )
+    .map(|response| response.data)
+}
+
+/// Evaluate a VP against a tx applied to the state at a past height. The node
+/// must enable the evaluation in its config, and be in archive mode to
+/// evaluate it below its last committed height.
+pub async fn eval_vp<C: crate::queries::Client + Sync>(
+    client: &C,
+    request: &EvalVpRequest,
+) -> Result<EvalVpResult, error::Error> {
+    let (data, height, prove) = (Some(request.serialize_to_vec()), None, false);
+    convert_response::<C, _>(
+        RPC.shell().eval_vp(client, data, height, prove).await,
+    )
+    .map(|response| response.data)
+}
+
 /// Dry run a transaction
 pub async fn dry_run_tx<N: Namada>(
     context: &N,
@@ -583,6 +1020,8 @@ pub struct TxResponse {
     pub hash: Hash,
     /// Response code
     pub code: ResultCode,
+    /// The code of the cause of the failure of the tx, if any
+    pub error_code: Option<ErrorCode>,
     /// Gas used. If there's an `inner_tx`, its gas is equal to this value.
     pub gas_used: Gas,
 }
@@ -620,6 +1059,7 @@ impl TryFrom<Event> for TxResponse {
         let gas_used = event
             .read_attribute::<GasUsedAttr>()
             .map_err(|err| err.to_string())?;
+        let error_code = event.read_attribute::<TxErrorCodeAttr>().ok();
 
         Ok(TxResponse {
             inner_tx,
@@ -628,6 +1068,7 @@ impl TryFrom<Event> for TxResponse {
             log,
             height,
             code,
+            error_code,
             gas_used,
         })
     }
@@ -701,6 +1142,23 @@ pub async fn get_validator_stake<C: crate::queries::Client + Sync>(
     .map(|t| t.unwrap_or_default())
 }
 
+/// Query the amount of tokens that can still be bonded to a validator under
+/// the cap of its share of the total stake. Returns `None` if the stake of the
+/// validators is not capped.
+pub async fn query_validator_remaining_capacity<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    validator: &Address,
+) -> Result<Option<token::Amount>, error::Error> {
+    convert_response::<C, _>(
+        RPC.vp()
+            .pos()
+            .validator_remaining_capacity(client, validator)
+            .await,
+    )
+}
+
 /// Query and return a validator's state
 pub async fn get_validator_state<C: crate::queries::Client + Sync>(
     client: &C,
@@ -756,6 +1214,19 @@ pub async fn query_proposal_by_id<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Query the anchored off-chain content hash and the metadata of a proposal
+pub async fn query_proposal_content_anchor<C: crate::queries::Client + Sync>(
+    client: &C,
+    proposal_id: u64,
+) -> Result<Option<ProposalContentAnchor>, Error> {
+    convert_response::<C, _>(
+        RPC.vp()
+            .gov()
+            .proposal_content_anchor(client, &proposal_id)
+            .await,
+    )
+}
+
 /// Query and return validator's commission rate and max commission rate change
 /// per epoch
 pub async fn query_commission_rate<C: crate::queries::Client + Sync>(
@@ -771,6 +1242,52 @@ pub async fn query_commission_rate<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Query and return validator's commission rate in the current epoch together
+/// with its pending changes up to the pipeline epoch
+pub async fn query_commission_schedule<C: crate::queries::Client + Sync>(
+    client: &C,
+    validator: &Address,
+) -> Result<Option<CommissionSchedule>, Error> {
+    convert_response::<C, Option<CommissionSchedule>>(
+        RPC.vp()
+            .pos()
+            .validator_commission_schedule(client, validator)
+            .await,
+    )
+}
+
+/// Check if the validator's commission rate can be changed to the given rate
+/// in the current epoch. Returns the reason of the rejection if the change
+/// would not be accepted.
+pub async fn check_commission_rate_change<C: crate::queries::Client + Sync>(
+    client: &C,
+    validator: &Address,
+    rate: Dec,
+) -> Result<Option<String>, Error> {
+    convert_response::<C, Option<String>>(
+        RPC.vp()
+            .pos()
+            .check_validator_commission_change(client, validator, &rate)
+            .await,
+    )
+}
+
+/// Query and return the estimated annualized rewards rates of a validator and
+/// its delegators
+pub async fn query_validator_rewards_estimate<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    validator: &Address,
+) -> Result<Option<ValidatorRewardsEstimate>, Error> {
+    convert_response::<C, Option<ValidatorRewardsEstimate>>(
+        RPC.vp()
+            .pos()
+            .validator_rewards_estimate(client, validator)
+            .await,
+    )
+}
+
 /// Query and return validator's metadata, including the commission rate and max
 /// commission rate change
 pub async fn query_metadata<C: crate::queries::Client + Sync>(
@@ -790,6 +1307,80 @@ pub async fn query_metadata<C: crate::queries::Client + Sync>(
     Ok((metadata, commission_info))
 }
 
+/// Query the operational metadata published by a validator
+pub async fn query_operational_metadata<C: crate::queries::Client + Sync>(
+    client: &C,
+    validator: &Address,
+) -> Result<Option<OperationalMetadata>, Error> {
+    convert_response::<C, _>(
+        RPC.vp()
+            .pos()
+            .validator_operational_metadata(client, validator)
+            .await,
+    )
+}
+
+/// Query the nonce of a validator's next operational metadata update
+pub async fn query_operational_metadata_nonce<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    validator: &Address,
+) -> Result<u64, Error> {
+    convert_response::<C, _>(
+        RPC.vp()
+            .pos()
+            .validator_operational_metadata_nonce(client, validator)
+            .await,
+    )
+}
+
+/// Query the telemetry of a validator's last heartbeat
+pub async fn query_validator_telemetry<C: crate::queries::Client + Sync>(
+    client: &C,
+    validator: &Address,
+) -> Result<Option<ValidatorTelemetry>, Error> {
+    convert_response::<C, _>(
+        RPC.vp().pos().validator_telemetry(client, validator).await,
+    )
+}
+
+/// Query the telemetry of the consensus validators at the given epoch, or at
+/// the current one when `None`, aggregated by the binary they reported
+pub async fn query_telemetry_summary<C: crate::queries::Client + Sync>(
+    client: &C,
+    epoch: Option<Epoch>,
+) -> Result<TelemetrySummary, Error> {
+    convert_response::<C, _>(
+        RPC.vp().pos().telemetry_summary(client, &epoch).await,
+    )
+}
+
+/// Query the evidence of validators' misbehavior received since the given
+/// epoch, or all the evidence that is still recorded when `None`, with the
+/// resulting slashes
+pub async fn query_recent_evidence<C: crate::queries::Client + Sync>(
+    client: &C,
+    since: Option<Epoch>,
+) -> Result<Vec<EvidenceInfo>, Error> {
+    convert_response::<C, _>(
+        RPC.vp().pos().recent_evidence(client, &since).await,
+    )
+}
+
+/// Query the protocol key of a validator in the current epoch
+pub async fn query_validator_protocol_key<C: crate::queries::Client + Sync>(
+    client: &C,
+    validator: &Address,
+) -> Result<Option<common::PublicKey>, Error> {
+    convert_response::<C, _>(
+        RPC.vp()
+            .pos()
+            .validator_protocol_key(client, validator)
+            .await,
+    )
+}
+
 /// Query and return the incoming redelegation epoch for a given pair of source
 /// validator and delegator, if there is any.
 pub async fn query_incoming_redelegations<C: crate::queries::Client + Sync>(
@@ -830,6 +1421,34 @@ pub async fn query_last_infraction_epoch<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Query the most recent records of a validator being jailed, from the oldest
+/// to the newest
+pub async fn query_validator_jail_history<C: crate::queries::Client + Sync>(
+    client: &C,
+    validator: &Address,
+) -> Result<Vec<JailRecord>, error::Error> {
+    convert_response::<C, _>(
+        RPC.vp()
+            .pos()
+            .validator_jail_history(client, validator)
+            .await,
+    )
+}
+
+/// Query the latest change of a validator's consensus key that is not in
+/// effect yet
+pub async fn query_pending_consensus_key<C: crate::queries::Client + Sync>(
+    client: &C,
+    validator: &Address,
+) -> Result<Option<PendingConsensusKey>, error::Error> {
+    convert_response::<C, _>(
+        RPC.vp()
+            .pos()
+            .validator_pending_consensus_key(client, validator)
+            .await,
+    )
+}
+
 /// Query the accunt substorage space of an address
 pub async fn get_account_info<C: crate::queries::Client + Sync>(
     client: &C,
@@ -840,6 +1459,68 @@ pub async fn get_account_info<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Query the record of a name in the name registry
+pub async fn query_name_record<C: crate::queries::Client + Sync>(
+    client: &C,
+    name: &str,
+) -> Result<Option<NameRecord>, error::Error> {
+    let key = name_registry::name_key(name);
+    query_storage_value_bytes(client, &key, None, false)
+        .await?
+        .0
+        .map(|bytes| {
+            NameRecord::try_from_slice(&bytes).map_err(|err| {
+                Error::from(EncodingError::Decoding(err.to_string()))
+            })
+        })
+        .transpose()
+}
+
+/// Resolve a name registered in the name registry to its target address
+pub async fn resolve_name<C: crate::queries::Client + Sync>(
+    client: &C,
+    name: &str,
+) -> Result<Option<Address>, error::Error> {
+    Ok(query_name_record(client, name)
+        .await?
+        .map(|record| record.target))
+}
+
+/// Query the withdrawal limits of a token of a faucet
+pub async fn query_faucet_limits<C: crate::queries::Client + Sync>(
+    client: &C,
+    faucet: &Address,
+    token: &Address,
+) -> Result<Option<FaucetLimits>, error::Error> {
+    let key = faucet::limits_key(faucet, token);
+    query_storage_value_bytes(client, &key, None, false)
+        .await?
+        .0
+        .map(|bytes| {
+            FaucetLimits::try_from_slice(&bytes).map_err(|err| {
+                Error::from(EncodingError::Decoding(err.to_string()))
+            })
+        })
+        .transpose()
+}
+
+/// Query the transfer policy of an account
+pub async fn query_transfer_policy<C: crate::queries::Client + Sync>(
+    client: &C,
+    owner: &Address,
+) -> Result<Option<TransferPolicy>, error::Error> {
+    let key = transfer_policy::transfer_policy_key(owner);
+    query_storage_value_bytes(client, &key, None, false)
+        .await?
+        .0
+        .map(|bytes| {
+            TransferPolicy::try_from_slice(&bytes).map_err(|err| {
+                Error::from(EncodingError::Decoding(err.to_string()))
+            })
+        })
+        .transpose()
+}
+
 /// Query if the public_key is revealed
 pub async fn is_public_key_revealed<C: crate::queries::Client + Sync>(
     client: &C,
@@ -885,9 +1566,16 @@ pub async fn query_proposal_result<C: crate::queries::Client + Sync>(
 
             let is_author_pgf_steward =
                 is_steward(client, &proposal.author).await;
-            let votes = query_proposal_votes(client, proposal_id)
+            let mut votes = query_proposal_votes(client, proposal_id)
                 .await
                 .unwrap_or_default();
+            // Count the votes of the voting delegates on behalf of their
+            // delegators
+            let delegated_votes =
+                query_proposal_delegated_votes(client, proposal_id)
+                    .await
+                    .unwrap_or_default();
+            votes.extend(delegated_votes);
             let tally_type = proposal.get_tally_type(is_author_pgf_steward);
             let total_staked_token =
                 get_total_staked_tokens(client, tally_epoch)
@@ -1036,20 +1724,96 @@ pub async fn query_proposal_votes<C: crate::queries::Client + Sync>(
     )
 }
 
-pub async fn query_next_epoch_info<C: crate::queries::Client + Sync>(
+/// Query the votes cast by the voting delegates on behalf of their delegators
+/// on a proposal
+pub async fn query_proposal_delegated_votes<
+    C: crate::queries::Client + Sync,
+>(
     client: &C,
-) -> Result<(BlockHeight, EpochDuration), error::Error> {
-    let this_epoch_first_height = convert_response::<C, BlockHeight>(
+    proposal_id: u64,
+) -> Result<Vec<Vote>, error::Error> {
+    convert_response::<C, Vec<Vote>>(
+        RPC.vp()
+            .gov()
+            .proposal_id_delegated_votes(client, &proposal_id)
+            .await,
+    )
+}
+
+/// Query the voting delegate of a delegator, if any
+pub async fn query_voting_delegate<C: crate::queries::Client + Sync>(
+    client: &C,
+    delegator: &Address,
+) -> Result<Option<Address>, error::Error> {
+    convert_response::<C, Option<Address>>(
+        RPC.vp().gov().voting_delegate(client, delegator).await,
+    )
+}
+
+/// Query the modules paused by governance with the epochs from which they are
+/// no longer paused
+pub async fn query_paused_modules<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<BTreeMap<PausableModule, Epoch>, error::Error> {
+    convert_response::<C, BTreeMap<PausableModule, Epoch>>(
+        RPC.vp().gov().paused_modules(client).await,
+    )
+}
+
+/// Query the current epoch, its predecessors and the expected start of the
+/// next epoch
+pub async fn query_epoch_info<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<EpochInfo, error::Error> {
+    convert_response::<C, EpochInfo>(RPC.shell().epoch_info(client).await)
+}
+
+/// Query the first block height of the given epoch, if the node knows it
+pub async fn query_first_block_height_of_epoch<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    epoch: Epoch,
+) -> Result<Option<BlockHeight>, error::Error> {
+    convert_response::<C, _>(
         RPC.shell()
-            .first_block_height_of_current_epoch(client)
+            .first_block_height_of_epoch(client, &epoch)
             .await,
-    )?;
+    )
+}
+
+/// Query the protocol, PoS, governance and PGF parameters at the given
+/// height, or at the last committed height if `None`. The parameters at a
+/// past height can only be read from a node that kept the storage diffs since
+/// that height.
+pub async fn query_chain_parameters<C: crate::queries::Client + Sync>(
+    client: &C,
+    height: Option<BlockHeight>,
+) -> Result<ChainParameters, error::Error> {
+    let height = height.unwrap_or_default();
+    convert_response::<C, _>(
+        RPC.shell().chain_parameters(client, &height).await,
+    )
+}
+
+/// Query the supply of the native token, the last epoch's PoS and PGF
+/// inflation, the annual inflation rate and the staked ratio.
+pub async fn query_supply_stats<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<SupplyStats, error::Error> {
+    convert_response::<C, _>(RPC.shell().supply_stats(client).await)
+}
 
-    let key = params_storage::get_epoch_duration_storage_key();
-    let epoch_duration: EpochDuration =
-        query_storage_value(client, &key).await?;
+pub async fn query_next_epoch_info<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<(BlockHeight, EpochDuration), error::Error> {
+    let EpochInfo {
+        first_block_height,
+        epoch_duration,
+        ..
+    } = query_epoch_info(client).await?;
 
-    Ok((this_epoch_first_height, epoch_duration))
+    Ok((first_block_height, epoch_duration))
 }
 
 /// Get the bond amount at the given epoch
@@ -1105,6 +1869,17 @@ pub async fn enriched_bonds_and_unbonds<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Get the bonds, unbonds, rewards and slashing exposure of an owner with all
+/// their validators, at the current epoch
+pub async fn query_staking_overview<C: crate::queries::Client + Sync>(
+    client: &C,
+    owner: &Address,
+) -> Result<StakingOverview, error::Error> {
+    convert_response::<C, _>(
+        RPC.vp().pos().staking_overview(client, owner).await,
+    )
+}
+
 /// Query the denomination of the given token
 pub async fn query_denom<C: crate::queries::Client + Sync>(
     client: &C,
@@ -1315,6 +2090,16 @@ pub async fn query_ibc_tokens<N: Namada>(
     Ok(tokens)
 }
 
+/// Query the full denom trace of an IBC token, e.g.
+/// `transfer/channel-0/uatom`, from the registry populated at the first
+/// receipt of the token. Returns `None` for unregistered tokens.
+pub async fn query_ibc_denom_trace<C: crate::queries::Client + Sync>(
+    client: &C,
+    token: &Address,
+) -> Result<Option<String>, error::Error> {
+    convert_response::<C, _>(RPC.shell().ibc_denom_trace(client, token).await)
+}
+
 /// Look up the IBC denomination from a IbcToken.
 pub async fn query_ibc_denom<N: Namada>(
     context: &N,
@@ -1322,12 +2107,20 @@ pub async fn query_ibc_denom<N: Namada>(
     owner: Option<&Address>,
 ) -> String {
     let hash = match Address::decode(token.as_ref()) {
-        Ok(Address::Internal(InternalAddress::IbcToken(hash))) => {
-            hash.to_string()
-        }
+        Ok(Address::Internal(InternalAddress::IbcToken(hash))) => hash,
         _ => return token.as_ref().to_string(),
     };
 
+    let ibc_token = Address::Internal(InternalAddress::IbcToken(hash.clone()));
+    if let Ok(Some(ibc_denom)) =
+        query_ibc_denom_trace(context.client(), &ibc_token).await
+    {
+        return ibc_denom;
+    }
+    // The token might have been received before the denom trace registry
+    // existed, fall back to the traces of the owners
+    let hash = hash.to_string();
+
     if let Some(owner) = owner {
         let ibc_trace_key = ibc_trace_key(owner.to_string(), &hash);
         if let Ok(ibc_denom) =
@@ -12,7 +12,9 @@ use masp_primitives::transaction::components::sapling::fees::{
 };
 use masp_primitives::transaction::Transaction;
 use namada_account::{AccountPublicKeysMap, InitAccount, UpdateAccount};
-use namada_core::address::{Address, ImplicitAddress, InternalAddress, MASP};
+use namada_core::address::{
+    Address, ImplicitAddress, InternalAddress, FEE_COLLECTOR, MASP,
+};
 use namada_core::arith::checked;
 use namada_core::collections::{HashMap, HashSet};
 use namada_core::key::*;
@@ -30,7 +32,7 @@ use namada_parameters::storage as parameter_storage;
 use namada_token::storage_key::balance_key;
 use namada_tx::data::pgf::UpdateStewardCommission;
 use namada_tx::data::pos::BecomeValidator;
-use namada_tx::data::{pos, Fee};
+use namada_tx::data::{pos, Fee, GasLimit, WrapperTx};
 use namada_tx::{MaspBuilder, Section, Tx};
 use prost::Message;
 use rand::rngs::OsRng;
@@ -415,6 +417,121 @@ pub struct TxSourcePostBalance {
     pub token: Address,
 }
 
+/// A human-readable breakdown of the fee of a wrapper tx
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeBreakdown {
+    /// The token in which the fee is paid
+    pub token: Address,
+    /// The gas limit of the tx
+    pub gas_limit: GasLimit,
+    /// The price paid per unit of gas
+    pub gas_price: DenominatedAmount,
+    /// The minimum price per unit of gas in the token, if the token is
+    /// accepted for the payment of fees
+    pub minimum_gas_price: Option<DenominatedAmount>,
+    /// The total fee, i.e. the gas limit times the price per unit of gas
+    pub total: DenominatedAmount,
+    /// The total fee converted to the native token at the ratio of the
+    /// minimum gas prices of the two tokens, if they are both known
+    pub native_total: Option<DenominatedAmount>,
+}
+
+impl FeeBreakdown {
+    /// Compute the fee breakdown of a wrapper tx with the gas cost table of
+    /// the chain, given the denominations of the fee and native tokens
+    pub fn new(
+        wrapper: &WrapperTx,
+        gas_cost_table: &BTreeMap<Address, Amount>,
+        native_token: &Address,
+        fee_token_denom: token::Denomination,
+        native_token_denom: token::Denomination,
+    ) -> Result<Self, Error> {
+        let token = wrapper.fee.token.clone();
+        let total = wrapper
+            .get_tx_fee()
+            .map_err(|err| Error::Other(err.to_string()))?;
+        let minimum_gas_price = gas_cost_table
+            .get(&token)
+            .map(|price| DenominatedAmount::new(*price, fee_token_denom));
+        let native_total = if &token == native_token {
+            Some(total)
+        } else {
+            // The raw total times the ratio of the raw minimum gas prices
+            let native_price = gas_cost_table.get(native_token);
+            let token_price = gas_cost_table.get(&token);
+            total
+                .scale(fee_token_denom)
+                .ok()
+                .zip(native_price.zip(token_price))
+                .and_then(|(total, (native_price, token_price))| {
+                    total.checked_mul(*native_price)?.checked_div(*token_price)
+                })
+                .map(|total| DenominatedAmount::new(total, native_token_denom))
+        };
+        Ok(Self {
+            token,
+            gas_limit: wrapper.gas_limit,
+            gas_price: wrapper.fee.amount_per_gas_unit,
+            minimum_gas_price,
+            total,
+            native_total,
+        })
+    }
+}
+
+impl Display for FeeBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Fee token: {}", self.token)?;
+        writeln!(f, "Gas limit: {}", u64::from(self.gas_limit))?;
+        write!(f, "Price per gas unit: {}", self.gas_price)?;
+        match &self.minimum_gas_price {
+            Some(minimum) => writeln!(f, " (minimum: {minimum})")?,
+            None => writeln!(f, " (the token is not accepted for fees)")?,
+        }
+        write!(f, "Total fee: {}", self.total)?;
+        match &self.native_total {
+            Some(native_total) => {
+                write!(f, " (worth {native_total} in the native token)")
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Compute the fee breakdown of the given wrapper tx with the current gas cost
+/// table of the chain
+pub async fn fee_breakdown<N: Namada>(
+    context: &N,
+    tx: &Tx,
+) -> Result<FeeBreakdown, Error> {
+    let wrapper = tx.header().wrapper().ok_or_else(|| {
+        Error::Other("Only the fees of wrapper txs can be displayed".into())
+    })?;
+    let gas_cost_key = parameter_storage::get_gas_cost_key();
+    let gas_cost_table =
+        rpc::query_storage_value::<_, BTreeMap<Address, Amount>>(
+            context.client(),
+            &gas_cost_key,
+        )
+        .await?;
+    let native_token = context.native_token();
+    let fee_token_denom = context
+        .denominate_amount(&wrapper.fee.token, Amount::zero())
+        .await
+        .denom();
+    let native_token_denom = context
+        .denominate_amount(&native_token, Amount::zero())
+        .await
+        .denom();
+    FeeBreakdown::new(
+        &wrapper,
+        &gas_cost_table,
+        &native_token,
+        fee_token_denom,
+        native_token_denom,
+    )
+}
+
 /// Validate the fee of the transaction and generate the fee unshielding
 /// transaction if needed
 pub async fn validate_fee_and_gen_unshield<N: Namada>(
@@ -487,29 +604,60 @@ pub async fn validate_fee_and_gen_unshield<N: Namada>(
     .await
     .unwrap_or_default();
 
-    let total_fee = checked!(fee_amount.amount() * u64::from(args.gas_limit))?;
+    let total_fee = fee_amount.try_mul(u64::from(args.gas_limit))?;
     let mut updated_balance = TxSourcePostBalance {
         post_balance: balance,
         source: fee_payer_address.clone(),
         token: args.fee_token.clone(),
     };
 
-    let unshield = match total_fee.checked_sub(balance) {
+    if args.shielded_fee && args.fee_unshield.is_none() {
+        return Err(Error::Other(
+            "Paying the fee out of the shielded pool requires a spending key \
+             for fee unshielding"
+                .to_string(),
+        ));
+    }
+    // A fee paid out of the shielded pool is unshielded in full, without
+    // touching the transparent balance of the fee payer
+    let shortfall = if args.shielded_fee {
+        Some(total_fee.amount())
+    } else {
+        total_fee.amount().checked_sub(balance)
+    };
+    let unshield = match shortfall {
         Some(diff) if !diff.is_zero() => {
             if let Some(spending_key) = args.fee_unshield.clone() {
-                // Unshield funds for fee payment
-                let target = namada_core::masp::TransferTarget::Address(
-                    fee_payer_address.clone(),
-                );
-                let fee_amount = DenominatedAmount::new(
-                    // NOTE: must unshield the total fee amount, not the
-                    // diff, because the ledger evaluates the transaction in
-                    // reverse (wrapper first, inner second) and cannot know
-                    // ahead of time if the inner will modify the balance of
-                    // the gas payer
-                    total_fee,
-                    0.into(),
-                );
+                // Unshield funds for fee payment, either to the fee collector
+                // which forwards them to the block proposer or to the fee
+                // payer
+                let (target, unshield_amount) = if args.shielded_fee {
+                    (FEE_COLLECTOR, diff)
+                } else {
+                    // Only unshield the shortfall of the transparent balance,
+                    // plus the requested headroom in case the balance changes
+                    // before the wrapper is applied, but never more than the
+                    // fee
+                    let headroom = match args.fee_unshield_headroom.clone() {
+                        Some(headroom) => validate_amount(
+                            context,
+                            headroom,
+                            &args.fee_token,
+                            args.force,
+                        )
+                        .await?
+                        .amount(),
+                        None => Amount::zero(),
+                    };
+                    let unshield_amount = diff
+                        .checked_add(headroom)
+                        .unwrap_or(total_fee.amount())
+                        .min(total_fee.amount());
+                    (fee_payer_address.clone(), unshield_amount)
+                };
+                let target = namada_core::masp::TransferTarget::Address(target);
+                let fee_amount =
+                    DenominatedAmount::new(unshield_amount, total_fee.denom());
 
                 match ShieldedContext::<N::ShieldedUtils>::gen_shielded_transfer(
                         context,
@@ -567,7 +715,10 @@ pub async fn validate_fee_and_gen_unshield<N: Namada>(
                             ));
                         }
 
-                                                Some(transaction)
+                        updated_balance.post_balance = checked!(
+                            balance + unshield_amount - total_fee.amount()
+                        )?;
+                        Some(transaction)
                     }
                     Ok(None) => {
                         if !args.force {
@@ -595,8 +746,7 @@ pub async fn validate_fee_and_gen_unshield<N: Namada>(
             } else {
                 let token_addr = args.fee_token.clone();
                 if !args.force {
-                    let fee_amount =
-                        context.format_amount(&token_addr, total_fee).await;
+                    let fee_amount = total_fee.to_string();
 
                     let balance =
                         context.format_amount(&token_addr, balance).await;
@@ -623,7 +773,7 @@ pub async fn validate_fee_and_gen_unshield<N: Namada>(
                 ));
             }
             updated_balance.post_balance =
-                checked!(updated_balance.post_balance - total_fee)?;
+                checked!(updated_balance.post_balance - total_fee.amount())?;
             None
         }
     };
@@ -1039,6 +1189,31 @@ fn proposal_type_to_ledger_vector(
                 }
             }
         }
+        ProposalType::PGFStewardElection(election) => {
+            output.push("Proposal type : PGF Steward Election".to_string());
+            for steward in &election.stewards {
+                output.push(format!("Elect : {}", steward));
+            }
+            output.push(format!("Term : {} epochs", election.term));
+        }
+        ProposalType::EmergencyPause(pause) => {
+            output.push("Proposal type : Emergency Pause".to_string());
+            for module in &pause.modules {
+                output.push(format!("Pause : {}", module));
+            }
+            output.push(format!("Duration : {} epochs", pause.epochs));
+        }
+        ProposalType::ExpeditedWithWasm(hash) => {
+            output.push("Proposal type : Expedited".to_string());
+            let extra = tx
+                .get_section(hash)
+                .and_then(|x| Section::extra_data_sec(x.as_ref()))
+                .expect("unable to load vp code")
+                .code
+                .hash();
+            output
+                .push(format!("Proposal hash : {}", HEXLOWER.encode(&extra.0)));
+        }
     }
 }
 
@@ -1181,11 +1356,10 @@ pub async fn to_ledger_vector(
                 .push(format!("Discord handle : {}", discord_handle));
         }
     } else if code_sec.tag == Some(TX_INIT_PROPOSAL.to_string()) {
-        let init_proposal_data = InitProposalData::try_from_slice(
-            &tx.data()
-                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
-        )
-        .map_err(|err| {
+        let init_proposal_data = tx
+            .decode_data::<InitProposalData>()
+            .ok_or_else(|| Error::Other("Invalid Data".to_string()))?
+            .map_err(|err| {
             Error::from(EncodingError::Conversion(err.to_string()))
         })?;
 
@@ -1349,11 +1523,10 @@ pub async fn to_ledger_vector(
             )]);
         }
     } else if code_sec.tag == Some(TX_TRANSFER_WASM.to_string()) {
-        let transfer = Transfer::try_from_slice(
-            &tx.data()
-                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
-        )
-        .map_err(|err| {
+        let transfer = tx
+            .decode_data::<Transfer>()
+            .ok_or_else(|| Error::Other("Invalid Data".to_string()))?
+            .map_err(|err| {
             Error::from(EncodingError::Conversion(err.to_string()))
         })?;
         // To facilitate lookups of MASP AssetTypes
@@ -1465,11 +1638,10 @@ pub async fn to_ledger_vector(
             }
         }
     } else if code_sec.tag == Some(TX_BOND_WASM.to_string()) {
-        let bond = pos::Bond::try_from_slice(
-            &tx.data()
-                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
-        )
-        .map_err(|err| {
+        let bond = tx
+            .decode_data::<pos::Bond>()
+            .ok_or_else(|| Error::Other("Invalid Data".to_string()))?
+            .map_err(|err| {
             Error::from(EncodingError::Conversion(err.to_string()))
         })?;
 
@@ -1498,11 +1670,10 @@ pub async fn to_ledger_vector(
             ),
         ]);
     } else if code_sec.tag == Some(TX_UNBOND_WASM.to_string()) {
-        let unbond = pos::Unbond::try_from_slice(
-            &tx.data()
-                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
-        )
-        .map_err(|err| {
+        let unbond = tx
+            .decode_data::<pos::Unbond>()
+            .ok_or_else(|| Error::Other("Invalid Data".to_string()))?
+            .map_err(|err| {
             Error::from(EncodingError::Conversion(err.to_string()))
         })?;
 
@@ -1531,11 +1702,10 @@ pub async fn to_ledger_vector(
             ),
         ]);
     } else if code_sec.tag == Some(TX_WITHDRAW_WASM.to_string()) {
-        let withdraw = pos::Withdraw::try_from_slice(
-            &tx.data()
-                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
-        )
-        .map_err(|err| {
+        let withdraw = tx
+            .decode_data::<pos::Withdraw>()
+            .ok_or_else(|| Error::Other("Invalid Data".to_string()))?
+            .map_err(|err| {
             Error::from(EncodingError::Conversion(err.to_string()))
         })?;
 
@@ -19,7 +19,12 @@ use masp_primitives::transaction::components::transparent::fees::{
 use masp_primitives::transaction::components::I128Sum;
 use masp_primitives::transaction::{builder, Transaction as MaspTransaction};
 use masp_primitives::zip32::ExtendedFullViewingKey;
-use namada_account::{InitAccount, UpdateAccount};
+use namada_account::faucet::{
+    FaucetLimits, FaucetWithdraw, UpdateFaucetLimits,
+};
+use namada_account::name_registry::{is_valid_name, UpdateName};
+use namada_account::transfer_policy::{TransferPolicy, UpdateTransferPolicy};
+use namada_account::{InitAccount, UpdateAccount, UpdateAccountSigners};
 use namada_core::address::{Address, InternalAddress, MASP};
 use namada_core::arith::checked;
 use namada_core::collections::HashSet;
@@ -44,7 +49,9 @@ use namada_core::storage::Epoch;
 use namada_core::time::DateTimeUtc;
 use namada_core::{storage, token};
 use namada_governance::cli::onchain::{
-    DefaultProposal, OnChainProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, EmergencyPauseProposal, ExpeditedProposal,
+    OnChainProposal, PgfFundingProposal, PgfStewardElectionProposal,
+    PgfStewardProposal,
 };
 use namada_governance::pgf::cli::steward::Commission;
 use namada_governance::storage::proposal::{
@@ -52,18 +59,26 @@ use namada_governance::storage::proposal::{
 };
 use namada_governance::storage::vote::ProposalVote;
 use namada_ibc::storage::{channel_key, ibc_token};
+use namada_proof_of_stake::operational_metadata::{
+    OperationalMetadata, OperationalMetadataUpdate,
+    MAX_OPERATIONAL_METADATA_FIELD_LEN,
+};
 use namada_proof_of_stake::parameters::{
     PosParams, MAX_VALIDATOR_METADATA_LEN,
 };
-use namada_proof_of_stake::types::{CommissionPair, ValidatorState};
+use namada_proof_of_stake::types::{
+    CommissionPair, ValidatorMetaData, ValidatorMetaDataField, ValidatorState,
+};
 use namada_token::storage_key::balance_key;
 use namada_token::DenominatedAmount;
 use namada_tx::data::pgf::UpdateStewardCommission;
 use namada_tx::data::pos::{BecomeValidator, ConsensusKeyChange};
-use namada_tx::data::{pos, ResultCode, TxResult};
+use namada_tx::data::protocol::{ProtocolTx, ProtocolTxType};
+use namada_tx::data::{pos, ResultCode, TxResult, TxType};
 pub use namada_tx::{Authorization, *};
 use num_traits::Zero;
 use rand_core::{OsRng, RngCore};
+use serde::Serialize;
 
 use crate::args::{self, InputAmount};
 use crate::control_flow::time;
@@ -96,10 +111,24 @@ pub const TX_REACTIVATE_VALIDATOR_WASM: &str = "tx_reactivate_validator.wasm";
 pub const TX_INIT_PROPOSAL: &str = "tx_init_proposal.wasm";
 /// Vote transaction WASM path
 pub const TX_VOTE_PROPOSAL: &str = "tx_vote_proposal.wasm";
+/// Delegate governance voting power transaction WASM path
+pub const TX_DELEGATE_VOTING_POWER_WASM: &str = "tx_delegate_voting_power.wasm";
 /// Reveal public key transaction WASM path
 pub const TX_REVEAL_PK: &str = "tx_reveal_pk.wasm";
 /// Update validity predicate WASM path
 pub const TX_UPDATE_ACCOUNT_WASM: &str = "tx_update_account.wasm";
+/// Update account signers WASM path
+pub const TX_UPDATE_ACCOUNT_SIGNERS_WASM: &str =
+    "tx_update_account_signers.wasm";
+/// Update name WASM path
+pub const TX_UPDATE_NAME_WASM: &str = "tx_update_name.wasm";
+/// Faucet withdrawal WASM path
+pub const TX_FAUCET_WITHDRAW_WASM: &str = "tx_faucet_withdraw.wasm";
+/// Update faucet limits WASM path
+pub const TX_UPDATE_FAUCET_LIMITS_WASM: &str = "tx_update_faucet_limits.wasm";
+/// Update transfer policy WASM path
+pub const TX_UPDATE_TRANSFER_POLICY_WASM: &str =
+    "tx_update_transfer_policy.wasm";
 /// Transfer transaction WASM path
 pub const TX_TRANSFER_WASM: &str = "tx_transfer.wasm";
 /// IBC transaction WASM path
@@ -130,6 +159,20 @@ pub const TX_UPDATE_STEWARD_COMMISSION: &str =
     "tx_update_steward_commission.wasm";
 /// Redelegate transaction WASM path
 pub const TX_REDELEGATE_WASM: &str = "tx_redelegate.wasm";
+/// Update delegation pool WASM path
+pub const TX_UPDATE_DELEGATION_POOL_WASM: &str =
+    "tx_update_delegation_pool.wasm";
+/// Bond to delegation pool WASM path
+pub const TX_BOND_TO_DELEGATION_POOL_WASM: &str =
+    "tx_bond_to_delegation_pool.wasm";
+/// Leave delegation pool WASM path
+pub const TX_LEAVE_DELEGATION_POOL_WASM: &str = "tx_leave_delegation_pool.wasm";
+/// Claim delegation pool rewards WASM path
+pub const TX_CLAIM_DELEGATION_POOL_REWARDS_WASM: &str =
+    "tx_claim_delegation_pool_rewards.wasm";
+/// Update automatic redelegation WASM path
+pub const TX_UPDATE_AUTO_REDELEGATION_WASM: &str =
+    "tx_update_auto_redelegation.wasm";
 
 /// Default timeout in seconds for requests to the `/accepted`
 /// and `/applied` ABCI query endpoints.
@@ -325,6 +368,13 @@ pub async fn broadcast_tx(
         }
     }?;
 
+    // Reject the tx before broadcasting it if the mempool would reject it for
+    // its size
+    let tx_bytes = tx.to_bytes();
+    let max_tx_bytes = rpc::query_max_tx_bytes(context.client()).await?;
+    namada_parameters::check_tx_bytes(tx_bytes.len(), max_tx_bytes)
+        .map_err(TxSubmitError::TxTooLarge)?;
+
     tracing::debug!(
         transaction = ?to_broadcast,
         "Broadcasting transaction",
@@ -333,9 +383,8 @@ pub async fn broadcast_tx(
     // TODO: configure an explicit timeout value? we need to hack away at
     // `tendermint-rs` for this, which is currently using a hard-coded 30s
     // timeout.
-    let response = lift_rpc_error(
-        context.client().broadcast_tx_sync(tx.to_bytes()).await,
-    )?;
+    let response =
+        lift_rpc_error(context.client().broadcast_tx_sync(tx_bytes).await)?;
 
     if response.code == 0.into() {
         display_line!(context.io(), "Transaction added to mempool.");
@@ -681,6 +730,13 @@ pub async fn build_validator_commission_change(
                         ));
                     }
                 }
+                check_commission_rate_change_err(
+                    context,
+                    &validator,
+                    *rate,
+                    tx_args.force,
+                )
+                .await?;
             }
             (None, None) => {
                 edisplay_line!(
@@ -850,6 +906,35 @@ pub async fn build_validator_metadata_change(
         }
     }
 
+    // The metadata fields required by the PoS parameters cannot be removed
+    let removed_required: Vec<_> = [
+        (ValidatorMetaDataField::Description, description),
+        (ValidatorMetaDataField::Website, website),
+        (ValidatorMetaDataField::DiscordHandle, discord_handle),
+        (ValidatorMetaDataField::Avatar, avatar),
+        (ValidatorMetaDataField::Name, name),
+    ]
+    .into_iter()
+    .filter(|(field, value)| {
+        params.required_validator_metadata.contains(field)
+            && value.as_ref().is_some_and(String::is_empty)
+    })
+    .map(|(field, _value)| field)
+    .collect();
+    if !removed_required.is_empty() {
+        edisplay_line!(
+            context.io(),
+            "Cannot remove the validator metadata fields required by the PoS \
+             parameters: {}.",
+            itertools::join(removed_required, ", ")
+        );
+        if !tx_args.force {
+            return Err(Error::Other(
+                "Cannot remove required validator metadata".to_string(),
+            ));
+        }
+    }
+
     // If there's a new commission rate, it must be valid
     if let Some(rate) = commission_rate.as_ref() {
         if *rate < Dec::zero() || *rate > Dec::one() {
@@ -907,6 +992,13 @@ pub async fn build_validator_metadata_change(
                         ));
                     }
                 }
+                check_commission_rate_change_err(
+                    context,
+                    &validator,
+                    *rate,
+                    tx_args.force,
+                )
+                .await?;
             }
             (None, None) => {
                 edisplay_line!(
@@ -1080,6 +1172,88 @@ pub async fn build_resign_steward(
     .map(|tx| (tx, signing_data))
 }
 
+/// Build a protocol tx publishing a validator's operational metadata, signed
+/// with the validator's protocol key
+pub async fn build_operational_metadata_change(
+    context: &impl Namada,
+    args::OperationalMetadataChange {
+        tx: tx_args,
+        validator,
+        protocol_key,
+        rpc_endpoint,
+        seed_node,
+        emergency_contact,
+    }: &args::OperationalMetadataChange,
+) -> Result<Tx> {
+    // The validator must actually be a validator
+    let validator =
+        known_validator_or_err(validator.clone(), tx_args.force, context)
+            .await?;
+
+    let metadata = OperationalMetadata {
+        rpc_endpoint: rpc_endpoint.clone(),
+        seed_node: seed_node.clone(),
+        emergency_contact: emergency_contact.clone(),
+    };
+    let fields = [
+        &metadata.rpc_endpoint,
+        &metadata.seed_node,
+        &metadata.emergency_contact,
+    ];
+    if fields.into_iter().flatten().any(|field| {
+        field.is_empty() || field.len() > MAX_OPERATIONAL_METADATA_FIELD_LEN
+    }) {
+        edisplay_line!(
+            context.io(),
+            "The operational metadata fields must not be empty or longer than \
+             {MAX_OPERATIONAL_METADATA_FIELD_LEN} characters"
+        );
+        return Err(Error::from(TxSubmitError::MetadataTooLong));
+    }
+
+    let protocol_key = match protocol_key {
+        Some(protocol_key) => protocol_key.clone(),
+        None => rpc::query_validator_protocol_key(context.client(), &validator)
+            .await?
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "The protocol key of the validator {validator} was not \
+                     found"
+                ))
+            })?,
+    };
+    let protocol_sk = signing::find_key_by_pk(
+        &mut *context.wallet_mut().await,
+        tx_args,
+        &protocol_key,
+    )?;
+
+    let epoch = rpc::query_epoch(context.client()).await?;
+    let nonce =
+        rpc::query_operational_metadata_nonce(context.client(), &validator)
+            .await?;
+    let update = OperationalMetadataUpdate {
+        validator,
+        epoch,
+        nonce,
+        metadata,
+    };
+
+    let mut tx = Tx::from_type(TxType::Protocol(Box::new(ProtocolTx {
+        pk: protocol_key,
+        tx: ProtocolTxType::ValidatorOperationalMetadata,
+    })));
+    tx.header.chain_id = tx_args.chain_id.clone().unwrap();
+    tx.header.expiration = tx_args.expiration.to_datetime();
+    tx.set_data(Data::new(update.serialize_to_vec()));
+    tx.add_section(Section::Authorization(Authorization::new(
+        tx.sechashes(),
+        [(0, protocol_sk)].into_iter().collect(),
+        None,
+    )));
+    Ok(tx)
+}
+
 /// Submit transaction to unjail a jailed validator
 pub async fn build_unjail_validator(
     context: &impl Namada,
@@ -1610,7 +1784,7 @@ pub async fn build_withdraw(
 
     let data = pos::Withdraw { validator, source };
 
-    build(
+    build_encoded(
         context,
         tx_args,
         tx_code_path.clone(),
@@ -1807,7 +1981,7 @@ pub async fn build_unbond(
         source: source.clone(),
     };
 
-    let tx = build(
+    let tx = build_encoded(
         context,
         tx_args,
         tx_code_path.clone(),
@@ -2010,7 +2184,7 @@ pub async fn build_bond(
     check_balance_too_low_err(
         &native_token,
         bond_source,
-        *amount,
+        token::DenominatedAmount::native(*amount),
         check_balance,
         tx_args.force,
         context,
@@ -2023,7 +2197,7 @@ pub async fn build_bond(
         source,
     };
 
-    build(
+    build_encoded(
         context,
         tx_args,
         tx_code_path.clone(),
@@ -2045,6 +2219,9 @@ pub async fn build_default_proposal(
         proposal_data: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_pgf_steward_election: _,
+        is_emergency_pause: _,
+        is_expedited: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: DefaultProposal,
@@ -2079,7 +2256,63 @@ pub async fn build_default_proposal(
             Ok(())
         };
     // TODO: need to pay the fee to submit a proposal, check enough balance
-    build(
+    build_encoded(
+        context,
+        tx,
+        tx_code_path.clone(),
+        init_proposal_data,
+        push_data,
+        unshield,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Build an expedited proposal governance
+pub async fn build_expedited_proposal(
+    context: &impl Namada,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_pgf_steward_election: _,
+        is_emergency_pause: _,
+        is_expedited: _,
+        tx_code_path,
+    }: &args::InitProposal,
+    proposal: ExpeditedProposal,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(proposal.proposal.author.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx,
+        Some(proposal.proposal.author.clone()),
+        default_signer,
+    )
+    .await?;
+    let (fee_amount, _updated_balance, unshield) =
+        validate_fee_and_gen_unshield(context, tx, &signing_data.fee_payer)
+            .await?;
+
+    let init_proposal_data = InitProposalData::try_from(proposal.clone())
+        .map_err(|e| TxSubmitError::InvalidProposal(e.to_string()))?;
+
+    let push_data =
+        |tx_builder: &mut Tx, init_proposal_data: &mut InitProposalData| {
+            let (_, extra_section_hash) = tx_builder
+                .add_extra_section(proposal_to_vec(proposal.proposal)?, None);
+            init_proposal_data.content = extra_section_hash;
+
+            let (_, extra_section_hash) =
+                tx_builder.add_extra_section(proposal.data, None);
+            init_proposal_data.r#type =
+                ProposalType::ExpeditedWithWasm(extra_section_hash);
+            Ok(())
+        };
+    build_encoded(
         context,
         tx,
         tx_code_path.clone(),
@@ -2355,6 +2588,46 @@ pub async fn build_become_validator(
         }
     }
 
+    // Validate the commission rate and the metadata against the requirements
+    // of the PoS parameters
+    let params: PosParams = rpc::get_pos_params(context.client()).await?;
+    if *commission_rate < params.min_commission_rate {
+        edisplay_line!(
+            context.io(),
+            "The validator commission rate must be at least the minimum \
+             commission rate {}.",
+            params.min_commission_rate
+        );
+        if !tx_args.force {
+            return Err(Error::Other(
+                "Validator commission rate is too low".to_string(),
+            ));
+        }
+    }
+    let metadata = ValidatorMetaData {
+        email: email.clone(),
+        description: description.clone(),
+        website: website.clone(),
+        discord_handle: discord_handle.clone(),
+        avatar: avatar.clone(),
+        name: name.clone(),
+    };
+    let missing_metadata =
+        metadata.missing_fields(&params.required_validator_metadata);
+    if !missing_metadata.is_empty() {
+        edisplay_line!(
+            context.io(),
+            "The validator metadata is missing the fields required by the PoS \
+             parameters: {}.",
+            itertools::join(missing_metadata, ", ")
+        );
+        if !tx_args.force {
+            return Err(Error::Other(
+                "Validator metadata is missing required fields".to_string(),
+            ));
+        }
+    }
+
     // check that all keys have been supplied correctly
     if [
         consensus_key.clone(),
@@ -2446,6 +2719,9 @@ pub async fn build_pgf_funding_proposal(
         proposal_data: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_pgf_steward_election: _,
+        is_emergency_pause: _,
+        is_expedited: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: PgfFundingProposal,
@@ -2472,7 +2748,7 @@ pub async fn build_pgf_funding_proposal(
         data.content = extra_section_hash;
         Ok(())
     };
-    build(
+    build_encoded(
         context,
         tx,
         tx_code_path.clone(),
@@ -2494,6 +2770,9 @@ pub async fn build_pgf_stewards_proposal(
         proposal_data: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_pgf_steward_election: _,
+        is_emergency_pause: _,
+        is_expedited: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: PgfStewardProposal,
@@ -2521,7 +2800,109 @@ pub async fn build_pgf_stewards_proposal(
         Ok(())
     };
 
-    build(
+    build_encoded(
+        context,
+        tx,
+        tx_code_path.clone(),
+        init_proposal_data,
+        add_section,
+        unshield,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Build a pgf steward election proposal governance
+pub async fn build_pgf_steward_election_proposal(
+    context: &impl Namada,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_pgf_steward_election: _,
+        is_emergency_pause: _,
+        is_expedited: _,
+        tx_code_path,
+    }: &args::InitProposal,
+    proposal: PgfStewardElectionProposal,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(proposal.proposal.author.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx,
+        Some(proposal.proposal.author.clone()),
+        default_signer,
+    )
+    .await?;
+    let (fee_amount, _updated_balance, unshield) =
+        validate_fee_and_gen_unshield(context, tx, &signing_data.fee_payer)
+            .await?;
+
+    let init_proposal_data = InitProposalData::try_from(proposal.clone())
+        .map_err(|e| TxSubmitError::InvalidProposal(e.to_string()))?;
+
+    let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
+        let (_, extra_section_hash) =
+            tx.add_extra_section(proposal_to_vec(proposal.proposal)?, None);
+        data.content = extra_section_hash;
+        Ok(())
+    };
+
+    build_encoded(
+        context,
+        tx,
+        tx_code_path.clone(),
+        init_proposal_data,
+        add_section,
+        unshield,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Build an emergency pause proposal governance
+pub async fn build_emergency_pause_proposal(
+    context: &impl Namada,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_pgf_steward_election: _,
+        is_emergency_pause: _,
+        is_expedited: _,
+        tx_code_path,
+    }: &args::InitProposal,
+    proposal: EmergencyPauseProposal,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(proposal.proposal.author.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx,
+        Some(proposal.proposal.author.clone()),
+        default_signer,
+    )
+    .await?;
+    let (fee_amount, _updated_balance, unshield) =
+        validate_fee_and_gen_unshield(context, tx, &signing_data.fee_payer)
+            .await?;
+
+    let init_proposal_data = InitProposalData::try_from(proposal.clone())
+        .map_err(|e| TxSubmitError::InvalidProposal(e.to_string()))?;
+
+    let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
+        let (_, extra_section_hash) =
+            tx.add_extra_section(proposal_to_vec(proposal.proposal)?, None);
+        data.content = extra_section_hash;
+        Ok(())
+    };
+
+    build_encoded(
         context,
         tx,
         tx_code_path.clone(),
@@ -2587,7 +2968,7 @@ pub async fn build_ibc_transfer(
     check_balance_too_low_err(
         &args.token,
         &source,
-        validated_amount.amount(),
+        validated_amount,
         check_balance,
         args.tx.force,
         context,
@@ -2778,27 +3159,81 @@ pub async fn build<F, D>(
 where
     F: FnOnce(&mut Tx, &mut D) -> Result<()>,
     D: BorshSerialize,
+{
+    if tx_args.data_encoding != DataEncoding::Borsh {
+        return Err(Error::Other(format!(
+            "The {} data encoding is not supported by the transaction {}",
+            tx_args.data_encoding,
+            path.to_string_lossy(),
+        )));
+    }
+    build_pow_flag(
+        context,
+        tx_args,
+        path,
+        data,
+        on_tx,
+        |tx, data| {
+            tx.add_data(data);
+            Ok(())
+        },
+        unshield,
+        fee_amount,
+        gas_payer,
+    )
+    .await
+}
+
+/// Abstraction for helping build transactions whose data can be encoded in
+/// the data encoding requested in the arguments
+#[allow(clippy::too_many_arguments)]
+pub async fn build_encoded<F, D>(
+    context: &impl Namada,
+    tx_args: &crate::args::Tx,
+    path: PathBuf,
+    data: D,
+    on_tx: F,
+    unshield: Option<masp_primitives::transaction::Transaction>,
+    fee_amount: DenominatedAmount,
+    gas_payer: &common::PublicKey,
+) -> Result<Tx>
+where
+    F: FnOnce(&mut Tx, &mut D) -> Result<()>,
+    D: BorshSerialize + Serialize,
 {
     build_pow_flag(
-        context, tx_args, path, data, on_tx, unshield, fee_amount, gas_payer,
+        context,
+        tx_args,
+        path,
+        data,
+        on_tx,
+        |tx, data| {
+            tx.add_encoded_data(data, tx_args.data_encoding)
+                .map_err(|err| EncodingError::Encode(err.to_string()))?;
+            Ok(())
+        },
+        unshield,
+        fee_amount,
+        gas_payer,
     )
     .await
 }
 
 #[allow(clippy::too_many_arguments)]
-async fn build_pow_flag<F, D>(
+async fn build_pow_flag<F, D, A>(
     context: &impl Namada,
     tx_args: &crate::args::Tx,
     path: PathBuf,
     mut data: D,
     on_tx: F,
+    add_data: A,
     unshield: Option<masp_primitives::transaction::Transaction>,
     fee_amount: DenominatedAmount,
     gas_payer: &common::PublicKey,
 ) -> Result<Tx>
 where
     F: FnOnce(&mut Tx, &mut D) -> Result<()>,
-    D: BorshSerialize,
+    A: FnOnce(&mut Tx, D) -> Result<()>,
 {
     let chain_id = tx_args.chain_id.clone().unwrap();
 
@@ -2813,12 +3248,11 @@ where
 
     on_tx(&mut tx_builder, &mut data)?;
 
-    tx_builder
-        .add_code_from_hash(
-            tx_code_hash,
-            Some(path.to_string_lossy().into_owned()),
-        )
-        .add_data(data);
+    tx_builder.add_code_from_hash(
+        tx_code_hash,
+        Some(path.to_string_lossy().into_owned()),
+    );
+    add_data(&mut tx_builder, data)?;
 
     prepare_tx(
         tx_args,
@@ -2934,7 +3368,7 @@ pub async fn build_transfer<N: Namada>(
     check_balance_too_low_err(
         &args.token,
         &source,
-        validated_amount.amount(),
+        validated_amount,
         check_balance,
         args.tx.force,
         context,
@@ -3005,7 +3439,7 @@ pub async fn build_transfer<N: Namada>(
         };
         Ok(())
     };
-    let tx = build_pow_flag(
+    let tx = build_encoded(
         context,
         &args.tx,
         args.tx_code_path.clone(),
@@ -3019,6 +3453,123 @@ pub async fn build_transfer<N: Namada>(
     Ok((tx, signing_data, shielded_tx_epoch))
 }
 
+/// Build a shielded transfer of several assets in a single MASP transaction
+pub async fn build_shielded_multi_asset_transfer<N: Namada>(
+    context: &N,
+    args: &mut args::TxShieldedMultiAssetTransfer,
+) -> Result<(Tx, SigningTxData, Option<Epoch>)> {
+    let source = args.source.effective_address();
+    let target = args.target.effective_address();
+    if source != MASP || target != MASP {
+        return Err(Error::Other(
+            "A multi-asset transfer must be from a spending key to a payment \
+             address"
+                .to_string(),
+        ));
+    }
+    if args.assets.is_empty() {
+        return Err(Error::Other(
+            "A multi-asset transfer must transfer at least one asset"
+                .to_string(),
+        ));
+    }
+    let signing_data = signing::aux_signing_data(
+        context,
+        &args.tx,
+        Some(source.clone()),
+        Some(source.clone()),
+    )
+    .await?;
+
+    let (fee_amount, _updated_balance, unshield) =
+        validate_fee_and_gen_unshield(
+            context,
+            &args.tx,
+            &signing_data.fee_payer,
+        )
+        .await?;
+
+    // validate the amounts given
+    let mut assets = Vec::with_capacity(args.assets.len());
+    for (token, amount) in args.assets.iter_mut() {
+        let validated_amount =
+            validate_amount(context, *amount, token, args.tx.force).await?;
+        *amount = InputAmount::Validated(validated_amount);
+        assets.push((token.clone(), validated_amount));
+    }
+
+    // Precompute asset types to increase chances of success in decoding
+    let token_map = context.wallet().await.get_addresses();
+    let tokens = token_map.values().collect();
+    let _ = context
+        .shielded_mut()
+        .await
+        .precompute_asset_types(context.client(), tokens)
+        .await;
+    let shielded_transfer =
+        ShieldedContext::<N::ShieldedUtils>::gen_shielded_multi_asset_transfer(
+            context,
+            &args.source,
+            &args.target,
+            &assets,
+            !(args.tx.dry_run || args.tx.dry_run_wrapper),
+        )
+        .await
+        .map_err(|err| TxSubmitError::MaspError(err.to_string()))?;
+    let shielded_tx_epoch = shielded_transfer.epoch;
+    // Get the decoded asset types used in the transaction to give offline
+    // wallet users more information
+    let asset_types = used_asset_types(context, &shielded_transfer.builder)
+        .await
+        .unwrap_or_default();
+
+    // The amount and token of a transfer between shielded addresses are
+    // redacted, as in `build_transfer`
+    let transfer = token::Transfer {
+        source,
+        target,
+        token: context.native_token(),
+        amount: token::Amount::zero().into(),
+        // Link the Transfer to the MASP Transaction by hash code
+        shielded: None,
+    };
+
+    let add_shielded = |tx: &mut Tx, transfer: &mut token::Transfer| {
+        let ShieldedTransfer {
+            builder,
+            masp_tx,
+            metadata,
+            epoch: _,
+        } = shielded_transfer;
+        // Add a MASP Transaction section to the Tx and get the tx hash
+        let masp_tx_hash = tx.add_masp_tx_section(masp_tx).1;
+        transfer.shielded = Some(masp_tx_hash);
+
+        tx.add_masp_builder(MaspBuilder {
+            asset_types,
+            // Store how the Info objects map to Descriptors/Outputs
+            metadata,
+            // Store the data that was used to construct the Transaction
+            builder,
+            // Link the Builder to the Transaction by hash code
+            target: masp_tx_hash,
+        });
+        Ok(())
+    };
+    let tx = build_encoded(
+        context,
+        &args.tx,
+        args.tx_code_path.clone(),
+        transfer,
+        add_shielded,
+        unshield,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await?;
+    Ok((tx, signing_data, Some(shielded_tx_epoch)))
+}
+
 // Construct the shielded part of the transaction, if any
 async fn construct_shielded_parts<N: Namada>(
     context: &N,
@@ -3048,7 +3599,7 @@ async fn construct_shielded_parts<N: Namada>(
         Err(Build(builder::Error::InsufficientFunds(_))) => {
             return Err(TxSubmitError::NegativeBalanceAfterTransfer(
                 Box::new(source.effective_address()),
-                amount.amount().to_string_native(),
+                amount.to_string(),
                 Box::new(token.clone()),
             )
             .into());
@@ -3278,6 +3829,428 @@ pub async fn build_update_account(
     .map(|tx| (tx, signing_data))
 }
 
+/// Build a transaction to add or remove public keys of an account and change
+/// its threshold. The transaction must be signed with the account's current
+/// keys, so it can be dumped and co-signed offline by the other signers.
+pub async fn build_update_account_signers(
+    context: &impl Namada,
+    args::TxUpdateAccountSigners {
+        tx: tx_args,
+        tx_code_path,
+        addr,
+        add_public_keys,
+        remove_public_keys,
+        threshold,
+    }: &args::TxUpdateAccountSigners,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(addr.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(addr.clone()),
+        default_signer,
+    )
+    .await?;
+    let (fee_amount, _, unshield) = validate_fee_and_gen_unshield(
+        context,
+        tx_args,
+        &signing_data.fee_payer,
+    )
+    .await?;
+
+    let Some(account) = rpc::get_account_info(context.client(), addr).await?
+    else {
+        return Err(Error::from(TxSubmitError::LocationDoesNotExist(
+            addr.clone(),
+        )));
+    };
+
+    let mut public_keys = account.get_all_public_keys();
+    for public_key in remove_public_keys {
+        let Some(index) = public_keys.iter().position(|pk| pk == public_key)
+        else {
+            edisplay_line!(
+                context.io(),
+                "The public key {public_key} doesn't belong to the account \
+                 {addr}."
+            );
+            if !tx_args.force {
+                return Err(Error::from(TxSubmitError::Other(format!(
+                    "The public key {public_key} doesn't belong to the \
+                     account {addr}"
+                ))));
+            }
+            continue;
+        };
+        public_keys.remove(index);
+    }
+    for public_key in add_public_keys {
+        if public_keys.contains(public_key) {
+            edisplay_line!(
+                context.io(),
+                "The public key {public_key} already belongs to the account \
+                 {addr}."
+            );
+            if !tx_args.force {
+                return Err(Error::from(TxSubmitError::Other(format!(
+                    "The public key {public_key} already belongs to the \
+                     account {addr}"
+                ))));
+            }
+        }
+        public_keys.push(public_key.clone());
+    }
+    let new_threshold = threshold.unwrap_or(account.threshold);
+    let is_valid_threshold = u8::try_from(public_keys.len()).is_ok_and(|num| {
+        namada_account::is_valid_threshold(num, new_threshold)
+    });
+    if !is_valid_threshold {
+        edisplay_line!(
+            context.io(),
+            "Invalid account threshold: the threshold {new_threshold} must be \
+             at least 1 and at most the number of the account's public keys \
+             {}.",
+            public_keys.len()
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidAccountThreshold));
+        }
+    }
+
+    let data = UpdateAccountSigners {
+        addr: account.address,
+        add_public_keys: add_public_keys.clone(),
+        remove_public_keys: remove_public_keys.clone(),
+        threshold: *threshold,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        unshield,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Build a transaction to register, update or delete a name in the name
+/// registry. The transaction is signed by the new owner of the name. When the
+/// owner changes, the current owner must co-sign it, e.g. offline with a
+/// dumped transaction.
+pub async fn build_update_name(
+    context: &impl Namada,
+    args::TxUpdateName {
+        tx: tx_args,
+        tx_code_path,
+        name,
+        owner,
+        target,
+    }: &args::TxUpdateName,
+) -> Result<(Tx, SigningTxData)> {
+    if !is_valid_name(name) {
+        edisplay_line!(
+            context.io(),
+            "Invalid name {name}. A name must be made of labels of lowercase \
+             letters, digits and hyphens separated by dots."
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::Other(format!(
+                "Invalid name {name}"
+            ))));
+        }
+    }
+    let record = rpc::query_name_record(context.client(), name).await?;
+    if record.is_none() && target.is_none() {
+        edisplay_line!(context.io(), "The name {name} is not registered.");
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::Other(format!(
+                "The name {name} is not registered"
+            ))));
+        }
+    }
+
+    if let Some(record) = record.filter(|record| &record.owner != owner) {
+        display_line!(
+            context.io(),
+            "The name {name} is owned by {}, who must also sign the \
+             transaction.",
+            record.owner
+        );
+    }
+
+    let default_signer = Some(owner.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(owner.clone()),
+        default_signer,
+    )
+    .await?;
+    let (fee_amount, _, unshield) = validate_fee_and_gen_unshield(
+        context,
+        tx_args,
+        &signing_data.fee_payer,
+    )
+    .await?;
+
+    let data = UpdateName {
+        name: name.clone(),
+        owner: owner.clone(),
+        target: target.clone(),
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        unshield,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Build a transaction to withdraw tokens from a faucet. The faucet doesn't
+/// sign the transaction, so it is signed by the target by default, which pays
+/// the fees.
+pub async fn build_faucet_withdraw(
+    context: &impl Namada,
+    args::TxFaucetWithdraw {
+        tx: tx_args,
+        tx_code_path,
+        faucet,
+        target,
+        token,
+        amount,
+    }: &args::TxFaucetWithdraw,
+) -> Result<(Tx, SigningTxData)> {
+    let amount = validate_amount(context, *amount, token, tx_args.force)
+        .await?
+        .amount();
+    let limits =
+        rpc::query_faucet_limits(context.client(), faucet, token).await?;
+    match limits {
+        None => {
+            edisplay_line!(
+                context.io(),
+                "The faucet {faucet} has no withdrawal limits for the token \
+                 {token}."
+            );
+            if !tx_args.force {
+                return Err(Error::from(TxSubmitError::Other(format!(
+                    "The token {token} cannot be withdrawn from the faucet \
+                     {faucet}"
+                ))));
+            }
+        }
+        Some(limits) if amount > limits.per_address => {
+            edisplay_line!(
+                context.io(),
+                "The amount {} exceeds the faucet's limit of {} per address \
+                 per epoch.",
+                context.format_amount(token, amount).await,
+                context.format_amount(token, limits.per_address).await
+            );
+            if !tx_args.force {
+                return Err(Error::from(TxSubmitError::Other(format!(
+                    "The withdrawal exceeds the limit of the faucet {faucet}"
+                ))));
+            }
+        }
+        Some(_) => {}
+    }
+
+    let default_signer = Some(target.clone());
+    let signing_data =
+        signing::aux_signing_data(context, tx_args, None, default_signer)
+            .await?;
+    let (fee_amount, _, unshield) = validate_fee_and_gen_unshield(
+        context,
+        tx_args,
+        &signing_data.fee_payer,
+    )
+    .await?;
+
+    let data = FaucetWithdraw {
+        faucet: faucet.clone(),
+        target: target.clone(),
+        token: token.clone(),
+        amount,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        unshield,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Build a transaction to set the withdrawal limits of a token of a faucet,
+/// signed by the faucet
+pub async fn build_update_faucet_limits(
+    context: &impl Namada,
+    args::TxUpdateFaucetLimits {
+        tx: tx_args,
+        tx_code_path,
+        faucet,
+        token,
+        per_address,
+        per_epoch,
+    }: &args::TxUpdateFaucetLimits,
+) -> Result<(Tx, SigningTxData)> {
+    let per_address =
+        validate_amount(context, *per_address, token, tx_args.force)
+            .await?
+            .amount();
+    let per_epoch = validate_amount(context, *per_epoch, token, tx_args.force)
+        .await?
+        .amount();
+    if per_address > per_epoch {
+        edisplay_line!(
+            context.io(),
+            "The limit per address exceeds the limit per epoch, which applies \
+             first."
+        );
+    }
+
+    let default_signer = Some(faucet.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(faucet.clone()),
+        default_signer,
+    )
+    .await?;
+    let (fee_amount, _, unshield) = validate_fee_and_gen_unshield(
+        context,
+        tx_args,
+        &signing_data.fee_payer,
+    )
+    .await?;
+
+    let data = UpdateFaucetLimits {
+        faucet: faucet.clone(),
+        token: token.clone(),
+        limits: FaucetLimits {
+            per_address,
+            per_epoch,
+        },
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        unshield,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Build a transaction to update or remove the transfer policy of an account,
+/// signed by the account. The given maximum amount and counterparties are
+/// applied on top of the current policy.
+pub async fn build_update_transfer_policy(
+    context: &impl Namada,
+    args::TxUpdateTransferPolicy {
+        tx: tx_args,
+        tx_code_path,
+        owner,
+        token,
+        max_amount,
+        counterparties,
+        remove,
+    }: &args::TxUpdateTransferPolicy,
+) -> Result<(Tx, SigningTxData)> {
+    let policy = if *remove {
+        None
+    } else {
+        let mut policy = rpc::query_transfer_policy(context.client(), owner)
+            .await?
+            .unwrap_or_default();
+        match (token, max_amount) {
+            (Some(token), Some(max_amount)) => {
+                let max_amount =
+                    validate_amount(context, *max_amount, token, tx_args.force)
+                        .await?
+                        .amount();
+                policy.max_amounts_per_tx.insert(token.clone(), max_amount);
+            }
+            (None, None) => {}
+            _ => {
+                return Err(Error::Other(
+                    "The maximum amount per tx and its token must be given \
+                     together"
+                        .to_string(),
+                ));
+            }
+        }
+        if !counterparties.is_empty() {
+            policy.allowed_counterparties =
+                counterparties.iter().cloned().collect();
+        }
+        if policy == TransferPolicy::default() {
+            edisplay_line!(
+                context.io(),
+                "The transfer policy of {owner} doesn't restrict any transfer."
+            );
+        }
+        Some(policy)
+    };
+
+    let default_signer = Some(owner.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(owner.clone()),
+        default_signer,
+    )
+    .await?;
+    let (fee_amount, _, unshield) = validate_fee_and_gen_unshield(
+        context,
+        tx_args,
+        &signing_data.fee_payer,
+    )
+    .await?;
+
+    let data = UpdateTransferPolicy {
+        owner: owner.clone(),
+        policy,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        unshield,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Submit a custom transaction
 pub async fn build_custom(
     context: &impl Namada,
@@ -3624,7 +4597,7 @@ enum CheckBalance {
 async fn check_balance_too_low_err<N: Namada>(
     token: &Address,
     source: &Address,
-    amount: token::Amount,
+    amount: token::DenominatedAmount,
     balance: CheckBalance,
     force: bool,
     context: &N,
@@ -3666,9 +4639,11 @@ async fn check_balance_too_low_err<N: Namada>(
         }
     };
 
-    match balance.checked_sub(amount) {
-        Some(_) => Ok(()),
-        None => {
+    // The balance is in the same base unit as the validated amount
+    let balance = token::DenominatedAmount::new(balance, amount.denom());
+    match balance.try_sub(amount) {
+        Ok(_) => Ok(()),
+        Err(token::DenominatedAmountError::Underflow) => {
             if force {
                 edisplay_line!(
                     context.io(),
@@ -3677,19 +4652,45 @@ async fn check_balance_too_low_err<N: Namada>(
                      and the balance is {}.",
                     source,
                     token,
-                    context.format_amount(token, amount).await,
-                    context.format_amount(token, balance).await,
+                    amount,
+                    balance,
                 );
                 Ok(())
             } else {
                 Err(Error::from(TxSubmitError::BalanceTooLow(
                     source.clone(),
                     token.clone(),
-                    amount.to_string_native(),
-                    balance.to_string_native(),
+                    amount.to_string(),
+                    balance.to_string(),
                 )))
             }
         }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Checks a validator's commission rate change against the ledger's own
+/// validation. Force overrides this.
+async fn check_commission_rate_change_err(
+    context: &impl Namada,
+    validator: &Address,
+    rate: Dec,
+    force: bool,
+) -> Result<()> {
+    let Some(reason) =
+        rpc::check_commission_rate_change(context.client(), validator, rate)
+            .await?
+    else {
+        return Ok(());
+    };
+    edisplay_line!(
+        context.io(),
+        "The commission rate change would be rejected: {reason}"
+    );
+    if force {
+        Ok(())
+    } else {
+        Err(Error::from(TxSubmitError::InvalidCommissionRate(rate)))
     }
 }
 
@@ -0,0 +1,361 @@
+//! Human-readable previews of the transactions to be signed, e.g. for the
+//! display of hardware wallets.
+//!
+//! A preview is derived solely from the sections of a transaction, so it is
+//! deterministic. Its format is versioned with [`TX_PREVIEW_VERSION`], which
+//! must be bumped on any change to the produced operations, labels or values
+//! so that wallets can detect previews they do not understand.
+
+use borsh::BorshDeserialize;
+use data_encoding::HEXLOWER;
+use namada_core::key::common;
+use namada_core::token::Transfer;
+use namada_governance::storage::proposal::{
+    InitProposalData, VoteProposalData,
+};
+use namada_tx::data::{pos, TxType};
+use namada_tx::Tx;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{EncodingError, Error};
+use crate::tx::{
+    TX_BOND_WASM, TX_CLAIM_REWARDS_WASM, TX_INIT_PROPOSAL, TX_REVEAL_PK,
+    TX_TRANSFER_WASM, TX_UNBOND_WASM, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
+};
+
+/// The version of the format of the transaction previews
+pub const TX_PREVIEW_VERSION: u8 = 1;
+
+/// A human-readable preview of a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxPreview {
+    /// The version of the preview format
+    pub version: u8,
+    /// The chain which the transaction is being submitted to
+    pub chain_id: String,
+    /// The time at which the transaction expires, in RFC 3339 format
+    pub expiration: Option<String>,
+    /// The hex encoded hash of the transaction code
+    pub code_hash: String,
+    /// The operation performed by the transaction, or `Custom` if the
+    /// transaction code is not recognized
+    pub operation: String,
+    /// The details of the operation, in display order
+    pub fields: Vec<PreviewField>,
+    /// The fee of the transaction, if it is wrapped
+    pub fee: Option<FeePreview>,
+    /// The memo of the transaction, hex encoded if it is not UTF-8
+    pub memo: Option<String>,
+}
+
+/// A labelled detail of a transaction preview
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreviewField {
+    /// The label of the detail
+    pub label: String,
+    /// The value of the detail
+    pub value: String,
+}
+
+/// The fee details of a transaction preview
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeePreview {
+    /// The address of the fee payer
+    pub payer: String,
+    /// The public key of the fee payer, used to sign the wrapper
+    pub public_key: String,
+    /// The address of the fee token
+    pub token: String,
+    /// The fee paid per gas unit
+    pub amount_per_gas_unit: String,
+    /// The maximum amount of gas that can be used
+    pub gas_limit: u64,
+    /// The maximum fee that can be paid
+    pub max_fee: String,
+}
+
+impl PreviewField {
+    fn new(label: &str, value: impl ToString) -> Self {
+        Self {
+            label: label.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+/// Produce the preview of the given transaction
+pub fn tx_preview(tx: &Tx) -> Result<TxPreview, Error> {
+    let code_sec = tx
+        .get_section(tx.code_sechash())
+        .ok_or_else(|| {
+            Error::Other("expected tx code section to be present".to_string())
+        })?
+        .code_sec()
+        .ok_or_else(|| {
+            Error::Other("expected section to have code tag".to_string())
+        })?;
+    let (operation, fields) = match code_sec.tag.as_deref() {
+        Some(TX_TRANSFER_WASM) => {
+            let transfer: Transfer = decode_data(tx)?;
+            let mut fields = vec![
+                PreviewField::new("Source", transfer.source),
+                PreviewField::new("Target", transfer.target),
+                PreviewField::new("Token", transfer.token),
+                PreviewField::new(
+                    "Amount",
+                    transfer.amount.to_string_precise(),
+                ),
+            ];
+            if let Some(shielded) = transfer.shielded {
+                fields.push(PreviewField::new(
+                    "Shielded section",
+                    HEXLOWER.encode(&shielded.0),
+                ));
+            }
+            ("Transfer", fields)
+        }
+        Some(tag @ (TX_BOND_WASM | TX_UNBOND_WASM)) => {
+            let bond: pos::Bond = decode_data(tx)?;
+            let mut fields = source_field(bond.source);
+            fields.extend([
+                PreviewField::new("Validator", bond.validator),
+                PreviewField::new("Amount", bond.amount.to_string_native()),
+            ]);
+            let operation = if tag == TX_BOND_WASM {
+                "Bond"
+            } else {
+                "Unbond"
+            };
+            (operation, fields)
+        }
+        Some(TX_WITHDRAW_WASM) => {
+            let withdraw: pos::Withdraw = decode_data(tx)?;
+            let mut fields = source_field(withdraw.source);
+            fields.push(PreviewField::new("Validator", withdraw.validator));
+            ("Withdraw", fields)
+        }
+        Some(TX_CLAIM_REWARDS_WASM) => {
+            let claim: pos::ClaimRewards = decode_data(tx)?;
+            let mut fields = source_field(claim.source);
+            fields.push(PreviewField::new("Validator", claim.validator));
+            ("Claim Rewards", fields)
+        }
+        Some(TX_INIT_PROPOSAL) => {
+            let proposal: InitProposalData = decode_data(tx)?;
+            let fields = vec![
+                PreviewField::new("Author", proposal.author),
+                PreviewField::new("Type", proposal.r#type),
+                PreviewField::new(
+                    "Content",
+                    HEXLOWER.encode(&proposal.content.0),
+                ),
+                PreviewField::new(
+                    "Voting start epoch",
+                    proposal.voting_start_epoch,
+                ),
+                PreviewField::new(
+                    "Voting end epoch",
+                    proposal.voting_end_epoch,
+                ),
+                PreviewField::new(
+                    "Activation epoch",
+                    proposal.activation_epoch,
+                ),
+            ];
+            ("Init Proposal", fields)
+        }
+        Some(TX_VOTE_PROPOSAL) => {
+            let vote: VoteProposalData = decode_data(tx)?;
+            let fields = vec![
+                PreviewField::new("ID", vote.id),
+                PreviewField::new("Vote", vote.vote),
+                PreviewField::new("Voter", vote.voter),
+            ];
+            ("Vote Proposal", fields)
+        }
+        Some(TX_REVEAL_PK) => {
+            let public_key: common::PublicKey = decode_data(tx)?;
+            (
+                "Reveal Pubkey",
+                vec![PreviewField::new("Public key", public_key)],
+            )
+        }
+        _ => ("Custom", vec![]),
+    };
+
+    let fee = match &tx.header.tx_type {
+        TxType::Wrapper(wrapper) => Some(FeePreview {
+            payer: wrapper.fee_payer().to_string(),
+            public_key: wrapper.pk.to_string(),
+            token: wrapper.fee.token.to_string(),
+            amount_per_gas_unit: wrapper
+                .fee
+                .amount_per_gas_unit
+                .to_string_precise(),
+            gas_limit: wrapper.gas_limit.into(),
+            max_fee: wrapper
+                .get_tx_fee()
+                .map_err(|err| Error::Other(err.to_string()))?
+                .to_string_precise(),
+        }),
+        _ => None,
+    };
+    let memo = tx.memo().map(|memo| match String::from_utf8(memo) {
+        Ok(memo) => memo,
+        Err(err) => HEXLOWER.encode(err.as_bytes()),
+    });
+
+    Ok(TxPreview {
+        version: TX_PREVIEW_VERSION,
+        chain_id: tx.header.chain_id.to_string(),
+        expiration: tx.header.expiration.as_ref().map(|exp| exp.to_rfc3339()),
+        code_hash: HEXLOWER.encode(&code_sec.code.hash().0),
+        operation: operation.to_string(),
+        fields,
+        fee,
+        memo,
+    })
+}
+
+/// Decode the data of the transaction in its encoding
+fn decode_data<T>(tx: &Tx) -> Result<T, Error>
+where
+    T: BorshDeserialize + DeserializeOwned,
+{
+    tx.decode_data()
+        .ok_or_else(|| Error::Other("Invalid Data".to_string()))?
+        .map_err(|err| Error::from(EncodingError::Conversion(err.to_string())))
+}
+
+/// The field of the optional source of a PoS operation
+fn source_field(source: Option<impl ToString>) -> Vec<PreviewField> {
+    source
+        .map(|source| PreviewField::new("Source", source))
+        .into_iter()
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::str::FromStr;
+
+    use namada_core::address::testing::{
+        established_address_1, established_address_2, nam,
+    };
+    use namada_core::chain::ChainId;
+    use namada_core::hash::Hash;
+    use namada_core::key::testing::keypair_1;
+    use namada_core::key::RefTo;
+    use namada_core::time::DateTimeUtc;
+    use namada_core::token::{Amount, DenominatedAmount};
+    use namada_governance::storage::vote::ProposalVote;
+    use namada_tx::data::wrapper::GasLimit;
+    use namada_tx::data::Fee;
+    use namada_tx::DataEncoding;
+
+    use super::*;
+
+    fn new_tx(tag: Option<&str>) -> Tx {
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code_from_hash(Hash::default(), tag.map(ToString::to_string));
+        tx
+    }
+
+    /// The transactions whose previews are recorded in the fixtures
+    fn fixture_txs() -> BTreeMap<&'static str, Tx> {
+        let mut transfer = new_tx(Some(TX_TRANSFER_WASM));
+        transfer.header.expiration =
+            Some(DateTimeUtc::from_str("2024-01-01T00:00:00Z").unwrap());
+        transfer
+            .add_encoded_data(
+                Transfer {
+                    source: established_address_1(),
+                    target: established_address_2(),
+                    token: nam(),
+                    amount: DenominatedAmount::new(
+                        Amount::from_u64(1_500_000),
+                        6.into(),
+                    ),
+                    shielded: None,
+                },
+                DataEncoding::Borsh,
+            )
+            .add_memo(b"rent")
+            .0
+            .add_wrapper(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::from_str("0.01")
+                        .unwrap(),
+                    token: nam(),
+                },
+                keypair_1().ref_to(),
+                GasLimit::from(10_000),
+                None,
+            );
+
+        let mut bond = new_tx(Some(TX_BOND_WASM));
+        bond.add_encoded_data(
+            pos::Bond {
+                validator: established_address_1(),
+                amount: Amount::native_whole(10),
+                source: Some(established_address_2()),
+            },
+            DataEncoding::JsonV1,
+        );
+
+        let mut withdraw = new_tx(Some(TX_WITHDRAW_WASM));
+        withdraw.add_encoded_data(
+            pos::Withdraw {
+                validator: established_address_1(),
+                source: None,
+            },
+            DataEncoding::Borsh,
+        );
+
+        let mut vote = new_tx(Some(TX_VOTE_PROPOSAL));
+        vote.add_encoded_data(
+            VoteProposalData {
+                id: 3,
+                vote: ProposalVote::Yay,
+                voter: established_address_2(),
+            },
+            DataEncoding::Borsh,
+        );
+
+        let mut reveal_pk = new_tx(Some(TX_REVEAL_PK));
+        reveal_pk.add_encoded_data(keypair_1().ref_to(), DataEncoding::Borsh);
+
+        let custom = new_tx(None);
+
+        BTreeMap::from([
+            ("transfer", transfer),
+            ("bond", bond),
+            ("withdraw", withdraw),
+            ("vote_proposal", vote),
+            ("reveal_pk", reveal_pk),
+            ("custom", custom),
+        ])
+    }
+
+    /// The preview format must not change without bumping its version
+    #[test]
+    fn test_tx_previews_fixture() {
+        let file = fs::File::open("../tests/fixtures/tx_previews.json")
+            .expect("file should open read only");
+        let fixtures: BTreeMap<String, TxPreview> =
+            serde_json::from_reader(file).expect("file should be proper JSON");
+        let txs = fixture_txs();
+        assert_eq!(
+            fixtures.keys().map(String::as_str).collect::<Vec<_>>(),
+            txs.keys().copied().collect::<Vec<_>>()
+        );
+
+        for (name, tx) in txs {
+            let preview = tx_preview(&tx).unwrap();
+            assert_eq!(preview, fixtures[name], "preview of {name} changed");
+        }
+    }
+}
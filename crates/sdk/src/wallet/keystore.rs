@@ -0,0 +1,466 @@
+//! Import and export of the transparent secret keys in the formats of other
+//! tools.
+//!
+//! The supported formats are:
+//!
+//! - [`KeyFormat::Namada`], the Borsh encoding of the wallet's keys,
+//! - [`KeyFormat::Hex`] and [`KeyFormat::Base64`], the raw 32 bytes seed of an
+//!   ed25519 secret key. The 64 bytes of the seed followed by the public key,
+//!   as used by some tools, are accepted on import,
+//! - [`KeyFormat::Tendermint`], the `priv_validator_key.json` of CometBFT
+//!   validators, with an ed25519 or secp256k1 key, and
+//! - [`KeyFormat::Keystore`], a JSON [`Keystore`] with the secret key encrypted
+//!   with a password, to move keys between wallets.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use borsh::BorshDeserialize;
+use borsh_ext::BorshSerializeExt;
+use data_encoding::{BASE64, HEXLOWER, HEXLOWER_PERMISSIVE};
+use namada_core::key::{
+    common, ed25519, secp256k1, tm_consensus_key_raw_hash, RefTo,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+use crate::wallet::keys::EncryptedKeypair;
+use crate::wallet::DecryptionError;
+
+/// The version of the [`Keystore`] format
+pub const KEYSTORE_VERSION: u32 = 1;
+/// The key derivation function of the [`Keystore`] encryption
+const KEYSTORE_KDF: &str = "argon2i";
+/// The cipher of the [`Keystore`] encryption
+const KEYSTORE_CIPHER: &str = "xchacha20-poly1305";
+
+/// The length of an ed25519 secret key seed
+const ED25519_SEED_LENGTH: usize = 32;
+
+/// A format of an exported secret key
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyFormat {
+    /// The Borsh encoding of the key
+    Namada,
+    /// The hex encoding of a raw ed25519 key
+    Hex,
+    /// The Base64 encoding of a raw ed25519 key
+    Base64,
+    /// A CometBFT `priv_validator_key.json`
+    Tendermint,
+    /// A password encrypted JSON keystore
+    Keystore,
+}
+
+impl Display for KeyFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format = match self {
+            Self::Namada => "namada",
+            Self::Hex => "hex",
+            Self::Base64 => "base64",
+            Self::Tendermint => "tendermint",
+            Self::Keystore => "keystore",
+        };
+        write!(f, "{format}")
+    }
+}
+
+impl FromStr for KeyFormat {
+    type Err = KeystoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "namada" => Ok(Self::Namada),
+            "hex" => Ok(Self::Hex),
+            "base64" => Ok(Self::Base64),
+            "tendermint" | "cometbft" => Ok(Self::Tendermint),
+            "keystore" => Ok(Self::Keystore),
+            _ => Err(KeystoreError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum KeystoreError {
+    #[error(
+        "Unknown key format {0}, expected one of namada, hex, base64, \
+         tendermint or keystore"
+    )]
+    UnknownFormat(String),
+    #[error("Only the ed25519 keys can be encoded in the {0} format")]
+    UnsupportedScheme(KeyFormat),
+    #[error("A password is required to encrypt or decrypt a keystore")]
+    MissingPassword,
+    #[error("Invalid key data: {0}")]
+    InvalidData(String),
+    #[error("The public key doesn't match the secret key")]
+    PublicKeyMismatch,
+    #[error("Unsupported keystore version {0}, expected {KEYSTORE_VERSION}")]
+    UnsupportedVersion(u32),
+    #[error("Unsupported keystore encryption {0}")]
+    UnsupportedEncryption(String),
+    #[error("{0}")]
+    Decryption(#[from] DecryptionError),
+}
+
+/// A portable JSON keystore of a secret key, encrypted with a password
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    /// The version of the keystore format
+    pub version: u32,
+    /// The public key of the encrypted secret key
+    pub public_key: String,
+    /// The key derivation function of the password
+    pub kdf: String,
+    /// The cipher of the secret key
+    pub cipher: String,
+    /// The hex encoding of the salt followed by the encrypted key
+    pub ciphertext: String,
+}
+
+impl Keystore {
+    /// Encrypt a secret key with a password
+    pub fn encrypt(
+        sk: &common::SecretKey,
+        password: Zeroizing<String>,
+    ) -> Self {
+        Self {
+            version: KEYSTORE_VERSION,
+            public_key: sk.ref_to().to_string(),
+            kdf: KEYSTORE_KDF.to_string(),
+            cipher: KEYSTORE_CIPHER.to_string(),
+            ciphertext: EncryptedKeypair::new(sk, password).to_string(),
+        }
+    }
+
+    /// Decrypt the secret key with its password, checking that it matches
+    /// the public key of the keystore
+    pub fn decrypt(
+        &self,
+        password: Zeroizing<String>,
+    ) -> Result<common::SecretKey, KeystoreError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(KeystoreError::UnsupportedVersion(self.version));
+        }
+        if self.kdf != KEYSTORE_KDF || self.cipher != KEYSTORE_CIPHER {
+            return Err(KeystoreError::UnsupportedEncryption(format!(
+                "{}/{}",
+                self.kdf, self.cipher
+            )));
+        }
+        let encrypted =
+            EncryptedKeypair::<common::SecretKey>::from_str(&self.ciphertext)
+                .map_err(|err| KeystoreError::InvalidData(err.to_string()))?;
+        let sk = encrypted.decrypt(password)?;
+        if sk.ref_to().to_string() != self.public_key {
+            return Err(KeystoreError::PublicKeyMismatch);
+        }
+        Ok(sk)
+    }
+}
+
+/// A typed key of a CometBFT `priv_validator_key.json`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct TendermintTypedKey {
+    #[serde(rename = "type")]
+    key_type: String,
+    value: String,
+}
+
+/// A CometBFT `priv_validator_key.json`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct TendermintKey {
+    address: String,
+    pub_key: TendermintTypedKey,
+    priv_key: TendermintTypedKey,
+}
+
+/// Encode a secret key in the given format. A password is required for the
+/// [`KeyFormat::Keystore`] format.
+pub fn encode_secret_key(
+    sk: &common::SecretKey,
+    format: KeyFormat,
+    password: Option<Zeroizing<String>>,
+) -> Result<Vec<u8>, KeystoreError> {
+    match format {
+        KeyFormat::Namada => Ok(sk.serialize_to_vec()),
+        KeyFormat::Hex => {
+            let seed = ed25519_seed(sk, format)?;
+            Ok(HEXLOWER.encode(&seed).into_bytes())
+        }
+        KeyFormat::Base64 => {
+            let seed = ed25519_seed(sk, format)?;
+            Ok(BASE64.encode(&seed).into_bytes())
+        }
+        KeyFormat::Tendermint => {
+            let key = tendermint_key(sk);
+            Ok(serde_json::to_vec_pretty(&key)
+                .expect("A key must be encodable to JSON"))
+        }
+        KeyFormat::Keystore => {
+            let password = password.ok_or(KeystoreError::MissingPassword)?;
+            let keystore = Keystore::encrypt(sk, password);
+            Ok(serde_json::to_vec_pretty(&keystore)
+                .expect("A keystore must be encodable to JSON"))
+        }
+    }
+}
+
+/// Decode a secret key from the given format. A password is required for the
+/// [`KeyFormat::Keystore`] format.
+pub fn decode_secret_key(
+    data: &[u8],
+    format: KeyFormat,
+    password: Option<Zeroizing<String>>,
+) -> Result<common::SecretKey, KeystoreError> {
+    match format {
+        KeyFormat::Namada => common::SecretKey::try_from_slice(data)
+            .map_err(|err| KeystoreError::InvalidData(err.to_string())),
+        KeyFormat::Hex => {
+            let bytes = HEXLOWER_PERMISSIVE
+                .decode(trim_ascii(data))
+                .map_err(|err| KeystoreError::InvalidData(err.to_string()))?;
+            ed25519_from_bytes(&bytes)
+        }
+        KeyFormat::Base64 => {
+            let bytes = BASE64
+                .decode(trim_ascii(data))
+                .map_err(|err| KeystoreError::InvalidData(err.to_string()))?;
+            ed25519_from_bytes(&bytes)
+        }
+        KeyFormat::Tendermint => {
+            let key: TendermintKey = serde_json::from_slice(data)
+                .map_err(|err| KeystoreError::InvalidData(err.to_string()))?;
+            tendermint_secret_key(&key)
+        }
+        KeyFormat::Keystore => {
+            let keystore: Keystore = serde_json::from_slice(data)
+                .map_err(|err| KeystoreError::InvalidData(err.to_string()))?;
+            let password = password.ok_or(KeystoreError::MissingPassword)?;
+            keystore.decrypt(password)
+        }
+    }
+}
+
+/// Encode a validator consensus key as a CometBFT `priv_validator_key.json`
+pub fn tendermint_key_json(sk: &common::SecretKey) -> serde_json::Value {
+    serde_json::to_value(tendermint_key(sk))
+        .expect("A key must be encodable to JSON")
+}
+
+/// Get the CometBFT `priv_validator_key.json` of a secret key. The ed25519
+/// private key is the seed followed by the public key.
+fn tendermint_key(sk: &common::SecretKey) -> TendermintKey {
+    let pk = sk.ref_to();
+    let (scheme, pk_bytes, sk_bytes) = match (sk, &pk) {
+        (common::SecretKey::Ed25519(sk), common::PublicKey::Ed25519(pk)) => (
+            "Ed25519",
+            pk.serialize_to_vec(),
+            [sk.serialize_to_vec(), pk.serialize_to_vec()].concat(),
+        ),
+        (
+            common::SecretKey::Secp256k1(sk),
+            common::PublicKey::Secp256k1(pk),
+        ) => ("Secp256k1", pk.serialize_to_vec(), sk.serialize_to_vec()),
+        _ => unreachable!("A public key has the scheme of its secret key"),
+    };
+    TendermintKey {
+        address: tm_consensus_key_raw_hash(&pk),
+        pub_key: TendermintTypedKey {
+            key_type: format!("tendermint/PubKey{scheme}"),
+            value: BASE64.encode(&pk_bytes),
+        },
+        priv_key: TendermintTypedKey {
+            key_type: format!("tendermint/PrivKey{scheme}"),
+            value: BASE64.encode(&sk_bytes),
+        },
+    }
+}
+
+/// Decode the secret key of a CometBFT `priv_validator_key.json`, checking
+/// that it matches its public key
+fn tendermint_secret_key(
+    key: &TendermintKey,
+) -> Result<common::SecretKey, KeystoreError> {
+    let decode = |value: &str| {
+        BASE64
+            .decode(value.as_bytes())
+            .map_err(|err| KeystoreError::InvalidData(err.to_string()))
+    };
+    let sk_bytes = decode(&key.priv_key.value)?;
+    let sk = match key.priv_key.key_type.as_str() {
+        "tendermint/PrivKeyEd25519" => ed25519_from_bytes(&sk_bytes)?,
+        "tendermint/PrivKeySecp256k1" => {
+            secp256k1::SecretKey::try_from_slice(&sk_bytes)
+                .map(common::SecretKey::Secp256k1)
+                .map_err(|err| KeystoreError::InvalidData(err.to_string()))?
+        }
+        key_type => {
+            return Err(KeystoreError::InvalidData(format!(
+                "Unsupported private key type {key_type}"
+            )));
+        }
+    };
+    let pk_bytes = decode(&key.pub_key.value)?;
+    let expected_pk_bytes = match sk.ref_to() {
+        common::PublicKey::Ed25519(pk) => pk.serialize_to_vec(),
+        common::PublicKey::Secp256k1(pk) => pk.serialize_to_vec(),
+    };
+    if pk_bytes != expected_pk_bytes {
+        return Err(KeystoreError::PublicKeyMismatch);
+    }
+    Ok(sk)
+}
+
+/// Get the seed of an ed25519 secret key
+fn ed25519_seed(
+    sk: &common::SecretKey,
+    format: KeyFormat,
+) -> Result<Vec<u8>, KeystoreError> {
+    match sk {
+        common::SecretKey::Ed25519(sk) => Ok(sk.serialize_to_vec()),
+        common::SecretKey::Secp256k1(_) => {
+            Err(KeystoreError::UnsupportedScheme(format))
+        }
+    }
+}
+
+/// Decode an ed25519 secret key from its seed, optionally followed by its
+/// public key
+fn ed25519_from_bytes(
+    bytes: &[u8],
+) -> Result<common::SecretKey, KeystoreError> {
+    if bytes.len() != ED25519_SEED_LENGTH
+        && bytes.len() != 2 * ED25519_SEED_LENGTH
+    {
+        return Err(KeystoreError::InvalidData(format!(
+            "Expected an ed25519 key of {} or {} bytes, got {} bytes",
+            ED25519_SEED_LENGTH,
+            2 * ED25519_SEED_LENGTH,
+            bytes.len()
+        )));
+    }
+    let (seed, pk_bytes) = bytes.split_at(ED25519_SEED_LENGTH);
+    let sk = ed25519::SecretKey::try_from_slice(seed)
+        .map_err(|err| KeystoreError::InvalidData(err.to_string()))?;
+    if !pk_bytes.is_empty() && pk_bytes != sk.ref_to().serialize_to_vec() {
+        return Err(KeystoreError::PublicKeyMismatch);
+    }
+    Ok(common::SecretKey::Ed25519(sk))
+}
+
+/// Trim the ASCII whitespaces around the text of a key, e.g. a trailing new
+/// line
+fn trim_ascii(data: &[u8]) -> &[u8] {
+    let start = data
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(data.len());
+    let end = data
+        .iter()
+        .rposition(|byte| !byte.is_ascii_whitespace())
+        .map_or(start, |end| end + 1);
+    &data[start..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ed25519_key() -> common::SecretKey {
+        common::SecretKey::Ed25519(
+            ed25519::SecretKey::try_from_slice(&[1; 32]).unwrap(),
+        )
+    }
+
+    fn secp256k1_key() -> common::SecretKey {
+        common::SecretKey::Secp256k1(
+            secp256k1::SecretKey::try_from_slice(&[1; 32]).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_key_formats_roundtrip() {
+        let sk = ed25519_key();
+        for format in [
+            KeyFormat::Namada,
+            KeyFormat::Hex,
+            KeyFormat::Base64,
+            KeyFormat::Tendermint,
+        ] {
+            let data = encode_secret_key(&sk, format, None).unwrap();
+            let decoded = decode_secret_key(&data, format, None).unwrap();
+            assert_eq!(decoded.ref_to(), sk.ref_to(), "{format}");
+            assert_eq!(
+                format.to_string().parse::<KeyFormat>().unwrap(),
+                format
+            );
+        }
+
+        // The secp256k1 keys have no raw format
+        let sk = secp256k1_key();
+        assert!(matches!(
+            encode_secret_key(&sk, KeyFormat::Hex, None),
+            Err(KeystoreError::UnsupportedScheme(KeyFormat::Hex))
+        ));
+        let data = encode_secret_key(&sk, KeyFormat::Tendermint, None).unwrap();
+        let decoded =
+            decode_secret_key(&data, KeyFormat::Tendermint, None).unwrap();
+        assert_eq!(decoded.ref_to(), sk.ref_to());
+    }
+
+    #[test]
+    fn test_raw_key_import() {
+        let sk = ed25519_key();
+        let pk = match sk.ref_to() {
+            common::PublicKey::Ed25519(pk) => pk.serialize_to_vec(),
+            _ => unreachable!(),
+        };
+
+        // The seed may be followed by the public key and by a new line
+        let mut data =
+            HEXLOWER.encode(&[[1; 32].as_slice(), pk.as_slice()].concat());
+        data.push('\n');
+        let decoded =
+            decode_secret_key(data.as_bytes(), KeyFormat::Hex, None).unwrap();
+        assert_eq!(decoded.ref_to(), sk.ref_to());
+
+        // but the public key must match the seed
+        let data = BASE64.encode(&[[1; 32], [2; 32]].concat());
+        assert!(matches!(
+            decode_secret_key(data.as_bytes(), KeyFormat::Base64, None),
+            Err(KeystoreError::PublicKeyMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_keystore() {
+        let sk = ed25519_key();
+        let password = || Some(Zeroizing::new("password".to_string()));
+        assert!(matches!(
+            encode_secret_key(&sk, KeyFormat::Keystore, None),
+            Err(KeystoreError::MissingPassword)
+        ));
+        let data =
+            encode_secret_key(&sk, KeyFormat::Keystore, password()).unwrap();
+        let decoded =
+            decode_secret_key(&data, KeyFormat::Keystore, password()).unwrap();
+        assert_eq!(decoded.ref_to(), sk.ref_to());
+
+        let wrong_password = Some(Zeroizing::new("wrong".to_string()));
+        assert!(matches!(
+            decode_secret_key(&data, KeyFormat::Keystore, wrong_password),
+            Err(KeystoreError::Decryption(DecryptionError::DecryptionError))
+        ));
+
+        // The public key of the keystore must match its secret key
+        let mut keystore: Keystore = serde_json::from_slice(&data).unwrap();
+        keystore.public_key = secp256k1_key().ref_to().to_string();
+        assert!(matches!(
+            keystore.decrypt(password().unwrap()),
+            Err(KeystoreError::PublicKeyMismatch)
+        ));
+    }
+}
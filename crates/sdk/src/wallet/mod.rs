@@ -2,8 +2,10 @@
 pub mod alias;
 mod derivation_path;
 mod keys;
+pub mod keystore;
 pub mod pre_genesis;
 pub mod store;
+pub mod vanity;
 
 use std::collections::BTreeMap;
 use std::fmt::Display;
@@ -1079,6 +1081,32 @@ impl<U: WalletIo> Wallet<U> {
             .map(Into::into)
     }
 
+    /// Derive a diversified payment address of the given viewing key with the
+    /// first valid diversifier index starting from `index` or, if none is
+    /// given, from the index following the last address derived from this key
+    /// in the wallet, and insert it under the given alias. Return the alias,
+    /// the payment address and its diversifier index.
+    pub fn gen_diversified_payment_addr(
+        &mut self,
+        alias: String,
+        viewing_key: ExtendedViewingKey,
+        index: Option<u64>,
+        force_alias: bool,
+    ) -> Option<(String, PaymentAddress, u64)> {
+        let index = index
+            .unwrap_or_else(|| self.store.next_diversifier_index(&viewing_key));
+        let (index, payment_addr) =
+            crate::masp::find_diversified_address(&viewing_key, index)?;
+        let alias = self.store.insert_diversified_payment_addr::<U>(
+            alias.into(),
+            viewing_key,
+            index,
+            payment_addr,
+            force_alias,
+        )?;
+        Some((alias.into(), payment_addr, index))
+    }
+
     /// Extend this wallet from another wallet (typically pre-genesis).
     /// Note that this method ignores `store.validator_data` if any.
     pub fn extend(&mut self, wallet: Self) {
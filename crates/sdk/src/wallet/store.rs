@@ -65,6 +65,10 @@ pub struct Store {
     spend_keys: BTreeMap<Alias, StoredKeypair<ExtendedSpendingKey>>,
     /// Payment address book
     payment_addrs: BiBTreeMap<Alias, PaymentAddress>,
+    /// The next diversifier indices from which to derive the payment addresses
+    /// of viewing keys
+    #[serde(default)]
+    diversifier_indices: BTreeMap<ExtendedViewingKey, u64>,
     /// Cryptographic keypairs
     secret_keys: BTreeMap<Alias, StoredKeypair<common::SecretKey>>,
     /// Known public keys
@@ -249,6 +253,18 @@ impl Store {
         &self.payment_addrs
     }
 
+    /// Get the next diversifier index from which to derive a payment address
+    /// of the given viewing key
+    pub fn next_diversifier_index(
+        &self,
+        viewing_key: &ExtendedViewingKey,
+    ) -> u64 {
+        self.diversifier_indices
+            .get(viewing_key)
+            .copied()
+            .unwrap_or_default()
+    }
+
     /// Get all known viewing keys by their alias.
     pub fn get_viewing_keys(&self) -> &BTreeMap<Alias, ExtendedViewingKey> {
         &self.view_keys
@@ -524,6 +540,25 @@ impl Store {
         Some(alias)
     }
 
+    /// Insert a payment address derived from the given viewing key with the
+    /// given diversifier index, and remember to derive the next one from the
+    /// following index
+    pub fn insert_diversified_payment_addr<U: WalletIo>(
+        &mut self,
+        alias: Alias,
+        viewing_key: ExtendedViewingKey,
+        index: u64,
+        payment_addr: PaymentAddress,
+        force: bool,
+    ) -> Option<Alias> {
+        let alias =
+            self.insert_payment_addr::<U>(alias, payment_addr, force)?;
+        let next_index = self.next_diversifier_index(&viewing_key);
+        self.diversifier_indices
+            .insert(viewing_key, next_index.max(index.saturating_add(1)));
+        Some(alias)
+    }
+
     /// Insert a new address with the given alias. If the alias is already used,
     /// will prompt for overwrite/reselection confirmation, which when declined,
     /// the address won't be added. Return the selected alias if the address has
@@ -595,6 +630,7 @@ impl Store {
             view_keys,
             spend_keys,
             payment_addrs,
+            diversifier_indices,
             secret_keys,
             public_keys,
             derivation_paths,
@@ -606,6 +642,7 @@ impl Store {
         view_keys.extend(store.view_keys);
         spend_keys.extend(store.spend_keys);
         payment_addrs.extend(store.payment_addrs);
+        diversifier_indices.extend(store.diversifier_indices);
         secret_keys.extend(store.secret_keys);
         public_keys.extend(store.public_keys);
         derivation_paths.extend(store.derivation_paths);
@@ -877,6 +914,76 @@ mod test_wallet {
         assert_eq!(&sk.to_string(), &sk_hard.to_string());
     }
 
+    #[derive(Clone)]
+    struct TestWalletUtils;
+
+    impl WalletIo for TestWalletUtils {
+        type Rng = rand_core::OsRng;
+    }
+
+    /// Test that the diversified payment addresses of a viewing key are
+    /// derived from increasing diversifier indices
+    #[test]
+    fn test_diversified_payment_addrs() {
+        let spend_key = zip32::ExtendedSpendingKey::master(&[0; 32]);
+        let viewing_key = ExtendedViewingKey::from(
+            zip32::ExtendedFullViewingKey::from(&spend_key),
+        );
+        let mut store = Store::default();
+        assert_eq!(store.next_diversifier_index(&viewing_key), 0);
+
+        let (first_index, first_addr) =
+            crate::masp::find_diversified_address(&viewing_key, 0).unwrap();
+        store
+            .insert_diversified_payment_addr::<TestWalletUtils>(
+                "first".into(),
+                viewing_key,
+                first_index,
+                first_addr,
+                false,
+            )
+            .unwrap();
+        let next_index = store.next_diversifier_index(&viewing_key);
+        assert_eq!(next_index, first_index + 1);
+
+        let (second_index, second_addr) =
+            crate::masp::find_diversified_address(&viewing_key, next_index)
+                .unwrap();
+        assert!(second_index > first_index);
+        assert_ne!(first_addr, second_addr);
+        store
+            .insert_diversified_payment_addr::<TestWalletUtils>(
+                "second".into(),
+                viewing_key,
+                second_index,
+                second_addr,
+                false,
+            )
+            .unwrap();
+        assert_eq!(
+            store.next_diversifier_index(&viewing_key),
+            second_index + 1
+        );
+        assert_eq!(store.find_payment_addr("first"), Some(&first_addr));
+        assert_eq!(store.find_payment_addr("second"), Some(&second_addr));
+
+        // Deriving an address from an earlier index again doesn't reuse the
+        // later indices
+        store
+            .insert_diversified_payment_addr::<TestWalletUtils>(
+                "again".into(),
+                viewing_key,
+                first_index,
+                first_addr,
+                false,
+            )
+            .unwrap();
+        assert_eq!(
+            store.next_diversifier_index(&viewing_key),
+            second_index + 1
+        );
+    }
+
     fn do_test_gen_sk_from_seed_and_derivation_path(
         scheme: SchemeType,
         seed: &str,
@@ -0,0 +1,283 @@
+//! Search of the secret keys whose implicit address matches a pattern.
+//!
+//! A [`VanityPattern`] is a prefix and a suffix of the bech32m encoding of an
+//! implicit address. Not every character is possible at every position: the
+//! first characters encode the kind of the address and the last character
+//! before the checksum is padded, so the patterns that no address can match
+//! are rejected. The other characters are uniformly distributed, so each
+//! character of a pattern multiplies the expected number of attempts of a
+//! search by up to 32.
+
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use namada_core::address::{Address, ImplicitAddress};
+use namada_core::key::{common, PublicKeyHash, RefTo, SchemeType};
+use rand::CryptoRng;
+use rand_core::RngCore;
+use thiserror::Error;
+
+use crate::wallet::gen_secret_key;
+
+/// The characters of the bech32 encoding, by value
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+/// The length of the bech32m checksum
+const CHECKSUM_LEN: usize = 6;
+/// The interval of the progress reports of a search
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum VanityError {
+    #[error(
+        "Invalid character {0:?}, the addresses only contain the characters \
+         {BECH32_CHARSET}"
+    )]
+    InvalidChar(char),
+    #[error("The pattern is empty")]
+    EmptyPattern,
+    #[error("The pattern is longer than the {0} characters of an address")]
+    TooLong(usize),
+    #[error(
+        "No address has the character {found:?} at position {position} of its \
+         data, expected one of {expected}"
+    )]
+    Unreachable {
+        position: usize,
+        found: char,
+        expected: String,
+    },
+}
+
+/// A prefix and a suffix of the data part of an implicit address, after the
+/// human-readable part and its `1` separator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VanityPattern {
+    prefix: String,
+    suffix: String,
+}
+
+impl VanityPattern {
+    /// Make a pattern, checking that some implicit addresses match it. The
+    /// prefix may include the human-readable part of the addresses, e.g.
+    /// `tnam1`, and both are case insensitive.
+    pub fn new(prefix: &str, suffix: &str) -> Result<Self, VanityError> {
+        let (hrp, data) = Self::data_bounds();
+        let prefix = prefix.to_lowercase();
+        let prefix = prefix
+            .strip_prefix(&format!("{hrp}1"))
+            .map(str::to_string)
+            .unwrap_or(prefix);
+        let suffix = suffix.to_lowercase();
+        if prefix.is_empty() && suffix.is_empty() {
+            return Err(VanityError::EmptyPattern);
+        }
+        let len = data.0.len();
+        if prefix.len() + suffix.len() > len {
+            return Err(VanityError::TooLong(len));
+        }
+
+        let suffix_start = len - suffix.len();
+        let chars = prefix.chars().enumerate().chain(
+            suffix
+                .chars()
+                .enumerate()
+                .map(|(i, c)| (suffix_start + i, c)),
+        );
+        for (position, c) in chars {
+            let value = bech32_value(c).ok_or(VanityError::InvalidChar(c))?;
+            // The bits that differ between the addresses of the min and max
+            // hashes are set by the hash, the other bits are fixed. The bits
+            // of the checksum are all set by the hash.
+            if position >= len - CHECKSUM_LEN {
+                continue;
+            }
+            let (zeros, ones) = (data.0[position], data.1[position]);
+            let free = zeros ^ ones;
+            if value & !free != zeros & !free {
+                let expected = BECH32_CHARSET
+                    .chars()
+                    .filter(|&c| {
+                        let value = bech32_value(c).unwrap_or_default();
+                        value & !free == zeros & !free
+                    })
+                    .collect();
+                return Err(VanityError::Unreachable {
+                    position,
+                    found: c,
+                    expected,
+                });
+            }
+        }
+        Ok(Self { prefix, suffix })
+    }
+
+    /// Check if an address matches the pattern
+    pub fn matches(&self, address: &Address) -> bool {
+        let address = address.encode();
+        let data = address
+            .rsplit_once('1')
+            .map_or(address.as_str(), |(_hrp, data)| data);
+        data.starts_with(&self.prefix) && data.ends_with(&self.suffix)
+    }
+
+    /// The expected number of attempts to find a matching address, saturated
+    /// at `u64::MAX`
+    pub fn expected_attempts(&self) -> u64 {
+        let (_hrp, data) = Self::data_bounds();
+        let len = data.0.len();
+        let suffix_start = len - self.suffix.len();
+        (0..self.prefix.len())
+            .chain(suffix_start..len)
+            .map(|position| {
+                if position >= len - CHECKSUM_LEN {
+                    32
+                } else {
+                    let free = data.0[position] ^ data.1[position];
+                    1u64 << free.count_ones()
+                }
+            })
+            .fold(1u64, u64::saturating_mul)
+    }
+
+    /// Get the human-readable part of the implicit addresses and the values
+    /// of the characters of the data of the addresses of the min and max
+    /// public key hashes
+    fn data_bounds() -> (String, (Vec<u8>, Vec<u8>)) {
+        let encode = |byte: &str| {
+            let pkh = PublicKeyHash::from_str(&byte.repeat(20))
+                .expect("A hash of 20 bytes must be valid");
+            Address::Implicit(ImplicitAddress(pkh)).encode()
+        };
+        let (zeros, ones) = (encode("00"), encode("FF"));
+        let (hrp, zeros) = zeros
+            .rsplit_once('1')
+            .expect("An address must have a separator");
+        let (_hrp, ones) = ones
+            .rsplit_once('1')
+            .expect("An address must have a separator");
+        let values = |data: &str| {
+            data.chars()
+                .map(|c| bech32_value(c).expect("An address must be bech32"))
+                .collect()
+        };
+        (hrp.to_string(), (values(zeros), values(ones)))
+    }
+}
+
+/// Get the value of a bech32 character
+fn bech32_value(c: char) -> Option<u8> {
+    BECH32_CHARSET
+        .find(c)
+        .and_then(|value| u8::try_from(value).ok())
+}
+
+/// Search a secret key of the given scheme whose implicit address matches the
+/// pattern, with a thread per RNG made by `new_rng`. The number of attempts
+/// so far is reported to `on_progress` about every second. Returns the key
+/// found and the number of attempts.
+pub fn search_vanity_key<R, F, P>(
+    pattern: &VanityPattern,
+    scheme: SchemeType,
+    threads: usize,
+    new_rng: F,
+    mut on_progress: P,
+) -> (common::SecretKey, u64)
+where
+    R: CryptoRng + RngCore,
+    F: Fn() -> R + Sync,
+    P: FnMut(u64),
+{
+    let found = AtomicBool::new(false);
+    let attempts = AtomicU64::new(0);
+    let (sender, receiver) = mpsc::channel();
+    std::thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            let sender = sender.clone();
+            let (found, attempts, new_rng) = (&found, &attempts, &new_rng);
+            scope.spawn(move || {
+                let mut rng = new_rng();
+                while !found.load(Ordering::Relaxed) {
+                    let sk = gen_secret_key(scheme, &mut rng);
+                    attempts.fetch_add(1, Ordering::Relaxed);
+                    if pattern.matches(&Address::from(&sk.ref_to())) {
+                        found.store(true, Ordering::Relaxed);
+                        // Only the first key found is received
+                        let _ = sender.send(sk);
+                    }
+                }
+            });
+        }
+        drop(sender);
+        loop {
+            match receiver.recv_timeout(PROGRESS_INTERVAL) {
+                Ok(sk) => return (sk, attempts.load(Ordering::Relaxed)),
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    on_progress(attempts.load(Ordering::Relaxed))
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    unreachable!("The workers only stop once a key is found")
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::OsRng;
+
+    use super::*;
+
+    #[test]
+    fn test_vanity_pattern() {
+        // The first character of the implicit addresses is fixed
+        let address = Address::from(
+            &gen_secret_key(SchemeType::Ed25519, &mut OsRng).ref_to(),
+        )
+        .encode();
+        let (hrp, data) = address.rsplit_once('1').unwrap();
+        let first = data.chars().next().unwrap();
+        let other = BECH32_CHARSET.chars().find(|&c| c != first).unwrap();
+        assert!(matches!(
+            VanityPattern::new(&other.to_string(), ""),
+            Err(VanityError::Unreachable { position: 0, .. })
+        ));
+
+        // The pattern matches its address, with or without the hrp
+        let pattern =
+            VanityPattern::new(&format!("{hrp}1{}", &data[..3]), &data[36..])
+                .unwrap();
+        assert!(pattern.matches(&Address::decode(&address).unwrap()));
+        assert_eq!(
+            pattern,
+            VanityPattern::new(&data[..3], &data[36..]).unwrap()
+        );
+        assert!(pattern.expected_attempts() >= 32 * 32 * 32 * 32);
+
+        assert!(matches!(
+            VanityPattern::new("b", ""),
+            Err(VanityError::InvalidChar('b'))
+        ));
+        assert!(matches!(
+            VanityPattern::new("", ""),
+            Err(VanityError::EmptyPattern)
+        ));
+    }
+
+    #[test]
+    fn test_search_vanity_key() {
+        let pattern = VanityPattern::new("", "q").unwrap();
+        let (sk, attempts) = search_vanity_key(
+            &pattern,
+            SchemeType::Ed25519,
+            2,
+            || OsRng,
+            |_| {},
+        );
+        assert!(pattern.matches(&Address::from(&sk.ref_to())));
+        assert!(attempts >= 1);
+    }
+}
@@ -0,0 +1,36 @@
+//! Bindings for the key and address types
+
+use std::str::FromStr;
+
+use namada_sdk::address::Address;
+use namada_sdk::hash::Hash;
+use namada_sdk::key::{common, RefTo, SigScheme};
+use wasm_bindgen::prelude::*;
+
+/// Get the public key of a secret key
+#[wasm_bindgen(js_name = publicKey)]
+pub fn public_key(secret_key: &str) -> Result<String, JsError> {
+    let secret_key = common::SecretKey::from_str(secret_key)?;
+    Ok(secret_key.ref_to().to_string())
+}
+
+/// Get the implicit address of a public key
+#[wasm_bindgen(js_name = implicitAddress)]
+pub fn implicit_address(public_key: &str) -> Result<String, JsError> {
+    let public_key = common::PublicKey::from_str(public_key)?;
+    Ok(Address::from(&public_key).encode())
+}
+
+/// Check that the given string is a valid address
+#[wasm_bindgen(js_name = isValidAddress)]
+pub fn is_valid_address(address: &str) -> bool {
+    Address::decode(address).is_ok()
+}
+
+/// Sign a hash, e.g. the sign bytes of a transaction, with a secret key
+#[wasm_bindgen(js_name = signHash)]
+pub fn sign_hash(secret_key: &str, hash: &str) -> Result<String, JsError> {
+    let secret_key = common::SecretKey::from_str(secret_key)?;
+    let hash = Hash::from_str(hash)?;
+    Ok(common::SigScheme::sign(&secret_key, hash).to_string())
+}
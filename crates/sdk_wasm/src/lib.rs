@@ -0,0 +1,23 @@
+//! WebAssembly bindings for the Namada SDK transaction builder.
+//!
+//! This crate compiles a subset of the [`namada_sdk`] to
+//! `wasm32-unknown-unknown` and exposes it through `wasm-bindgen`, so that web
+//! wallets can build and sign transactions without re-implementing the Borsh
+//! schemas of the protocol types. The TypeScript declarations are generated
+//! along with the package, e.g. with `make build-sdk-wasm`.
+//!
+//! # Structure
+//!
+//! - [`tx`]: build a transaction from the JSON representation of its data,
+//!   attach the fee, sign it and estimate its fee
+//! - [`key`]: parse keys and derive addresses
+//!
+//! Transactions are passed across the boundary as the bytes that are
+//! broadcast to a node, so that they can be stored and sent as is on the JS
+//! side. Keys, addresses, hashes and amounts are passed in their usual string
+//! encodings.
+
+pub mod key;
+pub mod tx;
+
+pub use namada_sdk;
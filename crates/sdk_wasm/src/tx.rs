@@ -0,0 +1,320 @@
+//! Bindings for the construction and the signing of transactions
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use namada_sdk::account::AccountPublicKeysMap;
+use namada_sdk::address::Address;
+use namada_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use namada_sdk::chain::ChainId;
+use namada_sdk::governance::storage::proposal::{
+    InitProposalData, VoteProposalData,
+};
+use namada_sdk::hash::Hash;
+use namada_sdk::key::common;
+use namada_sdk::time::DateTimeUtc;
+use namada_sdk::token::{self, Amount, DenominatedAmount};
+use namada_sdk::tx::data::{pos, Fee, GasLimit};
+use namada_sdk::tx::{
+    Authorization, DataEncoding, Section, Signer, Tx, TX_BOND_WASM,
+    TX_INIT_PROPOSAL, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
+    TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// Generic arguments required to construct a transaction
+#[wasm_bindgen]
+pub struct GlobalArgs {
+    chain_id: ChainId,
+    expiration: Option<DateTimeUtc>,
+    code_hash: Hash,
+    data_encoding: DataEncoding,
+}
+
+#[wasm_bindgen]
+impl GlobalArgs {
+    /// Parse the generic arguments of a transaction. The expiration is an
+    /// RFC 3339 date time and the data encoding is either `borsh`, the
+    /// default, or `json-v1`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        chain_id: &str,
+        code_hash: &str,
+        expiration: Option<String>,
+        data_encoding: Option<String>,
+    ) -> Result<GlobalArgs, JsError> {
+        Ok(Self {
+            chain_id: ChainId::from_str(chain_id)?,
+            expiration: expiration
+                .as_deref()
+                .map(DateTimeUtc::from_str)
+                .transpose()?,
+            code_hash: Hash::from_str(code_hash)?,
+            data_encoding: data_encoding
+                .as_deref()
+                .map(DataEncoding::from_str)
+                .transpose()
+                .map_err(|err| JsError::new(&err))?
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// The kinds of transactions that can be built
+#[derive(Debug, Clone, Copy)]
+enum TxKind {
+    Transfer,
+    Bond,
+    Unbond,
+    Withdraw,
+    InitProposal,
+    VoteProposal,
+    RevealPk,
+}
+
+impl TxKind {
+    /// The tag of the code of the transaction
+    fn code_tag(&self) -> &'static str {
+        match self {
+            Self::Transfer => TX_TRANSFER_WASM,
+            Self::Bond => TX_BOND_WASM,
+            Self::Unbond => TX_UNBOND_WASM,
+            Self::Withdraw => TX_WITHDRAW_WASM,
+            Self::InitProposal => TX_INIT_PROPOSAL,
+            Self::VoteProposal => TX_VOTE_PROPOSAL,
+            Self::RevealPk => TX_REVEAL_PK,
+        }
+    }
+
+    /// Check if the transaction code can decode its data in the given
+    /// encoding
+    fn supports_encoding(&self, encoding: DataEncoding) -> bool {
+        match self {
+            Self::Transfer
+            | Self::Bond
+            | Self::Unbond
+            | Self::Withdraw
+            | Self::InitProposal => true,
+            Self::VoteProposal | Self::RevealPk => {
+                encoding == DataEncoding::Borsh
+            }
+        }
+    }
+}
+
+impl Display for TxKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Transfer => write!(f, "transfer"),
+            Self::Bond => write!(f, "bond"),
+            Self::Unbond => write!(f, "unbond"),
+            Self::Withdraw => write!(f, "withdraw"),
+            Self::InitProposal => write!(f, "init-proposal"),
+            Self::VoteProposal => write!(f, "vote-proposal"),
+            Self::RevealPk => write!(f, "reveal-pk"),
+        }
+    }
+}
+
+impl FromStr for TxKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "transfer" => Ok(Self::Transfer),
+            "bond" => Ok(Self::Bond),
+            "unbond" => Ok(Self::Unbond),
+            "withdraw" => Ok(Self::Withdraw),
+            "init-proposal" => Ok(Self::InitProposal),
+            "vote-proposal" => Ok(Self::VoteProposal),
+            "reveal-pk" => Ok(Self::RevealPk),
+            _ => Err(format!("Unknown transaction kind {s}")),
+        }
+    }
+}
+
+/// Parse the JSON representation of the tx data and add it to the tx in the
+/// given encoding
+fn add_data<T>(
+    tx: &mut Tx,
+    data: &str,
+    encoding: DataEncoding,
+) -> Result<(), JsError>
+where
+    T: BorshSerialize + BorshDeserialize + Serialize + DeserializeOwned,
+{
+    let data: T = serde_json::from_str(data)?;
+    tx.add_encoded_data(data, encoding)?;
+    Ok(())
+}
+
+fn decode_tx(tx: &[u8]) -> Result<Tx, JsError> {
+    Ok(Tx::try_from(tx)?)
+}
+
+/// Build a transaction of the given kind, one of `transfer`, `bond`,
+/// `unbond`, `withdraw`, `init-proposal`, `vote-proposal` or `reveal-pk`,
+/// from the serde JSON representation of its data
+#[wasm_bindgen(js_name = buildTx)]
+pub fn build_tx(
+    args: &GlobalArgs,
+    kind: &str,
+    data: &str,
+) -> Result<Vec<u8>, JsError> {
+    let kind = TxKind::from_str(kind).map_err(|err| JsError::new(&err))?;
+    let encoding = args.data_encoding;
+    if !kind.supports_encoding(encoding) {
+        return Err(JsError::new(&format!(
+            "The {kind} transaction doesn't support the {encoding} data \
+             encoding"
+        )));
+    }
+
+    let mut tx = Tx::new(args.chain_id.clone(), args.expiration);
+    tx.add_code_from_hash(args.code_hash, Some(kind.code_tag().to_string()));
+    match kind {
+        TxKind::Transfer => {
+            add_data::<token::Transfer>(&mut tx, data, encoding)?
+        }
+        TxKind::Bond => add_data::<pos::Bond>(&mut tx, data, encoding)?,
+        TxKind::Unbond => add_data::<pos::Unbond>(&mut tx, data, encoding)?,
+        TxKind::Withdraw => add_data::<pos::Withdraw>(&mut tx, data, encoding)?,
+        TxKind::InitProposal => {
+            add_data::<InitProposalData>(&mut tx, data, encoding)?
+        }
+        TxKind::VoteProposal => {
+            add_data::<VoteProposalData>(&mut tx, data, encoding)?
+        }
+        TxKind::RevealPk => {
+            add_data::<common::PublicKey>(&mut tx, data, encoding)?
+        }
+    }
+    Ok(tx.to_bytes())
+}
+
+/// Attach the fee data to the tx
+#[wasm_bindgen(js_name = attachFee)]
+pub fn attach_fee(
+    tx: &[u8],
+    fee_per_gas_unit: &str,
+    token: &str,
+    fee_payer: &str,
+    gas_limit: u64,
+) -> Result<Vec<u8>, JsError> {
+    let mut tx = decode_tx(tx)?;
+    tx.add_wrapper(
+        Fee {
+            amount_per_gas_unit: DenominatedAmount::from_str(fee_per_gas_unit)?,
+            token: Address::decode(token)?,
+        },
+        common::PublicKey::from_str(fee_payer)?,
+        GasLimit::from(gas_limit),
+        None,
+    );
+    Ok(tx.to_bytes())
+}
+
+/// Estimate the fee paid for the given gas limit
+#[wasm_bindgen(js_name = estimateFee)]
+pub fn estimate_fee(
+    fee_per_gas_unit: &str,
+    gas_limit: u64,
+) -> Result<String, JsError> {
+    let fee_per_gas_unit = DenominatedAmount::from_str(fee_per_gas_unit)?;
+    let fee = fee_per_gas_unit
+        .checked_mul(Amount::from(GasLimit::from(gas_limit)).into())
+        .ok_or_else(|| JsError::new("The fee overflows"))?;
+    Ok(fee.to_string())
+}
+
+/// Get the hash that commits to the given targets and that is signed by an
+/// authorization
+fn sign_bytes(targets: Vec<Hash>) -> Hash {
+    Authorization {
+        targets,
+        signer: Signer::PubKeys(vec![]),
+        signatures: BTreeMap::new(),
+    }
+    .get_raw_hash()
+}
+
+/// Attach a signature of the given targets to the tx
+fn attach_signature(
+    mut tx: Tx,
+    targets: Vec<Hash>,
+    public_key: &str,
+    signature: &str,
+) -> Result<Vec<u8>, JsError> {
+    tx.protocol_filter();
+    tx.add_section(Section::Authorization(Authorization {
+        targets,
+        signer: Signer::PubKeys(vec![common::PublicKey::from_str(public_key)?]),
+        signatures: [(0, common::Signature::from_str(signature)?)]
+            .into_iter()
+            .collect(),
+    }));
+    Ok(tx.to_bytes())
+}
+
+/// Get the hash to sign to authorize the inner tx
+#[wasm_bindgen(js_name = txSignBytes)]
+pub fn tx_sign_bytes(tx: &[u8]) -> Result<String, JsError> {
+    let tx = decode_tx(tx)?;
+    Ok(sign_bytes(vec![tx.raw_header_hash()]).to_string())
+}
+
+/// Attach a signature of the inner tx sign bytes to the tx
+#[wasm_bindgen(js_name = attachTxSignature)]
+pub fn attach_tx_signature(
+    tx: &[u8],
+    public_key: &str,
+    signature: &str,
+) -> Result<Vec<u8>, JsError> {
+    let tx = decode_tx(tx)?;
+    let targets = vec![tx.raw_header_hash()];
+    attach_signature(tx, targets, public_key, signature)
+}
+
+/// Get the hash to sign to authorize the fee payment. The fee must be
+/// attached and the inner tx signed before.
+#[wasm_bindgen(js_name = wrapperSignBytes)]
+pub fn wrapper_sign_bytes(tx: &[u8]) -> Result<String, JsError> {
+    let tx = decode_tx(tx)?;
+    Ok(sign_bytes(tx.sechashes()).to_string())
+}
+
+/// Attach a signature of the wrapper sign bytes to the tx
+#[wasm_bindgen(js_name = attachWrapperSignature)]
+pub fn attach_wrapper_signature(
+    tx: &[u8],
+    public_key: &str,
+    signature: &str,
+) -> Result<Vec<u8>, JsError> {
+    let tx = decode_tx(tx)?;
+    let targets = tx.sechashes();
+    attach_signature(tx, targets, public_key, signature)
+}
+
+/// Sign the inner tx with the given secret key
+#[wasm_bindgen(js_name = signTx)]
+pub fn sign_tx(tx: &[u8], secret_key: &str) -> Result<Vec<u8>, JsError> {
+    let mut tx = decode_tx(tx)?;
+    tx.sign_raw(
+        vec![common::SecretKey::from_str(secret_key)?],
+        AccountPublicKeysMap::default(),
+        None,
+    );
+    Ok(tx.to_bytes())
+}
+
+/// Sign the fee payment with the given secret key. The fee must be attached
+/// and the inner tx signed before.
+#[wasm_bindgen(js_name = signWrapper)]
+pub fn sign_wrapper(tx: &[u8], secret_key: &str) -> Result<Vec<u8>, JsError> {
+    let mut tx = decode_tx(tx)?;
+    tx.sign_wrapper(common::SecretKey::from_str(secret_key)?);
+    Ok(tx.to_bytes())
+}
@@ -36,6 +36,7 @@ pub fn compute_inflation(
     epochs_per_year: u64,
     target_amount: Dec,
     last_amount: Dec,
+    protocol_version: u64,
 ) -> Uint {
     let controller = PDController::new(
         total_native_amount,
@@ -59,7 +60,7 @@ pub fn compute_inflation(
          coefficient: {control_coeff}"
     );
     controller
-        .compute_inflation(control_coeff, metric)
+        .compute_inflation(control_coeff, metric, protocol_version)
         .expect("Inflation calculation overflow")
 }
 
@@ -143,6 +144,7 @@ where
         .expect("Should not fail to convert Uint to Dec");
     let last_locked_dec = Dec::try_from(last_locked_amount.raw_amount())
         .expect("Should not fail to convert Uint to Dec");
+    let protocol_version = parameters::read_current_protocol_version(storage)?;
 
     // Initial computation of the new shielded inflation
     let inflation = compute_inflation(
@@ -155,6 +157,7 @@ where
         epochs_per_year,
         target_locked_dec,
         last_locked_dec,
+        protocol_version,
     );
 
     // inflation-per-token = inflation / locked tokens = n/PRECISION
@@ -244,6 +247,7 @@ where
     use std::cmp::Ordering;
     use std::collections::BTreeMap;
 
+    use masp_primitives::asset_type::AssetType;
     use masp_primitives::bls12_381;
     use masp_primitives::convert::AllowedConversion;
     use masp_primitives::ff::PrimeField;
@@ -494,6 +498,21 @@ where
         }
     }
 
+    // The conversions of the assets that are older than the max note age are
+    // pruned from the conversion tree. The pruned assets are kept as purely
+    // decoding entries, so that their notes can still be spent or unshielded
+    // at face value, without rewards. The reward assets are never pruned
+    // since they are always timestamped with the zeroth epoch.
+    let max_note_age: u64 = storage
+        .read(&parameters::storage::get_masp_max_note_age_key())?
+        .unwrap_or_default();
+    let oldest_kept =
+        epoch.checked_sub(max_note_age).filter(|_| max_note_age > 0);
+    let is_pruned = |asset_type: &AssetType, asset_epoch: &Epoch| {
+        oldest_kept.is_some_and(|oldest_kept| *asset_epoch < oldest_kept)
+            && !reward_assets.contains(asset_type)
+    };
+
     // Try to distribute Merkle leaf updating as evenly as possible across
     // multiple cores
     let num_threads = rayon::current_num_threads();
@@ -501,7 +520,11 @@ where
     let assets: Vec<_> = storage
         .conversion_state_mut()
         .assets
-        .values_mut()
+        .iter_mut()
+        .filter(|(asset_type, (_, asset_epoch, _, _))| {
+            !is_pruned(asset_type, asset_epoch)
+        })
+        .map(|(_, asset)| asset)
         .enumerate()
         .collect();
     // ceil(assets.len() / num_threads)
@@ -551,9 +574,30 @@ where
     // obtained
     storage.conversion_state_mut().tree =
         FrozenCommitmentTree::merge(&tree_parts);
+    // The pruned assets are placed at the first uncommitted position, without
+    // a conversion
+    let tree_size = storage.conversion_state().tree.size();
+    for (asset_type, (_, asset_epoch, conv, pos)) in
+        storage.conversion_state_mut().assets.iter_mut()
+    {
+        if is_pruned(asset_type, asset_epoch) {
+            *conv = MaspAmount::zero().into();
+            *pos = tree_size;
+        }
+    }
+    // Keep the previous anchor with the height at which it is replaced, so
+    // that it's still accepted for the grace period
+    let anchor_key = crate::storage_key::masp_convert_anchor_key();
+    if let Some(prev_anchor) = storage.read::<Hash>(&anchor_key)? {
+        let height = storage.get_block_height()?;
+        storage.write(
+            &crate::storage_key::masp_prev_convert_anchor_key(),
+            (prev_anchor, height),
+        )?;
+    }
     // Update the anchor in storage
     storage.write(
-        &crate::storage_key::masp_convert_anchor_key(),
+        &anchor_key,
         namada_core::hash::Hash(
             bls12_381::Scalar::from(storage.conversion_state().tree.root())
                 .to_bytes(),
@@ -598,10 +642,16 @@ where
 mod tests {
     use std::str::FromStr;
 
+    use masp_primitives::transaction::components::I128Sum;
+    use namada_controller::EXACT_INFLATION_PROTOCOL_VERSION;
     use namada_core::address;
+    use namada_core::borsh::BorshDeserialize;
     use namada_core::collections::HashMap;
     use namada_core::dec::testing::arb_non_negative_dec;
+    use namada_core::masp::encode_asset_type;
+    use namada_core::storage::{BlockHeight, Epoch};
     use namada_core::token::testing::arb_amount;
+    use namada_core::token::MaspDigitPos;
     use namada_storage::testing::TestStorage;
     use namada_trans_token::storage_key::{balance_key, minted_balance_key};
     use namada_trans_token::write_denom;
@@ -610,7 +660,7 @@ mod tests {
     use test_log::test;
 
     use super::*;
-    use crate::ShieldedParams;
+    use crate::{CompactConversionState, ShieldedParams};
 
     proptest! {
         #![proptest_config(Config {
@@ -632,54 +682,142 @@ mod tests {
     ) {
         const ROUNDS: usize = 10;
 
-        let mut s = TestStorage::default();
-        // Initialize the state
-        {
-            // Parameters
-            namada_parameters::init_test_storage(&mut s).unwrap();
-
-            // Tokens
-            let token_params = ShieldedParams {
-                max_reward_rate: Dec::from_str("0.1").unwrap(),
-                kp_gain_nom: Dec::from_str("0.1").unwrap(),
-                kd_gain_nom: Dec::from_str("0.1").unwrap(),
-                locked_amount_target: 10_000_u64,
-            };
-
-            for (token_addr, (alias, denom)) in tokens() {
-                namada_trans_token::write_params(&mut s, &token_addr).unwrap();
-                crate::write_params(&token_params, &mut s, &token_addr, &denom)
-                    .unwrap();
+        let mut s = init_storage(initial_balance, masp_locked_ratio);
 
-                write_denom(&mut s, &token_addr, denom).unwrap();
+        for i in 0..ROUNDS {
+            println!("Round {i}");
+            let anchor: Option<Hash> = s
+                .read(&crate::storage_key::masp_convert_anchor_key())
+                .unwrap();
+            update_allowed_conversions(&mut s).unwrap();
+            // The replaced anchor is kept for its grace period
+            let prev_anchor: Option<(Hash, BlockHeight)> = s
+                .read(&crate::storage_key::masp_prev_convert_anchor_key())
+                .unwrap();
+            assert_eq!(prev_anchor.map(|(anchor, _)| anchor), anchor);
+            println!();
+            println!();
+        }
+    }
 
-                // Write a minted token balance
-                let total_token_balance = initial_balance;
-                s.write(&minted_balance_key(&token_addr), total_token_balance)
-                    .unwrap();
+    /// Test that the conversions older than the max note age are pruned, that
+    /// their assets can still be decoded and that the conversion state can be
+    /// rebuilt from its compact form
+    #[test]
+    fn test_pruned_conversions() {
+        const MAX_NOTE_AGE: u64 = 3;
+
+        let mut s = init_storage(
+            Amount::native_whole(1_000_000),
+            Dec::from_str("0.1").unwrap(),
+        );
+        s.write(
+            &parameters::storage::get_masp_max_note_age_key(),
+            MAX_NOTE_AGE,
+        )
+        .unwrap();
+        let native_token = s.get_native_token().unwrap();
 
-                // Put the locked ratio into MASP
-                s.write(
-                    &balance_key(&token_addr, &address::MASP),
-                    masp_locked_ratio * total_token_balance,
-                )
-                .unwrap();
+        for epoch in 1..10 {
+            let epoch = Epoch(epoch);
+            s.set_block_epoch(epoch);
+            update_allowed_conversions(&mut s).unwrap();
 
-                // Insert tokens into MASP conversion state
-                let token_map_key = masp_token_map_key();
-                let mut token_map: namada_core::masp::TokenMap =
-                    s.read(&token_map_key).unwrap().unwrap_or_default();
-                token_map.insert(alias.to_string(), token_addr.clone());
-                s.write(&token_map_key, token_map).unwrap();
+            // Only the reward assets older than the max note age are
+            // committed, the others are purely decoding entries
+            let oldest_kept =
+                epoch.checked_sub(MAX_NOTE_AGE).unwrap_or_default();
+            let state = s.conversion_state();
+            for ((token, _, _), asset_epoch, conv, pos) in state.assets.values()
+            {
+                if *asset_epoch < oldest_kept
+                    && !(*token == native_token && *asset_epoch == Epoch(0))
+                {
+                    assert_eq!(*pos, state.tree.size());
+                    assert!(I128Sum::from(conv.clone()).is_zero());
+                }
+            }
+            // The assets of all the past epochs can still be decoded
+            for (token, (_, denom)) in tokens() {
+                for past_epoch in 0..epoch.0 {
+                    let asset_type = encode_asset_type(
+                        token.clone(),
+                        denom,
+                        MaspDigitPos::Zero,
+                        Some(Epoch(past_epoch)),
+                    )
+                    .unwrap();
+                    assert!(state.assets.contains_key(&asset_type));
+                }
+            }
+
+            // The compact state rebuilds the same conversion tree
+            let compact = CompactConversionState::try_from_slice(
+                &state.to_compact().serialize_to_vec(),
+            )
+            .unwrap();
+            let rebuilt = compact.into_conversion_state().unwrap();
+            assert_eq!(rebuilt.tree.root(), state.tree.root());
+            assert_eq!(rebuilt.normed_inflation, state.normed_inflation);
+            assert_eq!(rebuilt.assets.len(), state.assets.len());
+            for (asset_type, (key, asset_epoch, conv, pos)) in &state.assets {
+                let (rebuilt_key, rebuilt_epoch, rebuilt_conv, rebuilt_pos) =
+                    rebuilt.assets.get(asset_type).unwrap();
+                assert_eq!(rebuilt_key, key);
+                assert_eq!(rebuilt_epoch, asset_epoch);
+                assert_eq!(rebuilt_pos, pos);
+                assert_eq!(
+                    I128Sum::from(rebuilt_conv.clone()),
+                    I128Sum::from(conv.clone())
+                );
             }
         }
+    }
 
-        for i in 0..ROUNDS {
-            println!("Round {i}");
-            update_allowed_conversions(&mut s).unwrap();
-            println!();
-            println!();
+    /// Initialize the storage with the parameters and the MASP tokens
+    fn init_storage(
+        initial_balance: Amount,
+        masp_locked_ratio: Dec,
+    ) -> TestStorage {
+        let mut s = TestStorage::default();
+        // Parameters
+        namada_parameters::init_test_storage(&mut s).unwrap();
+
+        // Tokens
+        let token_params = ShieldedParams {
+            max_reward_rate: Dec::from_str("0.1").unwrap(),
+            kp_gain_nom: Dec::from_str("0.1").unwrap(),
+            kd_gain_nom: Dec::from_str("0.1").unwrap(),
+            locked_amount_target: 10_000_u64,
+        };
+
+        for (token_addr, (alias, denom)) in tokens() {
+            namada_trans_token::write_params(&mut s, &token_addr).unwrap();
+            crate::write_params(&token_params, &mut s, &token_addr, &denom)
+                .unwrap();
+
+            write_denom(&mut s, &token_addr, denom).unwrap();
+
+            // Write a minted token balance
+            let total_token_balance = initial_balance;
+            s.write(&minted_balance_key(&token_addr), total_token_balance)
+                .unwrap();
+
+            // Put the locked ratio into MASP
+            s.write(
+                &balance_key(&token_addr, &address::MASP),
+                masp_locked_ratio * total_token_balance,
+            )
+            .unwrap();
+
+            // Insert tokens into MASP conversion state
+            let token_map_key = masp_token_map_key();
+            let mut token_map: namada_core::masp::TokenMap =
+                s.read(&token_map_key).unwrap().unwrap_or_default();
+            token_map.insert(alias.to_string(), token_addr.clone());
+            s.write(&token_map_key, token_map).unwrap();
         }
+        s
     }
 
     pub fn tokens() -> HashMap<Address, (&'static str, Denomination)> {
@@ -730,6 +868,7 @@ mod tests {
                 epochs_per_year,
                 Dec::try_from(locked_tokens_target).unwrap(),
                 Dec::try_from(locked_tokens_last).unwrap(),
+                EXACT_INFLATION_PROTOCOL_VERSION,
             );
 
             let rate = Dec::try_from(inflation).unwrap()
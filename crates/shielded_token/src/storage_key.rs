@@ -15,6 +15,8 @@ pub const MASP_NOTE_COMMITMENT_TREE_KEY: &str = "commitment_tree";
 pub const MASP_NOTE_COMMITMENT_ANCHOR_PREFIX: &str = "note_commitment_anchor";
 /// Key segment prefix for the convert anchor
 pub const MASP_CONVERT_ANCHOR_KEY: &str = "convert_anchor";
+/// Key segment prefix for the convert anchor of the previous epoch
+pub const MASP_PREV_CONVERT_ANCHOR_KEY: &str = "prev_convert_anchor";
 /// The key for the token map
 pub const MASP_TOKEN_MAP_KEY: &str = "tokens";
 /// The key for the asset map
@@ -142,6 +144,14 @@ pub fn masp_convert_anchor_key() -> storage::Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get the key for the masp convert tree anchor of the previous epoch, with
+/// the height at which it was replaced
+pub fn masp_prev_convert_anchor_key() -> storage::Key {
+    storage::Key::from(address::MASP.to_db_key())
+        .push(&MASP_PREV_CONVERT_ANCHOR_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get the key for the masp token map
 pub fn masp_token_map_key() -> storage::Key {
     storage::Key::from(address::MASP.to_db_key())
@@ -0,0 +1,183 @@
+//! Read-only access to the state at a past height
+
+use std::collections::BTreeMap;
+
+use namada_core::address::Address;
+use namada_core::storage::{self, BlockHeight, Epoch, Epochs, Header, TxIndex};
+use namada_storage::{ResultExt, StorageRead};
+
+use crate::{DBIter, Error, Result, StateRead, StorageHasher, WlState, DB};
+
+/// A read-only [`StorageRead`] adapter over the state at a past height. The
+/// values are read from the diffs stored for every height, so the node must be
+/// in archive mode to read the keys that are not merklized. The block metadata
+/// of the state, other than the height and epoch, is that of the last
+/// committed block.
+#[derive(Debug)]
+pub struct HistoricalState<'a, D, H>
+where
+    D: DB + for<'iter> DBIter<'iter>,
+    H: StorageHasher,
+{
+    state: &'a WlState<D, H>,
+    height: BlockHeight,
+}
+
+impl<D, H> WlState<D, H>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    /// Get a read-only view of the state at the given height (or the last
+    /// committed height when 0 or above it).
+    pub fn at_height(&self, height: BlockHeight) -> HistoricalState<'_, D, H> {
+        let last_height = self.in_mem().get_last_block_height();
+        let height = if height == BlockHeight(0) || height > last_height {
+            last_height
+        } else {
+            height
+        };
+        HistoricalState {
+            state: self,
+            height,
+        }
+    }
+}
+
+impl<'a, D, H> HistoricalState<'a, D, H>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    /// The height of the state
+    pub fn height(&self) -> BlockHeight {
+        self.height
+    }
+
+    /// Get the keys modified after the height of the state with their values
+    /// at that height, `None` for the keys that didn't exist yet. Writing these
+    /// values over the last committed state restores the state at the height.
+    ///
+    /// The values are taken from the diffs of the heights since, so the cost
+    /// is linear in the number of keys changed since the height.
+    pub fn changes_since(
+        &self,
+    ) -> Result<Vec<(storage::Key, Option<Vec<u8>>)>> {
+        let last_height = self.state.in_mem().get_last_block_height();
+        if self.height == last_height {
+            return Ok(vec![]);
+        }
+        if !self.state.archive_mode {
+            return Err(Error::ArchiveModeRequired);
+        }
+
+        // The value of a key at the height of the state is its old value at
+        // the first height after it where it changed, or none if it was
+        // created at that height
+        let mut changes: BTreeMap<String, Option<Vec<u8>>> = BTreeMap::new();
+        let db = self.state.db();
+        let mut diffs_height = self.height.next_height();
+        while diffs_height <= last_height {
+            for (key, value, _gas) in db.iter_old_diffs(diffs_height, None) {
+                changes.entry(key).or_insert(Some(value));
+            }
+            for (key, _value, _gas) in db.iter_new_diffs(diffs_height, None) {
+                changes.entry(key).or_insert(None);
+            }
+            diffs_height = diffs_height.next_height();
+        }
+
+        changes
+            .into_iter()
+            .map(|(key, value)| {
+                let key = storage::Key::parse(key).map_err(Error::KeyError)?;
+                Ok((key, value))
+            })
+            .collect()
+    }
+}
+
+impl<D, H> StorageRead for HistoricalState<'_, D, H>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    type PrefixIter<'iter>
+        = std::vec::IntoIter<(storage::Key, Vec<u8>)>
+    where
+        Self: 'iter;
+
+    fn read_bytes(
+        &self,
+        key: &storage::Key,
+    ) -> namada_storage::Result<Option<Vec<u8>>> {
+        let (value, _gas) = self
+            .state
+            .db_read_with_height(key, self.height)
+            .into_storage_result()?;
+        Ok(value)
+    }
+
+    fn has_key(&self, key: &storage::Key) -> namada_storage::Result<bool> {
+        Ok(self.read_bytes(key)?.is_some())
+    }
+
+    fn iter_prefix<'iter>(
+        &'iter self,
+        prefix: &storage::Key,
+    ) -> namada_storage::Result<Self::PrefixIter<'iter>> {
+        Ok(self
+            .state
+            .db_prefix_with_height(prefix, self.height)
+            .into_storage_result()?
+            .into_iter())
+    }
+
+    fn iter_next<'iter>(
+        &'iter self,
+        iter: &mut Self::PrefixIter<'iter>,
+    ) -> namada_storage::Result<Option<(String, Vec<u8>)>> {
+        Ok(iter.next().map(|(key, value)| (key.to_string(), value)))
+    }
+
+    fn get_chain_id(&self) -> namada_storage::Result<String> {
+        Ok(self.state.in_mem().chain_id.to_string())
+    }
+
+    fn get_block_height(&self) -> namada_storage::Result<BlockHeight> {
+        Ok(self.height)
+    }
+
+    fn get_block_header(
+        &self,
+        height: BlockHeight,
+    ) -> namada_storage::Result<Option<Header>> {
+        StorageRead::get_block_header(self.state, height)
+    }
+
+    fn get_block_epoch(&self) -> namada_storage::Result<Epoch> {
+        self.state
+            .in_mem()
+            .block
+            .pred_epochs
+            .get_epoch(self.height)
+            .ok_or_else(|| {
+                namada_storage::Error::new_alloc(format!(
+                    "Epoch of height {} not found",
+                    self.height
+                ))
+            })
+    }
+
+    fn get_pred_epochs(&self) -> namada_storage::Result<Epochs> {
+        StorageRead::get_pred_epochs(self.state)
+    }
+
+    fn get_tx_index(&self) -> namada_storage::Result<TxIndex> {
+        Ok(TxIndex(0))
+    }
+
+    fn get_native_token(&self) -> namada_storage::Result<Address> {
+        StorageRead::get_native_token(self.state)
+    }
+}
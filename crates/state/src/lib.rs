@@ -1,5 +1,6 @@
 //! Ledger's state storage with key-value backed store and a merkle tree
 
+mod historical;
 mod host_env;
 mod in_memory;
 mod wl_state;
@@ -8,6 +9,7 @@ pub mod write_log;
 use std::fmt::Debug;
 use std::iter::Peekable;
 
+pub use historical::HistoricalState;
 pub use host_env::{TxHostEnvState, VpHostEnvState};
 pub use in_memory::{BlockStorage, InMemory, LastBlock};
 use namada_core::address::Address;
@@ -32,11 +34,11 @@ pub use namada_storage::conversion_state::{
 };
 pub use namada_storage::types::{KVBytes, PatternIterator, PrefixIterator};
 pub use namada_storage::{
-    collections, iter_prefix, iter_prefix_bytes, iter_prefix_with_filter,
-    mockdb, tx_queue, BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch,
-    DbError, DbResult, Error as StorageError, OptionExt,
-    Result as StorageResult, ResultExt, StorageHasher, StorageRead,
-    StorageWrite, DB,
+    collections, in_batch, iter_prefix, iter_prefix_bytes,
+    iter_prefix_with_filter, mockdb, tx_history, tx_queue, tx_results,
+    BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, DbError, DbResult,
+    Error as StorageError, OptionExt, Result as StorageResult, ResultExt,
+    StorageBatch, StorageHasher, StorageRead, StorageWrite, DB,
 };
 use thiserror::Error;
 pub use wl_state::{FullAccessState, TempWlState, WlState};
@@ -379,12 +381,39 @@ macro_rules! impl_storage_write_by_protocol {
     };
 }
 
+// Note: the batches of protocol writes are isolated in the tx write log, they
+// must not be used while a tx is applied.
+macro_rules! impl_storage_batch_by_protocol {
+    ($($type:ty)*) => {
+        impl<D, H> StorageBatch for $($type)*
+        where
+            D: 'static + DB + for<'iter> DBIter<'iter>,
+            H: 'static + StorageHasher,
+        {
+            fn begin_batch(&mut self) {
+                self.write_log_mut().begin_protocol_batch()
+            }
+
+            fn commit_batch(&mut self) {
+                self.write_log_mut().commit_protocol_batch()
+            }
+
+            fn drop_batch(&mut self) {
+                self.write_log_mut().drop_protocol_batch()
+            }
+        }
+    };
+}
+
 impl_storage_read!(FullAccessState<D, H>);
 impl_storage_read!(WlState<D, H>);
 impl_storage_read!(TempWlState<'_, D, H>);
 impl_storage_write_by_protocol!(FullAccessState<D, H>);
 impl_storage_write_by_protocol!(WlState<D, H>);
 impl_storage_write_by_protocol!(TempWlState<'_, D, H>);
+impl_storage_batch_by_protocol!(FullAccessState<D, H>);
+impl_storage_batch_by_protocol!(WlState<D, H>);
+impl_storage_batch_by_protocol!(TempWlState<'_, D, H>);
 
 impl_storage_read!(TxHostEnvState<'_, D, H>);
 impl_storage_read!(VpHostEnvState<'_, D, H>);
@@ -419,6 +448,10 @@ pub enum Error {
     Gas(namada_gas::Error),
     #[error("{0}")]
     StorageError(#[from] namada_storage::Error),
+    #[error(
+        "Reading at a past height is only supported by nodes in archive mode"
+    )]
+    ArchiveModeRequired,
 }
 
 impl From<MerkleTreeError> for Error {
@@ -583,6 +616,7 @@ pub mod testing {
                 db: MockDB::default(),
                 in_mem: Default::default(),
                 merkle_tree_key_filter: merklize_all_keys,
+                archive_mode: false,
             })
         }
     }
@@ -634,6 +668,7 @@ mod tests {
     use chrono::{TimeZone, Utc};
     use namada_core::address::InternalAddress;
     use namada_core::borsh::{BorshDeserialize, BorshSerializeExt};
+    use namada_core::dec::Dec;
     use namada_core::storage::DbKeySeg;
     use namada_core::time::{self, DateTimeUtc, Duration};
     use namada_parameters::{EpochDuration, Parameters};
@@ -723,6 +758,12 @@ mod tests {
                 fee_unshielding_descriptions_limit: 15,
                 minimum_gas_price: BTreeMap::default(),
                 is_native_token_transferable: true,
+                max_epoch_hooks_gas: 0,
+                epoch_hook_allowlist: vec![],
+                target_block_gas: 0,
+                base_fee_max_change_rate: Dec::zero(),
+                masp_convert_anchor_grace_blocks: 0,
+                masp_max_note_age: 0,
             };
             namada_parameters::init_storage(&parameters, &mut state).unwrap();
             // Initialize pred_epochs to the current height
@@ -45,6 +45,9 @@ where
     pub(crate) in_mem: InMemory<H>,
     /// Static merkle tree storage key filter
     pub merkle_tree_key_filter: fn(&storage::Key) -> bool,
+    /// When set, the diffs of all the keys are persisted, not only of the
+    /// merklized keys, so that any value can be read at a past height
+    pub archive_mode: bool,
 }
 
 /// State with a temporary write log. This is used for dry-running txs and ABCI
@@ -108,11 +111,20 @@ where
             db,
             in_mem,
             merkle_tree_key_filter,
+            archive_mode: false,
         });
         state.load_last_state();
         state
     }
 
+    /// Set the archive mode, in which the diffs of all the keys are persisted.
+    /// This must be set from the first block for the historical values to be
+    /// complete.
+    pub fn set_archive_mode(&mut self, archive_mode: bool) {
+        self.0.archive_mode = archive_mode;
+        self.0.db.set_archive_mode(archive_mode);
+    }
+
     #[allow(dead_code)]
     /// Check if the given address exists on chain and return the gas cost.
     pub fn db_exists(&self, addr: &Address) -> Result<(bool, u64)> {
@@ -226,6 +238,19 @@ where
         }
         debug_assert!(self.0.write_log.replay_protection.is_empty());
 
+        for (key, hash) in std::mem::take(&mut self.0.write_log.tx_history) {
+            self.db.write_tx_history_entry(batch, &key, &hash)?;
+        }
+
+        if let Some(up_to) = self.0.write_log.tx_results_pruned.take() {
+            self.db.prune_tx_results(batch, up_to)?;
+        }
+        for ((height, hash), result) in
+            std::mem::take(&mut self.0.write_log.tx_results)
+        {
+            self.db.write_tx_result(batch, height, &hash, &result)?;
+        }
+
         if let Some(address_gen) = self.0.write_log.address_gen.take() {
             self.0.in_mem.address_gen = address_gen
         }
@@ -270,7 +295,7 @@ where
             self.in_mem.block.height,
             key,
             value,
-            is_key_merklized,
+            is_key_merklized || self.archive_mode,
         )?)
     }
 
@@ -291,7 +316,7 @@ where
             batch,
             self.in_mem.block.height,
             key,
-            is_key_merklized,
+            is_key_merklized || self.archive_mode,
         )?)
     }
 
@@ -650,15 +675,18 @@ where
         {
             self.db_read(key)
         } else {
-            if !(self.merkle_tree_key_filter)(key) {
+            let value = if self.archive_mode {
+                self.db().read_archived_subspace_val(key, height)?
+            } else if (self.merkle_tree_key_filter)(key) {
+                self.db().read_subspace_val_with_height(
+                    key,
+                    height,
+                    self.in_mem().get_last_block_height(),
+                )?
+            } else {
                 return Ok((None, 0));
-            }
-
-            match self.db().read_subspace_val_with_height(
-                key,
-                height,
-                self.in_mem().get_last_block_height(),
-            )? {
+            };
+            match value {
                 Some(v) => {
                     let gas = (key.len() + v.len()) as u64
                         * STORAGE_ACCESS_GAS_PER_BYTE;
@@ -671,6 +699,42 @@ where
         }
     }
 
+    /// Returns the key-value pairs with the given prefix at the given height
+    /// (or the last committed height when 0). Reading at a past height is
+    /// only supported in archive mode.
+    pub fn db_prefix_with_height(
+        &self,
+        prefix: &storage::Key,
+        height: BlockHeight,
+    ) -> Result<Vec<(storage::Key, Vec<u8>)>> {
+        let last_height = self.in_mem().get_last_block_height();
+        if height == BlockHeight(0) || height >= last_height {
+            return self
+                .db()
+                .iter_prefix(Some(prefix))
+                .map(|(key, value, _gas)| {
+                    Ok((
+                        storage::Key::parse(key).map_err(Error::KeyError)?,
+                        value,
+                    ))
+                })
+                .collect();
+        }
+        if !self.archive_mode {
+            return Err(Error::ArchiveModeRequired);
+        }
+
+        // Every diff is persisted in archive mode, so the value of each key is
+        // the one of its last diff at or before the given height
+        self.db()
+            .read_archived_subspace_prefix(prefix, height)?
+            .into_iter()
+            .map(|(key, value)| {
+                Ok((storage::Key::parse(key).map_err(Error::KeyError)?, value))
+            })
+            .collect()
+    }
+
     /// Write a value to the specified subspace and returns the gas cost and the
     /// size difference
     #[cfg(any(test, feature = "testing", feature = "benches"))]
@@ -704,7 +768,7 @@ where
             self.in_mem.block.height,
             key,
             value,
-            is_key_merklized,
+            is_key_merklized || self.archive_mode,
         )?;
         Ok((gas, size_diff))
     }
@@ -724,7 +788,7 @@ where
             deleted_bytes_len = self.db.delete_subspace_val(
                 self.in_mem.block.height,
                 key,
-                is_key_merklized,
+                is_key_merklized || self.archive_mode,
             )?;
         }
         let gas = (key.len() + deleted_bytes_len as usize) as u64
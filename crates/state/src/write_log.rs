@@ -10,6 +10,7 @@ use namada_core::hash::Hash;
 use namada_core::storage;
 use namada_events::{Event, EventToEmit, EventType};
 use namada_gas::{MEMORY_ACCESS_GAS_PER_BYTE, STORAGE_WRITE_GAS_PER_BYTE};
+use namada_storage::tx_history;
 use patricia_tree::map::StringPatriciaMap;
 use thiserror::Error;
 
@@ -109,6 +110,17 @@ pub struct WriteLog {
     /// Storage modifications for the replay protection storage, always
     /// committed regardless of the result of the transaction
     pub(crate) replay_protection: HashSet<Hash>,
+    /// Entries of the node's index of the txs that changed the balances of
+    /// addresses, always committed with the block
+    pub(crate) tx_history: BTreeMap<storage::Key, Hash>,
+    /// Results of the applied txs to be stored in the node's store of the tx
+    /// results, always committed with the block
+    pub(crate) tx_results: BTreeMap<(storage::BlockHeight, Hash), Vec<u8>>,
+    /// The height up to which the stored tx results are pruned with the block
+    pub(crate) tx_results_pruned: Option<storage::BlockHeight>,
+    /// Whether a batch of protocol writes is in progress, in which case the
+    /// protocol writes are made in the tx write log
+    pub(crate) protocol_batch: bool,
 }
 
 /// Write log prefix iterator
@@ -139,6 +151,10 @@ impl Default for WriteLog {
                 tree: StringPatriciaMap::new(),
             },
             replay_protection: HashSet::with_capacity(1_000),
+            tx_history: BTreeMap::new(),
+            tx_results: BTreeMap::new(),
+            tx_results_pruned: None,
+            protocol_batch: false,
         }
     }
 }
@@ -276,7 +292,7 @@ impl WriteLog {
             return Err(Error::UpdateTemporaryValue);
         }
         if let Some(prev) = self
-            .block_write_log
+            .protocol_write_log()
             .insert(key.clone(), StorageModification::Write { value })
         {
             match prev {
@@ -376,7 +392,7 @@ impl WriteLog {
             return Err(Error::DeleteVp);
         }
         if let Some(prev) = self
-            .block_write_log
+            .protocol_write_log()
             .insert(key.clone(), StorageModification::Delete)
         {
             match prev {
@@ -390,6 +406,43 @@ impl WriteLog {
         Ok(())
     }
 
+    /// Get the log of the protocol writes: the tx write log during a batch of
+    /// protocol writes, the block write log otherwise
+    fn protocol_write_log(
+        &mut self,
+    ) -> &mut HashMap<storage::Key, StorageModification> {
+        if self.protocol_batch {
+            &mut self.tx_write_log
+        } else {
+            &mut self.block_write_log
+        }
+    }
+
+    /// Start a batch of protocol writes. Until the batch is committed or
+    /// dropped, the protocol writes are made in the tx write log, so that they
+    /// can be dropped as a whole. Must not be called while a tx is applied.
+    pub fn begin_protocol_batch(&mut self) {
+        debug_assert!(
+            self.tx_write_log.is_empty()
+                && self.tx_precommit_write_log.is_empty(),
+            "A batch of protocol writes cannot be started while a tx is \
+             applied"
+        );
+        self.protocol_batch = true;
+    }
+
+    /// Commit the current batch of protocol writes to the block
+    pub fn commit_protocol_batch(&mut self) {
+        self.protocol_batch = false;
+        self.commit_tx();
+    }
+
+    /// Drop the current batch of protocol writes
+    pub fn drop_protocol_batch(&mut self) {
+        self.protocol_batch = false;
+        self.drop_tx();
+    }
+
     /// Initialize a new account and return the gas cost.
     pub fn init_account(
         &mut self,
@@ -653,6 +706,35 @@ impl WriteLog {
         Ok(())
     }
 
+    /// Record that the tx at the given height and index changed the balances
+    /// of the given address
+    pub fn write_tx_history_entry(
+        &mut self,
+        owner: &Address,
+        height: storage::BlockHeight,
+        index: storage::TxIndex,
+        hash: Hash,
+    ) {
+        self.tx_history
+            .insert(tx_history::key(owner, height, index), hash);
+    }
+
+    /// Record the encoded result of the tx with the given hash applied at the
+    /// given height
+    pub fn write_tx_result(
+        &mut self,
+        height: storage::BlockHeight,
+        hash: Hash,
+        result: Vec<u8>,
+    ) {
+        self.tx_results.insert((height, hash), result);
+    }
+
+    /// Prune the stored results of the txs applied up to the given height
+    pub fn prune_tx_results(&mut self, up_to: storage::BlockHeight) {
+        self.tx_results_pruned = Some(up_to);
+    }
+
     /// Remove the transaction hash because redundant
     pub(crate) fn redundant_tx_hash(&mut self, hash: &Hash) -> Result<()> {
         if !self.replay_protection.swap_remove(hash) {
@@ -975,6 +1057,35 @@ mod tests {
         }
     }
 
+    // Test that a batch of protocol writes is visible until it's dropped and
+    // only reaches the block write log when committed
+    #[test]
+    fn test_protocol_batch() {
+        let mut write_log = WriteLog::default();
+        let key1 =
+            storage::Key::parse("key1").expect("cannot parse the key string");
+        let key2 =
+            storage::Key::parse("key2").expect("cannot parse the key string");
+        let val = "val".as_bytes().to_vec();
+
+        write_log.begin_protocol_batch();
+        write_log.protocol_write(&key1, val.clone()).unwrap();
+        assert!(write_log.read(&key1).0.is_some());
+        assert!(!write_log.block_write_log.contains_key(&key1));
+        write_log.drop_protocol_batch();
+        assert!(write_log.read(&key1).0.is_none());
+
+        write_log.begin_protocol_batch();
+        write_log.protocol_write(&key2, val.clone()).unwrap();
+        write_log.commit_protocol_batch();
+        assert!(write_log.block_write_log.contains_key(&key2));
+
+        // Outside of a batch, the protocol writes go to the block write log
+        write_log.protocol_write(&key1, val).unwrap();
+        assert!(write_log.block_write_log.contains_key(&key1));
+        assert!(write_log.tx_write_log.is_empty());
+    }
+
     // Test that writing a value on top of a temporary write is not allowed
     #[test]
     fn test_write_after_temp_disallowed() {
@@ -4,10 +4,13 @@ use std::collections::BTreeMap;
 
 use namada_core::address::Address;
 use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::masp::encode_asset_type;
 use namada_core::masp_primitives::asset_type::AssetType;
 use namada_core::masp_primitives::convert::AllowedConversion;
+use namada_core::masp_primitives::ff::PrimeField;
 use namada_core::masp_primitives::merkle_tree::FrozenCommitmentTree;
 use namada_core::masp_primitives::sapling;
+use namada_core::masp_primitives::transaction::components::I128Sum;
 use namada_core::storage::Epoch;
 use namada_core::token::{Denomination, MaspDigitPos};
 use namada_macros::BorshDeserializer;
@@ -36,6 +39,129 @@ pub struct ConversionState {
     >,
 }
 
+impl ConversionState {
+    /// Get the compact representation of the conversion state
+    pub fn to_compact(&self) -> CompactConversionState {
+        let mut tokens: Vec<(Address, Denomination)> = Vec::new();
+        let mut assets: Vec<_> = self
+            .assets
+            .values()
+            .map(|((token, denom, digit), epoch, conv, pos)| {
+                let token_idx = tokens
+                    .iter()
+                    .position(|(addr, _)| addr == token)
+                    .unwrap_or_else(|| {
+                        tokens.push((token.clone(), *denom));
+                        tokens.len() - 1
+                    });
+                let conversion = (*pos < self.tree.size())
+                    .then(|| I128Sum::from(conv.clone()));
+                (*pos, CompactAsset {
+                    token: token_idx as u32,
+                    digit: *digit,
+                    epoch: *epoch,
+                    conversion,
+                })
+            })
+            .collect();
+        // Order the assets by their position in the conversion tree, the
+        // decoding entries are last
+        assets.sort_by_key(|(pos, _)| *pos);
+        CompactConversionState {
+            normed_inflation: self.normed_inflation,
+            tokens,
+            assets: assets.into_iter().map(|(_, asset)| asset).collect(),
+        }
+    }
+}
+
+/// A compact representation of the conversion state, meant to be loaded by
+/// clients. The conversion tree is left out since its leaves are the
+/// commitments to the conversions, in order, and the asset types are left out
+/// since they can be derived from the assets' data.
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+)]
+pub struct CompactConversionState {
+    /// The last amount of the native token distributed
+    pub normed_inflation: Option<u128>,
+    /// The tokens of the assets with their denominations
+    pub tokens: Vec<(Address, Denomination)>,
+    /// The assets in the order of their position in the conversion tree
+    pub assets: Vec<CompactAsset>,
+}
+
+/// An asset of the compact conversion state
+#[derive(
+    Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, BorshDeserializer,
+)]
+pub struct CompactAsset {
+    /// The index of the asset's token in the tokens of the compact state
+    pub token: u32,
+    /// The digit position of the asset
+    pub digit: MaspDigitPos,
+    /// The epoch of the asset
+    pub epoch: Epoch,
+    /// The conversion from the asset to the latest epoch, if committed to the
+    /// conversion tree. Assets without a conversion are only used for
+    /// decoding.
+    pub conversion: Option<I128Sum>,
+}
+
+impl CompactConversionState {
+    /// Rebuild the full conversion state, including the conversion tree
+    pub fn into_conversion_state(self) -> std::io::Result<ConversionState> {
+        let mut leaves = Vec::new();
+        let mut decoding = Vec::new();
+        let mut assets = BTreeMap::new();
+        for asset in self.assets {
+            let Some((token, denom)) =
+                self.tokens.get(asset.token as usize).cloned()
+            else {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "Unknown token index in the compact conversion state",
+                ));
+            };
+            let asset_type = encode_asset_type(
+                token.clone(),
+                denom,
+                asset.digit,
+                Some(asset.epoch),
+            )?;
+            let key = (token, denom, asset.digit);
+            match asset.conversion {
+                Some(conversion) => {
+                    let conv = AllowedConversion::from(conversion);
+                    let pos = leaves.len();
+                    leaves.push(sapling::Node::new(conv.cmu().to_repr()));
+                    assets.insert(asset_type, (key, asset.epoch, conv, pos));
+                }
+                None => decoding.push((asset_type, (key, asset.epoch))),
+            }
+        }
+        // Decoding entries are placed at the first uncommitted position
+        let tree_size = leaves.len();
+        for (asset_type, (key, epoch)) in decoding {
+            assets.insert(
+                asset_type,
+                (key, epoch, I128Sum::zero().into(), tree_size),
+            );
+        }
+        Ok(ConversionState {
+            normed_inflation: self.normed_inflation,
+            tree: FrozenCommitmentTree::new(&leaves),
+            assets,
+        })
+    }
+}
+
 /// Able to borrow mutable conversion state.
 pub trait WithConversionState {
     /// Borrow immutable conversion state
@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use namada_core::address::EstablishedAddressGen;
+use namada_core::address::{Address, EstablishedAddressGen};
 use namada_core::hash::{Error as HashError, Hash};
 use namada_core::storage::{
     BlockHeight, BlockResults, DbColFam, Epoch, Epochs, EthEventsQueue, Header,
@@ -123,6 +123,11 @@ pub trait DB: Debug {
         cache: Option<&Self::Cache>,
     ) -> Self;
 
+    /// Set the archive mode, in which the persisted diffs are also indexed by
+    /// key to read the values at any past height with
+    /// [`DB::read_archived_subspace_val`]
+    fn set_archive_mode(&mut self, archive_mode: bool);
+
     /// Flush data on the memory to persistent them
     fn flush(&self, wait: bool) -> Result<()>;
 
@@ -154,6 +159,19 @@ pub trait DB: Debug {
     /// Check if the given replay protection entry exists
     fn has_replay_protection_entry(&self, hash: &Hash) -> Result<bool>;
 
+    /// Read the result of the applied tx with the given hash, if it is stored
+    fn read_tx_result(&self, tx_hash: &Hash) -> Result<Option<Vec<u8>>>;
+
+    /// Read at most `limit` entries of the index of the txs that changed the
+    /// balances of the given address, backwards from the entry with the given
+    /// key, included, or from the most recent entry
+    fn read_tx_history_rev(
+        &self,
+        owner: &Address,
+        start: Option<&Key>,
+        limit: usize,
+    ) -> Result<Vec<(String, Vec<u8>)>>;
+
     /// Read the latest value for account subspace key from the DB
     fn read_subspace_val(&self, key: &Key) -> Result<Option<Vec<u8>>>;
 
@@ -168,6 +186,25 @@ pub trait DB: Debug {
         last_height: BlockHeight,
     ) -> Result<Option<Vec<u8>>>;
 
+    /// Read the value for account subspace key at the given height from the
+    /// index of the persisted diffs by key. The diffs of the key must have
+    /// been persisted since the genesis, as in archive mode.
+    fn read_archived_subspace_val(
+        &self,
+        key: &Key,
+        height: BlockHeight,
+    ) -> Result<Option<Vec<u8>>>;
+
+    /// Read the account subspace key-vals with the given prefix at the given
+    /// height from the index of the persisted diffs by key, ordered by the
+    /// storage keys. The diffs of the keys must have been persisted since the
+    /// genesis, as in archive mode.
+    fn read_archived_subspace_prefix(
+        &self,
+        prefix: &Key,
+        height: BlockHeight,
+    ) -> Result<Vec<(String, Vec<u8>)>>;
+
     /// Read the value for the account diffs at the corresponding height from
     /// the DB
     fn read_diffs_val(
@@ -255,6 +292,32 @@ pub trait DB: Debug {
         batch: &mut Self::WriteBatch,
     ) -> Result<()>;
 
+    /// Write an entry of the index of the txs that changed the balances of
+    /// addresses, together with its entry indexed by height
+    fn write_tx_history_entry(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        key: &Key,
+        tx_hash: &Hash,
+    ) -> Result<()>;
+
+    /// Write the result of a tx applied at the given height, stored under the
+    /// hash of the tx
+    fn write_tx_result(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        tx_hash: &Hash,
+        result: &[u8],
+    ) -> Result<()>;
+
+    /// Prune the results of the txs applied up to the given height
+    fn prune_tx_results(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        up_to: BlockHeight,
+    ) -> Result<()>;
+
     /// Prune non-persisted diffs that are only kept for one block for rollback
     fn prune_non_persisted_diffs(
         &mut self,
@@ -319,6 +382,17 @@ pub trait DBIter<'iter> {
 
     /// Read replay protection storage from the current bucket
     fn iter_current_replay_protection(&'iter self) -> Self::PrefixIter;
+
+    /// Read the index of the txs that changed the balances of the given
+    /// address, ordered by height and tx index
+    fn iter_tx_history(&'iter self, owner: &Address) -> Self::PrefixIter;
+
+    /// Read the results of the txs applied at the given height, or at any
+    /// height, ordered by height
+    fn iter_tx_results(
+        &'iter self,
+        height: Option<BlockHeight>,
+    ) -> Self::PrefixIter;
 }
 
 /// Atomic batch write.
@@ -6,7 +6,9 @@ pub mod conversion_state;
 mod db;
 mod error;
 pub mod mockdb;
+pub mod tx_history;
 pub mod tx_queue;
+pub mod tx_results;
 pub mod types;
 
 pub use db::{Error as DbError, Result as DbResult, *};
@@ -158,6 +160,38 @@ pub trait StorageWrite {
     }
 }
 
+/// Storage that can isolate a batch of writes from the preceding ones, so that
+/// the batch can be dropped as a whole when one of its operations fails
+pub trait StorageBatch: StorageWrite {
+    /// Start a batch of writes
+    fn begin_batch(&mut self);
+
+    /// Keep the writes of the current batch
+    fn commit_batch(&mut self);
+
+    /// Drop the writes of the current batch
+    fn drop_batch(&mut self);
+}
+
+/// Apply the writes of the given operation in a batch, which is only kept if
+/// the operation succeeds.
+pub fn in_batch<S, T>(
+    storage: &mut S,
+    op: impl FnOnce(&mut S) -> Result<T>,
+) -> Result<T>
+where
+    S: StorageBatch,
+{
+    storage.begin_batch();
+    let result = op(storage);
+    if result.is_ok() {
+        storage.commit_batch();
+    } else {
+        storage.drop_batch();
+    }
+    result
+}
+
 /// Iterate items matching the given prefix, ordered by the storage keys.
 pub fn iter_prefix_bytes<'a>(
     storage: &'a impl StorageRead,
@@ -327,6 +361,13 @@ pub mod testing {
         }
     }
 
+    impl TestStorage {
+        /// Set the epoch of the current block
+        pub fn set_block_epoch(&mut self, epoch: Epoch) {
+            self.epoch = epoch;
+        }
+    }
+
     impl StorageRead for TestStorage {
         type PrefixIter<'iter> = PrefixIter<'iter> where Self: 'iter;
 
@@ -5,6 +5,7 @@ use std::collections::{btree_map, BTreeMap};
 use std::path::Path;
 
 use itertools::Either;
+use namada_core::address::Address;
 use namada_core::borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::hash::Hash;
 use namada_core::storage::{
@@ -22,6 +23,7 @@ use crate::db::{
     BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, Error, Result, DB,
 };
 use crate::types::{KVBytes, PatternIterator, PrefixIterator};
+use crate::{tx_history, tx_results};
 
 const SUBSPACE_CF: &str = "subspace";
 
@@ -95,6 +97,8 @@ impl DB for MockDB {
         Self::default()
     }
 
+    fn set_archive_mode(&mut self, _archive_mode: bool) {}
+
     fn flush(&self, _wait: bool) -> Result<()> {
         Ok(())
     }
@@ -340,6 +344,44 @@ impl DB for MockDB {
         Ok(false)
     }
 
+    fn read_tx_result(&self, tx_hash: &Hash) -> Result<Option<Vec<u8>>> {
+        let prefix_key = Key::parse("tx_results").map_err(Error::KeyError)?;
+        let height_key = prefix_key.join(&tx_results::height_key(tx_hash));
+        let height = match self.0.borrow().get(&height_key.to_string()) {
+            Some(bytes) => decode(bytes).map_err(Error::CodingError)?,
+            None => return Ok(None),
+        };
+        let result_key =
+            prefix_key.join(&tx_results::result_key(height, tx_hash));
+        Ok(self.0.borrow().get(&result_key.to_string()).cloned())
+    }
+
+    fn read_tx_history_rev(
+        &self,
+        owner: &Address,
+        start: Option<&Key>,
+        limit: usize,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        let stripped_prefix = "tx_history/";
+        let prefix = format!("{stripped_prefix}{}/", tx_history::prefix(owner));
+        let end = match start {
+            Some(start) => format!("{stripped_prefix}{start}"),
+            // The first key after the entries of the address
+            None => format!("{stripped_prefix}{}0", tx_history::prefix(owner)),
+        };
+        Ok(self
+            .0
+            .borrow()
+            .range(..=end)
+            .rev()
+            .take_while(|(key, _)| key.starts_with(&prefix))
+            .take(limit)
+            .map(|(key, value)| {
+                (key[stripped_prefix.len()..].to_owned(), value.clone())
+            })
+            .collect())
+    }
+
     fn read_diffs_val(
         &self,
         key: &Key,
@@ -378,6 +420,33 @@ impl DB for MockDB {
         self.read_subspace_val(key)
     }
 
+    fn read_archived_subspace_val(
+        &self,
+        key: &Key,
+        _height: BlockHeight,
+    ) -> Result<Option<Vec<u8>>> {
+        tracing::warn!(
+            "read_archived_subspace_val is not implemented, will read \
+             subspace value from latest height"
+        );
+        self.read_subspace_val(key)
+    }
+
+    fn read_archived_subspace_prefix(
+        &self,
+        prefix: &Key,
+        _height: BlockHeight,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        tracing::warn!(
+            "read_archived_subspace_prefix is not implemented, will read \
+             subspace values from latest height"
+        );
+        Ok(self
+            .iter_prefix(Some(prefix))
+            .map(|(key, value, _gas)| (key, value))
+            .collect())
+    }
+
     fn write_subspace_val(
         &mut self,
         height: BlockHeight,
@@ -606,6 +675,64 @@ impl DB for MockDB {
         Ok(())
     }
 
+    fn write_tx_history_entry(
+        &mut self,
+        _batch: &mut Self::WriteBatch,
+        key: &Key,
+        tx_hash: &Hash,
+    ) -> Result<()> {
+        let prefix_key = Key::parse("tx_history").map_err(Error::KeyError)?;
+        if let Some((owner, height, index)) = tx_history::parse_key(key) {
+            let height_key =
+                prefix_key.join(&tx_history::height_key(&owner, height, index));
+            self.0.borrow_mut().insert(height_key.to_string(), vec![]);
+        }
+        let key = prefix_key.join(key);
+        self.0.borrow_mut().insert(key.to_string(), encode(tx_hash));
+        Ok(())
+    }
+
+    fn write_tx_result(
+        &mut self,
+        _batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        tx_hash: &Hash,
+        result: &[u8],
+    ) -> Result<()> {
+        let prefix_key = Key::parse("tx_results").map_err(Error::KeyError)?;
+        let height_key = prefix_key.join(&tx_results::height_key(tx_hash));
+        let result_key =
+            prefix_key.join(&tx_results::result_key(height, tx_hash));
+        let mut db = self.0.borrow_mut();
+        db.insert(height_key.to_string(), encode(&height));
+        db.insert(result_key.to_string(), result.to_vec());
+        Ok(())
+    }
+
+    fn prune_tx_results(
+        &mut self,
+        _batch: &mut Self::WriteBatch,
+        up_to: BlockHeight,
+    ) -> Result<()> {
+        let prefix_key = Key::parse("tx_results").map_err(Error::KeyError)?;
+        let pruned: Vec<(BlockHeight, Hash)> = self
+            .iter_tx_results(None)
+            .filter_map(|(key, _, _)| {
+                tx_results::parse_result_key(&Key::parse(key).ok()?)
+            })
+            .take_while(|(height, _)| *height <= up_to)
+            .collect();
+        let mut db = self.0.borrow_mut();
+        for (height, hash) in pruned {
+            let height_key = prefix_key.join(&tx_results::height_key(&hash));
+            let result_key =
+                prefix_key.join(&tx_results::result_key(height, &hash));
+            db.remove(&height_key.to_string());
+            db.remove(&result_key.to_string());
+        }
+        Ok(())
+    }
+
     fn prune_non_persisted_diffs(
         &mut self,
         _batch: &mut Self::WriteBatch,
@@ -724,6 +851,24 @@ impl<'iter> DBIter<'iter> for MockDB {
         let iter = self.0.borrow().clone().into_iter();
         MockPrefixIterator::new(MockIterator { prefix, iter }, stripped_prefix)
     }
+
+    fn iter_tx_history(&'iter self, owner: &Address) -> Self::PrefixIter {
+        let stripped_prefix = "tx_history/".to_owned();
+        let prefix = format!("{stripped_prefix}{}/", tx_history::prefix(owner));
+        let iter = self.0.borrow().clone().into_iter();
+        MockPrefixIterator::new(MockIterator { prefix, iter }, stripped_prefix)
+    }
+
+    fn iter_tx_results(
+        &'iter self,
+        height: Option<BlockHeight>,
+    ) -> Self::PrefixIter {
+        let stripped_prefix = "tx_results/".to_owned();
+        let prefix =
+            format!("{stripped_prefix}{}/", tx_results::height_prefix(height));
+        let iter = self.0.borrow().clone().into_iter();
+        MockPrefixIterator::new(MockIterator { prefix, iter }, stripped_prefix)
+    }
 }
 
 /// A prefix iterator base for the [`MockPrefixIterator`].
@@ -0,0 +1,131 @@
+//! Keys of the optional index of the transactions that changed the balances of
+//! an address. The index is local to a node and is not part of the state.
+//!
+//! Every entry of an address is also indexed by height, so that the entries
+//! of a block can be removed without scanning the whole index.
+
+use namada_core::address::Address;
+use namada_core::storage::{BlockHeight, DbKeySeg, Key, KeySeg, TxIndex};
+
+const ERROR_MSG: &str = "Cannot obtain a valid db key";
+
+/// Sub-key of the entries by height, then by address and tx index
+const BY_HEIGHT_KEY: &str = "by_height";
+
+/// Get the prefix of the index entries of the given address
+pub fn prefix(owner: &Address) -> Key {
+    Key::from(owner.to_db_key())
+}
+
+/// Get the key of the index entry of the tx at the given height and index.
+/// The keys of an address are ordered by height and then by index.
+pub fn key(owner: &Address, height: BlockHeight, index: TxIndex) -> Key {
+    prefix(owner)
+        .push(&height.0)
+        .expect(ERROR_MSG)
+        .push(&index.0)
+        .expect(ERROR_MSG)
+}
+
+/// Parse the key of an index entry into the address, the height and the index
+/// of the tx
+pub fn parse_key(key: &Key) -> Option<(Address, BlockHeight, TxIndex)> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(owner),
+            DbKeySeg::StringSeg(height),
+            DbKeySeg::StringSeg(index),
+        ] => {
+            let height = u64::parse(height.clone()).ok()?;
+            let index = u32::parse(index.clone()).ok()?;
+            Some((owner.clone(), BlockHeight(height), TxIndex(index)))
+        }
+        _ => None,
+    }
+}
+
+/// Get the prefix of the entries of the txs at the given height, indexed by
+/// height
+pub fn height_prefix(height: BlockHeight) -> Key {
+    Key::from(BY_HEIGHT_KEY.to_owned().to_db_key())
+        .push(&height.0)
+        .expect(ERROR_MSG)
+}
+
+/// Get the key of the entry of the given address and tx, indexed by height
+pub fn height_key(owner: &Address, height: BlockHeight, index: TxIndex) -> Key {
+    height_prefix(height)
+        .push(owner)
+        .expect(ERROR_MSG)
+        .push(&index.0)
+        .expect(ERROR_MSG)
+}
+
+/// Parse the key of an entry indexed by height into the key of the entry of
+/// the address
+pub fn parse_height_key(key: &Key) -> Option<Key> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(height),
+            DbKeySeg::AddressSeg(owner),
+            DbKeySeg::StringSeg(index),
+        ] if prefix == BY_HEIGHT_KEY => {
+            let height = u64::parse(height.clone()).ok()?;
+            let index = u32::parse(index.clone()).ok()?;
+            Some(self::key(owner, BlockHeight(height), TxIndex(index)))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::address::testing::established_address_1;
+
+    use super::*;
+
+    #[test]
+    fn test_tx_history_key_roundtrip() {
+        let owner = established_address_1();
+        let key = key(&owner, BlockHeight(1234), TxIndex(5));
+        assert!(key.to_string().starts_with(&format!("{}/", prefix(&owner))));
+        assert_eq!(
+            parse_key(&Key::parse(key.to_string()).unwrap()),
+            Some((owner, BlockHeight(1234), TxIndex(5)))
+        );
+    }
+
+    #[test]
+    fn test_tx_history_height_key_roundtrip() {
+        let owner = established_address_1();
+        let key = height_key(&owner, BlockHeight(1234), TxIndex(5));
+        assert!(key.to_string().starts_with(&format!(
+            "{}/",
+            height_prefix(BlockHeight(1234))
+        )));
+        assert_eq!(
+            parse_height_key(&Key::parse(key.to_string()).unwrap()),
+            Some(self::key(&owner, BlockHeight(1234), TxIndex(5)))
+        );
+        assert_eq!(parse_key(&key), None);
+    }
+
+    #[test]
+    fn test_tx_history_keys_ordering() {
+        let owner = established_address_1();
+        let mut keys = vec![
+            key(&owner, BlockHeight(256), TxIndex(0)),
+            key(&owner, BlockHeight(9), TxIndex(300)),
+            key(&owner, BlockHeight(9), TxIndex(2)),
+        ];
+        keys.sort_by_key(|key| key.to_string());
+        assert_eq!(
+            keys.iter()
+                .filter_map(parse_key)
+                .map(|(_, h, i)| (h.0, i.0))
+                .collect::<Vec<_>>(),
+            vec![(9, 2), (9, 300), (256, 0)]
+        );
+    }
+}
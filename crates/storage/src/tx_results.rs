@@ -0,0 +1,86 @@
+//! Keys of the optional store of the results of the applied txs, by tx hash.
+//! The store is local to a node and is not part of the state.
+
+use namada_core::hash::Hash;
+use namada_core::storage::{BlockHeight, DbKeySeg, Key, KeySeg};
+
+const ERROR_MSG: &str = "Cannot obtain a valid db key";
+
+/// Sub-key of the results, by height and then by tx hash
+const BY_HEIGHT_KEY: &str = "by_height";
+
+/// Sub-key of the heights of the results, by tx hash
+const BY_HASH_KEY: &str = "by_hash";
+
+/// Get the prefix of the results of the txs applied at the given height, or
+/// at any height. The results are ordered by height.
+pub fn height_prefix(height: Option<BlockHeight>) -> Key {
+    let prefix = Key::from(BY_HEIGHT_KEY.to_owned().to_db_key());
+    match height {
+        Some(height) => prefix.push(&height.0).expect(ERROR_MSG),
+        None => prefix,
+    }
+}
+
+/// Get the key of the result of the tx with the given hash applied at the
+/// given height
+pub fn result_key(height: BlockHeight, tx_hash: &Hash) -> Key {
+    height_prefix(Some(height)).push(tx_hash).expect(ERROR_MSG)
+}
+
+/// Get the key of the height at which the tx with the given hash was applied
+pub fn height_key(tx_hash: &Hash) -> Key {
+    Key::from(BY_HASH_KEY.to_owned().to_db_key())
+        .push(tx_hash)
+        .expect(ERROR_MSG)
+}
+
+/// Parse the key of a result into the height at which the tx was applied and
+/// the hash of the tx
+pub fn parse_result_key(key: &Key) -> Option<(BlockHeight, Hash)> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(height),
+            DbKeySeg::StringSeg(hash),
+        ] if prefix == BY_HEIGHT_KEY => {
+            let height = u64::parse(height.clone()).ok()?;
+            let hash = Hash::parse(hash.clone()).ok()?;
+            Some((BlockHeight(height), hash))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tx_results_keys() {
+        let hash = Hash::sha256(b"tx");
+        let key = result_key(BlockHeight(1234), &hash);
+        assert!(key
+            .to_string()
+            .starts_with(&format!("{}/", height_prefix(None))));
+        assert_eq!(
+            parse_result_key(&Key::parse(key.to_string()).unwrap()),
+            Some((BlockHeight(1234), hash))
+        );
+        assert_eq!(parse_result_key(&height_key(&hash)), None);
+
+        // The results are ordered by height
+        let mut keys = vec![
+            result_key(BlockHeight(256), &hash),
+            result_key(BlockHeight(9), &hash),
+        ];
+        keys.sort_by_key(|key| key.to_string());
+        assert_eq!(
+            keys.iter()
+                .filter_map(parse_result_key)
+                .map(|(height, _)| height.0)
+                .collect::<Vec<_>>(),
+            vec![9, 256]
+        );
+    }
+}
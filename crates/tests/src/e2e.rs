@@ -17,4 +17,5 @@ pub mod helpers;
 pub mod ibc_tests;
 pub mod ledger_tests;
 pub mod setup;
+pub mod topology;
 pub mod wallet_tests;
@@ -169,7 +169,7 @@ pub fn find_balance(
 pub fn get_actor_rpc(test: &Test, who: Who) -> String {
     let base_dir = test.get_base_dir(who);
     let tendermint_mode = match who {
-        Who::NonValidator => TendermintMode::Full,
+        Who::NonValidator | Who::FullNode(_) => TendermintMode::Full,
         Who::Validator(_) => TendermintMode::Validator,
     };
     let config =
@@ -191,7 +191,7 @@ pub fn get_node_wallet(test: &Test, who: Who) -> Wallet<FsWalletUtils> {
 /// Get the public key of the validator
 pub fn get_validator_pk(test: &Test, who: Who) -> Option<common::PublicKey> {
     let index = match who {
-        Who::NonValidator => return None,
+        Who::NonValidator | Who::FullNode(_) => return None,
         Who::Validator(i) => i,
     };
     let mut wallet = get_node_wallet(test, who);
@@ -40,6 +40,7 @@ use super::helpers::{
     wait_for_block_height, wait_for_wasm_pre_compile,
 };
 use super::setup::{set_ethereum_bridge_mode, working_dir, NamadaCmd};
+use super::topology::Topology;
 use crate::e2e::helpers::{
     epoch_sleep, find_address, find_bonded_stake, get_actor_rpc, get_epoch,
     is_debug_mode, parse_reached_epoch,
@@ -2488,3 +2489,38 @@ fn rollback() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that a network partitioned in two halves with equal stake halts and
+/// that it resumes with a consistent state once the partition is healed:
+///
+/// 1. Launch 4 validators and a full node
+/// 2. Partition them with 2 validators on each side
+/// 3. Check that no side can commit new blocks
+/// 4. Heal the partition
+/// 5. Check that all nodes commit new blocks with the same app hash
+#[test]
+fn test_partition_and_heal() -> Result<()> {
+    // 1. Launch 4 validators and a full node
+    let mut cluster = Topology::new(4, 1).launch()?;
+    cluster.assert_liveness(2, 60)?;
+
+    // 2. Partition them with 2 validators on each side
+    cluster.partition(&[
+        &[Who::Validator(0), Who::Validator(1)],
+        &[Who::Validator(2), Who::Validator(3), Who::FullNode(0)],
+    ])?;
+
+    // 3. Check that no side can commit new blocks
+    cluster.assert_halted(Who::Validator(0), 20)?;
+    cluster.assert_halted(Who::Validator(2), 20)?;
+
+    // 4. Heal the partition
+    cluster.heal()?;
+
+    // 5. Check that all nodes commit new blocks with the same app hash
+    cluster.assert_liveness(3, 120)?;
+    let height = cluster.min_height()?;
+    cluster.assert_state_equal(height)?;
+
+    Ok(())
+}
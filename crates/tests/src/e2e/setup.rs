@@ -59,6 +59,9 @@ pub const ENV_VAR_KEEP_TEMP: &str = "NAMADA_E2E_KEEP_TEMP";
 /// Env. var for temporary path
 const ENV_VAR_TEMP_PATH: &str = "NAMADA_E2E_TEMP_PATH";
 
+/// The sub-dir of the test dir with the base dirs of the additional full nodes
+pub const FULL_NODES_DIR: &str = "full-nodes";
+
 /// Env. var to use a set of prebuilt binaries. This variable holds the path to
 /// a folder.
 pub const ENV_VAR_USE_PREBUILT_BINARIES: &str =
@@ -707,12 +710,15 @@ mod macros {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Who {
     // A non-validator
     NonValidator,
     // Genesis validator with a given index, starting from `0`
     Validator(u64),
+    // An additional non-validator full node with a given index, starting from
+    // `0`. These are set up by a [`super::topology::Topology`].
+    FullNode(u64),
 }
 
 impl Test {
@@ -766,6 +772,11 @@ impl Test {
                 .path()
                 .join(utils::NET_ACCOUNTS_DIR)
                 .join(format!("validator-{}", index)),
+            Who::FullNode(index) => self
+                .test_dir
+                .path()
+                .join(FULL_NODES_DIR)
+                .join(format!("full-node-{}", index)),
         }
     }
 
@@ -1037,6 +1048,23 @@ pub fn run_cmd<I, S>(
     base_dir: impl AsRef<Path>,
     loc: String,
 ) -> Result<NamadaCmd>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    run_cmd_with_env(bin, args, timeout_sec, working_dir, base_dir, [], loc)
+}
+
+/// Same as [`run_cmd`], but with additional env vars set for the command.
+pub fn run_cmd_with_env<I, S>(
+    bin: Bin,
+    args: I,
+    timeout_sec: Option<u64>,
+    working_dir: impl AsRef<Path>,
+    base_dir: impl AsRef<Path>,
+    envs: impl IntoIterator<Item = (&'static str, String)>,
+    loc: String,
+) -> Result<NamadaCmd>
 where
     I: IntoIterator<Item = S>,
     S: AsRef<OsStr>,
@@ -1059,6 +1087,7 @@ where
         .env("NAMADA_CMT_STDOUT", "true")
         .env("CMT_LOG_LEVEL", "info")
         .env("NAMADA_LOG_COLOR", "false")
+        .envs(envs)
         .current_dir(working_dir)
         .args(["--base-dir", &base_dir.as_ref().to_string_lossy()]);
 
@@ -0,0 +1,438 @@
+//! Programmatic network topologies for E2E tests, with fault injection.
+//!
+//! A [`Topology`] sets up and launches a network of genesis validators and
+//! full nodes. The running [`Cluster`] can then be partitioned, have its nodes
+//! restarted or running with a skewed clock, and be checked for liveness and
+//! state equality, e.g.:
+//!
+//! ```ignore
+//! let mut cluster = Topology::new(4, 1).launch()?;
+//! cluster.partition(&[
+//!     &[Who::Validator(0), Who::Validator(1)],
+//!     &[Who::Validator(2), Who::Validator(3), Who::FullNode(0)],
+//! ])?;
+//! cluster.assert_halted(Who::Validator(0), 20)?;
+//! cluster.heal()?;
+//! cluster.assert_liveness(3, 60)?;
+//! ```
+
+use std::fs;
+use std::str::FromStr;
+
+use color_eyre::eyre::Result;
+use eyre::eyre;
+use namada::tendermint::block::Height;
+use namada_apps::config::utils::convert_tm_addr_to_socket_addr;
+use namada_apps::config::{ethereum_bridge, Config, COMETBFT_DIR};
+use namada_apps::facade::tendermint_config::net::Address as TendermintAddress;
+use namada_apps::facade::tendermint_rpc::{Client, HttpClient};
+
+use super::helpers::{get_actor_rpc, get_height, wait_for_block_height};
+use super::setup::{
+    self, allow_duplicate_ips, copy_wasm_to_chain_dir, default_port_offset,
+    set_ethereum_bridge_mode, sleep, update_actor_config, Bin, NamadaBgCmd,
+    Test, Who,
+};
+use crate::strings::LEDGER_STARTED;
+
+/// The first P2P port of the full nodes. Their ports are offset from it with
+/// [`default_port_offset`], like the validators' ones.
+pub const FULL_NODE_BASE_PORT: u16 = 28656;
+
+/// Env. var holding the path to the `libfaketime` shared library, required to
+/// run nodes with a skewed clock
+pub const ENV_VAR_LIBFAKETIME: &str = "NAMADA_E2E_LIBFAKETIME";
+
+/// The layout of a test network.
+#[derive(Debug, Clone)]
+pub struct Topology {
+    validators: u8,
+    full_nodes: u8,
+    min_num_of_blocks: Option<u64>,
+    consensus_timeout_commit: Option<&'static str>,
+}
+
+impl Topology {
+    /// A network with the given number of genesis validators and of
+    /// additional full nodes.
+    pub fn new(validators: u8, full_nodes: u8) -> Self {
+        Self {
+            validators,
+            full_nodes,
+            min_num_of_blocks: None,
+            consensus_timeout_commit: None,
+        }
+    }
+
+    /// Set the minimum number of blocks per epoch.
+    pub fn min_num_of_blocks(mut self, min_num_of_blocks: u64) -> Self {
+        self.min_num_of_blocks = Some(min_num_of_blocks);
+        self
+    }
+
+    /// Set the CometBFT consensus commit timeout, e.g. `"1s"`.
+    pub fn consensus_timeout_commit(mut self, timeout: &'static str) -> Self {
+        self.consensus_timeout_commit = Some(timeout);
+        self
+    }
+
+    /// Set up the network and start all of its nodes.
+    pub fn launch(self) -> Result<Cluster> {
+        let test = setup::network(
+            |mut genesis, base_dir| {
+                if let Some(min_num_of_blocks) = self.min_num_of_blocks {
+                    genesis.parameters.parameters.min_num_of_blocks =
+                        min_num_of_blocks;
+                }
+                setup::set_validators(
+                    self.validators,
+                    genesis,
+                    base_dir,
+                    default_port_offset,
+                )
+            },
+            self.consensus_timeout_commit,
+        )?;
+
+        for index in 0..self.full_nodes {
+            join_full_node(&test, index)?;
+        }
+
+        let whos = (0..u64::from(self.validators))
+            .map(Who::Validator)
+            .chain((0..u64::from(self.full_nodes)).map(Who::FullNode));
+        let mut nodes = vec![];
+        for who in whos {
+            allow_duplicate_ips(&test, &test.net.chain_id, who);
+            set_ethereum_bridge_mode(
+                &test,
+                &test.net.chain_id,
+                who,
+                ethereum_bridge::ledger::Mode::Off,
+                None,
+            );
+            let config = load_config(&test, who);
+            nodes.push(Node {
+                who,
+                p2p_port: convert_tm_addr_to_socket_addr(
+                    &config.ledger.cometbft.p2p.laddr,
+                )
+                .port(),
+                peers: config.ledger.cometbft.p2p.persistent_peers.clone(),
+                pex: config.ledger.cometbft.p2p.pex,
+                clock_skew: None,
+                process: None,
+            });
+        }
+
+        let mut cluster = Cluster { test, nodes };
+        cluster.start_all()?;
+        Ok(cluster)
+    }
+}
+
+/// A node of a [`Cluster`]
+struct Node {
+    who: Who,
+    /// The port the node listens on for P2P connections
+    p2p_port: u16,
+    /// The persistent peers the node was configured with
+    peers: Vec<TendermintAddress>,
+    /// Whether the node was configured with peer exchange
+    pex: bool,
+    /// The `libfaketime` offset of the node's clock, if any
+    clock_skew: Option<String>,
+    /// The node's process, if it's running
+    process: Option<NamadaBgCmd>,
+}
+
+/// A running test network launched from a [`Topology`].
+pub struct Cluster {
+    test: Test,
+    nodes: Vec<Node>,
+}
+
+impl Cluster {
+    /// The test setup of the network.
+    pub fn test(&self) -> &Test {
+        &self.test
+    }
+
+    /// All the nodes of the network.
+    pub fn nodes(&self) -> impl Iterator<Item = Who> + '_ {
+        self.nodes.iter().map(|node| node.who)
+    }
+
+    /// The nodes that are currently running.
+    pub fn running_nodes(&self) -> impl Iterator<Item = Who> + '_ {
+        self.nodes
+            .iter()
+            .filter(|node| node.process.is_some())
+            .map(|node| node.who)
+    }
+
+    /// Start a stopped node.
+    pub fn start(&mut self, who: Who) -> Result<()> {
+        let Self { test, nodes } = self;
+        let node = find_node(nodes, who)?;
+        if node.process.is_some() {
+            return Err(eyre!("The node {who:?} is already running"));
+        }
+        let envs = match &node.clock_skew {
+            Some(skew) => {
+                let libfaketime =
+                    std::env::var(ENV_VAR_LIBFAKETIME).map_err(|_| {
+                        eyre!(
+                            "The env var {ENV_VAR_LIBFAKETIME} must point to \
+                             the libfaketime library to skew a node's clock"
+                        )
+                    })?;
+                vec![("LD_PRELOAD", libfaketime), ("FAKETIME", skew.clone())]
+            }
+            None => vec![],
+        };
+        let loc = format!("{}:{}", std::file!(), std::line!());
+        let mut process = setup::run_cmd_with_env(
+            Bin::Node,
+            ["ledger"],
+            Some(40),
+            &test.working_dir,
+            test.get_base_dir(who),
+            envs,
+            loc,
+        )?;
+        process.exp_string(LEDGER_STARTED)?;
+        node.process = Some(process.background());
+        Ok(())
+    }
+
+    /// Stop a running node.
+    pub fn stop(&mut self, who: Who) -> Result<()> {
+        let node = find_node(&mut self.nodes, who)?;
+        let process = node
+            .process
+            .take()
+            .ok_or_else(|| eyre!("The node {who:?} is not running"))?;
+        let mut process = process.foreground();
+        process.interrupt()?;
+        process.exp_eof()?;
+        Ok(())
+    }
+
+    /// Stop and start again a running node.
+    pub fn restart(&mut self, who: Who) -> Result<()> {
+        self.stop(who)?;
+        self.start(who)
+    }
+
+    /// Restart a node with its clock offset by the given `libfaketime`
+    /// specification (e.g. `"+30s"` or `"-1m"`), or with the system clock
+    /// when `None`. Requires the env var [`ENV_VAR_LIBFAKETIME`].
+    pub fn set_clock_skew(
+        &mut self,
+        who: Who,
+        skew: Option<&str>,
+    ) -> Result<()> {
+        find_node(&mut self.nodes, who)?.clock_skew = skew.map(str::to_owned);
+        self.restart(who)
+    }
+
+    /// Split the network into the given groups of nodes, which can only
+    /// connect to the nodes of the same group. All the nodes are restarted.
+    /// Any node that is not part of a group is left isolated.
+    pub fn partition(&mut self, groups: &[&[Who]]) -> Result<()> {
+        self.stop_all()?;
+        for node in &self.nodes {
+            let group = groups
+                .iter()
+                .find(|group| group.contains(&node.who))
+                .copied()
+                .unwrap_or_default();
+            let group_ports: Vec<u16> = self
+                .nodes
+                .iter()
+                .filter(|other| group.contains(&other.who))
+                .map(|other| other.p2p_port)
+                .collect();
+            let peers = node
+                .peers
+                .iter()
+                .filter(|peer| match peer {
+                    TendermintAddress::Tcp { port, .. } => {
+                        group_ports.contains(port)
+                    }
+                    TendermintAddress::Unix { .. } => false,
+                })
+                .cloned()
+                .collect();
+            set_peers(&self.test, node.who, peers, false)?;
+        }
+        self.start_all()
+    }
+
+    /// Undo a [`Cluster::partition`], letting all the nodes connect to their
+    /// original peers again. All the nodes are restarted.
+    pub fn heal(&mut self) -> Result<()> {
+        self.stop_all()?;
+        for node in &self.nodes {
+            set_peers(&self.test, node.who, node.peers.clone(), node.pex)?;
+        }
+        self.start_all()
+    }
+
+    /// Assert that all the running nodes commit at least the given number of
+    /// new blocks before the timeout.
+    pub fn assert_liveness(
+        &self,
+        blocks: u64,
+        timeout_secs: u64,
+    ) -> Result<()> {
+        for who in self.running_nodes() {
+            let rpc = get_actor_rpc(&self.test, who);
+            let height = get_height(&self.test, &rpc)?;
+            wait_for_block_height(
+                &self.test,
+                &rpc,
+                height + blocks,
+                timeout_secs,
+            )
+            .map_err(|err| eyre!("The node {who:?} is not live: {err}"))?;
+        }
+        Ok(())
+    }
+
+    /// Assert that a running node doesn't commit any new block for the given
+    /// number of seconds.
+    pub fn assert_halted(&self, who: Who, secs: u64) -> Result<()> {
+        let rpc = get_actor_rpc(&self.test, who);
+        let height = get_height(&self.test, &rpc)?;
+        sleep(secs);
+        let new_height = get_height(&self.test, &rpc)?;
+        if new_height != height {
+            return Err(eyre!(
+                "The node {who:?} committed blocks from height {height} to \
+                 {new_height}, while it was expected to be halted"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Assert that all the running nodes agree on the app hash of the block
+    /// at the given height, which they must have all committed.
+    pub fn assert_state_equal(&self, height: u64) -> Result<()> {
+        let mut app_hashes = vec![];
+        for who in self.running_nodes() {
+            let rpc = get_actor_rpc(&self.test, who);
+            let app_hash = self.test.async_runtime().block_on(async {
+                let client = HttpClient::new(rpc.as_str())?;
+                let response = client.block(Height::try_from(height)?).await?;
+                Ok::<_, eyre::Report>(response.block.header.app_hash)
+            })?;
+            app_hashes.push((who, app_hash));
+        }
+        if let Some(((first, expected), rest)) = app_hashes.split_first() {
+            for (who, app_hash) in rest {
+                if app_hash != expected {
+                    return Err(eyre!(
+                        "The app hash at height {height} of {who:?} is \
+                         {app_hash:?}, while it's {expected:?} for {first:?}"
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The lowest last committed block height among the running nodes.
+    pub fn min_height(&self) -> Result<u64> {
+        self.running_nodes()
+            .map(|who| get_height(&self.test, &get_actor_rpc(&self.test, who)))
+            .try_fold(u64::MAX, |min, height| Ok(min.min(height?)))
+    }
+
+    fn start_all(&mut self) -> Result<()> {
+        for who in self.nodes().collect::<Vec<_>>() {
+            self.start(who)?;
+        }
+        Ok(())
+    }
+
+    fn stop_all(&mut self) -> Result<()> {
+        for who in self.running_nodes().collect::<Vec<_>>() {
+            self.stop(who)?;
+        }
+        Ok(())
+    }
+}
+
+fn find_node(nodes: &mut [Node], who: Who) -> Result<&mut Node> {
+    nodes
+        .iter_mut()
+        .find(|node| node.who == who)
+        .ok_or_else(|| eyre!("No node {who:?} in the cluster"))
+}
+
+fn load_config(test: &Test, who: Who) -> Config {
+    Config::load(test.get_base_dir(who), &test.net.chain_id, None)
+}
+
+/// Configure the persistent peers of a stopped node and forget about any
+/// other peer it may have discovered.
+fn set_peers(
+    test: &Test,
+    who: Who,
+    peers: Vec<TendermintAddress>,
+    pex: bool,
+) -> Result<()> {
+    update_actor_config(test, &test.net.chain_id, who, |config| {
+        config.ledger.cometbft.p2p.persistent_peers = peers;
+        config.ledger.cometbft.p2p.pex = pex;
+    });
+    let addr_book = test
+        .get_chain_dir(who)
+        .join(COMETBFT_DIR)
+        .join("config")
+        .join("addrbook.json");
+    if addr_book.exists() {
+        fs::remove_file(addr_book)?;
+    }
+    Ok(())
+}
+
+/// Set up the base dir of an additional full node joined to the network, with
+/// its own ports.
+fn join_full_node(test: &Test, index: u8) -> Result<()> {
+    let who = Who::FullNode(index.into());
+    let base_dir = test.get_base_dir(who);
+    fs::create_dir_all(&base_dir)?;
+    let mut join_network = setup::run_cmd(
+        Bin::Client,
+        [
+            "utils",
+            "join-network",
+            "--chain-id",
+            test.net.chain_id.as_str(),
+            "--dont-prefetch-wasm",
+        ],
+        Some(5),
+        &test.working_dir,
+        &base_dir,
+        format!("{}:{}", std::file!(), std::line!()),
+    )?;
+    join_network.exp_string("Successfully configured for chain")?;
+    join_network.assert_success();
+    copy_wasm_to_chain_dir(&test.working_dir, &base_dir, &test.net.chain_id);
+
+    let first_port = FULL_NODE_BASE_PORT + default_port_offset(index);
+    update_actor_config(test, &test.net.chain_id, who, |config| {
+        let cometbft = &mut config.ledger.cometbft;
+        let with_port = |addr: &TendermintAddress, port: u16| {
+            let ip = convert_tm_addr_to_socket_addr(addr).ip();
+            TendermintAddress::from_str(&format!("{ip}:{port}")).unwrap()
+        };
+        cometbft.p2p.laddr = with_port(&cometbft.p2p.laddr, first_port);
+        cometbft.rpc.laddr = with_port(&cometbft.rpc.laddr, first_port + 1);
+        cometbft.proxy_app = with_port(&cometbft.proxy_app, first_port + 2);
+    });
+    Ok(())
+}
@@ -1,4 +1,5 @@
 pub mod eth_bridge_pool;
+pub mod pgf;
 pub mod pos;
 
 use std::cell::RefCell;
@@ -0,0 +1,959 @@
+//! # PGF validity predicate and inflation tests
+//!
+//! The testing strategy follows the PoS VP tests and relies on
+//! [proptest state machine testing](https://github.com/AltSysrq/proptest/pull/257).
+//!
+//! The abstract state models the PGF stewards with their reward distributions
+//! (commissions), the continuous fundings and the native token balances of
+//! every account that can receive funds. The following transitions are
+//! generated:
+//!
+//! 1. Valid steward transactions (update a commission, resign). These must be
+//!    accepted by the PGF VP.
+//! 1. Invalid transactions (invalid commission, commission update without the
+//!    steward's authorization, adding a steward or writing a funding from a
+//!    tx). A transaction containing any of these must be rejected.
+//! 1. Governance actions (add/remove a steward, add/remove a continuous
+//!    funding, retro funding). These are applied by the protocol at the end of
+//!    a block, the same way as the shell executes accepted proposals.
+//! 1. A new epoch, in which the PGF inflation is applied.
+//!
+//! After every protocol change the concrete balances are checked against the
+//! model and the native token total supply must be equal to the sum of the
+//! balances of all the accounts, i.e. no funds are created or destroyed
+//! outside of the minted inflation.
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::BTreeMap;
+
+    use namada::core::address::testing::address_from_simple_seed;
+    use namada::core::address::Address;
+    use namada::core::collections::HashMap;
+    use namada::core::dec::Dec;
+    use namada::gas::VpGasMeter;
+    use namada::governance::pgf::inflation::apply_inflation;
+    use namada::governance::pgf::parameters::PgfParameters;
+    use namada::governance::pgf::storage::keys as pgf_storage;
+    use namada::governance::pgf::storage::steward::StewardDetail;
+    use namada::governance::pgf::ADDRESS as PGF_ADDRESS;
+    use namada::governance::storage::proposal::{
+        PGFInternalTarget, PGFTarget, StoragePgfFunding,
+    };
+    use namada::ledger::pgf::PgfVp;
+    use namada::parameters::update_epochs_per_year_parameter;
+    use namada::state::StorageError;
+    use namada::token;
+    use namada_tx_prelude::action::{Action, PgfAction, Write};
+    use namada_tx_prelude::transaction::pgf::UpdateStewardCommission;
+    use namada_tx_prelude::TxEnv;
+    use proptest::prelude::*;
+    use proptest::test_runner::Config;
+    use proptest_state_machine::{
+        prop_state_machine, ReferenceStateMachine, StateMachineTest,
+    };
+    use test_log::test;
+
+    use crate::native_vp::TestNativeVpEnv;
+    use crate::tx::tx_host_env;
+
+    /// The number of accounts that can become stewards or receive funds
+    const NUM_ACCOUNTS: u64 = 6;
+    /// Max number of reward recipients in a generated commission
+    const MAX_RECIPIENTS: usize = 3;
+
+    prop_state_machine! {
+        #![proptest_config(Config {
+            cases: 10,
+            verbose: 1,
+            .. Config::default()
+        })]
+        #[test]
+        /// A `StateMachineTest` implemented on `PgfState`
+        fn pgf_vp_state_machine_test(sequential 1..100 => ConcretePgfState);
+    }
+
+    /// Abstract representation of a state of the PGF system
+    #[derive(Clone, Debug)]
+    struct AbstractPgfState {
+        /// Accounts that can become stewards or receive funds
+        accounts: Vec<Address>,
+        /// The account that holds the initial native token supply
+        holder: Address,
+        /// PGF inflation rate
+        pgf_inflation_rate: Dec,
+        /// Stewards inflation rate
+        stewards_inflation_rate: Dec,
+        /// Epochs per year parameter
+        epochs_per_year: u64,
+        /// Committed stewards with their reward distribution
+        stewards: BTreeMap<Address, HashMap<Address, Dec>>,
+        /// Committed continuous fundings with the id of the proposal that
+        /// added them
+        fundings: BTreeMap<Address, (u64, token::Amount)>,
+        /// The id to be used for the next governance action
+        next_proposal_id: u64,
+        /// Native token balances
+        balances: BTreeMap<Address, token::Amount>,
+        /// Native token total supply
+        total_supply: token::Amount,
+        /// Valid actions in the current tx
+        valid_actions: Vec<ValidPgfAction>,
+        /// Invalid actions in the current tx
+        invalid_actions: Vec<InvalidPgfAction>,
+    }
+
+    /// The PGF system under test
+    #[derive(Debug)]
+    struct ConcretePgfState {
+        is_current_tx_valid: bool,
+    }
+
+    /// State machine transitions
+    #[derive(Clone, Debug)]
+    enum Transition {
+        /// Commit all the tx changes already applied in the tx env
+        CommitTx,
+        /// Switch to a new epoch and apply the PGF inflation. This will also
+        /// commit the current tx.
+        NextEpoch,
+        /// A governance action applied by the protocol. This will also commit
+        /// the current tx.
+        Governance(GovAction),
+        /// Valid steward tx action
+        Valid(ValidPgfAction),
+        /// Invalid tx action
+        Invalid(InvalidPgfAction),
+    }
+
+    #[derive(Clone, Debug)]
+    enum ValidPgfAction {
+        UpdateCommission {
+            steward: Address,
+            commission: HashMap<Address, Dec>,
+        },
+        Resign {
+            steward: Address,
+        },
+    }
+
+    #[derive(Clone, Debug)]
+    enum InvalidPgfAction {
+        /// Commission with total sum greater than 1
+        InvalidCommission {
+            steward: Address,
+            commission: HashMap<Address, Dec>,
+        },
+        /// Valid commission, but not authorized by the steward
+        UnauthorizedCommission {
+            steward: Address,
+            commission: HashMap<Address, Dec>,
+        },
+        /// Stewards can only be added via governance
+        AddSteward { address: Address },
+        /// Fundings can only be updated via governance
+        WriteFunding {
+            target: Address,
+            amount: token::Amount,
+        },
+    }
+
+    #[derive(Clone, Debug)]
+    enum GovAction {
+        AddSteward(Address),
+        RemoveSteward(Address),
+        AddFunding {
+            target: Address,
+            amount: token::Amount,
+        },
+        RemoveFunding(Address),
+        Retro {
+            target: Address,
+            amount: token::Amount,
+        },
+    }
+
+    impl ValidPgfAction {
+        fn steward(&self) -> &Address {
+            match self {
+                ValidPgfAction::UpdateCommission { steward, .. }
+                | ValidPgfAction::Resign { steward } => steward,
+            }
+        }
+    }
+
+    impl InvalidPgfAction {
+        fn address(&self) -> Option<&Address> {
+            match self {
+                InvalidPgfAction::InvalidCommission { steward, .. }
+                | InvalidPgfAction::UnauthorizedCommission {
+                    steward, ..
+                } => Some(steward),
+                InvalidPgfAction::AddSteward { address } => Some(address),
+                InvalidPgfAction::WriteFunding { .. } => None,
+            }
+        }
+    }
+
+    impl StateMachineTest for ConcretePgfState {
+        type Reference = AbstractPgfState;
+        type SystemUnderTest = Self;
+
+        fn init_test(
+            initial_state: &<Self::Reference as ReferenceStateMachine>::State,
+        ) -> Self::SystemUnderTest {
+            tx_host_env::init();
+
+            tx_host_env::with(|env| {
+                let native_token = env.state.in_mem().native_token.clone();
+                env.spawn_accounts(&initial_state.accounts);
+                env.spawn_accounts([&initial_state.holder]);
+
+                update_epochs_per_year_parameter(
+                    &mut env.state,
+                    &initial_state.epochs_per_year,
+                )
+                .unwrap();
+                PgfParameters {
+                    stewards: initial_state.stewards.keys().cloned().collect(),
+                    pgf_inflation_rate: initial_state.pgf_inflation_rate,
+                    stewards_inflation_rate: initial_state
+                        .stewards_inflation_rate,
+                }
+                .init_storage(&mut env.state)
+                .unwrap();
+                for (owner, amount) in &initial_state.balances {
+                    token::credit_tokens(
+                        &mut env.state,
+                        &native_token,
+                        owner,
+                        *amount,
+                    )
+                    .unwrap();
+                }
+
+                env.commit_genesis();
+            });
+
+            Self {
+                is_current_tx_valid: true,
+            }
+        }
+
+        fn apply(
+            mut test_state: Self::SystemUnderTest,
+            ref_state: &<Self::Reference as ReferenceStateMachine>::State,
+            transition: <Self::Reference as ReferenceStateMachine>::Transition,
+        ) -> Self::SystemUnderTest {
+            match transition {
+                Transition::CommitTx => {
+                    test_state.commit_tx();
+                    tx_host_env::commit_tx_and_block();
+                }
+                Transition::NextEpoch => {
+                    test_state.commit_tx();
+                    tx_host_env::with(|env| {
+                        env.state.commit_tx();
+                        apply_inflation(&mut env.state, |_, _, _, _| {
+                            Err(StorageError::new_const(
+                                "IBC fundings are not modelled",
+                            ))
+                        })
+                        .unwrap();
+                        env.commit_tx_and_block();
+                        env.state.in_mem_mut().block.epoch =
+                            env.state.in_mem().block.epoch.next();
+                    });
+
+                    // Post-condition:
+                    test_state.check_protocol_state(ref_state);
+                }
+                Transition::Governance(action) => {
+                    test_state.commit_tx();
+                    tx_host_env::with(|env| {
+                        env.state.commit_tx();
+                        action.apply(env, ref_state.next_proposal_id - 1);
+                        env.commit_tx_and_block();
+                    });
+
+                    // Post-condition:
+                    test_state.check_protocol_state(ref_state);
+                }
+                Transition::Valid(action) => {
+                    action.apply();
+
+                    // Post-condition:
+                    test_state.validate_transitions();
+                }
+                Transition::Invalid(action) => {
+                    test_state.is_current_tx_valid = false;
+
+                    action.apply();
+
+                    // Post-condition:
+                    test_state.validate_transitions();
+                }
+            }
+            test_state
+        }
+    }
+
+    impl ReferenceStateMachine for AbstractPgfState {
+        type State = Self;
+        type Transition = Transition;
+
+        fn init_state() -> BoxedStrategy<Self::State> {
+            let accounts: Vec<Address> =
+                (0..NUM_ACCOUNTS).map(address_from_simple_seed).collect();
+            let holder = address_from_simple_seed(NUM_ACCOUNTS);
+            (
+                proptest::sample::subsequence(accounts.clone(), 0..=2),
+                0..=20_i128,
+                0..=5_i128,
+                1..=365_u64,
+                1_000..1_000_000_u64,
+                0..1_000_u64,
+            )
+                .prop_map(
+                    move |(
+                        stewards,
+                        pgf_inflation_rate,
+                        stewards_inflation_rate,
+                        epochs_per_year,
+                        holder_balance,
+                        pgf_balance,
+                    )| {
+                        let holder_balance =
+                            token::Amount::native_whole(holder_balance);
+                        let pgf_balance =
+                            token::Amount::native_whole(pgf_balance);
+                        let stewards = stewards
+                            .into_iter()
+                            .map(|steward| {
+                                let StewardDetail {
+                                    address,
+                                    reward_distribution,
+                                } = StewardDetail::base(steward);
+                                (address, reward_distribution)
+                            })
+                            .collect();
+                        Self {
+                            accounts: accounts.clone(),
+                            holder: holder.clone(),
+                            pgf_inflation_rate: Dec::new(pgf_inflation_rate, 2)
+                                .unwrap(),
+                            stewards_inflation_rate: Dec::new(
+                                stewards_inflation_rate,
+                                2,
+                            )
+                            .unwrap(),
+                            epochs_per_year,
+                            stewards,
+                            fundings: BTreeMap::new(),
+                            next_proposal_id: 0,
+                            balances: BTreeMap::from_iter([
+                                (holder.clone(), holder_balance),
+                                (PGF_ADDRESS, pgf_balance),
+                            ]),
+                            total_supply: holder_balance
+                                .checked_add(pgf_balance)
+                                .unwrap(),
+                            valid_actions: vec![],
+                            invalid_actions: vec![],
+                        }
+                    },
+                )
+                .boxed()
+        }
+
+        fn transitions(state: &Self::State) -> BoxedStrategy<Self::Transition> {
+            let stewards = state.current_stewards();
+            let accounts = state.accounts.clone();
+            let arb_account = proptest::sample::select(accounts.clone());
+            let arb_amount =
+                (0..1_000_u64).prop_map(token::Amount::native_whole);
+            let arb_gov_action = prop_oneof![
+                arb_account.clone().prop_map(GovAction::AddSteward),
+                arb_account.clone().prop_map(GovAction::RemoveSteward),
+                (arb_account.clone(), arb_amount.clone()).prop_map(
+                    |(target, amount)| GovAction::AddFunding { target, amount }
+                ),
+                arb_account.clone().prop_map(GovAction::RemoveFunding),
+                (arb_account.clone(), arb_amount.clone()).prop_map(
+                    |(target, amount)| GovAction::Retro { target, amount }
+                ),
+            ];
+            let arb_invalid_action = prop_oneof![
+                (
+                    arb_account.clone(),
+                    arb_invalid_commission(accounts.clone())
+                )
+                    .prop_map(|(steward, commission)| {
+                        InvalidPgfAction::InvalidCommission {
+                            steward,
+                            commission,
+                        }
+                    }),
+                (arb_account.clone(), arb_valid_commission(accounts.clone()))
+                    .prop_map(|(steward, commission)| {
+                        InvalidPgfAction::UnauthorizedCommission {
+                            steward,
+                            commission,
+                        }
+                    }),
+                arb_account.clone().prop_map(|address| {
+                    InvalidPgfAction::AddSteward { address }
+                }),
+                (arb_account, arb_amount).prop_map(|(target, amount)| {
+                    InvalidPgfAction::WriteFunding { target, amount }
+                }),
+            ];
+
+            if stewards.is_empty() {
+                prop_oneof![
+                    Just(Transition::CommitTx),
+                    Just(Transition::NextEpoch),
+                    arb_gov_action.prop_map(Transition::Governance),
+                    arb_invalid_action.prop_map(Transition::Invalid),
+                ]
+                .boxed()
+            } else {
+                let arb_steward = proptest::sample::select(stewards);
+                let arb_valid_action = prop_oneof![
+                    (arb_steward.clone(), arb_valid_commission(accounts))
+                        .prop_map(|(steward, commission)| {
+                            ValidPgfAction::UpdateCommission {
+                                steward,
+                                commission,
+                            }
+                        }),
+                    arb_steward
+                        .prop_map(|steward| ValidPgfAction::Resign { steward }),
+                ];
+                prop_oneof![
+                    Just(Transition::CommitTx),
+                    Just(Transition::NextEpoch),
+                    arb_gov_action.prop_map(Transition::Governance),
+                    arb_valid_action.prop_map(Transition::Valid),
+                    arb_invalid_action.prop_map(Transition::Invalid),
+                ]
+                .boxed()
+            }
+        }
+
+        fn apply(
+            mut state: Self::State,
+            transition: &Self::Transition,
+        ) -> Self::State {
+            match transition {
+                Transition::CommitTx => {
+                    state.commit_tx();
+                }
+                Transition::NextEpoch => {
+                    state.commit_tx();
+                    state.apply_inflation();
+                }
+                Transition::Governance(action) => {
+                    state.commit_tx();
+                    state.apply_gov_action(action);
+                }
+                Transition::Valid(action) => {
+                    state.valid_actions.push(action.clone());
+                }
+                Transition::Invalid(action) => {
+                    state.invalid_actions.push(action.clone());
+                }
+            }
+            state
+        }
+
+        fn preconditions(
+            state: &Self::State,
+            transition: &Self::Transition,
+        ) -> bool {
+            match transition {
+                Transition::CommitTx
+                | Transition::NextEpoch
+                | Transition::Governance(_) => true,
+                // Every steward may only be touched by a single action in a
+                // tx, otherwise a later action could fix up (or authorize) an
+                // earlier invalid one
+                Transition::Valid(action) => {
+                    let steward = action.steward();
+                    state.current_stewards().contains(steward)
+                        && !state.is_touched_in_tx(steward)
+                        && !(matches!(action, ValidPgfAction::Resign { .. })
+                            && state.invalid_actions.iter().any(|action| {
+                                matches!(
+                                    action,
+                                    InvalidPgfAction::AddSteward { .. }
+                                )
+                            }))
+                }
+                Transition::Invalid(action) => match action {
+                    InvalidPgfAction::InvalidCommission { steward, .. }
+                    | InvalidPgfAction::UnauthorizedCommission {
+                        steward,
+                        ..
+                    } => {
+                        state.current_stewards().contains(steward)
+                            && !state.is_touched_in_tx(steward)
+                    }
+                    InvalidPgfAction::AddSteward { address } => {
+                        // A resignation in the same tx would keep the number
+                        // of stewards unchanged
+                        !state.current_stewards().contains(address)
+                            && !state.is_touched_in_tx(address)
+                            && !state.valid_actions.iter().any(|action| {
+                                matches!(action, ValidPgfAction::Resign { .. })
+                            })
+                    }
+                    InvalidPgfAction::WriteFunding { .. } => true,
+                },
+            }
+        }
+    }
+
+    impl ConcretePgfState {
+        /// Drop the current tx if it's invalid and start a new one
+        fn commit_tx(&mut self) {
+            if !self.is_current_tx_valid {
+                tx_host_env::with(|env| {
+                    env.state.drop_tx();
+                });
+            }
+            self.is_current_tx_valid = true;
+        }
+
+        fn validate_transitions(&self) {
+            // Use the tx_env to run PGF VP
+            let tx_env = tx_host_env::take();
+
+            let gas_meter = RefCell::new(VpGasMeter::new_from_tx_meter(
+                &tx_env.gas_meter.borrow(),
+            ));
+            let vp_env = TestNativeVpEnv::from_tx_env(tx_env, PGF_ADDRESS);
+            let result = vp_env.validate_tx(&gas_meter, |ctx| PgfVp { ctx });
+
+            // Put the tx_env back before checking the result
+            tx_host_env::set(vp_env.tx_env);
+
+            // The expected result depends on the current state
+            match (self.is_current_tx_valid, result) {
+                (true, Ok(())) => {}
+                (true, Err(err)) => {
+                    panic!(
+                        "Validation of valid changes must pass! Got error: \
+                         {err}"
+                    );
+                }
+                (false, Err(_)) => {}
+                (false, Ok(())) => {
+                    panic!("Validation of invalid changes must fail!");
+                }
+            }
+        }
+
+        /// Check the committed PGF state and balances against the model and
+        /// assert the conservation of funds
+        fn check_protocol_state(&self, ref_state: &AbstractPgfState) {
+            tx_host_env::with(|env| {
+                let native_token = env.state.in_mem().native_token.clone();
+
+                let stewards: BTreeMap<Address, HashMap<Address, Dec>> =
+                    pgf_storage::stewards_handle()
+                        .iter(&env.state)
+                        .unwrap()
+                        .map(|res| {
+                            let (address, detail) = res.unwrap();
+                            (address, detail.reward_distribution)
+                        })
+                        .collect();
+                assert_eq!(stewards, ref_state.stewards);
+
+                let mut balances_sum = token::Amount::zero();
+                for owner in ref_state.all_owners() {
+                    let balance =
+                        token::read_balance(&env.state, &native_token, &owner)
+                            .unwrap();
+                    assert_eq!(
+                        balance,
+                        ref_state.balance(&owner),
+                        "Unexpected balance of {owner}"
+                    );
+                    balances_sum = balances_sum.checked_add(balance).unwrap();
+                }
+
+                let total_supply =
+                    token::read_total_supply(&env.state, &native_token)
+                        .unwrap();
+                assert_eq!(total_supply, ref_state.total_supply);
+                assert_eq!(
+                    total_supply, balances_sum,
+                    "The total supply must be equal to the sum of all the \
+                     balances"
+                );
+            })
+        }
+    }
+
+    impl ValidPgfAction {
+        /// Apply the action in the tx env in the same way as the steward txs
+        fn apply(self) {
+            let ctx = tx_host_env::ctx();
+            match self {
+                ValidPgfAction::UpdateCommission {
+                    steward,
+                    commission,
+                } => {
+                    ctx.insert_verifier(&steward).unwrap();
+                    ctx.push_action(Action::Pgf(
+                        PgfAction::UpdateStewardCommission(steward.clone()),
+                    ))
+                    .unwrap();
+                    tx_host_env::pgf::update_steward_commission(
+                        ctx,
+                        UpdateStewardCommission {
+                            steward,
+                            commission,
+                        },
+                    )
+                    .unwrap();
+                }
+                ValidPgfAction::Resign { steward } => {
+                    ctx.insert_verifier(&steward).unwrap();
+                    ctx.push_action(Action::Pgf(PgfAction::ResignSteward(
+                        steward.clone(),
+                    )))
+                    .unwrap();
+                    tx_host_env::pgf::remove_steward(ctx, &steward).unwrap();
+                }
+            }
+        }
+    }
+
+    impl InvalidPgfAction {
+        fn apply(self) {
+            let ctx = tx_host_env::ctx();
+            match self {
+                InvalidPgfAction::InvalidCommission {
+                    steward,
+                    commission,
+                } => {
+                    ctx.insert_verifier(&steward).unwrap();
+                    ctx.push_action(Action::Pgf(
+                        PgfAction::UpdateStewardCommission(steward.clone()),
+                    ))
+                    .unwrap();
+                    tx_host_env::pgf::update_steward_commission(
+                        ctx,
+                        UpdateStewardCommission {
+                            steward,
+                            commission,
+                        },
+                    )
+                    .unwrap();
+                }
+                InvalidPgfAction::UnauthorizedCommission {
+                    steward,
+                    commission,
+                } => {
+                    ctx.push_action(Action::Pgf(
+                        PgfAction::UpdateStewardCommission(steward.clone()),
+                    ))
+                    .unwrap();
+                    tx_host_env::pgf::update_steward_commission(
+                        ctx,
+                        UpdateStewardCommission {
+                            steward,
+                            commission,
+                        },
+                    )
+                    .unwrap();
+                }
+                InvalidPgfAction::AddSteward { address } => {
+                    ctx.insert_verifier(&address).unwrap();
+                    ctx.push_action(Action::Pgf(
+                        PgfAction::UpdateStewardCommission(address.clone()),
+                    ))
+                    .unwrap();
+                    let StewardDetail {
+                        address,
+                        reward_distribution,
+                    } = StewardDetail::base(address);
+                    tx_host_env::pgf::update_steward_commission(
+                        ctx,
+                        UpdateStewardCommission {
+                            steward: address,
+                            commission: reward_distribution,
+                        },
+                    )
+                    .unwrap();
+                }
+                InvalidPgfAction::WriteFunding { target, amount } => {
+                    pgf_storage::fundings_handle()
+                        .insert(
+                            ctx,
+                            target.to_string(),
+                            StoragePgfFunding::new(
+                                PGFTarget::Internal(PGFInternalTarget {
+                                    target,
+                                    amount,
+                                }),
+                                u64::MAX,
+                            ),
+                        )
+                        .unwrap();
+                }
+            }
+        }
+    }
+
+    impl GovAction {
+        /// Apply the action in the same way as the shell executes accepted
+        /// PGF proposals
+        fn apply(&self, env: &mut crate::tx::TestTxEnv, proposal_id: u64) {
+            let native_token = env.state.in_mem().native_token.clone();
+            match self {
+                GovAction::AddSteward(address) => {
+                    pgf_storage::stewards_handle()
+                        .insert(
+                            &mut env.state,
+                            address.clone(),
+                            StewardDetail::base(address.clone()),
+                        )
+                        .unwrap();
+                }
+                GovAction::RemoveSteward(address) => {
+                    pgf_storage::stewards_handle()
+                        .remove(&mut env.state, address)
+                        .unwrap();
+                }
+                GovAction::AddFunding { target, amount } => {
+                    pgf_storage::fundings_handle()
+                        .insert(
+                            &mut env.state,
+                            target.to_string(),
+                            StoragePgfFunding::new(
+                                PGFTarget::Internal(PGFInternalTarget {
+                                    target: target.clone(),
+                                    amount: *amount,
+                                }),
+                                proposal_id,
+                            ),
+                        )
+                        .unwrap();
+                }
+                GovAction::RemoveFunding(target) => {
+                    pgf_storage::fundings_handle()
+                        .remove(&mut env.state, &target.to_string())
+                        .unwrap();
+                }
+                GovAction::Retro { target, amount } => {
+                    // A failed retro payment is only logged by the shell
+                    let _ = token::transfer(
+                        &mut env.state,
+                        &native_token,
+                        &PGF_ADDRESS,
+                        target,
+                        *amount,
+                    );
+                }
+            }
+        }
+    }
+
+    impl AbstractPgfState {
+        /// Commit a transaction. The valid actions are applied to the
+        /// stewards, if the transaction is valid, and discarded otherwise.
+        fn commit_tx(&mut self) {
+            let valid_actions = std::mem::take(&mut self.valid_actions);
+            if self.invalid_actions.is_empty() {
+                for action in valid_actions {
+                    match action {
+                        ValidPgfAction::UpdateCommission {
+                            steward,
+                            commission,
+                        } => {
+                            self.stewards.insert(steward, commission);
+                        }
+                        ValidPgfAction::Resign { steward } => {
+                            self.stewards.remove(&steward);
+                        }
+                    }
+                }
+            }
+            self.invalid_actions = vec![];
+        }
+
+        fn apply_gov_action(&mut self, action: &GovAction) {
+            let proposal_id = self.next_proposal_id;
+            self.next_proposal_id += 1;
+            match action {
+                GovAction::AddSteward(address) => {
+                    let StewardDetail {
+                        address,
+                        reward_distribution,
+                    } = StewardDetail::base(address.clone());
+                    self.stewards.insert(address, reward_distribution);
+                }
+                GovAction::RemoveSteward(address) => {
+                    self.stewards.remove(address);
+                }
+                GovAction::AddFunding { target, amount } => {
+                    self.fundings
+                        .insert(target.clone(), (proposal_id, *amount));
+                }
+                GovAction::RemoveFunding(target) => {
+                    self.fundings.remove(target);
+                }
+                GovAction::Retro { target, amount } => {
+                    self.transfer_from_pgf(target, *amount);
+                }
+            }
+        }
+
+        /// Model of the PGF inflation applied at the beginning of a new epoch
+        fn apply_inflation(&mut self) {
+            let total_supply = self
+                .total_supply
+                .checked_sub(self.balance(&PGF_ADDRESS))
+                .unwrap();
+
+            let pgf_inflation = total_supply
+                .mul_floor(self.pgf_inflation_rate)
+                .unwrap()
+                .checked_div_u64(self.epochs_per_year)
+                .unwrap_or_default();
+            self.credit(&PGF_ADDRESS, pgf_inflation);
+
+            // The oldest fundings are paid first
+            let mut fundings: Vec<(u64, Address, token::Amount)> = self
+                .fundings
+                .iter()
+                .map(|(target, (id, amount))| (*id, target.clone(), *amount))
+                .collect();
+            fundings.sort();
+            for (_id, target, amount) in fundings {
+                self.transfer_from_pgf(&target, amount);
+            }
+
+            let stewards_inflation = total_supply
+                .mul_floor(self.stewards_inflation_rate)
+                .unwrap()
+                .checked_div_u64(self.epochs_per_year)
+                .unwrap_or_default();
+            let rewards: Vec<(Address, token::Amount)> = self
+                .stewards
+                .values()
+                .flat_map(|distribution| {
+                    distribution.iter().map(|(address, percentage)| {
+                        (
+                            address.clone(),
+                            stewards_inflation.mul_floor(*percentage).unwrap(),
+                        )
+                    })
+                })
+                .collect();
+            for (address, reward) in rewards {
+                self.credit(&address, reward);
+            }
+        }
+
+        /// Transfer from PGF account, if it has sufficient balance
+        fn transfer_from_pgf(
+            &mut self,
+            target: &Address,
+            amount: token::Amount,
+        ) {
+            if let Some(pgf_balance) =
+                self.balance(&PGF_ADDRESS).checked_sub(amount)
+            {
+                self.balances.insert(PGF_ADDRESS, pgf_balance);
+                let target_balance =
+                    self.balance(target).checked_add(amount).unwrap();
+                self.balances.insert(target.clone(), target_balance);
+            }
+        }
+
+        /// Mint new tokens
+        fn credit(&mut self, owner: &Address, amount: token::Amount) {
+            let balance = self.balance(owner).checked_add(amount).unwrap();
+            self.balances.insert(owner.clone(), balance);
+            self.total_supply = self.total_supply.checked_add(amount).unwrap();
+        }
+
+        fn balance(&self, owner: &Address) -> token::Amount {
+            self.balances.get(owner).copied().unwrap_or_default()
+        }
+
+        /// All the accounts that may hold native tokens
+        fn all_owners(&self) -> Vec<Address> {
+            let mut owners = self.accounts.clone();
+            owners.push(self.holder.clone());
+            owners.push(PGF_ADDRESS);
+            owners
+        }
+
+        /// The stewards with the valid actions of the current tx applied
+        fn current_stewards(&self) -> Vec<Address> {
+            let mut stewards = self.stewards.clone();
+            for action in &self.valid_actions {
+                if let ValidPgfAction::Resign { steward } = action {
+                    stewards.remove(steward);
+                }
+            }
+            stewards.into_keys().collect()
+        }
+
+        /// Find if the given address has been touched by any action in the
+        /// current tx
+        fn is_touched_in_tx(&self, address: &Address) -> bool {
+            self.valid_actions
+                .iter()
+                .any(|action| action.steward() == address)
+                || self
+                    .invalid_actions
+                    .iter()
+                    .any(|action| action.address() == Some(address))
+        }
+    }
+
+    /// Generate a valid reward distribution with up to [`MAX_RECIPIENTS`]
+    /// recipients
+    fn arb_valid_commission(
+        accounts: Vec<Address>,
+    ) -> impl Strategy<Value = HashMap<Address, Dec>> {
+        proptest::sample::subsequence(accounts, 1..=MAX_RECIPIENTS)
+            .prop_flat_map(|recipients| {
+                let max_percentage = 100 / recipients.len() as i128;
+                proptest::collection::vec(0..=max_percentage, recipients.len())
+                    .prop_map(move |percentages| {
+                        recipients
+                            .iter()
+                            .cloned()
+                            .zip(percentages.into_iter().map(|percentage| {
+                                Dec::new(percentage, 2).unwrap()
+                            }))
+                            .collect()
+                    })
+            })
+    }
+
+    /// Generate a reward distribution whose percentages sum up to more than
+    /// 100%
+    fn arb_invalid_commission(
+        accounts: Vec<Address>,
+    ) -> impl Strategy<Value = HashMap<Address, Dec>> {
+        (
+            arb_valid_commission(accounts.clone()),
+            proptest::sample::select(accounts),
+            101..=200_i128,
+        )
+            .prop_map(|(mut commission, recipient, percentage)| {
+                commission.insert(recipient, Dec::new(percentage, 2).unwrap());
+                commission
+            })
+    }
+}
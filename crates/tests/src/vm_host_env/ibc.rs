@@ -717,6 +717,14 @@ pub fn received_packet(
     }
 }
 
+/// Replace the memo of the transfer in the given packet
+pub fn set_packet_memo(packet: &mut Packet, memo: &str) {
+    let mut data: PacketData =
+        serde_json::from_slice(&packet.data).expect("invalid packet data");
+    data.memo = memo.to_string().into();
+    packet.data = serde_json::to_vec(&data).unwrap();
+}
+
 pub fn msg_timeout(packet: Packet, next_sequence_recv: Sequence) -> MsgTimeout {
     MsgTimeout {
         packet,
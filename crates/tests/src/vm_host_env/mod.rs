@@ -19,7 +19,7 @@ pub mod vp;
 mod tests {
 
     use std::cell::RefCell;
-    use std::collections::BTreeSet;
+    use std::collections::{BTreeMap, BTreeSet};
     use std::panic;
     use std::rc::Rc;
 
@@ -33,13 +33,18 @@ mod tests {
     use namada::core::{address, key};
     use namada::ibc::context::nft_transfer_mod::testing::DummyNftTransferModule;
     use namada::ibc::context::transfer_mod::testing::DummyTransferModule;
+    use namada::ibc::hook::MemoHook;
     use namada::ibc::primitives::ToProto;
     use namada::ibc::Error as IbcActionError;
     use namada::ledger::ibc::storage as ibc_storage;
     use namada::ledger::native_vp::ibc::{
-        get_dummy_header as tm_dummy_header, Error as IbcError,
+        get_dummy_genesis_validator, get_dummy_header as tm_dummy_header,
+        Error as IbcError,
     };
+    use namada::ledger::parameters::storage as parameters_storage;
+    use namada::ledger::pos;
     use namada::ledger::tx_env::TxEnv;
+    use namada::proof_of_stake::OwnedPosParams;
     use namada::token::{self, Amount};
     use namada::tx::Tx;
     use namada_test_utils::TestWasms;
@@ -369,6 +374,79 @@ mod tests {
         assert_eq!(expected, pred_epochs);
     }
 
+    #[test]
+    fn test_tx_get_pos_queries() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let validator = get_dummy_genesis_validator();
+        let params = tx_host_env::with(|env| {
+            namada::parameters::init_test_storage(&mut env.state).unwrap();
+            pos::test_utils::test_init_genesis(
+                &mut env.state,
+                OwnedPosParams::default(),
+                vec![get_dummy_genesis_validator()].into_iter(),
+                storage::Epoch(0),
+            )
+            .unwrap()
+        });
+        let epoch = storage::Epoch(0);
+
+        let stake = tx::ctx()
+            .get_validator_stake(&validator.address, epoch)
+            .unwrap();
+        assert_eq!(stake, validator.tokens);
+        let state = tx::ctx()
+            .get_validator_state(&validator.address, epoch)
+            .unwrap();
+        let expected = tx_host_env::with(|env| {
+            pos::validator_state_handle(&validator.address)
+                .get(&env.state, epoch, &params)
+                .unwrap()
+        });
+        assert_eq!(state, expected);
+        assert!(state.is_some());
+        let total_stake = tx::ctx().get_total_stake(epoch).unwrap();
+        assert_eq!(total_stake, validator.tokens);
+
+        // Non-validator address has no stake and no state
+        let non_validator = address::testing::established_address_2();
+        assert!(
+            tx::ctx()
+                .get_validator_stake(&non_validator, epoch)
+                .unwrap()
+                .is_zero()
+        );
+        assert!(
+            tx::ctx()
+                .get_validator_state(&non_validator, epoch)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_tx_get_gas_remaining() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let gas_limit: u64 =
+            tx_host_env::with(|env| env.gas_meter.borrow().tx_gas_limit.into());
+        let remaining = tx::ctx().get_gas_remaining().unwrap();
+        assert!(remaining < gas_limit);
+
+        // Charged gas is reflected in the remaining gas, together with the
+        // cost of the query itself
+        tx::ctx().charge_gas(1_000).unwrap();
+        let after_charge = tx::ctx().get_gas_remaining().unwrap();
+        assert!(after_charge < remaining - 1_000);
+
+        let expected: u64 = tx_host_env::with(|env| {
+            env.gas_meter.borrow().get_available_gas().into()
+        });
+        assert_eq!(after_charge, expected);
+    }
+
     /// An example how to write a VP host environment integration test
     #[test]
     fn test_vp_host_env() {
@@ -1433,6 +1511,117 @@ mod tests {
         assert_eq!(minted, Some(Amount::from_u64(100)));
     }
 
+    #[test]
+    fn test_ibc_receive_token_with_memo_hook() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+
+        // Set the initial state before starting transactions
+        let (token, receiver) = ibc::init_storage();
+        let (client_id, _client_state, mut writes) = ibc::prepare_client();
+        let (conn_id, conn_writes) = ibc::prepare_opened_connection(&client_id);
+        writes.extend(conn_writes);
+        let (port_id, channel_id, channel_writes) =
+            ibc::prepare_opened_channel(&conn_id, false);
+        writes.extend(channel_writes);
+
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.state.write_bytes(&key, &val).expect("write error");
+            });
+        });
+
+        // Register the hook and allow the received token to pay for gas
+        let denom = format!("{}/{}/{}", port_id, channel_id, token);
+        let ibc_token = ibc::ibc_token(&denom);
+        let hook = MemoHook {
+            code_hash: Hash::sha256(b"test_hook"),
+            gas_limit: 10,
+        };
+        tx_host_env::with(|env| {
+            env.state
+                .write(
+                    &parameters_storage::get_memo_hook_key("test_hook"),
+                    &hook,
+                )
+                .expect("write error");
+            env.state
+                .write(
+                    &parameters_storage::get_gas_cost_key(),
+                    BTreeMap::from([(ibc_token.clone(), Amount::from_u64(1))]),
+                )
+                .expect("write error");
+        });
+
+        // packet with a memo requesting the hook
+        let mut packet = ibc::received_packet(
+            port_id.clone(),
+            channel_id.clone(),
+            ibc::Sequence::from(1),
+            token.to_string(),
+            &receiver,
+        );
+        ibc::set_packet_memo(
+            &mut packet,
+            r#"{"namada_hook": {"name": "test_hook", "args": {"a": 1}}}"#,
+        );
+
+        // Start a transaction to receive a packet
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+        // receive a packet with the message and prepare its hook
+        let fee_payer = address::testing::established_address_2();
+        let verifiers = Rc::new(RefCell::new(BTreeSet::<Address>::new()));
+        let mut actions = tx_host_env::ibc::ibc_actions(tx::ctx(), verifiers);
+        actions
+            .execute(&tx_data)
+            .expect("receiving the token failed");
+        let pending = actions
+            .memo_hook(&tx_data, &fee_payer)
+            .expect("preparing the hook failed")
+            .expect("the hook should be pending");
+        drop(actions);
+
+        // The hook is given what's left after paying for its gas
+        assert_eq!(pending.code_hash, hook.code_hash);
+        assert_eq!(pending.gas_limit, hook.gas_limit);
+        assert_eq!(pending.data.receiver, receiver);
+        assert_eq!(pending.data.token, ibc_token);
+        assert_eq!(pending.data.amount, Amount::from_u64(90));
+
+        // Check
+        let mut env = tx_host_env::take();
+        env.verifiers.insert(ibc_token.clone());
+        let result = ibc::validate_ibc_vp_from_tx(&env, &tx);
+        assert!(
+            result.is_ok(),
+            "Expected VP to accept the tx, got {result:?}"
+        );
+        // Check the balances
+        tx_host_env::set(env);
+        let key = ibc::balance_key_with_ibc_prefix(denom.clone(), &receiver);
+        let balance: Option<Amount> =
+            tx_host_env::with(|env| env.state.read(&key).expect("read error"));
+        assert_eq!(balance, Some(Amount::from_u64(90)));
+        let key = ibc::balance_key_with_ibc_prefix(denom, &fee_payer);
+        let balance: Option<Amount> =
+            tx_host_env::with(|env| env.state.read(&key).expect("read error"));
+        assert_eq!(balance, Some(Amount::from_u64(10)));
+    }
+
     #[test]
     fn test_ibc_receive_no_token() {
         // The environment must be initialized first
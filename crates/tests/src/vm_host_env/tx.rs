@@ -506,9 +506,22 @@ mod native_tx_host_env {
     native_host_fn!(tx_get_block_header(height: u64) -> i64);
     native_host_fn!(tx_get_block_epoch() -> u64);
     native_host_fn!(tx_get_pred_epochs() -> i64);
+    native_host_fn!(tx_get_validator_stake(
+        addr_ptr: u64,
+        addr_len: u64,
+        epoch: u64,
+    ) -> i64);
+    native_host_fn!(tx_get_validator_state(
+        addr_ptr: u64,
+        addr_len: u64,
+        epoch: u64,
+    ) -> i64);
+    native_host_fn!(tx_get_total_stake(epoch: u64) -> i64);
+    native_host_fn!(tx_get_block_randomness() -> i64);
     native_host_fn!(tx_get_native_token(result_ptr: u64));
     native_host_fn!(tx_log_string(str_ptr: u64, str_len: u64));
     native_host_fn!(tx_charge_gas(used_gas: u64));
+    native_host_fn!(tx_get_gas_remaining() -> u64);
     native_host_fn!("non-result", tx_set_commitment_sentinel());
     native_host_fn!(tx_verify_tx_section_signature(
         hash_list_ptr: u64,
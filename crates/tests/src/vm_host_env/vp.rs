@@ -350,6 +350,18 @@ mod native_vp_host_env {
     native_host_fn!(vp_get_tx_code_hash(result_ptr: u64));
     native_host_fn!(vp_get_block_epoch() -> u64);
     native_host_fn!(vp_get_pred_epochs() -> i64);
+    native_host_fn!(vp_get_validator_stake(
+        addr_ptr: u64,
+        addr_len: u64,
+        epoch: u64,
+    ) -> i64);
+    native_host_fn!(vp_get_validator_state(
+        addr_ptr: u64,
+        addr_len: u64,
+        epoch: u64,
+    ) -> i64);
+    native_host_fn!(vp_get_total_stake(epoch: u64) -> i64);
+    native_host_fn!(vp_get_block_randomness() -> i64);
     native_host_fn!(vp_get_native_token(result_ptr: u64));
     native_host_fn!(vp_eval(
             vp_code_ptr: u64,
@@ -370,5 +382,6 @@ mod native_vp_host_env {
         max_signatures_len: u64,
     ));
     native_host_fn!(vp_charge_gas(used_gas: u64));
+    native_host_fn!(vp_get_gas_remaining() -> u64);
     native_host_fn!(vp_yield_value(buf_ptr: u64, buf_len: u64));
 }
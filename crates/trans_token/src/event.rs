@@ -208,6 +208,7 @@ impl<'k> EventAttributeEntry<'k> for Descriptor<'k> {
     type ValueOwned = String;
 
     const KEY: &'static str = "token-event-descriptor";
+    const VALUE_TYPE: &'static str = "string";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -222,6 +223,7 @@ impl EventAttributeEntry<'static> for TokenAddress {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "token-address";
+    const VALUE_TYPE: &'static str = "address";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -236,6 +238,7 @@ impl EventAttributeEntry<'static> for SourceAccount {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "source-account";
+    const VALUE_TYPE: &'static str = "user-account";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -250,6 +253,7 @@ impl EventAttributeEntry<'static> for TargetAccount {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "target-account";
+    const VALUE_TYPE: &'static str = "user-account";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -264,6 +268,7 @@ impl<'amt> EventAttributeEntry<'amt> for Amount<'amt> {
     type ValueOwned = Uint;
 
     const KEY: &'static str = "amount";
+    const VALUE_TYPE: &'static str = "uint";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -278,6 +283,7 @@ impl<'bal> EventAttributeEntry<'bal> for SourcePostBalance<'bal> {
     type ValueOwned = Uint;
 
     const KEY: &'static str = "source-post-balance";
+    const VALUE_TYPE: &'static str = "uint";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -292,6 +298,7 @@ impl<'bal> EventAttributeEntry<'bal> for TargetPostBalance<'bal> {
     type ValueOwned = Uint;
 
     const KEY: &'static str = "target-post-balance";
+    const VALUE_TYPE: &'static str = "uint";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -189,6 +189,10 @@ where
         Address::Internal(InternalAddress::IbcToken(_)) => {
             return Ok(Some(0u8.into()));
         }
+        // Bond receipts are denominated like the staking token they represent
+        Address::Internal(InternalAddress::BondReceipt(_)) => {
+            return Ok(Some(token::NATIVE_MAX_DECIMAL_PLACES.into()));
+        }
         token => (denom_key(token), false),
     };
     storage.read(&key).map(|opt_denom| {
@@ -220,6 +224,61 @@ where
     storage.write(&key, denom)
 }
 
+/// Check if all the balance changes of a given token are paused by its
+/// policy.
+pub fn is_token_paused<S>(storage: &S, token: &Address) -> storage::Result<bool>
+where
+    S: StorageRead,
+{
+    let key = paused_key(token);
+    Ok(storage.read(&key)?.unwrap_or_default())
+}
+
+/// Pause or resume all the balance changes of a given token.
+pub fn write_token_paused<S>(
+    storage: &mut S,
+    token: &Address,
+    paused: bool,
+) -> storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = paused_key(token);
+    storage.write(&key, paused)
+}
+
+/// Check if the balance of a given owner is frozen by the policy of a given
+/// token.
+pub fn is_owner_frozen<S>(
+    storage: &S,
+    token: &Address,
+    owner: &Address,
+) -> storage::Result<bool>
+where
+    S: StorageRead,
+{
+    let key = frozen_key(token, owner);
+    storage.has_key(&key)
+}
+
+/// Freeze or unfreeze the balance of a given owner of a given token.
+pub fn write_owner_frozen<S>(
+    storage: &mut S,
+    token: &Address,
+    owner: &Address,
+    frozen: bool,
+) -> storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = frozen_key(token, owner);
+    if frozen {
+        storage.write(&key, ())
+    } else {
+        storage.delete(&key)
+    }
+}
+
 /// Transfer `token` from `src` to `dest`. Returns an `Err` if `src` has
 /// insufficient balance or if the transfer the `dest` would overflow (This can
 /// only happen if the total supply doesn't fit in `token::Amount`).
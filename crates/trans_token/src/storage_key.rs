@@ -13,6 +13,12 @@ pub const MINTER_STORAGE_KEY: &str = "minter";
 pub const MINTED_STORAGE_KEY: &str = "minted";
 /// Key segment for token parameters
 pub const PARAMETERS_STORAGE_KEY: &str = "parameters";
+/// Key segment for the policy of a token
+pub const POLICY_STORAGE_KEY: &str = "policy";
+/// Key segment for the pause flag of a token's policy
+pub const PAUSED_STORAGE_KEY: &str = "paused";
+/// Key segment for the frozen owners of a token's policy
+pub const FROZEN_STORAGE_KEY: &str = "frozen";
 
 /// Gets the key for the given token address, error with the given
 /// message to expect if the key is not in the address
@@ -66,6 +72,34 @@ pub fn minter_key(token_addr: &Address) -> storage::Key {
     .expect("Cannot obtain a storage key")
 }
 
+/// Obtain a storage key prefix for the policy of a token.
+pub fn policy_prefix(token_addr: &Address) -> storage::Key {
+    storage::Key::from(
+        Address::Internal(InternalAddress::Multitoken).to_db_key(),
+    )
+    .push(&token_addr.to_db_key())
+    .expect("Cannot obtain a storage key")
+    .push(&POLICY_STORAGE_KEY.to_owned())
+    .expect("Cannot obtain a storage key")
+}
+
+/// Obtain a storage key for the flag pausing all the balance changes of a
+/// token.
+pub fn paused_key(token_addr: &Address) -> storage::Key {
+    policy_prefix(token_addr)
+        .push(&PAUSED_STORAGE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Obtain a storage key marking the balance of an owner of a token as frozen.
+pub fn frozen_key(token_addr: &Address, owner: &Address) -> storage::Key {
+    policy_prefix(token_addr)
+        .push(&FROZEN_STORAGE_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&owner.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Obtain a storage key for the minted multitoken balance.
 pub fn minted_balance_key(token_addr: &Address) -> storage::Key {
     balance_prefix(token_addr)
@@ -114,6 +148,37 @@ pub fn is_any_token_parameter_key(key: &storage::Key) -> Option<&Address> {
     }
 }
 
+/// Check if the given storage key is a policy key for an unspecified token.
+/// If it is, return the token address.
+pub fn is_any_token_policy_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::AddressSeg(token),
+            DbKeySeg::StringSeg(policy),
+            DbKeySeg::StringSeg(paused),
+        ] if *addr == Address::Internal(InternalAddress::Multitoken)
+            && policy == POLICY_STORAGE_KEY
+            && paused == PAUSED_STORAGE_KEY =>
+        {
+            Some(token)
+        }
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::AddressSeg(token),
+            DbKeySeg::StringSeg(policy),
+            DbKeySeg::StringSeg(frozen),
+            DbKeySeg::AddressSeg(_owner),
+        ] if *addr == Address::Internal(InternalAddress::Multitoken)
+            && policy == POLICY_STORAGE_KEY
+            && frozen == FROZEN_STORAGE_KEY =>
+        {
+            Some(token)
+        }
+        _ => None,
+    }
+}
+
 /// Check if the given storage key is a balance key for an unspecified token. If
 /// it is, return the token and owner address.
 pub fn is_any_token_balance_key(key: &storage::Key) -> Option<[&Address; 2]> {
@@ -9,7 +9,7 @@ use std::fmt;
 use namada_core::address::Address;
 use namada_core::borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::storage::KeySeg;
-use namada_core::{address, storage};
+use namada_core::{address, storage, token};
 
 pub use crate::data::pos::{
     Bond, ClaimRewards, Redelegation, Unbond, Withdraw,
@@ -24,6 +24,7 @@ pub enum Action {
     Pos(PosAction),
     Gov(GovAction),
     Pgf(PgfAction),
+    Ibc(IbcAction),
 }
 
 /// PoS tx actions.
@@ -41,6 +42,9 @@ pub enum PosAction {
     CommissionChange(Address),
     MetadataChange(Address),
     ConsensusKeyChange(Address),
+    DelegationPoolUpdate { name: String, operator: Address },
+    DelegationPoolMembership(Address),
+    AutoRedelegationUpdate(Address),
 }
 
 /// Gov tx actions.
@@ -48,6 +52,7 @@ pub enum PosAction {
 pub enum GovAction {
     InitProposal { author: Address },
     VoteProposal { id: u64, voter: Address },
+    DelegateVotingPower { delegator: Address },
 }
 
 /// PGF tx actions.
@@ -57,6 +62,19 @@ pub enum PgfAction {
     UpdateStewardCommission(Address),
 }
 
+/// IBC tx actions.
+#[derive(Clone, Debug, BorshDeserialize, BorshSerialize)]
+pub enum IbcAction {
+    /// A memo hook called for the receiver of a transfer with the received
+    /// amount minus the fee of the hook. The hook may bond the amount of the
+    /// native token on behalf of the receiver.
+    MemoHook {
+        receiver: Address,
+        token: Address,
+        amount: token::Amount,
+    },
+}
+
 /// Read actions from temporary storage
 pub trait Read {
     /// Storage access errors
@@ -0,0 +1,194 @@
+//! Stable error codes of the causes of transactions' failures.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use num_derive::{FromPrimitive, ToPrimitive};
+use num_traits::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+
+/// The cause of the failure of a transaction, attached to the tx results and
+/// events so that clients can tell failures apart without parsing the error
+/// messages. The codes are grouped by module, in ranges of 100. The codes
+/// must not change with versions, only new ones may be added.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    FromPrimitive,
+    ToPrimitive,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+)]
+#[serde(into = "u32", try_from = "u32")]
+pub enum ErrorCode {
+    // WARN: These codes shouldn't be changed between version!
+    // =========================================================================
+    // General errors: 1-99
+    /// An error without a more specific code
+    Unknown = 1,
+    /// A section of the tx is missing
+    MissingSection = 2,
+    /// A section of the tx has an invalid signature
+    InvalidSectionSignature = 3,
+    /// The tx has already been applied
+    ReplayAttempt = 4,
+    /// The wasm code of the tx failed
+    TxRuntimeError = 5,
+    /// The tx attempted to access a forbidden internal address
+    AccessForbidden = 6,
+    /// The address of a verifier doesn't exist
+    MissingAddress = 7,
+    /// An error while reading or writing storage
+    StorageError = 8,
+    // Gas: 100-199
+    /// The gas limit of the tx was exceeded
+    OutOfGas = 100,
+    // Fees: 200-299
+    /// The fee payment failed
+    FeePayment = 200,
+    /// The fee unshielding failed
+    FeeUnshielding = 201,
+    // Proof of stake: 300-399
+    /// Rejected by the PoS VP
+    PosVpRejected = 300,
+    /// The PoS VP panicked
+    PosVpRuntime = 301,
+    // Governance: 400-499
+    /// Rejected by the governance VP
+    GovernanceVpRejected = 400,
+    /// Rejected by the PGF VP
+    PgfVpRejected = 401,
+    // Multitoken: 500-599
+    /// Rejected by the multitoken VP
+    MultitokenVpRejected = 500,
+    /// Rejected by the MASP VP
+    MaspVpRejected = 501,
+    // IBC: 600-699
+    /// Rejected by the IBC VP
+    IbcVpRejected = 600,
+    // Parameters: 700-799
+    /// Rejected by the parameters VP
+    ParametersVpRejected = 700,
+    // Ethereum bridge: 800-899
+    /// Rejected by the Ethereum bridge VP
+    EthBridgeVpRejected = 800,
+    /// Rejected by the Ethereum bridge pool VP
+    BridgePoolVpRejected = 801,
+    /// Rejected by the non-usable tokens VP
+    NutVpRejected = 802,
+    // Accounts: 900-999
+    /// Rejected by the VP of an account
+    AccountVpRejected = 900,
+    /// Rejected by the name registry VP
+    NameRegistryVpRejected = 901,
+    // =========================================================================
+    // WARN: These codes shouldn't be changed between version!
+}
+
+impl ErrorCode {
+    /// Convert to `u32`.
+    pub fn to_u32(&self) -> u32 {
+        ToPrimitive::to_u32(self).unwrap()
+    }
+
+    /// Convert from `u32`.
+    pub fn from_u32(raw: u32) -> Option<Self> {
+        FromPrimitive::from_u32(raw)
+    }
+}
+
+impl From<ErrorCode> for u32 {
+    fn from(code: ErrorCode) -> u32 {
+        code.to_u32()
+    }
+}
+
+impl TryFrom<u32> for ErrorCode {
+    type Error = String;
+
+    fn try_from(raw: u32) -> Result<Self, Self::Error> {
+        Self::from_u32(raw)
+            .ok_or_else(|| format!("Unexpected error code {raw}"))
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_u32())
+    }
+}
+
+impl FromStr for ErrorCode {
+    type Err = std::io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let raw = u32::from_str(s).map_err(|e| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+        })?;
+        Self::from_u32(raw).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Unexpected error code",
+            )
+        })
+    }
+}
+
+impl BorshSerialize for ErrorCode {
+    fn serialize<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.to_u32(), writer)
+    }
+}
+
+impl BorshDeserialize for ErrorCode {
+    fn deserialize_reader<R: std::io::Read>(
+        reader: &mut R,
+    ) -> std::io::Result<Self> {
+        let raw = <u32 as BorshDeserialize>::deserialize_reader(reader)?;
+        Self::from_u32(raw).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Unexpected error code in input",
+            )
+        })
+    }
+}
+
+#[cfg(feature = "migrations")]
+namada_macros::derive_borshdeserializer!(ErrorCode);
+
+#[cfg(test)]
+mod test {
+    use namada_core::borsh::BorshSerializeExt;
+
+    use super::*;
+
+    /// Test that the error codes are encoded as their stable numbers
+    #[test]
+    fn test_error_code_encoding() {
+        let code = ErrorCode::MultitokenVpRejected;
+        assert_eq!(code.to_string(), "500");
+        assert_eq!(ErrorCode::from_str("500").unwrap(), code);
+        assert_eq!(serde_json::to_string(&code).unwrap(), "500");
+        assert_eq!(serde_json::from_str::<ErrorCode>("500").unwrap(), code);
+        assert_eq!(code.serialize_to_vec(), 500_u32.serialize_to_vec());
+        assert_eq!(
+            ErrorCode::try_from_slice(&code.serialize_to_vec()).unwrap(),
+            code
+        );
+        assert!(ErrorCode::from_u32(0).is_none());
+        assert!(serde_json::from_str::<ErrorCode>("0").is_err());
+    }
+}
@@ -3,6 +3,8 @@
 /// txs that contain decrypted payloads or assertions of
 /// non-decryptability
 pub mod decrypted;
+/// stable error codes of failed txs
+pub mod error_code;
 pub mod eval_vp;
 /// txs to manage pgf
 pub mod pgf;
@@ -13,12 +15,13 @@ pub mod protocol;
 /// wrapper txs with encrypted payloads
 pub mod wrapper;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{self, Display};
 use std::str::FromStr;
 
 use bitflags::bitflags;
 pub use decrypted::*;
+pub use error_code::ErrorCode;
 use namada_core::address::Address;
 use namada_core::borsh::{
     BorshDeserialize, BorshSchema, BorshSerialize, BorshSerializeExt,
@@ -26,7 +29,7 @@ use namada_core::borsh::{
 use namada_core::hash::Hash;
 use namada_core::storage;
 use namada_events::Event;
-use namada_gas::{Gas, VpsGas};
+use namada_gas::{Gas, GasBreakdown, VpsGas};
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
@@ -84,6 +87,8 @@ pub enum ResultCode {
     TooLarge = 11,
     /// Tx code is not allowlisted
     TxNotAllowlisted = 12,
+    /// Invalid account nonce
+    InvalidNonce = 13,
     // =========================================================================
     // WARN: These codes shouldn't be changed between version!
 }
@@ -99,7 +104,8 @@ impl ResultCode {
             Ok | WasmRuntimeError => true,
             InvalidTx | InvalidSig | AllocationError | ReplayTx
             | InvalidChainId | ExpiredTx | TxGasLimit | FeeError
-            | InvalidVoteExtension | TooLarge | TxNotAllowlisted => false,
+            | InvalidVoteExtension | TooLarge | TxNotAllowlisted
+            | InvalidNonce => false,
         }
     }
 
@@ -190,6 +196,8 @@ pub struct TxResult {
     pub initialized_accounts: Vec<Address>,
     /// Events emitted by the transaction
     pub events: BTreeSet<Event>,
+    /// Breakdown of the gas used by the transaction
+    pub gas_breakdown: GasBreakdown,
 }
 
 impl TxResult {
@@ -197,6 +205,12 @@ impl TxResult {
     pub fn is_accepted(&self) -> bool {
         self.vps_result.rejected_vps.is_empty()
     }
+
+    /// The error code of the rejection of the tx, from the first of the VPs
+    /// that rejected it, if any
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        self.vps_result.error_codes.values().next().copied()
+    }
 }
 
 bitflags! {
@@ -256,8 +270,12 @@ pub struct VpsResult {
     pub rejected_vps: BTreeSet<Address>,
     /// The total gas used by all the VPs
     pub gas_used: VpsGas,
+    /// The gas used by each of the VPs
+    pub gas_used_by_vp: BTreeMap<Address, Gas>,
     /// Errors occurred in any of the VPs, if any
     pub errors: Vec<(Address, String)>,
+    /// The codes of the errors of the VPs that rejected the transaction
+    pub error_codes: BTreeMap<Address, ErrorCode>,
     /// Validity predicate status flags, containing info
     /// about conditions that caused their evaluation to
     /// fail.
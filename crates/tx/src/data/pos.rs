@@ -1,5 +1,7 @@
 //! Types used for PoS system transactions
 
+use std::collections::BTreeMap;
+
 use namada_core::address::Address;
 use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use namada_core::dec::Dec;
@@ -226,6 +228,78 @@ pub struct ConsensusKeyChange {
     pub consensus_key: common::PublicKey,
 }
 
+/// An update of a delegation pool, which creates the pool if it doesn't exist
+/// yet.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Hash,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct UpdateDelegationPool {
+    /// The name of the pool
+    pub name: String,
+    /// The address allowed to update the pool
+    pub operator: Address,
+    /// The target weight of each validator of the pool, summing up to 1
+    pub validators: BTreeMap<Address, Dec>,
+}
+
+/// A bond to a delegation pool.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Hash,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct DelegationPoolBond {
+    /// The name of the pool
+    pub pool: String,
+    /// The delegator's address
+    pub source: Address,
+    /// The amount of tokens
+    pub amount: token::Amount,
+}
+
+/// An update of the automatic redelegation of a delegator's bonds away from
+/// its jailed validators.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Hash,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct AutoRedelegationUpdate {
+    /// The delegator's address
+    pub delegator: Address,
+    /// The validator to redelegate the bonds to, or `None` to opt out
+    pub fallback: Option<Address>,
+    /// The number of epochs that a validator must have been jailed for
+    /// before the bonds to it are redelegated
+    pub jailed_epochs: u64,
+}
+
 #[cfg(any(test, feature = "testing"))]
 /// Tests and strategies for proof-of-stake
 pub mod tests {
@@ -81,6 +81,12 @@ pub enum ProtocolTxType {
     BridgePoolVext,
     /// Validator set update signed by some validator
     ValSetUpdateVext,
+    /// The block proposer's contribution to the block seed
+    BlockSeed,
+    /// A validator's operational metadata, signed by its protocol key
+    ValidatorOperationalMetadata,
+    /// A validator's telemetry heartbeat, signed by its protocol key
+    TelemetryVext,
 }
 
 impl ProtocolTxType {
@@ -8,7 +8,7 @@ pub mod wrapper_tx {
 
     pub use ark_bls12_381::Bls12_381 as EllipticCurve;
     use masp_primitives::transaction::Transaction;
-    use namada_core::address::{Address, MASP};
+    use namada_core::address::{Address, FEE_COLLECTOR, MASP};
     use namada_core::borsh::{
         BorshDeserialize, BorshSchema, BorshSerialize, BorshSerializeExt,
     };
@@ -74,6 +74,30 @@ pub mod wrapper_tx {
         pub token: Address,
     }
 
+    /// A counter-based nonce of an account, declared by a wrapper tx. The
+    /// nonce must match the next nonce of the account and it gets incremented
+    /// when the wrapper is applied, which provides an ordering of the
+    /// account's txs on top of the hash-based replay protection.
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        BorshSerialize,
+        BorshDeserialize,
+        BorshDeserializer,
+        BorshSchema,
+        Serialize,
+        Deserialize,
+        Eq,
+    )]
+    pub struct AccountNonce {
+        /// The account whose nonce is used. Its public keys must include the
+        /// wrapper's public key, unless it's the fee payer itself.
+        pub account: Address,
+        /// The expected nonce of the account
+        pub nonce: u64,
+    }
+
     /// Gas limit of a transaction
     #[derive(
         Debug,
@@ -158,6 +182,8 @@ pub mod wrapper_tx {
         /// The hash of the optional, unencrypted, unshielding transaction for
         /// fee payment
         pub unshield_section_hash: Option<Hash>,
+        /// The optional account nonce consumed by this tx
+        pub nonce: Option<AccountNonce>,
     }
 
     impl WrapperTx {
@@ -177,9 +203,16 @@ pub mod wrapper_tx {
                 pk,
                 gas_limit,
                 unshield_section_hash: unshield_hash,
+                nonce: None,
             }
         }
 
+        /// Declare the account nonce consumed by this tx
+        pub fn with_nonce(mut self, nonce: AccountNonce) -> Self {
+            self.nonce = Some(nonce);
+            self
+        }
+
         /// Get the address of the implicit account associated
         /// with the public key
         /// NOTE: this is safe in case someone tried to use the masp address to
@@ -195,13 +228,32 @@ pub mod wrapper_tx {
             hasher
         }
 
-        /// Generates the fee unshielding tx for execution.
+        /// Generates the fee unshielding tx for execution. The `amount` is
+        /// the one unshielded by the MASP transaction to the `target`. If the
+        /// target is the fee payer, the amount may cover only a part of the
+        /// fee. If it is the fee collector, the fee is paid straight out of
+        /// the shielded pool and the amount must cover the whole fee.
         pub fn generate_fee_unshielding(
             &self,
             transfer_code_hash: Hash,
             transfer_code_tag: Option<String>,
             unshield: Transaction,
+            amount: DenominatedAmount,
+            target: Address,
         ) -> Result<Tx, WrapperTxErr> {
+            let fee = self.get_tx_fee()?;
+            if amount.is_zero() || amount > fee {
+                return Err(WrapperTxErr::InvalidUnshield(format!(
+                    "The unshielded amount {amount} must be positive and not \
+                     exceed the fee"
+                )));
+            }
+            if target == FEE_COLLECTOR && amount < fee {
+                return Err(WrapperTxErr::InvalidUnshield(format!(
+                    "The amount {amount} paid from the shielded pool must \
+                     cover the whole fee {fee}"
+                )));
+            }
             let mut tx = Tx::from_type(TxType::Raw);
             let masp_section = tx.add_section(Section::MaspTx(unshield));
             let masp_hash = Hash(
@@ -213,9 +265,9 @@ pub mod wrapper_tx {
 
             let transfer = Transfer {
                 source: MASP,
-                target: self.fee_payer(),
+                target,
                 token: self.fee.token.clone(),
-                amount: self.get_tx_fee()?,
+                amount,
                 shielded: Some(masp_hash),
             };
             let data = transfer.serialize_to_vec();
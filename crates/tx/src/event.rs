@@ -10,7 +10,7 @@ use namada_macros::BorshDeserializer;
 use namada_migrations::*;
 
 use super::Tx;
-use crate::data::{ResultCode, TxResult};
+use crate::data::{ErrorCode, ResultCode, TxResult};
 use crate::TxType;
 
 /// Transaction event.
@@ -72,6 +72,22 @@ impl EventAttributeEntry<'static> for Code {
     type ValueOwned = Self::Value;
 
     const KEY: &'static str = "code";
+    const VALUE_TYPE: &'static str = "result-code";
+
+    fn into_value(self) -> Self::Value {
+        self.0
+    }
+}
+
+/// Extend an [`Event`] with the code of the cause of a tx failure.
+pub struct TxErrorCode(pub ErrorCode);
+
+impl EventAttributeEntry<'static> for TxErrorCode {
+    type Value = ErrorCode;
+    type ValueOwned = Self::Value;
+
+    const KEY: &'static str = "error_code";
+    const VALUE_TYPE: &'static str = "error-code";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -86,6 +102,7 @@ impl<'result> EventAttributeEntry<'result> for InnerTx<'result> {
     type ValueOwned = TxResult;
 
     const KEY: &'static str = "inner_tx";
+    const VALUE_TYPE: &'static str = "tx-result";
 
     fn into_value(self) -> Self::Value {
         self.0
@@ -12,8 +12,9 @@ pub use namada_core::key::SignableEthMessage;
 pub use namada_core::sign::SignatureIndex;
 pub use types::{
     standalone_signature, verify_standalone_sig, Authorization, Code,
-    Commitment, CompressedAuthorization, Data, DecodeError, Header,
-    MaspBuilder, Memo, Section, Signed, Signer, Tx, TxError, VerifySigError,
+    Commitment, CompressedAuthorization, Data, DataEncoding, DecodeError,
+    EncodeError, EncodedData, Header, MaspBuilder, Memo, Section, Signed,
+    Signer, Tx, TxError, VerifySigError,
 };
 
 #[cfg(test)]
@@ -1,8 +1,10 @@
 use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
+use std::fmt::Display;
 use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
+use std::str::FromStr;
 
 use data_encoding::HEXUPPER;
 use masp_primitives::transaction::builder::Builder;
@@ -64,6 +66,15 @@ pub enum DecodeError {
     InvalidTimestamp(prost_types::TimestampError),
     #[error("Couldn't serialize transaction from JSON at {0}")]
     InvalidJSONDeserialization(String),
+    #[error("Error decoding the {0} transaction data: {1}")]
+    InvalidData(DataEncoding, String),
+}
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum EncodeError {
+    #[error("Error encoding the {0} transaction data: {1}")]
+    InvalidData(DataEncoding, String),
 }
 
 /// This can be used to sign an arbitrary tx. The signature is produced and
@@ -250,6 +261,133 @@ impl Data {
     }
 }
 
+/// The encoding of the transaction data
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub enum DataEncoding {
+    /// Borsh encoding
+    #[default]
+    Borsh,
+    /// Version 1 of the canonical JSON encoding: the serde JSON
+    /// representation of the data in the compact form, with the keys of
+    /// every object sorted. It lets external toolchains build txs without a
+    /// Borsh implementation.
+    JsonV1,
+}
+
+impl DataEncoding {
+    /// Encode the given tx data
+    pub fn encode<T>(
+        &self,
+        data: &T,
+    ) -> std::result::Result<Vec<u8>, EncodeError>
+    where
+        T: BorshSerialize + Serialize,
+    {
+        match self {
+            Self::Borsh => Ok(data.serialize_to_vec()),
+            Self::JsonV1 => {
+                // `serde_json::Map` keeps its keys sorted
+                serde_json::to_value(data)
+                    .and_then(|json| serde_json::to_vec(&json))
+                    .map_err(|err| {
+                        EncodeError::InvalidData(*self, err.to_string())
+                    })
+            }
+        }
+    }
+
+    /// Decode the given tx data
+    pub fn decode<T>(
+        &self,
+        bytes: &[u8],
+    ) -> std::result::Result<T, DecodeError>
+    where
+        T: BorshDeserialize + serde::de::DeserializeOwned,
+    {
+        match self {
+            Self::Borsh => T::try_from_slice(bytes).map_err(|err| {
+                DecodeError::InvalidData(*self, err.to_string())
+            }),
+            Self::JsonV1 => serde_json::from_slice(bytes).map_err(|err| {
+                DecodeError::InvalidData(*self, err.to_string())
+            }),
+        }
+    }
+}
+
+impl Display for DataEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Borsh => write!(f, "borsh"),
+            Self::JsonV1 => write!(f, "json-v1"),
+        }
+    }
+}
+
+impl FromStr for DataEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "borsh" => Ok(Self::Borsh),
+            "json-v1" | "json" => Ok(Self::JsonV1),
+            _ => Err(format!(
+                "Unknown tx data encoding {s}, expected borsh or json-v1"
+            )),
+        }
+    }
+}
+
+/// A section representing transaction data together with the encoding of its
+/// bytes. Borsh-encoded data is normally stored in a [`Data`] section instead,
+/// so this is used for the other encodings, like canonical JSON.
+#[derive(
+    Clone,
+    Debug,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct EncodedData {
+    pub salt: [u8; 8],
+    pub encoding: DataEncoding,
+    pub data: Vec<u8>,
+}
+
+impl EncodedData {
+    /// Make a new encoded data section with the given bytes
+    pub fn new(data: Vec<u8>, encoding: DataEncoding) -> Self {
+        let Data { salt, data } = Data::new(data);
+        Self {
+            salt,
+            encoding,
+            data,
+        }
+    }
+
+    /// Hash this encoded data section
+    pub fn hash<'a>(&self, hasher: &'a mut Sha256) -> &'a mut Sha256 {
+        hasher.update(self.serialize_to_vec());
+        hasher
+    }
+}
+
 /// Error representing the case where the supplied code has incorrect hash
 pub struct CommitmentError;
 
@@ -758,6 +896,9 @@ pub enum Section {
     MaspBuilder(MaspBuilder),
     /// Wrap a header with a section for the purposes of computing hashes
     Header(Header),
+    /// Transaction data tagged with its encoding (e.g. canonical JSON), which
+    /// can be decoded with [`Tx::decode_data`]
+    EncodedData(EncodedData),
 }
 
 impl Section {
@@ -780,6 +921,7 @@ impl Section {
                 hasher
             }
             Self::Header(header) => header.hash(hasher),
+            Self::EncodedData(data) => data.hash(hasher),
         }
     }
 
@@ -1179,10 +1321,35 @@ impl Tx {
             .map(Cow::as_ref)
         {
             Some(Section::Data(data)) => Some(data.data.clone()),
+            Some(Section::EncodedData(data)) => Some(data.data.clone()),
             _ => None,
         }
     }
 
+    /// Get the encoding of the data designated by the transaction data hash
+    /// in the header
+    pub fn data_encoding(&self) -> Option<DataEncoding> {
+        match self
+            .get_section(self.data_sechash())
+            .as_ref()
+            .map(Cow::as_ref)
+        {
+            Some(Section::Data(_)) => Some(DataEncoding::Borsh),
+            Some(Section::EncodedData(data)) => Some(data.encoding),
+            _ => None,
+        }
+    }
+
+    /// Decode the data designated by the transaction data hash in the header
+    /// according to its encoding
+    pub fn decode_data<T>(&self) -> Option<std::result::Result<T, DecodeError>>
+    where
+        T: BorshDeserialize + serde::de::DeserializeOwned,
+    {
+        let encoding = self.data_encoding()?;
+        self.data().map(|data| encoding.decode(&data))
+    }
+
     /// Convert this transaction into protobufs bytes
     pub fn to_bytes(&self) -> Vec<u8> {
         use prost::Message;
@@ -1368,6 +1535,59 @@ impl Tx {
         }
     }
 
+    /// Get the signatures that [`Tx::validate_tx`] verifies, together with
+    /// the public keys and the section hashes they must be verified against.
+    /// These can be verified in a batch with other txs' signatures. Returns
+    /// `None` for raw txs and for txs whose signatures cannot be determined
+    /// without verifying them, which must then be validated individually.
+    pub fn signatures_to_validate(
+        &self,
+    ) -> Option<
+        Vec<(
+            &common::PublicKey,
+            namada_core::hash::Hash,
+            &common::Signature,
+        )>,
+    > {
+        let public_key = match &self.header.tx_type {
+            TxType::Wrapper(wrapper) => &wrapper.pk,
+            TxType::Protocol(protocol) => &protocol.pk,
+            TxType::Raw => return None,
+        };
+        let hashes = self.sechashes();
+        for section in &self.sections {
+            let Section::Authorization(signatures) = section else {
+                continue;
+            };
+            // Same section selection as in `Tx::verify_signatures`
+            if !(hashes.iter().all(|x| {
+                signatures.targets.contains(x) || section.get_hash() == *x
+            }) && signatures
+                .targets
+                .iter()
+                .all(|x| self.get_section(x).is_some()))
+            {
+                continue;
+            }
+            let Signer::PubKeys(pks) = &signatures.signer else {
+                continue;
+            };
+            let raw_hash = signatures.get_raw_hash();
+            let mut to_validate = Vec::new();
+            for (idx, pk) in pks.iter().enumerate() {
+                if pk == public_key {
+                    let sig = signatures.signatures.get(&(idx as u8))?;
+                    to_validate.push((pk, raw_hash, sig));
+                }
+            }
+            // The first section with a signature of the key is decisive
+            if !to_validate.is_empty() {
+                return Some(to_validate);
+            }
+        }
+        None
+    }
+
     /// Filter out all the sections that must not be submitted to the protocol
     /// and return them.
     pub fn protocol_filter(&mut self) -> Vec<Section> {
@@ -1490,6 +1710,25 @@ impl Tx {
         self
     }
 
+    /// Add wasm data to the tx builder in the given encoding
+    pub fn add_encoded_data<T>(
+        &mut self,
+        data: T,
+        encoding: DataEncoding,
+    ) -> std::result::Result<&mut Self, EncodeError>
+    where
+        T: BorshSerialize + Serialize,
+    {
+        let bytes = encoding.encode(&data)?;
+        let sec = match encoding {
+            DataEncoding::Borsh => Section::Data(Data::new(bytes)),
+            _ => Section::EncodedData(EncodedData::new(bytes, encoding)),
+        };
+        self.set_data_sechash(sec.get_hash());
+        self.sections.push(sec);
+        Ok(self)
+    }
+
     /// Add wrapper tx to the tx builder
     pub fn add_wrapper(
         &mut self,
@@ -1616,4 +1855,45 @@ mod test {
             Tx::try_from(tmp.as_ref()).unwrap();
         }
     }
+
+    /// Test that the tx data can be added and decoded in every encoding and
+    /// that the JSON encoding is canonical
+    #[test]
+    fn test_tx_data_encoding() {
+        use namada_core::address::testing::established_address_1;
+        use namada_core::token;
+
+        use crate::data::pos::Bond;
+
+        let bond = Bond {
+            validator: established_address_1(),
+            amount: token::Amount::native_whole(1),
+            source: None,
+        };
+        for encoding in [DataEncoding::Borsh, DataEncoding::JsonV1] {
+            let mut tx = Tx::new(ChainId::default(), None);
+            tx.add_encoded_data(bond.clone(), encoding).unwrap();
+            assert_eq!(tx.data_encoding(), Some(encoding));
+            assert_eq!(tx.data(), Some(encoding.encode(&bond).unwrap()));
+            assert_eq!(tx.decode_data::<Bond>().unwrap().unwrap(), bond);
+
+            // The data encoding survives the tx serialization
+            let tx = Tx::try_from(tx.to_bytes().as_ref()).unwrap();
+            assert_eq!(tx.decode_data::<Bond>().unwrap().unwrap(), bond);
+        }
+
+        let json = DataEncoding::JsonV1.encode(&bond).unwrap();
+        let json = std::str::from_utf8(&json).unwrap();
+        assert!(json.find("amount") < json.find("source"));
+        assert!(json.find("source") < json.find("validator"));
+        assert!(!json.contains(' '));
+        assert!(DataEncoding::Borsh.decode::<Bond>(json.as_bytes()).is_err());
+
+        assert_eq!("json-v1".parse(), Ok(DataEncoding::JsonV1));
+        assert_eq!(
+            DataEncoding::JsonV1.to_string().parse(),
+            Ok(DataEncoding::JsonV1)
+        );
+        assert!("protobuf".parse::<DataEncoding>().is_err());
+    }
 }
@@ -79,6 +79,9 @@ pub trait TxEnv: StorageRead + StorageWrite {
     /// Request to charge the provided amount of gas for the current transaction
     fn charge_gas(&mut self, used_gas: u64) -> Result<()>;
 
+    /// Get the amount of gas still available to the current transaction
+    fn get_gas_remaining(&self) -> Result<u64>;
+
     /// Get events with a given [`EventType`].
     fn get_events(&self, event_type: &EventType) -> Result<Vec<Event>>;
 
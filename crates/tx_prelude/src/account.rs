@@ -15,3 +15,94 @@ pub fn init_account(
         data.threshold,
     )
 }
+
+/// Register or replace the epoch hook of an account. The hook's tx code must
+/// be allowlisted in the `epoch_hook_allowlist` parameter to be applied.
+pub fn register_epoch_hook(
+    ctx: &mut Ctx,
+    owner: &Address,
+    hook: EpochHook,
+) -> EnvResult<()> {
+    ctx.write(&namada_account::epoch_hook_key(owner), hook)?;
+    parameters_storage::epoch_hook_owners_handle()
+        .insert(ctx, owner.clone())?;
+    Ok(())
+}
+
+/// Remove the epoch hook of an account, if any.
+pub fn remove_epoch_hook(ctx: &mut Ctx, owner: &Address) -> EnvResult<()> {
+    ctx.delete(&namada_account::epoch_hook_key(owner))?;
+    parameters_storage::epoch_hook_owners_handle().remove(ctx, owner)?;
+    Ok(())
+}
+
+/// Register, update or delete a name in the name registry. The current and the
+/// new owner of the name must authorize the update.
+pub fn update_name(
+    ctx: &mut Ctx,
+    update: &name_registry::UpdateName,
+) -> EnvResult<()> {
+    if let Some(record) = name_registry::read_name_record(ctx, &update.name)? {
+        ctx.insert_verifier(&record.owner)?;
+    }
+    ctx.insert_verifier(&update.owner)?;
+    name_registry::update_name(ctx, update)
+}
+
+/// Withdraw tokens from a faucet to the target address, within the limits of
+/// the faucet checked by its validity predicate.
+pub fn faucet_withdraw(
+    ctx: &mut Ctx,
+    withdraw: &faucet::FaucetWithdraw,
+) -> EnvResult<()> {
+    faucet::record_withdrawal(ctx, withdraw)?;
+    token::transfer(
+        ctx,
+        &withdraw.faucet,
+        &withdraw.target,
+        &withdraw.token,
+        withdraw.amount,
+    )
+}
+
+/// Set the withdrawal limits of a token of a faucet. The faucet must
+/// authorize the update.
+pub fn update_faucet_limits(
+    ctx: &mut Ctx,
+    update: &faucet::UpdateFaucetLimits,
+) -> EnvResult<()> {
+    ctx.insert_verifier(&update.faucet)?;
+    faucet::update_limits(ctx, update)
+}
+
+/// Set or remove the transfer policy of an account. The account must
+/// authorize the update.
+pub fn update_transfer_policy(
+    ctx: &mut Ctx,
+    update: &transfer_policy::UpdateTransferPolicy,
+) -> EnvResult<()> {
+    ctx.insert_verifier(&update.owner)?;
+    transfer_policy::update_transfer_policy(ctx, update)
+}
+
+/// Add or remove public keys of an account and change its threshold. The
+/// account must authorize the update with its current public keys and
+/// threshold.
+pub fn update_account_signers(
+    ctx: &mut Ctx,
+    update: &UpdateAccountSigners,
+) -> EnvResult<()> {
+    use namada_account::event::AccountEvent;
+
+    // The tx must be authorized by the account
+    ctx.insert_verifier(&update.addr)?;
+
+    let (num_public_keys, threshold) =
+        namada_account::update_account_signers(ctx, update)?;
+    ctx.emit(AccountEvent::SignersUpdate {
+        owner: update.addr.clone(),
+        num_public_keys,
+        threshold,
+    });
+    Ok(())
+}
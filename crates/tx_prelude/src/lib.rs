@@ -111,6 +111,62 @@ impl Ctx {
             namada_tx_yield_value(value.as_ptr() as _, value.len() as _);
         }
     }
+
+    /// Call another tx code, identified by its hash, with the given data
+    /// within the execution of the current tx. The called tx code must be
+    /// allowed by the tx allowlist parameter. It shares the storage writes
+    /// and the gas meter of the current tx and a failure of the called tx
+    /// fails the current tx too.
+    pub fn call_tx<T: BorshSerialize>(
+        &mut self,
+        code_hash: &hash::Hash,
+        data: &T,
+    ) -> TxResult {
+        let data = data.serialize_to_vec();
+        unsafe {
+            namada_tx_call(
+                code_hash.0.as_ptr() as _,
+                code_hash.0.len() as _,
+                data.as_ptr() as _,
+                data.len() as _,
+            )
+        };
+        Ok(())
+    }
+
+    /// Call another tx code like [`Ctx::call_tx`], except that the called tx
+    /// can use at most the given amount of gas, in whole units.
+    pub fn call_tx_with_gas_limit<T: BorshSerialize>(
+        &mut self,
+        code_hash: &hash::Hash,
+        data: &T,
+        gas_limit: u64,
+    ) -> TxResult {
+        let data = data.serialize_to_vec();
+        unsafe {
+            namada_tx_call_with_gas_limit(
+                code_hash.0.as_ptr() as _,
+                code_hash.0.len() as _,
+                data.as_ptr() as _,
+                data.len() as _,
+                gas_limit,
+            )
+        };
+        Ok(())
+    }
+
+    /// Call another tx code, identified by its name (e.g. `tx_transfer.wasm`),
+    /// with the given data. See [`Ctx::call_tx`].
+    pub fn call_tx_by_name<T: BorshSerialize>(
+        &mut self,
+        name: &str,
+        data: &T,
+    ) -> TxResult {
+        let code_hash: hash::Hash = self
+            .read(&storage::Key::wasm_hash(name))?
+            .ok_or_err_msg("No tx code with the given name")?;
+        self.call_tx(&code_hash, data)
+    }
 }
 
 /// Result of `TxEnv`, `namada_storage::StorageRead` or
@@ -369,6 +425,10 @@ impl TxEnv for Ctx {
         Ok(())
     }
 
+    fn get_gas_remaining(&self) -> Result<u64, Error> {
+        Ok(unsafe { namada_tx_get_gas_remaining() })
+    }
+
     fn get_events(&self, event_type: &EventType) -> Result<Vec<Event>, Error> {
         let event_type = event_type.to_string();
         let read_result = unsafe {
@@ -410,16 +470,19 @@ impl namada_tx::action::Write for Ctx {
     }
 }
 
+/// The result of an IBC tx: the transfer of the IBC message and the memo hook
+/// requested by a received transfer, whose fee has already been paid
+pub type IbcExecuteResult =
+    (Option<token::Transfer>, Option<ibc::hook::PendingMemoHook>);
+
 /// Execute IBC tx.
 // Temp. workaround for <https://github.com/anoma/namada/issues/1831>
-pub fn tx_ibc_execute() -> Result<Option<token::Transfer>, Error> {
+pub fn tx_ibc_execute() -> Result<IbcExecuteResult, Error> {
     let result = unsafe { namada_tx_ibc_execute() };
     match read_from_buffer(result, namada_tx_result_buffer) {
-        Some(value) => {
-            Ok(Option::<token::Transfer>::try_from_slice(&value[..])
-                .expect("The conversion shouldn't fail"))
-        }
-        None => Ok(None),
+        Some(value) => Ok(IbcExecuteResult::try_from_slice(&value[..])
+            .expect("The conversion shouldn't fail")),
+        None => Ok((None, None)),
     }
 }
 
@@ -1,26 +1,111 @@
 //! Proof of Stake system integration with functions for transactions
 
+use std::collections::BTreeMap;
+
 use namada_core::dec::Dec;
+use namada_core::hash::Hash;
 use namada_core::{key, token};
+use namada_proof_of_stake::auto_redelegation::{
+    update_auto_redelegation, AutoRedelegation,
+};
+use namada_proof_of_stake::delegation_pool::{
+    bond_to_delegation_pool, claim_delegation_pool_rewards,
+    leave_delegation_pool, read_delegation_pool, update_delegation_pool,
+    DelegationPool,
+};
 pub use namada_proof_of_stake::parameters::PosParams;
 pub use namada_proof_of_stake::queries::find_delegation_validators;
 use namada_proof_of_stake::storage::read_pos_params;
-use namada_proof_of_stake::types::{ResultSlashing, ValidatorMetaData};
+use namada_proof_of_stake::types::{
+    ResultSlashing, ValidatorMetaData, ValidatorState,
+};
 use namada_proof_of_stake::{
     become_validator, bond_tokens, change_consensus_key,
     change_validator_commission_rate, change_validator_metadata,
     claim_reward_tokens, deactivate_validator, reactivate_validator,
-    redelegate_tokens, unbond_tokens, unjail_validator, withdraw_tokens,
+    redelegate_tokens, unbond_tokens, unbond_tokens_with_redeemer,
+    unjail_validator, withdraw_tokens,
 };
 pub use namada_proof_of_stake::{parameters, types};
 use namada_tx::action::{
     Action, ClaimRewards, PosAction, Redelegation, Unbond, Withdraw, Write,
 };
-use namada_tx::data::pos::{BecomeValidator, Bond};
+use namada_tx::data::pos::{
+    AutoRedelegationUpdate, BecomeValidator, Bond, DelegationPoolBond,
+    UpdateDelegationPool,
+};
 
 use super::*;
 
 impl Ctx {
+    /// Get the bonded stake of a validator at the given epoch. The
+    /// stake is zero for an address that is not a validator.
+    pub fn get_validator_stake(
+        &self,
+        validator: &Address,
+        epoch: Epoch,
+    ) -> EnvResult<token::Amount> {
+        let validator = validator.encode();
+        let read_result = unsafe {
+            namada_tx_get_validator_stake(
+                validator.as_ptr() as _,
+                validator.len() as _,
+                epoch.0,
+            )
+        };
+        let bytes = read_from_buffer(read_result, namada_tx_result_buffer)
+            .ok_or(Error::SimpleMessage(
+                "Missing result from `namada_tx_get_validator_stake` call",
+            ))?;
+        Ok(namada_core::decode(bytes).expect("Cannot decode validator stake"))
+    }
+
+    /// Get the state of a validator at the given epoch. Returns `None`
+    /// if the address is not a validator at the given epoch.
+    pub fn get_validator_state(
+        &self,
+        validator: &Address,
+        epoch: Epoch,
+    ) -> EnvResult<Option<ValidatorState>> {
+        let validator = validator.encode();
+        let read_result = unsafe {
+            namada_tx_get_validator_state(
+                validator.as_ptr() as _,
+                validator.len() as _,
+                epoch.0,
+            )
+        };
+        Ok(read_from_buffer(read_result, namada_tx_result_buffer).map(
+            |bytes| {
+                namada_core::decode(bytes)
+                    .expect("Cannot decode validator state")
+            },
+        ))
+    }
+
+    /// Get the total bonded stake at the given epoch.
+    pub fn get_total_stake(&self, epoch: Epoch) -> EnvResult<token::Amount> {
+        let read_result = unsafe { namada_tx_get_total_stake(epoch.0) };
+        let bytes = read_from_buffer(read_result, namada_tx_result_buffer)
+            .ok_or(Error::SimpleMessage(
+                "Missing result from `namada_tx_get_total_stake` call",
+            ))?;
+        Ok(namada_core::decode(bytes).expect("Cannot decode total stake"))
+    }
+
+    /// Get the randomness of the current block, i.e. its seed. Returns `None`
+    /// if no block seed has been derived yet. The seed can be biased by the
+    /// block proposer, refer to [`namada_proof_of_stake::block_seed`] for its
+    /// limitations.
+    pub fn get_block_randomness(&self) -> EnvResult<Option<Hash>> {
+        let read_result = unsafe { namada_tx_get_block_randomness() };
+        Ok(read_from_buffer(read_result, namada_tx_result_buffer).map(
+            |bytes| {
+                namada_core::decode(bytes).expect("Cannot decode block seed")
+            },
+        ))
+    }
+
     /// Self-bond tokens to a validator when `source` is `None` or equal to
     /// the `validator` address, or delegate tokens from the `source` to the
     /// `validator`.
@@ -67,6 +152,38 @@ impl Ctx {
         unbond_tokens(self, source, validator, amount, current_epoch, false)
     }
 
+    /// Unbond tokens like [`Self::unbond_tokens`], but burn the bond receipts
+    /// of the unbonded tokens from the `redeemer` instead of the source. Both
+    /// the source and the redeemer must authorize the tx.
+    pub fn unbond_tokens_with_redeemer(
+        &mut self,
+        source: Option<&Address>,
+        validator: &Address,
+        amount: token::Amount,
+        redeemer: &Address,
+    ) -> EnvResult<ResultSlashing> {
+        let verifier = source.as_ref().unwrap_or(&validator);
+        self.insert_verifier(verifier)?;
+        self.insert_verifier(redeemer)?;
+
+        self.push_action(Action::Pos(PosAction::Unbond(Unbond {
+            validator: validator.clone(),
+            amount,
+            source: source.cloned(),
+        })))?;
+
+        let current_epoch = self.get_block_epoch()?;
+        unbond_tokens_with_redeemer(
+            self,
+            source,
+            validator,
+            amount,
+            current_epoch,
+            false,
+            Some(redeemer),
+        )
+    }
+
     /// Withdraw unbonded tokens from a self-bond to a validator when
     /// `source` is `None` or equal to the `validator` address, or withdraw
     /// unbonded tokens delegated to the `validator` to the `source`.
@@ -300,4 +417,134 @@ impl Ctx {
             current_epoch,
         )
     }
+
+    /// Create or update a delegation pool. The update must be authorized by
+    /// both the prior operator of the pool, if any, and its new operator.
+    pub fn update_delegation_pool(
+        &mut self,
+        UpdateDelegationPool {
+            name,
+            operator,
+            validators,
+        }: UpdateDelegationPool,
+    ) -> TxResult {
+        let prior_operator = read_delegation_pool(self, &name)?
+            .map(|pool| pool.operator)
+            .filter(|prior_operator| prior_operator != &operator);
+        for operator in prior_operator.iter().chain([&operator]) {
+            // The tx must be authorized by the operators of the pool
+            self.insert_verifier(operator)?;
+
+            self.push_action(Action::Pos(PosAction::DelegationPoolUpdate {
+                name: name.clone(),
+                operator: operator.clone(),
+            }))?;
+        }
+
+        update_delegation_pool(
+            self,
+            &name,
+            DelegationPool {
+                operator,
+                validators,
+            },
+        )
+    }
+
+    /// Bond tokens to a delegation pool, joining it if the source is not yet
+    /// a member. Returns the amount bonded to each of the pool's validators.
+    pub fn bond_to_delegation_pool(
+        &mut self,
+        DelegationPoolBond {
+            pool,
+            source,
+            amount,
+        }: DelegationPoolBond,
+    ) -> EnvResult<BTreeMap<Address, token::Amount>> {
+        // The tx must be authorized by the source address
+        self.insert_verifier(&source)?;
+
+        self.push_action(Action::Pos(PosAction::DelegationPoolMembership(
+            source.clone(),
+        )))?;
+
+        let current_epoch = self.get_block_epoch()?;
+        let bonds = bond_to_delegation_pool(
+            self,
+            &source,
+            &pool,
+            amount,
+            current_epoch,
+        )?;
+        for (validator, amount) in &bonds {
+            self.push_action(Action::Pos(PosAction::Bond(Bond {
+                validator: validator.clone(),
+                amount: *amount,
+                source: Some(source.clone()),
+            })))?;
+        }
+        Ok(bonds)
+    }
+
+    /// Leave the delegation pool of the given delegator, keeping its bonds.
+    pub fn leave_delegation_pool(&mut self, delegator: &Address) -> TxResult {
+        // The tx must be authorized by the source address
+        self.insert_verifier(delegator)?;
+
+        self.push_action(Action::Pos(PosAction::DelegationPoolMembership(
+            delegator.clone(),
+        )))?;
+
+        leave_delegation_pool(self, delegator)
+    }
+
+    /// Claim the rewards of all the bonds of a member of a delegation pool.
+    pub fn claim_delegation_pool_rewards(
+        &mut self,
+        delegator: &Address,
+    ) -> EnvResult<token::Amount> {
+        // The tx must be authorized by the source address
+        self.insert_verifier(delegator)?;
+
+        let current_epoch = self.get_block_epoch()?;
+        let rewards =
+            claim_delegation_pool_rewards(self, delegator, current_epoch)?;
+        let mut total = token::Amount::zero();
+        for (validator, amount) in rewards {
+            self.push_action(Action::Pos(PosAction::ClaimRewards(
+                ClaimRewards {
+                    validator,
+                    source: Some(delegator.clone()),
+                },
+            )))?;
+            total = total.checked_add(amount).ok_or(Error::SimpleMessage(
+                "Overflow in the total delegation pool rewards",
+            ))?;
+        }
+        Ok(total)
+    }
+
+    /// Opt in or out of the automatic redelegation of the bonds of a
+    /// delegator away from its jailed validators.
+    pub fn update_auto_redelegation(
+        &mut self,
+        AutoRedelegationUpdate {
+            delegator,
+            fallback,
+            jailed_epochs,
+        }: AutoRedelegationUpdate,
+    ) -> TxResult {
+        // The tx must be authorized by the delegator
+        self.insert_verifier(&delegator)?;
+
+        self.push_action(Action::Pos(PosAction::AutoRedelegationUpdate(
+            delegator.clone(),
+        )))?;
+
+        let auto_redelegation = fallback.map(|fallback| AutoRedelegation {
+            fallback,
+            jailed_epochs,
+        });
+        update_auto_redelegation(self, &delegator, auto_redelegation)
+    }
 }
@@ -110,6 +110,26 @@ pub mod tx {
         // Get the predecessor epochs
         pub fn namada_tx_get_pred_epochs() -> i64;
 
+        // Get the bonded stake of a validator at the given epoch
+        pub fn namada_tx_get_validator_stake(
+            addr_ptr: u64,
+            addr_len: u64,
+            epoch: u64,
+        ) -> i64;
+
+        // Get the state of a validator at the given epoch
+        pub fn namada_tx_get_validator_state(
+            addr_ptr: u64,
+            addr_len: u64,
+            epoch: u64,
+        ) -> i64;
+
+        // Get the total bonded stake at the given epoch
+        pub fn namada_tx_get_total_stake(epoch: u64) -> i64;
+
+        // Get the randomness of the current block, i.e. its seed
+        pub fn namada_tx_get_block_randomness() -> i64;
+
         // Get the current tx index
         pub fn namada_tx_get_tx_index() -> u32;
 
@@ -122,6 +142,9 @@ pub mod tx {
         /// Charge the provided amount of gas for the current tx
         pub fn namada_tx_charge_gas(used_gas: u64);
 
+        /// Get the amount of gas still available to the current tx
+        pub fn namada_tx_get_gas_remaining() -> u64;
+
         /// Execute IBC tx.
         // Temp. workaround for <https://github.com/anoma/namada/issues/1831>
         pub fn namada_tx_ibc_execute() -> i64;
@@ -148,6 +171,23 @@ pub mod tx {
 
         // Yield a byte array value back to the host.
         pub fn namada_tx_yield_value(buf_ptr: u64, buf_len: u64);
+
+        // Call another allowed tx code with the given data
+        pub fn namada_tx_call(
+            code_hash_ptr: u64,
+            code_hash_len: u64,
+            data_ptr: u64,
+            data_len: u64,
+        );
+
+        // Call another allowed tx code with the given data and gas limit
+        pub fn namada_tx_call_with_gas_limit(
+            code_hash_ptr: u64,
+            code_hash_len: u64,
+            data_ptr: u64,
+            data_len: u64,
+            gas_limit: u64,
+        );
     }
 }
 
@@ -225,6 +265,26 @@ pub mod vp {
         // Get the predecessor epochs
         pub fn namada_vp_get_pred_epochs() -> i64;
 
+        // Get the bonded stake of a validator at the given epoch
+        pub fn namada_vp_get_validator_stake(
+            addr_ptr: u64,
+            addr_len: u64,
+            epoch: u64,
+        ) -> i64;
+
+        // Get the state of a validator at the given epoch
+        pub fn namada_vp_get_validator_state(
+            addr_ptr: u64,
+            addr_len: u64,
+            epoch: u64,
+        ) -> i64;
+
+        // Get the total bonded stake at the given epoch
+        pub fn namada_vp_get_total_stake(epoch: u64) -> i64;
+
+        // Get the randomness of the current block, i.e. its seed
+        pub fn namada_vp_get_block_randomness() -> i64;
+
         // Get the current tx index
         pub fn namada_vp_get_tx_index() -> u32;
 
@@ -265,6 +325,9 @@ pub mod vp {
 
         /// Charge the provided amount of gas for the current vp
         pub fn namada_vp_charge_gas(used_gas: u64);
+
+        /// Get the amount of gas still available to the current vp
+        pub fn namada_vp_get_gas_remaining() -> u64;
     }
 }
 
@@ -2,6 +2,7 @@
 
 pub mod bridge_pool_roots;
 pub mod ethereum_events;
+pub mod telemetry;
 pub mod validator_set_update;
 
 use namada_core::borsh::{
@@ -35,6 +36,9 @@ pub struct VoteExtension {
     pub bridge_pool_root: Option<bridge_pool_roots::SignedVext>,
     /// Vote extension data related with validator set updates.
     pub validator_set_update: Option<validator_set_update::SignedVext>,
+    /// The validator's telemetry heartbeat, if its node opted in to send
+    /// them.
+    pub telemetry: Option<telemetry::Vext>,
 }
 
 macro_rules! ethereum_tx_data_deserialize_inner {
@@ -211,6 +215,27 @@ impl EthereumTxData {
                 BorshDeserialize::try_from_slice(data)
                     .map(EthereumTxData::ValSetUpdateVext)
             },
+            ProtocolTxType::BlockSeed => {
+                return Err(TxError::Deserialization(
+                    "The block seed protocol tx does not carry Ethereum \
+                     data"
+                        .into(),
+                ));
+            }
+            ProtocolTxType::ValidatorOperationalMetadata => {
+                return Err(TxError::Deserialization(
+                    "The validator operational metadata protocol tx does not \
+                     carry Ethereum data"
+                        .into(),
+                ));
+            }
+            ProtocolTxType::TelemetryVext => {
+                return Err(TxError::Deserialization(
+                    "The telemetry vote extension protocol tx does not carry \
+                     Ethereum data"
+                        .into(),
+                ));
+            }
         };
         deserialize(data)
             .map_err(|err| TxError::Deserialization(err.to_string()))
@@ -0,0 +1,56 @@
+//! Vote extension types for the validators' telemetry heartbeats, reporting
+//! the binary run by their nodes.
+
+use namada_core::address::Address;
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::storage::BlockHeight;
+use namada_macros::BorshDeserializer;
+#[cfg(feature = "migrations")]
+use namada_migrations::*;
+use namada_tx::{Tx, TxError};
+
+/// A vote extension containing a validator's telemetry heartbeat.
+///
+/// Unlike the Ethereum vote extensions, the heartbeat isn't signed on its
+/// own, as it is authenticated by the protocol key signature of the protocol
+/// tx that carries it.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+)]
+pub struct TelemetryVext {
+    /// The validator sending the vote extension
+    pub validator_addr: Address,
+    /// The block height at which the vote extension was sent.
+    ///
+    /// This is used as replay protection, and tells the last
+    /// height at which the validator was seen live.
+    pub block_height: BlockHeight,
+    /// The version of the validator node's binary
+    pub binary_version: String,
+    /// The commit hash from which the validator node's binary was built
+    pub commit_hash: String,
+}
+
+/// Alias for [`TelemetryVext`].
+pub type Vext = TelemetryVext;
+
+impl TryFrom<&Tx> for Vext {
+    type Error = TxError;
+
+    fn try_from(tx: &Tx) -> Result<Self, TxError> {
+        let tx_data = tx.data().ok_or_else(|| {
+            TxError::Deserialization(
+                "Expected protocol tx type associated data".into(),
+            )
+        })?;
+        Self::try_from_slice(&tx_data)
+            .map_err(|err| TxError::Deserialization(err.to_string()))
+    }
+}
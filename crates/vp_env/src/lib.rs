@@ -8,9 +8,16 @@ use masp_primitives::transaction::Transaction;
 use namada_core::address::Address;
 use namada_core::borsh::BorshDeserialize;
 use namada_core::hash::Hash;
+use namada_core::ibc::core::channel::types::commitment::{
+    AcknowledgementCommitment, PacketCommitment,
+};
+use namada_core::ibc::core::host::types::identifiers::{
+    ChannelId, PortId, Sequence,
+};
 use namada_core::storage::{BlockHeight, Epoch, Epochs, Header, Key, TxIndex};
 use namada_core::token::Transfer;
 use namada_events::{Event, EventType};
+use namada_ibc::storage::{self as ibc_storage, SequenceKind};
 use namada_ibc::{decode_message, IbcMessage};
 use namada_storage::{OptionExt, StorageRead};
 use namada_tx::Tx;
@@ -113,9 +120,9 @@ where
     ) -> Result<Transaction, namada_storage::Error> {
         let signed = tx_data;
         let data = signed.data().ok_or_err_msg("No transaction data")?;
-        let transfer = match Transfer::try_from_slice(&data) {
-            Ok(transfer) => Some(transfer),
-            Err(_) => {
+        let transfer = match signed.decode_data::<Transfer>() {
+            Some(Ok(transfer)) => Some(transfer),
+            _ => {
                 match decode_message(&data).map_err(|_| {
                     namada_storage::Error::new_const("Unknown IBC message")
                 })? {
@@ -143,6 +150,9 @@ where
     /// Charge the provided gas for the current vp
     fn charge_gas(&self, used_gas: u64) -> Result<(), namada_storage::Error>;
 
+    /// Get the amount of gas still available to the current vp
+    fn get_gas_remaining(&self) -> Result<u64, namada_storage::Error>;
+
     // ---- Methods below have default implementation via `pre/post` ----
 
     /// Storage read prior state Borsh encoded value (before tx execution). It
@@ -200,4 +210,85 @@ where
     ) -> Result<bool, namada_storage::Error> {
         self.post().has_key(key)
     }
+
+    /// Read the commitment of a sent IBC packet in prior state (before tx
+    /// execution)
+    fn ibc_packet_commitment_pre(
+        &'view self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<Option<PacketCommitment>, namada_storage::Error> {
+        ibc_storage::read_packet_commitment(
+            &self.pre(),
+            port_id,
+            channel_id,
+            sequence,
+        )
+    }
+
+    /// Read the commitment of a sent IBC packet in posterior state (after tx
+    /// execution)
+    fn ibc_packet_commitment_post(
+        &'view self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<Option<PacketCommitment>, namada_storage::Error> {
+        ibc_storage::read_packet_commitment(
+            &self.post(),
+            port_id,
+            channel_id,
+            sequence,
+        )
+    }
+
+    /// Read the acknowledgement commitment of a received IBC packet in prior
+    /// state (before tx execution)
+    fn ibc_packet_ack_pre(
+        &'view self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<Option<AcknowledgementCommitment>, namada_storage::Error> {
+        ibc_storage::read_packet_ack(&self.pre(), port_id, channel_id, sequence)
+    }
+
+    /// Read the acknowledgement commitment of a received IBC packet in
+    /// posterior state (after tx execution)
+    fn ibc_packet_ack_post(
+        &'view self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<Option<AcknowledgementCommitment>, namada_storage::Error> {
+        ibc_storage::read_packet_ack(
+            &self.post(),
+            port_id,
+            channel_id,
+            sequence,
+        )
+    }
+
+    /// Read the next IBC sequence of the given kind of a channel in prior
+    /// state (before tx execution)
+    fn ibc_next_sequence_pre(
+        &'view self,
+        kind: SequenceKind,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Sequence, namada_storage::Error> {
+        ibc_storage::read_next_sequence(&self.pre(), kind, port_id, channel_id)
+    }
+
+    /// Read the next IBC sequence of the given kind of a channel in posterior
+    /// state (after tx execution)
+    fn ibc_next_sequence_post(
+        &'view self,
+        kind: SequenceKind,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<Sequence, namada_storage::Error> {
+        ibc_storage::read_next_sequence(&self.post(), kind, port_id, channel_id)
+    }
 }
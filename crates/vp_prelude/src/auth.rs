@@ -0,0 +1,402 @@
+//! Reusable authorization rules for validity predicates.
+//!
+//! The rules can be combined with [`any_of`] and [`all_of`] to express the
+//! conditions under which a VP accepts a tx, e.g.:
+//!
+//! ```ignore
+//! let rule = any_of(&[
+//!     &value_unchanged(balance_key),
+//!     &signed_by(&owner, &tx),
+//! ]);
+//! rule.authorize(ctx, &keys_changed)
+//! ```
+//!
+//! The combinators short-circuit, so the cheapest rules should come first.
+//! Note that a [`signed_by`] rule that fails aborts the whole VP, because the
+//! host rejects invalid signatures, so it should always be the last
+//! alternative of an [`any_of`].
+
+use namada_core::storage::Key;
+use namada_tx::action::{
+    Action, Bond, ClaimRewards, GovAction, PgfAction, PosAction, Redelegation,
+    Unbond, Withdraw,
+};
+
+use super::{
+    memo_hook_bondable_amount, token, verify_signatures, Address, BTreeSet,
+    Ctx, Tx, VerifySigGadget, VpEnv, VpError, VpErrorExtResult,
+};
+use crate::{VpEnvResult, VpResult};
+
+/// A rule authorizing the storage changes of a tx, evaluated in the context
+/// `C` of a VP.
+pub trait Authorization<C: ?Sized> {
+    /// Accept the changed keys or return an error.
+    fn authorize(&self, ctx: &C, keys_changed: &BTreeSet<Key>) -> VpResult;
+}
+
+impl<C: ?Sized, F> Authorization<C> for F
+where
+    F: Fn(&C, &BTreeSet<Key>) -> VpResult,
+{
+    fn authorize(&self, ctx: &C, keys_changed: &BTreeSet<Key>) -> VpResult {
+        self(ctx, keys_changed)
+    }
+}
+
+/// A rule accepting a tx when any of the given rules accepts it. See
+/// [`any_of`].
+pub struct AnyOf<'a, C: ?Sized> {
+    rules: &'a [&'a dyn Authorization<C>],
+}
+
+/// Accept a tx when any of the given rules accepts it. The rules are
+/// evaluated in the given order and the evaluation stops at the first one
+/// that accepts the tx. An empty list of rules rejects every tx.
+pub fn any_of<'a, C: ?Sized>(
+    rules: &'a [&'a dyn Authorization<C>],
+) -> AnyOf<'a, C> {
+    AnyOf { rules }
+}
+
+impl<C: ?Sized> Authorization<C> for AnyOf<'_, C> {
+    fn authorize(&self, ctx: &C, keys_changed: &BTreeSet<Key>) -> VpResult {
+        let mut last_err = VpError::Unspecified;
+        for rule in self.rules {
+            match rule.authorize(ctx, keys_changed) {
+                Ok(()) => return Ok(()),
+                Err(err) => last_err = err,
+            }
+        }
+        Err(last_err)
+    }
+}
+
+/// A rule accepting a tx when all of the given rules accept it. See
+/// [`all_of`].
+pub struct AllOf<'a, C: ?Sized> {
+    rules: &'a [&'a dyn Authorization<C>],
+}
+
+/// Accept a tx when all of the given rules accept it. The rules are evaluated
+/// in the given order and the evaluation stops at the first one that rejects
+/// the tx. An empty list of rules accepts every tx.
+pub fn all_of<'a, C: ?Sized>(
+    rules: &'a [&'a dyn Authorization<C>],
+) -> AllOf<'a, C> {
+    AllOf { rules }
+}
+
+impl<C: ?Sized> Authorization<C> for AllOf<'_, C> {
+    fn authorize(&self, ctx: &C, keys_changed: &BTreeSet<Key>) -> VpResult {
+        self.rules
+            .iter()
+            .try_for_each(|rule| rule.authorize(ctx, keys_changed))
+    }
+}
+
+/// A rule accepting a tx that changed at least one key under a prefix. See
+/// [`key_changed_under`].
+pub struct KeyChangedUnder {
+    prefix: Key,
+}
+
+/// Accept a tx that changed at least one key under the given prefix
+/// (including the prefix itself). It only looks at the changed keys and
+/// doesn't read storage.
+pub fn key_changed_under(prefix: Key) -> KeyChangedUnder {
+    KeyChangedUnder { prefix }
+}
+
+impl<C: ?Sized> Authorization<C> for KeyChangedUnder {
+    fn authorize(&self, _ctx: &C, keys_changed: &BTreeSet<Key>) -> VpResult {
+        if keys_changed
+            .iter()
+            .any(|key| key.segments.starts_with(&self.prefix.segments))
+        {
+            Ok(())
+        } else {
+            Err(VpError::Erased(format!(
+                "No key changed under the prefix {}",
+                self.prefix
+            )))
+        }
+    }
+}
+
+/// A rule accepting a tx that didn't modify the value of a key. See
+/// [`value_unchanged`].
+pub struct ValueUnchanged {
+    key: Key,
+}
+
+/// Accept a tx that didn't modify the value of the given key. Storage is only
+/// read when the key is among the changed keys, in which case the tx is still
+/// accepted if it wrote back the prior value.
+pub fn value_unchanged(key: Key) -> ValueUnchanged {
+    ValueUnchanged { key }
+}
+
+impl ValueUnchanged {
+    fn is_unchanged(&self, ctx: &Ctx) -> VpEnvResult<bool> {
+        let pre = ctx.read_bytes_pre(&self.key).into_vp_error()?;
+        let post = ctx.read_bytes_post(&self.key).into_vp_error()?;
+        Ok(pre == post)
+    }
+}
+
+impl Authorization<Ctx> for ValueUnchanged {
+    fn authorize(&self, ctx: &Ctx, keys_changed: &BTreeSet<Key>) -> VpResult {
+        if !keys_changed.contains(&self.key) || self.is_unchanged(ctx)? {
+            Ok(())
+        } else {
+            Err(VpError::Erased(format!(
+                "The value of the key {} was changed",
+                self.key
+            )))
+        }
+    }
+}
+
+/// A rule accepting a tx signed by the keys of an account. See
+/// [`signed_by`].
+pub struct SignedBy<'a> {
+    owner: &'a Address,
+    tx: &'a Tx,
+    gadget: Option<&'a VerifySigGadget>,
+}
+
+/// Accept a tx that carries enough valid signatures of the public keys of the
+/// given account to reach its threshold. An invalid or insufficient set of
+/// signatures aborts the VP.
+pub fn signed_by<'a>(owner: &'a Address, tx: &'a Tx) -> SignedBy<'a> {
+    SignedBy {
+        owner,
+        tx,
+        gadget: None,
+    }
+}
+
+impl<'a> SignedBy<'a> {
+    /// Verify the signatures with the given gadget, so that they are only
+    /// verified once for all the rules and checks sharing it.
+    pub fn with_gadget(mut self, gadget: &'a VerifySigGadget) -> Self {
+        self.gadget = Some(gadget);
+        self
+    }
+}
+
+impl Authorization<Ctx> for SignedBy<'_> {
+    fn authorize(&self, ctx: &Ctx, _keys_changed: &BTreeSet<Key>) -> VpResult {
+        match self.gadget {
+            Some(gadget) => gadget.verify_signatures(ctx, self.tx, self.owner),
+            None => verify_signatures(ctx, self.tx, self.owner),
+        }
+    }
+}
+
+/// A rule accepting a tx whose actions applied on behalf of an account are
+/// signed by it. See [`actions_signed_by`].
+pub struct ActionsSignedBy<'a> {
+    signed_by: SignedBy<'a>,
+    actions: &'a [Action],
+    allow_memo_hook_bonds: bool,
+}
+
+/// Accept a tx if none of the given actions applied by it has the account of
+/// the [`signed_by`] rule as its source, or if the rule accepts the tx.
+pub fn actions_signed_by<'a>(
+    signed_by: SignedBy<'a>,
+    actions: &'a [Action],
+) -> ActionsSignedBy<'a> {
+    ActionsSignedBy {
+        signed_by,
+        actions,
+        allow_memo_hook_bonds: false,
+    }
+}
+
+impl ActionsSignedBy<'_> {
+    /// Allow the bonds of the native tokens received by the account in the
+    /// IBC transfers whose memo hook was called without a signature. See
+    /// [`memo_hook_bondable_amount`].
+    pub fn allow_memo_hook_bonds(mut self) -> Self {
+        self.allow_memo_hook_bonds = true;
+        self
+    }
+
+    fn requires_signature(&self, ctx: &Ctx) -> VpEnvResult<bool> {
+        let owner = self.signed_by.owner;
+        // The received tokens that memo hooks may bond without a signature
+        let mut hook_bondable = if self.allow_memo_hook_bonds {
+            memo_hook_bondable_amount(ctx, self.actions, owner)?
+        } else {
+            token::Amount::zero()
+        };
+        for action in self.actions {
+            let source = match action {
+                Action::Pos(pos_action) => match pos_action {
+                    PosAction::BecomeValidator(source)
+                    | PosAction::DeactivateValidator(source)
+                    | PosAction::ReactivateValidator(source)
+                    | PosAction::Unjail(source)
+                    | PosAction::CommissionChange(source)
+                    | PosAction::MetadataChange(source)
+                    | PosAction::ConsensusKeyChange(source)
+                    | PosAction::DelegationPoolUpdate {
+                        operator: source,
+                        ..
+                    }
+                    | PosAction::DelegationPoolMembership(source)
+                    | PosAction::AutoRedelegationUpdate(source)
+                    | PosAction::Redelegation(Redelegation {
+                        owner: source,
+                        ..
+                    }) => source,
+                    PosAction::Bond(Bond {
+                        source,
+                        validator,
+                        amount,
+                    }) => {
+                        let source = source.as_ref().unwrap_or(validator);
+                        // The debit of the bonded tokens is checked with the
+                        // balance changes
+                        if source == owner {
+                            if let Some(rest) =
+                                hook_bondable.checked_sub(*amount)
+                            {
+                                hook_bondable = rest;
+                                continue;
+                            }
+                        }
+                        source
+                    }
+                    PosAction::Unbond(Unbond {
+                        source, validator, ..
+                    })
+                    | PosAction::Withdraw(Withdraw { source, validator })
+                    | PosAction::ClaimRewards(ClaimRewards {
+                        validator,
+                        source,
+                    }) => source.as_ref().unwrap_or(validator),
+                },
+                Action::Gov(
+                    GovAction::InitProposal { author: source }
+                    | GovAction::VoteProposal { voter: source, .. }
+                    | GovAction::DelegateVotingPower { delegator: source },
+                )
+                | Action::Pgf(
+                    PgfAction::ResignSteward(source)
+                    | PgfAction::UpdateStewardCommission(source),
+                ) => source,
+                Action::Ibc(_) => continue,
+            };
+            if source == owner {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl Authorization<Ctx> for ActionsSignedBy<'_> {
+    fn authorize(&self, ctx: &Ctx, keys_changed: &BTreeSet<Key>) -> VpResult {
+        if self.requires_signature(ctx)? {
+            self.signed_by.authorize(ctx, keys_changed)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::storage::DbKeySeg;
+
+    use super::*;
+
+    fn key(segments: &[&str]) -> Key {
+        Key {
+            segments: segments
+                .iter()
+                .map(|seg| DbKeySeg::StringSeg(seg.to_string()))
+                .collect(),
+        }
+    }
+
+    fn accept(_ctx: &(), _keys_changed: &BTreeSet<Key>) -> VpResult {
+        Ok(())
+    }
+
+    fn reject(_ctx: &(), _keys_changed: &BTreeSet<Key>) -> VpResult {
+        Err(VpError::Unspecified)
+    }
+
+    #[test]
+    fn test_any_of() {
+        let keys_changed = BTreeSet::new();
+        assert!(any_of::<()>(&[]).authorize(&(), &keys_changed).is_err());
+        assert!(any_of::<()>(&[&reject, &accept])
+            .authorize(&(), &keys_changed)
+            .is_ok());
+        assert!(any_of::<()>(&[&reject, &reject])
+            .authorize(&(), &keys_changed)
+            .is_err());
+        // The evaluation stops at the first rule that accepts
+        let panics = |_: &(), _: &BTreeSet<Key>| -> VpResult {
+            panic!("The rule shouldn't be evaluated")
+        };
+        assert!(any_of::<()>(&[&accept, &panics])
+            .authorize(&(), &keys_changed)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_all_of() {
+        let keys_changed = BTreeSet::new();
+        assert!(all_of::<()>(&[]).authorize(&(), &keys_changed).is_ok());
+        assert!(all_of::<()>(&[&accept, &accept])
+            .authorize(&(), &keys_changed)
+            .is_ok());
+        assert!(all_of::<()>(&[&accept, &reject])
+            .authorize(&(), &keys_changed)
+            .is_err());
+        // The evaluation stops at the first rule that rejects
+        let panics = |_: &(), _: &BTreeSet<Key>| -> VpResult {
+            panic!("The rule shouldn't be evaluated")
+        };
+        assert!(all_of::<()>(&[&reject, &panics])
+            .authorize(&(), &keys_changed)
+            .is_err());
+        // Combinators can be nested
+        let either = any_of::<()>(&[&reject, &accept]);
+        assert!(all_of::<()>(&[&either, &accept])
+            .authorize(&(), &keys_changed)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_key_changed_under() {
+        let keys_changed: BTreeSet<Key> =
+            [key(&["a", "b", "c"]), key(&["d"])].into_iter().collect();
+        assert!(key_changed_under(key(&["a"]))
+            .authorize(&(), &keys_changed)
+            .is_ok());
+        assert!(key_changed_under(key(&["a", "b"]))
+            .authorize(&(), &keys_changed)
+            .is_ok());
+        assert!(key_changed_under(key(&["d"]))
+            .authorize(&(), &keys_changed)
+            .is_ok());
+        assert!(key_changed_under(key(&["a", "c"]))
+            .authorize(&(), &keys_changed)
+            .is_err());
+        // Segments are matched whole
+        assert!(key_changed_under(key(&["a", "bc"]))
+            .authorize(&(), &keys_changed)
+            .is_err());
+        assert!(key_changed_under(key(&["d", "e"]))
+            .authorize(&(), &keys_changed)
+            .is_err());
+    }
+}
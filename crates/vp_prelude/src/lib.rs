@@ -6,12 +6,15 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![deny(rustdoc::private_intra_doc_links)]
 
+pub mod auth;
+pub mod user;
 pub mod ibc {
     pub use namada_ibc::event::{IbcEvent, IbcEventType};
-    pub use namada_ibc::storage::is_ibc_key;
+    pub use namada_ibc::storage::{is_ibc_key, SequenceKind};
 }
 
 // used in the VP input
+use core::cell::Cell;
 use core::slice;
 pub use std::collections::BTreeSet;
 use std::marker::PhantomData;
@@ -67,6 +70,47 @@ pub fn is_proposal_accepted(ctx: &Ctx, proposal_id: u64) -> VpEnvResult<bool> {
     ctx.has_key_pre(&proposal_execution_key).into_vp_error()
 }
 
+/// Checks if the tx is the execution of an accepted governance proposal, whose
+/// data is the proposal id. Any change from governance is allowed by the user
+/// VPs without further checks.
+pub fn is_accepted_proposal_tx(ctx: &Ctx, tx: &Tx) -> VpEnvResult<bool> {
+    tx.data()
+        .and_then(|tx_data| {
+            let proposal_id = u64::try_from_slice(&tx_data).ok()?;
+            Some(is_proposal_accepted(ctx, proposal_id))
+        })
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+/// Get the amount of the native token received by the given address in the
+/// IBC transfers of the tx whose memo hook was called, which the hooks may bond
+/// on behalf of the address without its signature. The balance of the address
+/// must still not decrease without its signature.
+pub fn memo_hook_bondable_amount(
+    ctx: &Ctx,
+    actions: &[tx::action::Action],
+    owner: &Address,
+) -> VpEnvResult<token::Amount> {
+    use tx::action::{Action, IbcAction};
+
+    let native_token = ctx.get_native_token().into_vp_error()?;
+    actions
+        .iter()
+        .filter_map(|action| match action {
+            Action::Ibc(IbcAction::MemoHook {
+                receiver,
+                token,
+                amount,
+            }) if receiver == owner && token == &native_token => Some(*amount),
+            _ => None,
+        })
+        .try_fold(token::Amount::zero(), |acc, amount| acc.checked_add(amount))
+        .ok_or_else(|| {
+            VpError::Erased("Overflow in the memo hook amounts".into())
+        })
+}
+
 /// Verify section signatures
 #[cold]
 #[inline(never)]
@@ -107,28 +151,28 @@ fn verify_signatures(ctx: &Ctx, tx: &Tx, owner: &Address) -> VpResult {
 #[derive(Default)]
 #[repr(transparent)]
 pub struct VerifySigGadget {
-    has_validated_sig: bool,
+    has_validated_sig: Cell<bool>,
 }
 
 impl VerifySigGadget {
     /// Create a new [`VerifySigGadget`].
     pub const fn new() -> Self {
         Self {
-            has_validated_sig: false,
+            has_validated_sig: Cell::new(false),
         }
     }
 
     /// Verify a tx signature, only paying the cost of this operation once.
     #[inline(always)]
     pub fn verify_signatures(
-        &mut self,
+        &self,
         ctx: &Ctx,
         tx_data: &Tx,
         owner: &Address,
     ) -> VpResult {
-        if !self.has_validated_sig {
+        if !self.has_validated_sig.get() {
             verify_signatures(ctx, tx_data, owner)?;
-            self.has_validated_sig = true;
+            self.has_validated_sig.set(true);
         }
         Ok(())
     }
@@ -138,7 +182,7 @@ impl VerifySigGadget {
     /// signatures.
     #[inline(always)]
     pub fn verify_signatures_when<F: FnOnce() -> bool>(
-        &mut self,
+        &self,
         predicate: F,
         ctx: &Ctx,
         tx_data: &Tx,
@@ -220,6 +264,81 @@ impl Ctx {
             namada_vp_yield_value(value.as_ptr() as _, value.len() as _);
         }
     }
+
+    /// Get the bonded stake of a validator at the given epoch, read from
+    /// the posterior storage. The stake is zero for an address that is not
+    /// a validator.
+    pub fn get_validator_stake(
+        &self,
+        validator: &Address,
+        epoch: Epoch,
+    ) -> namada_storage::Result<token::Amount> {
+        let validator = validator.encode();
+        let read_result = unsafe {
+            namada_vp_get_validator_stake(
+                validator.as_ptr() as _,
+                validator.len() as _,
+                epoch.0,
+            )
+        };
+        let bytes = read_from_buffer(read_result, namada_vp_result_buffer)
+            .ok_or(StorageError::SimpleMessage(
+                "Missing result from `namada_vp_get_validator_stake` call",
+            ))?;
+        Ok(namada_core::decode(bytes).expect("Cannot decode validator stake"))
+    }
+
+    /// Get the state of a validator at the given epoch, read from the
+    /// posterior storage. Returns `None` if the address is not a validator
+    /// at the given epoch.
+    pub fn get_validator_state(
+        &self,
+        validator: &Address,
+        epoch: Epoch,
+    ) -> namada_storage::Result<Option<proof_of_stake::types::ValidatorState>>
+    {
+        let validator = validator.encode();
+        let read_result = unsafe {
+            namada_vp_get_validator_state(
+                validator.as_ptr() as _,
+                validator.len() as _,
+                epoch.0,
+            )
+        };
+        Ok(read_from_buffer(read_result, namada_vp_result_buffer).map(
+            |bytes| {
+                namada_core::decode(bytes)
+                    .expect("Cannot decode validator state")
+            },
+        ))
+    }
+
+    /// Get the total bonded stake at the given epoch, read from the
+    /// posterior storage.
+    pub fn get_total_stake(
+        &self,
+        epoch: Epoch,
+    ) -> namada_storage::Result<token::Amount> {
+        let read_result = unsafe { namada_vp_get_total_stake(epoch.0) };
+        let bytes = read_from_buffer(read_result, namada_vp_result_buffer)
+            .ok_or(StorageError::SimpleMessage(
+                "Missing result from `namada_vp_get_total_stake` call",
+            ))?;
+        Ok(namada_core::decode(bytes).expect("Cannot decode total stake"))
+    }
+
+    /// Get the randomness of the current block, i.e. its seed. Returns `None`
+    /// if no block seed has been derived yet. The seed can be biased by the
+    /// block proposer, refer to [`proof_of_stake::block_seed`] for its
+    /// limitations.
+    pub fn get_block_randomness(&self) -> namada_storage::Result<Option<Hash>> {
+        let read_result = unsafe { namada_vp_get_block_randomness() };
+        Ok(read_from_buffer(read_result, namada_vp_result_buffer).map(
+            |bytes| {
+                namada_core::decode(bytes).expect("Cannot decode block seed")
+            },
+        ))
+    }
 }
 
 /// Read access to the prior storage (state before tx execution) via
@@ -393,6 +512,10 @@ impl<'view> VpEnv<'view> for Ctx {
         unsafe { namada_vp_charge_gas(used_gas) };
         Ok(())
     }
+
+    fn get_gas_remaining(&self) -> Result<u64, StorageError> {
+        Ok(unsafe { namada_vp_get_gas_remaining() })
+    }
 }
 
 impl namada_tx::action::Read for Ctx {
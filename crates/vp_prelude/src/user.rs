@@ -0,0 +1,187 @@
+//! Validation of the storage changes of user accounts, shared by the user VPs.
+//!
+//! A tx must be signed by the account for:
+//! - the actions applied on behalf of the account, except for the bonds of the
+//!   native tokens received in IBC transfers whose memo hook was called,
+//! - the debits of the account's balances,
+//! - the changes of the minter or policy of a token that is the account,
+//! - the changes of the account's VP, public keys and threshold, after which
+//!   the threshold must still be satisfiable by the public keys,
+//! - any other storage key changes.
+//!
+//! The credits of the account's balances, and the MASP and IBC changes, are
+//! allowed without a signature.
+
+use namada_core::booleans::BoolResultUnitExt;
+use namada_core::storage::Key;
+use namada_tx::action::Read;
+
+use super::auth::{actions_signed_by, signed_by, Authorization};
+use super::{
+    account, address, debug_log, ibc, log_string, token, Address, BTreeSet,
+    Ctx, Tx, VerifySigGadget, VpEnv, VpError, VpErrorExtResult,
+};
+use crate::VpResult;
+
+/// Validate the storage changes of a tx for the user account `addr`.
+pub fn validate_changes(
+    ctx: &Ctx,
+    tx: &Tx,
+    addr: &Address,
+    keys_changed: &BTreeSet<Key>,
+    verifiers: &BTreeSet<Address>,
+) -> VpResult {
+    let gadget = VerifySigGadget::new();
+
+    // Require authorization by signature when the source of an action is this
+    // VP's address
+    let actions = ctx.read_actions().into_vp_error()?;
+    actions_signed_by(signed_by(addr, tx).with_gadget(&gadget), &actions)
+        .allow_memo_hook_bonds()
+        .authorize(ctx, keys_changed)?;
+
+    keys_changed.iter().try_for_each(|key| {
+        let key_type: KeyType = key.into();
+        let validate_change = || match key_type {
+            KeyType::TokenBalance { owner } => {
+                if owner == addr {
+                    let pre: token::Amount =
+                        ctx.read_pre(key).into_vp_error()?.unwrap_or_default();
+                    let post: token::Amount =
+                        ctx.read_post(key).into_vp_error()?.unwrap_or_default();
+                    let change =
+                        post.change().checked_sub(pre.change()).unwrap();
+                    gadget.verify_signatures_when(
+                        // NB: debit has to signed, credit doesn't
+                        || change.is_negative(),
+                        ctx,
+                        tx,
+                        addr,
+                    )?;
+                    let sign = if change.non_negative() { "" } else { "-" };
+                    debug_log!("token key: {key}, change: {sign}{change:?}");
+                } else {
+                    // If this is not the owner, allow any change
+                    debug_log!(
+                        "This address ({}) is not of owner ({}) of token key: \
+                         {}",
+                        addr,
+                        owner,
+                        key
+                    );
+                }
+                Ok(())
+            }
+            KeyType::TokenMinted => {
+                verifiers.contains(&address::MULTITOKEN).ok_or_else(|| {
+                    VpError::Erased(
+                        "The Multitoken VP should have been a verifier for \
+                         this transaction, since a token was minted"
+                            .into(),
+                    )
+                })
+            }
+            KeyType::TokenMinter(minter_addr) => gadget.verify_signatures_when(
+                || minter_addr == addr,
+                ctx,
+                tx,
+                addr,
+            ),
+            KeyType::TokenPolicy(token) => {
+                gadget.verify_signatures_when(|| token == addr, ctx, tx, addr)
+            }
+            KeyType::Vp(owner) => {
+                let vp_overwritten: bool =
+                    ctx.has_key_post(key).into_vp_error()?;
+                gadget.verify_signatures_when(
+                    || owner == addr && vp_overwritten,
+                    ctx,
+                    tx,
+                    addr,
+                )
+            }
+            KeyType::AccountSigners(owner) => {
+                gadget.verify_signatures(ctx, tx, addr)?;
+                if owner == addr {
+                    validate_account_signers(ctx, owner)
+                } else {
+                    Ok(())
+                }
+            }
+            KeyType::Masp | KeyType::Ibc => Ok(()),
+            KeyType::Unknown => {
+                // Unknown changes require a valid signature
+                gadget.verify_signatures(ctx, tx, addr)
+            }
+        };
+        validate_change().inspect_err(|reason| {
+            log_string(format!(
+                "Modification on key {key} failed the checks of the user \
+                 account {addr}: {reason}"
+            ));
+        })
+    })
+}
+
+/// Check that the account's threshold can be satisfied by its public keys
+/// after the tx
+fn validate_account_signers(ctx: &Ctx, owner: &Address) -> VpResult {
+    let num_public_keys = account::public_keys(&ctx.post(), owner)
+        .into_vp_error()?
+        .len();
+    let threshold = account::threshold(&ctx.post(), owner)
+        .into_vp_error()?
+        .unwrap_or(1);
+    let is_valid = u8::try_from(num_public_keys).is_ok_and(|num_public_keys| {
+        account::is_valid_threshold(num_public_keys, threshold)
+    });
+    is_valid.ok_or_else(|| {
+        VpError::Erased(format!(
+            "The threshold {threshold} of the account {owner} cannot be \
+             satisfied by its {num_public_keys} public keys"
+        ))
+    })
+}
+
+enum KeyType<'a> {
+    TokenBalance { owner: &'a Address },
+    TokenMinted,
+    TokenMinter(&'a Address),
+    TokenPolicy(&'a Address),
+    Vp(&'a Address),
+    AccountSigners(&'a Address),
+    Masp,
+    Ibc,
+    Unknown,
+}
+
+impl<'a> From<&'a Key> for KeyType<'a> {
+    fn from(key: &'a Key) -> KeyType<'a> {
+        if let Some([_, owner]) =
+            token::storage_key::is_any_token_balance_key(key)
+        {
+            Self::TokenBalance { owner }
+        } else if token::storage_key::is_any_minted_balance_key(key).is_some() {
+            Self::TokenMinted
+        } else if let Some(minter) = token::storage_key::is_any_minter_key(key)
+        {
+            Self::TokenMinter(minter)
+        } else if let Some(token) =
+            token::storage_key::is_any_token_policy_key(key)
+        {
+            Self::TokenPolicy(token)
+        } else if let Some(address) = key.is_validity_predicate() {
+            Self::Vp(address)
+        } else if let Some(owner) =
+            account::is_pks_key(key).or_else(|| account::is_threshold_key(key))
+        {
+            Self::AccountSigners(owner)
+        } else if token::storage_key::is_masp_key(key) {
+            Self::Masp
+        } else if ibc::is_ibc_key(key) {
+            Self::Ibc
+        } else {
+            Self::Unknown
+        }
+    }
+}
@@ -0,0 +1,281 @@
+//! A harness to unit test validity predicates compiled to WASM, without
+//! running a node.
+//!
+//! The storage state before the tx (pre), the storage changes applied by the
+//! tx (post), the verifiers and the tx itself are all set up in memory. Then
+//! the VP is run against them, as it would be by the protocol:
+//!
+//! ```ignore
+//! let outcome = VpTester::new(vp_code, owner)
+//!     .write_pre(&balance_key, token::Amount::from(100))
+//!     .write_post(&balance_key, token::Amount::from(50))
+//!     .run();
+//! outcome.assert_rejected();
+//! ```
+
+#![doc(html_favicon_url = "https://dev.namada.net/master/favicon.png")]
+#![doc(html_logo_url = "https://dev.namada.net/master/rustdoc-logo.png")]
+#![warn(missing_docs)]
+#![deny(rustdoc::broken_intra_doc_links)]
+#![deny(rustdoc::private_intra_doc_links)]
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use namada::address::Address;
+use namada::core::borsh::{BorshSerialize, BorshSerializeExt};
+use namada::gas::{Gas, TxGasMeter, VpGasMeter};
+use namada::hash::Hash;
+use namada::ledger::storage::testing::TestState;
+use namada::state::{StateRead, StorageWrite};
+use namada::storage::{Key, TxIndex};
+use namada::tx::data::TxType;
+use namada::tx::{Data, Tx};
+use namada::vm::wasm::{self, run};
+
+/// The gas limit used to run the VP, unless another one is set
+pub const DEFAULT_GAS_LIMIT: u64 = 1_000_000_000_000;
+
+/// A builder of a VP run. Every run starts from a fresh in-memory storage, so
+/// the same tester can be run multiple times.
+#[derive(Debug, Clone)]
+pub struct VpTester {
+    vp_code: Vec<u8>,
+    address: Address,
+    pre: BTreeMap<Key, Vec<u8>>,
+    post: BTreeMap<Key, Option<Vec<u8>>>,
+    verifiers: BTreeSet<Address>,
+    tx: Tx,
+    gas_limit: u64,
+}
+
+impl VpTester {
+    /// Test the given VP wasm code as the VP of the given address
+    pub fn new(vp_code: impl Into<Vec<u8>>, address: Address) -> Self {
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.header.chain_id = TestState::default().in_mem().chain_id.clone();
+        Self {
+            vp_code: vp_code.into(),
+            address,
+            pre: BTreeMap::new(),
+            post: BTreeMap::new(),
+            verifiers: BTreeSet::new(),
+            tx,
+            gas_limit: DEFAULT_GAS_LIMIT,
+        }
+    }
+
+    /// Test the VP wasm code read from the given file as the VP of the given
+    /// address
+    pub fn from_file(
+        path: impl AsRef<Path>,
+        address: Address,
+    ) -> std::io::Result<Self> {
+        let vp_code = std::fs::read(path)?;
+        Ok(Self::new(vp_code, address))
+    }
+
+    /// Write a value to the storage state before the tx
+    pub fn write_pre<T: BorshSerialize>(mut self, key: &Key, val: T) -> Self {
+        self.pre.insert(key.clone(), val.serialize_to_vec());
+        self
+    }
+
+    /// Write a value from the tx. The key is added to the changed keys.
+    pub fn write_post<T: BorshSerialize>(mut self, key: &Key, val: T) -> Self {
+        self.post.insert(key.clone(), Some(val.serialize_to_vec()));
+        self
+    }
+
+    /// Delete a value from the tx. The key is added to the changed keys.
+    pub fn delete_post(mut self, key: &Key) -> Self {
+        self.post.insert(key.clone(), None);
+        self
+    }
+
+    /// Add a verifier requested by the tx. The owners of the changed keys are
+    /// always verifiers.
+    pub fn verifier(mut self, verifier: Address) -> Self {
+        self.verifiers.insert(verifier);
+        self
+    }
+
+    /// Set the data of the tx
+    pub fn tx_data<T: BorshSerialize>(mut self, data: &T) -> Self {
+        self.tx.set_data(Data::new(data.serialize_to_vec()));
+        self
+    }
+
+    /// Replace the tx, e.g. with one carrying signatures. The tx should have
+    /// the chain ID of [`VpTester::chain_id`].
+    pub fn tx(mut self, tx: Tx) -> Self {
+        self.tx = tx;
+        self
+    }
+
+    /// Set the gas limit of the run
+    pub fn gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    /// The chain ID of the in-memory storage
+    pub fn chain_id(&self) -> namada::chain::ChainId {
+        self.tx.header.chain_id.clone()
+    }
+
+    /// Run the VP
+    pub fn run(&self) -> VpRunOutcome {
+        let mut state = TestState::default();
+
+        // Store the VP code and the pre state and commit them, so that they
+        // are seen as the state before the tx
+        let vp_code_hash = Hash::sha256(&self.vp_code);
+        state
+            .write(&Key::wasm_code(&vp_code_hash), self.vp_code.clone())
+            .expect("Writing the VP code to storage shouldn't fail");
+        state
+            .write(
+                &Key::wasm_code_len(&vp_code_hash),
+                self.vp_code.len() as u64,
+            )
+            .expect("Writing the VP code length to storage shouldn't fail");
+        for (key, val) in &self.pre {
+            state
+                .write_bytes(key, val)
+                .expect("Writing the pre state shouldn't fail");
+        }
+        state.commit_tx();
+
+        for (key, val) in &self.post {
+            match val {
+                Some(val) => state.write_bytes(key, val),
+                None => state.delete(key),
+            }
+            .expect("Writing the post state shouldn't fail");
+        }
+        let (verifiers, keys_changed) = state
+            .write_log()
+            .verifiers_and_changed_keys(&self.verifiers);
+
+        let gas_meter = RefCell::new(VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(self.gas_limit.into()),
+        ));
+        let (vp_cache, _cache_dir) =
+            wasm::compilation_cache::common::testing::cache();
+        let result = run::vp(
+            vp_code_hash,
+            &self.tx,
+            &TxIndex::default(),
+            &self.address,
+            &state,
+            &gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_cache,
+        );
+        let gas_used = gas_meter.borrow().get_vp_consumed_gas();
+
+        VpRunOutcome {
+            result,
+            gas_used,
+            keys_changed,
+            verifiers,
+        }
+    }
+}
+
+/// The outcome of a VP run
+#[derive(Debug)]
+pub struct VpRunOutcome {
+    /// The VP result, an error if the tx was rejected
+    pub result: run::Result<()>,
+    /// The gas consumed by the VP
+    pub gas_used: Gas,
+    /// The keys changed by the tx
+    pub keys_changed: BTreeSet<Key>,
+    /// The verifiers of the tx
+    pub verifiers: BTreeSet<Address>,
+}
+
+impl VpRunOutcome {
+    /// Check if the VP accepted the tx
+    pub fn is_accepted(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    /// Assert that the VP accepted the tx
+    #[track_caller]
+    pub fn assert_accepted(&self) -> &Self {
+        if let Err(err) = &self.result {
+            panic!(
+                "Expected the VP to accept the tx, but it failed with {err}"
+            );
+        }
+        self
+    }
+
+    /// Assert that the VP rejected the tx
+    #[track_caller]
+    pub fn assert_rejected(&self) -> &Self {
+        assert!(
+            self.result.is_err(),
+            "Expected the VP to reject the tx, but it accepted it"
+        );
+        self
+    }
+
+    /// Assert that the VP didn't consume more than the given gas
+    #[track_caller]
+    pub fn assert_gas_used_at_most(&self, gas: impl Into<Gas>) -> &Self {
+        let gas = gas.into();
+        assert!(
+            self.gas_used <= gas,
+            "Expected the VP to use at most {gas} gas, but it used {}",
+            self.gas_used
+        );
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada::address::testing::established_address_1;
+    use namada_test_utils::TestWasms;
+
+    use super::*;
+
+    #[test]
+    fn test_vp_tester_accept_and_reject() {
+        let addr = established_address_1();
+
+        VpTester::new(TestWasms::VpAlwaysTrue.read_bytes(), addr.clone())
+            .run()
+            .assert_accepted();
+        VpTester::new(TestWasms::VpAlwaysFalse.read_bytes(), addr)
+            .run()
+            .assert_rejected();
+    }
+
+    #[test]
+    fn test_vp_tester_pre_and_post_state() {
+        let addr = established_address_1();
+        let key = Key::parse("testing").unwrap();
+        // The VP requires the key given in the tx data to have a value in the
+        // pre state
+        let tester =
+            VpTester::new(TestWasms::VpReadStorageKey.read_bytes(), addr)
+                .tx_data(&key);
+
+        let outcome = tester.clone().write_post(&key, 1_u64).run();
+        outcome.assert_rejected();
+        assert!(outcome.keys_changed.contains(&key));
+
+        let outcome = tester.write_pre(&key, 1_u64).run();
+        outcome.assert_accepted();
+        assert!(outcome.keys_changed.is_empty());
+        assert!(outcome.gas_used > Gas::from(0));
+        outcome.assert_gas_used_at_most(DEFAULT_GAS_LIMIT);
+    }
+}
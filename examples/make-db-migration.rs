@@ -6,13 +6,15 @@ use namada_apps::wasm_loader::read_wasm;
 use namada_macros::BorshDeserializer;
 use namada_parameters::storage;
 use namada_sdk::address::Address;
+use namada_sdk::dec::Dec;
 use namada_sdk::hash::Hash as CodeHash;
 use namada_sdk::masp_primitives::asset_type::AssetType;
 use namada_sdk::masp_primitives::convert::AllowedConversion;
 use namada_sdk::masp_primitives::merkle_tree::FrozenCommitmentTree;
 use namada_sdk::masp_primitives::sapling;
 use namada_sdk::migrations;
-use namada_sdk::proof_of_stake::Epoch;
+use namada_sdk::proof_of_stake::storage_key::params_key;
+use namada_sdk::proof_of_stake::{Epoch, OwnedPosParams};
 use namada_sdk::storage::{DbColFam, Key};
 use namada_sdk::token::{Denomination, MaspDigitPos};
 use namada_shielded_token::storage_key::masp_token_map_key;
@@ -54,6 +56,59 @@ impl From<ConversionState> for NewConversionState {
     }
 }
 
+/// The layout of the PoS parameters before the liquid staking receipts, the
+/// minimum commission rate, the required validator metadata and the max
+/// validator stake share were added
+#[derive(Debug, BorshSerialize, BorshDeserialize)]
+pub struct OldOwnedPosParams {
+    pub max_validator_slots: u64,
+    pub pipeline_len: u64,
+    pub unbonding_len: u64,
+    pub tm_votes_per_token: Dec,
+    pub block_proposer_reward: Dec,
+    pub block_vote_reward: Dec,
+    pub max_inflation_rate: Dec,
+    pub target_staked_ratio: Dec,
+    pub duplicate_vote_min_slash_rate: Dec,
+    pub light_client_attack_min_slash_rate: Dec,
+    pub cubic_slashing_window_length: u64,
+    pub validator_stake_threshold: Amount,
+    pub liveness_window_check: u64,
+    pub liveness_threshold: Dec,
+    pub rewards_gain_p: Dec,
+    pub rewards_gain_d: Dec,
+}
+
+impl From<OldOwnedPosParams> for OwnedPosParams {
+    fn from(value: OldOwnedPosParams) -> Self {
+        Self {
+            max_validator_slots: value.max_validator_slots,
+            pipeline_len: value.pipeline_len,
+            unbonding_len: value.unbonding_len,
+            tm_votes_per_token: value.tm_votes_per_token,
+            block_proposer_reward: value.block_proposer_reward,
+            block_vote_reward: value.block_vote_reward,
+            max_inflation_rate: value.max_inflation_rate,
+            target_staked_ratio: value.target_staked_ratio,
+            duplicate_vote_min_slash_rate: value.duplicate_vote_min_slash_rate,
+            light_client_attack_min_slash_rate: value
+                .light_client_attack_min_slash_rate,
+            cubic_slashing_window_length: value.cubic_slashing_window_length,
+            validator_stake_threshold: value.validator_stake_threshold,
+            liveness_window_check: value.liveness_window_check,
+            liveness_threshold: value.liveness_threshold,
+            rewards_gain_p: value.rewards_gain_p,
+            rewards_gain_d: value.rewards_gain_d,
+            // Keep the behavior of the chain unchanged until governance
+            // enables the new features
+            liquid_staking_receipts: false,
+            min_commission_rate: Dec::zero(),
+            required_validator_metadata: Default::default(),
+            max_validator_stake_share: None,
+        }
+    }
+}
+
 #[allow(dead_code)]
 fn example() {
     let person =
@@ -92,6 +147,38 @@ fn main() {
     se_migration()
 }
 
+/// Re-encode the stored PoS parameters with the new fields. The new
+/// `InternalAddress::BondReceipt` discriminant is appended after the existing
+/// ones, so the stored addresses don't need to be migrated.
+#[allow(dead_code)]
+fn pos_params_migration() {
+    let query_result = std::fs::read_to_string("pos_params.txt").unwrap();
+    let hex_bytes = query_result.split('\n').nth(2).unwrap();
+    let bytes = HEXUPPER
+        .decode(
+            hex_bytes
+                .strip_prefix("The value in bytes is ")
+                .unwrap()
+                .trim()
+                .as_bytes(),
+        )
+        .unwrap();
+    let old_params = OldOwnedPosParams::try_from_slice(&bytes).unwrap();
+    let new_params: OwnedPosParams = old_params.into();
+    let params_update = migrations::DbUpdateType::Add {
+        key: params_key(),
+        cf: DbColFam::SUBSPACE,
+        value: new_params.into(),
+        force: false,
+    };
+
+    let changes = migrations::DbChanges {
+        changes: [params_update].into_iter().collect(),
+    };
+    std::fs::write("migrations.json", serde_json::to_string(&changes).unwrap())
+        .unwrap();
+}
+
 // The current vp_user hash to be replaced on the SE
 const REMOVED_HASH: &str =
     "129EE7BEE68B02BFAE638DA2A634B8ECBFFA2CB3F46CFA8E172BAF009627EC78";
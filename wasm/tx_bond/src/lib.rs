@@ -5,11 +5,13 @@ use namada_tx_prelude::*;
 #[transaction]
 fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
     let signed = tx_data;
-    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
-        ctx.set_commitment_sentinel();
-        err
-    })?;
-    let bond = transaction::pos::Bond::try_from_slice(&data[..])
+    let bond = signed
+        .decode_data::<transaction::pos::Bond>()
+        .ok_or_err_msg("Missing data")
+        .map_err(|err| {
+            ctx.set_commitment_sentinel();
+            err
+        })?
         .wrap_err("Failed to decode Bond tx data")?;
 
     ctx.bond_tokens(bond.source.as_ref(), &bond.validator, bond.amount)
@@ -0,0 +1,16 @@
+//! A tx for a delegator to bond tokens to a delegation pool, which splits
+//! them across the validators of the pool.
+
+use namada_tx_prelude::*;
+
+#[transaction] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data")?;
+    let bond = transaction::pos::DelegationPoolBond::try_from_slice(&data[..])
+        .wrap_err("Failed to decode DelegationPoolBond value")?;
+    ctx.bond_to_delegation_pool(bond)
+        .wrap_err("Failed to bond tokens to delegation pool")?;
+
+    Ok(())
+}
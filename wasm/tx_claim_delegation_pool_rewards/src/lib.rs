@@ -0,0 +1,16 @@
+//! A tx for a member of a delegation pool to claim the rewards of all its
+//! bonds.
+
+use namada_tx_prelude::*;
+
+#[transaction] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data")?;
+    let delegator = Address::try_from_slice(&data[..])
+        .wrap_err("Failed to decode the address of the delegator")?;
+    ctx.claim_delegation_pool_rewards(&delegator)
+        .wrap_err("Failed to claim delegation pool rewards")?;
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+//! A tx to delegate or revoke the delegation of the governance voting power of
+//! an account.
+
+use namada_tx_prelude::action::{Action, GovAction, Write};
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let tx_data = governance::VotingDelegationData::try_from_slice(&data[..])
+        .wrap_err("Failed to decode VotingDelegationData value")?;
+
+    // The tx must be authorized by the delegator
+    ctx.insert_verifier(&tx_data.delegator)?;
+
+    ctx.push_action(Action::Gov(GovAction::DelegateVotingPower {
+        delegator: tx_data.delegator.clone(),
+    }))?;
+
+    debug_log!("apply_tx called to delegate the governance voting power");
+
+    governance::delegate_voting_power(ctx, &tx_data.delegator, tx_data.delegate)
+        .wrap_err("Failed to delegate the governance voting power")
+}
@@ -0,0 +1,15 @@
+//! A tx for withdrawing tokens from a faucet.
+
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data")?;
+    let withdraw = account::faucet::FaucetWithdraw::try_from_slice(&data[..])
+        .wrap_err("Failed to decode FaucetWithdraw tx data")?;
+    debug_log!("faucet withdraw from: {}", withdraw.faucet);
+
+    account::faucet_withdraw(ctx, &withdraw)
+        .wrap_err("Failed to withdraw from the faucet")
+}
@@ -3,6 +3,7 @@
 //! tx_data. This tx uses an IBC message wrapped inside
 //! `key::ed25519::SignedTxData` as its input as declared in `ibc` crate.
 
+use namada_tx_prelude::action::{Action, IbcAction, Write};
 use namada_tx_prelude::*;
 
 #[transaction]
@@ -17,7 +18,7 @@ fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
     // ibc::ibc_actions(ctx).execute(&data).into_storage_result()?;
 
     // Temp. workaround for <https://github.com/anoma/namada/issues/1831>
-    let transfer = tx_ibc_execute()?;
+    let (transfer, memo_hook) = tx_ibc_execute()?;
 
     if let Some(transfer) = transfer {
         let shielded = transfer
@@ -40,5 +41,22 @@ fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
         }
     }
 
+    // Call the hook requested in the memo of a received transfer. Its failure
+    // fails the receiving, so the transfer is refunded on the source chain.
+    if let Some(memo_hook) = memo_hook {
+        // Let the VP of the receiver accept the bond of the received tokens
+        // by the hook
+        ctx.push_action(Action::Ibc(IbcAction::MemoHook {
+            receiver: memo_hook.data.receiver.clone(),
+            token: memo_hook.data.token.clone(),
+            amount: memo_hook.data.amount,
+        }))?;
+        ctx.call_tx_with_gas_limit(
+            &memo_hook.code_hash,
+            &memo_hook.data,
+            memo_hook.gas_limit,
+        )?;
+    }
+
     Ok(())
 }
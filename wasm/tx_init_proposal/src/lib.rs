@@ -6,11 +6,13 @@ use namada_tx_prelude::*;
 
 #[transaction]
 fn apply_tx(ctx: &mut Ctx, tx: Tx) -> TxResult {
-    let data = tx.data().ok_or_err_msg("Missing data").map_err(|err| {
-        ctx.set_commitment_sentinel();
-        err
-    })?;
-    let tx_data = governance::InitProposalData::try_from_slice(&data[..])
+    let tx_data = tx
+        .decode_data::<governance::InitProposalData>()
+        .ok_or_err_msg("Missing data")
+        .map_err(|err| {
+            ctx.set_commitment_sentinel();
+            err
+        })?
         .wrap_err("Failed to decode InitProposalData value")?;
 
     // The tx must be authorized by the author address
@@ -0,0 +1,13 @@
+//! A tx for a delegator to leave its delegation pool.
+
+use namada_tx_prelude::*;
+
+#[transaction] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data")?;
+    let delegator = Address::try_from_slice(&data[..])
+        .wrap_err("Failed to decode the address of the delegator")?;
+    ctx.leave_delegation_pool(&delegator)
+        .wrap_err("Failed to leave delegation pool")
+}
@@ -7,11 +7,13 @@ use namada_tx_prelude::*;
 #[transaction]
 fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
     let signed = tx_data;
-    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
-        ctx.set_commitment_sentinel();
-        err
-    })?;
-    let transfer = token::Transfer::try_from_slice(&data[..])
+    let transfer = signed
+        .decode_data::<token::Transfer>()
+        .ok_or_err_msg("Missing data")
+        .map_err(|err| {
+            ctx.set_commitment_sentinel();
+            err
+        })?
         .wrap_err("Failed to decode token::Transfer tx data")?;
     debug_log!("apply_tx called with transfer: {:#?}", transfer);
 
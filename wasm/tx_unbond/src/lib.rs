@@ -6,11 +6,13 @@ use namada_tx_prelude::*;
 #[transaction]
 fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
     let signed = tx_data;
-    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
-        ctx.set_commitment_sentinel();
-        err
-    })?;
-    let unbond = transaction::pos::Unbond::try_from_slice(&data[..])
+    let unbond = signed
+        .decode_data::<transaction::pos::Unbond>()
+        .ok_or_err_msg("Missing data")
+        .map_err(|err| {
+            ctx.set_commitment_sentinel();
+            err
+        })?
         .wrap_err("Failed to decode Unbond tx data")?;
 
     ctx.unbond_tokens(unbond.source.as_ref(), &unbond.validator, unbond.amount)
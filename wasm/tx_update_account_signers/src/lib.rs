@@ -0,0 +1,16 @@
+//! A tx for adding or removing public keys of an account and changing its
+//! signature threshold.
+
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data")?;
+    let update = account::UpdateAccountSigners::try_from_slice(&data[..])
+        .wrap_err("Failed to decode UpdateAccountSigners tx data")?;
+    debug_log!("update signers of: {:#?}", update.addr);
+
+    account::update_account_signers(ctx, &update)
+        .wrap_err("Failed to update the account's signers")
+}
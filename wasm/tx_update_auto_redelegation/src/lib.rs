@@ -0,0 +1,15 @@
+//! A tx for a delegator to opt in or out of the automatic redelegation of its
+//! bonds away from its jailed validators.
+
+use namada_tx_prelude::*;
+
+#[transaction] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data")?;
+    let update =
+        transaction::pos::AutoRedelegationUpdate::try_from_slice(&data[..])
+            .wrap_err("Failed to decode AutoRedelegationUpdate value")?;
+    ctx.update_auto_redelegation(update)
+        .wrap_err("Failed to update automatic redelegation")
+}
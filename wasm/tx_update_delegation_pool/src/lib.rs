@@ -0,0 +1,14 @@
+//! A tx for an operator to create or update a delegation pool.
+
+use namada_tx_prelude::*;
+
+#[transaction] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data")?;
+    let update =
+        transaction::pos::UpdateDelegationPool::try_from_slice(&data[..])
+            .wrap_err("Failed to decode UpdateDelegationPool value")?;
+    ctx.update_delegation_pool(update)
+        .wrap_err("Failed to update delegation pool")
+}
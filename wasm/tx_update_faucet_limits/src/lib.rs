@@ -0,0 +1,15 @@
+//! A tx for setting the withdrawal limits of a token of a faucet.
+
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data")?;
+    let update = account::faucet::UpdateFaucetLimits::try_from_slice(&data[..])
+        .wrap_err("Failed to decode UpdateFaucetLimits tx data")?;
+    debug_log!("update faucet limits of: {}", update.faucet);
+
+    account::update_faucet_limits(ctx, &update)
+        .wrap_err("Failed to update the faucet limits")
+}
@@ -0,0 +1,14 @@
+//! A tx for registering, updating or deleting a name in the name registry.
+
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data")?;
+    let update = account::name_registry::UpdateName::try_from_slice(&data[..])
+        .wrap_err("Failed to decode UpdateName tx data")?;
+    debug_log!("update name: {}", update.name);
+
+    account::update_name(ctx, &update).wrap_err("Failed to update the name")
+}
@@ -0,0 +1,18 @@
+//! A tx for setting or removing the transfer policy of an account.
+
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data")?;
+    let update =
+        account::transfer_policy::UpdateTransferPolicy::try_from_slice(
+            &data[..],
+        )
+        .wrap_err("Failed to decode UpdateTransferPolicy tx data")?;
+    debug_log!("update transfer policy of: {}", update.owner);
+
+    account::update_transfer_policy(ctx, &update)
+        .wrap_err("Failed to update the transfer policy")
+}
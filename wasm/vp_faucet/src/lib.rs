@@ -0,0 +1,568 @@
+//! A faucet VP, for accounts that give away their tokens with rate limits.
+//!
+//! Any tx may debit the faucet's balance of a token without its signature, as
+//! long as the tx records the withdrawn amount in the faucet's storage and
+//! the amounts withdrawn in the current epoch, both to the receiving address
+//! and in total, stay within the limits that the faucet set for the token.
+//! Without limits for a token, it cannot be withdrawn.
+//!
+//! Changes of the limits, of the account's public keys and threshold and of
+//! any other storage key are allowed only with a valid signature, like for a
+//! user account.
+
+use std::collections::BTreeMap;
+
+use booleans::BoolResultUnitExt;
+use namada_vp_prelude::account::faucet;
+use namada_vp_prelude::auth::{actions_signed_by, signed_by, Authorization};
+use namada_vp_prelude::tx::action::Read;
+use namada_vp_prelude::*;
+
+#[validity_predicate]
+fn validate_tx(
+    ctx: &Ctx,
+    tx: Tx,
+    addr: Address,
+    keys_changed: BTreeSet<storage::Key>,
+    verifiers: BTreeSet<Address>,
+) -> VpResult {
+    debug_log!(
+        "vp_faucet called with faucet addr: {}, key_changed: {:?}, verifiers: \
+         {:?}",
+        addr,
+        keys_changed,
+        verifiers
+    );
+
+    // Check if this is a governance proposal first
+    if is_accepted_proposal_tx(ctx, &tx)? {
+        // Any change from governance is allowed without further checks
+        return Ok(());
+    }
+
+    let gadget = VerifySigGadget::new();
+
+    // Find the actions applied in the tx
+    let actions = ctx.read_actions().into_vp_error()?;
+
+    // Require authorization by signature when the source of an action is this
+    // VP's address
+    actions_signed_by(signed_by(&addr, &tx).with_gadget(&gadget), &actions)
+        .authorize(ctx, &keys_changed)?;
+
+    // The withdrawals of every token made by this tx, which are allowed
+    // without a signature if they are within the faucet's limits
+    let mut withdrawals: BTreeMap<&Address, Withdrawals> = BTreeMap::new();
+    // The amounts of every token credited to the other addresses, which must
+    // cover the recorded withdrawals
+    let mut credits: BTreeMap<&Address, Credits> = BTreeMap::new();
+
+    keys_changed.iter().try_for_each(|key| {
+        let key_type: KeyType = key.into();
+        let mut validate_change = || match key_type {
+            KeyType::TokenBalance { token, owner } => {
+                if owner == &addr {
+                    let pre: token::Amount =
+                        ctx.read_pre(key).into_vp_error()?.unwrap_or_default();
+                    let post: token::Amount =
+                        ctx.read_post(key).into_vp_error()?.unwrap_or_default();
+                    // NB: debit is checked against the withdrawal limits,
+                    // credit is always allowed
+                    if let Some(debit) =
+                        pre.checked_sub(post).filter(|debit| !debit.is_zero())
+                    {
+                        let withdrawals = withdrawals.entry(token).or_default();
+                        withdrawals.debited =
+                            withdrawals.debited.checked_add(debit).ok_or_else(
+                                || VpError::Erased("Debit overflow".into()),
+                            )?;
+                    }
+                    debug_log!(
+                        "token key: {key}, pre: {pre:?}, post: {post:?}"
+                    );
+                } else {
+                    let pre: token::Amount =
+                        ctx.read_pre(key).into_vp_error()?.unwrap_or_default();
+                    let post: token::Amount =
+                        ctx.read_post(key).into_vp_error()?.unwrap_or_default();
+                    if let Some(credit) =
+                        post.checked_sub(pre).filter(|credit| !credit.is_zero())
+                    {
+                        credits.entry(token).or_default().insert(owner, credit);
+                    }
+                }
+                Ok(())
+            }
+            KeyType::TokenMinted => {
+                verifiers.contains(&address::MULTITOKEN).ok_or_else(|| {
+                    VpError::Erased(
+                        "The Multitoken VP should have been a verifier for \
+                         this transaction, since a token was minted"
+                            .into(),
+                    )
+                })
+            }
+            KeyType::TokenMinter(owner)
+            | KeyType::TokenPolicy(owner)
+            | KeyType::FaucetLimits(owner) => gadget.verify_signatures_when(
+                || owner == &addr,
+                ctx,
+                &tx,
+                &addr,
+            ),
+            KeyType::FaucetWithdrawal {
+                faucet,
+                token,
+                target,
+            } => {
+                if faucet == &addr {
+                    let increase = withdrawn_increase(
+                        ctx,
+                        key,
+                        faucet,
+                        token,
+                        target.is_none(),
+                    )?;
+                    withdrawals
+                        .entry(token)
+                        .or_default()
+                        .record(increase, target);
+                }
+                Ok(())
+            }
+            KeyType::Vp(owner) => {
+                let vp_overwritten: bool =
+                    ctx.has_key_post(key).into_vp_error()?;
+                gadget.verify_signatures_when(
+                    || owner == &addr && vp_overwritten,
+                    ctx,
+                    &tx,
+                    &addr,
+                )
+            }
+            KeyType::AccountSigners(_) => {
+                gadget.verify_signatures(ctx, &tx, &addr)
+            }
+            KeyType::Masp | KeyType::Ibc => Ok(()),
+            KeyType::Unknown => {
+                // Unknown changes require a valid signature
+                gadget.verify_signatures(ctx, &tx, &addr)
+            }
+        };
+        validate_change().inspect_err(|reason| {
+            log_string(format!(
+                "Modification on key {key} failed vp_faucet: {reason}"
+            ));
+        })
+    })?;
+
+    // The withdrawals that are not fully recorded or that exceed the limits
+    // require a valid signature
+    withdrawals
+        .into_iter()
+        .try_for_each(|(token, withdrawals)| {
+            gadget
+                .verify_signatures_when(
+                    || !withdrawals.is_valid(credits.get(token)),
+                    ctx,
+                    &tx,
+                    &addr,
+                )
+                .inspect_err(|reason| {
+                    log_string(format!(
+                        "Withdrawal of token {token} failed vp_faucet: \
+                         {reason}"
+                    ));
+                })
+        })
+}
+
+/// The amounts of a token credited to addresses by a tx
+type Credits<'a> = BTreeMap<&'a Address, token::Amount>;
+
+/// The changes of a token of the faucet made by a tx
+#[derive(Default)]
+struct Withdrawals<'a> {
+    /// The amount debited from the faucet's balance
+    debited: token::Amount,
+    /// The increase of the recorded total amount withdrawn in the epoch
+    total: Option<token::Amount>,
+    /// The increases of the recorded amounts withdrawn in the epoch to every
+    /// address
+    per_address: BTreeMap<&'a Address, token::Amount>,
+    /// Whether a record is invalid, i.e. not of the current epoch, decreased
+    /// or over the limit
+    has_invalid_record: bool,
+}
+
+impl<'a> Withdrawals<'a> {
+    /// Add the increase of a withdrawal record to the given target, or of the
+    /// total record if `None`. The increase is `None` if the record is invalid.
+    fn record(
+        &mut self,
+        increase: Option<token::Amount>,
+        target: Option<&'a Address>,
+    ) {
+        match (increase, target) {
+            (Some(increase), None) => self.total = Some(increase),
+            (Some(increase), Some(target)) => {
+                self.per_address.insert(target, increase);
+            }
+            (None, _) => self.has_invalid_record = true,
+        }
+    }
+
+    /// Check that the debited amount is recorded both in total and per
+    /// address, with records within the limits, and that every address with a
+    /// recorded withdrawal got credited at least the recorded amount
+    fn is_valid(&self, credits: Option<&Credits<'_>>) -> bool {
+        let per_address_sum = self
+            .per_address
+            .values()
+            .try_fold(token::Amount::zero(), |sum, amount| {
+                sum.checked_add(*amount)
+            });
+        let is_credited = |(target, amount): (&&Address, &token::Amount)| {
+            credits
+                .and_then(|credits| credits.get(*target))
+                .is_some_and(|credit| credit >= amount)
+        };
+        !self.has_invalid_record
+            && self.total == Some(self.debited)
+            && per_address_sum == Some(self.debited)
+            && self.per_address.iter().all(is_credited)
+    }
+}
+
+/// Get the increase of the amount of a token withdrawn in the current epoch
+/// that is recorded at the given key. Returns `None` if the new record is not
+/// of the current epoch, if it decreased or if it exceeds the faucet's limit.
+fn withdrawn_increase(
+    ctx: &Ctx,
+    key: &storage::Key,
+    faucet: &Address,
+    token: &Address,
+    is_total: bool,
+) -> VpEnvResult<Option<token::Amount>> {
+    let epoch = ctx.get_block_epoch().into_vp_error()?;
+    let Some(limits) =
+        faucet::read_limits(&ctx.post(), faucet, token).into_vp_error()?
+    else {
+        return Ok(None);
+    };
+    let limit = if is_total {
+        limits.per_epoch
+    } else {
+        limits.per_address
+    };
+    let pre = faucet::read_withdrawn(&ctx.pre(), key, epoch).into_vp_error()?;
+    let post: Option<faucet::Withdrawal> =
+        ctx.read_post(key).into_vp_error()?;
+    Ok(post
+        .filter(|post| post.epoch == epoch && post.amount <= limit)
+        .and_then(|post| post.amount.checked_sub(pre)))
+}
+
+enum KeyType<'a> {
+    TokenBalance {
+        token: &'a Address,
+        owner: &'a Address,
+    },
+    TokenMinted,
+    TokenMinter(&'a Address),
+    TokenPolicy(&'a Address),
+    FaucetLimits(&'a Address),
+    FaucetWithdrawal {
+        faucet: &'a Address,
+        token: &'a Address,
+        /// The address of a per address record, or `None` for the total
+        target: Option<&'a Address>,
+    },
+    Vp(&'a Address),
+    AccountSigners(&'a Address),
+    Masp,
+    Ibc,
+    Unknown,
+}
+
+impl<'a> From<&'a storage::Key> for KeyType<'a> {
+    fn from(key: &'a storage::Key) -> KeyType<'a> {
+        if let Some([token, owner]) =
+            token::storage_key::is_any_token_balance_key(key)
+        {
+            Self::TokenBalance { token, owner }
+        } else if token::storage_key::is_any_minted_balance_key(key).is_some() {
+            Self::TokenMinted
+        } else if let Some(minter) = token::storage_key::is_any_minter_key(key)
+        {
+            Self::TokenMinter(minter)
+        } else if let Some(token) =
+            token::storage_key::is_any_token_policy_key(key)
+        {
+            Self::TokenPolicy(token)
+        } else if let Some([faucet, _token]) = faucet::is_limits_key(key) {
+            Self::FaucetLimits(faucet)
+        } else if let Some([faucet, token, target]) =
+            faucet::is_withdrawal_key(key)
+        {
+            Self::FaucetWithdrawal {
+                faucet,
+                token,
+                target: Some(target),
+            }
+        } else if let Some([faucet, token]) =
+            faucet::is_total_withdrawal_key(key)
+        {
+            Self::FaucetWithdrawal {
+                faucet,
+                token,
+                target: None,
+            }
+        } else if let Some(address) = key.is_validity_predicate() {
+            Self::Vp(address)
+        } else if let Some(owner) =
+            account::is_pks_key(key).or_else(|| account::is_threshold_key(key))
+        {
+            Self::AccountSigners(owner)
+        } else if token::storage_key::is_masp_key(key) {
+            Self::Masp
+        } else if ibc::is_ibc_key(key) {
+            Self::Ibc
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    use namada::tx::data::TxType;
+    use namada::tx::Data;
+    // Use this as `#[test]` annotation to enable logging
+    use namada_tests::log::test;
+    use namada_tests::tx::{self, tx_host_env, TestTxEnv};
+    use namada_tests::vp::*;
+
+    use super::*;
+
+    /// Initialize a tx environment with a faucet of NAM that has the given
+    /// balance and withdrawal limits
+    fn init_faucet(
+        faucet: &Address,
+        target: &Address,
+        balance: token::Amount,
+        limits: faucet::FaucetLimits,
+    ) -> TestTxEnv {
+        let mut tx_env = TestTxEnv::default();
+        let token = address::testing::nam();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([faucet, target, &token]);
+        tx_env.credit_tokens(faucet, &token, balance);
+        // write the denomination of NAM into storage
+        token::write_denom(
+            &mut tx_env.state,
+            &token,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        )
+        .unwrap();
+        faucet::update_limits(
+            &mut tx_env.state,
+            &faucet::UpdateFaucetLimits {
+                faucet: faucet.clone(),
+                token,
+                limits,
+            },
+        )
+        .unwrap();
+        tx_env
+    }
+
+    /// Test that an unsigned withdrawal within the limits is accepted.
+    #[test]
+    fn test_unsigned_withdrawal_within_limits_accepted() {
+        let vp_owner = address::testing::established_address_1();
+        let target = address::testing::established_address_2();
+        let token = address::testing::nam();
+        let limits = faucet::FaucetLimits {
+            per_address: token::Amount::native_whole(100),
+            per_epoch: token::Amount::native_whole(1_000),
+        };
+        let tx_env = init_faucet(
+            &vp_owner,
+            &target,
+            token::Amount::native_whole(10_000),
+            limits,
+        );
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Apply the withdrawal in a transaction
+            tx_host_env::account::faucet_withdraw(
+                tx::ctx(),
+                &faucet::FaucetWithdraw {
+                    faucet: address.clone(),
+                    target: target.clone(),
+                    token: token.clone(),
+                    amount: token::Amount::native_whole(100),
+                },
+            )
+            .unwrap();
+        });
+
+        let vp_env = vp_host_env::take();
+        let mut tx_data = Tx::from_type(TxType::Raw);
+        tx_data.set_data(Data::new(vec![]));
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        assert!(
+            validate_tx(&CTX, tx_data, vp_owner, keys_changed, verifiers)
+                .is_ok()
+        );
+    }
+
+    /// Test that an unsigned debit that isn't recorded as a withdrawal is
+    /// rejected.
+    #[test]
+    fn test_unsigned_unrecorded_debit_rejected() {
+        let vp_owner = address::testing::established_address_1();
+        let target = address::testing::established_address_2();
+        let token = address::testing::nam();
+        let limits = faucet::FaucetLimits {
+            per_address: token::Amount::native_whole(100),
+            per_epoch: token::Amount::native_whole(1_000),
+        };
+        let tx_env = init_faucet(
+            &vp_owner,
+            &target,
+            token::Amount::native_whole(10_000),
+            limits,
+        );
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Apply a plain transfer in a transaction
+            tx_host_env::token::transfer(
+                tx::ctx(),
+                address,
+                &target,
+                &token,
+                token::Amount::native_whole(100),
+            )
+            .unwrap();
+        });
+
+        let vp_env = vp_host_env::take();
+        let mut tx_data = Tx::from_type(TxType::Raw);
+        tx_data.set_data(Data::new(vec![]));
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        assert!(panic::catch_unwind(|| {
+            validate_tx(&CTX, tx_data, vp_owner, keys_changed, verifiers)
+        })
+        .err()
+        .map(|a| a.downcast_ref::<String>().cloned().unwrap())
+        .unwrap()
+        .contains("InvalidSectionSignature"));
+    }
+
+    /// Test that an unsigned withdrawal that is recorded for an address but
+    /// credited to another one is rejected.
+    #[test]
+    fn test_unsigned_withdrawal_to_other_address_rejected() {
+        let vp_owner = address::testing::established_address_1();
+        let target = address::testing::established_address_2();
+        let other = address::testing::established_address_3();
+        let token = address::testing::nam();
+        let limits = faucet::FaucetLimits {
+            per_address: token::Amount::native_whole(100),
+            per_epoch: token::Amount::native_whole(1_000),
+        };
+        let mut tx_env = init_faucet(
+            &vp_owner,
+            &target,
+            token::Amount::native_whole(10_000),
+            limits,
+        );
+        tx_env.spawn_accounts([&other]);
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Record a withdrawal to the target, but credit another address
+            faucet::record_withdrawal(
+                tx::ctx(),
+                &faucet::FaucetWithdraw {
+                    faucet: address.clone(),
+                    target: target.clone(),
+                    token: token.clone(),
+                    amount: token::Amount::native_whole(100),
+                },
+            )
+            .unwrap();
+            tx_host_env::token::transfer(
+                tx::ctx(),
+                address,
+                &other,
+                &token,
+                token::Amount::native_whole(100),
+            )
+            .unwrap();
+        });
+
+        let vp_env = vp_host_env::take();
+        let mut tx_data = Tx::from_type(TxType::Raw);
+        tx_data.set_data(Data::new(vec![]));
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        assert!(panic::catch_unwind(|| {
+            validate_tx(&CTX, tx_data, vp_owner, keys_changed, verifiers)
+        })
+        .err()
+        .map(|a| a.downcast_ref::<String>().cloned().unwrap())
+        .unwrap()
+        .contains("InvalidSectionSignature"));
+    }
+
+    /// Test that a withdrawal over the per address limit fails.
+    #[test]
+    fn test_withdrawal_over_limit_fails() {
+        let vp_owner = address::testing::established_address_1();
+        let target = address::testing::established_address_2();
+        let token = address::testing::nam();
+        let limits = faucet::FaucetLimits {
+            per_address: token::Amount::native_whole(100),
+            per_epoch: token::Amount::native_whole(1_000),
+        };
+        let tx_env = init_faucet(
+            &vp_owner,
+            &target,
+            token::Amount::native_whole(10_000),
+            limits,
+        );
+
+        vp_host_env::init_from_tx(vp_owner, tx_env, |address| {
+            let withdraw = faucet::FaucetWithdraw {
+                faucet: address.clone(),
+                target: target.clone(),
+                token: token.clone(),
+                amount: token::Amount::native_whole(60),
+            };
+            tx_host_env::account::faucet_withdraw(tx::ctx(), &withdraw)
+                .unwrap();
+            // The second withdrawal in the same epoch exceeds the limit
+            assert!(tx_host_env::account::faucet_withdraw(
+                tx::ctx(),
+                &withdraw
+            )
+            .is_err());
+        });
+    }
+}
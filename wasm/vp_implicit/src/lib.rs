@@ -12,7 +12,8 @@
 //! Any other storage key changes are allowed only with a valid signature.
 
 use booleans::BoolResultUnitExt;
-use namada_vp_prelude::tx::action::*;
+use namada_vp_prelude::auth::{actions_signed_by, signed_by, Authorization};
+use namada_vp_prelude::tx::action::Read;
 use namada_vp_prelude::*;
 
 #[validity_predicate]
@@ -31,81 +32,25 @@ fn validate_tx(
     );
 
     // Check if this is a governance proposal first
-    let is_gov_proposal = tx
-        .data()
-        .and_then(|tx_data| {
-            let proposal_id = u64::try_from_slice(&tx_data).ok()?;
-            Some(is_proposal_accepted(ctx, proposal_id))
-        })
-        .transpose()?
-        .unwrap_or(false);
-    if is_gov_proposal {
+    if is_accepted_proposal_tx(ctx, &tx)? {
         // Any change from governance is allowed without further checks
         return Ok(());
     }
 
-    let mut gadget = VerifySigGadget::new();
+    let gadget = VerifySigGadget::new();
 
     // Find the actions applied in the tx
     let actions = ctx.read_actions().into_vp_error()?;
 
     // Require authorization by signature when the source of an action is this
     // VP's address
-    for action in actions {
-        match action {
-            Action::Pos(pos_action) => match pos_action {
-                PosAction::BecomeValidator(source)
-                | PosAction::DeactivateValidator(source)
-                | PosAction::ReactivateValidator(source)
-                | PosAction::Unjail(source)
-                | PosAction::CommissionChange(source)
-                | PosAction::MetadataChange(source)
-                | PosAction::ConsensusKeyChange(source)
-                | PosAction::Redelegation(Redelegation {
-                    owner: source, ..
-                }) => gadget.verify_signatures_when(
-                    || source == addr,
-                    ctx,
-                    &tx,
-                    &addr,
-                )?,
-                PosAction::Bond(Bond {
-                    source, validator, ..
-                })
-                | PosAction::Unbond(Unbond {
-                    source, validator, ..
-                })
-                | PosAction::Withdraw(Withdraw { source, validator })
-                | PosAction::ClaimRewards(ClaimRewards { validator, source }) =>
-                {
-                    let source = source.unwrap_or(validator);
-                    gadget.verify_signatures_when(
-                        || source == addr,
-                        ctx,
-                        &tx,
-                        &addr,
-                    )?
-                }
-            },
-            Action::Gov(
-                GovAction::InitProposal { author: source }
-                | GovAction::VoteProposal { voter: source, .. },
-            )
-            | Action::Pgf(
-                PgfAction::ResignSteward(source)
-                | PgfAction::UpdateStewardCommission(source),
-            ) => gadget.verify_signatures_when(
-                || source == addr,
-                ctx,
-                &tx,
-                &addr,
-            )?,
-        }
-    }
+    actions_signed_by(signed_by(&addr, &tx).with_gadget(&gadget), &actions)
+        .allow_memo_hook_bonds()
+        .authorize(ctx, &keys_changed)?;
 
     keys_changed.iter().try_for_each(|key| {
         let key_type: KeyType = key.into();
-        let mut validate_change = || match key_type {
+        let validate_change = || match key_type {
             KeyType::Pk(owner) => {
                 if owner == &addr {
                     let key_was_not_already_revealed =
@@ -0,0 +1,249 @@
+//! A user VP with a transfer policy, for both non-validator and validator
+//! accounts.
+//!
+//! This VP applies the same checks as `vp_user`, which are shared in the
+//! `user` module of the VP prelude, and it additionally enforces the transfer
+//! policy registered in the account's storage, if any, on the tokens debited
+//! by every tx, even when it is signed: the amount of a token debited by a tx
+//! may not exceed the policy's maximum amount per tx and the addresses
+//! credited with the token in the tx must be allowed counterparties.
+//!
+//! Changes of the transfer policy require a valid signature(s), like any
+//! other storage key changes. The policy before the tx applies to the tx, so a
+//! tx cannot relax the policy to debit more tokens.
+
+use std::collections::BTreeMap;
+
+use namada_vp_prelude::account::transfer_policy;
+use namada_vp_prelude::*;
+
+#[validity_predicate]
+fn validate_tx(
+    ctx: &Ctx,
+    tx: Tx,
+    addr: Address,
+    keys_changed: BTreeSet<storage::Key>,
+    verifiers: BTreeSet<Address>,
+) -> VpResult {
+    debug_log!(
+        "vp_user_policy called with user addr: {}, key_changed: {:?}, \
+         verifiers: {:?}",
+        addr,
+        keys_changed,
+        verifiers
+    );
+
+    // Check if this is a governance proposal first
+    if is_accepted_proposal_tx(ctx, &tx)? {
+        // Any change from governance is allowed without further checks
+        return Ok(());
+    }
+
+    user::validate_changes(ctx, &tx, &addr, &keys_changed, &verifiers)?;
+
+    // The policy before the tx applies to its debits
+    let Some(policy) = transfer_policy::read_transfer_policy(&ctx.pre(), &addr)
+        .into_vp_error()?
+    else {
+        return Ok(());
+    };
+
+    // The transfers of every token made by this tx, which must comply with
+    // the account's transfer policy
+    let mut transfers: BTreeMap<&Address, Transfers> = BTreeMap::new();
+    for key in &keys_changed {
+        let Some([token, owner]) =
+            token::storage_key::is_any_token_balance_key(key)
+        else {
+            continue;
+        };
+        let pre: token::Amount =
+            ctx.read_pre(key).into_vp_error()?.unwrap_or_default();
+        let post: token::Amount =
+            ctx.read_post(key).into_vp_error()?.unwrap_or_default();
+        if owner == &addr {
+            if let Some(debit) =
+                pre.checked_sub(post).filter(|debit| !debit.is_zero())
+            {
+                let transfers = transfers.entry(token).or_default();
+                transfers.debited = transfers
+                    .debited
+                    .checked_add(debit)
+                    .ok_or_else(|| VpError::Erased("Debit overflow".into()))?;
+            }
+        } else if post > pre {
+            transfers.entry(token).or_default().receivers.insert(owner);
+        }
+    }
+
+    transfers
+        .into_iter()
+        .filter(|(_token, transfers)| !transfers.debited.is_zero())
+        .try_for_each(|(token, transfers)| {
+            policy
+                .check_transfer(token, transfers.debited, transfers.receivers)
+                .map_err(VpError::Erased)
+                .inspect_err(|reason| {
+                    log_string(format!(
+                        "Transfer of token {token} failed vp_user_policy: \
+                         {reason}"
+                    ));
+                })
+        })
+}
+
+/// The changes of the balances of a token made by a tx
+#[derive(Default)]
+struct Transfers<'a> {
+    /// The amount debited from the account's balance
+    debited: token::Amount,
+    /// The other addresses whose balance was credited
+    receivers: BTreeSet<&'a Address>,
+}
+
+#[cfg(test)]
+mod tests {
+    use namada::tx::{Authorization, Code, Data};
+    // Use this as `#[test]` annotation to enable logging
+    use namada_tests::log::test;
+    use namada_tests::tx::{self, tx_host_env, TestTxEnv};
+    use namada_tests::vp::*;
+    use namada_vp_prelude::account::AccountPublicKeysMap;
+    use namada_vp_prelude::key::RefTo;
+
+    use super::*;
+
+    /// Validate a transfer of NAM from the VP owner to the target, signed by
+    /// the owner whose account has the given transfer policy
+    fn validate_signed_transfer(
+        policy: transfer_policy::TransferPolicy,
+        target: &Address,
+        amount: token::Amount,
+    ) -> VpResult {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+        let token = address::testing::nam();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, target, &token]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key.clone()], 1);
+
+        // Credit the tokens to the VP owner before running the transaction to
+        // be able to transfer from it
+        tx_env.credit_tokens(
+            &vp_owner,
+            &token,
+            token::Amount::native_whole(1_000),
+        );
+        // write the denomination of NAM into storage
+        token::write_denom(
+            &mut tx_env.state,
+            &token,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        )
+        .unwrap();
+        transfer_policy::update_transfer_policy(
+            &mut tx_env.state,
+            &transfer_policy::UpdateTransferPolicy {
+                owner: vp_owner.clone(),
+                policy: Some(policy),
+            },
+        )
+        .unwrap();
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Apply transfer in a transaction
+            tx_host_env::token::transfer(
+                tx::ctx(),
+                address,
+                target,
+                &token,
+                amount,
+            )
+            .unwrap();
+        });
+
+        let pks_map = AccountPublicKeysMap::from_iter(vec![public_key]);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Authorization(Authorization::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(vec![keypair]),
+            None,
+        )));
+        let signed_tx = tx.clone();
+        vp_env.tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+    }
+
+    /// Test that a signed transfer that complies with the transfer policy is
+    /// accepted.
+    #[test]
+    fn test_signed_transfer_within_policy_accepted() {
+        let target = address::testing::established_address_2();
+        let policy = transfer_policy::TransferPolicy {
+            max_amounts_per_tx: BTreeMap::from([(
+                address::testing::nam(),
+                token::Amount::native_whole(100),
+            )]),
+            allowed_counterparties: BTreeSet::from([target.clone()]),
+        };
+        assert!(validate_signed_transfer(
+            policy,
+            &target,
+            token::Amount::native_whole(100)
+        )
+        .is_ok());
+    }
+
+    /// Test that a signed transfer over the maximum amount per tx of the
+    /// transfer policy is rejected.
+    #[test]
+    fn test_signed_transfer_over_max_amount_rejected() {
+        let target = address::testing::established_address_2();
+        let policy = transfer_policy::TransferPolicy {
+            max_amounts_per_tx: BTreeMap::from([(
+                address::testing::nam(),
+                token::Amount::native_whole(100),
+            )]),
+            allowed_counterparties: BTreeSet::new(),
+        };
+        assert!(validate_signed_transfer(
+            policy,
+            &target,
+            token::Amount::native_whole(101)
+        )
+        .is_err());
+    }
+
+    /// Test that a signed transfer to an address that is not an allowed
+    /// counterparty of the transfer policy is rejected.
+    #[test]
+    fn test_signed_transfer_to_other_counterparty_rejected() {
+        let target = address::testing::established_address_2();
+        let policy = transfer_policy::TransferPolicy {
+            max_amounts_per_tx: BTreeMap::new(),
+            allowed_counterparties: BTreeSet::from([
+                address::testing::established_address_3(),
+            ]),
+        };
+        assert!(validate_signed_transfer(
+            policy,
+            &target,
+            token::Amount::native_whole(1)
+        )
+        .is_err());
+    }
+}